@@ -2,6 +2,7 @@ package io.graphlite;
 
 import com.sun.jna.*;
 import com.sun.jna.ptr.IntByReference;
+import com.sun.jna.ptr.NativeLongByReference;
 import org.json.JSONObject;
 import org.json.JSONArray;
 
@@ -39,7 +40,19 @@ public class GraphLite implements Closeable {
         SESSION_ERROR(4),
         QUERY_ERROR(5),
         PANIC_ERROR(6),
-        JSON_ERROR(7);
+        JSON_ERROR(7),
+        NOT_EXECUTED(8),
+        SERIALIZATION_ERROR(9),
+        BACKUP_ERROR(10),
+        PARSE_ERROR(11),
+        CONSTRAINT_VIOLATION(12),
+        PERMISSION_DENIED(13),
+        TRANSACTION_CONFLICT(14),
+        GRAPH_NOT_FOUND(15),
+        TIMEOUT(16),
+        TYPE_MISMATCH(17),
+        OUT_OF_RANGE(18),
+        DISK_FULL(19);
 
         private final int code;
 
@@ -75,6 +88,96 @@ public class GraphLite implements Closeable {
         public ErrorCode getErrorCode() {
             return errorCode;
         }
+
+        /**
+         * Create the most specific exception type for an error code
+         */
+        static GraphLiteException forCode(ErrorCode errorCode, String message) {
+            if (errorCode == null) {
+                return new GraphLiteException(null, message);
+            }
+            switch (errorCode) {
+                case PARSE_ERROR:
+                    return new ParseException(message);
+                case CONSTRAINT_VIOLATION:
+                    return new ConstraintViolationException(message);
+                case PERMISSION_DENIED:
+                    return new PermissionDeniedException(message);
+                case TRANSACTION_CONFLICT:
+                    return new TransactionConflictException(message);
+                case GRAPH_NOT_FOUND:
+                    return new GraphNotFoundException(message);
+                case TIMEOUT:
+                    return new QueryTimeoutException(message);
+                case DISK_FULL:
+                    return new DiskFullException(message);
+                default:
+                    return new GraphLiteException(errorCode, message);
+            }
+        }
+    }
+
+    /**
+     * The statement could not be parsed
+     */
+    public static class ParseException extends GraphLiteException {
+        public ParseException(String message) {
+            super(ErrorCode.PARSE_ERROR, message);
+        }
+    }
+
+    /**
+     * The statement conflicts with existing data, e.g. a graph that already exists
+     */
+    public static class ConstraintViolationException extends GraphLiteException {
+        public ConstraintViolationException(String message) {
+            super(ErrorCode.CONSTRAINT_VIOLATION, message);
+        }
+    }
+
+    /**
+     * The operation is not allowed, or the database is read-only
+     */
+    public static class PermissionDeniedException extends GraphLiteException {
+        public PermissionDeniedException(String message) {
+            super(ErrorCode.PERMISSION_DENIED, message);
+        }
+    }
+
+    /**
+     * Transaction state prevents the operation
+     */
+    public static class TransactionConflictException extends GraphLiteException {
+        public TransactionConflictException(String message) {
+            super(ErrorCode.TRANSACTION_CONFLICT, message);
+        }
+    }
+
+    /**
+     * The graph, or another catalog object such as a schema, does not exist
+     */
+    public static class GraphNotFoundException extends GraphLiteException {
+        public GraphNotFoundException(String message) {
+            super(ErrorCode.GRAPH_NOT_FOUND, message);
+        }
+    }
+
+    /**
+     * The query ran past the query timeout
+     */
+    public static class QueryTimeoutException extends GraphLiteException {
+        public QueryTimeoutException(String message) {
+            super(ErrorCode.TIMEOUT, message);
+        }
+    }
+
+    /**
+     * The database volume is low on space, so writes are rejected
+     */
+    public static class DiskFullException extends GraphLiteException {
+        public DiskFullException(String message) {
+            super(ErrorCode.DISK_FULL, message);
+        }
     }
 
     // JNA interface to GraphLite C library
@@ -85,9 +188,20 @@ public class GraphLite implements Closeable {
         Pointer graphlite_create_session(Pointer db, String username, IntByReference error);
         Pointer graphlite_query(Pointer db, String sessionId, String query, IntByReference error);
         int graphlite_close_session(Pointer db, String sessionId, IntByReference error);
+        int graphlite_begin(Pointer db, String sessionId, IntByReference error);
+        int graphlite_commit(Pointer db, String sessionId, IntByReference error);
+        int graphlite_rollback(Pointer db, String sessionId, IntByReference error);
+        boolean graphlite_in_transaction(Pointer db, String sessionId, IntByReference error);
+        Pointer graphlite_list_graphs(Pointer db, String sessionId, NativeLongByReference countOut, IntByReference error);
+        void graphlite_free_string_array(Pointer array, NativeLong count);
+        int graphlite_create_graph(Pointer db, String sessionId, String graphPath, IntByReference error);
+        int graphlite_drop_graph(Pointer db, String sessionId, String graphPath, IntByReference error);
+        int graphlite_set_session_graph(Pointer db, String sessionId, String graphPath, IntByReference error);
         void graphlite_free_string(Pointer str);
         void graphlite_close(Pointer db);
         Pointer graphlite_version();
+        int graphlite_abi_version();
+        boolean graphlite_has_feature(String name);
 
         static GraphLiteNative loadLibrary() {
             String libName;
@@ -201,7 +315,7 @@ public class GraphLite implements Closeable {
 
         if (resultPtr == null) {
             ErrorCode errorCode = ErrorCode.fromInt(error.getValue());
-            throw new GraphLiteException(errorCode, "Query failed: " + query.substring(0, Math.min(100, query.length())));
+            throw GraphLiteException.forCode(errorCode, "Query failed: " + query.substring(0, Math.min(100, query.length())));
         }
 
         try {
@@ -223,6 +337,168 @@ public class GraphLite implements Closeable {
         query(sessionId, statement);
     }
 
+    /**
+     * List the full paths of all graphs, e.g. {@code /app/social}, sorted
+     *
+     * @param sessionId Session ID from createSession()
+     * @return Graph paths
+     * @throws GraphLiteException if listing fails
+     */
+    public List<String> listGraphs(String sessionId) {
+        checkClosed();
+
+        NativeLongByReference count = new NativeLongByReference();
+        IntByReference error = new IntByReference(0);
+        Pointer graphs = GraphLiteNative.INSTANCE.graphlite_list_graphs(dbHandle, sessionId, count, error);
+
+        if (graphs == null) {
+            ErrorCode errorCode = ErrorCode.fromInt(error.getValue());
+            throw GraphLiteException.forCode(errorCode, "Failed to list graphs");
+        }
+
+        try {
+            return Arrays.asList(graphs.getStringArray(0, count.getValue().intValue()));
+        } finally {
+            GraphLiteNative.INSTANCE.graphlite_free_string_array(graphs, count.getValue());
+        }
+    }
+
+    /**
+     * Create a graph; its schema must exist
+     *
+     * @param sessionId Session ID from createSession()
+     * @param graphPath Full graph path such as {@code /app/social}
+     * @throws ConstraintViolationException if the graph already exists
+     * @throws GraphNotFoundException if the schema does not exist
+     */
+    public void createGraph(String sessionId, String graphPath) {
+        checkClosed();
+
+        IntByReference error = new IntByReference(0);
+        int result = GraphLiteNative.INSTANCE.graphlite_create_graph(dbHandle, sessionId, graphPath, error);
+        checkResult(result, error, "Failed to create graph " + graphPath);
+    }
+
+    /**
+     * Drop a graph and its data
+     *
+     * @param sessionId Session ID from createSession()
+     * @param graphPath Full graph path such as {@code /app/social}
+     * @throws GraphNotFoundException if the graph does not exist
+     */
+    public void dropGraph(String sessionId, String graphPath) {
+        checkClosed();
+
+        IntByReference error = new IntByReference(0);
+        int result = GraphLiteNative.INSTANCE.graphlite_drop_graph(dbHandle, sessionId, graphPath, error);
+        checkResult(result, error, "Failed to drop graph " + graphPath);
+    }
+
+    /**
+     * Make a graph the session's current graph
+     *
+     * @param sessionId Session ID from createSession()
+     * @param graphPath Full graph path such as {@code /app/social}
+     * @throws GraphNotFoundException if the graph does not exist
+     */
+    public void setGraph(String sessionId, String graphPath) {
+        checkClosed();
+
+        IntByReference error = new IntByReference(0);
+        int result = GraphLiteNative.INSTANCE.graphlite_set_session_graph(dbHandle, sessionId, graphPath, error);
+        checkResult(result, error, "Failed to set graph " + graphPath);
+    }
+
+    /**
+     * Start a transaction on a session
+     *
+     * Statements run on the session afterwards belong to the transaction until
+     * {@link #commit(String)} or {@link #rollback(String)}.
+     *
+     * @param sessionId Session ID from createSession()
+     * @throws TransactionConflictException if the session already has an open transaction
+     */
+    public void begin(String sessionId) {
+        checkClosed();
+
+        IntByReference error = new IntByReference(0);
+        int result = GraphLiteNative.INSTANCE.graphlite_begin(dbHandle, sessionId, error);
+        checkResult(result, error, "Failed to begin transaction on session " + sessionId);
+    }
+
+    /**
+     * Commit the open transaction of a session
+     *
+     * @param sessionId Session ID from createSession()
+     * @throws TransactionConflictException if the session has no open transaction
+     */
+    public void commit(String sessionId) {
+        checkClosed();
+
+        IntByReference error = new IntByReference(0);
+        int result = GraphLiteNative.INSTANCE.graphlite_commit(dbHandle, sessionId, error);
+        checkResult(result, error, "Failed to commit on session " + sessionId);
+    }
+
+    /**
+     * Roll back the open transaction of a session
+     *
+     * @param sessionId Session ID from createSession()
+     * @throws TransactionConflictException if the session has no open transaction
+     */
+    public void rollback(String sessionId) {
+        checkClosed();
+
+        IntByReference error = new IntByReference(0);
+        int result = GraphLiteNative.INSTANCE.graphlite_rollback(dbHandle, sessionId, error);
+        checkResult(result, error, "Failed to roll back on session " + sessionId);
+    }
+
+    /**
+     * Check whether a session has an open transaction
+     *
+     * @param sessionId Session ID from createSession()
+     * @return true if a transaction is open
+     * @throws GraphLiteException if the session does not exist
+     */
+    public boolean inTransaction(String sessionId) {
+        checkClosed();
+
+        IntByReference error = new IntByReference(0);
+        boolean active = GraphLiteNative.INSTANCE.graphlite_in_transaction(dbHandle, sessionId, error);
+        if (error.getValue() != 0) {
+            ErrorCode errorCode = ErrorCode.fromInt(error.getValue());
+            throw GraphLiteException.forCode(errorCode, "Failed to read transaction state of session " + sessionId);
+        }
+        return active;
+    }
+
+    /**
+     * Run {@code body} in one transaction
+     *
+     * Commits when {@code body} returns and rolls back if it throws.
+     *
+     * @param sessionId Session ID from createSession()
+     * @param body Statements to run in the transaction
+     */
+    public void transaction(String sessionId, Runnable body) {
+        begin(sessionId);
+        try {
+            body.run();
+        } catch (RuntimeException | Error e) {
+            rollback(sessionId);
+            throw e;
+        }
+        commit(sessionId);
+    }
+
+    private static void checkResult(int result, IntByReference error, String message) {
+        if (result != 0) {
+            ErrorCode errorCode = ErrorCode.fromInt(error.getValue());
+            throw GraphLiteException.forCode(errorCode, message);
+        }
+    }
+
     /**
      * Close a session
      *
@@ -280,6 +556,25 @@ public class GraphLite implements Closeable {
         return "unknown";
     }
 
+    /**
+     * Get the C ABI version of the loaded library
+     *
+     * @return ABI version number
+     */
+    public static int abiVersion() {
+        return GraphLiteNative.INSTANCE.graphlite_abi_version();
+    }
+
+    /**
+     * Check whether the loaded library supports an optional feature
+     *
+     * @param name Feature name, e.g. "msgpack" or "backup"
+     * @return true if the feature is available
+     */
+    public static boolean hasFeature(String name) {
+        return GraphLiteNative.INSTANCE.graphlite_has_feature(name);
+    }
+
     private void checkClosed() {
         if (dbHandle == null) {
             throw new GraphLiteException(ErrorCode.NULL_POINTER, "Database is closed");
@@ -8,8 +8,9 @@ import ctypes
 import json
 import os
 import platform
+from contextlib import contextmanager
 from enum import IntEnum
-from typing import Dict, List, Any, Optional
+from typing import Dict, Iterator, List, Any, Optional
 from pathlib import Path
 
 
@@ -23,6 +24,18 @@ class ErrorCode(IntEnum):
     QUERY_ERROR = 5
     PANIC_ERROR = 6
     JSON_ERROR = 7
+    NOT_EXECUTED = 8
+    SERIALIZATION_ERROR = 9
+    BACKUP_ERROR = 10
+    PARSE_ERROR = 11
+    CONSTRAINT_VIOLATION = 12
+    PERMISSION_DENIED = 13
+    TRANSACTION_CONFLICT = 14
+    GRAPH_NOT_FOUND = 15
+    TIMEOUT = 16
+    TYPE_MISMATCH = 17
+    OUT_OF_RANGE = 18
+    DISK_FULL = 19
 
 
 class GraphLiteError(Exception):
@@ -34,6 +47,51 @@ class GraphLiteError(Exception):
         super().__init__(f"GraphLite error ({code.name}): {message}")
 
 
+class ParseError(GraphLiteError):
+    """The statement could not be parsed"""
+
+
+class ConstraintViolationError(GraphLiteError):
+    """The statement conflicts with existing data, e.g. a graph that already exists"""
+
+
+class PermissionDeniedError(GraphLiteError):
+    """The operation is not allowed, or the database is read-only"""
+
+
+class TransactionConflictError(GraphLiteError):
+    """Transaction state prevents the operation"""
+
+
+class GraphNotFoundError(GraphLiteError):
+    """The graph, or another catalog object such as a schema, does not exist"""
+
+
+class QueryTimeoutError(GraphLiteError):
+    """The query ran past the query timeout"""
+
+
+class DiskFullError(GraphLiteError):
+    """The database volume is low on space, so writes are rejected"""
+
+
+_ERROR_TYPES = {
+    ErrorCode.PARSE_ERROR: ParseError,
+    ErrorCode.CONSTRAINT_VIOLATION: ConstraintViolationError,
+    ErrorCode.PERMISSION_DENIED: PermissionDeniedError,
+    ErrorCode.TRANSACTION_CONFLICT: TransactionConflictError,
+    ErrorCode.GRAPH_NOT_FOUND: GraphNotFoundError,
+    ErrorCode.TIMEOUT: QueryTimeoutError,
+    ErrorCode.DISK_FULL: DiskFullError,
+}
+
+
+def _error(code: int, message: str) -> GraphLiteError:
+    """Exception of the most specific type for an FFI error code"""
+    code = ErrorCode(code)
+    return _ERROR_TYPES.get(code, GraphLiteError)(code, message)
+
+
 class QueryResult:
     """Query result wrapper with convenient access methods"""
 
@@ -170,6 +228,41 @@ _lib.graphlite_close_session.argtypes = [
 ]
 _lib.graphlite_close_session.restype = ctypes.c_int
 
+for _name in ("graphlite_begin", "graphlite_commit", "graphlite_rollback"):
+    getattr(_lib, _name).argtypes = [
+        ctypes.POINTER(_GraphLiteDB),
+        ctypes.c_char_p,
+        ctypes.POINTER(ctypes.c_int)
+    ]
+    getattr(_lib, _name).restype = ctypes.c_int
+
+_lib.graphlite_in_transaction.argtypes = [
+    ctypes.POINTER(_GraphLiteDB),
+    ctypes.c_char_p,
+    ctypes.POINTER(ctypes.c_int)
+]
+_lib.graphlite_in_transaction.restype = ctypes.c_bool
+
+_lib.graphlite_list_graphs.argtypes = [
+    ctypes.POINTER(_GraphLiteDB),
+    ctypes.c_char_p,
+    ctypes.POINTER(ctypes.c_size_t),
+    ctypes.POINTER(ctypes.c_int)
+]
+_lib.graphlite_list_graphs.restype = ctypes.POINTER(ctypes.c_char_p)
+
+_lib.graphlite_free_string_array.argtypes = [ctypes.POINTER(ctypes.c_char_p), ctypes.c_size_t]
+_lib.graphlite_free_string_array.restype = None
+
+for _name in ("graphlite_create_graph", "graphlite_drop_graph", "graphlite_set_session_graph"):
+    getattr(_lib, _name).argtypes = [
+        ctypes.POINTER(_GraphLiteDB),
+        ctypes.c_char_p,
+        ctypes.c_char_p,
+        ctypes.POINTER(ctypes.c_int)
+    ]
+    getattr(_lib, _name).restype = ctypes.c_int
+
 _lib.graphlite_free_string.argtypes = [ctypes.c_void_p]
 _lib.graphlite_free_string.restype = None
 
@@ -179,6 +272,12 @@ _lib.graphlite_close.restype = None
 _lib.graphlite_version.argtypes = []
 _lib.graphlite_version.restype = ctypes.c_void_p
 
+_lib.graphlite_abi_version.argtypes = []
+_lib.graphlite_abi_version.restype = ctypes.c_uint32
+
+_lib.graphlite_has_feature.argtypes = [ctypes.c_char_p]
+_lib.graphlite_has_feature.restype = ctypes.c_bool
+
 
 class GraphLite:
     """
@@ -214,8 +313,8 @@ class GraphLite:
         self._db = _lib.graphlite_open(path.encode('utf-8'), ctypes.byref(error))
 
         if not self._db:
-            raise GraphLiteError(
-                ErrorCode(error.value),
+            raise _error(
+                error.value,
                 f"Failed to open database at {path}"
             )
 
@@ -243,8 +342,8 @@ class GraphLite:
         )
 
         if not session_id_ptr:
-            raise GraphLiteError(
-                ErrorCode(error.value),
+            raise _error(
+                error.value,
                 f"Failed to create session for user '{username}'"
             )
 
@@ -281,8 +380,8 @@ class GraphLite:
         )
 
         if not result_ptr:
-            raise GraphLiteError(
-                ErrorCode(error.value),
+            raise _error(
+                error.value,
                 f"Query failed: {query[:100]}"
             )
 
@@ -309,6 +408,152 @@ class GraphLite:
         """
         self.query(session_id, statement)
 
+    def list_graphs(self, session_id: str) -> List[str]:
+        """
+        List the full paths of all graphs, e.g. ['/app/social'], sorted
+
+        Raises:
+            GraphLiteError: If listing fails
+        """
+        if not self._db:
+            raise GraphLiteError(ErrorCode.NULL_POINTER, "Database is closed")
+
+        count = ctypes.c_size_t(0)
+        error = ctypes.c_int(0)
+        graphs = _lib.graphlite_list_graphs(
+            self._db,
+            session_id.encode('utf-8'),
+            ctypes.byref(count),
+            ctypes.byref(error)
+        )
+        if not graphs:
+            raise _error(error.value, "Failed to list graphs")
+
+        try:
+            return [graphs[i].decode('utf-8') for i in range(count.value)]
+        finally:
+            _lib.graphlite_free_string_array(graphs, count)
+
+    def create_graph(self, session_id: str, graph_path: str) -> None:
+        """
+        Create a graph, e.g. '/app/social'; its schema must exist
+
+        Raises:
+            ConstraintViolationError: If the graph already exists
+            GraphNotFoundError: If the schema does not exist
+        """
+        self._graph_call("graphlite_create_graph", session_id, graph_path, "create graph")
+
+    def drop_graph(self, session_id: str, graph_path: str) -> None:
+        """
+        Drop a graph and its data
+
+        Raises:
+            GraphNotFoundError: If the graph does not exist
+        """
+        self._graph_call("graphlite_drop_graph", session_id, graph_path, "drop graph")
+
+    def set_graph(self, session_id: str, graph_path: str) -> None:
+        """
+        Make a graph the session's current graph
+
+        Raises:
+            GraphNotFoundError: If the graph does not exist
+        """
+        self._graph_call("graphlite_set_session_graph", session_id, graph_path, "set graph")
+
+    def _graph_call(self, function: str, session_id: str, graph_path: str, action: str) -> None:
+        if not self._db:
+            raise GraphLiteError(ErrorCode.NULL_POINTER, "Database is closed")
+
+        error = ctypes.c_int(0)
+        result = getattr(_lib, function)(
+            self._db,
+            session_id.encode('utf-8'),
+            graph_path.encode('utf-8'),
+            ctypes.byref(error)
+        )
+        if result != 0:
+            raise _error(error.value, f"Failed to {action} {graph_path}")
+
+    def begin(self, session_id: str) -> None:
+        """
+        Start a transaction on a session
+
+        Statements run on the session afterwards belong to the transaction
+        until commit() or rollback().
+
+        Raises:
+            TransactionConflictError: If the session already has an open transaction
+        """
+        self._transaction_control("graphlite_begin", session_id, "begin transaction")
+
+    def commit(self, session_id: str) -> None:
+        """
+        Commit the open transaction of a session
+
+        Raises:
+            TransactionConflictError: If the session has no open transaction
+        """
+        self._transaction_control("graphlite_commit", session_id, "commit")
+
+    def rollback(self, session_id: str) -> None:
+        """
+        Roll back the open transaction of a session
+
+        Raises:
+            TransactionConflictError: If the session has no open transaction
+        """
+        self._transaction_control("graphlite_rollback", session_id, "roll back")
+
+    def in_transaction(self, session_id: str) -> bool:
+        """Check whether a session has an open transaction"""
+        if not self._db:
+            raise GraphLiteError(ErrorCode.NULL_POINTER, "Database is closed")
+
+        error = ctypes.c_int(0)
+        active = _lib.graphlite_in_transaction(
+            self._db,
+            session_id.encode('utf-8'),
+            ctypes.byref(error)
+        )
+        if error.value != 0:
+            raise _error(error.value, f"Failed to read transaction state of session {session_id}")
+        return active
+
+    @contextmanager
+    def transaction(self, session_id: str) -> Iterator[None]:
+        """
+        Run the statements of a with-block in one transaction
+
+        Commits when the block finishes and rolls back if it raises.
+
+        Example:
+            >>> with db.transaction(session):
+            ...     db.execute(session, "INSERT (:Account {id: 1})")
+            ...     db.execute(session, "INSERT (:Account {id: 2})")
+        """
+        self.begin(session_id)
+        try:
+            yield
+        except BaseException:
+            self.rollback(session_id)
+            raise
+        self.commit(session_id)
+
+    def _transaction_control(self, function: str, session_id: str, action: str) -> None:
+        if not self._db:
+            raise GraphLiteError(ErrorCode.NULL_POINTER, "Database is closed")
+
+        error = ctypes.c_int(0)
+        result = getattr(_lib, function)(
+            self._db,
+            session_id.encode('utf-8'),
+            ctypes.byref(error)
+        )
+        if result != 0:
+            raise _error(error.value, f"Failed to {action} on session {session_id}")
+
     def close_session(self, session_id: str) -> None:
         """
         Close a session
@@ -330,8 +575,8 @@ class GraphLite:
         )
 
         if result != 0:
-            raise GraphLiteError(
-                ErrorCode(error.value),
+            raise _error(
+                error.value,
                 f"Failed to close session {session_id}"
             )
 
@@ -371,4 +616,14 @@ class GraphLite:
             # Don't free - version() returns a static string
             version = ctypes.string_at(version_ptr).decode('utf-8')
             return version
-        return "unknown"
\ No newline at end of file
+        return "unknown"
+
+    @staticmethod
+    def abi_version() -> int:
+        """Get the ABI version of the loaded GraphLite library"""
+        return _lib.graphlite_abi_version()
+
+    @staticmethod
+    def has_feature(name: str) -> bool:
+        """Check whether the loaded library supports an optional feature, e.g. 'msgpack'"""
+        return bool(_lib.graphlite_has_feature(name.encode('utf-8')))
\ No newline at end of file
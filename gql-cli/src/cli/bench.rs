@@ -0,0 +1,529 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! Benchmark for `graphlite bench`
+//!
+//! Creates a fresh database, loads the social sample graph (`gql.sample_data`)
+//! at the requested scale, then runs every workload for a fixed time at each
+//! concurrency level and reports throughput and latency percentiles. Read
+//! workloads run before write workloads, so reads always see the graph as
+//! generated. Parameters vary from one operation to the next but are chosen
+//! deterministically, so runs with the same settings do the same work.
+
+use colored::Colorize;
+use comfy_table::{presets::UTF8_FULL, Cell, CellAlignment, Color, Table};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Barrier;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use super::commands::{BenchArgs, BenchFormat};
+use super::watch::parse_interval;
+use graphlite::{QueryCoordinator, Value};
+
+/// Graph the sample data is loaded into
+const BENCH_GRAPH: &str = "/bench/social";
+
+/// Whether a workload reads or writes the graph
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum WorkloadKind {
+    Read,
+    Write,
+}
+
+impl WorkloadKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            WorkloadKind::Read => "read",
+            WorkloadKind::Write => "write",
+        }
+    }
+}
+
+/// What one operation of a workload needs to pick its parameters
+struct Operation {
+    /// Index of the worker thread running it
+    worker: u64,
+    /// Operations this worker has run before in the current run
+    sequence: u64,
+    /// Number shared by no other operation of the benchmark
+    unique: u64,
+    /// People in the generated graph; their ids are `1..=people`
+    people: u64,
+}
+
+impl Operation {
+    /// Id of an existing person, spread over the whole range
+    fn person(&self, salt: u64) -> Value {
+        let spread = self
+            .sequence
+            .wrapping_mul(7919)
+            .wrapping_add(self.worker.wrapping_mul(104_729))
+            .wrapping_add(salt);
+        Value::Number((spread % self.people + 1) as f64)
+    }
+
+    /// A number in `[low, high)`
+    fn number(&self, low: u64, high: u64) -> Value {
+        Value::Number((low + self.unique % (high - low)) as f64)
+    }
+}
+
+/// A query run over and over with varying parameters
+struct Workload {
+    name: &'static str,
+    kind: WorkloadKind,
+    query: &'static str,
+    params: fn(&Operation) -> Vec<(&'static str, Value)>,
+}
+
+/// Every workload, reads first
+static WORKLOADS: [Workload; 8] = [
+    Workload {
+        name: "point_lookup",
+        kind: WorkloadKind::Read,
+        query: "MATCH (p:Person {id: $id}) RETURN p.name AS name",
+        params: |op| vec![("id", op.person(0))],
+    },
+    Workload {
+        name: "neighbors",
+        kind: WorkloadKind::Read,
+        query: "MATCH (p:Person {id: $id})-[:KNOWS]->(f:Person) RETURN f.name AS name",
+        params: |op| vec![("id", op.person(0))],
+    },
+    Workload {
+        name: "two_hop",
+        kind: WorkloadKind::Read,
+        query: "MATCH (p:Person {id: $id})-[:KNOWS]->(:Person)-[:KNOWS]->(f:Person) \
+                RETURN COUNT(f) AS reach",
+        params: |op| vec![("id", op.person(0))],
+    },
+    Workload {
+        name: "filter_count",
+        kind: WorkloadKind::Read,
+        query: "MATCH (p:Person) WHERE p.age >= $age RETURN COUNT(p) AS people",
+        params: |op| vec![("age", op.number(18, 80))],
+    },
+    Workload {
+        name: "group_by_city",
+        kind: WorkloadKind::Read,
+        query: "MATCH (p:Person)-[:LIVES_IN]->(c:City) \
+                RETURN c.name AS city, COUNT(p) AS residents",
+        params: |_| Vec::new(),
+    },
+    Workload {
+        name: "insert_node",
+        kind: WorkloadKind::Write,
+        query: "INSERT (:Person {id: $id, name: $name, age: $age, interest: 'benchmark'})",
+        params: |op| {
+            let id = op.people + op.unique;
+            vec![
+                ("id", Value::Number(id as f64)),
+                ("name", Value::String(format!("Person{}", id))),
+                ("age", op.number(18, 80)),
+            ]
+        },
+    },
+    Workload {
+        name: "insert_edge",
+        kind: WorkloadKind::Write,
+        query: "MATCH (a:Person {id: $from}), (b:Person {id: $to}) \
+                INSERT (a)-[:KNOWS {since: $since}]->(b)",
+        params: |op| {
+            vec![
+                ("from", op.person(0)),
+                ("to", op.person(1)),
+                // Unique, so no two operations describe the same edge
+                ("since", Value::Number(op.unique as f64)),
+            ]
+        },
+    },
+    Workload {
+        name: "update_property",
+        kind: WorkloadKind::Write,
+        query: "MATCH (p:Person {id: $id}) SET p.age = $age",
+        params: |op| vec![("id", op.person(0)), ("age", op.number(18, 80))],
+    },
+];
+
+/// Measurements of one workload at one concurrency level
+struct RunStats {
+    workload: &'static str,
+    kind: WorkloadKind,
+    threads: usize,
+    operations: usize,
+    elapsed: Duration,
+    /// Latency of every measured operation, sorted
+    latencies: Vec<Duration>,
+}
+
+impl RunStats {
+    fn ops_per_sec(&self) -> f64 {
+        self.operations as f64 / self.elapsed.as_secs_f64().max(f64::EPSILON)
+    }
+
+    /// Nearest-rank percentile latency
+    fn percentile(&self, percent: f64) -> Duration {
+        if self.latencies.is_empty() {
+            return Duration::ZERO;
+        }
+        let rank = (percent / 100.0 * self.latencies.len() as f64).ceil() as usize;
+        self.latencies[rank.clamp(1, self.latencies.len()) - 1]
+    }
+
+    fn max(&self) -> Duration {
+        self.latencies.last().copied().unwrap_or_default()
+    }
+}
+
+/// What one worker thread measured
+struct WorkerRun {
+    latencies: Vec<Duration>,
+    measure_from: Instant,
+    finished: Instant,
+}
+
+/// The loaded benchmark graph
+struct Dataset {
+    nodes: u64,
+    edges: u64,
+    people: u64,
+    load_time: Duration,
+}
+
+/// Parse `--warmup`, which unlike other intervals may be zero (`0`, `0s`)
+pub fn parse_warmup(s: &str) -> Result<Duration, String> {
+    let number = s.trim().trim_end_matches(|c: char| c.is_ascii_alphabetic());
+    match number.parse::<f64>() {
+        Ok(0.0) => Ok(Duration::ZERO),
+        _ => parse_interval(s),
+    }
+}
+
+/// Run the benchmark in a new database, deleting it afterwards unless `--keep` is given
+pub fn run_bench(args: &BenchArgs) -> Result<(), String> {
+    let workloads = selected_workloads(&args.workload)?;
+    let threads: Vec<usize> = args.threads.iter().map(|&n| n.max(1)).collect();
+    let path = args.path.clone().unwrap_or_else(|| {
+        std::env::temp_dir().join(format!("graphlite-bench-{}", std::process::id()))
+    });
+    if path.exists() {
+        return Err(format!(
+            "{:?} already exists; the benchmark needs a path for a new database",
+            path
+        ));
+    }
+
+    let outcome = benchmark(&path, args, &workloads, &threads);
+
+    if args.keep {
+        eprintln!("Benchmark database kept at {:?}", path);
+    } else if let Err(e) = std::fs::remove_dir_all(&path) {
+        log::warn!("Failed to remove benchmark database {:?}: {}", path, e);
+    }
+
+    let (dataset, stats) = outcome?;
+    match args.format {
+        BenchFormat::Table => print_table(args, &dataset, &stats),
+        BenchFormat::Json => println!("{:#}", report_json(args, &dataset, &stats)),
+    }
+    Ok(())
+}
+
+/// Workloads named by `--workload`, or all of them
+fn selected_workloads(names: &[String]) -> Result<Vec<&'static Workload>, String> {
+    if names.is_empty() {
+        return Ok(WORKLOADS.iter().collect());
+    }
+    for name in names {
+        if !WORKLOADS.iter().any(|workload| workload.name == name) {
+            return Err(format!(
+                "Unknown workload '{}'. Valid workloads: {}",
+                name,
+                WORKLOADS
+                    .iter()
+                    .map(|workload| workload.name)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+    }
+    // Keep the suite order, so reads still run before writes
+    Ok(WORKLOADS
+        .iter()
+        .filter(|workload| names.iter().any(|name| name == workload.name))
+        .collect())
+}
+
+/// Load the graph and run every workload at every concurrency level
+fn benchmark(
+    path: &Path,
+    args: &BenchArgs,
+    workloads: &[&Workload],
+    threads: &[usize],
+) -> Result<(Dataset, Vec<RunStats>), String> {
+    let coordinator = QueryCoordinator::from_path(path)
+        .map_err(|e| format!("Failed to create benchmark database: {}", e))?;
+
+    eprintln!(
+        "{}",
+        format!("Loading social sample graph at scale {}...", args.scale).bold()
+    );
+    let dataset = load_dataset(&coordinator, args.scale)?;
+    eprintln!(
+        "  → {} nodes and {} edges in {} ms",
+        dataset.nodes,
+        dataset.edges,
+        dataset.load_time.as_millis()
+    );
+
+    // One session per worker, all on the benchmark graph
+    let workers = threads.iter().copied().max().unwrap_or(1);
+    let sessions = (0..workers)
+        .map(|_| open_session(&coordinator))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let unique = AtomicU64::new(1);
+    let mut stats = Vec::new();
+    for workload in workloads {
+        for &count in threads {
+            eprintln!("  → {} with {} thread(s)", workload.name, count);
+            stats.push(run_workload(
+                &coordinator,
+                &sessions[..count],
+                workload,
+                dataset.people,
+                &unique,
+                args,
+            )?);
+        }
+    }
+
+    for session_id in &sessions {
+        let _ = coordinator.close_session(session_id);
+    }
+    Ok((dataset, stats))
+}
+
+/// Create the benchmark graph and fill it with sample data
+fn load_dataset(coordinator: &QueryCoordinator, scale: f64) -> Result<Dataset, String> {
+    let session_id = open_session_on(coordinator, None)?;
+    let started = Instant::now();
+    let loaded = coordinator.process_query(
+        &format!("CALL gql.sample_data('social', {})", scale),
+        &session_id,
+    )?;
+    let load_time = started.elapsed();
+    let people =
+        coordinator.process_query("MATCH (p:Person) RETURN MAX(p.id) AS people", &session_id)?;
+    let _ = coordinator.close_session(&session_id);
+
+    let number = |result: &graphlite::QueryResult, column: &str| {
+        result
+            .rows
+            .first()
+            .and_then(|row| row.values.get(column))
+            .and_then(Value::as_number)
+            .ok_or_else(|| format!("Sample data load returned no '{}' value", column))
+    };
+    Ok(Dataset {
+        nodes: number(&loaded, "nodes_created")? as u64,
+        edges: number(&loaded, "edges_created")? as u64,
+        people: number(&people, "people")? as u64,
+        load_time,
+    })
+}
+
+/// Session with the benchmark graph as its current graph
+fn open_session(coordinator: &QueryCoordinator) -> Result<String, String> {
+    open_session_on(coordinator, Some(BENCH_GRAPH))
+}
+
+/// Session on `graph`, or on a newly created benchmark graph when `None`
+fn open_session_on(coordinator: &QueryCoordinator, graph: Option<&str>) -> Result<String, String> {
+    let session_id = coordinator.create_simple_session("bench")?;
+    let setup = match graph {
+        Some(graph) => vec![format!("SESSION SET GRAPH {}", graph)],
+        None => vec![
+            "CREATE SCHEMA IF NOT EXISTS /bench".to_string(),
+            format!("CREATE GRAPH {}", BENCH_GRAPH),
+            format!("SESSION SET GRAPH {}", BENCH_GRAPH),
+        ],
+    };
+    for statement in setup {
+        coordinator.process_query(&statement, &session_id)?;
+    }
+    Ok(session_id)
+}
+
+/// Run `workload` on one thread per session for the warmup and measured time
+///
+/// The first failing operation stops the whole benchmark, since a workload that
+/// fails says nothing useful about performance.
+fn run_workload(
+    coordinator: &QueryCoordinator,
+    sessions: &[String],
+    workload: &Workload,
+    people: u64,
+    unique: &AtomicU64,
+    args: &BenchArgs,
+) -> Result<RunStats, String> {
+    let start_line = Barrier::new(sessions.len());
+    let results: Vec<Result<WorkerRun, String>> = thread::scope(|scope| {
+        let handles: Vec<_> = sessions
+            .iter()
+            .enumerate()
+            .map(|(worker, session_id)| {
+                let start_line = &start_line;
+                scope.spawn(move || {
+                    start_line.wait();
+                    let measure_from = Instant::now() + args.warmup;
+                    let measure_until = measure_from + args.duration;
+                    let mut latencies = Vec::new();
+                    let mut finished = measure_from;
+                    for sequence in 0.. {
+                        let operation = Operation {
+                            worker: worker as u64,
+                            sequence,
+                            unique: unique.fetch_add(1, Ordering::Relaxed),
+                            people,
+                        };
+                        let params: HashMap<String, Value> = (workload.params)(&operation)
+                            .into_iter()
+                            .map(|(name, value)| (name.to_string(), value))
+                            .collect();
+
+                        let started = Instant::now();
+                        coordinator
+                            .process_query_with_params(workload.query, &params, session_id)
+                            .map_err(|e| format!("Workload {} failed: {}", workload.name, e))?;
+                        finished = Instant::now();
+
+                        if started >= measure_from {
+                            latencies.push(finished - started);
+                        }
+                        if finished >= measure_until {
+                            break;
+                        }
+                    }
+                    Ok(WorkerRun {
+                        latencies,
+                        measure_from,
+                        finished,
+                    })
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| {
+                handle
+                    .join()
+                    .unwrap_or_else(|_| Err(format!("Workload {} panicked", workload.name)))
+            })
+            .collect()
+    });
+
+    let runs = results.into_iter().collect::<Result<Vec<_>, _>>()?;
+    // From the first worker starting to measure until the last one finished
+    let elapsed = match (
+        runs.iter().map(|run| run.measure_from).min(),
+        runs.iter().map(|run| run.finished).max(),
+    ) {
+        (Some(from), Some(until)) => until.saturating_duration_since(from),
+        _ => args.duration,
+    };
+    let mut latencies: Vec<Duration> = runs.into_iter().flat_map(|run| run.latencies).collect();
+    latencies.sort_unstable();
+
+    Ok(RunStats {
+        workload: workload.name,
+        kind: workload.kind,
+        threads: sessions.len(),
+        operations: latencies.len(),
+        elapsed,
+        latencies,
+    })
+}
+
+fn millis(duration: Duration) -> f64 {
+    (duration.as_secs_f64() * 1_000_000.0).round() / 1000.0
+}
+
+/// Print the results as a table
+fn print_table(args: &BenchArgs, dataset: &Dataset, stats: &[RunStats]) {
+    println!(
+        "{}",
+        format!(
+            "GraphLite {} benchmark: social graph at scale {} ({} nodes, {} edges), {} ms per run",
+            graphlite::VERSION,
+            args.scale,
+            dataset.nodes,
+            dataset.edges,
+            args.duration.as_millis()
+        )
+        .bold()
+        .green()
+    );
+
+    let mut table = Table::new();
+    table.load_preset(UTF8_FULL);
+    table.set_header(
+        [
+            "workload", "type", "threads", "ops", "ops/s", "p50 ms", "p95 ms", "p99 ms", "max ms",
+        ]
+        .into_iter()
+        .map(|column| Cell::new(column).fg(Color::Green)),
+    );
+    for run in stats {
+        let number = |text: String| Cell::new(text).set_alignment(CellAlignment::Right);
+        table.add_row(vec![
+            Cell::new(run.workload),
+            Cell::new(run.kind.as_str()),
+            number(run.threads.to_string()),
+            number(run.operations.to_string()),
+            number(format!("{:.1}", run.ops_per_sec())),
+            number(format!("{:.3}", millis(run.percentile(50.0)))),
+            number(format!("{:.3}", millis(run.percentile(95.0)))),
+            number(format!("{:.3}", millis(run.percentile(99.0)))),
+            number(format!("{:.3}", millis(run.max()))),
+        ]);
+    }
+    println!("{}", table);
+}
+
+/// The results as a JSON document, for comparing runs over time
+fn report_json(args: &BenchArgs, dataset: &Dataset, stats: &[RunStats]) -> serde_json::Value {
+    serde_json::json!({
+        "version": graphlite::VERSION,
+        "dataset": {
+            "domain": "social",
+            "scale": args.scale,
+            "nodes": dataset.nodes,
+            "edges": dataset.edges,
+            "load_ms": millis(dataset.load_time),
+        },
+        "duration_ms": millis(args.duration),
+        "warmup_ms": millis(args.warmup),
+        "results": stats
+            .iter()
+            .map(|run| {
+                serde_json::json!({
+                    "workload": run.workload,
+                    "type": run.kind.as_str(),
+                    "threads": run.threads,
+                    "operations": run.operations,
+                    "ops_per_sec": (run.ops_per_sec() * 10.0).round() / 10.0,
+                    "latency_ms": {
+                        "p50": millis(run.percentile(50.0)),
+                        "p95": millis(run.percentile(95.0)),
+                        "p99": millis(run.percentile(99.0)),
+                        "max": millis(run.max()),
+                    },
+                })
+            })
+            .collect::<Vec<_>>(),
+    })
+}
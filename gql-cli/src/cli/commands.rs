@@ -3,8 +3,17 @@
 //
 //! CLI command definitions for GraphLite
 
-use clap::{Parser, Subcommand, ValueEnum};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use std::net::SocketAddr;
 use std::path::PathBuf;
+use std::time::Duration;
+
+use super::bench::parse_warmup;
+use super::errors::ErrorFormat;
+use super::params::{parse_param, QueryParam};
+use super::script::{parse_var, ScriptVar};
+use super::watch::{parse_interval, UntilCondition};
+use graphlite::{Notation, NumberFormat};
 
 /// Log level options
 #[derive(Clone, Copy, Debug, ValueEnum)]
@@ -71,17 +80,43 @@ pub enum Commands {
 
     /// Execute a GQL query
     Query {
-        /// The GQL query to execute
+        /// The GQL query to execute, or `-` to read `;`-separated statements from stdin
         query: String,
 
+        /// Bind a query parameter: `--param id=42 --param name='Ann'` (repeatable)
+        #[arg(long = "param", value_name = "NAME=VALUE", value_parser = parse_param)]
+        params: Vec<QueryParam>,
+
+        /// Read query parameters from a JSON object file
+        #[arg(long, value_name = "FILE")]
+        params_file: Option<PathBuf>,
+
         /// Database path
         #[arg(long, default_value = "./db")]
         path: PathBuf,
 
-        /// Output format
+        /// Output format: table, csv, json, ndjson or markdown
         #[arg(short, long, default_value = "table")]
         format: OutputFormat,
 
+        /// Leave out the column header row (and the table format's summary lines)
+        #[arg(long)]
+        no_header: bool,
+
+        #[command(flatten)]
+        numbers: NumberArgs,
+
+        #[command(flatten)]
+        limits: DisplayLimits,
+
+        /// Print no results, only errors
+        #[arg(short, long, conflicts_with = "watch")]
+        quiet: bool,
+
+        /// With statements from stdin, stop at the first that fails
+        #[arg(long, conflicts_with = "watch")]
+        fail_fast: bool,
+
         /// Show execution plan
         #[arg(short, long)]
         explain: bool,
@@ -89,6 +124,22 @@ pub enum Commands {
         /// Show Abstract Syntax Tree (AST)
         #[arg(long)]
         ast: bool,
+
+        /// Re-run the query on this interval (e.g. 5s, 500ms, 2m), highlighting changed rows
+        #[arg(short, long, value_parser = parse_interval)]
+        watch: Option<Duration>,
+
+        /// Stop watching once the result satisfies a condition (e.g. 'row_count > 0')
+        #[arg(long, requires = "watch")]
+        until: Option<UntilCondition>,
+
+        /// With --watch, redraw without highlighting changed rows
+        #[arg(long, requires = "watch")]
+        no_highlight: bool,
+
+        /// Error output format; failures also set a per-category exit code
+        #[arg(long, value_enum, default_value_t = ErrorFormat::Text)]
+        error_format: ErrorFormat,
     },
 
     /// Interactive GQL console (REPL)
@@ -100,6 +151,9 @@ pub enum Commands {
         /// Load sample data on startup
         #[arg(short, long)]
         sample: bool,
+
+        #[command(flatten)]
+        limits: DisplayLimits,
     },
 
     /// Install and initialize GraphLite
@@ -125,6 +179,73 @@ pub enum Commands {
         yes: bool,
     },
 
+    /// Check the stored data for corruption
+    Check {
+        /// Database path
+        #[arg(long, default_value = "./db")]
+        path: PathBuf,
+
+        /// Read every record and verify its checksum (slower)
+        #[arg(long)]
+        deep: bool,
+    },
+
+    /// Verify checksums, edge references and catalog consistency
+    Verify {
+        /// Database path
+        #[arg(long, default_value = "./db")]
+        path: PathBuf,
+
+        /// Delete edges whose source or target node is missing
+        #[arg(long)]
+        repair: bool,
+    },
+
+    /// Print per-graph counts, index sizes, disk usage and cache hit rates
+    Stats(StatsArgs),
+
+    /// Print the committed writes recorded in the write-ahead log as GQL
+    Waldump {
+        /// Database path
+        #[arg(long, default_value = "./db")]
+        path: PathBuf,
+
+        /// Start at this log sequence number
+        #[arg(long, default_value_t = 0)]
+        from_lsn: u64,
+
+        /// Output format
+        #[arg(short, long, value_enum, default_value_t = WaldumpFormat::Gql)]
+        format: WaldumpFormat,
+    },
+
+    /// Run a file of GQL statements separated by `;`
+    Run(RunArgs),
+
+    /// Load nodes or edges from a CSV or JSON Lines file
+    Import(ImportArgs),
+
+    /// Write a graph or query result to a CSV, JSON Lines or GraphML file
+    Export(ExportArgs),
+
+    /// Serve the database over an HTTP API
+    Serve(ServeArgs),
+
+    /// Benchmark read and write workloads on a generated graph
+    Bench(BenchArgs),
+
+    /// Run the GQL conformance corpus and report which features pass
+    Conformance(ConformanceArgs),
+
+    /// Apply or revert versioned GQL migration files
+    Migrate(MigrateArgs),
+
+    /// Add, remove and list users and change their passwords
+    Users(UsersArgs),
+
+    /// Create and drop roles and grant them to users
+    Roles(RolesArgs),
+
     /// Session management commands
     Session {
         #[command(subcommand)]
@@ -136,6 +257,423 @@ pub enum Commands {
     },
 }
 
+/// Arguments of `graphlite run`
+#[derive(Args)]
+pub struct RunArgs {
+    /// GQL script to run
+    pub script: PathBuf,
+
+    /// Database path
+    #[arg(long, default_value = "./db")]
+    pub path: PathBuf,
+
+    /// Run the script in one transaction, rolled back if a statement fails
+    #[arg(long)]
+    pub transaction: bool,
+
+    /// Stop at the first statement that fails (always on with --transaction)
+    #[arg(long)]
+    pub stop_on_error: bool,
+
+    /// Replace `${NAME}` in the script with VALUE (repeatable)
+    #[arg(long = "var", value_name = "NAME=VALUE", value_parser = parse_var)]
+    pub vars: Vec<ScriptVar>,
+
+    /// Output format of statement results: table, csv, json, ndjson or markdown
+    #[arg(short, long, default_value = "table")]
+    pub format: OutputFormat,
+
+    /// Only print errors and the summary
+    #[arg(short, long)]
+    pub quiet: bool,
+
+    #[command(flatten)]
+    pub numbers: NumberArgs,
+}
+
+/// Arguments of `graphlite import`
+#[derive(Args)]
+pub struct ImportArgs {
+    /// Database path
+    #[arg(long, default_value = "./db")]
+    pub path: PathBuf,
+
+    /// Graph to load into (e.g. /schema/graph)
+    #[arg(long)]
+    pub graph: String,
+
+    /// File to read
+    #[arg(long)]
+    pub file: PathBuf,
+
+    /// File format (default: from the extension, .csv/.tsv or .jsonl/.ndjson)
+    #[arg(long, value_enum)]
+    pub format: Option<ImportFormat>,
+
+    /// Load each record as a node with this label
+    #[arg(
+        long,
+        required_unless_present = "edge_label",
+        conflicts_with = "edge_label"
+    )]
+    pub label: Option<String>,
+
+    /// Load each record as an edge with this label between nodes already in the graph
+    #[arg(long, requires_all = ["from_column", "to_column"])]
+    pub edge_label: Option<String>,
+
+    /// Key column: must be unique among imported nodes; edge endpoints are matched
+    /// against this node property (default for edges: id)
+    #[arg(long)]
+    pub id_column: Option<String>,
+
+    /// Column with the key of each edge's start node
+    #[arg(long, requires = "edge_label")]
+    pub from_column: Option<String>,
+
+    /// Column with the key of each edge's end node
+    #[arg(long, requires = "edge_label")]
+    pub to_column: Option<String>,
+
+    /// Only match start nodes with this label
+    #[arg(long, requires = "edge_label")]
+    pub from_label: Option<String>,
+
+    /// Only match end nodes with this label
+    #[arg(long, requires = "edge_label")]
+    pub to_label: Option<String>,
+
+    /// CSV field separator
+    #[arg(long, default_value_t = ',')]
+    pub delimiter: char,
+
+    /// The CSV file has no header row; columns are named column1, column2, ...
+    #[arg(long)]
+    pub no_header: bool,
+
+    /// Keep CSV fields as strings instead of inferring numbers and booleans
+    #[arg(long)]
+    pub no_infer_types: bool,
+
+    /// Records written per batch; each batch is stored as a whole or not at all
+    #[arg(long, default_value_t = 10_000)]
+    pub batch_size: usize,
+}
+
+/// File format of `graphlite import`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum ImportFormat {
+    /// Comma-separated values with a header row
+    Csv,
+    /// One JSON object per line
+    Jsonl,
+}
+
+/// Arguments of `graphlite export`
+#[derive(Args)]
+pub struct ExportArgs {
+    /// Database path
+    #[arg(long, default_value = "./db")]
+    pub path: PathBuf,
+
+    /// Graph to export, or to run --query against (e.g. /schema/graph)
+    #[arg(long)]
+    pub graph: String,
+
+    /// Export the result of this query instead of the whole graph
+    #[arg(long)]
+    pub query: Option<String>,
+
+    /// Output format (default: from the --output extension, otherwise jsonl)
+    #[arg(short, long, value_enum)]
+    pub format: Option<ExportFormat>,
+
+    /// File to write (default: standard output)
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+}
+
+/// File format of `graphlite export`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum ExportFormat {
+    /// Comma-separated values with a header row
+    Csv,
+    /// One JSON object per line
+    Jsonl,
+    /// GraphML XML document
+    Graphml,
+}
+
+/// Arguments of `graphlite serve`
+#[derive(Args)]
+pub struct ServeArgs {
+    /// Database path
+    #[arg(long, default_value = "./db")]
+    pub path: PathBuf,
+
+    /// Address and port to listen on
+    #[arg(long, default_value = "127.0.0.1:7474")]
+    pub listen: SocketAddr,
+
+    /// Worker threads handling requests
+    #[arg(long, default_value_t = 4)]
+    pub threads: usize,
+
+    /// Let clients open sessions and run queries without credentials
+    #[arg(long)]
+    pub allow_anonymous: bool,
+
+    /// If another process has the database open, wait and take over when it exits
+    #[arg(long)]
+    pub standby: bool,
+
+    #[command(flatten)]
+    pub numbers: NumberArgs,
+}
+
+/// Arguments of `graphlite bench`
+#[derive(Args)]
+pub struct BenchArgs {
+    /// Directory for the benchmark database; must not exist yet (default: a temporary directory)
+    #[arg(long)]
+    pub path: Option<PathBuf>,
+
+    /// Keep the benchmark database instead of deleting it afterwards
+    #[arg(long)]
+    pub keep: bool,
+
+    /// Size of the generated social graph; 1.0 is 100 people, 200 posts and about 1,100 edges
+    #[arg(long, default_value_t = 10.0)]
+    pub scale: f64,
+
+    /// Concurrency levels; every workload runs once at each (e.g. 1,4,8)
+    #[arg(long, value_delimiter = ',', default_value = "1,4")]
+    pub threads: Vec<usize>,
+
+    /// How long each workload is measured at each concurrency level (e.g. 5s, 500ms)
+    #[arg(long, value_parser = parse_interval, default_value = "3s")]
+    pub duration: Duration,
+
+    /// Unmeasured time each run starts with, so caches are warm
+    #[arg(long, value_parser = parse_warmup, default_value = "500ms")]
+    pub warmup: Duration,
+
+    /// Only run this workload (repeatable): point_lookup, neighbors, two_hop,
+    /// filter_count, group_by_city, insert_node, insert_edge, update_property
+    #[arg(long)]
+    pub workload: Vec<String>,
+
+    /// Report format
+    #[arg(short, long, value_enum, default_value_t = BenchFormat::Table)]
+    pub format: BenchFormat,
+}
+
+/// Report format of `graphlite bench`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum BenchFormat {
+    /// Table for reading
+    #[default]
+    Table,
+    /// JSON document for comparing runs over time
+    Json,
+}
+
+/// Arguments of `graphlite conformance`
+#[derive(Args)]
+pub struct ConformanceArgs {
+    /// Only run features whose id starts with this (e.g. match. or txn.commit)
+    #[arg(long)]
+    pub feature: Option<String>,
+
+    /// Show why unsupported features fail as well as unexpected failures
+    #[arg(short, long)]
+    pub verbose: bool,
+
+    /// Report format
+    #[arg(short, long, value_enum, default_value_t = ConformanceFormat::Table)]
+    pub format: ConformanceFormat,
+}
+
+/// Report format of `graphlite conformance`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum ConformanceFormat {
+    /// Table for reading
+    #[default]
+    Table,
+    /// JSON document with one entry per feature
+    Json,
+}
+
+/// Arguments of `graphlite stats`
+#[derive(Args)]
+pub struct StatsArgs {
+    /// Database path
+    #[arg(long, default_value = "./db")]
+    pub path: PathBuf,
+
+    /// Only show this graph (full path, e.g. /social/people)
+    #[arg(long)]
+    pub graph: Option<String>,
+
+    /// Report format
+    #[arg(short, long, value_enum, default_value_t = StatsFormat::Table)]
+    pub format: StatsFormat,
+}
+
+/// Report format of `graphlite stats`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum StatsFormat {
+    /// Tables for reading
+    #[default]
+    Table,
+    /// JSON document with one entry per graph
+    Json,
+}
+
+/// Arguments of `graphlite migrate`
+#[derive(Args)]
+pub struct MigrateArgs {
+    #[command(subcommand)]
+    pub action: MigrateAction,
+
+    /// Database path
+    #[arg(long, default_value = "./db", global = true)]
+    pub path: PathBuf,
+
+    /// Directory of migration files (`<version>_<name>.gql`, or `.up.gql` with `.down.gql`)
+    #[arg(long, default_value = "./migrations", global = true)]
+    pub dir: PathBuf,
+
+    /// Graph applied versions are recorded in, shared with the SDK's `Migrations`
+    #[arg(long, default_value = "/graphlite/migrations", global = true)]
+    pub history_graph: String,
+}
+
+/// Migration subcommands
+#[derive(Subcommand)]
+pub enum MigrateAction {
+    /// List the migrations and whether each is applied
+    Status,
+
+    /// Apply the pending migrations in version order
+    Up {
+        /// Stop after applying this version
+        #[arg(long)]
+        to: Option<u64>,
+    },
+
+    /// Revert the latest applied migration with its down script
+    Down {
+        /// Revert every applied migration newer than this version instead
+        #[arg(long)]
+        to: Option<u64>,
+    },
+}
+
+/// Arguments of `graphlite users`
+#[derive(Args)]
+pub struct UsersArgs {
+    #[command(subcommand)]
+    pub action: UsersAction,
+
+    /// Database path
+    #[arg(long, default_value = "./db", global = true)]
+    pub path: PathBuf,
+}
+
+/// User management subcommands
+#[derive(Subcommand)]
+pub enum UsersAction {
+    /// Create a user
+    Add {
+        /// Name of the new user
+        username: String,
+
+        /// Password of the new user (prompted for if not given)
+        #[arg(long)]
+        new_password: Option<String>,
+
+        /// Role to grant the user; repeat for several
+        #[arg(long = "role", value_name = "ROLE")]
+        roles: Vec<String>,
+    },
+
+    /// Drop a user
+    Remove {
+        /// Name of the user
+        username: String,
+
+        /// Succeed without doing anything if the user does not exist
+        #[arg(long)]
+        if_exists: bool,
+    },
+
+    /// Change a user's password
+    Passwd {
+        /// Name of the user
+        username: String,
+
+        /// New password (prompted for if not given)
+        #[arg(long)]
+        new_password: Option<String>,
+    },
+
+    /// List the users with their roles
+    List,
+}
+
+/// Arguments of `graphlite roles`
+#[derive(Args)]
+pub struct RolesArgs {
+    #[command(subcommand)]
+    pub action: RolesAction,
+
+    /// Database path
+    #[arg(long, default_value = "./db", global = true)]
+    pub path: PathBuf,
+}
+
+/// Role management subcommands
+#[derive(Subcommand)]
+pub enum RolesAction {
+    /// Create a role
+    Create {
+        /// Name of the new role
+        role: String,
+    },
+
+    /// Drop a role that no user has
+    Drop {
+        /// Name of the role
+        role: String,
+
+        /// Succeed without doing anything if the role does not exist
+        #[arg(long)]
+        if_exists: bool,
+    },
+
+    /// Grant a role to a user
+    Grant {
+        /// Name of the role
+        role: String,
+
+        /// Name of the user
+        username: String,
+    },
+
+    /// Revoke a role from a user
+    Revoke {
+        /// Name of the role
+        role: String,
+
+        /// Name of the user
+        username: String,
+    },
+
+    /// List the roles with the users that have them
+    List,
+}
+
 /// Session management subcommands
 #[derive(Subcommand)]
 pub enum SessionAction {
@@ -173,12 +711,110 @@ pub enum SessionAction {
     Close,
 }
 
+/// Output format of `graphlite waldump`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum WaldumpFormat {
+    /// GQL script that replays the changes
+    #[default]
+    Gql,
+    /// One JSON object per statement
+    Json,
+}
+
 /// Output format options
 #[derive(Clone, Copy, Debug)]
 pub enum OutputFormat {
     Table,
     Json,
     Csv,
+    /// One compact JSON object per row (JSON Lines)
+    Ndjson,
+    /// GitHub-flavored Markdown table
+    Markdown,
+}
+
+/// How `graphlite query` prints results
+#[derive(Clone, Copy, Debug)]
+pub struct OutputOptions {
+    pub format: OutputFormat,
+    /// Print the column header row
+    pub header: bool,
+    /// How the JSON formats write numbers
+    pub numbers: NumberFormat,
+    /// Row and column limits of the table format
+    pub limits: DisplayLimits,
+}
+
+/// Row and width limits of the table and expanded displays
+///
+/// The data formats (CSV, JSON, NDJSON, Markdown) always hold every row and
+/// the whole of every value.
+#[derive(Args, Clone, Copy, Debug, Default)]
+pub struct DisplayLimits {
+    /// Show at most N rows of a table, with a footer counting the rest; 0 shows all
+    /// (console default: 1000)
+    #[arg(long, value_name = "N")]
+    pub max_rows: Option<usize>,
+
+    /// Cut table values longer than N characters, ending them with `…`; 0 keeps them whole
+    #[arg(long, value_name = "N")]
+    pub max_column_width: Option<usize>,
+}
+
+impl DisplayLimits {
+    /// Rows to show, `None` for all
+    pub fn rows(&self) -> Option<usize> {
+        self.max_rows.filter(|rows| *rows > 0)
+    }
+
+    /// `value`, cut to the maximum column width
+    pub fn cut(&self, value: String) -> String {
+        match self.max_column_width.filter(|width| *width > 0) {
+            Some(width) if value.chars().count() > width => {
+                let mut cut: String = value.chars().take(width - 1).collect();
+                cut.push('…');
+                cut
+            }
+            _ => value,
+        }
+    }
+}
+
+/// How `graphlite query` runs its statements and reports failures
+#[derive(Clone, Copy, Debug)]
+pub struct QueryRunOptions {
+    /// Print no results, only errors
+    pub quiet: bool,
+    /// Stop at the first statement that fails
+    pub fail_fast: bool,
+    /// How each failing statement is reported when several run
+    pub error_format: ErrorFormat,
+}
+
+/// Number options of the commands that write JSON
+#[derive(Args, Clone, Copy, Debug)]
+pub struct NumberArgs {
+    /// Round numbers that are not whole to this many decimal places in JSON output
+    #[arg(long, value_name = "N")]
+    pub decimals: Option<u8>,
+
+    /// Notation of numbers that are not whole in JSON output: auto, plain or scientific
+    #[arg(long, default_value = "auto")]
+    pub notation: Notation,
+
+    /// Write whole numbers in JSON output as floats (493603.0) instead of integers
+    #[arg(long)]
+    pub float_integers: bool,
+}
+
+impl NumberArgs {
+    pub fn number_format(&self) -> NumberFormat {
+        NumberFormat {
+            integers: !self.float_integers,
+            decimals: self.decimals,
+            notation: self.notation,
+        }
+    }
 }
 
 impl std::str::FromStr for OutputFormat {
@@ -189,7 +825,12 @@ impl std::str::FromStr for OutputFormat {
             "table" => Ok(OutputFormat::Table),
             "json" => Ok(OutputFormat::Json),
             "csv" => Ok(OutputFormat::Csv),
-            _ => Err(format!("Unknown output format: {}", s)),
+            "ndjson" | "jsonl" => Ok(OutputFormat::Ndjson),
+            "markdown" | "md" => Ok(OutputFormat::Markdown),
+            _ => Err(format!(
+                "Unknown output format: {} (expected table, csv, json, ndjson or markdown)",
+                s
+            )),
         }
     }
 }
@@ -0,0 +1,343 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! Tab completion for the interactive GQL console
+//!
+//! Completes GQL keywords, graph paths, labels after `:` in a node pattern,
+//! relationship types after `:` in an edge pattern, and property keys after `.`
+//! or inside `{...}`. Names come from the catalog procedures (`gql.list_graphs`,
+//! `gql.list_labels`, ...) and are reloaded after statements that can change
+//! them, so completion follows the session's current graph.
+
+use rustyline::completion::{Completer, Pair};
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::validate::Validator;
+use rustyline::{Context, Helper};
+use std::sync::{Arc, RwLock};
+
+use super::input::OpenState;
+use graphlite::{QueryCoordinator, QueryResult, QueryType, Value};
+
+/// Keywords offered wherever a keyword can start
+const KEYWORDS: &[&str] = &[
+    "ALL",
+    "ALTER",
+    "AND",
+    "AS",
+    "ASC",
+    "BY",
+    "CALL",
+    "CASE",
+    "CLEAR",
+    "CLOSE",
+    "COMMIT",
+    "CONTAINS",
+    "COUNT",
+    "CREATE",
+    "DELETE",
+    "DESC",
+    "DETACH",
+    "DISTINCT",
+    "DROP",
+    "EDGE",
+    "ELSE",
+    "END",
+    "ENDS",
+    "EXCEPT",
+    "EXISTS",
+    "FALSE",
+    "FILTER",
+    "FROM",
+    "GRAPH",
+    "GROUP",
+    "HAVING",
+    "IF",
+    "IN",
+    "INDEX",
+    "INSERT",
+    "INTERSECT",
+    "IS",
+    "LET",
+    "LIMIT",
+    "MATCH",
+    "NEXT",
+    "NODE",
+    "NOT",
+    "NULL",
+    "OFFSET",
+    "ON",
+    "OPTIONAL",
+    "OR",
+    "ORDER",
+    "PROCEDURE",
+    "REMOVE",
+    "RESET",
+    "RETURN",
+    "ROLE",
+    "ROLLBACK",
+    "SCHEMA",
+    "SESSION",
+    "SET",
+    "SKIP",
+    "STARTS",
+    "START",
+    "THEN",
+    "TRANSACTION",
+    "TRUE",
+    "TRUNCATE",
+    "TYPE",
+    "UNION",
+    "UNWIND",
+    "USE",
+    "USER",
+    "WHEN",
+    "WHERE",
+    "WITH",
+    "XOR",
+    "YIELD",
+];
+
+/// Keywords after which a graph reference follows
+const GRAPH_KEYWORDS: &[&str] = &["GRAPH", "FROM", "USE"];
+
+/// Catalog names completion draws from
+#[derive(Debug, Default)]
+struct CatalogNames {
+    /// Graph paths, e.g. `/app/social`
+    graphs: Vec<String>,
+    /// Node labels of the current graph
+    labels: Vec<String>,
+    /// Relationship types of the current graph
+    relationship_types: Vec<String>,
+    /// Node and edge property keys of the current graph
+    property_keys: Vec<String>,
+}
+
+/// Catalog names of a console session, shared with the line editor's helper
+///
+/// Names are read through a separate session of the same user, so the lookups
+/// do not appear in the console session's `\history`.
+#[derive(Clone)]
+pub struct CompletionCatalog {
+    coordinator: Arc<QueryCoordinator>,
+    session_id: String,
+    lookup_session_id: Option<String>,
+    names: Arc<RwLock<CatalogNames>>,
+}
+
+impl CompletionCatalog {
+    /// Catalog for console session `session_id` of `username`, loaded once
+    pub fn new(coordinator: Arc<QueryCoordinator>, session_id: &str, username: &str) -> Self {
+        let lookup_session_id = coordinator.create_simple_session(username).ok();
+        let catalog = Self {
+            coordinator,
+            session_id: session_id.to_string(),
+            lookup_session_id,
+            names: Arc::new(RwLock::new(CatalogNames::default())),
+        };
+        catalog.refresh();
+        catalog
+    }
+
+    /// Reload the names if `statement` may have changed them
+    ///
+    /// Only read-only `MATCH` and `SELECT` statements leave them as they were.
+    pub fn statement_executed(&self, statement: &str) {
+        let unchanged = self.coordinator.analyze_query(statement).is_ok_and(|info| {
+            info.is_read_only && matches!(info.query_type, QueryType::Match | QueryType::Select)
+        });
+        if !unchanged {
+            self.refresh();
+        }
+    }
+
+    /// Reload graph paths, and the labels, relationship types and property keys
+    /// of the console session's current graph
+    pub fn refresh(&self) {
+        let Some(lookup) = &self.lookup_session_id else {
+            return;
+        };
+        let mut names = CatalogNames {
+            graphs: self
+                .column_values(
+                    lookup,
+                    "CALL gql.list_graphs()",
+                    &["schema_name", "graph_name"],
+                )
+                .into_iter()
+                .map(|parts| format!("/{}/{}", parts[0], parts[1]))
+                .collect(),
+            ..CatalogNames::default()
+        };
+
+        let current_graph = self
+            .coordinator
+            .session_handle(&self.session_id)
+            .ok()
+            .and_then(|handle| handle.current_graph());
+        if let Some(graph) = current_graph {
+            let graph = graph.replace('\'', "''");
+            let single = |procedure: &str, column: &str| {
+                self.column_values(
+                    lookup,
+                    &format!("CALL gql.{}('{}')", procedure, graph),
+                    &[column],
+                )
+                .into_iter()
+                .map(|mut parts| parts.remove(0))
+                .collect::<Vec<_>>()
+            };
+            names.labels = single("list_labels", "label");
+            names.relationship_types = single("list_relationship_types", "relationship_type");
+            names.property_keys = single("list_property_keys", "property_key");
+        }
+
+        if let Ok(mut current) = self.names.write() {
+            *current = names;
+        }
+    }
+
+    /// String values of `columns` in every row of `query`; empty if it fails
+    fn column_values(&self, session_id: &str, query: &str, columns: &[&str]) -> Vec<Vec<String>> {
+        let Ok(result) = self.coordinator.process_query(query, session_id) else {
+            return Vec::new();
+        };
+        rows_of(&result, columns)
+    }
+}
+
+/// Rows whose `columns` all hold strings
+fn rows_of(result: &QueryResult, columns: &[&str]) -> Vec<Vec<String>> {
+    result
+        .rows
+        .iter()
+        .filter_map(|row| {
+            columns
+                .iter()
+                .map(|column| match row.values.get(*column) {
+                    Some(Value::String(value)) => Some(value.clone()),
+                    _ => None,
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Line editor helper providing tab completion
+pub struct GqlHelper {
+    catalog: CompletionCatalog,
+}
+
+impl GqlHelper {
+    pub fn new(catalog: CompletionCatalog) -> Self {
+        Self { catalog }
+    }
+}
+
+impl Completer for GqlHelper {
+    type Candidate = Pair;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<Pair>)> {
+        let before = &line[..pos];
+        let start = before
+            .char_indices()
+            .rev()
+            .find(|(_, c)| !(c.is_alphanumeric() || *c == '_' || *c == '/'))
+            .map_or(0, |(index, c)| index + c.len_utf8());
+        let word = &before[start..];
+
+        let Ok(names) = self.catalog.names.read() else {
+            return Ok((start, Vec::new()));
+        };
+        let candidates = candidates(&before[..start], word, &names);
+        Ok((
+            start,
+            candidates
+                .into_iter()
+                .map(|candidate| Pair {
+                    display: candidate.clone(),
+                    replacement: candidate,
+                })
+                .collect(),
+        ))
+    }
+}
+
+/// Completions of `word`, given the text before it
+fn candidates(prefix: &str, word: &str, names: &CatalogNames) -> Vec<String> {
+    let matching = |values: &[String]| -> Vec<String> {
+        let mut values: Vec<String> = values
+            .iter()
+            .filter(|value| value.starts_with(word))
+            .cloned()
+            .collect();
+        values.sort();
+        values.dedup();
+        values
+    };
+
+    let open = OpenState::scan(prefix).brackets.last().copied();
+    match prefix.chars().next_back() {
+        Some(':') if open == Some('[') => return matching(&names.relationship_types),
+        Some(':') if open != Some('{') => return matching(&names.labels),
+        Some('.') => return matching(&names.property_keys),
+        _ => {}
+    }
+    if word.starts_with('/') {
+        return matching(&names.graphs);
+    }
+    if open == Some('{') && prefix.trim_end().ends_with(['{', ',']) {
+        return matching(&names.property_keys);
+    }
+
+    let previous = prefix.split_whitespace().next_back().unwrap_or("");
+    if GRAPH_KEYWORDS
+        .iter()
+        .any(|keyword| previous.eq_ignore_ascii_case(keyword))
+        && !word.is_empty()
+    {
+        // Graphs of the current schema can be named without their schema
+        let graph_names: Vec<String> = names
+            .graphs
+            .iter()
+            .filter_map(|path| path.rsplit('/').next())
+            .map(str::to_string)
+            .collect();
+        let graphs = matching(&graph_names);
+        if !graphs.is_empty() {
+            return graphs;
+        }
+    }
+
+    if word.is_empty() {
+        return Vec::new();
+    }
+    // Keep the case the keyword was started in
+    let lowercase = word.chars().all(|c| !c.is_uppercase());
+    KEYWORDS
+        .iter()
+        .filter(|keyword| {
+            keyword.len() >= word.len() && keyword[..word.len()].eq_ignore_ascii_case(word)
+        })
+        .map(|keyword| match lowercase {
+            true => keyword.to_lowercase(),
+            false => keyword.to_string(),
+        })
+        .collect()
+}
+
+impl Hinter for GqlHelper {
+    type Hint = String;
+}
+
+impl Highlighter for GqlHelper {}
+
+impl Validator for GqlHelper {}
+
+impl Helper for GqlHelper {}
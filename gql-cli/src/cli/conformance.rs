@@ -0,0 +1,133 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! GQL conformance run for `graphlite conformance`
+//!
+//! Opens a new database in a temporary directory, runs the conformance corpus
+//! (`QueryCoordinator::run_conformance`) and reports every feature with its
+//! status. The run fails if a supported feature's test fails or an
+//! unsupported feature's test passes, so the matrix returned by
+//! `gql.supported_features()` can be trusted.
+
+use colored::Colorize;
+use comfy_table::{presets::UTF8_FULL, Cell, Color, Table};
+
+use super::commands::{ConformanceArgs, ConformanceFormat};
+use graphlite::{ConformanceReport, ConformanceStatus, QueryCoordinator};
+
+/// Run the conformance corpus and print the report
+pub fn run_conformance(args: &ConformanceArgs) -> Result<(), String> {
+    let path = std::env::temp_dir().join(format!("graphlite-conformance-{}", std::process::id()));
+    if path.exists() {
+        return Err(format!(
+            "{:?} already exists; remove it and run again",
+            path
+        ));
+    }
+
+    let outcome = QueryCoordinator::from_path(&path)
+        .and_then(|coordinator| coordinator.run_conformance(args.feature.as_deref()));
+    if let Err(e) = std::fs::remove_dir_all(&path) {
+        log::warn!("Failed to remove conformance database {:?}: {}", path, e);
+    }
+
+    let report = outcome?;
+    if report.results.is_empty() {
+        return Err(format!(
+            "No feature id starts with '{}'",
+            args.feature.as_deref().unwrap_or_default()
+        ));
+    }
+    match args.format {
+        ConformanceFormat::Table => print_table(args, &report),
+        ConformanceFormat::Json => println!("{:#}", report_json(&report)),
+    }
+
+    if report.is_ok() {
+        Ok(())
+    } else {
+        Err(format!(
+            "{} feature(s) failed and {} unsupported feature(s) passed; \
+             the feature matrix does not match the engine",
+            report.count(ConformanceStatus::Failed),
+            report.count(ConformanceStatus::UnexpectedPass)
+        ))
+    }
+}
+
+fn status_color(status: ConformanceStatus) -> Color {
+    match status {
+        ConformanceStatus::Passed => Color::Green,
+        ConformanceStatus::Unsupported => Color::DarkGrey,
+        ConformanceStatus::Failed | ConformanceStatus::UnexpectedPass => Color::Red,
+    }
+}
+
+fn print_table(args: &ConformanceArgs, report: &ConformanceReport) {
+    let mut table = Table::new();
+    table.load_preset(UTF8_FULL);
+    let mut header = vec!["feature", "category", "status", "description"];
+    if args.verbose {
+        header.push("detail");
+    }
+    table.set_header(
+        header
+            .into_iter()
+            .map(|column| Cell::new(column).fg(Color::Green)),
+    );
+    for result in &report.results {
+        let mut row = vec![
+            Cell::new(result.feature.id),
+            Cell::new(result.feature.category),
+            Cell::new(result.status.as_str()).fg(status_color(result.status)),
+            Cell::new(result.feature.description),
+        ];
+        if args.verbose {
+            row.push(Cell::new(result.message.as_deref().unwrap_or_default()));
+        }
+        table.add_row(row);
+    }
+    println!("{}", table);
+
+    // Mismatches are worth reading even without --verbose
+    if !args.verbose {
+        for result in &report.results {
+            if let (ConformanceStatus::Failed, Some(message)) = (result.status, &result.message) {
+                println!("{} {}: {}", "failed".red(), result.feature.id, message);
+            }
+        }
+    }
+    println!(
+        "{} passed, {} unsupported, {} failed, {} unexpected pass(es)",
+        report.count(ConformanceStatus::Passed),
+        report.count(ConformanceStatus::Unsupported),
+        report.count(ConformanceStatus::Failed),
+        report.count(ConformanceStatus::UnexpectedPass)
+    );
+}
+
+/// The results as a JSON document, one entry per feature
+fn report_json(report: &ConformanceReport) -> serde_json::Value {
+    serde_json::json!({
+        "version": graphlite::VERSION,
+        "passed": report.count(ConformanceStatus::Passed),
+        "unsupported": report.count(ConformanceStatus::Unsupported),
+        "failed": report.count(ConformanceStatus::Failed),
+        "unexpected_pass": report.count(ConformanceStatus::UnexpectedPass),
+        "features": report
+            .results
+            .iter()
+            .map(|result| {
+                serde_json::json!({
+                    "id": result.feature.id,
+                    "category": result.feature.category,
+                    "description": result.feature.description,
+                    "supported": result.feature.supported,
+                    "status": result.status.as_str(),
+                    "message": result.message,
+                    "duration_ms": result.duration.as_secs_f64() * 1000.0,
+                })
+            })
+            .collect::<Vec<_>>(),
+    })
+}
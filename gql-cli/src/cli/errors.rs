@@ -0,0 +1,144 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! Error reporting and exit codes for `graphlite query`
+//!
+//! Each failure category gets its own process exit code so scripts can branch on
+//! the kind of failure; `--error-format json` additionally prints the error as a
+//! JSON object on stderr.
+//!
+//! | Code | Category |
+//! |------|----------|
+//! | 0  | Success |
+//! | 1  | Other error |
+//! | 2  | Invalid command-line usage |
+//! | 3  | Database not found or cannot be opened |
+//! | 4  | Authentication failed |
+//! | 5  | Syntax error |
+//! | 6  | Invalid query (types, planning, unsupported features) |
+//! | 7  | Graph, schema or other object not found |
+//! | 8  | Constraint violation (e.g. object already exists) |
+//! | 9  | Permission denied or read-only database |
+//! | 10 | Transaction error |
+//! | 11 | Query timed out |
+//! | 12 | Row or memory limit exceeded |
+//! | 13 | Storage error |
+//! | 14 | Database volume low on space; writes rejected |
+
+use clap::ValueEnum;
+use colored::Colorize;
+use graphlite::QueryErrorKind;
+
+/// How errors are printed on stderr
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum ErrorFormat {
+    /// Human-readable message
+    #[default]
+    Text,
+    /// One JSON object: `{"error": {"category", "exit_code", "message"}}`
+    Json,
+}
+
+/// Failure category of a CLI query run
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FailureCategory {
+    /// Invalid arguments that clap could not reject up front
+    Usage,
+    /// The database does not exist or cannot be opened
+    Database,
+    /// The query or session failed inside the engine
+    Query(QueryErrorKind),
+}
+
+impl FailureCategory {
+    /// Process exit code for this category
+    pub fn exit_code(self) -> i32 {
+        match self {
+            FailureCategory::Usage => 2,
+            FailureCategory::Database => 3,
+            FailureCategory::Query(kind) => match kind {
+                QueryErrorKind::Other => 1,
+                QueryErrorKind::Authentication => 4,
+                QueryErrorKind::Syntax => 5,
+                QueryErrorKind::Validation => 6,
+                QueryErrorKind::NotFound => 7,
+                QueryErrorKind::ConstraintViolation => 8,
+                QueryErrorKind::PermissionDenied => 9,
+                QueryErrorKind::Transaction => 10,
+                QueryErrorKind::Timeout => 11,
+                QueryErrorKind::LimitExceeded => 12,
+                QueryErrorKind::Storage => 13,
+                QueryErrorKind::DiskFull => 14,
+            },
+        }
+    }
+
+    /// Name used in JSON output
+    pub fn name(self) -> &'static str {
+        match self {
+            FailureCategory::Usage => "usage",
+            FailureCategory::Database => "database",
+            FailureCategory::Query(kind) => kind.as_str(),
+        }
+    }
+}
+
+/// A failed `graphlite query` run
+#[derive(Debug)]
+pub struct QueryFailure {
+    pub category: FailureCategory,
+    pub message: String,
+}
+
+impl QueryFailure {
+    /// Invalid command-line input, such as an unreadable parameters file
+    pub fn usage(message: impl Into<String>) -> Self {
+        Self {
+            category: FailureCategory::Usage,
+            message: message.into(),
+        }
+    }
+
+    /// Failure to locate or open the database
+    pub fn database(message: impl Into<String>) -> Self {
+        Self {
+            category: FailureCategory::Database,
+            message: message.into(),
+        }
+    }
+
+    /// Failure reported by the engine, categorized from its message
+    pub fn query(message: impl Into<String>) -> Self {
+        let message = message.into();
+        Self {
+            category: FailureCategory::Query(QueryErrorKind::from_message(&message)),
+            message,
+        }
+    }
+
+    /// Print the failure on stderr in the requested format
+    pub fn report(&self, format: ErrorFormat) {
+        match format {
+            ErrorFormat::Text => eprintln!("{}", format!("Error: {}", self.message).red()),
+            ErrorFormat::Json => eprintln!(
+                "{}",
+                serde_json::json!({
+                    "error": {
+                        "category": self.category.name(),
+                        "exit_code": self.category.exit_code(),
+                        "message": self.message,
+                    }
+                })
+            ),
+        }
+    }
+}
+
+impl From<std::io::Error> for QueryFailure {
+    fn from(error: std::io::Error) -> Self {
+        Self {
+            category: FailureCategory::Query(QueryErrorKind::Other),
+            message: error.to_string(),
+        }
+    }
+}
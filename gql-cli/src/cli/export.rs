@@ -0,0 +1,410 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! Data export for `graphlite export`
+//!
+//! Writes a whole graph, or the result of a query run against it, as CSV, JSON
+//! Lines or GraphML.
+//!
+//! A whole graph is written one element per record: CSV uses the columns `_id`,
+//! `_labels`, `_start`, `_end` and `_type` followed by one column per property,
+//! JSON Lines uses one `{"type": "node" | "edge", ...}` object per line, and
+//! GraphML declares a key per property. A query result is written one row per
+//! record in CSV and JSON Lines; GraphML keeps the nodes and edges it returned.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::Write;
+use std::path::Path;
+
+use super::commands::{ExportArgs, ExportFormat};
+use super::output::ResultFormatter;
+use graphlite::{NumberFormat, QueryCoordinator, QueryResult, Value};
+
+/// Query returning every node of the current graph
+const ALL_NODES: &str = "MATCH (n) RETURN n";
+
+/// Query returning every edge of the current graph
+const ALL_EDGES: &str = "MATCH ()-[e]->() RETURN e";
+
+/// A node or edge to write
+struct Element {
+    id: String,
+    /// Node labels, or the edge label
+    labels: Vec<String>,
+    /// Start and end node ids of an edge
+    endpoints: Option<(String, String)>,
+    properties: BTreeMap<String, Value>,
+}
+
+impl Element {
+    fn is_edge(&self) -> bool {
+        self.endpoints.is_some()
+    }
+}
+
+/// Elements of a graph, nodes first, each kind ordered by id
+struct Elements(Vec<Element>);
+
+impl Elements {
+    /// Collect the nodes and edges held by the values of `results`, once each
+    fn collect<'a>(results: impl IntoIterator<Item = &'a QueryResult>) -> Self {
+        let mut nodes = BTreeMap::new();
+        let mut edges = BTreeMap::new();
+        for result in results {
+            for row in &result.rows {
+                for value in row.values.values() {
+                    match value {
+                        Value::Node(node) => {
+                            nodes.entry(node.id.clone()).or_insert_with(|| Element {
+                                id: node.id.clone(),
+                                labels: node.labels.clone(),
+                                endpoints: None,
+                                properties: node.properties.clone().into_iter().collect(),
+                            });
+                        }
+                        Value::Edge(edge) => {
+                            edges.entry(edge.id.clone()).or_insert_with(|| Element {
+                                id: edge.id.clone(),
+                                labels: vec![edge.label.clone()],
+                                endpoints: Some((edge.from_node.clone(), edge.to_node.clone())),
+                                properties: edge.properties.clone().into_iter().collect(),
+                            });
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+        Self(nodes.into_values().chain(edges.into_values()).collect())
+    }
+
+    /// Property names of nodes (`edges == false`) or edges, sorted
+    fn property_names(&self, edges: bool) -> BTreeSet<&str> {
+        self.0
+            .iter()
+            .filter(|element| element.is_edge() == edges)
+            .flat_map(|element| element.properties.keys().map(String::as_str))
+            .collect()
+    }
+}
+
+/// Write the graph or query result selected by `args`
+pub fn run_export(
+    coordinator: &QueryCoordinator,
+    session_id: &str,
+    args: &ExportArgs,
+) -> Result<(), String> {
+    let format = match (args.format, &args.output) {
+        (Some(format), _) => format,
+        (None, Some(output)) => format_from_extension(output),
+        (None, None) => ExportFormat::Jsonl,
+    };
+    let run = |query: &str| coordinator.process_query(query, session_id);
+
+    let mut output: Box<dyn Write> = match &args.output {
+        Some(path) => Box::new(std::io::BufWriter::new(
+            std::fs::File::create(path)
+                .map_err(|e| format!("Failed to create {}: {}", path.display(), e))?,
+        )),
+        None => Box::new(std::io::stdout().lock()),
+    };
+
+    let written = match (&args.query, format) {
+        (Some(query), ExportFormat::Graphml) => {
+            let elements = Elements::collect([&run(query)?]);
+            if elements.0.is_empty() {
+                return Err("GraphML export needs a query that returns nodes or edges".to_string());
+            }
+            write_graphml(&mut output, &elements)
+        }
+        (Some(query), format) => write_rows(&mut output, &run(query)?, format),
+        (None, format) => {
+            let elements = Elements::collect([&run(ALL_NODES)?, &run(ALL_EDGES)?]);
+            match format {
+                ExportFormat::Csv => write_elements_csv(&mut output, &elements),
+                ExportFormat::Jsonl => write_elements_jsonl(&mut output, &elements),
+                ExportFormat::Graphml => write_graphml(&mut output, &elements),
+            }
+        }
+    };
+    written
+        .and_then(|()| output.flush())
+        .map_err(|e| format!("Failed to write export: {}", e))
+}
+
+/// Format of an output file by its extension, JSON Lines unless `.csv` or `.graphml`
+fn format_from_extension(output: &Path) -> ExportFormat {
+    let extension = output
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(str::to_ascii_lowercase);
+    match extension.as_deref() {
+        Some("csv") => ExportFormat::Csv,
+        Some("graphml") | Some("xml") => ExportFormat::Graphml,
+        _ => ExportFormat::Jsonl,
+    }
+}
+
+/// Field text of a value; NULL is an empty field
+fn field(value: Option<&Value>) -> String {
+    match value {
+        None | Some(Value::Null) => String::new(),
+        Some(value) => ResultFormatter::value_to_string(value),
+    }
+}
+
+/// Write a query result one row per record (CSV or JSON Lines)
+fn write_rows(
+    output: &mut dyn Write,
+    result: &QueryResult,
+    format: ExportFormat,
+) -> std::io::Result<()> {
+    if format == ExportFormat::Csv {
+        let mut writer = csv::Writer::from_writer(output);
+        writer.write_record(&result.variables)?;
+        for row in &result.rows {
+            writer.write_record(
+                result
+                    .variables
+                    .iter()
+                    .map(|column| field(row.get_value(column))),
+            )?;
+        }
+        return writer.flush();
+    }
+
+    for row in &result.rows {
+        let object: serde_json::Map<String, serde_json::Value> = result
+            .variables
+            .iter()
+            .map(|column| {
+                let value = row
+                    .get_value(column)
+                    .map(ResultFormatter::value_to_json)
+                    .unwrap_or(serde_json::Value::Null);
+                (column.clone(), value)
+            })
+            .collect();
+        writeln!(output, "{}", json_line(&serde_json::Value::Object(object)))?;
+    }
+    Ok(())
+}
+
+/// Write every element as a CSV record
+fn write_elements_csv(output: &mut dyn Write, elements: &Elements) -> std::io::Result<()> {
+    let properties: BTreeSet<&str> = elements
+        .property_names(false)
+        .union(&elements.property_names(true))
+        .copied()
+        .collect();
+
+    let mut writer = csv::Writer::from_writer(output);
+    writer.write_record(
+        ["_id", "_labels", "_start", "_end", "_type"]
+            .into_iter()
+            .chain(properties.iter().copied()),
+    )?;
+    for element in &elements.0 {
+        let (labels, start, end, edge_type) = match &element.endpoints {
+            None => (
+                format!(":{}", element.labels.join(":")),
+                "",
+                "",
+                String::new(),
+            ),
+            Some((start, end)) => (
+                String::new(),
+                start.as_str(),
+                end.as_str(),
+                element.labels.join(":"),
+            ),
+        };
+        let mut record = vec![
+            element.id.clone(),
+            labels,
+            start.to_string(),
+            end.to_string(),
+            edge_type,
+        ];
+        record.extend(
+            properties
+                .iter()
+                .map(|name| field(element.properties.get(*name))),
+        );
+        writer.write_record(&record)?;
+    }
+    writer.flush()
+}
+
+/// Write every element as a JSON object on its own line
+fn write_elements_jsonl(output: &mut dyn Write, elements: &Elements) -> std::io::Result<()> {
+    for element in &elements.0 {
+        let properties: serde_json::Map<String, serde_json::Value> = element
+            .properties
+            .iter()
+            .map(|(name, value)| (name.clone(), ResultFormatter::value_to_json(value)))
+            .collect();
+        let line = match &element.endpoints {
+            None => serde_json::json!({
+                "type": "node",
+                "id": element.id,
+                "labels": element.labels,
+                "properties": properties,
+            }),
+            Some((from, to)) => serde_json::json!({
+                "type": "edge",
+                "id": element.id,
+                "label": element.labels.join(":"),
+                "from": from,
+                "to": to,
+                "properties": properties,
+            }),
+        };
+        writeln!(output, "{}", json_line(&line))?;
+    }
+    Ok(())
+}
+
+/// A JSON Lines record, with whole numbers written as integers
+fn json_line(value: &serde_json::Value) -> String {
+    NumberFormat::default()
+        .to_string(value)
+        .unwrap_or_else(|_| value.to_string())
+}
+
+/// Write the elements as a directed GraphML graph
+///
+/// Node labels and edge labels are stored in the `labels` and `label` keys, and
+/// every property gets a key typed `double`, `boolean` or `string` after its
+/// values. An edge whose endpoint is not among the elements (possible for query
+/// results) gets an empty node for it, so the document stays valid.
+fn write_graphml(output: &mut dyn Write, elements: &Elements) -> std::io::Result<()> {
+    writeln!(output, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(
+        output,
+        r#"<graphml xmlns="http://graphml.graphdrawing.org/xmlns">"#
+    )?;
+    writeln!(
+        output,
+        r#"  <key id="labels" for="node" attr.name="labels" attr.type="string"/>"#
+    )?;
+    writeln!(
+        output,
+        r#"  <key id="label" for="edge" attr.name="label" attr.type="string"/>"#
+    )?;
+
+    let mut keys = [BTreeMap::new(), BTreeMap::new()];
+    for (edges, (domain, prefix)) in [("node", 'n'), ("edge", 'e')].into_iter().enumerate() {
+        for (index, name) in elements.property_names(edges == 1).into_iter().enumerate() {
+            let id = format!("{}{}", prefix, index);
+            writeln!(
+                output,
+                r#"  <key id="{}" for="{}" attr.name="{}" attr.type="{}"/>"#,
+                id,
+                domain,
+                escape_xml(name),
+                graphml_type(elements, edges == 1, name)
+            )?;
+            keys[edges].insert(name, id);
+        }
+    }
+
+    writeln!(output, r#"  <graph id="G" edgedefault="directed">"#)?;
+    let node_ids: BTreeSet<&str> = elements
+        .0
+        .iter()
+        .filter(|element| !element.is_edge())
+        .map(|element| element.id.as_str())
+        .collect();
+    let missing: BTreeSet<&str> = elements
+        .0
+        .iter()
+        .filter_map(|element| element.endpoints.as_ref())
+        .flat_map(|(from, to)| [from.as_str(), to.as_str()])
+        .filter(|id| !node_ids.contains(id))
+        .collect();
+    for id in missing {
+        writeln!(output, r#"    <node id="{}"/>"#, escape_xml(id))?;
+    }
+
+    for element in &elements.0 {
+        match &element.endpoints {
+            None => {
+                writeln!(output, r#"    <node id="{}">"#, escape_xml(&element.id))?;
+                writeln!(
+                    output,
+                    r#"      <data key="labels">:{}</data>"#,
+                    escape_xml(&element.labels.join(":"))
+                )?;
+            }
+            Some((from, to)) => {
+                writeln!(
+                    output,
+                    r#"    <edge id="{}" source="{}" target="{}">"#,
+                    escape_xml(&element.id),
+                    escape_xml(from),
+                    escape_xml(to)
+                )?;
+                writeln!(
+                    output,
+                    r#"      <data key="label">{}</data>"#,
+                    escape_xml(&element.labels.join(":"))
+                )?;
+            }
+        }
+        let keys = &keys[usize::from(element.is_edge())];
+        for (name, value) in &element.properties {
+            if matches!(value, Value::Null) {
+                continue;
+            }
+            writeln!(
+                output,
+                r#"      <data key="{}">{}</data>"#,
+                keys[name.as_str()],
+                escape_xml(&ResultFormatter::value_to_string(value))
+            )?;
+        }
+        let tag = if element.is_edge() { "edge" } else { "node" };
+        writeln!(output, "    </{}>", tag)?;
+    }
+
+    writeln!(output, "  </graph>")?;
+    writeln!(output, "</graphml>")
+}
+
+/// GraphML type of a property: `double` or `boolean` if every value has that type
+fn graphml_type(elements: &Elements, edges: bool, name: &str) -> &'static str {
+    let values: Vec<&Value> = elements
+        .0
+        .iter()
+        .filter(|element| element.is_edge() == edges)
+        .filter_map(|element| element.properties.get(name))
+        .filter(|value| !matches!(value, Value::Null))
+        .collect();
+    if values.iter().all(|value| matches!(value, Value::Number(_))) {
+        "double"
+    } else if values
+        .iter()
+        .all(|value| matches!(value, Value::Boolean(_)))
+    {
+        "boolean"
+    } else {
+        "string"
+    }
+}
+
+/// Escape text for an XML attribute or element
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -5,12 +5,34 @@
 
 use colored::Colorize;
 use rustyline::{error::ReadlineError, CompletionType, Config, EditMode, Editor};
-use std::path::{Path, PathBuf};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Write;
+use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Instant;
 
-use super::commands::OutputFormat;
+use super::bench::run_bench;
+use super::commands::{
+    BenchArgs, ConformanceArgs, DisplayLimits, ExportArgs, ImportArgs, MigrateArgs, OutputFormat,
+    OutputOptions, QueryRunOptions, RolesArgs, RunArgs, ServeArgs, StatsArgs, UsersArgs,
+    WaldumpFormat,
+};
+use super::completion::{CompletionCatalog, GqlHelper};
+use super::conformance::run_conformance;
+use super::errors::QueryFailure;
+use super::export::run_export;
+use super::import::run_import;
+use super::input::{OpenState, ScriptStatement};
+use super::migrate::run_migrate;
 use super::output::ResultFormatter;
-use graphlite::QueryCoordinator;
+use super::pager::{print_paged, strip_ansi};
+use super::script::run_script;
+use super::security::{run_roles, run_users};
+use super::serve::run_server;
+use super::stats::run_stats;
+use super::watch::{run_watch, WatchOptions};
+use graphlite::{DecodedStatement, QueryCoordinator, QueryResult, Value};
 
 // Note: init_database_components has been removed.
 // All database initialization is now handled internally by QueryCoordinator::from_path()
@@ -117,6 +139,7 @@ pub fn handle_gql(
     path: PathBuf,
     user: Option<String>,
     password: Option<String>,
+    limits: DisplayLimits,
     _sample: bool,
 ) -> Result<(), Box<dyn std::error::Error>> {
     // Check if database exists
@@ -129,19 +152,7 @@ pub fn handle_gql(
     }
 
     // Prompt for credentials if not provided
-    let username = user.unwrap_or_else(|| {
-        print!("Username: ");
-        std::io::Write::flush(&mut std::io::stdout()).unwrap();
-        let mut input = String::new();
-        std::io::stdin().read_line(&mut input).unwrap();
-        input.trim().to_string()
-    });
-
-    let password = password.unwrap_or_else(|| {
-        print!("Password: ");
-        std::io::Write::flush(&mut std::io::stdout()).unwrap();
-        rpassword::read_password().unwrap()
-    });
+    let (username, password) = prompt_credentials(user, password);
 
     // Load database
     let coordinator = load_database(&path)?;
@@ -161,24 +172,32 @@ pub fn handle_gql(
         .history_ignore_space(true)
         .completion_type(CompletionType::List)
         .auto_add_history(false)
+        .max_history_size(1000)?
         .build();
 
-    let mut rl = Editor::<(), _>::with_config(config)?;
+    let catalog = CompletionCatalog::new(coordinator.clone(), &session_id, &username);
+    let mut rl = Editor::<GqlHelper, _>::with_config(config)?;
+    rl.set_helper(Some(GqlHelper::new(catalog.clone())));
 
-    let history_path = ".graphlite/.gql_history.txt";
-    if let Some(parent) = Path::new(&history_path).parent() {
-        std::fs::create_dir_all(parent)?;
+    let history_path = history_path();
+    if let Some(history_path) = &history_path {
+        let _ = rl.load_history(history_path);
     }
 
-    let _ = rl.load_history(&history_path);
-
     let mut query_buffer = String::new();
+    let limits = DisplayLimits {
+        max_rows: limits.max_rows.or(Some(CONSOLE_MAX_ROWS)),
+        ..limits
+    };
+    let mut console = ConsoleState::new(&coordinator, &username, limits);
 
     loop {
+        // Continuation prompts show the brackets, string or comment still open
         let prompt = if query_buffer.is_empty() {
             format!("{}::gql> ", username.cyan())
         } else {
-            format!("{}::...> ", username.cyan())
+            let marker = OpenState::scan(&query_buffer).prompt_marker();
+            format!("{}::{}> ", username.cyan(), marker)
         };
 
         let line = match rl.readline(&prompt) {
@@ -221,71 +240,458 @@ pub fn handle_gql(
                 "" => continue,
                 _ => {}
             }
+
+            if let Some(command) = trimmed.strip_prefix('\\') {
+                handle_backslash_command(
+                    &coordinator,
+                    &session_id,
+                    &catalog,
+                    command,
+                    &mut console,
+                );
+                continue;
+            }
         }
 
         // Append line to buffer
         query_buffer.push_str(&line);
         query_buffer.push('\n');
 
-        // Check if query is complete (ends with semicolon)
-        if trimmed.ends_with(';') {
+        // The statement is complete once it ends with a semicolon outside
+        // brackets, strings and comments
+        if OpenState::scan(&query_buffer).terminated {
             let query = query_buffer.trim().to_string();
             rl.add_history_entry(&query)?;
 
-            // Execute query
-            match coordinator.process_query(&query, &session_id) {
-                Ok(result) => {
-                    let output = ResultFormatter::format(&result, OutputFormat::Table);
-                    println!("{}", output);
+            run_repl_query(&coordinator, &session_id, &catalog, &query, &mut console);
+
+            query_buffer.clear();
+        }
+    }
+
+    // Save history
+    if let Some(history_path) = &history_path {
+        let _ = rl.save_history(history_path);
+    }
+
+    Ok(())
+}
+
+/// Console history file, `~/.graphlite_history`; `None` without a home directory
+fn history_path() -> Option<PathBuf> {
+    std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .filter(|home| !home.is_empty())
+        .map(|home| PathBuf::from(home).join(".graphlite_history"))
+}
+
+/// Rows of a result the console shows unless `--max-rows` says otherwise
+const CONSOLE_MAX_ROWS: usize = 1000;
+
+/// Console settings changed with backslash commands
+struct ConsoleState {
+    /// Expanded display, toggled with `\x`
+    expanded: bool,
+    /// Result format, set with `\format`
+    format: OutputFormat,
+    /// Rows and value widths shown in the table and expanded displays
+    limits: DisplayLimits,
+    /// Print how long each statement took, toggled with `\timing`
+    timing: bool,
+    /// File results are written to instead of the terminal, set with `\o`
+    output: Option<(PathBuf, File)>,
+    /// Session the meta-commands query the catalog on, so their `CALL`s do not
+    /// appear in the console session's `\history`
+    meta_session_id: Option<String>,
+}
+
+impl ConsoleState {
+    fn new(coordinator: &QueryCoordinator, username: &str, limits: DisplayLimits) -> Self {
+        Self {
+            expanded: false,
+            format: OutputFormat::Table,
+            limits,
+            timing: false,
+            output: None,
+            meta_session_id: coordinator.create_simple_session(username).ok(),
+        }
+    }
+
+    /// Format a result as the console is set up to
+    fn format_result(&self, result: &QueryResult) -> String {
+        match self.format {
+            OutputFormat::Table => {
+                let mut output = if self.expanded {
+                    ResultFormatter::format_expanded(result, self.limits)
+                } else {
+                    ResultFormatter::format_table_highlighted(result, &[], true, self.limits)
+                };
+                if let Some(timing) = &result.operator_timings {
+                    output.push_str(&ResultFormatter::format_timings(timing));
                 }
+                output
+            }
+            format => ResultFormatter::format(result, format),
+        }
+    }
+
+    /// Print formatted output to the `\o` file, or paged to the terminal
+    fn print(&mut self, output: &str) {
+        match &mut self.output {
+            Some((path, file)) => {
+                if let Err(e) = writeln!(file, "{}", strip_ansi(output).trim_end()) {
+                    eprintln!(
+                        "{}",
+                        format!("Error writing to {}: {}", path.display(), e).red()
+                    );
+                }
+            }
+            None => print_paged(output),
+        }
+    }
+}
+
+/// Execute a REPL statement and print its result, paging output that does not fit the terminal
+fn run_repl_query(
+    coordinator: &QueryCoordinator,
+    session_id: &str,
+    catalog: &CompletionCatalog,
+    query: &str,
+    console: &mut ConsoleState,
+) {
+    let started = Instant::now();
+    match coordinator.process_query(query, session_id) {
+        Ok(result) => {
+            catalog.statement_executed(query);
+            let output = console.format_result(&result);
+            console.print(&output);
+        }
+        Err(e) => {
+            // Don't show error for duplicate entries with IF NOT EXISTS
+            // These are gracefully handled and expected
+            if !e.contains("Duplicate entry") && !e.contains("already exists") {
+                eprintln!("{}", format!("Error: {}", e).red());
+            }
+        }
+    }
+    if console.timing {
+        println!(
+            "{}",
+            format!("Time: {:.3} ms", started.elapsed().as_secs_f64() * 1000.0).dimmed()
+        );
+    }
+}
+
+/// Handle a REPL command starting with a backslash (`\history`, `\replay n`, `\x`,
+/// `\profile`, `\format`, `\timing`, `\o` and the catalog meta-commands)
+fn handle_backslash_command(
+    coordinator: &QueryCoordinator,
+    session_id: &str,
+    catalog: &CompletionCatalog,
+    command: &str,
+    console: &mut ConsoleState,
+) {
+    // The file name may contain spaces
+    if let Some(file) = command
+        .strip_prefix('o')
+        .filter(|rest| rest.is_empty() || rest.starts_with(char::is_whitespace))
+    {
+        set_output(console, file.trim());
+        return;
+    }
+
+    let mut parts = command.split_whitespace();
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some("graphs"), None, None) => {
+            run_meta_queries(
+                coordinator,
+                console,
+                &[("", "CALL gql.list_graphs()".into())],
+            );
+        }
+        (Some(meta @ ("labels" | "indexes" | "schema")), graph, None) => {
+            let graph = match meta_graph(coordinator, session_id, graph) {
+                Ok(graph) => graph.replace('\'', "''"),
                 Err(e) => {
-                    // Don't show error for duplicate entries with IF NOT EXISTS
-                    // These are gracefully handled and expected
-                    if !e.contains("Duplicate entry") && !e.contains("already exists") {
-                        eprintln!("{}", format!("Error: {}", e).red());
+                    eprintln!("{}", format!("Error: {}", e).red());
+                    return;
+                }
+            };
+            let procedures: &[(&str, &str)] = match meta {
+                "labels" => &[("", "list_labels")],
+                "indexes" => &[("", "list_indexes")],
+                _ => &[
+                    ("Labels", "list_labels"),
+                    ("Relationship types", "list_relationship_types"),
+                    ("Property keys", "list_property_keys"),
+                    ("Indexes", "list_indexes"),
+                ],
+            };
+            let queries: Vec<(&str, String)> = procedures
+                .iter()
+                .map(|(title, procedure)| (*title, format!("CALL gql.{}('{}')", procedure, graph)))
+                .collect();
+            run_meta_queries(coordinator, console, &queries);
+        }
+        (Some("format"), format, None) => {
+            if let Some(format) = format {
+                match format.parse::<OutputFormat>() {
+                    Ok(format) => console.format = format,
+                    Err(e) => {
+                        eprintln!("{}", e.red());
+                        return;
                     }
                 }
             }
+            println!(
+                "{}",
+                format!("Output format is {}", format_name(console.format)).green()
+            );
+        }
+        (Some("timing"), mode, None) => {
+            console.timing = match mode {
+                None => !console.timing,
+                Some("on") => true,
+                Some("off") => false,
+                Some(_) => {
+                    eprintln!("{}", "Usage: \\timing [on|off]".red());
+                    return;
+                }
+            };
+            let state = if console.timing { "on" } else { "off" };
+            println!("{}", format!("Timing is {}", state).green());
+        }
+        (Some("x"), mode, None) => {
+            console.expanded = match mode {
+                None => !console.expanded,
+                Some("on") => true,
+                Some("off") => false,
+                Some(_) => {
+                    eprintln!("{}", "Usage: \\x [on|off]".red());
+                    return;
+                }
+            };
+            let state = if console.expanded { "on" } else { "off" };
+            println!("{}", format!("Expanded display is {}", state).green());
+        }
+        (Some("profile"), mode, None) => {
+            let handle = match coordinator.session_handle(session_id) {
+                Ok(handle) => handle,
+                Err(e) => {
+                    eprintln!("{}", format!("Error: {}", e).red());
+                    return;
+                }
+            };
+            let enabled = match mode {
+                None => !handle.operator_timing(),
+                Some("on") => true,
+                Some("off") => false,
+                Some(_) => {
+                    eprintln!("{}", "Usage: \\profile [on|off]".red());
+                    return;
+                }
+            };
+            if let Err(e) = handle.set_operator_timing(enabled) {
+                eprintln!("{}", format!("Error: {}", e).red());
+                return;
+            }
+            let state = if enabled { "on" } else { "off" };
+            println!("{}", format!("Operator timing is {}", state).green());
+        }
+        (Some("history"), limit, None) => {
+            let limit = match limit.map(str::parse::<usize>) {
+                None => None,
+                Some(Ok(limit)) => Some(limit),
+                Some(Err(_)) => {
+                    eprintln!("{}", "Usage: \\history [count]".red());
+                    return;
+                }
+            };
+            print_history(coordinator, session_id, limit);
+        }
+        (Some("replay"), Some(sequence), None) => {
+            let Ok(sequence) = sequence.parse::<u64>() else {
+                eprintln!("{}", "Usage: \\replay <n>".red());
+                return;
+            };
+            let history = match coordinator.session_history(session_id) {
+                Ok(history) => history,
+                Err(e) => {
+                    eprintln!("{}", format!("Error: {}", e).red());
+                    return;
+                }
+            };
+            match history.into_iter().find(|entry| entry.sequence == sequence) {
+                Some(entry) => {
+                    println!("{}", entry.statement.yellow());
+                    run_repl_query(coordinator, session_id, catalog, &entry.statement, console);
+                }
+                None => eprintln!(
+                    "{}",
+                    format!("No statement #{} in session history", sequence).red()
+                ),
+            }
+        }
+        _ => eprintln!(
+            "{}",
+            format!("Unknown command: \\{}. Type 'help' for commands", command).red()
+        ),
+    }
+}
 
-            query_buffer.clear();
+/// Send results to `file`, or back to the terminal if it is empty (`\o`)
+///
+/// The file is truncated, and results are written to it without colors.
+fn set_output(console: &mut ConsoleState, file: &str) {
+    if file.is_empty() {
+        if let Some((path, _)) = console.output.take() {
+            println!(
+                "{}",
+                format!("Output no longer goes to {}", path.display()).green()
+            );
         }
+        return;
     }
 
-    // Save history
-    let _ = rl.save_history(&history_path);
+    let path = PathBuf::from(file);
+    match File::create(&path) {
+        Ok(created) => {
+            println!("{}", format!("Output goes to {}", path.display()).green());
+            console.output = Some((path, created));
+        }
+        Err(e) => eprintln!(
+            "{}",
+            format!("Error: cannot write to {}: {}", path.display(), e).red()
+        ),
+    }
+}
 
-    Ok(())
+/// Full path of the graph a meta-command describes: `graph`, relative to the
+/// session schema unless it starts with `/`, or the session's current graph
+fn meta_graph(
+    coordinator: &QueryCoordinator,
+    session_id: &str,
+    graph: Option<&str>,
+) -> Result<String, String> {
+    let handle = coordinator.session_handle(session_id)?;
+    match graph {
+        Some(graph) if graph.starts_with('/') => Ok(graph.to_string()),
+        Some(graph) => handle
+            .current_schema()
+            .map(|schema| format!("/{}/{}", schema.trim_matches('/'), graph))
+            .ok_or_else(|| {
+                format!(
+                    "Graph '{}' is not a full path and no session schema is set",
+                    graph
+                )
+            }),
+        None => handle
+            .current_graph()
+            .ok_or_else(|| "No current graph; name one or run SESSION SET GRAPH first".to_string()),
+    }
+}
+
+/// Run catalog queries for a meta-command and print their results together,
+/// each under its title if it has one
+fn run_meta_queries(
+    coordinator: &QueryCoordinator,
+    console: &mut ConsoleState,
+    queries: &[(&str, String)],
+) {
+    let Some(meta_session_id) = &console.meta_session_id else {
+        eprintln!("{}", "Error: catalog meta-commands are unavailable".red());
+        return;
+    };
+    let mut output = String::new();
+    for (title, query) in queries {
+        let result = match coordinator.process_query(query, meta_session_id) {
+            Ok(result) => result,
+            Err(e) => {
+                eprintln!("{}", format!("Error: {}", e).red());
+                return;
+            }
+        };
+        if !output.is_empty() {
+            output.push('\n');
+        }
+        if !title.is_empty() {
+            output.push_str(&format!("{}\n", title.bold()));
+        }
+        output.push_str(console.format_result(&result).trim_end());
+        output.push('\n');
+    }
+    console.print(output.trim_end());
+}
+
+/// Name of an output format as `\format` takes it
+fn format_name(format: OutputFormat) -> &'static str {
+    match format {
+        OutputFormat::Table => "table",
+        OutputFormat::Json => "json",
+        OutputFormat::Csv => "csv",
+        OutputFormat::Ndjson => "ndjson",
+        OutputFormat::Markdown => "markdown",
+    }
+}
+
+/// Print the session's statement history, optionally only the last `limit` entries
+fn print_history(coordinator: &QueryCoordinator, session_id: &str, limit: Option<usize>) {
+    let history = match coordinator.session_history(session_id) {
+        Ok(history) => history,
+        Err(e) => {
+            eprintln!("{}", format!("Error: {}", e).red());
+            return;
+        }
+    };
+    if history.is_empty() {
+        println!("{}", "No statements in session history".yellow());
+        return;
+    }
+
+    let skip = limit.map_or(0, |limit| history.len().saturating_sub(limit));
+    for entry in history.iter().skip(skip) {
+        let sequence = format!("{:>4}", entry.sequence);
+        let sequence = if entry.succeeded() {
+            sequence.cyan()
+        } else {
+            sequence.red()
+        };
+        let statement = entry.statement.replace('\n', " ");
+        println!("{}  {} ({} ms)", sequence, statement, entry.duration_ms);
+    }
 }
 
 /// Handle the query command (one-off query execution)
+///
+/// A query of `-` reads statements from stdin and runs them in order on one
+/// session. Unless `fail_fast` is set every statement runs, each failure is
+/// reported as it happens, and the returned failure carries the category of
+/// the first.
+#[allow(clippy::too_many_arguments)]
 pub fn handle_query(
     path: PathBuf,
     query: String,
+    params: HashMap<String, Value>,
     user: Option<String>,
     password: Option<String>,
-    format: OutputFormat,
+    output: OutputOptions,
+    run: QueryRunOptions,
     explain: bool,
     ast: bool,
-) -> Result<(), Box<dyn std::error::Error>> {
-    // Check if database exists
-    if !path.exists() {
-        return Err(format!(
-            "Database not found at {:?}. Run 'cargo run -- install' first.",
-            path
-        )
-        .into());
-    }
-
-    // Load database
-    let coordinator = load_database(&path)?;
-
-    // Authenticate if credentials provided, otherwise use anonymous session
-    let session_id = if let (Some(u), Some(p)) = (user, password) {
-        authenticate(&coordinator, &u, &p)?
+) -> Result<(), QueryFailure> {
+    let statements = if query == "-" {
+        let statements = OpenState::split_statements(&read_stdin()?);
+        if statements.is_empty() {
+            return Err(QueryFailure::usage("No statements on stdin"));
+        }
+        statements
     } else {
-        // Create anonymous session (limited permissions)
-        coordinator.create_simple_session("anonymous")?
+        vec![ScriptStatement {
+            line: 1,
+            text: query,
+        }]
     };
+    let (coordinator, session_id) = open_query_session(&path, user, password)?;
 
     // Show AST if requested
     if ast {
@@ -303,18 +709,380 @@ pub fn handle_query(
         return Ok(());
     }
 
-    // Execute query
-    match coordinator.process_query(&query, &session_id) {
-        Ok(result) => {
-            let output = ResultFormatter::format(&result, format);
-            println!("{}", output);
-            Ok(())
+    let mut first_failure = None;
+    let mut failures = 0;
+    for statement in &statements {
+        let result = if params.is_empty() {
+            coordinator.process_query(&statement.text, &session_id)
+        } else {
+            coordinator.process_query_with_params(&statement.text, &params, &session_id)
+        };
+        let result = match result {
+            Ok(result) => result,
+            Err(e) if statements.len() == 1 => return Err(QueryFailure::query(e)),
+            Err(e) => {
+                let mut failure = QueryFailure::query(e);
+                failure.message = format!("Line {}: {}", statement.line, failure.message);
+                if run.fail_fast {
+                    return Err(failure);
+                }
+                failure.report(run.error_format);
+                failures += 1;
+                first_failure.get_or_insert(failure.category);
+                continue;
+            }
+        };
+        if run.quiet {
+            continue;
         }
-        Err(e) => {
-            eprintln!("{}", format!("Error: {}", e).red());
-            Err(e.into())
+        let formatted = ResultFormatter::format_with(&result, output);
+        if formatted.ends_with('\n') {
+            print!("{}", formatted);
+        } else {
+            println!("{}", formatted);
+        }
+    }
+
+    match first_failure {
+        Some(category) => Err(QueryFailure {
+            category,
+            message: format!("{} of {} statement(s) failed", failures, statements.len()),
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Read all of stdin, for a query of `-`
+fn read_stdin() -> Result<String, QueryFailure> {
+    let mut input = String::new();
+    std::io::Read::read_to_string(&mut std::io::stdin(), &mut input)
+        .map_err(|e| QueryFailure::usage(format!("Failed to read stdin: {}", e)))?;
+    Ok(input)
+}
+
+/// Handle the query command with `--watch`
+pub fn handle_watch(
+    path: PathBuf,
+    query: String,
+    params: HashMap<String, Value>,
+    user: Option<String>,
+    password: Option<String>,
+    output: OutputOptions,
+    watch: WatchOptions,
+) -> Result<(), QueryFailure> {
+    let query = if query == "-" { read_stdin()? } else { query };
+    let (coordinator, session_id) = open_query_session(&path, user, password)?;
+    run_watch(&coordinator, &session_id, &query, &params, output, &watch)
+}
+
+/// Handle the run command
+pub fn handle_run(
+    args: RunArgs,
+    user: Option<String>,
+    password: Option<String>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let (coordinator, session_id) =
+        open_query_session(&args.path, user, password).map_err(|failure| failure.message)?;
+    run_script(&coordinator, &session_id, &args)?;
+    Ok(())
+}
+
+/// Handle the migrate command
+pub fn handle_migrate(
+    args: MigrateArgs,
+    user: Option<String>,
+    password: Option<String>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let (coordinator, session_id) =
+        open_query_session(&args.path, user, password).map_err(|failure| failure.message)?;
+    run_migrate(&coordinator, &session_id, &args)?;
+    Ok(())
+}
+
+/// Handle the users command
+pub fn handle_users(
+    args: UsersArgs,
+    user: Option<String>,
+    password: Option<String>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let (coordinator, session_id, username) = open_user_session(&args.path, user, password)?;
+    run_users(&coordinator, &session_id, &username, &args)?;
+    Ok(())
+}
+
+/// Handle the roles command
+pub fn handle_roles(
+    args: RolesArgs,
+    user: Option<String>,
+    password: Option<String>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let (coordinator, session_id, username) = open_user_session(&args.path, user, password)?;
+    run_roles(&coordinator, &session_id, &username, &args)?;
+    Ok(())
+}
+
+/// Handle the import command
+pub fn handle_import(
+    args: ImportArgs,
+    user: Option<String>,
+    password: Option<String>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let (coordinator, session_id) =
+        open_query_session(&args.path, user, password).map_err(|failure| failure.message)?;
+    coordinator.process_query(&format!("SESSION SET GRAPH {}", args.graph), &session_id)?;
+    run_import(&coordinator, &session_id, &args)?;
+    Ok(())
+}
+
+/// Handle the export command
+pub fn handle_export(
+    args: ExportArgs,
+    user: Option<String>,
+    password: Option<String>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let (coordinator, session_id) =
+        open_query_session(&args.path, user, password).map_err(|failure| failure.message)?;
+    coordinator.process_query(&format!("SESSION SET GRAPH {}", args.graph), &session_id)?;
+    run_export(&coordinator, &session_id, &args)?;
+    Ok(())
+}
+
+/// Handle the serve command
+pub fn handle_serve(args: ServeArgs) -> Result<(), Box<dyn std::error::Error>> {
+    run_server(&args)
+}
+
+/// Handle the bench command
+///
+/// Always runs against a new database, so existing data is never touched.
+pub fn handle_bench(args: BenchArgs) -> Result<(), Box<dyn std::error::Error>> {
+    run_bench(&args)?;
+    Ok(())
+}
+
+/// Handle the conformance command
+///
+/// Runs against a new temporary database and fails if the engine disagrees
+/// with the feature matrix, so CI can gate on the exit status.
+pub fn handle_conformance(args: ConformanceArgs) -> Result<(), Box<dyn std::error::Error>> {
+    run_conformance(&args)?;
+    Ok(())
+}
+
+/// Handle the check command
+///
+/// Fails if any problem is found, so scripts can use the exit status.
+pub fn handle_check(path: PathBuf, deep: bool) -> Result<(), Box<dyn std::error::Error>> {
+    if !path.exists() {
+        return Err(format!("Database not found at {:?}", path).into());
+    }
+
+    let coordinator = load_database(&path)?;
+    let report = coordinator.check_storage(deep)?;
+    for problem in &report.problems {
+        println!("{}", problem.to_string().red());
+    }
+    println!(
+        "{} check: {} trees, {} records read, {} checksums verified, {} problem(s)",
+        if deep { "Deep" } else { "Quick" },
+        report.trees,
+        report.records,
+        report.verified,
+        report.problems.len()
+    );
+
+    if report.is_ok() {
+        println!("{}", "No corruption found".green());
+        Ok(())
+    } else {
+        Err(format!("{} corrupt record(s) found", report.problems.len()).into())
+    }
+}
+
+/// Handle the verify command
+///
+/// Runs the deep storage check, the edge reference check and the catalog
+/// check, printing every problem and a summary. Fails if any problem remains
+/// (dangling edges removed by `--repair` do not count).
+pub fn handle_verify(path: PathBuf, repair: bool) -> Result<(), Box<dyn std::error::Error>> {
+    if !path.exists() {
+        return Err(format!("Database not found at {:?}", path).into());
+    }
+
+    let coordinator = load_database(&path)?;
+    let report = coordinator.verify(repair)?;
+    for problem in &report.storage.problems {
+        println!("{}", problem.to_string().red());
+    }
+    for edge in &report.references.dangling_edges {
+        if edge.removed {
+            println!("{}", edge.to_string().yellow());
+        } else {
+            println!("{}", edge.to_string().red());
+        }
+    }
+    for problem in &report.catalog {
+        println!("{}", problem.to_string().red());
+    }
+
+    let removed = report
+        .references
+        .dangling_edges
+        .iter()
+        .filter(|edge| edge.removed)
+        .count();
+    println!(
+        "Storage: {} trees, {} records read, {} checksums verified, {} problem(s)",
+        report.storage.trees,
+        report.storage.records,
+        report.storage.verified,
+        report.storage.problems.len()
+    );
+    println!(
+        "References: {} graphs, {} nodes, {} edges, {} dangling edge(s){}",
+        report.references.graphs,
+        report.references.nodes,
+        report.references.edges,
+        report.references.dangling_edges.len(),
+        if removed > 0 {
+            format!(", {} removed", removed)
+        } else {
+            String::new()
+        }
+    );
+    println!("Catalog: {} problem(s)", report.catalog.len());
+
+    if report.is_ok() {
+        println!("{}", "Database verified".green());
+        Ok(())
+    } else if report.references.is_ok() || repair {
+        Err(format!("{} problem(s) found", report.problem_count() - removed).into())
+    } else {
+        Err(format!(
+            "{} problem(s) found; run with --repair to remove dangling edges",
+            report.problem_count()
+        )
+        .into())
+    }
+}
+
+/// Handle the stats command
+pub fn handle_stats(args: StatsArgs) -> Result<(), Box<dyn std::error::Error>> {
+    if !args.path.exists() {
+        return Err(format!("Database not found at {:?}", args.path).into());
+    }
+
+    let coordinator = load_database(&args.path)?;
+    run_stats(&coordinator, &args)?;
+    Ok(())
+}
+
+/// Handle the waldump command
+///
+/// Reads the write-ahead log files directly, so no session or credentials are
+/// needed and the database may be in use by another process.
+pub fn handle_waldump(
+    path: PathBuf,
+    from_lsn: u64,
+    format: WaldumpFormat,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if !path.exists() {
+        return Err(format!("Database not found at {:?}", path).into());
+    }
+
+    let statements = QueryCoordinator::decode_wal(&path, from_lsn)?;
+    match format {
+        WaldumpFormat::Gql => print!("{}", DecodedStatement::to_gql_script(&statements)),
+        WaldumpFormat::Json => {
+            for statement in &statements {
+                println!(
+                    "{}",
+                    serde_json::json!({
+                        "lsn": statement.lsn,
+                        "commit_lsn": statement.commit_lsn,
+                        "transaction_id": statement.transaction_id,
+                        "logged_at": statement.logged_at.to_rfc3339(),
+                        "schema": statement.schema,
+                        "graph": statement.graph,
+                        "statement": statement.statement,
+                    })
+                );
+            }
         }
     }
+    Ok(())
+}
+
+/// Open the database for a one-off query and create its session
+fn open_query_session(
+    path: &PathBuf,
+    user: Option<String>,
+    password: Option<String>,
+) -> Result<(Arc<QueryCoordinator>, String), QueryFailure> {
+    // Check if database exists
+    if !path.exists() {
+        return Err(QueryFailure::database(format!(
+            "Database not found at {:?}. Run 'cargo run -- install' first.",
+            path
+        )));
+    }
+
+    // Load database
+    let coordinator = load_database(path).map_err(|e| QueryFailure::database(e.to_string()))?;
+
+    // Authenticate if credentials provided, otherwise use anonymous session
+    let session_id = if let (Some(u), Some(p)) = (user, password) {
+        authenticate(&coordinator, &u, &p).map_err(|e| QueryFailure::query(e.to_string()))?
+    } else {
+        // Create anonymous session (limited permissions)
+        coordinator
+            .create_simple_session("anonymous")
+            .map_err(QueryFailure::query)?
+    };
+
+    Ok((coordinator, session_id))
+}
+
+/// Open the database and sign in, prompting for the credentials not given
+///
+/// Unlike `open_query_session` there is no anonymous session, for commands
+/// that need to know who runs them.
+fn open_user_session(
+    path: &PathBuf,
+    user: Option<String>,
+    password: Option<String>,
+) -> Result<(Arc<QueryCoordinator>, String, String), Box<dyn std::error::Error>> {
+    if !path.exists() {
+        return Err(format!(
+            "Database not found at {:?}. Run 'cargo run -- install' first.",
+            path
+        )
+        .into());
+    }
+
+    let (username, password) = prompt_credentials(user, password);
+    let coordinator = load_database(path)?;
+    let session_id = authenticate(&coordinator, &username, &password)?;
+    Ok((coordinator, session_id, username))
+}
+
+/// The username and password given, prompting for the missing ones
+fn prompt_credentials(user: Option<String>, password: Option<String>) -> (String, String) {
+    let username = user.unwrap_or_else(|| {
+        print!("Username: ");
+        std::io::Write::flush(&mut std::io::stdout()).unwrap();
+        let mut input = String::new();
+        std::io::stdin().read_line(&mut input).unwrap();
+        input.trim().to_string()
+    });
+
+    let password = password.unwrap_or_else(|| {
+        print!("Password: ");
+        std::io::Write::flush(&mut std::io::stdout()).unwrap();
+        rpassword::read_password().unwrap()
+    });
+
+    (username, password)
 }
 
 /// Load an existing database
@@ -343,9 +1111,60 @@ fn print_help() {
     println!("  {}  - Show this help message", "help".cyan());
     println!("  {}  - Exit the GQL console", "exit/quit".cyan());
     println!("  {}  - Clear the screen", "clear".cyan());
+    println!(
+        "  {}  - Show statements run in this session",
+        "\\history [n]".cyan()
+    );
+    println!(
+        "  {}  - Run statement n from the history again",
+        "\\replay n".cyan()
+    );
+    println!(
+        "  {}  - Toggle expanded display (one line per column)",
+        "\\x [on|off]".cyan()
+    );
+    println!(
+        "  {}  - Toggle time and row count per operator after each result",
+        "\\profile [on|off]".cyan()
+    );
+    println!(
+        "  {}  - Toggle the time each statement took",
+        "\\timing [on|off]".cyan()
+    );
+    println!(
+        "  {}  - Show results as table, json, csv, ndjson or markdown",
+        "\\format [name]".cyan()
+    );
+    println!(
+        "  {}  - Write results to a file; without a file, to the terminal again",
+        "\\o [file]".cyan()
+    );
+    println!("\n{}", "Catalog:".bold().green());
+    println!("  {}  - List graphs", "\\graphs".cyan());
+    println!(
+        "  {}  - List node labels of the current or given graph",
+        "\\labels [graph]".cyan()
+    );
+    println!(
+        "  {}  - List indexes of the current or given graph",
+        "\\indexes [graph]".cyan()
+    );
+    println!(
+        "  {}  - Show labels, relationship types, property keys and indexes",
+        "\\schema [graph]".cyan()
+    );
+    println!("\n{}", "Output:".bold().green());
+    println!("  NULL values are shown as ∅");
+    println!("  Results taller or wider than the terminal open in $PAGER (default: less)");
     println!("\n{}", "Query syntax:".bold().green());
     println!("  Multi-line queries are supported");
-    println!("  Terminate queries with semicolon (;)");
+    println!("  Terminate queries with semicolon (;) outside brackets, strings and comments");
+    println!("  The continuation prompt shows what is still open, e.g. ({{. inside a property map");
+    println!("  Press Ctrl+C to discard a partly entered query");
+    println!(
+        "  Press Up/Down to recall statements, Ctrl+R to search history (~/.graphlite_history)"
+    );
+    println!("  Press Tab to complete keywords, graph paths, labels, relationship types and property keys");
     println!("\n{}", "Examples:".bold().green());
     println!("  {}", "MATCH (n:Person) RETURN n;".yellow());
     println!("  {}", "CREATE SCHEMA /myschema;".yellow());
@@ -0,0 +1,139 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! Statement boundaries for the interactive GQL console and `graphlite run`
+//!
+//! The console collects lines until a statement ends with `;`. A `;` inside
+//! brackets, a string literal or a comment does not end it, so the console
+//! keeps reading and shows what is still open in the continuation prompt
+//! (`admin::({.>` inside a node's property map, `admin::'..>` inside a string).
+
+/// What is still open at the end of some statement text
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct OpenState {
+    /// Unclosed `(`, `[` and `{`, outermost first
+    pub brackets: Vec<char>,
+    /// Quote character of an unterminated string or quoted identifier
+    pub quote: Option<char>,
+    /// Whether the text ends inside a `/* ... */` comment
+    pub in_comment: bool,
+    /// Whether the text ends with `;` outside brackets, strings and comments
+    pub terminated: bool,
+}
+
+/// One statement of a script, without its terminating `;`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScriptStatement {
+    /// Line of the script the statement starts on, from 1
+    pub line: usize,
+    /// Statement text, trimmed
+    pub text: String,
+}
+
+impl OpenState {
+    /// Scan `text` for brackets, strings and comments left open
+    pub fn scan(text: &str) -> Self {
+        Self::scan_with(text, |_, _| {})
+    }
+
+    /// Scan `text`, calling `on_code` with the byte offset of every character
+    /// outside strings and comments that is not whitespace, and whether it is
+    /// a `;` ending a statement
+    fn scan_with(text: &str, mut on_code: impl FnMut(usize, bool)) -> Self {
+        let mut state = Self::default();
+        let mut chars = text.char_indices().peekable();
+        let mut line_comment = false;
+        while let Some((offset, c)) = chars.next() {
+            if line_comment {
+                line_comment = c != '\n';
+                continue;
+            }
+            if state.in_comment {
+                if c == '*' && chars.next_if(|&(_, next)| next == '/').is_some() {
+                    state.in_comment = false;
+                }
+                continue;
+            }
+            if let Some(quote) = state.quote {
+                if c == '\\' {
+                    chars.next();
+                } else if c == quote {
+                    state.quote = None;
+                }
+                continue;
+            }
+            if c.is_whitespace() {
+                continue;
+            }
+
+            state.terminated = false;
+            match c {
+                '\'' | '"' | '`' => state.quote = Some(c),
+                '/' if chars.next_if(|&(_, next)| next == '/').is_some() => {
+                    line_comment = true;
+                    continue;
+                }
+                '/' if chars.next_if(|&(_, next)| next == '*').is_some() => {
+                    state.in_comment = true;
+                    continue;
+                }
+                '(' | '[' | '{' => state.brackets.push(c),
+                ')' | ']' | '}' => {
+                    state.brackets.pop();
+                }
+                ';' => state.terminated = state.brackets.is_empty(),
+                _ => {}
+            }
+            on_code(offset, state.terminated);
+        }
+        state
+    }
+
+    /// Split a script into statements at every `;` that would end a console statement
+    ///
+    /// Text after the last `;` is a statement of its own unless it holds only
+    /// whitespace and comments, as are empty statements between two `;`.
+    pub fn split_statements(script: &str) -> Vec<ScriptStatement> {
+        // Byte ranges of the statements, from their first code character
+        let mut ranges = Vec::new();
+        let mut current: Option<usize> = None;
+        Self::scan_with(script, |offset, ends| match (ends, current) {
+            (true, Some(start)) => {
+                ranges.push(start..offset);
+                current = None;
+            }
+            (true, None) => {}
+            (false, None) => current = Some(offset),
+            (false, Some(_)) => {}
+        });
+        if let Some(start) = current {
+            ranges.push(start..script.len());
+        }
+
+        ranges
+            .into_iter()
+            .map(|range| ScriptStatement {
+                line: script[..range.start].matches('\n').count() + 1,
+                text: script[range].trim().to_string(),
+            })
+            .collect()
+    }
+
+    /// Three-character marker for the continuation prompt: the innermost
+    /// open constructs padded with dots, e.g. `({.`, `'..` or `...`
+    pub fn prompt_marker(&self) -> String {
+        let mut open: Vec<char> = self.brackets.clone();
+        if let Some(quote) = self.quote {
+            open.push(quote);
+        }
+        if self.in_comment {
+            open.push('*');
+        }
+        let skip = open.len().saturating_sub(3);
+        let mut marker: String = open.into_iter().skip(skip).collect();
+        while marker.chars().count() < 3 {
+            marker.push('.');
+        }
+        marker
+    }
+}
@@ -0,0 +1,697 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! Versioned GQL migrations for `graphlite migrate`
+//!
+//! A migration is a file named `<version>_<name>.gql` in the migrations
+//! directory, or a pair `<version>_<name>.up.gql` and `<version>_<name>.down.gql`
+//! when it can be reverted. Versions are whole numbers and order the
+//! migrations; other files in the directory are ignored.
+//!
+//! Applied versions are recorded as `SchemaMigration` nodes in the history
+//! graph, the same records the SDK's `Migrations` keeps, so migrations applied
+//! by an application and by the CLI are one history. Each migration runs in its
+//! own transaction together with its record, and a failing one is rolled back
+//! and stops the run. Catalog statements such as `CREATE GRAPH` take effect at
+//! once and are not rolled back, so write them with `IF NOT EXISTS`.
+//!
+//! Each script starts on the graph the session had when `migrate` was run,
+//! never on the history graph, and that graph is selected again afterwards.
+
+use colored::Colorize;
+use comfy_table::{presets::UTF8_FULL, Cell, CellAlignment, Color, Table};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use super::commands::{MigrateAction, MigrateArgs};
+use super::input::OpenState;
+use super::output::ResultFormatter;
+use graphlite::{QueryCoordinator, QueryErrorKind, Value};
+
+/// A migration found in the migrations directory
+#[derive(Debug, Default)]
+struct MigrationFile {
+    name: String,
+    up: Option<PathBuf>,
+    down: Option<PathBuf>,
+}
+
+/// A migration recorded in the history graph
+#[derive(Debug)]
+struct AppliedMigration {
+    name: String,
+    applied_at: String,
+}
+
+/// Run a `graphlite migrate` subcommand
+pub fn run_migrate(
+    coordinator: &QueryCoordinator,
+    session_id: &str,
+    args: &MigrateArgs,
+) -> Result<(), String> {
+    let migrations = read_migrations(&args.dir)?;
+    let caller_graph = coordinator.session_handle(session_id)?.current_graph();
+    let outcome = match args.action {
+        MigrateAction::Status => {
+            let applied = applied_migrations(coordinator, session_id, &args.history_graph)?;
+            print_status(&migrations, &applied);
+            Ok(())
+        }
+        MigrateAction::Up { to } => up(
+            coordinator,
+            session_id,
+            args,
+            &migrations,
+            caller_graph.as_deref(),
+            to,
+        ),
+        MigrateAction::Down { to } => down(
+            coordinator,
+            session_id,
+            args,
+            &migrations,
+            caller_graph.as_deref(),
+            to,
+        ),
+    };
+    let restored = select_graph(coordinator, session_id, caller_graph.as_deref());
+    outcome.and(restored)
+}
+
+/// Make `graph` the session graph, or clear it when there is none
+fn select_graph(
+    coordinator: &QueryCoordinator,
+    session_id: &str,
+    graph: Option<&str>,
+) -> Result<(), String> {
+    let statement = match graph {
+        Some(graph) => format!("SESSION SET GRAPH {}", graph),
+        None => "SESSION RESET GRAPH".to_string(),
+    };
+    coordinator.process_query(&statement, session_id)?;
+    Ok(())
+}
+
+/// The migrations in `dir` by version
+fn read_migrations(dir: &Path) -> Result<BTreeMap<u64, MigrationFile>, String> {
+    let entries = std::fs::read_dir(dir).map_err(|e| {
+        format!(
+            "Failed to read migrations directory {}: {}",
+            dir.display(),
+            e
+        )
+    })?;
+
+    let mut migrations: BTreeMap<u64, MigrationFile> = BTreeMap::new();
+    for entry in entries {
+        let path = entry.map_err(|e| e.to_string())?.path();
+        let Some(file_name) = path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        let Some(stem) = file_name.strip_suffix(".gql") else {
+            continue;
+        };
+        let (stem, down) = match (stem.strip_suffix(".up"), stem.strip_suffix(".down")) {
+            (Some(stem), _) => (stem, false),
+            (_, Some(stem)) => (stem, true),
+            _ => (stem, false),
+        };
+        let (version, name) = stem
+            .split_once('_')
+            .and_then(|(version, name)| Some((version.parse::<u64>().ok()?, name)))
+            .ok_or_else(|| {
+                format!(
+                    "Migration file {} is not named <version>_<name>.gql",
+                    file_name
+                )
+            })?;
+
+        let migration = migrations.entry(version).or_default();
+        if !migration.name.is_empty() && migration.name != name {
+            return Err(format!(
+                "Migration version {} is used twice ({} and {})",
+                version, migration.name, name
+            ));
+        }
+        migration.name = name.to_string();
+        let slot = if down {
+            &mut migration.down
+        } else {
+            &mut migration.up
+        };
+        if slot.is_some() {
+            return Err(format!(
+                "Migration version {} has more than one {} script",
+                version,
+                if down { "down" } else { "up" }
+            ));
+        }
+        *slot = Some(path);
+    }
+
+    if let Some((version, _)) = migrations.iter().find(|(_, m)| m.up.is_none()) {
+        return Err(format!(
+            "Migration {} has a down script but no up script",
+            version
+        ));
+    }
+    Ok(migrations)
+}
+
+/// Versions recorded in the history graph; none if the graph does not exist yet
+fn applied_migrations(
+    coordinator: &QueryCoordinator,
+    session_id: &str,
+    history_graph: &str,
+) -> Result<BTreeMap<u64, AppliedMigration>, String> {
+    if let Err(e) =
+        coordinator.process_query(&format!("SESSION SET GRAPH {}", history_graph), session_id)
+    {
+        return match QueryErrorKind::from_message(&e) {
+            QueryErrorKind::NotFound => Ok(BTreeMap::new()),
+            _ => Err(e),
+        };
+    }
+
+    let result = coordinator.process_query(
+        "MATCH (m:SchemaMigration) RETURN m.id AS id, m.name AS name, m.applied_at AS applied_at",
+        session_id,
+    )?;
+    Ok(result
+        .rows
+        .iter()
+        .filter_map(|row| {
+            let Some(Value::Number(id)) = row.get_value("id") else {
+                return None;
+            };
+            let text = |column| {
+                row.get_value(column)
+                    .map(ResultFormatter::value_to_string)
+                    .unwrap_or_default()
+            };
+            Some((
+                *id as u64,
+                AppliedMigration {
+                    name: text("name"),
+                    applied_at: text("applied_at"),
+                },
+            ))
+        })
+        .collect())
+}
+
+fn print_status(
+    migrations: &BTreeMap<u64, MigrationFile>,
+    applied: &BTreeMap<u64, AppliedMigration>,
+) {
+    let mut table = Table::new();
+    table.load_preset(UTF8_FULL);
+    table.set_header(
+        ["version", "name", "status", "applied at", "down script"]
+            .iter()
+            .map(|column| Cell::new(column).fg(Color::Green)),
+    );
+
+    let mut versions: Vec<u64> = migrations.keys().chain(applied.keys()).copied().collect();
+    versions.sort_unstable();
+    versions.dedup();
+    for version in &versions {
+        let file = migrations.get(version);
+        let record = applied.get(version);
+        let status = match (file, record) {
+            (Some(_), Some(_)) => Cell::new("applied").fg(Color::Green),
+            (Some(_), None) => Cell::new("pending").fg(Color::Yellow),
+            (None, _) => Cell::new("file missing").fg(Color::Red),
+        };
+        table.add_row(vec![
+            Cell::new(version).set_alignment(CellAlignment::Right),
+            Cell::new(
+                file.map(|file| file.name.as_str())
+                    .or(record.map(|record| record.name.as_str()))
+                    .unwrap_or_default(),
+            ),
+            status,
+            Cell::new(record.map_or("", |record| record.applied_at.as_str())),
+            Cell::new(if file.is_some_and(|file| file.down.is_some()) {
+                "yes"
+            } else {
+                "no"
+            }),
+        ]);
+    }
+    println!("{}", table);
+
+    let pending = migrations
+        .keys()
+        .filter(|version| !applied.contains_key(version))
+        .count();
+    println!(
+        "{} migration(s), {} applied, {} pending",
+        versions.len(),
+        applied.len(),
+        pending
+    );
+}
+
+/// Apply the pending migrations up to and including `to`
+fn up(
+    coordinator: &QueryCoordinator,
+    session_id: &str,
+    args: &MigrateArgs,
+    migrations: &BTreeMap<u64, MigrationFile>,
+    caller_graph: Option<&str>,
+    to: Option<u64>,
+) -> Result<(), String> {
+    create_history_graph(coordinator, session_id, &args.history_graph)?;
+    let applied = applied_migrations(coordinator, session_id, &args.history_graph)?;
+
+    let mut count = 0;
+    for (version, migration) in migrations {
+        if to.is_some_and(|to| *version > to) {
+            break;
+        }
+        if applied.contains_key(version) {
+            continue;
+        }
+        let Some(up) = &migration.up else {
+            continue;
+        };
+        let started = Instant::now();
+        let record = HashMap::from([
+            ("id".to_string(), Value::Number(*version as f64)),
+            ("name".to_string(), Value::String(migration.name.clone())),
+        ]);
+        run_migration(
+            coordinator,
+            session_id,
+            args,
+            caller_graph,
+            up,
+            "INSERT (:SchemaMigration {id: $id, name: $name, applied_at: NOW()})",
+            &record,
+        )
+        .map_err(|e| format!("Migration {} ({}) failed: {}", version, migration.name, e))?;
+        println!(
+            "{} {} {} ({} ms)",
+            "Applied".green(),
+            version,
+            migration.name,
+            started.elapsed().as_millis()
+        );
+        count += 1;
+    }
+
+    if count == 0 {
+        println!("Database is up to date");
+    } else {
+        println!("{} migration(s) applied", count);
+    }
+    Ok(())
+}
+
+/// Revert the latest applied migration, or every applied migration newer than `to`
+fn down(
+    coordinator: &QueryCoordinator,
+    session_id: &str,
+    args: &MigrateArgs,
+    migrations: &BTreeMap<u64, MigrationFile>,
+    caller_graph: Option<&str>,
+    to: Option<u64>,
+) -> Result<(), String> {
+    let applied = applied_migrations(coordinator, session_id, &args.history_graph)?;
+    let reverted: Vec<u64> = match to {
+        Some(to) => applied
+            .keys()
+            .rev()
+            .take_while(|version| **version > to)
+            .copied()
+            .collect(),
+        None => applied.keys().next_back().copied().into_iter().collect(),
+    };
+    if reverted.is_empty() {
+        println!("No migrations to revert");
+        return Ok(());
+    }
+
+    for version in &reverted {
+        let name = &applied[version].name;
+        let down = migrations
+            .get(version)
+            .and_then(|migration| migration.down.as_ref())
+            .ok_or_else(|| format!("Migration {} ({}) has no down script", version, name))?;
+        let started = Instant::now();
+        let record = HashMap::from([("id".to_string(), Value::Number(*version as f64))]);
+        run_migration(
+            coordinator,
+            session_id,
+            args,
+            caller_graph,
+            down,
+            "MATCH (m:SchemaMigration) WHERE m.id = $id DELETE m",
+            &record,
+        )
+        .map_err(|e| format!("Reverting migration {} ({}) failed: {}", version, name, e))?;
+        println!(
+            "{} {} {} ({} ms)",
+            "Reverted".green(),
+            version,
+            name,
+            started.elapsed().as_millis()
+        );
+    }
+    println!("{} migration(s) reverted", reverted.len());
+    Ok(())
+}
+
+fn create_history_graph(
+    coordinator: &QueryCoordinator,
+    session_id: &str,
+    history_graph: &str,
+) -> Result<(), String> {
+    let schema = history_graph
+        .rsplit_once('/')
+        .map(|(schema, _)| schema)
+        .filter(|schema| !schema.is_empty())
+        .ok_or_else(|| {
+            format!(
+                "Migration history graph must be a /schema/graph path, got '{}'",
+                history_graph
+            )
+        })?;
+    coordinator.process_query(
+        &format!("CREATE SCHEMA IF NOT EXISTS {}", schema),
+        session_id,
+    )?;
+    coordinator.process_query(
+        &format!("CREATE GRAPH IF NOT EXISTS {}", history_graph),
+        session_id,
+    )?;
+    Ok(())
+}
+
+/// Run the statements of `script` and `record`, the statement updating the
+/// history graph, in one transaction that is rolled back if any fails
+///
+/// The script runs on `caller_graph`, so statements that do not select a
+/// graph of their own never write into the history graph.
+fn run_migration(
+    coordinator: &QueryCoordinator,
+    session_id: &str,
+    args: &MigrateArgs,
+    caller_graph: Option<&str>,
+    script: &Path,
+    record: &str,
+    record_params: &HashMap<String, Value>,
+) -> Result<(), String> {
+    let text = std::fs::read_to_string(script)
+        .map_err(|e| format!("Failed to read {}: {}", script.display(), e))?;
+
+    coordinator.process_query("START TRANSACTION", session_id)?;
+    let outcome = (|| {
+        select_graph(coordinator, session_id, caller_graph)?;
+        for statement in OpenState::split_statements(&text) {
+            coordinator
+                .process_query(&statement.text, session_id)
+                .map_err(|e| format!("line {}: {}", statement.line, e))?;
+        }
+        coordinator.process_query(
+            &format!("SESSION SET GRAPH {}", args.history_graph),
+            session_id,
+        )?;
+        coordinator.process_query_with_params(record, record_params, session_id)?;
+        coordinator.process_query("COMMIT", session_id)
+    })();
+    if let Err(e) = outcome {
+        let _ = coordinator.process_query("ROLLBACK", session_id);
+        return Err(e);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(dir: &Path, name: &str, text: &str) {
+        std::fs::write(dir.join(name), text).unwrap();
+    }
+
+    fn args(dir: &Path, action: MigrateAction) -> MigrateArgs {
+        MigrateArgs {
+            action,
+            path: dir.join("db"),
+            dir: dir.join("migrations"),
+            history_graph: "/graphlite/migrations".to_string(),
+        }
+    }
+
+    fn count(coordinator: &QueryCoordinator, session_id: &str, query: &str) -> usize {
+        coordinator
+            .process_query(query, session_id)
+            .unwrap()
+            .rows
+            .len()
+    }
+
+    #[test]
+    fn test_read_migrations_pairs_up_and_down_scripts() {
+        let dir = tempfile::tempdir().unwrap();
+        write(dir.path(), "2_seed.up.gql", "");
+        write(dir.path(), "2_seed.down.gql", "");
+        write(dir.path(), "1_create.gql", "");
+        write(dir.path(), "README.md", "");
+
+        let migrations = read_migrations(dir.path()).unwrap();
+        assert_eq!(migrations.keys().copied().collect::<Vec<_>>(), [1, 2]);
+        assert_eq!(migrations[&1].name, "create");
+        assert!(migrations[&1].up.is_some() && migrations[&1].down.is_none());
+        assert_eq!(migrations[&2].name, "seed");
+        assert!(migrations[&2].up.is_some() && migrations[&2].down.is_some());
+    }
+
+    #[test]
+    fn test_read_migrations_rejects_duplicate_versions() {
+        let dir = tempfile::tempdir().unwrap();
+        write(dir.path(), "1_a.gql", "");
+        write(dir.path(), "1_b.gql", "");
+        let error = read_migrations(dir.path()).unwrap_err();
+        assert!(
+            error.contains("Migration version 1 is used twice"),
+            "{}",
+            error
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        write(dir.path(), "1_a.gql", "");
+        write(dir.path(), "1_a.up.gql", "");
+        let error = read_migrations(dir.path()).unwrap_err();
+        assert!(error.contains("more than one up script"), "{}", error);
+    }
+
+    #[test]
+    fn test_read_migrations_rejects_down_without_up() {
+        let dir = tempfile::tempdir().unwrap();
+        write(dir.path(), "1_a.gql", "");
+        write(dir.path(), "2_b.down.gql", "");
+        let error = read_migrations(dir.path()).unwrap_err();
+        assert_eq!(error, "Migration 2 has a down script but no up script");
+    }
+
+    #[test]
+    fn test_read_migrations_rejects_unversioned_files() {
+        for name in ["create.gql", "v1_create.gql", "1.gql"] {
+            let dir = tempfile::tempdir().unwrap();
+            write(dir.path(), name, "");
+            let error = read_migrations(dir.path()).unwrap_err();
+            assert!(
+                error.contains("is not named <version>_<name>.gql"),
+                "{}",
+                error
+            );
+        }
+    }
+
+    #[test]
+    fn test_scripts_run_on_the_caller_graph() {
+        let dir = tempfile::tempdir().unwrap();
+        let migrations_dir = dir.path().join("migrations");
+        std::fs::create_dir(&migrations_dir).unwrap();
+        write(
+            &migrations_dir,
+            "1_seed.up.gql",
+            "INSERT (:User {name: 'alice'})",
+        );
+        write(
+            &migrations_dir,
+            "1_seed.down.gql",
+            "MATCH (u:User) DELETE u",
+        );
+
+        let coordinator = QueryCoordinator::from_path(dir.path().join("db")).unwrap();
+        let session_id = coordinator.create_simple_session("admin").unwrap();
+        for statement in [
+            "CREATE SCHEMA /app",
+            "CREATE GRAPH /app/g",
+            "SESSION SET GRAPH /app/g",
+        ] {
+            coordinator.process_query(statement, &session_id).unwrap();
+        }
+
+        let up = args(dir.path(), MigrateAction::Up { to: None });
+        run_migrate(&coordinator, &session_id, &up).unwrap();
+        let graph = coordinator
+            .session_handle(&session_id)
+            .unwrap()
+            .current_graph();
+        assert_eq!(graph.as_deref(), Some("/app/g"));
+        assert_eq!(
+            count(&coordinator, &session_id, "MATCH (u:User) RETURN u"),
+            1
+        );
+
+        // The script wrote nothing into the history graph
+        coordinator
+            .process_query("SESSION SET GRAPH /graphlite/migrations", &session_id)
+            .unwrap();
+        assert_eq!(
+            count(&coordinator, &session_id, "MATCH (u:User) RETURN u"),
+            0
+        );
+        assert_eq!(
+            count(
+                &coordinator,
+                &session_id,
+                "MATCH (m:SchemaMigration) RETURN m"
+            ),
+            1
+        );
+
+        coordinator
+            .process_query("SESSION SET GRAPH /app/g", &session_id)
+            .unwrap();
+        let down = args(dir.path(), MigrateAction::Down { to: None });
+        run_migrate(&coordinator, &session_id, &down).unwrap();
+        assert_eq!(
+            count(&coordinator, &session_id, "MATCH (u:User) RETURN u"),
+            0
+        );
+    }
+
+    #[test]
+    fn test_scripts_without_a_caller_graph_select_no_graph() {
+        let dir = tempfile::tempdir().unwrap();
+        let migrations_dir = dir.path().join("migrations");
+        std::fs::create_dir(&migrations_dir).unwrap();
+        write(
+            &migrations_dir,
+            "1_graph.gql",
+            "CREATE SCHEMA IF NOT EXISTS /app; CREATE GRAPH IF NOT EXISTS /app/g",
+        );
+        write(
+            &migrations_dir,
+            "2_seed.gql",
+            "INSERT (:User {name: 'alice'})",
+        );
+
+        let coordinator = QueryCoordinator::from_path(dir.path().join("db")).unwrap();
+        let session_id = coordinator.create_simple_session("admin").unwrap();
+        let up = args(dir.path(), MigrateAction::Up { to: None });
+        let error = run_migrate(&coordinator, &session_id, &up).unwrap_err();
+        assert!(error.starts_with("Migration 2 (seed) failed"), "{}", error);
+        let graph = coordinator
+            .session_handle(&session_id)
+            .unwrap()
+            .current_graph();
+        assert_eq!(graph, None);
+
+        coordinator
+            .process_query("SESSION SET GRAPH /graphlite/migrations", &session_id)
+            .unwrap();
+        assert_eq!(
+            count(&coordinator, &session_id, "MATCH (u:User) RETURN u"),
+            0
+        );
+        assert_eq!(
+            count(
+                &coordinator,
+                &session_id,
+                "MATCH (m:SchemaMigration) RETURN m"
+            ),
+            1
+        );
+    }
+
+    #[test]
+    fn test_down_to_reverts_newer_migrations() {
+        let dir = tempfile::tempdir().unwrap();
+        let migrations_dir = dir.path().join("migrations");
+        std::fs::create_dir(&migrations_dir).unwrap();
+        write(
+            &migrations_dir,
+            "1_graph.gql",
+            "CREATE SCHEMA IF NOT EXISTS /app; CREATE GRAPH IF NOT EXISTS /app/g",
+        );
+        for version in 2..=4 {
+            write(
+                &migrations_dir,
+                &format!("{}_step.up.gql", version),
+                &format!(
+                    "SESSION SET GRAPH /app/g; INSERT (:Step {{n: {}}})",
+                    version
+                ),
+            );
+            write(
+                &migrations_dir,
+                &format!("{}_step.down.gql", version),
+                &format!(
+                    "SESSION SET GRAPH /app/g; MATCH (s:Step) WHERE s.n = {} DELETE s",
+                    version
+                ),
+            );
+        }
+
+        let coordinator = QueryCoordinator::from_path(dir.path().join("db")).unwrap();
+        let session_id = coordinator.create_simple_session("admin").unwrap();
+        let history = "/graphlite/migrations";
+        let applied = || {
+            applied_migrations(&coordinator, &session_id, history)
+                .unwrap()
+                .into_keys()
+                .collect::<Vec<_>>()
+        };
+
+        run_migrate(
+            &coordinator,
+            &session_id,
+            &args(dir.path(), MigrateAction::Up { to: None }),
+        )
+        .unwrap();
+        assert_eq!(applied(), [1, 2, 3, 4]);
+
+        run_migrate(
+            &coordinator,
+            &session_id,
+            &args(dir.path(), MigrateAction::Down { to: Some(2) }),
+        )
+        .unwrap();
+        assert_eq!(applied(), [1, 2]);
+        coordinator
+            .process_query("SESSION SET GRAPH /app/g", &session_id)
+            .unwrap();
+        assert_eq!(
+            count(&coordinator, &session_id, "MATCH (s:Step) RETURN s"),
+            1
+        );
+
+        // Migration 2 is reverted before migration 1, which has no down script
+        let error = run_migrate(
+            &coordinator,
+            &session_id,
+            &args(dir.path(), MigrateAction::Down { to: Some(0) }),
+        )
+        .unwrap_err();
+        assert_eq!(error, "Migration 1 (graph) has no down script");
+        assert_eq!(applied(), [1]);
+    }
+}
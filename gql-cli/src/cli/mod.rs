@@ -4,11 +4,32 @@
 //! CLI module for GraphLite
 //!
 //! Provides command-line interface for database initialization,
-//! interactive GQL console (REPL), and one-off query execution.
+//! interactive GQL console (REPL), one-off query and script execution, migrations, data import and export,
+//! storage checks and verification, database statistics, user and role management, WAL decoding, an HTTP server, benchmarks and a GQL
+//! conformance run.
 
+pub mod bench;
 pub mod commands;
+pub mod completion;
+pub mod conformance;
+pub mod errors;
+pub mod export;
 pub mod gqlcli;
+pub mod import;
+pub mod input;
+pub mod migrate;
 pub mod output;
+pub mod pager;
+pub mod params;
+pub mod script;
+pub mod security;
+pub mod serve;
+pub mod stats;
+pub mod watch;
 
-pub use commands::{Cli, Commands};
-pub use gqlcli::{handle_gql, handle_install, handle_query};
+pub use commands::{Cli, Commands, OutputOptions, QueryRunOptions};
+pub use gqlcli::{
+    handle_bench, handle_check, handle_conformance, handle_export, handle_gql, handle_import,
+    handle_install, handle_migrate, handle_query, handle_roles, handle_run, handle_serve,
+    handle_stats, handle_users, handle_verify, handle_waldump, handle_watch,
+};
@@ -3,88 +3,286 @@
 //
 //! Result formatting for CLI output
 
+use chrono::SecondsFormat;
 use colored::*;
-use comfy_table::{presets::UTF8_FULL, Cell, Color, Table};
-use graphlite::{QueryResult, Value};
+use comfy_table::{presets::UTF8_FULL, Attribute, Cell, CellAlignment, Color, Table};
+use graphlite::{NumberFormat, OperatorTiming, QueryResult, Value};
+
+use super::commands::{DisplayLimits, OutputFormat, OutputOptions};
+
+/// Marker shown for NULL values in table and expanded output
+const NULL_MARKER: &str = "∅";
 
 /// Result formatter for different output formats
 pub struct ResultFormatter;
 
 impl ResultFormatter {
     /// Format query results in the specified format
-    pub fn format(result: &QueryResult, format: crate::cli::commands::OutputFormat) -> String {
-        match format {
-            crate::cli::commands::OutputFormat::Table => Self::format_table(result),
-            crate::cli::commands::OutputFormat::Json => Self::format_json(result),
-            crate::cli::commands::OutputFormat::Csv => Self::format_csv(result),
-        }
+    pub fn format(result: &QueryResult, format: OutputFormat) -> String {
+        Self::format_with(
+            result,
+            OutputOptions {
+                format,
+                header: true,
+                numbers: NumberFormat::default(),
+                limits: DisplayLimits::default(),
+            },
+        )
     }
 
-    /// Format results as a table using comfy-table
-    fn format_table(result: &QueryResult) -> String {
-        // Check if this is a session command
-        if result.is_session_command() {
-            if let Some(msg) = result.get_session_message() {
-                return format!("{}\n", msg.to_string().green());
+    /// Format query results as `options` asks
+    ///
+    /// Without a header, the table, CSV and Markdown formats print data rows
+    /// only; the JSON formats are unaffected.
+    pub fn format_with(result: &QueryResult, options: OutputOptions) -> String {
+        match options.format {
+            OutputFormat::Table => {
+                Self::format_table_highlighted(result, &[], options.header, options.limits)
             }
+            OutputFormat::Json => Self::format_json(result, options.numbers),
+            OutputFormat::Csv => Self::format_csv(result, options.header),
+            OutputFormat::Ndjson => Self::format_ndjson(result, options.numbers),
+            OutputFormat::Markdown => Self::format_markdown(result, options.header),
         }
+    }
 
-        if result.rows.is_empty() {
-            return format!("{}\n", "No results found".yellow());
+    /// Format results as a table, drawing rows flagged in `highlighted` in bold cyan
+    ///
+    /// Numbers are right-aligned and NULLs are shown as a dimmed `∅`. Watch mode
+    /// highlights rows that changed since the previous run. Without a header the
+    /// summary lines and the column names are left out. Rows beyond
+    /// `limits.max_rows` are counted in a footer instead of shown.
+    pub fn format_table_highlighted(
+        result: &QueryResult,
+        highlighted: &[bool],
+        header: bool,
+        limits: DisplayLimits,
+    ) -> String {
+        if let Some(message) = Self::format_status(result) {
+            return message;
         }
 
-        let mut output = String::new();
-
-        // Header
-        output.push_str(&format!("{}\n", "Query Results".bold().green()));
-        output.push_str(&format!(
-            "Execution time: {} ms\n",
-            result.execution_time_ms
-        ));
-        output.push_str(&format!("Rows returned: {}\n\n", result.rows.len()));
+        let mut output = if header {
+            Self::format_summary(result)
+        } else {
+            String::new()
+        };
 
         // Create table
         let mut table = Table::new();
         table.load_preset(UTF8_FULL);
 
         // Table header
-        let header_cells: Vec<Cell> = result
-            .variables
-            .iter()
-            .map(|col| Cell::new(col).fg(Color::Green))
-            .collect();
-        table.set_header(header_cells);
+        if header {
+            let header_cells: Vec<Cell> = result
+                .variables
+                .iter()
+                .map(|col| Cell::new(col).fg(Color::Green))
+                .collect();
+            table.set_header(header_cells);
+        }
 
         // Table rows
-        for row in &result.rows {
-            let row_values: Vec<String> = result
+        let shown = limits.rows().unwrap_or(usize::MAX);
+        for (index, row) in result.rows.iter().take(shown).enumerate() {
+            let highlight = highlighted.get(index).copied().unwrap_or(false);
+            let row_cells: Vec<Cell> = result
                 .variables
                 .iter()
                 .map(|col| {
-                    row.get_value(col)
-                        .map(Self::value_to_string)
-                        .unwrap_or_else(|| "NULL".to_string())
+                    let cell = Self::value_to_cell(row.get_value(col), limits);
+                    if highlight {
+                        cell.fg(Color::Cyan).add_attribute(Attribute::Bold)
+                    } else {
+                        cell
+                    }
                 })
                 .collect();
-            table.add_row(row_values);
+            table.add_row(row_cells);
         }
 
         output.push_str(&table.to_string());
         output.push('\n');
+        output.push_str(&Self::format_hidden_rows(result, shown));
+        output.push_str(&Self::format_warnings(result));
+
+        output
+    }
+
+    /// Format results with one block per row and one line per column (`\x` in the REPL)
+    ///
+    /// Useful for rows too wide to read as a table.
+    pub fn format_expanded(result: &QueryResult, limits: DisplayLimits) -> String {
+        if let Some(message) = Self::format_status(result) {
+            return message;
+        }
+
+        let mut output = Self::format_summary(result);
+        let name_width = result
+            .variables
+            .iter()
+            .map(|col| col.chars().count())
+            .max()
+            .unwrap_or(0);
+
+        let shown = limits.rows().unwrap_or(usize::MAX);
+        for (index, row) in result.rows.iter().take(shown).enumerate() {
+            let values: Vec<Option<String>> = result
+                .variables
+                .iter()
+                .map(|col| match row.get_value(col) {
+                    None | Some(Value::Null) => None,
+                    Some(value) => Some(limits.cut(Self::value_to_string(value))),
+                })
+                .collect();
+            let value_width = values
+                .iter()
+                .map(|value| value.as_deref().map_or(1, |v| v.chars().count()))
+                .max()
+                .unwrap_or(0);
+
+            // Rule as wide as the record, like psql
+            let title = format!("-[ RECORD {} ]", index + 1);
+            let rule_width = name_width + 3 + value_width;
+            output.push_str(&format!(
+                "{}{}\n",
+                title.bold(),
+                "-".repeat(rule_width.saturating_sub(title.chars().count()))
+            ));
+            for (col, value) in result.variables.iter().zip(values) {
+                output.push_str(&format!(
+                    "{} | {}\n",
+                    format!("{:<width$}", col, width = name_width).green(),
+                    value.unwrap_or_else(|| NULL_MARKER.dimmed().to_string())
+                ));
+            }
+        }
+        output.push_str(&Self::format_hidden_rows(result, shown));
+        output.push_str(&Self::format_warnings(result));
+
+        output
+    }
+
+    /// Message shown instead of a table for session commands and empty results
+    fn format_status(result: &QueryResult) -> Option<String> {
+        // Check if this is a session command
+        if result.is_session_command() {
+            if let Some(msg) = result.get_session_message() {
+                return Some(format!("{}\n", msg.to_string().green()));
+            }
+        }
+
+        if result.rows.is_empty() {
+            return Some(format!("{}\n", "No results found".yellow()));
+        }
+
+        None
+    }
+
+    /// Result header with execution time and row count
+    fn format_summary(result: &QueryResult) -> String {
+        let mut output = String::new();
+        output.push_str(&format!("{}\n", "Query Results".bold().green()));
+        output.push_str(&format!(
+            "Execution time: {} ms\n",
+            result.execution_time_ms
+        ));
+        output.push_str(&format!("Rows returned: {}\n\n", result.rows.len()));
+        output
+    }
+
+    /// Footer counting the rows after the first `shown`, if there are any
+    fn format_hidden_rows(result: &QueryResult, shown: usize) -> String {
+        let hidden = result.rows.len().saturating_sub(shown);
+        if hidden == 0 {
+            return String::new();
+        }
+        format!(
+            "{}\n",
+            format!(
+                "… {} more row(s) not shown (--max-rows {} to show all)",
+                hidden,
+                result.rows.len()
+            )
+            .dimmed()
+        )
+    }
 
-        // Display warnings if any
+    /// Display warnings if any
+    fn format_warnings(result: &QueryResult) -> String {
+        let mut output = String::new();
         if !result.warnings.is_empty() {
             output.push_str(&format!("\n{}\n", "Warnings:".bold().yellow()));
             for (i, warning) in result.warnings.iter().enumerate() {
                 output.push_str(&format!("  {}. {}\n", i + 1, warning.yellow()));
             }
         }
+        output
+    }
 
+    /// Operator timing tree (`\profile` in the REPL), one indented line per operator
+    pub fn format_timings(timing: &OperatorTiming) -> String {
+        let mut output = format!("\n{}\n", "Operator timing:".bold().green());
+        Self::push_timing(&mut output, timing, 1);
         output
     }
 
+    fn push_timing(output: &mut String, timing: &OperatorTiming, depth: usize) {
+        output.push_str(&format!(
+            "{}{}  {} rows  {:.3} ms (self {:.3} ms)\n",
+            "  ".repeat(depth),
+            timing.operator.cyan(),
+            timing.rows,
+            timing.elapsed_us as f64 / 1000.0,
+            timing.self_us() as f64 / 1000.0
+        ));
+        for child in &timing.children {
+            Self::push_timing(output, child, depth + 1);
+        }
+    }
+
+    /// Displayed values of each row, in column order
+    pub fn row_strings(result: &QueryResult) -> Vec<Vec<String>> {
+        result
+            .rows
+            .iter()
+            .map(|row| {
+                result
+                    .variables
+                    .iter()
+                    .map(|col| match row.get_value(col) {
+                        None | Some(Value::Null) => NULL_MARKER.to_string(),
+                        Some(value) => Self::value_to_string(value),
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Table cell for a value, aligned and colored by type
+    fn value_to_cell(value: Option<&Value>, limits: DisplayLimits) -> Cell {
+        match value {
+            None | Some(Value::Null) => Cell::new(NULL_MARKER).fg(Color::DarkGrey),
+            Some(Value::Number(n)) => {
+                Cell::new(limits.cut(n.to_string())).set_alignment(CellAlignment::Right)
+            }
+            Some(Value::Boolean(b)) => Cell::new(b).fg(Color::Yellow),
+            Some(value) => Cell::new(limits.cut(Self::value_to_string(value))),
+        }
+    }
+
     /// Format results as JSON
-    fn format_json(result: &QueryResult) -> String {
+    fn format_json(result: &QueryResult, numbers: NumberFormat) -> String {
+        numbers
+            .to_string_pretty(&Self::result_to_json(result))
+            .unwrap_or_else(|_| {
+                "{\"status\": \"error\", \"error\": \"Could not serialize results to JSON\"}"
+                    .to_string()
+            })
+    }
+
+    /// JSON document of a result, as printed by `--format json` and returned by `graphlite serve`
+    pub fn result_to_json(result: &QueryResult) -> serde_json::Value {
         // Create a JSON-friendly representation
         let mut json_obj = serde_json::json!({
             "status": "success",
@@ -110,21 +308,111 @@ impl ResultFormatter {
             }
         }
 
-        let json_result = json_obj;
+        json_obj
+    }
+
+    /// Format results as one compact JSON object per row
+    fn format_ndjson(result: &QueryResult, numbers: NumberFormat) -> String {
+        let mut output = String::new();
+        for row in &result.rows {
+            let row_map: serde_json::Map<String, serde_json::Value> = result
+                .variables
+                .iter()
+                .map(|col| {
+                    let value = row
+                        .get_value(col)
+                        .map(Self::value_to_json)
+                        .unwrap_or(serde_json::Value::Null);
+                    (col.clone(), value)
+                })
+                .collect();
+            let line = serde_json::Value::Object(row_map);
+            output.push_str(
+                &numbers
+                    .to_string(&line)
+                    .unwrap_or_else(|_| line.to_string()),
+            );
+            output.push('\n');
+        }
+        output
+    }
+
+    /// Format results as a GitHub-flavored Markdown table
+    ///
+    /// Columns holding only numbers are right-aligned; `|` and line breaks in
+    /// values are escaped so each row stays on one line.
+    fn format_markdown(result: &QueryResult, header: bool) -> String {
+        let mut output = String::new();
+        // A table needs at least one column; session commands have none
+        if result.variables.is_empty() {
+            return output;
+        }
+
+        if header {
+            let names: Vec<String> = result
+                .variables
+                .iter()
+                .map(|col| Self::escape_markdown(col))
+                .collect();
+            output.push_str(&format!("| {} |\n", names.join(" | ")));
+            let rules: Vec<&str> = result
+                .variables
+                .iter()
+                .map(|col| {
+                    let numeric = result.rows.iter().all(|row| {
+                        matches!(
+                            row.get_value(col),
+                            None | Some(Value::Null) | Some(Value::Number(_))
+                        )
+                    });
+                    if numeric && !result.rows.is_empty() {
+                        "---:"
+                    } else {
+                        "---"
+                    }
+                })
+                .collect();
+            output.push_str(&format!("| {} |\n", rules.join(" | ")));
+        }
 
-        serde_json::to_string_pretty(&json_result).unwrap_or_else(|_| {
-            "{\"status\": \"error\", \"error\": \"Could not serialize results to JSON\"}"
-                .to_string()
-        })
+        for row in &result.rows {
+            let cells: Vec<String> = result
+                .variables
+                .iter()
+                .map(|col| {
+                    row.get_value(col)
+                        .map(|value| Self::escape_markdown(&Self::value_to_string(value)))
+                        .unwrap_or_default()
+                })
+                .collect();
+            output.push_str(&format!("| {} |\n", cells.join(" | ")));
+        }
+
+        output
+    }
+
+    /// Escape a Markdown table cell: backslashes and pipes, and line breaks as `<br>`
+    fn escape_markdown(text: &str) -> String {
+        text.replace('\\', "\\\\")
+            .replace('|', "\\|")
+            .replace("\r\n", "<br>")
+            .replace(['\n', '\r'], "<br>")
     }
 
     /// Format results as CSV
-    fn format_csv(result: &QueryResult) -> String {
+    fn format_csv(result: &QueryResult, header: bool) -> String {
         let mut output = String::new();
 
         // CSV header
-        output.push_str(&result.variables.join(","));
-        output.push('\n');
+        if header {
+            let names: Vec<String> = result
+                .variables
+                .iter()
+                .map(|col| Self::escape_csv(col))
+                .collect();
+            output.push_str(&names.join(","));
+            output.push('\n');
+        }
 
         // CSV rows
         for row in &result.rows {
@@ -153,15 +441,17 @@ impl ResultFormatter {
     }
 
     /// Convert a Value to a display string
-    fn value_to_string(value: &Value) -> String {
+    pub fn value_to_string(value: &Value) -> String {
         match value {
             Value::String(s) => s.clone(),
             Value::Number(n) => n.to_string(),
             Value::Boolean(b) => b.to_string(),
             Value::Null => "NULL".to_string(),
-            Value::DateTime(dt) => dt.to_string(),
-            Value::DateTimeWithFixedOffset(dt) => dt.to_string(),
-            Value::DateTimeWithNamedTz(tz, dt) => format!("{} {}", dt, tz),
+            Value::DateTime(dt) => dt.to_rfc3339_opts(SecondsFormat::AutoSi, true),
+            Value::DateTimeWithFixedOffset(dt) => dt.to_rfc3339_opts(SecondsFormat::AutoSi, true),
+            Value::DateTimeWithNamedTz(tz, dt) => {
+                format!("{} {}", dt.to_rfc3339_opts(SecondsFormat::AutoSi, true), tz)
+            }
             Value::TimeWindow(tw) => format!("TIME_WINDOW({} to {})", tw.start, tw.end),
             Value::Path(path) => format!("{:?}", path),
             Value::Array(arr) | Value::List(arr) => format!(
@@ -190,11 +480,12 @@ impl ResultFormatter {
                 format!("[{}:{}]", edge.id, edge.label)
             }
             Value::Temporal(temporal) => format!("TEMPORAL({:?})", temporal),
+            Value::Json(document) => document.to_string(),
         }
     }
 
     /// Convert a Value to a JSON value
-    fn value_to_json(value: &Value) -> serde_json::Value {
+    pub fn value_to_json(value: &Value) -> serde_json::Value {
         match value {
             Value::String(s) => serde_json::Value::String(s.clone()),
             Value::Number(n) => serde_json::json!(n),
@@ -222,7 +513,7 @@ impl ResultFormatter {
                     "type": "node",
                     "id": node.id,
                     "labels": node.labels,
-                    "properties": node.properties,
+                    "properties": Self::properties_to_json(&node.properties),
                 })
             }
             Value::Edge(edge) => {
@@ -232,22 +523,38 @@ impl ResultFormatter {
                     "label": edge.label,
                     "from": edge.from_node,
                     "to": edge.to_node,
-                    "properties": edge.properties,
+                    "properties": Self::properties_to_json(&edge.properties),
                 })
             }
             Value::Temporal(temporal) => {
                 serde_json::json!(format!("{:?}", temporal))
             }
+            Value::Json(document) => document.clone(),
         }
     }
 
+    /// Convert element properties to a JSON object of plain values
+    fn properties_to_json(
+        properties: &std::collections::HashMap<String, Value>,
+    ) -> serde_json::Value {
+        properties
+            .iter()
+            .map(|(name, value)| (name.clone(), Self::value_to_json(value)))
+            .collect::<serde_json::Map<_, _>>()
+            .into()
+    }
+
     /// Convert a Value to a CSV-safe string
     fn value_to_csv_string(value: &Value) -> String {
-        let s = Self::value_to_string(value);
-        if s.contains(',') || s.contains('"') || s.contains('\n') {
-            format!("\"{}\"", s.replace('"', "\"\""))
+        Self::escape_csv(&Self::value_to_string(value))
+    }
+
+    /// Quote a CSV field that holds a separator, quote or line break (RFC 4180)
+    fn escape_csv(field: &str) -> String {
+        if field.contains([',', '"', '\n', '\r']) {
+            format!("\"{}\"", field.replace('"', "\"\""))
         } else {
-            s
+            field.to_string()
         }
     }
 }
@@ -0,0 +1,80 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! Paging of long or wide REPL output
+//!
+//! Output that does not fit the terminal is piped through `$PAGER`
+//! (`less -SRFX` by default). Setting `PAGER` to an empty string disables paging.
+
+use std::io::{IsTerminal, Write};
+use std::process::{Command, Stdio};
+
+const DEFAULT_PAGER: &str = "less -SRFX";
+
+/// Print output, through the pager if it is taller or wider than the terminal
+pub fn print_paged(output: &str) {
+    if !needs_pager(output) || !run_pager(output) {
+        println!("{}", output);
+    }
+}
+
+fn needs_pager(output: &str) -> bool {
+    if !std::io::stdout().is_terminal() {
+        return false;
+    }
+    let Ok((columns, rows)) = crossterm::terminal::size() else {
+        return false;
+    };
+    // Leave room for the prompt below the output
+    output.lines().count() >= rows as usize
+        || output
+            .lines()
+            .any(|line| visible_width(line) > columns as usize)
+}
+
+/// Pipe output to the pager; false if no pager could be run
+fn run_pager(output: &str) -> bool {
+    let pager = std::env::var("PAGER").unwrap_or_else(|_| DEFAULT_PAGER.to_string());
+    let mut parts = pager.split_whitespace();
+    let Some(program) = parts.next() else {
+        return false;
+    };
+
+    let Ok(mut child) = Command::new(program)
+        .args(parts)
+        .stdin(Stdio::piped())
+        .spawn()
+    else {
+        return false;
+    };
+    if let Some(mut stdin) = child.stdin.take() {
+        // The user may quit the pager before reading everything
+        let _ = writeln!(stdin, "{}", output);
+    }
+    let _ = child.wait();
+    true
+}
+
+/// Width of a line on screen, ignoring ANSI color escapes
+fn visible_width(line: &str) -> usize {
+    strip_ansi(line).chars().count()
+}
+
+/// Output without ANSI color escapes, for writing to a file
+pub fn strip_ansi(output: &str) -> String {
+    let mut plain = String::with_capacity(output.len());
+    let mut chars = output.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // Skip a CSI sequence up to its final byte
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            plain.push(c);
+        }
+    }
+    plain
+}
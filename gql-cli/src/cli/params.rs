@@ -0,0 +1,105 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! Query parameters for `graphlite query`
+//!
+//! `--param name=value` binds `$name` in the query. The value is read as JSON
+//! when it parses as JSON (`42`, `true`, `null`, `[1, 2]`, `{"a": 1}`, `"42"`)
+//! and as a plain string otherwise; a value wrapped in single quotes is always a
+//! string. `--params-file` reads a JSON object of parameters, and `--param`
+//! entries override names from the file.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use super::errors::QueryFailure;
+use graphlite::Value;
+
+/// One `--param name=value` binding
+#[derive(Clone, Debug)]
+pub struct QueryParam {
+    pub name: String,
+    pub value: Value,
+}
+
+/// Parse a `--param` argument such as `id=42` or `name='Ann'`
+pub fn parse_param(s: &str) -> Result<QueryParam, String> {
+    let (name, text) = s
+        .split_once('=')
+        .ok_or_else(|| format!("Invalid parameter '{}': expected name=value", s))?;
+    let name = name.trim();
+    let name = name.strip_prefix('$').unwrap_or(name);
+    let valid_name = name
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !valid_name {
+        return Err(format!("Invalid parameter name '{}'", name));
+    }
+
+    let value = match text.strip_prefix('\'').and_then(|t| t.strip_suffix('\'')) {
+        Some(quoted) => Value::String(quoted.to_string()),
+        None => match serde_json::from_str::<serde_json::Value>(text) {
+            Ok(json) => json_to_value(&json)
+                .map_err(|e| format!("Invalid value for parameter '{}': {}", name, e))?,
+            Err(_) => Value::String(text.to_string()),
+        },
+    };
+
+    Ok(QueryParam {
+        name: name.to_string(),
+        value,
+    })
+}
+
+/// Combine the parameters of `--params-file` and `--param`, later entries winning
+pub fn collect_params(
+    file: Option<&Path>,
+    params: Vec<QueryParam>,
+) -> Result<HashMap<String, Value>, QueryFailure> {
+    let mut bound = match file {
+        Some(path) => load_params_file(path).map_err(QueryFailure::usage)?,
+        None => HashMap::new(),
+    };
+    bound.extend(params.into_iter().map(|param| (param.name, param.value)));
+    Ok(bound)
+}
+
+/// Read a JSON object of parameters
+pub fn load_params_file(path: &Path) -> Result<HashMap<String, Value>, String> {
+    let text = std::fs::read_to_string(path)
+        .map_err(|e| format!("Cannot read parameters file {:?}: {}", path, e))?;
+    let json: serde_json::Value = serde_json::from_str(&text)
+        .map_err(|e| format!("Invalid JSON in parameters file {:?}: {}", path, e))?;
+    let serde_json::Value::Object(entries) = json else {
+        return Err(format!(
+            "Parameters file {:?} must contain a JSON object",
+            path
+        ));
+    };
+    entries
+        .iter()
+        .map(|(name, value)| {
+            json_to_value(value)
+                .map(|value| (name.clone(), value))
+                .map_err(|e| format!("Parameter '{}': {}", name, e))
+        })
+        .collect()
+}
+
+/// Convert a JSON parameter value; objects are bound as JSON documents
+pub fn json_to_value(value: &serde_json::Value) -> Result<Value, &'static str> {
+    match value {
+        serde_json::Value::Null => Ok(Value::Null),
+        serde_json::Value::Bool(b) => Ok(Value::Boolean(*b)),
+        serde_json::Value::Number(n) => n.as_f64().map(Value::Number).ok_or("number out of range"),
+        serde_json::Value::String(s) => Ok(Value::String(s.clone())),
+        serde_json::Value::Array(items) => items
+            .iter()
+            .map(json_to_value)
+            .collect::<Result<_, _>>()
+            .map(Value::List),
+        serde_json::Value::Object(_) => Ok(Value::Json(value.clone())),
+    }
+}
@@ -0,0 +1,161 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! Script execution for `graphlite run`
+//!
+//! A script is a file of GQL statements separated by `;`, split the way the
+//! console splits its input, so a `;` inside a string or comment does not end
+//! a statement. The statements run in order on one session, so `SESSION SET
+//! GRAPH` carries over to the statements after it.
+//!
+//! `--var name=value` replaces every `${name}` in the script with `value`
+//! before it is split; the value is inserted as written, so it can name a
+//! graph or schema. Using a variable that has no value is an error.
+//!
+//! By default every statement runs, and the run fails at the end if any did.
+//! `--stop-on-error` stops at the first failure. `--transaction` runs the
+//! script in one transaction that is committed at the end, or rolled back at
+//! the first failure. Catalog statements such as `CREATE GRAPH` take effect
+//! at once and are not rolled back.
+
+use colored::Colorize;
+use std::collections::HashMap;
+
+use super::commands::{DisplayLimits, OutputOptions, RunArgs};
+use super::input::{OpenState, ScriptStatement};
+use super::output::ResultFormatter;
+use graphlite::QueryCoordinator;
+
+/// One `--var name=value` definition
+#[derive(Clone, Debug)]
+pub struct ScriptVar {
+    pub name: String,
+    pub value: String,
+}
+
+/// Parse a `--var` argument such as `graph=/app/main`
+pub fn parse_var(s: &str) -> Result<ScriptVar, String> {
+    let (name, value) = s
+        .split_once('=')
+        .ok_or_else(|| format!("Invalid variable '{}': expected name=value", s))?;
+    let name = name.trim();
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return Err(format!("Invalid variable name '{}'", name));
+    }
+    Ok(ScriptVar {
+        name: name.to_string(),
+        value: value.to_string(),
+    })
+}
+
+/// Replace every `${name}` in `script` with the value of `name`
+pub fn substitute_vars(script: &str, vars: &HashMap<String, String>) -> Result<String, String> {
+    let mut output = String::with_capacity(script.len());
+    let mut rest = script;
+    while let Some(start) = rest.find("${") {
+        output.push_str(&rest[..start]);
+        let line = script[..script.len() - rest.len() + start]
+            .matches('\n')
+            .count()
+            + 1;
+        let after = &rest[start + 2..];
+        let end = after
+            .find('}')
+            .ok_or_else(|| format!("Line {}: '${{' is not closed by '}}'", line))?;
+        let name = &after[..end];
+        let value = vars.get(name).ok_or_else(|| {
+            format!(
+                "Line {}: variable '{}' has no value; pass it with --var {}=...",
+                line, name, name
+            )
+        })?;
+        output.push_str(value);
+        rest = &after[end + 1..];
+    }
+    output.push_str(rest);
+    Ok(output)
+}
+
+/// Run the script of `args` on a session
+pub fn run_script(
+    coordinator: &QueryCoordinator,
+    session_id: &str,
+    args: &RunArgs,
+) -> Result<(), String> {
+    let script = std::fs::read_to_string(&args.script)
+        .map_err(|e| format!("Failed to read {}: {}", args.script.display(), e))?;
+    let vars: HashMap<String, String> = args
+        .vars
+        .iter()
+        .map(|var| (var.name.clone(), var.value.clone()))
+        .collect();
+    let statements = OpenState::split_statements(&substitute_vars(&script, &vars)?);
+    let output = OutputOptions {
+        format: args.format,
+        header: true,
+        numbers: args.numbers.number_format(),
+        limits: DisplayLimits::default(),
+    };
+
+    if args.transaction {
+        coordinator.process_query("START TRANSACTION", session_id)?;
+    }
+    let stop_on_error = args.stop_on_error || args.transaction;
+    let mut executed = 0;
+    let mut failures = 0;
+    for statement in &statements {
+        executed += 1;
+        if let Err(e) = run_statement(coordinator, session_id, statement, output, args.quiet) {
+            failures += 1;
+            eprintln!(
+                "{}",
+                format!("Error at line {}: {}", statement.line, e).red()
+            );
+            if stop_on_error {
+                break;
+            }
+        }
+    }
+
+    if args.transaction {
+        let end = if failures == 0 { "COMMIT" } else { "ROLLBACK" };
+        coordinator.process_query(end, session_id)?;
+    }
+    eprintln!(
+        "{} of {} statement(s) run, {} failed{}",
+        executed,
+        statements.len(),
+        failures,
+        if args.transaction && failures > 0 {
+            "; transaction rolled back"
+        } else {
+            ""
+        }
+    );
+
+    if failures == 0 {
+        Ok(())
+    } else {
+        Err(format!(
+            "{} statement(s) of {} failed",
+            failures,
+            args.script.display()
+        ))
+    }
+}
+
+/// Execute one statement and print its result unless `quiet`
+fn run_statement(
+    coordinator: &QueryCoordinator,
+    session_id: &str,
+    statement: &ScriptStatement,
+    output: OutputOptions,
+    quiet: bool,
+) -> Result<(), String> {
+    let result = coordinator.process_query(&statement.text, session_id)?;
+    if !quiet {
+        let formatted = ResultFormatter::format_with(&result, output);
+        println!("{}", formatted.trim_end());
+    }
+    Ok(())
+}
@@ -0,0 +1,289 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! User and role management for `graphlite users` and `graphlite roles`
+//!
+//! The subcommands run the same `CREATE USER`, `DROP USER`, `CREATE ROLE`,
+//! `DROP ROLE`, `GRANT ROLE` and `REVOKE ROLE` statements as the console, on a
+//! session of the user given with `-u`. There is no statement to change a
+//! password, so `users passwd` updates the security catalog through
+//! `QueryCoordinator::set_user_password`.
+//!
+//! Every subcommand needs a user with the `admin` role, except `users passwd`
+//! for one's own password. Names and the passwords of new users are written
+//! into the statements as string literals, so they cannot contain `'` or `\`.
+
+use colored::Colorize;
+use comfy_table::{presets::UTF8_FULL, Cell, Color, Table};
+use std::collections::BTreeMap;
+
+use super::commands::{RolesAction, RolesArgs, UsersAction, UsersArgs};
+use super::output::ResultFormatter;
+use graphlite::{QueryCoordinator, Value};
+
+/// A user as listed by `gql.list_users()`
+struct UserEntry {
+    name: String,
+    active: bool,
+    roles: Vec<String>,
+}
+
+/// Run a `graphlite users` subcommand as `caller`
+pub fn run_users(
+    coordinator: &QueryCoordinator,
+    session_id: &str,
+    caller: &str,
+    args: &UsersArgs,
+) -> Result<(), String> {
+    if !matches!(&args.action, UsersAction::Passwd { username, .. } if username == caller) {
+        require_admin(coordinator, session_id, caller)?;
+    }
+
+    match &args.action {
+        UsersAction::Add {
+            username,
+            new_password,
+            roles,
+        } => {
+            let password = new_password_or_prompt(new_password.clone(), username)?;
+            let mut statement = format!(
+                "CREATE USER {} PASSWORD {}",
+                literal("User name", username)?,
+                literal("Password", &password)?
+            );
+            if !roles.is_empty() {
+                let roles = roles
+                    .iter()
+                    .map(|role| literal("Role name", role))
+                    .collect::<Result<Vec<_>, _>>()?;
+                statement.push_str(&format!(" ROLES ({})", roles.join(", ")));
+            }
+            run_statement(coordinator, session_id, &statement)
+        }
+        UsersAction::Remove {
+            username,
+            if_exists,
+        } => run_statement(
+            coordinator,
+            session_id,
+            &format!(
+                "DROP USER {}{}",
+                if *if_exists { "IF EXISTS " } else { "" },
+                literal("User name", username)?
+            ),
+        ),
+        UsersAction::Passwd {
+            username,
+            new_password,
+        } => {
+            if !list_users(coordinator, session_id)?
+                .iter()
+                .any(|user| &user.name == username)
+            {
+                return Err(format!("User '{}' does not exist", username));
+            }
+            let password = new_password_or_prompt(new_password.clone(), username)?;
+            coordinator.set_user_password(username, &password)?;
+            println!(
+                "{}",
+                format!("Password of user '{}' changed", username).green()
+            );
+            Ok(())
+        }
+        UsersAction::List => {
+            let mut table = table(&["user", "active", "roles"]);
+            for user in list_users(coordinator, session_id)? {
+                table.add_row(vec![
+                    Cell::new(&user.name),
+                    Cell::new(if user.active { "yes" } else { "no" }),
+                    Cell::new(user.roles.join(", ")),
+                ]);
+            }
+            println!("{}", table);
+            Ok(())
+        }
+    }
+}
+
+/// Run a `graphlite roles` subcommand as `caller`
+pub fn run_roles(
+    coordinator: &QueryCoordinator,
+    session_id: &str,
+    caller: &str,
+    args: &RolesArgs,
+) -> Result<(), String> {
+    require_admin(coordinator, session_id, caller)?;
+
+    let statement = match &args.action {
+        RolesAction::Create { role } => format!("CREATE ROLE {}", literal("Role name", role)?),
+        RolesAction::Drop { role, if_exists } => format!(
+            "DROP ROLE {}{}",
+            if *if_exists { "IF EXISTS " } else { "" },
+            literal("Role name", role)?
+        ),
+        RolesAction::Grant { role, username } => format!(
+            "GRANT ROLE {} TO {}",
+            literal("Role name", role)?,
+            literal("User name", username)?
+        ),
+        RolesAction::Revoke { role, username } => format!(
+            "REVOKE ROLE {} FROM {}",
+            literal("Role name", role)?,
+            literal("User name", username)?
+        ),
+        RolesAction::List => return print_roles(coordinator, session_id),
+    };
+    run_statement(coordinator, session_id, &statement)
+}
+
+/// Fail unless `caller` has the `admin` role
+fn require_admin(
+    coordinator: &QueryCoordinator,
+    session_id: &str,
+    caller: &str,
+) -> Result<(), String> {
+    let is_admin = list_users(coordinator, session_id)?
+        .iter()
+        .any(|user| user.name == caller && user.roles.iter().any(|role| role == "admin"));
+    if is_admin {
+        Ok(())
+    } else {
+        Err(format!(
+            "User '{}' does not have the admin role; sign in as an administrator with -u",
+            caller
+        ))
+    }
+}
+
+/// `value` as a GQL string literal
+fn literal(what: &str, value: &str) -> Result<String, String> {
+    if value.is_empty() {
+        return Err(format!("{} must not be empty", what));
+    }
+    if value.contains(['\'', '\\']) {
+        return Err(format!("{} must not contain ' or \\", what));
+    }
+    Ok(format!("'{}'", value))
+}
+
+/// The password given on the command line, or one read twice from the terminal
+fn new_password_or_prompt(password: Option<String>, username: &str) -> Result<String, String> {
+    let password = match password {
+        Some(password) => password,
+        None => {
+            let read = |prompt: String| {
+                print!("{}", prompt);
+                std::io::Write::flush(&mut std::io::stdout()).map_err(|e| e.to_string())?;
+                rpassword::read_password().map_err(|e| format!("Failed to read password: {}", e))
+            };
+            let password = read(format!("New password for '{}': ", username))?;
+            if read("Repeat the password: ".to_string())? != password {
+                return Err("Passwords do not match".to_string());
+            }
+            password
+        }
+    };
+    if password.is_empty() {
+        return Err("Password must not be empty".to_string());
+    }
+    Ok(password)
+}
+
+/// Run a security statement and print the status it reports
+fn run_statement(
+    coordinator: &QueryCoordinator,
+    session_id: &str,
+    statement: &str,
+) -> Result<(), String> {
+    let result = coordinator.process_query(statement, session_id)?;
+    let status = result
+        .rows
+        .first()
+        .and_then(|row| row.get_value("status"))
+        .map(ResultFormatter::value_to_string)
+        .unwrap_or_else(|| "Done".to_string());
+    println!("{}", status.green());
+    Ok(())
+}
+
+/// The users, ordered by name
+fn list_users(coordinator: &QueryCoordinator, session_id: &str) -> Result<Vec<UserEntry>, String> {
+    let result = coordinator.process_query("CALL gql.list_users()", session_id)?;
+    let mut users: Vec<UserEntry> = result
+        .rows
+        .iter()
+        .filter_map(|row| {
+            let Some(Value::String(name)) = row.get_value("username") else {
+                return None;
+            };
+            let roles = match row.get_value("roles") {
+                Some(Value::String(roles)) => roles
+                    .split(", ")
+                    .filter(|role| !role.is_empty())
+                    .map(str::to_string)
+                    .collect(),
+                _ => Vec::new(),
+            };
+            Some(UserEntry {
+                name: name.clone(),
+                active: !matches!(row.get_value("active"), Some(Value::Boolean(false))),
+                roles,
+            })
+        })
+        .collect();
+    users.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(users)
+}
+
+fn print_roles(coordinator: &QueryCoordinator, session_id: &str) -> Result<(), String> {
+    let mut members: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for user in list_users(coordinator, session_id)? {
+        for role in user.roles {
+            members.entry(role).or_default().push(user.name.clone());
+        }
+    }
+
+    let result = coordinator.process_query("CALL gql.list_roles()", session_id)?;
+    let mut roles: Vec<(String, String)> = result
+        .rows
+        .iter()
+        .filter_map(|row| {
+            let Some(Value::String(role)) = row.get_value("role_name") else {
+                return None;
+            };
+            let description = row
+                .get_value("description")
+                .map(ResultFormatter::value_to_string)
+                .unwrap_or_default();
+            Some((role.clone(), description))
+        })
+        .collect();
+    roles.sort();
+
+    let mut table = table(&["role", "description", "users"]);
+    for (role, description) in &roles {
+        table.add_row(vec![
+            Cell::new(role),
+            Cell::new(description),
+            Cell::new(
+                members
+                    .get(role)
+                    .map(|users| users.join(", "))
+                    .unwrap_or_default(),
+            ),
+        ]);
+    }
+    println!("{}", table);
+    Ok(())
+}
+
+fn table(header: &[&str]) -> Table {
+    let mut table = Table::new();
+    table.load_preset(UTF8_FULL);
+    table.set_header(
+        header
+            .iter()
+            .map(|column| Cell::new(column).fg(Color::Green)),
+    );
+    table
+}
@@ -0,0 +1,647 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! HTTP API for `graphlite serve`
+//!
+//! Serves one database over HTTP with JSON request and response bodies. All
+//! worker threads share a single `QueryCoordinator` whose sessions live in the
+//! process-wide pool (`SessionMode::Global`), so a session opened by one
+//! request can be used by any later request.
+//!
+//! | Method and path          | Body                                   | Response |
+//! |--------------------------|----------------------------------------|----------|
+//! | `GET /health`            |                                        | `{"status": "ok", "version"}` |
+//! | `POST /sessions`         | `{"username", "password"}`             | `{"session_id"}` |
+//! | `DELETE /sessions/{id}`  |                                        | `{"status": "closed"}` |
+//! | `POST /query`            | `{"query", "session_id", "params"}`    | Result in the `graphlite query --format json` shape |
+//!
+//! `params` is an optional object binding `$name` parameters. Failures return
+//! `{"error": {"category", "message"}}` with an HTTP status derived from the
+//! error category. Unless the server was started with `--allow-anonymous`,
+//! sessions need a username and password, and queries need a session.
+//!
+//! Responses are compressed with zstd or gzip when the request's
+//! `Accept-Encoding` allows it, and carry the matching `Content-Encoding`.
+//!
+//! With `--standby` the server waits while another process has the database
+//! open and starts serving once that process exits, so a second server can be
+//! kept warm next to the primary; see `DatabaseConfig::standby_wait`.
+
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use colored::Colorize;
+use graphlite::{
+    ContentEncoding, DatabaseConfig, NumberFormat, QueryCoordinator, QueryErrorKind, SessionMode,
+};
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use super::commands::ServeArgs;
+use super::output::ResultFormatter;
+use super::params::json_to_value;
+
+/// Largest request body accepted
+const MAX_BODY_BYTES: u64 = 16 * 1024 * 1024;
+
+/// A failed request
+struct ApiError {
+    status: u16,
+    category: &'static str,
+    message: String,
+}
+
+impl ApiError {
+    fn new(status: u16, category: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            category,
+            message: message.into(),
+        }
+    }
+
+    /// Malformed request
+    fn bad_request(message: impl Into<String>) -> Self {
+        Self::new(400, "bad_request", message)
+    }
+
+    /// Failure reported by the engine, with a status matching its category
+    fn query(message: String) -> Self {
+        let kind = QueryErrorKind::from_message(&message);
+        let status = match kind {
+            QueryErrorKind::Syntax | QueryErrorKind::Validation => 400,
+            QueryErrorKind::Authentication => 401,
+            QueryErrorKind::PermissionDenied => 403,
+            QueryErrorKind::NotFound => 404,
+            QueryErrorKind::ConstraintViolation | QueryErrorKind::Transaction => 409,
+            QueryErrorKind::LimitExceeded => 429,
+            QueryErrorKind::Timeout => 504,
+            QueryErrorKind::DiskFull => 507,
+            QueryErrorKind::Storage | QueryErrorKind::Other => 500,
+        };
+        Self::new(status, kind.as_str(), message)
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "error": {
+                "category": self.category,
+                "message": self.message,
+            }
+        })
+    }
+}
+
+type ApiResult = Result<(u16, serde_json::Value), ApiError>;
+
+/// Serve the database until the process is interrupted
+pub fn run_server(args: &ServeArgs) -> Result<(), Box<dyn std::error::Error>> {
+    if !args.path.exists() {
+        return Err(format!(
+            "Database not found at {:?}. Run 'cargo run -- install' first.",
+            args.path
+        )
+        .into());
+    }
+
+    if args.standby {
+        if let Some(holder) = QueryCoordinator::lock_holder(&args.path) {
+            println!(
+                "{}",
+                format!(
+                    "{:?} is in use by process {}; waiting to take over",
+                    args.path, holder.pid
+                )
+                .yellow()
+            );
+        }
+    }
+    let coordinator = open_database(&args.path, args.standby)?;
+    let server = Server::http(args.listen)
+        .map_err(|e| format!("Failed to listen on {}: {}", args.listen, e))?;
+    let server = Arc::new(server);
+    let address = server.server_addr().to_ip().unwrap_or(args.listen);
+
+    let threads = args.threads.max(1);
+    let stopping = Arc::new(AtomicBool::new(false));
+    stop_on_interrupt(server.clone(), stopping.clone(), threads)?;
+
+    println!(
+        "{}",
+        format!("GraphLite serving {:?} on http://{}", args.path, address)
+            .bold()
+            .green()
+    );
+    println!("Press Ctrl+C to stop");
+
+    let workers: Vec<_> = (0..threads)
+        .map(|_| {
+            let server = server.clone();
+            let coordinator = coordinator.clone();
+            let stopping = stopping.clone();
+            let allow_anonymous = args.allow_anonymous;
+            let numbers = args.numbers.number_format();
+            thread::spawn(move || loop {
+                match server.recv() {
+                    Ok(request) => handle_request(&coordinator, request, allow_anonymous, numbers),
+                    Err(_) if stopping.load(Ordering::SeqCst) => break,
+                    Err(e) => log::warn!("Failed to accept HTTP request: {}", e),
+                }
+            })
+        })
+        .collect();
+    for worker in workers {
+        let _ = worker.join();
+    }
+
+    // The coordinator is dropped here, after every in-flight request has finished
+    println!("{}", "Server stopped".green());
+    Ok(())
+}
+
+/// Open the database with a process-wide session pool
+///
+/// As a standby, wait for whichever process holds the database to exit.
+fn open_database(path: &Path, standby: bool) -> Result<Arc<QueryCoordinator>, String> {
+    QueryCoordinator::from_path_with_config(
+        path,
+        DatabaseConfig {
+            session_mode: SessionMode::Global,
+            standby_wait: standby.then_some(Duration::MAX),
+            ..DatabaseConfig::default()
+        },
+    )
+}
+
+/// Wake every worker on Ctrl+C so it stops once its current request is answered
+fn stop_on_interrupt(
+    server: Arc<Server>,
+    stopping: Arc<AtomicBool>,
+    workers: usize,
+) -> std::io::Result<()> {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()?;
+    thread::spawn(move || {
+        if runtime.block_on(tokio::signal::ctrl_c()).is_ok() {
+            stopping.store(true, Ordering::SeqCst);
+            for _ in 0..workers {
+                server.unblock();
+            }
+        }
+    });
+    Ok(())
+}
+
+/// Route a request and send its response
+fn handle_request(
+    coordinator: &QueryCoordinator,
+    mut request: Request,
+    allow_anonymous: bool,
+    numbers: NumberFormat,
+) {
+    let method = request.method().clone();
+    let encoding = request
+        .headers()
+        .iter()
+        .find(|header| header.field.equiv("Accept-Encoding"))
+        .map_or(ContentEncoding::Identity, |header| {
+            ContentEncoding::negotiate(header.value.as_str())
+        });
+    let path = request
+        .url()
+        .split('?')
+        .next()
+        .unwrap_or_default()
+        .to_string();
+
+    let outcome = match (&method, path.as_str()) {
+        (Method::Get, "/health") => Ok((
+            200,
+            serde_json::json!({ "status": "ok", "version": graphlite::VERSION }),
+        )),
+        (Method::Post, "/sessions") => read_body(&mut request)
+            .and_then(|body| open_session(coordinator, &body, allow_anonymous)),
+        (Method::Delete, _) if path.starts_with("/sessions/") => {
+            close_session(coordinator, &path["/sessions/".len()..])
+        }
+        (Method::Post, "/query") => {
+            read_body(&mut request).and_then(|body| run_query(coordinator, &body, allow_anonymous))
+        }
+        (_, "/health" | "/sessions" | "/query") => Err(ApiError::new(
+            405,
+            "method_not_allowed",
+            format!("{} is not supported on {}", method, path),
+        )),
+        _ => Err(ApiError::new(
+            404,
+            "not_found",
+            format!("No endpoint at {}", path),
+        )),
+    };
+
+    let (status, body) = match outcome {
+        Ok(reply) => reply,
+        Err(error) => (error.status, error.to_json()),
+    };
+    log::debug!("{} {} -> {}", method, path, status);
+
+    let content_type = Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..])
+        .expect("static header is valid");
+    let vary =
+        Header::from_bytes(&b"Vary"[..], &b"Accept-Encoding"[..]).expect("static header is valid");
+    let serialize = || {
+        numbers
+            .to_vec(&body)
+            .unwrap_or_else(|_| body.to_string().into_bytes())
+    };
+    let (bytes, encoding) = match encoding.encode(serialize()) {
+        Ok(bytes) => (bytes, encoding),
+        Err(e) => {
+            log::warn!("Failed to compress HTTP response: {}", e);
+            (serialize(), ContentEncoding::Identity)
+        }
+    };
+    let mut response = Response::from_data(bytes)
+        .with_status_code(status)
+        .with_header(content_type)
+        .with_header(vary);
+    if encoding != ContentEncoding::Identity {
+        response.add_header(
+            Header::from_bytes(&b"Content-Encoding"[..], encoding.as_str().as_bytes())
+                .expect("encoding names are valid header values"),
+        );
+    }
+    if let Err(e) = request.respond(response) {
+        log::warn!("Failed to send HTTP response: {}", e);
+    }
+}
+
+/// Parse the request body as a JSON object
+fn read_body(
+    request: &mut Request,
+) -> Result<serde_json::Map<String, serde_json::Value>, ApiError> {
+    let mut body = String::new();
+    request
+        .as_reader()
+        .take(MAX_BODY_BYTES + 1)
+        .read_to_string(&mut body)
+        .map_err(|e| ApiError::bad_request(format!("Failed to read request body: {}", e)))?;
+    if body.len() as u64 > MAX_BODY_BYTES {
+        return Err(ApiError::new(
+            413,
+            "payload_too_large",
+            format!("Request body exceeds {} bytes", MAX_BODY_BYTES),
+        ));
+    }
+    if body.trim().is_empty() {
+        return Ok(serde_json::Map::new());
+    }
+    match serde_json::from_str(&body) {
+        Ok(serde_json::Value::Object(map)) => Ok(map),
+        Ok(_) => Err(ApiError::bad_request("Request body must be a JSON object")),
+        Err(e) => Err(ApiError::bad_request(format!("Invalid JSON body: {}", e))),
+    }
+}
+
+/// String field of a request body, `None` if absent or null
+fn string_field<'a>(
+    body: &'a serde_json::Map<String, serde_json::Value>,
+    name: &str,
+) -> Result<Option<&'a str>, ApiError> {
+    match body.get(name) {
+        None | Some(serde_json::Value::Null) => Ok(None),
+        Some(serde_json::Value::String(s)) => Ok(Some(s)),
+        Some(_) => Err(ApiError::bad_request(format!(
+            "'{}' must be a string",
+            name
+        ))),
+    }
+}
+
+/// POST /sessions
+fn open_session(
+    coordinator: &QueryCoordinator,
+    body: &serde_json::Map<String, serde_json::Value>,
+    allow_anonymous: bool,
+) -> ApiResult {
+    let username = string_field(body, "username")?;
+    let password = string_field(body, "password")?;
+    let session_id = match (username, password) {
+        (Some(username), Some(password)) => {
+            coordinator.authenticate_and_create_session(username, password)
+        }
+        (None, None) if allow_anonymous => coordinator.create_simple_session("anonymous"),
+        (None, None) => {
+            return Err(ApiError::new(
+                401,
+                "authentication",
+                "A username and password are required",
+            ))
+        }
+        _ => {
+            return Err(ApiError::bad_request(
+                "'username' and 'password' must be given together",
+            ))
+        }
+    }
+    .map_err(ApiError::query)?;
+    Ok((201, serde_json::json!({ "session_id": session_id })))
+}
+
+/// DELETE /sessions/{id}
+fn close_session(coordinator: &QueryCoordinator, session_id: &str) -> ApiResult {
+    // Closing an unknown session is not an error for the pool, so check first
+    coordinator
+        .session_handle(session_id)
+        .map_err(|e| ApiError::new(404, "not_found", e))?;
+    coordinator
+        .close_session(session_id)
+        .map_err(ApiError::query)?;
+    Ok((200, serde_json::json!({ "status": "closed" })))
+}
+
+/// POST /query
+///
+/// Without a `session_id` the query runs in a short-lived anonymous session.
+fn run_query(
+    coordinator: &QueryCoordinator,
+    body: &serde_json::Map<String, serde_json::Value>,
+    allow_anonymous: bool,
+) -> ApiResult {
+    let query =
+        string_field(body, "query")?.ok_or_else(|| ApiError::bad_request("'query' is required"))?;
+    let params = match body.get("params") {
+        None | Some(serde_json::Value::Null) => HashMap::new(),
+        Some(serde_json::Value::Object(params)) => params
+            .iter()
+            .map(|(name, value)| {
+                json_to_value(value)
+                    .map(|value| (name.clone(), value))
+                    .map_err(|e| ApiError::bad_request(format!("Parameter '{}': {}", name, e)))
+            })
+            .collect::<Result<_, _>>()?,
+        Some(_) => return Err(ApiError::bad_request("'params' must be a JSON object")),
+    };
+
+    let result = match string_field(body, "session_id")? {
+        Some(session_id) => coordinator.process_query_with_params(query, &params, session_id),
+        None if allow_anonymous => {
+            let session_id = coordinator
+                .create_simple_session("anonymous")
+                .map_err(ApiError::query)?;
+            let result = coordinator.process_query_with_params(query, &params, &session_id);
+            let _ = coordinator.close_session(&session_id);
+            result
+        }
+        None => {
+            return Err(ApiError::new(
+                401,
+                "authentication",
+                "'session_id' is required; open a session with POST /sessions",
+            ))
+        }
+    }
+    .map_err(ApiError::query)?;
+
+    Ok((200, ResultFormatter::result_to_json(&result)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use std::net::{SocketAddr, TcpStream};
+    use std::sync::OnceLock;
+
+    const PASSWORD: &str = "test_password_123";
+
+    /// The address of a server on an ephemeral port answering with `handle_request`
+    ///
+    /// The session pool is process-wide, so every test shares one server.
+    fn server() -> SocketAddr {
+        static SERVER: OnceLock<SocketAddr> = OnceLock::new();
+        *SERVER.get_or_init(|| {
+            let dir = tempfile::tempdir().unwrap().keep();
+            let coordinator = open_database(&dir.join("db"), false).unwrap();
+            coordinator.set_user_password("admin", PASSWORD).unwrap();
+            let server = Server::http("127.0.0.1:0").unwrap();
+            let address = server.server_addr().to_ip().unwrap();
+            thread::spawn(move || {
+                while let Ok(request) = server.recv() {
+                    handle_request(&coordinator, request, false, NumberFormat::default());
+                }
+            });
+            address
+        })
+    }
+
+    struct Reply {
+        status: u16,
+        headers: HashMap<String, String>,
+        body: serde_json::Value,
+    }
+
+    /// Send one request and decode the response body
+    fn send(method: &str, path: &str, headers: &[(&str, &str)], body: Option<&str>) -> Reply {
+        let mut stream = TcpStream::connect(server()).unwrap();
+        let mut request = format!(
+            "{} {} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n",
+            method, path
+        );
+        for (name, value) in headers {
+            request.push_str(&format!("{}: {}\r\n", name, value));
+        }
+        let body = body.unwrap_or_default();
+        request.push_str(&format!("Content-Length: {}\r\n\r\n{}", body.len(), body));
+        stream.write_all(request.as_bytes()).unwrap();
+
+        let mut response = Vec::new();
+        stream.read_to_end(&mut response).unwrap();
+        let split = response
+            .windows(4)
+            .position(|window| window == b"\r\n\r\n")
+            .expect("response has a header block");
+        let head = String::from_utf8(response[..split].to_vec()).unwrap();
+        let mut lines = head.lines();
+        let status = lines
+            .next()
+            .unwrap()
+            .split(' ')
+            .nth(1)
+            .unwrap()
+            .parse()
+            .unwrap();
+        let headers: HashMap<String, String> = lines
+            .filter_map(|line| line.split_once(": "))
+            .map(|(name, value)| (name.to_ascii_lowercase(), value.to_string()))
+            .collect();
+
+        let raw = &response[split + 4..];
+        let decoded = match headers.get("content-encoding").map(String::as_str) {
+            None => raw.to_vec(),
+            Some("gzip") => {
+                let mut decoded = Vec::new();
+                flate2::read::GzDecoder::new(raw)
+                    .read_to_end(&mut decoded)
+                    .unwrap();
+                decoded
+            }
+            Some("zstd") => zstd::decode_all(raw).unwrap(),
+            Some(other) => panic!("Unexpected Content-Encoding {}", other),
+        };
+        Reply {
+            status,
+            headers,
+            body: serde_json::from_slice(&decoded).unwrap(),
+        }
+    }
+
+    fn open_session() -> String {
+        let reply = send(
+            "POST",
+            "/sessions",
+            &[],
+            Some(&format!(
+                r#"{{"username": "admin", "password": "{}"}}"#,
+                PASSWORD
+            )),
+        );
+        assert_eq!(reply.status, 201);
+        reply.body["session_id"].as_str().unwrap().to_string()
+    }
+
+    fn query(session_id: &str, query: &str, headers: &[(&str, &str)]) -> Reply {
+        let body = serde_json::json!({ "query": query, "session_id": session_id });
+        send("POST", "/query", headers, Some(&body.to_string()))
+    }
+
+    /// Run `statements` in order, expecting each to succeed
+    fn run(session_id: &str, statements: &[&str]) {
+        for statement in statements {
+            assert_eq!(
+                query(session_id, statement, &[]).status,
+                200,
+                "{}",
+                statement
+            );
+        }
+    }
+
+    #[test]
+    fn test_health() {
+        let reply = send("GET", "/health", &[], None);
+        assert_eq!(reply.status, 200);
+        assert_eq!(reply.body["status"], "ok");
+        assert_eq!(reply.body["version"], graphlite::VERSION);
+        assert_eq!(reply.headers["content-type"], "application/json");
+
+        let reply = send("POST", "/health", &[], None);
+        assert_eq!(reply.status, 405);
+        assert_eq!(reply.body["error"]["category"], "method_not_allowed");
+        assert_eq!(send("GET", "/nowhere", &[], None).status, 404);
+    }
+
+    #[test]
+    fn test_sessions() {
+        let session_id = open_session();
+
+        let reply = send(
+            "POST",
+            "/sessions",
+            &[],
+            Some(r#"{"username": "admin", "password": "wrong"}"#),
+        );
+        assert_eq!(reply.status, 401);
+        let reply = send("POST", "/sessions", &[], Some("{}"));
+        assert_eq!(reply.status, 401);
+        let reply = send("POST", "/sessions", &[], Some(r#"{"username": "admin"}"#));
+        assert_eq!(reply.status, 400);
+        let reply = send("POST", "/sessions", &[], Some("not json"));
+        assert_eq!(reply.body["error"]["category"], "bad_request");
+
+        let path = format!("/sessions/{}", session_id);
+        let reply = send("DELETE", &path, &[], None);
+        assert_eq!(reply.status, 200);
+        assert_eq!(reply.body["status"], "closed");
+        assert_eq!(send("DELETE", &path, &[], None).status, 404);
+    }
+
+    #[test]
+    fn test_query() {
+        let session_id = open_session();
+        run(
+            &session_id,
+            &[
+                "CREATE SCHEMA IF NOT EXISTS /serve_test",
+                "CREATE GRAPH IF NOT EXISTS /serve_test/query",
+                "SESSION SET GRAPH /serve_test/query",
+                "INSERT (:Person {name: 'Alice', age: 30})",
+            ],
+        );
+
+        let body = serde_json::json!({
+            "query": "MATCH (p:Person) WHERE p.name = $name RETURN p.name AS name, p.age AS age",
+            "session_id": session_id,
+            "params": { "name": "Alice" },
+        });
+        let reply = send("POST", "/query", &[], Some(&body.to_string()));
+        assert_eq!(reply.status, 200);
+        assert_eq!(reply.body["rows"][0]["name"], "Alice");
+        assert_eq!(reply.body["rows"][0]["age"], 30);
+
+        let reply = query(&session_id, "MATCH (p:Person RETURN p", &[]);
+        assert_eq!(reply.status, 400);
+        let reply = send("POST", "/query", &[], Some(r#"{"query": "RETURN 1"}"#));
+        assert_eq!(reply.status, 401);
+    }
+
+    #[test]
+    fn test_responses_use_the_accepted_encoding() {
+        let session_id = open_session();
+        run(
+            &session_id,
+            &[
+                "CREATE SCHEMA IF NOT EXISTS /serve_test",
+                "CREATE GRAPH IF NOT EXISTS /serve_test/encoding",
+                "SESSION SET GRAPH /serve_test/encoding",
+                "INSERT (:Note {text: 'first note'}), (:Note {text: 'second note'}), \
+                 (:Note {text: 'third note'})",
+            ],
+        );
+        let statement = "MATCH (n:Note) RETURN n.text AS text";
+
+        let plain = query(&session_id, statement, &[]);
+        assert_eq!(plain.status, 200);
+        assert_eq!(plain.body["rows"].as_array().unwrap().len(), 3);
+        assert_eq!(plain.headers.get("content-encoding"), None);
+        assert_eq!(plain.headers["vary"], "Accept-Encoding");
+
+        for (accept, expected) in [
+            ("gzip", Some("gzip")),
+            ("gzip, deflate, br, zstd", Some("zstd")),
+            ("zstd;q=0.1, gzip", Some("gzip")),
+            ("br", None),
+        ] {
+            let reply = query(&session_id, statement, &[("Accept-Encoding", accept)]);
+            assert_eq!(reply.status, 200);
+            assert_eq!(
+                reply.headers.get("content-encoding").map(String::as_str),
+                expected,
+                "{}",
+                accept
+            );
+            assert_eq!(reply.body, plain.body, "{}", accept);
+        }
+
+        // Errors are compressed too
+        let reply = send("GET", "/nowhere", &[("Accept-Encoding", "gzip")], None);
+        assert_eq!(reply.status, 404);
+        assert_eq!(reply.headers["content-encoding"], "gzip");
+        assert_eq!(reply.body["error"]["category"], "not_found");
+    }
+}
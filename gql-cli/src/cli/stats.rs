@@ -0,0 +1,218 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! Database statistics for `graphlite stats`
+//!
+//! Prints what `QueryCoordinator::stats` reports: a summary line per graph,
+//! then each graph's label, relationship type, property key and index
+//! counts, the size of the database on disk and the cache usage. Cache hit
+//! rates count lookups since the database was opened, so in a fresh `stats`
+//! process they are only meaningful for caches warmed at startup.
+
+use colored::Colorize;
+use comfy_table::{presets::UTF8_FULL, Cell, CellAlignment, Color, Table};
+
+use super::commands::{StatsArgs, StatsFormat};
+use graphlite::{DatabaseStats, GraphStatistics, QueryCoordinator};
+
+/// Collect the database statistics and print them
+pub fn run_stats(coordinator: &QueryCoordinator, args: &StatsArgs) -> Result<(), String> {
+    let mut stats = coordinator.stats()?;
+    if let Some(graph) = &args.graph {
+        stats.graphs.retain(|stats| &stats.path == graph);
+        if stats.graphs.is_empty() {
+            return Err(format!("Graph does not exist: {}", graph));
+        }
+    }
+
+    match args.format {
+        StatsFormat::Table => print_tables(&stats),
+        StatsFormat::Json => println!("{:#}", stats_json(&stats)),
+    }
+    Ok(())
+}
+
+fn table(header: &[&str]) -> Table {
+    let mut table = Table::new();
+    table.load_preset(UTF8_FULL);
+    table.set_header(
+        header
+            .iter()
+            .map(|column| Cell::new(column).fg(Color::Green)),
+    );
+    table
+}
+
+fn count(n: impl ToString) -> Cell {
+    Cell::new(n).set_alignment(CellAlignment::Right)
+}
+
+/// Bytes as a short figure, e.g. `12.5 MiB`
+fn bytes(n: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut value = n as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", n)
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+fn print_tables(stats: &DatabaseStats) {
+    let mut graphs = table(&[
+        "graph",
+        "nodes",
+        "edges",
+        "labels",
+        "relationship types",
+        "indexes",
+    ]);
+    for graph in &stats.graphs {
+        graphs.add_row(vec![
+            Cell::new(&graph.path),
+            count(graph.nodes),
+            count(graph.edges),
+            count(graph.labels.len()),
+            count(graph.relationship_types.len()),
+            count(graph.indexes.len()),
+        ]);
+    }
+    println!("{}", graphs);
+
+    for graph in &stats.graphs {
+        print_graph(graph);
+    }
+
+    let mut caches = table(&["cache", "entries", "hit rate", "memory"]);
+    for cache in &stats.caches {
+        caches.add_row(vec![
+            Cell::new(cache.cache),
+            count(cache.entries),
+            count(
+                cache
+                    .hit_rate
+                    .map_or("-".to_string(), |rate| format!("{:.1}%", rate * 100.0)),
+            ),
+            count(bytes(cache.memory_bytes)),
+        ]);
+    }
+    println!("\n{}\n{}", "Caches".bold(), caches);
+
+    println!(
+        "\n{} graph(s), {} node(s), {} edge(s), {} on disk",
+        stats.graphs.len(),
+        stats.node_count(),
+        stats.edge_count(),
+        stats.disk_bytes.map_or("unknown size".to_string(), bytes)
+    );
+}
+
+fn print_graph(graph: &GraphStatistics) {
+    println!("\n{}", graph.path.bold());
+    if graph.nodes == 0 && graph.edges == 0 && graph.indexes.is_empty() {
+        println!("{}", "(empty)".dimmed());
+        return;
+    }
+
+    if !graph.labels.is_empty() {
+        let mut labels = table(&["label", "nodes"]);
+        for (label, nodes) in &graph.labels {
+            labels.add_row(vec![Cell::new(label), count(nodes)]);
+        }
+        println!("{}", labels);
+    }
+    if !graph.relationship_types.is_empty() {
+        let mut types = table(&["relationship type", "edges"]);
+        for (relationship_type, edges) in &graph.relationship_types {
+            types.add_row(vec![Cell::new(relationship_type), count(edges)]);
+        }
+        println!("{}", types);
+    }
+    if !graph.property_keys.is_empty() {
+        let mut keys = table(&["property key", "nodes", "edges"]);
+        for (key, nodes, edges) in &graph.property_keys {
+            keys.add_row(vec![Cell::new(key), count(nodes), count(edges)]);
+        }
+        println!("{}", keys);
+    }
+    if !graph.indexes.is_empty() {
+        let mut indexes = table(&["index", "kind", "on", "entries"]);
+        for index in &graph.indexes {
+            indexes.add_row(vec![
+                Cell::new(&index.name),
+                Cell::new(index.kind),
+                Cell::new(&index.target),
+                count(index.entries),
+            ]);
+        }
+        println!("{}", indexes);
+    }
+}
+
+/// The statistics as one JSON document
+fn stats_json(stats: &DatabaseStats) -> serde_json::Value {
+    serde_json::json!({
+        "disk_bytes": stats.disk_bytes,
+        "nodes": stats.node_count(),
+        "edges": stats.edge_count(),
+        "graphs": stats
+            .graphs
+            .iter()
+            .map(|graph| {
+                serde_json::json!({
+                    "path": graph.path,
+                    "nodes": graph.nodes,
+                    "edges": graph.edges,
+                    "labels": graph
+                        .labels
+                        .iter()
+                        .map(|(label, nodes)| serde_json::json!({"label": label, "nodes": nodes}))
+                        .collect::<Vec<_>>(),
+                    "relationship_types": graph
+                        .relationship_types
+                        .iter()
+                        .map(|(relationship_type, edges)| {
+                            serde_json::json!({"relationship_type": relationship_type, "edges": edges})
+                        })
+                        .collect::<Vec<_>>(),
+                    "property_keys": graph
+                        .property_keys
+                        .iter()
+                        .map(|(key, nodes, edges)| {
+                            serde_json::json!({"property_key": key, "nodes": nodes, "edges": edges})
+                        })
+                        .collect::<Vec<_>>(),
+                    "indexes": graph
+                        .indexes
+                        .iter()
+                        .map(|index| {
+                            serde_json::json!({
+                                "name": index.name,
+                                "kind": index.kind,
+                                "on": index.target,
+                                "entries": index.entries,
+                            })
+                        })
+                        .collect::<Vec<_>>(),
+                })
+            })
+            .collect::<Vec<_>>(),
+        "caches": stats
+            .caches
+            .iter()
+            .map(|cache| {
+                serde_json::json!({
+                    "cache": cache.cache,
+                    "entries": cache.entries,
+                    "hit_rate": cache.hit_rate,
+                    "memory_bytes": cache.memory_bytes,
+                })
+            })
+            .collect::<Vec<_>>(),
+    })
+}
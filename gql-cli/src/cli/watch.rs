@@ -0,0 +1,379 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! Watch mode for `graphlite query --watch`
+//!
+//! Re-runs a query on a fixed interval, redrawing the result each time and
+//! highlighting rows that were not in the previous result. With `--until` the
+//! loop stops as soon as the result satisfies a simple condition such as
+//! `row_count > 0`. `--no-highlight` turns the highlighting off for a plain
+//! redraw, e.g. when the output feeds a dashboard.
+
+use colored::Colorize;
+use crossterm::{cursor::MoveTo, execute, terminal::Clear, terminal::ClearType};
+use std::collections::HashMap;
+use std::io::{IsTerminal, Write};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::commands::{OutputFormat, OutputOptions};
+use super::errors::QueryFailure;
+use super::output::ResultFormatter;
+use graphlite::{QueryCoordinator, QueryResult, Value};
+
+/// Parse a watch interval such as `5s`, `500ms`, `2m` or `1h` (bare numbers are seconds)
+pub fn parse_interval(s: &str) -> Result<Duration, String> {
+    let s = s.trim();
+    let split = s
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(s.len());
+    let (number, unit) = s.split_at(split);
+    let number: f64 = number
+        .parse()
+        .map_err(|_| format!("Invalid interval '{}': expected e.g. 5s, 500ms or 2m", s))?;
+    let seconds = match unit.trim() {
+        "ms" => number / 1000.0,
+        "" | "s" => number,
+        "m" => number * 60.0,
+        "h" => number * 3600.0,
+        other => {
+            return Err(format!(
+                "Unknown interval unit '{}': use ms, s, m or h",
+                other
+            ))
+        }
+    };
+    if seconds <= 0.0 {
+        return Err("Interval must be greater than zero".to_string());
+    }
+    Duration::try_from_secs_f64(seconds).map_err(|_| format!("Interval too large: '{}'", s))
+}
+
+/// Comparison operator in an `--until` condition
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Comparison {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl Comparison {
+    fn holds(self, left: f64, right: f64) -> bool {
+        match self {
+            Comparison::Eq => left == right,
+            Comparison::Ne => left != right,
+            Comparison::Lt => left < right,
+            Comparison::Le => left <= right,
+            Comparison::Gt => left > right,
+            Comparison::Ge => left >= right,
+        }
+    }
+}
+
+/// Stop condition for watch mode, e.g. `row_count > 0` or `pending = 0`
+///
+/// The left side is `row_count` or a column name; a column is read from the
+/// first row of the result and must be numeric for the condition to hold.
+#[derive(Clone, Debug)]
+pub struct UntilCondition {
+    metric: String,
+    comparison: Comparison,
+    value: f64,
+}
+
+impl UntilCondition {
+    /// Whether the condition holds for a result
+    pub fn is_met(&self, result: &QueryResult) -> bool {
+        let actual = if self.metric == "row_count" {
+            Some(result.rows.len() as f64)
+        } else {
+            result
+                .rows
+                .first()
+                .and_then(|row| row.get_value(&self.metric))
+                .and_then(|value| match value {
+                    Value::Number(n) => Some(*n),
+                    _ => None,
+                })
+        };
+        actual.is_some_and(|actual| self.comparison.holds(actual, self.value))
+    }
+}
+
+impl FromStr for UntilCondition {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Two-character operators first so `>=` is not read as `>`
+        const OPERATORS: [(&str, Comparison); 7] = [
+            (">=", Comparison::Ge),
+            ("<=", Comparison::Le),
+            ("!=", Comparison::Ne),
+            ("==", Comparison::Eq),
+            (">", Comparison::Gt),
+            ("<", Comparison::Lt),
+            ("=", Comparison::Eq),
+        ];
+
+        let (position, symbol, comparison) = OPERATORS
+            .iter()
+            .filter_map(|(symbol, comparison)| {
+                s.find(symbol)
+                    .map(|position| (position, *symbol, *comparison))
+            })
+            .min_by_key(|(position, symbol, _)| (*position, std::cmp::Reverse(symbol.len())))
+            .ok_or_else(|| {
+                format!(
+                    "Invalid condition '{}': expected e.g. 'row_count > 0' or 'total >= 100'",
+                    s
+                )
+            })?;
+
+        let metric = s[..position].trim();
+        let value = s[position + symbol.len()..].trim();
+        if metric.is_empty() {
+            return Err(format!("Invalid condition '{}': missing column name", s));
+        }
+        let value = value
+            .parse()
+            .map_err(|_| format!("Invalid condition '{}': '{}' is not a number", s, value))?;
+
+        Ok(Self {
+            metric: metric.to_string(),
+            comparison,
+            value,
+        })
+    }
+}
+
+/// Settings of `graphlite query --watch`
+#[derive(Clone, Debug)]
+pub struct WatchOptions {
+    /// Time between runs
+    pub interval: Duration,
+    /// Stop once the result satisfies this condition
+    pub until: Option<UntilCondition>,
+    /// Highlight rows that were not in the previous run
+    pub highlight: bool,
+}
+
+/// Re-run `query` every `watch.interval` until interrupted or `watch.until` holds
+pub fn run_watch(
+    coordinator: &Arc<QueryCoordinator>,
+    session_id: &str,
+    query: &str,
+    params: &HashMap<String, Value>,
+    output: OutputOptions,
+    watch: &WatchOptions,
+) -> Result<(), QueryFailure> {
+    let mut stdout = std::io::stdout();
+    let interactive = stdout.is_terminal();
+    let mut previous: Option<Vec<Vec<String>>> = None;
+
+    loop {
+        let outcome = if params.is_empty() {
+            coordinator.process_query(query, session_id)
+        } else {
+            coordinator.process_query_with_params(query, params, session_id)
+        };
+
+        // Only redraw in place on a terminal; piped output keeps every run
+        if interactive {
+            execute!(stdout, Clear(ClearType::All), MoveTo(0, 0))?;
+        }
+        println!(
+            "{}",
+            format!(
+                "Every {:?}: {}    {}",
+                watch.interval,
+                query,
+                chrono::Local::now().format("%Y-%m-%d %H:%M:%S")
+            )
+            .dimmed()
+        );
+
+        match &outcome {
+            Ok(result) => {
+                let rows = ResultFormatter::row_strings(result);
+                let changed = if watch.highlight {
+                    changed_rows(previous.as_deref(), &rows)
+                } else {
+                    Vec::new()
+                };
+                let formatted = match output.format {
+                    OutputFormat::Table => ResultFormatter::format_table_highlighted(
+                        result,
+                        &changed,
+                        output.header,
+                        output.limits,
+                    ),
+                    _ => ResultFormatter::format_with(result, output),
+                };
+                if watch.highlight && previous.is_some() {
+                    println!(
+                        "{}\n",
+                        format!(
+                            "{} new or changed row(s) since last run",
+                            changed.iter().filter(|changed| **changed).count()
+                        )
+                        .cyan()
+                    );
+                }
+                println!("{}", formatted);
+                previous = Some(rows);
+            }
+            Err(e) => eprintln!("{}", format!("Error: {}", e).red()),
+        }
+        stdout.flush()?;
+
+        if let (Some(condition), Ok(result)) = (&watch.until, &outcome) {
+            if condition.is_met(result) {
+                println!("{}", "Watch condition met".green());
+                return Ok(());
+            }
+        }
+
+        std::thread::sleep(watch.interval);
+    }
+}
+
+/// Mark rows that did not appear in the previous run
+///
+/// Rows are compared by their displayed values; duplicates are matched one for
+/// one, so a second copy of an existing row counts as new. Nothing is marked on
+/// the first run.
+fn changed_rows(previous: Option<&[Vec<String>]>, current: &[Vec<String>]) -> Vec<bool> {
+    let Some(previous) = previous else {
+        return vec![false; current.len()];
+    };
+
+    let mut remaining: HashMap<&[String], usize> = HashMap::new();
+    for row in previous {
+        *remaining.entry(row.as_slice()).or_default() += 1;
+    }
+    current
+        .iter()
+        .map(|row| match remaining.get_mut(row.as_slice()) {
+            Some(count) if *count > 0 => {
+                *count -= 1;
+                false
+            }
+            _ => true,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use graphlite::Row;
+
+    #[test]
+    fn test_parse_interval_units() {
+        assert_eq!(parse_interval("500ms"), Ok(Duration::from_millis(500)));
+        assert_eq!(parse_interval("5s"), Ok(Duration::from_secs(5)));
+        assert_eq!(parse_interval("2m"), Ok(Duration::from_secs(120)));
+        assert_eq!(parse_interval("1h"), Ok(Duration::from_secs(3600)));
+        assert_eq!(parse_interval(" 1.5 s "), Ok(Duration::from_millis(1500)));
+        assert_eq!(parse_interval("3"), Ok(Duration::from_secs(3)));
+        assert_eq!(parse_interval("0.25"), Ok(Duration::from_millis(250)));
+    }
+
+    #[test]
+    fn test_parse_interval_rejects_bad_values() {
+        for zero in ["0", "0s", "0.0ms"] {
+            assert_eq!(
+                parse_interval(zero),
+                Err("Interval must be greater than zero".to_string())
+            );
+        }
+        for invalid in ["", "-5s", "s", "1.2.3s", "five"] {
+            assert!(parse_interval(invalid)
+                .unwrap_err()
+                .starts_with("Invalid interval"));
+        }
+        assert!(parse_interval("5d")
+            .unwrap_err()
+            .starts_with("Unknown interval unit 'd'"));
+        let huge = format!("{}h", "9".repeat(30));
+        assert!(parse_interval(&huge)
+            .unwrap_err()
+            .starts_with("Interval too large"));
+    }
+
+    fn result(rows: &[f64]) -> QueryResult {
+        let mut result = QueryResult::new();
+        result.rows = rows
+            .iter()
+            .map(|total| {
+                Row::from_values(HashMap::from([(
+                    "total".to_string(),
+                    Value::Number(*total),
+                )]))
+            })
+            .collect();
+        result
+    }
+
+    #[test]
+    fn test_until_condition_operators() {
+        let cases = [
+            ("row_count > 1", Comparison::Gt),
+            ("row_count >= 1", Comparison::Ge),
+            ("row_count < 1", Comparison::Lt),
+            ("row_count <= 1", Comparison::Le),
+            ("row_count = 1", Comparison::Eq),
+            ("row_count == 1", Comparison::Eq),
+            ("row_count != 1", Comparison::Ne),
+        ];
+        for (text, comparison) in cases {
+            let condition: UntilCondition = text.parse().unwrap();
+            assert_eq!(condition.metric, "row_count", "{}", text);
+            assert_eq!(condition.comparison, comparison, "{}", text);
+            assert_eq!(condition.value, 1.0, "{}", text);
+        }
+
+        let condition: UntilCondition = "total>=100".parse().unwrap();
+        assert_eq!(condition.metric, "total");
+        assert_eq!(condition.comparison, Comparison::Ge);
+        assert_eq!(condition.value, 100.0);
+    }
+
+    #[test]
+    fn test_until_condition_rejects_bad_conditions() {
+        for invalid in ["row_count", "row_count ~ 1", "row_count => 1", ""] {
+            assert!(
+                invalid.parse::<UntilCondition>().is_err(),
+                "'{}' should be rejected",
+                invalid
+            );
+        }
+        assert!("> 0"
+            .parse::<UntilCondition>()
+            .unwrap_err()
+            .contains("missing column name"));
+        assert!("row_count > many"
+            .parse::<UntilCondition>()
+            .unwrap_err()
+            .contains("'many' is not a number"));
+    }
+
+    #[test]
+    fn test_until_condition_is_met() {
+        let rows = "row_count > 1".parse::<UntilCondition>().unwrap();
+        assert!(!rows.is_met(&result(&[5.0])));
+        assert!(rows.is_met(&result(&[5.0, 6.0])));
+
+        // A column is read from the first row
+        let total = "total = 5".parse::<UntilCondition>().unwrap();
+        assert!(total.is_met(&result(&[5.0, 6.0])));
+        assert!(!total.is_met(&result(&[6.0, 5.0])));
+        assert!(!total.is_met(&result(&[])));
+        let missing = "other = 5".parse::<UntilCondition>().unwrap();
+        assert!(!missing.is_met(&result(&[5.0])));
+    }
+}
@@ -7,7 +7,9 @@ use clap::Parser;
 use colored::Colorize;
 
 mod cli;
-use cli::{Cli, Commands};
+use cli::params::collect_params;
+use cli::watch::WatchOptions;
+use cli::{Cli, Commands, OutputOptions, QueryRunOptions};
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Parse command line arguments first to get log level
@@ -46,15 +48,103 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             yes,
         } => cli::handle_install(path, admin_user, admin_password, force, yes),
 
-        Commands::Gql { path, sample } => cli::handle_gql(path, cli.user, cli.password, sample),
+        Commands::Gql {
+            path,
+            sample,
+            limits,
+        } => cli::handle_gql(path, cli.user, cli.password, limits, sample),
 
         Commands::Query {
             query,
+            params,
+            params_file,
             path,
             format,
+            no_header,
+            numbers,
+            limits,
+            quiet,
+            fail_fast,
             explain,
             ast,
-        } => cli::handle_query(path, query, cli.user, cli.password, format, explain, ast),
+            watch,
+            until,
+            no_highlight,
+            error_format,
+        } => {
+            let output = OutputOptions {
+                format,
+                header: !no_header,
+                numbers: numbers.number_format(),
+                limits,
+            };
+            let outcome =
+                collect_params(params_file.as_deref(), params).and_then(|params| match watch {
+                    Some(interval) => cli::handle_watch(
+                        path,
+                        query,
+                        params,
+                        cli.user,
+                        cli.password,
+                        output,
+                        WatchOptions {
+                            interval,
+                            until,
+                            highlight: !no_highlight,
+                        },
+                    ),
+                    None => cli::handle_query(
+                        path,
+                        query,
+                        params,
+                        cli.user,
+                        cli.password,
+                        output,
+                        QueryRunOptions {
+                            quiet,
+                            fail_fast,
+                            error_format,
+                        },
+                        explain,
+                        ast,
+                    ),
+                });
+            // Report here rather than returning the error, so the exit code reflects its category
+            if let Err(failure) = outcome {
+                failure.report(error_format);
+                std::process::exit(failure.category.exit_code());
+            }
+            Ok(())
+        }
+
+        Commands::Run(args) => cli::handle_run(args, cli.user, cli.password),
+
+        Commands::Migrate(args) => cli::handle_migrate(args, cli.user, cli.password),
+
+        Commands::Users(args) => cli::handle_users(args, cli.user, cli.password),
+
+        Commands::Roles(args) => cli::handle_roles(args, cli.user, cli.password),
+
+        Commands::Import(args) => cli::handle_import(args, cli.user, cli.password),
+
+        Commands::Export(args) => cli::handle_export(args, cli.user, cli.password),
+
+        Commands::Serve(args) => cli::handle_serve(args),
+
+        Commands::Bench(args) => cli::handle_bench(args),
+        Commands::Conformance(args) => cli::handle_conformance(args),
+
+        Commands::Check { path, deep } => cli::handle_check(path, deep),
+
+        Commands::Verify { path, repair } => cli::handle_verify(path, repair),
+
+        Commands::Stats(args) => cli::handle_stats(args),
+
+        Commands::Waldump {
+            path,
+            from_lsn,
+            format,
+        } => cli::handle_waldump(path, from_lsn, format),
 
         Commands::Session { action: _, path: _ } => {
             println!("{}", "Session management not yet implemented".yellow());
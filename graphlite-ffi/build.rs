@@ -7,6 +7,9 @@ fn main() {
         .with_crate(crate_dir)
         .with_language(cbindgen::Language::C)
         .with_documentation(true)
+        .with_autogen_warning(
+            "/* Generated by graphlite-ffi/build.rs with cbindgen. Do not edit by hand. */",
+        )
         .with_include_guard("GRAPHLITE_H")
         .with_no_includes()
         .with_pragma_once(true)
@@ -14,5 +17,6 @@ fn main() {
         .expect("Unable to generate C bindings")
         .write_to_file("graphlite.h");
 
-    println!("cargo:rerun-if-changed=src/lib.rs");
+    println!("cargo:rerun-if-changed=src");
+    println!("cargo:rerun-if-changed=build.rs");
 }
@@ -3,8 +3,39 @@
 
 #pragma once
 
+/* Generated by graphlite-ffi/build.rs with cbindgen. Do not edit by hand. */
+
+/**
+ * Version of the C ABI implemented by this library
+ *
+ * Incremented when an existing function, struct or enum value changes
+ * incompatibly. New functions, error codes and features do not change it.
+ */
+#define GRAPHLITE_ABI_VERSION 1
+
+/**
+ * Compression for `graphlite_query_compressed`
+ */
+typedef enum GraphLiteCompression {
+  /**
+   * Plain JSON bytes, without a terminating NUL
+   */
+  Uncompressed = 0,
+  /**
+   * gzip (RFC 1952), as sent with `Content-Encoding: gzip`
+   */
+  Gzip = 1,
+  /**
+   * Zstandard frame, as sent with `Content-Encoding: zstd`
+   */
+  Zstd = 2,
+} GraphLiteCompression;
+
 /**
  * Error codes returned by FFI functions
+ *
+ * Failed statements report the most specific code that applies (`ParseError`,
+ * `GraphNotFound`, ...); `QueryError` covers every other query failure.
  */
 typedef enum GraphLiteErrorCode {
   /**
@@ -16,7 +47,7 @@ typedef enum GraphLiteErrorCode {
    */
   NullPointer = 1,
   /**
-   * Invalid UTF-8 string
+   * Invalid UTF-8 string, or UTF-16 string with unpaired surrogates
    */
   InvalidUtf8 = 2,
   /**
@@ -39,10 +70,170 @@ typedef enum GraphLiteErrorCode {
    * JSON serialization failed
    */
   JsonError = 7,
+  /**
+   * Statement was not executed because an earlier statement in its batch failed
+   */
+  NotExecuted = 8,
+  /**
+   * Binary (MessagePack) serialization failed
+   */
+  SerializationError = 9,
+  /**
+   * Backup or restore failed
+   */
+  BackupError = 10,
+  /**
+   * The statement could not be parsed
+   */
+  ParseError = 11,
+  /**
+   * The statement conflicts with existing data, e.g. a graph or schema that already exists
+   */
+  ConstraintViolation = 12,
+  /**
+   * The session may not perform the operation, or the database is read-only
+   */
+  PermissionDenied = 13,
+  /**
+   * Transaction state prevents the operation, e.g. COMMIT without an open transaction
+   */
+  TransactionConflict = 14,
+  /**
+   * The graph, or another catalog object such as a schema, does not exist
+   */
+  GraphNotFound = 15,
+  /**
+   * The query ran longer than the handle's query timeout
+   */
+  Timeout = 16,
+  /**
+   * A row value has another type than the accessor reads
+   */
+  TypeMismatch = 17,
+  /**
+   * A row, column or label index is out of range
+   */
+  OutOfRange = 18,
+  /**
+   * The database volume is low on space, so writes are rejected; reads still work
+   */
+  DiskFull = 19,
 } GraphLiteErrorCode;
 
+/**
+ * Log level selection for `GraphLiteConfig`
+ */
+typedef enum GraphLiteLogLevel {
+  /**
+   * Leave the process-wide logger as it is (default)
+   */
+  Unchanged = 0,
+  Off = 1,
+  Error = 2,
+  Warn = 3,
+  Info = 4,
+  Debug = 5,
+  Trace = 6,
+} GraphLiteLogLevel;
+
+/**
+ * Notation of numbers in result JSON, for `GraphLiteNumberFormat`
+ */
+typedef enum GraphLiteNotation {
+  /**
+   * Shortest form, with an exponent only for very large and small magnitudes
+   * (fixed-point when `decimals` is set)
+   */
+  Auto = 0,
+  /**
+   * Never an exponent
+   */
+  Plain = 1,
+  /**
+   * Always an exponent
+   */
+  Scientific = 2,
+} GraphLiteNotation;
+
+/**
+ * Storage backend selection for `GraphLiteConfig`
+ */
+typedef enum GraphLiteStorageBackend {
+  /**
+   * Sled embedded database on disk (default)
+   */
+  Sled = 0,
+  /**
+   * In-memory storage; nothing is persisted after close
+   */
+  Memory = 1,
+} GraphLiteStorageBackend;
+
+/**
+ * Type of a value read with the `graphlite_row_get_*` functions
+ */
+typedef enum GraphLiteValueType {
+  /**
+   * NULL, or a column the row has no value for
+   */
+  Null = 0,
+  /**
+   * Read with `graphlite_row_get_bool`
+   */
+  Boolean = 1,
+  /**
+   * Read with `graphlite_row_get_double`, or `graphlite_row_get_int` if integral
+   */
+  Number = 2,
+  /**
+   * Read with `graphlite_row_get_string`
+   */
+  String = 3,
+  /**
+   * Read with `graphlite_row_get_node_id` and `graphlite_row_get_label`
+   */
+  Node = 4,
+  /**
+   * Read with the `graphlite_row_get_edge_*` functions and `graphlite_row_get_label`
+   */
+  Edge = 5,
+  /**
+   * Dates, lists, vectors, paths and other values; `graphlite_row_get_string`
+   * returns their text form
+   */
+  Other = 6,
+} GraphLiteValueType;
+
 typedef struct Arc_QueryCoordinator Arc_QueryCoordinator;
 
+/**
+ * Opaque handle to a query result read row by row
+ *
+ * Returned by `graphlite_query_result` and `graphlite_session_query_result`;
+ * must be freed with `graphlite_result_free`.
+ */
+typedef struct GraphLiteResult GraphLiteResult;
+
+/**
+ * Opaque handle to one row of a `GraphLiteResult`
+ *
+ * Rows belong to their result and stay valid until `graphlite_result_free`.
+ */
+typedef struct GraphLiteRow GraphLiteRow;
+
+/**
+ * Opaque handle to a session
+ *
+ * Created with `graphlite_session_open` or `graphlite_session_from_id` and freed
+ * with `graphlite_session_close`. The session is resolved once when the handle is
+ * created, so queries run through `graphlite_session_query` skip the session ID
+ * lookup and UTF-8 validation that `graphlite_query` does on every call. The
+ * session ID and current schema/graph are kept as C strings owned by the handle.
+ */
+typedef struct GraphLiteSession GraphLiteSession;
+
+typedef struct Option_Mutex Option_Mutex;
+
 /**
  * Opaque handle to a GraphLite database instance
  *
@@ -50,8 +241,164 @@ typedef struct Arc_QueryCoordinator Arc_QueryCoordinator;
  */
 typedef struct GraphLiteDB {
   struct Arc_QueryCoordinator coordinator;
+  /**
+   * Serializes calls on handles opened with `graphlite_open_shared`
+   */
+  struct Option_Mutex shared_lock;
+  /**
+   * How result JSON writes numbers, set with `graphlite_set_number_format`
+   */
+  Mutex<NumberFormat> number_format;
 } GraphLiteDB;
 
+/**
+ * Engine configuration for `graphlite_open_with_config`
+ *
+ * Obtain a populated instance with `graphlite_config_default` and override
+ * the fields you need; zero-valued sizes mean "use the engine default".
+ */
+typedef struct GraphLiteConfig {
+  /**
+   * Cache memory budget in bytes (0 = engine default)
+   */
+  uint64_t cache_size_bytes;
+  /**
+   * Reject statements that modify data or the catalog
+   */
+  bool read_only;
+  /**
+   * Persistent storage backend
+   */
+  enum GraphLiteStorageBackend storage_backend;
+  /**
+   * Upper bound on engine-managed memory in bytes (0 = unlimited)
+   */
+  uint64_t max_memory_bytes;
+  /**
+   * Maximum log level
+   */
+  enum GraphLiteLogLevel log_level;
+} GraphLiteConfig;
+
+/**
+ * Host function receiving log records
+ */
+typedef void (*GraphLiteLogCallback)(enum GraphLiteLogLevel level,
+                                     const char *target,
+                                     const char *message,
+                                     void *user_data);
+
+/**
+ * How the JSON returned by the query functions writes numbers
+ *
+ * Obtain the defaults with `graphlite_number_format_default` and apply the
+ * format to a handle with `graphlite_set_number_format`.
+ */
+typedef struct GraphLiteNumberFormat {
+  /**
+   * Write whole numbers as integers (`493603` rather than `493603.0`)
+   */
+  bool integers;
+  /**
+   * Decimal places of numbers that are not whole (negative = as many as needed)
+   */
+  int32_t decimals;
+  /**
+   * Notation of numbers that are not whole
+   */
+  enum GraphLiteNotation notation;
+} GraphLiteNumberFormat;
+
+/**
+ * CSV parsing options for `graphlite_import_csv`
+ *
+ * Obtain a populated instance with `graphlite_csv_options_default` and override
+ * the fields you need.
+ */
+typedef struct GraphLiteCsvOptions {
+  /**
+   * Field separator (default `,`)
+   */
+  char delimiter;
+  /**
+   * First record holds the property names; otherwise columns are named
+   * `column1`, `column2`, ... (default true)
+   */
+  bool has_header;
+  /**
+   * Store numeric and `true`/`false` fields as numbers and booleans instead of
+   * strings (default true)
+   */
+  bool infer_types;
+} GraphLiteCsvOptions;
+
+/**
+ * Outcome of `graphlite_import_csv`
+ */
+typedef struct GraphLiteImportSummary {
+  /**
+   * Data records read from the file
+   */
+  uint64_t rows;
+  /**
+   * Nodes created (records identical to an existing node are skipped)
+   */
+  uint64_t nodes_created;
+} GraphLiteImportSummary;
+
+/**
+ * Counts of the strings, buffers and handles handed out by GraphLite FFI functions
+ *
+ * The `outstanding_*` fields count what has been returned and not yet freed.
+ */
+typedef struct GraphLiteAllocationStats {
+  /**
+   * Strings to free with `graphlite_free_string`, including those in string arrays
+   */
+  uint64_t outstanding_strings;
+  /**
+   * UTF-16 strings to free with `graphlite_free_wstring`
+   */
+  uint64_t outstanding_wide_strings;
+  /**
+   * String arrays to free with `graphlite_free_string_array`
+   */
+  uint64_t outstanding_string_arrays;
+  /**
+   * Buffers to free with `graphlite_free_buffer`
+   */
+  uint64_t outstanding_buffers;
+  /**
+   * Database handles to close with `graphlite_close`
+   */
+  uint64_t outstanding_databases;
+  /**
+   * Session handles to close with `graphlite_session_close`
+   */
+  uint64_t outstanding_sessions;
+  /**
+   * Result handles to free with `graphlite_result_free`
+   */
+  uint64_t outstanding_results;
+  /**
+   * Allocations handed out since the library was loaded
+   */
+  uint64_t total_allocated;
+  /**
+   * Allocations freed since the library was loaded
+   */
+  uint64_t total_freed;
+  /**
+   * Frees rejected by allocation tracking: double frees, foreign pointers or the
+   * wrong free function
+   */
+  uint64_t invalid_frees;
+  /**
+   * Whether allocation tracking is enabled
+   */
+  bool tracking;
+} GraphLiteAllocationStats;
+
 /**
  * Initialize GraphLite database from path
  *
@@ -82,68 +429,126 @@ typedef struct GraphLiteDB {
 struct GraphLiteDB *graphlite_open(const char *path, enum GraphLiteErrorCode *error_out);
 
 /**
- * Create a simple session for the given username
+ * Initialize GraphLite database from path with a handle that is safe to share between threads
+ *
+ * Behaves like `graphlite_open`, but every call on the returned handle takes an internal
+ * lock, so host threads may call `graphlite_query` (and the other handle functions)
+ * concurrently without their own synchronization. Calls on the same handle run one at a
+ * time; open separate handles on separate databases for parallel execution.
  *
  * # Arguments
- * * `db` - Database handle (must not be null)
- * * `username` - C string with username (must not be null)
- * * `error_out` - Output parameter for error code (can be null)
+ * * `path` - C string with database path (must not be null)
+ * * `error_out` - Output parameter for error code (can be null if caller doesn't need it)
  *
  * # Returns
- * * C string with session ID on success (must be freed with `graphlite_free_string`)
- * * null pointer on error
+ * * Opaque handle to database on success
+ * * null pointer on error (check `error_out` for details)
  *
  * # Safety
- * * `db` must be a valid handle from `graphlite_open`
- * * `username` must be a valid null-terminated C string
- * * Returned string must be freed with `graphlite_free_string`
+ * * `path` must be a valid null-terminated C string
+ * * Returned handle must be freed with `graphlite_close` once no other thread is using it
  */
-char *graphlite_create_session(struct GraphLiteDB *db,
-                               const char *username,
-                               enum GraphLiteErrorCode *error_out);
+struct GraphLiteDB *graphlite_open_shared(const char *path, enum GraphLiteErrorCode *error_out);
 
 /**
- * Execute a GQL query and return results as JSON
+ * Get a configuration populated with the engine defaults
+ *
+ * # Returns
+ * * `GraphLiteConfig` equivalent to what `graphlite_open` uses
+ *
+ * # Example
+ * ```c
+ * GraphLiteConfig config = graphlite_config_default();
+ * config.read_only = true;
+ * config.cache_size_bytes = 64 * 1024 * 1024;
+ * ```
+ */
+struct GraphLiteConfig graphlite_config_default(void);
+
+/**
+ * Route GraphLite's log output to a host callback
+ *
+ * Without a callback, log records go to the default logger, which writes to
+ * stderr. With one, every record at or below the maximum level is passed to
+ * `callback` instead, so it ends up in the host's logging system. The callback
+ * is process-wide and replaces any callback registered before. Register it
+ * before opening a database, as the process can have only one logger: if some
+ * other logger was installed first (for example by a `graphlite_open_with_config`
+ * call with a `log_level`), registration fails.
  *
  * # Arguments
- * * `db` - Database handle (must not be null)
- * * `session_id` - C string with session ID (must not be null)
- * * `query` - C string with GQL query (must not be null)
- * * `error_out` - Output parameter for error code (can be null)
+ * * `callback` - Function receiving the level, target (the engine module that
+ *   logged) and message of each record; the strings are only valid during the
+ *   call. Null discards log records from now on.
+ * * `max_level` - Most verbose level passed to the callback (`Unchanged` keeps
+ *   the current maximum, which is `Off` until one is set)
+ * * `user_data` - Passed to every call of `callback` (can be null)
  *
  * # Returns
- * * JSON string with query results on success (must be freed with `graphlite_free_string`)
- * * null pointer on error
+ * * true if the callback was registered
+ * * false if another logger was installed in the process first
  *
  * # Safety
- * * `db` must be a valid handle from `graphlite_open`
- * * `session_id` must be from `graphlite_create_session`
- * * `query` must be a valid null-terminated C string
- * * Returned JSON string must be freed with `graphlite_free_string`
+ * * `callback` may be called from any thread, concurrently, until it is
+ *   replaced; it and `user_data` must stay valid and thread-safe until then
  *
- * # JSON Format
- * ```json
- * {
- *   "variables": ["col1", "col2"],
- *   "rows": [
- *     {"col1": "value1", "col2": 123},
- *     {"col1": "value2", "col2": 456}
- *   ],
- *   "row_count": 2
+ * # Example
+ * ```c
+ * void on_log(GraphLiteLogLevel level, const char* target, const char* message, void* user_data) {
+ *     fprintf((FILE*)user_data, "[%d] %s: %s\n", level, target, message);
  * }
+ *
+ * graphlite_set_log_callback(on_log, Info, app_log_file);
+ * GraphLiteDB* db = graphlite_open("./mydb", &error);
  * ```
  */
-char *graphlite_query(struct GraphLiteDB *db,
-                      const char *session_id,
-                      const char *query,
-                      enum GraphLiteErrorCode *error_out);
+bool graphlite_set_log_callback(GraphLiteLogCallback callback,
+                                enum GraphLiteLogLevel max_level,
+                                void *user_data);
 
 /**
- * Close a session
+ * Initialize GraphLite database from path with an explicit configuration
+ *
+ * # Arguments
+ * * `path` - C string with database path (must not be null)
+ * * `config` - Engine configuration (can be null to use defaults)
+ * * `error_out` - Output parameter for error code (can be null if caller doesn't need it)
+ *
+ * # Returns
+ * * Opaque handle to database on success
+ * * null pointer on error (check `error_out` for details)
+ *
+ * # Safety
+ * * `path` must be a valid null-terminated C string
+ * * `config` must be null or point to a valid `GraphLiteConfig`
+ * * Returned handle must be freed with `graphlite_close`
+ *
+ * # Example
+ * ```c
+ * GraphLiteConfig config = graphlite_config_default();
+ * config.read_only = true;
+ * GraphLiteErrorCode error;
+ * GraphLiteDB* db = graphlite_open_with_config("/path/to/db", &config, &error);
+ * ```
+ */
+struct GraphLiteDB *graphlite_open_with_config(const char *path,
+                                               const struct GraphLiteConfig *config,
+                                               enum GraphLiteErrorCode *error_out);
+
+/**
+ * Set per-query resource limits on a database handle
+ *
+ * Every query run through the handle afterwards fails with `QueryError` when it
+ * returns more than `max_result_rows` rows or when its intermediate results are
+ * estimated to need more than `max_memory_bytes`, and with `Timeout` when it runs
+ * longer than `query_timeout_ms`. Memory and time are checked between query operators. Pass 0
+ * for any limit to leave it unlimited.
  *
  * # Arguments
  * * `db` - Database handle (must not be null)
- * * `session_id` - C string with session ID (must not be null)
+ * * `max_result_rows` - Maximum rows per result (0 = unlimited)
+ * * `max_memory_bytes` - Per-query memory budget in bytes (0 = unlimited)
+ * * `query_timeout_ms` - Per-query timeout in milliseconds (0 = unlimited)
  * * `error_out` - Output parameter for error code (can be null)
  *
  * # Returns
@@ -151,17 +556,1441 @@ char *graphlite_query(struct GraphLiteDB *db,
  *
  * # Safety
  * * `db` must be a valid handle from `graphlite_open`
- * * `session_id` must be from `graphlite_create_session`
+ *
+ * # Example
+ * ```c
+ * // At most 10k rows, 256 MiB and 5 seconds per query
+ * graphlite_set_limits(db, 10000, 256 * 1024 * 1024, 5000, &error);
+ * ```
  */
-enum GraphLiteErrorCode graphlite_close_session(struct GraphLiteDB *db,
-                                                const char *session_id,
-                                                enum GraphLiteErrorCode *error_out);
+enum GraphLiteErrorCode graphlite_set_limits(struct GraphLiteDB *db,
+                                             uint64_t max_result_rows,
+                                             uint64_t max_memory_bytes,
+                                             uint64_t query_timeout_ms,
+                                             enum GraphLiteErrorCode *error_out);
 
 /**
- * Free a string returned by GraphLite FFI functions
+ * Get the number format database handles start with
  *
- * # Arguments
- * * `s` - C string to free (can be null, in which case this is a no-op)
+ * Whole numbers are integers, other numbers are written in their shortest form.
+ */
+struct GraphLiteNumberFormat graphlite_number_format_default(void);
+
+/**
+ * Set how numbers are written in the JSON returned for a database handle
+ *
+ * Applies to `graphlite_query`, `graphlite_session_query`, their UTF-16
+ * variants, `graphlite_query_compressed`, `graphlite_execute_many` and
+ * `graphlite_query_many`.
+ *
+ * # Arguments
+ * * `db` - Database handle (must not be null)
+ * * `format` - Number format (must not be null)
+ * * `error_out` - Output parameter for error code (can be null)
+ *
+ * # Returns
+ * * Error code (Success = 0, error otherwise)
+ *
+ * # Safety
+ * * `db` must be a valid handle from `graphlite_open`
+ * * `format` must point to a valid `GraphLiteNumberFormat`
+ *
+ * # Example
+ * ```c
+ * GraphLiteNumberFormat format = graphlite_number_format_default();
+ * format.decimals = 2;
+ * graphlite_set_number_format(db, &format, &error);
+ * ```
+ */
+enum GraphLiteErrorCode graphlite_set_number_format(struct GraphLiteDB *db,
+                                                    const struct GraphLiteNumberFormat *format,
+                                                    enum GraphLiteErrorCode *error_out);
+
+/**
+ * Create a simple session for the given username
+ *
+ * # Arguments
+ * * `db` - Database handle (must not be null)
+ * * `username` - C string with username (must not be null)
+ * * `error_out` - Output parameter for error code (can be null)
+ *
+ * # Returns
+ * * C string with session ID on success (must be freed with `graphlite_free_string`)
+ * * null pointer on error
+ *
+ * # Safety
+ * * `db` must be a valid handle from `graphlite_open`
+ * * `username` must be a valid null-terminated C string
+ * * Returned string must be freed with `graphlite_free_string`
+ */
+char *graphlite_create_session(struct GraphLiteDB *db,
+                               const char *username,
+                               enum GraphLiteErrorCode *error_out);
+
+/**
+ * Execute a GQL query and return results as JSON
+ *
+ * # Arguments
+ * * `db` - Database handle (must not be null)
+ * * `session_id` - C string with session ID (must not be null)
+ * * `query` - C string with GQL query (must not be null)
+ * * `error_out` - Output parameter for error code (can be null)
+ *
+ * # Returns
+ * * JSON string with query results on success (must be freed with `graphlite_free_string`)
+ * * null pointer on error
+ *
+ * # Safety
+ * * `db` must be a valid handle from `graphlite_open`
+ * * `session_id` must be from `graphlite_create_session`
+ * * `query` must be a valid null-terminated C string
+ * * Returned JSON string must be freed with `graphlite_free_string`
+ *
+ * # JSON Format
+ * ```json
+ * {
+ *   "variables": ["col1", "col2"],
+ *   "rows": [
+ *     {"col1": "value1", "col2": 123},
+ *     {"col1": "value2", "col2": 456}
+ *   ],
+ *   "row_count": 2
+ * }
+ * ```
+ */
+char *graphlite_query(struct GraphLiteDB *db,
+                      const char *session_id,
+                      const char *query,
+                      enum GraphLiteErrorCode *error_out);
+
+/**
+ * Execute a GQL query and return the result as MessagePack
+ *
+ * The buffer holds the same document `graphlite_query` returns as JSON, encoded
+ * as MessagePack with field names kept (see "Binary Result Format" in the README).
+ * Decoding it is considerably cheaper than parsing JSON for wide rows.
+ *
+ * # Arguments
+ * * `db` - Database handle (must not be null)
+ * * `session_id` - C string with session ID (must not be null)
+ * * `query` - C string with GQL query (must not be null)
+ * * `len_out` - Output parameter for the buffer length in bytes (must not be null)
+ * * `error_out` - Output parameter for error code (can be null)
+ *
+ * # Returns
+ * * Buffer with the MessagePack-encoded result (must be freed with `graphlite_free_buffer`)
+ * * null pointer on error
+ *
+ * # Safety
+ * * `db` must be a valid handle from `graphlite_open`
+ * * `session_id` must be from `graphlite_create_session`
+ * * `query` must be a valid null-terminated C string
+ * * `len_out` must point to writable memory
+ * * Returned buffer must be freed with `graphlite_free_buffer` using the length from `len_out`
+ *
+ * # Example
+ * ```c
+ * size_t len;
+ * GraphLiteErrorCode error;
+ * uint8_t* buf = graphlite_query_msgpack(db, session_id, "MATCH (n) RETURN n", &len, &error);
+ * if (buf) {
+ *     // decode buf[0..len] with any MessagePack library
+ *     graphlite_free_buffer(buf, len);
+ * }
+ * ```
+ */
+uint8_t *graphlite_query_msgpack(struct GraphLiteDB *db,
+                                 const char *session_id,
+                                 const char *query,
+                                 uintptr_t *len_out,
+                                 enum GraphLiteErrorCode *error_out);
+
+/**
+ * Execute a GQL query and return the JSON result compressed
+ *
+ * The buffer holds the document `graphlite_query` returns, compressed with
+ * `compression`. Text-heavy results typically shrink by an order of magnitude,
+ * which matters when the caller forwards them over a network: a server can pass
+ * the buffer on unchanged with the matching `Content-Encoding`.
+ *
+ * # Arguments
+ * * `db` - Database handle (must not be null)
+ * * `session_id` - C string with session ID (must not be null)
+ * * `query` - C string with GQL query (must not be null)
+ * * `compression` - Compression to apply
+ * * `len_out` - Output parameter for the buffer length in bytes (must not be null)
+ * * `error_out` - Output parameter for error code (can be null)
+ *
+ * # Returns
+ * * Buffer with the compressed JSON result (must be freed with `graphlite_free_buffer`)
+ * * null pointer on error
+ *
+ * # Safety
+ * * `db` must be a valid handle from `graphlite_open`
+ * * `session_id` must be from `graphlite_create_session`
+ * * `query` must be a valid null-terminated C string
+ * * `len_out` must point to writable memory
+ * * Returned buffer must be freed with `graphlite_free_buffer` using the length from `len_out`
+ *
+ * # Example
+ * ```c
+ * size_t len;
+ * GraphLiteErrorCode error;
+ * uint8_t* buf = graphlite_query_compressed(db, session_id, "MATCH (n) RETURN n",
+ *     Gzip, &len, &error);
+ * if (buf) {
+ *     // send buf[0..len] with "Content-Encoding: gzip"
+ *     graphlite_free_buffer(buf, len);
+ * }
+ * ```
+ */
+uint8_t *graphlite_query_compressed(struct GraphLiteDB *db,
+                                    const char *session_id,
+                                    const char *query,
+                                    enum GraphLiteCompression compression,
+                                    uintptr_t *len_out,
+                                    enum GraphLiteErrorCode *error_out);
+
+/**
+ * Execute a GQL query and return the result for reading row by row
+ *
+ * The result is read with `graphlite_result_next` (or `graphlite_result_row`) and
+ * the `graphlite_row_get_*` accessors, which return typed values without any JSON.
+ *
+ * # Arguments
+ * * `db` - Database handle (must not be null)
+ * * `session_id` - C string with session ID (must not be null)
+ * * `query` - C string with GQL query (must not be null)
+ * * `error_out` - Output parameter for error code (can be null)
+ *
+ * # Returns
+ * * Result handle on success (must be freed with `graphlite_result_free`)
+ * * null pointer on error
+ *
+ * # Safety
+ * * `db` must be a valid handle from `graphlite_open`
+ * * `session_id` must be from `graphlite_create_session`
+ * * `query` must be a valid null-terminated C string
+ *
+ * # Example
+ * ```c
+ * GraphLiteResult* result = graphlite_query_result(db, session_id,
+ *     "MATCH (p:Person) RETURN p.name AS name, p.age AS age", &error);
+ * const GraphLiteRow* row;
+ * while ((row = graphlite_result_next(result)) != NULL) {
+ *     const char* name = graphlite_row_get_string(row, 0, &error);
+ *     int64_t age = graphlite_row_get_int(row, 1, &error);
+ * }
+ * graphlite_result_free(result);
+ * ```
+ */
+struct GraphLiteResult *graphlite_query_result(struct GraphLiteDB *db,
+                                               const char *session_id,
+                                               const char *query,
+                                               enum GraphLiteErrorCode *error_out);
+
+/**
+ * Get the number of rows of a result
+ *
+ * # Returns
+ * * Row count, or 0 if `result` is null
+ *
+ * # Safety
+ * * `result` must be a valid handle from `graphlite_query_result`
+ */
+uintptr_t graphlite_result_row_count(const struct GraphLiteResult *result);
+
+/**
+ * Get the number of columns of a result
+ *
+ * # Returns
+ * * Column count, or 0 if `result` is null
+ *
+ * # Safety
+ * * `result` must be a valid handle from `graphlite_query_result`
+ */
+uintptr_t graphlite_result_column_count(const struct GraphLiteResult *result);
+
+/**
+ * Get the name of a result column, in RETURN order
+ *
+ * # Returns
+ * * Column name, owned by the result and valid until `graphlite_result_free`
+ *   (must NOT be freed)
+ * * null pointer if `result` is null or `column` is out of range
+ *
+ * # Safety
+ * * `result` must be a valid handle from `graphlite_query_result`
+ */
+const char *graphlite_result_column_name(const struct GraphLiteResult *result, uintptr_t column);
+
+/**
+ * Advance the result cursor and return the next row
+ *
+ * The first call returns the first row.
+ *
+ * # Returns
+ * * Row owned by the result and valid until `graphlite_result_free`
+ * * null pointer after the last row, or if `result` is null
+ *
+ * # Safety
+ * * `result` must be a valid handle from `graphlite_query_result`
+ */
+const struct GraphLiteRow *graphlite_result_next(struct GraphLiteResult *result);
+
+/**
+ * Get a row of a result by index, without moving the cursor
+ *
+ * # Returns
+ * * Row owned by the result and valid until `graphlite_result_free`
+ * * null pointer if `result` is null or `index` is out of range
+ *
+ * # Safety
+ * * `result` must be a valid handle from `graphlite_query_result`
+ */
+const struct GraphLiteRow *graphlite_result_row(const struct GraphLiteResult *result,
+                                                uintptr_t index);
+
+/**
+ * Free a result and all rows and strings read from it
+ *
+ * # Arguments
+ * * `result` - Result to free (can be null, in which case this is a no-op)
+ *
+ * # Safety
+ * * `result` must be a handle from `graphlite_query_result`
+ * * Must not be called more than once on the same handle
+ */
+void graphlite_result_free(struct GraphLiteResult *result);
+
+/**
+ * Get the number of columns of a row
+ *
+ * # Returns
+ * * Column count (the same for every row of a result), or 0 if `row` is null
+ *
+ * # Safety
+ * * `row` must be a row of a result that has not been freed
+ */
+uintptr_t graphlite_row_column_count(const struct GraphLiteRow *row);
+
+/**
+ * Get the type of a row value, to pick the accessor that reads it
+ *
+ * # Returns
+ * * Type of the value; `Null` also if `row` is null or `column` is out of range
+ *
+ * # Safety
+ * * `row` must be a row of a result that has not been freed
+ */
+enum GraphLiteValueType graphlite_row_value_type(const struct GraphLiteRow *row, uintptr_t column);
+
+/**
+ * Read a boolean row value
+ *
+ * # Returns
+ * * The value, or false with `error_out` set to `TypeMismatch` or `OutOfRange`
+ *
+ * # Safety
+ * * `row` must be a row of a result that has not been freed
+ */
+bool graphlite_row_get_bool(const struct GraphLiteRow *row,
+                            uintptr_t column,
+                            enum GraphLiteErrorCode *error_out);
+
+/**
+ * Read an integral number row value
+ *
+ * # Returns
+ * * The value, or 0 with `error_out` set to `TypeMismatch` (not a number, or a
+ *   number with a fractional part or outside the `int64_t` range) or `OutOfRange`
+ *
+ * # Safety
+ * * `row` must be a row of a result that has not been freed
+ */
+int64_t graphlite_row_get_int(const struct GraphLiteRow *row,
+                              uintptr_t column,
+                              enum GraphLiteErrorCode *error_out);
+
+/**
+ * Read a number row value
+ *
+ * # Returns
+ * * The value, or 0.0 with `error_out` set to `TypeMismatch` or `OutOfRange`
+ *
+ * # Safety
+ * * `row` must be a row of a result that has not been freed
+ */
+double graphlite_row_get_double(const struct GraphLiteRow *row,
+                                uintptr_t column,
+                                enum GraphLiteErrorCode *error_out);
+
+/**
+ * Read a string row value, or the text form of an `Other` value such as a date
+ *
+ * # Returns
+ * * The string, owned by the result and valid until `graphlite_result_free`
+ *   (must NOT be freed)
+ * * null pointer with `error_out` set to `TypeMismatch` (including NULL values and
+ *   strings containing a NUL byte) or `OutOfRange`
+ *
+ * # Safety
+ * * `row` must be a row of a result that has not been freed
+ */
+const char *graphlite_row_get_string(const struct GraphLiteRow *row,
+                                     uintptr_t column,
+                                     enum GraphLiteErrorCode *error_out);
+
+/**
+ * Read the storage id of a node row value
+ *
+ * # Returns
+ * * Node id, owned by the result and valid until `graphlite_result_free`
+ *   (must NOT be freed)
+ * * null pointer with `error_out` set to `TypeMismatch` or `OutOfRange`
+ *
+ * # Safety
+ * * `row` must be a row of a result that has not been freed
+ */
+const char *graphlite_row_get_node_id(const struct GraphLiteRow *row,
+                                      uintptr_t column,
+                                      enum GraphLiteErrorCode *error_out);
+
+/**
+ * Get the number of labels of a node or edge row value (always 1 for an edge)
+ *
+ * # Returns
+ * * Label count, or 0 with `error_out` set to `TypeMismatch` or `OutOfRange`
+ *
+ * # Safety
+ * * `row` must be a row of a result that has not been freed
+ */
+uintptr_t graphlite_row_get_label_count(const struct GraphLiteRow *row,
+                                        uintptr_t column,
+                                        enum GraphLiteErrorCode *error_out);
+
+/**
+ * Read a label of a node or edge row value
+ *
+ * # Arguments
+ * * `index` - Label index, below `graphlite_row_get_label_count`
+ *
+ * # Returns
+ * * Label, owned by the result and valid until `graphlite_result_free`
+ *   (must NOT be freed)
+ * * null pointer with `error_out` set to `TypeMismatch` or `OutOfRange`
+ *
+ * # Safety
+ * * `row` must be a row of a result that has not been freed
+ */
+const char *graphlite_row_get_label(const struct GraphLiteRow *row,
+                                    uintptr_t column,
+                                    uintptr_t index,
+                                    enum GraphLiteErrorCode *error_out);
+
+/**
+ * Read the storage id of an edge row value
+ *
+ * # Returns
+ * * Edge id, owned by the result and valid until `graphlite_result_free`
+ *   (must NOT be freed)
+ * * null pointer with `error_out` set to `TypeMismatch` or `OutOfRange`
+ *
+ * # Safety
+ * * `row` must be a row of a result that has not been freed
+ */
+const char *graphlite_row_get_edge_id(const struct GraphLiteRow *row,
+                                      uintptr_t column,
+                                      enum GraphLiteErrorCode *error_out);
+
+/**
+ * Read the source node id of an edge row value
+ *
+ * # Returns
+ * * Node id, owned by the result and valid until `graphlite_result_free`
+ *   (must NOT be freed)
+ * * null pointer with `error_out` set to `TypeMismatch` or `OutOfRange`
+ *
+ * # Safety
+ * * `row` must be a row of a result that has not been freed
+ */
+const char *graphlite_row_get_edge_source(const struct GraphLiteRow *row,
+                                          uintptr_t column,
+                                          enum GraphLiteErrorCode *error_out);
+
+/**
+ * Read the target node id of an edge row value
+ *
+ * # Returns
+ * * Node id, owned by the result and valid until `graphlite_result_free`
+ *   (must NOT be freed)
+ * * null pointer with `error_out` set to `TypeMismatch` or `OutOfRange`
+ *
+ * # Safety
+ * * `row` must be a row of a result that has not been freed
+ */
+const char *graphlite_row_get_edge_target(const struct GraphLiteRow *row,
+                                          uintptr_t column,
+                                          enum GraphLiteErrorCode *error_out);
+
+/**
+ * Get the type of a result value by row and column, to pick the accessor that reads it
+ *
+ * The `graphlite_result_get_*` functions read any cell directly, so bindings with
+ * random access semantics (a JDBC `ResultSet`, a data frame) need no cursor.
+ *
+ * # Returns
+ * * Type of the value; `Null` also if `result` is null or `row` or `column` is out of range
+ *
+ * # Safety
+ * * `result` must be a valid handle from `graphlite_query_result`
+ */
+enum GraphLiteValueType graphlite_result_value_type(const struct GraphLiteResult *result,
+                                                    uintptr_t row,
+                                                    uintptr_t column);
+
+/**
+ * Read a boolean result value by row and column
+ *
+ * # Returns
+ * * The value, or false with `error_out` set to `TypeMismatch` or `OutOfRange`
+ *
+ * # Safety
+ * * `result` must be a valid handle from `graphlite_query_result`
+ */
+bool graphlite_result_get_bool(const struct GraphLiteResult *result,
+                               uintptr_t row,
+                               uintptr_t column,
+                               enum GraphLiteErrorCode *error_out);
+
+/**
+ * Read an integral number result value by row and column
+ *
+ * # Returns
+ * * The value, or 0 with `error_out` set to `TypeMismatch` (not a number, or a
+ *   number with a fractional part or outside the `int64_t` range) or `OutOfRange`
+ *
+ * # Safety
+ * * `result` must be a valid handle from `graphlite_query_result`
+ */
+int64_t graphlite_result_get_int(const struct GraphLiteResult *result,
+                                 uintptr_t row,
+                                 uintptr_t column,
+                                 enum GraphLiteErrorCode *error_out);
+
+/**
+ * Read a number result value by row and column
+ *
+ * # Returns
+ * * The value, or 0.0 with `error_out` set to `TypeMismatch` or `OutOfRange`
+ *
+ * # Safety
+ * * `result` must be a valid handle from `graphlite_query_result`
+ */
+double graphlite_result_get_double(const struct GraphLiteResult *result,
+                                   uintptr_t row,
+                                   uintptr_t column,
+                                   enum GraphLiteErrorCode *error_out);
+
+/**
+ * Read a string result value by row and column, or the text form of an `Other` value
+ *
+ * # Returns
+ * * The string, owned by the result and valid until `graphlite_result_free`
+ *   (must NOT be freed)
+ * * null pointer with `error_out` set to `TypeMismatch` (including NULL values and
+ *   strings containing a NUL byte) or `OutOfRange`
+ *
+ * # Safety
+ * * `result` must be a valid handle from `graphlite_query_result`
+ */
+const char *graphlite_result_get_string(const struct GraphLiteResult *result,
+                                        uintptr_t row,
+                                        uintptr_t column,
+                                        enum GraphLiteErrorCode *error_out);
+
+/**
+ * Copy a number column into an array, one value per row
+ *
+ * # Arguments
+ * * `result` - Result handle (must not be null)
+ * * `column` - Column index
+ * * `values_out` - Array of at least `len` values (must not be null)
+ * * `nulls_out` - Array of at least `len` flags set for NULL values, which are
+ *   written as 0.0 (can be null, in which case a NULL value is a `TypeMismatch`)
+ * * `len` - Capacity of the arrays; rows past it are not copied
+ * * `error_out` - Output parameter for error code (can be null)
+ *
+ * # Returns
+ * * Number of rows copied; on `TypeMismatch` or `OutOfRange` the rows before the
+ *   failing one
+ *
+ * # Safety
+ * * `result` must be a valid handle from `graphlite_query_result`
+ * * `values_out` and a non-null `nulls_out` must point to `len` writable elements
+ */
+uintptr_t graphlite_result_get_double_column(const struct GraphLiteResult *result,
+                                             uintptr_t column,
+                                             double *values_out,
+                                             bool *nulls_out,
+                                             uintptr_t len,
+                                             enum GraphLiteErrorCode *error_out);
+
+/**
+ * Copy an integral number column into an array, one value per row
+ *
+ * Same as `graphlite_result_get_double_column`, with values read as by
+ * `graphlite_result_get_int`; NULL values are written as 0.
+ *
+ * # Safety
+ * * `result` must be a valid handle from `graphlite_query_result`
+ * * `values_out` and a non-null `nulls_out` must point to `len` writable elements
+ */
+uintptr_t graphlite_result_get_int_column(const struct GraphLiteResult *result,
+                                          uintptr_t column,
+                                          int64_t *values_out,
+                                          bool *nulls_out,
+                                          uintptr_t len,
+                                          enum GraphLiteErrorCode *error_out);
+
+/**
+ * Bulk load nodes into the session's current graph
+ *
+ * Node `i` is described by `labels[i]` and `properties[i]`. Rows are written
+ * directly to storage without parsing a GQL statement per node, and the batch
+ * is persisted in one step (all-or-nothing).
+ *
+ * # Arguments
+ * * `db` - Database handle (must not be null)
+ * * `session_id` - C string with session ID (must not be null)
+ * * `labels` - Array of `count` label lists separated by `:`, e.g. `"Person:Employee"`
+ *   (the array or individual entries can be null for unlabeled nodes)
+ * * `properties` - Array of `count` JSON objects, e.g. `{"name": "Alice", "age": 30}`
+ *   (the array or individual entries can be null for nodes without properties)
+ * * `count` - Number of nodes
+ * * `error_out` - Output parameter for error code (can be null)
+ *
+ * # Returns
+ * * JSON array with the storage id of each node, in input order
+ *   (must be freed with `graphlite_free_string`); use these ids as edge endpoints
+ * * null pointer on error
+ *
+ * # Safety
+ * * `db` must be a valid handle from `graphlite_open`
+ * * `session_id` must be from `graphlite_create_session`
+ * * Non-null `labels` and `properties` arrays must hold `count` pointers, each
+ *   null or a valid null-terminated C string
+ * * Returned JSON string must be freed with `graphlite_free_string`
+ */
+char *graphlite_bulk_insert_nodes(struct GraphLiteDB *db,
+                                  const char *session_id,
+                                  const char *const *labels,
+                                  const char *const *properties,
+                                  uintptr_t count,
+                                  enum GraphLiteErrorCode *error_out);
+
+/**
+ * Bulk load edges into the session's current graph
+ *
+ * Edge `i` runs from `from_ids[i]` to `to_ids[i]` with label `labels[i]` and
+ * properties `properties[i]`. Endpoints are node storage ids as returned by
+ * `graphlite_bulk_insert_nodes` and must already exist. Duplicate edges are
+ * skipped; the batch is persisted in one step (all-or-nothing).
+ *
+ * # Arguments
+ * * `db` - Database handle (must not be null)
+ * * `session_id` - C string with session ID (must not be null)
+ * * `from_ids` - Array of `count` source node ids (must not be null)
+ * * `to_ids` - Array of `count` target node ids (must not be null)
+ * * `labels` - Array of `count` edge labels (must not be null)
+ * * `properties` - Array of `count` JSON objects (the array or individual entries can be null)
+ * * `count` - Number of edges
+ * * `inserted_out` - Output parameter for the number of edges created (can be null)
+ * * `error_out` - Output parameter for error code (can be null)
+ *
+ * # Returns
+ * * Error code (Success = 0, error otherwise)
+ *
+ * # Safety
+ * * `db` must be a valid handle from `graphlite_open`
+ * * `session_id` must be from `graphlite_create_session`
+ * * `from_ids`, `to_ids` and `labels` must hold `count` valid null-terminated C strings
+ * * A non-null `properties` array must hold `count` pointers, each null or a valid
+ *   null-terminated C string
+ */
+enum GraphLiteErrorCode graphlite_bulk_insert_edges(struct GraphLiteDB *db,
+                                                    const char *session_id,
+                                                    const char *const *from_ids,
+                                                    const char *const *to_ids,
+                                                    const char *const *labels,
+                                                    const char *const *properties,
+                                                    uintptr_t count,
+                                                    uintptr_t *inserted_out,
+                                                    enum GraphLiteErrorCode *error_out);
+
+/**
+ * Get CSV options populated with the engine defaults
+ *
+ * # Example
+ * ```c
+ * GraphLiteCsvOptions options = graphlite_csv_options_default();
+ * options.delimiter = ';';
+ * ```
+ */
+struct GraphLiteCsvOptions graphlite_csv_options_default(void);
+
+/**
+ * Import a CSV file as nodes into the session's current graph
+ *
+ * Every record becomes a node labeled `label` with one property per column;
+ * empty fields leave the property unset. The file is read and parsed by the
+ * engine and loaded through the bulk insert path in one all-or-nothing write,
+ * so no GQL statement is built per row. Records identical to an existing node
+ * are skipped. The import cannot run inside an open transaction.
+ *
+ * # Arguments
+ * * `db` - Database handle (must not be null)
+ * * `session_id` - C string with session ID (must not be null)
+ * * `file_path` - C string with the path of the CSV file (must not be null)
+ * * `label` - C string with the node label (must not be null)
+ * * `options` - CSV parsing options (can be null to use defaults)
+ * * `summary_out` - Output parameter for the rows read and nodes created (can be null)
+ * * `error_out` - Output parameter for error code (can be null)
+ *
+ * # Returns
+ * * Error code (Success = 0, error otherwise); `QueryError` if the file cannot be
+ *   read or parsed, `GraphNotFound` if there is no current graph
+ *
+ * # Safety
+ * * `db` must be a valid handle from `graphlite_open`
+ * * `session_id` must be from `graphlite_create_session`
+ * * `file_path` and `label` must be valid null-terminated C strings
+ * * `options` must be null or point to a valid `GraphLiteCsvOptions`
+ *
+ * # Example
+ * ```c
+ * GraphLiteCsvOptions options = graphlite_csv_options_default();
+ * GraphLiteImportSummary summary;
+ * graphlite_import_csv(db, session, "people.csv", "Person", &options, &summary, &error);
+ * ```
+ */
+enum GraphLiteErrorCode graphlite_import_csv(struct GraphLiteDB *db,
+                                             const char *session_id,
+                                             const char *file_path,
+                                             const char *label,
+                                             const struct GraphLiteCsvOptions *options,
+                                             struct GraphLiteImportSummary *summary_out,
+                                             enum GraphLiteErrorCode *error_out);
+
+/**
+ * Execute several statements in a single transaction
+ *
+ * The statements run in order inside one transaction that is committed when all
+ * of them succeed. Execution stops at the first failing statement and the whole
+ * batch is rolled back. Transaction control statements (`START TRANSACTION`,
+ * `COMMIT`, `ROLLBACK`) are not allowed, and the session must not have an open
+ * transaction.
+ *
+ * # Arguments
+ * * `db` - Database handle (must not be null)
+ * * `session_id` - C string with session ID (must not be null)
+ * * `statements` - Array of `count` GQL statements (must not be null)
+ * * `count` - Number of statements
+ * * `status_out` - Array of `count` per-statement status codes (can be null):
+ *   `Success` for statements that ran, the error code of the statement that
+ *   failed and `NotExecuted` for the statements after it
+ * * `error_out` - Output parameter for error code (can be null); the code of the
+ *   failed statement means the batch was rolled back
+ *
+ * # Returns
+ * * JSON array with the result of each statement, in input order
+ *   (must be freed with `graphlite_free_string`)
+ * * null pointer on error
+ *
+ * # Safety
+ * * `db` must be a valid handle from `graphlite_open`
+ * * `session_id` must be from `graphlite_create_session`
+ * * `statements` must hold `count` valid null-terminated C strings
+ * * A non-null `status_out` must have room for `count` codes
+ * * Returned JSON string must be freed with `graphlite_free_string`
+ */
+char *graphlite_execute_many(struct GraphLiteDB *db,
+                             const char *session_id,
+                             const char *const *statements,
+                             uintptr_t count,
+                             enum GraphLiteErrorCode *status_out,
+                             enum GraphLiteErrorCode *error_out);
+
+/**
+ * Run several independent queries in one call
+ *
+ * Every query runs on its own, in order: a failing query neither stops nor
+ * undoes the others. Issuing a page's queries together saves a call through the
+ * FFI boundary per query.
+ *
+ * # Arguments
+ * * `db` - Database handle (must not be null)
+ * * `session_id` - C string with session ID (must not be null)
+ * * `queries` - Array of `count` GQL queries (must not be null)
+ * * `count` - Number of queries
+ * * `status_out` - Array of `count` per-query status codes (can be null)
+ * * `error_out` - Output parameter for error code (can be null); `Success` when
+ *   the queries ran, even if some of them failed
+ *
+ * # Returns
+ * * JSON array with one entry per query, in input order: the query result, or
+ *   `{"error": "<message>"}` if the query failed
+ *   (must be freed with `graphlite_free_string`)
+ * * null pointer on error
+ *
+ * # Safety
+ * * `db` must be a valid handle from `graphlite_open`
+ * * `session_id` must be from `graphlite_create_session`
+ * * `queries` must hold `count` valid null-terminated C strings
+ * * A non-null `status_out` must have room for `count` codes
+ * * Returned JSON string must be freed with `graphlite_free_string`
+ */
+char *graphlite_query_many(struct GraphLiteDB *db,
+                           const char *session_id,
+                           const char *const *queries,
+                           uintptr_t count,
+                           enum GraphLiteErrorCode *status_out,
+                           enum GraphLiteErrorCode *error_out);
+
+/**
+ * Close a session
+ *
+ * # Arguments
+ * * `db` - Database handle (must not be null)
+ * * `session_id` - C string with session ID (must not be null)
+ * * `error_out` - Output parameter for error code (can be null)
+ *
+ * # Returns
+ * * Error code (Success = 0, error otherwise)
+ *
+ * # Safety
+ * * `db` must be a valid handle from `graphlite_open`
+ * * `session_id` must be from `graphlite_create_session`
+ */
+enum GraphLiteErrorCode graphlite_close_session(struct GraphLiteDB *db,
+                                                const char *session_id,
+                                                enum GraphLiteErrorCode *error_out);
+
+/**
+ * Start a transaction on a session (`START TRANSACTION`)
+ *
+ * Statements run on the session afterwards, through `graphlite_query` or any other
+ * function taking its session ID, belong to the transaction until
+ * `graphlite_commit` or `graphlite_rollback`.
+ *
+ * # Arguments
+ * * `db` - Database handle (must not be null)
+ * * `session_id` - C string with session ID (must not be null)
+ * * `error_out` - Output parameter for error code (can be null)
+ *
+ * # Returns
+ * * Error code (Success = 0, `TransactionConflict` if the session already has an
+ *   open transaction)
+ *
+ * # Safety
+ * * `db` must be a valid handle from `graphlite_open`
+ * * `session_id` must be a valid null-terminated C string
+ *
+ * # Example
+ * ```c
+ * graphlite_begin(db, session_id, &error);
+ * char* a = graphlite_query(db, session_id, "INSERT (:Account {id: 1, balance: 100})", &error);
+ * char* b = graphlite_query(db, session_id, "INSERT (:Account {id: 2, balance: 0})", &error);
+ * if (a && b) {
+ *     graphlite_commit(db, session_id, &error);
+ * } else {
+ *     graphlite_rollback(db, session_id, &error);
+ * }
+ * ```
+ */
+enum GraphLiteErrorCode graphlite_begin(struct GraphLiteDB *db,
+                                        const char *session_id,
+                                        enum GraphLiteErrorCode *error_out);
+
+/**
+ * Commit the open transaction of a session (`COMMIT`)
+ *
+ * # Arguments
+ * * `db` - Database handle (must not be null)
+ * * `session_id` - C string with session ID (must not be null)
+ * * `error_out` - Output parameter for error code (can be null)
+ *
+ * # Returns
+ * * Error code (Success = 0, `TransactionConflict` if the session has no open
+ *   transaction)
+ *
+ * # Safety
+ * * `db` must be a valid handle from `graphlite_open`
+ * * `session_id` must be a valid null-terminated C string
+ */
+enum GraphLiteErrorCode graphlite_commit(struct GraphLiteDB *db,
+                                         const char *session_id,
+                                         enum GraphLiteErrorCode *error_out);
+
+/**
+ * Roll back the open transaction of a session (`ROLLBACK`)
+ *
+ * # Arguments
+ * * `db` - Database handle (must not be null)
+ * * `session_id` - C string with session ID (must not be null)
+ * * `error_out` - Output parameter for error code (can be null)
+ *
+ * # Returns
+ * * Error code (Success = 0, `TransactionConflict` if the session has no open
+ *   transaction)
+ *
+ * # Safety
+ * * `db` must be a valid handle from `graphlite_open`
+ * * `session_id` must be a valid null-terminated C string
+ */
+enum GraphLiteErrorCode graphlite_rollback(struct GraphLiteDB *db,
+                                           const char *session_id,
+                                           enum GraphLiteErrorCode *error_out);
+
+/**
+ * Check whether a session has an open transaction
+ *
+ * # Arguments
+ * * `db` - Database handle (must not be null)
+ * * `session_id` - C string with session ID (must not be null)
+ * * `error_out` - Output parameter for error code (can be null)
+ *
+ * # Returns
+ * * true if a transaction started with `graphlite_begin` (or `START TRANSACTION`)
+ *   is still open
+ * * false otherwise, or on error (`SessionError` if the session does not exist)
+ *
+ * # Safety
+ * * `db` must be a valid handle from `graphlite_open`
+ * * `session_id` must be a valid null-terminated C string
+ */
+bool graphlite_in_transaction(struct GraphLiteDB *db,
+                              const char *session_id,
+                              enum GraphLiteErrorCode *error_out);
+
+/**
+ * List the graphs of the database
+ *
+ * # Arguments
+ * * `db` - Database handle (must not be null)
+ * * `session_id` - C string with session ID (must not be null)
+ * * `count_out` - Output parameter for the number of graphs (must not be null)
+ * * `error_out` - Output parameter for error code (can be null)
+ *
+ * # Returns
+ * * Array of `*count_out` full graph paths such as `/app/social`, sorted
+ *   (must be freed with `graphlite_free_string_array`)
+ * * null pointer on error
+ *
+ * # Safety
+ * * `db` must be a valid handle from `graphlite_open`
+ * * `session_id` must be a valid null-terminated C string
+ * * `count_out` must point to writable memory
+ *
+ * # Example
+ * ```c
+ * size_t count;
+ * char** graphs = graphlite_list_graphs(db, session_id, &count, &error);
+ * for (size_t i = 0; i < count; i++) {
+ *     printf("%s\n", graphs[i]);
+ * }
+ * graphlite_free_string_array(graphs, count);
+ * ```
+ */
+char **graphlite_list_graphs(struct GraphLiteDB *db,
+                             const char *session_id,
+                             uintptr_t *count_out,
+                             enum GraphLiteErrorCode *error_out);
+
+/**
+ * Create a graph (`CREATE GRAPH`)
+ *
+ * # Arguments
+ * * `db` - Database handle (must not be null)
+ * * `session_id` - C string with session ID (must not be null)
+ * * `graph_path` - Full graph path such as `/app/social`, or a graph name in the
+ *   session's current schema (must not be null)
+ * * `error_out` - Output parameter for error code (can be null)
+ *
+ * # Returns
+ * * Error code (Success = 0, `ConstraintViolation` if the graph already exists,
+ *   `GraphNotFound` if its schema does not exist, `ParseError` if `graph_path`
+ *   is not a graph path)
+ *
+ * # Safety
+ * * `db` must be a valid handle from `graphlite_open`
+ * * `session_id` and `graph_path` must be valid null-terminated C strings
+ */
+enum GraphLiteErrorCode graphlite_create_graph(struct GraphLiteDB *db,
+                                               const char *session_id,
+                                               const char *graph_path,
+                                               enum GraphLiteErrorCode *error_out);
+
+/**
+ * Drop a graph and its data (`DROP GRAPH`)
+ *
+ * # Arguments
+ * * `db` - Database handle (must not be null)
+ * * `session_id` - C string with session ID (must not be null)
+ * * `graph_path` - Full graph path such as `/app/social`, or a graph name in the
+ *   session's current schema (must not be null)
+ * * `error_out` - Output parameter for error code (can be null)
+ *
+ * # Returns
+ * * Error code (Success = 0, `GraphNotFound` if the graph does not exist,
+ *   `ParseError` if `graph_path` is not a graph path)
+ *
+ * # Safety
+ * * `db` must be a valid handle from `graphlite_open`
+ * * `session_id` and `graph_path` must be valid null-terminated C strings
+ */
+enum GraphLiteErrorCode graphlite_drop_graph(struct GraphLiteDB *db,
+                                             const char *session_id,
+                                             const char *graph_path,
+                                             enum GraphLiteErrorCode *error_out);
+
+/**
+ * Set the current graph of a session (`SESSION SET GRAPH`)
+ *
+ * Same as `graphlite_session_set_graph`, for session IDs.
+ *
+ * # Arguments
+ * * `db` - Database handle (must not be null)
+ * * `session_id` - C string with session ID (must not be null)
+ * * `graph_path` - Full graph path such as `/app/social`, or a graph name in the
+ *   session's current schema (must not be null)
+ * * `error_out` - Output parameter for error code (can be null)
+ *
+ * # Returns
+ * * Error code (Success = 0, `GraphNotFound` if the graph does not exist,
+ *   `ParseError` if `graph_path` is not a graph path)
+ *
+ * # Safety
+ * * `db` must be a valid handle from `graphlite_open`
+ * * `session_id` and `graph_path` must be valid null-terminated C strings
+ */
+enum GraphLiteErrorCode graphlite_set_session_graph(struct GraphLiteDB *db,
+                                                    const char *session_id,
+                                                    const char *graph_path,
+                                                    enum GraphLiteErrorCode *error_out);
+
+/**
+ * Create a session for the given username and return a handle to it
+ *
+ * # Arguments
+ * * `db` - Database handle (must not be null)
+ * * `username` - C string with username (must not be null)
+ * * `error_out` - Output parameter for error code (can be null)
+ *
+ * # Returns
+ * * Session handle on success (must be closed with `graphlite_session_close`)
+ * * null pointer on error
+ *
+ * # Safety
+ * * `db` must be a valid handle from `graphlite_open`
+ * * `username` must be a valid null-terminated C string
+ *
+ * # Example
+ * ```c
+ * GraphLiteSession* session = graphlite_session_open(db, "admin", &error);
+ * graphlite_session_set_graph(db, session, "/app/social", &error);
+ * char* json = graphlite_session_query(db, session, "MATCH (n) RETURN n", &error);
+ * graphlite_free_string(json);
+ * graphlite_session_close(db, session, &error);
+ * ```
+ */
+struct GraphLiteSession *graphlite_session_open(struct GraphLiteDB *db,
+                                                const char *username,
+                                                enum GraphLiteErrorCode *error_out);
+
+/**
+ * Wrap an existing session ID in a session handle
+ *
+ * Lets callers that already hold a session ID from `graphlite_create_session`
+ * switch to the handle API. Both refer to the same session; closing the handle
+ * closes the session.
+ *
+ * # Arguments
+ * * `db` - Database handle (must not be null)
+ * * `session_id` - C string with session ID (must not be null)
+ * * `error_out` - Output parameter for error code (can be null)
+ *
+ * # Returns
+ * * Session handle on success (must be closed with `graphlite_session_close`)
+ * * null pointer on error (`SessionError` if the session does not exist)
+ *
+ * # Safety
+ * * `db` must be a valid handle from `graphlite_open`
+ * * `session_id` must be a valid null-terminated C string
+ */
+struct GraphLiteSession *graphlite_session_from_id(struct GraphLiteDB *db,
+                                                   const char *session_id,
+                                                   enum GraphLiteErrorCode *error_out);
+
+/**
+ * Get the ID of a session handle
+ *
+ * The ID can be passed to the functions that take a `session_id` string, such as
+ * `graphlite_bulk_insert_nodes`.
+ *
+ * # Returns
+ * * C string owned by the handle, valid until `graphlite_session_close` (must NOT be freed)
+ * * null pointer if `session` is null
+ *
+ * # Safety
+ * * `session` must be a valid handle from `graphlite_session_open`
+ */
+const char *graphlite_session_id(const struct GraphLiteSession *session);
+
+/**
+ * Execute a GQL query on a session handle and return results as JSON
+ *
+ * Same result format as `graphlite_query`.
+ *
+ * # Arguments
+ * * `db` - Database handle the session belongs to (must not be null)
+ * * `session` - Session handle (must not be null)
+ * * `query` - C string with GQL query (must not be null)
+ * * `error_out` - Output parameter for error code (can be null)
+ *
+ * # Returns
+ * * JSON string with query results on success (must be freed with `graphlite_free_string`)
+ * * null pointer on error
+ *
+ * # Safety
+ * * `db` must be a valid handle from `graphlite_open`
+ * * `session` must be a valid handle created from `db`
+ * * `query` must be a valid null-terminated C string
+ */
+char *graphlite_session_query(struct GraphLiteDB *db,
+                              struct GraphLiteSession *session,
+                              const char *query,
+                              enum GraphLiteErrorCode *error_out);
+
+/**
+ * Execute a GQL query on a session handle and return the result for reading row by row
+ *
+ * Same as `graphlite_query_result`, for session handles.
+ *
+ * # Arguments
+ * * `db` - Database handle the session belongs to (must not be null)
+ * * `session` - Session handle (must not be null)
+ * * `query` - C string with GQL query (must not be null)
+ * * `error_out` - Output parameter for error code (can be null)
+ *
+ * # Returns
+ * * Result handle on success (must be freed with `graphlite_result_free`)
+ * * null pointer on error
+ *
+ * # Safety
+ * * `db` must be a valid handle from `graphlite_open`
+ * * `session` must be a valid handle created from `db`
+ * * `query` must be a valid null-terminated C string
+ */
+struct GraphLiteResult *graphlite_session_query_result(struct GraphLiteDB *db,
+                                                       struct GraphLiteSession *session,
+                                                       const char *query,
+                                                       enum GraphLiteErrorCode *error_out);
+
+/**
+ * Set the current graph of a session (`SESSION SET GRAPH`)
+ *
+ * # Arguments
+ * * `db` - Database handle the session belongs to (must not be null)
+ * * `session` - Session handle (must not be null)
+ * * `graph_path` - Full graph path such as `/app/social`, or a graph name in the
+ *   current schema (must not be null)
+ * * `error_out` - Output parameter for error code (can be null)
+ *
+ * # Returns
+ * * Error code (Success = 0, `ParseError` if `graph_path` is not a graph path,
+ *   `GraphNotFound` if the graph does not exist)
+ *
+ * # Safety
+ * * `db` must be a valid handle from `graphlite_open`
+ * * `session` must be a valid handle created from `db`
+ * * `graph_path` must be a valid null-terminated C string
+ */
+enum GraphLiteErrorCode graphlite_session_set_graph(struct GraphLiteDB *db,
+                                                    struct GraphLiteSession *session,
+                                                    const char *graph_path,
+                                                    enum GraphLiteErrorCode *error_out);
+
+/**
+ * Set the current schema of a session (`SESSION SET SCHEMA`)
+ *
+ * # Arguments
+ * * `db` - Database handle the session belongs to (must not be null)
+ * * `session` - Session handle (must not be null)
+ * * `schema_path` - Schema path such as `/app` (must not be null)
+ * * `error_out` - Output parameter for error code (can be null)
+ *
+ * # Returns
+ * * Error code (Success = 0, `ParseError` if `schema_path` is not a schema path,
+ *   `GraphNotFound` if the schema does not exist)
+ *
+ * # Safety
+ * * `db` must be a valid handle from `graphlite_open`
+ * * `session` must be a valid handle created from `db`
+ * * `schema_path` must be a valid null-terminated C string
+ */
+enum GraphLiteErrorCode graphlite_session_set_schema(struct GraphLiteDB *db,
+                                                     struct GraphLiteSession *session,
+                                                     const char *schema_path,
+                                                     enum GraphLiteErrorCode *error_out);
+
+/**
+ * Get the current graph of a session
+ *
+ * # Returns
+ * * Graph path such as `/app/social`, owned by the handle and valid until the next
+ *   call with this session handle (must NOT be freed)
+ * * null pointer if no graph is set or `session` is null
+ *
+ * # Safety
+ * * `session` must be a valid handle from `graphlite_session_open`
+ */
+const char *graphlite_session_current_graph(struct GraphLiteSession *session);
+
+/**
+ * Get the current schema of a session
+ *
+ * # Returns
+ * * Schema path such as `/app`, owned by the handle and valid until the next call
+ *   with this session handle (must NOT be freed)
+ * * null pointer if no schema is set or `session` is null
+ *
+ * # Safety
+ * * `session` must be a valid handle from `graphlite_session_open`
+ */
+const char *graphlite_session_current_schema(struct GraphLiteSession *session);
+
+/**
+ * Evaluate console input on a session and return the output as text
+ *
+ * Accepts what a user types at the `graphlite gql` prompt: a GQL statement or
+ * one of these meta-commands:
+ * * `\dg` - list graphs
+ * * `\timing [on|off]` - show the execution time after each statement
+ * * `\x [on|off]` - expanded display, one line per column
+ * * `\?` - list the meta-commands
+ *
+ * Results are rendered as plain-text tables without colors. `\timing` and `\x`
+ * settings belong to the session handle and last until it is closed. This lets
+ * language bindings offer an interactive console by reading a line, calling
+ * `graphlite_eval` and printing the returned text.
+ *
+ * # Arguments
+ * * `db` - Database handle the session belongs to (must not be null)
+ * * `session` - Session handle (must not be null)
+ * * `text` - C string with a statement or meta-command (must not be null)
+ * * `error_out` - Output parameter for error code (can be null)
+ *
+ * # Returns
+ * * Rendered text (must be freed with `graphlite_free_string`). If the statement
+ *   or meta-command fails, the text holds the error message and `error_out` is set
+ *   to the failure's error code (`QueryError` for a malformed meta-command)
+ * * null pointer on invalid arguments
+ *
+ * # Safety
+ * * `db` must be a valid handle from `graphlite_open`
+ * * `session` must be a valid handle created from `db`
+ * * `text` must be a valid null-terminated C string
+ *
+ * # Example
+ * ```c
+ * char* out = graphlite_eval(db, session, "\\dg", &error);
+ * printf("%s", out);
+ * graphlite_free_string(out);
+ * ```
+ */
+char *graphlite_eval(struct GraphLiteDB *db,
+                     struct GraphLiteSession *session,
+                     const char *text,
+                     enum GraphLiteErrorCode *error_out);
+
+/**
+ * Close a session and free its handle
+ *
+ * The handle is freed even if closing the session fails.
+ *
+ * # Arguments
+ * * `db` - Database handle the session belongs to (must not be null)
+ * * `session` - Session handle (can be null, in which case this is a no-op)
+ * * `error_out` - Output parameter for error code (can be null)
+ *
+ * # Returns
+ * * Error code (Success = 0, error otherwise)
+ *
+ * # Safety
+ * * `db` must be a valid handle from `graphlite_open`
+ * * `session` must be a handle created from `db`, and must not be used afterwards
+ */
+enum GraphLiteErrorCode graphlite_session_close(struct GraphLiteDB *db,
+                                                struct GraphLiteSession *session,
+                                                enum GraphLiteErrorCode *error_out);
+
+/**
+ * Initialize GraphLite database from a UTF-16 path
+ *
+ * Same as `graphlite_open`, for hosts such as Windows and the JVM that hold
+ * strings as UTF-16 (`wchar_t` on Windows, `char[]` in Java).
+ *
+ * # Arguments
+ * * `path` - Null-terminated UTF-16 string with database path (must not be null)
+ * * `error_out` - Output parameter for error code (can be null)
+ *
+ * # Returns
+ * * Opaque handle to database on success
+ * * null pointer on error (`InvalidUtf8` if `path` has unpaired surrogates)
+ *
+ * # Safety
+ * * `path` must be a valid null-terminated UTF-16 string
+ * * Returned handle must be freed with `graphlite_close`
+ */
+struct GraphLiteDB *graphlite_open_w(const uint16_t *path, enum GraphLiteErrorCode *error_out);
+
+/**
+ * Create a simple session for a UTF-16 username
+ *
+ * Same as `graphlite_create_session`, with the session ID returned as UTF-16.
+ *
+ * # Arguments
+ * * `db` - Database handle (must not be null)
+ * * `username` - Null-terminated UTF-16 string with username (must not be null)
+ * * `error_out` - Output parameter for error code (can be null)
+ *
+ * # Returns
+ * * UTF-16 string with session ID on success (must be freed with `graphlite_free_wstring`)
+ * * null pointer on error
+ *
+ * # Safety
+ * * `db` must be a valid handle from `graphlite_open`
+ * * `username` must be a valid null-terminated UTF-16 string
+ * * Returned string must be freed with `graphlite_free_wstring`
+ */
+uint16_t *graphlite_create_session_w(struct GraphLiteDB *db,
+                                     const uint16_t *username,
+                                     enum GraphLiteErrorCode *error_out);
+
+/**
+ * Execute a UTF-16 GQL query and return results as UTF-16 JSON
+ *
+ * Same as `graphlite_query`, with the JSON document returned as UTF-16.
+ *
+ * # Arguments
+ * * `db` - Database handle (must not be null)
+ * * `session_id` - Null-terminated UTF-16 string with session ID (must not be null)
+ * * `query` - Null-terminated UTF-16 string with GQL query (must not be null)
+ * * `error_out` - Output parameter for error code (can be null)
+ *
+ * # Returns
+ * * UTF-16 JSON string with query results on success (must be freed with `graphlite_free_wstring`)
+ * * null pointer on error
+ *
+ * # Safety
+ * * `db` must be a valid handle from `graphlite_open`
+ * * `session_id` and `query` must be valid null-terminated UTF-16 strings
+ * * Returned string must be freed with `graphlite_free_wstring`
+ */
+uint16_t *graphlite_query_w(struct GraphLiteDB *db,
+                            const uint16_t *session_id,
+                            const uint16_t *query,
+                            enum GraphLiteErrorCode *error_out);
+
+/**
+ * Execute a UTF-16 GQL query on a session handle and return results as UTF-16 JSON
+ *
+ * Same as `graphlite_session_query`, with the JSON document returned as UTF-16.
+ *
+ * # Arguments
+ * * `db` - Database handle the session belongs to (must not be null)
+ * * `session` - Session handle (must not be null)
+ * * `query` - Null-terminated UTF-16 string with GQL query (must not be null)
+ * * `error_out` - Output parameter for error code (can be null)
+ *
+ * # Returns
+ * * UTF-16 JSON string with query results on success (must be freed with `graphlite_free_wstring`)
+ * * null pointer on error
+ *
+ * # Safety
+ * * `db` must be a valid handle from `graphlite_open`
+ * * `session` must be a valid handle created from `db`
+ * * `query` must be a valid null-terminated UTF-16 string
+ * * Returned string must be freed with `graphlite_free_wstring`
+ */
+uint16_t *graphlite_session_query_w(struct GraphLiteDB *db,
+                                    struct GraphLiteSession *session,
+                                    const uint16_t *query,
+                                    enum GraphLiteErrorCode *error_out);
+
+/**
+ * Back up an open database to a new directory
+ *
+ * The engine copies its data into `dest_path` itself, so the database stays
+ * open and usable and no files are copied from under it. The backup is a
+ * complete database directory: open it with `graphlite_open` or copy it into
+ * place with `graphlite_restore`. Fails while any session has an open
+ * transaction.
+ *
+ * # Arguments
+ * * `db` - Database handle (must not be null)
+ * * `dest_path` - C string with the backup directory; must not exist or be empty
+ *   (must not be null)
+ * * `error_out` - Output parameter for error code (can be null)
+ *
+ * # Returns
+ * * Error code (Success = 0, `BackupError` if the backup could not be written)
+ *
+ * # Safety
+ * * `db` must be a valid handle from `graphlite_open`
+ * * `dest_path` must be a valid null-terminated C string
+ */
+enum GraphLiteErrorCode graphlite_backup(struct GraphLiteDB *db,
+                                         const char *dest_path,
+                                         enum GraphLiteErrorCode *error_out);
+
+/**
+ * Restore a backup into a new database directory
+ *
+ * Copies the backup at `backup_path` (written by `graphlite_backup`) into
+ * `dest_db_path`, which can then be opened with `graphlite_open`. No database
+ * handle is needed; the backup itself must not be open.
+ *
+ * # Arguments
+ * * `backup_path` - C string with the backup directory (must not be null)
+ * * `dest_db_path` - C string with the new database directory; must not exist or be
+ *   empty (must not be null)
+ * * `error_out` - Output parameter for error code (can be null)
+ *
+ * # Returns
+ * * Error code (Success = 0, `BackupError` if the backup could not be restored)
+ *
+ * # Safety
+ * * `backup_path` and `dest_db_path` must be valid null-terminated C strings
+ *
+ * # Example
+ * ```c
+ * graphlite_backup(db, "/backups/app-db", &error);
+ * // ... later, e.g. after data loss
+ * graphlite_restore("/backups/app-db", "/data/app-db-restored", &error);
+ * GraphLiteDB* restored = graphlite_open("/data/app-db-restored", &error);
+ * ```
+ */
+enum GraphLiteErrorCode graphlite_restore(const char *backup_path,
+                                          const char *dest_db_path,
+                                          enum GraphLiteErrorCode *error_out);
+
+/**
+ * Free a string returned by GraphLite FFI functions
+ *
+ * # Arguments
+ * * `s` - C string to free (can be null, in which case this is a no-op)
  *
  * # Safety
  * * `s` must be a string returned by a GraphLite FFI function
@@ -170,6 +1999,45 @@ enum GraphLiteErrorCode graphlite_close_session(struct GraphLiteDB *db,
  */
 void graphlite_free_string(char *s);
 
+/**
+ * Free a string array returned by GraphLite FFI functions
+ *
+ * # Arguments
+ * * `array` - Array to free (can be null, in which case this is a no-op)
+ * * `count` - Number of strings reported when the array was returned
+ *
+ * # Safety
+ * * `array` must be an array returned by a GraphLite FFI function, such as
+ *   `graphlite_list_graphs`, with its reported count
+ * * Must not be called more than once on the same array
+ */
+void graphlite_free_string_array(char **array, uintptr_t count);
+
+/**
+ * Free a buffer returned by GraphLite FFI functions
+ *
+ * # Arguments
+ * * `buf` - Buffer to free (can be null, in which case this is a no-op)
+ * * `len` - Length reported when the buffer was returned
+ *
+ * # Safety
+ * * `buf` must be a buffer returned by a GraphLite FFI function, with its reported length
+ * * Must not be called more than once on the same buffer
+ */
+void graphlite_free_buffer(uint8_t *buf, uintptr_t len);
+
+/**
+ * Free a UTF-16 string returned by the `_w` GraphLite FFI functions
+ *
+ * # Arguments
+ * * `s` - UTF-16 string to free (can be null, in which case this is a no-op)
+ *
+ * # Safety
+ * * `s` must be a string returned by a `_w` GraphLite FFI function
+ * * Must not be called more than once on the same string
+ */
+void graphlite_free_wstring(uint16_t *s);
+
 /**
  * Close database connection and free resources
  *
@@ -180,9 +2048,43 @@ void graphlite_free_string(char *s);
  * * `db` must be a handle from `graphlite_open`
  * * Must not be called more than once on the same handle
  * * Must not be used after calling this function
+ * * Must not race with other calls on the handle, even for `graphlite_open_shared` handles
  */
 void graphlite_close(struct GraphLiteDB *db);
 
+/**
+ * Get the counts of strings, buffers and handles handed out and freed
+ *
+ * Binding test suites can compare the outstanding counts before and after a
+ * test to find leaks. The counts cover the whole process, not one database.
+ *
+ * # Example
+ * ```c
+ * GraphLiteAllocationStats before = graphlite_allocation_stats();
+ * // ... run a test ...
+ * GraphLiteAllocationStats after = graphlite_allocation_stats();
+ * assert(after.outstanding_strings == before.outstanding_strings);
+ * ```
+ */
+struct GraphLiteAllocationStats graphlite_allocation_stats(void);
+
+/**
+ * Enable allocation tracking, a debug mode for binding authors
+ *
+ * With tracking enabled the library remembers the address of everything it hands
+ * out. Freeing a pointer twice, freeing a pointer GraphLite did not return, or
+ * freeing it with the wrong function is logged as an error, counted in
+ * `invalid_frees` and otherwise ignored. `graphlite_close` logs a warning listing
+ * whatever is still outstanding.
+ *
+ * Tracking can only be enabled while nothing is outstanding, so call this before
+ * opening the first database. It stays enabled until the library is unloaded.
+ *
+ * # Returns
+ * * true if tracking is enabled
+ */
+bool graphlite_enable_allocation_tracking(void);
+
 /**
  * Get the version string of GraphLite
  *
@@ -192,4 +2094,35 @@ void graphlite_close(struct GraphLiteDB *db);
  */
 const char *graphlite_version(void);
 
+/**
+ * Get the ABI version of the loaded library
+ *
+ * Bindings that load the library at runtime should compare this with the
+ * `GRAPHLITE_ABI_VERSION` they were written against before calling anything else.
+ *
+ * # Returns
+ * * `GRAPHLITE_ABI_VERSION` of the compiled library
+ */
+uint32_t graphlite_abi_version(void);
+
+/**
+ * Check whether the loaded library supports an optional feature
+ *
+ * Feature names are matched case-insensitively: `sled`, `memory`, `msgpack`,
+ * `bulk-insert`, `csv-import`, `backup`, `session-handles`, `eval`, `row-accessors`,
+ * `transactions`, `graph-management`, `wide-strings`, `allocation-stats`,
+ * `random-access` and `log-callback`. Any other name, including features this
+ * build does not provide such as `arrow`, `vectors` or `full-text`, returns false.
+ *
+ * # Arguments
+ * * `name` - C string with the feature name (null returns false)
+ *
+ * # Returns
+ * * true if the feature is available
+ *
+ * # Safety
+ * * `name` must be null or a valid null-terminated C string
+ */
+bool graphlite_has_feature(const char *name);
+
 #endif  /* GRAPHLITE_H */
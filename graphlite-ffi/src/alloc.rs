@@ -0,0 +1,173 @@
+//! Accounting of the memory handed to the host
+//!
+//! Every string, buffer and handle returned across the FFI is counted when it is
+//! handed out and again when it comes back to its free function, which is what
+//! `graphlite_allocation_stats` reports. With tracking enabled the addresses are
+//! kept as well: freeing something twice, or freeing a pointer with the wrong
+//! free function, is then reported and ignored instead of corrupting the heap,
+//! and whatever is still outstanding is logged when a database is closed.
+
+use std::collections::BTreeMap;
+use std::sync::{Mutex, MutexGuard};
+
+/// What an allocation handed to the host is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Kind {
+    String,
+    WideString,
+    StringArray,
+    Buffer,
+    Database,
+    Session,
+    Result,
+}
+
+const KINDS: [Kind; 7] = [
+    Kind::String,
+    Kind::WideString,
+    Kind::StringArray,
+    Kind::Buffer,
+    Kind::Database,
+    Kind::Session,
+    Kind::Result,
+];
+
+impl Kind {
+    fn name(self) -> &'static str {
+        match self {
+            Kind::String => "strings",
+            Kind::WideString => "UTF-16 strings",
+            Kind::StringArray => "string arrays",
+            Kind::Buffer => "buffers",
+            Kind::Database => "database handles",
+            Kind::Session => "session handles",
+            Kind::Result => "result handles",
+        }
+    }
+}
+
+/// Snapshot of the ledger
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct Stats {
+    pub outstanding: [u64; KINDS.len()],
+    pub allocated: u64,
+    pub freed: u64,
+    pub invalid_frees: u64,
+    pub tracking: bool,
+}
+
+impl Stats {
+    pub(crate) fn outstanding(&self, kind: Kind) -> u64 {
+        self.outstanding[kind as usize]
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct Ledger {
+    state: Mutex<(Stats, BTreeMap<usize, Kind>)>,
+}
+
+impl Ledger {
+    pub(crate) const fn new() -> Self {
+        Self {
+            state: Mutex::new((
+                Stats {
+                    outstanding: [0; KINDS.len()],
+                    allocated: 0,
+                    freed: 0,
+                    invalid_frees: 0,
+                    tracking: false,
+                },
+                BTreeMap::new(),
+            )),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, (Stats, BTreeMap<usize, Kind>)> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Count `ptr` as handed to the host
+    pub(crate) fn track<T>(&self, ptr: *mut T, kind: Kind) -> *mut T {
+        if !ptr.is_null() {
+            let (stats, live) = &mut *self.lock();
+            stats.outstanding[kind as usize] += 1;
+            stats.allocated += 1;
+            if stats.tracking {
+                live.insert(ptr as usize, kind);
+            }
+        }
+        ptr
+    }
+
+    /// Count `ptr` as given back; false if tracking knows it is not a live `kind`,
+    /// in which case it must not be freed
+    pub(crate) fn untrack<T>(&self, ptr: *const T, kind: Kind) -> bool {
+        let (stats, live) = &mut *self.lock();
+        if stats.tracking {
+            match live.get(&(ptr as usize)) {
+                Some(found) if *found == kind => {
+                    live.remove(&(ptr as usize));
+                }
+                Some(found) => {
+                    stats.invalid_frees += 1;
+                    log::error!(
+                        "GraphLite FFI: {:p} is one of the {}, but was freed as one of the {}",
+                        ptr,
+                        found.name(),
+                        kind.name()
+                    );
+                    return false;
+                }
+                None => {
+                    stats.invalid_frees += 1;
+                    log::error!(
+                        "GraphLite FFI: {:p} was freed twice or not returned by GraphLite ({})",
+                        ptr,
+                        kind.name()
+                    );
+                    return false;
+                }
+            }
+        }
+        let outstanding = &mut stats.outstanding[kind as usize];
+        *outstanding = outstanding.saturating_sub(1);
+        stats.freed += 1;
+        true
+    }
+
+    /// Start tracking addresses; only possible while nothing is outstanding
+    pub(crate) fn enable_tracking(&self) -> bool {
+        let (stats, _) = &mut *self.lock();
+        if !stats.tracking && stats.outstanding.iter().all(|count| *count == 0) {
+            stats.tracking = true;
+        }
+        stats.tracking
+    }
+
+    pub(crate) fn stats(&self) -> Stats {
+        self.lock().0
+    }
+
+    /// Log the allocations still outstanding, if tracking is enabled
+    pub(crate) fn report_leaks(&self) {
+        let stats = self.stats();
+        if !stats.tracking {
+            return;
+        }
+        let leaks: Vec<String> = KINDS
+            .iter()
+            .filter(|kind| stats.outstanding(**kind) > 0)
+            .map(|kind| format!("{} {}", stats.outstanding(*kind), kind.name()))
+            .collect();
+        if !leaks.is_empty() {
+            log::warn!(
+                "GraphLite FFI: still outstanding after graphlite_close: {}",
+                leaks.join(", ")
+            );
+        }
+    }
+}
+
+/// Ledger of everything the library hands out
+pub(crate) static LEDGER: Ledger = Ledger::new();
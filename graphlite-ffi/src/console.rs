@@ -0,0 +1,193 @@
+//! Text console behind `graphlite_eval`
+//!
+//! Runs GQL statements and the REPL meta-commands on a session and renders the
+//! outcome as plain text (no colors or escape codes), so language bindings can
+//! offer an interactive console by printing whatever comes back.
+
+use crate::GraphLiteErrorCode;
+use comfy_table::{presets::UTF8_FULL, Cell, CellAlignment, Table};
+use graphlite::{QueryCoordinator, QueryResult, SessionHandle, Value};
+use std::time::Instant;
+
+/// Marker shown for NULL values, as in the CLI
+const NULL_MARKER: &str = "∅";
+
+const HELP: &str = "\
+Meta-commands:
+  \\dg               List graphs
+  \\timing [on|off]  Toggle display of statement execution time
+  \\x [on|off]       Toggle expanded display (one line per column)
+  \\?                Show this help
+Anything else is run as a GQL statement.
+";
+
+/// Display settings of one console, kept on the session handle
+#[derive(Debug, Default)]
+pub(crate) struct Console {
+    timing: bool,
+    expanded: bool,
+}
+
+impl Console {
+    /// Run a GQL statement or meta-command and render the outcome
+    ///
+    /// Returns the rendered text, or the rendered error message and its error code
+    /// if the statement or meta-command failed.
+    pub(crate) fn eval(
+        &mut self,
+        coordinator: &QueryCoordinator,
+        session: &SessionHandle,
+        text: &str,
+    ) -> Result<String, (String, GraphLiteErrorCode)> {
+        let text = text.trim();
+        if text.is_empty() {
+            return Ok(String::new());
+        }
+        match text.strip_prefix('\\') {
+            Some(command) => self.meta_command(coordinator, session, command),
+            None => self.run(coordinator, session, text),
+        }
+    }
+
+    fn meta_command(
+        &mut self,
+        coordinator: &QueryCoordinator,
+        session: &SessionHandle,
+        command: &str,
+    ) -> Result<String, (String, GraphLiteErrorCode)> {
+        let mut parts = command.split_whitespace();
+        match (parts.next(), parts.next(), parts.next()) {
+            (Some("dg"), None, None) => self.run(coordinator, session, "CALL gql.list_graphs()"),
+            (Some("timing"), mode, None) => {
+                self.timing = toggle(self.timing, mode, "\\timing [on|off]").map_err(usage)?;
+                Ok(format!("Timing is {}.\n", on_off(self.timing)))
+            }
+            (Some("x"), mode, None) => {
+                self.expanded = toggle(self.expanded, mode, "\\x [on|off]").map_err(usage)?;
+                Ok(format!("Expanded display is {}.\n", on_off(self.expanded)))
+            }
+            (Some("?"), None, None) => Ok(HELP.to_string()),
+            _ => Err(usage(format!(
+                "ERROR: Unknown command: \\{}. Type \\? for help\n",
+                command
+            ))),
+        }
+    }
+
+    fn run(
+        &self,
+        coordinator: &QueryCoordinator,
+        session: &SessionHandle,
+        statement: &str,
+    ) -> Result<String, (String, GraphLiteErrorCode)> {
+        let started = Instant::now();
+        let result = coordinator.process_query_with_handle(statement, session);
+        let elapsed = started.elapsed();
+
+        let mut output = match &result {
+            Ok(result) => self.render(result),
+            Err(e) => format!("ERROR: {}\n", e),
+        };
+        if self.timing {
+            output.push_str(&format!("Time: {:.3} ms\n", elapsed.as_secs_f64() * 1000.0));
+        }
+        match result {
+            Ok(_) => Ok(output),
+            Err(e) => Err((output, GraphLiteErrorCode::from_query_error(&e))),
+        }
+    }
+
+    fn render(&self, result: &QueryResult) -> String {
+        if let Some(message) = result.get_session_message() {
+            return format!("{}\n", message);
+        }
+
+        let mut output = if self.expanded {
+            render_expanded(result)
+        } else if result.variables.is_empty() {
+            String::new()
+        } else {
+            render_table(result)
+        };
+        output.push_str(&format!(
+            "({} row{})\n",
+            result.rows.len(),
+            if result.rows.len() == 1 { "" } else { "s" }
+        ));
+        for warning in &result.warnings {
+            output.push_str(&format!("WARNING: {}\n", warning));
+        }
+        output
+    }
+}
+
+/// New value of an on/off setting: `None` toggles it
+fn toggle(current: bool, mode: Option<&str>, usage: &str) -> Result<bool, String> {
+    match mode {
+        None => Ok(!current),
+        Some("on") => Ok(true),
+        Some("off") => Ok(false),
+        Some(_) => Err(format!("ERROR: Usage: {}\n", usage)),
+    }
+}
+
+/// Error of a malformed meta-command
+fn usage(text: String) -> (String, GraphLiteErrorCode) {
+    (text, GraphLiteErrorCode::QueryError)
+}
+
+fn on_off(value: bool) -> &'static str {
+    if value {
+        "on"
+    } else {
+        "off"
+    }
+}
+
+fn render_table(result: &QueryResult) -> String {
+    let mut table = Table::new();
+    table.load_preset(UTF8_FULL);
+    table.set_header(result.variables.iter().map(Cell::new));
+    for row in &result.rows {
+        table.add_row(result.variables.iter().map(|col| {
+            let cell = Cell::new(value_text(row.get_value(col)));
+            match row.get_value(col) {
+                Some(Value::Number(_)) => cell.set_alignment(CellAlignment::Right),
+                _ => cell,
+            }
+        }));
+    }
+    format!("{}\n", table)
+}
+
+fn render_expanded(result: &QueryResult) -> String {
+    let name_width = result
+        .variables
+        .iter()
+        .map(|col| col.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    let mut output = String::new();
+    for (index, row) in result.rows.iter().enumerate() {
+        output.push_str(&format!("-[ RECORD {} ]\n", index + 1));
+        for col in &result.variables {
+            output.push_str(&format!(
+                "{:<width$} | {}\n",
+                col,
+                value_text(row.get_value(col)),
+                width = name_width
+            ));
+        }
+    }
+    output
+}
+
+/// Displayed text of a value; strings are shown without quotes
+fn value_text(value: Option<&Value>) -> String {
+    match value {
+        None | Some(Value::Null) => NULL_MARKER.to_string(),
+        Some(Value::String(s)) => s.clone(),
+        Some(value) => value.to_string(),
+    }
+}
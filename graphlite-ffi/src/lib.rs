@@ -14,14 +14,31 @@
 //! - Pointers are valid and non-null (unless documented otherwise)
 //! - Returned strings are freed with `graphlite_free_string`
 //! - Database handles are closed with `graphlite_close`
-//! - No concurrent access to the same handle without synchronization
+//! - Session handles are closed with `graphlite_session_close`
+//! - No concurrent access to the same handle without synchronization, unless the
+//!   handle was opened with `graphlite_open_shared`
 
-use graphlite::QueryCoordinator;
-use std::ffi::{CStr, CString};
+mod alloc;
+mod console;
+mod logging;
+mod rows;
+
+use alloc::{Kind, LEDGER};
+use console::Console;
+use graphlite::{
+    BulkEdge, BulkNode, ContentEncoding, CsvImportOptions, DatabaseConfig, Notation, NumberFormat,
+    QueryCoordinator, QueryErrorKind, QueryLimits, QueryResult, SessionHandle, StorageType, Value,
+};
+pub use logging::GraphLiteLogCallback;
+use rows::{CellError, EdgeField};
+pub use rows::{GraphLiteResult, GraphLiteRow, GraphLiteValueType};
+use std::collections::HashMap;
+use std::ffi::{c_void, CStr, CString};
 use std::os::raw::c_char;
 use std::panic::{self, AssertUnwindSafe};
 use std::ptr;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
 
 /// Opaque handle to a GraphLite database instance
 ///
@@ -29,9 +46,43 @@ use std::sync::Arc;
 #[repr(C)]
 pub struct GraphLiteDB {
     coordinator: Arc<QueryCoordinator>,
+    /// Serializes calls on handles opened with `graphlite_open_shared`
+    shared_lock: Option<Mutex<()>>,
+    /// How result JSON writes numbers, set with `graphlite_set_number_format`
+    number_format: Mutex<NumberFormat>,
+}
+
+impl GraphLiteDB {
+    fn new(coordinator: Arc<QueryCoordinator>) -> Self {
+        Self {
+            coordinator,
+            shared_lock: None,
+            number_format: Mutex::new(NumberFormat::default()),
+        }
+    }
+
+    fn number_format(&self) -> NumberFormat {
+        *self
+            .number_format
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Take the handle lock for the duration of a call (no-op for unshared handles)
+    ///
+    /// A panic while the lock is held is already reported as `PanicError`, so a
+    /// poisoned lock is recovered rather than failing every later call.
+    fn lock_shared(&self) -> Option<MutexGuard<'_, ()>> {
+        self.shared_lock
+            .as_ref()
+            .map(|lock| lock.lock().unwrap_or_else(|poisoned| poisoned.into_inner()))
+    }
 }
 
 /// Error codes returned by FFI functions
+///
+/// Failed statements report the most specific code that applies (`ParseError`,
+/// `GraphNotFound`, ...); `QueryError` covers every other query failure.
 #[repr(C)]
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum GraphLiteErrorCode {
@@ -39,7 +90,7 @@ pub enum GraphLiteErrorCode {
     Success = 0,
     /// Null pointer was passed
     NullPointer = 1,
-    /// Invalid UTF-8 string
+    /// Invalid UTF-8 string, or UTF-16 string with unpaired surrogates
     InvalidUtf8 = 2,
     /// Failed to open database
     DatabaseOpenError = 3,
@@ -51,6 +102,63 @@ pub enum GraphLiteErrorCode {
     PanicError = 6,
     /// JSON serialization failed
     JsonError = 7,
+    /// Statement was not executed because an earlier statement in its batch failed
+    NotExecuted = 8,
+    /// Binary (MessagePack) serialization failed
+    SerializationError = 9,
+    /// Backup or restore failed
+    BackupError = 10,
+    /// The statement could not be parsed
+    ParseError = 11,
+    /// The statement conflicts with existing data, e.g. a graph or schema that already exists
+    ConstraintViolation = 12,
+    /// The session may not perform the operation, or the database is read-only
+    PermissionDenied = 13,
+    /// Transaction state prevents the operation, e.g. COMMIT without an open transaction
+    TransactionConflict = 14,
+    /// The graph, or another catalog object such as a schema, does not exist
+    GraphNotFound = 15,
+    /// The query ran longer than the handle's query timeout
+    Timeout = 16,
+    /// A row value has another type than the accessor reads
+    TypeMismatch = 17,
+    /// A row, column or label index is out of range
+    OutOfRange = 18,
+    /// The database volume is low on space, so writes are rejected; reads still work
+    DiskFull = 19,
+}
+
+impl GraphLiteErrorCode {
+    /// Code for a failed statement, chosen from the coordinator's error message
+    fn from_query_error(message: &str) -> Self {
+        // A closed or unknown session is a session problem, not a missing graph
+        if message.starts_with("Session not found") {
+            return GraphLiteErrorCode::SessionError;
+        }
+        match QueryErrorKind::from_message(message) {
+            QueryErrorKind::Syntax => GraphLiteErrorCode::ParseError,
+            QueryErrorKind::ConstraintViolation => GraphLiteErrorCode::ConstraintViolation,
+            QueryErrorKind::PermissionDenied => GraphLiteErrorCode::PermissionDenied,
+            QueryErrorKind::Transaction => GraphLiteErrorCode::TransactionConflict,
+            QueryErrorKind::NotFound => GraphLiteErrorCode::GraphNotFound,
+            QueryErrorKind::Timeout => GraphLiteErrorCode::Timeout,
+            QueryErrorKind::DiskFull => GraphLiteErrorCode::DiskFull,
+            QueryErrorKind::Validation
+            | QueryErrorKind::Authentication
+            | QueryErrorKind::LimitExceeded
+            | QueryErrorKind::Storage
+            | QueryErrorKind::Other => GraphLiteErrorCode::QueryError,
+        }
+    }
+}
+
+impl From<CellError> for GraphLiteErrorCode {
+    fn from(error: CellError) -> Self {
+        match error {
+            CellError::OutOfRange => GraphLiteErrorCode::OutOfRange,
+            CellError::TypeMismatch => GraphLiteErrorCode::TypeMismatch,
+        }
+    }
 }
 
 /// Initialize GraphLite database from path
@@ -104,7 +212,271 @@ pub unsafe extern "C" fn graphlite_open(
         match QueryCoordinator::from_path(path_str) {
             Ok(coordinator) => {
                 set_error(error_out, GraphLiteErrorCode::Success);
-                Box::into_raw(Box::new(GraphLiteDB { coordinator }))
+                LEDGER.track(
+                    Box::into_raw(Box::new(GraphLiteDB::new(coordinator))),
+                    Kind::Database,
+                )
+            }
+            Err(_) => {
+                set_error(error_out, GraphLiteErrorCode::DatabaseOpenError);
+                ptr::null_mut()
+            }
+        }
+    }));
+
+    match result {
+        Ok(ptr) => ptr,
+        Err(_) => {
+            set_error(error_out, GraphLiteErrorCode::PanicError);
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Initialize GraphLite database from path with a handle that is safe to share between threads
+///
+/// Behaves like `graphlite_open`, but every call on the returned handle takes an internal
+/// lock, so host threads may call `graphlite_query` (and the other handle functions)
+/// concurrently without their own synchronization. Calls on the same handle run one at a
+/// time; open separate handles on separate databases for parallel execution.
+///
+/// # Arguments
+/// * `path` - C string with database path (must not be null)
+/// * `error_out` - Output parameter for error code (can be null if caller doesn't need it)
+///
+/// # Returns
+/// * Opaque handle to database on success
+/// * null pointer on error (check `error_out` for details)
+///
+/// # Safety
+/// * `path` must be a valid null-terminated C string
+/// * Returned handle must be freed with `graphlite_close` once no other thread is using it
+#[no_mangle]
+pub unsafe extern "C" fn graphlite_open_shared(
+    path: *const c_char,
+    error_out: *mut GraphLiteErrorCode,
+) -> *mut GraphLiteDB {
+    let db = unsafe { graphlite_open(path, error_out) };
+    if !db.is_null() {
+        unsafe { (*db).shared_lock = Some(Mutex::new(())) };
+    }
+    db
+}
+
+/// Storage backend selection for `GraphLiteConfig`
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum GraphLiteStorageBackend {
+    /// Sled embedded database on disk (default)
+    Sled = 0,
+    /// In-memory storage; nothing is persisted after close
+    Memory = 1,
+}
+
+/// Log level selection for `GraphLiteConfig`
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum GraphLiteLogLevel {
+    /// Leave the process-wide logger as it is (default)
+    Unchanged = 0,
+    Off = 1,
+    Error = 2,
+    Warn = 3,
+    Info = 4,
+    Debug = 5,
+    Trace = 6,
+}
+
+impl GraphLiteLogLevel {
+    fn filter(self) -> Option<log::LevelFilter> {
+        match self {
+            GraphLiteLogLevel::Unchanged => None,
+            GraphLiteLogLevel::Off => Some(log::LevelFilter::Off),
+            GraphLiteLogLevel::Error => Some(log::LevelFilter::Error),
+            GraphLiteLogLevel::Warn => Some(log::LevelFilter::Warn),
+            GraphLiteLogLevel::Info => Some(log::LevelFilter::Info),
+            GraphLiteLogLevel::Debug => Some(log::LevelFilter::Debug),
+            GraphLiteLogLevel::Trace => Some(log::LevelFilter::Trace),
+        }
+    }
+}
+
+/// Engine configuration for `graphlite_open_with_config`
+///
+/// Obtain a populated instance with `graphlite_config_default` and override
+/// the fields you need; zero-valued sizes mean "use the engine default".
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct GraphLiteConfig {
+    /// Cache memory budget in bytes (0 = engine default)
+    pub cache_size_bytes: u64,
+    /// Reject statements that modify data or the catalog
+    pub read_only: bool,
+    /// Persistent storage backend
+    pub storage_backend: GraphLiteStorageBackend,
+    /// Upper bound on engine-managed memory in bytes (0 = unlimited)
+    pub max_memory_bytes: u64,
+    /// Maximum log level
+    pub log_level: GraphLiteLogLevel,
+}
+
+impl Default for GraphLiteConfig {
+    fn default() -> Self {
+        Self {
+            cache_size_bytes: 0,
+            read_only: false,
+            storage_backend: GraphLiteStorageBackend::Sled,
+            max_memory_bytes: 0,
+            log_level: GraphLiteLogLevel::Unchanged,
+        }
+    }
+}
+
+impl From<&GraphLiteConfig> for DatabaseConfig {
+    fn from(config: &GraphLiteConfig) -> Self {
+        let non_zero =
+            |bytes: u64| (bytes > 0).then(|| usize::try_from(bytes).unwrap_or(usize::MAX));
+
+        DatabaseConfig {
+            storage_type: match config.storage_backend {
+                GraphLiteStorageBackend::Sled => StorageType::Sled,
+                GraphLiteStorageBackend::Memory => StorageType::Memory,
+            },
+            read_only: config.read_only,
+            cache_size_bytes: non_zero(config.cache_size_bytes),
+            max_memory_bytes: non_zero(config.max_memory_bytes),
+            log_level: config.log_level.filter(),
+            ..DatabaseConfig::default()
+        }
+    }
+}
+
+/// Get a configuration populated with the engine defaults
+///
+/// # Returns
+/// * `GraphLiteConfig` equivalent to what `graphlite_open` uses
+///
+/// # Example
+/// ```c
+/// GraphLiteConfig config = graphlite_config_default();
+/// config.read_only = true;
+/// config.cache_size_bytes = 64 * 1024 * 1024;
+/// ```
+#[no_mangle]
+pub extern "C" fn graphlite_config_default() -> GraphLiteConfig {
+    GraphLiteConfig::default()
+}
+
+/// Route GraphLite's log output to a host callback
+///
+/// Without a callback, log records go to the default logger, which writes to
+/// stderr. With one, every record at or below the maximum level is passed to
+/// `callback` instead, so it ends up in the host's logging system. The callback
+/// is process-wide and replaces any callback registered before. Register it
+/// before opening a database, as the process can have only one logger: if some
+/// other logger was installed first (for example by a `graphlite_open_with_config`
+/// call with a `log_level`), registration fails.
+///
+/// # Arguments
+/// * `callback` - Function receiving the level, target (the engine module that
+///   logged) and message of each record; the strings are only valid during the
+///   call. Null discards log records from now on.
+/// * `max_level` - Most verbose level passed to the callback (`Unchanged` keeps
+///   the current maximum, which is `Off` until one is set)
+/// * `user_data` - Passed to every call of `callback` (can be null)
+///
+/// # Returns
+/// * true if the callback was registered
+/// * false if another logger was installed in the process first
+///
+/// # Safety
+/// * `callback` may be called from any thread, concurrently, until it is
+///   replaced; it and `user_data` must stay valid and thread-safe until then
+///
+/// # Example
+/// ```c
+/// void on_log(GraphLiteLogLevel level, const char* target, const char* message, void* user_data) {
+///     fprintf((FILE*)user_data, "[%d] %s: %s\n", level, target, message);
+/// }
+///
+/// graphlite_set_log_callback(on_log, Info, app_log_file);
+/// GraphLiteDB* db = graphlite_open("./mydb", &error);
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn graphlite_set_log_callback(
+    callback: GraphLiteLogCallback,
+    max_level: GraphLiteLogLevel,
+    user_data: *mut c_void,
+) -> bool {
+    if !logging::set_callback(callback, user_data) {
+        return false;
+    }
+    if let Some(level) = max_level.filter() {
+        log::set_max_level(level);
+    }
+    true
+}
+
+/// Initialize GraphLite database from path with an explicit configuration
+///
+/// # Arguments
+/// * `path` - C string with database path (must not be null)
+/// * `config` - Engine configuration (can be null to use defaults)
+/// * `error_out` - Output parameter for error code (can be null if caller doesn't need it)
+///
+/// # Returns
+/// * Opaque handle to database on success
+/// * null pointer on error (check `error_out` for details)
+///
+/// # Safety
+/// * `path` must be a valid null-terminated C string
+/// * `config` must be null or point to a valid `GraphLiteConfig`
+/// * Returned handle must be freed with `graphlite_close`
+///
+/// # Example
+/// ```c
+/// GraphLiteConfig config = graphlite_config_default();
+/// config.read_only = true;
+/// GraphLiteErrorCode error;
+/// GraphLiteDB* db = graphlite_open_with_config("/path/to/db", &config, &error);
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn graphlite_open_with_config(
+    path: *const c_char,
+    config: *const GraphLiteConfig,
+    error_out: *mut GraphLiteErrorCode,
+) -> *mut GraphLiteDB {
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        // Check for null pointer
+        if path.is_null() {
+            set_error(error_out, GraphLiteErrorCode::NullPointer);
+            return ptr::null_mut();
+        }
+
+        // Convert C string to Rust string
+        let c_str = unsafe { CStr::from_ptr(path) };
+        let path_str = match c_str.to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                set_error(error_out, GraphLiteErrorCode::InvalidUtf8);
+                return ptr::null_mut();
+            }
+        };
+
+        let config = if config.is_null() {
+            GraphLiteConfig::default()
+        } else {
+            unsafe { *config }
+        };
+
+        // Create QueryCoordinator
+        match QueryCoordinator::from_path_with_config(path_str, DatabaseConfig::from(&config)) {
+            Ok(coordinator) => {
+                set_error(error_out, GraphLiteErrorCode::Success);
+                LEDGER.track(
+                    Box::into_raw(Box::new(GraphLiteDB::new(coordinator))),
+                    Kind::Database,
+                )
             }
             Err(_) => {
                 set_error(error_out, GraphLiteErrorCode::DatabaseOpenError);
@@ -122,6 +494,182 @@ pub unsafe extern "C" fn graphlite_open(
     }
 }
 
+/// Set per-query resource limits on a database handle
+///
+/// Every query run through the handle afterwards fails with `QueryError` when it
+/// returns more than `max_result_rows` rows or when its intermediate results are
+/// estimated to need more than `max_memory_bytes`, and with `Timeout` when it runs
+/// longer than `query_timeout_ms`. Memory and time are checked between query operators. Pass 0
+/// for any limit to leave it unlimited.
+///
+/// # Arguments
+/// * `db` - Database handle (must not be null)
+/// * `max_result_rows` - Maximum rows per result (0 = unlimited)
+/// * `max_memory_bytes` - Per-query memory budget in bytes (0 = unlimited)
+/// * `query_timeout_ms` - Per-query timeout in milliseconds (0 = unlimited)
+/// * `error_out` - Output parameter for error code (can be null)
+///
+/// # Returns
+/// * Error code (Success = 0, error otherwise)
+///
+/// # Safety
+/// * `db` must be a valid handle from `graphlite_open`
+///
+/// # Example
+/// ```c
+/// // At most 10k rows, 256 MiB and 5 seconds per query
+/// graphlite_set_limits(db, 10000, 256 * 1024 * 1024, 5000, &error);
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn graphlite_set_limits(
+    db: *mut GraphLiteDB,
+    max_result_rows: u64,
+    max_memory_bytes: u64,
+    query_timeout_ms: u64,
+    error_out: *mut GraphLiteErrorCode,
+) -> GraphLiteErrorCode {
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        if db.is_null() {
+            set_error(error_out, GraphLiteErrorCode::NullPointer);
+            return GraphLiteErrorCode::NullPointer;
+        }
+
+        let db_ref = unsafe { &*db };
+        let _guard = db_ref.lock_shared();
+
+        let non_zero =
+            |value: u64| (value > 0).then(|| usize::try_from(value).unwrap_or(usize::MAX));
+        db_ref.coordinator.set_query_limits(QueryLimits {
+            max_result_rows: non_zero(max_result_rows),
+            max_memory_bytes: non_zero(max_memory_bytes),
+            query_timeout: (query_timeout_ms > 0).then(|| Duration::from_millis(query_timeout_ms)),
+        });
+
+        set_error(error_out, GraphLiteErrorCode::Success);
+        GraphLiteErrorCode::Success
+    }));
+
+    match result {
+        Ok(code) => code,
+        Err(_) => {
+            set_error(error_out, GraphLiteErrorCode::PanicError);
+            GraphLiteErrorCode::PanicError
+        }
+    }
+}
+
+/// Notation of numbers in result JSON, for `GraphLiteNumberFormat`
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum GraphLiteNotation {
+    /// Shortest form, with an exponent only for very large and small magnitudes
+    /// (fixed-point when `decimals` is set)
+    Auto = 0,
+    /// Never an exponent
+    Plain = 1,
+    /// Always an exponent
+    Scientific = 2,
+}
+
+/// How the JSON returned by the query functions writes numbers
+///
+/// Obtain the defaults with `graphlite_number_format_default` and apply the
+/// format to a handle with `graphlite_set_number_format`.
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct GraphLiteNumberFormat {
+    /// Write whole numbers as integers (`493603` rather than `493603.0`)
+    pub integers: bool,
+    /// Decimal places of numbers that are not whole (negative = as many as needed)
+    pub decimals: i32,
+    /// Notation of numbers that are not whole
+    pub notation: GraphLiteNotation,
+}
+
+impl From<&GraphLiteNumberFormat> for NumberFormat {
+    fn from(format: &GraphLiteNumberFormat) -> Self {
+        NumberFormat {
+            integers: format.integers,
+            decimals: u32::try_from(format.decimals)
+                .ok()
+                .map(|decimals| decimals.min(u8::MAX.into()) as u8),
+            notation: match format.notation {
+                GraphLiteNotation::Auto => Notation::Auto,
+                GraphLiteNotation::Plain => Notation::Plain,
+                GraphLiteNotation::Scientific => Notation::Scientific,
+            },
+        }
+    }
+}
+
+/// Get the number format database handles start with
+///
+/// Whole numbers are integers, other numbers are written in their shortest form.
+#[no_mangle]
+pub extern "C" fn graphlite_number_format_default() -> GraphLiteNumberFormat {
+    GraphLiteNumberFormat {
+        integers: true,
+        decimals: -1,
+        notation: GraphLiteNotation::Auto,
+    }
+}
+
+/// Set how numbers are written in the JSON returned for a database handle
+///
+/// Applies to `graphlite_query`, `graphlite_session_query`, their UTF-16
+/// variants, `graphlite_query_compressed`, `graphlite_execute_many` and
+/// `graphlite_query_many`.
+///
+/// # Arguments
+/// * `db` - Database handle (must not be null)
+/// * `format` - Number format (must not be null)
+/// * `error_out` - Output parameter for error code (can be null)
+///
+/// # Returns
+/// * Error code (Success = 0, error otherwise)
+///
+/// # Safety
+/// * `db` must be a valid handle from `graphlite_open`
+/// * `format` must point to a valid `GraphLiteNumberFormat`
+///
+/// # Example
+/// ```c
+/// GraphLiteNumberFormat format = graphlite_number_format_default();
+/// format.decimals = 2;
+/// graphlite_set_number_format(db, &format, &error);
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn graphlite_set_number_format(
+    db: *mut GraphLiteDB,
+    format: *const GraphLiteNumberFormat,
+    error_out: *mut GraphLiteErrorCode,
+) -> GraphLiteErrorCode {
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        if db.is_null() || format.is_null() {
+            set_error(error_out, GraphLiteErrorCode::NullPointer);
+            return GraphLiteErrorCode::NullPointer;
+        }
+
+        let db_ref = unsafe { &*db };
+        let format = NumberFormat::from(unsafe { &*format });
+        *db_ref
+            .number_format
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = format;
+
+        set_error(error_out, GraphLiteErrorCode::Success);
+        GraphLiteErrorCode::Success
+    }));
+
+    match result {
+        Ok(code) => code,
+        Err(_) => {
+            set_error(error_out, GraphLiteErrorCode::PanicError);
+            GraphLiteErrorCode::PanicError
+        }
+    }
+}
+
 /// Create a simple session for the given username
 ///
 /// # Arguments
@@ -155,6 +703,7 @@ pub unsafe extern "C" fn graphlite_create_session(
         }
 
         let db_ref = unsafe { &*db };
+        let _guard = db_ref.lock_shared();
 
         // Convert C string to Rust string
         let c_str = unsafe { CStr::from_ptr(username) };
@@ -171,7 +720,7 @@ pub unsafe extern "C" fn graphlite_create_session(
             Ok(session_id) => match CString::new(session_id) {
                 Ok(c_string) => {
                     set_error(error_out, GraphLiteErrorCode::Success);
-                    c_string.into_raw()
+                    LEDGER.track(c_string.into_raw(), Kind::String)
                 }
                 Err(_) => {
                     set_error(error_out, GraphLiteErrorCode::InvalidUtf8);
@@ -231,63 +780,86 @@ pub unsafe extern "C" fn graphlite_query(
     error_out: *mut GraphLiteErrorCode,
 ) -> *mut c_char {
     let result = panic::catch_unwind(AssertUnwindSafe(|| {
-        // Check for null pointers
-        if db.is_null() || session_id.is_null() || query.is_null() {
-            set_error(error_out, GraphLiteErrorCode::NullPointer);
-            return ptr::null_mut();
-        }
-
-        let db_ref = unsafe { &*db };
+        let result = unsafe { run_query(db, session_id, query, error_out) }?;
+        result_to_json(&result, unsafe { &*db }.number_format(), error_out)
+    }));
 
-        // Convert C strings to Rust strings
-        let session_c_str = unsafe { CStr::from_ptr(session_id) };
-        let session_str = match session_c_str.to_str() {
-            Ok(s) => s,
-            Err(_) => {
-                set_error(error_out, GraphLiteErrorCode::InvalidUtf8);
-                return ptr::null_mut();
-            }
-        };
+    match result {
+        Ok(ptr) => ptr.unwrap_or(ptr::null_mut()),
+        Err(_) => {
+            set_error(error_out, GraphLiteErrorCode::PanicError);
+            ptr::null_mut()
+        }
+    }
+}
 
-        let query_c_str = unsafe { CStr::from_ptr(query) };
-        let query_str = match query_c_str.to_str() {
-            Ok(s) => s,
-            Err(_) => {
-                set_error(error_out, GraphLiteErrorCode::InvalidUtf8);
-                return ptr::null_mut();
-            }
-        };
+/// Execute a GQL query and return the result as MessagePack
+///
+/// The buffer holds the same document `graphlite_query` returns as JSON, encoded
+/// as MessagePack with field names kept (see "Binary Result Format" in the README).
+/// Decoding it is considerably cheaper than parsing JSON for wide rows.
+///
+/// # Arguments
+/// * `db` - Database handle (must not be null)
+/// * `session_id` - C string with session ID (must not be null)
+/// * `query` - C string with GQL query (must not be null)
+/// * `len_out` - Output parameter for the buffer length in bytes (must not be null)
+/// * `error_out` - Output parameter for error code (can be null)
+///
+/// # Returns
+/// * Buffer with the MessagePack-encoded result (must be freed with `graphlite_free_buffer`)
+/// * null pointer on error
+///
+/// # Safety
+/// * `db` must be a valid handle from `graphlite_open`
+/// * `session_id` must be from `graphlite_create_session`
+/// * `query` must be a valid null-terminated C string
+/// * `len_out` must point to writable memory
+/// * Returned buffer must be freed with `graphlite_free_buffer` using the length from `len_out`
+///
+/// # Example
+/// ```c
+/// size_t len;
+/// GraphLiteErrorCode error;
+/// uint8_t* buf = graphlite_query_msgpack(db, session_id, "MATCH (n) RETURN n", &len, &error);
+/// if (buf) {
+///     // decode buf[0..len] with any MessagePack library
+///     graphlite_free_buffer(buf, len);
+/// }
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn graphlite_query_msgpack(
+    db: *mut GraphLiteDB,
+    session_id: *const c_char,
+    query: *const c_char,
+    len_out: *mut usize,
+    error_out: *mut GraphLiteErrorCode,
+) -> *mut u8 {
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        if len_out.is_null() {
+            set_error(error_out, GraphLiteErrorCode::NullPointer);
+            return None;
+        }
+        unsafe { *len_out = 0 };
 
-        // Execute query
-        match db_ref.coordinator.process_query(query_str, session_str) {
-            Ok(result) => {
-                // Serialize to JSON
-                match serde_json::to_string(&result) {
-                    Ok(json) => match CString::new(json) {
-                        Ok(c_string) => {
-                            set_error(error_out, GraphLiteErrorCode::Success);
-                            c_string.into_raw()
-                        }
-                        Err(_) => {
-                            set_error(error_out, GraphLiteErrorCode::JsonError);
-                            ptr::null_mut()
-                        }
-                    },
-                    Err(_) => {
-                        set_error(error_out, GraphLiteErrorCode::JsonError);
-                        ptr::null_mut()
-                    }
-                }
+        let result = unsafe { run_query(db, session_id, query, error_out) }?;
+
+        match rmp_serde::to_vec_named(&result) {
+            Ok(bytes) => {
+                let buffer = bytes.into_boxed_slice();
+                unsafe { *len_out = buffer.len() };
+                set_error(error_out, GraphLiteErrorCode::Success);
+                Some(LEDGER.track(Box::into_raw(buffer) as *mut u8, Kind::Buffer))
             }
             Err(_) => {
-                set_error(error_out, GraphLiteErrorCode::QueryError);
-                ptr::null_mut()
+                set_error(error_out, GraphLiteErrorCode::SerializationError);
+                None
             }
         }
     }));
 
     match result {
-        Ok(ptr) => ptr,
+        Ok(ptr) => ptr.unwrap_or(ptr::null_mut()),
         Err(_) => {
             set_error(error_out, GraphLiteErrorCode::PanicError);
             ptr::null_mut()
@@ -295,152 +867,5047 @@ pub unsafe extern "C" fn graphlite_query(
     }
 }
 
-/// Close a session
+/// Compression for `graphlite_query_compressed`
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum GraphLiteCompression {
+    /// Plain JSON bytes, without a terminating NUL
+    Uncompressed = 0,
+    /// gzip (RFC 1952), as sent with `Content-Encoding: gzip`
+    Gzip = 1,
+    /// Zstandard frame, as sent with `Content-Encoding: zstd`
+    Zstd = 2,
+}
+
+impl From<GraphLiteCompression> for ContentEncoding {
+    fn from(compression: GraphLiteCompression) -> Self {
+        match compression {
+            GraphLiteCompression::Uncompressed => ContentEncoding::Identity,
+            GraphLiteCompression::Gzip => ContentEncoding::Gzip,
+            GraphLiteCompression::Zstd => ContentEncoding::Zstd,
+        }
+    }
+}
+
+/// Execute a GQL query and return the JSON result compressed
+///
+/// The buffer holds the document `graphlite_query` returns, compressed with
+/// `compression`. Text-heavy results typically shrink by an order of magnitude,
+/// which matters when the caller forwards them over a network: a server can pass
+/// the buffer on unchanged with the matching `Content-Encoding`.
 ///
 /// # Arguments
 /// * `db` - Database handle (must not be null)
 /// * `session_id` - C string with session ID (must not be null)
+/// * `query` - C string with GQL query (must not be null)
+/// * `compression` - Compression to apply
+/// * `len_out` - Output parameter for the buffer length in bytes (must not be null)
 /// * `error_out` - Output parameter for error code (can be null)
 ///
 /// # Returns
-/// * Error code (Success = 0, error otherwise)
+/// * Buffer with the compressed JSON result (must be freed with `graphlite_free_buffer`)
+/// * null pointer on error
 ///
 /// # Safety
 /// * `db` must be a valid handle from `graphlite_open`
 /// * `session_id` must be from `graphlite_create_session`
+/// * `query` must be a valid null-terminated C string
+/// * `len_out` must point to writable memory
+/// * Returned buffer must be freed with `graphlite_free_buffer` using the length from `len_out`
+///
+/// # Example
+/// ```c
+/// size_t len;
+/// GraphLiteErrorCode error;
+/// uint8_t* buf = graphlite_query_compressed(db, session_id, "MATCH (n) RETURN n",
+///     Gzip, &len, &error);
+/// if (buf) {
+///     // send buf[0..len] with "Content-Encoding: gzip"
+///     graphlite_free_buffer(buf, len);
+/// }
+/// ```
 #[no_mangle]
-pub unsafe extern "C" fn graphlite_close_session(
+pub unsafe extern "C" fn graphlite_query_compressed(
     db: *mut GraphLiteDB,
     session_id: *const c_char,
+    query: *const c_char,
+    compression: GraphLiteCompression,
+    len_out: *mut usize,
     error_out: *mut GraphLiteErrorCode,
-) -> GraphLiteErrorCode {
+) -> *mut u8 {
     let result = panic::catch_unwind(AssertUnwindSafe(|| {
-        if db.is_null() || session_id.is_null() {
+        if len_out.is_null() {
             set_error(error_out, GraphLiteErrorCode::NullPointer);
-            return GraphLiteErrorCode::NullPointer;
+            return None;
         }
+        unsafe { *len_out = 0 };
 
-        let db_ref = unsafe { &*db };
+        let result = unsafe { run_query(db, session_id, query, error_out) }?;
 
-        let session_c_str = unsafe { CStr::from_ptr(session_id) };
-        let session_str = match session_c_str.to_str() {
-            Ok(s) => s,
+        let json = match unsafe { &*db }.number_format().to_vec(&result) {
+            Ok(json) => json,
             Err(_) => {
-                set_error(error_out, GraphLiteErrorCode::InvalidUtf8);
-                return GraphLiteErrorCode::InvalidUtf8;
+                set_error(error_out, GraphLiteErrorCode::JsonError);
+                return None;
             }
         };
-
-        match db_ref.coordinator.close_session(session_str) {
-            Ok(_) => {
+        match ContentEncoding::from(compression).encode(json) {
+            Ok(bytes) => {
+                let buffer = bytes.into_boxed_slice();
+                unsafe { *len_out = buffer.len() };
                 set_error(error_out, GraphLiteErrorCode::Success);
-                GraphLiteErrorCode::Success
+                Some(LEDGER.track(Box::into_raw(buffer) as *mut u8, Kind::Buffer))
             }
             Err(_) => {
-                set_error(error_out, GraphLiteErrorCode::SessionError);
-                GraphLiteErrorCode::SessionError
+                set_error(error_out, GraphLiteErrorCode::SerializationError);
+                None
             }
         }
     }));
 
     match result {
-        Ok(code) => code,
+        Ok(ptr) => ptr.unwrap_or(ptr::null_mut()),
         Err(_) => {
             set_error(error_out, GraphLiteErrorCode::PanicError);
-            GraphLiteErrorCode::PanicError
+            ptr::null_mut()
         }
     }
 }
 
-/// Free a string returned by GraphLite FFI functions
-///
-/// # Arguments
-/// * `s` - C string to free (can be null, in which case this is a no-op)
+/// Parse the query arguments and run the query, setting `error_out` on failure
 ///
 /// # Safety
-/// * `s` must be a string returned by a GraphLite FFI function
-/// * Must not be called more than once on the same string
-/// * Must not be called on strings not allocated by GraphLite
-#[no_mangle]
-pub unsafe extern "C" fn graphlite_free_string(s: *mut c_char) {
-    if !s.is_null() {
-        unsafe {
-            drop(CString::from_raw(s));
+/// Same requirements as `graphlite_query`.
+unsafe fn run_query(
+    db: *mut GraphLiteDB,
+    session_id: *const c_char,
+    query: *const c_char,
+    error_out: *mut GraphLiteErrorCode,
+) -> Option<QueryResult> {
+    // Check for null pointers
+    if db.is_null() || session_id.is_null() || query.is_null() {
+        set_error(error_out, GraphLiteErrorCode::NullPointer);
+        return None;
+    }
+
+    let db_ref = unsafe { &*db };
+    let _guard = db_ref.lock_shared();
+
+    // Convert C strings to Rust strings
+    let session_c_str = unsafe { CStr::from_ptr(session_id) };
+    let session_str = match session_c_str.to_str() {
+        Ok(s) => s,
+        Err(_) => {
+            set_error(error_out, GraphLiteErrorCode::InvalidUtf8);
+            return None;
+        }
+    };
+
+    let query_c_str = unsafe { CStr::from_ptr(query) };
+    let query_str = match query_c_str.to_str() {
+        Ok(s) => s,
+        Err(_) => {
+            set_error(error_out, GraphLiteErrorCode::InvalidUtf8);
+            return None;
+        }
+    };
+
+    // Execute query
+    match db_ref.coordinator.process_query(query_str, session_str) {
+        Ok(result) => Some(result),
+        Err(e) => {
+            set_error(error_out, GraphLiteErrorCode::from_query_error(&e));
+            None
         }
     }
 }
 
-/// Close database connection and free resources
+/// Serialize a query result to a JSON C string, setting `error_out`
+fn result_to_json(
+    result: &QueryResult,
+    numbers: NumberFormat,
+    error_out: *mut GraphLiteErrorCode,
+) -> Option<*mut c_char> {
+    match numbers.to_string(result) {
+        Ok(json) => match CString::new(json) {
+            Ok(c_string) => {
+                set_error(error_out, GraphLiteErrorCode::Success);
+                Some(LEDGER.track(c_string.into_raw(), Kind::String))
+            }
+            Err(_) => {
+                set_error(error_out, GraphLiteErrorCode::JsonError);
+                None
+            }
+        },
+        Err(_) => {
+            set_error(error_out, GraphLiteErrorCode::JsonError);
+            None
+        }
+    }
+}
+
+/// Execute a GQL query and return the result for reading row by row
+///
+/// The result is read with `graphlite_result_next` (or `graphlite_result_row`) and
+/// the `graphlite_row_get_*` accessors, which return typed values without any JSON.
 ///
 /// # Arguments
-/// * `db` - Database handle to close (can be null, in which case this is a no-op)
+/// * `db` - Database handle (must not be null)
+/// * `session_id` - C string with session ID (must not be null)
+/// * `query` - C string with GQL query (must not be null)
+/// * `error_out` - Output parameter for error code (can be null)
+///
+/// # Returns
+/// * Result handle on success (must be freed with `graphlite_result_free`)
+/// * null pointer on error
 ///
 /// # Safety
-/// * `db` must be a handle from `graphlite_open`
-/// * Must not be called more than once on the same handle
-/// * Must not be used after calling this function
+/// * `db` must be a valid handle from `graphlite_open`
+/// * `session_id` must be from `graphlite_create_session`
+/// * `query` must be a valid null-terminated C string
+///
+/// # Example
+/// ```c
+/// GraphLiteResult* result = graphlite_query_result(db, session_id,
+///     "MATCH (p:Person) RETURN p.name AS name, p.age AS age", &error);
+/// const GraphLiteRow* row;
+/// while ((row = graphlite_result_next(result)) != NULL) {
+///     const char* name = graphlite_row_get_string(row, 0, &error);
+///     int64_t age = graphlite_row_get_int(row, 1, &error);
+/// }
+/// graphlite_result_free(result);
+/// ```
 #[no_mangle]
-pub unsafe extern "C" fn graphlite_close(db: *mut GraphLiteDB) {
-    if !db.is_null() {
-        unsafe {
-            drop(Box::from_raw(db));
+pub unsafe extern "C" fn graphlite_query_result(
+    db: *mut GraphLiteDB,
+    session_id: *const c_char,
+    query: *const c_char,
+    error_out: *mut GraphLiteErrorCode,
+) -> *mut GraphLiteResult {
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        let result = unsafe { run_query(db, session_id, query, error_out) }?;
+        set_error(error_out, GraphLiteErrorCode::Success);
+        Some(LEDGER.track(
+            Box::into_raw(Box::new(GraphLiteResult::new(&result))),
+            Kind::Result,
+        ))
+    }));
+
+    match result {
+        Ok(ptr) => ptr.unwrap_or(ptr::null_mut()),
+        Err(_) => {
+            set_error(error_out, GraphLiteErrorCode::PanicError);
+            ptr::null_mut()
         }
     }
 }
 
-/// Get the version string of GraphLite
+/// Get the number of rows of a result
 ///
 /// # Returns
-/// * Static C string with version (e.g., "0.1.0")
-/// * Must NOT be freed (it's a static string)
+/// * Row count, or 0 if `result` is null
+///
+/// # Safety
+/// * `result` must be a valid handle from `graphlite_query_result`
 #[no_mangle]
-pub extern "C" fn graphlite_version() -> *const c_char {
-    // Using a static string so it doesn't need to be freed
-    const VERSION: &[u8] = b"0.1.0\0";
-    VERSION.as_ptr() as *const c_char
+pub unsafe extern "C" fn graphlite_result_row_count(result: *const GraphLiteResult) -> usize {
+    if result.is_null() {
+        return 0;
+    }
+    unsafe { &*result }.row_count()
 }
 
-// Helper function to set error code if output pointer is not null
-fn set_error(error_out: *mut GraphLiteErrorCode, code: GraphLiteErrorCode) {
-    if !error_out.is_null() {
-        unsafe {
-            *error_out = code;
-        }
+/// Get the number of columns of a result
+///
+/// # Returns
+/// * Column count, or 0 if `result` is null
+///
+/// # Safety
+/// * `result` must be a valid handle from `graphlite_query_result`
+#[no_mangle]
+pub unsafe extern "C" fn graphlite_result_column_count(result: *const GraphLiteResult) -> usize {
+    if result.is_null() {
+        return 0;
     }
+    unsafe { &*result }.column_count()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::ffi::CString;
+/// Get the name of a result column, in RETURN order
+///
+/// # Returns
+/// * Column name, owned by the result and valid until `graphlite_result_free`
+///   (must NOT be freed)
+/// * null pointer if `result` is null or `column` is out of range
+///
+/// # Safety
+/// * `result` must be a valid handle from `graphlite_query_result`
+#[no_mangle]
+pub unsafe extern "C" fn graphlite_result_column_name(
+    result: *const GraphLiteResult,
+    column: usize,
+) -> *const c_char {
+    if result.is_null() {
+        return ptr::null();
+    }
+    unsafe { &*result }.column_name(column)
+}
 
-    #[test]
-    fn test_version() {
-        let version_ptr = graphlite_version();
-        assert!(!version_ptr.is_null());
-        let version = unsafe { CStr::from_ptr(version_ptr) };
-        assert_eq!(version.to_str().unwrap(), "0.1.0");
+/// Advance the result cursor and return the next row
+///
+/// The first call returns the first row.
+///
+/// # Returns
+/// * Row owned by the result and valid until `graphlite_result_free`
+/// * null pointer after the last row, or if `result` is null
+///
+/// # Safety
+/// * `result` must be a valid handle from `graphlite_query_result`
+#[no_mangle]
+pub unsafe extern "C" fn graphlite_result_next(
+    result: *mut GraphLiteResult,
+) -> *const GraphLiteRow {
+    if result.is_null() {
+        return ptr::null();
     }
+    unsafe { &mut *result }
+        .next_row()
+        .map_or(ptr::null(), |row| row as *const GraphLiteRow)
+}
 
-    #[test]
-    fn test_null_pointer_handling() {
-        let mut error = GraphLiteErrorCode::Success;
+/// Get a row of a result by index, without moving the cursor
+///
+/// # Returns
+/// * Row owned by the result and valid until `graphlite_result_free`
+/// * null pointer if `result` is null or `index` is out of range
+///
+/// # Safety
+/// * `result` must be a valid handle from `graphlite_query_result`
+#[no_mangle]
+pub unsafe extern "C" fn graphlite_result_row(
+    result: *const GraphLiteResult,
+    index: usize,
+) -> *const GraphLiteRow {
+    if result.is_null() {
+        return ptr::null();
+    }
+    unsafe { &*result }
+        .row(index)
+        .map_or(ptr::null(), |row| row as *const GraphLiteRow)
+}
 
-        // Test null path
-        let db = unsafe { graphlite_open(ptr::null(), &mut error) };
-        assert!(db.is_null());
-        assert_eq!(error, GraphLiteErrorCode::NullPointer);
+/// Free a result and all rows and strings read from it
+///
+/// # Arguments
+/// * `result` - Result to free (can be null, in which case this is a no-op)
+///
+/// # Safety
+/// * `result` must be a handle from `graphlite_query_result`
+/// * Must not be called more than once on the same handle
+#[no_mangle]
+pub unsafe extern "C" fn graphlite_result_free(result: *mut GraphLiteResult) {
+    if !result.is_null() && LEDGER.untrack(result, Kind::Result) {
+        unsafe {
+            drop(Box::from_raw(result));
+        }
     }
+}
 
-    #[test]
-    fn test_open_close() {
-        let mut error = GraphLiteErrorCode::Success;
-        let path = CString::new("/tmp/test_ffi_db").unwrap();
+/// Get the number of columns of a row
+///
+/// # Returns
+/// * Column count (the same for every row of a result), or 0 if `row` is null
+///
+/// # Safety
+/// * `row` must be a row of a result that has not been freed
+#[no_mangle]
+pub unsafe extern "C" fn graphlite_row_column_count(row: *const GraphLiteRow) -> usize {
+    if row.is_null() {
+        return 0;
+    }
+    unsafe { &*row }.column_count()
+}
 
-        let db = unsafe { graphlite_open(path.as_ptr(), &mut error) };
+/// Get the type of a row value, to pick the accessor that reads it
+///
+/// # Returns
+/// * Type of the value; `Null` also if `row` is null or `column` is out of range
+///
+/// # Safety
+/// * `row` must be a row of a result that has not been freed
+#[no_mangle]
+pub unsafe extern "C" fn graphlite_row_value_type(
+    row: *const GraphLiteRow,
+    column: usize,
+) -> GraphLiteValueType {
+    if row.is_null() {
+        return GraphLiteValueType::Null;
+    }
+    unsafe { &*row }
+        .value_type(column)
+        .unwrap_or(GraphLiteValueType::Null)
+}
+
+/// Read a boolean row value
+///
+/// # Returns
+/// * The value, or false with `error_out` set to `TypeMismatch` or `OutOfRange`
+///
+/// # Safety
+/// * `row` must be a row of a result that has not been freed
+#[no_mangle]
+pub unsafe extern "C" fn graphlite_row_get_bool(
+    row: *const GraphLiteRow,
+    column: usize,
+    error_out: *mut GraphLiteErrorCode,
+) -> bool {
+    unsafe { read_row(row, error_out, false, |row| row.get_bool(column)) }
+}
+
+/// Read an integral number row value
+///
+/// # Returns
+/// * The value, or 0 with `error_out` set to `TypeMismatch` (not a number, or a
+///   number with a fractional part or outside the `int64_t` range) or `OutOfRange`
+///
+/// # Safety
+/// * `row` must be a row of a result that has not been freed
+#[no_mangle]
+pub unsafe extern "C" fn graphlite_row_get_int(
+    row: *const GraphLiteRow,
+    column: usize,
+    error_out: *mut GraphLiteErrorCode,
+) -> i64 {
+    unsafe { read_row(row, error_out, 0, |row| row.get_int(column)) }
+}
+
+/// Read a number row value
+///
+/// # Returns
+/// * The value, or 0.0 with `error_out` set to `TypeMismatch` or `OutOfRange`
+///
+/// # Safety
+/// * `row` must be a row of a result that has not been freed
+#[no_mangle]
+pub unsafe extern "C" fn graphlite_row_get_double(
+    row: *const GraphLiteRow,
+    column: usize,
+    error_out: *mut GraphLiteErrorCode,
+) -> f64 {
+    unsafe { read_row(row, error_out, 0.0, |row| row.get_double(column)) }
+}
+
+/// Read a string row value, or the text form of an `Other` value such as a date
+///
+/// # Returns
+/// * The string, owned by the result and valid until `graphlite_result_free`
+///   (must NOT be freed)
+/// * null pointer with `error_out` set to `TypeMismatch` (including NULL values and
+///   strings containing a NUL byte) or `OutOfRange`
+///
+/// # Safety
+/// * `row` must be a row of a result that has not been freed
+#[no_mangle]
+pub unsafe extern "C" fn graphlite_row_get_string(
+    row: *const GraphLiteRow,
+    column: usize,
+    error_out: *mut GraphLiteErrorCode,
+) -> *const c_char {
+    unsafe { read_row(row, error_out, ptr::null(), |row| row.get_string(column)) }
+}
+
+/// Read the storage id of a node row value
+///
+/// # Returns
+/// * Node id, owned by the result and valid until `graphlite_result_free`
+///   (must NOT be freed)
+/// * null pointer with `error_out` set to `TypeMismatch` or `OutOfRange`
+///
+/// # Safety
+/// * `row` must be a row of a result that has not been freed
+#[no_mangle]
+pub unsafe extern "C" fn graphlite_row_get_node_id(
+    row: *const GraphLiteRow,
+    column: usize,
+    error_out: *mut GraphLiteErrorCode,
+) -> *const c_char {
+    unsafe { read_row(row, error_out, ptr::null(), |row| row.get_node_id(column)) }
+}
+
+/// Get the number of labels of a node or edge row value (always 1 for an edge)
+///
+/// # Returns
+/// * Label count, or 0 with `error_out` set to `TypeMismatch` or `OutOfRange`
+///
+/// # Safety
+/// * `row` must be a row of a result that has not been freed
+#[no_mangle]
+pub unsafe extern "C" fn graphlite_row_get_label_count(
+    row: *const GraphLiteRow,
+    column: usize,
+    error_out: *mut GraphLiteErrorCode,
+) -> usize {
+    unsafe { read_row(row, error_out, 0, |row| row.get_label_count(column)) }
+}
+
+/// Read a label of a node or edge row value
+///
+/// # Arguments
+/// * `index` - Label index, below `graphlite_row_get_label_count`
+///
+/// # Returns
+/// * Label, owned by the result and valid until `graphlite_result_free`
+///   (must NOT be freed)
+/// * null pointer with `error_out` set to `TypeMismatch` or `OutOfRange`
+///
+/// # Safety
+/// * `row` must be a row of a result that has not been freed
+#[no_mangle]
+pub unsafe extern "C" fn graphlite_row_get_label(
+    row: *const GraphLiteRow,
+    column: usize,
+    index: usize,
+    error_out: *mut GraphLiteErrorCode,
+) -> *const c_char {
+    unsafe {
+        read_row(row, error_out, ptr::null(), |row| {
+            row.get_label(column, index)
+        })
+    }
+}
+
+/// Read the storage id of an edge row value
+///
+/// # Returns
+/// * Edge id, owned by the result and valid until `graphlite_result_free`
+///   (must NOT be freed)
+/// * null pointer with `error_out` set to `TypeMismatch` or `OutOfRange`
+///
+/// # Safety
+/// * `row` must be a row of a result that has not been freed
+#[no_mangle]
+pub unsafe extern "C" fn graphlite_row_get_edge_id(
+    row: *const GraphLiteRow,
+    column: usize,
+    error_out: *mut GraphLiteErrorCode,
+) -> *const c_char {
+    unsafe {
+        read_row(row, error_out, ptr::null(), |row| {
+            row.get_edge_field(column, EdgeField::Id)
+        })
+    }
+}
+
+/// Read the source node id of an edge row value
+///
+/// # Returns
+/// * Node id, owned by the result and valid until `graphlite_result_free`
+///   (must NOT be freed)
+/// * null pointer with `error_out` set to `TypeMismatch` or `OutOfRange`
+///
+/// # Safety
+/// * `row` must be a row of a result that has not been freed
+#[no_mangle]
+pub unsafe extern "C" fn graphlite_row_get_edge_source(
+    row: *const GraphLiteRow,
+    column: usize,
+    error_out: *mut GraphLiteErrorCode,
+) -> *const c_char {
+    unsafe {
+        read_row(row, error_out, ptr::null(), |row| {
+            row.get_edge_field(column, EdgeField::Source)
+        })
+    }
+}
+
+/// Read the target node id of an edge row value
+///
+/// # Returns
+/// * Node id, owned by the result and valid until `graphlite_result_free`
+///   (must NOT be freed)
+/// * null pointer with `error_out` set to `TypeMismatch` or `OutOfRange`
+///
+/// # Safety
+/// * `row` must be a row of a result that has not been freed
+#[no_mangle]
+pub unsafe extern "C" fn graphlite_row_get_edge_target(
+    row: *const GraphLiteRow,
+    column: usize,
+    error_out: *mut GraphLiteErrorCode,
+) -> *const c_char {
+    unsafe {
+        read_row(row, error_out, ptr::null(), |row| {
+            row.get_edge_field(column, EdgeField::Target)
+        })
+    }
+}
+
+/// Get the type of a result value by row and column, to pick the accessor that reads it
+///
+/// The `graphlite_result_get_*` functions read any cell directly, so bindings with
+/// random access semantics (a JDBC `ResultSet`, a data frame) need no cursor.
+///
+/// # Returns
+/// * Type of the value; `Null` also if `result` is null or `row` or `column` is out of range
+///
+/// # Safety
+/// * `result` must be a valid handle from `graphlite_query_result`
+#[no_mangle]
+pub unsafe extern "C" fn graphlite_result_value_type(
+    result: *const GraphLiteResult,
+    row: usize,
+    column: usize,
+) -> GraphLiteValueType {
+    unsafe { graphlite_row_value_type(graphlite_result_row(result, row), column) }
+}
+
+/// Read a boolean result value by row and column
+///
+/// # Returns
+/// * The value, or false with `error_out` set to `TypeMismatch` or `OutOfRange`
+///
+/// # Safety
+/// * `result` must be a valid handle from `graphlite_query_result`
+#[no_mangle]
+pub unsafe extern "C" fn graphlite_result_get_bool(
+    result: *const GraphLiteResult,
+    row: usize,
+    column: usize,
+    error_out: *mut GraphLiteErrorCode,
+) -> bool {
+    unsafe { read_cell(result, row, error_out, false, |row| row.get_bool(column)) }
+}
+
+/// Read an integral number result value by row and column
+///
+/// # Returns
+/// * The value, or 0 with `error_out` set to `TypeMismatch` (not a number, or a
+///   number with a fractional part or outside the `int64_t` range) or `OutOfRange`
+///
+/// # Safety
+/// * `result` must be a valid handle from `graphlite_query_result`
+#[no_mangle]
+pub unsafe extern "C" fn graphlite_result_get_int(
+    result: *const GraphLiteResult,
+    row: usize,
+    column: usize,
+    error_out: *mut GraphLiteErrorCode,
+) -> i64 {
+    unsafe { read_cell(result, row, error_out, 0, |row| row.get_int(column)) }
+}
+
+/// Read a number result value by row and column
+///
+/// # Returns
+/// * The value, or 0.0 with `error_out` set to `TypeMismatch` or `OutOfRange`
+///
+/// # Safety
+/// * `result` must be a valid handle from `graphlite_query_result`
+#[no_mangle]
+pub unsafe extern "C" fn graphlite_result_get_double(
+    result: *const GraphLiteResult,
+    row: usize,
+    column: usize,
+    error_out: *mut GraphLiteErrorCode,
+) -> f64 {
+    unsafe { read_cell(result, row, error_out, 0.0, |row| row.get_double(column)) }
+}
+
+/// Read a string result value by row and column, or the text form of an `Other` value
+///
+/// # Returns
+/// * The string, owned by the result and valid until `graphlite_result_free`
+///   (must NOT be freed)
+/// * null pointer with `error_out` set to `TypeMismatch` (including NULL values and
+///   strings containing a NUL byte) or `OutOfRange`
+///
+/// # Safety
+/// * `result` must be a valid handle from `graphlite_query_result`
+#[no_mangle]
+pub unsafe extern "C" fn graphlite_result_get_string(
+    result: *const GraphLiteResult,
+    row: usize,
+    column: usize,
+    error_out: *mut GraphLiteErrorCode,
+) -> *const c_char {
+    unsafe {
+        read_cell(result, row, error_out, ptr::null(), |row| {
+            row.get_string(column)
+        })
+    }
+}
+
+/// Copy a number column into an array, one value per row
+///
+/// # Arguments
+/// * `result` - Result handle (must not be null)
+/// * `column` - Column index
+/// * `values_out` - Array of at least `len` values (must not be null)
+/// * `nulls_out` - Array of at least `len` flags set for NULL values, which are
+///   written as 0.0 (can be null, in which case a NULL value is a `TypeMismatch`)
+/// * `len` - Capacity of the arrays; rows past it are not copied
+/// * `error_out` - Output parameter for error code (can be null)
+///
+/// # Returns
+/// * Number of rows copied; on `TypeMismatch` or `OutOfRange` the rows before the
+///   failing one
+///
+/// # Safety
+/// * `result` must be a valid handle from `graphlite_query_result`
+/// * `values_out` and a non-null `nulls_out` must point to `len` writable elements
+#[no_mangle]
+pub unsafe extern "C" fn graphlite_result_get_double_column(
+    result: *const GraphLiteResult,
+    column: usize,
+    values_out: *mut f64,
+    nulls_out: *mut bool,
+    len: usize,
+    error_out: *mut GraphLiteErrorCode,
+) -> usize {
+    unsafe {
+        read_column(
+            result,
+            column,
+            values_out,
+            nulls_out,
+            len,
+            error_out,
+            |row| row.get_double(column),
+        )
+    }
+}
+
+/// Copy an integral number column into an array, one value per row
+///
+/// Same as `graphlite_result_get_double_column`, with values read as by
+/// `graphlite_result_get_int`; NULL values are written as 0.
+///
+/// # Safety
+/// * `result` must be a valid handle from `graphlite_query_result`
+/// * `values_out` and a non-null `nulls_out` must point to `len` writable elements
+#[no_mangle]
+pub unsafe extern "C" fn graphlite_result_get_int_column(
+    result: *const GraphLiteResult,
+    column: usize,
+    values_out: *mut i64,
+    nulls_out: *mut bool,
+    len: usize,
+    error_out: *mut GraphLiteErrorCode,
+) -> usize {
+    unsafe {
+        read_column(
+            result,
+            column,
+            values_out,
+            nulls_out,
+            len,
+            error_out,
+            |row| row.get_int(column),
+        )
+    }
+}
+
+/// Run a row accessor on row `row` of a result, like `read_row`
+///
+/// # Safety
+/// `result` must be null or a valid handle from `graphlite_query_result`.
+unsafe fn read_cell<T>(
+    result: *const GraphLiteResult,
+    row: usize,
+    error_out: *mut GraphLiteErrorCode,
+    default: T,
+    read: impl FnOnce(&GraphLiteRow) -> Result<T, CellError>,
+) -> T {
+    if result.is_null() {
+        set_error(error_out, GraphLiteErrorCode::NullPointer);
+        return default;
+    }
+    match unsafe { &*result }.row(row) {
+        Some(row) => unsafe { read_row(row, error_out, default, read) },
+        None => {
+            set_error(error_out, GraphLiteErrorCode::OutOfRange);
+            default
+        }
+    }
+}
+
+/// Copy column `column` of every row with `read`, returning the number of rows copied
+///
+/// # Safety
+/// `result` must be null or a valid handle from `graphlite_query_result`;
+/// `values_out` and a non-null `nulls_out` must point to `len` writable elements.
+unsafe fn read_column<T: Default>(
+    result: *const GraphLiteResult,
+    column: usize,
+    values_out: *mut T,
+    nulls_out: *mut bool,
+    len: usize,
+    error_out: *mut GraphLiteErrorCode,
+    read: impl Fn(&GraphLiteRow) -> Result<T, CellError>,
+) -> usize {
+    if result.is_null() || values_out.is_null() {
+        set_error(error_out, GraphLiteErrorCode::NullPointer);
+        return 0;
+    }
+    let result = unsafe { &*result };
+    if column >= result.column_count() {
+        set_error(error_out, GraphLiteErrorCode::OutOfRange);
+        return 0;
+    }
+
+    let count = result.row_count().min(len);
+    for index in 0..count {
+        let row = result.row(index).expect("index is below the row count");
+        let is_null = row.value_type(column) == Ok(GraphLiteValueType::Null);
+        let value = if is_null && !nulls_out.is_null() {
+            T::default()
+        } else {
+            match read(row) {
+                Ok(value) => value,
+                Err(error) => {
+                    set_error(error_out, error.into());
+                    return index;
+                }
+            }
+        };
+        unsafe {
+            values_out.add(index).write(value);
+            if !nulls_out.is_null() {
+                nulls_out.add(index).write(is_null);
+            }
+        }
+    }
+    set_error(error_out, GraphLiteErrorCode::Success);
+    count
+}
+
+/// Run a row accessor, setting `error_out` and returning `default` on failure
+///
+/// # Safety
+/// `row` must be null or a row of a result that has not been freed.
+unsafe fn read_row<T>(
+    row: *const GraphLiteRow,
+    error_out: *mut GraphLiteErrorCode,
+    default: T,
+    read: impl FnOnce(&GraphLiteRow) -> Result<T, CellError>,
+) -> T {
+    if row.is_null() {
+        set_error(error_out, GraphLiteErrorCode::NullPointer);
+        return default;
+    }
+    match read(unsafe { &*row }) {
+        Ok(value) => {
+            set_error(error_out, GraphLiteErrorCode::Success);
+            value
+        }
+        Err(error) => {
+            set_error(error_out, error.into());
+            default
+        }
+    }
+}
+
+/// Bulk load nodes into the session's current graph
+///
+/// Node `i` is described by `labels[i]` and `properties[i]`. Rows are written
+/// directly to storage without parsing a GQL statement per node, and the batch
+/// is persisted in one step (all-or-nothing).
+///
+/// # Arguments
+/// * `db` - Database handle (must not be null)
+/// * `session_id` - C string with session ID (must not be null)
+/// * `labels` - Array of `count` label lists separated by `:`, e.g. `"Person:Employee"`
+///   (the array or individual entries can be null for unlabeled nodes)
+/// * `properties` - Array of `count` JSON objects, e.g. `{"name": "Alice", "age": 30}`
+///   (the array or individual entries can be null for nodes without properties)
+/// * `count` - Number of nodes
+/// * `error_out` - Output parameter for error code (can be null)
+///
+/// # Returns
+/// * JSON array with the storage id of each node, in input order
+///   (must be freed with `graphlite_free_string`); use these ids as edge endpoints
+/// * null pointer on error
+///
+/// # Safety
+/// * `db` must be a valid handle from `graphlite_open`
+/// * `session_id` must be from `graphlite_create_session`
+/// * Non-null `labels` and `properties` arrays must hold `count` pointers, each
+///   null or a valid null-terminated C string
+/// * Returned JSON string must be freed with `graphlite_free_string`
+#[no_mangle]
+pub unsafe extern "C" fn graphlite_bulk_insert_nodes(
+    db: *mut GraphLiteDB,
+    session_id: *const c_char,
+    labels: *const *const c_char,
+    properties: *const *const c_char,
+    count: usize,
+    error_out: *mut GraphLiteErrorCode,
+) -> *mut c_char {
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        if db.is_null() || session_id.is_null() {
+            set_error(error_out, GraphLiteErrorCode::NullPointer);
+            return ptr::null_mut();
+        }
+
+        let db_ref = unsafe { &*db };
+        let _guard = db_ref.lock_shared();
+
+        let session_str = match unsafe { CStr::from_ptr(session_id) }.to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                set_error(error_out, GraphLiteErrorCode::InvalidUtf8);
+                return ptr::null_mut();
+            }
+        };
+
+        let mut nodes = Vec::with_capacity(count);
+        for i in 0..count {
+            let node_labels = match unsafe { c_str_at(labels, i) } {
+                Ok(s) => s
+                    .unwrap_or("")
+                    .split(':')
+                    .map(str::trim)
+                    .filter(|label| !label.is_empty())
+                    .map(String::from)
+                    .collect(),
+                Err(code) => {
+                    set_error(error_out, code);
+                    return ptr::null_mut();
+                }
+            };
+            let node_properties = match unsafe { c_str_at(properties, i) }
+                .and_then(|json| parse_properties(json.unwrap_or("")))
+            {
+                Ok(p) => p,
+                Err(code) => {
+                    set_error(error_out, code);
+                    return ptr::null_mut();
+                }
+            };
+            nodes.push(BulkNode {
+                labels: node_labels,
+                properties: node_properties,
+            });
+        }
+
+        match db_ref.coordinator.bulk_insert_nodes(session_str, nodes) {
+            Ok(ids) => match serde_json::to_string(&ids) {
+                Ok(json) => match CString::new(json) {
+                    Ok(c_string) => {
+                        set_error(error_out, GraphLiteErrorCode::Success);
+                        LEDGER.track(c_string.into_raw(), Kind::String)
+                    }
+                    Err(_) => {
+                        set_error(error_out, GraphLiteErrorCode::JsonError);
+                        ptr::null_mut()
+                    }
+                },
+                Err(_) => {
+                    set_error(error_out, GraphLiteErrorCode::JsonError);
+                    ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                set_error(error_out, GraphLiteErrorCode::from_query_error(&e));
+                ptr::null_mut()
+            }
+        }
+    }));
+
+    match result {
+        Ok(ptr) => ptr,
+        Err(_) => {
+            set_error(error_out, GraphLiteErrorCode::PanicError);
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Bulk load edges into the session's current graph
+///
+/// Edge `i` runs from `from_ids[i]` to `to_ids[i]` with label `labels[i]` and
+/// properties `properties[i]`. Endpoints are node storage ids as returned by
+/// `graphlite_bulk_insert_nodes` and must already exist. Duplicate edges are
+/// skipped; the batch is persisted in one step (all-or-nothing).
+///
+/// # Arguments
+/// * `db` - Database handle (must not be null)
+/// * `session_id` - C string with session ID (must not be null)
+/// * `from_ids` - Array of `count` source node ids (must not be null)
+/// * `to_ids` - Array of `count` target node ids (must not be null)
+/// * `labels` - Array of `count` edge labels (must not be null)
+/// * `properties` - Array of `count` JSON objects (the array or individual entries can be null)
+/// * `count` - Number of edges
+/// * `inserted_out` - Output parameter for the number of edges created (can be null)
+/// * `error_out` - Output parameter for error code (can be null)
+///
+/// # Returns
+/// * Error code (Success = 0, error otherwise)
+///
+/// # Safety
+/// * `db` must be a valid handle from `graphlite_open`
+/// * `session_id` must be from `graphlite_create_session`
+/// * `from_ids`, `to_ids` and `labels` must hold `count` valid null-terminated C strings
+/// * A non-null `properties` array must hold `count` pointers, each null or a valid
+///   null-terminated C string
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub unsafe extern "C" fn graphlite_bulk_insert_edges(
+    db: *mut GraphLiteDB,
+    session_id: *const c_char,
+    from_ids: *const *const c_char,
+    to_ids: *const *const c_char,
+    labels: *const *const c_char,
+    properties: *const *const c_char,
+    count: usize,
+    inserted_out: *mut usize,
+    error_out: *mut GraphLiteErrorCode,
+) -> GraphLiteErrorCode {
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        if db.is_null()
+            || session_id.is_null()
+            || (count > 0 && (from_ids.is_null() || to_ids.is_null() || labels.is_null()))
+        {
+            return GraphLiteErrorCode::NullPointer;
+        }
+
+        let db_ref = unsafe { &*db };
+        let _guard = db_ref.lock_shared();
+
+        let session_str = match unsafe { CStr::from_ptr(session_id) }.to_str() {
+            Ok(s) => s,
+            Err(_) => return GraphLiteErrorCode::InvalidUtf8,
+        };
+
+        let required =
+            |array: *const *const c_char, index: usize| match unsafe { c_str_at(array, index) } {
+                Ok(Some(s)) => Ok(s.to_string()),
+                Ok(None) => Err(GraphLiteErrorCode::NullPointer),
+                Err(code) => Err(code),
+            };
+
+        let mut edges = Vec::with_capacity(count);
+        for i in 0..count {
+            let edge = required(from_ids, i).and_then(|from_node| {
+                let to_node = required(to_ids, i)?;
+                let label = required(labels, i)?;
+                let edge_properties = unsafe { c_str_at(properties, i) }
+                    .and_then(|json| parse_properties(json.unwrap_or("")))?;
+                Ok(BulkEdge {
+                    from_node,
+                    to_node,
+                    label,
+                    properties: edge_properties,
+                })
+            });
+            match edge {
+                Ok(edge) => edges.push(edge),
+                Err(code) => return code,
+            }
+        }
+
+        match db_ref.coordinator.bulk_insert_edges(session_str, edges) {
+            Ok(inserted) => {
+                if !inserted_out.is_null() {
+                    unsafe {
+                        *inserted_out = inserted;
+                    }
+                }
+                GraphLiteErrorCode::Success
+            }
+            Err(e) => GraphLiteErrorCode::from_query_error(&e),
+        }
+    }));
+
+    let code = result.unwrap_or(GraphLiteErrorCode::PanicError);
+    set_error(error_out, code);
+    code
+}
+
+/// CSV parsing options for `graphlite_import_csv`
+///
+/// Obtain a populated instance with `graphlite_csv_options_default` and override
+/// the fields you need.
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct GraphLiteCsvOptions {
+    /// Field separator (default `,`)
+    pub delimiter: c_char,
+    /// First record holds the property names; otherwise columns are named
+    /// `column1`, `column2`, ... (default true)
+    pub has_header: bool,
+    /// Store numeric and `true`/`false` fields as numbers and booleans instead of
+    /// strings (default true)
+    pub infer_types: bool,
+}
+
+impl Default for GraphLiteCsvOptions {
+    fn default() -> Self {
+        let defaults = CsvImportOptions::default();
+        Self {
+            delimiter: defaults.delimiter as c_char,
+            has_header: defaults.has_header,
+            infer_types: defaults.infer_types,
+        }
+    }
+}
+
+impl From<&GraphLiteCsvOptions> for CsvImportOptions {
+    fn from(options: &GraphLiteCsvOptions) -> Self {
+        CsvImportOptions {
+            delimiter: options.delimiter as u8,
+            has_header: options.has_header,
+            infer_types: options.infer_types,
+        }
+    }
+}
+
+/// Outcome of `graphlite_import_csv`
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct GraphLiteImportSummary {
+    /// Data records read from the file
+    pub rows: u64,
+    /// Nodes created (records identical to an existing node are skipped)
+    pub nodes_created: u64,
+}
+
+/// Get CSV options populated with the engine defaults
+///
+/// # Example
+/// ```c
+/// GraphLiteCsvOptions options = graphlite_csv_options_default();
+/// options.delimiter = ';';
+/// ```
+#[no_mangle]
+pub extern "C" fn graphlite_csv_options_default() -> GraphLiteCsvOptions {
+    GraphLiteCsvOptions::default()
+}
+
+/// Import a CSV file as nodes into the session's current graph
+///
+/// Every record becomes a node labeled `label` with one property per column;
+/// empty fields leave the property unset. The file is read and parsed by the
+/// engine and loaded through the bulk insert path in one all-or-nothing write,
+/// so no GQL statement is built per row. Records identical to an existing node
+/// are skipped. The import cannot run inside an open transaction.
+///
+/// # Arguments
+/// * `db` - Database handle (must not be null)
+/// * `session_id` - C string with session ID (must not be null)
+/// * `file_path` - C string with the path of the CSV file (must not be null)
+/// * `label` - C string with the node label (must not be null)
+/// * `options` - CSV parsing options (can be null to use defaults)
+/// * `summary_out` - Output parameter for the rows read and nodes created (can be null)
+/// * `error_out` - Output parameter for error code (can be null)
+///
+/// # Returns
+/// * Error code (Success = 0, error otherwise); `QueryError` if the file cannot be
+///   read or parsed, `GraphNotFound` if there is no current graph
+///
+/// # Safety
+/// * `db` must be a valid handle from `graphlite_open`
+/// * `session_id` must be from `graphlite_create_session`
+/// * `file_path` and `label` must be valid null-terminated C strings
+/// * `options` must be null or point to a valid `GraphLiteCsvOptions`
+///
+/// # Example
+/// ```c
+/// GraphLiteCsvOptions options = graphlite_csv_options_default();
+/// GraphLiteImportSummary summary;
+/// graphlite_import_csv(db, session, "people.csv", "Person", &options, &summary, &error);
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn graphlite_import_csv(
+    db: *mut GraphLiteDB,
+    session_id: *const c_char,
+    file_path: *const c_char,
+    label: *const c_char,
+    options: *const GraphLiteCsvOptions,
+    summary_out: *mut GraphLiteImportSummary,
+    error_out: *mut GraphLiteErrorCode,
+) -> GraphLiteErrorCode {
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        if db.is_null() || session_id.is_null() || file_path.is_null() || label.is_null() {
+            return GraphLiteErrorCode::NullPointer;
+        }
+
+        let db_ref = unsafe { &*db };
+        let _guard = db_ref.lock_shared();
+
+        let (session_str, path_str, label_str) = match (
+            unsafe { CStr::from_ptr(session_id) }.to_str(),
+            unsafe { CStr::from_ptr(file_path) }.to_str(),
+            unsafe { CStr::from_ptr(label) }.to_str(),
+        ) {
+            (Ok(session), Ok(path), Ok(label)) => (session, path, label),
+            _ => return GraphLiteErrorCode::InvalidUtf8,
+        };
+
+        let options = if options.is_null() {
+            GraphLiteCsvOptions::default()
+        } else {
+            unsafe { *options }
+        };
+
+        match db_ref.coordinator.import_csv(
+            session_str,
+            path_str,
+            label_str,
+            &CsvImportOptions::from(&options),
+        ) {
+            Ok(summary) => {
+                if !summary_out.is_null() {
+                    unsafe {
+                        *summary_out = GraphLiteImportSummary {
+                            rows: summary.rows as u64,
+                            nodes_created: summary.nodes_created as u64,
+                        };
+                    }
+                }
+                GraphLiteErrorCode::Success
+            }
+            Err(e) => GraphLiteErrorCode::from_query_error(&e),
+        }
+    }));
+
+    let code = result.unwrap_or(GraphLiteErrorCode::PanicError);
+    set_error(error_out, code);
+    code
+}
+
+/// Execute several statements in a single transaction
+///
+/// The statements run in order inside one transaction that is committed when all
+/// of them succeed. Execution stops at the first failing statement and the whole
+/// batch is rolled back. Transaction control statements (`START TRANSACTION`,
+/// `COMMIT`, `ROLLBACK`) are not allowed, and the session must not have an open
+/// transaction.
+///
+/// # Arguments
+/// * `db` - Database handle (must not be null)
+/// * `session_id` - C string with session ID (must not be null)
+/// * `statements` - Array of `count` GQL statements (must not be null)
+/// * `count` - Number of statements
+/// * `status_out` - Array of `count` per-statement status codes (can be null):
+///   `Success` for statements that ran, the error code of the statement that
+///   failed and `NotExecuted` for the statements after it
+/// * `error_out` - Output parameter for error code (can be null); the code of the
+///   failed statement means the batch was rolled back
+///
+/// # Returns
+/// * JSON array with the result of each statement, in input order
+///   (must be freed with `graphlite_free_string`)
+/// * null pointer on error
+///
+/// # Safety
+/// * `db` must be a valid handle from `graphlite_open`
+/// * `session_id` must be from `graphlite_create_session`
+/// * `statements` must hold `count` valid null-terminated C strings
+/// * A non-null `status_out` must have room for `count` codes
+/// * Returned JSON string must be freed with `graphlite_free_string`
+#[no_mangle]
+pub unsafe extern "C" fn graphlite_execute_many(
+    db: *mut GraphLiteDB,
+    session_id: *const c_char,
+    statements: *const *const c_char,
+    count: usize,
+    status_out: *mut GraphLiteErrorCode,
+    error_out: *mut GraphLiteErrorCode,
+) -> *mut c_char {
+    let set_status = |index: usize, code: GraphLiteErrorCode| {
+        if !status_out.is_null() {
+            unsafe {
+                *status_out.add(index) = code;
+            }
+        }
+    };
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        (0..count).for_each(|i| set_status(i, GraphLiteErrorCode::NotExecuted));
+
+        if db.is_null() || session_id.is_null() || (count > 0 && statements.is_null()) {
+            set_error(error_out, GraphLiteErrorCode::NullPointer);
+            return ptr::null_mut();
+        }
+
+        let db_ref = unsafe { &*db };
+        let _guard = db_ref.lock_shared();
+
+        let session_str = match unsafe { CStr::from_ptr(session_id) }.to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                set_error(error_out, GraphLiteErrorCode::InvalidUtf8);
+                return ptr::null_mut();
+            }
+        };
+
+        let mut batch = Vec::with_capacity(count);
+        for i in 0..count {
+            match unsafe { c_str_at(statements, i) } {
+                Ok(Some(statement)) => batch.push(statement),
+                Ok(None) => {
+                    set_error(error_out, GraphLiteErrorCode::NullPointer);
+                    return ptr::null_mut();
+                }
+                Err(code) => {
+                    set_error(error_out, code);
+                    return ptr::null_mut();
+                }
+            }
+        }
+
+        match db_ref.coordinator.process_batch(&batch, session_str) {
+            Ok(results) => {
+                (0..count).for_each(|i| set_status(i, GraphLiteErrorCode::Success));
+                match db_ref.number_format().to_string(&results) {
+                    Ok(json) => match CString::new(json) {
+                        Ok(c_string) => {
+                            set_error(error_out, GraphLiteErrorCode::Success);
+                            LEDGER.track(c_string.into_raw(), Kind::String)
+                        }
+                        Err(_) => {
+                            set_error(error_out, GraphLiteErrorCode::JsonError);
+                            ptr::null_mut()
+                        }
+                    },
+                    Err(_) => {
+                        set_error(error_out, GraphLiteErrorCode::JsonError);
+                        ptr::null_mut()
+                    }
+                }
+            }
+            Err(err) => {
+                let code = GraphLiteErrorCode::from_query_error(&err.message);
+                (0..err.index.min(count)).for_each(|i| set_status(i, GraphLiteErrorCode::Success));
+                if err.index < count {
+                    set_status(err.index, code);
+                }
+                set_error(error_out, code);
+                ptr::null_mut()
+            }
+        }
+    }));
+
+    match result {
+        Ok(ptr) => ptr,
+        Err(_) => {
+            set_error(error_out, GraphLiteErrorCode::PanicError);
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Run several independent queries in one call
+///
+/// Every query runs on its own, in order: a failing query neither stops nor
+/// undoes the others. Issuing a page's queries together saves a call through the
+/// FFI boundary per query.
+///
+/// # Arguments
+/// * `db` - Database handle (must not be null)
+/// * `session_id` - C string with session ID (must not be null)
+/// * `queries` - Array of `count` GQL queries (must not be null)
+/// * `count` - Number of queries
+/// * `status_out` - Array of `count` per-query status codes (can be null)
+/// * `error_out` - Output parameter for error code (can be null); `Success` when
+///   the queries ran, even if some of them failed
+///
+/// # Returns
+/// * JSON array with one entry per query, in input order: the query result, or
+///   `{"error": "<message>"}` if the query failed
+///   (must be freed with `graphlite_free_string`)
+/// * null pointer on error
+///
+/// # Safety
+/// * `db` must be a valid handle from `graphlite_open`
+/// * `session_id` must be from `graphlite_create_session`
+/// * `queries` must hold `count` valid null-terminated C strings
+/// * A non-null `status_out` must have room for `count` codes
+/// * Returned JSON string must be freed with `graphlite_free_string`
+#[no_mangle]
+pub unsafe extern "C" fn graphlite_query_many(
+    db: *mut GraphLiteDB,
+    session_id: *const c_char,
+    queries: *const *const c_char,
+    count: usize,
+    status_out: *mut GraphLiteErrorCode,
+    error_out: *mut GraphLiteErrorCode,
+) -> *mut c_char {
+    let set_status = |index: usize, code: GraphLiteErrorCode| {
+        if !status_out.is_null() {
+            unsafe {
+                *status_out.add(index) = code;
+            }
+        }
+    };
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        (0..count).for_each(|i| set_status(i, GraphLiteErrorCode::NotExecuted));
+
+        if db.is_null() || session_id.is_null() || (count > 0 && queries.is_null()) {
+            set_error(error_out, GraphLiteErrorCode::NullPointer);
+            return ptr::null_mut();
+        }
+
+        let db_ref = unsafe { &*db };
+        let _guard = db_ref.lock_shared();
+
+        let session_str = match unsafe { CStr::from_ptr(session_id) }.to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                set_error(error_out, GraphLiteErrorCode::InvalidUtf8);
+                return ptr::null_mut();
+            }
+        };
+
+        let mut batch = Vec::with_capacity(count);
+        for i in 0..count {
+            match unsafe { c_str_at(queries, i) } {
+                Ok(Some(query)) => batch.push(query),
+                Ok(None) => {
+                    set_error(error_out, GraphLiteErrorCode::NullPointer);
+                    return ptr::null_mut();
+                }
+                Err(code) => {
+                    set_error(error_out, code);
+                    return ptr::null_mut();
+                }
+            }
+        }
+
+        let mut entries = Vec::with_capacity(count);
+        for (index, outcome) in db_ref
+            .coordinator
+            .process_queries(&batch, session_str)
+            .into_iter()
+            .enumerate()
+        {
+            let entry = match outcome {
+                Ok(result) => {
+                    set_status(index, GraphLiteErrorCode::Success);
+                    serde_json::to_value(&result)
+                }
+                Err(message) => {
+                    set_status(index, GraphLiteErrorCode::from_query_error(&message));
+                    Ok(serde_json::json!({ "error": message }))
+                }
+            };
+            match entry {
+                Ok(entry) => entries.push(entry),
+                Err(_) => {
+                    set_error(error_out, GraphLiteErrorCode::JsonError);
+                    return ptr::null_mut();
+                }
+            }
+        }
+
+        let json = match db_ref.number_format().to_string(&entries) {
+            Ok(json) => json,
+            Err(_) => {
+                set_error(error_out, GraphLiteErrorCode::JsonError);
+                return ptr::null_mut();
+            }
+        };
+        match CString::new(json) {
+            Ok(c_string) => {
+                set_error(error_out, GraphLiteErrorCode::Success);
+                LEDGER.track(c_string.into_raw(), Kind::String)
+            }
+            Err(_) => {
+                set_error(error_out, GraphLiteErrorCode::JsonError);
+                ptr::null_mut()
+            }
+        }
+    }));
+
+    match result {
+        Ok(ptr) => ptr,
+        Err(_) => {
+            set_error(error_out, GraphLiteErrorCode::PanicError);
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Close a session
+///
+/// # Arguments
+/// * `db` - Database handle (must not be null)
+/// * `session_id` - C string with session ID (must not be null)
+/// * `error_out` - Output parameter for error code (can be null)
+///
+/// # Returns
+/// * Error code (Success = 0, error otherwise)
+///
+/// # Safety
+/// * `db` must be a valid handle from `graphlite_open`
+/// * `session_id` must be from `graphlite_create_session`
+#[no_mangle]
+pub unsafe extern "C" fn graphlite_close_session(
+    db: *mut GraphLiteDB,
+    session_id: *const c_char,
+    error_out: *mut GraphLiteErrorCode,
+) -> GraphLiteErrorCode {
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        if db.is_null() || session_id.is_null() {
+            set_error(error_out, GraphLiteErrorCode::NullPointer);
+            return GraphLiteErrorCode::NullPointer;
+        }
+
+        let db_ref = unsafe { &*db };
+        let _guard = db_ref.lock_shared();
+
+        let session_c_str = unsafe { CStr::from_ptr(session_id) };
+        let session_str = match session_c_str.to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                set_error(error_out, GraphLiteErrorCode::InvalidUtf8);
+                return GraphLiteErrorCode::InvalidUtf8;
+            }
+        };
+
+        match db_ref.coordinator.close_session(session_str) {
+            Ok(_) => {
+                set_error(error_out, GraphLiteErrorCode::Success);
+                GraphLiteErrorCode::Success
+            }
+            Err(_) => {
+                set_error(error_out, GraphLiteErrorCode::SessionError);
+                GraphLiteErrorCode::SessionError
+            }
+        }
+    }));
+
+    match result {
+        Ok(code) => code,
+        Err(_) => {
+            set_error(error_out, GraphLiteErrorCode::PanicError);
+            GraphLiteErrorCode::PanicError
+        }
+    }
+}
+
+/// Start a transaction on a session (`START TRANSACTION`)
+///
+/// Statements run on the session afterwards, through `graphlite_query` or any other
+/// function taking its session ID, belong to the transaction until
+/// `graphlite_commit` or `graphlite_rollback`.
+///
+/// # Arguments
+/// * `db` - Database handle (must not be null)
+/// * `session_id` - C string with session ID (must not be null)
+/// * `error_out` - Output parameter for error code (can be null)
+///
+/// # Returns
+/// * Error code (Success = 0, `TransactionConflict` if the session already has an
+///   open transaction)
+///
+/// # Safety
+/// * `db` must be a valid handle from `graphlite_open`
+/// * `session_id` must be a valid null-terminated C string
+///
+/// # Example
+/// ```c
+/// graphlite_begin(db, session_id, &error);
+/// char* a = graphlite_query(db, session_id, "INSERT (:Account {id: 1, balance: 100})", &error);
+/// char* b = graphlite_query(db, session_id, "INSERT (:Account {id: 2, balance: 0})", &error);
+/// if (a && b) {
+///     graphlite_commit(db, session_id, &error);
+/// } else {
+///     graphlite_rollback(db, session_id, &error);
+/// }
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn graphlite_begin(
+    db: *mut GraphLiteDB,
+    session_id: *const c_char,
+    error_out: *mut GraphLiteErrorCode,
+) -> GraphLiteErrorCode {
+    unsafe { run_transaction_control(db, session_id, "START TRANSACTION", error_out) }
+}
+
+/// Commit the open transaction of a session (`COMMIT`)
+///
+/// # Arguments
+/// * `db` - Database handle (must not be null)
+/// * `session_id` - C string with session ID (must not be null)
+/// * `error_out` - Output parameter for error code (can be null)
+///
+/// # Returns
+/// * Error code (Success = 0, `TransactionConflict` if the session has no open
+///   transaction)
+///
+/// # Safety
+/// * `db` must be a valid handle from `graphlite_open`
+/// * `session_id` must be a valid null-terminated C string
+#[no_mangle]
+pub unsafe extern "C" fn graphlite_commit(
+    db: *mut GraphLiteDB,
+    session_id: *const c_char,
+    error_out: *mut GraphLiteErrorCode,
+) -> GraphLiteErrorCode {
+    unsafe { run_transaction_control(db, session_id, "COMMIT", error_out) }
+}
+
+/// Roll back the open transaction of a session (`ROLLBACK`)
+///
+/// # Arguments
+/// * `db` - Database handle (must not be null)
+/// * `session_id` - C string with session ID (must not be null)
+/// * `error_out` - Output parameter for error code (can be null)
+///
+/// # Returns
+/// * Error code (Success = 0, `TransactionConflict` if the session has no open
+///   transaction)
+///
+/// # Safety
+/// * `db` must be a valid handle from `graphlite_open`
+/// * `session_id` must be a valid null-terminated C string
+#[no_mangle]
+pub unsafe extern "C" fn graphlite_rollback(
+    db: *mut GraphLiteDB,
+    session_id: *const c_char,
+    error_out: *mut GraphLiteErrorCode,
+) -> GraphLiteErrorCode {
+    unsafe { run_transaction_control(db, session_id, "ROLLBACK", error_out) }
+}
+
+/// Check whether a session has an open transaction
+///
+/// # Arguments
+/// * `db` - Database handle (must not be null)
+/// * `session_id` - C string with session ID (must not be null)
+/// * `error_out` - Output parameter for error code (can be null)
+///
+/// # Returns
+/// * true if a transaction started with `graphlite_begin` (or `START TRANSACTION`)
+///   is still open
+/// * false otherwise, or on error (`SessionError` if the session does not exist)
+///
+/// # Safety
+/// * `db` must be a valid handle from `graphlite_open`
+/// * `session_id` must be a valid null-terminated C string
+#[no_mangle]
+pub unsafe extern "C" fn graphlite_in_transaction(
+    db: *mut GraphLiteDB,
+    session_id: *const c_char,
+    error_out: *mut GraphLiteErrorCode,
+) -> bool {
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        if db.is_null() || session_id.is_null() {
+            set_error(error_out, GraphLiteErrorCode::NullPointer);
+            return false;
+        }
+
+        let db_ref = unsafe { &*db };
+        let _guard = db_ref.lock_shared();
+
+        let session_str = match unsafe { CStr::from_ptr(session_id) }.to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                set_error(error_out, GraphLiteErrorCode::InvalidUtf8);
+                return false;
+            }
+        };
+
+        match db_ref.coordinator.session_handle(session_str) {
+            Ok(handle) => {
+                set_error(error_out, GraphLiteErrorCode::Success);
+                handle.in_transaction()
+            }
+            Err(_) => {
+                set_error(error_out, GraphLiteErrorCode::SessionError);
+                false
+            }
+        }
+    }));
+
+    match result {
+        Ok(in_transaction) => in_transaction,
+        Err(_) => {
+            set_error(error_out, GraphLiteErrorCode::PanicError);
+            false
+        }
+    }
+}
+
+/// Run a transaction control statement on a session
+///
+/// # Safety
+/// Same requirements as `graphlite_begin`.
+unsafe fn run_transaction_control(
+    db: *mut GraphLiteDB,
+    session_id: *const c_char,
+    statement: &str,
+    error_out: *mut GraphLiteErrorCode,
+) -> GraphLiteErrorCode {
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        if db.is_null() || session_id.is_null() {
+            set_error(error_out, GraphLiteErrorCode::NullPointer);
+            return GraphLiteErrorCode::NullPointer;
+        }
+
+        let db_ref = unsafe { &*db };
+        let _guard = db_ref.lock_shared();
+
+        let session_str = match unsafe { CStr::from_ptr(session_id) }.to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                set_error(error_out, GraphLiteErrorCode::InvalidUtf8);
+                return GraphLiteErrorCode::InvalidUtf8;
+            }
+        };
+
+        let code = match db_ref.coordinator.process_query(statement, session_str) {
+            Ok(_) => GraphLiteErrorCode::Success,
+            Err(e) => GraphLiteErrorCode::from_query_error(&e),
+        };
+        set_error(error_out, code);
+        code
+    }));
+
+    match result {
+        Ok(code) => code,
+        Err(_) => {
+            set_error(error_out, GraphLiteErrorCode::PanicError);
+            GraphLiteErrorCode::PanicError
+        }
+    }
+}
+
+/// List the graphs of the database
+///
+/// # Arguments
+/// * `db` - Database handle (must not be null)
+/// * `session_id` - C string with session ID (must not be null)
+/// * `count_out` - Output parameter for the number of graphs (must not be null)
+/// * `error_out` - Output parameter for error code (can be null)
+///
+/// # Returns
+/// * Array of `*count_out` full graph paths such as `/app/social`, sorted
+///   (must be freed with `graphlite_free_string_array`)
+/// * null pointer on error
+///
+/// # Safety
+/// * `db` must be a valid handle from `graphlite_open`
+/// * `session_id` must be a valid null-terminated C string
+/// * `count_out` must point to writable memory
+///
+/// # Example
+/// ```c
+/// size_t count;
+/// char** graphs = graphlite_list_graphs(db, session_id, &count, &error);
+/// for (size_t i = 0; i < count; i++) {
+///     printf("%s\n", graphs[i]);
+/// }
+/// graphlite_free_string_array(graphs, count);
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn graphlite_list_graphs(
+    db: *mut GraphLiteDB,
+    session_id: *const c_char,
+    count_out: *mut usize,
+    error_out: *mut GraphLiteErrorCode,
+) -> *mut *mut c_char {
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        if db.is_null() || session_id.is_null() || count_out.is_null() {
+            set_error(error_out, GraphLiteErrorCode::NullPointer);
+            return ptr::null_mut();
+        }
+        unsafe { *count_out = 0 };
+
+        let db_ref = unsafe { &*db };
+        let _guard = db_ref.lock_shared();
+
+        let session_str = match unsafe { CStr::from_ptr(session_id) }.to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                set_error(error_out, GraphLiteErrorCode::InvalidUtf8);
+                return ptr::null_mut();
+            }
+        };
+
+        let result = match db_ref
+            .coordinator
+            .process_query("CALL gql.list_graphs()", session_str)
+        {
+            Ok(result) => result,
+            Err(e) => {
+                set_error(error_out, GraphLiteErrorCode::from_query_error(&e));
+                return ptr::null_mut();
+            }
+        };
+
+        let mut paths: Vec<String> = result
+            .rows
+            .iter()
+            .filter_map(
+                |row| match (row.get_value("schema_name"), row.get_value("graph_name")) {
+                    (Some(Value::String(schema)), Some(Value::String(graph))) => {
+                        Some(format!("/{}/{}", schema, graph))
+                    }
+                    _ => None,
+                },
+            )
+            .collect();
+        paths.sort();
+
+        let mut strings = Vec::with_capacity(paths.len());
+        for path in paths {
+            match CString::new(path) {
+                Ok(c_string) => strings.push(LEDGER.track(c_string.into_raw(), Kind::String)),
+                Err(_) => {
+                    strings
+                        .into_iter()
+                        .for_each(|s| unsafe { graphlite_free_string(s) });
+                    set_error(error_out, GraphLiteErrorCode::InvalidUtf8);
+                    return ptr::null_mut();
+                }
+            }
+        }
+
+        unsafe { *count_out = strings.len() };
+        set_error(error_out, GraphLiteErrorCode::Success);
+        LEDGER.track(
+            Box::into_raw(strings.into_boxed_slice()) as *mut *mut c_char,
+            Kind::StringArray,
+        )
+    }));
+
+    match result {
+        Ok(ptr) => ptr,
+        Err(_) => {
+            set_error(error_out, GraphLiteErrorCode::PanicError);
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Create a graph (`CREATE GRAPH`)
+///
+/// # Arguments
+/// * `db` - Database handle (must not be null)
+/// * `session_id` - C string with session ID (must not be null)
+/// * `graph_path` - Full graph path such as `/app/social`, or a graph name in the
+///   session's current schema (must not be null)
+/// * `error_out` - Output parameter for error code (can be null)
+///
+/// # Returns
+/// * Error code (Success = 0, `ConstraintViolation` if the graph already exists,
+///   `GraphNotFound` if its schema does not exist, `ParseError` if `graph_path`
+///   is not a graph path)
+///
+/// # Safety
+/// * `db` must be a valid handle from `graphlite_open`
+/// * `session_id` and `graph_path` must be valid null-terminated C strings
+#[no_mangle]
+pub unsafe extern "C" fn graphlite_create_graph(
+    db: *mut GraphLiteDB,
+    session_id: *const c_char,
+    graph_path: *const c_char,
+    error_out: *mut GraphLiteErrorCode,
+) -> GraphLiteErrorCode {
+    unsafe { run_graph_statement(db, session_id, "CREATE GRAPH", graph_path, error_out) }
+}
+
+/// Drop a graph and its data (`DROP GRAPH`)
+///
+/// # Arguments
+/// * `db` - Database handle (must not be null)
+/// * `session_id` - C string with session ID (must not be null)
+/// * `graph_path` - Full graph path such as `/app/social`, or a graph name in the
+///   session's current schema (must not be null)
+/// * `error_out` - Output parameter for error code (can be null)
+///
+/// # Returns
+/// * Error code (Success = 0, `GraphNotFound` if the graph does not exist,
+///   `ParseError` if `graph_path` is not a graph path)
+///
+/// # Safety
+/// * `db` must be a valid handle from `graphlite_open`
+/// * `session_id` and `graph_path` must be valid null-terminated C strings
+#[no_mangle]
+pub unsafe extern "C" fn graphlite_drop_graph(
+    db: *mut GraphLiteDB,
+    session_id: *const c_char,
+    graph_path: *const c_char,
+    error_out: *mut GraphLiteErrorCode,
+) -> GraphLiteErrorCode {
+    unsafe { run_graph_statement(db, session_id, "DROP GRAPH", graph_path, error_out) }
+}
+
+/// Set the current graph of a session (`SESSION SET GRAPH`)
+///
+/// Same as `graphlite_session_set_graph`, for session IDs.
+///
+/// # Arguments
+/// * `db` - Database handle (must not be null)
+/// * `session_id` - C string with session ID (must not be null)
+/// * `graph_path` - Full graph path such as `/app/social`, or a graph name in the
+///   session's current schema (must not be null)
+/// * `error_out` - Output parameter for error code (can be null)
+///
+/// # Returns
+/// * Error code (Success = 0, `GraphNotFound` if the graph does not exist,
+///   `ParseError` if `graph_path` is not a graph path)
+///
+/// # Safety
+/// * `db` must be a valid handle from `graphlite_open`
+/// * `session_id` and `graph_path` must be valid null-terminated C strings
+#[no_mangle]
+pub unsafe extern "C" fn graphlite_set_session_graph(
+    db: *mut GraphLiteDB,
+    session_id: *const c_char,
+    graph_path: *const c_char,
+    error_out: *mut GraphLiteErrorCode,
+) -> GraphLiteErrorCode {
+    unsafe { run_graph_statement(db, session_id, "SESSION SET GRAPH", graph_path, error_out) }
+}
+
+/// Run `<statement> <graph_path>` on a session
+///
+/// The path is checked before it is put in the statement, so it cannot carry
+/// anything but a graph path.
+///
+/// # Safety
+/// Same requirements as `graphlite_create_graph`.
+unsafe fn run_graph_statement(
+    db: *mut GraphLiteDB,
+    session_id: *const c_char,
+    statement: &str,
+    graph_path: *const c_char,
+    error_out: *mut GraphLiteErrorCode,
+) -> GraphLiteErrorCode {
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        if db.is_null() || session_id.is_null() || graph_path.is_null() {
+            set_error(error_out, GraphLiteErrorCode::NullPointer);
+            return GraphLiteErrorCode::NullPointer;
+        }
+
+        let db_ref = unsafe { &*db };
+        let _guard = db_ref.lock_shared();
+
+        let (session_str, path_str) = match (
+            unsafe { CStr::from_ptr(session_id) }.to_str(),
+            unsafe { CStr::from_ptr(graph_path) }.to_str(),
+        ) {
+            (Ok(session), Ok(path)) => (session, path.trim()),
+            _ => {
+                set_error(error_out, GraphLiteErrorCode::InvalidUtf8);
+                return GraphLiteErrorCode::InvalidUtf8;
+            }
+        };
+        if !is_graph_path(path_str) {
+            set_error(error_out, GraphLiteErrorCode::ParseError);
+            return GraphLiteErrorCode::ParseError;
+        }
+
+        let statement = format!("{} {}", statement, path_str);
+        let code = match db_ref.coordinator.process_query(&statement, session_str) {
+            Ok(_) => GraphLiteErrorCode::Success,
+            Err(e) => GraphLiteErrorCode::from_query_error(&e),
+        };
+        set_error(error_out, code);
+        code
+    }));
+
+    match result {
+        Ok(code) => code,
+        Err(_) => {
+            set_error(error_out, GraphLiteErrorCode::PanicError);
+            GraphLiteErrorCode::PanicError
+        }
+    }
+}
+
+// Helper function to check for an identifier usable as a schema or graph name
+fn is_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+// Helper function to check for `/schema/graph` or a bare graph name
+fn is_graph_path(path: &str) -> bool {
+    match path.strip_prefix('/') {
+        Some(rest) => rest.split('/').count() >= 2 && rest.split('/').all(is_name),
+        None => is_name(path),
+    }
+}
+
+// Helper function to check for `/schema` or a bare schema name
+fn is_schema_path(path: &str) -> bool {
+    is_name(path.strip_prefix('/').unwrap_or(path))
+}
+
+/// Opaque handle to a session
+///
+/// Created with `graphlite_session_open` or `graphlite_session_from_id` and freed
+/// with `graphlite_session_close`. The session is resolved once when the handle is
+/// created, so queries run through `graphlite_session_query` skip the session ID
+/// lookup and UTF-8 validation that `graphlite_query` does on every call. The
+/// session ID and current schema/graph are kept as C strings owned by the handle.
+pub struct GraphLiteSession {
+    handle: SessionHandle,
+    id: CString,
+    current_schema: Option<CString>,
+    current_graph: Option<CString>,
+    /// Display settings for `graphlite_eval`
+    console: Console,
+}
+
+impl GraphLiteSession {
+    fn new(handle: SessionHandle) -> Option<Self> {
+        let id = CString::new(handle.id()).ok()?;
+        let mut session = Self {
+            handle,
+            id,
+            current_schema: None,
+            current_graph: None,
+            console: Console::default(),
+        };
+        session.refresh();
+        Some(session)
+    }
+
+    /// Update the cached schema and graph from the session
+    fn refresh(&mut self) {
+        fn update(cached: &mut Option<CString>, current: Option<String>) {
+            let unchanged = match (cached.as_ref(), current.as_deref()) {
+                (Some(cached), Some(current)) => cached.as_bytes() == current.as_bytes(),
+                (None, None) => true,
+                _ => false,
+            };
+            if !unchanged {
+                *cached = current.and_then(|value| CString::new(value).ok());
+            }
+        }
+        update(&mut self.current_schema, self.handle.current_schema());
+        update(&mut self.current_graph, self.handle.current_graph());
+    }
+}
+
+/// Create a session for the given username and return a handle to it
+///
+/// # Arguments
+/// * `db` - Database handle (must not be null)
+/// * `username` - C string with username (must not be null)
+/// * `error_out` - Output parameter for error code (can be null)
+///
+/// # Returns
+/// * Session handle on success (must be closed with `graphlite_session_close`)
+/// * null pointer on error
+///
+/// # Safety
+/// * `db` must be a valid handle from `graphlite_open`
+/// * `username` must be a valid null-terminated C string
+///
+/// # Example
+/// ```c
+/// GraphLiteSession* session = graphlite_session_open(db, "admin", &error);
+/// graphlite_session_set_graph(db, session, "/app/social", &error);
+/// char* json = graphlite_session_query(db, session, "MATCH (n) RETURN n", &error);
+/// graphlite_free_string(json);
+/// graphlite_session_close(db, session, &error);
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn graphlite_session_open(
+    db: *mut GraphLiteDB,
+    username: *const c_char,
+    error_out: *mut GraphLiteErrorCode,
+) -> *mut GraphLiteSession {
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        if db.is_null() || username.is_null() {
+            set_error(error_out, GraphLiteErrorCode::NullPointer);
+            return ptr::null_mut();
+        }
+
+        let db_ref = unsafe { &*db };
+        let _guard = db_ref.lock_shared();
+
+        let username_str = match unsafe { CStr::from_ptr(username) }.to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                set_error(error_out, GraphLiteErrorCode::InvalidUtf8);
+                return ptr::null_mut();
+            }
+        };
+
+        let coordinator = &db_ref.coordinator;
+        match coordinator
+            .create_simple_session(username_str)
+            .and_then(|session_id| coordinator.session_handle(&session_id))
+        {
+            Ok(handle) => new_session_handle(handle, error_out),
+            Err(_) => {
+                set_error(error_out, GraphLiteErrorCode::SessionError);
+                ptr::null_mut()
+            }
+        }
+    }));
+
+    match result {
+        Ok(ptr) => ptr,
+        Err(_) => {
+            set_error(error_out, GraphLiteErrorCode::PanicError);
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Wrap an existing session ID in a session handle
+///
+/// Lets callers that already hold a session ID from `graphlite_create_session`
+/// switch to the handle API. Both refer to the same session; closing the handle
+/// closes the session.
+///
+/// # Arguments
+/// * `db` - Database handle (must not be null)
+/// * `session_id` - C string with session ID (must not be null)
+/// * `error_out` - Output parameter for error code (can be null)
+///
+/// # Returns
+/// * Session handle on success (must be closed with `graphlite_session_close`)
+/// * null pointer on error (`SessionError` if the session does not exist)
+///
+/// # Safety
+/// * `db` must be a valid handle from `graphlite_open`
+/// * `session_id` must be a valid null-terminated C string
+#[no_mangle]
+pub unsafe extern "C" fn graphlite_session_from_id(
+    db: *mut GraphLiteDB,
+    session_id: *const c_char,
+    error_out: *mut GraphLiteErrorCode,
+) -> *mut GraphLiteSession {
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        if db.is_null() || session_id.is_null() {
+            set_error(error_out, GraphLiteErrorCode::NullPointer);
+            return ptr::null_mut();
+        }
+
+        let db_ref = unsafe { &*db };
+        let _guard = db_ref.lock_shared();
+
+        let session_str = match unsafe { CStr::from_ptr(session_id) }.to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                set_error(error_out, GraphLiteErrorCode::InvalidUtf8);
+                return ptr::null_mut();
+            }
+        };
+
+        match db_ref.coordinator.session_handle(session_str) {
+            Ok(handle) => new_session_handle(handle, error_out),
+            Err(_) => {
+                set_error(error_out, GraphLiteErrorCode::SessionError);
+                ptr::null_mut()
+            }
+        }
+    }));
+
+    match result {
+        Ok(ptr) => ptr,
+        Err(_) => {
+            set_error(error_out, GraphLiteErrorCode::PanicError);
+            ptr::null_mut()
+        }
+    }
+}
+
+// Helper function to box a session handle for the caller
+fn new_session_handle(
+    handle: SessionHandle,
+    error_out: *mut GraphLiteErrorCode,
+) -> *mut GraphLiteSession {
+    match GraphLiteSession::new(handle) {
+        Some(session) => {
+            set_error(error_out, GraphLiteErrorCode::Success);
+            LEDGER.track(Box::into_raw(Box::new(session)), Kind::Session)
+        }
+        None => {
+            set_error(error_out, GraphLiteErrorCode::InvalidUtf8);
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Get the ID of a session handle
+///
+/// The ID can be passed to the functions that take a `session_id` string, such as
+/// `graphlite_bulk_insert_nodes`.
+///
+/// # Returns
+/// * C string owned by the handle, valid until `graphlite_session_close` (must NOT be freed)
+/// * null pointer if `session` is null
+///
+/// # Safety
+/// * `session` must be a valid handle from `graphlite_session_open`
+#[no_mangle]
+pub unsafe extern "C" fn graphlite_session_id(session: *const GraphLiteSession) -> *const c_char {
+    if session.is_null() {
+        return ptr::null();
+    }
+    unsafe { &*session }.id.as_ptr()
+}
+
+/// Execute a GQL query on a session handle and return results as JSON
+///
+/// Same result format as `graphlite_query`.
+///
+/// # Arguments
+/// * `db` - Database handle the session belongs to (must not be null)
+/// * `session` - Session handle (must not be null)
+/// * `query` - C string with GQL query (must not be null)
+/// * `error_out` - Output parameter for error code (can be null)
+///
+/// # Returns
+/// * JSON string with query results on success (must be freed with `graphlite_free_string`)
+/// * null pointer on error
+///
+/// # Safety
+/// * `db` must be a valid handle from `graphlite_open`
+/// * `session` must be a valid handle created from `db`
+/// * `query` must be a valid null-terminated C string
+#[no_mangle]
+pub unsafe extern "C" fn graphlite_session_query(
+    db: *mut GraphLiteDB,
+    session: *mut GraphLiteSession,
+    query: *const c_char,
+    error_out: *mut GraphLiteErrorCode,
+) -> *mut c_char {
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        if query.is_null() {
+            set_error(error_out, GraphLiteErrorCode::NullPointer);
+            return None;
+        }
+        let query_str = match unsafe { CStr::from_ptr(query) }.to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                set_error(error_out, GraphLiteErrorCode::InvalidUtf8);
+                return None;
+            }
+        };
+
+        match unsafe { run_session_query(db, session, query_str) } {
+            Ok(result) => result_to_json(&result, unsafe { &*db }.number_format(), error_out),
+            Err(code) => {
+                set_error(error_out, code);
+                None
+            }
+        }
+    }));
+
+    match result {
+        Ok(ptr) => ptr.unwrap_or(ptr::null_mut()),
+        Err(_) => {
+            set_error(error_out, GraphLiteErrorCode::PanicError);
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Execute a GQL query on a session handle and return the result for reading row by row
+///
+/// Same as `graphlite_query_result`, for session handles.
+///
+/// # Arguments
+/// * `db` - Database handle the session belongs to (must not be null)
+/// * `session` - Session handle (must not be null)
+/// * `query` - C string with GQL query (must not be null)
+/// * `error_out` - Output parameter for error code (can be null)
+///
+/// # Returns
+/// * Result handle on success (must be freed with `graphlite_result_free`)
+/// * null pointer on error
+///
+/// # Safety
+/// * `db` must be a valid handle from `graphlite_open`
+/// * `session` must be a valid handle created from `db`
+/// * `query` must be a valid null-terminated C string
+#[no_mangle]
+pub unsafe extern "C" fn graphlite_session_query_result(
+    db: *mut GraphLiteDB,
+    session: *mut GraphLiteSession,
+    query: *const c_char,
+    error_out: *mut GraphLiteErrorCode,
+) -> *mut GraphLiteResult {
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        if query.is_null() {
+            set_error(error_out, GraphLiteErrorCode::NullPointer);
+            return None;
+        }
+        let query_str = match unsafe { CStr::from_ptr(query) }.to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                set_error(error_out, GraphLiteErrorCode::InvalidUtf8);
+                return None;
+            }
+        };
+
+        match unsafe { run_session_query(db, session, query_str) } {
+            Ok(result) => {
+                set_error(error_out, GraphLiteErrorCode::Success);
+                Some(LEDGER.track(
+                    Box::into_raw(Box::new(GraphLiteResult::new(&result))),
+                    Kind::Result,
+                ))
+            }
+            Err(code) => {
+                set_error(error_out, code);
+                None
+            }
+        }
+    }));
+
+    match result {
+        Ok(ptr) => ptr.unwrap_or(ptr::null_mut()),
+        Err(_) => {
+            set_error(error_out, GraphLiteErrorCode::PanicError);
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Set the current graph of a session (`SESSION SET GRAPH`)
+///
+/// # Arguments
+/// * `db` - Database handle the session belongs to (must not be null)
+/// * `session` - Session handle (must not be null)
+/// * `graph_path` - Full graph path such as `/app/social`, or a graph name in the
+///   current schema (must not be null)
+/// * `error_out` - Output parameter for error code (can be null)
+///
+/// # Returns
+/// * Error code (Success = 0, `ParseError` if `graph_path` is not a graph path,
+///   `GraphNotFound` if the graph does not exist)
+///
+/// # Safety
+/// * `db` must be a valid handle from `graphlite_open`
+/// * `session` must be a valid handle created from `db`
+/// * `graph_path` must be a valid null-terminated C string
+#[no_mangle]
+pub unsafe extern "C" fn graphlite_session_set_graph(
+    db: *mut GraphLiteDB,
+    session: *mut GraphLiteSession,
+    graph_path: *const c_char,
+    error_out: *mut GraphLiteErrorCode,
+) -> GraphLiteErrorCode {
+    unsafe { set_session_context(db, session, SessionTarget::Graph, graph_path, error_out) }
+}
+
+/// Set the current schema of a session (`SESSION SET SCHEMA`)
+///
+/// # Arguments
+/// * `db` - Database handle the session belongs to (must not be null)
+/// * `session` - Session handle (must not be null)
+/// * `schema_path` - Schema path such as `/app` (must not be null)
+/// * `error_out` - Output parameter for error code (can be null)
+///
+/// # Returns
+/// * Error code (Success = 0, `ParseError` if `schema_path` is not a schema path,
+///   `GraphNotFound` if the schema does not exist)
+///
+/// # Safety
+/// * `db` must be a valid handle from `graphlite_open`
+/// * `session` must be a valid handle created from `db`
+/// * `schema_path` must be a valid null-terminated C string
+#[no_mangle]
+pub unsafe extern "C" fn graphlite_session_set_schema(
+    db: *mut GraphLiteDB,
+    session: *mut GraphLiteSession,
+    schema_path: *const c_char,
+    error_out: *mut GraphLiteErrorCode,
+) -> GraphLiteErrorCode {
+    unsafe { set_session_context(db, session, SessionTarget::Schema, schema_path, error_out) }
+}
+
+/// What `set_session_context` sets
+#[derive(Clone, Copy)]
+enum SessionTarget {
+    Graph,
+    Schema,
+}
+
+impl SessionTarget {
+    fn keyword(self) -> &'static str {
+        match self {
+            SessionTarget::Graph => "GRAPH",
+            SessionTarget::Schema => "SCHEMA",
+        }
+    }
+}
+
+/// Run `SESSION SET <target> <path>` on a session handle
+///
+/// # Safety
+/// Same requirements as `graphlite_session_set_graph`.
+unsafe fn set_session_context(
+    db: *mut GraphLiteDB,
+    session: *mut GraphLiteSession,
+    target: SessionTarget,
+    path: *const c_char,
+    error_out: *mut GraphLiteErrorCode,
+) -> GraphLiteErrorCode {
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        if path.is_null() {
+            set_error(error_out, GraphLiteErrorCode::NullPointer);
+            return GraphLiteErrorCode::NullPointer;
+        }
+        let path_str = match unsafe { CStr::from_ptr(path) }.to_str() {
+            Ok(s) => s.trim(),
+            Err(_) => {
+                set_error(error_out, GraphLiteErrorCode::InvalidUtf8);
+                return GraphLiteErrorCode::InvalidUtf8;
+            }
+        };
+        // The path is spliced into the statement, so it must be nothing but a path
+        let is_path = match target {
+            SessionTarget::Graph => is_graph_path(path_str),
+            SessionTarget::Schema => is_schema_path(path_str),
+        };
+        if !is_path {
+            set_error(error_out, GraphLiteErrorCode::ParseError);
+            return GraphLiteErrorCode::ParseError;
+        }
+
+        let statement = format!("SESSION SET {} {}", target.keyword(), path_str);
+        let code = match unsafe { run_session_query(db, session, &statement) } {
+            Ok(_) => GraphLiteErrorCode::Success,
+            Err(code) => code,
+        };
+        set_error(error_out, code);
+        code
+    }));
+
+    match result {
+        Ok(code) => code,
+        Err(_) => {
+            set_error(error_out, GraphLiteErrorCode::PanicError);
+            GraphLiteErrorCode::PanicError
+        }
+    }
+}
+
+/// Run a statement on a session handle and refresh its cached context
+///
+/// # Safety
+/// Same requirements as `graphlite_session_query`.
+unsafe fn run_session_query(
+    db: *mut GraphLiteDB,
+    session: *mut GraphLiteSession,
+    query: &str,
+) -> Result<QueryResult, GraphLiteErrorCode> {
+    if db.is_null() || session.is_null() {
+        return Err(GraphLiteErrorCode::NullPointer);
+    }
+
+    let db_ref = unsafe { &*db };
+    let session_ref = unsafe { &mut *session };
+    let _guard = db_ref.lock_shared();
+
+    let result = db_ref
+        .coordinator
+        .process_query_with_handle(query, &session_ref.handle);
+    session_ref.refresh();
+
+    result.map_err(|e| GraphLiteErrorCode::from_query_error(&e))
+}
+
+/// Get the current graph of a session
+///
+/// # Returns
+/// * Graph path such as `/app/social`, owned by the handle and valid until the next
+///   call with this session handle (must NOT be freed)
+/// * null pointer if no graph is set or `session` is null
+///
+/// # Safety
+/// * `session` must be a valid handle from `graphlite_session_open`
+#[no_mangle]
+pub unsafe extern "C" fn graphlite_session_current_graph(
+    session: *mut GraphLiteSession,
+) -> *const c_char {
+    if session.is_null() {
+        return ptr::null();
+    }
+    let session_ref = unsafe { &mut *session };
+    session_ref.refresh();
+    session_ref
+        .current_graph
+        .as_ref()
+        .map_or(ptr::null(), |graph| graph.as_ptr())
+}
+
+/// Get the current schema of a session
+///
+/// # Returns
+/// * Schema path such as `/app`, owned by the handle and valid until the next call
+///   with this session handle (must NOT be freed)
+/// * null pointer if no schema is set or `session` is null
+///
+/// # Safety
+/// * `session` must be a valid handle from `graphlite_session_open`
+#[no_mangle]
+pub unsafe extern "C" fn graphlite_session_current_schema(
+    session: *mut GraphLiteSession,
+) -> *const c_char {
+    if session.is_null() {
+        return ptr::null();
+    }
+    let session_ref = unsafe { &mut *session };
+    session_ref.refresh();
+    session_ref
+        .current_schema
+        .as_ref()
+        .map_or(ptr::null(), |schema| schema.as_ptr())
+}
+
+/// Evaluate console input on a session and return the output as text
+///
+/// Accepts what a user types at the `graphlite gql` prompt: a GQL statement or
+/// one of these meta-commands:
+/// * `\dg` - list graphs
+/// * `\timing [on|off]` - show the execution time after each statement
+/// * `\x [on|off]` - expanded display, one line per column
+/// * `\?` - list the meta-commands
+///
+/// Results are rendered as plain-text tables without colors. `\timing` and `\x`
+/// settings belong to the session handle and last until it is closed. This lets
+/// language bindings offer an interactive console by reading a line, calling
+/// `graphlite_eval` and printing the returned text.
+///
+/// # Arguments
+/// * `db` - Database handle the session belongs to (must not be null)
+/// * `session` - Session handle (must not be null)
+/// * `text` - C string with a statement or meta-command (must not be null)
+/// * `error_out` - Output parameter for error code (can be null)
+///
+/// # Returns
+/// * Rendered text (must be freed with `graphlite_free_string`). If the statement
+///   or meta-command fails, the text holds the error message and `error_out` is set
+///   to the failure's error code (`QueryError` for a malformed meta-command)
+/// * null pointer on invalid arguments
+///
+/// # Safety
+/// * `db` must be a valid handle from `graphlite_open`
+/// * `session` must be a valid handle created from `db`
+/// * `text` must be a valid null-terminated C string
+///
+/// # Example
+/// ```c
+/// char* out = graphlite_eval(db, session, "\\dg", &error);
+/// printf("%s", out);
+/// graphlite_free_string(out);
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn graphlite_eval(
+    db: *mut GraphLiteDB,
+    session: *mut GraphLiteSession,
+    text: *const c_char,
+    error_out: *mut GraphLiteErrorCode,
+) -> *mut c_char {
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        if db.is_null() || session.is_null() || text.is_null() {
+            set_error(error_out, GraphLiteErrorCode::NullPointer);
+            return ptr::null_mut();
+        }
+        let text_str = match unsafe { CStr::from_ptr(text) }.to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                set_error(error_out, GraphLiteErrorCode::InvalidUtf8);
+                return ptr::null_mut();
+            }
+        };
+
+        let db_ref = unsafe { &*db };
+        let session_ref = unsafe { &mut *session };
+        let _guard = db_ref.lock_shared();
+
+        let output = session_ref
+            .console
+            .eval(&db_ref.coordinator, &session_ref.handle, text_str);
+        session_ref.refresh();
+
+        let (text, code) = match output {
+            Ok(text) => (text, GraphLiteErrorCode::Success),
+            Err(failure) => failure,
+        };
+        match CString::new(text) {
+            Ok(c_string) => {
+                set_error(error_out, code);
+                LEDGER.track(c_string.into_raw(), Kind::String)
+            }
+            Err(_) => {
+                set_error(error_out, GraphLiteErrorCode::InvalidUtf8);
+                ptr::null_mut()
+            }
+        }
+    }));
+
+    match result {
+        Ok(ptr) => ptr,
+        Err(_) => {
+            set_error(error_out, GraphLiteErrorCode::PanicError);
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Close a session and free its handle
+///
+/// The handle is freed even if closing the session fails.
+///
+/// # Arguments
+/// * `db` - Database handle the session belongs to (must not be null)
+/// * `session` - Session handle (can be null, in which case this is a no-op)
+/// * `error_out` - Output parameter for error code (can be null)
+///
+/// # Returns
+/// * Error code (Success = 0, error otherwise)
+///
+/// # Safety
+/// * `db` must be a valid handle from `graphlite_open`
+/// * `session` must be a handle created from `db`, and must not be used afterwards
+#[no_mangle]
+pub unsafe extern "C" fn graphlite_session_close(
+    db: *mut GraphLiteDB,
+    session: *mut GraphLiteSession,
+    error_out: *mut GraphLiteErrorCode,
+) -> GraphLiteErrorCode {
+    if session.is_null() {
+        set_error(error_out, GraphLiteErrorCode::Success);
+        return GraphLiteErrorCode::Success;
+    }
+    if !LEDGER.untrack(session, Kind::Session) {
+        set_error(error_out, GraphLiteErrorCode::SessionError);
+        return GraphLiteErrorCode::SessionError;
+    }
+    let session = unsafe { Box::from_raw(session) };
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        if db.is_null() {
+            set_error(error_out, GraphLiteErrorCode::NullPointer);
+            return GraphLiteErrorCode::NullPointer;
+        }
+
+        let db_ref = unsafe { &*db };
+        let _guard = db_ref.lock_shared();
+
+        match db_ref.coordinator.close_session(session.handle.id()) {
+            Ok(_) => {
+                set_error(error_out, GraphLiteErrorCode::Success);
+                GraphLiteErrorCode::Success
+            }
+            Err(_) => {
+                set_error(error_out, GraphLiteErrorCode::SessionError);
+                GraphLiteErrorCode::SessionError
+            }
+        }
+    }));
+
+    match result {
+        Ok(code) => code,
+        Err(_) => {
+            set_error(error_out, GraphLiteErrorCode::PanicError);
+            GraphLiteErrorCode::PanicError
+        }
+    }
+}
+
+/// Initialize GraphLite database from a UTF-16 path
+///
+/// Same as `graphlite_open`, for hosts such as Windows and the JVM that hold
+/// strings as UTF-16 (`wchar_t` on Windows, `char[]` in Java).
+///
+/// # Arguments
+/// * `path` - Null-terminated UTF-16 string with database path (must not be null)
+/// * `error_out` - Output parameter for error code (can be null)
+///
+/// # Returns
+/// * Opaque handle to database on success
+/// * null pointer on error (`InvalidUtf8` if `path` has unpaired surrogates)
+///
+/// # Safety
+/// * `path` must be a valid null-terminated UTF-16 string
+/// * Returned handle must be freed with `graphlite_close`
+#[no_mangle]
+pub unsafe extern "C" fn graphlite_open_w(
+    path: *const u16,
+    error_out: *mut GraphLiteErrorCode,
+) -> *mut GraphLiteDB {
+    match unsafe { wide_to_c_string(path) } {
+        Ok(path) => unsafe { graphlite_open(path.as_ptr(), error_out) },
+        Err(code) => {
+            set_error(error_out, code);
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Create a simple session for a UTF-16 username
+///
+/// Same as `graphlite_create_session`, with the session ID returned as UTF-16.
+///
+/// # Arguments
+/// * `db` - Database handle (must not be null)
+/// * `username` - Null-terminated UTF-16 string with username (must not be null)
+/// * `error_out` - Output parameter for error code (can be null)
+///
+/// # Returns
+/// * UTF-16 string with session ID on success (must be freed with `graphlite_free_wstring`)
+/// * null pointer on error
+///
+/// # Safety
+/// * `db` must be a valid handle from `graphlite_open`
+/// * `username` must be a valid null-terminated UTF-16 string
+/// * Returned string must be freed with `graphlite_free_wstring`
+#[no_mangle]
+pub unsafe extern "C" fn graphlite_create_session_w(
+    db: *mut GraphLiteDB,
+    username: *const u16,
+    error_out: *mut GraphLiteErrorCode,
+) -> *mut u16 {
+    match unsafe { wide_to_c_string(username) } {
+        Ok(username) => unsafe {
+            c_string_to_wide(graphlite_create_session(db, username.as_ptr(), error_out))
+        },
+        Err(code) => {
+            set_error(error_out, code);
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Execute a UTF-16 GQL query and return results as UTF-16 JSON
+///
+/// Same as `graphlite_query`, with the JSON document returned as UTF-16.
+///
+/// # Arguments
+/// * `db` - Database handle (must not be null)
+/// * `session_id` - Null-terminated UTF-16 string with session ID (must not be null)
+/// * `query` - Null-terminated UTF-16 string with GQL query (must not be null)
+/// * `error_out` - Output parameter for error code (can be null)
+///
+/// # Returns
+/// * UTF-16 JSON string with query results on success (must be freed with `graphlite_free_wstring`)
+/// * null pointer on error
+///
+/// # Safety
+/// * `db` must be a valid handle from `graphlite_open`
+/// * `session_id` and `query` must be valid null-terminated UTF-16 strings
+/// * Returned string must be freed with `graphlite_free_wstring`
+#[no_mangle]
+pub unsafe extern "C" fn graphlite_query_w(
+    db: *mut GraphLiteDB,
+    session_id: *const u16,
+    query: *const u16,
+    error_out: *mut GraphLiteErrorCode,
+) -> *mut u16 {
+    let args = unsafe { wide_to_c_string(session_id) }
+        .and_then(|session_id| Ok((session_id, unsafe { wide_to_c_string(query) }?)));
+    match args {
+        Ok((session_id, query)) => unsafe {
+            c_string_to_wide(graphlite_query(
+                db,
+                session_id.as_ptr(),
+                query.as_ptr(),
+                error_out,
+            ))
+        },
+        Err(code) => {
+            set_error(error_out, code);
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Execute a UTF-16 GQL query on a session handle and return results as UTF-16 JSON
+///
+/// Same as `graphlite_session_query`, with the JSON document returned as UTF-16.
+///
+/// # Arguments
+/// * `db` - Database handle the session belongs to (must not be null)
+/// * `session` - Session handle (must not be null)
+/// * `query` - Null-terminated UTF-16 string with GQL query (must not be null)
+/// * `error_out` - Output parameter for error code (can be null)
+///
+/// # Returns
+/// * UTF-16 JSON string with query results on success (must be freed with `graphlite_free_wstring`)
+/// * null pointer on error
+///
+/// # Safety
+/// * `db` must be a valid handle from `graphlite_open`
+/// * `session` must be a valid handle created from `db`
+/// * `query` must be a valid null-terminated UTF-16 string
+/// * Returned string must be freed with `graphlite_free_wstring`
+#[no_mangle]
+pub unsafe extern "C" fn graphlite_session_query_w(
+    db: *mut GraphLiteDB,
+    session: *mut GraphLiteSession,
+    query: *const u16,
+    error_out: *mut GraphLiteErrorCode,
+) -> *mut u16 {
+    match unsafe { wide_to_c_string(query) } {
+        Ok(query) => unsafe {
+            c_string_to_wide(graphlite_session_query(
+                db,
+                session,
+                query.as_ptr(),
+                error_out,
+            ))
+        },
+        Err(code) => {
+            set_error(error_out, code);
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Back up an open database to a new directory
+///
+/// The engine copies its data into `dest_path` itself, so the database stays
+/// open and usable and no files are copied from under it. The backup is a
+/// complete database directory: open it with `graphlite_open` or copy it into
+/// place with `graphlite_restore`. Fails while any session has an open
+/// transaction.
+///
+/// # Arguments
+/// * `db` - Database handle (must not be null)
+/// * `dest_path` - C string with the backup directory; must not exist or be empty
+///   (must not be null)
+/// * `error_out` - Output parameter for error code (can be null)
+///
+/// # Returns
+/// * Error code (Success = 0, `BackupError` if the backup could not be written)
+///
+/// # Safety
+/// * `db` must be a valid handle from `graphlite_open`
+/// * `dest_path` must be a valid null-terminated C string
+#[no_mangle]
+pub unsafe extern "C" fn graphlite_backup(
+    db: *mut GraphLiteDB,
+    dest_path: *const c_char,
+    error_out: *mut GraphLiteErrorCode,
+) -> GraphLiteErrorCode {
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        if db.is_null() || dest_path.is_null() {
+            return GraphLiteErrorCode::NullPointer;
+        }
+
+        let db_ref = unsafe { &*db };
+        let _guard = db_ref.lock_shared();
+
+        let dest_str = match unsafe { CStr::from_ptr(dest_path) }.to_str() {
+            Ok(s) => s,
+            Err(_) => return GraphLiteErrorCode::InvalidUtf8,
+        };
+
+        match db_ref.coordinator.backup(dest_str) {
+            Ok(_) => GraphLiteErrorCode::Success,
+            Err(_) => GraphLiteErrorCode::BackupError,
+        }
+    }));
+
+    let code = result.unwrap_or(GraphLiteErrorCode::PanicError);
+    set_error(error_out, code);
+    code
+}
+
+/// Restore a backup into a new database directory
+///
+/// Copies the backup at `backup_path` (written by `graphlite_backup`) into
+/// `dest_db_path`, which can then be opened with `graphlite_open`. No database
+/// handle is needed; the backup itself must not be open.
+///
+/// # Arguments
+/// * `backup_path` - C string with the backup directory (must not be null)
+/// * `dest_db_path` - C string with the new database directory; must not exist or be
+///   empty (must not be null)
+/// * `error_out` - Output parameter for error code (can be null)
+///
+/// # Returns
+/// * Error code (Success = 0, `BackupError` if the backup could not be restored)
+///
+/// # Safety
+/// * `backup_path` and `dest_db_path` must be valid null-terminated C strings
+///
+/// # Example
+/// ```c
+/// graphlite_backup(db, "/backups/app-db", &error);
+/// // ... later, e.g. after data loss
+/// graphlite_restore("/backups/app-db", "/data/app-db-restored", &error);
+/// GraphLiteDB* restored = graphlite_open("/data/app-db-restored", &error);
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn graphlite_restore(
+    backup_path: *const c_char,
+    dest_db_path: *const c_char,
+    error_out: *mut GraphLiteErrorCode,
+) -> GraphLiteErrorCode {
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        if backup_path.is_null() || dest_db_path.is_null() {
+            return GraphLiteErrorCode::NullPointer;
+        }
+
+        let (backup_str, dest_str) = match (
+            unsafe { CStr::from_ptr(backup_path) }.to_str(),
+            unsafe { CStr::from_ptr(dest_db_path) }.to_str(),
+        ) {
+            (Ok(backup), Ok(dest)) => (backup, dest),
+            _ => return GraphLiteErrorCode::InvalidUtf8,
+        };
+
+        match QueryCoordinator::restore_backup(backup_str, dest_str) {
+            Ok(_) => GraphLiteErrorCode::Success,
+            Err(_) => GraphLiteErrorCode::BackupError,
+        }
+    }));
+
+    let code = result.unwrap_or(GraphLiteErrorCode::PanicError);
+    set_error(error_out, code);
+    code
+}
+
+/// Free a string returned by GraphLite FFI functions
+///
+/// # Arguments
+/// * `s` - C string to free (can be null, in which case this is a no-op)
+///
+/// # Safety
+/// * `s` must be a string returned by a GraphLite FFI function
+/// * Must not be called more than once on the same string
+/// * Must not be called on strings not allocated by GraphLite
+#[no_mangle]
+pub unsafe extern "C" fn graphlite_free_string(s: *mut c_char) {
+    if !s.is_null() && LEDGER.untrack(s, Kind::String) {
+        unsafe {
+            drop(CString::from_raw(s));
+        }
+    }
+}
+
+/// Free a string array returned by GraphLite FFI functions
+///
+/// # Arguments
+/// * `array` - Array to free (can be null, in which case this is a no-op)
+/// * `count` - Number of strings reported when the array was returned
+///
+/// # Safety
+/// * `array` must be an array returned by a GraphLite FFI function, such as
+///   `graphlite_list_graphs`, with its reported count
+/// * Must not be called more than once on the same array
+#[no_mangle]
+pub unsafe extern "C" fn graphlite_free_string_array(array: *mut *mut c_char, count: usize) {
+    if !array.is_null() && LEDGER.untrack(array, Kind::StringArray) {
+        let strings = unsafe { Box::from_raw(ptr::slice_from_raw_parts_mut(array, count)) };
+        for s in strings.iter() {
+            unsafe { graphlite_free_string(*s) };
+        }
+    }
+}
+
+/// Free a buffer returned by GraphLite FFI functions
+///
+/// # Arguments
+/// * `buf` - Buffer to free (can be null, in which case this is a no-op)
+/// * `len` - Length reported when the buffer was returned
+///
+/// # Safety
+/// * `buf` must be a buffer returned by a GraphLite FFI function, with its reported length
+/// * Must not be called more than once on the same buffer
+#[no_mangle]
+pub unsafe extern "C" fn graphlite_free_buffer(buf: *mut u8, len: usize) {
+    if !buf.is_null() && LEDGER.untrack(buf, Kind::Buffer) {
+        unsafe {
+            drop(Box::from_raw(ptr::slice_from_raw_parts_mut(buf, len)));
+        }
+    }
+}
+
+/// Free a UTF-16 string returned by the `_w` GraphLite FFI functions
+///
+/// # Arguments
+/// * `s` - UTF-16 string to free (can be null, in which case this is a no-op)
+///
+/// # Safety
+/// * `s` must be a string returned by a `_w` GraphLite FFI function
+/// * Must not be called more than once on the same string
+#[no_mangle]
+pub unsafe extern "C" fn graphlite_free_wstring(s: *mut u16) {
+    if !s.is_null() && LEDGER.untrack(s, Kind::WideString) {
+        let len = unsafe { wide_len(s) };
+        unsafe {
+            drop(Box::from_raw(ptr::slice_from_raw_parts_mut(s, len + 1)));
+        }
+    }
+}
+
+/// Close database connection and free resources
+///
+/// # Arguments
+/// * `db` - Database handle to close (can be null, in which case this is a no-op)
+///
+/// # Safety
+/// * `db` must be a handle from `graphlite_open`
+/// * Must not be called more than once on the same handle
+/// * Must not be used after calling this function
+/// * Must not race with other calls on the handle, even for `graphlite_open_shared` handles
+#[no_mangle]
+pub unsafe extern "C" fn graphlite_close(db: *mut GraphLiteDB) {
+    if !db.is_null() && LEDGER.untrack(db, Kind::Database) {
+        unsafe {
+            drop(Box::from_raw(db));
+        }
+        LEDGER.report_leaks();
+    }
+}
+
+/// Counts of the strings, buffers and handles handed out by GraphLite FFI functions
+///
+/// The `outstanding_*` fields count what has been returned and not yet freed.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct GraphLiteAllocationStats {
+    /// Strings to free with `graphlite_free_string`, including those in string arrays
+    pub outstanding_strings: u64,
+    /// UTF-16 strings to free with `graphlite_free_wstring`
+    pub outstanding_wide_strings: u64,
+    /// String arrays to free with `graphlite_free_string_array`
+    pub outstanding_string_arrays: u64,
+    /// Buffers to free with `graphlite_free_buffer`
+    pub outstanding_buffers: u64,
+    /// Database handles to close with `graphlite_close`
+    pub outstanding_databases: u64,
+    /// Session handles to close with `graphlite_session_close`
+    pub outstanding_sessions: u64,
+    /// Result handles to free with `graphlite_result_free`
+    pub outstanding_results: u64,
+    /// Allocations handed out since the library was loaded
+    pub total_allocated: u64,
+    /// Allocations freed since the library was loaded
+    pub total_freed: u64,
+    /// Frees rejected by allocation tracking: double frees, foreign pointers or the
+    /// wrong free function
+    pub invalid_frees: u64,
+    /// Whether allocation tracking is enabled
+    pub tracking: bool,
+}
+
+/// Get the counts of strings, buffers and handles handed out and freed
+///
+/// Binding test suites can compare the outstanding counts before and after a
+/// test to find leaks. The counts cover the whole process, not one database.
+///
+/// # Example
+/// ```c
+/// GraphLiteAllocationStats before = graphlite_allocation_stats();
+/// // ... run a test ...
+/// GraphLiteAllocationStats after = graphlite_allocation_stats();
+/// assert(after.outstanding_strings == before.outstanding_strings);
+/// ```
+#[no_mangle]
+pub extern "C" fn graphlite_allocation_stats() -> GraphLiteAllocationStats {
+    let stats = LEDGER.stats();
+    GraphLiteAllocationStats {
+        outstanding_strings: stats.outstanding(Kind::String),
+        outstanding_wide_strings: stats.outstanding(Kind::WideString),
+        outstanding_string_arrays: stats.outstanding(Kind::StringArray),
+        outstanding_buffers: stats.outstanding(Kind::Buffer),
+        outstanding_databases: stats.outstanding(Kind::Database),
+        outstanding_sessions: stats.outstanding(Kind::Session),
+        outstanding_results: stats.outstanding(Kind::Result),
+        total_allocated: stats.allocated,
+        total_freed: stats.freed,
+        invalid_frees: stats.invalid_frees,
+        tracking: stats.tracking,
+    }
+}
+
+/// Enable allocation tracking, a debug mode for binding authors
+///
+/// With tracking enabled the library remembers the address of everything it hands
+/// out. Freeing a pointer twice, freeing a pointer GraphLite did not return, or
+/// freeing it with the wrong function is logged as an error, counted in
+/// `invalid_frees` and otherwise ignored. `graphlite_close` logs a warning listing
+/// whatever is still outstanding.
+///
+/// Tracking can only be enabled while nothing is outstanding, so call this before
+/// opening the first database. It stays enabled until the library is unloaded.
+///
+/// # Returns
+/// * true if tracking is enabled
+#[no_mangle]
+pub extern "C" fn graphlite_enable_allocation_tracking() -> bool {
+    LEDGER.enable_tracking()
+}
+
+/// Get the version string of GraphLite
+///
+/// # Returns
+/// * Static C string with version (e.g., "0.1.0")
+/// * Must NOT be freed (it's a static string)
+#[no_mangle]
+pub extern "C" fn graphlite_version() -> *const c_char {
+    // Using a static string so it doesn't need to be freed
+    const VERSION: &[u8] = b"0.1.0\0";
+    VERSION.as_ptr() as *const c_char
+}
+
+/// Version of the C ABI implemented by this library
+///
+/// Incremented when an existing function, struct or enum value changes
+/// incompatibly. New functions, error codes and features do not change it.
+pub const GRAPHLITE_ABI_VERSION: u32 = 1;
+
+// Layout of the types passed by value across the C ABI, checked at compile time.
+// A failing assertion means a change breaks existing bindings: bump
+// `GRAPHLITE_ABI_VERSION` and update the expected layout together.
+const _: () = {
+    use std::mem::{align_of, offset_of, size_of};
+
+    assert!(size_of::<GraphLiteErrorCode>() == 4);
+    assert!(size_of::<GraphLiteStorageBackend>() == 4);
+    assert!(size_of::<GraphLiteLogLevel>() == 4);
+    assert!(size_of::<GraphLiteValueType>() == 4);
+
+    assert!(size_of::<GraphLiteConfig>() == 32);
+    assert!(align_of::<GraphLiteConfig>() == 8);
+    assert!(offset_of!(GraphLiteConfig, cache_size_bytes) == 0);
+    assert!(offset_of!(GraphLiteConfig, read_only) == 8);
+    assert!(offset_of!(GraphLiteConfig, storage_backend) == 12);
+    assert!(offset_of!(GraphLiteConfig, max_memory_bytes) == 16);
+    assert!(offset_of!(GraphLiteConfig, log_level) == 24);
+
+    assert!(size_of::<GraphLiteCsvOptions>() == 3);
+    assert!(align_of::<GraphLiteCsvOptions>() == 1);
+    assert!(offset_of!(GraphLiteCsvOptions, delimiter) == 0);
+    assert!(offset_of!(GraphLiteCsvOptions, has_header) == 1);
+    assert!(offset_of!(GraphLiteCsvOptions, infer_types) == 2);
+
+    assert!(size_of::<GraphLiteImportSummary>() == 16);
+    assert!(align_of::<GraphLiteImportSummary>() == 8);
+    assert!(offset_of!(GraphLiteImportSummary, rows) == 0);
+    assert!(offset_of!(GraphLiteImportSummary, nodes_created) == 8);
+
+    assert!(size_of::<GraphLiteAllocationStats>() == 88);
+    assert!(align_of::<GraphLiteAllocationStats>() == 8);
+    assert!(offset_of!(GraphLiteAllocationStats, outstanding_strings) == 0);
+    assert!(offset_of!(GraphLiteAllocationStats, total_allocated) == 56);
+    assert!(offset_of!(GraphLiteAllocationStats, invalid_frees) == 72);
+    assert!(offset_of!(GraphLiteAllocationStats, tracking) == 80);
+};
+
+/// Optional capabilities compiled into this library, as named by `graphlite_has_feature`
+const FEATURES: &[&str] = &[
+    // Storage backends (`GraphLiteStorageBackend`)
+    "sled",
+    "memory",
+    // Optional APIs
+    "msgpack",          // graphlite_query_msgpack
+    "bulk-insert",      // graphlite_bulk_insert_nodes / graphlite_bulk_insert_edges
+    "csv-import",       // graphlite_import_csv
+    "backup",           // graphlite_backup / graphlite_restore
+    "session-handles",  // graphlite_session_*
+    "eval",             // graphlite_eval
+    "row-accessors",    // graphlite_query_result / graphlite_result_* / graphlite_row_*
+    "transactions",     // graphlite_begin / graphlite_commit / graphlite_rollback
+    "graph-management", // graphlite_list_graphs / graphlite_create_graph / graphlite_drop_graph
+    "wide-strings",     // graphlite_open_w / graphlite_query_w / ... / graphlite_free_wstring
+    "allocation-stats", // graphlite_allocation_stats / graphlite_enable_allocation_tracking
+    "random-access",    // graphlite_result_value_type / graphlite_result_get_*
+    "log-callback",     // graphlite_set_log_callback
+];
+
+/// Get the ABI version of the loaded library
+///
+/// Bindings that load the library at runtime should compare this with the
+/// `GRAPHLITE_ABI_VERSION` they were written against before calling anything else.
+///
+/// # Returns
+/// * `GRAPHLITE_ABI_VERSION` of the compiled library
+#[no_mangle]
+pub extern "C" fn graphlite_abi_version() -> u32 {
+    GRAPHLITE_ABI_VERSION
+}
+
+/// Check whether the loaded library supports an optional feature
+///
+/// Feature names are matched case-insensitively: `sled`, `memory`, `msgpack`,
+/// `bulk-insert`, `csv-import`, `backup`, `session-handles`, `eval`, `row-accessors`,
+/// `transactions`, `graph-management`, `wide-strings`, `allocation-stats`,
+/// `random-access` and `log-callback`. Any other name, including features this
+/// build does not provide such as `arrow`, `vectors` or `full-text`, returns false.
+///
+/// # Arguments
+/// * `name` - C string with the feature name (null returns false)
+///
+/// # Returns
+/// * true if the feature is available
+///
+/// # Safety
+/// * `name` must be null or a valid null-terminated C string
+#[no_mangle]
+pub unsafe extern "C" fn graphlite_has_feature(name: *const c_char) -> bool {
+    if name.is_null() {
+        return false;
+    }
+    match unsafe { CStr::from_ptr(name) }.to_str() {
+        Ok(name) => FEATURES
+            .iter()
+            .any(|feature| feature.eq_ignore_ascii_case(name.trim())),
+        Err(_) => false,
+    }
+}
+
+// Helper function to set error code if output pointer is not null
+fn set_error(error_out: *mut GraphLiteErrorCode, code: GraphLiteErrorCode) {
+    if !error_out.is_null() {
+        unsafe {
+            *error_out = code;
+        }
+    }
+}
+
+// Helper function to count the code units of a null-terminated UTF-16 string
+unsafe fn wide_len(s: *const u16) -> usize {
+    let mut len = 0;
+    while unsafe { *s.add(len) } != 0 {
+        len += 1;
+    }
+    len
+}
+
+// Helper function to convert a null-terminated UTF-16 string into a C string
+unsafe fn wide_to_c_string(s: *const u16) -> Result<CString, GraphLiteErrorCode> {
+    if s.is_null() {
+        return Err(GraphLiteErrorCode::NullPointer);
+    }
+    let units = unsafe { std::slice::from_raw_parts(s, wide_len(s)) };
+    let text = String::from_utf16(units).map_err(|_| GraphLiteErrorCode::InvalidUtf8)?;
+    // The terminator was the first zero unit, so the text has no interior nul
+    CString::new(text).map_err(|_| GraphLiteErrorCode::InvalidUtf8)
+}
+
+// Helper function to move a string returned by an FFI function into a UTF-16 string
+unsafe fn c_string_to_wide(s: *mut c_char) -> *mut u16 {
+    if s.is_null() {
+        return ptr::null_mut();
+    }
+    LEDGER.untrack(s, Kind::String);
+    let c_string = unsafe { CString::from_raw(s) };
+    let mut units: Vec<u16> = c_string.to_string_lossy().encode_utf16().collect();
+    units.push(0);
+    LEDGER.track(
+        Box::into_raw(units.into_boxed_slice()) as *mut u16,
+        Kind::WideString,
+    )
+}
+
+// Helper function to read entry `index` of a C string array (null array or entry = None)
+unsafe fn c_str_at<'a>(
+    array: *const *const c_char,
+    index: usize,
+) -> Result<Option<&'a str>, GraphLiteErrorCode> {
+    if array.is_null() {
+        return Ok(None);
+    }
+    let entry = unsafe { *array.add(index) };
+    if entry.is_null() {
+        return Ok(None);
+    }
+    unsafe { CStr::from_ptr(entry) }
+        .to_str()
+        .map(Some)
+        .map_err(|_| GraphLiteErrorCode::InvalidUtf8)
+}
+
+// Helper function to parse a JSON object of bulk-load properties (empty = no properties)
+fn parse_properties(json: &str) -> Result<HashMap<String, Value>, GraphLiteErrorCode> {
+    if json.trim().is_empty() {
+        return Ok(HashMap::new());
+    }
+    match serde_json::from_str::<serde_json::Value>(json) {
+        Ok(serde_json::Value::Object(map)) => map
+            .into_iter()
+            .map(|(key, value)| json_to_value(value).map(|v| (key, v)))
+            .collect(),
+        _ => Err(GraphLiteErrorCode::JsonError),
+    }
+}
+
+// Helper function to convert a JSON property value into a GraphLite value; objects
+// are stored as JSON documents
+fn json_to_value(value: serde_json::Value) -> Result<Value, GraphLiteErrorCode> {
+    match value {
+        serde_json::Value::Null => Ok(Value::Null),
+        serde_json::Value::Bool(b) => Ok(Value::Boolean(b)),
+        serde_json::Value::Number(n) => n
+            .as_f64()
+            .map(Value::Number)
+            .ok_or(GraphLiteErrorCode::JsonError),
+        serde_json::Value::String(s) => Ok(Value::String(s)),
+        serde_json::Value::Array(items) => items
+            .into_iter()
+            .map(json_to_value)
+            .collect::<Result<Vec<_>, _>>()
+            .map(Value::List),
+        serde_json::Value::Object(_) => Ok(Value::Json(value)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::CString;
+
+    #[test]
+    fn test_version() {
+        let version_ptr = graphlite_version();
+        assert!(!version_ptr.is_null());
+        let version = unsafe { CStr::from_ptr(version_ptr) };
+        assert_eq!(version.to_str().unwrap(), "0.1.0");
+    }
+
+    #[test]
+    fn test_null_pointer_handling() {
+        let mut error = GraphLiteErrorCode::Success;
+
+        // Test null path
+        let db = unsafe { graphlite_open(ptr::null(), &mut error) };
+        assert!(db.is_null());
+        assert_eq!(error, GraphLiteErrorCode::NullPointer);
+    }
+
+    #[test]
+    fn test_open_close() {
+        let mut error = GraphLiteErrorCode::Success;
+        let path = CString::new("/tmp/test_ffi_db").unwrap();
+
+        let db = unsafe { graphlite_open(path.as_ptr(), &mut error) };
+        assert!(!db.is_null());
+        assert_eq!(error, GraphLiteErrorCode::Success);
+
+        unsafe { graphlite_close(db) };
+    }
+
+    #[test]
+    fn test_open_with_config_read_only() {
+        let mut error = GraphLiteErrorCode::Success;
+        let path = CString::new("/tmp/test_ffi_db_config").unwrap();
+
+        let mut config = graphlite_config_default();
+        config.read_only = true;
+        config.cache_size_bytes = 16 * 1024 * 1024;
+
+        let db = unsafe { graphlite_open_with_config(path.as_ptr(), &config, &mut error) };
+        assert!(!db.is_null());
+        assert_eq!(error, GraphLiteErrorCode::Success);
+
+        let username = CString::new("reader").unwrap();
+        let session = unsafe { graphlite_create_session(db, username.as_ptr(), &mut error) };
+        assert!(!session.is_null());
+
+        let ddl = CString::new("CREATE SCHEMA /read_only_schema").unwrap();
+        let result = unsafe { graphlite_query(db, session, ddl.as_ptr(), &mut error) };
+        assert!(result.is_null());
+        assert_eq!(error, GraphLiteErrorCode::PermissionDenied);
+
+        unsafe {
+            graphlite_free_string(session);
+            graphlite_close(db);
+        }
+    }
+
+    #[test]
+    fn test_open_with_null_config_uses_defaults() {
+        let mut error = GraphLiteErrorCode::Success;
+        let path = CString::new("/tmp/test_ffi_db_null_config").unwrap();
+
+        let db = unsafe { graphlite_open_with_config(path.as_ptr(), ptr::null(), &mut error) };
+        assert!(!db.is_null());
+        assert_eq!(error, GraphLiteErrorCode::Success);
+        assert!(!unsafe { &*db }.coordinator.is_read_only());
+
+        unsafe { graphlite_close(db) };
+    }
+
+    #[test]
+    fn test_bulk_insert_nodes_and_edges() {
+        let mut error = GraphLiteErrorCode::Success;
+        let dir = std::env::temp_dir().join(format!("test_ffi_bulk_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let path = CString::new(dir.to_str().unwrap()).unwrap();
+
+        let db = unsafe { graphlite_open(path.as_ptr(), &mut error) };
+        assert!(!db.is_null());
+        let username = CString::new("loader").unwrap();
+        let session = unsafe { graphlite_create_session(db, username.as_ptr(), &mut error) };
+        assert!(!session.is_null());
+
+        for statement in [
+            "CREATE SCHEMA /bulk",
+            "CREATE GRAPH /bulk/people",
+            "SESSION SET GRAPH /bulk/people",
+        ] {
+            let query = CString::new(statement).unwrap();
+            let result = unsafe { graphlite_query(db, session, query.as_ptr(), &mut error) };
+            assert_eq!(error, GraphLiteErrorCode::Success, "{}", statement);
+            unsafe { graphlite_free_string(result) };
+        }
+
+        let labels: Vec<CString> = ["Person", "Person:Employee"]
+            .iter()
+            .map(|l| CString::new(*l).unwrap())
+            .collect();
+        let properties: Vec<CString> = [r#"{"name": "Alice", "age": 30}"#, r#"{"name": "Bob"}"#]
+            .iter()
+            .map(|p| CString::new(*p).unwrap())
+            .collect();
+        let label_ptrs: Vec<*const c_char> = labels.iter().map(|l| l.as_ptr()).collect();
+        let property_ptrs: Vec<*const c_char> = properties.iter().map(|p| p.as_ptr()).collect();
+
+        let ids_json = unsafe {
+            graphlite_bulk_insert_nodes(
+                db,
+                session,
+                label_ptrs.as_ptr(),
+                property_ptrs.as_ptr(),
+                2,
+                &mut error,
+            )
+        };
+        assert_eq!(error, GraphLiteErrorCode::Success);
+        let ids: Vec<String> =
+            serde_json::from_str(unsafe { CStr::from_ptr(ids_json) }.to_str().unwrap()).unwrap();
+        assert_eq!(ids.len(), 2);
+        unsafe { graphlite_free_string(ids_json) };
+
+        let from = CString::new(ids[0].as_str()).unwrap();
+        let to = CString::new(ids[1].as_str()).unwrap();
+        let knows = CString::new("KNOWS").unwrap();
+        let mut inserted = 0usize;
+        let code = unsafe {
+            graphlite_bulk_insert_edges(
+                db,
+                session,
+                &from.as_ptr(),
+                &to.as_ptr(),
+                &knows.as_ptr(),
+                ptr::null(),
+                1,
+                &mut inserted,
+                &mut error,
+            )
+        };
+        assert_eq!(code, GraphLiteErrorCode::Success);
+        assert_eq!(inserted, 1);
+
+        let query =
+            CString::new("MATCH (a:Person)-[:KNOWS]->(b:Employee) RETURN a.name AS a, b.name AS b")
+                .unwrap();
+        let result = unsafe { graphlite_query(db, session, query.as_ptr(), &mut error) };
+        assert_eq!(error, GraphLiteErrorCode::Success);
+        let json = unsafe { CStr::from_ptr(result) }
+            .to_str()
+            .unwrap()
+            .to_string();
+        assert!(json.contains("Alice") && json.contains("Bob"), "{}", json);
+
+        let bad_json = CString::new("not json").unwrap();
+        let ids_json = unsafe {
+            graphlite_bulk_insert_nodes(db, session, ptr::null(), &bad_json.as_ptr(), 1, &mut error)
+        };
+        assert!(ids_json.is_null());
+        assert_eq!(error, GraphLiteErrorCode::JsonError);
+
+        unsafe {
+            graphlite_free_string(result);
+            graphlite_free_string(session);
+            graphlite_close(db);
+        }
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_import_csv() {
+        let mut error = GraphLiteErrorCode::Success;
+        let dir = std::env::temp_dir().join(format!("test_ffi_import_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let csv_path = dir.join("people.csv");
+        std::fs::write(&csv_path, "name;age\nAlice;30\nBob;25\nAlice;30\n").unwrap();
+        let path = CString::new(dir.join("db").to_str().unwrap()).unwrap();
+
+        let db = unsafe { graphlite_open(path.as_ptr(), &mut error) };
+        assert!(!db.is_null());
+        let username = CString::new("importer").unwrap();
+        let session = unsafe { graphlite_create_session(db, username.as_ptr(), &mut error) };
+        assert!(!session.is_null());
+
+        let file = CString::new(csv_path.to_str().unwrap()).unwrap();
+        let label = CString::new("Person").unwrap();
+        let mut options = graphlite_csv_options_default();
+        options.delimiter = b';' as c_char;
+        let mut summary = GraphLiteImportSummary::default();
+
+        // No current graph yet
+        let code = unsafe {
+            graphlite_import_csv(
+                db,
+                session,
+                file.as_ptr(),
+                label.as_ptr(),
+                &options,
+                &mut summary,
+                &mut error,
+            )
+        };
+        assert_eq!(code, GraphLiteErrorCode::QueryError);
+
+        for statement in [
+            "CREATE SCHEMA /import",
+            "CREATE GRAPH /import/people",
+            "SESSION SET GRAPH /import/people",
+        ] {
+            let query = CString::new(statement).unwrap();
+            let result = unsafe { graphlite_query(db, session, query.as_ptr(), &mut error) };
+            assert_eq!(error, GraphLiteErrorCode::Success, "{}", statement);
+            unsafe { graphlite_free_string(result) };
+        }
+
+        let code = unsafe {
+            graphlite_import_csv(
+                db,
+                session,
+                file.as_ptr(),
+                label.as_ptr(),
+                &options,
+                &mut summary,
+                &mut error,
+            )
+        };
+        assert_eq!(code, GraphLiteErrorCode::Success);
+        assert_eq!(
+            summary,
+            GraphLiteImportSummary {
+                rows: 3,
+                nodes_created: 2
+            }
+        );
+
+        let query =
+            CString::new("MATCH (p:Person) WHERE p.age > 26 RETURN p.name AS name").unwrap();
+        let result = unsafe { graphlite_query(db, session, query.as_ptr(), &mut error) };
+        assert_eq!(error, GraphLiteErrorCode::Success);
+        let json = unsafe { CStr::from_ptr(result) }
+            .to_str()
+            .unwrap()
+            .to_string();
+        assert!(json.contains("Alice") && !json.contains("Bob"), "{}", json);
+
+        let missing = CString::new(dir.join("missing.csv").to_str().unwrap()).unwrap();
+        let code = unsafe {
+            graphlite_import_csv(
+                db,
+                session,
+                missing.as_ptr(),
+                label.as_ptr(),
+                ptr::null(),
+                ptr::null_mut(),
+                &mut error,
+            )
+        };
+        assert_eq!(code, GraphLiteErrorCode::QueryError);
+        assert_eq!(error, GraphLiteErrorCode::QueryError);
+
+        unsafe {
+            graphlite_free_string(result);
+            graphlite_free_string(session);
+            graphlite_close(db);
+        }
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_backup_and_restore() {
+        let mut error = GraphLiteErrorCode::Success;
+        let dir = std::env::temp_dir().join(format!("test_ffi_backup_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let path = CString::new(dir.join("db").to_str().unwrap()).unwrap();
+        let backup = CString::new(dir.join("backup").to_str().unwrap()).unwrap();
+        let restored = CString::new(dir.join("restored").to_str().unwrap()).unwrap();
+
+        let db = unsafe { graphlite_open(path.as_ptr(), &mut error) };
+        assert!(!db.is_null());
+        let username = CString::new("backup").unwrap();
+        let session = unsafe { graphlite_create_session(db, username.as_ptr(), &mut error) };
+        for statement in [
+            "CREATE SCHEMA /snap",
+            "CREATE GRAPH /snap/g",
+            "SESSION SET GRAPH /snap/g",
+            "INSERT (:Item {name: 'kept'})",
+        ] {
+            let query = CString::new(statement).unwrap();
+            let result = unsafe { graphlite_query(db, session, query.as_ptr(), &mut error) };
+            assert_eq!(error, GraphLiteErrorCode::Success, "{}", statement);
+            unsafe { graphlite_free_string(result) };
+        }
+
+        let code = unsafe { graphlite_backup(db, backup.as_ptr(), &mut error) };
+        assert_eq!(code, GraphLiteErrorCode::Success);
+        // Backups never overwrite existing data
+        let code = unsafe { graphlite_backup(db, backup.as_ptr(), &mut error) };
+        assert_eq!(code, GraphLiteErrorCode::BackupError);
+        unsafe {
+            graphlite_free_string(session);
+            graphlite_close(db);
+        }
+
+        let code = unsafe { graphlite_restore(backup.as_ptr(), restored.as_ptr(), &mut error) };
+        assert_eq!(code, GraphLiteErrorCode::Success);
+        assert_eq!(
+            unsafe { graphlite_restore(ptr::null(), restored.as_ptr(), ptr::null_mut()) },
+            GraphLiteErrorCode::NullPointer
+        );
+
+        let db = unsafe { graphlite_open(restored.as_ptr(), &mut error) };
+        assert!(!db.is_null());
+        let session = unsafe { graphlite_create_session(db, username.as_ptr(), &mut error) };
+        let query = CString::new("SESSION SET GRAPH /snap/g").unwrap();
+        let result = unsafe { graphlite_query(db, session, query.as_ptr(), &mut error) };
+        assert_eq!(error, GraphLiteErrorCode::Success);
+        unsafe { graphlite_free_string(result) };
+        let query = CString::new("MATCH (n:Item) RETURN n.name AS name").unwrap();
+        let result = unsafe { graphlite_query(db, session, query.as_ptr(), &mut error) };
+        assert_eq!(error, GraphLiteErrorCode::Success);
+        let json = unsafe { CStr::from_ptr(result) }.to_str().unwrap();
+        assert!(json.contains("kept"), "{}", json);
+
+        unsafe {
+            graphlite_free_string(result);
+            graphlite_free_string(session);
+            graphlite_close(db);
+        }
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_execute_many() {
+        let mut error = GraphLiteErrorCode::Success;
+        let dir = std::env::temp_dir().join(format!("test_ffi_batch_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let path = CString::new(dir.to_str().unwrap()).unwrap();
+
+        let db = unsafe { graphlite_open(path.as_ptr(), &mut error) };
+        assert!(!db.is_null());
+        let username = CString::new("batch").unwrap();
+        let session = unsafe { graphlite_create_session(db, username.as_ptr(), &mut error) };
+        assert!(!session.is_null());
+
+        let run = |statements: &[&str], status: &mut Vec<GraphLiteErrorCode>| {
+            let owned: Vec<CString> = statements
+                .iter()
+                .map(|s| CString::new(*s).unwrap())
+                .collect();
+            let ptrs: Vec<*const c_char> = owned.iter().map(|s| s.as_ptr()).collect();
+            *status = vec![GraphLiteErrorCode::Success; statements.len()];
+            let mut error = GraphLiteErrorCode::Success;
+            let result = unsafe {
+                graphlite_execute_many(
+                    db,
+                    session,
+                    ptrs.as_ptr(),
+                    ptrs.len(),
+                    status.as_mut_ptr(),
+                    &mut error,
+                )
+            };
+            let json = (!result.is_null()).then(|| {
+                let json = unsafe { CStr::from_ptr(result) }
+                    .to_str()
+                    .unwrap()
+                    .to_string();
+                unsafe { graphlite_free_string(result) };
+                json
+            });
+            (error, json)
+        };
+
+        let mut status = Vec::new();
+        let (error, json) = run(
+            &[
+                "CREATE SCHEMA /batch",
+                "CREATE GRAPH /batch/people",
+                "SESSION SET GRAPH /batch/people",
+            ],
+            &mut status,
+        );
+        assert_eq!(error, GraphLiteErrorCode::Success, "{:?}", json);
+
+        let (error, json) = run(
+            &[
+                "INSERT (:Person {name: 'Alice'})",
+                "INSERT (:Person {name: 'Bob'})",
+                "MATCH (p:Person) RETURN count(p) AS people",
+            ],
+            &mut status,
+        );
+        assert_eq!(error, GraphLiteErrorCode::Success);
+        assert_eq!(status, vec![GraphLiteErrorCode::Success; 3]);
+        let results: serde_json::Value = serde_json::from_str(&json.unwrap()).unwrap();
+        assert_eq!(results.as_array().unwrap().len(), 3);
+
+        // A failing statement rolls back the whole batch
+        let (error, json) = run(
+            &[
+                "INSERT (:Person {name: 'Carol'})",
+                "INSERT (broken",
+                "INSERT (:Person {name: 'Dave'})",
+            ],
+            &mut status,
+        );
+        assert_eq!(error, GraphLiteErrorCode::ParseError);
+        assert!(json.is_none());
+        assert_eq!(
+            status,
+            vec![
+                GraphLiteErrorCode::Success,
+                GraphLiteErrorCode::ParseError,
+                GraphLiteErrorCode::NotExecuted
+            ]
+        );
+
+        let (_, json) = run(&["MATCH (p:Person) RETURN count(p) AS people"], &mut status);
+        assert!(json.unwrap().contains(r#""people":{"Number":2}"#));
+
+        unsafe {
+            graphlite_free_string(session);
+            graphlite_close(db);
+        }
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_query_many() {
+        let mut error = GraphLiteErrorCode::Success;
+        let dir = std::env::temp_dir().join(format!("test_ffi_many_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let path = CString::new(dir.to_str().unwrap()).unwrap();
+
+        let db = unsafe { graphlite_open(path.as_ptr(), &mut error) };
+        assert!(!db.is_null());
+        let username = CString::new("many").unwrap();
+        let session = unsafe { graphlite_create_session(db, username.as_ptr(), &mut error) };
+        assert!(!session.is_null());
+
+        let queries = [
+            "CREATE SCHEMA /many",
+            "CREATE GRAPH /many/people",
+            "SESSION SET GRAPH /many/people",
+            "INSERT (:Person {name: 'Alice'})",
+            "INSERT (broken",
+            "MATCH (p:Person) RETURN count(p) AS people",
+        ];
+        let owned: Vec<CString> = queries.iter().map(|q| CString::new(*q).unwrap()).collect();
+        let ptrs: Vec<*const c_char> = owned.iter().map(|q| q.as_ptr()).collect();
+        let mut status = vec![GraphLiteErrorCode::Success; queries.len()];
+        let result = unsafe {
+            graphlite_query_many(
+                db,
+                session,
+                ptrs.as_ptr(),
+                ptrs.len(),
+                status.as_mut_ptr(),
+                &mut error,
+            )
+        };
+        assert_eq!(error, GraphLiteErrorCode::Success);
+        assert!(!result.is_null());
+        let json = unsafe { CStr::from_ptr(result) }
+            .to_str()
+            .unwrap()
+            .to_string();
+        unsafe { graphlite_free_string(result) };
+
+        // The parse error is reported in place; the queries after it still run
+        assert_eq!(status[4], GraphLiteErrorCode::ParseError);
+        assert!(status
+            .iter()
+            .enumerate()
+            .all(|(i, code)| i == 4 || *code == GraphLiteErrorCode::Success));
+        let results: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let results = results.as_array().unwrap();
+        assert_eq!(results.len(), queries.len());
+        assert!(results[4]["error"].is_string());
+        assert!(results[5].to_string().contains(r#""people":{"Number":1}"#));
+
+        unsafe {
+            graphlite_free_string(session);
+            graphlite_close(db);
+        }
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_transactions() {
+        let mut error = GraphLiteErrorCode::Success;
+        let dir = std::env::temp_dir().join(format!("test_ffi_tx_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let path = CString::new(dir.to_str().unwrap()).unwrap();
+
+        let db = unsafe { graphlite_open(path.as_ptr(), &mut error) };
+        assert!(!db.is_null());
+        let username = CString::new("tx").unwrap();
+        let session = unsafe { graphlite_create_session(db, username.as_ptr(), &mut error) };
+        assert!(!session.is_null());
+
+        let query = |statement: &str| {
+            let statement = CString::new(statement).unwrap();
+            let mut error = GraphLiteErrorCode::Success;
+            let result = unsafe { graphlite_query(db, session, statement.as_ptr(), &mut error) };
+            assert!(!result.is_null(), "{:?}", error);
+            let json = unsafe { CStr::from_ptr(result) }
+                .to_str()
+                .unwrap()
+                .to_string();
+            unsafe { graphlite_free_string(result) };
+            json
+        };
+        let in_transaction = || unsafe { graphlite_in_transaction(db, session, ptr::null_mut()) };
+
+        query("CREATE SCHEMA /tx");
+        query("CREATE GRAPH /tx/bank");
+        query("SESSION SET GRAPH /tx/bank");
+        assert!(!in_transaction());
+
+        // Rolled back changes are discarded
+        assert_eq!(
+            unsafe { graphlite_begin(db, session, &mut error) },
+            GraphLiteErrorCode::Success
+        );
+        assert!(in_transaction());
+        assert_eq!(
+            unsafe { graphlite_begin(db, session, &mut error) },
+            GraphLiteErrorCode::TransactionConflict
+        );
+        assert_eq!(error, GraphLiteErrorCode::TransactionConflict);
+        query("INSERT (:Account {id: 1})");
+        assert_eq!(
+            unsafe { graphlite_rollback(db, session, &mut error) },
+            GraphLiteErrorCode::Success
+        );
+        assert!(!in_transaction());
+        assert!(query("MATCH (a:Account) RETURN count(a) AS n").contains(r#""n":{"Number":0}"#));
+
+        // Committed changes are kept
+        assert_eq!(
+            unsafe { graphlite_begin(db, session, &mut error) },
+            GraphLiteErrorCode::Success
+        );
+        query("INSERT (:Account {id: 1})");
+        query("INSERT (:Account {id: 2})");
+        assert_eq!(
+            unsafe { graphlite_commit(db, session, &mut error) },
+            GraphLiteErrorCode::Success
+        );
+        assert!(query("MATCH (a:Account) RETURN count(a) AS n").contains(r#""n":{"Number":2}"#));
+
+        // Ending a transaction that is not open is a typed error
+        assert_eq!(
+            unsafe { graphlite_commit(db, session, &mut error) },
+            GraphLiteErrorCode::TransactionConflict
+        );
+        assert_eq!(
+            unsafe { graphlite_rollback(db, session, &mut error) },
+            GraphLiteErrorCode::TransactionConflict
+        );
+        assert_eq!(
+            unsafe { graphlite_begin(db, ptr::null(), &mut error) },
+            GraphLiteErrorCode::NullPointer
+        );
+        let unknown = CString::new("no-such-session").unwrap();
+        assert!(!unsafe { graphlite_in_transaction(db, unknown.as_ptr(), &mut error) });
+        assert_eq!(error, GraphLiteErrorCode::SessionError);
+
+        unsafe {
+            graphlite_free_string(session);
+            graphlite_close(db);
+        }
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_graph_management() {
+        let mut error = GraphLiteErrorCode::Success;
+        let dir = std::env::temp_dir().join(format!("test_ffi_graphs_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let path = CString::new(dir.to_str().unwrap()).unwrap();
+
+        let db = unsafe { graphlite_open(path.as_ptr(), &mut error) };
+        assert!(!db.is_null());
+        let username = CString::new("graphs").unwrap();
+        let session = unsafe { graphlite_create_session(db, username.as_ptr(), &mut error) };
+        assert!(!session.is_null());
+
+        let list = || {
+            let mut count = usize::MAX;
+            let mut error = GraphLiteErrorCode::Success;
+            let array = unsafe { graphlite_list_graphs(db, session, &mut count, &mut error) };
+            assert_eq!(error, GraphLiteErrorCode::Success);
+            assert!(!array.is_null());
+            let graphs: Vec<String> = (0..count)
+                .map(|i| {
+                    unsafe { CStr::from_ptr(*array.add(i)) }
+                        .to_str()
+                        .unwrap()
+                        .to_string()
+                })
+                .collect();
+            unsafe { graphlite_free_string_array(array, count) };
+            graphs
+        };
+        let graph_call = |function: unsafe extern "C" fn(
+            *mut GraphLiteDB,
+            *const c_char,
+            *const c_char,
+            *mut GraphLiteErrorCode,
+        ) -> GraphLiteErrorCode,
+                          graph: &str| {
+            let graph = CString::new(graph).unwrap();
+            let mut error = GraphLiteErrorCode::Success;
+            let code = unsafe { function(db, session, graph.as_ptr(), &mut error) };
+            assert_eq!(code, error);
+            code
+        };
+
+        assert!(list().is_empty());
+        let statement = CString::new("CREATE SCHEMA /app").unwrap();
+        let result = unsafe { graphlite_query(db, session, statement.as_ptr(), &mut error) };
+        unsafe { graphlite_free_string(result) };
+
+        assert_eq!(
+            graph_call(graphlite_create_graph, "/app/social"),
+            GraphLiteErrorCode::Success
+        );
+        assert_eq!(
+            graph_call(graphlite_create_graph, "/app/billing"),
+            GraphLiteErrorCode::Success
+        );
+        assert_eq!(list(), ["/app/billing", "/app/social"]);
+
+        assert_eq!(
+            graph_call(graphlite_create_graph, "/app/social"),
+            GraphLiteErrorCode::ConstraintViolation
+        );
+        assert_eq!(
+            graph_call(graphlite_create_graph, "/missing/social"),
+            GraphLiteErrorCode::GraphNotFound
+        );
+        assert_eq!(
+            graph_call(graphlite_create_graph, "/app/x; DROP GRAPH /app/social"),
+            GraphLiteErrorCode::ParseError
+        );
+
+        assert_eq!(
+            graph_call(graphlite_set_session_graph, "/app/social"),
+            GraphLiteErrorCode::Success
+        );
+        let statement = CString::new("INSERT (:Member {name: 'Alice'})").unwrap();
+        let result = unsafe { graphlite_query(db, session, statement.as_ptr(), &mut error) };
+        assert!(!result.is_null(), "{:?}", error);
+        unsafe { graphlite_free_string(result) };
+        assert_eq!(
+            graph_call(graphlite_set_session_graph, "/app/nothing"),
+            GraphLiteErrorCode::GraphNotFound
+        );
+
+        assert_eq!(
+            graph_call(graphlite_drop_graph, "/app/billing"),
+            GraphLiteErrorCode::Success
+        );
+        assert_eq!(
+            graph_call(graphlite_drop_graph, "/app/billing"),
+            GraphLiteErrorCode::GraphNotFound
+        );
+        assert_eq!(list(), ["/app/social"]);
+
+        unsafe {
+            graphlite_free_string_array(ptr::null_mut(), 0);
+            graphlite_free_string(session);
+            graphlite_close(db);
+        }
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_open_shared_concurrent_queries() {
+        let mut error = GraphLiteErrorCode::Success;
+        let dir = std::env::temp_dir().join(format!("test_ffi_shared_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let path = CString::new(dir.to_str().unwrap()).unwrap();
+
+        let db = unsafe { graphlite_open_shared(path.as_ptr(), &mut error) };
+        assert!(!db.is_null());
+        assert_eq!(error, GraphLiteErrorCode::Success);
+
+        let query = |session: *mut c_char, statement: &str| {
+            let mut error = GraphLiteErrorCode::Success;
+            let statement = CString::new(statement).unwrap();
+            let result = unsafe { graphlite_query(db, session, statement.as_ptr(), &mut error) };
+            assert_eq!(error, GraphLiteErrorCode::Success);
+            let json = unsafe { CStr::from_ptr(result) }
+                .to_str()
+                .unwrap()
+                .to_string();
+            unsafe { graphlite_free_string(result) };
+            json
+        };
+
+        let username = CString::new("setup").unwrap();
+        let session = unsafe { graphlite_create_session(db, username.as_ptr(), &mut error) };
+        query(session, "CREATE SCHEMA /shared");
+        query(session, "CREATE GRAPH /shared/g");
+
+        // Raw pointers are not Send; the handle itself is what is being shared
+        let handle = db as usize;
+        let workers: Vec<_> = (0..4)
+            .map(|worker| {
+                std::thread::spawn(move || {
+                    let db = handle as *mut GraphLiteDB;
+                    let mut error = GraphLiteErrorCode::Success;
+                    let username = CString::new(format!("worker{}", worker)).unwrap();
+                    let session =
+                        unsafe { graphlite_create_session(db, username.as_ptr(), &mut error) };
+                    assert!(!session.is_null());
+                    for statement in
+                        std::iter::once("SESSION SET GRAPH /shared/g".to_string())
+                            .chain((0..10).map(|i| {
+                                format!("INSERT (:Item {{worker: {}, i: {}}})", worker, i)
+                            }))
+                            .chain(std::iter::once(
+                                "MATCH (n:Item) RETURN count(n) AS items".to_string(),
+                            ))
+                    {
+                        let statement = CString::new(statement).unwrap();
+                        let result =
+                            unsafe { graphlite_query(db, session, statement.as_ptr(), &mut error) };
+                        assert_eq!(error, GraphLiteErrorCode::Success);
+                        unsafe { graphlite_free_string(result) };
+                    }
+                    unsafe { graphlite_close_session(db, session, &mut error) };
+                    unsafe { graphlite_free_string(session) };
+                })
+            })
+            .collect();
+        for worker in workers {
+            worker.join().unwrap();
+        }
+
+        query(session, "SESSION SET GRAPH /shared/g");
+        let json = query(session, "MATCH (n:Item) RETURN count(n) AS items");
+        assert!(json.contains(r#""items":{"Number":40}"#), "{}", json);
+
+        unsafe {
+            graphlite_free_string(session);
+            graphlite_close(db);
+        }
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_set_limits() {
+        let mut error = GraphLiteErrorCode::Success;
+        let dir = std::env::temp_dir().join(format!("test_ffi_limits_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let path = CString::new(dir.to_str().unwrap()).unwrap();
+
+        let db = unsafe { graphlite_open(path.as_ptr(), &mut error) };
+        assert!(!db.is_null());
+        let username = CString::new("limits").unwrap();
+        let session = unsafe { graphlite_create_session(db, username.as_ptr(), &mut error) };
+        assert!(!session.is_null());
+
+        let query = |statement: &str| {
+            let mut error = GraphLiteErrorCode::Success;
+            let statement = CString::new(statement).unwrap();
+            let result = unsafe { graphlite_query(db, session, statement.as_ptr(), &mut error) };
+            if !result.is_null() {
+                unsafe { graphlite_free_string(result) };
+            }
+            error
+        };
+
+        for statement in [
+            "CREATE SCHEMA /limits",
+            "CREATE GRAPH /limits/g",
+            "SESSION SET GRAPH /limits/g",
+            "INSERT (:Item {i: 1}), (:Item {i: 2}), (:Item {i: 3})",
+        ] {
+            assert_eq!(query(statement), GraphLiteErrorCode::Success);
+        }
+
+        let code = unsafe { graphlite_set_limits(db, 2, 0, 0, &mut error) };
+        assert_eq!(code, GraphLiteErrorCode::Success);
+        assert_eq!(
+            query("MATCH (n:Item) RETURN n.i"),
+            GraphLiteErrorCode::QueryError
+        );
+        assert_eq!(
+            query("MATCH (n:Item) RETURN count(n) AS items"),
+            GraphLiteErrorCode::Success
+        );
+
+        // Zero lifts the limit again
+        unsafe { graphlite_set_limits(db, 0, 0, 0, &mut error) };
+        assert_eq!(
+            query("MATCH (n:Item) RETURN n.i"),
+            GraphLiteErrorCode::Success
+        );
+
+        let code = unsafe { graphlite_set_limits(ptr::null_mut(), 1, 1, 1, &mut error) };
+        assert_eq!(code, GraphLiteErrorCode::NullPointer);
+
+        unsafe {
+            graphlite_free_string(session);
+            graphlite_close(db);
+        }
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_set_number_format() {
+        let mut error = GraphLiteErrorCode::Success;
+        let dir = std::env::temp_dir().join(format!("test_ffi_numbers_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let path = CString::new(dir.to_str().unwrap()).unwrap();
+
+        let db = unsafe { graphlite_open(path.as_ptr(), &mut error) };
+        assert!(!db.is_null());
+        let username = CString::new("numbers").unwrap();
+        let session = unsafe { graphlite_create_session(db, username.as_ptr(), &mut error) };
+        assert!(!session.is_null());
+
+        let query = |statement: &str| {
+            let mut error = GraphLiteErrorCode::Success;
+            let statement = CString::new(statement).unwrap();
+            let result = unsafe { graphlite_query(db, session, statement.as_ptr(), &mut error) };
+            assert_eq!(error, GraphLiteErrorCode::Success);
+            let json = unsafe { CStr::from_ptr(result) }
+                .to_str()
+                .unwrap()
+                .to_string();
+            unsafe { graphlite_free_string(result) };
+            json
+        };
+        let set = |format: GraphLiteNumberFormat| {
+            let mut error = GraphLiteErrorCode::Success;
+            unsafe { graphlite_set_number_format(db, &format, &mut error) }
+        };
+
+        for statement in [
+            "CREATE SCHEMA /numbers",
+            "CREATE GRAPH /numbers/g",
+            "SESSION SET GRAPH /numbers/g",
+        ] {
+            query(statement);
+        }
+
+        let values = "RETURN 493603 AS id, 2.0 / 3 AS ratio";
+        let json = query(values);
+        assert!(json.contains(r#""id":{"Number":493603}"#), "{}", json);
+        assert!(
+            json.contains(r#""ratio":{"Number":0.6666666666666666}"#),
+            "{}",
+            json
+        );
+
+        let rounded = GraphLiteNumberFormat {
+            decimals: 2,
+            ..graphlite_number_format_default()
+        };
+        assert_eq!(set(rounded), GraphLiteErrorCode::Success);
+        assert!(query(values).contains(r#""ratio":{"Number":0.67}"#));
+
+        let float = GraphLiteNumberFormat {
+            integers: false,
+            notation: GraphLiteNotation::Scientific,
+            ..graphlite_number_format_default()
+        };
+        assert_eq!(set(float), GraphLiteErrorCode::Success);
+        assert!(query(values).contains(r#""id":{"Number":4.93603e5}"#));
+
+        assert_eq!(
+            unsafe { graphlite_set_number_format(db, ptr::null(), &mut error) },
+            GraphLiteErrorCode::NullPointer
+        );
+
+        unsafe {
+            graphlite_free_string(session);
+            graphlite_close(db);
+        }
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_query_msgpack_matches_json() {
+        let mut error = GraphLiteErrorCode::Success;
+        let dir = std::env::temp_dir().join(format!("test_ffi_msgpack_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let path = CString::new(dir.to_str().unwrap()).unwrap();
+
+        let db = unsafe { graphlite_open(path.as_ptr(), &mut error) };
+        assert!(!db.is_null());
+        let username = CString::new("msgpack").unwrap();
+        let session = unsafe { graphlite_create_session(db, username.as_ptr(), &mut error) };
+        assert!(!session.is_null());
+
+        for statement in [
+            "CREATE SCHEMA /binary",
+            "CREATE GRAPH /binary/g",
+            "SESSION SET GRAPH /binary/g",
+            "INSERT (:Person {name: 'Alice', age: 30, tags: ['a', 'b']}), (:Person {name: 'Bob'})",
+        ] {
+            let statement = CString::new(statement).unwrap();
+            let result = unsafe { graphlite_query(db, session, statement.as_ptr(), &mut error) };
+            assert_eq!(error, GraphLiteErrorCode::Success);
+            unsafe { graphlite_free_string(result) };
+        }
+
+        // MessagePack keeps every number a float, so compare with float JSON
+        let float = GraphLiteNumberFormat {
+            integers: false,
+            ..graphlite_number_format_default()
+        };
+        unsafe { graphlite_set_number_format(db, &float, &mut error) };
+        assert_eq!(error, GraphLiteErrorCode::Success);
+
+        let query = CString::new(
+            "MATCH (p:Person) RETURN p.name AS name, p.age AS age, p.tags AS tags ORDER BY name",
+        )
+        .unwrap();
+        let json = unsafe { graphlite_query(db, session, query.as_ptr(), &mut error) };
+        assert_eq!(error, GraphLiteErrorCode::Success);
+        let from_json: serde_json::Value =
+            serde_json::from_str(unsafe { CStr::from_ptr(json) }.to_str().unwrap()).unwrap();
+        unsafe { graphlite_free_string(json) };
+
+        let mut len = 0usize;
+        let buf =
+            unsafe { graphlite_query_msgpack(db, session, query.as_ptr(), &mut len, &mut error) };
+        assert_eq!(error, GraphLiteErrorCode::Success);
+        assert!(!buf.is_null() && len > 0);
+        let bytes = unsafe { std::slice::from_raw_parts(buf, len) };
+        let mut from_msgpack: serde_json::Value = rmp_serde::from_slice(bytes).unwrap();
+        unsafe { graphlite_free_buffer(buf, len) };
+
+        // Execution time is the only field allowed to differ between the two runs
+        from_msgpack["execution_time_ms"] = from_json["execution_time_ms"].clone();
+        assert_eq!(from_msgpack, from_json);
+
+        let broken = CString::new("MATCH (").unwrap();
+        let buf =
+            unsafe { graphlite_query_msgpack(db, session, broken.as_ptr(), &mut len, &mut error) };
+        assert!(buf.is_null());
+        assert_eq!(len, 0);
+        assert_eq!(error, GraphLiteErrorCode::ParseError);
+
+        unsafe {
+            graphlite_free_string(session);
+            graphlite_close(db);
+        }
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_query_compressed_round_trips() {
+        use std::io::Read;
+
+        let mut error = GraphLiteErrorCode::Success;
+        let dir = std::env::temp_dir().join(format!("test_ffi_compressed_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let path = CString::new(dir.to_str().unwrap()).unwrap();
+
+        let db = unsafe { graphlite_open(path.as_ptr(), &mut error) };
+        assert!(!db.is_null());
+        let username = CString::new("compressed").unwrap();
+        let session = unsafe { graphlite_create_session(db, username.as_ptr(), &mut error) };
+        assert!(!session.is_null());
+
+        let bio = "A long and repetitive biography. ".repeat(20);
+        let insert = (0..50)
+            .map(|i| format!("(:Person {{name: 'Person {}', bio: '{}'}})", i, bio))
+            .collect::<Vec<_>>()
+            .join(", ");
+        for statement in [
+            "CREATE SCHEMA /compressed".to_string(),
+            "CREATE GRAPH /compressed/g".to_string(),
+            "SESSION SET GRAPH /compressed/g".to_string(),
+            format!("INSERT {}", insert),
+        ] {
+            let statement = CString::new(statement).unwrap();
+            let result = unsafe { graphlite_query(db, session, statement.as_ptr(), &mut error) };
+            assert_eq!(error, GraphLiteErrorCode::Success);
+            unsafe { graphlite_free_string(result) };
+        }
+
+        let query =
+            CString::new("MATCH (p:Person) RETURN p.name AS name, p.bio AS bio ORDER BY name")
+                .unwrap();
+        let mut query_compressed = |compression| {
+            let mut len = 0usize;
+            let buf = unsafe {
+                graphlite_query_compressed(
+                    db,
+                    session,
+                    query.as_ptr(),
+                    compression,
+                    &mut len,
+                    &mut error,
+                )
+            };
+            assert_eq!(error, GraphLiteErrorCode::Success);
+            assert!(!buf.is_null() && len > 0);
+            let bytes = unsafe { std::slice::from_raw_parts(buf, len) }.to_vec();
+            unsafe { graphlite_free_buffer(buf, len) };
+            bytes
+        };
+
+        let plain = query_compressed(GraphLiteCompression::Uncompressed);
+        let from_plain: serde_json::Value = serde_json::from_slice(&plain).unwrap();
+        assert_eq!(from_plain["rows"].as_array().unwrap().len(), 50);
+
+        let gzip = query_compressed(GraphLiteCompression::Gzip);
+        let mut json = Vec::new();
+        flate2::read::GzDecoder::new(gzip.as_slice())
+            .read_to_end(&mut json)
+            .unwrap();
+        let from_gzip: serde_json::Value = serde_json::from_slice(&json).unwrap();
+        assert_eq!(from_gzip["rows"], from_plain["rows"]);
+        assert!(
+            gzip.len() * 10 < plain.len(),
+            "{} vs {}",
+            gzip.len(),
+            plain.len()
+        );
+
+        let zstd = query_compressed(GraphLiteCompression::Zstd);
+        let from_zstd: serde_json::Value =
+            serde_json::from_slice(&zstd::decode_all(zstd.as_slice()).unwrap()).unwrap();
+        assert_eq!(from_zstd["rows"], from_plain["rows"]);
+        assert!(
+            zstd.len() * 10 < plain.len(),
+            "{} vs {}",
+            zstd.len(),
+            plain.len()
+        );
+
+        let mut len = 0usize;
+        let broken = CString::new("MATCH (").unwrap();
+        let buf = unsafe {
+            graphlite_query_compressed(
+                db,
+                session,
+                broken.as_ptr(),
+                GraphLiteCompression::Gzip,
+                &mut len,
+                &mut error,
+            )
+        };
+        assert!(buf.is_null());
+        assert_eq!(len, 0);
+        assert_eq!(error, GraphLiteErrorCode::ParseError);
+
+        unsafe {
+            graphlite_free_string(session);
+            graphlite_close(db);
+        }
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_session_handle() {
+        let mut error = GraphLiteErrorCode::Success;
+        let dir = std::env::temp_dir().join(format!("test_ffi_session_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let path = CString::new(dir.to_str().unwrap()).unwrap();
+
+        let db = unsafe { graphlite_open(path.as_ptr(), &mut error) };
+        assert!(!db.is_null());
+        let username = CString::new("handle").unwrap();
+        let session = unsafe { graphlite_session_open(db, username.as_ptr(), &mut error) };
+        assert_eq!(error, GraphLiteErrorCode::Success);
+        assert!(!session.is_null());
+        assert!(unsafe { graphlite_session_current_graph(session) }.is_null());
+
+        for statement in ["CREATE SCHEMA /handles", "CREATE GRAPH /handles/g"] {
+            let statement = CString::new(statement).unwrap();
+            let result =
+                unsafe { graphlite_session_query(db, session, statement.as_ptr(), &mut error) };
+            assert_eq!(error, GraphLiteErrorCode::Success);
+            unsafe { graphlite_free_string(result) };
+        }
+
+        let schema = CString::new("/handles").unwrap();
+        let code =
+            unsafe { graphlite_session_set_schema(db, session, schema.as_ptr(), &mut error) };
+        assert_eq!(code, GraphLiteErrorCode::Success);
+        let graph = CString::new("g").unwrap();
+        let code =
+            unsafe { graphlite_session_set_graph(db, session, graph.as_ptr(), ptr::null_mut()) };
+        assert_eq!(code, GraphLiteErrorCode::Success);
+        let current = |f: unsafe extern "C" fn(*mut GraphLiteSession) -> *const c_char| {
+            unsafe { CStr::from_ptr(f(session)) }
+                .to_str()
+                .unwrap()
+                .to_string()
+        };
+        assert_eq!(current(graphlite_session_current_schema), "/handles");
+        assert_eq!(current(graphlite_session_current_graph), "/handles/g");
+
+        let missing = CString::new("/handles/missing").unwrap();
+        let code =
+            unsafe { graphlite_session_set_graph(db, session, missing.as_ptr(), &mut error) };
+        assert_eq!(code, GraphLiteErrorCode::GraphNotFound);
+        assert_eq!(error, GraphLiteErrorCode::GraphNotFound);
+        assert_eq!(current(graphlite_session_current_graph), "/handles/g");
+
+        // Anything but a path is rejected before it reaches the engine
+        for (set, text) in [
+            (
+                graphlite_session_set_graph as unsafe extern "C" fn(_, _, _, _) -> _,
+                "/handles/g; CREATE GRAPH /handles/injected",
+            ),
+            (
+                graphlite_session_set_schema,
+                "/handles CREATE SCHEMA /injected",
+            ),
+            (graphlite_session_set_schema, "/handles/g"),
+        ] {
+            let text = CString::new(text).unwrap();
+            let code = unsafe { set(db, session, text.as_ptr(), &mut error) };
+            assert_eq!(code, GraphLiteErrorCode::ParseError);
+            assert_eq!(error, GraphLiteErrorCode::ParseError);
+        }
+        let injected = CString::new("/handles/injected").unwrap();
+        let code =
+            unsafe { graphlite_session_set_graph(db, session, injected.as_ptr(), &mut error) };
+        assert_eq!(code, GraphLiteErrorCode::GraphNotFound);
+        assert_eq!(current(graphlite_session_current_schema), "/handles");
+        assert_eq!(current(graphlite_session_current_graph), "/handles/g");
+
+        // The string ID refers to the same session
+        let insert = CString::new("INSERT (:Item {n: 1})").unwrap();
+        let session_id = unsafe { graphlite_session_id(session) };
+        let result = unsafe { graphlite_query(db, session_id, insert.as_ptr(), &mut error) };
+        assert_eq!(error, GraphLiteErrorCode::Success);
+        unsafe { graphlite_free_string(result) };
+        let count = CString::new("MATCH (i:Item) RETURN count(i) AS items").unwrap();
+        let json = unsafe { graphlite_session_query(db, session, count.as_ptr(), &mut error) };
+        let parsed: serde_json::Value =
+            serde_json::from_str(unsafe { CStr::from_ptr(json) }.to_str().unwrap()).unwrap();
+        assert_eq!(parsed["rows"][0]["values"]["items"]["Number"], 1.0);
+        unsafe { graphlite_free_string(json) };
+
+        let same = unsafe { graphlite_session_from_id(db, session_id, &mut error) };
+        assert_eq!(error, GraphLiteErrorCode::Success);
+        assert_eq!(current(graphlite_session_current_graph), unsafe {
+            CStr::from_ptr(graphlite_session_current_graph(same))
+                .to_str()
+                .unwrap()
+        });
+
+        let code = unsafe { graphlite_session_close(db, session, &mut error) };
+        assert_eq!(code, GraphLiteErrorCode::Success);
+
+        // Closing through one handle closes the session for all of them
+        let json = unsafe { graphlite_session_query(db, same, count.as_ptr(), &mut error) };
+        assert!(json.is_null());
+        assert_eq!(error, GraphLiteErrorCode::SessionError);
+        unsafe { graphlite_session_close(db, same, &mut error) };
+
+        let unknown = CString::new("no-such-session").unwrap();
+        let handle = unsafe { graphlite_session_from_id(db, unknown.as_ptr(), &mut error) };
+        assert!(handle.is_null());
+        assert_eq!(error, GraphLiteErrorCode::SessionError);
+
+        unsafe { graphlite_close(db) };
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_eval() {
+        let mut error = GraphLiteErrorCode::Success;
+        let dir = std::env::temp_dir().join(format!("test_ffi_eval_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let path = CString::new(dir.to_str().unwrap()).unwrap();
+
+        let db = unsafe { graphlite_open(path.as_ptr(), &mut error) };
+        assert!(!db.is_null());
+        let username = CString::new("console").unwrap();
+        let session = unsafe { graphlite_session_open(db, username.as_ptr(), &mut error) };
+        assert!(!session.is_null());
+
+        let mut eval = |text: &str| {
+            let text = CString::new(text).unwrap();
+            let output = unsafe { graphlite_eval(db, session, text.as_ptr(), &mut error) };
+            assert!(!output.is_null());
+            let rendered = unsafe { CStr::from_ptr(output) }
+                .to_str()
+                .unwrap()
+                .to_string();
+            unsafe { graphlite_free_string(output) };
+            (rendered, error)
+        };
+
+        for statement in [
+            "CREATE SCHEMA /console",
+            "CREATE GRAPH /console/g",
+            "SESSION SET GRAPH /console/g",
+            "INSERT (:Item {name: 'a', n: 1})",
+        ] {
+            assert_eq!(eval(statement).1, GraphLiteErrorCode::Success);
+        }
+
+        let (output, code) = eval("MATCH (i:Item) RETURN i.name AS name, i.n AS n;");
+        assert_eq!(code, GraphLiteErrorCode::Success);
+        assert!(output.contains("│ a    ┆ 1 │"), "{}", output);
+        assert!(output.ends_with("(1 row)\n"), "{}", output);
+
+        let (output, _) = eval("\\dg");
+        assert!(output.contains("console"), "{}", output);
+
+        assert_eq!(eval("\\timing on").0, "Timing is on.\n");
+        assert_eq!(eval("\\x").0, "Expanded display is on.\n");
+        let (output, _) = eval("MATCH (i:Item) RETURN i.name AS name");
+        assert!(
+            output.starts_with("-[ RECORD 1 ]\nname | a\n"),
+            "{}",
+            output
+        );
+        assert!(output.contains("Time: "), "{}", output);
+
+        let (output, code) = eval("MATCH (n RETURN n");
+        assert_eq!(code, GraphLiteErrorCode::ParseError);
+        assert!(output.starts_with("ERROR: Parse error"), "{}", output);
+        let (output, code) = eval("\\nope");
+        assert_eq!(code, GraphLiteErrorCode::QueryError);
+        assert!(output.contains("Unknown command"), "{}", output);
+
+        unsafe { graphlite_session_close(db, session, &mut error) };
+        unsafe { graphlite_close(db) };
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_error_codes() {
+        let mut error = GraphLiteErrorCode::Success;
+        let dir = std::env::temp_dir().join(format!("test_ffi_errors_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let path = CString::new(dir.to_str().unwrap()).unwrap();
+
+        let db = unsafe { graphlite_open(path.as_ptr(), &mut error) };
+        assert!(!db.is_null());
+        let username = CString::new("errors").unwrap();
+        let session = unsafe { graphlite_create_session(db, username.as_ptr(), &mut error) };
+        assert!(!session.is_null());
+
+        let query = |statement: &str| {
+            let mut error = GraphLiteErrorCode::Success;
+            let statement = CString::new(statement).unwrap();
+            let result = unsafe { graphlite_query(db, session, statement.as_ptr(), &mut error) };
+            if !result.is_null() {
+                unsafe { graphlite_free_string(result) };
+            }
+            error
+        };
+
+        assert_eq!(query("CREATE SCHEMA /errors"), GraphLiteErrorCode::Success);
+        let cases = [
+            ("MATCH (n RETURN n", GraphLiteErrorCode::ParseError),
+            (
+                "CREATE SCHEMA /errors",
+                GraphLiteErrorCode::ConstraintViolation,
+            ),
+            ("COMMIT", GraphLiteErrorCode::TransactionConflict),
+            (
+                "SESSION SET GRAPH /errors/missing",
+                GraphLiteErrorCode::GraphNotFound,
+            ),
+            ("MATCH (n) RETURN n", GraphLiteErrorCode::GraphNotFound),
+        ];
+        for (statement, expected) in cases {
+            assert_eq!(query(statement), expected, "{}", statement);
+        }
+
+        unsafe {
+            graphlite_free_string(session);
+            graphlite_close(db);
+        }
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_wide_string_functions() {
+        let wide = |text: &str| text.encode_utf16().chain([0]).collect::<Vec<u16>>();
+        let from_wide = |s: *mut u16| {
+            assert!(!s.is_null());
+            let units = unsafe { std::slice::from_raw_parts(s, wide_len(s)) };
+            let text = String::from_utf16(units).unwrap();
+            unsafe { graphlite_free_wstring(s) };
+            text
+        };
+
+        let mut error = GraphLiteErrorCode::Success;
+        let dir = std::env::temp_dir().join(format!("test_ffi_wide_ü_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let path = wide(dir.to_str().unwrap());
+        let db = unsafe { graphlite_open_w(path.as_ptr(), &mut error) };
+        assert_eq!(error, GraphLiteErrorCode::Success);
+        assert!(!db.is_null());
+
+        let username = wide("admin");
+        let session_id =
+            from_wide(unsafe { graphlite_create_session_w(db, username.as_ptr(), &mut error) });
+        let session_id = wide(&session_id);
+        let query = |text: &str, error: &mut GraphLiteErrorCode| {
+            let text = wide(text);
+            unsafe { graphlite_query_w(db, session_id.as_ptr(), text.as_ptr(), error) }
+        };
+        for statement in [
+            "CREATE SCHEMA /wide",
+            "CREATE GRAPH /wide/g",
+            "SESSION SET GRAPH /wide/g",
+            "INSERT (:City {name: 'Zürich 🏔'})",
+        ] {
+            from_wide(query(statement, &mut error));
+            assert_eq!(error, GraphLiteErrorCode::Success, "{}", statement);
+        }
+        let json = from_wide(query("MATCH (c:City) RETURN c.name AS name", &mut error));
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed["rows"][0]["values"]["name"]["String"], "Zürich 🏔");
+
+        // Session handles take UTF-16 queries too
+        let username = CString::new("admin").unwrap();
+        let session = unsafe { graphlite_session_open(db, username.as_ptr(), &mut error) };
+        assert!(!session.is_null());
+        for text in [
+            "SESSION SET GRAPH /wide/g",
+            "MATCH (c:City) RETURN count(c) AS n",
+        ] {
+            let text = wide(text);
+            let json = from_wide(unsafe {
+                graphlite_session_query_w(db, session, text.as_ptr(), &mut error)
+            });
+            assert_eq!(error, GraphLiteErrorCode::Success);
+            assert!(json.contains("rows"));
+        }
+        unsafe { graphlite_session_close(db, session, &mut error) };
+        unsafe { graphlite_free_wstring(ptr::null_mut()) };
+
+        // Unpaired surrogates are rejected
+        let invalid = [0x0041, 0xD800, 0x0042, 0];
+        let result =
+            unsafe { graphlite_query_w(db, session_id.as_ptr(), invalid.as_ptr(), &mut error) };
+        assert!(result.is_null());
+        assert_eq!(error, GraphLiteErrorCode::InvalidUtf8);
+        let result = unsafe { graphlite_query_w(db, ptr::null(), invalid.as_ptr(), &mut error) };
+        assert!(result.is_null());
+        assert_eq!(error, GraphLiteErrorCode::NullPointer);
+
+        unsafe { graphlite_close(db) };
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_allocation_ledger() {
+        use crate::alloc::Ledger;
+
+        let ledger = Ledger::new();
+        let (mut a, mut b) = (0u8, 0u8);
+        let a = &mut a as *mut u8;
+        assert_eq!(ledger.track(a, Kind::Buffer), a);
+        assert_eq!(ledger.stats().outstanding(Kind::Buffer), 1);
+        // Tracking can only start with nothing outstanding
+        assert!(!ledger.enable_tracking());
+        assert!(ledger.untrack(a, Kind::Buffer));
+        // Without tracking a second free cannot be told apart
+        assert!(ledger.untrack(a, Kind::Buffer));
+        assert!(ledger.track(ptr::null_mut::<u8>(), Kind::String).is_null());
+        let stats = ledger.stats();
+        assert_eq!(
+            (stats.allocated, stats.freed, stats.invalid_frees),
+            (1, 2, 0)
+        );
+
+        assert!(ledger.enable_tracking());
+        ledger.track(a, Kind::String);
+        assert!(!ledger.untrack(a, Kind::Buffer));
+        assert!(ledger.untrack(a, Kind::String));
+        assert!(!ledger.untrack(a, Kind::String));
+        assert!(!ledger.untrack(&mut b as *mut u8, Kind::Session));
+        let stats = ledger.stats();
+        assert!(stats.tracking);
+        assert_eq!(stats.invalid_frees, 3);
+        assert_eq!(stats.outstanding(Kind::String), 0);
+    }
+
+    #[test]
+    fn test_allocation_stats() {
+        let mut error = GraphLiteErrorCode::Success;
+        let dir = std::env::temp_dir().join(format!("test_ffi_alloc_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let path = CString::new(dir.to_str().unwrap()).unwrap();
+
+        // Other tests allocate concurrently, so only the totals are reliable here
+        let before = graphlite_allocation_stats();
+        let db = unsafe { graphlite_open(path.as_ptr(), &mut error) };
+        let username = CString::new("admin").unwrap();
+        let session_id = unsafe { graphlite_create_session(db, username.as_ptr(), &mut error) };
+        let during = graphlite_allocation_stats();
+        assert!(during.total_allocated >= before.total_allocated + 2);
+        assert!(during.outstanding_databases >= 1);
+        assert!(during.outstanding_strings >= 1);
+
+        unsafe {
+            graphlite_free_string(session_id);
+            graphlite_close(db);
+        }
+        let after = graphlite_allocation_stats();
+        assert!(after.total_freed >= during.total_freed + 2);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_abi_version_and_features() {
+        assert_eq!(graphlite_abi_version(), GRAPHLITE_ABI_VERSION);
+
+        let has = |name: &str| {
+            let name = CString::new(name).unwrap();
+            unsafe { graphlite_has_feature(name.as_ptr()) }
+        };
+        assert!(has("sled"));
+        assert!(has("Session-Handles"));
+        assert!(has("eval"));
+        assert!(has("transactions"));
+        assert!(has("graph-management"));
+        assert!(has("wide-strings"));
+        assert!(has("allocation-stats"));
+        assert!(has("random-access"));
+        assert!(has("log-callback"));
+        assert!(!has("arrow"));
+        assert!(!has("full-text"));
+        assert!(!has(""));
+        assert!(!unsafe { graphlite_has_feature(ptr::null()) });
+    }
+
+    #[test]
+    fn test_row_accessors() {
+        let mut error = GraphLiteErrorCode::Success;
+        let dir = std::env::temp_dir().join(format!("test_ffi_rows_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let path = CString::new(dir.to_str().unwrap()).unwrap();
+
+        let db = unsafe { graphlite_open(path.as_ptr(), &mut error) };
+        assert!(!db.is_null());
+        let username = CString::new("rows").unwrap();
+        let session = unsafe { graphlite_session_open(db, username.as_ptr(), &mut error) };
+        assert!(!session.is_null());
+
+        let query_result = |text: &str, error: &mut GraphLiteErrorCode| {
+            let text = CString::new(text).unwrap();
+            unsafe { graphlite_session_query_result(db, session, text.as_ptr(), error) }
+        };
+        for statement in [
+            "CREATE SCHEMA /rows",
+            "CREATE GRAPH /rows/g",
+            "SESSION SET GRAPH /rows/g",
+            "INSERT (:Person:Employee {name: 'Alice', age: 30, score: 1.5, active: true})-[:KNOWS]->(:Person {name: 'Bob', age: 25})",
+        ] {
+            let result = query_result(statement, &mut error);
+            assert_eq!(error, GraphLiteErrorCode::Success, "{}", statement);
+            unsafe { graphlite_result_free(result) };
+        }
+
+        let result = query_result(
+            "MATCH (p:Employee)-[k:KNOWS]->(q) \
+             RETURN p, p.name AS name, p.age AS age, p.score AS score, p.active AS active, k, q, p.missing AS missing",
+            &mut error,
+        );
+        assert_eq!(error, GraphLiteErrorCode::Success);
+        assert_eq!(unsafe { graphlite_result_row_count(result) }, 1);
+        assert_eq!(unsafe { graphlite_result_column_count(result) }, 8);
+        let column_name = unsafe { CStr::from_ptr(graphlite_result_column_name(result, 1)) };
+        assert_eq!(column_name.to_str().unwrap(), "name");
+        assert!(unsafe { graphlite_result_column_name(result, 8) }.is_null());
+
+        let row = unsafe { graphlite_result_next(result) };
+        assert!(!row.is_null());
+        assert_eq!(row, unsafe { graphlite_result_row(result, 0) });
+        assert!(unsafe { graphlite_result_next(result) }.is_null());
+        assert_eq!(unsafe { graphlite_row_column_count(row) }, 8);
+
+        let text = |ptr: *const c_char| unsafe { CStr::from_ptr(ptr) }.to_str().unwrap();
+        let types: Vec<_> = (0..9)
+            .map(|column| unsafe { graphlite_row_value_type(row, column) })
+            .collect();
+        assert_eq!(
+            types,
+            [
+                GraphLiteValueType::Node,
+                GraphLiteValueType::String,
+                GraphLiteValueType::Number,
+                GraphLiteValueType::Number,
+                GraphLiteValueType::Boolean,
+                GraphLiteValueType::Edge,
+                GraphLiteValueType::Node,
+                GraphLiteValueType::Null,
+                GraphLiteValueType::Null,
+            ]
+        );
+
+        unsafe {
+            let alice = text(graphlite_row_get_node_id(row, 0, &mut error)).to_string();
+            assert_eq!(error, GraphLiteErrorCode::Success);
+            assert_eq!(graphlite_row_get_label_count(row, 0, &mut error), 2);
+            let mut labels: Vec<_> = (0..2)
+                .map(|i| text(graphlite_row_get_label(row, 0, i, &mut error)))
+                .collect();
+            labels.sort();
+            assert_eq!(labels, ["Employee", "Person"]);
+            assert!(graphlite_row_get_label(row, 0, 2, &mut error).is_null());
+            assert_eq!(error, GraphLiteErrorCode::OutOfRange);
+
+            assert_eq!(text(graphlite_row_get_string(row, 1, &mut error)), "Alice");
+            assert_eq!(graphlite_row_get_int(row, 2, &mut error), 30);
+            assert_eq!(graphlite_row_get_double(row, 3, &mut error), 1.5);
+            assert_eq!(graphlite_row_get_int(row, 3, &mut error), 0);
+            assert_eq!(error, GraphLiteErrorCode::TypeMismatch);
+            assert!(graphlite_row_get_bool(row, 4, &mut error));
+            assert_eq!(error, GraphLiteErrorCode::Success);
+
+            assert_eq!(
+                text(graphlite_row_get_label(row, 5, 0, &mut error)),
+                "KNOWS"
+            );
+            assert!(!graphlite_row_get_edge_id(row, 5, &mut error).is_null());
+            assert_eq!(
+                text(graphlite_row_get_edge_source(row, 5, &mut error)),
+                alice
+            );
+            let bob = text(graphlite_row_get_node_id(row, 6, &mut error));
+            assert_eq!(text(graphlite_row_get_edge_target(row, 5, &mut error)), bob);
+
+            assert!(graphlite_row_get_string(row, 7, &mut error).is_null());
+            assert_eq!(error, GraphLiteErrorCode::TypeMismatch);
+            assert!(graphlite_row_get_node_id(row, 1, &mut error).is_null());
+            assert_eq!(error, GraphLiteErrorCode::TypeMismatch);
+            assert_eq!(graphlite_row_get_double(row, 8, &mut error), 0.0);
+            assert_eq!(error, GraphLiteErrorCode::OutOfRange);
+            assert!(!graphlite_row_get_bool(ptr::null(), 0, &mut error));
+            assert_eq!(error, GraphLiteErrorCode::NullPointer);
+
+            graphlite_result_free(result);
+        }
+
+        // The session-id variant reports query errors like graphlite_query
+        let session_id = unsafe { graphlite_session_id(session) };
+        let bad = CString::new("MATCH (n RETURN n").unwrap();
+        let result = unsafe { graphlite_query_result(db, session_id, bad.as_ptr(), &mut error) };
+        assert!(result.is_null());
+        assert_eq!(error, GraphLiteErrorCode::ParseError);
+
+        unsafe { graphlite_session_close(db, session, &mut error) };
+        unsafe { graphlite_close(db) };
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_result_random_access() {
+        let mut error = GraphLiteErrorCode::Success;
+        let dir = std::env::temp_dir().join(format!("test_ffi_columns_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let path = CString::new(dir.to_str().unwrap()).unwrap();
+
+        let db = unsafe { graphlite_open(path.as_ptr(), &mut error) };
         assert!(!db.is_null());
+        let username = CString::new("columns").unwrap();
+        let session = unsafe { graphlite_session_open(db, username.as_ptr(), &mut error) };
+        assert!(!session.is_null());
+
+        let query_result = |text: &str, error: &mut GraphLiteErrorCode| {
+            let text = CString::new(text).unwrap();
+            unsafe { graphlite_session_query_result(db, session, text.as_ptr(), error) }
+        };
+        for statement in [
+            "CREATE SCHEMA /columns",
+            "CREATE GRAPH /columns/g",
+            "SESSION SET GRAPH /columns/g",
+            "INSERT (:Reading {seq: 1, value: 0.5, ok: true}), (:Reading {seq: 2, ok: false}), (:Reading {seq: 3, value: 2.25, ok: true})",
+        ] {
+            let result = query_result(statement, &mut error);
+            assert_eq!(error, GraphLiteErrorCode::Success, "{}", statement);
+            unsafe { graphlite_result_free(result) };
+        }
+
+        let result = query_result(
+            "MATCH (r:Reading) RETURN r.seq AS seq, r.value AS value, r.ok AS ok, 'reading' AS kind ORDER BY seq",
+            &mut error,
+        );
         assert_eq!(error, GraphLiteErrorCode::Success);
+        assert_eq!(unsafe { graphlite_result_row_count(result) }, 3);
+
+        unsafe {
+            // Cells are read in any order, without moving the cursor
+            assert_eq!(graphlite_result_get_int(result, 2, 0, &mut error), 3);
+            assert_eq!(graphlite_result_get_double(result, 0, 1, &mut error), 0.5);
+            assert!(!graphlite_result_get_bool(result, 1, 2, &mut error));
+            let kind = graphlite_result_get_string(result, 1, 3, &mut error);
+            assert_eq!(CStr::from_ptr(kind).to_str().unwrap(), "reading");
+            assert_eq!(error, GraphLiteErrorCode::Success);
+            assert_eq!(
+                graphlite_result_value_type(result, 1, 1),
+                GraphLiteValueType::Null
+            );
+            assert_eq!(
+                graphlite_result_value_type(result, 2, 1),
+                GraphLiteValueType::Number
+            );
+            assert!(!graphlite_result_next(result).is_null());
+
+            assert_eq!(graphlite_result_get_double(result, 1, 1, &mut error), 0.0);
+            assert_eq!(error, GraphLiteErrorCode::TypeMismatch);
+            assert_eq!(graphlite_result_get_int(result, 3, 0, &mut error), 0);
+            assert_eq!(error, GraphLiteErrorCode::OutOfRange);
+            assert_eq!(
+                graphlite_result_value_type(result, 3, 0),
+                GraphLiteValueType::Null
+            );
+            assert!(graphlite_result_get_string(ptr::null(), 0, 0, &mut error).is_null());
+            assert_eq!(error, GraphLiteErrorCode::NullPointer);
+
+            // Whole columns are copied with NULL flags
+            let mut seqs = [0i64; 4];
+            let copied = graphlite_result_get_int_column(
+                result,
+                0,
+                seqs.as_mut_ptr(),
+                ptr::null_mut(),
+                seqs.len(),
+                &mut error,
+            );
+            assert_eq!((copied, error), (3, GraphLiteErrorCode::Success));
+            assert_eq!(seqs, [1, 2, 3, 0]);
+
+            let mut values = [f64::NAN; 3];
+            let mut nulls = [true; 3];
+            let copied = graphlite_result_get_double_column(
+                result,
+                1,
+                values.as_mut_ptr(),
+                nulls.as_mut_ptr(),
+                values.len(),
+                &mut error,
+            );
+            assert_eq!((copied, error), (3, GraphLiteErrorCode::Success));
+            assert_eq!(values, [0.5, 0.0, 2.25]);
+            assert_eq!(nulls, [false, true, false]);
 
+            // Only `len` rows are copied; without NULL flags a NULL stops the copy
+            let copied = graphlite_result_get_double_column(
+                result,
+                1,
+                values.as_mut_ptr(),
+                ptr::null_mut(),
+                1,
+                &mut error,
+            );
+            assert_eq!((copied, error), (1, GraphLiteErrorCode::Success));
+            let copied = graphlite_result_get_double_column(
+                result,
+                1,
+                values.as_mut_ptr(),
+                ptr::null_mut(),
+                values.len(),
+                &mut error,
+            );
+            assert_eq!((copied, error), (1, GraphLiteErrorCode::TypeMismatch));
+            let copied = graphlite_result_get_int_column(
+                result,
+                4,
+                seqs.as_mut_ptr(),
+                ptr::null_mut(),
+                seqs.len(),
+                &mut error,
+            );
+            assert_eq!((copied, error), (0, GraphLiteErrorCode::OutOfRange));
+
+            graphlite_result_free(result);
+        }
+
+        unsafe { graphlite_session_close(db, session, &mut error) };
         unsafe { graphlite_close(db) };
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_log_callback() {
+        type Records = Mutex<Vec<(GraphLiteLogLevel, String, String)>>;
+
+        unsafe extern "C" fn record(
+            level: GraphLiteLogLevel,
+            target: *const c_char,
+            message: *const c_char,
+            user_data: *mut c_void,
+        ) {
+            let records = unsafe { &*(user_data as *const Records) };
+            let text = |ptr| unsafe { CStr::from_ptr(ptr) }.to_str().unwrap().to_string();
+            records
+                .lock()
+                .unwrap()
+                .push((level, text(target), text(message)));
+        }
+
+        // Other tests log concurrently, so only records of this target are checked
+        let ours = |records: &Records| -> Vec<_> {
+            records
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|(_, target, _)| target == "ffi_log_test")
+                .cloned()
+                .collect()
+        };
+
+        let records: &'static Records = Box::leak(Box::default());
+        let user_data = records as *const Records as *mut c_void;
+        assert!(unsafe {
+            graphlite_set_log_callback(Some(record), GraphLiteLogLevel::Info, user_data)
+        });
+        log::warn!(target: "ffi_log_test", "cache at {}%", 90);
+        log::debug!(target: "ffi_log_test", "not at this level");
+        log::info!(target: "ffi_log_test", "nul \0 removed");
+        assert_eq!(
+            ours(records),
+            [
+                (
+                    GraphLiteLogLevel::Warn,
+                    "ffi_log_test".to_string(),
+                    "cache at 90%".to_string()
+                ),
+                (
+                    GraphLiteLogLevel::Info,
+                    "ffi_log_test".to_string(),
+                    "nul  removed".to_string()
+                ),
+            ]
+        );
+
+        // Null discards records from now on
+        assert!(unsafe {
+            graphlite_set_log_callback(None, GraphLiteLogLevel::Unchanged, ptr::null_mut())
+        });
+        log::warn!(target: "ffi_log_test", "discarded");
+        assert_eq!(ours(records).len(), 2);
     }
 }
@@ -0,0 +1,93 @@
+//! Logger behind `graphlite_set_log_callback`
+//!
+//! Forwards the engine's `log` records to a callback of the host, so they end up
+//! in the host application's logging system. The `log` crate allows one logger
+//! per process, so this logger is installed on the first registration and stays
+//! installed; later registrations only swap the callback.
+
+use crate::GraphLiteLogLevel;
+use std::ffi::{c_void, CString};
+use std::os::raw::c_char;
+use std::sync::{OnceLock, RwLock};
+
+/// Host function receiving log records
+pub type GraphLiteLogCallback = Option<
+    unsafe extern "C" fn(
+        level: GraphLiteLogLevel,
+        target: *const c_char,
+        message: *const c_char,
+        user_data: *mut c_void,
+    ),
+>;
+
+#[derive(Clone, Copy)]
+struct Sink {
+    callback: unsafe extern "C" fn(GraphLiteLogLevel, *const c_char, *const c_char, *mut c_void),
+    user_data: *mut c_void,
+}
+
+// SAFETY: the host promises that the callback and its user data may be used from
+// any thread when registering them
+unsafe impl Send for Sink {}
+unsafe impl Sync for Sink {}
+
+struct HostLogger {
+    sink: RwLock<Option<Sink>>,
+}
+
+static LOGGER: HostLogger = HostLogger {
+    sink: RwLock::new(None),
+};
+
+/// Whether `LOGGER` is the logger of the process
+static INSTALLED: OnceLock<bool> = OnceLock::new();
+
+impl log::Log for HostLogger {
+    fn enabled(&self, _metadata: &log::Metadata) -> bool {
+        self.sink.read().map(|sink| sink.is_some()).unwrap_or(false)
+    }
+
+    fn log(&self, record: &log::Record) {
+        // Copied out so the callback runs without the lock and may register another one
+        let Some(sink) = *self.sink.read().unwrap_or_else(|e| e.into_inner()) else {
+            return;
+        };
+        let level = match record.level() {
+            log::Level::Error => GraphLiteLogLevel::Error,
+            log::Level::Warn => GraphLiteLogLevel::Warn,
+            log::Level::Info => GraphLiteLogLevel::Info,
+            log::Level::Debug => GraphLiteLogLevel::Debug,
+            log::Level::Trace => GraphLiteLogLevel::Trace,
+        };
+        let target = c_string(record.target().to_string());
+        let message = c_string(record.args().to_string());
+        unsafe { (sink.callback)(level, target.as_ptr(), message.as_ptr(), sink.user_data) };
+    }
+
+    fn flush(&self) {}
+}
+
+/// Convert to a C string, dropping interior NUL bytes
+fn c_string(text: String) -> CString {
+    CString::new(text).unwrap_or_else(|e| {
+        let mut bytes = e.into_vec();
+        bytes.retain(|byte| *byte != 0);
+        CString::new(bytes).expect("NUL bytes were removed")
+    })
+}
+
+/// Route log records to `callback`, or discard them if it is `None`
+///
+/// Returns false, without registering anything, if another logger was installed
+/// in the process before the first registration.
+pub(crate) fn set_callback(callback: GraphLiteLogCallback, user_data: *mut c_void) -> bool {
+    if !*INSTALLED.get_or_init(|| log::set_logger(&LOGGER).is_ok()) {
+        return false;
+    }
+
+    *LOGGER.sink.write().unwrap_or_else(|e| e.into_inner()) = callback.map(|callback| Sink {
+        callback,
+        user_data,
+    });
+    true
+}
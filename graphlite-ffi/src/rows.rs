@@ -0,0 +1,273 @@
+//! Typed result cursor behind `graphlite_query_result`
+//!
+//! A query result is converted once into rows of typed cells whose strings are
+//! already NUL-terminated, so the `graphlite_row_get_*` accessors can hand out
+//! borrowed pointers and plain numbers without any JSON on either side.
+
+use graphlite::{QueryResult, Value};
+use std::ffi::{c_char, CString};
+use std::ptr;
+
+/// Type of a value read with the `graphlite_row_get_*` functions
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum GraphLiteValueType {
+    /// NULL, or a column the row has no value for
+    Null = 0,
+    /// Read with `graphlite_row_get_bool`
+    Boolean = 1,
+    /// Read with `graphlite_row_get_double`, or `graphlite_row_get_int` if integral
+    Number = 2,
+    /// Read with `graphlite_row_get_string`
+    String = 3,
+    /// Read with `graphlite_row_get_node_id` and `graphlite_row_get_label`
+    Node = 4,
+    /// Read with the `graphlite_row_get_edge_*` functions and `graphlite_row_get_label`
+    Edge = 5,
+    /// Dates, lists, vectors, paths and other values; `graphlite_row_get_string`
+    /// returns their text form
+    Other = 6,
+}
+
+/// One value of a row, with strings kept as C strings
+///
+/// A string containing a NUL byte cannot be handed out and is stored as `None`.
+#[derive(Debug)]
+enum Cell {
+    Null,
+    Boolean(bool),
+    Number(f64),
+    String(Option<CString>),
+    Node {
+        id: Option<CString>,
+        labels: Vec<Option<CString>>,
+    },
+    Edge {
+        id: Option<CString>,
+        label: Option<CString>,
+        source: Option<CString>,
+        target: Option<CString>,
+    },
+    Other(Option<CString>),
+}
+
+impl Cell {
+    fn new(value: Option<&Value>) -> Self {
+        let text = |s: &str| CString::new(s).ok();
+        match value {
+            None | Some(Value::Null) => Cell::Null,
+            Some(Value::Boolean(b)) => Cell::Boolean(*b),
+            Some(Value::Number(n)) => Cell::Number(*n),
+            Some(Value::String(s)) => Cell::String(text(s)),
+            Some(Value::Node(node)) => Cell::Node {
+                id: text(&node.id),
+                labels: node.labels.iter().map(|label| text(label)).collect(),
+            },
+            Some(Value::Edge(edge)) => Cell::Edge {
+                id: text(&edge.id),
+                label: text(&edge.label),
+                source: text(&edge.from_node),
+                target: text(&edge.to_node),
+            },
+            Some(value) => Cell::Other(text(&value.to_string())),
+        }
+    }
+
+    fn value_type(&self) -> GraphLiteValueType {
+        match self {
+            Cell::Null => GraphLiteValueType::Null,
+            Cell::Boolean(_) => GraphLiteValueType::Boolean,
+            Cell::Number(_) => GraphLiteValueType::Number,
+            Cell::String(_) => GraphLiteValueType::String,
+            Cell::Node { .. } => GraphLiteValueType::Node,
+            Cell::Edge { .. } => GraphLiteValueType::Edge,
+            Cell::Other(_) => GraphLiteValueType::Other,
+        }
+    }
+}
+
+/// Opaque handle to one row of a `GraphLiteResult`
+///
+/// Rows belong to their result and stay valid until `graphlite_result_free`.
+pub struct GraphLiteRow {
+    cells: Vec<Cell>,
+}
+
+/// Why a `graphlite_row_get_*` call returned no value
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum CellError {
+    /// The column index is past the last column
+    OutOfRange,
+    /// The value has another type (see `graphlite_row_value_type`), or is a
+    /// string containing a NUL byte
+    TypeMismatch,
+}
+
+impl GraphLiteRow {
+    pub(crate) fn column_count(&self) -> usize {
+        self.cells.len()
+    }
+
+    fn cell(&self, column: usize) -> Result<&Cell, CellError> {
+        self.cells.get(column).ok_or(CellError::OutOfRange)
+    }
+
+    pub(crate) fn value_type(&self, column: usize) -> Result<GraphLiteValueType, CellError> {
+        Ok(self.cell(column)?.value_type())
+    }
+
+    pub(crate) fn get_bool(&self, column: usize) -> Result<bool, CellError> {
+        match self.cell(column)? {
+            Cell::Boolean(b) => Ok(*b),
+            _ => Err(CellError::TypeMismatch),
+        }
+    }
+
+    pub(crate) fn get_double(&self, column: usize) -> Result<f64, CellError> {
+        match self.cell(column)? {
+            Cell::Number(n) => Ok(*n),
+            _ => Err(CellError::TypeMismatch),
+        }
+    }
+
+    /// The value as an integer; fails for fractional or out-of-range numbers
+    pub(crate) fn get_int(&self, column: usize) -> Result<i64, CellError> {
+        let n = self.get_double(column)?;
+        // i64::MAX is not representable as f64, so compare against 2^63 exclusively
+        if n.fract() == 0.0 && n >= i64::MIN as f64 && n < i64::MAX as f64 {
+            Ok(n as i64)
+        } else {
+            Err(CellError::TypeMismatch)
+        }
+    }
+
+    pub(crate) fn get_string(&self, column: usize) -> Result<*const c_char, CellError> {
+        match self.cell(column)? {
+            Cell::String(s) | Cell::Other(s) => as_ptr(s),
+            _ => Err(CellError::TypeMismatch),
+        }
+    }
+
+    pub(crate) fn get_node_id(&self, column: usize) -> Result<*const c_char, CellError> {
+        match self.cell(column)? {
+            Cell::Node { id, .. } => as_ptr(id),
+            _ => Err(CellError::TypeMismatch),
+        }
+    }
+
+    /// Number of labels of a node; an edge has exactly one
+    pub(crate) fn get_label_count(&self, column: usize) -> Result<usize, CellError> {
+        match self.cell(column)? {
+            Cell::Node { labels, .. } => Ok(labels.len()),
+            Cell::Edge { .. } => Ok(1),
+            _ => Err(CellError::TypeMismatch),
+        }
+    }
+
+    pub(crate) fn get_label(
+        &self,
+        column: usize,
+        index: usize,
+    ) -> Result<*const c_char, CellError> {
+        match self.cell(column)? {
+            Cell::Node { labels, .. } => as_ptr(labels.get(index).ok_or(CellError::OutOfRange)?),
+            Cell::Edge { label, .. } if index == 0 => as_ptr(label),
+            Cell::Edge { .. } => Err(CellError::OutOfRange),
+            _ => Err(CellError::TypeMismatch),
+        }
+    }
+
+    /// Id, source node id or target node id of an edge
+    pub(crate) fn get_edge_field(
+        &self,
+        column: usize,
+        field: EdgeField,
+    ) -> Result<*const c_char, CellError> {
+        match self.cell(column)? {
+            Cell::Edge {
+                id, source, target, ..
+            } => as_ptr(match field {
+                EdgeField::Id => id,
+                EdgeField::Source => source,
+                EdgeField::Target => target,
+            }),
+            _ => Err(CellError::TypeMismatch),
+        }
+    }
+}
+
+/// Node-id fields of an edge
+#[derive(Debug, Copy, Clone)]
+pub(crate) enum EdgeField {
+    Id,
+    Source,
+    Target,
+}
+
+fn as_ptr(s: &Option<CString>) -> Result<*const c_char, CellError> {
+    s.as_ref()
+        .map(|s| s.as_ptr())
+        .ok_or(CellError::TypeMismatch)
+}
+
+/// Opaque handle to a query result read row by row
+///
+/// Returned by `graphlite_query_result` and `graphlite_session_query_result`;
+/// must be freed with `graphlite_result_free`.
+pub struct GraphLiteResult {
+    columns: Vec<Option<CString>>,
+    rows: Vec<GraphLiteRow>,
+    /// Index of the row `graphlite_result_next` returns next
+    cursor: usize,
+}
+
+impl GraphLiteResult {
+    pub(crate) fn new(result: &QueryResult) -> Self {
+        let rows = result
+            .rows
+            .iter()
+            .map(|row| GraphLiteRow {
+                cells: result
+                    .variables
+                    .iter()
+                    .map(|column| Cell::new(row.get_value(column)))
+                    .collect(),
+            })
+            .collect();
+        Self {
+            columns: result
+                .variables
+                .iter()
+                .map(|column| CString::new(column.as_str()).ok())
+                .collect(),
+            rows,
+            cursor: 0,
+        }
+    }
+
+    pub(crate) fn row_count(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub(crate) fn column_count(&self) -> usize {
+        self.columns.len()
+    }
+
+    pub(crate) fn column_name(&self, column: usize) -> *const c_char {
+        match self.columns.get(column) {
+            Some(Some(name)) => name.as_ptr(),
+            _ => ptr::null(),
+        }
+    }
+
+    pub(crate) fn row(&self, index: usize) -> Option<&GraphLiteRow> {
+        self.rows.get(index)
+    }
+
+    /// Advance the cursor and return the row it passed, or `None` after the last row
+    pub(crate) fn next_row(&mut self) -> Option<&GraphLiteRow> {
+        let row = self.rows.get(self.cursor)?;
+        self.cursor += 1;
+        Some(row)
+    }
+}
@@ -268,6 +268,10 @@ pub struct UnwindStatement {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MatchClause {
     pub patterns: Vec<PathPattern>,
+    /// OPTIONAL MATCH: rows the patterns do not match are kept, with the
+    /// pattern's new variables bound to NULL
+    #[serde(default)]
+    pub optional: bool,
     pub location: Location,
 }
 
@@ -307,10 +311,32 @@ impl PathType {
     }
 }
 
-/// Path pattern: [identifier =] [path_type] node (edge node)* with optional path type constraint
+/// Path search prefix selecting which of the matching paths are kept
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum PathSearch {
+    /// ANY SHORTEST - one path of minimal length per pair of end points
+    AnyShortest,
+    /// ALL SHORTEST - every path of minimal length per pair of end points
+    AllShortest,
+}
+
+impl PathSearch {
+    /// Get the string representation for queries
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PathSearch::AnyShortest => "ANY SHORTEST",
+            PathSearch::AllShortest => "ALL SHORTEST",
+        }
+    }
+}
+
+/// Path pattern: [identifier =] [search] [path_type] node (edge node)* with optional path type constraint
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PathPattern {
-    pub assignment: Option<String>,  // Optional path variable assignment
+    pub assignment: Option<String>, // Optional path variable assignment
+    /// Path search prefix; None matches every path
+    #[serde(default)]
+    pub search: Option<PathSearch>,
     pub path_type: Option<PathType>, // None means default (WALK)
     pub elements: Vec<PatternElement>,
     pub location: Location,
@@ -479,9 +505,14 @@ pub struct LimitClause {
 }
 
 /// GROUP BY clause: GROUP BY expression, expression, ...
+///
+/// With `ROLLUP (...)` or `GROUPING SETS (...)`, `expressions` holds each
+/// distinct grouping key once and `grouping_sets` lists the keys of every
+/// grouping as indexes into `expressions`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GroupClause {
     pub expressions: Vec<Expression>,
+    pub grouping_sets: Option<Vec<Vec<usize>>>,
     pub location: Location,
 }
 
@@ -508,6 +539,8 @@ pub enum Expression {
     Subquery(SubqueryExpression),
     ExistsSubquery(ExistsSubqueryExpression),
     NotExistsSubquery(NotExistsSubqueryExpression),
+    CountSubquery(CountSubqueryExpression),
+    CollectSubquery(CollectSubqueryExpression),
     InSubquery(InSubqueryExpression),
     NotInSubquery(NotInSubqueryExpression),
     QuantifiedComparison(QuantifiedComparisonExpression),
@@ -539,6 +572,12 @@ pub struct FunctionCall {
     pub name: String,
     pub distinct: DistinctQualifier,
     pub arguments: Vec<Expression>,
+    /// Order of the collected values: COLLECT(x ORDER BY x.date DESC)
+    #[serde(default)]
+    pub order_clause: Option<Box<OrderClause>>,
+    /// Number of collected values kept: COLLECT(x ORDER BY x.date LIMIT 5)
+    #[serde(default)]
+    pub limit_clause: Option<Box<LimitClause>>,
     pub location: Location,
 }
 
@@ -630,6 +669,7 @@ pub enum CatalogStatement {
     DropGraph(DropGraphStatement),
     TruncateGraph(TruncateGraphStatement),
     ClearGraph(ClearGraphStatement),
+    AlterGraph(AlterGraphStatement),
     CreateGraphType(CreateGraphTypeStatement),
     DropGraphType(DropGraphTypeStatement),
     AlterGraphType(AlterGraphTypeStatement),
@@ -665,6 +705,9 @@ pub struct DropSchemaStatement {
 pub struct CreateGraphStatement {
     pub graph_path: CatalogPath,
     pub graph_type_spec: Option<GraphTypeSpec>,
+    /// Graph type the graph is bound to: CREATE GRAPH /app/lab TYPED /app/LabType
+    #[serde(default)]
+    pub graph_type_ref: Option<CatalogPath>,
     pub if_not_exists: bool,
     pub or_replace: bool,
     pub as_query: Option<Box<Query>>,
@@ -694,6 +737,18 @@ pub struct ClearGraphStatement {
     pub location: Location,
 }
 
+/// ALTER GRAPH statement: ALTER GRAPH path SET DESCRIPTION '...', TAGS [...], OWNER '...'
+///
+/// Only the items present in the statement are changed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlterGraphStatement {
+    pub graph_path: CatalogPath,
+    pub description: Option<String>,
+    pub tags: Option<Vec<String>>,
+    pub owner: Option<String>,
+    pub location: Location,
+}
+
 /// CREATE GRAPH TYPE statement
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateGraphTypeStatement {
@@ -807,9 +862,19 @@ pub struct PropertyTypeList {
 pub struct PropertyTypeDecl {
     pub name: String,
     pub type_spec: TypeSpec,
+    /// Node the property refers to by key: `owner STRING REFERENCES Person(email)`
+    #[serde(default)]
+    pub references: Option<PropertyReference>,
     pub location: Location,
 }
 
+/// Target of a reference property: the node of type `label` whose `key` equals the value
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PropertyReference {
+    pub label: String,
+    pub key: String,
+}
+
 /// Type specification
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum TypeSpec {
@@ -871,6 +936,8 @@ pub enum TypeSpec {
         max_length: Option<usize>,
     },
     Record,
+    /// Schema-less JSON document
+    Json,
     Graph {
         graph_type_spec: Option<Box<GraphTypeSpec>>,
     },
@@ -888,6 +955,8 @@ pub enum DataStatement {
     MatchRemove(MatchRemoveStatement),
     Delete(DeleteStatement),
     MatchDelete(MatchDeleteStatement),
+    DeleteEdgesOfType(DeleteEdgesOfTypeStatement),
+    Merge(MergeStatement),
 }
 
 /// INSERT statement
@@ -957,6 +1026,17 @@ pub struct DeleteStatement {
     pub location: Location,
 }
 
+/// DELETE EDGES OF TYPE statement: bulk removal of one relationship type
+///
+/// The optional WHERE condition is evaluated against each edge's properties,
+/// referenced by bare name (`WHERE phase < 2`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeleteEdgesOfTypeStatement {
+    pub label: String,
+    pub where_clause: Option<WhereClause>,
+    pub location: Location,
+}
+
 /// MATCH SET statement: combines MATCH for binding variables with SET
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MatchSetStatement {
@@ -988,12 +1068,29 @@ pub struct MatchDeleteStatement {
     pub location: Location,
 }
 
+/// MERGE statement: [MATCH pattern... [WHERE condition]] MERGE path_pattern
+/// [ON CREATE SET item, ...] [ON MATCH SET item, ...]
+///
+/// For every row of the MATCH (or once without one) the pattern is matched;
+/// when it has no match its missing nodes and edges are created. `on_create`
+/// then applies to the created elements and `on_match` to the matched ones.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergeStatement {
+    pub match_clause: Option<MatchClause>,
+    pub where_clause: Option<WhereClause>,
+    pub pattern: PathPattern,
+    pub on_create: Vec<SetItem>,
+    pub on_match: Vec<SetItem>,
+    pub location: Location,
+}
+
 /// Session statements for managing session state and variables
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum SessionStatement {
     Set(SessionSetStatement),
     Reset(SessionResetStatement),
     Close(SessionCloseStatement),
+    ShowCurrentGraph(ShowCurrentGraphStatement),
 }
 
 /// SESSION SET statement
@@ -1062,6 +1159,12 @@ pub struct SessionCloseStatement {
     pub location: Location,
 }
 
+/// SHOW CURRENT [PROPERTY] GRAPH statement - reports the session's graph and schema context
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShowCurrentGraphStatement {
+    pub location: Location,
+}
+
 /// DECLARE statement: DECLARE variable type [= initial_value] [, variable type [= initial_value]]*
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DeclareStatement {
@@ -1259,6 +1362,32 @@ pub struct NotExistsSubqueryExpression {
     pub location: Location,
 }
 
+/// COUNT subquery expression: COUNT { pattern [WHERE condition] } or COUNT { MATCH ... }
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CountSubqueryExpression {
+    pub subquery: Box<PatternSubquery>,
+    pub location: Location,
+}
+
+/// COLLECT subquery expression: COLLECT { MATCH ... RETURN expression }
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CollectSubqueryExpression {
+    pub subquery: Box<PatternSubquery>,
+    pub location: Location,
+}
+
+/// Body of COUNT { } and COLLECT { }: patterns with an optional WHERE and RETURN
+///
+/// Evaluated once per outer row; variables bound by the outer query keep their
+/// values inside the patterns.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PatternSubquery {
+    pub patterns: Vec<PathPattern>,
+    pub where_clause: Option<WhereClause>,
+    pub return_clause: Option<ReturnClause>,
+    pub location: Location,
+}
+
 /// IN subquery expression: expr IN (subquery)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InSubqueryExpression {
@@ -1428,6 +1557,7 @@ impl std::fmt::Display for TypeSpec {
                 None => write!(f, "{}[]", element_type),
             },
             TypeSpec::Record => write!(f, "RECORD"),
+            TypeSpec::Json => write!(f, "JSON"),
             TypeSpec::Graph { .. } => write!(f, "GRAPH"),
             TypeSpec::BindingTable => write!(f, "BINDING_TABLE"),
         }
@@ -1624,6 +1754,9 @@ pub enum TransactionStatement {
     Commit(CommitStatement),
     Rollback(RollbackStatement),
     SetTransactionCharacteristics(SetTransactionCharacteristicsStatement),
+    Savepoint(SavepointStatement),
+    RollbackToSavepoint(RollbackToSavepointStatement),
+    ReleaseSavepoint(ReleaseSavepointStatement),
 }
 
 /// START TRANSACTION statement
@@ -1647,6 +1780,27 @@ pub struct RollbackStatement {
     pub location: Location,
 }
 
+/// SAVEPOINT name statement
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavepointStatement {
+    pub name: String,
+    pub location: Location,
+}
+
+/// ROLLBACK [WORK] TO [SAVEPOINT] name statement
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RollbackToSavepointStatement {
+    pub name: String,
+    pub location: Location,
+}
+
+/// RELEASE [SAVEPOINT] name statement
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReleaseSavepointStatement {
+    pub name: String,
+    pub location: Location,
+}
+
 /// SET TRANSACTION statement
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SetTransactionCharacteristicsStatement {
@@ -1715,17 +1869,32 @@ pub enum IndexStatement {
 }
 
 /// CREATE GRAPH INDEX statement
+///
+/// `table` holds the node label, or the relationship type for an edge
+/// index (`CREATE INDEX ON ()-[r:TYPE]-() (property)`).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateIndexStatement {
     pub name: String,
     pub table: String,
     pub columns: Vec<String>,
+    /// JSONPath into the indexed column, for `(JSON_GET(column, '$.path'))`
+    #[serde(default)]
+    pub json_path: Option<String>,
+    pub entity: IndexEntity,
     pub index_type: IndexTypeSpecifier,
     pub options: IndexOptions,
     pub if_not_exists: bool,
     pub location: Location,
 }
 
+/// Kind of graph element an index covers
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum IndexEntity {
+    #[default]
+    Node,
+    Edge,
+}
+
 /// DROP INDEX statement
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DropIndexStatement {
@@ -434,7 +434,10 @@ fn is_keyword_match(input: &str, keyword: &str) -> bool {
     }
 
     // Check if the input starts with the keyword (case insensitive)
-    if !input[..keyword.len()].eq_ignore_ascii_case(keyword) {
+    if !input
+        .get(..keyword.len())
+        .is_some_and(|s| s.eq_ignore_ascii_case(keyword))
+    {
         return false;
     }
 
@@ -472,458 +475,592 @@ fn simple_patterns(input: &str) -> IResult<&str, Token> {
     }
     // Keywords (case insensitive check) - longer keywords first
     else if input.len() >= 11
-        && input[..11].eq_ignore_ascii_case("DESTINATION")
+        && input
+            .get(..11)
+            .is_some_and(|s| s.eq_ignore_ascii_case("DESTINATION"))
         && (input.len() == 11
             || (!input.chars().nth(11).unwrap().is_alphanumeric()
                 && input.chars().nth(11).unwrap() != '_'))
     {
         Ok((&input[11..], Token::Destination))
     } else if input.len() >= 10
-        && input[..10].eq_ignore_ascii_case("NORMALIZED")
+        && input
+            .get(..10)
+            .is_some_and(|s| s.eq_ignore_ascii_case("NORMALIZED"))
         && (input.len() == 10
             || (!input.chars().nth(10).unwrap().is_alphanumeric()
                 && input.chars().nth(10).unwrap() != '_'))
     {
         Ok((&input[10..], Token::Normalized))
     } else if input.len() >= 8
-        && input[..8].eq_ignore_ascii_case("DIRECTED")
+        && input
+            .get(..8)
+            .is_some_and(|s| s.eq_ignore_ascii_case("DIRECTED"))
         && (input.len() == 8
             || (!input.chars().nth(8).unwrap().is_alphanumeric()
                 && input.chars().nth(8).unwrap() != '_'))
     {
         Ok((&input[8..], Token::Directed))
     } else if input.len() >= 8
-        && input[..8].eq_ignore_ascii_case("PROPERTY")
+        && input
+            .get(..8)
+            .is_some_and(|s| s.eq_ignore_ascii_case("PROPERTY"))
         && (input.len() == 8
             || (!input.chars().nth(8).unwrap().is_alphanumeric()
                 && input.chars().nth(8).unwrap() != '_'))
     {
         Ok((&input[8..], Token::Property))
     } else if input.len() >= 8
-        && input[..8].eq_ignore_ascii_case("DISTINCT")
+        && input
+            .get(..8)
+            .is_some_and(|s| s.eq_ignore_ascii_case("DISTINCT"))
         && (input.len() == 8
             || (!input.chars().nth(8).unwrap().is_alphanumeric()
                 && input.chars().nth(8).unwrap() != '_'))
     {
         Ok((&input[8..], Token::Distinct))
     } else if input.len() >= 8
-        && input[..8].eq_ignore_ascii_case("CONTAINS")
+        && input
+            .get(..8)
+            .is_some_and(|s| s.eq_ignore_ascii_case("CONTAINS"))
         && (input.len() == 8
             || (!input.chars().nth(8).unwrap().is_alphanumeric()
                 && input.chars().nth(8).unwrap() != '_'))
     {
         Ok((&input[8..], Token::Contains))
     } else if input.len() >= 6
-        && input[..6].eq_ignore_ascii_case("WITHIN")
+        && input
+            .get(..6)
+            .is_some_and(|s| s.eq_ignore_ascii_case("WITHIN"))
         && (input.len() == 6
             || (!input.chars().nth(6).unwrap().is_alphanumeric()
                 && input.chars().nth(6).unwrap() != '_'))
     {
         Ok((&input[6..], Token::Within))
     } else if input.len() >= 7
-        && input[..7].eq_ignore_ascii_case("REPLACE")
+        && input
+            .get(..7)
+            .is_some_and(|s| s.eq_ignore_ascii_case("REPLACE"))
         && (input.len() == 7
             || (input.chars().nth(7).unwrap_or(' ') != '('
                 && !input.chars().nth(7).unwrap_or(' ').is_alphanumeric()))
     {
         Ok((&input[7..], Token::Replace))
     } else if input.len() >= 7
-        && input[..7].eq_ignore_ascii_case("BOOLEAN")
+        && input
+            .get(..7)
+            .is_some_and(|s| s.eq_ignore_ascii_case("BOOLEAN"))
         && (input.len() == 7
             || (!input.chars().nth(7).unwrap().is_alphanumeric()
                 && input.chars().nth(7).unwrap() != '_'))
     {
         Ok((&input[7..], Token::BooleanType))
     } else if input.len() >= 7
-        && input[..7].eq_ignore_ascii_case("INTEGER")
+        && input
+            .get(..7)
+            .is_some_and(|s| s.eq_ignore_ascii_case("INTEGER"))
         && (input.len() == 7
             || (!input.chars().nth(7).unwrap().is_alphanumeric()
                 && input.chars().nth(7).unwrap() != '_'))
     {
         Ok((&input[7..], Token::IntegerType))
     } else if input.len() >= 8
-        && input[..8].eq_ignore_ascii_case("SMALLINT")
+        && input
+            .get(..8)
+            .is_some_and(|s| s.eq_ignore_ascii_case("SMALLINT"))
         && (input.len() == 8
             || (!input.chars().nth(8).unwrap().is_alphanumeric()
                 && input.chars().nth(8).unwrap() != '_'))
     {
         Ok((&input[8..], Token::SmallIntType))
     } else if input.len() >= 6
-        && input[..6].eq_ignore_ascii_case("BIGINT")
+        && input
+            .get(..6)
+            .is_some_and(|s| s.eq_ignore_ascii_case("BIGINT"))
         && (input.len() == 6
             || (!input.chars().nth(6).unwrap().is_alphanumeric()
                 && input.chars().nth(6).unwrap() != '_'))
     {
         Ok((&input[6..], Token::BigIntType))
     } else if input.len() >= 7
-        && input[..7].eq_ignore_ascii_case("DECIMAL")
+        && input
+            .get(..7)
+            .is_some_and(|s| s.eq_ignore_ascii_case("DECIMAL"))
         && (input.len() == 7
             || (!input.chars().nth(7).unwrap().is_alphanumeric()
                 && input.chars().nth(7).unwrap() != '_'))
     {
         Ok((&input[7..], Token::DecimalType))
     } else if input.len() >= 6
-        && input[..6].eq_ignore_ascii_case("DOUBLE")
+        && input
+            .get(..6)
+            .is_some_and(|s| s.eq_ignore_ascii_case("DOUBLE"))
         && (input.len() == 6
             || (!input.chars().nth(6).unwrap().is_alphanumeric()
                 && input.chars().nth(6).unwrap() != '_'))
     {
         Ok((&input[6..], Token::DoubleType))
     } else if input.len() >= 5
-        && input[..5].eq_ignore_ascii_case("FLOAT")
+        && input
+            .get(..5)
+            .is_some_and(|s| s.eq_ignore_ascii_case("FLOAT"))
         && (input.len() == 5
             || (!input.chars().nth(5).unwrap().is_alphanumeric()
                 && input.chars().nth(5).unwrap() != '_'))
     {
         Ok((&input[5..], Token::FloatType))
     } else if input.len() >= 4
-        && input[..4].eq_ignore_ascii_case("REAL")
+        && input
+            .get(..4)
+            .is_some_and(|s| s.eq_ignore_ascii_case("REAL"))
         && (input.len() == 4
             || (!input.chars().nth(4).unwrap().is_alphanumeric()
                 && input.chars().nth(4).unwrap() != '_'))
     {
         Ok((&input[4..], Token::RealType))
     } else if input.len() >= 7
-        && input[..7].eq_ignore_ascii_case("CASCADE")
+        && input
+            .get(..7)
+            .is_some_and(|s| s.eq_ignore_ascii_case("CASCADE"))
         && (input.len() == 7
             || (!input.chars().nth(7).unwrap().is_alphanumeric()
                 && input.chars().nth(7).unwrap() != '_'))
     {
         Ok((&input[7..], Token::Cascade))
     } else if input.len() >= 7
-        && input[..7].eq_ignore_ascii_case("UNKNOWN")
+        && input
+            .get(..7)
+            .is_some_and(|s| s.eq_ignore_ascii_case("UNKNOWN"))
         && (input.len() == 7
             || (!input.chars().nth(7).unwrap().is_alphanumeric()
                 && input.chars().nth(7).unwrap() != '_'))
     {
         Ok((&input[7..], Token::Unknown))
     } else if input.len() >= 8
-        && input[..8].eq_ignore_ascii_case("RESTRICT")
+        && input
+            .get(..8)
+            .is_some_and(|s| s.eq_ignore_ascii_case("RESTRICT"))
         && (input.len() == 8
             || (!input.chars().nth(8).unwrap().is_alphanumeric()
                 && input.chars().nth(8).unwrap() != '_'))
     {
         Ok((&input[8..], Token::Restrict))
     } else if input.len() >= 14
-        && input[..14].eq_ignore_ascii_case("CHARACTERISTICS")
+        && input
+            .get(..14)
+            .is_some_and(|s| s.eq_ignore_ascii_case("CHARACTERISTICS"))
         && (input.len() == 14
             || (!input.chars().nth(14).unwrap().is_alphanumeric()
                 && input.chars().nth(14).unwrap() != '_'))
     {
         Ok((&input[14..], Token::Characteristics))
     } else if input.len() >= 10
-        && input[..10].eq_ignore_ascii_case("PARAMETERS")
+        && input
+            .get(..10)
+            .is_some_and(|s| s.eq_ignore_ascii_case("PARAMETERS"))
         && (input.len() == 10
             || (!input.chars().nth(10).unwrap().is_alphanumeric()
                 && input.chars().nth(10).unwrap() != '_'))
     {
         Ok((&input[10..], Token::Parameters))
     } else if input.len() >= 9
-        && input[..9].eq_ignore_ascii_case("PARAMETER")
+        && input
+            .get(..9)
+            .is_some_and(|s| s.eq_ignore_ascii_case("PARAMETER"))
         && (input.len() == 9
             || (!input.chars().nth(9).unwrap().is_alphanumeric()
                 && input.chars().nth(9).unwrap() != '_'))
     {
         Ok((&input[9..], Token::Parameter))
     } else if input.len() >= 7
-        && input[..7].eq_ignore_ascii_case("SESSION")
+        && input
+            .get(..7)
+            .is_some_and(|s| s.eq_ignore_ascii_case("SESSION"))
         && (input.len() == 7
             || (!input.chars().nth(7).unwrap().is_alphanumeric()
                 && input.chars().nth(7).unwrap() != '_'))
     {
         Ok((&input[7..], Token::Session))
     } else if input.len() >= 7
-        && input[..7].eq_ignore_ascii_case("BINDING")
+        && input
+            .get(..7)
+            .is_some_and(|s| s.eq_ignore_ascii_case("BINDING"))
         && (input.len() == 7
             || (!input.chars().nth(7).unwrap().is_alphanumeric()
                 && input.chars().nth(7).unwrap() != '_'))
     {
         Ok((&input[7..], Token::Binding))
     } else if input.len() >= 7
-        && input[..7].eq_ignore_ascii_case("CURRENT")
+        && input
+            .get(..7)
+            .is_some_and(|s| s.eq_ignore_ascii_case("CURRENT"))
         && (input.len() == 7
             || !input.chars().nth(7).unwrap_or(' ').is_alphanumeric()
                 && input.chars().nth(7).unwrap_or(' ') != '_')
     {
         Ok((&input[7..], Token::Current))
     } else if input.len() >= 8
-        && input[..8].eq_ignore_ascii_case("PASSWORD")
+        && input
+            .get(..8)
+            .is_some_and(|s| s.eq_ignore_ascii_case("PASSWORD"))
         && (input.len() == 8
             || (!input.chars().nth(8).unwrap().is_alphanumeric()
                 && input.chars().nth(8).unwrap() != '_'))
     {
         Ok((&input[8..], Token::Password))
     } else if input.len() >= 6
-        && input[..6].eq_ignore_ascii_case("STRING")
+        && input
+            .get(..6)
+            .is_some_and(|s| s.eq_ignore_ascii_case("STRING"))
         && (input.len() == 6
             || (!input.chars().nth(6).unwrap().is_alphanumeric()
                 && input.chars().nth(6).unwrap() != '_'))
     {
         Ok((&input[6..], Token::StringType))
     } else if input.len() >= 11
-        && input[..11].eq_ignore_ascii_case("DESCRIPTION")
+        && input
+            .get(..11)
+            .is_some_and(|s| s.eq_ignore_ascii_case("DESCRIPTION"))
         && (input.len() == 11
             || !input.chars().nth(11).unwrap_or(' ').is_alphanumeric()
                 && input.chars().nth(11).unwrap_or(' ') != '_')
     {
         Ok((&input[11..], Token::Description))
     } else if input.len() >= 8
-        && input[..8].eq_ignore_ascii_case("DESCRIBE")
+        && input
+            .get(..8)
+            .is_some_and(|s| s.eq_ignore_ascii_case("DESCRIBE"))
         && (input.len() == 8
             || !input.chars().nth(8).unwrap_or(' ').is_alphanumeric()
                 && input.chars().nth(8).unwrap_or(' ') != '_')
     {
         Ok((&input[8..], Token::Describe))
     } else if input.len() >= 8
-        && input[..8].eq_ignore_ascii_case("VALIDATE")
+        && input
+            .get(..8)
+            .is_some_and(|s| s.eq_ignore_ascii_case("VALIDATE"))
         && (input.len() == 8
             || !input.chars().nth(8).unwrap_or(' ').is_alphanumeric()
                 && input.chars().nth(8).unwrap_or(' ') != '_')
     {
         Ok((&input[8..], Token::Validate))
     } else if input.len() >= 7
-        && input[..7].eq_ignore_ascii_case("VERSION")
+        && input
+            .get(..7)
+            .is_some_and(|s| s.eq_ignore_ascii_case("VERSION"))
         && (input.len() == 7
             || !input.chars().nth(7).unwrap_or(' ').is_alphanumeric()
                 && input.chars().nth(7).unwrap_or(' ') != '_')
     {
         Ok((&input[7..], Token::Version))
     } else if input.len() >= 7
-        && input[..7].eq_ignore_ascii_case("AGAINST")
+        && input
+            .get(..7)
+            .is_some_and(|s| s.eq_ignore_ascii_case("AGAINST"))
         && (input.len() == 7
             || !input.chars().nth(7).unwrap_or(' ').is_alphanumeric()
                 && input.chars().nth(7).unwrap_or(' ') != '_')
     {
         Ok((&input[7..], Token::Against))
     } else if input.len() >= 6
-        && input[..6].eq_ignore_ascii_case("SCHEMA")
+        && input
+            .get(..6)
+            .is_some_and(|s| s.eq_ignore_ascii_case("SCHEMA"))
         && (input.len() == 6
             || !input.chars().nth(6).unwrap_or(' ').is_alphanumeric()
                 && input.chars().nth(6).unwrap_or(' ') != '_')
     {
         Ok((&input[6..], Token::Schema))
     } else if input.len() >= 6
-        && input[..6].eq_ignore_ascii_case("INLINE")
+        && input
+            .get(..6)
+            .is_some_and(|s| s.eq_ignore_ascii_case("INLINE"))
         && (input.len() == 6
             || !input.chars().nth(6).unwrap_or(' ').is_alphanumeric()
                 && input.chars().nth(6).unwrap_or(' ') != '_')
     {
         Ok((&input[6..], Token::Inline))
     } else if input.len() >= 5
-        && input[..5].eq_ignore_ascii_case("ALTER")
+        && input
+            .get(..5)
+            .is_some_and(|s| s.eq_ignore_ascii_case("ALTER"))
         && (input.len() == 5
             || !input.chars().nth(5).unwrap_or(' ').is_alphanumeric()
                 && input.chars().nth(5).unwrap_or(' ') != '_')
     {
         Ok((&input[5..], Token::Alter))
     } else if input.len() >= 4
-        && input[..4].eq_ignore_ascii_case("SHOW")
+        && input
+            .get(..4)
+            .is_some_and(|s| s.eq_ignore_ascii_case("SHOW"))
         && (input.len() == 4
             || !input.chars().nth(4).unwrap_or(' ').is_alphanumeric()
                 && input.chars().nth(4).unwrap_or(' ') != '_')
     {
         Ok((&input[4..], Token::Show))
     } else if input.len() >= 4
-        && input[..4].eq_ignore_ascii_case("DATA")
+        && input
+            .get(..4)
+            .is_some_and(|s| s.eq_ignore_ascii_case("DATA"))
         && (input.len() == 4
             || !input.chars().nth(4).unwrap_or(' ').is_alphanumeric()
                 && input.chars().nth(4).unwrap_or(' ') != '_')
     {
         Ok((&input[4..], Token::Data))
     } else if input.len() >= 4
-        && input[..4].eq_ignore_ascii_case("FILE")
+        && input
+            .get(..4)
+            .is_some_and(|s| s.eq_ignore_ascii_case("FILE"))
         && (input.len() == 4
             || !input.chars().nth(4).unwrap_or(' ').is_alphanumeric()
                 && input.chars().nth(4).unwrap_or(' ') != '_')
     {
         Ok((&input[4..], Token::File))
     } else if input.len() >= 3
-        && input[..3].eq_ignore_ascii_case("URL")
+        && input
+            .get(..3)
+            .is_some_and(|s| s.eq_ignore_ascii_case("URL"))
         && (input.len() == 3
             || !input.chars().nth(3).unwrap_or(' ').is_alphanumeric()
                 && input.chars().nth(3).unwrap_or(' ') != '_')
     {
         Ok((&input[3..], Token::Url))
     } else if input.len() >= 7
-        && input[..7].eq_ignore_ascii_case("ACYCLIC")
+        && input
+            .get(..7)
+            .is_some_and(|s| s.eq_ignore_ascii_case("ACYCLIC"))
         && (input.len() == 7
             || (!input.chars().nth(7).unwrap().is_alphanumeric()
                 && input.chars().nth(7).unwrap() != '_'))
     {
         Ok((&input[7..], Token::Acyclic))
     } else if input.len() >= 6
-        && input[..6].eq_ignore_ascii_case("SIMPLE")
+        && input
+            .get(..6)
+            .is_some_and(|s| s.eq_ignore_ascii_case("SIMPLE"))
         && (input.len() == 6
             || (!input.chars().nth(6).unwrap_or(' ').is_alphanumeric()
                 && input.chars().nth(6).unwrap_or(' ') != '_'))
     {
         Ok((&input[6..], Token::Simple))
     } else if input.len() >= 5
-        && input[..5].eq_ignore_ascii_case("TRAIL")
+        && input
+            .get(..5)
+            .is_some_and(|s| s.eq_ignore_ascii_case("TRAIL"))
         && (input.len() == 5
             || (!input.chars().nth(5).unwrap().is_alphanumeric()
                 && input.chars().nth(5).unwrap() != '_'))
     {
         Ok((&input[5..], Token::Trail))
     } else if input.len() >= 4
-        && input[..4].eq_ignore_ascii_case("WALK")
+        && input
+            .get(..4)
+            .is_some_and(|s| s.eq_ignore_ascii_case("WALK"))
         && (input.len() == 4
             || (!input.chars().nth(4).unwrap().is_alphanumeric()
                 && input.chars().nth(4).unwrap() != '_'))
     {
         Ok((&input[4..], Token::Walk))
     } else if input.len() >= 4
-        && input[..4].eq_ignore_ascii_case("PATH")
+        && input
+            .get(..4)
+            .is_some_and(|s| s.eq_ignore_ascii_case("PATH"))
         && (input.len() == 4
             || (!input.chars().nth(4).unwrap().is_alphanumeric()
                 && input.chars().nth(4).unwrap() != '_'))
     {
         Ok((&input[4..], Token::Path))
     } else if input.len() >= 6
-        && input[..6].eq_ignore_ascii_case("CREATE")
+        && input
+            .get(..6)
+            .is_some_and(|s| s.eq_ignore_ascii_case("CREATE"))
         && (input.len() == 6 || !input.chars().nth(6).unwrap_or(' ').is_alphanumeric())
     {
         Ok((&input[6..], Token::Create))
     } else if input.len() >= 6
-        && input[..6].eq_ignore_ascii_case("INSERT")
+        && input
+            .get(..6)
+            .is_some_and(|s| s.eq_ignore_ascii_case("INSERT"))
         && (input.len() == 6
             || (!input.chars().nth(6).unwrap().is_alphanumeric()
                 && input.chars().nth(6).unwrap() != '_'))
     {
         Ok((&input[6..], Token::Insert))
     } else if input.len() >= 6
-        && input[..6].eq_ignore_ascii_case("DELETE")
+        && input
+            .get(..6)
+            .is_some_and(|s| s.eq_ignore_ascii_case("DELETE"))
         && (input.len() == 6
             || (!input.chars().nth(6).unwrap().is_alphanumeric()
                 && input.chars().nth(6).unwrap() != '_'))
     {
         Ok((&input[6..], Token::Delete))
     } else if input.len() >= 6
-        && input[..6].eq_ignore_ascii_case("REMOVE")
+        && input
+            .get(..6)
+            .is_some_and(|s| s.eq_ignore_ascii_case("REMOVE"))
         && (input.len() == 6
             || (!input.chars().nth(6).unwrap().is_alphanumeric()
                 && input.chars().nth(6).unwrap() != '_'))
     {
         Ok((&input[6..], Token::Remove))
     } else if input.len() >= 6
-        && input[..6].eq_ignore_ascii_case("UNWIND")
+        && input
+            .get(..6)
+            .is_some_and(|s| s.eq_ignore_ascii_case("UNWIND"))
         && (input.len() == 6
             || (!input.chars().nth(6).unwrap().is_alphanumeric()
                 && input.chars().nth(6).unwrap() != '_'))
     {
         Ok((&input[6..], Token::Unwind))
     } else if input.len() >= 6
-        && input[..6].eq_ignore_ascii_case("STARTS")
+        && input
+            .get(..6)
+            .is_some_and(|s| s.eq_ignore_ascii_case("STARTS"))
         && (input.len() == 6
             || (!input.chars().nth(6).unwrap().is_alphanumeric()
                 && input.chars().nth(6).unwrap() != '_'))
     {
         Ok((&input[6..], Token::Starts))
     } else if input.len() >= 6
-        && input[..6].eq_ignore_ascii_case("EXISTS")
+        && input
+            .get(..6)
+            .is_some_and(|s| s.eq_ignore_ascii_case("EXISTS"))
         && (input.len() == 6
             || (!input.chars().nth(6).unwrap().is_alphanumeric()
                 && input.chars().nth(6).unwrap() != '_'))
     {
         Ok((&input[6..], Token::Exists))
     } else if input.len() >= 7
-        && input[..7].eq_ignore_ascii_case("MATCHES")
+        && input
+            .get(..7)
+            .is_some_and(|s| s.eq_ignore_ascii_case("MATCHES"))
         && (input.len() == 7
             || (!input.chars().nth(7).unwrap().is_alphanumeric()
                 && input.chars().nth(7).unwrap() != '_'))
     {
         Ok((&input[7..], Token::Matches))
     } else if input.len() >= 6
-        && input[..6].eq_ignore_ascii_case("SOURCE")
+        && input
+            .get(..6)
+            .is_some_and(|s| s.eq_ignore_ascii_case("SOURCE"))
         && (input.len() == 6
             || (!input.chars().nth(6).unwrap().is_alphanumeric()
                 && input.chars().nth(6).unwrap() != '_'))
     {
         Ok((&input[6..], Token::Source))
     } else if input.len() >= 4
-        && input[..4].eq_ignore_ascii_case("CASE")
+        && input
+            .get(..4)
+            .is_some_and(|s| s.eq_ignore_ascii_case("CASE"))
         && (input.len() == 4
             || (!input.chars().nth(4).unwrap().is_alphanumeric()
                 && input.chars().nth(4).unwrap() != '_'))
     {
         Ok((&input[4..], Token::Case))
     } else if input.len() >= 4
-        && input[..4].eq_ignore_ascii_case("CAST")
+        && input
+            .get(..4)
+            .is_some_and(|s| s.eq_ignore_ascii_case("CAST"))
         && (input.len() == 4
             || (!input.chars().nth(4).unwrap().is_alphanumeric()
                 && input.chars().nth(4).unwrap() != '_'))
     {
         Ok((&input[4..], Token::Cast))
     } else if input.len() >= 4
-        && input[..4].eq_ignore_ascii_case("WHEN")
+        && input
+            .get(..4)
+            .is_some_and(|s| s.eq_ignore_ascii_case("WHEN"))
         && (input.len() == 4
             || (!input.chars().nth(4).unwrap().is_alphanumeric()
                 && input.chars().nth(4).unwrap() != '_'))
     {
         Ok((&input[4..], Token::When))
     } else if input.len() >= 4
-        && input[..4].eq_ignore_ascii_case("THEN")
+        && input
+            .get(..4)
+            .is_some_and(|s| s.eq_ignore_ascii_case("THEN"))
         && (input.len() == 4
             || (!input.chars().nth(4).unwrap().is_alphanumeric()
                 && input.chars().nth(4).unwrap() != '_'))
     {
         Ok((&input[4..], Token::Then))
     } else if input.len() >= 4
-        && input[..4].eq_ignore_ascii_case("ELSE")
+        && input
+            .get(..4)
+            .is_some_and(|s| s.eq_ignore_ascii_case("ELSE"))
         && (input.len() == 4
             || (!input.chars().nth(4).unwrap().is_alphanumeric()
                 && input.chars().nth(4).unwrap() != '_'))
     {
         Ok((&input[4..], Token::Else))
     } else if input.len() >= 3
-        && input[..3].eq_ignore_ascii_case("END")
+        && input
+            .get(..3)
+            .is_some_and(|s| s.eq_ignore_ascii_case("END"))
         && (input.len() == 3
             || (!input.chars().nth(3).unwrap().is_alphanumeric()
                 && input.chars().nth(3).unwrap() != '_'))
     {
         Ok((&input[3..], Token::End))
     } else if input.len() >= 7
-        && input[..7].eq_ignore_ascii_case("LEADING")
+        && input
+            .get(..7)
+            .is_some_and(|s| s.eq_ignore_ascii_case("LEADING"))
         && (input.len() == 7 || !input.chars().nth(7).unwrap_or(' ').is_alphanumeric())
     {
         Ok((&input[7..], Token::Leading))
     } else if input.len() >= 8
-        && input[..8].eq_ignore_ascii_case("TRAILING")
+        && input
+            .get(..8)
+            .is_some_and(|s| s.eq_ignore_ascii_case("TRAILING"))
         && (input.len() == 8 || !input.chars().nth(8).unwrap_or(' ').is_alphanumeric())
     {
         Ok((&input[8..], Token::Trailing))
     } else if input.len() >= 4
-        && input[..4].eq_ignore_ascii_case("BOTH")
+        && input
+            .get(..4)
+            .is_some_and(|s| s.eq_ignore_ascii_case("BOTH"))
         && (input.len() == 4 || !input.chars().nth(4).unwrap_or(' ').is_alphanumeric())
     {
         Ok((&input[4..], Token::Both))
     } else if input.len() >= 3
-        && input[..3].eq_ignore_ascii_case("LET")
+        && input
+            .get(..3)
+            .is_some_and(|s| s.eq_ignore_ascii_case("LET"))
         && (input.len() == 3 || !input.chars().nth(3).unwrap_or(' ').is_alphanumeric())
     {
         Ok((&input[3..], Token::Let))
     } else if input.len() >= 3
-        && input[..3].eq_ignore_ascii_case("FOR")
+        && input
+            .get(..3)
+            .is_some_and(|s| s.eq_ignore_ascii_case("FOR"))
         && (input.len() == 3 || !input.chars().nth(3).unwrap_or(' ').is_alphanumeric())
     {
         Ok((&input[3..], Token::For))
     } else if input.len() >= 6
-        && input[..6].eq_ignore_ascii_case("FILTER")
+        && input
+            .get(..6)
+            .is_some_and(|s| s.eq_ignore_ascii_case("FILTER"))
         && (input.len() == 6 || !input.chars().nth(6).unwrap_or(' ').is_alphanumeric())
     {
         Ok((&input[6..], Token::Filter))
     } else if input.len() >= 7
-        && input[..7].eq_ignore_ascii_case("DECLARE")
+        && input
+            .get(..7)
+            .is_some_and(|s| s.eq_ignore_ascii_case("DECLARE"))
         && (input.len() == 7 || !input.chars().nth(7).unwrap_or(' ').is_alphanumeric())
     {
         Ok((&input[7..], Token::Declare))
     } else if input.len() >= 4
-        && input[..4].eq_ignore_ascii_case("NEXT")
+        && input
+            .get(..4)
+            .is_some_and(|s| s.eq_ignore_ascii_case("NEXT"))
         && (input.len() == 4 || !input.chars().nth(4).unwrap_or(' ').is_alphanumeric())
     {
         Ok((&input[4..], Token::Next))
     } else if input.len() >= 2
-        && input[..2].eq_ignore_ascii_case("AT")
+        && input.get(..2).is_some_and(|s| s.eq_ignore_ascii_case("AT"))
         && (input.len() == 2
             || (!input.chars().nth(2).unwrap_or(' ').is_alphanumeric()
                 && input.chars().nth(2).unwrap_or(' ') != '_'))
@@ -962,98 +1099,126 @@ fn simple_patterns(input: &str) -> IResult<&str, Token> {
     } else if is_keyword_match(input, "COMMIT") {
         Ok((&input[6..], Token::Commit))
     } else if input.len() >= 6
-        && input[..6].eq_ignore_ascii_case("VERTEX")
+        && input
+            .get(..6)
+            .is_some_and(|s| s.eq_ignore_ascii_case("VERTEX"))
         && (input.len() == 6
             || (!input.chars().nth(6).unwrap().is_alphanumeric()
                 && input.chars().nth(6).unwrap() != '_'))
     {
         Ok((&input[6..], Token::Vertex))
     } else if input.len() >= 8
-        && input[..8].eq_ignore_ascii_case("NODETACH")
+        && input
+            .get(..8)
+            .is_some_and(|s| s.eq_ignore_ascii_case("NODETACH"))
         && (input.len() == 8
             || (!input.chars().nth(8).unwrap().is_alphanumeric()
                 && input.chars().nth(8).unwrap() != '_'))
     {
         Ok((&input[8..], Token::NoDetach))
     } else if input.len() >= 6
-        && input[..6].eq_ignore_ascii_case("DETACH")
+        && input
+            .get(..6)
+            .is_some_and(|s| s.eq_ignore_ascii_case("DETACH"))
         && (input.len() == 6
             || (!input.chars().nth(6).unwrap().is_alphanumeric()
                 && input.chars().nth(6).unwrap() != '_'))
     {
         Ok((&input[6..], Token::Detach))
     } else if input.len() >= 6
-        && input[..6].eq_ignore_ascii_case("SELECT")
+        && input
+            .get(..6)
+            .is_some_and(|s| s.eq_ignore_ascii_case("SELECT"))
         && (input.len() == 6
             || (!input.chars().nth(6).unwrap().is_alphanumeric()
                 && input.chars().nth(6).unwrap() != '_'))
     {
         Ok((&input[6..], Token::Select))
     } else if input.len() >= 4
-        && input[..4].eq_ignore_ascii_case("FROM")
+        && input
+            .get(..4)
+            .is_some_and(|s| s.eq_ignore_ascii_case("FROM"))
         && (input.len() == 4
             || (!input.chars().nth(4).unwrap().is_alphanumeric()
                 && input.chars().nth(4).unwrap() != '_'))
     {
         Ok((&input[4..], Token::From))
     } else if input.len() >= 6
-        && input[..6].eq_ignore_ascii_case("RETURN")
+        && input
+            .get(..6)
+            .is_some_and(|s| s.eq_ignore_ascii_case("RETURN"))
         && (input.len() == 6
             || (!input.chars().nth(6).unwrap().is_alphanumeric()
                 && input.chars().nth(6).unwrap() != '_'))
     {
         Ok((&input[6..], Token::Return))
     } else if input.len() >= 5
-        && input[..5].eq_ignore_ascii_case("MATCH")
+        && input
+            .get(..5)
+            .is_some_and(|s| s.eq_ignore_ascii_case("MATCH"))
         && (input.len() == 5
             || (!input.chars().nth(5).unwrap_or(' ').is_alphanumeric()
                 && input.chars().nth(5).unwrap_or(' ') != '_'))
     {
         Ok((&input[5..], Token::Match))
     } else if input.len() >= 5
-        && input[..5].eq_ignore_ascii_case("WHERE")
+        && input
+            .get(..5)
+            .is_some_and(|s| s.eq_ignore_ascii_case("WHERE"))
         && (input.len() == 5
             || (!input.chars().nth(5).unwrap().is_alphanumeric()
                 && input.chars().nth(5).unwrap() != '_'))
     {
         Ok((&input[5..], Token::Where))
     } else if input.len() >= 5
-        && input[..5].eq_ignore_ascii_case("MERGE")
+        && input
+            .get(..5)
+            .is_some_and(|s| s.eq_ignore_ascii_case("MERGE"))
         && (input.len() == 5
             || (!input.chars().nth(5).unwrap().is_alphanumeric()
                 && input.chars().nth(5).unwrap() != '_'))
     {
         Ok((&input[5..], Token::Merge))
     } else if input.len() >= 5
-        && input[..5].eq_ignore_ascii_case("STORE")
+        && input
+            .get(..5)
+            .is_some_and(|s| s.eq_ignore_ascii_case("STORE"))
         && (input.len() == 5
             || (!input.chars().nth(5).unwrap().is_alphanumeric()
                 && input.chars().nth(5).unwrap() != '_'))
     {
         Ok((&input[5..], Token::Store))
     } else if input.len() >= 5
-        && input[..5].eq_ignore_ascii_case("ORDER")
+        && input
+            .get(..5)
+            .is_some_and(|s| s.eq_ignore_ascii_case("ORDER"))
         && (input.len() == 5
             || (!input.chars().nth(5).unwrap().is_alphanumeric()
                 && input.chars().nth(5).unwrap() != '_'))
     {
         Ok((&input[5..], Token::Order))
     } else if input.len() >= 5
-        && input[..5].eq_ignore_ascii_case("LIMIT")
+        && input
+            .get(..5)
+            .is_some_and(|s| s.eq_ignore_ascii_case("LIMIT"))
         && (input.len() == 5
             || (!input.chars().nth(5).unwrap().is_alphanumeric()
                 && input.chars().nth(5).unwrap() != '_'))
     {
         Ok((&input[5..], Token::Limit))
     } else if input.len() >= 6
-        && input[..6].eq_ignore_ascii_case("HAVING")
+        && input
+            .get(..6)
+            .is_some_and(|s| s.eq_ignore_ascii_case("HAVING"))
         && (input.len() == 6
             || (!input.chars().nth(6).unwrap().is_alphanumeric()
                 && input.chars().nth(6).unwrap() != '_'))
     {
         Ok((&input[6..], Token::Having))
     } else if input.len() >= 6
-        && input[..6].eq_ignore_ascii_case("OFFSET")
+        && input
+            .get(..6)
+            .is_some_and(|s| s.eq_ignore_ascii_case("OFFSET"))
         && (input.len() == 6
             || (!input.chars().nth(6).unwrap().is_alphanumeric()
                 && input.chars().nth(6).unwrap() != '_'))
@@ -1062,133 +1227,167 @@ fn simple_patterns(input: &str) -> IResult<&str, Token> {
     } else if is_keyword_match(input, "GRAPH") {
         Ok((&input[5..], Token::Graph))
     } else if input.len() >= 5
-        && input[..5].eq_ignore_ascii_case("GROUP")
+        && input
+            .get(..5)
+            .is_some_and(|s| s.eq_ignore_ascii_case("GROUP"))
         && (input.len() == 5
             || (!input.chars().nth(5).unwrap().is_alphanumeric()
                 && input.chars().nth(5).unwrap() != '_'))
     {
         Ok((&input[5..], Token::Group))
     } else if input.len() >= 5
-        && input[..5].eq_ignore_ascii_case("TYPES")
+        && input
+            .get(..5)
+            .is_some_and(|s| s.eq_ignore_ascii_case("TYPES"))
         && (input.len() == 5
             || (!input.chars().nth(5).unwrap().is_alphanumeric()
                 && input.chars().nth(5).unwrap() != '_'))
     {
         Ok((&input[5..], Token::Types))
     } else if input.len() >= 5
-        && input[..5].eq_ignore_ascii_case("ROLES")
+        && input
+            .get(..5)
+            .is_some_and(|s| s.eq_ignore_ascii_case("ROLES"))
         && (input.len() == 5
             || (!input.chars().nth(5).unwrap().is_alphanumeric()
                 && input.chars().nth(5).unwrap() != '_'))
     {
         Ok((&input[5..], Token::Roles))
     } else if input.len() >= 5
-        && input[..5].eq_ignore_ascii_case("YIELD")
+        && input
+            .get(..5)
+            .is_some_and(|s| s.eq_ignore_ascii_case("YIELD"))
         && (input.len() == 5
             || (!input.chars().nth(5).unwrap().is_alphanumeric()
                 && input.chars().nth(5).unwrap() != '_'))
     {
         Ok((&input[5..], Token::Yield))
     } else if input.len() >= 5
-        && input[..5].eq_ignore_ascii_case("RESET")
+        && input
+            .get(..5)
+            .is_some_and(|s| s.eq_ignore_ascii_case("RESET"))
         && (input.len() == 5
             || (!input.chars().nth(5).unwrap().is_alphanumeric()
                 && input.chars().nth(5).unwrap() != '_'))
     {
         Ok((&input[5..], Token::Reset))
     } else if input.len() >= 5
-        && input[..5].eq_ignore_ascii_case("CLOSE")
+        && input
+            .get(..5)
+            .is_some_and(|s| s.eq_ignore_ascii_case("CLOSE"))
         && (input.len() == 5
             || (!input.chars().nth(5).unwrap().is_alphanumeric()
                 && input.chars().nth(5).unwrap() != '_'))
     {
         Ok((&input[5..], Token::Close))
     } else if input.len() >= 5
-        && input[..5].eq_ignore_ascii_case("VALUE")
+        && input
+            .get(..5)
+            .is_some_and(|s| s.eq_ignore_ascii_case("VALUE"))
         && (input.len() == 5
             || (!input.chars().nth(5).unwrap().is_alphanumeric()
                 && input.chars().nth(5).unwrap() != '_'))
     {
         Ok((&input[5..], Token::Value))
     } else if input.len() >= 5
-        && input[..5].eq_ignore_ascii_case("TABLE")
+        && input
+            .get(..5)
+            .is_some_and(|s| s.eq_ignore_ascii_case("TABLE"))
         && (input.len() == 5
             || (!input.chars().nth(5).unwrap().is_alphanumeric()
                 && input.chars().nth(5).unwrap() != '_'))
     {
         Ok((&input[5..], Token::Table))
     } else if input.len() >= 5
-        && input[..5].eq_ignore_ascii_case("TYPED")
+        && input
+            .get(..5)
+            .is_some_and(|s| s.eq_ignore_ascii_case("TYPED"))
         && (input.len() == 5
             || (!input.chars().nth(5).unwrap().is_alphanumeric()
                 && input.chars().nth(5).unwrap() != '_'))
     {
         Ok((&input[5..], Token::Typed))
     } else if input.len() >= 4
-        && input[..4].eq_ignore_ascii_case("TIME")
+        && input
+            .get(..4)
+            .is_some_and(|s| s.eq_ignore_ascii_case("TIME"))
         && (input.len() == 4
             || (!input.chars().nth(4).unwrap().is_alphanumeric()
                 && input.chars().nth(4).unwrap() != '_'))
     {
         Ok((&input[4..], Token::Time))
     } else if input.len() >= 4
-        && input[..4].eq_ignore_ascii_case("ZONE")
+        && input
+            .get(..4)
+            .is_some_and(|s| s.eq_ignore_ascii_case("ZONE"))
         && (input.len() == 4
             || (!input.chars().nth(4).unwrap().is_alphanumeric()
                 && input.chars().nth(4).unwrap() != '_'))
     {
         Ok((&input[4..], Token::Zone))
     } else if input.len() >= 4
-        && input[..4].eq_ignore_ascii_case("HOME")
+        && input
+            .get(..4)
+            .is_some_and(|s| s.eq_ignore_ascii_case("HOME"))
         && (input.len() == 4
             || (!input.chars().nth(4).unwrap().is_alphanumeric()
                 && input.chars().nth(4).unwrap() != '_'))
     {
         Ok((&input[4..], Token::Home))
     } else if input.len() >= 4
-        && &input[..4] == "USER"
+        && input.starts_with("USER")
         && (input.len() == 4
             || !input.chars().nth(4).unwrap_or(' ').is_alphanumeric()
                 && input.chars().nth(4).unwrap_or(' ') != '_')
     {
         Ok((&input[4..], Token::User))
     } else if input.len() >= 4
-        && input[..4].eq_ignore_ascii_case("ROLE")
+        && input
+            .get(..4)
+            .is_some_and(|s| s.eq_ignore_ascii_case("ROLE"))
         && (input.len() == 4
             || (!input.chars().nth(4).unwrap().is_alphanumeric()
                 && input.chars().nth(4).unwrap() != '_'))
     {
         Ok((&input[4..], Token::Role))
     } else if input.len() >= 5
-        && input[..5].eq_ignore_ascii_case("GRANT")
+        && input
+            .get(..5)
+            .is_some_and(|s| s.eq_ignore_ascii_case("GRANT"))
         && (input.len() == 5
             || (!input.chars().nth(5).unwrap().is_alphanumeric()
                 && input.chars().nth(5).unwrap() != '_'))
     {
         Ok((&input[5..], Token::Grant))
     } else if input.len() >= 6
-        && input[..6].eq_ignore_ascii_case("REVOKE")
+        && input
+            .get(..6)
+            .is_some_and(|s| s.eq_ignore_ascii_case("REVOKE"))
         && (input.len() == 6
             || (!input.chars().nth(6).unwrap().is_alphanumeric()
                 && input.chars().nth(6).unwrap() != '_'))
     {
         Ok((&input[6..], Token::Revoke))
     } else if input.len() >= 9
-        && input[..9].eq_ignore_ascii_case("PROCEDURE")
+        && input
+            .get(..9)
+            .is_some_and(|s| s.eq_ignore_ascii_case("PROCEDURE"))
         && (input.len() == 9
             || (!input.chars().nth(9).unwrap().is_alphanumeric()
                 && input.chars().nth(9).unwrap() != '_'))
     {
         Ok((&input[9..], Token::Procedure))
     } else if input.len() >= 2
-        && input[..2].eq_ignore_ascii_case("TO")
+        && input.get(..2).is_some_and(|s| s.eq_ignore_ascii_case("TO"))
         && (input.len() == 2
             || (!input.chars().nth(2).unwrap().is_alphanumeric()
                 && input.chars().nth(2).unwrap() != '_'))
     {
         Ok((&input[2..], Token::To))
     } else if input.len() >= 4
-        && input[..4].eq_ignore_ascii_case("NODE")
+        && input
+            .get(..4)
+            .is_some_and(|s| s.eq_ignore_ascii_case("NODE"))
         && (input.len() == 4
             || (!input.chars().nth(4).unwrap().is_alphanumeric()
                 && input.chars().nth(4).unwrap() != '_'))
@@ -1204,166 +1403,214 @@ fn simple_patterns(input: &str) -> IResult<&str, Token> {
             )))
         }
     } else if input.len() >= 4
-        && input[..4].eq_ignore_ascii_case("EDGE")
+        && input
+            .get(..4)
+            .is_some_and(|s| s.eq_ignore_ascii_case("EDGE"))
         && (input.len() == 4
             || (!input.chars().nth(4).unwrap().is_alphanumeric()
                 && input.chars().nth(4).unwrap() != '_'))
     {
         Ok((&input[4..], Token::Edge))
     } else if input.len() >= 4
-        && input[..4].eq_ignore_ascii_case("WITH")
+        && input
+            .get(..4)
+            .is_some_and(|s| s.eq_ignore_ascii_case("WITH"))
         && (input.len() == 4
             || (!input.chars().nth(4).unwrap().is_alphanumeric()
                 && input.chars().nth(4).unwrap() != '_'))
     {
         Ok((&input[4..], Token::With))
     } else if input.len() >= 4
-        && input[..4].eq_ignore_ascii_case("CALL")
+        && input
+            .get(..4)
+            .is_some_and(|s| s.eq_ignore_ascii_case("CALL"))
         && (input.len() == 4
             || (!input.chars().nth(4).unwrap().is_alphanumeric()
                 && input.chars().nth(4).unwrap() != '_'))
     {
         Ok((&input[4..], Token::Call))
     } else if input.len() >= 4
-        && input[..4].eq_ignore_ascii_case("LOAD")
+        && input
+            .get(..4)
+            .is_some_and(|s| s.eq_ignore_ascii_case("LOAD"))
         && (input.len() == 4
             || (!input.chars().nth(4).unwrap().is_alphanumeric()
                 && input.chars().nth(4).unwrap() != '_'))
     {
         Ok((&input[4..], Token::Load))
     } else if input.len() >= 4
-        && input[..4].eq_ignore_ascii_case("ENDS")
+        && input
+            .get(..4)
+            .is_some_and(|s| s.eq_ignore_ascii_case("ENDS"))
         && (input.len() == 4
             || (!input.chars().nth(4).unwrap().is_alphanumeric()
                 && input.chars().nth(4).unwrap() != '_'))
     {
         Ok((&input[4..], Token::Ends))
     } else if input.len() >= 4
-        && input[..4].eq_ignore_ascii_case("LIKE")
+        && input
+            .get(..4)
+            .is_some_and(|s| s.eq_ignore_ascii_case("LIKE"))
         && (input.len() == 4 || is_word_boundary(input.chars().nth(4).unwrap_or(' ')))
     {
         Ok((&input[4..], Token::Like))
     } else if input.len() >= 4
-        && input[..4].eq_ignore_ascii_case("TYPE")
+        && input
+            .get(..4)
+            .is_some_and(|s| s.eq_ignore_ascii_case("TYPE"))
         && (input.len() == 4
             || (!input.chars().nth(4).unwrap().is_alphanumeric()
                 && input.chars().nth(4).unwrap() != '_'))
     {
         Ok((&input[4..], Token::Type))
     } else if input.len() >= 4
-        && input[..4].eq_ignore_ascii_case("COPY")
+        && input
+            .get(..4)
+            .is_some_and(|s| s.eq_ignore_ascii_case("COPY"))
         && (input.len() == 4
             || (!input.chars().nth(4).unwrap().is_alphanumeric()
                 && input.chars().nth(4).unwrap() != '_'))
     {
         Ok((&input[4..], Token::Copy))
     } else if input.len() >= 8
-        && input[..8].eq_ignore_ascii_case("TRUNCATE")
+        && input
+            .get(..8)
+            .is_some_and(|s| s.eq_ignore_ascii_case("TRUNCATE"))
         && (input.len() == 8
             || (!input.chars().nth(8).unwrap().is_alphanumeric()
                 && input.chars().nth(8).unwrap() != '_'))
     {
         Ok((&input[8..], Token::Truncate))
     } else if input.len() >= 5
-        && input[..5].eq_ignore_ascii_case("CLEAR")
+        && input
+            .get(..5)
+            .is_some_and(|s| s.eq_ignore_ascii_case("CLEAR"))
         && (input.len() == 5
             || (!input.chars().nth(5).unwrap().is_alphanumeric()
                 && input.chars().nth(5).unwrap() != '_'))
     {
         Ok((&input[5..], Token::Clear))
     } else if input.len() >= 4
-        && input[..4].eq_ignore_ascii_case("DROP")
+        && input
+            .get(..4)
+            .is_some_and(|s| s.eq_ignore_ascii_case("DROP"))
         && (input.len() == 4
             || (!input.chars().nth(4).unwrap().is_alphanumeric()
                 && input.chars().nth(4).unwrap() != '_'))
     {
         Ok((&input[4..], Token::Drop))
     } else if input.len() >= 8
-        && input[..8].eq_ignore_ascii_case("REGISTER")
+        && input
+            .get(..8)
+            .is_some_and(|s| s.eq_ignore_ascii_case("REGISTER"))
         && (input.len() == 8
             || (!input.chars().nth(8).unwrap().is_alphanumeric()
                 && input.chars().nth(8).unwrap() != '_'))
     {
         Ok((&input[8..], Token::Register))
     } else if input.len() >= 10
-        && input[..10].eq_ignore_ascii_case("UNREGISTER")
+        && input
+            .get(..10)
+            .is_some_and(|s| s.eq_ignore_ascii_case("UNREGISTER"))
         && (input.len() == 10
             || (!input.chars().nth(10).unwrap().is_alphanumeric()
                 && input.chars().nth(10).unwrap() != '_'))
     {
         Ok((&input[10..], Token::Unregister))
     } else if input.len() >= 4
-        && input[..4].eq_ignore_ascii_case("SKIP")
+        && input
+            .get(..4)
+            .is_some_and(|s| s.eq_ignore_ascii_case("SKIP"))
         && (input.len() == 4
             || (!input.chars().nth(4).unwrap().is_alphanumeric()
                 && input.chars().nth(4).unwrap() != '_'))
     {
         Ok((&input[4..], Token::Skip))
     } else if input.len() >= 7
-        && input[..7].eq_ignore_ascii_case("LEADING")
+        && input
+            .get(..7)
+            .is_some_and(|s| s.eq_ignore_ascii_case("LEADING"))
         && (input.len() == 7
             || (!input.chars().nth(7).unwrap().is_alphanumeric()
                 && input.chars().nth(7).unwrap() != '_'))
     {
         Ok((&input[7..], Token::Leading))
     } else if input.len() >= 8
-        && input[..8].eq_ignore_ascii_case("TRAILING")
+        && input
+            .get(..8)
+            .is_some_and(|s| s.eq_ignore_ascii_case("TRAILING"))
         && (input.len() == 8
             || (!input.chars().nth(8).unwrap().is_alphanumeric()
                 && input.chars().nth(8).unwrap() != '_'))
     {
         Ok((&input[8..], Token::Trailing))
     } else if input.len() >= 4
-        && input[..4].eq_ignore_ascii_case("BOTH")
+        && input
+            .get(..4)
+            .is_some_and(|s| s.eq_ignore_ascii_case("BOTH"))
         && (input.len() == 4
             || !input.chars().nth(4).unwrap_or(' ').is_alphanumeric()
                 && input.chars().nth(4).unwrap_or(' ') != '_')
     {
         Ok((&input[4..], Token::Both))
     } else if input.len() >= 3
-        && input[..3].eq_ignore_ascii_case("SET")
+        && input
+            .get(..3)
+            .is_some_and(|s| s.eq_ignore_ascii_case("SET"))
         && (input.len() == 3
             || (!input.chars().nth(3).unwrap().is_alphanumeric()
                 && input.chars().nth(3).unwrap() != '_'))
     {
         Ok((&input[3..], Token::Set))
     } else if input.len() >= 10
-        && input[..10].eq_ignore_ascii_case("DESCENDING")
+        && input
+            .get(..10)
+            .is_some_and(|s| s.eq_ignore_ascii_case("DESCENDING"))
         && (input.len() == 10
             || (!input.chars().nth(10).unwrap().is_alphanumeric()
                 && input.chars().nth(10).unwrap() != '_'))
     {
         Ok((&input[10..], Token::Descending))
     } else if input.len() >= 9
-        && input[..9].eq_ignore_ascii_case("INTERSECT")
+        && input
+            .get(..9)
+            .is_some_and(|s| s.eq_ignore_ascii_case("INTERSECT"))
         && (input.len() == 9
             || (!input.chars().nth(9).unwrap().is_alphanumeric()
                 && input.chars().nth(9).unwrap() != '_'))
     {
         Ok((&input[9..], Token::Intersect))
     } else if input.len() >= 9
-        && input[..9].eq_ignore_ascii_case("ASCENDING")
+        && input
+            .get(..9)
+            .is_some_and(|s| s.eq_ignore_ascii_case("ASCENDING"))
         && (input.len() == 9
             || (!input.chars().nth(9).unwrap().is_alphanumeric()
                 && input.chars().nth(9).unwrap() != '_'))
     {
         Ok((&input[9..], Token::Ascending))
     } else if input.len() >= 6
-        && input[..6].eq_ignore_ascii_case("EXCEPT")
+        && input
+            .get(..6)
+            .is_some_and(|s| s.eq_ignore_ascii_case("EXCEPT"))
         && (input.len() == 6
             || (!input.chars().nth(6).unwrap().is_alphanumeric()
                 && input.chars().nth(6).unwrap() != '_'))
     {
         Ok((&input[6..], Token::Except))
     } else if input.len() >= 5
-        && input[..5].eq_ignore_ascii_case("UNION")
+        && input
+            .get(..5)
+            .is_some_and(|s| s.eq_ignore_ascii_case("UNION"))
         && (input.len() == 5
             || (!input.chars().nth(5).unwrap().is_alphanumeric()
                 && input.chars().nth(5).unwrap() != '_'))
     {
         Ok((&input[5..], Token::Union))
     } else if input.len() >= 5
-        && input[..5].eq_ignore_ascii_case("NULLS")
+        && input
+            .get(..5)
+            .is_some_and(|s| s.eq_ignore_ascii_case("NULLS"))
         && (input.len() == 5
             || (!input.chars().nth(5).unwrap().is_alphanumeric()
                 && input.chars().nth(5).unwrap() != '_'))
@@ -1374,112 +1621,128 @@ fn simple_patterns(input: &str) -> IResult<&str, Token> {
     } else if is_keyword_match(input, "LAST") {
         Ok((&input[4..], Token::Last))
     } else if input.len() >= 4
-        && input[..4].eq_ignore_ascii_case("DESC")
+        && input
+            .get(..4)
+            .is_some_and(|s| s.eq_ignore_ascii_case("DESC"))
         && (input.len() == 4
             || (!input.chars().nth(4).unwrap().is_alphanumeric()
                 && input.chars().nth(4).unwrap() != '_'))
     {
         Ok((&input[4..], Token::Desc))
     } else if input.len() >= 3
-        && input[..3].eq_ignore_ascii_case("ASC")
+        && input
+            .get(..3)
+            .is_some_and(|s| s.eq_ignore_ascii_case("ASC"))
         && (input.len() == 3
             || (!input.chars().nth(3).unwrap().is_alphanumeric()
                 && input.chars().nth(3).unwrap() != '_'))
     {
         Ok((&input[3..], Token::Asc))
     } else if input.len() >= 3
-        && input[..3].eq_ignore_ascii_case("ALL")
+        && input
+            .get(..3)
+            .is_some_and(|s| s.eq_ignore_ascii_case("ALL"))
         && (input.len() == 3
             || (!input.chars().nth(3).unwrap().is_alphanumeric()
                 && input.chars().nth(3).unwrap() != '_'))
     {
         Ok((&input[3..], Token::All))
     } else if input.len() >= 3
-        && input[..3].eq_ignore_ascii_case("ANY")
+        && input
+            .get(..3)
+            .is_some_and(|s| s.eq_ignore_ascii_case("ANY"))
         && (input.len() == 3
             || (!input.chars().nth(3).unwrap().is_alphanumeric()
                 && input.chars().nth(3).unwrap() != '_'))
     {
         Ok((&input[3..], Token::Any))
     } else if input.len() >= 4
-        && input[..4].eq_ignore_ascii_case("SOME")
+        && input
+            .get(..4)
+            .is_some_and(|s| s.eq_ignore_ascii_case("SOME"))
         && (input.len() == 4
             || (!input.chars().nth(4).unwrap().is_alphanumeric()
                 && input.chars().nth(4).unwrap() != '_'))
     {
         Ok((&input[4..], Token::Some))
     } else if input.len() >= 3
-        && input[..3].eq_ignore_ascii_case("AND")
+        && input
+            .get(..3)
+            .is_some_and(|s| s.eq_ignore_ascii_case("AND"))
         && (input.len() == 3
             || (!input.chars().nth(3).unwrap().is_alphanumeric()
                 && input.chars().nth(3).unwrap() != '_'))
     {
         Ok((&input[3..], Token::And))
     } else if input.len() >= 3
-        && input[..3].eq_ignore_ascii_case("NOT")
+        && input
+            .get(..3)
+            .is_some_and(|s| s.eq_ignore_ascii_case("NOT"))
         && (input.len() == 3
             || (!input.chars().nth(3).unwrap().is_alphanumeric()
                 && input.chars().nth(3).unwrap() != '_'))
     {
         Ok((&input[3..], Token::Not))
     } else if input.len() >= 3
-        && input[..3].eq_ignore_ascii_case("XOR")
+        && input
+            .get(..3)
+            .is_some_and(|s| s.eq_ignore_ascii_case("XOR"))
         && (input.len() == 3
             || (!input.chars().nth(3).unwrap().is_alphanumeric()
                 && input.chars().nth(3).unwrap() != '_'))
     {
         Ok((&input[3..], Token::Xor))
     } else if input.len() >= 2
-        && input[..2].eq_ignore_ascii_case("BY")
+        && input.get(..2).is_some_and(|s| s.eq_ignore_ascii_case("BY"))
         && (input.len() == 2
             || (!input.chars().nth(2).unwrap().is_alphanumeric()
                 && input.chars().nth(2).unwrap() != '_'))
     {
         Ok((&input[2..], Token::By))
     } else if input.len() >= 2
-        && input[..2].eq_ignore_ascii_case("ON")
+        && input.get(..2).is_some_and(|s| s.eq_ignore_ascii_case("ON"))
         && (input.len() == 2
             || (!input.chars().nth(2).unwrap().is_alphanumeric()
                 && input.chars().nth(2).unwrap() != '_'))
     {
         Ok((&input[2..], Token::On))
     } else if input.len() >= 2
-        && input[..2].eq_ignore_ascii_case("AS")
+        && input.get(..2).is_some_and(|s| s.eq_ignore_ascii_case("AS"))
         && (input.len() == 2
             || (!input.chars().nth(2).unwrap().is_alphanumeric()
                 && input.chars().nth(2).unwrap() != '_'))
     {
         Ok((&input[2..], Token::As))
     } else if input.len() >= 2
-        && input[..2].eq_ignore_ascii_case("OR")
+        && input.get(..2).is_some_and(|s| s.eq_ignore_ascii_case("OR"))
         && (input.len() == 2
             || (!input.chars().nth(2).unwrap().is_alphanumeric()
                 && input.chars().nth(2).unwrap() != '_'))
     {
         Ok((&input[2..], Token::Or))
     } else if input.len() >= 2
-        && input[..2].eq_ignore_ascii_case("OF")
+        && input.get(..2).is_some_and(|s| s.eq_ignore_ascii_case("OF"))
         && (input.len() == 2
             || (!input.chars().nth(2).unwrap().is_alphanumeric()
                 && input.chars().nth(2).unwrap() != '_'))
     {
         Ok((&input[2..], Token::Of))
     } else if input.len() >= 2
-        && input[..2].eq_ignore_ascii_case("IS")
+        && input.get(..2).is_some_and(|s| s.eq_ignore_ascii_case("IS"))
         && (input.len() == 2
             || (!input.chars().nth(2).unwrap_or(' ').is_alphanumeric()
                 && input.chars().nth(2).unwrap_or(' ') != '_'))
     {
         Ok((&input[2..], Token::Is))
     } else if input.len() >= 2
-        && input[..2].eq_ignore_ascii_case("IF")
+        && input.get(..2).is_some_and(|s| s.eq_ignore_ascii_case("IF"))
         && (input.len() == 2
             || (!input.chars().nth(2).unwrap().is_alphanumeric()
                 && input.chars().nth(2).unwrap() != '_'))
     {
         Ok((&input[2..], Token::If))
     } else if input.len() >= 2
-        && input[..2].eq_ignore_ascii_case("IN")
+        && input.get(..2).is_some_and(|s| s.eq_ignore_ascii_case("IN"))
         && (input.len() == 2
             || (!input.chars().nth(2).unwrap().is_alphanumeric()
                 && input.chars().nth(2).unwrap() != '_'))
@@ -1488,21 +1751,27 @@ fn simple_patterns(input: &str) -> IResult<&str, Token> {
     }
     // Boolean literals
     else if input.len() >= 4
-        && input[..4].eq_ignore_ascii_case("true")
+        && input
+            .get(..4)
+            .is_some_and(|s| s.eq_ignore_ascii_case("true"))
         && (input.len() == 4
             || (!input.chars().nth(4).unwrap().is_alphanumeric()
                 && input.chars().nth(4).unwrap() != '_'))
     {
         Ok((&input[4..], Token::Boolean(true)))
     } else if input.len() >= 5
-        && input[..5].eq_ignore_ascii_case("false")
+        && input
+            .get(..5)
+            .is_some_and(|s| s.eq_ignore_ascii_case("false"))
         && (input.len() == 5
             || (!input.chars().nth(5).unwrap().is_alphanumeric()
                 && input.chars().nth(5).unwrap() != '_'))
     {
         Ok((&input[5..], Token::Boolean(false)))
     } else if input.len() >= 4
-        && input[..4].eq_ignore_ascii_case("NULL")
+        && input
+            .get(..4)
+            .is_some_and(|s| s.eq_ignore_ascii_case("NULL"))
         && (input.len() == 4
             || (!input.chars().nth(4).unwrap().is_alphanumeric()
                 && input.chars().nth(4).unwrap() != '_'))
@@ -1863,4 +2132,26 @@ pub fn tokenize(input: &str) -> Result<Vec<Token>, String> {
     lexer.tokenize()
 }
 
+/// Byte ranges and names of the `$name` tokens in `input`
+///
+/// Uses the same rules as `tokenize`, so `$` inside string literals, delimited
+/// identifiers and comments is not reported.
+pub fn variable_spans(input: &str) -> Result<Vec<(std::ops::Range<usize>, String)>, String> {
+    let mut remaining = input;
+    let mut spans = Vec::new();
+    while !remaining.is_empty() {
+        let (next_remaining, token) =
+            token(remaining).map_err(|e| format!("Lexer error: {:?}", e))?;
+        if next_remaining.len() == remaining.len() {
+            return Err(format!("Lexer made no progress at '{}'", remaining));
+        }
+        if let Token::Variable(name) = token {
+            let start = input.len() - remaining.len();
+            spans.push((start..input.len() - next_remaining.len(), name));
+        }
+        remaining = next_remaining;
+    }
+    Ok(spans)
+}
+
 // Removed unused test_lexer function
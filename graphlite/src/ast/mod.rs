@@ -7,6 +7,7 @@
 mod ast;
 pub use ast::*;
 pub mod lexer;
+pub mod parameters;
 pub mod parser;
 pub mod pretty_printer;
 pub mod validator;
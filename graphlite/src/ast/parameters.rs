@@ -0,0 +1,174 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! Query parameter binding
+//!
+//! `bind_parameters` replaces every `$name` in a query with a GQL literal for
+//! the value bound to `name`. Parameters are found with the lexer, so a `$` in
+//! a string literal, delimited identifier or comment is left alone, and each
+//! value is rendered as a single literal: a string value can never close its
+//! quotes and inject GQL.
+//!
+//! String literals keep backslashes as written, so a string is quoted with
+//! whichever quote character it does not contain. Strings containing both are
+//! rejected rather than altered.
+
+use super::lexer::variable_spans;
+use crate::storage::Value;
+use std::collections::HashMap;
+
+/// Replace the `$name` parameters of `query` with literals for `params`
+///
+/// Every parameter in the query must have a value; values that are not
+/// referenced are ignored.
+pub fn bind_parameters(query: &str, params: &HashMap<String, Value>) -> Result<String, String> {
+    let mut bound = String::with_capacity(query.len());
+    let mut copied = 0;
+    for (span, name) in variable_spans(query)? {
+        let value = params
+            .get(&name)
+            .ok_or_else(|| format!("No value bound for parameter ${}", name))?;
+        let literal = literal(value).map_err(|e| format!("Parameter ${}: {}", name, e))?;
+        bound.push_str(&query[copied..span.start]);
+        // Keep a negative number from forming `--` with a preceding minus
+        if literal.starts_with('-') && bound.ends_with('-') {
+            bound.push(' ');
+        }
+        bound.push_str(&literal);
+        copied = span.end;
+    }
+    bound.push_str(&query[copied..]);
+    Ok(bound)
+}
+
+/// Render a value as a GQL literal
+fn literal(value: &Value) -> Result<String, String> {
+    match value {
+        Value::Null => Ok("NULL".to_string()),
+        Value::Boolean(b) => Ok(if *b { "TRUE" } else { "FALSE" }.to_string()),
+        Value::Number(n) => number_literal(*n),
+        Value::String(s) => string_literal(s),
+        Value::DateTime(dt) | Value::DateTimeWithNamedTz(_, dt) => {
+            Ok(format!("DATETIME('{}')", dt.to_rfc3339()))
+        }
+        Value::DateTimeWithFixedOffset(dt) => Ok(format!("DATETIME('{}')", dt.to_rfc3339())),
+        Value::Array(items) | Value::List(items) => {
+            let items = items.iter().map(literal).collect::<Result<Vec<_>, _>>()?;
+            Ok(format!("[{}]", items.join(", ")))
+        }
+        Value::Vector(items) => {
+            let items = items
+                .iter()
+                .map(|n| number_literal(f64::from(*n)))
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(format!("[{}]", items.join(", ")))
+        }
+        Value::Json(document) => {
+            // Apostrophes only occur inside JSON strings, where an escape keeps them out
+            // of the way of the quotes
+            let text = document.to_string().replace('\'', "\\u0027");
+            Ok(format!("JSON({})", string_literal(&text)?))
+        }
+        other => Err(format!(
+            "{} values cannot be bound as parameters",
+            other.type_name()
+        )),
+    }
+}
+
+fn number_literal(n: f64) -> Result<String, String> {
+    if !n.is_finite() {
+        return Err(format!("{} has no GQL literal", n));
+    }
+    // Integers beyond 2^53 are rendered as floats so they cannot overflow i64
+    if n.fract() == 0.0 && n.abs() < 9_007_199_254_740_992.0 {
+        return Ok(format!("{}", n as i64));
+    }
+    let text = n.to_string();
+    Ok(if text.contains('.') {
+        text
+    } else {
+        format!("{}.0", text)
+    })
+}
+
+fn string_literal(s: &str) -> Result<String, String> {
+    ['\'', '"']
+        .into_iter()
+        .find(|quote| quotable(s, *quote))
+        .map(|quote| format!("{}{}{}", quote, s, quote))
+        .ok_or_else(|| {
+            "strings containing both quote characters cannot be bound as parameters".to_string()
+        })
+}
+
+/// Whether the lexer reads `s` back unchanged between `quote` characters
+fn quotable(s: &str, quote: char) -> bool {
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            // The lexer keeps the escaped character; a trailing backslash would
+            // escape the closing quote
+            if chars.next().is_none() {
+                return false;
+            }
+        } else if c == quote {
+            return false;
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(values: &[(&str, Value)]) -> HashMap<String, Value> {
+        values
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_bind_parameters_renders_literals() {
+        let bound = bind_parameters(
+            "MATCH (p:Person {id: $id}) WHERE p.name = $name AND p.score > 0-$min RETURN p, '$id'",
+            &params(&[
+                ("id", Value::Number(42.0)),
+                ("name", Value::String("O'Brien".to_string())),
+                ("min", Value::Number(-1.5)),
+            ]),
+        )
+        .unwrap();
+        assert_eq!(
+            bound,
+            "MATCH (p:Person {id: 42}) WHERE p.name = \"O'Brien\" AND p.score > 0- -1.5 RETURN p, '$id'"
+        );
+
+        let bound = bind_parameters(
+            "RETURN $tags, $flag, $missing_ok",
+            &params(&[
+                (
+                    "tags",
+                    Value::List(vec![Value::String("a".to_string()), Value::Null]),
+                ),
+                ("flag", Value::Boolean(true)),
+                ("missing_ok", Value::Number(1e20)),
+            ]),
+        )
+        .unwrap();
+        assert_eq!(bound, "RETURN ['a', NULL], TRUE, 100000000000000000000.0");
+    }
+
+    #[test]
+    fn test_bind_parameters_rejects_unsafe_values() {
+        let query = "MATCH (p {name: $name}) RETURN p";
+        let quotes = params(&[("name", Value::String("a'b\"c".to_string()))]);
+        assert!(bind_parameters(query, &quotes).is_err());
+        let trailing_backslash = params(&[("name", Value::String("ab\\".to_string()))]);
+        assert!(bind_parameters(query, &trailing_backslash).is_err());
+        let error = bind_parameters(query, &HashMap::new()).unwrap_err();
+        assert!(error.contains("$name"), "{}", error);
+    }
+}
@@ -6,9 +6,9 @@
 use log::debug;
 use nom::{
     branch::alt,
-    combinator::{map, opt, success, value, verify},
-    multi::{many0, many1, separated_list1},
-    sequence::{delimited, pair, preceded, terminated, tuple},
+    combinator::{map, not, opt, success, value, verify},
+    multi::{many0, many1, separated_list0, separated_list1},
+    sequence::{delimited, pair, preceded, separated_pair, terminated, tuple},
     IResult,
 };
 
@@ -702,8 +702,16 @@ fn mutation_pipeline(tokens: &[Token]) -> IResult<&[Token], Query> {
     );
 
     // Try parsing: segments (with possible UNWIND in last segment) + mutation
+    // Mutation pipelines run their first segment only, so OPTIONAL MATCH is not accepted here
     let parse_without_unwind = map(
-        tuple((many1(query_segment), final_mutation)),
+        tuple((
+            verify(many1(query_segment), |segments: &Vec<QuerySegment>| {
+                segments
+                    .iter()
+                    .all(|segment| !segment.match_clause.optional)
+            }),
+            final_mutation,
+        )),
         |(segments, final_mutation)| {
             Query::MutationPipeline(MutationPipeline {
                 segments,
@@ -867,11 +875,11 @@ fn unwind_clause(tokens: &[Token]) -> IResult<&[Token], UnwindClause> {
     )(tokens)
 }
 
-/// Parse a query segment: MATCH [WHERE] [WITH] [WHERE] [ORDER BY] [LIMIT] [UNWIND ... AS ...] [WHERE]
+/// Parse a query segment: [OPTIONAL] MATCH [WHERE] [WITH] [WHERE] [ORDER BY] [LIMIT] [UNWIND ... AS ...] [WHERE]
 fn query_segment(tokens: &[Token]) -> IResult<&[Token], QuerySegment> {
     map(
         tuple((
-            match_clause,
+            segment_match_clause,
             opt(where_clause),
             opt(with_clause),
             opt(unwind_clause),
@@ -1158,22 +1166,42 @@ fn match_clause(tokens: &[Token]) -> IResult<&[Token], MatchClause> {
             patterns.extend(additional_patterns.into_iter().map(|(_, pattern)| pattern));
             MatchClause {
                 patterns,
+                optional: false,
                 location: Location::default(),
             }
         },
     )(tokens)
 }
 
-/// Parse path pattern: [identifier =] [path_type] node (edge node)*
+/// Parse the OPTIONAL of OPTIONAL MATCH
+fn optional_keyword(tokens: &[Token]) -> IResult<&[Token], String> {
+    // OPTIONAL is not a reserved word, since graph types use it as a property qualifier
+    verify(identifier, |id: &str| id.eq_ignore_ascii_case("OPTIONAL"))(tokens)
+}
+
+/// Parse a MATCH clause of a query segment: [OPTIONAL] MATCH patterns
+fn segment_match_clause(tokens: &[Token]) -> IResult<&[Token], MatchClause> {
+    map(
+        pair(opt(optional_keyword), match_clause),
+        |(optional, mut match_clause)| {
+            match_clause.optional = optional.is_some();
+            match_clause
+        },
+    )(tokens)
+}
+
+/// Parse path pattern: [identifier =] [search] [path_type] node (edge node)*
 fn path_pattern(tokens: &[Token]) -> IResult<&[Token], PathPattern> {
     map(
         tuple((
             opt(tuple((identifier, expect_token(Token::Equal)))),
+            opt(path_search_prefix),
             opt(path_type_keywords),
             many1(pattern_element),
         )),
-        |(assignment, path_type, elements)| PathPattern {
+        |(assignment, search, path_type, elements)| PathPattern {
             assignment: assignment.map(|(id, _)| id),
+            search,
             path_type,
             elements,
             location: Location::default(),
@@ -1181,6 +1209,21 @@ fn path_pattern(tokens: &[Token]) -> IResult<&[Token], PathPattern> {
     )(tokens)
 }
 
+/// Parse path search prefix: ANY SHORTEST | ALL SHORTEST
+fn path_search_prefix(tokens: &[Token]) -> IResult<&[Token], PathSearch> {
+    // SHORTEST is not a reserved word
+    alt((
+        map(
+            pair(expect_token(Token::Any), expect_identifier("SHORTEST")),
+            |_| PathSearch::AnyShortest,
+        ),
+        map(
+            pair(expect_token(Token::All), expect_identifier("SHORTEST")),
+            |_| PathSearch::AllShortest,
+        ),
+    ))(tokens)
+}
+
 /// Parse path type keywords: WALK | TRAIL | SIMPLE PATH | ACYCLIC PATH
 fn path_type_keywords(tokens: &[Token]) -> IResult<&[Token], PathType> {
     alt((
@@ -1201,11 +1244,15 @@ fn path_type_keywords(tokens: &[Token]) -> IResult<&[Token], PathType> {
     ))(tokens)
 }
 
-/// Parse path quantifier: {n,m} | {n,} | {,m} | {n} | ? (per ISO GQL grammar)
+/// Parse path quantifier: {n,m} | {n,} | {,m} | {n} | ? | + | * (per ISO GQL grammar)
 fn path_quantifier(tokens: &[Token]) -> IResult<&[Token], PathQuantifier> {
     alt((
         // Optional pattern: ?
         map(expect_token(Token::Question), |_| PathQuantifier::Optional),
+        // One or more: +
+        map(expect_token(Token::Plus), |_| PathQuantifier::AtLeast(1)),
+        // Zero or more: *
+        map(expect_token(Token::Star), |_| PathQuantifier::AtLeast(0)),
         // Braced quantifiers: {n,m} | {n,} | {,m} | {n}
         delimited(
             expect_token(Token::LeftBrace),
@@ -1236,6 +1283,33 @@ fn path_quantifier(tokens: &[Token]) -> IResult<&[Token], PathQuantifier> {
     ))(tokens)
 }
 
+/// Parse variable-length quantifier inside edge brackets: * | *n | *n..m | *n.. | *..m
+///
+/// A missing lower bound is 1, as in `-[:KNOWS*..3]->`; `*` alone is one or more hops.
+fn star_quantifier(tokens: &[Token]) -> IResult<&[Token], PathQuantifier> {
+    map(
+        preceded(
+            expect_token(Token::Star),
+            pair(
+                opt(integer_literal),
+                opt(preceded(
+                    pair(expect_token(Token::Dot), expect_token(Token::Dot)),
+                    opt(integer_literal),
+                )),
+            ),
+        ),
+        |(min, range)| match (min, range) {
+            (None, None) => PathQuantifier::AtLeast(1),
+            (Some(n), None) => PathQuantifier::Exact(n as u32),
+            (min, Some(None)) => PathQuantifier::AtLeast(min.unwrap_or(1) as u32),
+            (min, Some(Some(max))) => PathQuantifier::Range {
+                min: min.unwrap_or(1) as u32,
+                max: max as u32,
+            },
+        },
+    )(tokens)
+}
+
 /// Parse pattern element (node or edge)
 fn pattern_element(tokens: &[Token]) -> IResult<&[Token], PatternElement> {
     alt((
@@ -1290,12 +1364,24 @@ fn edge_pattern(tokens: &[Token]) -> IResult<&[Token], Edge> {
                 // Allow empty patterns like -[]->
                 value((None, None), success(())),
             )),
+            opt(star_quantifier),
             opt(property_map),
             expect_token(Token::RightBracket),
             opt(path_quantifier), // ISO GQL: quantifier after ] but before final direction
             edge_direction,
+            opt(path_quantifier), // or after the whole edge pattern: -[:KNOWS]->{1,3}
         )),
-        |(left_dir, _, (identifier, labels), properties, _, quantifier, right_dir)| {
+        |(
+            left_dir,
+            _,
+            (identifier, labels),
+            star,
+            properties,
+            _,
+            quantifier,
+            right_dir,
+            trailing_quantifier,
+        )| {
             let direction = match (left_dir, right_dir) {
                 (EdgeDirection::Incoming, EdgeDirection::Outgoing) => EdgeDirection::Both,
                 (EdgeDirection::Incoming, _) => EdgeDirection::Incoming,
@@ -1307,7 +1393,7 @@ fn edge_pattern(tokens: &[Token]) -> IResult<&[Token], Edge> {
                 labels: labels.unwrap_or_default(),
                 properties,
                 direction,
-                quantifier, // ISO GQL: quantifier parsed after ] but before final direction
+                quantifier: star.or(quantifier).or(trailing_quantifier),
                 location: Location::default(),
             }
         },
@@ -1521,25 +1607,129 @@ fn order_item(tokens: &[Token]) -> IResult<&[Token], OrderItem> {
 }
 
 /// Parse GROUP BY clause: GROUP BY expression, expression, ...
+///
+/// Elements may also be `ROLLUP (expression, ...)` or
+/// `GROUPING SETS ((expression, ...), ...)`. The grouping sets of a clause are
+/// the cross product of the sets of its elements, so `GROUP BY a, ROLLUP (b)`
+/// groups by `(a, b)` and `(a)`.
 fn group_clause(tokens: &[Token]) -> IResult<&[Token], GroupClause> {
     map(
         tuple((
             expect_token(Token::Group),
             expect_token(Token::By),
-            expression,
-            many0(tuple((expect_token(Token::Comma), expression))),
+            grouping_element,
+            many0(tuple((expect_token(Token::Comma), grouping_element))),
         )),
-        |(_, _, first_expr, additional_exprs)| {
-            let mut expressions = vec![first_expr];
-            expressions.extend(additional_exprs.into_iter().map(|(_, expr)| expr));
+        |(_, _, first_element, additional_elements)| {
+            let elements: Vec<GroupingElement> = std::iter::once(first_element)
+                .chain(additional_elements.into_iter().map(|(_, element)| element))
+                .collect();
+
+            let mut expressions: Vec<Expression> = Vec::new();
+            let mut key_index = |expr: Expression| {
+                let key = format!("{:?}", expr);
+                match expressions.iter().position(|e| format!("{:?}", e) == key) {
+                    Some(index) => index,
+                    None => {
+                        expressions.push(expr);
+                        expressions.len() - 1
+                    }
+                }
+            };
+
+            let mut has_sets = false;
+            let mut grouping_sets: Vec<Vec<usize>> = vec![Vec::new()];
+            for element in elements {
+                let element_sets = match element {
+                    GroupingElement::Expression(expr) => vec![vec![expr]],
+                    GroupingElement::Sets(sets) => {
+                        has_sets = true;
+                        sets
+                    }
+                };
+                let element_sets: Vec<Vec<usize>> = element_sets
+                    .into_iter()
+                    .map(|set| set.into_iter().map(&mut key_index).collect())
+                    .collect();
+                grouping_sets = grouping_sets
+                    .iter()
+                    .flat_map(|prefix| {
+                        element_sets.iter().map(move |set| {
+                            let mut combined = prefix.clone();
+                            for index in set {
+                                if !combined.contains(index) {
+                                    combined.push(*index);
+                                }
+                            }
+                            combined
+                        })
+                    })
+                    .collect();
+            }
+
             GroupClause {
                 expressions,
+                grouping_sets: has_sets.then_some(grouping_sets),
                 location: Location::default(),
             }
         },
     )(tokens)
 }
 
+/// One element of a GROUP BY clause
+enum GroupingElement {
+    Expression(Expression),
+    Sets(Vec<Vec<Expression>>),
+}
+
+/// Parse a GROUP BY element: ROLLUP (...), GROUPING SETS (...) or an expression
+fn grouping_element(tokens: &[Token]) -> IResult<&[Token], GroupingElement> {
+    alt((
+        map(
+            preceded(
+                expect_identifier("ROLLUP"),
+                delimited(
+                    expect_token(Token::LeftParen),
+                    separated_list1(expect_token(Token::Comma), expression),
+                    expect_token(Token::RightParen),
+                ),
+            ),
+            |keys| {
+                // ROLLUP (a, b) groups by (a, b), (a) and ()
+                let sets = (0..=keys.len())
+                    .rev()
+                    .map(|len| keys[..len].to_vec())
+                    .collect();
+                GroupingElement::Sets(sets)
+            },
+        ),
+        map(
+            preceded(
+                tuple((expect_identifier("GROUPING"), expect_identifier("SETS"))),
+                delimited(
+                    expect_token(Token::LeftParen),
+                    separated_list1(expect_token(Token::Comma), grouping_set),
+                    expect_token(Token::RightParen),
+                ),
+            ),
+            GroupingElement::Sets,
+        ),
+        map(expression, GroupingElement::Expression),
+    ))(tokens)
+}
+
+/// Parse one set of GROUPING SETS: (expression, ...), () or an expression
+fn grouping_set(tokens: &[Token]) -> IResult<&[Token], Vec<Expression>> {
+    alt((
+        delimited(
+            expect_token(Token::LeftParen),
+            separated_list0(expect_token(Token::Comma), expression),
+            expect_token(Token::RightParen),
+        ),
+        map(expression, |expr| vec![expr]),
+    ))(tokens)
+}
+
 /// Parse HAVING clause: HAVING expression
 fn having_clause(tokens: &[Token]) -> IResult<&[Token], HavingClause> {
     map(
@@ -2071,6 +2261,9 @@ fn primary_expression(tokens: &[Token]) -> IResult<&[Token], Expression> {
         map(not_exists_subquery, Expression::NotExistsSubquery),
         // EXISTS subquery must be checked before general parenthesized expressions
         map(exists_subquery, Expression::ExistsSubquery),
+        // COUNT { ... } and COLLECT { ... } must be checked before COUNT(...) and COLLECT(...) calls
+        map(count_subquery, Expression::CountSubquery),
+        map(collect_subquery, Expression::CollectSubquery),
         // Try pattern expression first (for WHERE clause patterns)
         map(pattern_expression, Expression::Pattern),
         // Try subquery first, then fall back to parenthesized expression
@@ -2121,6 +2314,7 @@ fn expression_list(tokens: &[Token]) -> IResult<&[Token], Vec<Expression>> {
 /// Parse function call: name(args...) using ISO GQL compliant token-based parsing
 fn function_call(tokens: &[Token]) -> IResult<&[Token], FunctionCall> {
     // ISO GQL: <function-call> ::= <identifier> "(" [DISTINCT|ALL] [<expression> ("," <expression>)*] ")"
+    // Aggregates may end their arguments with [ORDER BY <sort-items>] [LIMIT <count>]
 
     // Parse function name (identifier)
     let (tokens, name) = identifier(tokens)?;
@@ -2161,7 +2355,7 @@ fn function_call(tokens: &[Token]) -> IResult<&[Token], FunctionCall> {
                         remaining = &remaining[1..]; // consume comma
                         continue;
                     }
-                    Some(Token::RightParen) => break,
+                    Some(Token::RightParen | Token::Order | Token::Limit) => break,
                     _ => {
                         return Err(nom::Err::Error(nom::error::Error::new(
                             remaining,
@@ -2173,6 +2367,26 @@ fn function_call(tokens: &[Token]) -> IResult<&[Token], FunctionCall> {
         }
     }
 
+    // Parse optional ORDER BY and LIMIT of COLLECT, the only aggregate that
+    // depends on the order of its input: COLLECT(x ORDER BY x.date DESC LIMIT 5)
+    let (remaining, order_clause, limit_clause) = if name == "COLLECT" {
+        let (remaining, order_clause) = opt(order_clause)(remaining)?;
+        let (remaining, limit_clause) = opt(limit_clause)(remaining)?;
+        (
+            remaining,
+            order_clause.map(Box::new),
+            limit_clause.map(Box::new),
+        )
+    } else if matches!(remaining.first(), Some(Token::Order | Token::Limit)) {
+        // Do not backtrack into reading the function name as a variable
+        return Err(nom::Err::Failure(nom::error::Error::new(
+            remaining,
+            nom::error::ErrorKind::Verify,
+        )));
+    } else {
+        (remaining, None, None)
+    };
+
     // Parse closing parenthesis
     let (remaining, _) = expect_token(Token::RightParen)(remaining)?;
 
@@ -2182,6 +2396,8 @@ fn function_call(tokens: &[Token]) -> IResult<&[Token], FunctionCall> {
             name,
             distinct,
             arguments,
+            order_clause,
+            limit_clause,
             location: Location::default(),
         },
     ))
@@ -2277,6 +2493,8 @@ fn trim_function_call(tokens: &[Token]) -> IResult<&[Token], FunctionCall> {
             name: "TRIM".to_string(),
             distinct: DistinctQualifier::None,
             arguments,
+            order_clause: None,
+            limit_clause: None,
             location: Location::default(),
         },
     ))
@@ -2611,6 +2829,7 @@ fn catalog_statement(tokens: &[Token]) -> IResult<&[Token], CatalogStatement> {
         map(drop_graph_statement, CatalogStatement::DropGraph),
         map(truncate_graph_statement, CatalogStatement::TruncateGraph),
         map(clear_graph_statement, CatalogStatement::ClearGraph),
+        map(alter_graph_statement, CatalogStatement::AlterGraph),
         map(create_user_statement, CatalogStatement::CreateUser),
         map(drop_user_statement, CatalogStatement::DropUser),
         map(create_role_statement, CatalogStatement::CreateRole),
@@ -2726,13 +2945,25 @@ fn create_graph_statement(tokens: &[Token]) -> IResult<&[Token], CreateGraphStat
                 expect_token(Token::Exists),
             ))),
             catalog_path,
+            opt(preceded(expect_token(Token::Typed), catalog_path)),
             opt(graph_type_spec),
             opt(tuple((expect_token(Token::As), query))),
         )),
-        |(_, or_replace, _, _, if_not_exists, graph_path, graph_type_spec, as_query)| {
+        |(
+            _,
+            or_replace,
+            _,
+            _,
+            if_not_exists,
+            graph_path,
+            graph_type_ref,
+            graph_type_spec,
+            as_query,
+        )| {
             CreateGraphStatement {
                 graph_path,
                 graph_type_spec,
+                graph_type_ref,
                 if_not_exists: if_not_exists.is_some(),
                 or_replace: or_replace.is_some(),
                 as_query: as_query.map(|(_, query)| Box::new(query)),
@@ -2813,6 +3044,73 @@ fn clear_graph_statement(tokens: &[Token]) -> IResult<&[Token], ClearGraphStatem
     )(tokens)
 }
 
+/// One item of the SET list of ALTER GRAPH
+enum AlterGraphItem {
+    Description(String),
+    Tags(Vec<String>),
+    Owner(String),
+}
+
+/// Parse ALTER GRAPH statement: ALTER GRAPH path SET DESCRIPTION '...', TAGS ['a', 'b'], OWNER '...'
+fn alter_graph_statement(tokens: &[Token]) -> IResult<&[Token], AlterGraphStatement> {
+    let (tokens, (_, _, graph_path, _, items)) = tuple((
+        expect_token(Token::Alter),
+        expect_token(Token::Graph),
+        catalog_path,
+        expect_token(Token::Set),
+        separated_list1(expect_token(Token::Comma), alter_graph_item),
+    ))(tokens)?;
+
+    let mut statement = AlterGraphStatement {
+        graph_path,
+        description: None,
+        tags: None,
+        owner: None,
+        location: Location::default(),
+    };
+    for item in items {
+        match item {
+            AlterGraphItem::Description(description) => statement.description = Some(description),
+            AlterGraphItem::Tags(tags) => statement.tags = Some(tags),
+            AlterGraphItem::Owner(owner) => statement.owner = Some(owner),
+        }
+    }
+    Ok((tokens, statement))
+}
+
+/// Parse DESCRIPTION '...', TAGS [...] or OWNER '...'
+fn alter_graph_item(tokens: &[Token]) -> IResult<&[Token], AlterGraphItem> {
+    alt((
+        map(
+            preceded(expect_token(Token::Description), string_literal),
+            AlterGraphItem::Description,
+        ),
+        map(
+            preceded(expect_identifier("TAGS"), string_list),
+            AlterGraphItem::Tags,
+        ),
+        map(
+            preceded(expect_identifier("OWNER"), string_literal),
+            AlterGraphItem::Owner,
+        ),
+    ))(tokens)
+}
+
+/// Parse a list of string literals: ['a', 'b']
+fn string_list(tokens: &[Token]) -> IResult<&[Token], Vec<String>> {
+    // The lexer turns `[]` into an empty vector literal
+    if let Some((Token::Vector(values), rest)) = tokens.split_first() {
+        if values.is_empty() {
+            return Ok((rest, Vec::new()));
+        }
+    }
+    delimited(
+        expect_token(Token::LeftBracket),
+        separated_list0(expect_token(Token::Comma), string_literal),
+        expect_token(Token::RightBracket),
+    )(tokens)
+}
+
 /// Parse CREATE GRAPH TYPE statement
 fn create_graph_type_statement(tokens: &[Token]) -> IResult<&[Token], CreateGraphTypeStatement> {
     map(
@@ -3117,15 +3415,36 @@ fn property_type_list(tokens: &[Token]) -> IResult<&[Token], PropertyTypeList> {
     )(tokens)
 }
 
-/// Parse property type declaration: name type_spec
+/// Parse property type declaration: name type_spec [REFERENCES label(key)]
 fn property_type_decl(tokens: &[Token]) -> IResult<&[Token], PropertyTypeDecl> {
-    map(tuple((identifier, type_spec)), |(name, type_spec)| {
-        PropertyTypeDecl {
+    map(
+        tuple((identifier, type_spec, opt(property_reference))),
+        |(name, type_spec, references)| PropertyTypeDecl {
             name,
             type_spec,
+            references,
             location: Location::default(),
-        }
-    })(tokens)
+        },
+    )(tokens)
+}
+
+/// Parse the target of a reference property: REFERENCES label(key)
+fn property_reference(tokens: &[Token]) -> IResult<&[Token], PropertyReference> {
+    // REFERENCES is not a reserved word, like VECTOR below
+    map(
+        preceded(
+            verify(identifier, |id: &str| id.eq_ignore_ascii_case("REFERENCES")),
+            tuple((
+                identifier,
+                delimited(
+                    expect_token(Token::LeftParen),
+                    identifier,
+                    expect_token(Token::RightParen),
+                ),
+            )),
+        ),
+        |(label, key)| PropertyReference { label, key },
+    )(tokens)
 }
 
 /// Parse type specification (simplified for now)
@@ -3157,15 +3476,23 @@ fn type_spec(tokens: &[Token]) -> IResult<&[Token], TypeSpec> {
             verify(identifier, |id: &str| id.eq_ignore_ascii_case("VECTOR")),
             |_| TypeSpec::Vector { dimension: None },
         ),
+        map(
+            verify(identifier, |id: &str| id.eq_ignore_ascii_case("JSON")),
+            |_| TypeSpec::Json,
+        ),
     ))(tokens)
 }
 
-/// Parse session statement: SESSION SET/RESET/CLOSE
+/// Parse session statement: SESSION SET/RESET/CLOSE, SHOW CURRENT GRAPH
 fn session_statement(tokens: &[Token]) -> IResult<&[Token], SessionStatement> {
     alt((
         map(session_set_statement, SessionStatement::Set),
         map(session_reset_statement, SessionStatement::Reset),
         map(session_close_statement, SessionStatement::Close),
+        map(
+            show_current_graph_statement,
+            SessionStatement::ShowCurrentGraph,
+        ),
     ))(tokens)
 }
 
@@ -3343,6 +3670,21 @@ fn session_close_statement(tokens: &[Token]) -> IResult<&[Token], SessionCloseSt
     )(tokens)
 }
 
+/// Parse SHOW CURRENT [PROPERTY] GRAPH statement
+fn show_current_graph_statement(tokens: &[Token]) -> IResult<&[Token], ShowCurrentGraphStatement> {
+    map(
+        tuple((
+            expect_token(Token::Show),
+            expect_token(Token::Current),
+            opt(expect_token(Token::Property)),
+            expect_token(Token::Graph),
+        )),
+        |_| ShowCurrentGraphStatement {
+            location: Location::default(),
+        },
+    )(tokens)
+}
+
 /// Parse graph expression for session context
 fn graph_expression(tokens: &[Token]) -> IResult<&[Token], GraphExpression> {
     log::debug!(
@@ -3912,7 +4254,7 @@ fn searched_when_branch(tokens: &[Token]) -> IResult<&[Token], SearchedWhenBranc
     )(tokens)
 }
 
-/// Parse data modification statements (INSERT, MATCH INSERT, SET, REMOVE, DELETE)
+/// Parse data modification statements (INSERT, MATCH INSERT, SET, REMOVE, DELETE, MERGE)
 fn data_statement(tokens: &[Token]) -> IResult<&[Token], DataStatement> {
     log::debug!(
         "data_statement called with tokens: {:?}",
@@ -3924,10 +4266,15 @@ fn data_statement(tokens: &[Token]) -> IResult<&[Token], DataStatement> {
         map(match_set_statement, DataStatement::MatchSet),
         map(match_remove_statement, DataStatement::MatchRemove),
         map(match_delete_statement, DataStatement::MatchDelete),
+        map(merge_statement, DataStatement::Merge),
         // Then try standalone patterns
         map(insert_statement, DataStatement::Insert),
         map(set_statement, DataStatement::Set),
         map(remove_statement, DataStatement::Remove),
+        map(
+            delete_edges_of_type_statement,
+            DataStatement::DeleteEdgesOfType,
+        ),
         map(delete_statement, DataStatement::Delete),
     ))(tokens);
     if result.is_err() {
@@ -3969,6 +4316,67 @@ fn match_insert_statement(tokens: &[Token]) -> IResult<&[Token], MatchInsertStat
     result
 }
 
+/// Parse MERGE statement: [MATCH pattern... [WHERE condition]] MERGE path_pattern
+/// [ON CREATE SET item, item] [ON MATCH SET item, item]
+fn merge_statement(tokens: &[Token]) -> IResult<&[Token], MergeStatement> {
+    let (remaining, statement) = map(
+        tuple((
+            opt(pair(match_clause, opt(where_clause))),
+            expect_token(Token::Merge),
+            graph_pattern,
+            many0(merge_action),
+        )),
+        |(matched, _, pattern, actions)| {
+            let (match_clause, where_clause) = match matched {
+                Some((match_clause, where_clause)) => (Some(match_clause), where_clause),
+                None => (None, None),
+            };
+            let mut on_create = Vec::new();
+            let mut on_match = Vec::new();
+            for (created, items) in actions {
+                if created {
+                    on_create.extend(items);
+                } else {
+                    on_match.extend(items);
+                }
+            }
+            MergeStatement {
+                match_clause,
+                where_clause,
+                pattern,
+                on_create,
+                on_match,
+                location: Location::default(),
+            }
+        },
+    )(tokens)?;
+
+    // MERGE ends the statement; a RETURN or another clause after it is not supported
+    match remaining.first() {
+        None | Some(Token::Semicolon) | Some(Token::Eof) => Ok((remaining, statement)),
+        Some(_) => Err(nom::Err::Error(nom::error::Error::new(
+            remaining,
+            nom::error::ErrorKind::Eof,
+        ))),
+    }
+}
+
+/// Parse ON CREATE SET item, item or ON MATCH SET item, item; true for ON CREATE
+fn merge_action(tokens: &[Token]) -> IResult<&[Token], (bool, Vec<SetItem>)> {
+    map(
+        tuple((
+            expect_token(Token::On),
+            alt((
+                value(true, expect_token(Token::Create)),
+                value(false, expect_token(Token::Match)),
+            )),
+            expect_token(Token::Set),
+            separated_list1(expect_token(Token::Comma), set_item),
+        )),
+        |(_, created, _, items)| (created, items),
+    )(tokens)
+}
+
 /// Parse MATCH SET statement: MATCH pattern... [WITH ...] [WHERE condition] SET item, item
 fn match_set_statement(tokens: &[Token]) -> IResult<&[Token], MatchSetStatement> {
     log::debug!(
@@ -4083,6 +4491,7 @@ fn graph_pattern(tokens: &[Token]) -> IResult<&[Token], PathPattern> {
         // If that fails, try single node pattern
         map(node_pattern, |node| PathPattern {
             assignment: None,
+            search: None,
             path_type: None,
             elements: vec![PatternElement::Node(node)],
             location: Location::default(),
@@ -4209,6 +4618,28 @@ fn delete_statement(tokens: &[Token]) -> IResult<&[Token], DeleteStatement> {
     )(tokens)
 }
 
+/// Parse DELETE EDGES OF TYPE statement: DELETE EDGES OF TYPE :label [WHERE condition]
+fn delete_edges_of_type_statement(
+    tokens: &[Token],
+) -> IResult<&[Token], DeleteEdgesOfTypeStatement> {
+    map(
+        tuple((
+            expect_token(Token::Delete),
+            expect_identifier("EDGES"),
+            expect_token(Token::Of),
+            expect_token(Token::Type),
+            expect_token(Token::Colon),
+            identifier,
+            opt(where_clause),
+        )),
+        |(_, _, _, _, _, label, where_clause)| DeleteEdgesOfTypeStatement {
+            label,
+            where_clause,
+            location: Location::default(),
+        },
+    )(tokens)
+}
+
 /// Parse EXISTS subquery: EXISTS(subquery)
 fn exists_subquery(tokens: &[Token]) -> IResult<&[Token], ExistsSubqueryExpression> {
     map(
@@ -4242,6 +4673,51 @@ fn not_exists_subquery(tokens: &[Token]) -> IResult<&[Token], NotExistsSubqueryE
     )(tokens)
 }
 
+/// Parse COUNT subquery: COUNT { pattern_subquery }
+fn count_subquery(tokens: &[Token]) -> IResult<&[Token], CountSubqueryExpression> {
+    map(
+        tuple((expect_identifier("COUNT"), pattern_subquery)),
+        |(_, subquery)| CountSubqueryExpression {
+            subquery: Box::new(subquery),
+            location: Location::default(),
+        },
+    )(tokens)
+}
+
+/// Parse COLLECT subquery: COLLECT { pattern_subquery }
+fn collect_subquery(tokens: &[Token]) -> IResult<&[Token], CollectSubqueryExpression> {
+    map(
+        tuple((expect_identifier("COLLECT"), pattern_subquery)),
+        |(_, subquery)| CollectSubqueryExpression {
+            subquery: Box::new(subquery),
+            location: Location::default(),
+        },
+    )(tokens)
+}
+
+/// Parse the braced body of COUNT and COLLECT:
+/// { [MATCH] pattern (, pattern)* [WHERE condition] [RETURN items] }
+fn pattern_subquery(tokens: &[Token]) -> IResult<&[Token], PatternSubquery> {
+    map(
+        tuple((
+            expect_token(Token::LeftBrace),
+            alt((
+                map(match_clause, |match_clause| match_clause.patterns),
+                separated_list1(expect_token(Token::Comma), path_pattern),
+            )),
+            opt(where_clause),
+            opt(return_clause),
+            expect_token(Token::RightBrace),
+        )),
+        |(_, patterns, where_clause, return_clause, _)| PatternSubquery {
+            patterns,
+            where_clause,
+            return_clause,
+            location: Location::default(),
+        },
+    )(tokens)
+}
+
 /// Parse general subquery: (subquery)
 fn subquery_expression(tokens: &[Token]) -> IResult<&[Token], SubqueryExpression> {
     map(
@@ -4382,6 +4858,8 @@ fn variable_declaration_for_procedure_body(
 ) -> IResult<&[Token], VariableDeclaration> {
     map(
         tuple((
+            // OPTIONAL MATCH starts a query, not a declaration of OPTIONAL
+            not(pair(optional_keyword, expect_token(Token::Match))),
             // Optional type specification
             opt(type_spec),
             // Variable identifier
@@ -4389,7 +4867,7 @@ fn variable_declaration_for_procedure_body(
             // Optional initial value
             opt(preceded(tag_token(Token::Equal), expression)),
         )),
-        |(type_spec, variable_name, initial_value)| {
+        |(_, type_spec, variable_name, initial_value)| {
             VariableDeclaration {
                 variable_name,
                 type_spec: type_spec.unwrap_or(TypeSpec::Vector { dimension: None }), // Default to vector type when unspecified
@@ -4564,7 +5042,16 @@ fn transaction_statement(tokens: &[Token]) -> IResult<&[Token], TransactionState
             TransactionStatement::StartTransaction,
         ),
         map(commit_statement, TransactionStatement::Commit),
+        map(
+            rollback_to_savepoint_statement,
+            TransactionStatement::RollbackToSavepoint,
+        ),
         map(rollback_statement, TransactionStatement::Rollback),
+        map(savepoint_statement, TransactionStatement::Savepoint),
+        map(
+            release_savepoint_statement,
+            TransactionStatement::ReleaseSavepoint,
+        ),
         map(
             set_transaction_characteristics_statement,
             TransactionStatement::SetTransactionCharacteristics,
@@ -4615,14 +5102,61 @@ fn rollback_statement(tokens: &[Token]) -> IResult<&[Token], RollbackStatement>
         tuple((
             expect_token(Token::Rollback),
             opt(expect_token(Token::Work)),
+            // An incomplete ROLLBACK TO must not roll back the whole transaction
+            not(expect_token(Token::To)),
         )),
-        |(_, work)| RollbackStatement {
+        |(_, work, _)| RollbackStatement {
             work: work.is_some(),
             location: Location::default(),
         },
     )(tokens)
 }
 
+/// Parse ROLLBACK [WORK] TO [SAVEPOINT] name
+fn rollback_to_savepoint_statement(
+    tokens: &[Token],
+) -> IResult<&[Token], RollbackToSavepointStatement> {
+    map(
+        tuple((
+            expect_token(Token::Rollback),
+            opt(expect_token(Token::Work)),
+            expect_token(Token::To),
+            opt(expect_identifier("SAVEPOINT")),
+            identifier,
+        )),
+        |(_, _, _, _, name)| RollbackToSavepointStatement {
+            name,
+            location: Location::default(),
+        },
+    )(tokens)
+}
+
+/// Parse SAVEPOINT name
+fn savepoint_statement(tokens: &[Token]) -> IResult<&[Token], SavepointStatement> {
+    map(
+        tuple((expect_identifier("SAVEPOINT"), identifier)),
+        |(_, name)| SavepointStatement {
+            name,
+            location: Location::default(),
+        },
+    )(tokens)
+}
+
+/// Parse RELEASE [SAVEPOINT] name
+fn release_savepoint_statement(tokens: &[Token]) -> IResult<&[Token], ReleaseSavepointStatement> {
+    map(
+        tuple((
+            expect_identifier("RELEASE"),
+            opt(expect_identifier("SAVEPOINT")),
+            identifier,
+        )),
+        |(_, _, name)| ReleaseSavepointStatement {
+            name,
+            location: Location::default(),
+        },
+    )(tokens)
+}
+
 /// Parse SET TRANSACTION characteristics
 fn set_transaction_characteristics_statement(
     tokens: &[Token],
@@ -4869,18 +5403,38 @@ fn create_index_statement(tokens: &[Token]) -> IResult<&[Token], CreateIndexStat
     let if_not_exists = if_not_exists.is_some();
 
     // Parse index name - use lenient parser to accept various tokens for better error messages
-    let (tokens, name) = parse_index_name(tokens)?;
+    let (tokens, name) = opt(parse_index_name)(tokens)?;
 
-    // Parse ON table_name
+    // Parse ON table_name, or ON ()-[r:TYPE]-() for a relationship property index
     let (tokens, _) = expect_token(Token::On)(tokens)?;
-    let (tokens, table) = parse_table_name(tokens)?;
+    let (tokens, (entity, table)) = alt((
+        map(index_edge_target, |label| (IndexEntity::Edge, label)),
+        map(parse_table_name, |table| (IndexEntity::Node, table)),
+    ))(tokens)?;
 
     // Parse optional column list (column1, column2, ...)
     let (tokens, columns) = opt(delimited(
         expect_token(Token::LeftParen),
-        separated_list1(expect_token(Token::Comma), identifier),
+        separated_list1(expect_token(Token::Comma), index_column),
         expect_token(Token::RightParen),
     ))(tokens)?;
+    let (columns, paths): (Vec<String>, Vec<Option<String>>) =
+        columns.unwrap_or_default().into_iter().unzip();
+    let json_path = paths.into_iter().flatten().next();
+
+    // Unnamed indexes are named after what they cover, e.g. idx_inhibits_ic50
+    let name = name.unwrap_or_else(|| {
+        let mut parts = vec!["idx".to_string(), table.to_lowercase()];
+        parts.extend(columns.iter().map(|column| column.to_lowercase()));
+        if let Some(path) = &json_path {
+            parts.extend(
+                path.split(|c: char| !c.is_alphanumeric() && c != '_')
+                    .filter(|part| !part.is_empty())
+                    .map(str::to_lowercase),
+            );
+        }
+        parts.join("_")
+    });
 
     // Parse optional USING clause
     let (tokens, _using_type) = opt(preceded(
@@ -4906,7 +5460,9 @@ fn create_index_statement(tokens: &[Token]) -> IResult<&[Token], CreateIndexStat
         CreateIndexStatement {
             name,
             table,
-            columns: columns.unwrap_or_default(),
+            columns,
+            json_path,
+            entity,
             index_type,
             options: options.unwrap_or_default(),
             if_not_exists,
@@ -4915,6 +5471,37 @@ fn create_index_statement(tokens: &[Token]) -> IResult<&[Token], CreateIndexStat
     ))
 }
 
+/// Parse one column of an index: a property, or `JSON_GET(property, '$.path')` for a
+/// value inside a JSON document property
+fn index_column(tokens: &[Token]) -> IResult<&[Token], (String, Option<String>)> {
+    alt((
+        map(
+            preceded(
+                expect_identifier("JSON_GET"),
+                delimited(
+                    expect_token(Token::LeftParen),
+                    separated_pair(identifier, expect_token(Token::Comma), string_literal),
+                    expect_token(Token::RightParen),
+                ),
+            ),
+            |(column, path)| (column, Some(path)),
+        ),
+        map(identifier, |column| (column, None)),
+    ))(tokens)
+}
+
+/// Parse the `()-[r:TYPE]-()` target of a relationship index, returning the relationship type
+fn index_edge_target(tokens: &[Token]) -> IResult<&[Token], String> {
+    let (remaining, (_, edge, _)) = tuple((node_pattern, edge_pattern, node_pattern))(tokens)?;
+    match edge.labels.as_slice() {
+        [label] => Ok((remaining, label.clone())),
+        _ => Err(nom::Err::Failure(nom::error::Error::new(
+            tokens,
+            nom::error::ErrorKind::Verify,
+        ))),
+    }
+}
+
 /// Parse DROP INDEX statement
 fn drop_index_statement(tokens: &[Token]) -> IResult<&[Token], DropIndexStatement> {
     let (tokens, _) = expect_token(Token::Drop)(tokens)?;
@@ -5480,6 +6067,42 @@ fn test_parser_variable_length_edge_pattern() {
     assert!(result.is_ok(), "Variable-length edge pattern should parse");
 }
 
+#[test]
+fn test_parser_star_quantifiers() {
+    for (pattern, expected) in [
+        ("[:NEXT*]", "AtLeast(1)"),
+        ("[:NEXT*2]", "Exact(2)"),
+        ("[:NEXT*1..3]", "Range { min: 1, max: 3 }"),
+        ("[:NEXT*2..]", "AtLeast(2)"),
+        ("[r:NEXT*..4 {weight: 1}]", "Range { min: 1, max: 4 }"),
+        ("[:NEXT]+", "AtLeast(1)"),
+    ] {
+        let query = format!("MATCH (a)-{}->(b) RETURN b", pattern);
+        let document = parse_query(&query).unwrap_or_else(|e| panic!("{}: {:?}", query, e));
+        let debug = format!("{:?}", document);
+        assert!(
+            debug.contains(&format!("quantifier: Some({})", expected)),
+            "{} should have quantifier {}",
+            query,
+            expected
+        );
+    }
+}
+
+#[test]
+fn test_parser_path_search_prefix() {
+    let query = "MATCH p = ALL SHORTEST (a)-[:KNOWS]->+(b) RETURN p";
+    let document = parse_query(query).expect("ALL SHORTEST should parse");
+    assert!(format!("{:?}", document).contains("search: Some(AllShortest)"));
+
+    let query = "MATCH any shortest (a)-[:KNOWS*]-(b) RETURN b";
+    let document = parse_query(query).expect("ANY SHORTEST should parse");
+    assert!(format!("{:?}", document).contains("search: Some(AnyShortest)"));
+
+    // SHORTEST stays usable as a name
+    assert!(parse_query("MATCH (shortest)-[:KNOWS]->(b) RETURN shortest").is_ok());
+}
+
 #[test]
 fn test_parser_variable_length_with_path_assignment() {
     let query = "MATCH path = (node0:ChainNode {id: 0})-[:NEXT]{1,3}->(node_end) RETURN count(path) as one_to_three_hop_paths";
@@ -5562,3 +6185,50 @@ fn test_parser_with_then_match() {
         result.err()
     );
 }
+
+#[test]
+fn test_parser_show_current_graph() {
+    for query in ["SHOW CURRENT GRAPH", "show current property graph"] {
+        let document = parse_query(query).expect("SHOW CURRENT GRAPH should parse");
+        assert!(matches!(
+            document.statement,
+            Statement::SessionStatement(SessionStatement::ShowCurrentGraph(_))
+        ));
+    }
+}
+
+#[test]
+fn test_parser_create_edge_property_index() {
+    let document = parse_query("CREATE INDEX ON ()-[r:INHIBITS]-() (IC50)")
+        .expect("Edge property index should parse");
+    match document.statement {
+        Statement::IndexStatement(IndexStatement::Create(create)) => {
+            assert_eq!(create.entity, IndexEntity::Edge);
+            assert_eq!(create.table, "INHIBITS");
+            assert_eq!(create.columns, vec!["IC50".to_string()]);
+            assert_eq!(create.name, "idx_inhibits_ic50");
+        }
+        other => panic!("Expected CREATE INDEX, got {:?}", other),
+    }
+
+    let document = parse_query("CREATE INDEX ON ()-[r:INHIBITS]-() (JSON_GET(meta, '$.assay.ph'))")
+        .expect("JSON path index should parse");
+    match document.statement {
+        Statement::IndexStatement(IndexStatement::Create(create)) => {
+            assert_eq!(create.columns, vec!["meta".to_string()]);
+            assert_eq!(create.json_path.as_deref(), Some("$.assay.ph"));
+            assert_eq!(create.name, "idx_inhibits_meta_assay_ph");
+        }
+        other => panic!("Expected CREATE INDEX, got {:?}", other),
+    }
+
+    let document =
+        parse_query("CREATE INDEX person_name ON Person (name)").expect("Node index should parse");
+    match document.statement {
+        Statement::IndexStatement(IndexStatement::Create(create)) => {
+            assert_eq!(create.entity, IndexEntity::Node);
+            assert_eq!(create.name, "person_name");
+        }
+        other => panic!("Expected CREATE INDEX, got {:?}", other),
+    }
+}
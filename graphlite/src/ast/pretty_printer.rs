@@ -539,6 +539,18 @@ fn print_expression(expr: &Expression, indent: usize) {
             debug!("{}Query:", get_indent(indent + 1));
             print_query(&subquery_expr.query, indent + 2);
         }
+        Expression::CountSubquery(subquery_expr) => {
+            debug!("{}CountSubqueryExpression", get_indent(indent));
+            for pattern in &subquery_expr.subquery.patterns {
+                print_path_pattern(pattern, indent + 1);
+            }
+        }
+        Expression::CollectSubquery(subquery_expr) => {
+            debug!("{}CollectSubqueryExpression", get_indent(indent));
+            for pattern in &subquery_expr.subquery.patterns {
+                print_path_pattern(pattern, indent + 1);
+            }
+        }
         Expression::NotExistsSubquery(subquery_expr) => {
             debug!("{}NotExistsSubqueryExpression", get_indent(indent));
             debug!("{}Query:", get_indent(indent + 1));
@@ -779,6 +791,22 @@ fn print_transaction_statement(transaction_stmt: &TransactionStatement, indent:
                 debug!("{}Work: true", get_indent(indent + 1));
             }
         }
+        TransactionStatement::Savepoint(savepoint_stmt) => {
+            debug!("{}{}", get_indent(indent), "Savepoint Statement");
+            debug!("{}Name: {}", get_indent(indent + 1), savepoint_stmt.name);
+        }
+        TransactionStatement::RollbackToSavepoint(rollback_stmt) => {
+            debug!(
+                "{}{}",
+                get_indent(indent),
+                "Rollback To Savepoint Statement"
+            );
+            debug!("{}Name: {}", get_indent(indent + 1), rollback_stmt.name);
+        }
+        TransactionStatement::ReleaseSavepoint(release_stmt) => {
+            debug!("{}{}", get_indent(indent), "Release Savepoint Statement");
+            debug!("{}Name: {}", get_indent(indent + 1), release_stmt.name);
+        }
         TransactionStatement::SetTransactionCharacteristics(set_stmt) => {
             debug!(
                 "{}{}",
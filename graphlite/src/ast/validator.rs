@@ -52,6 +52,10 @@
 //! - **Variable Scope**: Prevents access to undeclared variables
 //! - **Function Access**: Controls access to built-in functions
 //!
+//! ## 9. Graph Type Validations
+//! - **Schema Names**: `validate_against_graph_type` flags labels, edge types and
+//!   properties that the graph type of a typed graph does not define
+//!
 //! # Built-in Functions Supported
 //!
 //! ## Temporal Functions
@@ -94,6 +98,7 @@
 //! ```
 
 use super::ast::*;
+use crate::schema::types::{GraphTypeDefinition, PropertyDefinition};
 use crate::types::{GqlType, TypeValidator};
 use std::collections::{HashMap, HashSet};
 
@@ -525,6 +530,16 @@ impl ValidationContext {
             },
         );
 
+        // PATH_LENGTH function - returns the number of edges in a path
+        self.function_signatures.insert(
+            "PATH_LENGTH".to_string(),
+            FunctionSignature {
+                argument_types: vec![GqlType::Reference { target_type: None }], // Takes a path
+                return_type: GqlType::Double,
+                variadic: false,
+            },
+        );
+
         // Text search functions
         self.function_signatures.insert(
             "TEXT_SEARCH".to_string(),
@@ -1211,6 +1226,44 @@ fn validate_expression(
             // Validate the pattern within the WHERE clause context
             validate_path_pattern(&pattern_expr.pattern, ctx, errors);
         }
+        Expression::CountSubquery(count_expr) => {
+            validate_pattern_subquery(&count_expr.subquery, ctx, errors);
+        }
+        Expression::CollectSubquery(collect_expr) => {
+            validate_pattern_subquery(&collect_expr.subquery, ctx, errors);
+            let items = collect_expr
+                .subquery
+                .return_clause
+                .as_ref()
+                .map_or(0, |return_clause| return_clause.items.len());
+            if items != 1 {
+                errors.push(ValidationError {
+                    message: "COLLECT { } must end with a RETURN of exactly one expression"
+                        .to_string(),
+                    location: Some(collect_expr.location.clone()),
+                    error_type: ValidationErrorType::Structural,
+                });
+            }
+        }
+    }
+}
+
+/// Validate the body of COUNT { } and COLLECT { }
+fn validate_pattern_subquery(
+    subquery: &crate::ast::PatternSubquery,
+    ctx: &mut ValidationContext,
+    errors: &mut Vec<ValidationError>,
+) {
+    for pattern in &subquery.patterns {
+        validate_path_pattern(pattern, ctx, errors);
+    }
+    if let Some(where_clause) = &subquery.where_clause {
+        validate_expression(&where_clause.condition, ctx, errors);
+    }
+    if let Some(return_clause) = &subquery.return_clause {
+        for item in &return_clause.items {
+            validate_expression(&item.expression, ctx, errors);
+        }
     }
 }
 
@@ -1456,6 +1509,17 @@ fn infer_expression_type(
             // EXISTS always returns boolean
             Ok(GqlType::Boolean)
         }
+        Expression::CountSubquery(_) => {
+            // COUNT always returns an integer
+            Ok(GqlType::BigInt)
+        }
+        Expression::CollectSubquery(_) => {
+            // COLLECT always returns a list
+            Ok(GqlType::List {
+                element_type: Box::new(GqlType::String { max_length: None }),
+                max_length: None,
+            })
+        }
         Expression::InSubquery(_) => {
             // IN always returns boolean
             Ok(GqlType::Boolean)
@@ -1558,6 +1622,11 @@ fn validate_function_call(
         validate_expression(arg, ctx, errors);
     }
 
+    // Validate the sort keys of an ordered COLLECT
+    for item in func_call.order_clause.iter().flat_map(|order| &order.items) {
+        validate_expression(&item.expression, ctx, errors);
+    }
+
     // For variadic functions (like COUNT), allow flexible argument counts
     if signature.variadic {
         if func_name_upper == "COUNT" {
@@ -1858,7 +1927,7 @@ fn validate_function_call(
     );
 
     // Functions that can handle any type and should skip strict validation
-    let is_flexible_function = matches!(func_name_upper.as_str(), "TYPE" | "SIZE");
+    let is_flexible_function = matches!(func_name_upper.as_str(), "TYPE" | "SIZE" | "PATH_LENGTH");
 
     // Skip strict type validation for functions that can handle type coercion at runtime
     if !is_aggregation_function && !is_flexible_function {
@@ -2778,3 +2847,430 @@ fn validate_drop_graph_statement(
         }
     }
 }
+
+/// Check the labels, edge types and properties a statement refers to against
+/// the graph type of the graph it runs on
+///
+/// A name the graph type does not define can only match nothing, so a typo such
+/// as `:Protien` would otherwise surface as an empty result. Every unknown name
+/// is reported; the caller decides whether they are errors or warnings.
+pub fn validate_against_graph_type(
+    document: &Document,
+    graph_type: &GraphTypeDefinition,
+) -> Vec<ValidationError> {
+    let mut checker = GraphTypeChecker {
+        graph_type,
+        bindings: HashMap::new(),
+        errors: Vec::new(),
+    };
+    checker.check_statement(&document.statement);
+    checker.errors
+}
+
+/// Element a pattern variable is bound to, with the labels given for it
+enum PatternBinding {
+    Node(Vec<String>),
+    Edge(Vec<String>),
+}
+
+struct GraphTypeChecker<'a> {
+    graph_type: &'a GraphTypeDefinition,
+    bindings: HashMap<String, PatternBinding>,
+    errors: Vec<ValidationError>,
+}
+
+impl<'a> GraphTypeChecker<'a> {
+    fn check_statement(&mut self, statement: &Statement) {
+        match statement {
+            Statement::Query(query) => self.check_query(query),
+            Statement::DataStatement(DataStatement::Insert(insert)) => {
+                self.check_patterns(&insert.graph_patterns);
+            }
+            Statement::DataStatement(DataStatement::MatchInsert(stmt)) => {
+                self.check_match(&stmt.match_clause, stmt.where_clause.as_ref());
+                self.check_with(stmt.with_clause.as_ref());
+                self.check_patterns(&stmt.insert_graph_patterns);
+            }
+            Statement::DataStatement(DataStatement::MatchSet(stmt)) => {
+                self.check_match(&stmt.match_clause, stmt.where_clause.as_ref());
+                self.check_with(stmt.with_clause.as_ref());
+                self.check_set_items(&stmt.items);
+            }
+            Statement::DataStatement(DataStatement::MatchRemove(stmt)) => {
+                self.check_match(&stmt.match_clause, stmt.where_clause.as_ref());
+                self.check_with(stmt.with_clause.as_ref());
+                self.check_remove_items(&stmt.items);
+            }
+            Statement::DataStatement(DataStatement::MatchDelete(stmt)) => {
+                self.check_match(&stmt.match_clause, stmt.where_clause.as_ref());
+                self.check_with(stmt.with_clause.as_ref());
+            }
+            Statement::DataStatement(DataStatement::Merge(stmt)) => {
+                if let Some(match_clause) = &stmt.match_clause {
+                    self.check_match(match_clause, stmt.where_clause.as_ref());
+                }
+                self.check_patterns(std::slice::from_ref(&stmt.pattern));
+                self.check_set_items(&stmt.on_create);
+                self.check_set_items(&stmt.on_match);
+            }
+            _ => {}
+        }
+    }
+
+    fn check_query(&mut self, query: &Query) {
+        match query {
+            Query::Basic(basic) => {
+                self.check_match(&basic.match_clause, basic.where_clause.as_ref());
+                self.check_return(
+                    &basic.return_clause,
+                    basic.group_clause.as_ref(),
+                    basic.having_clause.as_ref(),
+                    basic.order_clause.as_ref(),
+                );
+            }
+            Query::SetOperation(set_op) => {
+                self.check_query(&set_op.left);
+                self.check_query(&set_op.right);
+            }
+            Query::Limited {
+                query,
+                order_clause,
+                ..
+            } => {
+                self.check_query(query);
+                self.check_order(order_clause.as_ref());
+            }
+            Query::WithQuery(with_query) => {
+                self.check_segments(&with_query.segments);
+                self.check_return(
+                    &with_query.final_return,
+                    with_query.group_clause.as_ref(),
+                    with_query.having_clause.as_ref(),
+                    with_query.order_clause.as_ref(),
+                );
+            }
+            Query::MutationPipeline(pipeline) => {
+                self.check_segments(&pipeline.segments);
+                match &pipeline.final_mutation {
+                    FinalMutation::Set(items) => self.check_set_items(items),
+                    FinalMutation::Remove(items) => self.check_remove_items(items),
+                    FinalMutation::Delete { .. } => {}
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn check_segments(&mut self, segments: &[QuerySegment]) {
+        for segment in segments {
+            self.check_match(&segment.match_clause, segment.where_clause.as_ref());
+            self.check_with(segment.with_clause.as_ref());
+        }
+    }
+
+    fn check_match(&mut self, match_clause: &MatchClause, where_clause: Option<&WhereClause>) {
+        self.check_patterns(&match_clause.patterns);
+        if let Some(where_clause) = where_clause {
+            self.check_expression(&where_clause.condition);
+        }
+    }
+
+    fn check_with(&mut self, with_clause: Option<&WithClause>) {
+        let Some(with_clause) = with_clause else {
+            return;
+        };
+        for item in &with_clause.items {
+            self.check_expression(&item.expression);
+        }
+        if let Some(where_clause) = &with_clause.where_clause {
+            self.check_expression(&where_clause.condition);
+        }
+        self.check_order(with_clause.order_clause.as_ref());
+    }
+
+    fn check_return(
+        &mut self,
+        return_clause: &ReturnClause,
+        group_clause: Option<&GroupClause>,
+        having_clause: Option<&HavingClause>,
+        order_clause: Option<&OrderClause>,
+    ) {
+        for item in &return_clause.items {
+            self.check_expression(&item.expression);
+        }
+        for expression in group_clause.iter().flat_map(|group| &group.expressions) {
+            self.check_expression(expression);
+        }
+        if let Some(having_clause) = having_clause {
+            self.check_expression(&having_clause.condition);
+        }
+        self.check_order(order_clause);
+    }
+
+    fn check_order(&mut self, order_clause: Option<&OrderClause>) {
+        for item in order_clause.iter().flat_map(|order| &order.items) {
+            self.check_expression(&item.expression);
+        }
+    }
+
+    fn check_set_items(&mut self, items: &[SetItem]) {
+        for item in items {
+            match item {
+                SetItem::Property { property, value } => {
+                    self.check_property_access(property);
+                    self.check_expression(value);
+                }
+                SetItem::Variable { value, .. } => self.check_expression(value),
+                SetItem::Label { labels, .. } => {
+                    for term in &labels.terms {
+                        for factor in &term.factors {
+                            if let LabelFactor::Identifier(label) = factor {
+                                self.check_label(label, &labels.location);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn check_remove_items(&mut self, items: &[RemoveItem]) {
+        for item in items {
+            if let RemoveItem::Property(property) = item {
+                self.check_property_access(property);
+            }
+        }
+    }
+
+    fn check_patterns(&mut self, patterns: &[PathPattern]) {
+        for element in patterns.iter().flat_map(|pattern| &pattern.elements) {
+            let (identifier, binding, properties, location) = match element {
+                PatternElement::Node(node) => {
+                    for label in &node.labels {
+                        self.check_label(label, &node.location);
+                    }
+                    (
+                        &node.identifier,
+                        PatternBinding::Node(node.labels.clone()),
+                        &node.properties,
+                        &node.location,
+                    )
+                }
+                PatternElement::Edge(edge) => {
+                    for label in &edge.labels {
+                        self.check_edge_type(label, &edge.location);
+                    }
+                    (
+                        &edge.identifier,
+                        PatternBinding::Edge(edge.labels.clone()),
+                        &edge.properties,
+                        &edge.location,
+                    )
+                }
+            };
+
+            for property in properties.iter().flat_map(|map| &map.properties) {
+                if let Some(message) = self.unknown_property(&binding, &property.key) {
+                    self.report(message, location);
+                }
+                self.check_expression(&property.value);
+            }
+
+            // A variable repeated across patterns keeps the labels of every occurrence
+            if let Some(identifier) = identifier {
+                match (self.bindings.get_mut(identifier), binding) {
+                    (Some(PatternBinding::Node(known)), PatternBinding::Node(labels))
+                    | (Some(PatternBinding::Edge(known)), PatternBinding::Edge(labels)) => {
+                        known.extend(labels);
+                    }
+                    (_, binding) => {
+                        self.bindings.insert(identifier.clone(), binding);
+                    }
+                }
+            }
+        }
+    }
+
+    fn check_expression(&mut self, expression: &Expression) {
+        match expression {
+            Expression::PropertyAccess(access) => self.check_property_access(access),
+            Expression::Binary(binary) => {
+                self.check_expression(&binary.left);
+                self.check_expression(&binary.right);
+            }
+            Expression::Unary(unary) => self.check_expression(&unary.expression),
+            Expression::FunctionCall(func_call) => {
+                for argument in &func_call.arguments {
+                    self.check_expression(argument);
+                }
+                for item in func_call.order_clause.iter().flat_map(|order| &order.items) {
+                    self.check_expression(&item.expression);
+                }
+            }
+            Expression::Case(case_expr) => match &case_expr.case_type {
+                CaseType::Simple(simple) => {
+                    self.check_expression(&simple.test_expression);
+                    for branch in &simple.when_branches {
+                        for value in &branch.when_values {
+                            self.check_expression(value);
+                        }
+                        self.check_expression(&branch.then_expression);
+                    }
+                    if let Some(else_expression) = &simple.else_expression {
+                        self.check_expression(else_expression);
+                    }
+                }
+                CaseType::Searched(searched) => {
+                    for branch in &searched.when_branches {
+                        self.check_expression(&branch.condition);
+                        self.check_expression(&branch.then_expression);
+                    }
+                    if let Some(else_expression) = &searched.else_expression {
+                        self.check_expression(else_expression);
+                    }
+                }
+            },
+            Expression::Cast(cast) => self.check_expression(&cast.expression),
+            Expression::IsPredicate(predicate) => {
+                self.check_expression(&predicate.subject);
+                if let Some(target) = &predicate.target {
+                    self.check_expression(target);
+                }
+            }
+            Expression::ArrayIndex(index) => {
+                self.check_expression(&index.array);
+                self.check_expression(&index.index);
+            }
+            // Subqueries bind variables of their own
+            _ => {}
+        }
+    }
+
+    fn check_label(&mut self, label: &str, location: &Location) {
+        let labels = self.graph_type.node_types.iter().map(|t| t.label.as_str());
+        if !labels.clone().any(|defined| defined == label) {
+            let message = format!(
+                "Label '{}' is not defined in graph type '{}'{}",
+                label,
+                self.graph_type.name,
+                did_you_mean(label, labels)
+            );
+            self.report(message, location);
+        }
+    }
+
+    fn check_edge_type(&mut self, edge_type: &str, location: &Location) {
+        let edge_types = self
+            .graph_type
+            .edge_types
+            .iter()
+            .map(|t| t.type_name.as_str());
+        if !edge_types.clone().any(|defined| defined == edge_type) {
+            let message = format!(
+                "Edge type '{}' is not defined in graph type '{}'{}",
+                edge_type,
+                self.graph_type.name,
+                did_you_mean(edge_type, edge_types)
+            );
+            self.report(message, location);
+        }
+    }
+
+    /// Only variables bound by a pattern are checked; WITH aliases and UNWIND
+    /// variables may hold anything
+    fn check_property_access(&mut self, access: &PropertyAccess) {
+        let message = self
+            .bindings
+            .get(&access.object)
+            .and_then(|binding| self.unknown_property(binding, &access.property));
+        if let Some(message) = message {
+            self.report(message, &access.location);
+        }
+    }
+
+    /// Error message if no type the element may have defines `property`
+    ///
+    /// Elements whose labels are all unknown may have the properties of any type
+    /// of their kind.
+    fn unknown_property(&self, binding: &PatternBinding, property: &str) -> Option<String> {
+        let graph_type = self.graph_type;
+        let (kind, labels) = match binding {
+            PatternBinding::Node(labels) => ("node", labels),
+            PatternBinding::Edge(labels) => ("edge", labels),
+        };
+        let all: Vec<(&'a str, &'a [PropertyDefinition])> = match binding {
+            PatternBinding::Node(_) => graph_type
+                .node_types
+                .iter()
+                .map(|t| (t.label.as_str(), t.properties.as_slice()))
+                .collect(),
+            PatternBinding::Edge(_) => graph_type
+                .edge_types
+                .iter()
+                .map(|t| (t.type_name.as_str(), t.properties.as_slice()))
+                .collect(),
+        };
+        let labelled: Vec<_> = all
+            .iter()
+            .filter(|(name, _)| labels.iter().any(|label| label == name))
+            .copied()
+            .collect();
+        let (owner, types) = if labelled.is_empty() {
+            (format!("any {} type", kind), all)
+        } else {
+            let names: Vec<String> = labelled
+                .iter()
+                .map(|(name, _)| format!(":{}", name))
+                .collect();
+            (names.join(", "), labelled)
+        };
+        let defined = types
+            .iter()
+            .flat_map(|(_, properties)| properties.iter())
+            .map(|definition| definition.name.as_str());
+        if defined.clone().any(|name| name == property) {
+            return None;
+        }
+        Some(format!(
+            "Property '{}' is not defined for {} in graph type '{}'{}",
+            property,
+            owner,
+            graph_type.name,
+            did_you_mean(property, defined)
+        ))
+    }
+
+    fn report(&mut self, message: String, location: &Location) {
+        self.errors.push(ValidationError {
+            message,
+            location: Some(location.clone()),
+            error_type: ValidationErrorType::Semantic,
+        });
+    }
+}
+
+/// Suggestion for a misspelled name: the closest candidate at most two edits away
+fn did_you_mean<'n>(name: &str, candidates: impl Iterator<Item = &'n str>) -> String {
+    candidates
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= 2)
+        .min()
+        .map(|(_, candidate)| format!(" (did you mean '{}'?)", candidate))
+        .unwrap_or_default()
+}
+
+/// Levenshtein distance between two names, ignoring case
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.to_lowercase().chars().collect();
+    let b: Vec<char> = b.to_lowercase().chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
@@ -35,6 +35,9 @@ pub struct CacheConfig {
 
     /// Invalidation strategy
     pub invalidation_strategy: InvalidationStrategy,
+
+    /// Number of most frequently used graphs reloaded into the storage cache on startup (0 disables warming)
+    pub warm_start_graphs: usize,
 }
 
 /// Configuration for a specific cache level
@@ -83,6 +86,11 @@ pub enum EvictionPolicy {
     Size,
     /// Adaptive Replacement Cache (balance recency/frequency)
     Arc,
+    /// Cost-aware: evict the entry with the highest size / (recompute cost × frequency)
+    ///
+    /// The recompute cost is the execution time recorded with the entry, so results
+    /// of expensive queries outlive cheap ones of the same size and popularity.
+    CostAware,
 }
 
 /// Cache invalidation strategies
@@ -133,6 +141,7 @@ impl Default for CacheConfig {
                 primary: Box::new(InvalidationStrategy::TagBased),
                 fallback: Box::new(InvalidationStrategy::Ttl),
             },
+            warm_start_graphs: 8,
         }
     }
 }
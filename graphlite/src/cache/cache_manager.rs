@@ -10,8 +10,10 @@ use crate::exec::QueryResult;
 use crate::plan::logical::LogicalPlan;
 use crate::plan::physical::PhysicalPlan;
 use crate::plan::trace::PlanTrace;
+use crate::storage::StorageManager;
 
 use super::{
+    hot_set::{HotGraph, HotSet, HotSetSnapshot},
     plan_cache::{create_plan_cache_key, CacheEfficiencyMetrics, PlanCacheStats},
     result_cache::{
         create_query_cache_key, CacheHit, CacheParameter, CacheStats as ResultCacheStats,
@@ -45,6 +47,9 @@ pub struct CacheManager {
     // Event tracking
     events: Arc<RwLock<Vec<CacheEvent>>>,
     max_events: usize,
+
+    // Graph access tracking for warm starts
+    hot_set: Arc<HotSet>,
 }
 
 /// Global cache statistics across all cache types
@@ -210,6 +215,7 @@ impl CacheManager {
             invalidation_manager: Arc::new(InvalidationManager::new(invalidation_strategy, 1000)),
             events: Arc::new(RwLock::new(Vec::new())),
             max_events: 10000,
+            hot_set: Arc::new(HotSet::new()),
         })
     }
 
@@ -508,6 +514,11 @@ impl CacheManager {
         }
     }
 
+    /// Number of cached query results and the memory they hold
+    pub fn result_cache_usage(&self) -> (usize, usize) {
+        self.result_cache.usage()
+    }
+
     /// Get current graph version (for session catalog caching)
     pub fn get_graph_version(&self) -> u64 {
         *self.graph_version.read().unwrap()
@@ -586,6 +597,43 @@ impl CacheManager {
         self.events.write().unwrap().clear();
     }
 
+    /// Record a graph read so the graph can be reloaded on the next startup
+    pub fn record_graph_access(&self, graph_name: &str) {
+        self.hot_set.record_access(graph_name);
+    }
+
+    /// Stop tracking a graph, e.g. one that no longer exists
+    pub fn forget_graph(&self, graph_name: &str) {
+        self.hot_set.remove(graph_name);
+    }
+
+    /// The graphs to warm on startup, most frequently used first
+    pub fn hot_graphs(&self) -> Vec<HotGraph> {
+        if !self.config.enabled {
+            return Vec::new();
+        }
+        self.hot_set.hottest(self.config.warm_start_graphs)
+    }
+
+    /// Persist the graph access metadata
+    pub fn save_hot_set(&self, storage: &StorageManager) -> Result<(), String> {
+        let data = serde_json::to_vec(&self.hot_set.snapshot())
+            .map_err(|e| format!("Failed to serialize cache hot set: {}", e))?;
+        storage
+            .save_cache_metadata(&data)
+            .map_err(|e| e.to_string())
+    }
+
+    /// Merge the graph access metadata persisted by a previous run
+    pub fn load_hot_set(&self, storage: &StorageManager) -> Result<(), String> {
+        if let Some(data) = storage.load_cache_metadata().map_err(|e| e.to_string())? {
+            let snapshot: HotSetSnapshot = serde_json::from_slice(&data)
+                .map_err(|e| format!("Failed to parse cache hot set: {}", e))?;
+            self.hot_set.restore(snapshot);
+        }
+        Ok(())
+    }
+
     /// Get recent cache events for debugging
     pub fn get_recent_events(&self, limit: Option<usize>) -> Vec<CacheEvent> {
         let events = self.events.read().unwrap();
@@ -0,0 +1,109 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! Graph access tracking for cache warming
+//!
+//! The hot set records how often each graph is read so that the most frequently
+//! used graphs can be reloaded into the storage cache right after a restart,
+//! instead of paying the load cost on the first query that touches them.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::RwLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Access metadata for a single graph
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HotGraph {
+    pub name: String,
+    pub accesses: u64,
+    /// Seconds since the Unix epoch
+    pub last_accessed: u64,
+}
+
+/// Persisted form of the hot set
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HotSetSnapshot {
+    pub graphs: Vec<HotGraph>,
+}
+
+/// Thread-safe graph access tracker
+#[derive(Debug, Default)]
+pub struct HotSet {
+    graphs: RwLock<HashMap<String, HotGraph>>,
+}
+
+impl HotSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record one read of a graph
+    pub fn record_access(&self, graph_name: &str) {
+        let now = unix_now();
+        if let Ok(mut graphs) = self.graphs.write() {
+            let entry = graphs
+                .entry(graph_name.to_string())
+                .or_insert_with(|| HotGraph {
+                    name: graph_name.to_string(),
+                    accesses: 0,
+                    last_accessed: now,
+                });
+            entry.accesses += 1;
+            entry.last_accessed = now;
+        }
+    }
+
+    /// Forget a graph, e.g. after it has been dropped
+    pub fn remove(&self, graph_name: &str) {
+        if let Ok(mut graphs) = self.graphs.write() {
+            graphs.remove(graph_name);
+        }
+    }
+
+    /// The `limit` most accessed graphs, most recently used first on ties
+    pub fn hottest(&self, limit: usize) -> Vec<HotGraph> {
+        let mut graphs: Vec<HotGraph> = match self.graphs.read() {
+            Ok(graphs) => graphs.values().cloned().collect(),
+            Err(_) => return Vec::new(),
+        };
+        graphs.sort_by(|a, b| {
+            b.accesses
+                .cmp(&a.accesses)
+                .then(b.last_accessed.cmp(&a.last_accessed))
+                .then(a.name.cmp(&b.name))
+        });
+        graphs.truncate(limit);
+        graphs
+    }
+
+    pub fn snapshot(&self) -> HotSetSnapshot {
+        HotSetSnapshot {
+            graphs: self.hottest(usize::MAX),
+        }
+    }
+
+    /// Merge a persisted snapshot into the tracker, keeping the larger counts
+    pub fn restore(&self, snapshot: HotSetSnapshot) {
+        if let Ok(mut graphs) = self.graphs.write() {
+            for graph in snapshot.graphs {
+                match graphs.get_mut(&graph.name) {
+                    Some(existing) => {
+                        existing.accesses = existing.accesses.max(graph.accesses);
+                        existing.last_accessed = existing.last_accessed.max(graph.last_accessed);
+                    }
+                    None => {
+                        graphs.insert(graph.name.clone(), graph);
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
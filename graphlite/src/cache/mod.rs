@@ -9,9 +9,11 @@
 //! - Subquery results
 //! - Metadata lookups
 //! - Statistics and cardinality estimates
+//! - Graph access tracking for warming the storage cache after a restart
 
 pub mod cache_config;
 pub mod cache_manager;
+pub mod hot_set;
 pub mod invalidation;
 pub mod plan_cache;
 pub mod result_cache;
@@ -68,6 +68,18 @@ pub struct QueryResultEntry {
     pub compression_ratio: Option<f32>, // If compressed
 }
 
+impl QueryResultEntry {
+    /// Score under [`EvictionPolicy::CostAware`]; the highest score is evicted first
+    ///
+    /// Bytes held per microsecond of recorded execution time per use, so large,
+    /// cheap, rarely used results go before small, expensive, popular ones.
+    pub fn eviction_score(&self) -> f64 {
+        let recompute_cost = self.execution_time.as_micros().max(1) as f64;
+        let frequency = self.metadata.access_count as f64 + 1.0;
+        self.size_bytes() as f64 / (recompute_cost * frequency)
+    }
+}
+
 impl CacheValue for QueryResultEntry {
     fn size_bytes(&self) -> usize {
         let base_size = std::mem::size_of::<Self>();
@@ -125,6 +137,13 @@ impl<K: Clone + Eq + Hash> LRUTracker<K> {
         }
     }
 
+    fn remove(&mut self, key: &K) {
+        if let Some(pos) = self.positions.remove(key) {
+            self.order.remove(pos);
+            self.update_positions();
+        }
+    }
+
     fn remove_lru(&mut self) -> Option<K> {
         if let Some(key) = self.order.pop_back() {
             self.positions.remove(&key);
@@ -161,7 +180,7 @@ pub struct ResultCache {
 
     // Cache statistics
     stats: Arc<RwLock<CacheStats>>,
-    _eviction_policy: EvictionPolicy,
+    eviction_policy: EvictionPolicy,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -200,7 +219,7 @@ impl ResultCache {
         l1_max_memory: usize,
         l2_max_entries: usize,
         l2_max_memory: usize,
-        _eviction_policy: EvictionPolicy,
+        eviction_policy: EvictionPolicy,
     ) -> Self {
         Self {
             l1_cache: Arc::new(RwLock::new(HashMap::new())),
@@ -216,7 +235,7 @@ impl ResultCache {
             l2_current_memory: Arc::new(RwLock::new(0)),
 
             stats: Arc::new(RwLock::new(CacheStats::default())),
-            _eviction_policy,
+            eviction_policy,
         }
     }
 
@@ -394,8 +413,10 @@ impl ResultCache {
             || current_entries >= self.l1_max_entries
         {
             let mut lru = self.l1_lru.write().unwrap();
-            while let Some(key_to_evict) = lru.remove_lru() {
-                if let Some(evicted_entry) = self.l1_cache.write().unwrap().remove(&key_to_evict) {
+            while let Some(key_to_evict) = self.next_victim(&self.l1_cache, &mut lru) {
+                // Bind first: the write guard must not live through the block below
+                let evicted = self.l1_cache.write().unwrap().remove(&key_to_evict);
+                if let Some(evicted_entry) = evicted {
                     let evicted_size = evicted_entry.size_bytes();
 
                     {
@@ -435,8 +456,10 @@ impl ResultCache {
             || current_entries >= self.l2_max_entries
         {
             let mut lru = self.l2_lru.write().unwrap();
-            while let Some(key_to_evict) = lru.remove_lru() {
-                if let Some(evicted_entry) = self.l2_cache.write().unwrap().remove(&key_to_evict) {
+            while let Some(key_to_evict) = self.next_victim(&self.l2_cache, &mut lru) {
+                // Bind first: the write guard must not live through the block below
+                let evicted = self.l2_cache.write().unwrap().remove(&key_to_evict);
+                if let Some(evicted_entry) = evicted {
                     let evicted_size = evicted_entry.size_bytes();
 
                     {
@@ -463,6 +486,34 @@ impl ResultCache {
         }
     }
 
+    /// Pick the next entry of a level to evict and drop it from the level's LRU order
+    ///
+    /// Cost-aware eviction takes the entry with the highest eviction score, the least
+    /// recently used one among equal scores; every other policy evicts in LRU order.
+    fn next_victim(
+        &self,
+        cache: &RwLock<HashMap<QueryCacheKey, QueryResultEntry>>,
+        lru: &mut LRUTracker<QueryCacheKey>,
+    ) -> Option<QueryCacheKey> {
+        match self.eviction_policy {
+            EvictionPolicy::CostAware => {
+                let victim = cache
+                    .read()
+                    .unwrap()
+                    .iter()
+                    .max_by(|(_, a), (_, b)| {
+                        a.eviction_score()
+                            .total_cmp(&b.eviction_score())
+                            .then(b.metadata.last_accessed.cmp(&a.metadata.last_accessed))
+                    })
+                    .map(|(key, _)| key.clone())?;
+                lru.remove(&victim);
+                Some(victim)
+            }
+            _ => lru.remove_lru(),
+        }
+    }
+
     /// Invalidate entries by graph version
     pub fn invalidate_by_graph_version(&self, version: u64) {
         let mut removed_keys = Vec::new();
@@ -520,6 +571,14 @@ impl ResultCache {
         self.stats.read().unwrap().clone()
     }
 
+    /// Number of cached results and the memory they hold, over both levels
+    pub fn usage(&self) -> (usize, usize) {
+        let entries = self.l1_cache.read().unwrap().len() + self.l2_cache.read().unwrap().len();
+        let memory =
+            *self.l1_current_memory.read().unwrap() + *self.l2_current_memory.read().unwrap();
+        (entries, memory)
+    }
+
     /// Clear all cached results
     pub fn clear(&self) {
         self.l1_cache.write().unwrap().clear();
@@ -551,3 +610,71 @@ pub fn create_query_cache_key(
         user_context,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::Value;
+
+    fn result() -> QueryResult {
+        QueryResult {
+            rows: vec![Row::from_values(HashMap::from([(
+                "n".to_string(),
+                Value::Number(1.0),
+            )]))],
+            variables: vec!["n".to_string()],
+            execution_time_ms: 0,
+            rows_affected: 1,
+            session_result: None,
+            warnings: Vec::new(),
+            operator_timings: None,
+        }
+    }
+
+    /// Room for two entries in L1; evicted L1 entries are demoted to L2
+    fn cache(policy: EvictionPolicy) -> ResultCache {
+        ResultCache::new(2, 1024 * 1024, 10, 1024 * 1024, policy)
+    }
+
+    fn key(query: &str) -> QueryCacheKey {
+        create_query_cache_key(query, Vec::new(), 0, None)
+    }
+
+    fn level(cache: &ResultCache, query: &str) -> Option<CacheLevel> {
+        cache.get(&key(query)).map(|hit| hit.hit_level)
+    }
+
+    #[test]
+    fn test_cost_aware_eviction_keeps_expensive_results() {
+        let cache = cache(EvictionPolicy::CostAware);
+        cache.insert(key("expensive"), result(), Duration::from_millis(500), 0);
+        cache.insert(key("cheap"), result(), Duration::from_millis(1), 0);
+        cache.insert(key("new"), result(), Duration::from_millis(10), 0);
+
+        assert_eq!(level(&cache, "expensive"), Some(CacheLevel::L1));
+        assert_eq!(level(&cache, "cheap"), Some(CacheLevel::L2));
+
+        // LRU demotes the oldest entry regardless of what it cost
+        let cache = self::cache(EvictionPolicy::Lru);
+        cache.insert(key("expensive"), result(), Duration::from_millis(500), 0);
+        cache.insert(key("cheap"), result(), Duration::from_millis(1), 0);
+        cache.insert(key("new"), result(), Duration::from_millis(10), 0);
+
+        assert_eq!(level(&cache, "expensive"), Some(CacheLevel::L2));
+        assert_eq!(level(&cache, "cheap"), Some(CacheLevel::L1));
+    }
+
+    #[test]
+    fn test_cost_aware_eviction_weighs_frequency() {
+        let cache = cache(EvictionPolicy::CostAware);
+        cache.insert(key("popular"), result(), Duration::from_millis(5), 0);
+        cache.insert(key("once"), result(), Duration::from_millis(5), 0);
+        for _ in 0..3 {
+            assert_eq!(level(&cache, "popular"), Some(CacheLevel::L1));
+        }
+        cache.insert(key("new"), result(), Duration::from_millis(5), 0);
+
+        assert_eq!(level(&cache, "popular"), Some(CacheLevel::L1));
+        assert_eq!(level(&cache, "once"), Some(CacheLevel::L2));
+    }
+}
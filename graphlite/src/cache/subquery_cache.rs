@@ -169,6 +169,7 @@ fn values_equal(a: &Value, b: &Value) -> bool {
         }
         (Value::Vector(a), Value::Vector(b)) => a == b,
         (Value::Path(a), Value::Path(b)) => a == b,
+        (Value::Json(a), Value::Json(b)) => a == b,
         _ => false,
     }
 }
@@ -265,6 +266,10 @@ fn hash_value<H: Hasher>(value: &Value, state: &mut H) {
             // Hash the temporal value - we'll hash its debug representation for now
             format!("{:?}", temporal).hash(state);
         }
+        Value::Json(document) => {
+            15u8.hash(state);
+            document.to_string().hash(state);
+        }
     }
 }
 
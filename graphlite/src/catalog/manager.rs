@@ -11,6 +11,7 @@ use super::error::{CatalogError, CatalogResult};
 use super::operations::{CatalogOperation, CatalogResponse, EntityType, QueryType};
 use super::registry::CatalogRegistry;
 use super::traits::CatalogSchema;
+use crate::schema::types::GraphTypeDefinition;
 use crate::storage::StorageManager;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
@@ -119,6 +120,29 @@ impl CatalogManager {
             .execute_read_only(operation)
     }
 
+    /// Graph type a graph (`/schema/graph`) is bound to with `CREATE GRAPH ... TYPED`
+    pub fn graph_type_of(&self, graph_path: &str) -> Option<GraphTypeDefinition> {
+        let graph = self
+            .query_read_only(
+                "graph_metadata",
+                QueryType::GetGraph,
+                serde_json::json!({ "name": graph_path.trim_start_matches('/') }),
+            )
+            .ok()?;
+        let CatalogResponse::Query { results } = graph else {
+            return None;
+        };
+        let type_name = results.get("graph_type_id")?.get("name")?.as_str()?;
+        let graph_type = self
+            .query_read_only(
+                "graph_type",
+                QueryType::GetGraphType,
+                serde_json::json!({ "name": type_name }),
+            )
+            .ok()?;
+        serde_json::from_value(graph_type.data()?.clone()).ok()
+    }
+
     /// Get catalog metadata without knowing the specific type
     ///
     /// Returns information about a catalog's capabilities, supported entities,
@@ -53,6 +53,11 @@ impl GraphTypeId {
     }
 }
 
+// Tags and owner are kept as graph properties, so catalogs persisted before
+// they existed still load
+const TAGS_PROPERTY: &str = "tags";
+const OWNER_PROPERTY: &str = "owner";
+
 /// Property graph instance
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Graph {
@@ -136,6 +141,33 @@ impl Graph {
     pub fn get_property(&self, key: &str) -> Option<&GraphProperty> {
         self.properties.get(key)
     }
+
+    /// Tags set with `ALTER GRAPH ... SET TAGS [...]`
+    pub fn tags(&self) -> Vec<String> {
+        match self.properties.get(TAGS_PROPERTY) {
+            Some(GraphProperty::List(tags)) => tags
+                .iter()
+                .filter_map(|tag| match tag {
+                    GraphProperty::String(tag) => Some(tag.clone()),
+                    _ => None,
+                })
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Check whether the graph is tagged with `tag`, ignoring case
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags().iter().any(|t| t.eq_ignore_ascii_case(tag))
+    }
+
+    /// Owner set with `ALTER GRAPH ... SET OWNER '...'`
+    pub fn owner(&self) -> Option<String> {
+        match self.properties.get(OWNER_PROPERTY) {
+            Some(GraphProperty::String(owner)) => Some(owner.clone()),
+            _ => None,
+        }
+    }
 }
 
 /// Graph-level properties  
@@ -480,6 +512,71 @@ impl GraphMetadataCatalog {
         self.graphs.values().find(|g| g.id.name == name)
     }
 
+    /// Answer a catalog query; queries never modify the catalog
+    fn query(&self, query_type: QueryType, params: &Value) -> CatalogResult<CatalogResponse> {
+        match query_type {
+            QueryType::GetGraph => {
+                if let Some(name) = params.get("name").and_then(|v| v.as_str()) {
+                    if let Some(graph) = self.get_graph(name) {
+                        Ok(CatalogResponse::Query {
+                            results: serde_json::to_value(graph)?,
+                        })
+                    } else {
+                        Err(CatalogError::NotFound(format!(
+                            "Graph '{}' not found",
+                            name
+                        )))
+                    }
+                } else {
+                    Err(CatalogError::InvalidParameters(
+                        "Missing 'name' parameter".to_string(),
+                    ))
+                }
+            }
+            QueryType::GetGraphType => {
+                if let Some(name) = params.get("name").and_then(|v| v.as_str()) {
+                    if let Some(graph_type) = self.get_graph_type(name) {
+                        Ok(CatalogResponse::Query {
+                            results: serde_json::to_value(graph_type)?,
+                        })
+                    } else {
+                        Err(CatalogError::NotFound(format!(
+                            "Graph type '{}' not found",
+                            name
+                        )))
+                    }
+                } else {
+                    Err(CatalogError::InvalidParameters(
+                        "Missing 'name' parameter".to_string(),
+                    ))
+                }
+            }
+            QueryType::List => {
+                if let Some(schema_name) = params.get("schema_name").and_then(|v| v.as_str()) {
+                    let entity_type = params
+                        .get("entity_type")
+                        .and_then(|v| v.as_str())
+                        .unwrap_or("graph");
+
+                    let results = match entity_type {
+                        "graph" => serde_json::to_value(self.get_graphs_by_schema(schema_name))?,
+                        "graph_type" => {
+                            serde_json::to_value(self.get_graph_types_by_schema(schema_name))?
+                        }
+                        _ => return Ok(CatalogResponse::NotSupported),
+                    };
+
+                    Ok(CatalogResponse::Query { results })
+                } else {
+                    Err(CatalogError::InvalidParameters(
+                        "Missing 'schema_name' parameter".to_string(),
+                    ))
+                }
+            }
+            _ => Ok(CatalogResponse::NotSupported),
+        }
+    }
+
     /// Get a graph type by name (backward compatibility - searches all schemas)
     fn get_graph_type(&self, name: &str) -> Option<&GraphType> {
         // First try direct lookup for backward compatibility
@@ -588,6 +685,26 @@ impl GraphMetadataCatalog {
             }
         }
 
+        if let Some(tags) = updates.get("tags").and_then(|v| v.as_array()) {
+            let mut unique: Vec<String> = Vec::new();
+            for tag in tags.iter().filter_map(|t| t.as_str()).map(str::trim) {
+                if !tag.is_empty() && !unique.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+                    unique.push(tag.to_string());
+                }
+            }
+            graph.properties.insert(
+                TAGS_PROPERTY.to_string(),
+                GraphProperty::List(unique.into_iter().map(GraphProperty::String).collect()),
+            );
+        }
+
+        if let Some(owner) = updates.get("owner").and_then(|v| v.as_str()) {
+            graph.properties.insert(
+                OWNER_PROPERTY.to_string(),
+                GraphProperty::String(owner.to_string()),
+            );
+        }
+
         graph.modified_at = chrono::Utc::now();
         Ok(())
     }
@@ -798,69 +915,7 @@ impl CatalogProvider for GraphMetadataCatalog {
                 _ => Ok(CatalogResponse::NotSupported),
             },
 
-            CatalogOperation::Query { query_type, params } => match query_type {
-                QueryType::GetGraph => {
-                    if let Some(name) = params.get("name").and_then(|v| v.as_str()) {
-                        if let Some(graph) = self.get_graph(name) {
-                            Ok(CatalogResponse::Query {
-                                results: serde_json::to_value(graph)?,
-                            })
-                        } else {
-                            Err(CatalogError::NotFound(format!(
-                                "Graph '{}' not found",
-                                name
-                            )))
-                        }
-                    } else {
-                        Err(CatalogError::InvalidParameters(
-                            "Missing 'name' parameter".to_string(),
-                        ))
-                    }
-                }
-                QueryType::GetGraphType => {
-                    if let Some(name) = params.get("name").and_then(|v| v.as_str()) {
-                        if let Some(graph_type) = self.get_graph_type(name) {
-                            Ok(CatalogResponse::Query {
-                                results: serde_json::to_value(graph_type)?,
-                            })
-                        } else {
-                            Err(CatalogError::NotFound(format!(
-                                "Graph type '{}' not found",
-                                name
-                            )))
-                        }
-                    } else {
-                        Err(CatalogError::InvalidParameters(
-                            "Missing 'name' parameter".to_string(),
-                        ))
-                    }
-                }
-                QueryType::List => {
-                    if let Some(schema_name) = params.get("schema_name").and_then(|v| v.as_str()) {
-                        let entity_type = params
-                            .get("entity_type")
-                            .and_then(|v| v.as_str())
-                            .unwrap_or("graph");
-
-                        let results = match entity_type {
-                            "graph" => {
-                                serde_json::to_value(self.get_graphs_by_schema(schema_name))?
-                            }
-                            "graph_type" => {
-                                serde_json::to_value(self.get_graph_types_by_schema(schema_name))?
-                            }
-                            _ => return Ok(CatalogResponse::NotSupported),
-                        };
-
-                        Ok(CatalogResponse::Query { results })
-                    } else {
-                        Err(CatalogError::InvalidParameters(
-                            "Missing 'schema_name' parameter".to_string(),
-                        ))
-                    }
-                }
-                _ => Ok(CatalogResponse::NotSupported),
-            },
+            CatalogOperation::Query { query_type, params } => self.query(query_type, &params),
 
             CatalogOperation::Update {
                 entity_type,
@@ -881,7 +936,7 @@ impl CatalogProvider for GraphMetadataCatalog {
                 filters,
             } => match entity_type {
                 EntityType::Graph => {
-                    let graphs: Vec<&Graph> = if let Some(filters) = filters {
+                    let mut graphs: Vec<&Graph> = if let Some(filters) = &filters {
                         if let Some(schema_name) =
                             filters.get("schema_name").and_then(|v| v.as_str())
                         {
@@ -892,6 +947,13 @@ impl CatalogProvider for GraphMetadataCatalog {
                     } else {
                         self.graphs.values().collect()
                     };
+                    if let Some(tag) = filters
+                        .as_ref()
+                        .and_then(|f| f.get("tag"))
+                        .and_then(|v| v.as_str())
+                    {
+                        graphs.retain(|g| g.has_tag(tag));
+                    }
 
                     Ok(CatalogResponse::List {
                         items: graphs
@@ -927,6 +989,15 @@ impl CatalogProvider for GraphMetadataCatalog {
         }
     }
 
+    fn execute_read_only(&self, op: CatalogOperation) -> CatalogResult<CatalogResponse> {
+        match op {
+            CatalogOperation::Query { query_type, params } => self.query(query_type, &params),
+            _ => Err(CatalogError::NotSupported(
+                "Only query operations are supported in read-only mode".to_string(),
+            )),
+        }
+    }
+
     fn save(&self) -> CatalogResult<Vec<u8>> {
         let state = GraphMetadataState {
             graphs: self.graphs.clone(),
@@ -6,22 +6,52 @@
 //! Implements vendor-specific system procedures for accessing catalog metadata via ISO GQL CALL syntax:
 //! - CALL gql.list_schemas() YIELD schema_name
 //! - CALL gql.list_graphs() YIELD graph_name, schema_name
+//! - CALL gql.find_graphs([tag]) YIELD graph_name, schema_name, description, tags, owner
 //! - CALL gql.list_graph_types() YIELD graph_type_name, schema_name
+//! - CALL gql.list_labels([graph]) YIELD label, nodes
+//! - CALL gql.list_relationship_types([graph]) YIELD relationship_type, edges
+//! - CALL gql.list_property_keys([graph]) YIELD property_key, nodes, edges
+//! - CALL gql.list_indexes([graph]) YIELD index_name, kind, target, entries
 //! - CALL gql.list_functions() YIELD name
 //! - CALL gql.list_roles() YIELD role_name
 //! - CALL gql.list_users() YIELD user_name
 //! - CALL gql.show_session() YIELD session_id, user_name, schema_name, graph_name
+//! - CALL gql.session_history([limit]) YIELD sequence, statement, executed_at, duration_ms, status, error
 //! - CALL gql.cache_stats() YIELD cache_type, entries, hit_rate, memory_bytes
 //! - CALL gql.clear_cache([cache_type]) YIELD status, cleared_caches
+//! - CALL gql.warm_cache([graph]) YIELD graph_name, nodes, edges, node_labels, edge_labels
+//! - CALL gql.sample_data([domain], [scale]) YIELD domain, scale, nodes_created, edges_created, graph_name
+//! - CALL gql.decode_wal([from_lsn]) YIELD lsn, commit_lsn, transaction_id, logged_at, schema_name, graph_name, statement
+//! - CALL gql.query_fingerprint(query) YIELD fingerprint
+//! - CALL gql.pin_plan(fingerprint) YIELD fingerprint, query, estimated_cost, pinned_at, uses
+//! - CALL gql.unpin_plan(fingerprint) YIELD fingerprint, unpinned
+//! - CALL gql.list_pinned_plans() YIELD fingerprint, query, estimated_cost, pinned_at, uses
+//! - CALL gql.outbox_append(topic, payload, [graph]) YIELD graph_name, topic, offset
+//! - CALL gql.outbox_poll(consumer, [limit], [graph]) YIELD offset, topic, payload, created_at, transaction_id
+//! - CALL gql.outbox_ack(consumer, offset, [graph]) YIELD consumer, graph_name, acked_offset, pending
+//! - CALL gql.supported_features([category]) YIELD feature_id, category, description, supported
+//! - CALL gql.active_queries() YIELD query_id, session_id, fingerprint, query, started_at, elapsed_ms, operator
 
 use super::manager::CatalogManager;
 use super::operations::{CatalogOperation, CatalogResponse, EntityType, QueryType};
+use super::providers::graph_metadata::Graph;
+use crate::coordinator::bulk::{self, BulkEdge};
+use crate::coordinator::conformance;
+use crate::coordinator::stats;
+use crate::exec::active_queries::ActiveQueries;
 use crate::exec::error::ExecutionError;
 use crate::exec::result::{QueryResult, Row};
+use crate::exec::temp_space::TempSpace;
+use crate::plan::pinning::{self, PinnedPlan, PlanPins};
 use crate::session::SessionProvider;
+use crate::storage::graph_cache::GraphStats;
+use crate::storage::json_path;
+use crate::storage::outbox::PendingEvent;
+use crate::storage::sample_data::{SampleData, SampleDomain};
 use crate::storage::Value;
 use serde_json::json;
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::Arc;
 
 /// System catalog procedures registry (vendor-specific system procedures)
@@ -30,6 +60,10 @@ pub struct SystemProcedures {
     storage: Arc<crate::storage::StorageManager>,
     cache_manager: Option<Arc<crate::cache::CacheManager>>,
     session_provider: Option<Arc<dyn SessionProvider>>,
+    wal_dir: Option<PathBuf>,
+    plan_pins: Option<Arc<PlanPins>>,
+    temp_space: Option<Arc<TempSpace>>,
+    active_queries: Option<Arc<ActiveQueries>>,
 }
 
 impl SystemProcedures {
@@ -43,6 +77,10 @@ impl SystemProcedures {
             storage,
             cache_manager,
             session_provider: None,
+            wal_dir: None,
+            plan_pins: None,
+            temp_space: None,
+            active_queries: None,
         }
     }
 
@@ -52,6 +90,30 @@ impl SystemProcedures {
         self
     }
 
+    /// Set the WAL directory read by `gql.decode_wal`
+    pub fn with_wal_dir(mut self, wal_dir: PathBuf) -> Self {
+        self.wal_dir = Some(wal_dir);
+        self
+    }
+
+    /// Set the pinned plans managed by `gql.pin_plan` and `gql.unpin_plan`
+    pub fn with_plan_pins(mut self, plan_pins: Arc<PlanPins>) -> Self {
+        self.plan_pins = Some(plan_pins);
+        self
+    }
+
+    /// Set the temp space reported by `gql.storage_stats`
+    pub fn with_temp_space(mut self, temp_space: Arc<TempSpace>) -> Self {
+        self.temp_space = Some(temp_space);
+        self
+    }
+
+    /// Set the running statements listed by `gql.active_queries`
+    pub fn with_active_queries(mut self, active_queries: Arc<ActiveQueries>) -> Self {
+        self.active_queries = Some(active_queries);
+        self
+    }
+
     /// Execute a system procedure by name
     ///
     /// Only gql.* namespace procedures are supported.
@@ -76,7 +138,12 @@ impl SystemProcedures {
         match normalized_name.as_str() {
             "gql.list_schemas" => self.list_schemas(args, session_id),
             "gql.list_graphs" => self.list_graphs(args, session_id),
+            "gql.find_graphs" => self.find_graphs(args),
             "gql.list_graph_types" => self.list_graph_types(args),
+            "gql.list_labels" => self.list_labels(args, session_id),
+            "gql.list_relationship_types" => self.list_relationship_types(args, session_id),
+            "gql.list_property_keys" => self.list_property_keys(args, session_id),
+            "gql.list_indexes" => self.list_indexes(args, session_id),
             "gql.list_functions" => self.list_functions(args),
             "gql.list_roles" => self.list_roles(args),
             "gql.list_users" => self.list_users(args),
@@ -87,10 +154,29 @@ impl SystemProcedures {
                     None => Err(ExecutionError::RuntimeError("show_session requires an active session".to_string()))
                 }
             },
+            "gql.session_history" => match session_id {
+                Some(id) => self.session_history(args, id),
+                None => Err(ExecutionError::RuntimeError(
+                    "session_history requires an active session".to_string(),
+                )),
+            },
             "gql.cache_stats" => self.cache_stats(args),
             "gql.clear_cache" => self.clear_cache(args),
+            "gql.warm_cache" => self.warm_cache(args, session_id),
+            "gql.sample_data" => self.sample_data(args, session_id),
+            "gql.decode_wal" => self.decode_wal(args),
+            "gql.query_fingerprint" => self.query_fingerprint(args),
+            "gql.pin_plan" => self.pin_plan(args),
+            "gql.unpin_plan" => self.unpin_plan(args),
+            "gql.list_pinned_plans" => self.list_pinned_plans(args),
+            "gql.storage_stats" => self.storage_stats(args),
+            "gql.outbox_append" => self.outbox_append(args, session_id),
+            "gql.outbox_poll" => self.outbox_poll(args, session_id),
+            "gql.outbox_ack" => self.outbox_ack(args, session_id),
+            "gql.supported_features" => self.supported_features(args),
+            "gql.active_queries" => self.active_queries(args),
             _ => Err(ExecutionError::UnsupportedOperator(format!(
-                "System procedure not found or not supported: {}. Available system procedures: list_schemas, list_graphs, list_graph_types, list_functions, list_roles, list_users, authenticate_user, show_session, cache_stats, clear_cache",
+                "System procedure not found or not supported: {}. Available system procedures: list_schemas, list_graphs, find_graphs, list_graph_types, list_labels, list_relationship_types, list_property_keys, list_indexes, list_functions, list_roles, list_users, authenticate_user, show_session, session_history, cache_stats, clear_cache, warm_cache, sample_data, decode_wal, query_fingerprint, pin_plan, unpin_plan, list_pinned_plans, storage_stats, outbox_append, outbox_poll, outbox_ack, supported_features, active_queries",
                 procedure_name
             ))),
         }
@@ -109,14 +195,33 @@ impl SystemProcedures {
             normalized_name.as_str(),
             "gql.list_schemas"
                 | "gql.list_graphs"
+                | "gql.find_graphs"
                 | "gql.list_graph_types"
+                | "gql.list_labels"
+                | "gql.list_relationship_types"
+                | "gql.list_property_keys"
+                | "gql.list_indexes"
                 | "gql.list_functions"
                 | "gql.list_roles"
                 | "gql.list_users"
                 | "gql.authenticate_user"
                 | "gql.show_session"
+                | "gql.session_history"
                 | "gql.cache_stats"
                 | "gql.clear_cache"
+                | "gql.warm_cache"
+                | "gql.sample_data"
+                | "gql.decode_wal"
+                | "gql.query_fingerprint"
+                | "gql.pin_plan"
+                | "gql.unpin_plan"
+                | "gql.list_pinned_plans"
+                | "gql.storage_stats"
+                | "gql.outbox_append"
+                | "gql.outbox_poll"
+                | "gql.outbox_ack"
+                | "gql.supported_features"
+                | "gql.active_queries"
         )
     }
 
@@ -239,6 +344,7 @@ impl SystemProcedures {
             rows,
             variables: columns,
             execution_time_ms: 0,
+            operator_timings: None,
         })
     }
 
@@ -344,6 +450,102 @@ impl SystemProcedures {
             rows,
             variables: columns,
             execution_time_ms: 0,
+            operator_timings: None,
+        })
+    }
+
+    /// CALL gql.find_graphs([tag]) YIELD graph_name, schema_name, description, tags, owner
+    ///
+    /// Lists the graphs tagged with `tag` (ignoring case) together with the metadata
+    /// set by ALTER GRAPH, or every graph if no tag is given.
+    fn find_graphs(&self, args: Vec<Value>) -> Result<QueryResult, ExecutionError> {
+        if args.len() > 1 {
+            return Err(ExecutionError::RuntimeError(
+                "gql.find_graphs expects at most 1 argument: ([tag])".to_string(),
+            ));
+        }
+        let filters = match args.first() {
+            None | Some(Value::Null) => None,
+            Some(Value::String(tag)) => Some(json!({ "tag": tag })),
+            Some(other) => {
+                return Err(ExecutionError::RuntimeError(format!(
+                    "gql.find_graphs tag must be a string, got {:?}",
+                    other
+                )))
+            }
+        };
+
+        let response = self
+            .catalog_manager
+            .write()
+            .map_err(|_| {
+                ExecutionError::RuntimeError("Failed to acquire catalog manager lock".to_string())
+            })?
+            .execute(
+                "graph_metadata",
+                CatalogOperation::List {
+                    entity_type: EntityType::Graph,
+                    filters,
+                },
+            )
+            .map_err(|e| ExecutionError::CatalogError(format!("Failed to find graphs: {}", e)))?;
+
+        let mut graphs: Vec<Graph> = match response {
+            CatalogResponse::List { items } => items
+                .into_iter()
+                .filter_map(|item| serde_json::from_value(item).ok())
+                .collect(),
+            _ => Vec::new(),
+        };
+        graphs
+            .sort_by(|a, b| (&a.id.schema_name, &a.id.name).cmp(&(&b.id.schema_name, &b.id.name)));
+
+        let columns = vec![
+            "graph_name".to_string(),
+            "schema_name".to_string(),
+            "description".to_string(),
+            "tags".to_string(),
+            "owner".to_string(),
+        ];
+        let rows: Vec<Row> = graphs
+            .into_iter()
+            .map(|graph| {
+                let tags = graph.tags().into_iter().map(Value::String).collect();
+                Row::from_values(HashMap::from([
+                    (
+                        "graph_name".to_string(),
+                        Value::String(graph.id.name.clone()),
+                    ),
+                    (
+                        "schema_name".to_string(),
+                        Value::String(graph.id.schema_name.clone()),
+                    ),
+                    (
+                        "description".to_string(),
+                        graph
+                            .description
+                            .clone()
+                            .map(Value::String)
+                            .unwrap_or(Value::Null),
+                    ),
+                    ("tags".to_string(), Value::List(tags)),
+                    (
+                        "owner".to_string(),
+                        graph.owner().map(Value::String).unwrap_or(Value::Null),
+                    ),
+                ]))
+            })
+            .collect();
+
+        Ok(QueryResult {
+            rows_affected: rows.len(),
+            session_result: None,
+            warnings: Vec::new(),
+
+            rows,
+            variables: columns,
+            execution_time_ms: 0,
+            operator_timings: None,
         })
     }
 
@@ -403,6 +605,7 @@ impl SystemProcedures {
             rows,
             variables: columns,
             execution_time_ms: 0,
+            operator_timings: None,
         })
     }
 
@@ -430,6 +633,7 @@ impl SystemProcedures {
             rows,
             variables: columns,
             execution_time_ms: 0,
+            operator_timings: None,
         })
     }
 
@@ -605,9 +809,293 @@ impl SystemProcedures {
             rows,
             variables: columns,
             execution_time_ms: 0,
+            operator_timings: None,
+        })
+    }
+
+    /// CALL gql.session_history([limit]) YIELD sequence, statement, executed_at, duration_ms, status, error
+    ///
+    /// Lists the statements previously executed in the calling session, oldest first.
+    /// With a limit only the most recent `limit` statements are returned.
+    fn session_history(
+        &self,
+        args: Vec<Value>,
+        session_id: &str,
+    ) -> Result<QueryResult, ExecutionError> {
+        if args.len() > 1 {
+            return Err(ExecutionError::RuntimeError(
+                "gql.session_history expects at most 1 argument: ([limit])".to_string(),
+            ));
+        }
+        let limit = match args.first() {
+            None | Some(Value::Null) => None,
+            Some(Value::Number(n)) if *n >= 0.0 && n.fract() == 0.0 => Some(*n as usize),
+            Some(other) => {
+                return Err(ExecutionError::RuntimeError(format!(
+                    "gql.session_history limit must be a non-negative integer, got {:?}",
+                    other
+                )))
+            }
+        };
+
+        let session_arc = self
+            .session_provider
+            .as_ref()
+            .and_then(|provider| provider.get_session(session_id))
+            .ok_or_else(|| {
+                ExecutionError::RuntimeError(format!("Session not found: {}", session_id))
+            })?;
+        let session_state = session_arc.read().map_err(|_| {
+            ExecutionError::RuntimeError("Failed to acquire session lock".to_string())
+        })?;
+
+        let history = &session_state.history;
+        let skip = limit.map_or(0, |limit| history.len().saturating_sub(limit));
+        let rows: Vec<Row> = history
+            .entries()
+            .skip(skip)
+            .map(|entry| {
+                let mut row_values = HashMap::new();
+                row_values.insert("sequence".to_string(), Value::Number(entry.sequence as f64));
+                row_values.insert(
+                    "statement".to_string(),
+                    Value::String(entry.statement.clone()),
+                );
+                row_values.insert(
+                    "executed_at".to_string(),
+                    Value::DateTime(entry.executed_at),
+                );
+                row_values.insert(
+                    "duration_ms".to_string(),
+                    Value::Number(entry.duration_ms as f64),
+                );
+                row_values.insert(
+                    "status".to_string(),
+                    Value::String(if entry.succeeded() { "ok" } else { "error" }.to_string()),
+                );
+                row_values.insert(
+                    "error".to_string(),
+                    entry.error.clone().map_or(Value::Null, Value::String),
+                );
+                Row::from_values(row_values)
+            })
+            .collect();
+
+        Ok(QueryResult {
+            rows_affected: rows.len(),
+            session_result: None,
+            warnings: Vec::new(),
+            rows,
+            variables: vec![
+                "sequence".to_string(),
+                "statement".to_string(),
+                "executed_at".to_string(),
+                "duration_ms".to_string(),
+                "status".to_string(),
+                "error".to_string(),
+            ],
+            execution_time_ms: 0,
+            operator_timings: None,
+        })
+    }
+
+    /// CALL gql.decode_wal([from_lsn]) YIELD lsn, commit_lsn, transaction_id, logged_at, schema_name, graph_name, statement
+    ///
+    /// Lists the committed write statements recorded in the write-ahead log, in the
+    /// order they became durable, starting at log sequence number `from_lsn`.
+    fn decode_wal(&self, args: Vec<Value>) -> Result<QueryResult, ExecutionError> {
+        if args.len() > 1 {
+            return Err(ExecutionError::RuntimeError(
+                "gql.decode_wal expects at most 1 argument: ([from_lsn])".to_string(),
+            ));
+        }
+        let from_lsn = match args.first() {
+            None | Some(Value::Null) => 0,
+            Some(Value::Number(n)) if *n >= 0.0 && n.fract() == 0.0 => *n as u64,
+            Some(other) => {
+                return Err(ExecutionError::RuntimeError(format!(
+                    "gql.decode_wal from_lsn must be a non-negative integer, got {:?}",
+                    other
+                )))
+            }
+        };
+
+        let wal_dir = self.wal_dir.as_ref().ok_or_else(|| {
+            ExecutionError::RuntimeError("Write-ahead log is not available".to_string())
+        })?;
+        let statements = crate::txn::decode::decode_wal(wal_dir, from_lsn)
+            .map_err(ExecutionError::RuntimeError)?;
+
+        let text = |value: &Option<String>| value.clone().map_or(Value::Null, Value::String);
+        let rows: Vec<Row> = statements
+            .iter()
+            .map(|decoded| {
+                let mut row_values = HashMap::new();
+                row_values.insert("lsn".to_string(), Value::Number(decoded.lsn as f64));
+                row_values.insert(
+                    "commit_lsn".to_string(),
+                    Value::Number(decoded.commit_lsn as f64),
+                );
+                row_values.insert(
+                    "transaction_id".to_string(),
+                    decoded
+                        .transaction_id
+                        .map_or(Value::Null, |id| Value::String(id.to_string())),
+                );
+                row_values.insert("logged_at".to_string(), Value::DateTime(decoded.logged_at));
+                row_values.insert("schema_name".to_string(), text(&decoded.schema));
+                row_values.insert("graph_name".to_string(), text(&decoded.graph));
+                row_values.insert(
+                    "statement".to_string(),
+                    Value::String(decoded.statement.clone()),
+                );
+                Row::from_values(row_values)
+            })
+            .collect();
+
+        Ok(QueryResult {
+            rows_affected: rows.len(),
+            session_result: None,
+            warnings: Vec::new(),
+            rows,
+            variables: vec![
+                "lsn".to_string(),
+                "commit_lsn".to_string(),
+                "transaction_id".to_string(),
+                "logged_at".to_string(),
+                "schema_name".to_string(),
+                "graph_name".to_string(),
+                "statement".to_string(),
+            ],
+            execution_time_ms: 0,
+            operator_timings: None,
+        })
+    }
+
+    /// CALL gql.query_fingerprint(query) YIELD fingerprint
+    ///
+    /// Returns the fingerprint `gql.pin_plan` identifies the query by.
+    fn query_fingerprint(&self, args: Vec<Value>) -> Result<QueryResult, ExecutionError> {
+        let query = match args.as_slice() {
+            [Value::String(query)] => query,
+            _ => {
+                return Err(ExecutionError::RuntimeError(
+                    "gql.query_fingerprint expects 1 argument: (query)".to_string(),
+                ))
+            }
+        };
+
+        let mut row_values = HashMap::new();
+        row_values.insert(
+            "fingerprint".to_string(),
+            Value::String(pinning::fingerprint(query)),
+        );
+        Ok(QueryResult {
+            rows_affected: 1,
+            session_result: None,
+            warnings: Vec::new(),
+            rows: vec![Row::from_values(row_values)],
+            variables: vec!["fingerprint".to_string()],
+            execution_time_ms: 0,
+            operator_timings: None,
+        })
+    }
+
+    fn plan_pins(&self) -> Result<&PlanPins, ExecutionError> {
+        self.plan_pins.as_deref().ok_or_else(|| {
+            ExecutionError::RuntimeError("Plan pinning is not available".to_string())
+        })
+    }
+
+    fn fingerprint_arg(procedure: &str, args: &[Value]) -> Result<String, ExecutionError> {
+        match args {
+            [Value::String(fingerprint)] => Ok(fingerprint.clone()),
+            _ => Err(ExecutionError::RuntimeError(format!(
+                "{} expects 1 argument: (fingerprint)",
+                procedure
+            ))),
+        }
+    }
+
+    fn pinned_plans_result(pins: Vec<(PinnedPlan, u64)>) -> QueryResult {
+        let rows: Vec<Row> = pins
+            .into_iter()
+            .map(|(pin, uses)| {
+                let mut row_values = HashMap::new();
+                row_values.insert("fingerprint".to_string(), Value::String(pin.fingerprint));
+                row_values.insert("query".to_string(), Value::String(pin.query));
+                row_values.insert(
+                    "estimated_cost".to_string(),
+                    Value::Number(pin.plan.estimated_cost),
+                );
+                row_values.insert("pinned_at".to_string(), Value::DateTime(pin.pinned_at));
+                row_values.insert("uses".to_string(), Value::Number(uses as f64));
+                Row::from_values(row_values)
+            })
+            .collect();
+
+        QueryResult {
+            rows_affected: rows.len(),
+            session_result: None,
+            warnings: Vec::new(),
+            rows,
+            variables: vec![
+                "fingerprint".to_string(),
+                "query".to_string(),
+                "estimated_cost".to_string(),
+                "pinned_at".to_string(),
+                "uses".to_string(),
+            ],
+            execution_time_ms: 0,
+            operator_timings: None,
+        }
+    }
+
+    /// CALL gql.pin_plan(fingerprint) YIELD fingerprint, query, estimated_cost, pinned_at, uses
+    ///
+    /// Pins the plan the planner currently produces for a recently executed query.
+    /// Later executions of the query reuse it until it is unpinned or a schema
+    /// change invalidates it.
+    fn pin_plan(&self, args: Vec<Value>) -> Result<QueryResult, ExecutionError> {
+        let fingerprint = Self::fingerprint_arg("gql.pin_plan", &args)?;
+        let pin = self
+            .plan_pins()?
+            .pin(&fingerprint)
+            .map_err(ExecutionError::RuntimeError)?;
+        Ok(Self::pinned_plans_result(vec![(pin, 0)]))
+    }
+
+    /// CALL gql.unpin_plan(fingerprint) YIELD fingerprint, unpinned
+    fn unpin_plan(&self, args: Vec<Value>) -> Result<QueryResult, ExecutionError> {
+        let fingerprint = Self::fingerprint_arg("gql.unpin_plan", &args)?;
+        let unpinned = self.plan_pins()?.unpin(&fingerprint);
+
+        let mut row_values = HashMap::new();
+        row_values.insert("fingerprint".to_string(), Value::String(fingerprint));
+        row_values.insert("unpinned".to_string(), Value::Boolean(unpinned));
+        Ok(QueryResult {
+            rows_affected: 1,
+            session_result: None,
+            warnings: Vec::new(),
+            rows: vec![Row::from_values(row_values)],
+            variables: vec!["fingerprint".to_string(), "unpinned".to_string()],
+            execution_time_ms: 0,
+            operator_timings: None,
         })
     }
 
+    /// CALL gql.list_pinned_plans() YIELD fingerprint, query, estimated_cost, pinned_at, uses
+    ///
+    /// `uses` counts the executions that used the pinned plan since startup.
+    fn list_pinned_plans(&self, args: Vec<Value>) -> Result<QueryResult, ExecutionError> {
+        if !args.is_empty() {
+            return Err(ExecutionError::RuntimeError(
+                "gql.list_pinned_plans expects no arguments".to_string(),
+            ));
+        }
+        Ok(Self::pinned_plans_result(self.plan_pins()?.list()))
+    }
+
     /// CALL gql.list_roles() YIELD role_name, description, created_at
     /// Lists all roles in the security catalog
     fn list_roles(&self, _args: Vec<Value>) -> Result<QueryResult, ExecutionError> {
@@ -680,6 +1168,7 @@ impl SystemProcedures {
             rows,
             variables: columns,
             execution_time_ms: 0,
+            operator_timings: None,
         })
     }
 
@@ -780,6 +1269,7 @@ impl SystemProcedures {
             rows,
             variables: columns,
             execution_time_ms: 0,
+            operator_timings: None,
         })
     }
 
@@ -894,9 +1384,354 @@ impl SystemProcedures {
             rows,
             variables: columns,
             execution_time_ms: 0,
+            operator_timings: None,
         })
     }
 
+    /// CALL gql.storage_stats() YIELD temp_path, temp_quota_bytes, temp_used_bytes,
+    /// temp_peak_bytes, active_temp_queries, temp_files_created, temp_quota_rejections
+    /// CALL gql.active_queries() YIELD query_id, session_id, fingerprint, query, started_at, elapsed_ms, operator
+    ///
+    /// Lists the statements running right now, oldest first, with the physical
+    /// operator each is executing. Includes the statement making the call.
+    fn active_queries(&self, args: Vec<Value>) -> Result<QueryResult, ExecutionError> {
+        if !args.is_empty() {
+            return Err(ExecutionError::RuntimeError(
+                "gql.active_queries expects no arguments".to_string(),
+            ));
+        }
+        let queries = self
+            .active_queries
+            .as_ref()
+            .map(|queries| queries.list())
+            .unwrap_or_default();
+
+        let rows: Vec<Row> = queries
+            .into_iter()
+            .map(|query| {
+                Row::from_values([
+                    ("query_id".to_string(), Value::Number(query.query_id as f64)),
+                    ("session_id".to_string(), Value::String(query.session_id)),
+                    ("fingerprint".to_string(), Value::String(query.fingerprint)),
+                    ("query".to_string(), Value::String(query.query)),
+                    ("started_at".to_string(), Value::DateTime(query.started_at)),
+                    (
+                        "elapsed_ms".to_string(),
+                        Value::Number(query.elapsed.as_millis() as f64),
+                    ),
+                    (
+                        "operator".to_string(),
+                        query.operator.map_or(Value::Null, Value::String),
+                    ),
+                ])
+            })
+            .collect();
+
+        Ok(QueryResult {
+            rows_affected: rows.len(),
+            session_result: None,
+            warnings: Vec::new(),
+            rows,
+            variables: [
+                "query_id",
+                "session_id",
+                "fingerprint",
+                "query",
+                "started_at",
+                "elapsed_ms",
+                "operator",
+            ]
+            .iter()
+            .map(|column| column.to_string())
+            .collect(),
+            execution_time_ms: 0,
+            operator_timings: None,
+        })
+    }
+
+    fn storage_stats(&self, args: Vec<Value>) -> Result<QueryResult, ExecutionError> {
+        if !args.is_empty() {
+            return Err(ExecutionError::RuntimeError(
+                "gql.storage_stats expects no arguments".to_string(),
+            ));
+        }
+        let columns = [
+            "temp_path",
+            "temp_quota_bytes",
+            "temp_used_bytes",
+            "temp_peak_bytes",
+            "active_temp_queries",
+            "temp_files_created",
+            "temp_quota_rejections",
+        ];
+
+        // Executors built without a database directory have no temp space
+        let stats = self.temp_space.as_ref().map(|space| space.stats());
+        let values = match &stats {
+            Some(stats) => vec![
+                Value::String(stats.path.display().to_string()),
+                stats
+                    .quota_bytes
+                    .map_or(Value::Null, |quota| Value::Number(quota as f64)),
+                Value::Number(stats.used_bytes as f64),
+                Value::Number(stats.peak_bytes as f64),
+                Value::Number(stats.active_queries as f64),
+                Value::Number(stats.files_created as f64),
+                Value::Number(stats.quota_rejections as f64),
+            ],
+            None => vec![
+                Value::Null,
+                Value::Null,
+                Value::Number(0.0),
+                Value::Number(0.0),
+                Value::Number(0.0),
+                Value::Number(0.0),
+                Value::Number(0.0),
+            ],
+        };
+
+        let row_values: HashMap<String, Value> =
+            columns.iter().map(|c| c.to_string()).zip(values).collect();
+        Ok(Self::introspection_result(
+            vec![Row::from_values(row_values)],
+            &columns,
+        ))
+    }
+
+    /// CALL gql.supported_features([category]) YIELD feature_id, category, description, supported
+    ///
+    /// Returns the GQL feature matrix of this version, optionally limited to
+    /// one category. `graphlite conformance` checks the matrix against the
+    /// engine.
+    fn supported_features(&self, args: Vec<Value>) -> Result<QueryResult, ExecutionError> {
+        let category = match args.as_slice() {
+            [] => None,
+            [Value::String(category)] => Some(category.as_str()),
+            [other] => {
+                return Err(ExecutionError::RuntimeError(format!(
+                    "gql.supported_features category must be a string, got {:?}",
+                    other
+                )))
+            }
+            _ => {
+                return Err(ExecutionError::RuntimeError(
+                    "gql.supported_features expects at most 1 argument: ([category])".to_string(),
+                ))
+            }
+        };
+        let columns = ["feature_id", "category", "description", "supported"];
+
+        let rows = conformance::FEATURES
+            .iter()
+            .filter(|feature| category.is_none_or(|category| feature.category == category))
+            .map(|feature| {
+                let values = vec![
+                    Value::String(feature.id.to_string()),
+                    Value::String(feature.category.to_string()),
+                    Value::String(feature.description.to_string()),
+                    Value::Boolean(feature.supported),
+                ];
+                Row::from_values(columns.iter().map(|c| c.to_string()).zip(values))
+            })
+            .collect();
+        Ok(Self::introspection_result(rows, &columns))
+    }
+
+    /// CALL gql.outbox_append(topic, payload, [graph]) YIELD graph_name, topic, offset
+    ///
+    /// Appends an event to the outbox of `graph` (default: the current graph).
+    /// Inside a transaction the event is written when the transaction commits
+    /// and `offset` is null; otherwise it is written at once.
+    fn outbox_append(
+        &self,
+        args: Vec<Value>,
+        session_id: Option<&str>,
+    ) -> Result<QueryResult, ExecutionError> {
+        if !(2..=3).contains(&args.len()) {
+            return Err(ExecutionError::RuntimeError(
+                "gql.outbox_append expects 2 or 3 arguments: (topic, payload, [graph])".to_string(),
+            ));
+        }
+        let topic = match &args[0] {
+            Value::String(topic) if !topic.is_empty() => topic.clone(),
+            other => {
+                return Err(ExecutionError::RuntimeError(format!(
+                    "gql.outbox_append topic must be a non-empty string, got {:?}",
+                    other
+                )))
+            }
+        };
+        let payload = json_path::value_to_json(&args[1]).ok_or_else(|| {
+            ExecutionError::RuntimeError(format!(
+                "gql.outbox_append payload must be a JSON document, list or scalar, got {}",
+                args[1].type_name()
+            ))
+        })?;
+        let graph_path = self.outbox_graph("gql.outbox_append", args.get(2), session_id)?;
+        let event = PendingEvent {
+            graph_path: graph_path.clone(),
+            topic: topic.clone(),
+            payload,
+        };
+
+        let transaction_state = session_id
+            .and_then(|id| self.session_provider.as_ref()?.get_session(id))
+            .and_then(|session| Some(session.read().ok()?.transaction_state.clone()));
+        let offset = match transaction_state {
+            Some(state) if state.has_active_transaction()? => {
+                state.append_event(event)?;
+                Value::Null
+            }
+            _ => {
+                let offsets = self.storage.append_outbox_events(vec![event], None)?;
+                Value::Number(offsets[0] as f64)
+            }
+        };
+
+        let row_values = HashMap::from([
+            ("graph_name".to_string(), Value::String(graph_path)),
+            ("topic".to_string(), Value::String(topic)),
+            ("offset".to_string(), offset),
+        ]);
+        Ok(Self::introspection_result(
+            vec![Row::from_values(row_values)],
+            &["graph_name", "topic", "offset"],
+        ))
+    }
+
+    /// CALL gql.outbox_poll(consumer, [limit], [graph]) YIELD offset, topic, payload,
+    /// created_at, transaction_id
+    ///
+    /// Returns up to `limit` (default 100) committed events after the consumer's
+    /// acknowledged offset, oldest first. Polling does not move the consumer;
+    /// `gql.outbox_ack` does.
+    fn outbox_poll(
+        &self,
+        args: Vec<Value>,
+        session_id: Option<&str>,
+    ) -> Result<QueryResult, ExecutionError> {
+        if args.is_empty() || args.len() > 3 {
+            return Err(ExecutionError::RuntimeError(
+                "gql.outbox_poll expects 1 to 3 arguments: (consumer, [limit], [graph])"
+                    .to_string(),
+            ));
+        }
+        let consumer = Self::consumer_arg("gql.outbox_poll", &args[0])?;
+        let limit = match args.get(1) {
+            None | Some(Value::Null) => 100,
+            Some(Value::Number(n)) if *n >= 1.0 && n.fract() == 0.0 => *n as usize,
+            Some(other) => {
+                return Err(ExecutionError::RuntimeError(format!(
+                    "gql.outbox_poll limit must be a positive integer, got {:?}",
+                    other
+                )))
+            }
+        };
+        let graph_path = self.outbox_graph("gql.outbox_poll", args.get(2), session_id)?;
+
+        let rows = self
+            .storage
+            .poll_outbox(&graph_path, &consumer, limit)?
+            .into_iter()
+            .map(|event| {
+                Row::from_values(HashMap::from([
+                    ("offset".to_string(), Value::Number(event.offset as f64)),
+                    ("topic".to_string(), Value::String(event.topic)),
+                    (
+                        "payload".to_string(),
+                        json_path::json_to_value(&event.payload),
+                    ),
+                    ("created_at".to_string(), Value::DateTime(event.created_at)),
+                    (
+                        "transaction_id".to_string(),
+                        event
+                            .transaction_id
+                            .map_or(Value::Null, |id| Value::String(id.to_string())),
+                    ),
+                ]))
+            })
+            .collect();
+        Ok(Self::introspection_result(
+            rows,
+            &["offset", "topic", "payload", "created_at", "transaction_id"],
+        ))
+    }
+
+    /// CALL gql.outbox_ack(consumer, offset, [graph]) YIELD consumer, graph_name,
+    /// acked_offset, pending
+    ///
+    /// Records that the consumer has processed the events up to `offset`. An
+    /// offset at or below the consumer's current one is ignored, so retried
+    /// acknowledgements are safe; offset 0 registers a consumer without
+    /// acknowledging anything.
+    fn outbox_ack(
+        &self,
+        args: Vec<Value>,
+        session_id: Option<&str>,
+    ) -> Result<QueryResult, ExecutionError> {
+        if !(2..=3).contains(&args.len()) {
+            return Err(ExecutionError::RuntimeError(
+                "gql.outbox_ack expects 2 or 3 arguments: (consumer, offset, [graph])".to_string(),
+            ));
+        }
+        let consumer = Self::consumer_arg("gql.outbox_ack", &args[0])?;
+        let offset = match &args[1] {
+            Value::Number(n) if *n >= 0.0 && n.fract() == 0.0 => *n as u64,
+            other => {
+                return Err(ExecutionError::RuntimeError(format!(
+                    "gql.outbox_ack offset must be a non-negative integer, got {:?}",
+                    other
+                )))
+            }
+        };
+        let graph_path = self.outbox_graph("gql.outbox_ack", args.get(2), session_id)?;
+
+        let ack = self
+            .storage
+            .ack_outbox(&graph_path, &consumer, offset)
+            .map_err(|e| ExecutionError::RuntimeError(e.to_string()))?;
+        let row_values = HashMap::from([
+            ("consumer".to_string(), Value::String(consumer)),
+            ("graph_name".to_string(), Value::String(graph_path)),
+            (
+                "acked_offset".to_string(),
+                Value::Number(ack.acked_offset as f64),
+            ),
+            ("pending".to_string(), Value::Number(ack.pending as f64)),
+        ]);
+        Ok(Self::introspection_result(
+            vec![Row::from_values(row_values)],
+            &["consumer", "graph_name", "acked_offset", "pending"],
+        ))
+    }
+
+    /// Graph whose outbox an outbox procedure works on
+    fn outbox_graph(
+        &self,
+        procedure: &str,
+        graph: Option<&Value>,
+        session_id: Option<&str>,
+    ) -> Result<String, ExecutionError> {
+        let graph_path = self.resolve_graph(procedure, graph, session_id)?;
+        if !self.graph_in_catalog(&graph_path)? {
+            return Err(ExecutionError::CatalogError(format!(
+                "Graph does not exist: {}",
+                graph_path
+            )));
+        }
+        Ok(graph_path)
+    }
+
+    fn consumer_arg(procedure: &str, value: &Value) -> Result<String, ExecutionError> {
+        match value {
+            Value::String(consumer) if !consumer.is_empty() => Ok(consumer.clone()),
+            other => Err(ExecutionError::RuntimeError(format!(
+                "{} consumer must be a non-empty string, got {:?}",
+                procedure, other
+            ))),
+        }
+    }
+
     /// CALL gql.clear_cache([cache_type]) YIELD status, cleared_caches
     fn clear_cache(&self, args: Vec<Value>) -> Result<QueryResult, ExecutionError> {
         let cache_type = if !args.is_empty() {
@@ -965,6 +1800,427 @@ impl SystemProcedures {
             rows,
             variables: columns,
             execution_time_ms: 0,
+            operator_timings: None,
+        })
+    }
+
+    /// CALL gql.warm_cache([graph]) YIELD graph_name, nodes, edges, node_labels, edge_labels
+    /// Loads graphs into the storage cache ahead of the first query that needs them
+    ///
+    /// With a graph path (absolute, or relative to the session schema) only that graph
+    /// is loaded. Without one, the most frequently used graphs tracked by the cache
+    /// manager are loaded, which is also what happens when a database is opened.
+    fn warm_cache(
+        &self,
+        args: Vec<Value>,
+        session_id: Option<&str>,
+    ) -> Result<QueryResult, ExecutionError> {
+        if args.len() > 1 {
+            return Err(ExecutionError::RuntimeError(
+                "gql.warm_cache expects at most 1 argument: ([graph])".to_string(),
+            ));
+        }
+
+        let warmed = match args.first() {
+            None | Some(Value::Null) => self.warm_hot_set(),
+            Some(Value::String(graph)) => {
+                let graph_path = self.qualify_graph_name(graph, session_id)?;
+                let stats = self.warm_graph(&graph_path)?.ok_or_else(|| {
+                    ExecutionError::CatalogError(format!("Graph does not exist: {}", graph_path))
+                })?;
+                if let Some(cache_manager) = &self.cache_manager {
+                    cache_manager.record_graph_access(&graph_path);
+                    if let Err(e) = cache_manager.save_hot_set(&self.storage) {
+                        log::warn!("{}", e);
+                    }
+                }
+                vec![(graph_path, stats)]
+            }
+            Some(other) => {
+                return Err(ExecutionError::RuntimeError(format!(
+                    "gql.warm_cache graph must be a string, got {:?}",
+                    other
+                )))
+            }
+        };
+
+        let columns = vec![
+            "graph_name".to_string(),
+            "nodes".to_string(),
+            "edges".to_string(),
+            "node_labels".to_string(),
+            "edge_labels".to_string(),
+        ];
+        let rows: Vec<Row> = warmed
+            .into_iter()
+            .map(|(graph_name, stats)| {
+                let mut row_values = HashMap::new();
+                row_values.insert("graph_name".to_string(), Value::String(graph_name));
+                row_values.insert("nodes".to_string(), Value::Number(stats.node_count as f64));
+                row_values.insert("edges".to_string(), Value::Number(stats.edge_count as f64));
+                row_values.insert(
+                    "node_labels".to_string(),
+                    Value::Number(stats.node_label_count as f64),
+                );
+                row_values.insert(
+                    "edge_labels".to_string(),
+                    Value::Number(stats.edge_label_count as f64),
+                );
+                Row::from_values(row_values)
+            })
+            .collect();
+
+        Ok(QueryResult {
+            rows_affected: rows.len(),
+            session_result: None,
+            warnings: Vec::new(),
+
+            rows,
+            variables: columns,
+            execution_time_ms: 0,
+            operator_timings: None,
+        })
+    }
+
+    /// Load the cache manager's hot set into the storage cache
+    ///
+    /// Access metadata persisted by a previous run is merged in first. Graphs that
+    /// have since been dropped are forgotten instead of loaded.
+    pub fn warm_hot_set(&self) -> Vec<(String, GraphStats)> {
+        let Some(cache_manager) = &self.cache_manager else {
+            return Vec::new();
+        };
+        if let Err(e) = cache_manager.load_hot_set(&self.storage) {
+            log::warn!("{}", e);
+        }
+
+        let mut warmed = Vec::new();
+        for hot_graph in cache_manager.hot_graphs() {
+            match self.warm_graph(&hot_graph.name) {
+                Ok(Some(stats)) => warmed.push((hot_graph.name, stats)),
+                Ok(None) => cache_manager.forget_graph(&hot_graph.name),
+                Err(e) => log::warn!("Failed to warm graph '{}': {}", hot_graph.name, e),
+            }
+        }
+        warmed
+    }
+
+    /// Load one graph into the storage cache, returning `None` if it is not in the catalog
+    fn warm_graph(&self, graph_path: &str) -> Result<Option<GraphStats>, ExecutionError> {
+        if !self.graph_in_catalog(graph_path)? {
+            return Ok(None);
+        }
+
+        Ok(self
+            .storage
+            .get_graph(graph_path)?
+            .map(|graph| graph.stats()))
+    }
+
+    /// Whether the catalog has a graph at `graph_path`
+    ///
+    /// Storage loads an unknown path as an empty graph, so existence is decided here.
+    fn graph_in_catalog(&self, graph_path: &str) -> Result<bool, ExecutionError> {
+        let mut catalog_manager = self.catalog_manager.write().map_err(|_| {
+            ExecutionError::RuntimeError("Failed to acquire catalog manager lock".to_string())
+        })?;
+        Ok(matches!(
+            catalog_manager.execute(
+                "graph_metadata",
+                CatalogOperation::Query {
+                    query_type: QueryType::GetGraph,
+                    params: json!({ "name": graph_path.trim_start_matches('/') }),
+                },
+            ),
+            Ok(CatalogResponse::Query { .. })
+        ))
+    }
+
+    /// CALL gql.list_labels([graph]) YIELD label, nodes
+    fn list_labels(
+        &self,
+        args: Vec<Value>,
+        session_id: Option<&str>,
+    ) -> Result<QueryResult, ExecutionError> {
+        let graph = self.introspected_graph("gql.list_labels", &args, session_id)?;
+        let rows = graph
+            .label_counts()
+            .into_iter()
+            .map(|(label, nodes)| {
+                Row::from_values(HashMap::from([
+                    ("label".to_string(), Value::String(label)),
+                    ("nodes".to_string(), Value::Number(nodes as f64)),
+                ]))
+            })
+            .collect();
+        Ok(Self::introspection_result(rows, &["label", "nodes"]))
+    }
+
+    /// CALL gql.list_relationship_types([graph]) YIELD relationship_type, edges
+    fn list_relationship_types(
+        &self,
+        args: Vec<Value>,
+        session_id: Option<&str>,
+    ) -> Result<QueryResult, ExecutionError> {
+        let graph = self.introspected_graph("gql.list_relationship_types", &args, session_id)?;
+        let rows = graph
+            .relationship_type_counts()
+            .into_iter()
+            .map(|(relationship_type, edges)| {
+                Row::from_values(HashMap::from([
+                    (
+                        "relationship_type".to_string(),
+                        Value::String(relationship_type),
+                    ),
+                    ("edges".to_string(), Value::Number(edges as f64)),
+                ]))
+            })
+            .collect();
+        Ok(Self::introspection_result(
+            rows,
+            &["relationship_type", "edges"],
+        ))
+    }
+
+    /// CALL gql.list_property_keys([graph]) YIELD property_key, nodes, edges
+    fn list_property_keys(
+        &self,
+        args: Vec<Value>,
+        session_id: Option<&str>,
+    ) -> Result<QueryResult, ExecutionError> {
+        let graph = self.introspected_graph("gql.list_property_keys", &args, session_id)?;
+        let rows = graph
+            .property_key_counts()
+            .into_iter()
+            .map(|(property_key, nodes, edges)| {
+                Row::from_values(HashMap::from([
+                    ("property_key".to_string(), Value::String(property_key)),
+                    ("nodes".to_string(), Value::Number(nodes as f64)),
+                    ("edges".to_string(), Value::Number(edges as f64)),
+                ]))
+            })
+            .collect();
+        Ok(Self::introspection_result(
+            rows,
+            &["property_key", "nodes", "edges"],
+        ))
+    }
+
+    /// CALL gql.list_indexes([graph]) YIELD index_name, kind, target, entries
+    fn list_indexes(
+        &self,
+        args: Vec<Value>,
+        session_id: Option<&str>,
+    ) -> Result<QueryResult, ExecutionError> {
+        let graph = self.introspected_graph("gql.list_indexes", &args, session_id)?;
+        let rows = stats::index_statistics(&graph)
+            .into_iter()
+            .map(|index| {
+                Row::from_values(HashMap::from([
+                    ("index_name".to_string(), Value::String(index.name)),
+                    ("kind".to_string(), Value::String(index.kind.to_string())),
+                    ("target".to_string(), Value::String(index.target)),
+                    ("entries".to_string(), Value::Number(index.entries as f64)),
+                ]))
+            })
+            .collect();
+        Ok(Self::introspection_result(
+            rows,
+            &["index_name", "kind", "target", "entries"],
+        ))
+    }
+
+    /// Load the graph an introspection procedure describes: its `[graph]` argument
+    /// (absolute, or relative to the session schema), or the session's current graph
+    fn introspected_graph(
+        &self,
+        procedure: &str,
+        args: &[Value],
+        session_id: Option<&str>,
+    ) -> Result<crate::storage::GraphCache, ExecutionError> {
+        if args.len() > 1 {
+            return Err(ExecutionError::RuntimeError(format!(
+                "{} expects at most 1 argument: ([graph])",
+                procedure
+            )));
+        }
+
+        let graph_path = self.resolve_graph(procedure, args.first(), session_id)?;
+        let graph = if self.graph_in_catalog(&graph_path)? {
+            self.storage.get_graph(&graph_path)?
+        } else {
+            None
+        };
+        graph.ok_or_else(|| {
+            ExecutionError::CatalogError(format!("Graph does not exist: {}", graph_path))
+        })
+    }
+
+    /// Full path of a procedure's `[graph]` argument (absolute, or relative to
+    /// the session schema), or of the session's current graph
+    fn resolve_graph(
+        &self,
+        procedure: &str,
+        graph: Option<&Value>,
+        session_id: Option<&str>,
+    ) -> Result<String, ExecutionError> {
+        match graph {
+            None | Some(Value::Null) => session_id
+                .and_then(|id| self.session_provider.as_ref()?.get_session(id))
+                .and_then(|session| session.read().ok()?.current_graph.clone())
+                .ok_or_else(|| {
+                    ExecutionError::RuntimeError(format!(
+                        "{} requires a graph argument or a current graph. Use SESSION SET GRAPH first",
+                        procedure
+                    ))
+                }),
+            Some(Value::String(graph)) => self.qualify_graph_name(graph, session_id),
+            Some(other) => Err(ExecutionError::RuntimeError(format!(
+                "{} graph must be a string, got {:?}",
+                procedure, other
+            ))),
+        }
+    }
+
+    fn introspection_result(rows: Vec<Row>, columns: &[&str]) -> QueryResult {
+        QueryResult {
+            rows_affected: rows.len(),
+            session_result: None,
+            warnings: Vec::new(),
+            rows,
+            variables: columns.iter().map(|c| c.to_string()).collect(),
+            execution_time_ms: 0,
+            operator_timings: None,
+        }
+    }
+
+    /// Turn a graph name relative to the session schema into a full path
+    fn qualify_graph_name(
+        &self,
+        graph: &str,
+        session_id: Option<&str>,
+    ) -> Result<String, ExecutionError> {
+        if graph.starts_with('/') {
+            return Ok(graph.to_string());
+        }
+
+        let schema = session_id
+            .and_then(|id| self.session_provider.as_ref()?.get_session(id))
+            .and_then(|session| session.read().ok()?.current_schema.clone())
+            .ok_or_else(|| {
+                ExecutionError::RuntimeError(format!(
+                    "Graph '{}' is not a full path and no session schema is set",
+                    graph
+                ))
+            })?;
+        Ok(format!("/{}/{}", schema.trim_matches('/'), graph))
+    }
+
+    /// CALL gql.sample_data([domain], [scale]) YIELD domain, scale, nodes_created, edges_created, graph_name
+    /// Loads a generated dataset into the session's current graph
+    ///
+    /// `domain` is one of fraud (default), social, pharma or supply_chain; `scale`
+    /// (default 1.0) multiplies every node and edge count. Generation is deterministic,
+    /// so loading the same domain and scale twice adds nothing new.
+    fn sample_data(
+        &self,
+        args: Vec<Value>,
+        session_id: Option<&str>,
+    ) -> Result<QueryResult, ExecutionError> {
+        if args.len() > 2 {
+            return Err(ExecutionError::RuntimeError(
+                "gql.sample_data expects at most 2 arguments: ([domain], [scale])".to_string(),
+            ));
+        }
+
+        let domain = match args.first() {
+            None | Some(Value::Null) => SampleDomain::default(),
+            Some(Value::String(s)) => s.parse().map_err(ExecutionError::RuntimeError)?,
+            Some(other) => {
+                return Err(ExecutionError::RuntimeError(format!(
+                    "gql.sample_data domain must be a string, got {:?}",
+                    other
+                )))
+            }
+        };
+        let scale = match args.get(1) {
+            None | Some(Value::Null) => 1.0,
+            Some(Value::Number(n)) => *n,
+            Some(other) => {
+                return Err(ExecutionError::RuntimeError(format!(
+                    "gql.sample_data scale must be a number, got {:?}",
+                    other
+                )))
+            }
+        };
+
+        let graph_name = session_id
+            .and_then(|id| self.session_provider.as_ref()?.get_session(id))
+            .and_then(|session| session.read().ok()?.current_graph.clone())
+            .ok_or_else(|| {
+                ExecutionError::RuntimeError(
+                    "gql.sample_data requires a current graph. Use SESSION SET GRAPH first"
+                        .to_string(),
+                )
+            })?;
+
+        let sample = SampleData::generate(domain, scale).map_err(ExecutionError::RuntimeError)?;
+
+        let write_lock = self.storage.graph_write_lock(&graph_name);
+        let _writing = write_lock.lock().unwrap_or_else(|e| e.into_inner());
+        let mut graph = self.storage.get_graph(&graph_name)?.ok_or_else(|| {
+            ExecutionError::StorageError(format!("Graph not found: {}", graph_name))
+        })?;
+        let (ids, nodes_created) =
+            bulk::insert_nodes(&mut graph, sample.nodes).map_err(ExecutionError::RuntimeError)?;
+        let edges = sample
+            .edges
+            .into_iter()
+            .map(|edge| BulkEdge {
+                from_node: ids[edge.from].clone(),
+                to_node: ids[edge.to].clone(),
+                label: edge.label,
+                properties: edge.properties,
+            })
+            .collect();
+        let edges_created =
+            bulk::insert_edges(&mut graph, edges).map_err(ExecutionError::RuntimeError)?;
+
+        if nodes_created + edges_created > 0 {
+            self.storage.save_graph(&graph_name, graph).map_err(|e| {
+                ExecutionError::StorageError(format!("Failed to save graph: {}", e))
+            })?;
+        }
+
+        let columns = vec![
+            "domain".to_string(),
+            "scale".to_string(),
+            "nodes_created".to_string(),
+            "edges_created".to_string(),
+            "graph_name".to_string(),
+        ];
+        let mut row_values = HashMap::new();
+        row_values.insert("domain".to_string(), Value::String(domain.to_string()));
+        row_values.insert("scale".to_string(), Value::Number(scale));
+        row_values.insert(
+            "nodes_created".to_string(),
+            Value::Number(nodes_created as f64),
+        );
+        row_values.insert(
+            "edges_created".to_string(),
+            Value::Number(edges_created as f64),
+        );
+        row_values.insert("graph_name".to_string(), Value::String(graph_name));
+
+        Ok(QueryResult {
+            rows_affected: nodes_created + edges_created,
+            session_result: None,
+            warnings: Vec::new(),
+
+            rows: vec![Row::from_values(row_values)],
+            variables: columns,
+            execution_time_ms: 0,
+            operator_timings: None,
         })
     }
 
@@ -1066,6 +2322,7 @@ impl SystemProcedures {
                     rows,
                     variables: columns,
                     execution_time_ms: 0,
+                    operator_timings: None,
                 })
             } else {
                 // Authentication failed
@@ -1093,13 +2350,32 @@ pub fn is_system_procedure(procedure_name: &str) -> bool {
         normalized.as_str(),
         "gql.list_schemas"
             | "gql.list_graphs"
+            | "gql.find_graphs"
             | "gql.list_graph_types"
+            | "gql.list_labels"
+            | "gql.list_relationship_types"
+            | "gql.list_property_keys"
+            | "gql.list_indexes"
             | "gql.list_functions"
             | "gql.list_roles"
             | "gql.list_users"
             | "gql.authenticate_user"
             | "gql.show_session"
+            | "gql.session_history"
             | "gql.cache_stats"
             | "gql.clear_cache"
+            | "gql.warm_cache"
+            | "gql.sample_data"
+            | "gql.decode_wal"
+            | "gql.query_fingerprint"
+            | "gql.pin_plan"
+            | "gql.unpin_plan"
+            | "gql.list_pinned_plans"
+            | "gql.storage_stats"
+            | "gql.outbox_append"
+            | "gql.outbox_poll"
+            | "gql.outbox_ack"
+            | "gql.supported_features"
+            | "gql.active_queries"
     )
 }
@@ -0,0 +1,186 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! Bulk loading of nodes and edges
+//!
+//! `QueryCoordinator::bulk_insert_nodes` and `QueryCoordinator::bulk_insert_edges`
+//! write pre-built records straight into the session's current graph, skipping
+//! the parse/plan cycle that an `INSERT` statement per row would cost.
+
+use crate::storage::types::GraphError;
+use crate::storage::{Edge, GraphCache, Node, Value};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+/// A node to be bulk loaded
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BulkNode {
+    pub labels: Vec<String>,
+    pub properties: HashMap<String, Value>,
+}
+
+/// An edge to be bulk loaded between two existing node ids
+#[derive(Debug, Clone, PartialEq)]
+pub struct BulkEdge {
+    pub from_node: String,
+    pub to_node: String,
+    pub label: String,
+    pub properties: HashMap<String, Value>,
+}
+
+/// Add nodes to `graph`, returning the storage id of every input node (in order)
+/// and the number of nodes actually created
+///
+/// Ids are content hashes, so a node identical to one already in the graph maps
+/// onto the existing id instead of being created twice.
+pub(crate) fn insert_nodes(
+    graph: &mut GraphCache,
+    nodes: Vec<BulkNode>,
+) -> Result<(Vec<String>, usize), String> {
+    let mut ids = Vec::with_capacity(nodes.len());
+    let mut created = 0usize;
+
+    for bulk_node in nodes {
+        let id = node_content_id(&bulk_node.labels, &bulk_node.properties);
+        let node = Node {
+            id: id.clone(),
+            labels: bulk_node.labels,
+            properties: bulk_node.properties,
+        };
+        match graph.add_node(node) {
+            Ok(()) => created += 1,
+            Err(GraphError::NodeAlreadyExists(_)) => {
+                log::debug!("Bulk insert: node '{}' already exists, skipping", id);
+            }
+            Err(e) => return Err(format!("Failed to add node '{}': {}", id, e)),
+        }
+        ids.push(id);
+    }
+
+    Ok((ids, created))
+}
+
+/// Add edges to `graph`, returning the number of edges actually created
+///
+/// Both endpoints must already exist; duplicate edges are skipped.
+pub(crate) fn insert_edges(graph: &mut GraphCache, edges: Vec<BulkEdge>) -> Result<usize, String> {
+    let mut created = 0usize;
+
+    for bulk_edge in edges {
+        let id = edge_content_id(
+            &bulk_edge.from_node,
+            &bulk_edge.to_node,
+            &bulk_edge.label,
+            &bulk_edge.properties,
+        );
+        let edge = Edge {
+            id: id.clone(),
+            from_node: bulk_edge.from_node,
+            to_node: bulk_edge.to_node,
+            label: bulk_edge.label,
+            properties: bulk_edge.properties,
+        };
+        match graph.add_edge(edge) {
+            Ok(()) => created += 1,
+            Err(GraphError::EdgeAlreadyExists(_)) => {
+                log::debug!("Bulk insert: edge '{}' already exists, skipping", id);
+            }
+            Err(e) => return Err(format!("Failed to add edge '{}': {}", id, e)),
+        }
+    }
+
+    Ok(created)
+}
+
+/// Generate a content-based hash ID for a node
+fn node_content_id(labels: &[String], properties: &HashMap<String, Value>) -> String {
+    let mut hasher = DefaultHasher::new();
+
+    let mut sorted_labels = labels.to_vec();
+    sorted_labels.sort();
+    for label in &sorted_labels {
+        label.hash(&mut hasher);
+    }
+    hash_properties(properties, &mut hasher);
+
+    format!("node_{:x}", hasher.finish())
+}
+
+/// Generate a content-based hash ID for an edge
+fn edge_content_id(
+    from_node_id: &str,
+    to_node_id: &str,
+    label: &str,
+    properties: &HashMap<String, Value>,
+) -> String {
+    let mut hasher = DefaultHasher::new();
+
+    from_node_id.hash(&mut hasher);
+    to_node_id.hash(&mut hasher);
+    label.hash(&mut hasher);
+    hash_properties(properties, &mut hasher);
+
+    format!("edge_{:x}", hasher.finish())
+}
+
+/// Hash properties sorted by key so the id does not depend on map ordering
+fn hash_properties(properties: &HashMap<String, Value>, hasher: &mut DefaultHasher) {
+    let mut sorted_properties: Vec<_> = properties.iter().collect();
+    sorted_properties.sort_by_key(|(k, _)| *k);
+    for (key, value) in sorted_properties {
+        key.hash(hasher);
+        format!("{:?}", value).hash(hasher);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn person(name: &str) -> BulkNode {
+        BulkNode {
+            labels: vec!["Person".to_string()],
+            properties: HashMap::from([("name".to_string(), Value::String(name.to_string()))]),
+        }
+    }
+
+    #[test]
+    fn test_insert_nodes_dedupes_identical_content() {
+        let mut graph = GraphCache::new();
+        let (ids, created) = insert_nodes(
+            &mut graph,
+            vec![person("Alice"), person("Bob"), person("Alice")],
+        )
+        .unwrap();
+
+        assert_eq!(created, 2);
+        assert_eq!(ids.len(), 3);
+        assert_eq!(ids[0], ids[2]);
+        assert_ne!(ids[0], ids[1]);
+        assert_eq!(graph.node_count().unwrap(), 2);
+    }
+
+    #[test]
+    fn test_insert_edges_requires_existing_endpoints() {
+        let mut graph = GraphCache::new();
+        let (ids, _) = insert_nodes(&mut graph, vec![person("Alice"), person("Bob")]).unwrap();
+
+        let knows = BulkEdge {
+            from_node: ids[0].clone(),
+            to_node: ids[1].clone(),
+            label: "KNOWS".to_string(),
+            properties: HashMap::new(),
+        };
+        assert_eq!(
+            insert_edges(&mut graph, vec![knows.clone(), knows.clone()]).unwrap(),
+            1
+        );
+
+        let dangling = BulkEdge {
+            to_node: "node_missing".to_string(),
+            ..knows
+        };
+        assert!(insert_edges(&mut graph, vec![dangling]).is_err());
+    }
+}
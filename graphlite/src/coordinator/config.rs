@@ -0,0 +1,155 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! Database configuration for embedders
+//!
+//! `DatabaseConfig` collects the engine knobs that are otherwise fixed by
+//! `QueryCoordinator::from_path` (storage backend, cache budget, read-only access,
+//! logging) so that hosts can tune the engine without environment variables.
+
+use crate::cache::CacheConfig;
+use crate::exec::temp_space::DEFAULT_TEMP_SPACE_QUOTA_BYTES;
+use crate::session::history::DEFAULT_SESSION_HISTORY_SIZE;
+use crate::session::SessionMode;
+use crate::storage::StorageType;
+use crate::txn::spill::DEFAULT_UNDO_SPILL_THRESHOLD;
+use std::time::Duration;
+
+/// Default `DatabaseConfig::min_free_disk_bytes`: room for a few WAL segments and a storage flush
+pub const DEFAULT_MIN_FREE_DISK_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Configuration used by `QueryCoordinator::from_path_with_config`
+///
+/// `DatabaseConfig::default()` matches the behaviour of `QueryCoordinator::from_path`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DatabaseConfig {
+    /// Persistent storage backend
+    pub storage_type: StorageType,
+    /// Session pool sharing mode
+    pub session_mode: SessionMode,
+    /// Reject statements that modify data or the catalog
+    pub read_only: bool,
+    /// Total memory budget for the query/plan caches in bytes (`None` = engine default, `Some(0)` = caching disabled)
+    pub cache_size_bytes: Option<usize>,
+    /// Upper bound on engine-managed memory in bytes; the cache budget is clamped to it (`None` = unlimited)
+    pub max_memory_bytes: Option<usize>,
+    /// Maximum log level; `None` leaves the global logger untouched
+    pub log_level: Option<log::LevelFilter>,
+    /// Number of recent statements kept per session for `gql.session_history()` (0 = disabled)
+    pub session_history_size: usize,
+    /// Free bytes the database volume must keep; writes fail with a "Disk full" error below it (`None` = unchecked)
+    pub min_free_disk_bytes: Option<u64>,
+    /// Undo operations a transaction keeps in memory before spilling them to disk (`None` = never spill)
+    pub undo_spill_threshold: Option<usize>,
+    /// Bytes that spilling queries may keep in `<db>/tmp` at once (`None` = unlimited)
+    pub temp_space_quota_bytes: Option<u64>,
+    /// How long to wait for another process to release the database directory (`None` = fail at once)
+    ///
+    /// A process opened with a wait is a warm standby: it takes over as soon as
+    /// the current holder shuts down or crashes. `Duration::MAX` waits forever.
+    pub standby_wait: Option<Duration>,
+}
+
+impl Default for DatabaseConfig {
+    fn default() -> Self {
+        Self {
+            storage_type: StorageType::Sled,
+            session_mode: SessionMode::Instance,
+            read_only: false,
+            cache_size_bytes: None,
+            max_memory_bytes: None,
+            log_level: None,
+            session_history_size: DEFAULT_SESSION_HISTORY_SIZE,
+            min_free_disk_bytes: Some(DEFAULT_MIN_FREE_DISK_BYTES),
+            undo_spill_threshold: Some(DEFAULT_UNDO_SPILL_THRESHOLD),
+            temp_space_quota_bytes: Some(DEFAULT_TEMP_SPACE_QUOTA_BYTES),
+            standby_wait: None,
+        }
+    }
+}
+
+impl DatabaseConfig {
+    /// Build the cache configuration implied by the cache and memory budgets
+    ///
+    /// Per-level budgets keep the default L1/L2/L3 proportions.
+    pub(crate) fn cache_config(&self) -> CacheConfig {
+        let mut config = CacheConfig::default();
+
+        let budget = match (self.cache_size_bytes, self.max_memory_bytes) {
+            (Some(size), Some(max)) => Some(size.min(max)),
+            (Some(size), None) => Some(size),
+            (None, Some(max)) => Some(config.max_memory_bytes.min(max)),
+            (None, None) => None,
+        };
+
+        if let Some(budget) = budget {
+            if budget == 0 {
+                config.enabled = false;
+            }
+            let default_total = config.max_memory_bytes.max(1);
+            let scale = |level_bytes: usize| -> usize {
+                ((level_bytes as u128 * budget as u128) / default_total as u128) as usize
+            };
+            config.l1_config.max_memory_bytes = scale(config.l1_config.max_memory_bytes);
+            config.l2_config.max_memory_bytes = scale(config.l2_config.max_memory_bytes);
+            config.l3_config.max_memory_bytes = scale(config.l3_config.max_memory_bytes);
+            config.max_memory_bytes = budget;
+        }
+
+        config
+    }
+
+    /// Apply the configured log level to the global logger
+    ///
+    /// Installs a default logger if none is registered yet.
+    pub(crate) fn apply_log_level(&self) {
+        if let Some(level) = self.log_level {
+            // Another logger may already be installed by the host; only the level is ours to set
+            let _ = env_logger::Builder::new().filter_level(level).try_init();
+            log::set_max_level(level);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_cache_config_is_unchanged() {
+        let config = DatabaseConfig::default().cache_config();
+        assert_eq!(
+            config.max_memory_bytes,
+            CacheConfig::default().max_memory_bytes
+        );
+        assert!(config.enabled);
+    }
+
+    #[test]
+    fn test_cache_budget_clamped_to_max_memory() {
+        let config = DatabaseConfig {
+            cache_size_bytes: Some(256 * 1024 * 1024),
+            max_memory_bytes: Some(64 * 1024 * 1024),
+            ..Default::default()
+        }
+        .cache_config();
+
+        assert_eq!(config.max_memory_bytes, 64 * 1024 * 1024);
+        assert!(
+            config.l1_config.max_memory_bytes
+                + config.l2_config.max_memory_bytes
+                + config.l3_config.max_memory_bytes
+                <= 64 * 1024 * 1024
+        );
+    }
+
+    #[test]
+    fn test_zero_cache_size_disables_cache() {
+        let config = DatabaseConfig {
+            cache_size_bytes: Some(0),
+            ..Default::default()
+        }
+        .cache_config();
+        assert!(!config.enabled);
+    }
+}
@@ -0,0 +1,999 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! GQL conformance corpus and feature matrix
+//!
+//! `FEATURES` lists language features of ISO GQL and says which ones this
+//! version supports; `CALL gql.supported_features()` returns the same table.
+//! Every feature carries a small test: statements run in a fresh graph
+//! holding `SEED`, and an expectation checked against the last statement.
+//!
+//! `QueryCoordinator::run_conformance` runs the corpus. A supported feature
+//! whose test fails is a regression; an unsupported feature whose test
+//! passes means the matrix is out of date. Either makes the run fail, so the
+//! matrix stays honest as the engine changes. Feature ids are GraphLite's own,
+//! grouped by category; they are not the ISO feature codes.
+
+use std::time::{Duration, Instant};
+
+use super::query_coordinator::QueryCoordinator;
+use crate::storage::Value;
+
+/// Schema the conformance run creates its graphs in
+pub const CONFORMANCE_SCHEMA: &str = "/conformance";
+
+/// Data every test starts from
+///
+/// Alice (30, Paris) knows Bob (25, London), who knows Carol (35, Paris);
+/// Alice works at Acme.
+const SEED: &[&str] = &[
+    "INSERT (:Person {name: 'Alice', age: 30, city: 'Paris'}), \
+     (:Person {name: 'Bob', age: 25, city: 'London'}), \
+     (:Person {name: 'Carol', age: 35, city: 'Paris'}), \
+     (:Company {name: 'Acme'})",
+    "MATCH (a:Person {name: 'Alice'}), (b:Person {name: 'Bob'}) \
+     INSERT (a)-[:KNOWS {since: 2020}]->(b)",
+    "MATCH (b:Person {name: 'Bob'}), (c:Person {name: 'Carol'}) \
+     INSERT (b)-[:KNOWS {since: 2021}]->(c)",
+    "MATCH (a:Person {name: 'Alice'}), (c:Company) INSERT (a)-[:WORKS_AT]->(c)",
+];
+
+/// Value expected in the first row of a result
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Expected {
+    Number(f64),
+    Text(&'static str),
+    Boolean(bool),
+    Null,
+    List(&'static [Expected]),
+}
+
+impl Expected {
+    fn matches(&self, value: &Value) -> bool {
+        match (self, value) {
+            (Expected::Number(expected), Value::Number(actual)) => expected == actual,
+            (Expected::Text(expected), Value::String(actual)) => expected == actual,
+            (Expected::Boolean(expected), Value::Boolean(actual)) => expected == actual,
+            (Expected::Null, Value::Null) => true,
+            (Expected::List(expected), Value::List(actual)) => {
+                expected.len() == actual.len()
+                    && expected.iter().zip(actual).all(|(e, a)| e.matches(a))
+            }
+            _ => false,
+        }
+    }
+}
+
+/// What the last statement of a feature test must do
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Expectation {
+    /// Succeed with this many rows
+    Rows(usize),
+    /// Succeed with this value in the named column of the first row
+    Value(&'static str, Expected),
+    /// Succeed with all of these values in the first row
+    Row(&'static [(&'static str, Expected)]),
+    /// Succeed with these values of a column, in order
+    Column(&'static str, &'static [Expected]),
+    /// Fail with an error containing this text
+    Error(&'static str),
+}
+
+/// One entry of the feature matrix
+#[derive(Debug)]
+pub struct Feature {
+    /// Stable identifier such as `match.directed_edge`
+    pub id: &'static str,
+    /// Group of related features
+    pub category: &'static str,
+    /// What the feature is, with an example where it helps
+    pub description: &'static str,
+    /// Whether this version supports the feature
+    pub supported: bool,
+    /// Statements of the test, run in order in a graph holding `SEED`
+    pub statements: &'static [&'static str],
+    /// What the last statement must do
+    pub expect: Expectation,
+}
+
+/// Outcome of one feature test
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConformanceStatus {
+    /// Supported, and the test passed
+    Passed,
+    /// Supported, but the test failed
+    Failed,
+    /// Not supported, and the test failed as expected
+    Unsupported,
+    /// Not supported, yet the test passed; the matrix needs updating
+    UnexpectedPass,
+}
+
+impl ConformanceStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ConformanceStatus::Passed => "passed",
+            ConformanceStatus::Failed => "failed",
+            ConformanceStatus::Unsupported => "unsupported",
+            ConformanceStatus::UnexpectedPass => "unexpected_pass",
+        }
+    }
+}
+
+/// Result of running one feature test
+#[derive(Debug, Clone)]
+pub struct ConformanceResult {
+    pub feature: &'static Feature,
+    pub status: ConformanceStatus,
+    /// Why the test failed, if it did
+    pub message: Option<String>,
+    pub duration: Duration,
+}
+
+/// Results of `QueryCoordinator::run_conformance`, in corpus order
+#[derive(Debug, Clone, Default)]
+pub struct ConformanceReport {
+    pub results: Vec<ConformanceResult>,
+}
+
+impl ConformanceReport {
+    /// Number of results with the given status
+    pub fn count(&self, status: ConformanceStatus) -> usize {
+        self.results.iter().filter(|r| r.status == status).count()
+    }
+
+    /// Whether the engine behaved as the feature matrix says
+    pub fn is_ok(&self) -> bool {
+        self.count(ConformanceStatus::Failed) == 0
+            && self.count(ConformanceStatus::UnexpectedPass) == 0
+    }
+}
+
+/// Run the tests of the features whose id starts with `filter` (all if `None`)
+pub(crate) fn run(
+    coordinator: &QueryCoordinator,
+    filter: Option<&str>,
+) -> Result<ConformanceReport, String> {
+    let session = coordinator.create_simple_session("admin")?;
+    let drop_schema = format!("DROP SCHEMA IF EXISTS {} CASCADE", CONFORMANCE_SCHEMA);
+    // A run that was interrupted may have left its schema behind
+    let _ = coordinator.process_query(&drop_schema, &session);
+    let outcome = run_in_session(coordinator, &session, filter);
+    let _ = coordinator.process_query(&drop_schema, &session);
+    let _ = coordinator.close_session(&session);
+    outcome
+}
+
+fn run_in_session(
+    coordinator: &QueryCoordinator,
+    session: &str,
+    filter: Option<&str>,
+) -> Result<ConformanceReport, String> {
+    coordinator
+        .process_query(&format!("CREATE SCHEMA {}", CONFORMANCE_SCHEMA), session)
+        .map_err(|e| format!("Cannot create {}: {}", CONFORMANCE_SCHEMA, e))?;
+
+    let mut report = ConformanceReport::default();
+    for (index, feature) in FEATURES.iter().enumerate() {
+        if filter.is_some_and(|prefix| !feature.id.starts_with(prefix)) {
+            continue;
+        }
+        let started = Instant::now();
+        let graph = format!("{}/case_{}", CONFORMANCE_SCHEMA, index);
+        let outcome = prepare(coordinator, session, &graph)
+            .map_err(|e| format!("setup failed: {}", e))
+            .and_then(|_| check(coordinator, session, feature));
+        let status = match (feature.supported, outcome.is_ok()) {
+            (true, true) => ConformanceStatus::Passed,
+            (true, false) => ConformanceStatus::Failed,
+            (false, false) => ConformanceStatus::Unsupported,
+            (false, true) => ConformanceStatus::UnexpectedPass,
+        };
+        report.results.push(ConformanceResult {
+            feature,
+            status,
+            message: outcome.err(),
+            duration: started.elapsed(),
+        });
+        // Leave no open transaction behind for the next test
+        let _ = coordinator.process_query("ROLLBACK", session);
+    }
+    Ok(report)
+}
+
+/// Create the test's graph, make it current and load the seed data
+fn prepare(coordinator: &QueryCoordinator, session: &str, graph: &str) -> Result<(), String> {
+    coordinator.process_query(&format!("CREATE GRAPH {}", graph), session)?;
+    coordinator.process_query(
+        &format!("SESSION SET SCHEMA {}", CONFORMANCE_SCHEMA),
+        session,
+    )?;
+    coordinator.process_query(&format!("SESSION SET GRAPH {}", graph), session)?;
+    for statement in SEED {
+        coordinator.process_query(statement, session)?;
+    }
+    Ok(())
+}
+
+/// Run a feature's statements and check the last one
+fn check(coordinator: &QueryCoordinator, session: &str, feature: &Feature) -> Result<(), String> {
+    let (last, setup) = feature
+        .statements
+        .split_last()
+        .ok_or_else(|| "test has no statements".to_string())?;
+    for statement in setup {
+        coordinator
+            .process_query(statement, session)
+            .map_err(|e| format!("{}: {}", statement, e))?;
+    }
+
+    let result = coordinator.process_query(last, session);
+    match (feature.expect, result) {
+        (Expectation::Error(text), Err(error)) if error.contains(text) => Ok(()),
+        (Expectation::Error(text), Err(error)) => Err(format!(
+            "expected an error containing '{}', got: {}",
+            text, error
+        )),
+        (Expectation::Error(text), Ok(_)) => Err(format!(
+            "expected an error containing '{}', but the statement succeeded",
+            text
+        )),
+        (_, Err(error)) => Err(error),
+        (Expectation::Rows(expected), Ok(result)) if result.rows.len() == expected => Ok(()),
+        (Expectation::Rows(expected), Ok(result)) => Err(format!(
+            "expected {} row(s), got {}",
+            expected,
+            result.rows.len()
+        )),
+        (Expectation::Value(column, expected), Ok(result)) => {
+            match result.rows.first().and_then(|row| row.values.get(column)) {
+                Some(value) if expected.matches(value) => Ok(()),
+                actual => Err(format!(
+                    "expected {} = {:?}, got {:?}",
+                    column, expected, actual
+                )),
+            }
+        }
+        (Expectation::Row(expected), Ok(result)) => {
+            let first = result.rows.first();
+            for (column, expected) in expected {
+                match first.and_then(|row| row.values.get(*column)) {
+                    Some(value) if expected.matches(value) => {}
+                    actual => {
+                        return Err(format!(
+                            "expected {} = {:?}, got {:?}",
+                            column, expected, actual
+                        ))
+                    }
+                }
+            }
+            Ok(())
+        }
+        (Expectation::Column(column, expected), Ok(result)) => {
+            let actual: Vec<Option<&Value>> = result
+                .rows
+                .iter()
+                .map(|row| row.values.get(column))
+                .collect();
+            let matches = actual.len() == expected.len()
+                && actual
+                    .iter()
+                    .zip(expected)
+                    .all(|(value, expected)| value.is_some_and(|v| expected.matches(v)));
+            if matches {
+                Ok(())
+            } else {
+                Err(format!(
+                    "expected {} = {:?}, got {:?}",
+                    column, expected, actual
+                ))
+            }
+        }
+    }
+}
+
+use Expectation::{Column, Error, Row, Rows, Value as Is};
+use Expected::{Boolean, List, Null, Number, Text};
+
+/// The feature matrix, grouped by category
+pub static FEATURES: &[Feature] = &[
+    // Pattern matching
+    Feature {
+        id: "match.node_label",
+        category: "pattern_matching",
+        description: "Node patterns with a label: MATCH (p:Person)",
+        supported: true,
+        statements: &["MATCH (p:Person) RETURN p.name AS name"],
+        expect: Rows(3),
+    },
+    Feature {
+        id: "match.property_map",
+        category: "pattern_matching",
+        description: "Property maps in node patterns: (p:Person {name: 'Bob'})",
+        supported: true,
+        statements: &["MATCH (p:Person {name: 'Bob'}) RETURN p.age AS age"],
+        expect: Is("age", Number(25.0)),
+    },
+    Feature {
+        id: "match.where",
+        category: "pattern_matching",
+        description: "WHERE clause after a graph pattern",
+        supported: true,
+        statements: &["MATCH (p:Person) WHERE p.age > 26 RETURN p.name AS name"],
+        expect: Rows(2),
+    },
+    Feature {
+        id: "match.element_where",
+        category: "pattern_matching",
+        description: "WHERE inside an element pattern: (p:Person WHERE p.age > 26)",
+        supported: false,
+        statements: &["MATCH (p:Person WHERE p.age > 26) RETURN p.name AS name"],
+        expect: Rows(2),
+    },
+    Feature {
+        id: "match.directed_edge",
+        category: "pattern_matching",
+        description: "Directed edge patterns: (a)-[:KNOWS]->(b)",
+        supported: true,
+        statements: &["MATCH (a:Person)-[:KNOWS]->(b:Person) RETURN a.name AS a, b.name AS b"],
+        expect: Rows(2),
+    },
+    Feature {
+        id: "match.reverse_edge",
+        category: "pattern_matching",
+        description: "Edges pointing left: (a)<-[:KNOWS]-(b)",
+        supported: true,
+        statements: &["MATCH (a:Person)<-[:KNOWS]-(b:Person) RETURN a.name AS name"],
+        expect: Rows(2),
+    },
+    Feature {
+        id: "match.undirected_edge",
+        category: "pattern_matching",
+        description: "Edges in either direction: (a)-[:KNOWS]-(b)",
+        supported: true,
+        statements: &["MATCH (a:Person)-[:KNOWS]-(b:Person) RETURN a.name AS name"],
+        expect: Rows(4),
+    },
+    Feature {
+        id: "match.edge_variable",
+        category: "pattern_matching",
+        description: "Edge variables with property access: [e:KNOWS] ... e.since",
+        supported: true,
+        statements: &[
+            "MATCH (a:Person)-[e:KNOWS]->(b:Person) WHERE e.since > 2020 RETURN b.name AS name",
+        ],
+        expect: Is("name", Text("Carol")),
+    },
+    Feature {
+        id: "match.multi_hop",
+        category: "pattern_matching",
+        description: "Paths of several edges: (a)-[:KNOWS]->(b)-[:KNOWS]->(c)",
+        supported: true,
+        statements: &["MATCH (a:Person)-[:KNOWS]->(b)-[:KNOWS]->(c) RETURN c.name AS name"],
+        expect: Is("name", Text("Carol")),
+    },
+    Feature {
+        id: "match.edge_label_alternation",
+        category: "pattern_matching",
+        description: "Edge label alternatives: [:KNOWS|WORKS_AT]",
+        supported: true,
+        statements: &["MATCH (a:Person)-[:KNOWS|WORKS_AT]->(b) RETURN count(b) AS n"],
+        expect: Is("n", Number(3.0)),
+    },
+    Feature {
+        id: "match.node_label_disjunction",
+        category: "pattern_matching",
+        description: "Node label alternatives: (n:Person|Company)",
+        supported: false,
+        statements: &["MATCH (n:Person|Company) RETURN count(n) AS n"],
+        expect: Is("n", Number(4.0)),
+    },
+    Feature {
+        id: "match.label_negation",
+        category: "pattern_matching",
+        description: "Negated labels: (n:!Person)",
+        supported: false,
+        statements: &["MATCH (n:!Person) RETURN count(n) AS n"],
+        expect: Is("n", Number(1.0)),
+    },
+    Feature {
+        id: "match.comma_patterns",
+        category: "pattern_matching",
+        description: "Several comma-separated patterns in one MATCH",
+        supported: true,
+        statements: &["MATCH (a:Person), (c:Company) RETURN count(*) AS n"],
+        expect: Is("n", Number(3.0)),
+    },
+    Feature {
+        id: "match.bounded_quantifier",
+        category: "pattern_matching",
+        description: "Bounded edge quantifiers: -[:KNOWS]{1,2}->",
+        supported: true,
+        statements: &["MATCH (a:Person {name: 'Alice'})-[:KNOWS]{1,2}->(b) RETURN b.name AS name"],
+        expect: Rows(2),
+    },
+    Feature {
+        id: "match.shortest_path",
+        category: "pattern_matching",
+        description: "Path search prefixes: ANY SHORTEST, ALL SHORTEST",
+        supported: true,
+        statements: &[
+            "MATCH ANY SHORTEST (a:Person {name: 'Alice'})-[:KNOWS]->+(c:Person {name: 'Carol'}) \
+             RETURN c.name AS name",
+        ],
+        expect: Rows(1),
+    },
+    Feature {
+        id: "match.optional",
+        category: "pattern_matching",
+        description: "OPTIONAL MATCH after a MATCH",
+        supported: true,
+        statements: &[
+            "MATCH (p:Person) OPTIONAL MATCH (p)-[:WORKS_AT]->(c:Company) \
+             RETURN p.name AS person, c.name AS company",
+        ],
+        expect: Rows(3),
+    },
+    Feature {
+        id: "match.exists_subquery",
+        category: "pattern_matching",
+        description: "EXISTS { MATCH ... } predicates in WHERE",
+        supported: false,
+        statements: &[
+            "MATCH (p:Person) WHERE EXISTS { MATCH (p)-[:WORKS_AT]->() } RETURN p.name AS name",
+        ],
+        expect: Rows(1),
+    },
+    // Expressions and functions
+    Feature {
+        id: "expr.arithmetic",
+        category: "expressions",
+        description: "Arithmetic operators: + - * / %",
+        supported: true,
+        statements: &["RETURN (1 + 2) * 4 - 10 / 5 + 7 % 3 AS x"],
+        expect: Is("x", Number(11.0)),
+    },
+    Feature {
+        id: "expr.boolean",
+        category: "expressions",
+        description: "Boolean operators: AND, OR, NOT",
+        supported: true,
+        statements: &["RETURN true AND NOT false OR false AS b"],
+        expect: Is("b", Boolean(true)),
+    },
+    Feature {
+        id: "expr.string_predicates",
+        category: "expressions",
+        description: "STARTS WITH, ENDS WITH and CONTAINS",
+        supported: true,
+        statements: &[
+            "MATCH (p:Person) WHERE p.name STARTS WITH 'C' AND p.name ENDS WITH 'l' \
+             AND p.name CONTAINS 'ar' RETURN p.name AS name",
+        ],
+        expect: Is("name", Text("Carol")),
+    },
+    Feature {
+        id: "expr.in_list",
+        category: "expressions",
+        description: "IN with a list: p.name IN ['Alice', 'Bob']",
+        supported: true,
+        statements: &["MATCH (p:Person) WHERE p.name IN ['Alice', 'Bob'] RETURN p.name AS name"],
+        expect: Rows(2),
+    },
+    Feature {
+        id: "expr.null_predicates",
+        category: "expressions",
+        description: "IS NULL and IS NOT NULL",
+        supported: true,
+        statements: &["MATCH (p:Person) WHERE p.email IS NULL RETURN count(p) AS n"],
+        expect: Is("n", Number(3.0)),
+    },
+    Feature {
+        id: "expr.between",
+        category: "expressions",
+        description: "Range predicates: x BETWEEN a AND b",
+        supported: false,
+        statements: &["MATCH (p:Person) WHERE p.age BETWEEN 25 AND 30 RETURN count(p) AS n"],
+        expect: Is("n", Number(2.0)),
+    },
+    Feature {
+        id: "expr.case",
+        category: "expressions",
+        description: "Searched CASE expressions",
+        supported: true,
+        statements: &[
+            "MATCH (p:Person {name: 'Bob'}) \
+             RETURN CASE WHEN p.age > 28 THEN 'senior' ELSE 'junior' END AS band",
+        ],
+        expect: Is("band", Text("junior")),
+    },
+    Feature {
+        id: "expr.cast",
+        category: "expressions",
+        description: "CAST between strings and numbers",
+        supported: true,
+        statements: &["RETURN CAST('42' AS INTEGER) AS n"],
+        expect: Is("n", Number(42.0)),
+    },
+    Feature {
+        id: "expr.concatenation",
+        category: "expressions",
+        description: "String concatenation with ||",
+        supported: true,
+        statements: &["RETURN 'graph' || 'lite' AS s"],
+        expect: Is("s", Text("graphlite")),
+    },
+    Feature {
+        id: "expr.null_propagation",
+        category: "expressions",
+        description: "Functions of NULL return NULL",
+        supported: true,
+        statements: &["RETURN upper(NULL) AS u"],
+        expect: Is("u", Null),
+    },
+    Feature {
+        id: "expr.division_by_zero",
+        category: "expressions",
+        description: "Division by zero raises an error",
+        supported: true,
+        statements: &["RETURN 1 / 0 AS x"],
+        expect: Error("Division by zero"),
+    },
+    Feature {
+        id: "func.string",
+        category: "functions",
+        description: "String functions: upper, lower, trim, substring",
+        supported: true,
+        statements: &["RETURN upper('ab') || lower('CD') || trim('  e ') || substring('xyz', 0, 2) AS s"],
+        expect: Is("s", Text("ABcdexy")),
+    },
+    Feature {
+        id: "func.char_length",
+        category: "functions",
+        description: "CHAR_LENGTH of a string",
+        supported: false,
+        statements: &["RETURN char_length('abc') AS n"],
+        expect: Is("n", Number(3.0)),
+    },
+    Feature {
+        id: "func.numeric",
+        category: "functions",
+        description: "Numeric functions: abs, round, sqrt",
+        supported: true,
+        statements: &["RETURN abs(-3) + round(2.6) + sqrt(16) AS n"],
+        expect: Is("n", Number(10.0)),
+    },
+    Feature {
+        id: "func.coalesce",
+        category: "functions",
+        description: "COALESCE of possibly missing values",
+        supported: true,
+        statements: &["MATCH (p:Person {name: 'Bob'}) RETURN coalesce(p.email, 'none') AS c"],
+        expect: Is("c", Text("none")),
+    },
+    Feature {
+        id: "func.current_date",
+        category: "functions",
+        description: "CURRENT_DATE without parentheses",
+        supported: false,
+        statements: &["RETURN CURRENT_DATE AS d"],
+        expect: Rows(1),
+    },
+    Feature {
+        id: "func.list_size",
+        category: "functions",
+        description: "size of a list literal",
+        supported: true,
+        statements: &["RETURN size([1, 2, 3]) AS n"],
+        expect: Is("n", Number(3.0)),
+    },
+    // Result shaping
+    Feature {
+        id: "result.distinct",
+        category: "result",
+        description: "RETURN DISTINCT",
+        supported: true,
+        statements: &["MATCH (p:Person) RETURN DISTINCT p.city AS city"],
+        expect: Rows(2),
+    },
+    Feature {
+        id: "result.order_by",
+        category: "result",
+        description: "ORDER BY a returned column, ascending and descending",
+        supported: true,
+        statements: &["MATCH (p:Person) RETURN p.name AS name, p.age AS age ORDER BY age DESC"],
+        expect: Column("name", &[Text("Carol"), Text("Alice"), Text("Bob")]),
+    },
+    Feature {
+        id: "result.limit",
+        category: "result",
+        description: "LIMIT after ORDER BY",
+        supported: true,
+        statements: &["MATCH (p:Person) RETURN p.name AS name ORDER BY name LIMIT 2"],
+        expect: Column("name", &[Text("Alice"), Text("Bob")]),
+    },
+    Feature {
+        id: "result.offset",
+        category: "result",
+        description: "OFFSET after ORDER BY",
+        supported: false,
+        statements: &["MATCH (p:Person) RETURN p.name AS name ORDER BY name OFFSET 1"],
+        expect: Column("name", &[Text("Bob"), Text("Carol")]),
+    },
+    // Aggregation
+    Feature {
+        id: "agg.functions",
+        category: "aggregation",
+        description: "count, sum, avg, min and max",
+        supported: true,
+        statements: &[
+            "MATCH (p:Person) RETURN count(p) AS c, sum(p.age) AS s, avg(p.age) AS a, \
+             min(p.age) AS lo, max(p.age) AS hi",
+        ],
+        expect: Row(&[
+            ("c", Number(3.0)),
+            ("s", Number(90.0)),
+            ("a", Number(30.0)),
+            ("lo", Number(25.0)),
+            ("hi", Number(35.0)),
+        ]),
+    },
+    Feature {
+        id: "agg.in_expression",
+        category: "aggregation",
+        description: "Aggregates inside expressions: count(p) + sum(p.age)",
+        supported: false,
+        statements: &["MATCH (p:Person) RETURN count(p) + sum(p.age) AS total"],
+        expect: Is("total", Number(93.0)),
+    },
+    Feature {
+        id: "agg.implicit_grouping",
+        category: "aggregation",
+        description: "Grouping by the non-aggregated columns of RETURN",
+        supported: true,
+        statements: &["MATCH (p:Person) RETURN p.city AS city, count(p) AS n"],
+        expect: Rows(2),
+    },
+    Feature {
+        id: "agg.group_by_having",
+        category: "aggregation",
+        description: "GROUP BY with HAVING",
+        supported: true,
+        statements: &[
+            "MATCH (p:Person) RETURN p.city AS city, count(p) AS n GROUP BY city HAVING n > 1",
+        ],
+        expect: Row(&[("n", Number(2.0))]),
+    },
+    Feature {
+        id: "agg.count_distinct",
+        category: "aggregation",
+        description: "count(DISTINCT ...)",
+        supported: false,
+        statements: &["MATCH (p:Person) RETURN count(DISTINCT p.city) AS n"],
+        expect: Is("n", Number(2.0)),
+    },
+    Feature {
+        id: "agg.collect",
+        category: "aggregation",
+        description: "collect into a list, optionally ordered",
+        supported: true,
+        statements: &["MATCH (p:Person) RETURN collect(p.name ORDER BY p.age DESC) AS names"],
+        expect: Is(
+            "names",
+            List(&[Text("Carol"), Text("Alice"), Text("Bob")]),
+        ),
+    },
+    Feature {
+        id: "agg.rollup",
+        category: "aggregation",
+        description: "GROUP BY ROLLUP with subtotal rows",
+        supported: true,
+        statements: &["MATCH (p:Person) RETURN p.city AS city, count(p) AS n GROUP BY ROLLUP(city)"],
+        expect: Rows(3),
+    },
+    // Query composition
+    Feature {
+        id: "compose.union",
+        category: "composition",
+        description: "UNION and UNION ALL",
+        supported: true,
+        statements: &[
+            "MATCH (p:Person) RETURN p.name AS name UNION ALL MATCH (c:Company) RETURN c.name AS name",
+        ],
+        expect: Rows(4),
+    },
+    Feature {
+        id: "compose.except",
+        category: "composition",
+        description: "EXCEPT",
+        supported: true,
+        statements: &[
+            "MATCH (p:Person) RETURN p.name AS name \
+             EXCEPT MATCH (p:Person {name: 'Bob'}) RETURN p.name AS name",
+        ],
+        expect: Rows(2),
+    },
+    Feature {
+        id: "compose.intersect",
+        category: "composition",
+        description: "INTERSECT",
+        supported: true,
+        statements: &[
+            "MATCH (p:Person) RETURN p.name AS name \
+             INTERSECT MATCH (p:Person {name: 'Bob'}) RETURN p.name AS name",
+        ],
+        expect: Is("name", Text("Bob")),
+    },
+    Feature {
+        id: "compose.let",
+        category: "composition",
+        description: "LET variable definitions",
+        supported: true,
+        statements: &["LET x = 5 RETURN x"],
+        expect: Is("x", Number(5.0)),
+    },
+    Feature {
+        id: "compose.for",
+        category: "composition",
+        description: "FOR over a list, one row per element",
+        supported: false,
+        statements: &["FOR x IN [1, 2, 3] RETURN x"],
+        expect: Rows(3),
+    },
+    Feature {
+        id: "compose.next",
+        category: "composition",
+        description: "NEXT passing the rows of one statement to the next",
+        supported: false,
+        statements: &["MATCH (p:Person) RETURN p.name AS name NEXT RETURN count(name) AS n"],
+        expect: Is("n", Number(3.0)),
+    },
+    // Data modification
+    Feature {
+        id: "dml.insert_nodes",
+        category: "data_modification",
+        description: "INSERT of nodes with labels and properties",
+        supported: true,
+        statements: &[
+            "INSERT (:Person {name: 'Dave', age: 41}), (:Person {name: 'Erin', age: 22})",
+            "MATCH (p:Person) RETURN count(p) AS n",
+        ],
+        expect: Is("n", Number(5.0)),
+    },
+    Feature {
+        id: "dml.insert_path",
+        category: "data_modification",
+        description: "INSERT of a path creating nodes and an edge",
+        supported: true,
+        statements: &[
+            "INSERT (:Tag {name: 'a'})-[:NEXT]->(:Tag {name: 'b'})",
+            "MATCH (a:Tag)-[:NEXT]->(b:Tag) RETURN b.name AS name",
+        ],
+        expect: Is("name", Text("b")),
+    },
+    Feature {
+        id: "dml.match_insert",
+        category: "data_modification",
+        description: "INSERT of an edge between matched nodes",
+        supported: true,
+        statements: &[
+            "MATCH (c:Person {name: 'Carol'}), (a:Person {name: 'Alice'}) INSERT (c)-[:KNOWS]->(a)",
+            "MATCH (:Person)-[k:KNOWS]->(:Person) RETURN count(k) AS n",
+        ],
+        expect: Is("n", Number(3.0)),
+    },
+    Feature {
+        id: "dml.set_property",
+        category: "data_modification",
+        description: "SET of a property from an expression",
+        supported: true,
+        statements: &[
+            "MATCH (p:Person {name: 'Bob'}) SET p.age = p.age + 1",
+            "MATCH (p:Person {name: 'Bob'}) RETURN p.age AS age",
+        ],
+        expect: Is("age", Number(26.0)),
+    },
+    Feature {
+        id: "dml.remove_property",
+        category: "data_modification",
+        description: "REMOVE of a property",
+        supported: true,
+        statements: &[
+            "MATCH (p:Person {name: 'Bob'}) REMOVE p.city",
+            "MATCH (p:Person {name: 'Bob'}) RETURN p.city AS city",
+        ],
+        expect: Is("city", Null),
+    },
+    Feature {
+        id: "dml.set_label",
+        category: "data_modification",
+        description: "SET of a label, found by later label scans",
+        supported: false,
+        statements: &[
+            "MATCH (p:Person {name: 'Bob'}) SET p:Employee",
+            "MATCH (p:Employee) RETURN count(p) AS n",
+        ],
+        expect: Is("n", Number(1.0)),
+    },
+    Feature {
+        id: "dml.delete_edge",
+        category: "data_modification",
+        description: "DELETE of matched edges",
+        supported: true,
+        statements: &[
+            "MATCH (:Person)-[w:WORKS_AT]->(:Company) DELETE w",
+            "MATCH ()-[w:WORKS_AT]->() RETURN count(w) AS n",
+        ],
+        expect: Is("n", Number(0.0)),
+    },
+    Feature {
+        id: "dml.detach_delete",
+        category: "data_modification",
+        description: "DETACH DELETE of a node with edges",
+        supported: true,
+        statements: &[
+            "MATCH (p:Person {name: 'Bob'}) DETACH DELETE p",
+            "MATCH (a)-[k:KNOWS]->(b) RETURN count(k) AS n",
+        ],
+        expect: Is("n", Number(0.0)),
+    },
+    Feature {
+        id: "dml.nodetach_delete",
+        category: "data_modification",
+        description: "DELETE of a node with edges is refused",
+        supported: true,
+        statements: &["MATCH (p:Person {name: 'Bob'}) NODETACH DELETE p"],
+        expect: Error("DETACH DELETE"),
+    },
+    // Catalog
+    Feature {
+        id: "ddl.create_graph",
+        category: "catalog",
+        description: "CREATE GRAPH and CREATE GRAPH IF NOT EXISTS",
+        supported: true,
+        statements: &[
+            "CREATE GRAPH /conformance/ddl_create",
+            "CREATE GRAPH IF NOT EXISTS /conformance/ddl_create",
+        ],
+        expect: Rows(1),
+    },
+    Feature {
+        id: "ddl.drop_graph_if_exists",
+        category: "catalog",
+        description: "DROP GRAPH IF EXISTS of a missing graph",
+        supported: false,
+        statements: &["DROP GRAPH IF EXISTS /conformance/ddl_missing"],
+        expect: Rows(1),
+    },
+    Feature {
+        id: "ddl.drop_schema_if_exists",
+        category: "catalog",
+        description: "DROP SCHEMA IF EXISTS of a missing schema",
+        supported: true,
+        statements: &["DROP SCHEMA IF EXISTS /conformance_missing"],
+        expect: Rows(1),
+    },
+    Feature {
+        id: "ddl.graph_copy",
+        category: "catalog",
+        description: "CREATE GRAPH ... AS COPY OF",
+        supported: true,
+        statements: &["CREATE GRAPH /conformance/ddl_copy AS COPY OF /conformance/case_0"],
+        expect: Rows(1),
+    },
+    Feature {
+        id: "ddl.graph_type",
+        category: "catalog",
+        description: "CREATE GRAPH TYPE and typed graphs",
+        supported: true,
+        statements: &[
+            "CREATE GRAPH TYPE /conformance/DdlType ( NODE TYPE Person (name STRING) )",
+            "CREATE GRAPH /conformance/ddl_typed TYPED /conformance/DdlType",
+        ],
+        expect: Rows(1),
+    },
+    Feature {
+        id: "ddl.create_index",
+        category: "catalog",
+        description: "CREATE INDEX on a node property",
+        supported: true,
+        statements: &["CREATE INDEX conformance_name ON Person (name)"],
+        expect: Rows(1),
+    },
+    // Sessions and transactions
+    Feature {
+        id: "session.set_graph",
+        category: "session",
+        description: "SESSION SET GRAPH with a name relative to the session schema",
+        supported: true,
+        statements: &[
+            "CREATE GRAPH /conformance/session_graph",
+            "SESSION SET GRAPH session_graph",
+            "MATCH (n) RETURN count(n) AS n",
+        ],
+        expect: Is("n", Number(0.0)),
+    },
+    Feature {
+        id: "session.time_zone",
+        category: "session",
+        description: "SESSION SET TIME ZONE",
+        supported: true,
+        statements: &["SESSION SET TIME ZONE 'UTC'"],
+        expect: Rows(0),
+    },
+    Feature {
+        id: "txn.commit",
+        category: "transactions",
+        description: "START TRANSACTION ... COMMIT",
+        supported: true,
+        statements: &[
+            "START TRANSACTION",
+            "INSERT (:Person {name: 'Dave'})",
+            "COMMIT",
+            "MATCH (p:Person) RETURN count(p) AS n",
+        ],
+        expect: Is("n", Number(4.0)),
+    },
+    Feature {
+        id: "txn.rollback",
+        category: "transactions",
+        description: "ROLLBACK undoes the transaction's changes",
+        supported: true,
+        statements: &[
+            "START TRANSACTION",
+            "INSERT (:Person {name: 'Dave'})",
+            "ROLLBACK",
+            "MATCH (p:Person) RETURN count(p) AS n",
+        ],
+        expect: Is("n", Number(3.0)),
+    },
+    Feature {
+        id: "txn.savepoint",
+        category: "transactions",
+        description: "SAVEPOINT and ROLLBACK TO SAVEPOINT",
+        supported: true,
+        statements: &[
+            "START TRANSACTION",
+            "INSERT (:Person {name: 'Dave'})",
+            "SAVEPOINT s1",
+            "INSERT (:Person {name: 'Erin'})",
+            "ROLLBACK TO SAVEPOINT s1",
+            "COMMIT",
+            "MATCH (p:Person) RETURN count(p) AS n",
+        ],
+        expect: Is("n", Number(4.0)),
+    },
+    Feature {
+        id: "txn.read_only",
+        category: "transactions",
+        description: "READ ONLY transactions refuse writes",
+        supported: true,
+        statements: &["START TRANSACTION READ ONLY", "INSERT (:Person {name: 'Dave'})"],
+        expect: Error("read-only"),
+    },
+    Feature {
+        id: "txn.serializable",
+        category: "transactions",
+        description: "ISOLATION LEVEL SERIALIZABLE",
+        supported: false,
+        statements: &["START TRANSACTION ISOLATION LEVEL SERIALIZABLE"],
+        expect: Rows(1),
+    },
+    // Procedures
+    Feature {
+        id: "call.yield",
+        category: "procedures",
+        description: "CALL with YIELD of selected columns",
+        supported: true,
+        statements: &["CALL gql.supported_features('procedures') YIELD feature_id"],
+        expect: Is("feature_id", Text("call.yield")),
+    },
+    Feature {
+        id: "call.yield_where",
+        category: "procedures",
+        description: "CALL ... YIELD ... WHERE",
+        supported: true,
+        statements: &["CALL gql.list_graphs() YIELD graph_name WHERE graph_name = 'nothing'"],
+        expect: Rows(0),
+    },
+];
@@ -0,0 +1,177 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! Database directory lock and warm standby takeover
+//!
+//! A coordinator opened on a directory holds an exclusive lock on
+//! `<db>/graphlite.lock` until it is dropped, so a second process opening the
+//! same directory fails with a "Database is locked" error naming the holder.
+//! With `DatabaseConfig::standby_wait` set, the second process waits instead:
+//! it polls the lock every `POLL_INTERVAL` and opens the database as soon as
+//! the lock is free. The operating system releases the lock when its holder
+//! exits, so the standby takes over after a crash as well as after a clean
+//! shutdown, and opens the database the way any restart would.
+//!
+//! The holder writes its process id and the time it took the lock into the
+//! file; `QueryCoordinator::lock_holder` reads them back. The file itself is
+//! never removed, since deleting a lock file another process is waiting on
+//! would let two processes hold "the" lock at once.
+
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use fs2::FileExt;
+
+/// Name of the lock file inside the database directory
+pub const LOCK_FILE_NAME: &str = "graphlite.lock";
+
+/// How often a standby retries the lock
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Process holding a database directory, returned by `QueryCoordinator::lock_holder`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LockHolder {
+    /// Process id of the holder
+    pub pid: u32,
+    /// When the holder took the lock
+    pub since: DateTime<Utc>,
+}
+
+/// Exclusive lock on a database directory, released on drop
+#[derive(Debug)]
+pub(crate) struct DatabaseLock {
+    file: File,
+    path: PathBuf,
+}
+
+impl DatabaseLock {
+    /// Lock `db_path`, waiting up to `standby_wait` for another holder to let go
+    pub(crate) fn acquire(db_path: &Path, standby_wait: Option<Duration>) -> Result<Self, String> {
+        std::fs::create_dir_all(db_path)
+            .map_err(|e| format!("Failed to create database directory {:?}: {}", db_path, e))?;
+        let path = db_path.join(LOCK_FILE_NAME);
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)
+            .map_err(|e| format!("Failed to open lock file {:?}: {}", path, e))?;
+
+        let deadline = standby_wait.and_then(|wait| Instant::now().checked_add(wait));
+        let mut waiting = false;
+        loop {
+            match file.try_lock_exclusive() {
+                Ok(()) => break,
+                Err(e) if e.kind() != fs2::lock_contended_error().kind() => {
+                    return Err(format!("Failed to lock {:?}: {}", path, e));
+                }
+                Err(_) => {}
+            }
+            let holder = describe(read_holder(&mut file));
+            let Some(wait) = standby_wait else {
+                return Err(format!(
+                    "Database is locked: {:?} is in use by {}",
+                    db_path, holder
+                ));
+            };
+            if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                return Err(format!(
+                    "Database is locked: {:?} is still in use by {} after waiting {:?}",
+                    db_path, holder, wait
+                ));
+            }
+            if !waiting {
+                log::info!(
+                    "{} is in use by {}; waiting as a standby",
+                    db_path.display(),
+                    holder
+                );
+                waiting = true;
+            }
+            std::thread::sleep(POLL_INTERVAL);
+        }
+        if waiting {
+            log::info!("Took over {}", db_path.display());
+        }
+
+        let record = format!("{}\n{}\n", std::process::id(), Utc::now().to_rfc3339());
+        file.set_len(0)
+            .and_then(|_| file.seek(SeekFrom::Start(0)))
+            .and_then(|_| file.write_all(record.as_bytes()))
+            .and_then(|_| file.sync_data())
+            .map_err(|e| format!("Failed to write lock file {:?}: {}", path, e))?;
+        Ok(Self { file, path })
+    }
+
+    /// Current holder of `db_path`, if any process holds it
+    pub(crate) fn holder(db_path: &Path) -> Option<LockHolder> {
+        let mut file = File::open(db_path.join(LOCK_FILE_NAME)).ok()?;
+        if file.try_lock_shared().is_ok() {
+            let _ = file.unlock();
+            return None;
+        }
+        read_holder(&mut file)
+    }
+}
+
+impl Drop for DatabaseLock {
+    fn drop(&mut self) {
+        if let Err(e) = self.file.unlock() {
+            log::warn!("Failed to release {:?}: {}", self.path, e);
+        }
+    }
+}
+
+fn read_holder(file: &mut File) -> Option<LockHolder> {
+    let mut text = String::new();
+    file.seek(SeekFrom::Start(0)).ok()?;
+    file.read_to_string(&mut text).ok()?;
+    let mut lines = text.lines();
+    let pid = lines.next()?.trim().parse().ok()?;
+    let since = DateTime::parse_from_rfc3339(lines.next()?.trim()).ok()?;
+    Some(LockHolder {
+        pid,
+        since: since.with_timezone(&Utc),
+    })
+}
+
+fn describe(holder: Option<LockHolder>) -> String {
+    match holder {
+        Some(holder) => format!(
+            "process {} (since {})",
+            holder.pid,
+            holder.since.format("%Y-%m-%d %H:%M:%S UTC")
+        ),
+        None => "another process".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_second_lock_fails_until_first_is_dropped() {
+        let dir = tempfile::tempdir().unwrap();
+        let first = DatabaseLock::acquire(dir.path(), None).unwrap();
+
+        let holder = DatabaseLock::holder(dir.path()).expect("lock should be held");
+        assert_eq!(holder.pid, std::process::id());
+
+        let error = DatabaseLock::acquire(dir.path(), None).unwrap_err();
+        assert!(error.contains("Database is locked"), "{}", error);
+        assert!(error.contains(&std::process::id().to_string()), "{}", error);
+
+        let error =
+            DatabaseLock::acquire(dir.path(), Some(Duration::from_millis(250))).unwrap_err();
+        assert!(error.contains("after waiting"), "{}", error);
+
+        drop(first);
+        assert_eq!(DatabaseLock::holder(dir.path()), None);
+        DatabaseLock::acquire(dir.path(), None).unwrap();
+    }
+}
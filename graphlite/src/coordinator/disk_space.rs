@@ -0,0 +1,152 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! Free-space monitoring of the database volume
+//!
+//! Running out of space in the middle of a WAL append or a storage flush can
+//! leave a half-written record behind. Before each write the coordinator asks
+//! the `DiskMonitor` whether the volume still has `DatabaseConfig::min_free_disk_bytes`
+//! free; below that the write fails with a "Disk full" error
+//! (`QueryErrorKind::DiskFull`) while reads keep working. Writes resume on their
+//! own once space is freed.
+//!
+//! Free space is read at most once per `CHECK_INTERVAL` while the volume is
+//! above the threshold, and on every write while it is below, so recovery is
+//! noticed immediately. Entering and leaving the low-space state is logged, and
+//! `QueryCoordinator::disk_space` reports the current figures.
+
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// How long a free-space reading is reused while the volume has room
+const CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Free space of the database volume, returned by `QueryCoordinator::disk_space`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DiskSpaceStatus {
+    /// Free bytes at the last reading, if the volume could be read
+    pub free_bytes: Option<u64>,
+    /// Threshold below which writes are rejected (`None` = monitoring off)
+    pub min_free_bytes: Option<u64>,
+    /// Whether writes are being rejected right now
+    pub writes_rejected: bool,
+    /// Writes rejected for lack of space since the database was opened
+    pub rejected_writes: u64,
+}
+
+#[derive(Debug, Default)]
+struct MonitorState {
+    status: DiskSpaceStatus,
+    checked_at: Option<Instant>,
+}
+
+/// Checks the volume holding the database before writes
+#[derive(Debug)]
+pub(crate) struct DiskMonitor {
+    /// Database directory; `None` for coordinators built without a path
+    path: Option<PathBuf>,
+    state: Mutex<MonitorState>,
+}
+
+impl DiskMonitor {
+    pub(crate) fn new(path: Option<PathBuf>, min_free_bytes: Option<u64>) -> Self {
+        let state = MonitorState {
+            status: DiskSpaceStatus {
+                min_free_bytes,
+                ..DiskSpaceStatus::default()
+            },
+            checked_at: None,
+        };
+        Self {
+            path,
+            state: Mutex::new(state),
+        }
+    }
+
+    /// Change the threshold; `None` turns monitoring off
+    /// Database directory being monitored
+    pub(crate) fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    pub(crate) fn set_min_free_bytes(&self, min_free_bytes: Option<u64>) {
+        let mut state = self.lock();
+        state.status.min_free_bytes = min_free_bytes;
+        state.checked_at = None;
+        if min_free_bytes.is_none() {
+            state.status.writes_rejected = false;
+        }
+    }
+
+    /// Current figures, reading the volume if the last reading is stale
+    pub(crate) fn status(&self) -> DiskSpaceStatus {
+        let mut state = self.lock();
+        self.refresh(&mut state);
+        state.status.clone()
+    }
+
+    /// Fail with a "Disk full" error if the volume is below the threshold
+    pub(crate) fn check_write(&self) -> Result<(), String> {
+        let mut state = self.lock();
+        let Some(min_free_bytes) = state.status.min_free_bytes else {
+            return Ok(());
+        };
+        self.refresh(&mut state);
+        if !state.status.writes_rejected {
+            return Ok(());
+        }
+
+        state.status.rejected_writes += 1;
+        Err(format!(
+            "Disk full: {} bytes free on the database volume, below the {} byte minimum; writes are rejected until space is freed",
+            state.status.free_bytes.unwrap_or(0),
+            min_free_bytes
+        ))
+    }
+
+    fn refresh(&self, state: &mut MonitorState) {
+        let (Some(path), Some(min_free_bytes)) = (&self.path, state.status.min_free_bytes) else {
+            return;
+        };
+        let fresh = state
+            .checked_at
+            .is_some_and(|checked_at| checked_at.elapsed() < CHECK_INTERVAL);
+        if fresh && !state.status.writes_rejected {
+            return;
+        }
+
+        state.checked_at = Some(Instant::now());
+        let free_bytes = match fs2::available_space(path) {
+            Ok(free_bytes) => free_bytes,
+            Err(e) => {
+                // An unreadable volume is no reason to stop writes
+                log::warn!("Failed to read free space of {}: {}", path.display(), e);
+                state.status.free_bytes = None;
+                return;
+            }
+        };
+        state.status.free_bytes = Some(free_bytes);
+
+        let low = free_bytes < min_free_bytes;
+        if low && !state.status.writes_rejected {
+            log::error!(
+                "Free space on the volume of {} fell to {} bytes, below the {} byte minimum; rejecting writes",
+                path.display(),
+                free_bytes,
+                min_free_bytes
+            );
+        } else if !low && state.status.writes_rejected {
+            log::warn!(
+                "Free space on the volume of {} is back to {} bytes; accepting writes again",
+                path.display(),
+                free_bytes
+            );
+        }
+        state.status.writes_rejected = low;
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, MonitorState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
@@ -0,0 +1,210 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! Classification of coordinator error messages
+//!
+//! `QueryCoordinator` reports failures as strings of the form
+//! `Parse error: ...`, `Validation error: ...` or `Execution error: Variant(...)`.
+//! `QueryErrorKind::from_message` maps such a message onto a small set of
+//! categories so embedders (the CLI, FFI bindings) can branch on the kind of
+//! failure without matching message text themselves.
+
+/// Category of a failed query
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum QueryErrorKind {
+    /// The statement could not be parsed
+    Syntax,
+    /// The statement parsed but is invalid (types, planning, unsupported features)
+    Validation,
+    /// A graph, schema, session or other object does not exist
+    NotFound,
+    /// The statement conflicts with existing data, e.g. a duplicate name
+    ConstraintViolation,
+    /// The session may not perform the operation, or the database is read-only
+    PermissionDenied,
+    /// Invalid credentials
+    Authentication,
+    /// Transaction state prevents the operation
+    Transaction,
+    /// The query ran past its timeout
+    Timeout,
+    /// The query exceeded a row, memory or write-rate limit
+    LimitExceeded,
+    /// The database volume is low on space, so writes are rejected
+    DiskFull,
+    /// Reading or writing storage failed
+    Storage,
+    /// Any other failure
+    Other,
+}
+
+impl QueryErrorKind {
+    /// Classify an error message returned by `QueryCoordinator`
+    pub fn from_message(message: &str) -> Self {
+        if message.starts_with("Parse error:") {
+            return Self::Syntax;
+        }
+        if message.starts_with("Validation error:") || message.starts_with("Planning error:") {
+            return Self::Validation;
+        }
+        if message.starts_with("Authentication failed") {
+            return Self::Authentication;
+        }
+        if message.starts_with("Database is open in read-only mode") {
+            return Self::PermissionDenied;
+        }
+        if message.starts_with("Write rate limit exceeded") {
+            return Self::LimitExceeded;
+        }
+        if message.starts_with("Disk full") {
+            return Self::DiskFull;
+        }
+
+        // Execution errors carry the ExecutionError variant name
+        if let Some(detail) = message.strip_prefix("Execution error: ") {
+            let variant: String = detail
+                .chars()
+                .take_while(|c| c.is_ascii_alphanumeric())
+                .collect();
+            match variant.as_str() {
+                "SyntaxError" => return Self::Syntax,
+                "ValidationError"
+                | "SchemaValidation"
+                | "TypeError"
+                | "ExpressionError"
+                | "InvalidQuery"
+                | "UnsupportedOperator"
+                | "PlanningError" => return Self::Validation,
+                "NotFound" => return Self::NotFound,
+                "QueryTimeout" => return Self::Timeout,
+                "MemoryLimitExceeded" | "ResultLimitExceeded" | "TempSpaceExceeded" => {
+                    return Self::LimitExceeded
+                }
+                _ => {}
+            }
+            if let Some(kind) = Self::from_text(detail) {
+                return kind;
+            }
+            if variant == "StorageError" {
+                return Self::Storage;
+            }
+            return Self::Other;
+        }
+
+        Self::from_text(message).unwrap_or(Self::Other)
+    }
+
+    /// Stable snake_case name, e.g. for machine-readable output
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Syntax => "syntax",
+            Self::Validation => "validation",
+            Self::NotFound => "not_found",
+            Self::ConstraintViolation => "constraint_violation",
+            Self::PermissionDenied => "permission_denied",
+            Self::Authentication => "authentication",
+            Self::Transaction => "transaction",
+            Self::Timeout => "timeout",
+            Self::LimitExceeded => "limit_exceeded",
+            Self::DiskFull => "disk_full",
+            Self::Storage => "storage",
+            Self::Other => "other",
+        }
+    }
+
+    /// Categories recognizable from the wording of runtime and catalog errors
+    fn from_text(text: &str) -> Option<Self> {
+        let lower = text.to_lowercase();
+        if lower.contains("permission denied") || lower.contains("access denied") {
+            Some(Self::PermissionDenied)
+        } else if lower.contains("transaction") {
+            Some(Self::Transaction)
+        } else if lower.contains("already exists")
+            || lower.contains("duplicate")
+            || lower.contains("constraint")
+        {
+            Some(Self::ConstraintViolation)
+        } else if lower.contains("not found")
+            || lower.contains("does not exist")
+            || lower.contains("no graph context")
+        {
+            Some(Self::NotFound)
+        } else {
+            None
+        }
+    }
+}
+
+impl std::fmt::Display for QueryErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_message() {
+        let cases = [
+            ("Parse error: UnexpectedToken(Match)", QueryErrorKind::Syntax),
+            (
+                "Execution error: TypeError(\"cannot add\")",
+                QueryErrorKind::Validation,
+            ),
+            (
+                "Execution error: CatalogError(\"Graph does not exist: /nope/g\")",
+                QueryErrorKind::NotFound,
+            ),
+            (
+                "Execution error: CatalogError(\"Failed to create schema: Duplicate entry: Schema 'w' already exists\")",
+                QueryErrorKind::ConstraintViolation,
+            ),
+            (
+                "Execution error: RuntimeError(\"No active transaction to commit\")",
+                QueryErrorKind::Transaction,
+            ),
+            (
+                "Execution error: QueryTimeout { timeout_ms: 5 }",
+                QueryErrorKind::Timeout,
+            ),
+            (
+                "Execution error: ResultLimitExceeded { limit: 1, rows: 2 }",
+                QueryErrorKind::LimitExceeded,
+            ),
+            (
+                "Execution error: TempSpaceExceeded { limit: 16, requested: 20 }",
+                QueryErrorKind::LimitExceeded,
+            ),
+            (
+                "Execution error: StorageError(\"disk full\")",
+                QueryErrorKind::Storage,
+            ),
+            (
+                "Database is open in read-only mode: Insert statements are not allowed",
+                QueryErrorKind::PermissionDenied,
+            ),
+            (
+                "Write rate limit exceeded for graph /s/g: 0 write(s) queued, next slot in 900 ms (limit 1/s)",
+                QueryErrorKind::LimitExceeded,
+            ),
+            (
+                "Disk full: 1024 bytes free on the database volume, below the 67108864 byte minimum; writes are rejected until space is freed",
+                QueryErrorKind::DiskFull,
+            ),
+            (
+                "Authentication failed: Invalid credentials",
+                QueryErrorKind::Authentication,
+            ),
+            ("Session not found: abc", QueryErrorKind::NotFound),
+            (
+                "Execution error: RuntimeError(\"division by zero\")",
+                QueryErrorKind::Other,
+            ),
+        ];
+        for (message, kind) in cases {
+            assert_eq!(QueryErrorKind::from_message(message), kind, "{}", message);
+        }
+    }
+}
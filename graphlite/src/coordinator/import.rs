@@ -0,0 +1,297 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! CSV and JSON Lines import
+//!
+//! `QueryCoordinator::import_csv` turns every record of a CSV file into a node
+//! with one label and one property per column, then loads them through the bulk
+//! insert path in a single all-or-nothing write.
+//!
+//! `read_csv_records` and `read_json_lines_records` read files into property
+//! maps for callers that build nodes and edges themselves, such as
+//! `graphlite import`.
+
+use super::bulk::BulkNode;
+use crate::storage::Value;
+use std::collections::HashMap;
+use std::io::{BufRead, Read};
+
+/// How a CSV file is read
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CsvImportOptions {
+    /// Field separator
+    pub delimiter: u8,
+    /// Whether the first record holds the property names; without a header the
+    /// columns are named `column1`, `column2`, ...
+    pub has_header: bool,
+    /// Store numbers and `true`/`false` as numbers and booleans instead of strings
+    pub infer_types: bool,
+}
+
+impl Default for CsvImportOptions {
+    fn default() -> Self {
+        Self {
+            delimiter: b',',
+            has_header: true,
+            infer_types: true,
+        }
+    }
+}
+
+/// Outcome of a CSV import
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ImportSummary {
+    /// Data records read from the file
+    pub rows: usize,
+    /// Nodes actually created (records identical to an existing node are skipped)
+    pub nodes_created: usize,
+}
+
+/// Read every record of `input` as a node labeled `label`
+///
+/// Empty fields leave the property unset. Fails on the first malformed record or
+/// on a record whose field count differs from the header.
+pub(crate) fn read_csv_nodes<R: Read>(
+    input: R,
+    label: &str,
+    options: &CsvImportOptions,
+) -> Result<Vec<BulkNode>, String> {
+    Ok(read_csv_records(input, options)?
+        .into_iter()
+        .map(|properties| BulkNode {
+            labels: vec![label.to_string()],
+            properties,
+        })
+        .collect())
+}
+
+/// Read every record of a CSV file as a property map, one entry per non-empty field
+///
+/// Fails on the first malformed record or on a record whose field count differs
+/// from the header.
+pub fn read_csv_records<R: Read>(
+    input: R,
+    options: &CsvImportOptions,
+) -> Result<Vec<HashMap<String, Value>>, String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(options.delimiter)
+        .has_headers(options.has_header)
+        .from_reader(input);
+
+    let columns: Option<Vec<String>> = if options.has_header {
+        let headers = reader.headers().map_err(csv_error)?;
+        let mut columns = Vec::with_capacity(headers.len());
+        for (index, name) in headers.iter().enumerate() {
+            let name = name.trim();
+            if name.is_empty() {
+                return Err(format!(
+                    "CSV header has an empty column name at column {}",
+                    index + 1
+                ));
+            }
+            if columns.iter().any(|column| column == name) {
+                return Err(format!("CSV header has duplicate column '{}'", name));
+            }
+            columns.push(name.to_string());
+        }
+        Some(columns)
+    } else {
+        None
+    };
+
+    let mut records = Vec::new();
+    for record in reader.records() {
+        let record = record.map_err(csv_error)?;
+        records.push(
+            record
+                .iter()
+                .enumerate()
+                .filter(|(_, field)| !field.is_empty())
+                .map(|(index, field)| {
+                    let name = match &columns {
+                        Some(columns) => columns[index].clone(),
+                        None => format!("column{}", index + 1),
+                    };
+                    (name, field_value(field, options.infer_types))
+                })
+                .collect(),
+        );
+    }
+
+    Ok(records)
+}
+
+/// Read every line of a JSON Lines file as a property map
+///
+/// Each non-blank line must hold one JSON object. Values keep their JSON type;
+/// `null` leaves the property unset, arrays become lists and nested objects are
+/// stored as JSON documents.
+pub fn read_json_lines_records<R: BufRead>(
+    input: R,
+) -> Result<Vec<HashMap<String, Value>>, String> {
+    let mut records = Vec::new();
+    for (index, line) in input.lines().enumerate() {
+        let line_number = index + 1;
+        let line = line.map_err(|e| format!("Failed to read line {}: {}", line_number, e))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let object = match serde_json::from_str(&line) {
+            Ok(serde_json::Value::Object(object)) => object,
+            Ok(_) => return Err(format!("Line {} is not a JSON object", line_number)),
+            Err(e) => return Err(format!("Invalid JSON at line {}: {}", line_number, e)),
+        };
+
+        let mut properties = HashMap::with_capacity(object.len());
+        for (name, value) in object {
+            if let Some(value) = json_value(&value) {
+                properties.insert(name, value);
+            }
+        }
+        records.push(properties);
+    }
+    Ok(records)
+}
+
+/// Text a value is matched by when it identifies a node: strings as they are,
+/// anything else as displayed, so a key read as `42` from one file matches `"42"`
+/// from another
+pub fn key_text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Convert one JSON value, `None` for `null`; objects are kept as JSON documents
+fn json_value(value: &serde_json::Value) -> Option<Value> {
+    match value {
+        serde_json::Value::Null => None,
+        serde_json::Value::Bool(b) => Some(Value::Boolean(*b)),
+        serde_json::Value::Number(n) => n.as_f64().map(Value::Number),
+        serde_json::Value::String(s) => Some(Value::String(s.clone())),
+        serde_json::Value::Array(items) => Some(Value::List(
+            items
+                .iter()
+                .map(|item| json_value(item).unwrap_or(Value::Null))
+                .collect(),
+        )),
+        serde_json::Value::Object(_) => Some(Value::Json(value.clone())),
+    }
+}
+
+/// Convert one field, inferring numbers and booleans when asked to
+fn field_value(field: &str, infer_types: bool) -> Value {
+    if infer_types {
+        let trimmed = field.trim();
+        if let Ok(number) = trimmed.parse::<f64>() {
+            // Keep "NaN", "inf" and the like as text
+            if number.is_finite() {
+                return Value::Number(number);
+            }
+        }
+        if trimmed.eq_ignore_ascii_case("true") {
+            return Value::Boolean(true);
+        }
+        if trimmed.eq_ignore_ascii_case("false") {
+            return Value::Boolean(false);
+        }
+    }
+    Value::String(field.to_string())
+}
+
+/// Error message with the line of the offending record
+fn csv_error(error: csv::Error) -> String {
+    match error.position() {
+        Some(position) => format!("CSV error at line {}: {}", position.line(), error),
+        None => format!("CSV error: {}", error),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn property<'a>(node: &'a BulkNode, name: &str) -> Option<&'a Value> {
+        node.properties.get(name)
+    }
+
+    #[test]
+    fn test_read_csv_nodes_infers_types() {
+        let input = "name,age,active,city\nAlice,30,true,\n\"Smith, Bob\",41.5,FALSE,Paris\n";
+        let nodes =
+            read_csv_nodes(input.as_bytes(), "Person", &CsvImportOptions::default()).unwrap();
+
+        assert_eq!(nodes.len(), 2);
+        assert_eq!(nodes[0].labels, vec!["Person".to_string()]);
+        assert_eq!(property(&nodes[0], "age"), Some(&Value::Number(30.0)));
+        assert_eq!(property(&nodes[0], "active"), Some(&Value::Boolean(true)));
+        assert_eq!(property(&nodes[0], "city"), None);
+        assert_eq!(
+            property(&nodes[1], "name"),
+            Some(&Value::String("Smith, Bob".to_string()))
+        );
+        assert_eq!(property(&nodes[1], "active"), Some(&Value::Boolean(false)));
+
+        let options = CsvImportOptions {
+            delimiter: b';',
+            has_header: false,
+            infer_types: false,
+        };
+        let nodes = read_csv_nodes("x;1\n".as_bytes(), "Raw", &options).unwrap();
+        assert_eq!(
+            property(&nodes[0], "column2"),
+            Some(&Value::String("1".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_read_csv_nodes_rejects_malformed_input() {
+        let options = CsvImportOptions::default();
+
+        let error = read_csv_nodes("a,b\n1,2\n3\n".as_bytes(), "T", &options).unwrap_err();
+        assert!(error.contains("line 3"), "{}", error);
+        assert!(read_csv_nodes("a,a\n1,2\n".as_bytes(), "T", &options)
+            .unwrap_err()
+            .contains("duplicate column 'a'"));
+        assert!(read_csv_nodes("a,\n1,2\n".as_bytes(), "T", &options)
+            .unwrap_err()
+            .contains("empty column name"));
+    }
+
+    #[test]
+    fn test_read_json_lines_records() {
+        let input = "{\"id\": 1, \"name\": \"Alice\", \"tags\": [\"a\", \"b\"], \"age\": null}\n\n{\"id\": \"1\", \"active\": true}\n";
+        let records = read_json_lines_records(input.as_bytes()).unwrap();
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].get("id"), Some(&Value::Number(1.0)));
+        assert_eq!(records[0].get("age"), None);
+        assert_eq!(
+            records[0].get("tags"),
+            Some(&Value::List(vec![
+                Value::String("a".to_string()),
+                Value::String("b".to_string())
+            ]))
+        );
+        assert_eq!(records[1].get("active"), Some(&Value::Boolean(true)));
+        assert_eq!(key_text(&records[0]["id"]), key_text(&records[1]["id"]));
+
+        assert!(read_json_lines_records("[1]\n".as_bytes())
+            .unwrap_err()
+            .contains("Line 1 is not a JSON object"));
+
+        // Nested objects are kept as JSON documents
+        let records =
+            read_json_lines_records("{\"a\": {\"b\": 1}, \"c\": [{\"d\": 2}]}\n".as_bytes())
+                .unwrap();
+        assert_eq!(
+            records[0].get("a"),
+            Some(&Value::Json(serde_json::json!({"b": 1})))
+        );
+        assert_eq!(
+            records[0].get("c"),
+            Some(&Value::List(vec![Value::Json(serde_json::json!({"d": 2}))]))
+        );
+    }
+}
@@ -6,9 +6,35 @@
 //! The QueryCoordinator provides a unified entry point for query execution,
 //! properly coordinating all database components (session, storage, catalog, execution).
 
+pub mod bulk;
+pub mod config;
+pub mod conformance;
+pub mod db_lock;
+pub mod disk_space;
+pub mod error_kind;
+pub mod import;
 pub mod query_coordinator;
+pub mod stats;
+pub mod throttle;
+pub mod verify;
 
-pub use query_coordinator::{QueryCoordinator, QueryInfo, QueryPlan, QueryType};
+pub use bulk::{BulkEdge, BulkNode};
+pub use config::DatabaseConfig;
+pub use conformance::{ConformanceReport, ConformanceResult, ConformanceStatus, Feature};
+pub use db_lock::LockHolder;
+pub use disk_space::DiskSpaceStatus;
+pub use error_kind::QueryErrorKind;
+pub use import::{
+    key_text, read_csv_records, read_json_lines_records, CsvImportOptions, ImportSummary,
+};
+pub use query_coordinator::{
+    BatchError, QueryCoordinator, QueryInfo, QueryPlan, QueryType, SessionHandle,
+};
+pub use stats::{CacheStatistics, DatabaseStats, GraphStatistics, IndexStatistics};
+pub use throttle::{GraphWriteMetrics, WriteLimit, WriteMetrics};
+pub use verify::{CatalogProblem, VerifyReport};
 
 // Re-export types needed for the public API
-pub use crate::exec::{QueryResult, Row};
+pub use crate::exec::{
+    ActiveQuery, Notation, NumberFormat, OperatorTiming, QueryLimits, QueryResult, Row,
+};
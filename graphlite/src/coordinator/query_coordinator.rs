@@ -6,15 +6,29 @@
 //! This provides a clean API that wraps the session manager and properly
 //! coordinates query execution through the standard GraphLite components.
 
+use super::bulk::{self, BulkEdge, BulkNode};
+use super::config::DatabaseConfig;
+use super::conformance::{self, ConformanceReport};
+use super::db_lock::{DatabaseLock, LockHolder};
+use super::disk_space::{DiskMonitor, DiskSpaceStatus};
+use super::import::{self, CsvImportOptions, ImportSummary};
+use super::stats::{self, DatabaseStats};
+use super::throttle::{self, Admission, WriteLimit, WriteMetrics, WriteThrottle};
+use super::verify::{self, VerifyReport};
 use crate::ast::parser::parse_query;
 use crate::cache::CacheManager;
 use crate::catalog::manager::CatalogManager;
-use crate::exec::{ExecutionRequest, QueryExecutor, QueryResult};
+use crate::exec::temp_space::TempSpace;
+use crate::exec::{ActiveQuery, ExecutionRequest, QueryExecutor, QueryLimits, QueryResult};
 use crate::session::{
-    GlobalSessionProvider, InstanceSessionProvider, SessionManager, SessionMode, SessionProvider,
+    GlobalSessionProvider, HistoryEntry, InstanceSessionProvider, SessionManager, SessionMode,
+    SessionProvider, UserSession,
 };
-use crate::storage::{StorageManager, StorageMethod, StorageType};
+use crate::storage::integrity::StorageCheckReport;
+use crate::storage::{StorageManager, StorageMethod, Value};
+use crate::txn::decode::{DecodedStatement, SavepointRecord, StatementRecord};
 use crate::txn::TransactionManager;
+use std::collections::HashMap;
 use std::panic::{RefUnwindSafe, UnwindSafe};
 use std::path::Path;
 use std::sync::Arc;
@@ -32,6 +46,20 @@ pub struct QueryCoordinator {
     session_provider: Arc<dyn SessionProvider>,
     /// Query executor
     executor: Arc<QueryExecutor>,
+    /// Reject data and catalog modifications (see `DatabaseConfig::read_only`)
+    read_only: bool,
+    /// Resource limits applied to every query
+    limits: RwLock<QueryLimits>,
+    /// Number of statements kept in each session's history
+    history_size: usize,
+    /// Per-graph write-rate limits and write counters
+    write_throttle: WriteThrottle,
+    /// Held shared by every write and exclusively by `export_snapshot`
+    write_gate: RwLock<()>,
+    /// Rejects writes while the database volume is low on space
+    disk_monitor: DiskMonitor,
+    /// Lock on the database directory; last so it is released after the storage closes
+    _lock: Option<DatabaseLock>,
 }
 
 // Explicitly mark QueryCoordinator as UnwindSafe for FFI panic handling.
@@ -42,6 +70,15 @@ pub struct QueryCoordinator {
 impl UnwindSafe for QueryCoordinator {}
 impl RefUnwindSafe for QueryCoordinator {}
 
+impl Drop for QueryCoordinator {
+    fn drop(&mut self) {
+        // Remember which graphs were in use so the next open can warm them
+        if !self.read_only {
+            self.executor.save_cache_hot_set();
+        }
+    }
+}
+
 impl QueryCoordinator {
     /// Create a new QueryCoordinator from a database path (Simplified API)
     ///
@@ -69,50 +106,7 @@ impl QueryCoordinator {
     ///     .expect("Failed to execute query");
     /// ```
     pub fn from_path(db_path: impl AsRef<Path>) -> Result<Arc<Self>, String> {
-        let path = db_path.as_ref().to_path_buf();
-
-        // Initialize storage
-        let storage = Arc::new(
-            StorageManager::new(path.clone(), StorageMethod::DiskOnly, StorageType::Sled)
-                .map_err(|e| format!("Failed to initialize storage: {}", e))?,
-        );
-
-        // Initialize catalog manager
-        let catalog_manager = Arc::new(RwLock::new(CatalogManager::new(storage.clone())));
-
-        // Initialize transaction manager with database path
-        let transaction_manager = Arc::new(
-            TransactionManager::new(path.clone())
-                .map_err(|e| format!("Failed to initialize transaction manager: {}", e))?,
-        );
-
-        // Initialize cache manager
-        let cache_config = crate::cache::CacheConfig::default();
-        let cache_manager =
-            Some(Arc::new(CacheManager::new(cache_config).map_err(|e| {
-                format!("Failed to initialize cache manager: {}", e)
-            })?));
-
-        // Create instance-based session provider (embedded mode)
-        let session_provider: Arc<dyn SessionProvider> = Arc::new(InstanceSessionProvider::new(
-            transaction_manager.clone(),
-            storage.clone(),
-            catalog_manager.clone(),
-        ));
-
-        // Create query executor
-        let executor = Arc::new(
-            QueryExecutor::new(
-                storage.clone(),
-                catalog_manager.clone(),
-                transaction_manager.clone(),
-                session_provider.clone(),
-                cache_manager,
-            )
-            .map_err(|e| format!("Failed to initialize query executor: {}", e))?,
-        );
-
-        Ok(Arc::new(Self::new(executor, session_provider)))
+        Self::from_path_with_config(db_path, DatabaseConfig::default())
     }
 
     /// Create a new QueryCoordinator with specified session mode
@@ -158,12 +152,52 @@ impl QueryCoordinator {
     pub fn from_path_with_mode(
         db_path: impl AsRef<Path>,
         mode: SessionMode,
+    ) -> Result<Arc<Self>, String> {
+        Self::from_path_with_config(
+            db_path,
+            DatabaseConfig {
+                session_mode: mode,
+                ..DatabaseConfig::default()
+            },
+        )
+    }
+
+    /// Create a new QueryCoordinator with an explicit engine configuration
+    ///
+    /// # Arguments
+    /// * `db_path` - Path to the database directory
+    /// * `config` - Storage backend, cache budget, read-only flag, and log level
+    ///
+    /// # Returns
+    /// * `Ok(Arc<QueryCoordinator>)` - Initialized coordinator ready for use
+    /// * `Err(String)` - Error message if initialization fails
+    ///
+    /// # Example
+    /// ```no_run
+    /// use graphlite::{DatabaseConfig, QueryCoordinator};
+    ///
+    /// let config = DatabaseConfig {
+    ///     read_only: true,
+    ///     cache_size_bytes: Some(64 * 1024 * 1024),
+    ///     ..DatabaseConfig::default()
+    /// };
+    /// let coordinator = QueryCoordinator::from_path_with_config("./mydb", config)?;
+    /// # Ok::<(), String>(())
+    /// ```
+    pub fn from_path_with_config(
+        db_path: impl AsRef<Path>,
+        config: DatabaseConfig,
     ) -> Result<Arc<Self>, String> {
         let path = db_path.as_ref().to_path_buf();
 
+        config.apply_log_level();
+
+        // Keep other processes out of the directory, or wait for them as a standby
+        let lock = DatabaseLock::acquire(&path, config.standby_wait)?;
+
         // Initialize storage
         let storage = Arc::new(
-            StorageManager::new(path.clone(), StorageMethod::DiskOnly, StorageType::Sled)
+            StorageManager::new(path.clone(), StorageMethod::DiskOnly, config.storage_type)
                 .map_err(|e| format!("Failed to initialize storage: {}", e))?,
         );
 
@@ -175,16 +209,19 @@ impl QueryCoordinator {
             TransactionManager::new(path.clone())
                 .map_err(|e| format!("Failed to initialize transaction manager: {}", e))?,
         );
+        transaction_manager
+            .undo_spill()
+            .set_threshold(config.undo_spill_threshold);
 
         // Initialize cache manager
-        let cache_config = crate::cache::CacheConfig::default();
+        let cache_config = config.cache_config();
         let cache_manager =
             Some(Arc::new(CacheManager::new(cache_config).map_err(|e| {
                 format!("Failed to initialize cache manager: {}", e)
             })?));
 
         // Create session provider based on mode
-        let session_provider: Arc<dyn SessionProvider> = match mode {
+        let session_provider: Arc<dyn SessionProvider> = match config.session_mode {
             SessionMode::Instance => {
                 // Instance mode: Each coordinator has isolated sessions
                 Arc::new(InstanceSessionProvider::new(
@@ -203,6 +240,10 @@ impl QueryCoordinator {
             }
         };
 
+        // Scratch space for spilling queries, cleared of anything a crash left behind
+        let temp_space = Arc::new(TempSpace::new(&path));
+        temp_space.set_quota(config.temp_space_quota_bytes);
+
         // Create query executor
         let executor = Arc::new(
             QueryExecutor::new(
@@ -212,10 +253,22 @@ impl QueryCoordinator {
                 session_provider.clone(),
                 cache_manager,
             )
-            .map_err(|e| format!("Failed to initialize query executor: {}", e))?,
+            .map_err(|e| format!("Failed to initialize query executor: {}", e))?
+            .with_temp_space(temp_space),
         );
 
-        Ok(Arc::new(Self::new(executor, session_provider)))
+        // Reload the graphs that were most used before the last shutdown
+        executor.warm_cache_on_startup();
+
+        let mut coordinator = Self::new(executor, session_provider);
+        coordinator.read_only = config.read_only || storage.is_snapshot();
+        if coordinator.read_only && !config.read_only {
+            log::info!("{} is a snapshot; opened read-only", path.display());
+        }
+        coordinator.history_size = config.session_history_size;
+        coordinator.disk_monitor = DiskMonitor::new(Some(path), config.min_free_disk_bytes);
+        coordinator._lock = Some(lock);
+        Ok(Arc::new(coordinator))
     }
 
     /// Create a new QueryCoordinator (Advanced API)
@@ -230,9 +283,376 @@ impl QueryCoordinator {
         Self {
             session_provider,
             executor,
+            read_only: false,
+            limits: RwLock::new(QueryLimits::default()),
+            history_size: DatabaseConfig::default().session_history_size,
+            write_throttle: WriteThrottle::default(),
+            write_gate: RwLock::new(()),
+            disk_monitor: DiskMonitor::new(None, None),
+            _lock: None,
+        }
+    }
+
+    /// Process currently holding the database at `db_path`, if it is open anywhere
+    ///
+    /// A standby can use this to report what it is waiting for; see
+    /// `DatabaseConfig::standby_wait`.
+    pub fn lock_holder(db_path: impl AsRef<Path>) -> Option<LockHolder> {
+        DatabaseLock::holder(db_path.as_ref())
+    }
+
+    /// Whether this coordinator rejects data and catalog modifications
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    /// Set the resource limits applied to every subsequent query
+    ///
+    /// Queries that exceed a limit fail with an execution error instead of
+    /// returning a result. Limits apply to all sessions of this coordinator.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use graphlite::{QueryCoordinator, QueryLimits};
+    /// use std::time::Duration;
+    ///
+    /// let coordinator = QueryCoordinator::from_path("./mydb")?;
+    /// coordinator.set_query_limits(QueryLimits {
+    ///     max_result_rows: Some(10_000),
+    ///     max_memory_bytes: Some(256 * 1024 * 1024),
+    ///     query_timeout: Some(Duration::from_secs(5)),
+    /// });
+    /// # Ok::<(), String>(())
+    /// ```
+    pub fn set_query_limits(&self, limits: QueryLimits) {
+        *self.limits.write().unwrap_or_else(|e| e.into_inner()) = limits;
+    }
+
+    /// The resource limits currently applied to queries
+    pub fn query_limits(&self) -> QueryLimits {
+        *self.limits.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Limit the rate of writes to one graph, or lift the limit with `None`
+    ///
+    /// Write statements (INSERT, SET, REMOVE, DELETE and mutating MATCH or CALL
+    /// statements) run against the session's current graph, and each bulk load or
+    /// CSV import, count as one write. Once the limit's burst is used up, writes
+    /// wait for their slot and their result carries a warning with the delay and
+    /// queue depth; a write that would wait longer than `max_wait` fails with a
+    /// "Write rate limit exceeded" error instead.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use graphlite::{QueryCoordinator, WriteLimit};
+    /// use std::time::Duration;
+    ///
+    /// let coordinator = QueryCoordinator::from_path("./mydb")?;
+    /// coordinator.set_write_limit(
+    ///     "/app/events",
+    ///     Some(WriteLimit {
+    ///         writes_per_second: 200.0,
+    ///         burst: 50,
+    ///         max_wait: Duration::from_secs(2),
+    ///     }),
+    /// )?;
+    /// # Ok::<(), String>(())
+    /// ```
+    pub fn set_write_limit(&self, graph: &str, limit: Option<WriteLimit>) -> Result<(), String> {
+        self.write_throttle.set_limit(graph, limit)
+    }
+
+    /// The write limit of a graph, if any
+    pub fn write_limit(&self, graph: &str) -> Option<WriteLimit> {
+        self.write_throttle.limit(graph)
+    }
+
+    /// Write counters, queue depths and WAL flush lag
+    ///
+    /// Lets ingest clients watch backpressure and slow down before writes start
+    /// waiting or failing.
+    pub fn write_metrics(&self) -> WriteMetrics {
+        WriteMetrics {
+            graphs: self.write_throttle.metrics(),
+            wal_flush_lag: self.executor.transaction_manager().wal_flush_lag(),
         }
     }
 
+    /// The statements running right now, oldest first
+    ///
+    /// Each entry carries the session, the query text and its fingerprint, the
+    /// time it has been running and the physical operator it is executing,
+    /// which shows where a query that seems to hang is stuck. The same list is
+    /// returned by `CALL gql.active_queries()`.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use graphlite::QueryCoordinator;
+    /// let coordinator = QueryCoordinator::from_path("./mydb")?;
+    /// for query in coordinator.active_queries() {
+    ///     println!("{} {:?} {:?}", query.query_id, query.elapsed, query.operator);
+    /// }
+    /// # Ok::<(), String>(())
+    /// ```
+    pub fn active_queries(&self) -> Vec<ActiveQuery> {
+        self.executor.active_queries()
+    }
+
+    /// Free space of the database volume and the writes rejected for lack of it
+    ///
+    /// Writes fail with a "Disk full" error (`QueryErrorKind::DiskFull`) while
+    /// the volume has less than `DatabaseConfig::min_free_disk_bytes` free;
+    /// reads keep working and writes resume once space is freed.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use graphlite::QueryCoordinator;
+    /// let coordinator = QueryCoordinator::from_path("./mydb")?;
+    /// let disk = coordinator.disk_space();
+    /// if disk.writes_rejected {
+    ///     eprintln!("low on disk: {:?} bytes free", disk.free_bytes);
+    /// }
+    /// # Ok::<(), String>(())
+    /// ```
+    pub fn disk_space(&self) -> DiskSpaceStatus {
+        self.disk_monitor.status()
+    }
+
+    /// Change the free space the database volume must keep, or stop checking with `None`
+    pub fn set_min_free_disk_bytes(&self, min_free_bytes: Option<u64>) {
+        self.disk_monitor.set_min_free_bytes(min_free_bytes);
+    }
+
+    /// Write a copy of the database to `dest_path` while it stays open
+    ///
+    /// Data is copied through the storage engine, so the backup is a complete
+    /// database directory that `from_path` (or `restore_backup`) can use. `dest_path`
+    /// must not exist or be an empty directory. Fails while any session has an open
+    /// transaction, so the backup holds committed data only; statements running
+    /// concurrently on other threads may or may not be included.
+    ///
+    /// # Returns
+    /// * `Ok(usize)` - Number of storage entries copied
+    /// * `Err(String)` - Error message if the backup could not be written
+    ///
+    /// # Example
+    /// ```no_run
+    /// use graphlite::QueryCoordinator;
+    ///
+    /// let coordinator = QueryCoordinator::from_path("./mydb")?;
+    /// coordinator.backup("./backups/mydb-2025-01-01")?;
+    /// # Ok::<(), String>(())
+    /// ```
+    pub fn backup<P: AsRef<Path>>(&self, dest_path: P) -> Result<usize, String> {
+        let open_transactions = self
+            .executor
+            .transaction_manager()
+            .get_active_transaction_ids()
+            .map_err(|e| format!("Failed to check transactions: {}", e))?;
+        if !open_transactions.is_empty() {
+            return Err("Cannot back up while transactions are open".to_string());
+        }
+
+        self.executor
+            .storage()
+            .backup_to(dest_path)
+            .map_err(|e| format!("Backup failed: {}", e))
+    }
+
+    /// Write a consistent, compacted read-only copy of the database to `dest_path`
+    ///
+    /// Meant for a separate analytics process: it opens the snapshot with
+    /// `from_path` and runs heavy scans there without slowing down this database.
+    /// A snapshot always opens read-only; `restore_backup` turns it into a normal
+    /// writable database. Writes wait while the snapshot is taken, so it holds
+    /// exactly the statements that completed before it. Entries are rewritten into
+    /// a fresh storage directory, leaving behind the space of deleted data.
+    /// `dest_path` must not exist or be an empty directory. Fails while any
+    /// session has an open transaction.
+    ///
+    /// # Returns
+    /// * `Ok(usize)` - Number of storage entries copied
+    /// * `Err(String)` - Error message if the snapshot could not be written
+    ///
+    /// # Example
+    /// ```no_run
+    /// use graphlite::QueryCoordinator;
+    ///
+    /// let coordinator = QueryCoordinator::from_path("./mydb")?;
+    /// coordinator.export_snapshot("./snapshots/mydb-nightly")?;
+    ///
+    /// // In the analytics process
+    /// let analytics = QueryCoordinator::from_path("./snapshots/mydb-nightly")?;
+    /// assert!(analytics.is_read_only());
+    /// # Ok::<(), String>(())
+    /// ```
+    pub fn export_snapshot<P: AsRef<Path>>(&self, dest_path: P) -> Result<usize, String> {
+        let _no_writes = self.write_gate.write().unwrap_or_else(|e| e.into_inner());
+        let open_transactions = self
+            .executor
+            .transaction_manager()
+            .get_active_transaction_ids()
+            .map_err(|e| format!("Failed to check transactions: {}", e))?;
+        if !open_transactions.is_empty() {
+            return Err("Cannot export a snapshot while transactions are open".to_string());
+        }
+
+        self.executor
+            .storage()
+            .snapshot_to(dest_path)
+            .map_err(|e| format!("Snapshot failed: {}", e))
+    }
+
+    /// Check the stored data for unreadable or corrupt records
+    ///
+    /// The quick check (`deep = false`) opens every storage tree and reads its first
+    /// record. The deep check reads every record and verifies the checksum of each
+    /// node and edge, which takes time proportional to the database size. Problems
+    /// are listed in the report rather than returned as an error.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use graphlite::QueryCoordinator;
+    ///
+    /// let coordinator = QueryCoordinator::from_path("./mydb")?;
+    /// let report = coordinator.check_storage(true)?;
+    /// for problem in &report.problems {
+    ///     eprintln!("{}", problem);
+    /// }
+    /// # Ok::<(), String>(())
+    /// ```
+    pub fn check_storage(&self, deep: bool) -> Result<StorageCheckReport, String> {
+        self.executor
+            .storage()
+            .check_storage(deep)
+            .map_err(|e| e.to_string())
+    }
+
+    /// Run the GQL conformance corpus against this database
+    ///
+    /// Each feature in `conformance::FEATURES` (optionally only those whose id
+    /// starts with `filter`) runs its test in a fresh graph under the
+    /// `/conformance` schema, which is dropped afterwards. The report says
+    /// whether the engine behaves as the feature matrix claims; run it on a
+    /// scratch database, as `graphlite conformance` does.
+    pub fn run_conformance(&self, filter: Option<&str>) -> Result<ConformanceReport, String> {
+        conformance::run(self, filter)
+    }
+
+    /// Verify the integrity of the whole database
+    ///
+    /// Runs the deep storage check, checks that every stored edge references
+    /// stored nodes, and checks the catalog: every graph's schema and graph
+    /// type must exist and every stored graph needs a catalog entry. With
+    /// `repair`, edges referencing missing nodes are deleted; other problems
+    /// are only reported. Writes are held off while the check runs.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use graphlite::QueryCoordinator;
+    ///
+    /// let coordinator = QueryCoordinator::from_path("./mydb")?;
+    /// let report = coordinator.verify(false)?;
+    /// if !report.is_ok() {
+    ///     eprintln!("{} problem(s) found", report.problem_count());
+    /// }
+    /// # Ok::<(), String>(())
+    /// ```
+    pub fn verify(&self, repair: bool) -> Result<VerifyReport, String> {
+        if repair && self.read_only {
+            return Err("Database is open in read-only mode: cannot repair".to_string());
+        }
+        let _no_writes = self.write_gate.write().unwrap_or_else(|e| e.into_inner());
+        let catalog_manager = self.session_provider.get_catalog_manager();
+        let mut catalog = catalog_manager
+            .write()
+            .map_err(|_| "Failed to acquire catalog write lock".to_string())?;
+        verify::verify(&self.executor.storage(), &mut catalog, repair)
+    }
+
+    /// Statistics of the whole database
+    ///
+    /// For every graph in the catalog: node and edge counts, the number of
+    /// nodes per label and edges per relationship type, how many nodes and
+    /// edges have each property key, and the entries of each index. Also the
+    /// size of the database directory on disk and the entries, memory and hit
+    /// rate of each cache. Every graph is loaded to count it.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use graphlite::QueryCoordinator;
+    ///
+    /// let coordinator = QueryCoordinator::from_path("./mydb")?;
+    /// let stats = coordinator.stats()?;
+    /// for graph in &stats.graphs {
+    ///     println!("{}: {} nodes, {} edges", graph.path, graph.nodes, graph.edges);
+    /// }
+    /// # Ok::<(), String>(())
+    /// ```
+    pub fn stats(&self) -> Result<DatabaseStats, String> {
+        let catalog_manager = self.session_provider.get_catalog_manager();
+        let mut catalog = catalog_manager
+            .write()
+            .map_err(|_| "Failed to acquire catalog write lock".to_string())?;
+        let cache_manager = self.executor.cache_manager();
+        stats::collect(
+            &self.executor.storage(),
+            &mut catalog,
+            cache_manager.as_deref(),
+            self.disk_monitor.path(),
+        )
+    }
+
+    /// Number of corrupt records detected since the process started
+    ///
+    /// Counts checksum mismatches and undecodable records found while loading
+    /// graphs or running `check_storage`; anything above zero means the storage
+    /// files were damaged outside GraphLite.
+    pub fn corrupt_records_detected(&self) -> u64 {
+        crate::storage::integrity::corrupt_records_detected()
+    }
+
+    /// Restore a backup written by `backup` into a new database directory
+    ///
+    /// `dest_path` must not exist or be an empty directory, and the backup must not
+    /// be open. Open the restored database with `from_path` afterwards.
+    ///
+    /// # Returns
+    /// * `Ok(usize)` - Number of storage entries copied
+    /// * `Err(String)` - Error message if the backup could not be restored
+    pub fn restore_backup<P: AsRef<Path>, Q: AsRef<Path>>(
+        backup_path: P,
+        dest_path: Q,
+    ) -> Result<usize, String> {
+        crate::storage::backup::restore(backup_path.as_ref(), dest_path.as_ref())
+            .map_err(|e| format!("Restore failed: {}", e))
+    }
+
+    /// Decode the write-ahead log of a database into the GQL statements it records
+    ///
+    /// Returns the committed write statements in the order they became durable,
+    /// starting at log sequence number `from_lsn`; `DecodedStatement::to_gql_script`
+    /// turns them into a script that replays the changes. Reads the WAL files
+    /// directly, so the database may be open or closed. Bulk loads, imports and
+    /// user or role statements are not logged and do not appear.
+    ///
+    /// The same data is available from GQL via `CALL gql.decode_wal(from_lsn)`.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use graphlite::{DecodedStatement, QueryCoordinator};
+    ///
+    /// let statements = QueryCoordinator::decode_wal("./mydb", 0)?;
+    /// print!("{}", DecodedStatement::to_gql_script(&statements));
+    /// # Ok::<(), String>(())
+    /// ```
+    pub fn decode_wal<P: AsRef<Path>>(
+        db_path: P,
+        from_lsn: u64,
+    ) -> Result<Vec<DecodedStatement>, String> {
+        crate::txn::decode::decode_wal(&db_path.as_ref().join("wal"), from_lsn)
+    }
+
     /// Execute a query with session ID
     ///
     /// This is the main entry point for query execution.
@@ -245,36 +665,372 @@ impl QueryCoordinator {
     /// * `Ok(QueryResult)` - Query result on success
     /// * `Err(String)` - Error message on failure
     pub fn process_query(&self, query_text: &str, session_id: &str) -> Result<QueryResult, String> {
+        let session = self.session_provider.get_session(session_id);
+        self.process_query_in_session(query_text, session_id, session)
+    }
+
+    /// Execute a query with `$name` parameters bound to values
+    ///
+    /// Each parameter is replaced by a literal for its value before the query is
+    /// parsed, so values are never interpreted as GQL. Every parameter in the
+    /// query needs a value; strings containing both `'` and `"`, and node, edge,
+    /// path and temporal values, cannot be bound.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use graphlite::{QueryCoordinator, Value};
+    /// # use std::collections::HashMap;
+    /// # let coordinator = QueryCoordinator::from_path("./mydb").unwrap();
+    /// # let session_id = coordinator.create_simple_session("user").unwrap();
+    /// let params = HashMap::from([("name".to_string(), Value::String("Alice".to_string()))]);
+    /// let result = coordinator.process_query_with_params(
+    ///     "MATCH (p:Person {name: $name}) RETURN p.age",
+    ///     &params,
+    ///     &session_id,
+    /// )?;
+    /// # Ok::<(), String>(())
+    /// ```
+    pub fn process_query_with_params(
+        &self,
+        query_text: &str,
+        params: &HashMap<String, Value>,
+        session_id: &str,
+    ) -> Result<QueryResult, String> {
+        let query_text = crate::ast::parameters::bind_parameters(query_text, params)
+            .map_err(|e| format!("Validation error: {}", e))?;
+        self.process_query(&query_text, session_id)
+    }
+
+    /// Execute a query on a session handle
+    ///
+    /// Same as `process_query`, but the session was resolved when the handle was
+    /// created, so repeated queries skip the session lookup.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use graphlite::QueryCoordinator;
+    /// # let coordinator = QueryCoordinator::from_path("./mydb").unwrap();
+    /// let session_id = coordinator.create_simple_session("user")?;
+    /// let session = coordinator.session_handle(&session_id)?;
+    /// coordinator.process_query_with_handle("SESSION SET SCHEMA /app", &session)?;
+    /// assert_eq!(session.current_schema().as_deref(), Some("/app"));
+    /// # Ok::<(), String>(())
+    /// ```
+    pub fn process_query_with_handle(
+        &self,
+        query_text: &str,
+        handle: &SessionHandle,
+    ) -> Result<QueryResult, String> {
+        if !handle.is_active() {
+            return Err(format!("Session not found: {}", handle.id));
+        }
+        self.process_query_in_session(query_text, &handle.id, Some(handle.session.clone()))
+    }
+
+    /// Resolve a session ID into a handle for `process_query_with_handle`
+    ///
+    /// The handle stays usable until the session is closed.
+    pub fn session_handle(&self, session_id: &str) -> Result<SessionHandle, String> {
+        let session = self
+            .session_provider
+            .get_session(session_id)
+            .ok_or_else(|| format!("Session not found: {}", session_id))?;
+        Ok(SessionHandle {
+            id: session_id.to_string(),
+            session,
+        })
+    }
+
+    /// Execute a statement and record it in the session history
+    fn process_query_in_session(
+        &self,
+        query_text: &str,
+        session_id: &str,
+        session: Option<Arc<RwLock<UserSession>>>,
+    ) -> Result<QueryResult, String> {
+        let executed_at = chrono::Utc::now();
+        let started = std::time::Instant::now();
+        let result = self
+            .execute_statement(query_text, session_id, session.as_ref())
+            .map(|mut result| {
+                result.align_columns();
+                result
+            });
+        if let Some(session) = &session {
+            self.record_history(
+                session,
+                query_text,
+                executed_at,
+                started.elapsed(),
+                result.as_ref().err(),
+            );
+        }
+        result
+    }
+
+    /// Recent statements of a session, oldest first
+    ///
+    /// Each session keeps the last `DatabaseConfig::session_history_size` statements
+    /// passed to `process_query`, including the ones that failed. The same data is
+    /// available from GQL via `CALL gql.session_history()`.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use graphlite::QueryCoordinator;
+    /// # let coordinator = QueryCoordinator::from_path("./mydb").unwrap();
+    /// # let session_id = coordinator.create_simple_session("user").unwrap();
+    /// for entry in coordinator.session_history(&session_id)? {
+    ///     println!("{}: {}", entry.sequence, entry.statement);
+    /// }
+    /// # Ok::<(), String>(())
+    /// ```
+    pub fn session_history(&self, session_id: &str) -> Result<Vec<HistoryEntry>, String> {
+        let session_arc = self
+            .session_provider
+            .get_session(session_id)
+            .ok_or_else(|| format!("Session not found: {}", session_id))?;
+        let session = session_arc
+            .read()
+            .map_err(|e| format!("Failed to acquire session read lock: {}", e))?;
+        Ok(session.history.entries().cloned().collect())
+    }
+
+    /// Append a finished statement to the session's history
+    fn record_history(
+        &self,
+        session: &RwLock<UserSession>,
+        query_text: &str,
+        executed_at: chrono::DateTime<chrono::Utc>,
+        elapsed: std::time::Duration,
+        error: Option<&String>,
+    ) {
+        let Ok(mut session) = session.write() else {
+            return;
+        };
+        session.history.set_capacity(self.history_size);
+        session.history.record(
+            query_text.trim(),
+            executed_at,
+            elapsed.as_millis() as u64,
+            error.cloned(),
+        );
+    }
+
+    /// Parse and execute one statement
+    fn execute_statement(
+        &self,
+        query_text: &str,
+        session_id: &str,
+        session: Option<&Arc<RwLock<UserSession>>>,
+    ) -> Result<QueryResult, String> {
         // Parse query
         let document = parse_query(query_text).map_err(|e| format!("Parse error: {:?}", e))?;
 
-        // Get session
-        let session = self.session_provider.get_session(session_id);
+        let info = Self::statement_info(&document.statement);
+        let savepoint = Self::savepoint_record(&document.statement);
+        if self.read_only && info.modifies_data() {
+            return Err(format!(
+                "Database is open in read-only mode: {:?} statements are not allowed",
+                info.query_type
+            ));
+        }
+        if info.modifies_data() {
+            self.disk_monitor.check_write()?;
+        }
+        if info.modifies_data()
+            && session.is_some_and(|s| {
+                s.read()
+                    .ok()
+                    .and_then(|s| s.transaction_state.is_read_only().ok())
+                    .unwrap_or(false)
+            })
+        {
+            return Err(format!(
+                "Transaction is read-only: {:?} statements are not allowed",
+                info.query_type
+            ));
+        }
+
+        // Names the session graph's type does not define fail in strict mode
+        let schema_warnings = self.check_graph_type(&document, session)?;
+
+        // Writes wait for a slot if their graph is rate limited
+        let write_graph = if info.writes_graph_data() {
+            session.and_then(|s| s.read().ok()?.current_graph.clone())
+        } else {
+            None
+        };
+        let admission = match &write_graph {
+            Some(graph) => Some(self.write_throttle.acquire(graph)?),
+            None => None,
+        };
 
         // Create execution request
         let request = ExecutionRequest::new(document.statement)
-            .with_session(session)
-            .with_query_text(Some(query_text.to_string()));
+            .with_session(session.cloned())
+            .with_query_text(Some(query_text.to_string()))
+            .with_limits(self.query_limits());
 
-        // Execute query
-        let result = self
+        // Execute query; writes are held back while a snapshot is taken
+        let writing = info.modifies_data().then(|| self.writing());
+        let mut result = self
             .executor
             .execute_query(request)
             .map_err(|e| format!("Execution error: {:?}", e))?;
+        drop(writing);
+
+        result.warnings.extend(schema_warnings);
+        if let (Some(graph), Some(admission)) = (&write_graph, &admission) {
+            result
+                .warnings
+                .extend(self.backpressure_warnings(graph, admission));
+        }
 
         // Process any session results (SET GRAPH, SET SCHEMA, etc.)
         if let Some(ref session_result) = result.session_result {
-            self.handle_session_result(session_result, session_id)?;
+            self.handle_session_result(session_result, session_id, session)?;
+        }
+
+        if info.modifies_data() || savepoint.is_some() {
+            self.log_statement(&info, query_text, session, savepoint);
         }
 
         Ok(result)
     }
 
+    /// Check a statement against the graph type of the session graph
+    ///
+    /// Labels, edge types and properties the graph type does not define fail the
+    /// statement when the session's schema enforcement mode is `strict` and are
+    /// returned as warnings in `advisory` mode. Untyped graphs are not checked.
+    fn check_graph_type(
+        &self,
+        document: &crate::ast::Document,
+        session: Option<&Arc<RwLock<UserSession>>>,
+    ) -> Result<Vec<String>, String> {
+        let Some((mode, graph)) = session.and_then(|s| {
+            let s = s.read().ok()?;
+            Some((
+                s.get_schema_enforcement_mode().to_string(),
+                s.current_graph.clone()?,
+            ))
+        }) else {
+            return Ok(Vec::new());
+        };
+        if mode == "disabled" {
+            return Ok(Vec::new());
+        }
+        let graph_type = self
+            .session_provider
+            .get_catalog_manager()
+            .read()
+            .ok()
+            .and_then(|catalog| catalog.graph_type_of(&graph));
+        let Some(graph_type) = graph_type else {
+            return Ok(Vec::new());
+        };
+
+        let problems: Vec<String> =
+            crate::ast::validator::validate_against_graph_type(document, &graph_type)
+                .into_iter()
+                .map(|error| error.message)
+                .collect();
+        if mode == "strict" && !problems.is_empty() {
+            return Err(format!("Schema validation error: {}", problems.join("; ")));
+        }
+        Ok(problems)
+    }
+
+    /// Savepoint set, rolled back to or released by a statement
+    fn savepoint_record(statement: &crate::ast::Statement) -> Option<SavepointRecord> {
+        use crate::ast::TransactionStatement;
+        match statement {
+            crate::ast::Statement::TransactionStatement(txn) => match txn {
+                TransactionStatement::Savepoint(s) => Some(SavepointRecord::Set(s.name.clone())),
+                TransactionStatement::RollbackToSavepoint(s) => {
+                    Some(SavepointRecord::RollbackTo(s.name.clone()))
+                }
+                TransactionStatement::ReleaseSavepoint(s) => {
+                    Some(SavepointRecord::Release(s.name.clone()))
+                }
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Log a successful write statement to the WAL for logical decoding
+    ///
+    /// User and role statements are left out so passwords never reach the log.
+    /// Savepoint statements are logged too, so decoding can drop the writes they undo.
+    fn log_statement(
+        &self,
+        info: &QueryInfo,
+        query_text: &str,
+        session: Option<&Arc<RwLock<UserSession>>>,
+        savepoint: Option<SavepointRecord>,
+    ) {
+        if matches!(
+            info.query_type,
+            QueryType::CreateUser
+                | QueryType::DropUser
+                | QueryType::CreateRole
+                | QueryType::DropRole
+                | QueryType::GrantRole
+                | QueryType::RevokeRole
+        ) {
+            return;
+        }
+
+        let (schema, graph, transaction_id) = match session.and_then(|s| s.read().ok()) {
+            Some(session) => (
+                session.current_schema.clone(),
+                session.current_graph.clone(),
+                session
+                    .transaction_state
+                    .current_transaction_id()
+                    .ok()
+                    .flatten(),
+            ),
+            None => (None, None, None),
+        };
+        let record = StatementRecord {
+            schema,
+            graph,
+            statement: query_text.trim().to_string(),
+            savepoint,
+        };
+        if let Err(e) = self
+            .executor
+            .transaction_manager()
+            .log_statement(transaction_id, record.to_description())
+        {
+            log::warn!("Failed to log statement for WAL decoding: {}", e);
+        }
+    }
+
+    /// Warnings telling a writer it is being slowed down
+    fn backpressure_warnings(&self, graph: &str, admission: &Admission) -> Vec<String> {
+        let mut warnings: Vec<String> = admission
+            .warning(graph, self.write_throttle.limit(graph))
+            .into_iter()
+            .collect();
+        let wal_flush_lag = self.executor.transaction_manager().wal_flush_lag();
+        if wal_flush_lag > throttle::WAL_FLUSH_LAG_WARNING {
+            warnings.push(format!(
+                "WAL flush took {} ms; disk syncs are slowing writes down",
+                wal_flush_lag.as_millis()
+            ));
+        }
+        warnings
+    }
+
     /// Handle session-modifying results (SET GRAPH, SET SCHEMA)
     fn handle_session_result(
         &self,
         session_result: &crate::exec::SessionResult,
         session_id: &str,
+        session: Option<&Arc<RwLock<UserSession>>>,
     ) -> Result<(), String> {
         use crate::ast::GraphExpression;
 
@@ -283,11 +1039,8 @@ impl QueryCoordinator {
                 graph_expression,
                 validated: _,
             } => {
-                // Get session
-                let session_arc = self
-                    .session_provider
-                    .get_session(session_id)
-                    .ok_or_else(|| format!("Session not found: {}", session_id))?;
+                let session_arc =
+                    session.ok_or_else(|| format!("Session not found: {}", session_id))?;
 
                 let mut session = session_arc
                     .write()
@@ -343,11 +1096,8 @@ impl QueryCoordinator {
                 schema_reference,
                 validated: _,
             } => {
-                // Get session
-                let session_arc = self
-                    .session_provider
-                    .get_session(session_id)
-                    .ok_or_else(|| format!("Session not found: {}", session_id))?;
+                let session_arc =
+                    session.ok_or_else(|| format!("Session not found: {}", session_id))?;
 
                 let mut session = session_arc
                     .write()
@@ -360,6 +1110,36 @@ impl QueryCoordinator {
 
                 Ok(())
             }
+            crate::exec::SessionResult::Reset { args } => {
+                use crate::ast::{SessionResetArgs, SessionResetTarget as ResetAll};
+                use crate::session::models::SessionResetTarget;
+
+                let session_arc =
+                    session.ok_or_else(|| format!("Session not found: {}", session_id))?;
+
+                let mut session = session_arc
+                    .write()
+                    .map_err(|e| format!("Failed to acquire session write lock: {}", e))?;
+
+                let target = args.as_ref().map(|args| match args {
+                    SessionResetArgs::All {
+                        target: ResetAll::Parameters,
+                    } => SessionResetTarget::AllParameters,
+                    SessionResetArgs::All {
+                        target: ResetAll::Characteristics,
+                    } => SessionResetTarget::AllCharacteristics,
+                    SessionResetArgs::Schema => SessionResetTarget::Schema,
+                    SessionResetArgs::Graph => SessionResetTarget::Graph,
+                    SessionResetArgs::TimeZone => SessionResetTarget::TimeZone,
+                    SessionResetArgs::Parameter { parameter } => {
+                        SessionResetTarget::Parameter(parameter.clone())
+                    }
+                });
+                session.reset(target);
+                log::debug!("Session {} reset", session_id);
+
+                Ok(())
+            }
             _ => Ok(()), // Other session results don't need special handling
         }
     }
@@ -549,6 +1329,312 @@ impl QueryCoordinator {
         Ok(())
     }
 
+    /// Bulk load nodes into the session's current graph
+    ///
+    /// Records are written directly to storage without parsing or planning a
+    /// statement per node, and the whole batch is persisted in one save.
+    /// Node ids are content hashes: a node identical to an existing one is not
+    /// created again, but its id is still returned.
+    ///
+    /// Bulk loads are applied immediately and cannot run inside an open transaction.
+    ///
+    /// # Returns
+    /// * `Ok(Vec<String>)` - Storage id of each input node, in input order
+    /// * `Err(String)` - Error message if the batch could not be loaded (nothing is written)
+    pub fn bulk_insert_nodes(
+        &self,
+        session_id: &str,
+        nodes: Vec<BulkNode>,
+    ) -> Result<Vec<String>, String> {
+        self.bulk_load(session_id, |graph| {
+            let (ids, created) = bulk::insert_nodes(graph, nodes)?;
+            Ok((ids, created > 0))
+        })
+    }
+
+    /// Bulk load edges into the session's current graph
+    ///
+    /// Endpoints are storage ids, such as those returned by `bulk_insert_nodes`,
+    /// and must already exist. Duplicate edges are skipped. As with nodes, the
+    /// batch is all-or-nothing and cannot run inside an open transaction.
+    ///
+    /// # Returns
+    /// * `Ok(usize)` - Number of edges created
+    /// * `Err(String)` - Error message if the batch could not be loaded (nothing is written)
+    pub fn bulk_insert_edges(
+        &self,
+        session_id: &str,
+        edges: Vec<BulkEdge>,
+    ) -> Result<usize, String> {
+        self.bulk_load(session_id, |graph| {
+            let created = bulk::insert_edges(graph, edges)?;
+            Ok((created, created > 0))
+        })
+    }
+
+    /// Import a CSV file as nodes into the session's current graph
+    ///
+    /// Every record becomes a node labeled `label` with one property per column
+    /// (see `CsvImportOptions` for header and type handling). The file is read in
+    /// full before anything is written, then loaded like `bulk_insert_nodes`: in
+    /// one save, skipping records identical to an existing node, and not inside an
+    /// open transaction.
+    ///
+    /// # Returns
+    /// * `Ok(ImportSummary)` - Records read and nodes created
+    /// * `Err(String)` - Error message if the file could not be read or loaded (nothing is written)
+    pub fn import_csv<P: AsRef<Path>>(
+        &self,
+        session_id: &str,
+        path: P,
+        label: &str,
+        options: &CsvImportOptions,
+    ) -> Result<ImportSummary, String> {
+        let label = label.trim();
+        if label.is_empty() {
+            return Err("CSV import requires a node label".to_string());
+        }
+
+        let path = path.as_ref();
+        self.bulk_load(session_id, |graph| {
+            let file = std::fs::File::open(path)
+                .map_err(|e| format!("Failed to open CSV file {}: {}", path.display(), e))?;
+            let nodes = import::read_csv_nodes(std::io::BufReader::new(file), label, options)?;
+            let rows = nodes.len();
+
+            let (_, nodes_created) = bulk::insert_nodes(graph, nodes)?;
+            let summary = ImportSummary {
+                rows,
+                nodes_created,
+            };
+            Ok((summary, nodes_created > 0))
+        })
+    }
+
+    /// Storage ids of nodes in the session's current graph, keyed by property `key`
+    ///
+    /// Only nodes carrying `key` (and `label`, if given) are included. Keys are
+    /// rendered with `key_text`, so they can be matched against values read from
+    /// another file. Endpoints of `bulk_insert_edges` can be looked up this way
+    /// when nodes were loaded in an earlier run.
+    ///
+    /// # Returns
+    /// * `Ok(HashMap<String, String>)` - Storage id by key
+    /// * `Err(String)` - Error message if there is no current graph or two nodes share a key
+    pub fn node_ids_by_key(
+        &self,
+        session_id: &str,
+        label: Option<&str>,
+        key: &str,
+    ) -> Result<HashMap<String, String>, String> {
+        let session = self
+            .session_provider
+            .get_session(session_id)
+            .ok_or_else(|| format!("Session not found: {}", session_id))?;
+        let graph_name = session
+            .read()
+            .map_err(|e| format!("Failed to read session: {}", e))?
+            .current_graph
+            .clone()
+            .ok_or_else(|| "No current graph set. Use SESSION SET GRAPH first".to_string())?;
+        let graph = self
+            .executor
+            .storage()
+            .get_graph(&graph_name)
+            .map_err(|e| format!("Failed to get graph: {}", e))?
+            .ok_or_else(|| format!("Graph not found: {}", graph_name))?;
+
+        let nodes = match label {
+            Some(label) => graph.get_nodes_by_label(label),
+            None => graph.get_all_nodes(),
+        };
+        let mut ids = HashMap::new();
+        for node in nodes {
+            let Some(value) = node.properties.get(key) else {
+                continue;
+            };
+            let text = import::key_text(value);
+            if ids.insert(text.clone(), node.id.clone()).is_some() {
+                return Err(format!(
+                    "More than one node has {} = {}; keys must be unique",
+                    key, text
+                ));
+            }
+        }
+        Ok(ids)
+    }
+
+    /// Load the session's current graph, let `load` change it and save the result
+    ///
+    /// The graph's write lock is held from loading to saving, as data statements
+    /// hold it, so a bulk load and a concurrent INSERT or MERGE into the same graph
+    /// do not overwrite each other's changes. `load` returns its result and
+    /// whether it changed the graph; an unchanged graph is not saved.
+    fn bulk_load<T>(
+        &self,
+        session_id: &str,
+        load: impl FnOnce(&mut crate::storage::GraphCache) -> Result<(T, bool), String>,
+    ) -> Result<T, String> {
+        let _writing = self.writing();
+        let graph_name = self.bulk_target_graph(session_id)?;
+        let storage = self.executor.storage();
+        let write_lock = storage.graph_write_lock(&graph_name);
+        let _locked = write_lock.lock().unwrap_or_else(|e| e.into_inner());
+
+        let mut graph = storage
+            .get_graph(&graph_name)
+            .map_err(|e| format!("Failed to get graph: {}", e))?
+            .ok_or_else(|| format!("Graph not found: {}", graph_name))?;
+        let (result, changed) = load(&mut graph)?;
+        if changed {
+            storage
+                .save_graph(&graph_name, graph)
+                .map_err(|e| format!("Failed to save graph: {}", e))?;
+        }
+        Ok(result)
+    }
+
+    /// Resolve the graph a bulk load writes into
+    fn bulk_target_graph(&self, session_id: &str) -> Result<String, String> {
+        if self.read_only {
+            return Err(
+                "Database is open in read-only mode: bulk inserts are not allowed".to_string(),
+            );
+        }
+        self.disk_monitor.check_write()?;
+
+        let session = self
+            .session_provider
+            .get_session(session_id)
+            .ok_or_else(|| format!("Session not found: {}", session_id))?;
+        let graph_name = session
+            .read()
+            .map_err(|e| format!("Failed to read session: {}", e))?
+            .current_graph
+            .clone()
+            .ok_or_else(|| {
+                "No current graph set. Use SESSION SET GRAPH before bulk inserting".to_string()
+            })?;
+
+        let open_transactions = self
+            .executor
+            .get_session_transactions(session_id)
+            .map_err(|e| format!("Failed to check transactions: {}", e))?;
+        if !open_transactions.is_empty() {
+            return Err("Bulk inserts cannot run inside an open transaction".to_string());
+        }
+        self.write_throttle.acquire(&graph_name)?;
+        Ok(graph_name)
+    }
+
+    /// Hold off `export_snapshot` while a write runs
+    fn writing(&self) -> std::sync::RwLockReadGuard<'_, ()> {
+        self.write_gate.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Execute several statements in one transaction
+    ///
+    /// The statements run in order between an implicit `START TRANSACTION` and
+    /// `COMMIT`. Execution stops at the first failing statement and the
+    /// transaction is rolled back, so either every statement takes effect or none
+    /// does. Statements are parsed before the transaction starts; transaction
+    /// control statements are not allowed in a batch, and a batch cannot run while
+    /// the session already has an open transaction.
+    ///
+    /// # Returns
+    /// * `Ok(Vec<QueryResult>)` - Result of each statement, in input order
+    /// * `Err(BatchError)` - Index and message of the statement that failed
+    ///   (`index == statements.len()` if the commit itself failed)
+    pub fn process_batch(
+        &self,
+        statements: &[&str],
+        session_id: &str,
+    ) -> Result<Vec<QueryResult>, BatchError> {
+        if statements.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        for (index, statement) in statements.iter().enumerate() {
+            let document = parse_query(statement).map_err(|e| BatchError {
+                index,
+                message: format!("Parse error: {:?}", e),
+            })?;
+            if matches!(
+                Self::statement_info(&document.statement).query_type,
+                QueryType::StartTransaction
+                    | QueryType::Commit
+                    | QueryType::Rollback
+                    | QueryType::Savepoint
+                    | QueryType::RollbackToSavepoint
+                    | QueryType::ReleaseSavepoint
+            ) {
+                return Err(BatchError {
+                    index,
+                    message: "Transaction control statements are not allowed in a batch"
+                        .to_string(),
+                });
+            }
+        }
+
+        // Fails if the session already has an open transaction
+        self.process_query("START TRANSACTION", session_id)
+            .map_err(|e| BatchError {
+                index: 0,
+                message: format!("Failed to start batch transaction: {}", e),
+            })?;
+
+        let mut results = Vec::with_capacity(statements.len());
+        for (index, statement) in statements.iter().enumerate() {
+            match self.process_query(statement, session_id) {
+                Ok(result) => results.push(result),
+                Err(message) => {
+                    if let Err(e) = self.process_query("ROLLBACK", session_id) {
+                        log::warn!("Failed to roll back batch: {}", e);
+                    }
+                    return Err(BatchError { index, message });
+                }
+            }
+        }
+
+        self.process_query("COMMIT", session_id)
+            .map_err(|message| BatchError {
+                index: statements.len(),
+                message,
+            })?;
+
+        Ok(results)
+    }
+
+    /// Execute several independent queries in one call
+    ///
+    /// Unlike `process_batch`, every query runs on its own: a failing query
+    /// neither stops nor undoes the others. The session is resolved once for the
+    /// whole call and the queries run in order, each seeing the session context
+    /// left by the ones before it. This suits pages that issue many small reads
+    /// at once.
+    ///
+    /// The name differs from `process_batch`, which already runs statements as
+    /// one transaction. Only the session lookup is shared: each query is still
+    /// parsed and planned on its own, so a query text repeated in the batch is
+    /// planned again every time.
+    ///
+    /// # Returns
+    /// The result or error message of each query, in input order
+    pub fn process_queries(
+        &self,
+        queries: &[&str],
+        session_id: &str,
+    ) -> Vec<Result<QueryResult, String>> {
+        let handle = match self.session_handle(session_id) {
+            Ok(handle) => handle,
+            Err(e) => return queries.iter().map(|_| Err(e.clone())).collect(),
+        };
+        queries
+            .iter()
+            .map(|query| self.process_query_with_handle(query, &handle))
+            .collect()
+    }
+
     /// Close a session
     ///
     /// Removes the session from the session manager.
@@ -602,14 +1688,48 @@ impl QueryCoordinator {
     /// assert!(coordinator.validate_query("MATCH (n RETURN n").is_err());
     /// ```
     pub fn validate_query(&self, query: &str) -> Result<(), String> {
+        Self::check_query(query).map(|_| ())
+    }
+
+    /// Check a query's syntax without a database
+    ///
+    /// Parses and validates `query` like `validate_query` and returns the names of
+    /// its `$name` parameters in order of first appearance. The SDK's `gql!` macro
+    /// calls this at compile time.
+    ///
+    /// # Example
+    /// ```
+    /// # use graphlite::QueryCoordinator;
+    /// let params = QueryCoordinator::check_query(
+    ///     "MATCH (p:Person {name: $name}) WHERE p.age > $age RETURN p",
+    /// )?;
+    /// assert_eq!(params, vec!["name", "age"]);
+    /// assert!(QueryCoordinator::check_query("MATCH (n RETURN n").is_err());
+    /// # Ok::<(), String>(())
+    /// ```
+    pub fn check_query(query: &str) -> Result<Vec<String>, String> {
+        let mut names = Vec::new();
+        for (_, name) in crate::ast::lexer::variable_spans(query)? {
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+
+        // Parameters are bound as literals before parsing, so check with placeholders
+        let placeholders = names
+            .iter()
+            .map(|name| (name.clone(), Value::Number(0.0)))
+            .collect();
+        let query = crate::ast::parameters::bind_parameters(query, &placeholders)?;
+
         // Parse the query
-        let document = parse_query(query).map_err(|e| format!("Parse error: {:?}", e))?;
+        let document = parse_query(&query).map_err(|e| format!("Parse error: {:?}", e))?;
 
         // Validate the parsed query (pass false for has_graph_context since we're just validating syntax)
         crate::ast::validator::validate_query(&document, false)
             .map_err(|e| format!("Validation error: {:?}", e))?;
 
-        Ok(())
+        Ok(names)
     }
 
     /// Check if a query is syntactically valid
@@ -661,8 +1781,13 @@ impl QueryCoordinator {
         // Parse the query
         let document = parse_query(query).map_err(|e| format!("Parse error: {:?}", e))?;
 
+        Ok(Self::statement_info(&document.statement))
+    }
+
+    /// Classify a parsed statement
+    fn statement_info(statement: &crate::ast::Statement) -> QueryInfo {
         // Analyze the statement type
-        let query_type = match &document.statement {
+        let query_type = match statement {
             crate::ast::Statement::Query(_) => QueryType::Match,
             crate::ast::Statement::Select(_) => QueryType::Select,
             crate::ast::Statement::Call(_) => QueryType::Call,
@@ -686,6 +1811,7 @@ impl QueryCoordinator {
                     CatalogStatement::RevokeRole { .. } => QueryType::RevokeRole,
                     CatalogStatement::ClearGraph { .. } => QueryType::ClearGraph,
                     CatalogStatement::TruncateGraph { .. } => QueryType::TruncateGraph,
+                    CatalogStatement::AlterGraph(_) => QueryType::AlterGraph,
                 }
             }
             crate::ast::Statement::DataStatement(data) => {
@@ -699,6 +1825,8 @@ impl QueryCoordinator {
                     DataStatement::MatchSet { .. } => QueryType::MatchSet,
                     DataStatement::MatchRemove { .. } => QueryType::MatchRemove,
                     DataStatement::MatchDelete { .. } => QueryType::MatchDelete,
+                    DataStatement::DeleteEdgesOfType { .. } => QueryType::DeleteEdgesOfType,
+                    DataStatement::Merge { .. } => QueryType::Merge,
                 }
             }
             crate::ast::Statement::SessionStatement(session) => {
@@ -711,6 +1839,7 @@ impl QueryCoordinator {
                     },
                     SessionStatement::Reset(_) => QueryType::SessionReset,
                     SessionStatement::Close(_) => QueryType::SessionClose,
+                    SessionStatement::ShowCurrentGraph(_) => QueryType::ShowCurrentGraph,
                 }
             }
             crate::ast::Statement::TransactionStatement(txn) => {
@@ -719,6 +1848,9 @@ impl QueryCoordinator {
                     TransactionStatement::StartTransaction(_) => QueryType::StartTransaction,
                     TransactionStatement::Commit(_) => QueryType::Commit,
                     TransactionStatement::Rollback(_) => QueryType::Rollback,
+                    TransactionStatement::Savepoint(_) => QueryType::Savepoint,
+                    TransactionStatement::RollbackToSavepoint(_) => QueryType::RollbackToSavepoint,
+                    TransactionStatement::ReleaseSavepoint(_) => QueryType::ReleaseSavepoint,
                     TransactionStatement::SetTransactionCharacteristics(_) => {
                         QueryType::SetTransactionCharacteristics
                     }
@@ -750,12 +1882,31 @@ impl QueryCoordinator {
                 | QueryType::SessionSetGraph
                 | QueryType::SessionSetSchema
                 | QueryType::SessionReset
+                | QueryType::ShowCurrentGraph
+        ) && !matches!(
+            statement,
+            crate::ast::Statement::Query(crate::ast::Query::MutationPipeline(_))
+        ) && !matches!(
+            statement,
+            crate::ast::Statement::Call(call) if matches!(
+                call.procedure_name.as_str(),
+                "gql.sample_data" | "gql.outbox_append" | "gql.outbox_ack"
+            )
         );
 
-        Ok(QueryInfo {
+        QueryInfo {
             query_type,
             is_read_only,
-        })
+        }
+    }
+
+    /// Fingerprint identifying a query for plan pinning
+    ///
+    /// Queries that differ only in whitespace share a fingerprint. Pin the plan
+    /// of a query that has run with `CALL gql.pin_plan(fingerprint)`; the same
+    /// value is returned by `CALL gql.query_fingerprint(query)`.
+    pub fn query_fingerprint(query: &str) -> String {
+        crate::plan::pinning::fingerprint(query)
     }
 
     /// Explain the query execution plan without executing the query
@@ -849,6 +2000,127 @@ impl QueryPlan {
     }
 }
 
+/// A session resolved once for repeated queries
+///
+/// Created by `QueryCoordinator::session_handle` and used with
+/// `QueryCoordinator::process_query_with_handle`. Clones share the same session.
+#[derive(Clone)]
+pub struct SessionHandle {
+    id: String,
+    session: Arc<RwLock<UserSession>>,
+}
+
+impl SessionHandle {
+    /// Session ID, usable with the `&str` based APIs
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Whether the session is still open
+    pub fn is_active(&self) -> bool {
+        self.session.read().map(|s| s.active).unwrap_or(false)
+    }
+
+    /// Current schema path, e.g. `/app`
+    pub fn current_schema(&self) -> Option<String> {
+        self.session.read().ok()?.current_schema.clone()
+    }
+
+    /// Current graph path, e.g. `/app/social`
+    pub fn current_graph(&self) -> Option<String> {
+        self.session.read().ok()?.current_graph.clone()
+    }
+
+    /// Whether the session has an open transaction
+    pub fn in_transaction(&self) -> bool {
+        self.session
+            .read()
+            .ok()
+            .and_then(|s| s.transaction_state.has_active_transaction().ok())
+            .unwrap_or(false)
+    }
+
+    /// Value of session parameter `name`
+    pub fn parameter(&self, name: &str) -> Option<Value> {
+        self.session.read().ok()?.get_parameter(name).cloned()
+    }
+
+    /// Set session parameter `name`, which statements of the session can read
+    /// as a variable
+    pub fn set_parameter(&self, name: &str, value: Value) -> Result<(), String> {
+        self.session
+            .write()
+            .map_err(|e| format!("Failed to acquire session write lock: {}", e))?
+            .set_parameter(name.to_string(), value);
+        Ok(())
+    }
+
+    /// Schema enforcement mode: `strict`, `advisory` (the default) or `disabled`
+    pub fn schema_enforcement_mode(&self) -> String {
+        self.session
+            .read()
+            .map(|s| s.get_schema_enforcement_mode().to_string())
+            .unwrap_or_else(|_| "advisory".to_string())
+    }
+
+    /// Set how statements that do not match the session graph's graph type are
+    /// handled: `strict` rejects them, `advisory` runs them with warnings and
+    /// `disabled` skips the check
+    pub fn set_schema_enforcement_mode(&self, mode: &str) -> Result<(), String> {
+        if !matches!(mode, "strict" | "advisory" | "disabled") {
+            return Err(format!(
+                "Invalid schema enforcement mode '{}': expected strict, advisory or disabled",
+                mode
+            ));
+        }
+        self.session
+            .write()
+            .map_err(|e| format!("Failed to acquire session write lock: {}", e))?
+            .set_schema_enforcement_mode(mode);
+        Ok(())
+    }
+
+    /// Whether queries return per-operator timing in `QueryResult::operator_timings`
+    pub fn operator_timing(&self) -> bool {
+        self.session
+            .read()
+            .map(|s| s.get_operator_timing())
+            .unwrap_or(false)
+    }
+
+    /// Turn per-operator timing of this session's queries on or off
+    pub fn set_operator_timing(&self, enabled: bool) -> Result<(), String> {
+        self.session
+            .write()
+            .map_err(|e| format!("Failed to acquire session write lock: {}", e))?
+            .set_operator_timing(enabled);
+        Ok(())
+    }
+}
+
+impl std::fmt::Debug for SessionHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SessionHandle")
+            .field("id", &self.id)
+            .finish()
+    }
+}
+
+/// Failure of a statement batch run by `QueryCoordinator::process_batch`
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchError {
+    /// Index of the statement that failed
+    pub index: usize,
+    /// Error message
+    pub message: String,
+}
+
+impl std::fmt::Display for BatchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Statement {} failed: {}", self.index, self.message)
+    }
+}
+
 /// Information about a parsed query
 #[derive(Debug, Clone)]
 pub struct QueryInfo {
@@ -858,6 +2130,56 @@ pub struct QueryInfo {
     pub is_read_only: bool,
 }
 
+impl QueryInfo {
+    /// Whether executing the query writes nodes or edges of the current graph
+    pub fn writes_graph_data(&self) -> bool {
+        match self.query_type {
+            QueryType::Match | QueryType::Call => !self.is_read_only,
+            QueryType::Insert
+            | QueryType::Set
+            | QueryType::Remove
+            | QueryType::Delete
+            | QueryType::MatchInsert
+            | QueryType::MatchSet
+            | QueryType::MatchRemove
+            | QueryType::MatchDelete
+            | QueryType::DeleteEdgesOfType
+            | QueryType::Merge => true,
+            _ => false,
+        }
+    }
+
+    /// Whether executing the query changes data or catalog state
+    ///
+    /// Session and transaction control statements are not modifications.
+    pub fn modifies_data(&self) -> bool {
+        match self.query_type {
+            // Queries are reads unless they end in a mutation pipeline; calls unless
+            // the procedure loads data
+            QueryType::Match | QueryType::Call => !self.is_read_only,
+            QueryType::Select
+            | QueryType::SessionSet
+            | QueryType::SessionSetGraph
+            | QueryType::SessionSetSchema
+            | QueryType::SessionReset
+            | QueryType::SessionClose
+            | QueryType::ShowCurrentGraph
+            | QueryType::StartTransaction
+            | QueryType::Commit
+            | QueryType::Rollback
+            | QueryType::Savepoint
+            | QueryType::RollbackToSavepoint
+            | QueryType::ReleaseSavepoint
+            | QueryType::SetTransactionCharacteristics
+            | QueryType::Declare
+            | QueryType::Let
+            | QueryType::Next
+            | QueryType::AtLocation => false,
+            _ => true,
+        }
+    }
+}
+
 /// Types of query operations
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum QueryType {
@@ -871,6 +2193,7 @@ pub enum QueryType {
     DropSchema,
     CreateGraph,
     DropGraph,
+    AlterGraph,
     CreateGraphType,
     DropGraphType,
     AlterGraphType,
@@ -899,6 +2222,8 @@ pub enum QueryType {
     MatchSet,
     MatchRemove,
     MatchDelete,
+    DeleteEdgesOfType,
+    Merge,
 
     // Session operations
     SessionSet,
@@ -906,11 +2231,15 @@ pub enum QueryType {
     SessionSetSchema,
     SessionReset,
     SessionClose,
+    ShowCurrentGraph,
 
     // Transaction operations
     StartTransaction,
     Commit,
     Rollback,
+    Savepoint,
+    RollbackToSavepoint,
+    ReleaseSavepoint,
     SetTransactionCharacteristics,
 
     // Other operations
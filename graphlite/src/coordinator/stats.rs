@@ -0,0 +1,233 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! Database statistics
+//!
+//! `QueryCoordinator::stats` describes every graph in the catalog (node and
+//! edge counts, label and relationship type histograms, property key counts and
+//! index sizes) together with the size of the database directory on disk and
+//! the hit rates of the caches. Counting loads each graph, so the figures are
+//! exact but the call costs as much as reading the whole database once.
+
+use std::path::Path;
+
+use crate::cache::CacheManager;
+use crate::catalog::manager::CatalogManager;
+use crate::catalog::operations::{CatalogOperation, CatalogResponse, EntityType};
+use crate::storage::{GraphCache, StorageManager};
+
+/// Statistics of a whole database, returned by `QueryCoordinator::stats`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DatabaseStats {
+    /// Graphs in the catalog, ordered by path
+    pub graphs: Vec<GraphStatistics>,
+    /// Bytes of all files in the database directory (`None` for coordinators
+    /// built without a path)
+    pub disk_bytes: Option<u64>,
+    pub caches: Vec<CacheStatistics>,
+}
+
+impl DatabaseStats {
+    pub fn node_count(&self) -> usize {
+        self.graphs.iter().map(|graph| graph.nodes).sum()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.graphs.iter().map(|graph| graph.edges).sum()
+    }
+}
+
+/// Contents of one graph
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GraphStatistics {
+    /// Full path, e.g. `/social/people`
+    pub path: String,
+    pub nodes: usize,
+    pub edges: usize,
+    /// Node labels with the number of nodes carrying each, ordered by label
+    pub labels: Vec<(String, usize)>,
+    /// Edge labels with the number of edges carrying each, ordered by label
+    pub relationship_types: Vec<(String, usize)>,
+    /// Property keys with the number of nodes and of edges that have each,
+    /// ordered by key
+    pub property_keys: Vec<(String, usize, usize)>,
+    pub indexes: Vec<IndexStatistics>,
+}
+
+/// Size of one index of a graph
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexStatistics {
+    /// Index name; node key indexes are named after their label and property
+    pub name: String,
+    /// `edge_property` (CREATE INDEX) or `node_key` (graph type KEY)
+    pub kind: &'static str,
+    /// What the index covers, e.g. `:KNOWS(since)`
+    pub target: String,
+    /// Nodes or edges the index holds
+    pub entries: usize,
+}
+
+/// Usage of one cache
+#[derive(Debug, Clone, PartialEq)]
+pub struct CacheStatistics {
+    /// `storage_cache`, `result_cache`, `plan_cache` or `subquery_cache`
+    pub cache: &'static str,
+    pub entries: usize,
+    /// Fraction of lookups answered from the cache since the database was
+    /// opened (`None` where lookups are not counted)
+    pub hit_rate: Option<f64>,
+    /// Estimated memory held
+    pub memory_bytes: u64,
+}
+
+pub(crate) fn collect(
+    storage: &StorageManager,
+    catalog_manager: &mut CatalogManager,
+    cache_manager: Option<&CacheManager>,
+    db_path: Option<&Path>,
+) -> Result<DatabaseStats, String> {
+    // Caches first, before loading the graphs changes them
+    let caches = cache_statistics(storage, cache_manager);
+
+    let mut graphs = Vec::new();
+    for path in graph_paths(catalog_manager)? {
+        let graph = storage
+            .get_graph(&path)
+            .map_err(|e| format!("Failed to load graph '{}': {}", path, e))?
+            .unwrap_or_default();
+        let stats = graph.stats();
+        graphs.push(GraphStatistics {
+            path,
+            nodes: stats.node_count,
+            edges: stats.edge_count,
+            labels: graph.label_counts(),
+            relationship_types: graph.relationship_type_counts(),
+            property_keys: graph.property_key_counts(),
+            indexes: index_statistics(&graph),
+        });
+    }
+
+    let disk_bytes = match db_path {
+        Some(path) => Some(
+            directory_size(path)
+                .map_err(|e| format!("Failed to measure {}: {}", path.display(), e))?,
+        ),
+        None => None,
+    };
+
+    Ok(DatabaseStats {
+        graphs,
+        disk_bytes,
+        caches,
+    })
+}
+
+/// Indexes of `graph`, ordered by name
+pub(crate) fn index_statistics(graph: &GraphCache) -> Vec<IndexStatistics> {
+    let mut indexes: Vec<IndexStatistics> = graph
+        .edge_property_indexes()
+        .map(|index| IndexStatistics {
+            name: index.name.clone(),
+            kind: "edge_property",
+            target: format!(":{}({})", index.label, index.target()),
+            entries: index.len(),
+        })
+        .chain(graph.node_key_indexes().map(|index| IndexStatistics {
+            name: format!("{}_{}_key", index.label, index.property),
+            kind: "node_key",
+            target: format!(":{}({})", index.label, index.property),
+            entries: index.len(),
+        }))
+        .collect();
+    indexes.sort_by(|a, b| a.name.cmp(&b.name));
+    indexes
+}
+
+/// Full paths of the graphs in the catalog, sorted
+fn graph_paths(catalog_manager: &mut CatalogManager) -> Result<Vec<String>, String> {
+    let items = match catalog_manager.execute(
+        "graph_metadata",
+        CatalogOperation::List {
+            entity_type: EntityType::Graph,
+            filters: None,
+        },
+    ) {
+        Ok(CatalogResponse::List { items }) => items,
+        Ok(_) => Vec::new(),
+        Err(e) => return Err(format!("Failed to list graphs: {}", e)),
+    };
+
+    let mut paths: Vec<String> = items
+        .iter()
+        .filter_map(|graph| {
+            let id = graph.get("id")?;
+            Some(format!(
+                "/{}/{}",
+                id.get("schema_name")?.as_str()?,
+                id.get("name")?.as_str()?
+            ))
+        })
+        .collect();
+    paths.sort();
+    Ok(paths)
+}
+
+fn cache_statistics(
+    storage: &StorageManager,
+    cache_manager: Option<&CacheManager>,
+) -> Vec<CacheStatistics> {
+    let (graphs, graph_bytes) = storage.get_cache_stats();
+    let mut caches = vec![CacheStatistics {
+        cache: "storage_cache",
+        entries: graphs,
+        hit_rate: None,
+        memory_bytes: graph_bytes as u64,
+    }];
+
+    if let Some(cache_manager) = cache_manager {
+        let stats = cache_manager.get_stats();
+        let (results, result_bytes) = cache_manager.result_cache_usage();
+        caches.push(CacheStatistics {
+            cache: "result_cache",
+            entries: results,
+            hit_rate: Some(stats.result_cache.hit_rate()),
+            memory_bytes: result_bytes as u64,
+        });
+        caches.push(CacheStatistics {
+            cache: "plan_cache",
+            entries: stats.plan_cache.current_entries,
+            hit_rate: Some(stats.plan_cache.hit_rate()),
+            memory_bytes: stats.plan_cache.current_memory_bytes as u64,
+        });
+        if let Some(subquery) = &stats.subquery_cache {
+            caches.push(CacheStatistics {
+                cache: "subquery_cache",
+                entries: subquery.current_entries,
+                hit_rate: Some(subquery.hit_rate()),
+                memory_bytes: subquery.memory_bytes as u64,
+            });
+        }
+    }
+    caches
+}
+
+/// Total size of the files under `path`; files removed while counting are skipped
+fn directory_size(path: &Path) -> std::io::Result<u64> {
+    let mut total = 0;
+    for entry in std::fs::read_dir(path)? {
+        let size = entry.and_then(|entry| {
+            let metadata = entry.metadata()?;
+            if metadata.is_dir() {
+                directory_size(&entry.path())
+            } else {
+                Ok(metadata.len())
+            }
+        });
+        total += match size {
+            Ok(size) => size,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => 0,
+            Err(e) => return Err(e),
+        };
+    }
+    Ok(total)
+}
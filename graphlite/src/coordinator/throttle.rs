@@ -0,0 +1,306 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! Per-graph write throttling and backpressure metrics
+//!
+//! `QueryCoordinator::set_write_limit` caps the rate of write statements (and bulk
+//! loads) on one graph with a token bucket. A write that finds the bucket empty
+//! reserves the next free slot and waits for it, so concurrent writers queue up
+//! in arrival order; a write whose slot is further away than `max_wait` is
+//! rejected instead. Writers learn about the delay through a warning on their
+//! `QueryResult`, and `QueryCoordinator::write_metrics` reports the counters and
+//! current queue depth of every graph written to since the database was opened.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// WAL flushes slower than this add a backpressure warning to write results
+pub(crate) const WAL_FLUSH_LAG_WARNING: Duration = Duration::from_millis(100);
+
+/// Write-rate limit of one graph
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WriteLimit {
+    /// Sustained write statements per second
+    pub writes_per_second: f64,
+    /// Writes that may run back to back before the sustained rate applies
+    pub burst: u32,
+    /// Longest a write waits for its slot before it is rejected
+    pub max_wait: Duration,
+}
+
+impl WriteLimit {
+    /// Limit `writes_per_second` with a burst of one and waits of up to 30 seconds
+    pub fn per_second(writes_per_second: f64) -> Self {
+        Self {
+            writes_per_second,
+            burst: 1,
+            max_wait: Duration::from_secs(30),
+        }
+    }
+}
+
+/// Write activity of one graph
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GraphWriteMetrics {
+    /// Graph path, e.g. `/app/social`
+    pub graph: String,
+    /// The graph's write limit, if any
+    pub limit: Option<WriteLimit>,
+    /// Writes admitted, including those that waited
+    pub writes: u64,
+    /// Writes that had to wait for a slot
+    pub throttled: u64,
+    /// Writes rejected because their wait would have exceeded `max_wait`
+    pub rejected: u64,
+    /// Writes waiting for a slot right now
+    pub queue_depth: usize,
+    /// Total time writes spent waiting
+    pub total_wait: Duration,
+}
+
+/// Backpressure snapshot returned by `QueryCoordinator::write_metrics`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WriteMetrics {
+    /// One entry per graph written to, sorted by path
+    pub graphs: Vec<GraphWriteMetrics>,
+    /// Time the most recent write-ahead log flush took to reach disk
+    pub wal_flush_lag: Duration,
+}
+
+/// A write admitted by `WriteThrottle::acquire`
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Admission {
+    /// Time spent waiting for a slot
+    pub waited: Duration,
+    /// Writes that were already waiting when this one arrived
+    pub queued_ahead: usize,
+}
+
+impl Admission {
+    /// Warning for the writer if it was delayed
+    pub(crate) fn warning(&self, graph: &str, limit: Option<WriteLimit>) -> Option<String> {
+        let limit = limit?;
+        if self.waited.is_zero() {
+            return None;
+        }
+        Some(format!(
+            "Write to {} throttled for {} ms ({} write(s) queued ahead, limit {}/s)",
+            graph,
+            self.waited.as_millis(),
+            self.queued_ahead,
+            limit.writes_per_second
+        ))
+    }
+}
+
+#[derive(Debug)]
+struct GraphState {
+    metrics: GraphWriteMetrics,
+    /// Available slots; negative while writes are queued
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl GraphState {
+    fn new(graph: &str, limit: Option<WriteLimit>) -> Self {
+        Self {
+            metrics: GraphWriteMetrics {
+                graph: graph.to_string(),
+                limit,
+                ..GraphWriteMetrics::default()
+            },
+            tokens: limit.map_or(0.0, |limit| f64::from(limit.burst.max(1))),
+            refilled_at: Instant::now(),
+        }
+    }
+
+    fn refill(&mut self, limit: &WriteLimit, now: Instant) {
+        let elapsed = now.duration_since(self.refilled_at).as_secs_f64();
+        self.tokens =
+            (self.tokens + elapsed * limit.writes_per_second).min(f64::from(limit.burst.max(1)));
+        self.refilled_at = now;
+    }
+}
+
+/// Per-graph token buckets and write counters
+#[derive(Debug, Default)]
+pub(crate) struct WriteThrottle {
+    graphs: Mutex<HashMap<String, GraphState>>,
+}
+
+impl WriteThrottle {
+    /// Set or clear the write limit of a graph
+    pub(crate) fn set_limit(&self, graph: &str, limit: Option<WriteLimit>) -> Result<(), String> {
+        if let Some(limit) = &limit {
+            if !(limit.writes_per_second.is_finite() && limit.writes_per_second > 0.0) {
+                return Err(format!(
+                    "Write limit must be a positive number of writes per second, got {}",
+                    limit.writes_per_second
+                ));
+            }
+        }
+        let mut graphs = self.graphs.lock().unwrap_or_else(|e| e.into_inner());
+        match graphs.get_mut(graph) {
+            Some(state) => {
+                let metrics = std::mem::take(&mut state.metrics);
+                *state = GraphState::new(graph, limit);
+                state.metrics = GraphWriteMetrics { limit, ..metrics };
+            }
+            None => {
+                graphs.insert(graph.to_string(), GraphState::new(graph, limit));
+            }
+        }
+        Ok(())
+    }
+
+    /// The write limit of a graph, if any
+    pub(crate) fn limit(&self, graph: &str) -> Option<WriteLimit> {
+        let graphs = self.graphs.lock().unwrap_or_else(|e| e.into_inner());
+        graphs.get(graph).and_then(|state| state.metrics.limit)
+    }
+
+    /// Wait for a write slot on `graph`
+    ///
+    /// Blocks the calling thread while earlier writes use up the graph's rate. Fails
+    /// without waiting if the slot is further away than the limit's `max_wait`.
+    pub(crate) fn acquire(&self, graph: &str) -> Result<Admission, String> {
+        let (wait, queued_ahead, limit) = {
+            let mut graphs = self.graphs.lock().unwrap_or_else(|e| e.into_inner());
+            let state = graphs
+                .entry(graph.to_string())
+                .or_insert_with(|| GraphState::new(graph, None));
+
+            let Some(limit) = state.metrics.limit else {
+                state.metrics.writes += 1;
+                return Ok(Admission {
+                    waited: Duration::ZERO,
+                    queued_ahead: 0,
+                });
+            };
+
+            state.refill(&limit, Instant::now());
+            let wait = if state.tokens >= 1.0 {
+                Duration::ZERO
+            } else {
+                Duration::from_secs_f64((1.0 - state.tokens) / limit.writes_per_second)
+            };
+            if wait > limit.max_wait {
+                state.metrics.rejected += 1;
+                return Err(format!(
+                    "Write rate limit exceeded for graph {}: {} write(s) queued, next slot in {} ms (limit {}/s)",
+                    graph,
+                    state.metrics.queue_depth,
+                    wait.as_millis(),
+                    limit.writes_per_second
+                ));
+            }
+
+            // Reserve the slot now so later writers queue behind this one
+            state.tokens -= 1.0;
+            state.metrics.writes += 1;
+            let queued_ahead = state.metrics.queue_depth;
+            if !wait.is_zero() {
+                state.metrics.throttled += 1;
+                state.metrics.queue_depth += 1;
+                state.metrics.total_wait += wait;
+            }
+            (wait, queued_ahead, limit)
+        };
+
+        if !wait.is_zero() {
+            std::thread::sleep(wait);
+            let mut graphs = self.graphs.lock().unwrap_or_else(|e| e.into_inner());
+            if let Some(state) = graphs.get_mut(graph) {
+                state.metrics.queue_depth = state.metrics.queue_depth.saturating_sub(1);
+            }
+        }
+        log::trace!(
+            "Write to {} admitted after {:?} (limit {}/s)",
+            graph,
+            wait,
+            limit.writes_per_second
+        );
+
+        Ok(Admission {
+            waited: wait,
+            queued_ahead,
+        })
+    }
+
+    /// Counters of every graph written to, sorted by path
+    pub(crate) fn metrics(&self) -> Vec<GraphWriteMetrics> {
+        let graphs = self.graphs.lock().unwrap_or_else(|e| e.into_inner());
+        let mut metrics: Vec<GraphWriteMetrics> =
+            graphs.values().map(|state| state.metrics.clone()).collect();
+        metrics.sort_by(|a, b| a.graph.cmp(&b.graph));
+        metrics
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_acquire_throttles_and_rejects() {
+        let throttle = WriteThrottle::default();
+        assert!(throttle.acquire("/s/free").unwrap().waited.is_zero());
+
+        throttle
+            .set_limit(
+                "/s/g",
+                Some(WriteLimit {
+                    writes_per_second: 20.0,
+                    burst: 2,
+                    max_wait: Duration::from_millis(80),
+                }),
+            )
+            .unwrap();
+
+        // The burst passes straight through, then writes are spaced 50 ms apart
+        assert!(throttle.acquire("/s/g").unwrap().waited.is_zero());
+        assert!(throttle.acquire("/s/g").unwrap().waited.is_zero());
+        let admission = throttle.acquire("/s/g").unwrap();
+        assert!(admission.waited >= Duration::from_millis(40));
+        assert!(admission
+            .warning("/s/g", throttle.limit("/s/g"))
+            .unwrap()
+            .contains("throttled"));
+
+        // The next write waits for the following slot as well
+        assert!(!throttle.acquire("/s/g").unwrap().waited.is_zero());
+        let metrics = throttle.metrics();
+        assert_eq!(metrics[0].graph, "/s/free");
+        assert_eq!(metrics[1].writes, 4);
+        assert_eq!(metrics[1].throttled, 2);
+        assert_eq!(metrics[1].queue_depth, 0);
+
+        assert!(throttle
+            .set_limit("/s/g", Some(WriteLimit::per_second(0.0)))
+            .is_err());
+        throttle.set_limit("/s/g", None).unwrap();
+        assert!(throttle.acquire("/s/g").unwrap().waited.is_zero());
+        assert_eq!(throttle.metrics()[1].writes, 5);
+    }
+
+    #[test]
+    fn test_acquire_rejects_past_max_wait() {
+        let throttle = WriteThrottle::default();
+        throttle
+            .set_limit(
+                "/s/g",
+                Some(WriteLimit {
+                    writes_per_second: 1.0,
+                    burst: 1,
+                    max_wait: Duration::from_millis(10),
+                }),
+            )
+            .unwrap();
+
+        throttle.acquire("/s/g").unwrap();
+        let error = throttle.acquire("/s/g").unwrap_err();
+        assert!(error.contains("Write rate limit exceeded"), "{}", error);
+        assert_eq!(throttle.metrics()[0].rejected, 1);
+    }
+}
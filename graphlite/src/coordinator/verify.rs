@@ -0,0 +1,151 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! Whole-database integrity verification
+//!
+//! `QueryCoordinator::verify` combines three checks:
+//! - storage: the deep `check_storage`, verifying the checksum of every record
+//! - references: every stored edge's source and target node are stored
+//! - catalog: every graph's schema and graph type exist, and every graph in
+//!   storage has a catalog entry
+//!
+//! Only dangling edges can be repaired, by deleting them; the other problems
+//! are reported for an operator to resolve.
+
+use std::collections::HashSet;
+
+use crate::catalog::manager::CatalogManager;
+use crate::catalog::operations::{CatalogOperation, CatalogResponse, EntityType};
+use crate::storage::integrity::{ReferenceCheckReport, StorageCheckReport};
+use crate::storage::{DataAdapter, StorageManager};
+
+/// An inconsistency between catalog entries, or between the catalog and storage
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CatalogProblem {
+    /// Graph the problem concerns, as `/schema/graph` or by its storage name
+    pub graph: String,
+    /// What is wrong
+    pub reason: String,
+}
+
+impl std::fmt::Display for CatalogProblem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Graph '{}': {}", self.graph, self.reason)
+    }
+}
+
+/// Outcome of `QueryCoordinator::verify`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct VerifyReport {
+    /// Record checksums and decoding
+    pub storage: StorageCheckReport,
+    /// Edges referencing missing nodes
+    pub references: ReferenceCheckReport,
+    /// Catalog inconsistencies
+    pub catalog: Vec<CatalogProblem>,
+}
+
+impl VerifyReport {
+    /// Whether no problem remains (repaired dangling edges do not count)
+    pub fn is_ok(&self) -> bool {
+        self.storage.is_ok() && self.references.is_ok() && self.catalog.is_empty()
+    }
+
+    /// Number of problems found, including repaired ones
+    pub fn problem_count(&self) -> usize {
+        self.storage.problems.len() + self.references.dangling_edges.len() + self.catalog.len()
+    }
+}
+
+pub(crate) fn verify(
+    storage: &StorageManager,
+    catalog_manager: &mut CatalogManager,
+    repair: bool,
+) -> Result<VerifyReport, String> {
+    let storage_report = storage.check_storage(true).map_err(|e| e.to_string())?;
+    let references = storage
+        .check_references(repair)
+        .map_err(|e| e.to_string())?;
+    let catalog = check_catalog(catalog_manager, &references.stored_graphs)?;
+    Ok(VerifyReport {
+        storage: storage_report,
+        references,
+        catalog,
+    })
+}
+
+/// Entries of one catalog, as the JSON objects the provider lists
+fn list(
+    catalog_manager: &mut CatalogManager,
+    catalog: &str,
+    entity_type: EntityType,
+) -> Result<Vec<serde_json::Value>, String> {
+    match catalog_manager.execute(
+        catalog,
+        CatalogOperation::List {
+            entity_type,
+            filters: None,
+        },
+    ) {
+        Ok(CatalogResponse::List { items }) => Ok(items),
+        Ok(_) => Ok(Vec::new()),
+        Err(e) => Err(format!("Failed to list {} catalog: {}", catalog, e)),
+    }
+}
+
+fn id_field<'a>(item: &'a serde_json::Value, field: &str) -> Option<&'a str> {
+    item.get("id")?.get(field)?.as_str()
+}
+
+fn check_catalog(
+    catalog_manager: &mut CatalogManager,
+    stored_graphs: &[String],
+) -> Result<Vec<CatalogProblem>, String> {
+    let schemas: HashSet<String> = list(catalog_manager, "schema", EntityType::Schema)?
+        .iter()
+        .filter_map(|schema| id_field(schema, "name").map(str::to_string))
+        .collect();
+    // Graph types are listed by qualified name (`schema.name`), as graphs refer to them
+    let graph_types: HashSet<String> = list(catalog_manager, "graph_type", EntityType::GraphType)?
+        .iter()
+        .filter_map(|graph_type| graph_type.get("name")?.as_str().map(str::to_string))
+        .collect();
+
+    let mut problems = Vec::new();
+    let mut catalog_graphs = HashSet::new();
+    for graph in list(catalog_manager, "graph_metadata", EntityType::Graph)? {
+        let (Some(schema), Some(name)) =
+            (id_field(&graph, "schema_name"), id_field(&graph, "name"))
+        else {
+            continue;
+        };
+        let path = format!("/{}/{}", schema, name);
+        catalog_graphs.insert(DataAdapter::normalize_graph_path(&path));
+        if !schemas.contains(schema) {
+            problems.push(CatalogProblem {
+                graph: path.clone(),
+                reason: format!("schema '{}' does not exist", schema),
+            });
+        }
+        let graph_type = graph
+            .get("graph_type_id")
+            .and_then(|id| id.get("name"))
+            .and_then(|name| name.as_str());
+        if let Some(graph_type) = graph_type.filter(|t| !graph_types.contains(*t)) {
+            problems.push(CatalogProblem {
+                graph: path,
+                reason: format!("graph type '{}' does not exist", graph_type),
+            });
+        }
+    }
+
+    for stored in stored_graphs {
+        if !catalog_graphs.contains(&DataAdapter::normalize_graph_path(stored)) {
+            problems.push(CatalogProblem {
+                graph: stored.clone(),
+                reason: "stored data has no catalog entry".to_string(),
+            });
+        }
+    }
+    Ok(problems)
+}
@@ -0,0 +1,179 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! Registry of the statements running right now
+//!
+//! The executor registers every statement for as long as it runs, together
+//! with the physical operator it is executing at the moment. A statement that
+//! seems to hang shows up in `CALL gql.active_queries()` and
+//! `QueryCoordinator::active_queries` with the operator it is stuck in. The
+//! call listing the statements is itself running, so it lists itself too.
+
+use chrono::{DateTime, Utc};
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use crate::plan::pinning::fingerprint;
+
+/// A running statement, returned by `ActiveQueries::list`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActiveQuery {
+    /// Identifier of this execution, unique while the database is open
+    pub query_id: u64,
+    pub session_id: String,
+    /// Fingerprint of the query text, as from `gql.query_fingerprint`
+    pub fingerprint: String,
+    pub query: String,
+    pub started_at: DateTime<Utc>,
+    /// Time since the statement started
+    pub elapsed: Duration,
+    /// Physical operator executing right now (`None` before planning, after
+    /// the plan finished, or for statements that run no plan)
+    pub operator: Option<String>,
+}
+
+/// Statements running on a database
+#[derive(Debug, Default)]
+pub struct ActiveQueries {
+    next_id: AtomicU64,
+    running: Mutex<BTreeMap<u64, RunningQuery>>,
+}
+
+#[derive(Debug)]
+struct RunningQuery {
+    session_id: String,
+    query: String,
+    started_at: DateTime<Utc>,
+    started: Instant,
+    operators: OperatorStack,
+}
+
+impl ActiveQueries {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a statement; it is listed until the returned guard is dropped
+    pub fn register(self: &Arc<Self>, session_id: &str, query: &str) -> ActiveQueryGuard {
+        let query_id = self.next_id.fetch_add(1, Ordering::Relaxed) + 1;
+        let operators = OperatorStack::default();
+        self.lock().insert(
+            query_id,
+            RunningQuery {
+                session_id: session_id.to_string(),
+                query: query.to_string(),
+                started_at: Utc::now(),
+                started: Instant::now(),
+                operators: operators.clone(),
+            },
+        );
+        ActiveQueryGuard {
+            queries: self.clone(),
+            query_id,
+            operators,
+        }
+    }
+
+    /// The running statements, oldest first
+    pub fn list(&self) -> Vec<ActiveQuery> {
+        self.lock()
+            .iter()
+            .map(|(query_id, running)| ActiveQuery {
+                query_id: *query_id,
+                session_id: running.session_id.clone(),
+                fingerprint: fingerprint(&running.query),
+                query: running.query.clone(),
+                started_at: running.started_at,
+                elapsed: running.started.elapsed(),
+                operator: running.operators.current().map(str::to_string),
+            })
+            .collect()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, BTreeMap<u64, RunningQuery>> {
+        self.running.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Keeps a statement listed in `ActiveQueries` while it runs
+#[derive(Debug)]
+pub struct ActiveQueryGuard {
+    queries: Arc<ActiveQueries>,
+    query_id: u64,
+    operators: OperatorStack,
+}
+
+impl ActiveQueryGuard {
+    /// Operator tracking for the statement's execution context
+    pub fn operators(&self) -> OperatorStack {
+        self.operators.clone()
+    }
+}
+
+impl Drop for ActiveQueryGuard {
+    fn drop(&mut self) {
+        self.queries.lock().remove(&self.query_id);
+    }
+}
+
+/// Operators a statement is executing, innermost last
+///
+/// Operators run depth-first, so each `enter` is matched by the next `exit`.
+/// Clones share the stack, like `OperatorTimer`.
+#[derive(Debug, Clone, Default)]
+pub struct OperatorStack {
+    operators: Arc<Mutex<Vec<&'static str>>>,
+}
+
+impl OperatorStack {
+    /// An operator starts
+    pub fn enter(&self, operator: &'static str) {
+        self.lock().push(operator);
+    }
+
+    /// The operator started by the matching `enter` finished
+    pub fn exit(&self) {
+        self.lock().pop();
+    }
+
+    /// Innermost operator still running
+    pub fn current(&self) -> Option<&'static str> {
+        self.lock().last().copied()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Vec<&'static str>> {
+        self.operators.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registered_queries_are_listed_until_dropped() {
+        let queries = Arc::new(ActiveQueries::new());
+        let first = queries.register("s1", "MATCH (n) RETURN n");
+        let _second = queries.register("s2", "RETURN 1");
+
+        first.operators().enter("Project");
+        first.operators().enter("NodeSeqScan");
+        let listed = queries.list();
+        assert_eq!(listed.len(), 2);
+        assert!(listed[1].query_id > listed[0].query_id);
+        assert_eq!(listed[0].session_id, "s1");
+        assert_eq!(listed[0].fingerprint, fingerprint("MATCH (n)  RETURN n;"));
+        assert_eq!(listed[0].operator.as_deref(), Some("NodeSeqScan"));
+        assert_eq!(listed[1].operator, None);
+
+        first.operators().exit();
+        assert_eq!(queries.list()[0].operator.as_deref(), Some("Project"));
+
+        drop(first);
+        let listed = queries.list();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].query, "RETURN 1");
+    }
+}
@@ -0,0 +1,146 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! Compression of serialized results
+//!
+//! Text-heavy JSON results shrink by an order of magnitude with gzip or
+//! Zstandard. `ContentEncoding` compresses a serialized result and names the
+//! matching HTTP `Content-Encoding`, and picks one from an `Accept-Encoding`
+//! header. `graphlite serve` and `graphlite_query_compressed` in the FFI both
+//! compress through it, so their output is byte-for-byte the same.
+
+use std::io::{self, Write};
+
+/// How a response body is compressed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ContentEncoding {
+    /// Not compressed
+    #[default]
+    Identity,
+    /// gzip (RFC 1952)
+    Gzip,
+    /// A Zstandard frame (RFC 8878)
+    Zstd,
+}
+
+impl ContentEncoding {
+    /// Token used in `Content-Encoding` and `Accept-Encoding` headers
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ContentEncoding::Identity => "identity",
+            ContentEncoding::Gzip => "gzip",
+            ContentEncoding::Zstd => "zstd",
+        }
+    }
+
+    /// Compress `bytes`
+    pub fn encode(self, bytes: Vec<u8>) -> io::Result<Vec<u8>> {
+        match self {
+            ContentEncoding::Identity => Ok(bytes),
+            ContentEncoding::Gzip => {
+                let mut encoder = flate2::write::GzEncoder::new(
+                    Vec::with_capacity(bytes.len() / 4),
+                    flate2::Compression::default(),
+                );
+                encoder.write_all(&bytes)?;
+                encoder.finish()
+            }
+            ContentEncoding::Zstd => zstd::encode_all(bytes.as_slice(), 0),
+        }
+    }
+
+    /// The encoding to answer a request with, given its `Accept-Encoding` header
+    ///
+    /// Picks the supported coding with the highest quality value, Zstandard
+    /// over gzip when they tie, and `Identity` when the client accepts neither.
+    /// `*` stands for any coding not listed; `q=0` rules a coding out.
+    pub fn negotiate(accept_encoding: &str) -> Self {
+        let mut wildcard = None;
+        let mut listed: Vec<(&str, f32)> = Vec::new();
+        for item in accept_encoding.split(',') {
+            let mut parts = item.split(';');
+            let coding = parts.next().unwrap_or_default().trim();
+            if coding.is_empty() {
+                continue;
+            }
+            let quality = parts
+                .filter_map(|param| param.trim().strip_prefix("q="))
+                .find_map(|q| q.trim().parse::<f32>().ok())
+                .unwrap_or(1.0);
+            if coding == "*" {
+                wildcard = Some(quality);
+            } else {
+                listed.push((coding, quality));
+            }
+        }
+
+        let quality = |encoding: ContentEncoding| {
+            listed
+                .iter()
+                .find(|(coding, _)| coding.eq_ignore_ascii_case(encoding.as_str()))
+                .map(|(_, quality)| *quality)
+                .or(wildcard)
+                .unwrap_or(0.0)
+        };
+        [ContentEncoding::Zstd, ContentEncoding::Gzip]
+            .into_iter()
+            .map(|encoding| (encoding, quality(encoding)))
+            .filter(|(_, quality)| *quality > 0.0)
+            .fold(
+                None,
+                |best: Option<(ContentEncoding, f32)>, candidate| match best {
+                    Some(best) if best.1 >= candidate.1 => Some(best),
+                    _ => Some(candidate),
+                },
+            )
+            .map_or(ContentEncoding::Identity, |(encoding, _)| encoding)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    #[test]
+    fn test_negotiate() {
+        let cases = [
+            ("", ContentEncoding::Identity),
+            ("gzip", ContentEncoding::Gzip),
+            ("gzip, deflate, br, zstd", ContentEncoding::Zstd),
+            ("GZIP", ContentEncoding::Gzip),
+            ("zstd;q=0.5, gzip", ContentEncoding::Gzip),
+            ("gzip;q=0.5, zstd;q=0.5", ContentEncoding::Zstd),
+            ("zstd;q=0, gzip;q=0", ContentEncoding::Identity),
+            ("deflate, br", ContentEncoding::Identity),
+            ("*", ContentEncoding::Zstd),
+            ("zstd;q=0, *;q=0.1", ContentEncoding::Gzip),
+            ("identity", ContentEncoding::Identity),
+        ];
+        for (header, expected) in cases {
+            assert_eq!(ContentEncoding::negotiate(header), expected, "{}", header);
+        }
+    }
+
+    #[test]
+    fn test_encode_round_trips() {
+        let json = br#"{"rows":[{"name":"Alice"},{"name":"Alice"},{"name":"Alice"}]}"#.repeat(50);
+
+        assert_eq!(
+            ContentEncoding::Identity.encode(json.clone()).unwrap(),
+            json
+        );
+
+        let gzip = ContentEncoding::Gzip.encode(json.clone()).unwrap();
+        let mut decoded = Vec::new();
+        flate2::read::GzDecoder::new(gzip.as_slice())
+            .read_to_end(&mut decoded)
+            .unwrap();
+        assert_eq!(decoded, json);
+        assert!(gzip.len() * 10 < json.len());
+
+        let zstd = ContentEncoding::Zstd.encode(json.clone()).unwrap();
+        assert_eq!(zstd::decode_all(zstd.as_slice()).unwrap(), json);
+        assert!(zstd.len() * 10 < json.len());
+    }
+}
@@ -3,13 +3,18 @@
 //
 //! Execution context for variable management and session lookup
 
+use crate::exec::active_queries::OperatorStack;
+use crate::exec::error::ExecutionError;
+use crate::exec::limits::QueryGuard;
+use crate::exec::temp_space::{QueryTempDir, TempSpace};
+use crate::exec::timing::OperatorTimer;
 use crate::functions::FunctionRegistry;
 use crate::session::models::{Session, UserSession};
 use crate::session::SessionProvider;
 use crate::storage::{StorageManager, Value};
 use crate::types::GqlType;
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 #[derive(Clone)]
 pub struct ExecutionContext {
@@ -39,6 +44,17 @@ pub struct ExecutionContext {
     pub current_transaction: Option<String>,
     /// Warnings generated during execution (e.g., duplicate insert detection)
     pub warnings: Vec<String>,
+    /// Resource limits for the running query
+    pub query_guard: Option<QueryGuard>,
+    /// Collects per-operator timing when the session asked for it
+    pub operator_timer: Option<OperatorTimer>,
+    /// Operators running right now, reported by `gql.active_queries`
+    pub active_operators: Option<OperatorStack>,
+    /// Scratch space that operators spill to
+    pub temp_space: Option<Arc<TempSpace>>,
+    /// This query's temp directory, shared by clones of the context and created on first use
+    #[allow(dead_code)] // ROADMAP v0.5.0 - Spill-to-disk sort, aggregation and cursors
+    temp_dir: Arc<Mutex<Option<Arc<QueryTempDir>>>>,
 }
 
 // Manual Debug implementation to avoid SessionProvider Debug requirement
@@ -66,6 +82,10 @@ impl std::fmt::Debug for ExecutionContext {
             .field("current_user", &self.current_user)
             .field("current_transaction", &self.current_transaction)
             .field("warnings", &self.warnings)
+            .field("query_guard", &self.query_guard)
+            .field("operator_timer", &self.operator_timer)
+            .field("active_operators", &self.active_operators)
+            .field("temp_space", &self.temp_space)
             .finish()
     }
 }
@@ -86,6 +106,11 @@ impl ExecutionContext {
             current_user: None,
             current_transaction: None,
             warnings: Vec::new(),
+            query_guard: None,
+            operator_timer: None,
+            active_operators: None,
+            temp_space: None,
+            temp_dir: Arc::new(Mutex::new(None)),
         }
     }
 
@@ -101,6 +126,48 @@ impl ExecutionContext {
         self
     }
 
+    /// Enforce resource limits while executing
+    pub fn with_query_guard(mut self, query_guard: QueryGuard) -> Self {
+        self.query_guard = Some(query_guard);
+        self
+    }
+
+    /// Time every executed operator
+    pub fn with_operator_timer(mut self, operator_timer: OperatorTimer) -> Self {
+        self.operator_timer = Some(operator_timer);
+        self
+    }
+
+    /// Report the operators that run in `active_operators`
+    pub fn with_active_operators(mut self, active_operators: OperatorStack) -> Self {
+        self.active_operators = Some(active_operators);
+        self
+    }
+
+    /// Let operators spill to `temp_space`
+    pub fn with_temp_space(mut self, temp_space: Arc<TempSpace>) -> Self {
+        self.temp_space = Some(temp_space);
+        self
+    }
+
+    /// This query's temp directory, created on the first call
+    ///
+    /// The directory and its files are removed once the last clone of the
+    /// context is dropped at the end of the query.
+    #[allow(dead_code)] // ROADMAP v0.5.0 - Spill-to-disk sort, aggregation and cursors
+    pub fn temp_dir(&self) -> Result<Arc<QueryTempDir>, ExecutionError> {
+        let temp_space = self.temp_space.as_ref().ok_or_else(|| {
+            ExecutionError::RuntimeError("No temp space available for spilling".to_string())
+        })?;
+        let mut temp_dir = self.temp_dir.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(dir) = temp_dir.as_ref() {
+            return Ok(dir.clone());
+        }
+        let dir = Arc::new(temp_space.query_dir()?);
+        *temp_dir = Some(dir.clone());
+        Ok(dir)
+    }
+
     /// Add a warning to the execution context
     pub fn add_warning(&mut self, warning: String) {
         self.warnings.push(warning);
@@ -47,6 +47,15 @@ pub enum ExecutionError {
 
     #[error("Memory limit exceeded: requested {requested} bytes, limit {limit} bytes")]
     MemoryLimitExceeded { limit: usize, requested: usize },
+
+    #[error("Query timed out after {timeout_ms} ms")]
+    QueryTimeout { timeout_ms: u64 },
+
+    #[error("Result limit exceeded: {rows} rows, limit {limit} rows")]
+    ResultLimitExceeded { limit: usize, rows: usize },
+
+    #[error("Temp space quota exceeded: requested {requested} bytes, quota {limit} bytes")]
+    TempSpaceExceeded { limit: u64, requested: u64 },
 }
 
 impl From<StorageError> for ExecutionError {
@@ -4,21 +4,28 @@
 //! Main query executor implementation
 
 use std::collections::HashMap;
+use std::ops::Bound;
 use std::sync::Arc;
 
 use crate::catalog::system_procedures::{is_system_procedure, SystemProcedures};
-use crate::plan::logical::PathElement;
-use crate::plan::physical::{PhysicalNode, PhysicalPlan, ProjectionItem, SortItem};
+use crate::plan::logical::{PathElement, HAVING_AGGREGATE_PREFIX};
+use crate::plan::physical::{
+    EdgePropertyPredicate, PhysicalNode, PhysicalPlan, ProjectionItem, SortItem,
+};
 
 use crate::ast::{
     AtLocationStatement, BasicQuery, CaseType, CatalogPath, CatalogStatement, DeclareStatement,
-    EdgeDirection, Expression, FunctionCall, GraphExpression, Location, MatchClause, NextStatement,
+    DistinctQualifier, EdgeDirection, Expression, FunctionCall, GraphExpression, LimitClause,
+    Location, MatchClause, NextStatement, NullsOrdering, OrderClause, OrderDirection, PathPattern,
     PathQuantifier, PathType, ProcedureBodyStatement, PropertyAccess, ReturnClause, ReturnItem,
     SearchedCaseExpression, SelectItems, SessionStatement, SimpleCaseExpression, Statement,
     TransactionStatement, TypeSpec, Variable, WhereClause, WithClause, WithQuery,
 };
 use crate::cache::CacheManager;
-use crate::storage::{GraphCache, StorageManager, Value};
+use crate::plan::pinning::{fingerprint, PlanPins};
+use crate::storage::multi_graph::UnionConflictPolicy;
+use crate::storage::value::PathValue;
+use crate::storage::{GraphCache, StorageError, StorageManager, Value};
 use crate::txn::{TransactionId, TransactionLog, TransactionManager, UndoOperation};
 
 use crate::catalog::manager::CatalogManager;
@@ -29,9 +36,14 @@ use crate::types::{
 };
 use serde_json::json;
 
+use super::active_queries::{ActiveQueries, ActiveQuery};
 use super::context::ExecutionContext;
 use super::error::ExecutionError;
+use super::limits::{QueryGuard, QueryLimits};
+use super::path_search::{self, HopPattern};
 use super::result::{QueryResult, Row};
+use super::temp_space::TempSpace;
+use super::timing::OperatorTimer;
 use crate::session::models::UserSession;
 use crate::session::SessionProvider;
 
@@ -53,6 +65,8 @@ pub struct ExecutionRequest {
     pub physical_plan: Option<PhysicalPlan>,
     /// Whether this query requires graph context (from validator)
     pub requires_graph_context: Option<bool>,
+    /// Resource limits to enforce while executing
+    pub limits: QueryLimits,
 }
 
 impl ExecutionRequest {
@@ -65,6 +79,7 @@ impl ExecutionRequest {
             query_text: None,
             physical_plan: None,
             requires_graph_context: None,
+            limits: QueryLimits::default(),
         }
     }
 
@@ -97,6 +112,12 @@ impl ExecutionRequest {
         self.requires_graph_context = Some(requires_graph_context);
         self
     }
+
+    /// Set the resource limits
+    pub fn with_limits(mut self, limits: QueryLimits) -> Self {
+        self.limits = limits;
+        self
+    }
 }
 
 /// Main query executor focused purely on execution
@@ -115,6 +136,15 @@ pub struct QueryExecutor {
     // Cache management
     cache_manager: Option<Arc<CacheManager>>,
 
+    // Plans pinned with gql.pin_plan
+    plan_pins: Arc<PlanPins>,
+
+    // Scratch space for operators that spill to disk
+    temp_space: Option<Arc<TempSpace>>,
+
+    // Statements running right now, listed by gql.active_queries
+    active_queries: Arc<ActiveQueries>,
+
     // Transaction management (session-agnostic)
     transaction_manager: Arc<TransactionManager>,
     current_transaction: Arc<std::sync::RwLock<Option<TransactionId>>>,
@@ -147,6 +177,74 @@ impl QueryExecutor {
         self.transaction_manager.clone()
     }
 
+    pub fn cache_manager(&self) -> Option<Arc<CacheManager>> {
+        self.cache_manager.clone()
+    }
+
+    /// The statements running right now, oldest first
+    pub fn active_queries(&self) -> Vec<ActiveQuery> {
+        self.active_queries.list()
+    }
+
+    /// Reload the graphs that were most used before the last shutdown into the storage cache
+    pub fn warm_cache_on_startup(&self) -> usize {
+        self.system_procedures.warm_hot_set().len()
+    }
+
+    /// Persist graph access metadata for the next startup's cache warming
+    pub fn save_cache_hot_set(&self) {
+        if let Some(cache_manager) = &self.cache_manager {
+            if let Err(e) = cache_manager.save_hot_set(&self.storage) {
+                log::warn!("{}", e);
+            }
+        }
+    }
+
+    /// Count a graph read towards the cache manager's hot set
+    fn record_graph_access(&self, graph_name: &str) {
+        if let Some(cache_manager) = &self.cache_manager {
+            cache_manager.record_graph_access(graph_name);
+        }
+    }
+
+    /// Use the plan pinned for a basic query's fingerprint, if any
+    ///
+    /// Queries without a pin are remembered so that `gql.pin_plan` can pin them.
+    fn apply_pinned_plan(&self, mut request: ExecutionRequest) -> ExecutionRequest {
+        if request.physical_plan.is_some()
+            || !matches!(
+                request.statement,
+                Statement::Query(crate::ast::Query::Basic(_))
+            )
+        {
+            return request;
+        }
+        if let Some(query_text) = &request.query_text {
+            let fingerprint = fingerprint(query_text);
+            match self.plan_pins.pinned_plan(&fingerprint) {
+                Some(plan) => request.physical_plan = Some(plan),
+                None => self.plan_pins.record_query(&fingerprint, query_text),
+            }
+        }
+        request
+    }
+
+    /// Remove pinned plans after a statement that changes the schema
+    fn invalidate_pinned_plans(&self, statement: &Statement) {
+        let reason = match statement {
+            Statement::CatalogStatement(
+                CatalogStatement::DropSchema(_)
+                | CatalogStatement::DropGraph(_)
+                | CatalogStatement::CreateGraphType(_)
+                | CatalogStatement::DropGraphType(_)
+                | CatalogStatement::AlterGraphType(_),
+            ) => "a catalog change",
+            Statement::IndexStatement(_) => "an index change",
+            _ => return,
+        };
+        self.plan_pins.invalidate(reason);
+    }
+
     /// Unified execution entry point - all queries flow through here
     pub fn execute_query(&self, request: ExecutionRequest) -> Result<QueryResult, ExecutionError> {
         log::debug!(
@@ -178,6 +276,7 @@ impl QueryExecutor {
                             query_text: Some(query.to_string()),
                             physical_plan: None,
                             requires_graph_context: request.requires_graph_context,
+                            limits: request.limits,
                         };
 
                         // Execute the individual query normally
@@ -212,6 +311,22 @@ impl QueryExecutor {
 
         // Step 3: Create execution context with session information
         let mut context = self.create_execution_context_from_session(request.session.as_ref());
+        if !request.limits.is_unlimited() {
+            context = context.with_query_guard(QueryGuard::start(request.limits));
+        }
+        let timing = request
+            .session
+            .as_ref()
+            .and_then(|session_lock| session_lock.read().ok())
+            .is_some_and(|session| session.get_operator_timing());
+        if timing {
+            context = context.with_operator_timer(OperatorTimer::new());
+        }
+        let active_query = self.active_queries.register(
+            &context.session_id,
+            request.query_text.as_deref().unwrap_or_default(),
+        );
+        context = context.with_active_operators(active_query.operators());
 
         // Set the resolved graph in context if available
         if let Some(graph) = &resolved_graph {
@@ -219,7 +334,15 @@ impl QueryExecutor {
         }
 
         // Step 4: Route to appropriate execution path based on statement type
-        let result = self.route_and_execute(&request, &mut context, resolved_graph.as_ref())?;
+        let request = self.apply_pinned_plan(request);
+        let mut result = self.route_and_execute(&request, &mut context, resolved_graph.as_ref())?;
+        self.invalidate_pinned_plans(&request.statement);
+        if let Some(guard) = &context.query_guard {
+            guard.check_result_rows(result.rows.len())?;
+        }
+        if let Some(timer) = &context.operator_timer {
+            result.operator_timings = timer.finish();
+        }
 
         // Step 5: Audit if enabled and query text provided
         if let Some(query_text) = &request.query_text {
@@ -245,7 +368,7 @@ impl QueryExecutor {
     ) -> Result<Arc<GraphCache>, ExecutionError> {
         // Priority 1: Explicit graph expression in query
         if let Some(graph_expr) = &request.graph_expr {
-            return self.resolve_graph_expression(Some(graph_expr));
+            return self.resolve_graph_reference(Some(graph_expr), request.session.as_ref());
         }
 
         // Priority 2: Session's current graph
@@ -253,7 +376,10 @@ impl QueryExecutor {
             if let Ok(session) = session_lock.read() {
                 if let Some(current_graph_path) = &session.current_graph {
                     match self.storage.get_graph(current_graph_path)? {
-                        Some(graph) => return Ok(Arc::new(graph)),
+                        Some(graph) => {
+                            self.record_graph_access(current_graph_path);
+                            return Ok(Arc::new(graph));
+                        }
                         None => {
                             return Err(ExecutionError::RuntimeError(format!(
                                 "Session graph '{}' not found",
@@ -294,6 +420,10 @@ impl QueryExecutor {
         } else {
             context
         };
+        let context = match &self.temp_space {
+            Some(temp_space) => context.with_temp_space(temp_space.clone()),
+            None => context,
+        };
 
         context
             .with_function_registry(self.function_registry.clone())
@@ -349,6 +479,7 @@ impl QueryExecutor {
             rows_affected: 0,
             session_result: None,
             warnings: Vec::new(),
+            operator_timings: None,
         })
     }
 
@@ -370,6 +501,7 @@ impl QueryExecutor {
             rows_affected: 0,
             session_result: None,
             warnings: Vec::new(),
+            operator_timings: None,
         })
     }
 
@@ -390,31 +522,6 @@ impl QueryExecutor {
         );
     }
 
-    /// Resolve a graph expression to an actual graph (helper for internal use)
-    fn resolve_graph_expression(
-        &self,
-        _graph_expr: Option<&GraphExpression>,
-    ) -> Result<Arc<GraphCache>, ExecutionError> {
-        // For now, ignore the graph expression and use the first available graph
-        // This is a quick fix - the proper solution is to use resolve_graph_for_execution
-        // with proper session context
-        match self.storage.list_graphs() {
-            Ok(graph_names) if !graph_names.is_empty() => {
-                let graph_name = &graph_names[0];
-                match self.storage.get_graph(graph_name)? {
-                    Some(graph) => Ok(Arc::new(graph)),
-                    None => Err(ExecutionError::RuntimeError(format!(
-                        "Graph {} not found",
-                        graph_name
-                    ))),
-                }
-            }
-            _ => Err(ExecutionError::RuntimeError(
-                "No graphs available".to_string(),
-            )),
-        }
-    }
-
     /// Create a new query executor with provided Storage and Catalog managers
     /// This ensures we use singleton instances from SessionManager
     pub fn new(
@@ -424,13 +531,19 @@ impl QueryExecutor {
         session_provider: Arc<dyn SessionProvider>,
         cache_manager: Option<Arc<CacheManager>>,
     ) -> Result<Self, ExecutionError> {
+        let plan_pins = Arc::new(PlanPins::load(storage_manager.clone()));
+        let active_queries = Arc::new(ActiveQueries::new());
+
         // Create system procedures with the provided managers
         let system_procedures = SystemProcedures::new(
             catalog_manager.clone(),
             storage_manager.clone(),
             cache_manager.clone(),
         )
-        .with_session_provider(session_provider.clone());
+        .with_session_provider(session_provider.clone())
+        .with_wal_dir(transaction_manager.wal_dir().to_path_buf())
+        .with_plan_pins(plan_pins.clone())
+        .with_active_queries(active_queries.clone());
 
         Ok(Self {
             storage: storage_manager,
@@ -439,6 +552,9 @@ impl QueryExecutor {
             system_procedures,
             session_provider,
             cache_manager,
+            plan_pins,
+            temp_space: None,
+            active_queries,
             transaction_manager,
             current_transaction: Arc::new(std::sync::RwLock::new(None)),
             transaction_logs: Arc::new(std::sync::RwLock::new(std::collections::HashMap::new())),
@@ -450,6 +566,13 @@ impl QueryExecutor {
         })
     }
 
+    /// Give queries a temp space to spill to, reported by `gql.storage_stats`
+    pub fn with_temp_space(mut self, temp_space: Arc<TempSpace>) -> Self {
+        self.system_procedures = self.system_procedures.with_temp_space(temp_space.clone());
+        self.temp_space = Some(temp_space);
+        self
+    }
+
     /// Get a graph with lazy loading (now handled by StorageManager)
     fn lazy_load_graph(&self, graph_name: &str) -> Result<Option<GraphCache>, ExecutionError> {
         log::debug!("Getting graph '{}' with lazy loading", graph_name);
@@ -473,12 +596,22 @@ impl QueryExecutor {
     ) -> Result<Arc<GraphCache>, ExecutionError> {
         match graph_expr {
             Some(GraphExpression::Reference(catalog_path)) => {
-                // Explicit graph reference - resolve directly
-                let graph_name = catalog_path.to_string();
+                // Explicit graph reference - resolve directly, never substituting another graph
+                let qualified_path = self.qualify_graph_path(catalog_path, session)?;
+                let graph_name = qualified_path.to_string();
+                if !self.validate_graph_expression_via_catalog(&GraphExpression::Reference(
+                    qualified_path,
+                )) {
+                    return Err(ExecutionError::CatalogError(format!(
+                        "Graph does not exist: {}",
+                        graph_name
+                    )));
+                }
 
                 match self.lazy_load_graph(&graph_name)? {
                     Some(graph) => {
                         log::debug!("Resolved graph reference to existing graph: {}", graph_name);
+                        self.record_graph_access(&graph_name);
                         Ok(Arc::new(graph))
                     }
                     None => {
@@ -503,6 +636,7 @@ impl QueryExecutor {
                                         "Resolved CurrentGraph from session: {}",
                                         current_graph_path
                                     );
+                                    self.record_graph_access(current_graph_path);
                                     return Ok(Arc::new(graph));
                                 }
                                 None => {
@@ -520,42 +654,12 @@ impl QueryExecutor {
                     "CurrentGraph reference requires session with SET GRAPH. Use SESSION SET GRAPH <path> first.".to_string()
                 ))
             }
-            Some(GraphExpression::Union {
-                left,
-                right,
-                all: _,
-            }) => {
-                // Resolve left and right graph names
-                let left_name = match left.as_ref() {
-                    GraphExpression::Reference(catalog_path) => {
-                        catalog_path.segments.last().ok_or_else(|| {
-                            ExecutionError::RuntimeError("Invalid left graph path".to_string())
-                        })?
-                    }
-                    _ => {
-                        return Err(ExecutionError::RuntimeError(
-                            "Unsupported left graph expression in union".to_string(),
-                        ))
-                    }
-                };
-
-                let right_name = match right.as_ref() {
-                    GraphExpression::Reference(catalog_path) => {
-                        catalog_path.segments.last().ok_or_else(|| {
-                            ExecutionError::RuntimeError("Invalid right graph path".to_string())
-                        })?
-                    }
-                    _ => {
-                        return Err(ExecutionError::RuntimeError(
-                            "Unsupported right graph expression in union".to_string(),
-                        ))
-                    }
-                };
-
-                // Perform graph union using unified storage
-                let union_result = self
-                    .storage
-                    .create_graph_union(vec![left_name.clone(), right_name.clone()])?;
+            Some(union @ GraphExpression::Union { .. }) => {
+                let policy = session
+                    .and_then(|session_lock| session_lock.read().ok())
+                    .map(|session_guard| session_guard.get_union_conflict_policy())
+                    .unwrap_or_default();
+                let (_, union_result) = self.resolve_union_operand(union, session, policy)?;
                 Ok(Arc::new(union_result))
             }
             None => Err(ExecutionError::RuntimeError(
@@ -564,6 +668,88 @@ impl QueryExecutor {
         }
     }
 
+    /// Qualify a graph reference to its full `/<schema>/<graph>` path
+    ///
+    /// A bare graph name is resolved against the session's current schema; without
+    /// one the reference is rejected rather than guessed.
+    fn qualify_graph_path(
+        &self,
+        catalog_path: &CatalogPath,
+        session: Option<&Arc<std::sync::RwLock<crate::session::models::UserSession>>>,
+    ) -> Result<CatalogPath, ExecutionError> {
+        match catalog_path.segments.len() {
+            2 => Ok(catalog_path.clone()),
+            1 => {
+                let current_schema = session
+                    .and_then(|session_lock| session_lock.read().ok())
+                    .and_then(|session_guard| session_guard.current_schema.clone());
+                match current_schema {
+                    Some(schema) => {
+                        let schema_name = schema.trim_start_matches('/').to_string();
+                        Ok(CatalogPath::new(
+                            vec![schema_name, catalog_path.segments[0].clone()],
+                            catalog_path.location.clone(),
+                        ))
+                    }
+                    None => Err(ExecutionError::RuntimeError(format!(
+                        "Cannot resolve relative graph path '{}' without a current schema. Use SESSION SET SCHEMA or provide full path /<schema-name>/<graph-name>",
+                        catalog_path.segments[0]
+                    ))),
+                }
+            }
+            _ => Err(ExecutionError::RuntimeError(format!(
+                "Invalid graph path '{}'. Use /<schema-name>/<graph-name> format.",
+                catalog_path
+            ))),
+        }
+    }
+
+    /// Resolve one side of a graph UNION to a named graph
+    ///
+    /// Unlike plain references, union operands must already exist; nested unions
+    /// are evaluated recursively with the same conflict policy.
+    fn resolve_union_operand(
+        &self,
+        graph_expr: &GraphExpression,
+        session: Option<&Arc<std::sync::RwLock<crate::session::models::UserSession>>>,
+        policy: UnionConflictPolicy,
+    ) -> Result<(String, GraphCache), ExecutionError> {
+        match graph_expr {
+            GraphExpression::Reference(catalog_path) => {
+                let qualified_path = self.qualify_graph_path(catalog_path, session)?;
+                let graph_name = qualified_path.to_string();
+                if !self.validate_graph_expression_via_catalog(&GraphExpression::Reference(
+                    qualified_path,
+                )) {
+                    return Err(StorageError::GraphNotFound(graph_name).into());
+                }
+                let graph = self
+                    .lazy_load_graph(&graph_name)?
+                    .ok_or_else(|| StorageError::GraphNotFound(graph_name.clone()))?;
+                Ok((graph_name, graph))
+            }
+            GraphExpression::CurrentGraph => {
+                let graph = self.resolve_graph_reference(Some(graph_expr), session)?;
+                Ok(("CURRENT_GRAPH".to_string(), (*graph).clone()))
+            }
+            GraphExpression::Union { left, right, all } => {
+                let left = self.resolve_union_operand(left, session, policy)?;
+                let right = self.resolve_union_operand(right, session, policy)?;
+                let name = format!(
+                    "({} UNION{} {})",
+                    left.0,
+                    if *all { " ALL" } else { "" },
+                    right.0
+                );
+                let union_result =
+                    self.storage
+                        .cache()
+                        .union_graphs(vec![left, right], *all, policy)?;
+                Ok((name, union_result))
+            }
+        }
+    }
+
     /// Resolve graph name from graph expression for data modifications
     pub fn resolve_graph_name_for_modification(
         &self,
@@ -940,7 +1126,7 @@ impl QueryExecutor {
                 }
             }
             Statement::SessionStatement(session_stmt) => {
-                self.execute_session_statement(session_stmt)
+                self.execute_session_statement(session_stmt, session)
             }
             Statement::Declare(declare_stmt) => {
                 // Execute DECLARE statement to define local variables
@@ -982,7 +1168,7 @@ impl QueryExecutor {
         use crate::plan::optimizer::QueryPlanner;
 
         // Resolve graph expression to actual graph
-        let graph = self.resolve_graph_expression(graph_expr)?;
+        let graph = self.resolve_graph_reference(graph_expr, None)?;
 
         // Create a Document and Statement wrapper for the planner
         let query = Query::Basic(basic_query.clone());
@@ -1187,22 +1373,22 @@ impl QueryExecutor {
         );
         let mut current_context = outer_context.clone();
 
-        let mut final_results = Vec::new();
+        // A single row without variables, which every first MATCH extends
+        let mut final_results = vec![Row::new()];
 
         // Execute each query segment in sequence, piping results forward
         for (i, segment) in with_query.segments.iter().enumerate() {
-            // Execute MATCH clause for this segment
-            let mut segment_results =
+            // Execute MATCH clause for this segment and join it to the rows so far;
+            // the WHERE clause that comes BEFORE WITH belongs to the MATCH
+            let matched =
                 self.execute_match_with_context(&segment.match_clause, &current_context)?;
-
-            // Apply WHERE clause if present (this is the WHERE clause that comes BEFORE WITH)
-            if let Some(where_clause) = &segment.where_clause {
-                segment_results = self.apply_where_filter_to_rows(
-                    segment_results,
-                    where_clause,
-                    &current_context,
-                )?;
-            }
+            let segment_results = self.join_match_rows(
+                final_results,
+                matched,
+                &segment.match_clause,
+                segment.where_clause.as_ref(),
+                &current_context,
+            )?;
 
             // Apply WITH clause to transform and filter results (if present)
             let mut with_results = if let Some(with_clause) = &segment.with_clause {
@@ -1282,33 +1468,118 @@ impl QueryExecutor {
         Ok(result)
     }
 
-    /// Execute a MATCH clause with context support for WITH queries
-    fn execute_match_with_context(
+    /// Join the rows of a segment's MATCH to the rows of the segments before it
+    ///
+    /// Rows are joined on the variables both sides bind, comparing nodes and
+    /// edges by id; without shared variables every pair of rows is combined.
+    /// `where_clause` filters the joined rows. For OPTIONAL MATCH, a row left
+    /// without a match is kept once, with the MATCH's new variables bound to NULL.
+    fn join_match_rows(
         &self,
+        rows: Vec<Row>,
+        matched: Vec<Row>,
         match_clause: &MatchClause,
+        where_clause: Option<&WhereClause>,
         context: &ExecutionContext,
     ) -> Result<Vec<Row>, ExecutionError> {
-        // Extract all variable names from the MATCH clause patterns
-        let mut variables = std::collections::HashSet::<String>::new();
-        for pattern in &match_clause.patterns {
-            for element in &pattern.elements {
-                match element {
-                    crate::ast::PatternElement::Node(node) => {
-                        if let Some(ref var) = node.identifier {
-                            variables.insert(var.clone());
+        let variables = Self::match_clause_variables(match_clause);
+        let shared: Vec<String> = match rows.first() {
+            Some(row) => variables
+                .iter()
+                .filter(|var| row.values.contains_key(*var))
+                .cloned()
+                .collect(),
+            None => Vec::new(),
+        };
+        let join_key = |row: &Row| -> Option<Vec<String>> {
+            shared
+                .iter()
+                .map(|var| match row.values.get(var)? {
+                    Value::Null => None,
+                    Value::Node(node) => Some(format!("node:{}", node.id)),
+                    Value::Edge(edge) => Some(format!("edge:{}", edge.id)),
+                    value => Some(format!("{:?}", value)),
+                })
+                .collect()
+        };
+
+        let mut matches_by_key: HashMap<Vec<String>, Vec<&Row>> = HashMap::new();
+        for row in &matched {
+            if let Some(key) = join_key(row) {
+                matches_by_key.entry(key).or_default().push(row);
+            }
+        }
+
+        let mut joined_rows = Vec::new();
+        for row in rows {
+            let candidates = join_key(&row)
+                .and_then(|key| matches_by_key.get(&key))
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            let mut joined: Vec<Row> = candidates
+                .iter()
+                .map(|candidate| {
+                    let mut joined = row.clone();
+                    for (var, value) in &candidate.values {
+                        if !joined.values.contains_key(var) {
+                            joined.values.insert(var.clone(), value.clone());
                         }
                     }
-                    crate::ast::PatternElement::Edge(edge) => {
-                        if let Some(ref var) = edge.identifier {
-                            variables.insert(var.clone());
-                        }
+                    for (var, entity) in &candidate.source_entities {
+                        joined
+                            .source_entities
+                            .entry(var.clone())
+                            .or_insert_with(|| entity.clone());
+                    }
+                    joined
+                })
+                .collect();
+            if let Some(where_clause) = where_clause {
+                joined = self.apply_where_filter_to_rows(joined, where_clause, context)?;
+            }
+
+            if joined.is_empty() && match_clause.optional {
+                let mut padded = row;
+                for var in &variables {
+                    if !padded.values.contains_key(var) {
+                        padded.values.insert(var.clone(), Value::Null);
+                    }
+                }
+                joined_rows.push(padded);
+            } else {
+                joined_rows.extend(joined);
+            }
+        }
+        Ok(joined_rows)
+    }
+
+    /// Node and edge variables of the patterns of a MATCH clause, in order of appearance
+    fn match_clause_variables(match_clause: &MatchClause) -> Vec<String> {
+        let mut variables = Vec::new();
+        for pattern in &match_clause.patterns {
+            for element in &pattern.elements {
+                let identifier = match element {
+                    crate::ast::PatternElement::Node(node) => &node.identifier,
+                    crate::ast::PatternElement::Edge(edge) => &edge.identifier,
+                };
+                if let Some(var) = identifier {
+                    if !variables.contains(var) {
+                        variables.push(var.clone());
                     }
                 }
             }
         }
+        variables
+    }
 
+    /// Execute a MATCH clause with context support for WITH queries
+    fn execute_match_with_context(
+        &self,
+        match_clause: &MatchClause,
+        context: &ExecutionContext,
+    ) -> Result<Vec<Row>, ExecutionError> {
         // Create RETURN items for all variables found in the MATCH clause
-        let return_items: Vec<ReturnItem> = variables
+        let return_items: Vec<ReturnItem> = Self::match_clause_variables(match_clause)
             .into_iter()
             .map(|var| ReturnItem {
                 expression: Expression::Variable(Variable {
@@ -1576,7 +1847,27 @@ impl QueryExecutor {
             return Ok(result_rows);
         }
 
-        // For aggregation functions, use the existing processor path
+        // Grouped aggregation is grouped on the rows here. The processor pairs
+        // the nodes of different variables by position, which goes wrong once
+        // a row binds a variable to NULL, such as an OPTIONAL MATCH that
+        // matched nothing
+        let is_grouped = with_clause.items.iter().any(|item| {
+            !matches!(
+                &item.expression,
+                Expression::FunctionCall(func_call)
+                    if Self::is_with_aggregation_function(&func_call.name)
+            )
+        });
+        if is_grouped {
+            return self.execute_with_aggregation_by_group(
+                with_clause,
+                input_rows,
+                context,
+                &enhanced_context,
+            );
+        }
+
+        // Aggregation over all rows as one group goes to the processor
         let (variable_bindings, edges) = self.convert_rows_to_processor_format(input_rows)?;
 
         use crate::exec::with_clause_processor::WithClauseProcessor;
@@ -1590,6 +1881,99 @@ impl QueryExecutor {
         self.convert_processor_result_to_rows(with_result)
     }
 
+    /// Execute a WITH clause with aggregation one group of rows at a time
+    ///
+    /// Rows are grouped on the values of the non-aggregated WITH items, NULL
+    /// being a value like any other, and the processor aggregates the nodes the
+    /// rows of each group bind. A variable bound to NULL binds no node, so
+    /// aggregates such as COUNT skip it.
+    fn execute_with_aggregation_by_group(
+        &self,
+        with_clause: &WithClause,
+        input_rows: Vec<Row>,
+        context: &ExecutionContext,
+        processor_context: &ExecutionContext,
+    ) -> Result<Vec<Row>, ExecutionError> {
+        use crate::exec::with_clause_processor::WithClauseProcessor;
+
+        let mut grouping_items = Vec::new();
+        let mut aggregate_items = Vec::new();
+        for (index, item) in with_clause.items.iter().enumerate() {
+            // Name unaliased columns after their variable or position
+            let alias = item
+                .alias
+                .clone()
+                .unwrap_or_else(|| match &item.expression {
+                    Expression::Variable(var) => var.name.clone(),
+                    _ => format!("expr_{}", index),
+                });
+            let is_aggregate = matches!(
+                &item.expression,
+                Expression::FunctionCall(func_call)
+                    if Self::is_with_aggregation_function(&func_call.name)
+            );
+            if is_aggregate {
+                let mut item = item.clone();
+                item.alias = Some(alias);
+                aggregate_items.push(item);
+            } else {
+                grouping_items.push((&item.expression, alias));
+            }
+        }
+        let aggregates = WithClause {
+            items: aggregate_items,
+            where_clause: None,
+            ..with_clause.clone()
+        };
+
+        let mut groups: Vec<(Vec<Value>, Vec<Row>)> = Vec::new();
+        let mut group_index: HashMap<Vec<String>, usize> = HashMap::new();
+        for row in input_rows {
+            let values = grouping_items
+                .iter()
+                .map(|(expression, _)| self.evaluate_expression_in_row(expression, &row, context))
+                .collect::<Result<Vec<_>, _>>()?;
+            let key = values
+                .iter()
+                .map(|value| match value {
+                    Value::Node(node) => format!("node:{}", node.id),
+                    Value::Edge(edge) => format!("edge:{}", edge.id),
+                    value => format!("{:?}", value),
+                })
+                .collect();
+            let index = *group_index.entry(key).or_insert_with(|| {
+                groups.push((values, Vec::new()));
+                groups.len() - 1
+            });
+            groups[index].1.push(row);
+        }
+
+        let mut result_rows = Vec::new();
+        for (values, rows) in groups {
+            let (variable_bindings, edges) = self.convert_rows_to_processor_format(rows)?;
+            let with_result = WithClauseProcessor::process_with_clause(
+                &aggregates,
+                &variable_bindings,
+                &edges,
+                processor_context,
+            )?;
+
+            let mut row_values = with_result.computed_values;
+            for ((_, alias), value) in grouping_items.iter().zip(values) {
+                row_values.insert(alias.clone(), value);
+            }
+            let row = Row::from_values(row_values);
+
+            if let Some(where_clause) = &with_clause.where_clause {
+                if !self.evaluate_where_expression_on_row(where_clause, &row, context)? {
+                    continue;
+                }
+            }
+            result_rows.push(row);
+        }
+        Ok(result_rows)
+    }
+
     /// Check if a function name represents an aggregation function
     fn is_with_aggregation_function(func_name: &str) -> bool {
         matches!(
@@ -1700,9 +2084,14 @@ impl QueryExecutor {
                 edges.push(edge);
             }
 
-            // For each node variable, create a node with properties from the row
+            // For each node variable, create a node with properties from the row;
+            // a variable bound to NULL binds no node
             for node_var in &vars {
-                if let Some(node_value) = row.values.get(node_var) {
+                if let Some(node_value) = row
+                    .values
+                    .get(node_var)
+                    .filter(|value| !matches!(value, Value::Null))
+                {
                     // Create node with properties collected from row
                     let mut node = self.try_convert_value_to_node(node_var, node_value)?;
 
@@ -2287,6 +2676,18 @@ impl QueryExecutor {
                             use crate::ast::DistinctQualifier;
                             if let Some(arg_expr) = func_call.arguments.first() {
                                 let mut collected_values = Vec::new();
+                                let sorted_rows;
+                                let rows = match &func_call.order_clause {
+                                    Some(order_clause) => {
+                                        sorted_rows = self.sort_aggregate_rows(
+                                            order_clause,
+                                            with_rows.clone(),
+                                            context,
+                                        )?;
+                                        &sorted_rows
+                                    }
+                                    None => &with_rows,
+                                };
 
                                 match func_call.distinct {
                                     DistinctQualifier::Distinct => {
@@ -2294,7 +2695,7 @@ impl QueryExecutor {
                                         let mut unique_values = std::collections::HashSet::new();
                                         let mut unique_list = Vec::new();
 
-                                        for row in &with_rows {
+                                        for row in rows {
                                             // Evaluate the expression (handles both Variable and PropertyAccess)
                                             if let Ok(value) = self
                                                 .evaluate_expression_in_row(arg_expr, row, context)
@@ -2311,7 +2712,7 @@ impl QueryExecutor {
                                     }
                                     _ => {
                                         // Regular COLLECT
-                                        for row in &with_rows {
+                                        for row in rows {
                                             // Evaluate the expression (handles both Variable and PropertyAccess)
                                             if let Ok(value) = self
                                                 .evaluate_expression_in_row(arg_expr, row, context)
@@ -2324,7 +2725,10 @@ impl QueryExecutor {
                                     }
                                 }
 
-                                Ok(Value::List(collected_values))
+                                Ok(Self::limit_aggregate_list(
+                                    func_call.limit_clause.as_deref(),
+                                    Value::List(collected_values),
+                                ))
                             } else {
                                 Err(ExecutionError::UnsupportedOperator(
                                     "COLLECT requires an argument".to_string(),
@@ -2800,6 +3204,7 @@ impl QueryExecutor {
             // Graph-independent catalog procedures
             "gql.list_schemas"
             | "gql.list_graphs"
+            | "gql.find_graphs"
             | "gql.list_graph_types"
             | "gql.describe_graph_type"
             | "gql.list_node_types"
@@ -2830,13 +3235,38 @@ impl QueryExecutor {
             | "gql.list_functions" => false,
 
             // Cache management procedures that don't need graph context
-            "gql.clear_cache" | "gql.cache_stats" => false,
+            "gql.clear_cache" | "gql.cache_stats" | "gql.warm_cache" => false,
 
             // Procedures that can work with explicit parameters or session context
-            "gql.graph_stats" | "gql.sample_data" => false, // These handle their own graph resolution
+            "gql.graph_stats"
+            | "gql.sample_data"
+            | "gql.list_labels"
+            | "gql.list_relationship_types"
+            | "gql.list_property_keys" => false, // These handle their own graph resolution
 
             // Session-dependent procedures that don't need graph context
-            "gql.show_session" => false,
+            "gql.show_session" | "gql.session_history" => false,
+
+            // Log procedures that don't need graph context
+            "gql.decode_wal" => false,
+
+            // Plan pinning procedures that don't need graph context
+            "gql.query_fingerprint"
+            | "gql.pin_plan"
+            | "gql.unpin_plan"
+            | "gql.list_pinned_plans" => false,
+
+            // Storage statistics don't need graph context
+            "gql.storage_stats" => false,
+
+            // Outbox procedures resolve their own graph
+            "gql.outbox_append" | "gql.outbox_poll" | "gql.outbox_ack" => false,
+
+            // The feature matrix is static
+            "gql.supported_features" => false,
+
+            // Running statements span all graphs
+            "gql.active_queries" => false,
 
             // All other procedures (unknown gql.* or non-gql.*)
             // Route to execute_call_statement_without_graph where proper errors will be raised
@@ -2995,6 +3425,8 @@ impl QueryExecutor {
             Expression::Subquery(_) => true, // Subqueries typically need graph context
             Expression::ExistsSubquery(_) => true, // EXISTS subqueries need graph context
             Expression::NotExistsSubquery(_) => true, // NOT EXISTS subqueries need graph context
+            Expression::CountSubquery(_) => true, // COUNT subqueries need graph context
+            Expression::CollectSubquery(_) => true, // COLLECT subqueries need graph context
             Expression::InSubquery(in_subquery) => {
                 // Check left expression, subquery itself assumed to need context
                 self.expression_needs_graph_context(&in_subquery.expression)
@@ -3056,7 +3488,7 @@ impl QueryExecutor {
         // If there's a YIELD clause, filter the columns
         if let Some(yield_clause) = &call_stmt.yield_clause {
             for row in &mut result.rows {
-                let mut filtered_values = std::collections::HashMap::new();
+                let mut filtered_values = indexmap::IndexMap::new();
 
                 for yield_item in &yield_clause.items {
                     let column_name = &yield_item.column_name;
@@ -3163,7 +3595,8 @@ impl QueryExecutor {
             crate::ast::MatchClause {
                 patterns: vec![crate::ast::PathPattern {
                     assignment: None, // No path assignment
-                    path_type: None,  // Default path type
+                    search: None,
+                    path_type: None, // Default path type
                     elements: vec![crate::ast::PatternElement::Node(crate::ast::Node {
                         identifier: Some("n".to_string()),
                         labels: vec![],
@@ -3172,6 +3605,7 @@ impl QueryExecutor {
                     })],
                     location: crate::ast::Location::default(),
                 }],
+                optional: false,
                 location: crate::ast::Location::default(),
             }
         });
@@ -3274,6 +3708,7 @@ impl QueryExecutor {
             rows_affected: 1,
             session_result: None,
             warnings: Vec::new(),
+            operator_timings: None,
         })
     }
 
@@ -3308,6 +3743,9 @@ impl QueryExecutor {
             CatalogStatement::ClearGraph(_) => {
                 Err(ExecutionError::UnsupportedOperator("CLEAR GRAPH is now handled by dedicated executor structs via DDLStatementCoordinator".to_string()))
             },
+            CatalogStatement::AlterGraph(_) => {
+                Err(ExecutionError::UnsupportedOperator("ALTER GRAPH is now handled by dedicated executor structs via DDLStatementCoordinator".to_string()))
+            },
             CatalogStatement::CreateGraphType(_) => {
                 Err(ExecutionError::UnsupportedOperator("CREATE GRAPH TYPE is now handled by dedicated executor structs via DDLStatementCoordinator".to_string()))
             },
@@ -3359,6 +3797,7 @@ impl QueryExecutor {
                     )]))],
                     variables: vec!["status".to_string()],
                     execution_time_ms: execution_time,
+                    operator_timings: None,
                 };
                 Ok(result)
             }
@@ -3409,6 +3848,7 @@ impl QueryExecutor {
                     rows,
                     variables,
                     execution_time_ms: execution_time,
+                    operator_timings: None,
                 };
 
                 Ok(query_result)
@@ -3423,6 +3863,16 @@ impl QueryExecutor {
         &self,
         node: &PhysicalNode,
         context: &mut ExecutionContext,
+    ) -> Result<Vec<Row>, ExecutionError> {
+        Self::timed(node, context, |context| {
+            self.execute_operator_without_graph(node, context)
+        })
+    }
+
+    fn execute_operator_without_graph(
+        &self,
+        node: &PhysicalNode,
+        context: &mut ExecutionContext,
     ) -> Result<Vec<Row>, ExecutionError> {
         match node {
             // Graph-independent operations that work with scalar values
@@ -3451,6 +3901,56 @@ impl QueryExecutor {
         node: &PhysicalNode,
         context: &mut ExecutionContext,
         graph: &Arc<GraphCache>,
+    ) -> Result<Vec<Row>, ExecutionError> {
+        Self::timed(node, context, |context| {
+            let Some(guard) = context.query_guard.clone() else {
+                return self.execute_operator_with_graph(node, context, graph);
+            };
+
+            guard.check_deadline()?;
+            let charged_before = guard.charged_bytes();
+            let rows = self.execute_operator_with_graph(node, context, graph)?;
+            guard.check_deadline()?;
+            guard.charge_rows(charged_before, &rows)?;
+            Ok(rows)
+        })
+    }
+
+    /// Run one operator, recording its time and row count when the context has a
+    /// timer, and reporting it as running in `gql.active_queries`
+    fn timed(
+        node: &PhysicalNode,
+        context: &mut ExecutionContext,
+        run: impl FnOnce(&mut ExecutionContext) -> Result<Vec<Row>, ExecutionError>,
+    ) -> Result<Vec<Row>, ExecutionError> {
+        let operators = context.active_operators.clone();
+        if let Some(operators) = &operators {
+            operators.enter(node.operator_name());
+        }
+
+        let result = match context.operator_timer.clone() {
+            None => run(context),
+            Some(timer) => {
+                timer.enter();
+                let started = std::time::Instant::now();
+                let result = run(context);
+                let rows = result.as_ref().map_or(0, Vec::len);
+                timer.exit(node.operator_name(), rows, started.elapsed());
+                result
+            }
+        };
+
+        if let Some(operators) = &operators {
+            operators.exit();
+        }
+        result
+    }
+
+    fn execute_operator_with_graph(
+        &self,
+        node: &PhysicalNode,
+        context: &mut ExecutionContext,
+        graph: &Arc<GraphCache>,
     ) -> Result<Vec<Row>, ExecutionError> {
         match node {
             PhysicalNode::NodeSeqScan {
@@ -3466,6 +3966,35 @@ impl QueryExecutor {
                 graph,
             ),
 
+            PhysicalNode::EdgeIndexScan {
+                from_variable,
+                from_labels,
+                from_properties,
+                edge_variable,
+                edge_label,
+                to_variable,
+                direction,
+                predicates,
+                fallback,
+                ..
+            } => {
+                let Some(edges) = self.select_edges_by_predicates(edge_label, predicates, graph)
+                else {
+                    // No relationship index covers the predicates
+                    return self.execute_node_with_graph(fallback, context, graph);
+                };
+                self.execute_edge_index_scan_with_graph(
+                    from_variable,
+                    from_labels,
+                    from_properties.as_ref(),
+                    edge_variable,
+                    to_variable,
+                    direction,
+                    edges,
+                    graph,
+                )
+            }
+
             PhysicalNode::Filter {
                 condition, input, ..
             } => {
@@ -3499,23 +4028,37 @@ impl QueryExecutor {
 
             PhysicalNode::HashAggregate {
                 group_by,
+                grouping_sets,
                 aggregates,
                 input,
                 ..
             } => {
                 log::debug!("EXECUTING HashAggregate NODE");
                 let input_rows = self.execute_node_with_graph(input, context, graph)?;
-                self.execute_hash_aggregate(group_by, aggregates, input_rows, context)
+                self.execute_hash_aggregate(
+                    group_by,
+                    grouping_sets.as_deref(),
+                    aggregates,
+                    input_rows,
+                    context,
+                )
             }
 
             PhysicalNode::SortAggregate {
                 group_by,
+                grouping_sets,
                 aggregates,
                 input,
                 ..
             } => {
                 let input_rows = self.execute_node_with_graph(input, context, graph)?;
-                self.execute_sort_aggregate(group_by, aggregates, input_rows, context)
+                self.execute_sort_aggregate(
+                    group_by,
+                    grouping_sets.as_deref(),
+                    aggregates,
+                    input_rows,
+                    context,
+                )
             }
 
             PhysicalNode::Limit {
@@ -3552,18 +4095,37 @@ impl QueryExecutor {
                 )
             }
 
-            PhysicalNode::IndexedExpand {
+            PhysicalNode::AdjacencyCount {
                 from_variable,
-                edge_variable,
-                to_variable,
                 edge_labels,
                 direction,
-                properties,
+                count_variable,
                 input,
                 ..
             } => {
                 let input_rows = self.execute_node_with_graph(input, context, graph)?;
-                self.execute_indexed_expand_with_graph(
+                self.execute_adjacency_count_with_graph(
+                    from_variable,
+                    edge_labels,
+                    direction,
+                    count_variable,
+                    input_rows,
+                    graph,
+                )
+            }
+
+            PhysicalNode::IndexedExpand {
+                from_variable,
+                edge_variable,
+                to_variable,
+                edge_labels,
+                direction,
+                properties,
+                input,
+                ..
+            } => {
+                let input_rows = self.execute_node_with_graph(input, context, graph)?;
+                self.execute_indexed_expand_with_graph(
                     from_variable,
                     edge_variable.as_deref(),
                     to_variable,
@@ -3616,6 +4178,74 @@ impl QueryExecutor {
                 )
             }
 
+            PhysicalNode::VariableLengthExpand {
+                from_variable,
+                edge_variable,
+                to_variable,
+                path_variable,
+                edge_labels,
+                direction,
+                edge_properties,
+                to_labels,
+                min_hops,
+                max_hops,
+                input,
+                ..
+            } => {
+                let input_rows = self.execute_node_with_graph(input, context, graph)?;
+                let hop = HopPattern {
+                    edge_labels,
+                    direction,
+                    min_hops: *min_hops,
+                    max_hops: *max_hops,
+                };
+                self.execute_variable_length_expand_with_graph(
+                    from_variable,
+                    edge_variable.as_deref(),
+                    to_variable,
+                    path_variable.as_deref(),
+                    &hop,
+                    edge_properties.as_ref(),
+                    to_labels,
+                    input_rows,
+                    graph,
+                )
+            }
+
+            PhysicalNode::ShortestPath {
+                from_variable,
+                edge_variable,
+                to_variable,
+                path_variable,
+                edge_labels,
+                direction,
+                edge_properties,
+                min_hops,
+                max_hops,
+                all,
+                input,
+                ..
+            } => {
+                let input_rows = self.execute_node_with_graph(input, context, graph)?;
+                let hop = HopPattern {
+                    edge_labels,
+                    direction,
+                    min_hops: *min_hops,
+                    max_hops: *max_hops,
+                };
+                self.execute_shortest_path_with_graph(
+                    from_variable,
+                    edge_variable.as_deref(),
+                    to_variable,
+                    path_variable.as_deref(),
+                    &hop,
+                    edge_properties.as_ref(),
+                    *all,
+                    input_rows,
+                    graph,
+                )
+            }
+
             PhysicalNode::WithQuery { original_query, .. } => {
                 // Execute WITH query using the specialized WITH query execution logic
                 let query_result = self.execute_with_query_with_context(original_query, context)?;
@@ -3994,6 +4624,148 @@ impl QueryExecutor {
         Ok(rows)
     }
 
+    /// Pick the edges selected by the most selective indexed predicate
+    ///
+    /// Lower and upper bounds on the same property are combined into one range, and
+    /// each indexed range is costed by the number of edges it selects. Returns `None`
+    /// if no predicate is backed by a relationship property index.
+    fn select_edges_by_predicates<'g>(
+        &self,
+        edge_label: &str,
+        predicates: &[EdgePropertyPredicate],
+        graph: &'g GraphCache,
+    ) -> Option<Vec<&'g crate::storage::Edge>> {
+        use crate::ast::Operator;
+
+        let mut ranges: Vec<(&EdgePropertyPredicate, Bound<Value>, Bound<Value>)> = Vec::new();
+        for predicate in predicates {
+            let value = self.literal_to_value(&predicate.value);
+            let (lower, upper) = match predicate.operator {
+                Operator::Equal => (Bound::Included(value.clone()), Bound::Included(value)),
+                Operator::LessThan => (Bound::Unbounded, Bound::Excluded(value)),
+                Operator::LessEqual => (Bound::Unbounded, Bound::Included(value)),
+                Operator::GreaterThan => (Bound::Excluded(value), Bound::Unbounded),
+                Operator::GreaterEqual => (Bound::Included(value), Bound::Unbounded),
+                _ => continue,
+            };
+
+            match ranges.iter_mut().find(|(existing, _, _)| {
+                existing.property == predicate.property && existing.path == predicate.path
+            }) {
+                // Fill in an open side; a second bound on the same side is left to the filter
+                Some((_, existing_lower, existing_upper)) => {
+                    if matches!(existing_lower, Bound::Unbounded) {
+                        *existing_lower = lower;
+                    }
+                    if matches!(existing_upper, Bound::Unbounded) {
+                        *existing_upper = upper;
+                    }
+                }
+                None => ranges.push((predicate, lower, upper)),
+            }
+        }
+
+        let (predicate, lower, upper) = ranges
+            .iter()
+            .filter_map(|(predicate, lower, upper)| {
+                graph
+                    .estimate_edges_by_property_range(
+                        edge_label,
+                        &predicate.property,
+                        predicate.path.as_ref(),
+                        lower.as_ref(),
+                        upper.as_ref(),
+                    )
+                    .map(|count| (count, predicate, lower, upper))
+            })
+            .min_by_key(|(count, ..)| *count)
+            .map(|(_, predicate, lower, upper)| (predicate, lower, upper))?;
+
+        log::debug!(
+            "Driving '{}' relationships by indexed property '{}'",
+            edge_label,
+            predicate.property
+        );
+        graph.get_edges_by_property_range(
+            edge_label,
+            &predicate.property,
+            predicate.path.as_ref(),
+            lower.as_ref(),
+            upper.as_ref(),
+        )
+    }
+
+    /// Bind the endpoints of index-selected edges, producing the same rows as a
+    /// node scan followed by an expand
+    #[allow(clippy::too_many_arguments)]
+    fn execute_edge_index_scan_with_graph(
+        &self,
+        from_variable: &str,
+        from_labels: &[String],
+        from_properties: Option<&HashMap<String, Expression>>,
+        edge_variable: &str,
+        to_variable: &str,
+        direction: &EdgeDirection,
+        edges: Vec<&crate::storage::Edge>,
+        graph: &Arc<GraphCache>,
+    ) -> Result<Vec<Row>, ExecutionError> {
+        fn bind_node(row: &mut Row, variable: &str, node: &crate::storage::Node) {
+            let node_value = Value::Node(node.clone());
+            row.set_value(variable.to_string(), node_value.clone());
+            row.with_entity(variable, &node_value);
+            row.set_value(format!("{}.id", variable), Value::String(node.id.clone()));
+            for (prop_name, prop_value) in &node.properties {
+                row.set_value(format!("{}.{}", variable, prop_name), prop_value.clone());
+            }
+        }
+
+        let mut rows = Vec::new();
+
+        for edge in edges {
+            let endpoints: &[(&String, &String)] = match direction {
+                EdgeDirection::Outgoing => &[(&edge.from_node, &edge.to_node)],
+                EdgeDirection::Incoming => &[(&edge.to_node, &edge.from_node)],
+                EdgeDirection::Both | EdgeDirection::Undirected => &[
+                    (&edge.from_node, &edge.to_node),
+                    (&edge.to_node, &edge.from_node),
+                ],
+            };
+
+            for (start_id, end_id) in endpoints {
+                let (Some(start), Some(end)) = (graph.get_node(start_id), graph.get_node(end_id))
+                else {
+                    continue;
+                };
+                if !from_labels.iter().all(|label| start.labels.contains(label)) {
+                    continue;
+                }
+                if let Some(property_filters) = from_properties {
+                    if !self.node_matches_properties(start, property_filters)? {
+                        continue;
+                    }
+                }
+
+                let mut row = Row::new();
+                bind_node(&mut row, from_variable, start);
+
+                let edge_value = Value::Edge(edge.clone());
+                row.set_value(edge_variable.to_string(), edge_value.clone());
+                row.with_entity(edge_variable, &edge_value);
+                for (prop_name, prop_value) in &edge.properties {
+                    row.set_value(
+                        format!("{}.{}", edge_variable, prop_name),
+                        prop_value.clone(),
+                    );
+                }
+
+                bind_node(&mut row, to_variable, end);
+                rows.push(row);
+            }
+        }
+
+        Ok(rows)
+    }
+
     /// Execute a filter operation
     fn execute_filter(
         &self,
@@ -4026,31 +4798,22 @@ impl QueryExecutor {
         Ok(filtered_rows)
     }
 
-    /// Execute a HAVING clause filter
-    /// For now, use the same logic as regular filter but with better error handling
+    /// Execute a HAVING filter over aggregated rows
+    ///
+    /// The planner has replaced every aggregate in the condition with the
+    /// column of the input row that holds it, so the condition is evaluated
+    /// against each row's columns. A NULL result drops the row. The hidden
+    /// aggregate columns computed only for HAVING are removed from the rows
+    /// that pass.
     fn execute_having(
         &self,
         condition: &Expression,
         input_rows: Vec<Row>,
         context: &mut ExecutionContext,
-    ) -> Result<Vec<Row>, ExecutionError> {
-        // The key insight: HAVING should work on aggregated results
-        // The issue is that count(*) tries to find a "*" variable, but in HAVING context
-        // we should return 1 for count(*) since we're dealing with single aggregated rows
-
-        self.execute_filter_with_having_support(condition, input_rows, context)
-    }
-
-    /// Execute filter with special HAVING support for aggregate functions
-    fn execute_filter_with_having_support(
-        &self,
-        condition: &Expression,
-        input_rows: Vec<Row>,
-        context: &mut ExecutionContext,
     ) -> Result<Vec<Row>, ExecutionError> {
         let mut filtered_rows = Vec::new();
 
-        for row in input_rows {
+        for mut row in input_rows {
             // Clear local variables from previous row to prevent variable leakage
             context.clear_locals();
 
@@ -4059,16 +4822,10 @@ impl QueryExecutor {
                 context.set_variable(name.clone(), value.clone());
             }
 
-            // Debug: print what columns are available in this row (uncomment for debugging)
-            // println!("HAVING DEBUG: Row contains columns: {:?}", row.values.keys().collect::<Vec<_>>());
-            // for (name, value) in &row.values {
-            //     println!("  {} = {:?}", name, value);
-            // }
-
-            // Don't modify the * variable - let the HAVING condition evaluation handle aggregate substitution
-
-            // Evaluate the condition
-            if self.evaluate_having_condition(condition, &row, context)? {
+            let result = self.evaluate_expression(condition, context)?;
+            if result.as_boolean().unwrap_or(false) {
+                row.values
+                    .retain(|name, _| !name.starts_with(HAVING_AGGREGATE_PREFIX));
                 filtered_rows.push(row);
             }
         }
@@ -4076,63 +4833,6 @@ impl QueryExecutor {
         Ok(filtered_rows)
     }
 
-    /// Evaluate HAVING condition with special handling for count(*)
-    fn evaluate_having_condition(
-        &self,
-        condition: &Expression,
-        row: &Row,
-        context: &mut ExecutionContext,
-    ) -> Result<bool, ExecutionError> {
-        // Special handling for binary expressions that contain count(*)
-        match condition {
-            Expression::Binary(binary_expr) => {
-                // Check if left side is count(*)
-                if let Expression::FunctionCall(func_call) = binary_expr.left.as_ref() {
-                    if func_call.name.to_uppercase() == "COUNT"
-                        && func_call.arguments.len() == 1
-                        && matches!(func_call.arguments[0], Expression::Variable(ref var) if var.name == "*")
-                    {
-                        // Use the computed account_count value instead of evaluating count(*)
-                        let left_value =
-                            if let Some(account_count) = row.values.get("account_count") {
-                                account_count.clone()
-                            } else {
-                                Value::Number(0.0) // Default fallback
-                            };
-
-                        let _right_value = self.evaluate_expression(&binary_expr.right, context)?;
-
-                        // Create a temporary variable with the computed account_count value
-                        let temp_var_name = "__temp_count__".to_string();
-                        context.set_variable(temp_var_name.clone(), left_value.clone());
-
-                        // Create a temporary expression that uses our computed value instead of count(*)
-                        let temp_condition = Expression::Binary(crate::ast::BinaryExpression {
-                            left: Box::new(Expression::Variable(Variable {
-                                name: temp_var_name,
-                                location: Location::default(),
-                            })),
-                            operator: binary_expr.operator.clone(),
-                            right: binary_expr.right.clone(),
-                            location: Location::default(),
-                        });
-
-                        let result = self.evaluate_expression(&temp_condition, context)?;
-                        return Ok(result.as_boolean().unwrap_or(false));
-                    }
-                }
-                // For other binary expressions, evaluate normally
-                let result = self.evaluate_expression(condition, context)?;
-                Ok(result.as_boolean().unwrap_or(false))
-            }
-            _ => {
-                // For non-binary expressions, evaluate normally
-                let result = self.evaluate_expression(condition, context)?;
-                Ok(result.as_boolean().unwrap_or(false))
-            }
-        }
-    }
-
     /// Execute a projection operation
     fn execute_project(
         &self,
@@ -4450,6 +5150,22 @@ impl QueryExecutor {
                 Ok((Value::Boolean(has_results), GqlType::Boolean))
             }
 
+            Expression::CountSubquery(count_subquery_expr) => {
+                let count = self.evaluate_count_subquery(count_subquery_expr, context)?;
+                Ok((count, GqlType::BigInt))
+            }
+
+            Expression::CollectSubquery(collect_subquery_expr) => {
+                let list = self.evaluate_collect_subquery(collect_subquery_expr, context)?;
+                Ok((
+                    list,
+                    GqlType::List {
+                        element_type: Box::new(GqlType::String { max_length: None }),
+                        max_length: None,
+                    },
+                ))
+            }
+
             Expression::NotExistsSubquery(not_exists_subquery_expr) => {
                 // NOT EXISTS optimized: returns true if no results exist
                 let has_results =
@@ -4599,13 +5315,16 @@ impl QueryExecutor {
                     return Ok(value);
                 }
 
-                // If not found, try to access property from the node variable directly
-                if let Some(crate::storage::Value::Node(node)) =
-                    context.get_variable(&prop_access.object).as_ref()
+                // If not found, try to access property from the node or edge variable directly
+                let properties = match context.get_variable(&prop_access.object) {
+                    Some(Value::Node(node)) => Some(node.properties),
+                    Some(Value::Edge(edge)) => Some(edge.properties),
+                    _ => None,
+                };
+                if let Some(prop_value) =
+                    properties.and_then(|mut properties| properties.remove(&prop_access.property))
                 {
-                    if let Some(prop_value) = node.properties.get(&prop_access.property) {
-                        return Ok(prop_value.clone());
-                    }
+                    return Ok(prop_value);
                 }
 
                 // Return NULL if property doesn't exist (SQL standard behavior)
@@ -4685,6 +5404,14 @@ impl QueryExecutor {
                 Ok(Value::Boolean(has_results))
             }
 
+            Expression::CountSubquery(count_subquery_expr) => {
+                self.evaluate_count_subquery(count_subquery_expr, context)
+            }
+
+            Expression::CollectSubquery(collect_subquery_expr) => {
+                self.evaluate_collect_subquery(collect_subquery_expr, context)
+            }
+
             Expression::NotExistsSubquery(not_exists_subquery_expr) => {
                 // NOT EXISTS optimized: returns true if no results exist
                 let has_results =
@@ -5610,6 +6337,11 @@ impl QueryExecutor {
         func_call: &FunctionCall,
         context: &ExecutionContext,
     ) -> Result<Value, ExecutionError> {
+        // RESOLVE works on the reference property itself, not only on its value
+        if func_call.name.eq_ignore_ascii_case("RESOLVE") {
+            return self.resolve_reference(func_call, context);
+        }
+
         // Check if this is a system procedure call
         if is_system_procedure(&func_call.name) {
             // Evaluate arguments
@@ -5672,6 +6404,99 @@ impl QueryExecutor {
         Ok(result)
     }
 
+    /// Evaluate `RESOLVE(n.ref)`: the node the reference property `ref` refers to
+    ///
+    /// The graph type of the current graph names the node type and key property
+    /// the reference points at. The node is looked up in the graph's key index,
+    /// or found by scanning the nodes of that type when the graph has none.
+    /// Returns NULL when the property is NULL or no node holds the key.
+    fn resolve_reference(
+        &self,
+        func_call: &FunctionCall,
+        context: &ExecutionContext,
+    ) -> Result<Value, ExecutionError> {
+        let [reference_expr @ Expression::PropertyAccess(reference)] =
+            func_call.arguments.as_slice()
+        else {
+            return Err(ExecutionError::ExpressionError(
+                "RESOLVE expects a reference property, as in RESOLVE(n.owner)".to_string(),
+            ));
+        };
+
+        let labels = match context.get_variable(&reference.object) {
+            Some(Value::Node(node)) => node.labels,
+            Some(Value::Null) => return Ok(Value::Null),
+            _ => {
+                return Err(ExecutionError::ExpressionError(format!(
+                    "RESOLVE({}.{}) needs '{}' to be a node",
+                    reference.object, reference.property, reference.object
+                )))
+            }
+        };
+        let key = self.evaluate_expression(reference_expr, context)?;
+        if key.is_null() {
+            return Ok(Value::Null);
+        }
+
+        let graph_path = context.get_current_graph_name().ok_or_else(|| {
+            ExecutionError::RuntimeError("RESOLVE needs a current graph".to_string())
+        })?;
+        let graph_type = self
+            .catalog_manager
+            .read()
+            .ok()
+            .and_then(|catalog| catalog.graph_type_of(&graph_path))
+            .ok_or_else(|| {
+                ExecutionError::ExpressionError(format!(
+                    "RESOLVE needs a typed graph, but graph '{}' has no graph type",
+                    graph_path
+                ))
+            })?;
+        let (label, key_property) = graph_type
+            .node_reference(&labels, &reference.property)
+            .ok_or_else(|| {
+                ExecutionError::ExpressionError(format!(
+                    "Property '{}' of '{}' is not a reference property in graph type '{}'",
+                    reference.property, reference.object, graph_type.name
+                ))
+            })?;
+
+        let graph = match &context.current_graph {
+            Some(graph) => graph.clone(),
+            None => Arc::new(
+                self.storage
+                    .get_graph(&graph_path)
+                    .map_err(|e| ExecutionError::StorageError(e.to_string()))?
+                    .ok_or_else(|| {
+                        ExecutionError::StorageError(format!("Graph '{}' not found", graph_path))
+                    })?,
+            ),
+        };
+        let nodes = graph
+            .get_nodes_by_key(label, key_property, &key)
+            .unwrap_or_else(|| {
+                graph
+                    .get_nodes_by_label(label)
+                    .into_iter()
+                    .filter(|node| node.properties.get(key_property) == Some(&key))
+                    .collect()
+            });
+
+        match nodes.as_slice() {
+            [] => Ok(Value::Null),
+            [node] => Ok(Value::Node((*node).clone())),
+            _ => Err(ExecutionError::ExpressionError(format!(
+                "RESOLVE({}.{}) is ambiguous: {} :{} nodes have {} = {}",
+                reference.object,
+                reference.property,
+                nodes.len(),
+                label,
+                key_property,
+                key
+            ))),
+        }
+    }
+
     /// Execute a generic function
     fn execute_generic_function(
         &self,
@@ -5717,28 +6542,35 @@ impl QueryExecutor {
     fn execute_hash_aggregate(
         &self,
         group_by: &[Expression],
+        grouping_sets: Option<&[Vec<usize>]>,
         aggregates: &[crate::plan::physical::AggregateItem],
         input_rows: Vec<Row>,
         context: &mut ExecutionContext,
     ) -> Result<Vec<Row>, ExecutionError> {
-        self.execute_aggregate(group_by, aggregates, input_rows, context)
+        self.execute_aggregate(group_by, grouping_sets, aggregates, input_rows, context)
     }
 
     /// Execute sort aggregation
     fn execute_sort_aggregate(
         &self,
         group_by: &[Expression],
+        grouping_sets: Option<&[Vec<usize>]>,
         aggregates: &[crate::plan::physical::AggregateItem],
         input_rows: Vec<Row>,
         context: &mut ExecutionContext,
     ) -> Result<Vec<Row>, ExecutionError> {
-        self.execute_aggregate(group_by, aggregates, input_rows, context)
+        self.execute_aggregate(group_by, grouping_sets, aggregates, input_rows, context)
     }
 
     /// Common aggregation logic
+    ///
+    /// Produces one row per group of each grouping set. Keys left out of a
+    /// grouping set are NULL in its rows; without grouping sets the rows are
+    /// grouped by all of `group_by`.
     fn execute_aggregate(
         &self,
         group_by: &[Expression],
+        grouping_sets: Option<&[Vec<usize>]>,
         aggregates: &[crate::plan::physical::AggregateItem],
         input_rows: Vec<Row>,
         context: &mut ExecutionContext,
@@ -5757,10 +6589,8 @@ impl QueryExecutor {
             );
         }
 
-        // Group rows by the group_by expressions
-        let mut groups: HashMap<String, Vec<Row>> = HashMap::new();
-        let mut group_key_to_values: HashMap<String, Vec<Value>> = HashMap::new();
-
+        // Evaluate the group_by expressions once per row
+        let mut keyed_rows = Vec::with_capacity(input_rows.len());
         for row in input_rows {
             // Clear local variables from previous row to prevent variable leakage
             context.clear_locals();
@@ -5770,9 +6600,7 @@ impl QueryExecutor {
                 context.set_variable(name.clone(), value.clone());
             }
 
-            // Create group key from group_by expressions
-            let mut group_key_values = Vec::new();
-            let mut group_key_strings = Vec::new();
+            let mut key_values = Vec::with_capacity(group_by.len());
             for expr in group_by {
                 let value = self.evaluate_expression(expr, context)?;
                 log::debug!(
@@ -5780,158 +6608,185 @@ impl QueryExecutor {
                     expr,
                     value
                 );
-                group_key_values.push(value.clone());
-                group_key_strings.push(value.to_string());
+                key_values.push(value);
             }
-            let group_key = group_key_strings.join("|");
-            log::debug!("AGGREGATE DEBUG: Group key: '{}'", group_key);
-
-            // Store the mapping from key to actual values for later use
-            group_key_to_values.insert(group_key.clone(), group_key_values);
-
-            // Add row to appropriate group
-            groups.entry(group_key).or_default().push(row);
+            keyed_rows.push((key_values, row));
         }
 
+        let all_keys: Vec<usize> = (0..group_by.len()).collect();
+        let grouping_sets = grouping_sets.unwrap_or(std::slice::from_ref(&all_keys));
+
         // Process each group
         let mut result_rows = Vec::new();
 
-        // Debug: Show what groups were created
-        log::debug!("AGGREGATE DEBUG: Created {} groups", groups.len());
-        for (key, rows) in &groups {
-            log::debug!("  Group '{}': {} rows", key, rows.len());
-        }
+        for grouping_set in grouping_sets {
+            // Groups in order of first appearance. Keys use the Debug form of each
+            // value so values of different types never share a group.
+            let mut group_indexes: HashMap<Vec<String>, usize> = HashMap::new();
+            let mut groups: Vec<(Vec<Value>, Vec<Row>)> = Vec::new();
+            for (key_values, row) in &keyed_rows {
+                let values: Vec<Value> = grouping_set
+                    .iter()
+                    .map(|index| key_values[*index].clone())
+                    .collect();
+                let group_key = values.iter().map(|value| format!("{:?}", value)).collect();
+                let index = *group_indexes.entry(group_key).or_insert_with(|| {
+                    groups.push((values, Vec::new()));
+                    groups.len() - 1
+                });
+                groups[index].1.push(row.clone());
+            }
 
-        // Special case: if no groups and no GROUP BY expressions, create a single group for aggregation
-        if groups.is_empty() && group_by.is_empty() {
-            // Create empty group for pure aggregation (like COUNT(*) with no matching rows)
-            groups.insert("".to_string(), Vec::new());
-        }
+            // Debug: Show what groups were created
+            log::debug!(
+                "AGGREGATE DEBUG: Created {} groups for grouping set {:?}",
+                groups.len(),
+                grouping_set
+            );
 
-        for (group_key, group_rows) in groups {
-            let mut result_row = Row::new();
+            // Special case: without grouping keys, aggregate even an empty input
+            // (like COUNT(*) with no matching rows) into a single group
+            if groups.is_empty() && grouping_set.is_empty() {
+                groups.push((Vec::new(), Vec::new()));
+            }
 
-            // Create maps to store computed values
-            let mut group_by_values = HashMap::new();
-            let mut aggregate_values = HashMap::new();
+            for (set_values, group_rows) in groups {
+                let mut result_row = Row::new();
 
-            // Compute group-by values using the preserved value types
-            if let Some(actual_values) = group_key_to_values.get(&group_key) {
+                // Create maps to store computed values
+                let mut group_by_values = HashMap::new();
+                let mut aggregate_values = HashMap::new();
+
+                // Keys outside this grouping set are NULL
                 for (i, expr) in group_by.iter().enumerate() {
                     let column_name = self.expression_to_string(expr);
-                    if let Some(value) = actual_values.get(i) {
-                        group_by_values.insert(column_name, value.clone());
-                    }
+                    let value = grouping_set
+                        .iter()
+                        .position(|index| *index == i)
+                        .map_or(Value::Null, |position| set_values[position].clone());
+                    group_by_values.insert(column_name, value);
                 }
-            }
 
-            // Process aggregates for this group using the function registry
-            for aggregate in aggregates {
-                let function_name = match &aggregate.function {
-                    crate::plan::logical::AggregateFunction::Count => "COUNT",
-                    crate::plan::logical::AggregateFunction::Sum => "SUM",
-                    crate::plan::logical::AggregateFunction::Avg => "AVERAGE",
-                    crate::plan::logical::AggregateFunction::Min => "MIN",
-                    crate::plan::logical::AggregateFunction::Max => "MAX",
-                    crate::plan::logical::AggregateFunction::Collect => "COLLECT",
-                };
+                // Process aggregates for this group using the function registry
+                for aggregate in aggregates {
+                    let function_name = match &aggregate.function {
+                        crate::plan::logical::AggregateFunction::Count => "COUNT",
+                        crate::plan::logical::AggregateFunction::Sum => "SUM",
+                        crate::plan::logical::AggregateFunction::Avg => "AVERAGE",
+                        crate::plan::logical::AggregateFunction::Min => "MIN",
+                        crate::plan::logical::AggregateFunction::Max => "MAX",
+                        crate::plan::logical::AggregateFunction::Collect => "COLLECT",
+                    };
 
-                // Evaluate the aggregate expression arguments
-                let mut evaluated_args = Vec::new();
+                    // Evaluate the aggregate expression arguments
+                    let mut evaluated_args = Vec::new();
 
-                // For aggregate functions, we need to pass the column/property reference
-                if let Some(function) = self.function_registry.get(function_name) {
-                    // Debug: Show what expression type we're handling (commented out for production)
-                    // println!("AGGREGATE DEBUG: Processing {} with expression: {:?}", function_name, aggregate.expression);
-
-                    // Handle different argument types
-                    match &aggregate.expression {
-                        Expression::PropertyAccess(prop) => {
-                            // Pass the full property path (e.g., "e.salary")
-                            let full_property = format!("{}.{}", prop.object, prop.property);
-                            evaluated_args.push(Value::String(full_property));
-                        }
-                        Expression::Variable(var) => {
-                            // For aggregate functions over variables, pass the variable name as string
-                            // so the function can process it across all rows in the group
-                            evaluated_args.push(Value::String(var.name.clone()));
-                        }
-                        Expression::Literal(crate::ast::Literal::Integer(1)) => {
-                            // COUNT(*) case - pass a dummy value
-                            evaluated_args.push(Value::String("*".to_string()));
-                        }
-                        _ => {
-                            // For other expression types, evaluate them using the existing context
-                            let value = self.evaluate_expression(&aggregate.expression, context)?;
-                            evaluated_args.push(value);
+                    // For aggregate functions, we need to pass the column/property reference
+                    if let Some(function) = self.function_registry.get(function_name) {
+                        // Debug: Show what expression type we're handling (commented out for production)
+                        // println!("AGGREGATE DEBUG: Processing {} with expression: {:?}", function_name, aggregate.expression);
+
+                        // Handle different argument types
+                        match &aggregate.expression {
+                            Expression::PropertyAccess(prop) => {
+                                // Pass the full property path (e.g., "e.salary")
+                                let full_property = format!("{}.{}", prop.object, prop.property);
+                                evaluated_args.push(Value::String(full_property));
+                            }
+                            Expression::Variable(var) => {
+                                // For aggregate functions over variables, pass the variable name as string
+                                // so the function can process it across all rows in the group
+                                evaluated_args.push(Value::String(var.name.clone()));
+                            }
+                            Expression::Literal(crate::ast::Literal::Integer(1)) => {
+                                // COUNT(*) case - pass a dummy value
+                                evaluated_args.push(Value::String("*".to_string()));
+                            }
+                            _ => {
+                                // For other expression types, evaluate them using the existing context
+                                let value =
+                                    self.evaluate_expression(&aggregate.expression, context)?;
+                                evaluated_args.push(value);
+                            }
                         }
-                    }
 
-                    // Create function context for this group with storage access
-                    let function_context = FunctionContext::with_storage(
-                        group_rows.clone(),
-                        context.variables.clone(),
-                        evaluated_args.clone(),
-                        context.storage_manager.clone(),
-                        context.current_graph.clone(),
-                        context.get_current_graph_name(),
-                    );
+                        // Ordered aggregates see the rows of the group in their ORDER BY order
+                        let rows = match &aggregate.order_clause {
+                            Some(order_clause) => {
+                                self.sort_aggregate_rows(order_clause, group_rows.clone(), context)?
+                            }
+                            None => group_rows.clone(),
+                        };
 
-                    // Debug: Show what we're passing to the function (commented out for production)
-                    // println!("AGGREGATE DEBUG: Calling {} with {} rows and args: {:?}", function_name, group_rows.len(), evaluated_args);
+                        // Create function context for this group with storage access
+                        let function_context = FunctionContext::with_storage(
+                            rows,
+                            context.variables.clone(),
+                            evaluated_args.clone(),
+                            context.storage_manager.clone(),
+                            context.current_graph.clone(),
+                            context.get_current_graph_name(),
+                        );
 
-                    let result = function.execute(&function_context).map_err(|e| {
-                        ExecutionError::UnsupportedOperator(format!(
-                            "Aggregate function error: {}",
-                            e
-                        ))
-                    })?;
+                        // Debug: Show what we're passing to the function (commented out for production)
+                        // println!("AGGREGATE DEBUG: Calling {} with {} rows and args: {:?}", function_name, group_rows.len(), evaluated_args);
 
-                    // Debug: Show the result (commented out for production)
-                    // println!("AGGREGATE DEBUG: {} returned: {:?}", function_name, result);
+                        let result = function.execute(&function_context).map_err(|e| {
+                            ExecutionError::UnsupportedOperator(format!(
+                                "Aggregate function error: {}",
+                                e
+                            ))
+                        })?;
+
+                        let result =
+                            Self::limit_aggregate_list(aggregate.limit_clause.as_ref(), result);
+
+                        // Debug: Show the result (commented out for production)
+                        // println!("AGGREGATE DEBUG: {} returned: {:?}", function_name, result);
+
+                        let column_name = aggregate.alias.clone().unwrap_or_else(|| {
+                            format!(
+                                "{}_{}",
+                                function_name,
+                                self.expression_to_string(&aggregate.expression)
+                            )
+                        });
+                        aggregate_values.insert(column_name, result);
+                    }
+                }
+
+                // Add group key values to result
+                for expr in group_by.iter() {
+                    let column_name = self.expression_to_string(expr);
+                    if let Some(value) = group_by_values.get(&column_name) {
+                        result_row.values.insert(column_name, value.clone());
+                    }
+                }
 
+                // Add aggregate values to result
+                for aggregate in aggregates {
                     let column_name = aggregate.alias.clone().unwrap_or_else(|| {
+                        let function_name = match &aggregate.function {
+                            crate::plan::logical::AggregateFunction::Count => "COUNT",
+                            crate::plan::logical::AggregateFunction::Sum => "SUM",
+                            crate::plan::logical::AggregateFunction::Avg => "AVERAGE",
+                            crate::plan::logical::AggregateFunction::Min => "MIN",
+                            crate::plan::logical::AggregateFunction::Max => "MAX",
+                            crate::plan::logical::AggregateFunction::Collect => "COLLECT",
+                        };
                         format!(
                             "{}_{}",
                             function_name,
                             self.expression_to_string(&aggregate.expression)
                         )
                     });
-                    aggregate_values.insert(column_name, result);
-                }
-            }
-
-            // Add group key values to result
-            for expr in group_by.iter() {
-                let column_name = self.expression_to_string(expr);
-                if let Some(value) = group_by_values.get(&column_name) {
-                    result_row.values.insert(column_name, value.clone());
+                    if let Some(value) = aggregate_values.get(&column_name) {
+                        result_row.values.insert(column_name, value.clone());
+                    }
                 }
-            }
 
-            // Add aggregate values to result
-            for aggregate in aggregates {
-                let column_name = aggregate.alias.clone().unwrap_or_else(|| {
-                    let function_name = match &aggregate.function {
-                        crate::plan::logical::AggregateFunction::Count => "COUNT",
-                        crate::plan::logical::AggregateFunction::Sum => "SUM",
-                        crate::plan::logical::AggregateFunction::Avg => "AVERAGE",
-                        crate::plan::logical::AggregateFunction::Min => "MIN",
-                        crate::plan::logical::AggregateFunction::Max => "MAX",
-                        crate::plan::logical::AggregateFunction::Collect => "COLLECT",
-                    };
-                    format!(
-                        "{}_{}",
-                        function_name,
-                        self.expression_to_string(&aggregate.expression)
-                    )
-                });
-                if let Some(value) = aggregate_values.get(&column_name) {
-                    result_row.values.insert(column_name, value.clone());
-                }
+                result_rows.push(result_row);
             }
-
-            result_rows.push(result_row);
         }
 
         Ok(result_rows)
@@ -6145,6 +7000,15 @@ impl QueryExecutor {
         &self,
         node: &crate::storage::Node,
         property_filters: &HashMap<String, Expression>,
+    ) -> Result<bool, ExecutionError> {
+        self.properties_match(&node.properties, property_filters)
+    }
+
+    /// Check if an element's properties match the property filters of its pattern
+    fn properties_match(
+        &self,
+        properties: &HashMap<String, Value>,
+        property_filters: &HashMap<String, Expression>,
     ) -> Result<bool, ExecutionError> {
         for (prop_name, expected_expr) in property_filters {
             // Evaluate the expected value expression
@@ -6161,15 +7025,15 @@ impl QueryExecutor {
                 }
             };
 
-            // Check if the node has this property with the expected value
-            match node.properties.get(prop_name) {
+            // Check if the element has this property with the expected value
+            match properties.get(prop_name) {
                 Some(actual_value) => {
                     if actual_value != &expected_value {
                         return Ok(false);
                     }
                 }
                 None => {
-                    // Node doesn't have this property
+                    // Element doesn't have this property
                     return Ok(false);
                 }
             }
@@ -6208,19 +7072,25 @@ impl QueryExecutor {
 
             {
                 // Find edges based on direction
+                // Incoming edges come from the reverse adjacency index, already
+                // restricted to the requested labels
                 let edges = match direction {
                     EdgeDirection::Outgoing => graph.get_outgoing_edges(from_id),
-                    EdgeDirection::Incoming => graph.get_incoming_edges(from_id),
+                    EdgeDirection::Incoming => {
+                        graph.get_incoming_edges_with_labels(from_id, edge_labels)
+                    }
                     EdgeDirection::Both => {
                         // Handle both directions - this is the key fix!
                         let mut both_edges = graph.get_outgoing_edges(from_id);
-                        both_edges.extend(graph.get_incoming_edges(from_id));
+                        both_edges
+                            .extend(graph.get_incoming_edges_with_labels(from_id, edge_labels));
                         both_edges
                     }
                     EdgeDirection::Undirected => {
                         // For undirected, treat as both directions
                         let mut undirected_edges = graph.get_outgoing_edges(from_id);
-                        undirected_edges.extend(graph.get_incoming_edges(from_id));
+                        undirected_edges
+                            .extend(graph.get_incoming_edges_with_labels(from_id, edge_labels));
                         undirected_edges
                     }
                 };
@@ -6285,27 +7155,280 @@ impl QueryExecutor {
                             // IMPORTANT: Track the target node entity for identity-based set operations
                             result_row.with_entity(to_variable, &to_node_value);
 
-                            // Add the node ID as a special .id property
-                            let id_property_name = format!("{}.id", to_variable);
-                            result_row
-                                .set_value(id_property_name, Value::String(to_node.id.clone()));
+                            // Add the node ID as a special .id property
+                            let id_property_name = format!("{}.id", to_variable);
+                            result_row
+                                .set_value(id_property_name, Value::String(to_node.id.clone()));
+
+                            // Add target node properties
+                            for (prop_name, prop_value) in &to_node.properties {
+                                let qualified_prop = format!("{}.{}", to_variable, prop_name);
+                                result_row.set_value(qualified_prop, prop_value.clone());
+                            }
+
+                            result_rows.push(result_row);
+                        }
+                    }
+                }
+            } // Close the block introduced by the node ID extraction fix
+        }
+
+        Ok(result_rows)
+    }
+
+    /// Execute a quantified expand, binding one row per walk of the allowed length
+    ///
+    /// The edge variable is bound to the list of edges walked and the path
+    /// variable to the path up to the target, continuing a path bound by an
+    /// earlier hop of the same pattern.
+    #[allow(clippy::too_many_arguments)]
+    fn execute_variable_length_expand_with_graph(
+        &self,
+        from_variable: &str,
+        edge_variable: Option<&str>,
+        to_variable: &str,
+        path_variable: Option<&str>,
+        hop: &HopPattern<'_>,
+        edge_properties: Option<&HashMap<String, Expression>>,
+        to_labels: &[String],
+        input_rows: Vec<Row>,
+        graph: &Arc<GraphCache>,
+    ) -> Result<Vec<Row>, ExecutionError> {
+        let mut result_rows = Vec::new();
+
+        for input_row in input_rows.iter() {
+            let from_id = match input_row.get_value(from_variable) {
+                Some(Value::String(id)) => id.as_str(),
+                Some(Value::Node(node)) => node.id.as_str(),
+                Some(_) => continue,
+                None => {
+                    return Err(ExecutionError::RuntimeError(format!(
+                        "Variable not found: {}",
+                        from_variable
+                    )))
+                }
+            };
+            // Walks borrow their start from the graph, not from the row
+            let Some(start) = graph.get_node(from_id) else {
+                continue;
+            };
+            // A target bound earlier in the pattern, as in (a)-[*]->(a)
+            let bound_to = match input_row.get_value(to_variable) {
+                Some(Value::String(id)) => Some(id.as_str()),
+                Some(Value::Node(node)) => Some(node.id.as_str()),
+                _ => None,
+            };
+
+            let mut edge_error = None;
+            let walks = path_search::variable_length_walks(graph, &start.id, hop, |edge| {
+                match edge_properties {
+                    Some(filters) => match self.properties_match(&edge.properties, filters) {
+                        Ok(matches) => matches,
+                        Err(e) => {
+                            edge_error.get_or_insert(e);
+                            false
+                        }
+                    },
+                    None => true,
+                }
+            });
+            if let Some(e) = edge_error {
+                return Err(e);
+            }
+
+            for walk in walks {
+                if bound_to.is_some_and(|id| id != walk.end()) {
+                    continue;
+                }
+                let Some(to_node) = graph.get_node(walk.end()) else {
+                    continue;
+                };
+                if !to_labels.iter().all(|label| to_node.labels.contains(label)) {
+                    continue;
+                }
+
+                let mut result_row = input_row.clone();
+
+                if let Some(edge_var) = edge_variable {
+                    let edges = walk
+                        .edges
+                        .iter()
+                        .map(|edge| Value::Edge((*edge).clone()))
+                        .collect();
+                    result_row.set_value(edge_var.to_string(), Value::List(edges));
+                }
+
+                let to_node_value = Value::Node(to_node.clone());
+                result_row.set_value(to_variable.to_string(), to_node_value.clone());
+                result_row.with_entity(to_variable, &to_node_value);
+                result_row.set_value(
+                    format!("{}.id", to_variable),
+                    Value::String(to_node.id.clone()),
+                );
+                for (prop_name, prop_value) in &to_node.properties {
+                    result_row
+                        .set_value(format!("{}.{}", to_variable, prop_name), prop_value.clone());
+                }
+
+                if let Some(path_var) = path_variable {
+                    let mut path = match input_row.get_value(path_var) {
+                        Some(Value::Path(path))
+                            if path.get_nodes().last() == Some(&walk.nodes[0]) =>
+                        {
+                            path.clone()
+                        }
+                        _ => PathValue::new(),
+                    };
+                    walk.extend_path(&mut path);
+                    result_row.set_value(path_var.to_string(), Value::Path(path));
+                }
+
+                result_rows.push(result_row);
+            }
+        }
+
+        Ok(result_rows)
+    }
+
+    /// Execute a shortest path search, binding one row per shortest path found
+    /// between the end points of each input row
+    #[allow(clippy::too_many_arguments)]
+    fn execute_shortest_path_with_graph(
+        &self,
+        from_variable: &str,
+        edge_variable: Option<&str>,
+        to_variable: &str,
+        path_variable: Option<&str>,
+        hop: &HopPattern<'_>,
+        edge_properties: Option<&HashMap<String, Expression>>,
+        all: bool,
+        input_rows: Vec<Row>,
+        graph: &Arc<GraphCache>,
+    ) -> Result<Vec<Row>, ExecutionError> {
+        let node_id = |row: &Row, variable: &str| match row.get_value(variable) {
+            Some(Value::String(id)) => Ok(Some(id.clone())),
+            Some(Value::Node(node)) => Ok(Some(node.id.clone())),
+            Some(_) => Ok(None),
+            None => Err(ExecutionError::RuntimeError(format!(
+                "Variable not found: {}",
+                variable
+            ))),
+        };
 
-                            // Add target node properties
-                            for (prop_name, prop_value) in &to_node.properties {
-                                let qualified_prop = format!("{}.{}", to_variable, prop_name);
-                                result_row.set_value(qualified_prop, prop_value.clone());
-                            }
+        let mut result_rows = Vec::new();
+        for input_row in input_rows.iter() {
+            let (Some(from_id), Some(to_id)) = (
+                node_id(input_row, from_variable)?,
+                node_id(input_row, to_variable)?,
+            ) else {
+                continue;
+            };
+            // Searches borrow their end points from the graph, not from the row
+            let (Some(start), Some(end)) = (graph.get_node(&from_id), graph.get_node(&to_id))
+            else {
+                continue;
+            };
 
-                            result_rows.push(result_row);
+            let mut edge_error = None;
+            let walks = path_search::shortest_walks(graph, &start.id, &end.id, hop, all, |edge| {
+                match edge_properties {
+                    Some(filters) => match self.properties_match(&edge.properties, filters) {
+                        Ok(matches) => matches,
+                        Err(e) => {
+                            edge_error.get_or_insert(e);
+                            false
                         }
-                    }
+                    },
+                    None => true,
                 }
-            } // Close the block introduced by the node ID extraction fix
+            });
+            if let Some(e) = edge_error {
+                return Err(e);
+            }
+
+            for walk in walks {
+                let mut result_row = input_row.clone();
+                if let Some(edge_var) = edge_variable {
+                    let edges = walk
+                        .edges
+                        .iter()
+                        .map(|edge| Value::Edge((*edge).clone()))
+                        .collect();
+                    result_row.set_value(edge_var.to_string(), Value::List(edges));
+                }
+                if let Some(path_var) = path_variable {
+                    let mut path = PathValue::new();
+                    walk.extend_path(&mut path);
+                    result_row.set_value(path_var.to_string(), Value::Path(path));
+                }
+                result_rows.push(result_row);
+            }
         }
 
         Ok(result_rows)
     }
 
+    /// Attach relationship counts from the adjacency indexes to each input row
+    ///
+    /// Rows whose node has no matching relationship are dropped, as the expand this
+    /// replaces would have produced no rows for them.
+    fn execute_adjacency_count_with_graph(
+        &self,
+        from_variable: &str,
+        edge_labels: &[String],
+        direction: &EdgeDirection,
+        count_variable: &str,
+        input_rows: Vec<Row>,
+        graph: &Arc<GraphCache>,
+    ) -> Result<Vec<Row>, ExecutionError> {
+        let mut result_rows = Vec::new();
+        for mut row in input_rows {
+            let from_id = match row.get_value(from_variable) {
+                Some(Value::String(id)) => id.clone(),
+                Some(Value::Node(node)) => node.id.clone(),
+                Some(_) => continue,
+                None => {
+                    return Err(ExecutionError::RuntimeError(format!(
+                        "Variable not found: {}",
+                        from_variable
+                    )))
+                }
+            };
+            let count = Self::relationship_count(graph, &from_id, edge_labels, direction);
+            if count > 0 {
+                row.set_value(count_variable.to_string(), Value::Number(count as f64));
+                result_rows.push(row);
+            }
+        }
+        Ok(result_rows)
+    }
+
+    /// Number of relationships an expand from `node_id` would follow
+    ///
+    /// Both and undirected expansions follow outgoing and incoming edges, so a
+    /// self-loop counts twice there as well.
+    fn relationship_count(
+        graph: &GraphCache,
+        node_id: &str,
+        edge_labels: &[String],
+        direction: &EdgeDirection,
+    ) -> usize {
+        let count = |degree: &dyn Fn(Option<&str>) -> usize| {
+            if edge_labels.is_empty() {
+                degree(None)
+            } else {
+                edge_labels.iter().map(|label| degree(Some(label))).sum()
+            }
+        };
+        let outgoing = || count(&|label| graph.out_degree(node_id, label));
+        let incoming = || count(&|label| graph.in_degree(node_id, label));
+        match direction {
+            EdgeDirection::Outgoing => outgoing(),
+            EdgeDirection::Incoming => incoming(),
+            EdgeDirection::Both | EdgeDirection::Undirected => outgoing() + incoming(),
+        }
+    }
+
     /// Execute an indexed expand operation with specific graph
     fn execute_indexed_expand_with_graph(
         &self,
@@ -6590,7 +7713,9 @@ impl QueryExecutor {
                 // Get edges based on direction
                 let edges = match element.direction {
                     EdgeDirection::Outgoing => graph.get_outgoing_edges(current_node_id),
-                    EdgeDirection::Incoming => graph.get_incoming_edges(current_node_id),
+                    EdgeDirection::Incoming => {
+                        graph.get_incoming_edges_with_labels(current_node_id, &element.edge_labels)
+                    }
                     EdgeDirection::Both | EdgeDirection::Undirected => {
                         graph.get_connected_edges(current_node_id)
                     }
@@ -6674,7 +7799,7 @@ impl QueryExecutor {
     fn eval_order_by_expression(
         &self,
         expr: &Expression,
-        row_values: &std::collections::HashMap<String, Value>,
+        row_values: &indexmap::IndexMap<String, Value>,
         base_context: &ExecutionContext,
         prop_access: Option<&PropertyAccess>,
     ) -> Result<Value, ExecutionError> {
@@ -6726,7 +7851,7 @@ impl QueryExecutor {
 
                 match (val_a, val_b) {
                     (Ok(a_val), Ok(b_val)) => {
-                        let cmp = self.compare_values(&a_val, &b_val, sort_item.nulls_first);
+                        let cmp = Self::compare_values(&a_val, &b_val, sort_item.nulls_first);
                         match cmp {
                             Some(std::cmp::Ordering::Equal) => continue, // Try next sort key
                             Some(ordering) => {
@@ -6748,9 +7873,43 @@ impl QueryExecutor {
         Ok(input_rows)
     }
 
-    /// Compare two values for sorting with NULLS ordering support
-    fn compare_values(
+    /// Sort the rows of a group for an ordered aggregate, such as
+    /// `COLLECT(x ORDER BY x.date DESC)`
+    fn sort_aggregate_rows(
         &self,
+        order_clause: &OrderClause,
+        rows: Vec<Row>,
+        context: &ExecutionContext,
+    ) -> Result<Vec<Row>, ExecutionError> {
+        let sort_items: Vec<SortItem> = order_clause
+            .items
+            .iter()
+            .map(|item| SortItem {
+                expression: item.expression.clone(),
+                ascending: item.direction == OrderDirection::Ascending,
+                nulls_first: item.nulls_ordering == Some(NullsOrdering::First),
+            })
+            .collect();
+        self.execute_in_memory_sort(&sort_items, rows, context)
+    }
+
+    /// Keep the values of an aggregated list selected by the aggregate's LIMIT
+    /// and OFFSET, as in `COLLECT(x ORDER BY x.date DESC LIMIT 5)`
+    fn limit_aggregate_list(limit_clause: Option<&LimitClause>, value: Value) -> Value {
+        match (limit_clause, value) {
+            (Some(limit), Value::List(values)) => Value::List(
+                values
+                    .into_iter()
+                    .skip(limit.offset.unwrap_or(0))
+                    .take(limit.count)
+                    .collect(),
+            ),
+            (_, value) => value,
+        }
+    }
+
+    /// Compare two values for sorting with NULLS ordering support
+    pub(crate) fn compare_values(
         a: &Value,
         b: &Value,
         nulls_first: bool,
@@ -6823,6 +7982,7 @@ impl QueryExecutor {
     fn execute_session_statement(
         &self,
         stmt: &SessionStatement,
+        session: Option<&Arc<std::sync::RwLock<UserSession>>>,
     ) -> Result<QueryResult, ExecutionError> {
         use crate::ast::SessionSetClause;
         use crate::exec::result::SessionResult;
@@ -6889,8 +8049,38 @@ impl QueryExecutor {
                     }
                 }
             }
-            SessionStatement::Reset(_) => Ok(QueryResult::for_session(SessionResult::Reset)),
+            SessionStatement::Reset(reset_stmt) => {
+                Ok(QueryResult::for_session(SessionResult::Reset {
+                    args: reset_stmt.args.clone(),
+                }))
+            }
             SessionStatement::Close(_) => Ok(QueryResult::for_session(SessionResult::Close)),
+            SessionStatement::ShowCurrentGraph(_) => {
+                // Read-only: report the context queries will resolve against
+                let (current_graph, current_schema) = match session {
+                    Some(session_lock) => {
+                        let session_guard = session_lock.read().map_err(|_| {
+                            ExecutionError::RuntimeError("Failed to read session state".to_string())
+                        })?;
+                        (
+                            session_guard.current_graph.clone(),
+                            session_guard.current_schema.clone(),
+                        )
+                    }
+                    None => (None, None),
+                };
+
+                let variables = vec!["current_graph".to_string(), "current_schema".to_string()];
+                let values = vec![
+                    current_graph.map(Value::String).unwrap_or(Value::Null),
+                    current_schema.map(Value::String).unwrap_or(Value::Null),
+                ];
+
+                let mut result = QueryResult::new();
+                result.rows.push(Row::from_positional(values, &variables));
+                result.variables = variables;
+                Ok(result)
+            }
         }
     }
 
@@ -6982,23 +8172,13 @@ impl QueryExecutor {
                     ExecutionError::PlanningError(format!("Failed to plan query: {}", e))
                 })?;
 
-                // Execute the plan - get default graph if needed
-                let graph_names = self.storage.get_graph_names().map_err(|e| {
-                    ExecutionError::RuntimeError(format!("Failed to get graph names: {}", e))
-                })?;
-                let first_graph_name = graph_names.first().ok_or_else(|| {
+                // Execute against the graph resolved for the enclosing statement
+                let graph_arc = context.current_graph.clone().ok_or_else(|| {
                     ExecutionError::RuntimeError(
-                        "No graphs available for query execution".to_string(),
+                        "No graph context available. Use SESSION SET GRAPH or specify FROM clause."
+                            .to_string(),
                     )
                 })?;
-                let graph = self
-                    .storage
-                    .get_graph(first_graph_name)
-                    .map_err(|e| {
-                        ExecutionError::RuntimeError(format!("Failed to get graph: {}", e))
-                    })?
-                    .ok_or_else(|| ExecutionError::RuntimeError("Graph not found".to_string()))?;
-                let graph_arc = Arc::new(graph);
                 self.execute_with_graph(&plan, &graph_arc, context)
             }
             crate::ast::Query::SetOperation(set_op) => self.execute_set_operation(set_op, context),
@@ -7751,6 +8931,7 @@ impl QueryExecutor {
             rows_affected,
             session_result: None,
             warnings: Vec::new(),
+            operator_timings: None,
         })
     }
 
@@ -7896,6 +9077,7 @@ impl QueryExecutor {
             rows_affected,
             session_result: None,
             warnings: Vec::new(),
+            operator_timings: None,
         })
     }
 
@@ -8012,6 +9194,7 @@ impl QueryExecutor {
             rows_affected,
             session_result: None,
             warnings: Vec::new(),
+            operator_timings: None,
         })
     }
 
@@ -8094,6 +9277,7 @@ impl QueryExecutor {
             Value::Node(_) => GqlType::String { max_length: None }, // Nodes are complex objects, use String for now
             Value::Edge(_) => GqlType::String { max_length: None }, // Edges are complex objects, use String for now
             Value::Temporal(_) => GqlType::String { max_length: None }, // Temporal values are complex, use String for now
+            Value::Json(_) => GqlType::Json,
         }
     }
 
@@ -8594,6 +9778,7 @@ impl QueryExecutor {
             warnings: Vec::new(),
 
             execution_time_ms: 0,
+            operator_timings: None,
         })
     }
 
@@ -8625,6 +9810,7 @@ impl QueryExecutor {
                     warnings: Vec::new(),
 
                     execution_time_ms: 0,
+                    operator_timings: None,
                 })
             }
         }
@@ -8688,6 +9874,7 @@ impl QueryExecutor {
                                     warnings: Vec::new(),
 
                                     execution_time_ms: 0,
+                                    operator_timings: None,
                                 };
                                 results.push(result.clone());
                                 last_result = Some(result);
@@ -9062,6 +10249,8 @@ impl QueryExecutor {
             Expression::PathConstructor(_) => false,
             Expression::Subquery(_) => false,
             Expression::ExistsSubquery(_) => false,
+            Expression::CountSubquery(_) => false,
+            Expression::CollectSubquery(_) => false,
             Expression::NotExistsSubquery(_) => false,
             Expression::InSubquery(_) => false,
             Expression::NotInSubquery(_) => false,
@@ -9318,8 +10507,15 @@ impl QueryExecutor {
                     variables.push(self.expression_to_string(expr));
                 }
 
-                // Add aggregate columns
+                // Add aggregate columns, except those computed only for HAVING
                 for aggregate in aggregates {
+                    if aggregate
+                        .alias
+                        .as_deref()
+                        .is_some_and(|alias| alias.starts_with(HAVING_AGGREGATE_PREFIX))
+                    {
+                        continue;
+                    }
                     let column_name = aggregate.alias.clone().unwrap_or_else(|| {
                         let function_name = match &aggregate.function {
                             crate::plan::logical::AggregateFunction::Count => "COUNT",
@@ -9431,6 +10627,487 @@ impl QueryExecutor {
         Ok(Value::Boolean(pattern_matches))
     }
 
+    /// Evaluate COUNT { ... } for the current row
+    ///
+    /// A single relationship from a bound node whose far end is unconstrained is
+    /// counted from the adjacency indexes; any other body is matched outwards from
+    /// the nodes the outer row binds by `match_pattern_subquery`.
+    fn evaluate_count_subquery(
+        &self,
+        count_expr: &crate::ast::CountSubqueryExpression,
+        context: &ExecutionContext,
+    ) -> Result<Value, ExecutionError> {
+        let subquery = &count_expr.subquery;
+        if let Some(count) = self.count_relationships_from_index(subquery, context)? {
+            return Ok(Value::Number(count as f64));
+        }
+
+        let matches = self.match_pattern_subquery(subquery, context)?;
+        let count = match &subquery.return_clause {
+            Some(return_clause) if return_clause.distinct == DistinctQualifier::Distinct => {
+                let expressions: Vec<&Expression> = return_clause
+                    .items
+                    .iter()
+                    .map(|item| &item.expression)
+                    .collect();
+                let mut distinct_rows: Vec<Vec<Value>> = Vec::new();
+                for row in self.evaluate_subquery_rows(&expressions, &matches, context)? {
+                    if !distinct_rows.contains(&row) {
+                        distinct_rows.push(row);
+                    }
+                }
+                distinct_rows.len()
+            }
+            _ => matches.len(),
+        };
+        Ok(Value::Number(count as f64))
+    }
+
+    /// Evaluate COLLECT { ... RETURN expression } for the current row
+    fn evaluate_collect_subquery(
+        &self,
+        collect_expr: &crate::ast::CollectSubqueryExpression,
+        context: &ExecutionContext,
+    ) -> Result<Value, ExecutionError> {
+        let subquery = &collect_expr.subquery;
+        let return_clause = subquery
+            .return_clause
+            .as_ref()
+            .filter(|return_clause| return_clause.items.len() == 1)
+            .ok_or_else(|| {
+                ExecutionError::ExpressionError(
+                    "COLLECT { } must end with a RETURN of exactly one expression".to_string(),
+                )
+            })?;
+
+        let matches = self.match_pattern_subquery(subquery, context)?;
+        let rows =
+            self.evaluate_subquery_rows(&[&return_clause.items[0].expression], &matches, context)?;
+        let mut values = Vec::with_capacity(rows.len());
+        for value in rows.into_iter().flatten() {
+            if return_clause.distinct != DistinctQualifier::Distinct || !values.contains(&value) {
+                values.push(value);
+            }
+        }
+        Ok(Value::List(values))
+    }
+
+    /// Evaluate `expressions` once per subquery match, with the match's bindings in scope
+    fn evaluate_subquery_rows(
+        &self,
+        expressions: &[&Expression],
+        matches: &[HashMap<String, Value>],
+        context: &ExecutionContext,
+    ) -> Result<Vec<Vec<Value>>, ExecutionError> {
+        let mut scope = context.clone();
+        let mut rows = Vec::with_capacity(matches.len());
+        for bindings in matches {
+            for (name, value) in bindings {
+                scope.set_variable(name.clone(), value.clone());
+            }
+            rows.push(
+                expressions
+                    .iter()
+                    .map(|expression| self.evaluate_expression(expression, &scope))
+                    .collect::<Result<Vec<_>, _>>()?,
+            );
+        }
+        Ok(rows)
+    }
+
+    /// Answer COUNT { (n)-[:TYPE]->() } from the adjacency indexes
+    ///
+    /// Returns `None` when the body is anything other than one relationship from a
+    /// bound node to an unconstrained node, with no WHERE or RETURN DISTINCT.
+    fn count_relationships_from_index(
+        &self,
+        subquery: &crate::ast::PatternSubquery,
+        context: &ExecutionContext,
+    ) -> Result<Option<usize>, ExecutionError> {
+        use crate::ast::PatternElement;
+
+        let [pattern] = subquery.patterns.as_slice() else {
+            return Ok(None);
+        };
+        let [PatternElement::Node(left), PatternElement::Edge(edge), PatternElement::Node(right)] =
+            pattern.elements.as_slice()
+        else {
+            return Ok(None);
+        };
+        if subquery.where_clause.is_some()
+            || subquery
+                .return_clause
+                .as_ref()
+                .is_some_and(|r| r.distinct == DistinctQualifier::Distinct)
+            || pattern.assignment.is_some()
+            || edge.properties.is_some()
+            || edge.quantifier.is_some()
+            || left.properties.is_some()
+            || right.properties.is_some()
+        {
+            return Ok(None);
+        }
+
+        let bound_node = |pattern_node: &crate::ast::Node| {
+            pattern_node
+                .identifier
+                .as_ref()
+                .and_then(|name| match context.get_variable(name) {
+                    Some(Value::Node(node)) => Some(node),
+                    _ => None,
+                })
+        };
+        let is_free = |pattern_node: &crate::ast::Node| {
+            pattern_node.labels.is_empty()
+                && pattern_node
+                    .identifier
+                    .as_ref()
+                    .is_none_or(|name| context.get_variable(name).is_none())
+        };
+        // Count from the bound end, reversing the direction when that is the right node
+        let (anchor, anchor_pattern, direction) = match (bound_node(left), bound_node(right)) {
+            (Some(node), None) if is_free(right) => (node, left, edge.direction.clone()),
+            (None, Some(node)) if is_free(left) => {
+                let direction = match edge.direction {
+                    EdgeDirection::Outgoing => EdgeDirection::Incoming,
+                    EdgeDirection::Incoming => EdgeDirection::Outgoing,
+                    ref other => other.clone(),
+                };
+                (node, right, direction)
+            }
+            _ => return Ok(None),
+        };
+        if edge
+            .identifier
+            .as_ref()
+            .is_some_and(|name| context.get_variable(name).is_some())
+        {
+            return Ok(None);
+        }
+
+        let graph = context.current_graph.as_ref().ok_or_else(|| {
+            ExecutionError::RuntimeError(
+                "No graph context available for COUNT subquery".to_string(),
+            )
+        })?;
+        if !anchor_pattern
+            .labels
+            .iter()
+            .all(|label| anchor.labels.contains(label))
+        {
+            return Ok(Some(0));
+        }
+        Ok(Some(Self::relationship_count(
+            graph,
+            &anchor.id,
+            &edge.labels,
+            &direction,
+        )))
+    }
+
+    /// Find the matches of a COUNT { } or COLLECT { } body for the current row
+    ///
+    /// Each match holds the variables the body binds beyond the outer row.
+    /// Patterns are matched from a node the outer row or an earlier pattern
+    /// binds, so correlated bodies only visit that node's neighbourhood; a
+    /// pattern without a bound node starts from a label scan.
+    fn match_pattern_subquery(
+        &self,
+        subquery: &crate::ast::PatternSubquery,
+        context: &ExecutionContext,
+    ) -> Result<Vec<HashMap<String, Value>>, ExecutionError> {
+        let graph = context.current_graph.as_ref().ok_or_else(|| {
+            ExecutionError::RuntimeError("No graph context available for subquery".to_string())
+        })?;
+
+        let mut matches = vec![HashMap::new()];
+        for pattern in &subquery.patterns {
+            let mut extended = Vec::new();
+            for bindings in &mut matches {
+                self.match_subquery_pattern(graph, pattern, bindings, context, &mut extended)?;
+            }
+            matches = extended;
+        }
+
+        if let Some(where_clause) = &subquery.where_clause {
+            let mut scope = context.clone();
+            let mut kept = Vec::with_capacity(matches.len());
+            for bindings in matches {
+                for (name, value) in &bindings {
+                    scope.set_variable(name.clone(), value.clone());
+                }
+                if matches!(
+                    self.evaluate_expression(&where_clause.condition, &scope)?,
+                    Value::Boolean(true)
+                ) {
+                    kept.push(bindings);
+                }
+            }
+            matches = kept;
+        }
+        Ok(matches)
+    }
+
+    /// Extend `bindings` with every match of one linear path pattern
+    fn match_subquery_pattern(
+        &self,
+        graph: &GraphCache,
+        pattern: &PathPattern,
+        bindings: &mut HashMap<String, Value>,
+        context: &ExecutionContext,
+        matches: &mut Vec<HashMap<String, Value>>,
+    ) -> Result<(), ExecutionError> {
+        use crate::ast::PatternElement;
+
+        let unsupported = |what: &str| {
+            ExecutionError::UnsupportedOperator(format!(
+                "COUNT {{ }} and COLLECT {{ }} do not support {}",
+                what
+            ))
+        };
+        if pattern.assignment.is_some() {
+            return Err(unsupported("path variables"));
+        }
+        let elements = &pattern.elements;
+        let well_formed = elements.len() % 2 == 1
+            && elements
+                .iter()
+                .enumerate()
+                .all(|(i, element)| match element {
+                    PatternElement::Node(_) => i % 2 == 0,
+                    PatternElement::Edge(edge) => i % 2 == 1 && edge.quantifier.is_none(),
+                });
+        if !well_formed {
+            return Err(unsupported("quantified or malformed patterns"));
+        }
+
+        // Start from a bound node when there is one, walking right and then left from it
+        let bound_node = |i: usize| match &elements[i] {
+            PatternElement::Node(node) => node
+                .identifier
+                .as_ref()
+                .and_then(|name| Self::subquery_binding(name, bindings, context))
+                .and_then(|value| match value {
+                    Value::Node(node) => Some(node.id),
+                    _ => None,
+                }),
+            PatternElement::Edge(_) => None,
+        };
+        let anchor = (0..elements.len())
+            .step_by(2)
+            .find(|&i| bound_node(i).is_some())
+            .unwrap_or(0);
+        let candidates: Vec<&crate::storage::Node> = match bound_node(anchor) {
+            Some(id) => graph.get_node(&id).into_iter().collect(),
+            None => match &elements[anchor] {
+                PatternElement::Node(node) => match node.labels.first() {
+                    Some(label) => graph.get_nodes_by_label(label),
+                    None => graph.get_all_nodes(),
+                },
+                PatternElement::Edge(_) => Vec::new(),
+            },
+        };
+        let steps: Vec<(usize, usize)> = (anchor + 1..elements.len())
+            .step_by(2)
+            .map(|edge| (edge, edge - 1))
+            .chain((1..anchor).rev().step_by(2).map(|edge| (edge, edge + 1)))
+            .collect();
+
+        let PatternElement::Node(anchor_pattern) = &elements[anchor] else {
+            return Ok(());
+        };
+        let mut node_ids = vec![None; elements.len()];
+        for node in candidates {
+            let Some(bound_name) =
+                self.bind_subquery_node(anchor_pattern, node, bindings, context)?
+            else {
+                continue;
+            };
+            node_ids[anchor] = Some(node.id.clone());
+            self.walk_subquery_pattern(
+                graph,
+                elements,
+                &steps,
+                &mut node_ids,
+                bindings,
+                context,
+                matches,
+            )?;
+            if let Some(name) = bound_name {
+                bindings.remove(&name);
+            }
+        }
+        Ok(())
+    }
+
+    /// Follow the remaining (edge, from node) steps of a pattern from already matched nodes
+    #[allow(clippy::too_many_arguments)]
+    fn walk_subquery_pattern(
+        &self,
+        graph: &GraphCache,
+        elements: &[crate::ast::PatternElement],
+        steps: &[(usize, usize)],
+        node_ids: &mut Vec<Option<String>>,
+        bindings: &mut HashMap<String, Value>,
+        context: &ExecutionContext,
+        matches: &mut Vec<HashMap<String, Value>>,
+    ) -> Result<(), ExecutionError> {
+        use crate::ast::PatternElement;
+
+        let Some((&(edge_index, from), rest)) = steps.split_first() else {
+            matches.push(bindings.clone());
+            return Ok(());
+        };
+        let to = 2 * edge_index - from;
+        let (PatternElement::Edge(edge_pattern), PatternElement::Node(node_pattern)) =
+            (&elements[edge_index], &elements[to])
+        else {
+            return Ok(());
+        };
+        let Some(from_id) = node_ids[from].clone() else {
+            return Ok(());
+        };
+
+        // Pattern directions are written left to right; walking leftwards flips them
+        let forward = to > from;
+        let (follow_outgoing, follow_incoming) = match (&edge_pattern.direction, forward) {
+            (EdgeDirection::Outgoing, true) | (EdgeDirection::Incoming, false) => (true, false),
+            (EdgeDirection::Incoming, true) | (EdgeDirection::Outgoing, false) => (false, true),
+            _ => (true, true),
+        };
+        let mut candidates: Vec<(&crate::storage::Edge, &str)> = Vec::new();
+        if follow_outgoing {
+            candidates.extend(
+                graph
+                    .get_outgoing_edges(&from_id)
+                    .into_iter()
+                    .map(|edge| (edge, edge.to_node.as_str())),
+            );
+        }
+        if follow_incoming {
+            candidates.extend(
+                graph
+                    .get_incoming_edges(&from_id)
+                    .into_iter()
+                    // A self-loop was already found as an outgoing edge
+                    .filter(|edge| !(follow_outgoing && edge.from_node == edge.to_node))
+                    .map(|edge| (edge, edge.from_node.as_str())),
+            );
+        }
+
+        for (edge, far_id) in candidates {
+            if !edge_pattern.labels.is_empty() && !edge_pattern.labels.contains(&edge.label) {
+                continue;
+            }
+            if !self.subquery_properties_match(
+                edge_pattern.properties.as_ref(),
+                &edge.properties,
+                context,
+            )? {
+                continue;
+            }
+            let Some(far_node) = graph.get_node(far_id) else {
+                continue;
+            };
+
+            let edge_binding = match &edge_pattern.identifier {
+                Some(name) => match Self::subquery_binding(name, bindings, context) {
+                    Some(Value::Edge(bound)) if bound.id == edge.id => None,
+                    Some(_) => continue,
+                    None => {
+                        bindings.insert(name.clone(), Value::Edge(edge.clone()));
+                        Some(name.clone())
+                    }
+                },
+                None => None,
+            };
+            let node_binding =
+                self.bind_subquery_node(node_pattern, far_node, bindings, context)?;
+            if let Some(node_binding) = node_binding {
+                node_ids[to] = Some(far_node.id.clone());
+                self.walk_subquery_pattern(
+                    graph, elements, rest, node_ids, bindings, context, matches,
+                )?;
+                node_ids[to] = None;
+                if let Some(name) = node_binding {
+                    bindings.remove(&name);
+                }
+            }
+            if let Some(name) = edge_binding {
+                bindings.remove(&name);
+            }
+        }
+        Ok(())
+    }
+
+    /// Check `node` against a node pattern and bind the pattern variable to it
+    ///
+    /// Returns `None` when the node does not match, otherwise the name of the
+    /// variable newly bound (to be removed by the caller when backtracking).
+    fn bind_subquery_node(
+        &self,
+        pattern: &crate::ast::Node,
+        node: &crate::storage::Node,
+        bindings: &mut HashMap<String, Value>,
+        context: &ExecutionContext,
+    ) -> Result<Option<Option<String>>, ExecutionError> {
+        if !pattern
+            .labels
+            .iter()
+            .all(|label| node.labels.contains(label))
+            || !self.subquery_properties_match(
+                pattern.properties.as_ref(),
+                &node.properties,
+                context,
+            )?
+        {
+            return Ok(None);
+        }
+        let Some(name) = &pattern.identifier else {
+            return Ok(Some(None));
+        };
+        match Self::subquery_binding(name, bindings, context) {
+            Some(Value::Node(bound)) if bound.id == node.id => Ok(Some(None)),
+            Some(_) => Ok(None),
+            None => {
+                bindings.insert(name.clone(), Value::Node(node.clone()));
+                Ok(Some(Some(name.clone())))
+            }
+        }
+    }
+
+    /// Value of a variable bound by the subquery so far or by the outer row
+    fn subquery_binding(
+        name: &str,
+        bindings: &HashMap<String, Value>,
+        context: &ExecutionContext,
+    ) -> Option<Value> {
+        bindings
+            .get(name)
+            .cloned()
+            .or_else(|| context.get_variable(name))
+    }
+
+    /// Check the `{key: value}` map of a subquery pattern element
+    fn subquery_properties_match(
+        &self,
+        expected: Option<&crate::ast::PropertyMap>,
+        actual: &HashMap<String, Value>,
+        context: &ExecutionContext,
+    ) -> Result<bool, ExecutionError> {
+        let Some(expected) = expected else {
+            return Ok(true);
+        };
+        for property in &expected.properties {
+            let expected_value = self.evaluate_expression(&property.value, context)?;
+            match actual.get(&property.key) {
+                Some(value) if self.values_equal(value, &expected_value)? => {}
+                _ => return Ok(false),
+            }
+        }
+        Ok(true)
+    }
+
     /// Execute UNWIND operation
     fn execute_unwind(
         &self,
@@ -0,0 +1,103 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! Per-query resource limits
+//!
+//! Embedders set `QueryLimits` on a `QueryCoordinator`; every query then runs with a
+//! `QueryGuard` in its execution context. The guard is checked between physical
+//! operators, so a query stops at the first operator boundary after its deadline
+//! passes or its intermediate results outgrow the memory budget.
+
+use super::error::ExecutionError;
+use super::memory_budget::MemoryBudget;
+use super::result::Row;
+use crate::storage::Value;
+use std::time::{Duration, Instant};
+
+/// Resource limits applied to every query on a coordinator
+///
+/// `None` leaves the corresponding resource unlimited.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct QueryLimits {
+    /// Fail queries that return more rows than this
+    pub max_result_rows: Option<usize>,
+    /// Fail queries whose intermediate results are estimated to exceed this many bytes
+    pub max_memory_bytes: Option<usize>,
+    /// Fail queries that run longer than this
+    pub query_timeout: Option<Duration>,
+}
+
+impl QueryLimits {
+    /// Whether no limit is set
+    pub fn is_unlimited(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Enforces `QueryLimits` for one query execution
+#[derive(Debug, Clone)]
+pub struct QueryGuard {
+    limits: QueryLimits,
+    deadline: Option<Instant>,
+    budget: Option<MemoryBudget>,
+}
+
+impl QueryGuard {
+    /// Start the clock for a query
+    pub fn start(limits: QueryLimits) -> Self {
+        Self {
+            limits,
+            deadline: limits.query_timeout.map(|timeout| Instant::now() + timeout),
+            budget: limits.max_memory_bytes.map(MemoryBudget::new),
+        }
+    }
+
+    /// Fail if the query has run past its timeout
+    pub fn check_deadline(&self) -> Result<(), ExecutionError> {
+        match (self.deadline, self.limits.query_timeout) {
+            (Some(deadline), Some(timeout)) if Instant::now() > deadline => {
+                Err(ExecutionError::QueryTimeout {
+                    timeout_ms: timeout.as_millis() as u64,
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Bytes currently charged against the memory budget
+    pub fn charged_bytes(&self) -> usize {
+        self.budget.as_ref().map_or(0, |budget| budget.allocated())
+    }
+
+    /// Replace the charges made since `charged_before` with the size of `rows`
+    ///
+    /// An operator's inputs are dropped once it has produced its output, so only the
+    /// output stays charged.
+    pub fn charge_rows(&self, charged_before: usize, rows: &[Row]) -> Result<(), ExecutionError> {
+        let Some(budget) = &self.budget else {
+            return Ok(());
+        };
+        budget.release(budget.allocated().saturating_sub(charged_before));
+        budget.allocate(rows.iter().map(estimated_row_bytes).sum())
+    }
+
+    /// Fail if a finished query returned too many rows
+    pub fn check_result_rows(&self, rows: usize) -> Result<(), ExecutionError> {
+        match self.limits.max_result_rows {
+            Some(limit) if rows > limit => Err(ExecutionError::ResultLimitExceeded { limit, rows }),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Shallow size estimate of a row; nested values are counted by their inline size
+fn estimated_row_bytes(row: &Row) -> usize {
+    let binding = std::mem::size_of::<String>() + std::mem::size_of::<Value>();
+    std::mem::size_of::<Row>()
+        + row
+            .values
+            .keys()
+            .map(|key| binding + key.len())
+            .sum::<usize>()
+        + row.positional_values.len() * std::mem::size_of::<Value>()
+}
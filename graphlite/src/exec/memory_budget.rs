@@ -12,9 +12,7 @@ use std::sync::Arc;
 
 /// Memory budget tracker for query execution
 ///
-/// **Planned Feature** - Memory limit enforcement for query execution
-/// See ROADMAP.md: "Memory Budget Management"
-/// Target: v0.2.0 (High Priority)
+/// Enforces `QueryLimits::max_memory_bytes` through `QueryGuard`.
 ///
 /// Tracks allocated memory during query execution and enforces limits.
 /// Provides early termination when memory budget is exceeded.
@@ -40,7 +38,6 @@ use std::sync::Arc;
 /// - Minimal overhead (~8 bytes per ExecutionContext)
 /// - Configurable limits per-session or per-query
 /// - Graceful degradation instead of hard crashes
-#[allow(dead_code)]
 #[derive(Clone)]
 pub struct MemoryBudget {
     /// Maximum allowed memory in bytes
@@ -73,7 +70,6 @@ impl MemoryBudget {
     /// - Development: 100MB (100 * 1024 * 1024)
     /// - Production: 1GB (1024 * 1024 * 1024)
     /// - High-memory queries: 5GB (5 * 1024 * 1024 * 1024)
-    #[allow(dead_code)] // ROADMAP v0.5.0 - Memory budget enforcement for resource control
     pub fn new(limit: usize) -> Self {
         Self {
             limit,
@@ -103,7 +99,6 @@ impl MemoryBudget {
     /// let row_size = std::mem::size_of::<Row>();
     /// budget.allocate(1000 * row_size)?;
     /// ```
-    #[allow(dead_code)] // ROADMAP v0.2.0 - Memory budget enforcement for large queries (see ROADMAP.md §1)
     pub fn allocate(&self, bytes: usize) -> Result<(), ExecutionError> {
         let current = self.allocated.fetch_add(bytes, Ordering::SeqCst);
         let new_total = current + bytes;
@@ -142,13 +137,11 @@ impl MemoryBudget {
     /// # Note
     /// It's the caller's responsibility to track how much was allocated.
     /// Releasing more than was allocated will underflow (saturating to 0).
-    #[allow(dead_code)] // ROADMAP v0.2.0 - Release allocated memory (see ROADMAP.md §1)
     pub fn release(&self, bytes: usize) {
         self.allocated.fetch_sub(bytes, Ordering::SeqCst);
     }
 
     /// Get currently allocated memory in bytes
-    #[allow(dead_code)] // ROADMAP v0.2.0 - Memory usage tracking (see ROADMAP.md §1)
     pub fn allocated(&self) -> usize {
         self.allocated.load(Ordering::SeqCst)
     }
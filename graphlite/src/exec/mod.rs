@@ -6,12 +6,20 @@
 //! This module provides the execution engine that takes physical query plans
 //! and executes them against graph storage to produce query results.
 
+pub mod active_queries;
+#[cfg(feature = "compression")]
+pub mod content_encoding;
 pub mod context;
 pub mod error;
 pub mod executor;
+pub mod limits;
 pub mod lock_tracker;
+pub mod number_format;
+pub mod path_search;
 pub mod result;
 pub mod row_iterator;
+pub mod temp_space;
+pub mod timing;
 pub mod write_stmt; // Phase 4: Week 6.5 - Memory Optimization
                     // Text search not supported in GraphLite
                     // pub mod text_search_iterator; // Phase 4: Week 6.5 - Lazy text search
@@ -29,9 +37,15 @@ pub use read_engine::processors::unwind_preprocessor;
 pub use read_engine::processors::with_clause_processor;
 
 // Re-export the main types for convenience
+pub use active_queries::ActiveQuery;
+#[cfg(feature = "compression")]
+pub use content_encoding::ContentEncoding;
 pub use context::ExecutionContext;
 pub use error::ExecutionError;
 pub use executor::{ExecutionRequest, QueryExecutor};
+pub use limits::QueryLimits;
+pub use number_format::{Notation, NumberFormat};
 pub use result::{QueryResult, Row, SessionResult};
+pub use timing::OperatorTiming;
 // Text search not supported in GraphLite
 // pub use text_search_iterator::TextSearchIterator;
@@ -0,0 +1,276 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! Number formatting for JSON output
+//!
+//! Every GraphLite number is an `f64`, which serde writes with a fractional
+//! part: an id of 493603 comes out as `493603.0`. `NumberFormat` serializes
+//! any value to JSON with whole numbers written as integers, other numbers
+//! rounded to a fixed number of decimal places, and the choice of plain or
+//! scientific notation. The CLI, `graphlite serve` and the FFI JSON functions
+//! all write results through it.
+
+use serde::Serialize;
+use serde_json::ser::{CompactFormatter, Formatter, PrettyFormatter};
+use std::fmt::{Display, LowerExp};
+use std::io;
+
+/// Largest whole number an `f64` holds exactly, together with all smaller ones
+const MAX_EXACT_INTEGER: f64 = 9_007_199_254_740_992.0;
+
+/// How numbers that are not written as integers are written
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Notation {
+    /// Shortest text that reads back as the same number, with an exponent for
+    /// very large and very small magnitudes (`1e21`, `1e-7`); fixed-point when
+    /// decimal places are set
+    #[default]
+    Auto,
+    /// Never an exponent (`0.0000001`)
+    Plain,
+    /// Always an exponent (`4.93603e5`)
+    Scientific,
+}
+
+impl std::str::FromStr for Notation {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "auto" => Ok(Notation::Auto),
+            "plain" => Ok(Notation::Plain),
+            "scientific" => Ok(Notation::Scientific),
+            _ => Err(format!(
+                "Invalid notation '{}'. Valid options: auto, plain, scientific",
+                s
+            )),
+        }
+    }
+}
+
+/// How numbers are written when values are serialized to JSON
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NumberFormat {
+    /// Write whole numbers up to 2^53 in magnitude as integers (`493603`, not `493603.0`)
+    pub integers: bool,
+    /// Round other numbers to this many decimal places (`None`: as many as needed)
+    pub decimals: Option<u8>,
+    /// Notation of numbers not written as integers
+    pub notation: Notation,
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        Self {
+            integers: true,
+            decimals: None,
+            notation: Notation::Auto,
+        }
+    }
+}
+
+impl NumberFormat {
+    /// serde's own output: every number with a fractional part or exponent
+    pub const FLOAT: Self = Self {
+        integers: false,
+        decimals: None,
+        notation: Notation::Auto,
+    };
+
+    /// Serialize `value` as compact JSON
+    pub fn to_string<T: Serialize + ?Sized>(&self, value: &T) -> serde_json::Result<String> {
+        let json = self.to_vec(value)?;
+        // serde_json only writes valid UTF-8
+        Ok(String::from_utf8(json).expect("JSON output is UTF-8"))
+    }
+
+    /// Serialize `value` as indented JSON
+    pub fn to_string_pretty<T: Serialize + ?Sized>(&self, value: &T) -> serde_json::Result<String> {
+        let mut json = Vec::new();
+        self.write(&mut json, PrettyFormatter::new(), value)?;
+        Ok(String::from_utf8(json).expect("JSON output is UTF-8"))
+    }
+
+    /// Serialize `value` as compact JSON bytes
+    pub fn to_vec<T: Serialize + ?Sized>(&self, value: &T) -> serde_json::Result<Vec<u8>> {
+        let mut json = Vec::new();
+        self.write(&mut json, CompactFormatter, value)?;
+        Ok(json)
+    }
+
+    fn write<F: Formatter, T: Serialize + ?Sized>(
+        &self,
+        writer: &mut Vec<u8>,
+        layout: F,
+        value: &T,
+    ) -> serde_json::Result<()> {
+        let formatter = NumberFormatter {
+            layout,
+            format: *self,
+        };
+        value.serialize(&mut serde_json::Serializer::with_formatter(
+            writer, formatter,
+        ))
+    }
+
+    /// Text of a finite number, or `None` to keep serde's shortest form
+    fn text<T: Copy + Display + LowerExp + Into<f64>>(&self, value: T) -> Option<String> {
+        let number: f64 = value.into();
+        if self.integers && number.fract() == 0.0 && number.abs() <= MAX_EXACT_INTEGER {
+            return Some((number as i64).to_string());
+        }
+        let text = match (self.notation, self.decimals) {
+            (Notation::Auto, None) => return None,
+            (Notation::Auto | Notation::Plain, Some(decimals)) => {
+                format!("{:.*}", usize::from(decimals), value)
+            }
+            (Notation::Plain, None) => value.to_string(),
+            (Notation::Scientific, Some(decimals)) => {
+                format!("{:.*e}", usize::from(decimals), value)
+            }
+            (Notation::Scientific, None) => format!("{:e}", value),
+        };
+        // Without integers, whole numbers still read as floats
+        if self.integers || self.decimals.is_some() || text.contains(['.', 'e']) {
+            Some(text)
+        } else {
+            Some(text + ".0")
+        }
+    }
+}
+
+/// JSON formatter writing numbers as a `NumberFormat` asks and everything
+/// else as `layout` does
+struct NumberFormatter<F> {
+    layout: F,
+    format: NumberFormat,
+}
+
+impl<F: Formatter> Formatter for NumberFormatter<F> {
+    fn write_f32<W: ?Sized + io::Write>(&mut self, writer: &mut W, value: f32) -> io::Result<()> {
+        match self.format.text(value) {
+            Some(text) => writer.write_all(text.as_bytes()),
+            None => self.layout.write_f32(writer, value),
+        }
+    }
+
+    fn write_f64<W: ?Sized + io::Write>(&mut self, writer: &mut W, value: f64) -> io::Result<()> {
+        match self.format.text(value) {
+            Some(text) => writer.write_all(text.as_bytes()),
+            None => self.layout.write_f64(writer, value),
+        }
+    }
+
+    fn begin_array<W: ?Sized + io::Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.layout.begin_array(writer)
+    }
+
+    fn end_array<W: ?Sized + io::Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.layout.end_array(writer)
+    }
+
+    fn begin_array_value<W: ?Sized + io::Write>(
+        &mut self,
+        writer: &mut W,
+        first: bool,
+    ) -> io::Result<()> {
+        self.layout.begin_array_value(writer, first)
+    }
+
+    fn end_array_value<W: ?Sized + io::Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.layout.end_array_value(writer)
+    }
+
+    fn begin_object<W: ?Sized + io::Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.layout.begin_object(writer)
+    }
+
+    fn end_object<W: ?Sized + io::Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.layout.end_object(writer)
+    }
+
+    fn begin_object_key<W: ?Sized + io::Write>(
+        &mut self,
+        writer: &mut W,
+        first: bool,
+    ) -> io::Result<()> {
+        self.layout.begin_object_key(writer, first)
+    }
+
+    fn end_object_key<W: ?Sized + io::Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.layout.end_object_key(writer)
+    }
+
+    fn begin_object_value<W: ?Sized + io::Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.layout.begin_object_value(writer)
+    }
+
+    fn end_object_value<W: ?Sized + io::Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.layout.end_object_value(writer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::Value;
+
+    fn write(format: NumberFormat, value: f64) -> String {
+        format.to_string(&serde_json::json!(value)).unwrap()
+    }
+
+    #[test]
+    fn test_whole_numbers_are_integers() {
+        let format = NumberFormat::default();
+        assert_eq!(write(format, 493603.0), "493603");
+        assert_eq!(write(format, -2.0), "-2");
+        assert_eq!(write(format, 0.25), "0.25");
+        assert_eq!(write(format, 1e300), "1e300");
+        assert_eq!(write(NumberFormat::FLOAT, 493603.0), "493603.0");
+        assert_eq!(
+            format.to_string(&Value::Number(42.0)).unwrap(),
+            r#"{"Number":42}"#
+        );
+        // Non-finite numbers stay null
+        assert_eq!(write(format, f64::NAN), "null");
+    }
+
+    #[test]
+    fn test_decimals_and_notation() {
+        let fixed = NumberFormat {
+            decimals: Some(2),
+            ..NumberFormat::default()
+        };
+        assert_eq!(write(fixed, 1.23456), "1.23");
+        assert_eq!(write(fixed, 7.0), "7");
+
+        let plain = NumberFormat {
+            notation: Notation::Plain,
+            ..NumberFormat::FLOAT
+        };
+        assert_eq!(write(plain, 1e-7), "0.0000001");
+        assert_eq!(write(plain, 12.0), "12.0");
+
+        let scientific = NumberFormat {
+            notation: Notation::Scientific,
+            decimals: Some(1),
+            ..NumberFormat::FLOAT
+        };
+        assert_eq!(write(scientific, 493603.0), "4.9e5");
+
+        let vector = serde_json::json!([0.5f32, 2.0f32]);
+        assert_eq!(
+            NumberFormat::default().to_string(&vector).unwrap(),
+            "[0.5,2]"
+        );
+    }
+
+    #[test]
+    fn test_pretty_output_keeps_layout() {
+        let value = serde_json::json!({"a": [1.0, 2.5]});
+        assert_eq!(
+            NumberFormat::default().to_string_pretty(&value).unwrap(),
+            serde_json::to_string_pretty(&serde_json::json!({"a": [1, 2.5]})).unwrap()
+        );
+    }
+}
@@ -0,0 +1,452 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! Path search over a graph for variable-length and shortest path patterns
+//!
+//! Searches work on node ids and edges borrowed from the `GraphCache`; binding
+//! the paths they find into result rows is up to the executor.
+
+use std::collections::HashMap;
+
+use crate::ast::EdgeDirection;
+use crate::storage::value::{PathElement, PathValue};
+use crate::storage::{Edge, GraphCache};
+
+/// One quantified hop of a pattern: `-[:KNOWS*1..3]->`
+#[derive(Debug, Clone, Copy)]
+pub struct HopPattern<'a> {
+    /// Labels an edge may have; any edge when empty
+    pub edge_labels: &'a [String],
+    pub direction: &'a EdgeDirection,
+    pub min_hops: u32,
+    /// No upper bound when `None`
+    pub max_hops: Option<u32>,
+}
+
+/// A path found by a search: `nodes` has one more entry than `edges`
+#[derive(Debug, Clone)]
+pub struct Walk<'g> {
+    pub nodes: Vec<&'g str>,
+    pub edges: Vec<&'g Edge>,
+}
+
+impl<'g> Walk<'g> {
+    fn start(node_id: &'g str) -> Self {
+        Self {
+            nodes: vec![node_id],
+            edges: Vec::new(),
+        }
+    }
+
+    /// Id of the node the walk ends on
+    pub fn end(&self) -> &'g str {
+        self.nodes[self.nodes.len() - 1]
+    }
+
+    /// Append the walk to `path`, which must end on the walk's first node
+    pub fn extend_path(&self, path: &mut PathValue) {
+        if path.elements.is_empty() {
+            path.add_element(PathElement {
+                node_id: self.nodes[0].to_string(),
+                edge_id: None,
+            });
+        }
+        for (edge, node_id) in self.edges.iter().zip(&self.nodes[1..]) {
+            if let Some(last) = path.elements.last_mut() {
+                last.edge_id = Some(edge.id.clone());
+            }
+            path.add_element(PathElement {
+                node_id: node_id.to_string(),
+                edge_id: None,
+            });
+        }
+    }
+}
+
+/// Edges `hop` may follow from `node_id`, each with the node at its other end
+pub fn neighbors<'g>(
+    graph: &'g GraphCache,
+    node_id: &str,
+    edge_labels: &[String],
+    direction: &EdgeDirection,
+) -> Vec<(&'g Edge, &'g str)> {
+    let outgoing = || {
+        graph
+            .get_outgoing_edges(node_id)
+            .into_iter()
+            .filter(|edge| edge_labels.is_empty() || edge_labels.contains(&edge.label))
+            .map(|edge| (edge, edge.to_node.as_str()))
+    };
+    // Incoming edges come from the reverse adjacency index, already restricted to
+    // the requested labels
+    let incoming = || {
+        graph
+            .get_incoming_edges_with_labels(node_id, edge_labels)
+            .into_iter()
+            .map(|edge| (edge, edge.from_node.as_str()))
+    };
+    match direction {
+        EdgeDirection::Outgoing => outgoing().collect(),
+        EdgeDirection::Incoming => incoming().collect(),
+        EdgeDirection::Both | EdgeDirection::Undirected => outgoing().chain(incoming()).collect(),
+    }
+}
+
+/// Every walk from `start` that matches `hop`, shortest first
+///
+/// The search is breadth first, one hop per level. A walk never returns to a
+/// node it has visited, so cycles end it, and an open-ended `hop` stops once no
+/// walk can grow. `follow` decides which edges may be walked.
+pub fn variable_length_walks<'g>(
+    graph: &'g GraphCache,
+    start: &'g str,
+    hop: &HopPattern<'_>,
+    mut follow: impl FnMut(&Edge) -> bool,
+) -> Vec<Walk<'g>> {
+    let mut walks = Vec::new();
+    let mut frontier = vec![Walk::start(start)];
+    let mut depth = 0;
+    while !frontier.is_empty() {
+        if depth >= hop.min_hops {
+            walks.extend(frontier.iter().cloned());
+        }
+        if hop.max_hops.is_some_and(|max| depth >= max) {
+            break;
+        }
+
+        let mut next = Vec::new();
+        for walk in &frontier {
+            for (edge, neighbor) in neighbors(graph, walk.end(), hop.edge_labels, hop.direction) {
+                if walk.nodes.contains(&neighbor) || !follow(edge) {
+                    continue;
+                }
+                let mut extended = walk.clone();
+                extended.nodes.push(neighbor);
+                extended.edges.push(edge);
+                next.push(extended);
+            }
+        }
+        frontier = next;
+        depth += 1;
+    }
+    walks
+}
+
+/// Shortest walks from `start` to `end` that match `hop`
+///
+/// A bidirectional breadth-first search: each round grows the side with the
+/// smaller frontier by one hop, and the search ends on the first round where
+/// the two sides meet. With `all` every walk of that length is returned,
+/// otherwise just one. A node is its own shortest path only when zero hops
+/// are allowed.
+///
+/// The search cannot enforce a minimum above one hop: the first walk found
+/// may be shorter, and a longer walk is not a shortest one. The planner rejects
+/// such patterns, so `hop.min_hops` is at most one here.
+pub fn shortest_walks<'g>(
+    graph: &'g GraphCache,
+    start: &'g str,
+    end: &'g str,
+    hop: &HopPattern<'_>,
+    all: bool,
+    mut follow: impl FnMut(&Edge) -> bool,
+) -> Vec<Walk<'g>> {
+    debug_assert!(
+        hop.min_hops <= 1,
+        "shortest path search got a minimum of {} hops",
+        hop.min_hops
+    );
+    if start == end {
+        return if hop.min_hops == 0 {
+            vec![Walk::start(start)]
+        } else {
+            Vec::new()
+        };
+    }
+
+    let mut forward = SearchSide::new(start, hop.direction.clone());
+    let mut backward = SearchSide::new(end, reversed(hop.direction));
+    loop {
+        let length = forward.depth + backward.depth;
+        if hop.max_hops.is_some_and(|max| length >= max)
+            || forward.frontier.is_empty()
+            || backward.frontier.is_empty()
+        {
+            return Vec::new();
+        }
+
+        let meeting: Vec<&'g str> = {
+            let (grown, other) = if forward.frontier.len() <= backward.frontier.len() {
+                (&mut forward, &backward)
+            } else {
+                (&mut backward, &forward)
+            };
+            grown.expand(graph, hop.edge_labels, &mut follow);
+            // Neither side had reached the other before this round, so any
+            // node they share is on both frontiers
+            grown
+                .frontier
+                .iter()
+                .copied()
+                .filter(|node| other.visited.contains_key(node))
+                .collect()
+        };
+
+        let mut walks = Vec::new();
+        for node in meeting {
+            for head in forward.walks_to(node, all) {
+                for tail in backward.walks_to(node, all) {
+                    let mut walk = head.clone();
+                    walk.nodes.extend(tail.nodes.iter().rev().skip(1));
+                    walk.edges.extend(tail.edges.iter().rev());
+                    if !all {
+                        return vec![walk];
+                    }
+                    walks.push(walk);
+                }
+            }
+        }
+        if !walks.is_empty() {
+            return walks;
+        }
+    }
+}
+
+/// Direction of an edge pattern seen from its far end
+fn reversed(direction: &EdgeDirection) -> EdgeDirection {
+    match direction {
+        EdgeDirection::Outgoing => EdgeDirection::Incoming,
+        EdgeDirection::Incoming => EdgeDirection::Outgoing,
+        other => other.clone(),
+    }
+}
+
+/// Edges reaching a node from the level before, each with the node at its
+/// other end
+type Parents<'g> = Vec<(&'g Edge, &'g str)>;
+
+/// One side of a bidirectional search
+struct SearchSide<'g> {
+    direction: EdgeDirection,
+    /// Hops from the side's root to its frontier
+    depth: u32,
+    frontier: Vec<&'g str>,
+    /// Depth each node was reached at, with its parents
+    visited: HashMap<&'g str, (u32, Parents<'g>)>,
+}
+
+impl<'g> SearchSide<'g> {
+    fn new(root: &'g str, direction: EdgeDirection) -> Self {
+        Self {
+            direction,
+            depth: 0,
+            frontier: vec![root],
+            visited: HashMap::from([(root, (0, Vec::new()))]),
+        }
+    }
+
+    /// Move the frontier one hop further from the root
+    fn expand(
+        &mut self,
+        graph: &'g GraphCache,
+        edge_labels: &[String],
+        follow: &mut impl FnMut(&Edge) -> bool,
+    ) {
+        let depth = self.depth + 1;
+        let mut next = Vec::new();
+        for node in std::mem::take(&mut self.frontier) {
+            for (edge, neighbor) in neighbors(graph, node, edge_labels, &self.direction) {
+                if !follow(edge) {
+                    continue;
+                }
+                match self.visited.get_mut(neighbor) {
+                    None => {
+                        self.visited.insert(neighbor, (depth, vec![(edge, node)]));
+                        next.push(neighbor);
+                    }
+                    Some((reached, parents)) if *reached == depth => parents.push((edge, node)),
+                    Some(_) => {}
+                }
+            }
+        }
+        self.frontier = next;
+        self.depth = depth;
+    }
+
+    /// Walks from the root to a visited node, or just the first one found
+    fn walks_to(&self, node: &'g str, all: bool) -> Vec<Walk<'g>> {
+        let parents = &self.visited[node].1;
+        if parents.is_empty() {
+            return vec![Walk::start(node)];
+        }
+        let parents = if all { &parents[..] } else { &parents[..1] };
+        parents
+            .iter()
+            .flat_map(|(edge, previous)| {
+                self.walks_to(previous, all)
+                    .into_iter()
+                    .map(move |mut walk| {
+                        walk.nodes.push(node);
+                        walk.edges.push(edge);
+                        walk
+                    })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::Node;
+    use std::collections::HashMap;
+
+    fn graph(edges: &[(&str, &str)]) -> GraphCache {
+        let mut graph = GraphCache::new();
+        for (from, to) in edges {
+            for id in [from, to] {
+                if graph.get_node(id).is_none() {
+                    graph
+                        .add_node(Node {
+                            id: id.to_string(),
+                            labels: vec!["N".to_string()],
+                            properties: HashMap::new(),
+                        })
+                        .unwrap();
+                }
+            }
+            graph
+                .add_edge(Edge {
+                    id: format!("{}{}", from, to),
+                    from_node: from.to_string(),
+                    to_node: to.to_string(),
+                    label: "NEXT".to_string(),
+                    properties: HashMap::new(),
+                })
+                .unwrap();
+        }
+        graph
+    }
+
+    /// a -> b -> c -> a, plus c -> d
+    fn cycle_graph() -> GraphCache {
+        graph(&[("a", "b"), ("b", "c"), ("c", "a"), ("c", "d")])
+    }
+
+    fn ends(walks: &[Walk<'_>]) -> Vec<String> {
+        walks.iter().map(|walk| walk.nodes.concat()).collect()
+    }
+
+    #[test]
+    fn test_bounded_walks_are_shortest_first() {
+        let graph = cycle_graph();
+        let labels = vec!["NEXT".to_string()];
+        let hop = HopPattern {
+            edge_labels: &labels,
+            direction: &EdgeDirection::Outgoing,
+            min_hops: 1,
+            max_hops: Some(2),
+        };
+        let walks = variable_length_walks(&graph, "a", &hop, |_| true);
+        assert_eq!(ends(&walks), vec!["ab", "abc"]);
+    }
+
+    #[test]
+    fn test_open_ended_walks_stop_at_cycles() {
+        let graph = cycle_graph();
+        let hop = HopPattern {
+            edge_labels: &[],
+            direction: &EdgeDirection::Outgoing,
+            min_hops: 0,
+            max_hops: None,
+        };
+        let walks = variable_length_walks(&graph, "a", &hop, |_| true);
+        assert_eq!(ends(&walks), vec!["a", "ab", "abc", "abcd"]);
+
+        // Undirected walks reach d from b both ways round the cycle
+        let hop = HopPattern {
+            direction: &EdgeDirection::Undirected,
+            min_hops: 2,
+            ..hop
+        };
+        let mut walks = ends(&variable_length_walks(&graph, "b", &hop, |_| true));
+        walks.sort();
+        assert_eq!(walks, vec!["bac", "bacd", "bca", "bcd"]);
+    }
+
+    #[test]
+    fn test_walk_extends_path() {
+        let graph = cycle_graph();
+        let hop = HopPattern {
+            edge_labels: &[],
+            direction: &EdgeDirection::Outgoing,
+            min_hops: 2,
+            max_hops: Some(2),
+        };
+        let walks = variable_length_walks(&graph, "a", &hop, |edge| edge.id != "ca");
+        let mut path = PathValue::new();
+        walks[0].extend_path(&mut path);
+        assert_eq!(path.get_nodes(), vec!["a", "b", "c"]);
+        assert_eq!(path.get_edges(), vec!["ab", "bc"]);
+        assert_eq!(path.length(), 2);
+    }
+
+    #[test]
+    fn test_shortest_walks_follow_direction() {
+        let graph = cycle_graph();
+        let mut hop = HopPattern {
+            edge_labels: &[],
+            direction: &EdgeDirection::Outgoing,
+            min_hops: 1,
+            max_hops: None,
+        };
+        let shortest = |hop: &HopPattern<'_>, start, end| {
+            ends(&shortest_walks(&graph, start, end, hop, false, |_| true))
+        };
+        assert_eq!(shortest(&hop, "a", "d"), vec!["abcd"]);
+        assert_eq!(shortest(&hop, "d", "a"), Vec::<String>::new());
+
+        hop.direction = &EdgeDirection::Incoming;
+        assert_eq!(shortest(&hop, "d", "a"), vec!["dcba"]);
+
+        hop.direction = &EdgeDirection::Undirected;
+        assert_eq!(shortest(&hop, "a", "d"), vec!["acd"]);
+        hop.max_hops = Some(1);
+        assert_eq!(shortest(&hop, "a", "d"), Vec::<String>::new());
+
+        // Zero hops only reach the start
+        assert_eq!(shortest(&hop, "a", "a"), Vec::<String>::new());
+        hop.min_hops = 0;
+        assert_eq!(shortest(&hop, "a", "a"), vec!["a"]);
+    }
+
+    #[test]
+    fn test_all_shortest_walks() {
+        // Two paths of two hops from e to h, and a longer one through i
+        let graph = graph(&[
+            ("e", "f"),
+            ("e", "g"),
+            ("f", "h"),
+            ("g", "h"),
+            ("e", "i"),
+            ("i", "j"),
+            ("j", "h"),
+        ]);
+        let hop = HopPattern {
+            edge_labels: &[],
+            direction: &EdgeDirection::Outgoing,
+            min_hops: 1,
+            max_hops: None,
+        };
+        let mut walks = ends(&shortest_walks(&graph, "e", "h", &hop, true, |_| true));
+        walks.sort();
+        assert_eq!(walks, vec!["efh", "egh"]);
+
+        // Without the edges into h from f and g, the longer path is the shortest
+        let walks = shortest_walks(&graph, "e", "h", &hop, true, |edge| {
+            !matches!(edge.id.as_str(), "fh" | "gh")
+        });
+        assert_eq!(ends(&walks), vec!["eijh"]);
+        assert_eq!(walks[0].edges.len(), 3);
+    }
+}
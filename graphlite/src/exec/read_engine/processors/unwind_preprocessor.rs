@@ -227,6 +227,7 @@ impl UnwindPreprocessor {
             rows_affected: total_affected,
             session_result: None,
             warnings: Vec::new(),
+            operator_timings: None,
         })
     }
 
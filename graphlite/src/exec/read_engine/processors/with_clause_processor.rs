@@ -6,8 +6,11 @@
 //! This module provides shared functionality for processing WITH clauses across all MATCH
 //! statement types (MATCH-INSERT, MATCH-SET, MATCH-DELETE, MATCH-REMOVE).
 
-use crate::ast::{DistinctQualifier, Expression, FunctionCall, Literal, WithClause, WithItem};
-use crate::exec::{ExecutionContext, ExecutionError};
+use crate::ast::{
+    DistinctQualifier, Expression, FunctionCall, Literal, NullsOrdering, OrderDirection,
+    WithClause, WithItem,
+};
+use crate::exec::{ExecutionContext, ExecutionError, QueryExecutor};
 use crate::functions::FunctionContext;
 use crate::storage::{Edge, Node, Value};
 use std::collections::HashMap;
@@ -893,19 +896,7 @@ impl WithClauseProcessor {
                     Ok(Value::Null)
                 }
             }
-            "COLLECT" => {
-                if let Some(Expression::Variable(var)) = func_call.arguments.first() {
-                    let mut collected = Vec::new();
-                    if let Some(nodes) = group_nodes.get(&var.name) {
-                        for node in nodes {
-                            collected.push(Value::String(node.id.clone()));
-                        }
-                    }
-                    Ok(Value::String(format!("{:?}", collected)))
-                } else {
-                    Ok(Value::String("[]".to_string()))
-                }
-            }
+            "COLLECT" => Self::collect_nodes(func_call, group_nodes),
             _ => {
                 log::warn!("Unsupported group aggregation function: {}", func_call.name);
                 Ok(Value::Null)
@@ -1137,20 +1128,7 @@ impl WithClauseProcessor {
                     Ok(Value::Null)
                 }
             }
-            "COLLECT" => {
-                if let Some(Expression::Variable(var)) = func_call.arguments.first() {
-                    let mut collected = Vec::new();
-                    if let Some(nodes) = variable_bindings.get(&var.name) {
-                        for node in nodes {
-                            collected.push(Value::String(node.id.clone()));
-                        }
-                    }
-                    // For now, return as a string representation
-                    Ok(Value::String(format!("{:?}", collected)))
-                } else {
-                    Ok(Value::String("[]".to_string()))
-                }
-            }
+            "COLLECT" => Self::collect_nodes(func_call, variable_bindings),
             _ => {
                 log::warn!("Unsupported aggregation function: {}", func_call.name);
                 Ok(Value::Null)
@@ -1158,6 +1136,88 @@ impl WithClauseProcessor {
         }
     }
 
+    /// Evaluate COLLECT over the nodes bound to a variable
+    ///
+    /// `COLLECT(a)` lists the node ids and `COLLECT(a.name)` the property of each
+    /// node. An ORDER BY may sort by properties of the collected variable, and a
+    /// LIMIT keeps the first values.
+    fn collect_nodes(
+        func_call: &FunctionCall,
+        variable_bindings: &HashMap<String, Vec<Node>>,
+    ) -> Result<Value, ExecutionError> {
+        let (variable, property) = match func_call.arguments.first() {
+            Some(Expression::Variable(var)) => (&var.name, None),
+            Some(Expression::PropertyAccess(prop)) => (&prop.object, Some(&prop.property)),
+            _ => return Ok(Value::String("[]".to_string())),
+        };
+        let mut nodes: Vec<&Node> = variable_bindings
+            .get(variable)
+            .map(|nodes| nodes.iter().collect())
+            .unwrap_or_default();
+
+        if let Some(order_clause) = &func_call.order_clause {
+            let mut sort_keys = Vec::new();
+            for item in &order_clause.items {
+                match &item.expression {
+                    Expression::PropertyAccess(prop) if prop.object == *variable => {
+                        sort_keys.push((&prop.property, item));
+                    }
+                    _ => {
+                        return Err(ExecutionError::UnsupportedOperator(format!(
+                            "COLLECT in WITH can only be ordered by properties of '{}'",
+                            variable
+                        )))
+                    }
+                }
+            }
+            nodes.sort_by(|a, b| {
+                for (property, item) in &sort_keys {
+                    let a_value = a.properties.get(*property).unwrap_or(&Value::Null);
+                    let b_value = b.properties.get(*property).unwrap_or(&Value::Null);
+                    let nulls_first = item.nulls_ordering == Some(NullsOrdering::First);
+                    let ordering = QueryExecutor::compare_values(a_value, b_value, nulls_first)
+                        .unwrap_or(std::cmp::Ordering::Equal);
+                    if ordering != std::cmp::Ordering::Equal {
+                        return match item.direction {
+                            OrderDirection::Ascending => ordering,
+                            OrderDirection::Descending => ordering.reverse(),
+                        };
+                    }
+                }
+                std::cmp::Ordering::Equal
+            });
+        }
+        if let Some(limit_clause) = &func_call.limit_clause {
+            nodes = nodes
+                .into_iter()
+                .skip(limit_clause.offset.unwrap_or(0))
+                .take(limit_clause.count)
+                .collect();
+        }
+
+        match property {
+            Some(property) => Ok(Value::List(
+                nodes
+                    .iter()
+                    .map(|node| {
+                        node.properties
+                            .get(property)
+                            .cloned()
+                            .unwrap_or(Value::Null)
+                    })
+                    .collect(),
+            )),
+            None => {
+                let collected: Vec<Value> = nodes
+                    .iter()
+                    .map(|node| Value::String(node.id.clone()))
+                    .collect();
+                // For now, return as a string representation
+                Ok(Value::String(format!("{:?}", collected)))
+            }
+        }
+    }
+
     /// Check if an expression involves aggregation
     fn is_aggregation_expression(expr: &Expression) -> bool {
         match expr {
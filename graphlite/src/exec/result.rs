@@ -3,8 +3,10 @@
 //
 //! Query execution results for graph databases
 
-use crate::ast::{CatalogPath, GraphExpression};
+use crate::ast::{CatalogPath, GraphExpression, SessionResetArgs};
+use crate::exec::timing::OperatorTiming;
 use crate::storage::Value;
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::hash::{Hash, Hasher};
@@ -34,8 +36,8 @@ pub enum SessionResult {
     },
     /// Set session timezone
     SetTimeZone { timezone: String },
-    /// Reset session to defaults
-    Reset,
+    /// Reset session to defaults, or only the setting `args` names
+    Reset { args: Option<SessionResetArgs> },
     /// Close session
     Close,
 }
@@ -53,6 +55,9 @@ pub struct QueryResult {
     /// Warnings generated during query execution (e.g., duplicate insert detection)
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub warnings: Vec<String>,
+    /// Time and rows per executed operator, when the session has operator timing on
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub operator_timings: Option<OperatorTiming>,
 }
 
 impl Default for QueryResult {
@@ -71,6 +76,7 @@ impl QueryResult {
             rows_affected: 0,
             session_result: None,
             warnings: Vec::new(),
+            operator_timings: None,
         }
     }
 
@@ -83,6 +89,7 @@ impl QueryResult {
             rows_affected: 0,
             session_result: Some(session_result),
             warnings: Vec::new(),
+            operator_timings: None,
         }
     }
 
@@ -110,6 +117,34 @@ impl QueryResult {
         self
     }
 
+    /// Put every row's values in the order of `variables`
+    ///
+    /// Values without a matching column keep their relative order after the
+    /// columns. Rows already in column order are left untouched.
+    pub fn align_columns(&mut self) {
+        if self.variables.is_empty() {
+            return;
+        }
+        let position: HashMap<&str, usize> = self
+            .variables
+            .iter()
+            .enumerate()
+            .map(|(i, name)| (name.as_str(), i))
+            .collect();
+        let rank = |name: &str| position.get(name).copied().unwrap_or(usize::MAX);
+        for row in &mut self.rows {
+            let ordered = row
+                .values
+                .keys()
+                .zip(row.values.keys().skip(1))
+                .all(|(a, b)| rank(a) <= rank(b));
+            if !ordered {
+                // Stable, so unknown values keep their order
+                row.values.sort_by_cached_key(|name, _| rank(name));
+            }
+        }
+    }
+
     /// Check if this result contains a session command
     pub fn is_session_command(&self) -> bool {
         self.session_result.is_some()
@@ -139,7 +174,8 @@ impl QueryResult {
             SessionResult::SetTimeZone { timezone } => {
                 format!("Session timezone set to: {}", timezone)
             }
-            SessionResult::Reset => "Session reset to defaults".to_string(),
+            SessionResult::Reset { args: None } => "Session reset to defaults".to_string(),
+            SessionResult::Reset { args: Some(_) } => "Session setting reset".to_string(),
             SessionResult::Close => "Session closed".to_string(),
         })
     }
@@ -148,8 +184,8 @@ impl QueryResult {
 /// Single result row representing variable bindings
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Row {
-    /// Named variable bindings (e.g., "p.name" -> "Alice")
-    pub values: HashMap<String, Value>,
+    /// Named variable bindings (e.g., "p.name" -> "Alice"), in column order
+    pub values: IndexMap<String, Value>,
     /// Positional values for set operations (ordered by variable declaration)
     pub positional_values: Vec<Value>,
     /// Source entity IDs for identity-based set operations
@@ -168,7 +204,7 @@ impl Row {
     /// Create a new empty row
     pub fn new() -> Self {
         Self {
-            values: HashMap::new(),
+            values: IndexMap::new(),
             positional_values: Vec::new(),
             source_entities: HashMap::new(),
             text_score: None,
@@ -176,10 +212,13 @@ impl Row {
         }
     }
 
-    /// Create a row from a HashMap of values (backward compatibility)
-    pub fn from_values(values: HashMap<String, Value>) -> Self {
+    /// Create a row from named values, kept in the order given
+    ///
+    /// Values from a `HashMap` arrive in no particular order; results put them
+    /// in column order before they are returned (see `QueryResult::align_columns`).
+    pub fn from_values(values: impl IntoIterator<Item = (String, Value)>) -> Self {
         Self {
-            values,
+            values: values.into_iter().collect(),
             positional_values: Vec::new(),
             source_entities: HashMap::new(),
             text_score: None,
@@ -189,7 +228,7 @@ impl Row {
 
     /// Create a row from positional values with variable names
     pub fn from_positional(values: Vec<Value>, variables: &[String]) -> Self {
-        let mut named_values = HashMap::new();
+        let mut named_values = IndexMap::new();
         for (value, var_name) in values.iter().zip(variables.iter()) {
             named_values.insert(var_name.clone(), value.clone());
         }
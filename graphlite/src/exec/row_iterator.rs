@@ -184,7 +184,7 @@ mod tests {
     use std::collections::HashMap;
 
     fn create_test_row(id: usize) -> Row {
-        let mut values = HashMap::new();
+        let mut values = indexmap::IndexMap::new();
         values.insert("id".to_string(), Value::Number(id as f64));
         Row {
             values,
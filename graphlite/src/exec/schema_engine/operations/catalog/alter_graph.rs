@@ -0,0 +1,111 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+use crate::ast::AlterGraphStatement;
+use crate::catalog::error::CatalogError;
+use crate::catalog::manager::CatalogManager;
+use crate::catalog::operations::{CatalogOperation, CatalogResponse, EntityType};
+use crate::exec::schema_engine::operations::DDLStatementExecutor;
+use crate::exec::write_stmt::{ExecutionContext, StatementExecutor};
+use crate::exec::ExecutionError;
+use crate::storage::StorageManager;
+use crate::txn::state::OperationType;
+
+/// Executor for ALTER GRAPH statements
+pub struct AlterGraphExecutor {
+    statement: AlterGraphStatement,
+}
+
+impl AlterGraphExecutor {
+    /// Create a new AlterGraphExecutor
+    pub fn new(statement: AlterGraphStatement) -> Self {
+        Self { statement }
+    }
+
+    /// Resolve the graph path to its schema and graph name
+    fn resolve_path(&self, context: &ExecutionContext) -> Result<(String, String), ExecutionError> {
+        let segments = &self.statement.graph_path.segments;
+        match segments.len() {
+            2 => Ok((segments[0].clone(), segments[1].clone())),
+            1 => match context.get_current_schema() {
+                Some(session_schema) => Ok((
+                    session_schema
+                        .strip_prefix('/')
+                        .unwrap_or(&session_schema)
+                        .to_string(),
+                    segments[0].clone(),
+                )),
+                None => Err(ExecutionError::RuntimeError(
+                    "Cannot alter graph with relative path: no current schema set. Use 'SESSION SET SCHEMA schema_name' or provide full path '/schema_name/graph_name'".to_string()
+                )),
+            },
+            _ => Err(ExecutionError::RuntimeError(
+                "Invalid graph path: must specify either graph name (when schema is set) or full path /schema_name/graph_name".to_string()
+            )),
+        }
+    }
+}
+
+impl StatementExecutor for AlterGraphExecutor {
+    fn operation_type(&self) -> OperationType {
+        OperationType::Other
+    }
+
+    fn operation_description(&self, _context: &ExecutionContext) -> String {
+        format!(
+            "ALTER GRAPH /{}",
+            self.statement.graph_path.segments.join("/")
+        )
+    }
+}
+
+impl DDLStatementExecutor for AlterGraphExecutor {
+    fn execute_ddl_operation(
+        &self,
+        context: &ExecutionContext,
+        catalog_manager: &mut CatalogManager,
+        _storage: &StorageManager,
+    ) -> Result<(String, usize), ExecutionError> {
+        let (schema_name, graph_name) = self.resolve_path(context)?;
+        let full_graph_path = format!("/{}/{}", schema_name, graph_name);
+
+        let mut updates = serde_json::Map::new();
+        if let Some(description) = &self.statement.description {
+            updates.insert("description".to_string(), description.clone().into());
+        }
+        if let Some(tags) = &self.statement.tags {
+            updates.insert("tags".to_string(), tags.clone().into());
+        }
+        if let Some(owner) = &self.statement.owner {
+            updates.insert("owner".to_string(), owner.clone().into());
+        }
+
+        // The catalog keys graphs by schema-qualified name
+        let update_op = CatalogOperation::Update {
+            entity_type: EntityType::Graph,
+            name: format!("{}/{}", schema_name, graph_name),
+            updates: serde_json::Value::Object(updates),
+        };
+
+        match catalog_manager.execute("graph_metadata", update_op) {
+            Ok(CatalogResponse::Success { .. }) => {
+                Ok((format!("Graph '{}' altered", full_graph_path), 1))
+            }
+            Ok(CatalogResponse::Error { message }) => Err(ExecutionError::CatalogError(format!(
+                "Failed to alter graph '{}': {}",
+                full_graph_path, message
+            ))),
+            Ok(_) => Err(ExecutionError::CatalogError(
+                "Unexpected response from graph_metadata catalog".to_string(),
+            )),
+            Err(CatalogError::NotFound(_)) => Err(ExecutionError::CatalogError(format!(
+                "Graph '{}' not found",
+                full_graph_path
+            ))),
+            Err(e) => Err(ExecutionError::CatalogError(format!(
+                "Failed to alter graph '{}': {}",
+                full_graph_path, e
+            ))),
+        }
+    }
+}
@@ -65,7 +65,10 @@ impl DDLStatementExecutor for ClearGraphExecutor {
         };
 
         // Step 1: Clear all graph data from storage FIRST
-        // CLEAR removes all nodes and edges but keeps the graph and its schema
+        // CLEAR removes all nodes and edges but keeps the graph and its schema;
+        // the write lock keeps a data statement in flight from saving them back
+        let write_lock = storage.graph_write_lock(&full_path);
+        let _writing = write_lock.lock().unwrap_or_else(|e| e.into_inner());
         let empty_graph = crate::storage::GraphCache::new();
         storage.save_graph(&full_path, empty_graph).map_err(|e| {
             ExecutionError::StorageError(format!(
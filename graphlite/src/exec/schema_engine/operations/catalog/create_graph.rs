@@ -1,12 +1,14 @@
 // Copyright (c) 2024-2025 DeepGraph Inc.
 // SPDX-License-Identifier: Apache-2.0
 //
-use crate::ast::CreateGraphStatement;
+use crate::ast::{CatalogPath, CreateGraphStatement};
+use crate::catalog::error::CatalogError;
 use crate::catalog::manager::CatalogManager;
 use crate::catalog::operations::{CatalogOperation, CatalogResponse, EntityType, QueryType};
 use crate::exec::schema_engine::operations::DDLStatementExecutor;
 use crate::exec::write_stmt::{ExecutionContext, StatementExecutor};
 use crate::exec::ExecutionError;
+use crate::schema::types::GraphTypeDefinition;
 use crate::storage::StorageManager;
 use crate::txn::state::OperationType;
 use log::{info, warn};
@@ -23,6 +25,35 @@ impl CreateGraphExecutor {
     }
 }
 
+impl CreateGraphExecutor {
+    /// Graph type a `TYPED` clause refers to, which must exist
+    fn referenced_graph_type(
+        type_path: &CatalogPath,
+        catalog_manager: &mut CatalogManager,
+    ) -> Result<GraphTypeDefinition, ExecutionError> {
+        // Named the way CREATE GRAPH TYPE names it
+        let type_name = type_path.segments.join(".");
+        let graph_type_query = CatalogOperation::Query {
+            query_type: QueryType::GetGraphType,
+            params: serde_json::json!({ "name": type_name }),
+        };
+
+        match catalog_manager.execute("graph_type", graph_type_query) {
+            Ok(CatalogResponse::Success { data: Some(data) }) => serde_json::from_value(data)
+                .map_err(|e| {
+                    ExecutionError::SchemaValidation(format!(
+                        "Graph type '{}' could not be read: {}",
+                        type_name, e
+                    ))
+                }),
+            Ok(_) | Err(_) => Err(ExecutionError::SchemaValidation(format!(
+                "Graph type '{}' not found for CREATE GRAPH",
+                type_name
+            ))),
+        }
+    }
+}
+
 impl StatementExecutor for CreateGraphExecutor {
     fn operation_type(&self) -> OperationType {
         OperationType::CreateGraph
@@ -122,7 +153,13 @@ impl DDLStatementExecutor for CreateGraphExecutor {
         }
 
         // Step 2: Validate graph type if specified
-        let graph_type_name: Option<String> = if let Some(_spec) = &self.statement.graph_type_spec {
+        let graph_type = match &self.statement.graph_type_ref {
+            Some(type_path) => Some(Self::referenced_graph_type(type_path, catalog_manager)?),
+            None => None,
+        };
+        let graph_type_name: Option<String> = if let Some(graph_type) = &graph_type {
+            Some(graph_type.name.clone())
+        } else if let Some(_spec) = &self.statement.graph_type_spec {
             // For now, we'll use a default name. In a full implementation,
             // this would be parsed from the spec or provided as a reference
             let type_name = "DefaultGraphType"; // This should come from spec
@@ -191,7 +228,11 @@ impl DDLStatementExecutor for CreateGraphExecutor {
                     crate::catalog::operations::CatalogResponse::Success { data: _ } => {
                         // Step 2: Create the graph in storage after successful catalog creation
                         // Create empty graph in storage after successful catalog creation
-                        let empty_graph = crate::storage::GraphCache::new();
+                        let mut empty_graph = crate::storage::GraphCache::new();
+                        // Keys that reference properties refer to are looked up by RESOLVE
+                        for (label, key) in graph_type.iter().flat_map(|t| t.reference_keys()) {
+                            empty_graph.create_node_key_index(&label, &key);
+                        }
                         if let Err(e) = storage.save_graph(&full_graph_path, empty_graph) {
                             log::error!("Failed to create graph in storage: {}", e);
                             // TODO: Rollback catalog operation if storage creation fails
@@ -252,6 +293,10 @@ impl DDLStatementExecutor for CreateGraphExecutor {
                     )),
                 }
             }
+            Err(CatalogError::DuplicateEntry(_)) if self.statement.if_not_exists => {
+                let message = format!("Graph '{}' already exists (if not exists)", graph_name);
+                Ok((message, 0))
+            }
             Err(e) => Err(ExecutionError::CatalogError(format!(
                 "Failed to create graph: {}",
                 e
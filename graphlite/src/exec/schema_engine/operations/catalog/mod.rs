@@ -1,8 +1,9 @@
 // Copyright (c) 2024-2025 DeepGraph Inc.
 // SPDX-License-Identifier: Apache-2.0
 //
-//! Catalog operations (CREATE GRAPH, DROP GRAPH, CLEAR, TRUNCATE, SCHEMA)
+//! Catalog operations (CREATE GRAPH, DROP GRAPH, ALTER GRAPH, CLEAR, TRUNCATE, SCHEMA)
 
+pub mod alter_graph;
 pub mod clear_graph;
 pub mod create_graph;
 pub mod create_schema;
@@ -10,6 +11,7 @@ pub mod drop_graph;
 pub mod drop_schema;
 pub mod truncate_graph;
 
+pub use alter_graph::*;
 pub use clear_graph::*;
 pub use create_graph::*;
 pub use create_schema::*;
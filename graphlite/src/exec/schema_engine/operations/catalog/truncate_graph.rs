@@ -62,7 +62,10 @@ impl DDLStatementExecutor for TruncateGraphExecutor {
         };
 
         // Step 1: Clear all graph data from storage FIRST
-        // TRUNCATE removes all nodes and edges but keeps the graph schema
+        // TRUNCATE removes all nodes and edges but keeps the graph schema; the
+        // write lock keeps a data statement in flight from saving them back
+        let write_lock = storage.graph_write_lock(&full_path);
+        let _writing = write_lock.lock().unwrap_or_else(|e| e.into_inner());
         let empty_graph = crate::storage::GraphCache::new();
         storage.save_graph(&full_path, empty_graph).map_err(|e| {
             ExecutionError::StorageError(format!(
@@ -89,6 +89,10 @@ impl DDLStatementCoordinator {
                 let stmt_executor = ClearGraphExecutor::new(clear_graph.clone());
                 stmt_executor.execute(context, catalog_manager, &storage)
             }
+            CatalogStatement::AlterGraph(alter_graph) => {
+                let stmt_executor = AlterGraphExecutor::new(alter_graph.clone());
+                stmt_executor.execute(context, catalog_manager, &storage)
+            }
             CatalogStatement::CreateUser(create_user) => {
                 let stmt_executor = CreateUserExecutor::new(create_user.clone());
                 stmt_executor.execute(context, catalog_manager, &storage)
@@ -152,6 +156,7 @@ impl DDLStatementCoordinator {
                     }
                     CatalogStatement::CreateGraph(_)
                     | CatalogStatement::DropGraph(_)
+                    | CatalogStatement::AlterGraph(_)
                     | CatalogStatement::CreateGraphType(_)
                     | CatalogStatement::DropGraphType(_)
                     | CatalogStatement::AlterGraphType(_) => {
@@ -187,7 +192,7 @@ impl DDLStatementCoordinator {
                     session_result: None,
                     warnings: Vec::new(),
                     rows: vec![Row {
-                        values: std::collections::HashMap::from([(
+                        values: indexmap::IndexMap::from([(
                             "status".to_string(),
                             crate::storage::Value::String(message.clone()),
                         )]),
@@ -198,6 +203,7 @@ impl DDLStatementCoordinator {
                     }],
                     variables: vec!["status".to_string()],
                     execution_time_ms: execution_time,
+                    operator_timings: None,
                 })
             }
             Err(e) => {
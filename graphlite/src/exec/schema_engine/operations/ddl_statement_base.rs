@@ -45,6 +45,7 @@ pub enum DDLStatementType {
     DropSchema,
     CreateGraph,
     DropGraph,
+    AlterGraph,
     CreateGraphType,
     DropGraphType,
     TruncateGraph,
@@ -66,8 +66,9 @@ impl DDLStatementExecutor for CreateGraphTypeExecutor {
         }
 
         // Parse GraphTypeSpec to extract node and edge type definitions
-        let node_types = self.parse_vertex_types(&self.statement.graph_type_spec);
+        let mut node_types = self.parse_vertex_types(&self.statement.graph_type_spec);
         let edge_types = self.parse_edge_types(&self.statement.graph_type_spec);
+        Self::resolve_reference_keys(&mut node_types, &edge_types)?;
 
         let graph_type_def = GraphTypeDefinition {
             name: name.clone(),
@@ -103,6 +104,71 @@ impl DDLStatementExecutor for CreateGraphTypeExecutor {
 }
 
 impl CreateGraphTypeExecutor {
+    /// Constraints of a property declaration: a `ForeignKey` to `Label.key` for
+    /// `REFERENCES Label(key)`
+    fn reference_constraints(
+        prop_decl: &crate::ast::PropertyTypeDecl,
+    ) -> Vec<crate::schema::types::Constraint> {
+        use crate::schema::types::{Constraint, ForeignKeyAction};
+
+        prop_decl
+            .references
+            .iter()
+            .map(|target| Constraint::ForeignKey {
+                references: format!("{}.{}", target.label, target.key),
+                on_delete: ForeignKeyAction::NoAction,
+            })
+            .collect()
+    }
+
+    /// Check that every reference property refers to a declared key property, and
+    /// mark those key properties unique
+    fn resolve_reference_keys(
+        node_types: &mut [crate::schema::types::NodeTypeDefinition],
+        edge_types: &[crate::schema::types::EdgeTypeDefinition],
+    ) -> Result<(), ExecutionError> {
+        use crate::schema::types::Constraint;
+
+        let references: Vec<(String, String, String)> = node_types
+            .iter()
+            .flat_map(|node_type| &node_type.properties)
+            .chain(
+                edge_types
+                    .iter()
+                    .flat_map(|edge_type| &edge_type.properties),
+            )
+            .filter_map(|property| {
+                property
+                    .reference()
+                    .map(|(label, key)| (property.name.clone(), label.to_string(), key.to_string()))
+            })
+            .collect();
+
+        for (property, label, key) in references {
+            let key_property = node_types
+                .iter_mut()
+                .filter(|node_type| node_type.label == label)
+                .flat_map(|node_type| node_type.properties.iter_mut())
+                .find(|definition| definition.name == key)
+                .ok_or_else(|| {
+                    ExecutionError::SchemaValidation(format!(
+                        "Property '{}' references {}({}), which is not a property of a node type in this graph type",
+                        property, label, key
+                    ))
+                })?;
+
+            key_property.unique = true;
+            if !key_property
+                .constraints
+                .iter()
+                .any(|constraint| matches!(constraint, Constraint::Unique))
+            {
+                key_property.constraints.push(Constraint::Unique);
+            }
+        }
+        Ok(())
+    }
+
     /// Parse vertex types from GraphTypeSpec into NodeTypeDefinition
     fn parse_vertex_types(
         &self,
@@ -136,6 +202,7 @@ impl CreateGraphTypeExecutor {
                                 crate::ast::TypeSpec::Date => DataType::Date,
                                 crate::ast::TypeSpec::LocalTime { .. } => DataType::Time,
                                 crate::ast::TypeSpec::LocalDateTime { .. } => DataType::DateTime,
+                                crate::ast::TypeSpec::Json => DataType::Json,
                                 _ => DataType::String, // Default to string for unsupported types
                             };
 
@@ -149,7 +216,7 @@ impl CreateGraphTypeExecutor {
                                 deprecated: false,
                                 deprecation_message: None,
                                 validation_pattern: None,
-                                constraints: vec![],
+                                constraints: Self::reference_constraints(prop_decl),
                             }
                         })
                         .collect()
@@ -203,6 +270,7 @@ impl CreateGraphTypeExecutor {
                                 crate::ast::TypeSpec::Date => DataType::Date,
                                 crate::ast::TypeSpec::LocalTime { .. } => DataType::Time,
                                 crate::ast::TypeSpec::LocalDateTime { .. } => DataType::DateTime,
+                                crate::ast::TypeSpec::Json => DataType::Json,
                                 _ => DataType::String,
                             };
 
@@ -216,7 +284,7 @@ impl CreateGraphTypeExecutor {
                                 deprecated: false,
                                 deprecation_message: None,
                                 validation_pattern: None,
-                                constraints: vec![],
+                                constraints: Self::reference_constraints(prop_decl),
                             }
                         })
                         .collect()
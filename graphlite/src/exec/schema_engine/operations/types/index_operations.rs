@@ -18,8 +18,8 @@ thread_local! {
 
 use crate::ast::{
     AlterIndexOperation, AlterIndexStatement, CreateIndexStatement, DropIndexStatement,
-    GraphIndexTypeSpecifier, IndexStatement, IndexTypeSpecifier, OptimizeIndexStatement,
-    ReindexStatement, Value,
+    GraphIndexTypeSpecifier, IndexEntity, IndexStatement, IndexTypeSpecifier,
+    OptimizeIndexStatement, ReindexStatement, Value,
 };
 use crate::catalog::manager::CatalogManager;
 use crate::exec::schema_engine::operations::DDLStatementExecutor;
@@ -27,6 +27,7 @@ use crate::exec::write_stmt::{ExecutionContext, StatementExecutor};
 use crate::exec::{ExecutionError, QueryResult};
 use crate::schema::integration::index_validator::IndexSchemaValidator;
 use crate::storage::indexes::{GraphIndexType, IndexConfig, IndexError, IndexManager, IndexType};
+use crate::storage::json_path::JsonPath;
 use crate::storage::StorageManager;
 
 /// Coordinator for index DDL statement execution
@@ -91,7 +92,7 @@ impl IndexStatementCoordinator {
         );
 
         // Create a result row with the message for display
-        let mut row_values = std::collections::HashMap::new();
+        let mut row_values = indexmap::IndexMap::new();
         row_values.insert(
             "message".to_string(),
             crate::storage::value::Value::String(message.clone()),
@@ -112,6 +113,7 @@ impl IndexStatementCoordinator {
             rows_affected: affected,
             session_result: None,
             warnings: Vec::new(),
+            operator_timings: None,
         })
     }
 }
@@ -215,6 +217,74 @@ impl CreateIndexExecutor {
         })
     }
 
+    /// Check whether the current graph already holds a relationship index with this name
+    ///
+    /// The IndexManager only knows indexes created since startup, while
+    /// relationship indexes are persisted with their graph.
+    fn graph_has_edge_index(&self, context: &ExecutionContext, storage: &StorageManager) -> bool {
+        context
+            .get_current_graph_name()
+            .and_then(|graph_name| storage.get_graph(&graph_name).ok().flatten())
+            .is_some_and(|graph| {
+                graph
+                    .edge_property_indexes()
+                    .any(|index| index.name == self.statement.name)
+            })
+    }
+
+    /// Build a relationship property index on the current graph, returning the number of edges indexed
+    fn build_edge_property_index(
+        &self,
+        context: &ExecutionContext,
+        storage: &StorageManager,
+    ) -> Result<usize, ExecutionError> {
+        let property = match self.statement.columns.as_slice() {
+            [property] => property,
+            _ => {
+                return Err(ExecutionError::InvalidQuery(format!(
+                    "Relationship index '{}' must cover exactly one property",
+                    self.statement.name
+                )))
+            }
+        };
+
+        let graph_name = context.get_current_graph_name().ok_or_else(|| {
+            ExecutionError::InvalidQuery(
+                "No graph selected for relationship index creation".to_string(),
+            )
+        })?;
+        let write_lock = storage.graph_write_lock(&graph_name);
+        let _writing = write_lock.lock().unwrap_or_else(|e| e.into_inner());
+        let mut graph = storage
+            .get_graph(&graph_name)
+            .map_err(|e| ExecutionError::StorageError(format!("Failed to get graph: {}", e)))?
+            .ok_or_else(|| {
+                ExecutionError::StorageError(format!("Graph '{}' not found", graph_name))
+            })?;
+
+        let path = self
+            .statement
+            .json_path
+            .as_deref()
+            .map(JsonPath::parse)
+            .transpose()
+            .map_err(ExecutionError::InvalidQuery)?;
+        let indexed = graph
+            .create_edge_property_index(
+                &self.statement.name,
+                &self.statement.table,
+                property,
+                path.as_ref(),
+            )
+            .map_err(|e| ExecutionError::InvalidQuery(e.to_string()))?;
+
+        storage.save_graph(&graph_name, graph).map_err(|e| {
+            ExecutionError::StorageError(format!("Failed to save graph '{}': {}", graph_name, e))
+        })?;
+
+        Ok(indexed)
+    }
+
     /// Validate that the label and properties exist in the schema (advisory only)
     fn validate_schema(
         &self,
@@ -339,6 +409,14 @@ impl DDLStatementExecutor for CreateIndexExecutor {
         // Validate index name format
         Self::validate_index_name(&self.statement.name)?;
 
+        // Values inside JSON documents are only indexed for relationship predicates
+        if self.statement.json_path.is_some() && self.statement.entity == IndexEntity::Node {
+            return Err(ExecutionError::InvalidQuery(format!(
+                "Index '{}': JSON_GET paths can only be indexed on relationship properties",
+                self.statement.name
+            )));
+        }
+
         info!(
             "Creating index '{}' on table '{}'",
             self.statement.name, self.statement.table
@@ -355,8 +433,12 @@ impl DDLStatementExecutor for CreateIndexExecutor {
         // Convert index type
         let index_type = self.convert_index_type()?;
 
-        // Validate schema using graph type definitions
-        if let Err(e) = self.validate_schema(context, storage, catalog_manager) {
+        // Validate schema using graph type definitions (node labels only)
+        let schema_check = match self.statement.entity {
+            IndexEntity::Node => self.validate_schema(context, storage, catalog_manager),
+            IndexEntity::Edge => Ok(()),
+        };
+        if let Err(e) = schema_check {
             // Check enforcement mode from session configuration if available
             let enforcement_mode = context
                 .get_variable("schema_enforcement_mode")
@@ -401,7 +483,8 @@ impl DDLStatementExecutor for CreateIndexExecutor {
 
         // Check if index already exists using IndexManager (which checks metadata)
         // IndexManager.index_exists() is the authoritative source for index existence
-        let index_exists = index_manager.index_exists(&self.statement.name);
+        let index_exists = index_manager.index_exists(&self.statement.name)
+            || self.graph_has_edge_index(context, storage);
 
         log::debug!(
             "DEBUG CreateIndexExecutor: Checking if index exists: {}",
@@ -428,6 +511,12 @@ impl DDLStatementExecutor for CreateIndexExecutor {
         }
 
         log::debug!("DEBUG CreateIndexExecutor: Index doesn't exist, proceeding with creation");
+
+        // Relationship property indexes live on the graph itself
+        let edges_indexed = match self.statement.entity {
+            IndexEntity::Edge => Some(self.build_edge_property_index(context, storage)?),
+            IndexEntity::Node => None,
+        };
         log::debug!("DEBUG CreateIndexExecutor: Checking async runtime context");
 
         // Create the index (async operation, block on it using shared runtime)
@@ -476,9 +565,13 @@ impl DDLStatementExecutor for CreateIndexExecutor {
             "schema_name": context.get_current_schema().unwrap_or_else(|| "default".to_string()),
             "graph_name": context.get_current_graph_name(),
             "index_type": index_type_str,
-            "entity_type": "node",
+            "entity_type": match self.statement.entity {
+                IndexEntity::Node => "node",
+                IndexEntity::Edge => "edge",
+            },
             "label": self.statement.table.clone(),  // Add label for query filtering
             "properties": self.statement.columns.clone(),
+            "json_path": self.statement.json_path.clone(),
             "unique": false,
             "parameters": self.statement.options.parameters.iter()
                 .map(|(k, v)| (k.clone(), format!("{:?}", v)))
@@ -512,7 +605,13 @@ impl DDLStatementExecutor for CreateIndexExecutor {
             self.statement.name, index_type
         );
 
-        let message = format!("Index '{}' created successfully", self.statement.name);
+        let message = match edges_indexed {
+            Some(count) => format!(
+                "Index '{}' created successfully ({} relationships indexed)",
+                self.statement.name, count
+            ),
+            None => format!("Index '{}' created successfully", self.statement.name),
+        };
         Ok((message, 1))
     }
 }
@@ -541,6 +640,33 @@ impl DropIndexExecutor {
             ExecutionError::StorageError("IndexManager not initialized in storage".to_string())
         })
     }
+
+    /// Drop a relationship property index from the current graph, returning whether one existed
+    fn drop_edge_property_index(
+        &self,
+        context: &ExecutionContext,
+        storage: &StorageManager,
+    ) -> Result<bool, ExecutionError> {
+        let Some(graph_name) = context.get_current_graph_name() else {
+            return Ok(false);
+        };
+        let write_lock = storage.graph_write_lock(&graph_name);
+        let _writing = write_lock.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(mut graph) = storage
+            .get_graph(&graph_name)
+            .map_err(|e| ExecutionError::StorageError(format!("Failed to get graph: {}", e)))?
+        else {
+            return Ok(false);
+        };
+
+        if !graph.drop_edge_property_index(&self.statement.name) {
+            return Ok(false);
+        }
+        storage.save_graph(&graph_name, graph).map_err(|e| {
+            ExecutionError::StorageError(format!("Failed to save graph '{}': {}", graph_name, e))
+        })?;
+        Ok(true)
+    }
 }
 
 impl StatementExecutor for DropIndexExecutor {
@@ -566,6 +692,9 @@ impl DDLStatementExecutor for DropIndexExecutor {
     ) -> Result<(String, usize), ExecutionError> {
         info!("Dropping index '{}'", self.statement.name);
 
+        // Relationship property indexes are stored with the current graph
+        let dropped_edge_index = self.drop_edge_property_index(_context, storage)?;
+
         // Check if index exists in Catalog (single source of truth)
         let index_exists = _catalog_manager
             .execute(
@@ -577,7 +706,7 @@ impl DDLStatementExecutor for DropIndexExecutor {
             )
             .is_ok();
 
-        if !index_exists {
+        if !index_exists && !dropped_edge_index {
             if self.statement.if_exists {
                 let message = format!(
                     "Index '{}' does not exist (skipped due to IF EXISTS)",
@@ -271,7 +271,7 @@ mod tests {
     use std::collections::HashMap;
 
     fn create_test_row(id: usize, score: f64) -> Row {
-        let mut values = HashMap::new();
+        let mut values = indexmap::IndexMap::new();
         values.insert("id".to_string(), Value::Number(id as f64));
         values.insert("score".to_string(), Value::Number(score));
 
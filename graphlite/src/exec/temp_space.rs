@@ -0,0 +1,388 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! Scratch disk space for operators that spill intermediate results
+//!
+//! Sorts, aggregations and cursors that outgrow memory write their runs to
+//! files under `<db>/tmp`. Each query gets a directory of its own there, created
+//! on the first request through `ExecutionContext::temp_dir` and removed when
+//! the query's context is dropped, whether the query succeeded or failed.
+//! Whatever a crashed process left behind is wiped when the database is opened.
+//!
+//! Bytes written to temp files are charged against a quota shared by all
+//! queries (`DatabaseConfig::temp_space_quota_bytes`); a write that would exceed
+//! it fails with `ExecutionError::TempSpaceExceeded`. Usage is reported by
+//! `CALL gql.storage_stats()`.
+
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, RwLock};
+
+use super::error::ExecutionError;
+
+/// Default `DatabaseConfig::temp_space_quota_bytes`
+pub const DEFAULT_TEMP_SPACE_QUOTA_BYTES: u64 = 1024 * 1024 * 1024;
+
+/// Temp directory, inside the database directory
+const TEMP_DIR: &str = "tmp";
+
+/// Temp space usage, returned by `TempSpace::stats`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TempSpaceStats {
+    /// Directory holding the per-query directories
+    pub path: PathBuf,
+    /// Bytes all queries together may write (`None` = unlimited)
+    pub quota_bytes: Option<u64>,
+    /// Bytes held by temp files right now
+    pub used_bytes: u64,
+    /// Highest `used_bytes` since the database was opened
+    pub peak_bytes: u64,
+    /// Queries holding a temp directory right now
+    pub active_queries: usize,
+    /// Temp files created since the database was opened
+    pub files_created: u64,
+    /// Writes rejected for exceeding the quota since the database was opened
+    pub quota_rejections: u64,
+}
+
+/// Scratch space shared by the queries of a database
+#[derive(Debug)]
+pub struct TempSpace {
+    /// Temp root
+    root: PathBuf,
+    quota: RwLock<Option<u64>>,
+    used: AtomicU64,
+    peak: AtomicU64,
+    active_queries: AtomicUsize,
+    next_dir: AtomicU64,
+    files_created: AtomicU64,
+    quota_rejections: AtomicU64,
+}
+
+impl TempSpace {
+    /// Temp space in `db_path`, discarding files left by a previous process
+    pub fn new(db_path: &Path) -> Self {
+        let root = db_path.join(TEMP_DIR);
+        if root.exists() {
+            if let Err(e) = std::fs::remove_dir_all(&root) {
+                log::warn!(
+                    "Failed to remove stale temp space {}: {}",
+                    root.display(),
+                    e
+                );
+            }
+        }
+        Self {
+            root,
+            quota: RwLock::new(Some(DEFAULT_TEMP_SPACE_QUOTA_BYTES)),
+            used: AtomicU64::new(0),
+            peak: AtomicU64::new(0),
+            active_queries: AtomicUsize::new(0),
+            next_dir: AtomicU64::new(0),
+            files_created: AtomicU64::new(0),
+            quota_rejections: AtomicU64::new(0),
+        }
+    }
+
+    /// Directory holding the per-query directories
+    pub fn path(&self) -> &Path {
+        &self.root
+    }
+
+    /// Bytes all queries together may write
+    pub fn quota(&self) -> Option<u64> {
+        *self.quota.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Change the quota; `None` lets temp files grow until the volume is full
+    pub fn set_quota(&self, quota: Option<u64>) {
+        *self.quota.write().unwrap_or_else(|e| e.into_inner()) = quota;
+    }
+
+    /// Current usage
+    pub fn stats(&self) -> TempSpaceStats {
+        TempSpaceStats {
+            path: self.root.clone(),
+            quota_bytes: self.quota(),
+            used_bytes: self.used.load(Ordering::Relaxed),
+            peak_bytes: self.peak.load(Ordering::Relaxed),
+            active_queries: self.active_queries.load(Ordering::Relaxed),
+            files_created: self.files_created.load(Ordering::Relaxed),
+            quota_rejections: self.quota_rejections.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Create a directory for one query, removed when the returned value is dropped
+    pub fn query_dir(self: &Arc<Self>) -> Result<QueryTempDir, ExecutionError> {
+        let id = self.next_dir.fetch_add(1, Ordering::Relaxed);
+        let path = self.root.join(format!("query_{}", id));
+        std::fs::create_dir_all(&path).map_err(|e| {
+            ExecutionError::StorageError(format!(
+                "Failed to create temp directory {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        self.active_queries.fetch_add(1, Ordering::Relaxed);
+        Ok(QueryTempDir {
+            space: self.clone(),
+            path,
+            charged: AtomicU64::new(0),
+            next_file: AtomicU64::new(0),
+        })
+    }
+
+    /// Charge `bytes` against the quota
+    fn reserve(&self, bytes: u64) -> Result<(), ExecutionError> {
+        let quota = self.quota();
+        let mut used = self.used.load(Ordering::Relaxed);
+        loop {
+            let wanted = used.saturating_add(bytes);
+            if let Some(quota) = quota {
+                if wanted > quota {
+                    self.quota_rejections.fetch_add(1, Ordering::Relaxed);
+                    return Err(ExecutionError::TempSpaceExceeded {
+                        limit: quota,
+                        requested: wanted,
+                    });
+                }
+            }
+            match self.used.compare_exchange_weak(
+                used,
+                wanted,
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => {
+                    self.peak.fetch_max(wanted, Ordering::Relaxed);
+                    return Ok(());
+                }
+                Err(current) => used = current,
+            }
+        }
+    }
+
+    fn release(&self, bytes: u64) {
+        let _ = self
+            .used
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |used| {
+                Some(used.saturating_sub(bytes))
+            });
+    }
+}
+
+impl Drop for TempSpace {
+    fn drop(&mut self) {
+        if self.root.exists() {
+            let _ = std::fs::remove_dir_all(&self.root);
+        }
+    }
+}
+
+/// Temp directory of one query
+#[derive(Debug)]
+pub struct QueryTempDir {
+    space: Arc<TempSpace>,
+    path: PathBuf,
+    /// Bytes this query's files hold, released on drop
+    charged: AtomicU64,
+    next_file: AtomicU64,
+}
+
+impl QueryTempDir {
+    /// Directory of this query
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Bytes written to this query's temp files
+    pub fn used_bytes(&self) -> u64 {
+        self.charged.load(Ordering::Relaxed)
+    }
+
+    /// Create a new temp file; `label` only shows up in its name
+    pub fn create_file(self: &Arc<Self>, label: &str) -> Result<TempFile, ExecutionError> {
+        let id = self.next_file.fetch_add(1, Ordering::Relaxed);
+        let path = self.path.join(format!("{}_{}.tmp", label, id));
+        let file = File::options()
+            .read(true)
+            .write(true)
+            .create_new(true)
+            .open(&path)
+            .map_err(|e| {
+                ExecutionError::StorageError(format!(
+                    "Failed to create temp file {}: {}",
+                    path.display(),
+                    e
+                ))
+            })?;
+        self.space.files_created.fetch_add(1, Ordering::Relaxed);
+        Ok(TempFile {
+            dir: self.clone(),
+            path,
+            writer: BufWriter::new(file),
+        })
+    }
+}
+
+impl Drop for QueryTempDir {
+    fn drop(&mut self) {
+        if let Err(e) = std::fs::remove_dir_all(&self.path) {
+            log::warn!(
+                "Failed to remove temp directory {}: {}",
+                self.path.display(),
+                e
+            );
+        }
+        self.space.release(*self.charged.get_mut());
+        self.space.active_queries.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Append-only temp file whose size counts against the temp space quota
+#[derive(Debug)]
+pub struct TempFile {
+    /// Keeps the directory alive while the file is in use
+    dir: Arc<QueryTempDir>,
+    path: PathBuf,
+    writer: BufWriter<File>,
+}
+
+impl TempFile {
+    /// Location of the file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append `data`, failing without writing if it would exceed the quota
+    pub fn append(&mut self, data: &[u8]) -> Result<(), ExecutionError> {
+        let bytes = data.len() as u64;
+        self.dir.space.reserve(bytes)?;
+        self.dir.charged.fetch_add(bytes, Ordering::Relaxed);
+        self.writer
+            .write_all(data)
+            .map_err(|e| io_error(&self.path, e))
+    }
+
+    /// Finish writing and read the file from the start
+    pub fn into_reader(self) -> Result<BufReader<File>, ExecutionError> {
+        let path = self.path;
+        let mut file = self
+            .writer
+            .into_inner()
+            .map_err(|e| io_error(&path, e.into_error()))?;
+        file.seek(SeekFrom::Start(0))
+            .map_err(|e| io_error(&path, e))?;
+        Ok(BufReader::new(file))
+    }
+}
+
+fn io_error(path: &Path, e: std::io::Error) -> ExecutionError {
+    ExecutionError::StorageError(format!(
+        "Failed to write temp file {}: {}",
+        path.display(),
+        e
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    #[test]
+    fn test_query_dir_removed_and_released_on_drop() {
+        let db = tempfile::tempdir().unwrap();
+        let space = Arc::new(TempSpace::new(db.path()));
+
+        let dir = Arc::new(space.query_dir().unwrap());
+        let mut file = dir.create_file("sort_run").unwrap();
+        file.append(b"hello ").unwrap();
+        file.append(b"world").unwrap();
+        let mut contents = String::new();
+        file.into_reader()
+            .unwrap()
+            .read_to_string(&mut contents)
+            .unwrap();
+        assert_eq!(contents, "hello world");
+
+        let stats = space.stats();
+        assert_eq!(stats.used_bytes, 11);
+        assert_eq!(stats.active_queries, 1);
+        assert_eq!(stats.files_created, 1);
+
+        let path = dir.path().to_path_buf();
+        drop(dir);
+        assert!(!path.exists());
+        let stats = space.stats();
+        assert_eq!(stats.used_bytes, 0);
+        assert_eq!(stats.peak_bytes, 11);
+        assert_eq!(stats.active_queries, 0);
+    }
+
+    #[test]
+    fn test_quota_is_shared_by_queries() {
+        let db = tempfile::tempdir().unwrap();
+        let space = Arc::new(TempSpace::new(db.path()));
+        space.set_quota(Some(16));
+
+        let first = Arc::new(space.query_dir().unwrap());
+        let second = Arc::new(space.query_dir().unwrap());
+        first.create_file("a").unwrap().append(&[0; 10]).unwrap();
+        let err = second
+            .create_file("b")
+            .unwrap()
+            .append(&[0; 10])
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            ExecutionError::TempSpaceExceeded {
+                limit: 16,
+                requested: 20
+            }
+        ));
+        assert_eq!(space.stats().quota_rejections, 1);
+
+        drop(first);
+        second.create_file("b").unwrap().append(&[0; 10]).unwrap();
+    }
+
+    #[test]
+    fn test_context_clones_share_one_query_dir() {
+        use crate::exec::ExecutionContext;
+        use crate::storage::{StorageManager, StorageMethod, StorageType};
+
+        let db = tempfile::tempdir().unwrap();
+        let storage = Arc::new(
+            StorageManager::new(db.path(), StorageMethod::DiskOnly, StorageType::Sled).unwrap(),
+        );
+        let space = Arc::new(TempSpace::new(db.path()));
+        let context =
+            ExecutionContext::new("session".to_string(), storage).with_temp_space(space.clone());
+
+        let clone = context.clone();
+        let dir = context.temp_dir().unwrap();
+        assert_eq!(clone.temp_dir().unwrap().path(), dir.path());
+        assert_eq!(space.stats().active_queries, 1);
+
+        let path = dir.path().to_path_buf();
+        drop((dir, context));
+        assert!(path.exists());
+        drop(clone);
+        assert!(!path.exists());
+        assert_eq!(space.stats().active_queries, 0);
+    }
+
+    #[test]
+    fn test_stale_temp_space_is_wiped() {
+        let db = tempfile::tempdir().unwrap();
+        let stale = db.path().join(TEMP_DIR).join("query_0");
+        std::fs::create_dir_all(&stale).unwrap();
+        std::fs::write(stale.join("run_0.tmp"), b"left by a crash").unwrap();
+
+        let _space = TempSpace::new(db.path());
+        assert!(!db.path().join(TEMP_DIR).exists());
+    }
+}
@@ -0,0 +1,139 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! Per-operator timing of query execution
+//!
+//! With the `operator_timing` session parameter set to `true`, every query that
+//! runs a physical plan returns an `OperatorTiming` tree in
+//! `QueryResult::operator_timings`: one entry per executed operator with its
+//! wall time and output row count. Collecting it costs a clock read and a short
+//! lock per operator, cheap enough to leave on in production.
+
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Timing of one executed operator and the operators it read from
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OperatorTiming {
+    /// Physical operator, e.g. `NodeSeqScan` or `HashAggregate`
+    pub operator: String,
+    /// Rows the operator produced
+    pub rows: usize,
+    /// Wall time in microseconds, including the time spent in `children`
+    pub elapsed_us: u64,
+    /// Input operators, in execution order
+    pub children: Vec<OperatorTiming>,
+}
+
+impl OperatorTiming {
+    /// Session parameter that turns timing on (see `UserSession::get_operator_timing`)
+    pub const SESSION_PARAMETER: &'static str = "operator_timing";
+
+    /// Wall time spent in this operator alone, excluding its children
+    pub fn self_us(&self) -> u64 {
+        let children: u64 = self.children.iter().map(|child| child.elapsed_us).sum();
+        self.elapsed_us.saturating_sub(children)
+    }
+
+    /// Number of operators in this tree
+    pub fn operator_count(&self) -> usize {
+        1 + self
+            .children
+            .iter()
+            .map(OperatorTiming::operator_count)
+            .sum::<usize>()
+    }
+}
+
+/// Builds the timing tree while a plan executes
+///
+/// Operators run depth-first on one thread, so each `enter` is matched by the
+/// next `exit` at the same depth. Clones share the tree, which lets subqueries
+/// executed with a cloned context nest under the operator that ran them.
+#[derive(Debug, Clone, Default)]
+pub struct OperatorTimer {
+    state: Arc<Mutex<TimerState>>,
+}
+
+#[derive(Debug, Default)]
+struct TimerState {
+    /// Children collected so far by each operator still running
+    open: Vec<Vec<OperatorTiming>>,
+    /// Finished top-level operators
+    roots: Vec<OperatorTiming>,
+}
+
+impl OperatorTimer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// An operator starts
+    pub fn enter(&self) {
+        self.lock().open.push(Vec::new());
+    }
+
+    /// The operator started by the matching `enter` finished
+    pub fn exit(&self, operator: &str, rows: usize, elapsed: Duration) {
+        let mut state = self.lock();
+        let children = state.open.pop().unwrap_or_default();
+        let timing = OperatorTiming {
+            operator: operator.to_string(),
+            rows,
+            elapsed_us: elapsed.as_micros().min(u64::MAX as u128) as u64,
+            children,
+        };
+        match state.open.last_mut() {
+            Some(parent) => parent.push(timing),
+            None => state.roots.push(timing),
+        }
+    }
+
+    /// The tree of the plan that ran, if any operator was timed
+    ///
+    /// A statement that ran several plans (e.g. a set operation) gets a
+    /// `Statement` root over them.
+    pub fn finish(&self) -> Option<OperatorTiming> {
+        let mut state = self.lock();
+        state.open.clear();
+        let mut roots = std::mem::take(&mut state.roots);
+        match roots.len() {
+            0 => None,
+            1 => roots.pop(),
+            _ => Some(OperatorTiming {
+                operator: "Statement".to_string(),
+                rows: roots.last().map_or(0, |root| root.rows),
+                elapsed_us: roots.iter().map(|root| root.elapsed_us).sum(),
+                children: roots,
+            }),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, TimerState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timer_nests_operators() {
+        let timer = OperatorTimer::new();
+        timer.enter(); // Project
+        timer.enter(); // Filter
+        timer.enter(); // NodeSeqScan
+        timer.exit("NodeSeqScan", 10, Duration::from_micros(40));
+        timer.exit("Filter", 3, Duration::from_micros(55));
+        timer.exit("Project", 3, Duration::from_micros(60));
+
+        let root = timer.finish().unwrap();
+        assert_eq!(root.operator, "Project");
+        assert_eq!(root.operator_count(), 3);
+        assert_eq!(root.self_us(), 5);
+        assert_eq!(root.children[0].children[0].rows, 10);
+        assert!(timer.finish().is_none());
+    }
+}
@@ -42,7 +42,8 @@ impl TransactionStatementExecutor for CommitExecutor {
         let transaction_state = context.transaction_state().ok_or_else(|| {
             ExecutionError::RuntimeError("No transaction state available".to_string())
         })?;
-        transaction_state.commit_transaction()?;
+        // Outbox events appended in the transaction are written as it commits
+        transaction_state.commit_transaction_with_storage(context.storage_manager.as_ref())?;
 
         let message = "Transaction committed successfully";
 
@@ -56,6 +57,7 @@ impl TransactionStatementExecutor for CommitExecutor {
             rows_affected: 0,
             session_result: None,
             warnings: Vec::new(),
+            operator_timings: None,
         })
     }
 }
@@ -117,6 +119,7 @@ impl CommitExecutor {
             rows_affected: 0,
             session_result: None,
             warnings: Vec::new(),
+            operator_timings: None,
         })
     }
 }
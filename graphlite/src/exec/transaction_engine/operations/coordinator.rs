@@ -6,8 +6,11 @@ use crate::exec::write_stmt::{ExecutionContext, TransactionStatementExecutor};
 use crate::exec::{ExecutionError, QueryExecutor, QueryResult};
 
 use super::{
-    commit::CommitExecutor, rollback::RollbackExecutor,
-    set_characteristics::SetTransactionCharacteristicsExecutor, start::StartTransactionExecutor,
+    commit::CommitExecutor,
+    rollback::RollbackExecutor,
+    savepoint::{ReleaseSavepointExecutor, RollbackToSavepointExecutor, SavepointExecutor},
+    set_characteristics::SetTransactionCharacteristicsExecutor,
+    start::StartTransactionExecutor,
 };
 
 pub struct TransactionCoordinator;
@@ -29,6 +32,15 @@ impl TransactionCoordinator {
             TransactionStatement::Rollback(rollback_stmt) => {
                 Box::new(RollbackExecutor::new(rollback_stmt.clone()))
             }
+            TransactionStatement::Savepoint(savepoint_stmt) => {
+                Box::new(SavepointExecutor::new(savepoint_stmt.clone()))
+            }
+            TransactionStatement::RollbackToSavepoint(rollback_stmt) => {
+                Box::new(RollbackToSavepointExecutor::new(rollback_stmt.clone()))
+            }
+            TransactionStatement::ReleaseSavepoint(release_stmt) => {
+                Box::new(ReleaseSavepointExecutor::new(release_stmt.clone()))
+            }
             TransactionStatement::SetTransactionCharacteristics(set_stmt) => {
                 Box::new(SetTransactionCharacteristicsExecutor::new(set_stmt.clone()))
             }
@@ -1,11 +1,12 @@
 // Copyright (c) 2024-2025 DeepGraph Inc.
 // SPDX-License-Identifier: Apache-2.0
 //
-//! Transaction operations (BEGIN, COMMIT, ROLLBACK)
+//! Transaction operations (BEGIN, COMMIT, ROLLBACK, SAVEPOINT)
 
 pub mod commit;
 pub mod coordinator;
 pub mod rollback;
+pub mod savepoint;
 pub mod set_characteristics;
 pub mod start;
 pub mod transaction_base;
@@ -64,6 +64,7 @@ impl TransactionStatementExecutor for RollbackExecutor {
             rows_affected: 0,
             session_result: None,
             warnings: Vec::new(),
+            operator_timings: None,
         })
     }
 }
@@ -146,6 +147,7 @@ impl RollbackExecutor {
             rows_affected: 0,
             session_result: None,
             warnings: Vec::new(),
+            operator_timings: None,
         })
     }
 
@@ -164,17 +166,13 @@ impl RollbackExecutor {
         }
 
         // Get the graph name from the undo operation
-        let graph_name = match undo_op {
-            UndoOperation::InsertNode { graph_path, .. }
-            | UndoOperation::UpdateNode { graph_path, .. }
-            | UndoOperation::DeleteNode { graph_path, .. }
-            | UndoOperation::InsertEdge { graph_path, .. }
-            | UndoOperation::UpdateEdge { graph_path, .. }
-            | UndoOperation::DeleteEdge { graph_path, .. } => graph_path,
-            UndoOperation::Batch { .. } => unreachable!("Batch handled above"),
-        };
+        let graph_name = undo_op
+            .graph_path()
+            .expect("only a batch has no graph, and batches are handled above");
 
-        // Get the current graph for modification
+        // Get the current graph for modification, under the lock data statements take
+        let write_lock = unified_storage.graph_write_lock(graph_name);
+        let _writing = write_lock.lock().unwrap_or_else(|e| e.into_inner());
         let mut graph = unified_storage
             .get_graph(graph_name)
             .map_err(|e| {
@@ -0,0 +1,156 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+use crate::ast::{ReleaseSavepointStatement, RollbackToSavepointStatement, SavepointStatement};
+use crate::exec::write_stmt::{ExecutionContext, StatementExecutor, TransactionStatementExecutor};
+use crate::exec::{ExecutionError, QueryResult, Row};
+use crate::storage::value::Value;
+use crate::txn::state::OperationType;
+use std::collections::HashMap;
+
+/// Executor for `SAVEPOINT name`
+pub struct SavepointExecutor {
+    statement: SavepointStatement,
+}
+
+impl SavepointExecutor {
+    pub fn new(statement: SavepointStatement) -> Self {
+        Self { statement }
+    }
+}
+
+impl StatementExecutor for SavepointExecutor {
+    fn operation_type(&self) -> OperationType {
+        OperationType::Other
+    }
+
+    fn operation_description(&self, _context: &ExecutionContext) -> String {
+        format!("SAVEPOINT {}", self.statement.name)
+    }
+
+    fn requires_write_permission(&self) -> bool {
+        false // Transaction control doesn't require graph write permissions
+    }
+}
+
+impl TransactionStatementExecutor for SavepointExecutor {
+    fn execute_transaction_operation(
+        &self,
+        context: &ExecutionContext,
+    ) -> Result<QueryResult, ExecutionError> {
+        let transaction_state = context.transaction_state().ok_or_else(|| {
+            ExecutionError::RuntimeError("No transaction state available".to_string())
+        })?;
+        transaction_state.create_savepoint(&self.statement.name)?;
+
+        Ok(status_result(format!(
+            "Savepoint {} set",
+            self.statement.name
+        )))
+    }
+}
+
+/// Executor for `ROLLBACK TO SAVEPOINT name`
+pub struct RollbackToSavepointExecutor {
+    statement: RollbackToSavepointStatement,
+}
+
+impl RollbackToSavepointExecutor {
+    pub fn new(statement: RollbackToSavepointStatement) -> Self {
+        Self { statement }
+    }
+}
+
+impl StatementExecutor for RollbackToSavepointExecutor {
+    fn operation_type(&self) -> OperationType {
+        OperationType::Other
+    }
+
+    fn operation_description(&self, _context: &ExecutionContext) -> String {
+        format!("ROLLBACK TO SAVEPOINT {}", self.statement.name)
+    }
+
+    fn requires_write_permission(&self) -> bool {
+        false // Transaction control doesn't require graph write permissions
+    }
+}
+
+impl TransactionStatementExecutor for RollbackToSavepointExecutor {
+    fn execute_transaction_operation(
+        &self,
+        context: &ExecutionContext,
+    ) -> Result<QueryResult, ExecutionError> {
+        let transaction_state = context.transaction_state().ok_or_else(|| {
+            ExecutionError::RuntimeError("No transaction state available".to_string())
+        })?;
+
+        // Get the storage manager from context for applying undo operations
+        let storage_manager = context.storage_manager.as_ref().ok_or_else(|| {
+            ExecutionError::RuntimeError("No storage manager available".to_string())
+        })?;
+
+        transaction_state.rollback_to_savepoint(&self.statement.name, storage_manager)?;
+
+        Ok(status_result(format!(
+            "Rolled back to savepoint {}",
+            self.statement.name
+        )))
+    }
+}
+
+/// Executor for `RELEASE SAVEPOINT name`
+pub struct ReleaseSavepointExecutor {
+    statement: ReleaseSavepointStatement,
+}
+
+impl ReleaseSavepointExecutor {
+    pub fn new(statement: ReleaseSavepointStatement) -> Self {
+        Self { statement }
+    }
+}
+
+impl StatementExecutor for ReleaseSavepointExecutor {
+    fn operation_type(&self) -> OperationType {
+        OperationType::Other
+    }
+
+    fn operation_description(&self, _context: &ExecutionContext) -> String {
+        format!("RELEASE SAVEPOINT {}", self.statement.name)
+    }
+
+    fn requires_write_permission(&self) -> bool {
+        false // Transaction control doesn't require graph write permissions
+    }
+}
+
+impl TransactionStatementExecutor for ReleaseSavepointExecutor {
+    fn execute_transaction_operation(
+        &self,
+        context: &ExecutionContext,
+    ) -> Result<QueryResult, ExecutionError> {
+        let transaction_state = context.transaction_state().ok_or_else(|| {
+            ExecutionError::RuntimeError("No transaction state available".to_string())
+        })?;
+        transaction_state.release_savepoint(&self.statement.name)?;
+
+        Ok(status_result(format!(
+            "Savepoint {} released",
+            self.statement.name
+        )))
+    }
+}
+
+fn status_result(message: String) -> QueryResult {
+    QueryResult {
+        rows: vec![Row::from_values(HashMap::from([(
+            "status".to_string(),
+            Value::String(message),
+        )]))],
+        variables: vec!["status".to_string()],
+        execution_time_ms: 0,
+        rows_affected: 0,
+        session_result: None,
+        warnings: Vec::new(),
+        operator_timings: None,
+    }
+}
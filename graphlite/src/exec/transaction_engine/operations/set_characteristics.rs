@@ -85,6 +85,7 @@ impl SetTransactionCharacteristicsExecutor {
             rows_affected: 0,
             session_result: None,
             warnings: Vec::new(),
+            operator_timings: None,
         })
     }
 }
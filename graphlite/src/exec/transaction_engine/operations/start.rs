@@ -10,15 +10,38 @@ use std::collections::HashMap;
 use std::sync::RwLock;
 
 pub struct StartTransactionExecutor {
-    // Statement data passed via execute_start_transaction parameter, not stored
+    statement: StartTransactionStatement,
 }
 
 impl StartTransactionExecutor {
-    pub fn new(_statement: StartTransactionStatement) -> Self {
-        Self {}
+    pub fn new(statement: StartTransactionStatement) -> Self {
+        Self { statement }
     }
 }
 
+/// Isolation level and access mode requested by a START TRANSACTION statement
+fn requested_characteristics(
+    statement: &StartTransactionStatement,
+) -> (
+    Option<crate::txn::IsolationLevel>,
+    Option<crate::txn::state::AccessMode>,
+) {
+    let Some(ref characteristics) = statement.characteristics else {
+        return (None, None);
+    };
+    let isolation_level = characteristics.isolation_level.as_ref().map(|il| match il {
+        IsolationLevel::ReadUncommitted => crate::txn::IsolationLevel::ReadUncommitted,
+        IsolationLevel::ReadCommitted => crate::txn::IsolationLevel::ReadCommitted,
+        IsolationLevel::RepeatableRead => crate::txn::IsolationLevel::RepeatableRead,
+        IsolationLevel::Serializable => crate::txn::IsolationLevel::Serializable,
+    });
+    let access_mode = characteristics.access_mode.as_ref().map(|am| match am {
+        AccessMode::ReadOnly => crate::txn::state::AccessMode::ReadOnly,
+        AccessMode::ReadWrite => crate::txn::state::AccessMode::ReadWrite,
+    });
+    (isolation_level, access_mode)
+}
+
 impl StatementExecutor for StartTransactionExecutor {
     fn operation_type(&self) -> OperationType {
         OperationType::Begin
@@ -42,7 +65,8 @@ impl TransactionStatementExecutor for StartTransactionExecutor {
         let transaction_state = context.transaction_state().ok_or_else(|| {
             ExecutionError::RuntimeError("No transaction state available".to_string())
         })?;
-        let txn_id = transaction_state.begin_transaction()?;
+        let (isolation_level, access_mode) = requested_characteristics(&self.statement);
+        let txn_id = transaction_state.begin_transaction_with(isolation_level, access_mode)?;
 
         let message = "Transaction started successfully";
 
@@ -59,6 +83,7 @@ impl TransactionStatementExecutor for StartTransactionExecutor {
             rows_affected: 0,
             session_result: None,
             warnings: Vec::new(),
+            operator_timings: None,
         })
     }
 }
@@ -83,36 +108,9 @@ impl StartTransactionExecutor {
         }
         drop(current_txn);
 
-        // Extract isolation level and access mode from statement
-        let (isolation_level, access_mode) =
-            if let Some(ref characteristics) = statement.characteristics {
-                (
-                    characteristics.isolation_level.clone(),
-                    characteristics.access_mode.clone(),
-                )
-            } else {
-                (None, None)
-            };
-
         // Start transaction using transaction manager
-        let txn_id = transaction_manager.start_transaction(
-            isolation_level.map(|il| match il {
-                IsolationLevel::ReadUncommitted => {
-                    crate::txn::isolation::IsolationLevel::ReadUncommitted
-                }
-                IsolationLevel::ReadCommitted => {
-                    crate::txn::isolation::IsolationLevel::ReadCommitted
-                }
-                IsolationLevel::RepeatableRead => {
-                    crate::txn::isolation::IsolationLevel::RepeatableRead
-                }
-                IsolationLevel::Serializable => crate::txn::isolation::IsolationLevel::Serializable,
-            }),
-            access_mode.map(|am| match am {
-                AccessMode::ReadOnly => crate::txn::state::AccessMode::ReadOnly,
-                AccessMode::ReadWrite => crate::txn::state::AccessMode::ReadWrite,
-            }),
-        )?;
+        let (isolation_level, access_mode) = requested_characteristics(statement);
+        let txn_id = transaction_manager.start_transaction(isolation_level, access_mode)?;
 
         // Initialize transaction log for this transaction
         {
@@ -142,6 +140,7 @@ impl StartTransactionExecutor {
             rows_affected: 0,
             session_result: None,
             warnings: Vec::new(),
+            operator_timings: None,
         })
     }
 }
@@ -3,9 +3,9 @@
 //
 use crate::ast::{DataStatement, GraphExpression};
 use crate::exec::write_engine::operations::{
-    planned_insert::PlannedInsertExecutor, DataStatementExecutor, DeleteExecutor,
-    MatchDeleteExecutor, MatchInsertExecutor, MatchRemoveExecutor, MatchSetExecutor,
-    RemoveExecutor, SetExecutor,
+    planned_insert::PlannedInsertExecutor, DataStatementExecutor, DeleteEdgesOfTypeExecutor,
+    DeleteExecutor, MatchDeleteExecutor, MatchInsertExecutor, MatchRemoveExecutor,
+    MatchSetExecutor, MergeExecutor, RemoveExecutor, SetExecutor,
 };
 use crate::exec::write_stmt::ExecutionContext;
 use crate::exec::{ExecutionError, QueryResult, Row};
@@ -67,6 +67,10 @@ impl DataStatementCoordinator {
             DataStatement::MatchDelete(match_delete_stmt) => {
                 Box::new(MatchDeleteExecutor::new(match_delete_stmt.clone()))
             }
+            DataStatement::DeleteEdgesOfType(delete_edges_stmt) => {
+                Box::new(DeleteEdgesOfTypeExecutor::new(delete_edges_stmt.clone()))
+            }
+            DataStatement::Merge(merge_stmt) => Box::new(MergeExecutor::new(merge_stmt.clone())),
         };
         log::debug!("DataStatementCoordinator: Calling executor.execute()");
         let result = stmt_executor.execute(context, &storage);
@@ -84,7 +88,7 @@ impl DataStatementCoordinator {
                     rows_affected,
                     session_result: None,
                     rows: vec![Row {
-                        values: std::collections::HashMap::from([(
+                        values: indexmap::IndexMap::from([(
                             "status".to_string(),
                             crate::storage::Value::String(message.clone()),
                         )]),
@@ -96,6 +100,7 @@ impl DataStatementCoordinator {
                     variables: vec!["status".to_string()],
                     execution_time_ms: execution_time,
                     warnings: warnings.clone(),
+                    operator_timings: None,
                 };
 
                 // If there are warnings, add them to the result rows for visibility
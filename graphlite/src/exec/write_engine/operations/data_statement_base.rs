@@ -60,7 +60,10 @@ pub trait DataStatementExecutor: StatementExecutor {
             graph_name
         );
 
-        // Step 2: Get the current graph for modification
+        // Step 2: Get the current graph for modification, holding the graph's write
+        // lock until it is saved so concurrent writers do not overwrite each other
+        let write_lock = storage.graph_write_lock(graph_name);
+        let _writing = write_lock.lock().unwrap_or_else(|e| e.into_inner());
         log::debug!("UNIFIED_FLOW: Getting graph '{}' from storage", graph_name);
         let mut graph = storage
             .get_graph(graph_name)
@@ -0,0 +1,255 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+use std::cmp::Ordering;
+use std::collections::HashSet;
+
+use crate::ast::{DeleteEdgesOfTypeStatement, Expression, Literal, Operator};
+use crate::exec::write_engine::operations::DataStatementExecutor;
+use crate::exec::write_stmt::{ExecutionContext, StatementExecutor};
+use crate::exec::ExecutionError;
+use crate::storage::{Edge, GraphCache, Value};
+use crate::txn::{state::OperationType, UndoOperation};
+
+/// Executor for DELETE EDGES OF TYPE statements
+///
+/// Removes a whole relationship type (or the part of it selected by WHERE) through
+/// `GraphCache::remove_edges_by_label` instead of matching and deleting edge by edge.
+pub struct DeleteEdgesOfTypeExecutor {
+    statement: DeleteEdgesOfTypeStatement,
+}
+
+impl DeleteEdgesOfTypeExecutor {
+    /// Create a new DeleteEdgesOfTypeExecutor
+    pub fn new(statement: DeleteEdgesOfTypeStatement) -> Self {
+        Self { statement }
+    }
+
+    /// Ids of the edges of the statement's type that satisfy the WHERE condition
+    fn select_edges(
+        graph: &GraphCache,
+        label: &str,
+        condition: &Expression,
+    ) -> Result<HashSet<String>, ExecutionError> {
+        let mut selected = HashSet::new();
+        for edge in graph.get_edges_by_label(label) {
+            // NULL (unknown) conditions do not select the edge
+            if let Some(Value::Boolean(true)) = Self::evaluate(edge, condition)? {
+                selected.insert(edge.id.clone());
+            }
+        }
+        Ok(selected)
+    }
+
+    /// Evaluate an expression against an edge's properties
+    ///
+    /// Bare identifiers name edge properties; a missing property is NULL (`None`).
+    fn evaluate(edge: &Edge, expr: &Expression) -> Result<Option<Value>, ExecutionError> {
+        match expr {
+            Expression::Literal(literal) => Ok(Self::literal_to_value(literal)),
+            Expression::Variable(var) => Ok(edge
+                .properties
+                .get(&var.name)
+                .filter(|value| !matches!(value, Value::Null))
+                .cloned()),
+            Expression::Unary(unary) if unary.operator == Operator::Not => Ok(Self::as_condition(
+                Self::evaluate(edge, &unary.expression)?,
+            )?
+            .map(|b| Value::Boolean(!b))),
+            Expression::Binary(binary) => {
+                let left = Self::evaluate(edge, &binary.left)?;
+                let right = Self::evaluate(edge, &binary.right)?;
+                let result = match binary.operator {
+                    Operator::And => {
+                        match (Self::as_condition(left)?, Self::as_condition(right)?) {
+                            (Some(false), _) | (_, Some(false)) => Some(false),
+                            (Some(true), Some(true)) => Some(true),
+                            _ => None,
+                        }
+                    }
+                    Operator::Or => match (Self::as_condition(left)?, Self::as_condition(right)?) {
+                        (Some(true), _) | (_, Some(true)) => Some(true),
+                        (Some(false), Some(false)) => Some(false),
+                        _ => None,
+                    },
+                    Operator::Equal
+                    | Operator::NotEqual
+                    | Operator::LessThan
+                    | Operator::LessEqual
+                    | Operator::GreaterThan
+                    | Operator::GreaterEqual => match (left, right) {
+                        (Some(l), Some(r)) => {
+                            let ordering = Self::compare(&l, &r)?;
+                            Some(match binary.operator {
+                                Operator::Equal => ordering == Ordering::Equal,
+                                Operator::NotEqual => ordering != Ordering::Equal,
+                                Operator::LessThan => ordering == Ordering::Less,
+                                Operator::LessEqual => ordering != Ordering::Greater,
+                                Operator::GreaterThan => ordering == Ordering::Greater,
+                                _ => ordering != Ordering::Less,
+                            })
+                        }
+                        _ => None,
+                    },
+                    ref other => {
+                        return Err(ExecutionError::UnsupportedOperator(format!(
+                            "Operator {:?} is not supported in DELETE EDGES WHERE clauses",
+                            other
+                        )))
+                    }
+                };
+                Ok(result.map(Value::Boolean))
+            }
+            other => Err(ExecutionError::UnsupportedOperator(format!(
+                "Expression is not supported in DELETE EDGES WHERE clauses: {:?}",
+                other
+            ))),
+        }
+    }
+
+    /// Interpret a value as a condition (`None` = unknown)
+    fn as_condition(value: Option<Value>) -> Result<Option<bool>, ExecutionError> {
+        match value {
+            None => Ok(None),
+            Some(Value::Boolean(b)) => Ok(Some(b)),
+            Some(other) => Err(ExecutionError::TypeError(format!(
+                "Expected a boolean condition, got {:?}",
+                other
+            ))),
+        }
+    }
+
+    /// Compare two non-null values of the same type
+    fn compare(left: &Value, right: &Value) -> Result<Ordering, ExecutionError> {
+        let ordering = match (left, right) {
+            (Value::Number(l), Value::Number(r)) => l.partial_cmp(r),
+            (Value::String(l), Value::String(r)) => Some(l.cmp(r)),
+            (Value::Boolean(l), Value::Boolean(r)) => Some(l.cmp(r)),
+            _ => None,
+        };
+        ordering.ok_or_else(|| {
+            ExecutionError::TypeError(format!("Cannot compare {:?} with {:?}", left, right))
+        })
+    }
+
+    /// Convert AST literal to storage value (NULL = `None`)
+    fn literal_to_value(literal: &Literal) -> Option<Value> {
+        match literal {
+            Literal::String(s) => Some(Value::String(s.clone())),
+            Literal::Integer(i) => Some(Value::Number(*i as f64)),
+            Literal::Float(f) => Some(Value::Number(*f)),
+            Literal::Boolean(b) => Some(Value::Boolean(*b)),
+            Literal::Null => None,
+            Literal::DateTime(dt) => Some(Value::String(dt.clone())),
+            Literal::Duration(dur) => Some(Value::String(dur.clone())),
+            Literal::TimeWindow(tw) => Some(Value::String(tw.clone())),
+            Literal::Vector(vec) => Some(Value::Vector(vec.iter().map(|&f| f as f32).collect())),
+            Literal::List(list) => Some(Value::List(
+                list.iter()
+                    .map(|item| Self::literal_to_value(item).unwrap_or(Value::Null))
+                    .collect(),
+            )),
+        }
+    }
+}
+
+impl StatementExecutor for DeleteEdgesOfTypeExecutor {
+    fn operation_type(&self) -> OperationType {
+        OperationType::Delete
+    }
+
+    fn operation_description(&self, context: &ExecutionContext) -> String {
+        let graph_name = context
+            .get_graph_name()
+            .unwrap_or_else(|_| "unknown".to_string());
+        format!(
+            "DELETE EDGES OF TYPE :{} in graph '{}'",
+            self.statement.label, graph_name
+        )
+    }
+}
+
+impl DataStatementExecutor for DeleteEdgesOfTypeExecutor {
+    fn execute_modification(
+        &self,
+        graph: &mut GraphCache,
+        context: &mut ExecutionContext,
+    ) -> Result<(UndoOperation, usize), ExecutionError> {
+        let graph_name = context.get_graph_name()?;
+        let label = &self.statement.label;
+
+        // Evaluate the whole selection before removing anything, so an evaluation
+        // error leaves the graph untouched
+        let selection = match &self.statement.where_clause {
+            Some(where_clause) => Some(Self::select_edges(graph, label, &where_clause.condition)?),
+            None => None,
+        };
+
+        let removed = graph.remove_edges_by_label(label, selection.as_ref());
+        let deleted_count = removed.len();
+        log::debug!(
+            "DELETE EDGES: Removed {} :{} edges from graph '{}'",
+            deleted_count,
+            label,
+            graph_name
+        );
+
+        let undo_op = UndoOperation::Batch {
+            operations: removed
+                .into_iter()
+                .map(|edge| UndoOperation::DeleteEdge {
+                    graph_path: graph_name.clone(),
+                    edge_id: edge.id.clone(),
+                    deleted_edge: edge,
+                })
+                .collect(),
+        };
+
+        Ok((undo_op, deleted_count))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::parser::parse_query;
+    use crate::ast::{DataStatement, Statement};
+    use std::collections::HashMap;
+
+    fn tested_in(phase: Option<f64>) -> Edge {
+        Edge {
+            id: "e1".to_string(),
+            from_node: "c".to_string(),
+            to_node: "t".to_string(),
+            label: "TESTED_IN".to_string(),
+            properties: phase
+                .map(|p| HashMap::from([("phase".to_string(), Value::Number(p))]))
+                .unwrap_or_default(),
+        }
+    }
+
+    fn condition(query: &str) -> Expression {
+        match parse_query(query).unwrap().statement {
+            Statement::DataStatement(DataStatement::DeleteEdgesOfType(stmt)) => {
+                stmt.where_clause.unwrap().condition
+            }
+            other => panic!("Unexpected statement: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_where_condition_evaluation() {
+        let expr = condition("DELETE EDGES OF TYPE :TESTED_IN WHERE phase < 2 OR phase >= 4");
+        let selected = |phase| {
+            DeleteEdgesOfTypeExecutor::evaluate(&tested_in(phase), &expr)
+                .unwrap()
+                .map(|value| value == Value::Boolean(true))
+        };
+        assert_eq!(selected(Some(1.0)), Some(true));
+        assert_eq!(selected(Some(3.0)), Some(false));
+        assert_eq!(selected(None), None);
+
+        let expr = condition("DELETE EDGES OF TYPE :TESTED_IN WHERE phase = 'one'");
+        assert!(DeleteEdgesOfTypeExecutor::evaluate(&tested_in(Some(1.0)), &expr).is_err());
+    }
+}
@@ -297,6 +297,7 @@ impl DataStatementExecutor for InsertExecutor {
                                         Value::Boolean(b) => serde_json::Value::Bool(*b),
                                         Value::Null => serde_json::Value::Null,
                                         Value::Vector(vec) => serde_json::json!(vec),
+                                        Value::Json(document) => document.clone(),
                                         Value::List(list) => {
                                             // Convert list recursively (simplified for now)
                                             serde_json::json!(list)
@@ -0,0 +1,823 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+
+use uuid::Uuid;
+
+use crate::ast::{
+    Edge as EdgePattern, EdgeDirection, Expression, LabelFactor, MergeStatement,
+    Node as NodePattern, Operator, PathPattern, PatternElement, PropertyMap, SetItem,
+};
+use crate::exec::result::Row;
+use crate::exec::write_engine::operations::DataStatementExecutor;
+use crate::exec::write_stmt::{ExecutionContext, StatementExecutor};
+use crate::exec::ExecutionError;
+use crate::functions::FunctionContext;
+use crate::storage::{Edge, GraphCache, Node, Value};
+use crate::txn::{state::OperationType, UndoOperation};
+
+/// Graph element a pattern variable is bound to, by id
+#[derive(Debug, Clone, PartialEq)]
+enum Bound {
+    Node(String),
+    Edge(String),
+}
+
+/// Pattern variables of one row
+type Binding = HashMap<String, Bound>;
+
+/// A SET item evaluated for one row
+enum Assignment {
+    Property(Bound, String, Value),
+    Labels(String, Vec<String>),
+}
+
+/// Executor for MERGE statements
+///
+/// Matching and creating run on the graph the unified write flow loaded while
+/// holding the graph's write lock, so two concurrent MERGEs of the same
+/// pattern cannot both miss it and both create it. Rows are merged in order,
+/// and a row sees what the rows before it created.
+pub struct MergeExecutor {
+    statement: MergeStatement,
+}
+
+impl MergeExecutor {
+    /// Create a new MergeExecutor
+    pub fn new(statement: MergeStatement) -> Self {
+        Self { statement }
+    }
+
+    /// Reject patterns MERGE cannot create
+    fn validate(&self) -> Result<(), ExecutionError> {
+        let pattern = &self.statement.pattern;
+        if pattern.assignment.is_some() {
+            return Err(ExecutionError::UnsupportedOperator(
+                "Path variables are not supported in MERGE".to_string(),
+            ));
+        }
+
+        let matched: HashSet<&String> = self
+            .statement
+            .match_clause
+            .iter()
+            .flat_map(|match_clause| &match_clause.patterns)
+            .flat_map(|pattern| &pattern.elements)
+            .filter_map(|element| match element {
+                PatternElement::Node(node) => node.identifier.as_ref(),
+                PatternElement::Edge(edge) => edge.identifier.as_ref(),
+            })
+            .collect();
+
+        for element in &pattern.elements {
+            match element {
+                PatternElement::Node(node) => {
+                    if let Some(identifier) = node.identifier.as_ref() {
+                        if matched.contains(identifier)
+                            && (!node.labels.is_empty() || node.properties.is_some())
+                        {
+                            return Err(ExecutionError::ValidationError(format!(
+                                "Variable '{}' is bound by MATCH; MERGE cannot add labels or properties to it",
+                                identifier
+                            )));
+                        }
+                    }
+                }
+                PatternElement::Edge(edge) => {
+                    if edge.quantifier.is_some() {
+                        return Err(ExecutionError::UnsupportedOperator(
+                            "Quantified edges are not supported in MERGE".to_string(),
+                        ));
+                    }
+                    if edge.labels.len() != 1 {
+                        return Err(ExecutionError::ValidationError(
+                            "Each edge in a MERGE pattern needs exactly one label".to_string(),
+                        ));
+                    }
+                    if let Some(identifier) = edge.identifier.as_ref() {
+                        if matched.contains(identifier) {
+                            return Err(ExecutionError::ValidationError(format!(
+                                "Edge variable '{}' is bound by MATCH and cannot be merged",
+                                identifier
+                            )));
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Rows of the MATCH clause that pass WHERE; a single empty row without MATCH
+    fn matched_rows(
+        &self,
+        graph: &GraphCache,
+        context: &ExecutionContext,
+    ) -> Result<Vec<Binding>, ExecutionError> {
+        let mut rows = vec![Binding::new()];
+        let Some(match_clause) = &self.statement.match_clause else {
+            return Ok(rows);
+        };
+
+        // Comma-separated patterns join on the variables they share
+        for pattern in &match_clause.patterns {
+            let mut extended = Vec::new();
+            for row in &rows {
+                extended.extend(Self::match_path(graph, pattern, row, context)?);
+            }
+            rows = extended;
+        }
+
+        if let Some(where_clause) = &self.statement.where_clause {
+            let mut kept = Vec::new();
+            for row in rows {
+                if Self::evaluate(graph, &where_clause.condition, &row, context)?
+                    == Value::Boolean(true)
+                {
+                    kept.push(row);
+                }
+            }
+            rows = kept;
+        }
+        Ok(rows)
+    }
+
+    /// Every extension of `row` that matches `pattern`
+    fn match_path(
+        graph: &GraphCache,
+        pattern: &PathPattern,
+        row: &Binding,
+        context: &ExecutionContext,
+    ) -> Result<Vec<Binding>, ExecutionError> {
+        let mut matches = Vec::new();
+        let Some(PatternElement::Node(first)) = pattern.elements.first() else {
+            return Ok(matches);
+        };
+
+        for node_id in Self::node_candidates(graph, first, row) {
+            let mut binding = row.clone();
+            if Self::bind_node(graph, first, &node_id, &mut binding, context)? {
+                Self::extend_path(
+                    graph,
+                    &pattern.elements,
+                    1,
+                    &node_id,
+                    binding,
+                    context,
+                    &mut matches,
+                )?;
+            }
+        }
+        Ok(matches)
+    }
+
+    /// Match the pattern from `elements[index]` on, starting at node `from`
+    fn extend_path(
+        graph: &GraphCache,
+        elements: &[PatternElement],
+        index: usize,
+        from: &str,
+        binding: Binding,
+        context: &ExecutionContext,
+        matches: &mut Vec<Binding>,
+    ) -> Result<(), ExecutionError> {
+        let (Some(PatternElement::Edge(edge_pattern)), Some(PatternElement::Node(node_pattern))) =
+            (elements.get(index), elements.get(index + 1))
+        else {
+            matches.push(binding);
+            return Ok(());
+        };
+
+        for (edge, to) in Self::incident_edges(graph, from, &edge_pattern.direction) {
+            let mut extended = binding.clone();
+            if Self::bind_edge(graph, edge_pattern, edge, &mut extended, context)?
+                && Self::bind_node(graph, node_pattern, to, &mut extended, context)?
+            {
+                Self::extend_path(graph, elements, index + 2, to, extended, context, matches)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Ids of the nodes that could match `pattern` in `row`
+    fn node_candidates(graph: &GraphCache, pattern: &NodePattern, row: &Binding) -> Vec<String> {
+        match pattern.identifier.as_ref().and_then(|id| row.get(id)) {
+            Some(Bound::Node(id)) => vec![id.clone()],
+            Some(Bound::Edge(_)) => Vec::new(),
+            None => {
+                let nodes = match pattern.labels.first() {
+                    Some(label) => graph.get_nodes_by_label(label),
+                    None => graph.get_all_nodes(),
+                };
+                nodes.into_iter().map(|node| node.id.clone()).collect()
+            }
+        }
+    }
+
+    /// Edges at `node_id` in `direction`, each with the node at its other end
+    fn incident_edges<'g>(
+        graph: &'g GraphCache,
+        node_id: &str,
+        direction: &EdgeDirection,
+    ) -> Vec<(&'g Edge, &'g str)> {
+        let outgoing = graph
+            .get_outgoing_edges(node_id)
+            .into_iter()
+            .map(|edge| (edge, edge.to_node.as_str()));
+        let incoming = graph
+            .get_incoming_edges(node_id)
+            .into_iter()
+            .map(|edge| (edge, edge.from_node.as_str()));
+        match direction {
+            EdgeDirection::Outgoing => outgoing.collect(),
+            EdgeDirection::Incoming => incoming.collect(),
+            EdgeDirection::Both | EdgeDirection::Undirected => outgoing.chain(incoming).collect(),
+        }
+    }
+
+    /// Check a node against `pattern` and bind the pattern's variable to it
+    fn bind_node(
+        graph: &GraphCache,
+        pattern: &NodePattern,
+        node_id: &str,
+        binding: &mut Binding,
+        context: &ExecutionContext,
+    ) -> Result<bool, ExecutionError> {
+        let Some(node) = graph.get_node(node_id) else {
+            return Ok(false);
+        };
+        if !pattern
+            .labels
+            .iter()
+            .all(|label| node.labels.contains(label))
+        {
+            return Ok(false);
+        }
+        let expected =
+            Self::pattern_properties(graph, pattern.properties.as_ref(), binding, context)?;
+        if !expected
+            .iter()
+            .all(|(key, value)| node.properties.get(key) == Some(value))
+        {
+            return Ok(false);
+        }
+        Ok(Self::bind(
+            pattern.identifier.as_ref(),
+            Bound::Node(node.id.clone()),
+            binding,
+        ))
+    }
+
+    /// Check an edge against `pattern` and bind the pattern's variable to it
+    fn bind_edge(
+        graph: &GraphCache,
+        pattern: &EdgePattern,
+        edge: &Edge,
+        binding: &mut Binding,
+        context: &ExecutionContext,
+    ) -> Result<bool, ExecutionError> {
+        if !pattern.labels.is_empty() && !pattern.labels.contains(&edge.label) {
+            return Ok(false);
+        }
+        let expected =
+            Self::pattern_properties(graph, pattern.properties.as_ref(), binding, context)?;
+        if !expected
+            .iter()
+            .all(|(key, value)| edge.properties.get(key) == Some(value))
+        {
+            return Ok(false);
+        }
+        Ok(Self::bind(
+            pattern.identifier.as_ref(),
+            Bound::Edge(edge.id.clone()),
+            binding,
+        ))
+    }
+
+    /// Bind `identifier` to `element`; false if it is already bound to another element
+    fn bind(identifier: Option<&String>, element: Bound, binding: &mut Binding) -> bool {
+        let Some(identifier) = identifier else {
+            return true;
+        };
+        match binding.get(identifier) {
+            Some(bound) => *bound == element,
+            None => {
+                binding.insert(identifier.clone(), element);
+                true
+            }
+        }
+    }
+
+    /// Values of a pattern's property map; NULL cannot be matched or created
+    fn pattern_properties(
+        graph: &GraphCache,
+        properties: Option<&PropertyMap>,
+        row: &Binding,
+        context: &ExecutionContext,
+    ) -> Result<HashMap<String, Value>, ExecutionError> {
+        let mut values = HashMap::new();
+        for property in properties.iter().flat_map(|map| &map.properties) {
+            let value = Self::evaluate(graph, &property.value, row, context)?;
+            if value.is_null() {
+                return Err(ExecutionError::ValidationError(format!(
+                    "MERGE cannot use NULL as the value of property '{}'",
+                    property.key
+                )));
+            }
+            values.insert(property.key.clone(), value);
+        }
+        Ok(values)
+    }
+
+    /// Create the nodes and edges of the pattern that `row` does not bind
+    ///
+    /// Returns the number of elements created.
+    fn create_path(
+        graph: &mut GraphCache,
+        pattern: &PathPattern,
+        row: &mut Binding,
+        context: &ExecutionContext,
+        graph_name: &str,
+        undo_operations: &mut Vec<UndoOperation>,
+    ) -> Result<usize, ExecutionError> {
+        let mut created = 0;
+        let mut node_ids = Vec::new();
+        for element in &pattern.elements {
+            let PatternElement::Node(node_pattern) = element else {
+                continue;
+            };
+            let bound = node_pattern
+                .identifier
+                .as_ref()
+                .and_then(|identifier| row.get(identifier));
+            let node_id = match bound {
+                Some(Bound::Node(id)) => id.clone(),
+                Some(Bound::Edge(_)) => {
+                    return Err(ExecutionError::ValidationError(format!(
+                        "Variable '{}' is an edge and cannot be used as a node",
+                        node_pattern.identifier.as_deref().unwrap_or_default()
+                    )))
+                }
+                None => {
+                    let node = Node {
+                        id: format!("merge_node_{}", Uuid::new_v4().simple()),
+                        labels: node_pattern.labels.clone(),
+                        properties: Self::pattern_properties(
+                            graph,
+                            node_pattern.properties.as_ref(),
+                            row,
+                            context,
+                        )?,
+                    };
+                    let id = node.id.clone();
+                    graph.add_node(node).map_err(|e| {
+                        ExecutionError::RuntimeError(format!("Failed to create node: {}", e))
+                    })?;
+                    undo_operations.push(UndoOperation::InsertNode {
+                        graph_path: graph_name.to_string(),
+                        node_id: id.clone(),
+                    });
+                    if let Some(identifier) = &node_pattern.identifier {
+                        row.insert(identifier.clone(), Bound::Node(id.clone()));
+                    }
+                    created += 1;
+                    id
+                }
+            };
+            node_ids.push(node_id);
+        }
+
+        // Edge i of the pattern joins nodes i and i + 1
+        let edges = pattern.elements.iter().filter_map(|element| match element {
+            PatternElement::Edge(edge) => Some(edge),
+            PatternElement::Node(_) => None,
+        });
+        for (i, edge_pattern) in edges.enumerate() {
+            let (left, right) = (&node_ids[i], &node_ids[i + 1]);
+            let (from_node, to_node) = match edge_pattern.direction {
+                EdgeDirection::Incoming => (right.clone(), left.clone()),
+                _ => (left.clone(), right.clone()),
+            };
+            let edge = Edge {
+                id: format!("merge_edge_{}", Uuid::new_v4().simple()),
+                from_node,
+                to_node,
+                label: edge_pattern.labels[0].clone(),
+                properties: Self::pattern_properties(
+                    graph,
+                    edge_pattern.properties.as_ref(),
+                    row,
+                    context,
+                )?,
+            };
+            let id = edge.id.clone();
+            graph.add_edge(edge).map_err(|e| {
+                ExecutionError::RuntimeError(format!("Failed to create edge: {}", e))
+            })?;
+            undo_operations.push(UndoOperation::InsertEdge {
+                graph_path: graph_name.to_string(),
+                edge_id: id.clone(),
+            });
+            if let Some(identifier) = &edge_pattern.identifier {
+                row.insert(identifier.clone(), Bound::Edge(id));
+            }
+            created += 1;
+        }
+        Ok(created)
+    }
+
+    /// Apply ON CREATE or ON MATCH items to the elements `row` binds
+    ///
+    /// Every value is evaluated before anything changes, so all items see the
+    /// elements as they were. Returns the number of properties and labels set.
+    fn apply_set_items(
+        graph: &mut GraphCache,
+        items: &[SetItem],
+        row: &Binding,
+        context: &ExecutionContext,
+        graph_name: &str,
+        undo_operations: &mut Vec<UndoOperation>,
+    ) -> Result<usize, ExecutionError> {
+        let mut assignments = Vec::new();
+        for item in items {
+            match item {
+                SetItem::Property { property, value } => {
+                    let element = row.get(&property.object).cloned().ok_or_else(|| {
+                        ExecutionError::ExpressionError(format!(
+                            "Variable '{}' is not bound in MERGE",
+                            property.object
+                        ))
+                    })?;
+                    let value = Self::evaluate(graph, value, row, context)?;
+                    assignments.push(Assignment::Property(
+                        element,
+                        property.property.clone(),
+                        value,
+                    ));
+                }
+                SetItem::Label { variable, labels } => {
+                    let Some(Bound::Node(node_id)) = row.get(variable) else {
+                        return Err(ExecutionError::ExpressionError(format!(
+                            "Variable '{}' is not a node bound in MERGE",
+                            variable
+                        )));
+                    };
+                    let labels = labels
+                        .terms
+                        .iter()
+                        .flat_map(|term| &term.factors)
+                        .filter_map(|factor| match factor {
+                            LabelFactor::Identifier(label) => Some(label.clone()),
+                            _ => None,
+                        })
+                        .collect();
+                    assignments.push(Assignment::Labels(node_id.clone(), labels));
+                }
+                SetItem::Variable { variable, .. } => {
+                    return Err(ExecutionError::UnsupportedOperator(format!(
+                        "SET {} = ... is not supported in MERGE; set its properties one by one",
+                        variable
+                    )))
+                }
+            }
+        }
+
+        let mut saved = HashSet::new();
+        let mut updated = 0;
+        for assignment in assignments {
+            match assignment {
+                Assignment::Property(Bound::Node(id), key, value) => {
+                    Self::save_node_state(graph, &id, graph_name, &mut saved, undo_operations);
+                    if let Some(node) = graph.get_node_mut(&id) {
+                        node.set_property(key, value);
+                        updated += 1;
+                    }
+                }
+                Assignment::Property(Bound::Edge(id), key, value) => {
+                    if saved.insert(id.clone()) {
+                        if let Some(edge) = graph.get_edge(&id) {
+                            undo_operations.push(UndoOperation::UpdateEdge {
+                                graph_path: graph_name.to_string(),
+                                edge_id: id.clone(),
+                                old_properties: edge.properties.clone(),
+                                old_label: edge.label.clone(),
+                            });
+                        }
+                    }
+                    if let Some(edge) = graph.get_edge_mut(&id) {
+                        edge.set_property(key, value);
+                        updated += 1;
+                    }
+                }
+                Assignment::Labels(id, labels) => {
+                    Self::save_node_state(graph, &id, graph_name, &mut saved, undo_operations);
+                    if let Some(node) = graph.get_node_mut(&id) {
+                        for label in labels {
+                            if !node.labels.contains(&label) {
+                                node.labels.push(label);
+                                updated += 1;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        Ok(updated)
+    }
+
+    /// Record a node's properties and labels for rollback, once per statement
+    fn save_node_state(
+        graph: &GraphCache,
+        node_id: &str,
+        graph_name: &str,
+        saved: &mut HashSet<String>,
+        undo_operations: &mut Vec<UndoOperation>,
+    ) {
+        if !saved.insert(node_id.to_string()) {
+            return;
+        }
+        if let Some(node) = graph.get_node(node_id) {
+            undo_operations.push(UndoOperation::UpdateNode {
+                graph_path: graph_name.to_string(),
+                node_id: node_id.to_string(),
+                old_properties: node.properties.clone(),
+                old_labels: node.labels.clone(),
+            });
+        }
+    }
+
+    /// Evaluate an expression for `row`; properties of bound variables are read
+    /// from the graph as it is now
+    fn evaluate(
+        graph: &GraphCache,
+        expr: &Expression,
+        row: &Binding,
+        context: &ExecutionContext,
+    ) -> Result<Value, ExecutionError> {
+        match expr {
+            Expression::PropertyAccess(access) => {
+                let properties = match row.get(&access.object) {
+                    Some(Bound::Node(id)) => graph.get_node(id).map(|node| &node.properties),
+                    Some(Bound::Edge(id)) => graph.get_edge(id).map(|edge| &edge.properties),
+                    None => {
+                        return Err(ExecutionError::ExpressionError(format!(
+                            "Variable '{}' is not bound in MERGE",
+                            access.object
+                        )))
+                    }
+                };
+                Ok(properties
+                    .and_then(|properties| properties.get(&access.property))
+                    .cloned()
+                    .unwrap_or(Value::Null))
+            }
+            Expression::Variable(variable) => match row.get(&variable.name) {
+                Some(Bound::Node(id) | Bound::Edge(id)) => Ok(Value::String(id.clone())),
+                None => context.evaluate_simple_expression(expr),
+            },
+            Expression::Unary(unary) => {
+                let value = Self::evaluate(graph, &unary.expression, row, context)?;
+                match (&unary.operator, value) {
+                    (_, Value::Null) => Ok(Value::Null),
+                    (Operator::Not, Value::Boolean(b)) => Ok(Value::Boolean(!b)),
+                    (Operator::Minus, Value::Number(n)) => Ok(Value::Number(-n)),
+                    (operator, value) => Err(ExecutionError::TypeError(format!(
+                        "Cannot apply {:?} to {:?}",
+                        operator, value
+                    ))),
+                }
+            }
+            Expression::Binary(binary) => {
+                let left = Self::evaluate(graph, &binary.left, row, context)?;
+                let right = Self::evaluate(graph, &binary.right, row, context)?;
+                Self::evaluate_binary(&binary.operator, left, right)
+            }
+            Expression::FunctionCall(call) => {
+                let registry = context.function_registry.as_ref().ok_or_else(|| {
+                    ExecutionError::RuntimeError(
+                        "Function registry not available in execution context".to_string(),
+                    )
+                })?;
+                let function = registry.get(&call.name).ok_or_else(|| {
+                    ExecutionError::UnsupportedOperator(format!(
+                        "Function not found: {}",
+                        call.name
+                    ))
+                })?;
+                let arguments = call
+                    .arguments
+                    .iter()
+                    .map(|argument| Self::evaluate(graph, argument, row, context))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let function_context = FunctionContext::with_storage(
+                    vec![Row::new()],
+                    context.variables.clone(),
+                    arguments,
+                    context.storage_manager.clone(),
+                    context.current_graph.clone(),
+                    context.get_current_graph_name(),
+                );
+                function.execute(&function_context).map_err(|e| {
+                    ExecutionError::UnsupportedOperator(format!("Function execution error: {}", e))
+                })
+            }
+            _ => context.evaluate_simple_expression(expr),
+        }
+    }
+
+    /// Apply a binary operator; NULL operands give NULL except where AND and OR
+    /// are decided by the other operand
+    fn evaluate_binary(
+        operator: &Operator,
+        left: Value,
+        right: Value,
+    ) -> Result<Value, ExecutionError> {
+        match (operator, &left, &right) {
+            (Operator::And, Value::Boolean(false), _)
+            | (Operator::And, _, Value::Boolean(false)) => return Ok(Value::Boolean(false)),
+            (Operator::Or, Value::Boolean(true), _) | (Operator::Or, _, Value::Boolean(true)) => {
+                return Ok(Value::Boolean(true))
+            }
+            _ => {}
+        }
+        if left.is_null() || right.is_null() {
+            return Ok(Value::Null);
+        }
+
+        let result = match (operator, &left, &right) {
+            // Both true for AND and both false for OR by now
+            (Operator::And | Operator::Or, Value::Boolean(l), Value::Boolean(_)) => {
+                Value::Boolean(*l)
+            }
+            (Operator::Plus, Value::Number(l), Value::Number(r)) => Value::Number(l + r),
+            (Operator::Minus, Value::Number(l), Value::Number(r)) => Value::Number(l - r),
+            (Operator::Star, Value::Number(l), Value::Number(r)) => Value::Number(l * r),
+            (Operator::Slash | Operator::Percent, Value::Number(_), Value::Number(r))
+                if *r == 0.0 =>
+            {
+                return Err(ExecutionError::RuntimeError("Division by zero".to_string()))
+            }
+            (Operator::Slash, Value::Number(l), Value::Number(r)) => Value::Number(l / r),
+            (Operator::Percent, Value::Number(l), Value::Number(r)) => Value::Number(l % r),
+            (Operator::Plus | Operator::Concat, Value::String(l), Value::String(r)) => {
+                Value::String(format!("{}{}", l, r))
+            }
+            (Operator::Equal, _, _) => Value::Boolean(left == right),
+            (Operator::NotEqual, _, _) => Value::Boolean(left != right),
+            (
+                Operator::LessThan
+                | Operator::LessEqual
+                | Operator::GreaterThan
+                | Operator::GreaterEqual,
+                _,
+                _,
+            ) => {
+                let ordering = match (&left, &right) {
+                    (Value::Number(l), Value::Number(r)) => l.partial_cmp(r),
+                    (Value::String(l), Value::String(r)) => Some(l.cmp(r)),
+                    (Value::Boolean(l), Value::Boolean(r)) => Some(l.cmp(r)),
+                    _ => None,
+                }
+                .ok_or_else(|| {
+                    ExecutionError::TypeError(format!("Cannot compare {:?} with {:?}", left, right))
+                })?;
+                Value::Boolean(match operator {
+                    Operator::LessThan => ordering == Ordering::Less,
+                    Operator::LessEqual => ordering != Ordering::Greater,
+                    Operator::GreaterThan => ordering == Ordering::Greater,
+                    _ => ordering != Ordering::Less,
+                })
+            }
+            _ => {
+                return Err(ExecutionError::UnsupportedOperator(format!(
+                    "Operator {:?} is not supported on {:?} and {:?} in MERGE",
+                    operator, left, right
+                )))
+            }
+        };
+        Ok(result)
+    }
+}
+
+impl StatementExecutor for MergeExecutor {
+    fn operation_type(&self) -> OperationType {
+        OperationType::Update
+    }
+
+    fn operation_description(&self, context: &ExecutionContext) -> String {
+        let graph_name = context
+            .get_graph_name()
+            .unwrap_or_else(|_| "unknown".to_string());
+        format!("MERGE in graph '{}'", graph_name)
+    }
+}
+
+impl DataStatementExecutor for MergeExecutor {
+    fn execute_modification(
+        &self,
+        graph: &mut GraphCache,
+        context: &mut ExecutionContext,
+    ) -> Result<(UndoOperation, usize), ExecutionError> {
+        self.validate()?;
+        let graph_name = context.get_graph_name()?;
+        let pattern = &self.statement.pattern;
+
+        let mut undo_operations = Vec::new();
+        let mut affected = 0;
+        let (mut created, mut matched) = (0, 0);
+        for row in self.matched_rows(graph, context)? {
+            let matches = Self::match_path(graph, pattern, &row, context)?;
+            if matches.is_empty() {
+                let mut row = row;
+                affected += Self::create_path(
+                    graph,
+                    pattern,
+                    &mut row,
+                    context,
+                    &graph_name,
+                    &mut undo_operations,
+                )?;
+                affected += Self::apply_set_items(
+                    graph,
+                    &self.statement.on_create,
+                    &row,
+                    context,
+                    &graph_name,
+                    &mut undo_operations,
+                )?;
+                created += 1;
+            } else {
+                for row in &matches {
+                    affected += Self::apply_set_items(
+                        graph,
+                        &self.statement.on_match,
+                        row,
+                        context,
+                        &graph_name,
+                        &mut undo_operations,
+                    )?;
+                }
+                matched += matches.len();
+            }
+        }
+        log::debug!(
+            "MERGE: {} pattern(s) created and {} matched in graph '{}'",
+            created,
+            matched,
+            graph_name
+        );
+
+        // Undone in reverse, so updates are reverted before the elements they
+        // changed are removed
+        undo_operations.reverse();
+        Ok((
+            UndoOperation::Batch {
+                operations: undo_operations,
+            },
+            affected,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_binary_null_handling() {
+        let evaluate =
+            |operator, left, right| MergeExecutor::evaluate_binary(&operator, left, right).unwrap();
+        assert_eq!(
+            evaluate(Operator::Plus, Value::Null, Value::Number(1.0)),
+            Value::Null
+        );
+        assert_eq!(
+            evaluate(Operator::And, Value::Null, Value::Boolean(false)),
+            Value::Boolean(false)
+        );
+        assert_eq!(
+            evaluate(Operator::Or, Value::Null, Value::Boolean(true)),
+            Value::Boolean(true)
+        );
+        assert_eq!(
+            evaluate(Operator::And, Value::Boolean(true), Value::Null),
+            Value::Null
+        );
+        assert_eq!(
+            evaluate(
+                Operator::Concat,
+                Value::String("a".to_string()),
+                Value::String("b".to_string())
+            ),
+            Value::String("ab".to_string())
+        );
+        assert!(MergeExecutor::evaluate_binary(
+            &Operator::Slash,
+            Value::Number(1.0),
+            Value::Number(0.0)
+        )
+        .is_err());
+    }
+}
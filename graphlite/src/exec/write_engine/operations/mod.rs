@@ -1,17 +1,19 @@
 // Copyright (c) 2024-2025 DeepGraph Inc.
 // SPDX-License-Identifier: Apache-2.0
 //
-//! Write operations (INSERT, SET, DELETE, REMOVE)
+//! Write operations (INSERT, SET, DELETE, DELETE EDGES OF TYPE, REMOVE, MERGE)
 //! Data modification logic for the write engine
 
 pub mod coordinator;
 pub mod data_statement_base;
 pub mod delete;
+pub mod delete_edges;
 pub mod insert;
 pub mod match_delete;
 pub mod match_insert;
 pub mod match_remove;
 pub mod match_set;
+pub mod merge;
 pub mod planned_insert;
 pub mod remove;
 pub mod set;
@@ -19,9 +21,11 @@ pub mod set;
 pub use coordinator::*;
 pub use data_statement_base::*;
 pub use delete::*;
+pub use delete_edges::*;
 pub use match_delete::*;
 pub use match_insert::*;
 pub use match_remove::*;
 pub use match_set::*;
+pub use merge::*;
 pub use remove::*;
 pub use set::*;
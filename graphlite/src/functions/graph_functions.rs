@@ -124,24 +124,6 @@ impl Function for LabelsFunction {
                         }
                     }
                 }
-
-                // Method 3: Try storage_manager with all available graphs
-                if let Some(ref storage_manager) = context.storage_manager {
-                    if let Ok(graph_names) = storage_manager.get_graph_names() {
-                        for graph_name in graph_names {
-                            if let Ok(Some(graph)) = storage_manager.get_graph(&graph_name) {
-                                if let Some(node) = graph.get_node(&node_id) {
-                                    let labels: Vec<Value> = node
-                                        .labels
-                                        .iter()
-                                        .map(|label| Value::String(label.clone()))
-                                        .collect();
-                                    return Ok(Value::List(labels));
-                                }
-                            }
-                        }
-                    }
-                }
             }
             _ => {
                 // For unsupported input types, return empty labels
@@ -221,6 +203,7 @@ impl Function for TypeFunction {
             Value::TimeWindow(_) => Ok(Value::String("TIMEWINDOW".to_string())),
             Value::Path(_) => Ok(Value::String("PATH".to_string())),
             Value::Temporal(_) => Ok(Value::String("TEMPORAL".to_string())),
+            Value::Json(_) => Ok(Value::String("JSON".to_string())),
         }
     }
 }
@@ -636,3 +619,48 @@ impl Function for SizeFunction {
         }
     }
 }
+
+/// PATH_LENGTH function - returns the number of edges in a path
+#[derive(Debug)]
+pub struct PathLengthFunction;
+
+impl PathLengthFunction {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Function for PathLengthFunction {
+    fn name(&self) -> &str {
+        "PATH_LENGTH"
+    }
+
+    fn description(&self) -> &str {
+        "Returns the number of edges in a path"
+    }
+
+    fn argument_count(&self) -> usize {
+        1
+    }
+
+    fn return_type(&self) -> &str {
+        "NUMBER"
+    }
+
+    fn execute(&self, context: &FunctionContext) -> FunctionResult<Value> {
+        if context.arguments.len() != 1 {
+            return Err(FunctionError::InvalidArgumentCount {
+                expected: 1,
+                actual: context.arguments.len(),
+            });
+        }
+
+        match &context.arguments[0] {
+            Value::Path(path) => Ok(Value::Number(path.length() as f64)),
+            Value::Null => Ok(Value::Null),
+            value => Err(FunctionError::InvalidArgumentType {
+                message: format!("Expected PATH, got {:?}", value),
+            }),
+        }
+    }
+}
@@ -0,0 +1,282 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! JSON document function implementations
+//!
+//! This module contains functions for schema-less JSON property values:
+//! - JSON: Builds a JSON document from a string of JSON text or a GQL value
+//! - JSON_GET: Returns the value at a JSONPath in a document
+//! - JSON_EXISTS: Checks whether a document has a value at a JSONPath
+
+use super::function_trait::{Function, FunctionContext, FunctionError, FunctionResult};
+use crate::storage::json_path::{self, JsonPath};
+use crate::storage::Value;
+
+/// Document argument of JSON_GET and JSON_EXISTS
+///
+/// Strings are parsed as JSON text and lists are read as JSON arrays.
+fn document_argument(value: &Value) -> FunctionResult<Option<serde_json::Value>> {
+    match value {
+        Value::Null => Ok(None),
+        Value::String(text) => {
+            serde_json::from_str(text)
+                .map(Some)
+                .map_err(|e| FunctionError::InvalidArgumentType {
+                    message: format!("First argument is not valid JSON: {}", e),
+                })
+        }
+        Value::Json(_) | Value::List(_) | Value::Array(_) => json_path::value_to_json(value)
+            .map(Some)
+            .ok_or_else(|| FunctionError::InvalidArgumentType {
+                message: "First argument holds values with no JSON form".to_string(),
+            }),
+        other => Err(FunctionError::InvalidArgumentType {
+            message: format!(
+                "First argument must be a JSON document or JSON text, got {}",
+                other.type_name()
+            ),
+        }),
+    }
+}
+
+/// Path argument of JSON_GET and JSON_EXISTS
+fn path_argument(value: &Value) -> FunctionResult<JsonPath> {
+    let path = value
+        .as_string()
+        .ok_or_else(|| FunctionError::InvalidArgumentType {
+            message: "Second argument must be a JSON path string".to_string(),
+        })?;
+    JsonPath::parse(path).map_err(|message| FunctionError::InvalidArgumentType { message })
+}
+
+// ==============================================================================
+// JSON FUNCTION
+// ==============================================================================
+
+/// JSON function - builds a JSON document value
+#[derive(Debug)]
+pub struct JsonFunction;
+
+impl JsonFunction {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Function for JsonFunction {
+    fn name(&self) -> &str {
+        "JSON"
+    }
+
+    fn description(&self) -> &str {
+        "Parses JSON text into a JSON document, or converts a value to one"
+    }
+
+    fn argument_count(&self) -> usize {
+        1 // JSON(text_or_value)
+    }
+
+    fn execute(&self, context: &FunctionContext) -> FunctionResult<Value> {
+        context.validate_argument_count(1)?;
+
+        match context.get_argument(0)? {
+            Value::Null => Ok(Value::Null),
+            Value::String(text) => serde_json::from_str(text).map(Value::Json).map_err(|e| {
+                FunctionError::InvalidArgumentType {
+                    message: format!("Invalid JSON text: {}", e),
+                }
+            }),
+            other => json_path::value_to_json(other)
+                .map(Value::Json)
+                .ok_or_else(|| FunctionError::InvalidArgumentType {
+                    message: format!("{} values cannot be converted to JSON", other.type_name()),
+                }),
+        }
+    }
+
+    fn return_type(&self) -> &str {
+        "Json"
+    }
+
+    fn graph_context_required(&self) -> bool {
+        false // JSON functions are pure scalar functions
+    }
+}
+
+// ==============================================================================
+// JSON_GET FUNCTION
+// ==============================================================================
+
+/// JSON_GET function - extracts the value at a JSONPath
+///
+/// Scalars come back as GQL values, arrays and objects as JSON documents, and a
+/// path with nothing at it gives NULL.
+#[derive(Debug)]
+pub struct JsonGetFunction;
+
+impl JsonGetFunction {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Function for JsonGetFunction {
+    fn name(&self) -> &str {
+        "JSON_GET"
+    }
+
+    fn description(&self) -> &str {
+        "Returns the value at a JSONPath in a JSON document, or NULL if there is none"
+    }
+
+    fn argument_count(&self) -> usize {
+        2 // JSON_GET(document, path)
+    }
+
+    fn execute(&self, context: &FunctionContext) -> FunctionResult<Value> {
+        context.validate_argument_count(2)?;
+
+        let path = path_argument(context.get_argument(1)?)?;
+        let Some(document) = document_argument(context.get_argument(0)?)? else {
+            return Ok(Value::Null);
+        };
+
+        Ok(path
+            .select(&document)
+            .map(json_path::json_to_value)
+            .unwrap_or(Value::Null))
+    }
+
+    fn return_type(&self) -> &str {
+        "Any"
+    }
+
+    fn graph_context_required(&self) -> bool {
+        false // JSON functions are pure scalar functions
+    }
+}
+
+// ==============================================================================
+// JSON_EXISTS FUNCTION
+// ==============================================================================
+
+/// JSON_EXISTS function - checks whether a JSONPath selects a value
+///
+/// A member holding JSON null exists; a NULL document gives NULL.
+#[derive(Debug)]
+pub struct JsonExistsFunction;
+
+impl JsonExistsFunction {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Function for JsonExistsFunction {
+    fn name(&self) -> &str {
+        "JSON_EXISTS"
+    }
+
+    fn description(&self) -> &str {
+        "Returns true if a JSON document has a value at a JSONPath"
+    }
+
+    fn argument_count(&self) -> usize {
+        2 // JSON_EXISTS(document, path)
+    }
+
+    fn execute(&self, context: &FunctionContext) -> FunctionResult<Value> {
+        context.validate_argument_count(2)?;
+
+        let path = path_argument(context.get_argument(1)?)?;
+        let Some(document) = document_argument(context.get_argument(0)?)? else {
+            return Ok(Value::Null);
+        };
+
+        Ok(Value::Boolean(path.select(&document).is_some()))
+    }
+
+    fn return_type(&self) -> &str {
+        "Boolean"
+    }
+
+    fn graph_context_required(&self) -> bool {
+        false // JSON functions are pure scalar functions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    fn call(func: &dyn Function, arguments: Vec<Value>) -> FunctionResult<Value> {
+        func.execute(&FunctionContext::new(vec![], HashMap::new(), arguments))
+    }
+
+    fn path(text: &str) -> Value {
+        Value::String(text.to_string())
+    }
+
+    #[test]
+    fn test_json_parses_text_and_converts_values() {
+        let func = JsonFunction::new();
+        assert_eq!(
+            call(&func, vec![path(r#"{"a": [1, 2]}"#)]).unwrap(),
+            Value::Json(json!({"a": [1, 2]}))
+        );
+        assert_eq!(
+            call(&func, vec![Value::List(vec![Value::Number(1.0)])]).unwrap(),
+            Value::Json(json!([1.0]))
+        );
+        assert!(call(&func, vec![Value::Null]).unwrap().is_null());
+        assert!(call(&func, vec![path("{not json")]).is_err());
+    }
+
+    #[test]
+    fn test_json_get() {
+        let func = JsonGetFunction::new();
+        let document = Value::Json(json!({"a": {"b": "x", "c": [1, 2]}}));
+
+        assert_eq!(
+            call(&func, vec![document.clone(), path("$.a.b")]).unwrap(),
+            Value::String("x".to_string())
+        );
+        assert_eq!(
+            call(&func, vec![document.clone(), path("$.a.c")]).unwrap(),
+            Value::Json(json!([1, 2]))
+        );
+        assert_eq!(
+            call(&func, vec![document.clone(), path("$.a.c[1]")]).unwrap(),
+            Value::Number(2.0)
+        );
+        assert!(call(&func, vec![document.clone(), path("$.missing")])
+            .unwrap()
+            .is_null());
+        assert_eq!(
+            call(&func, vec![path(r#"{"n": 3}"#), path("$.n")]).unwrap(),
+            Value::Number(3.0)
+        );
+        assert!(call(&func, vec![document, path("a.b")]).is_err());
+        assert!(call(&func, vec![Value::Number(1.0), path("$")]).is_err());
+    }
+
+    #[test]
+    fn test_json_exists() {
+        let func = JsonExistsFunction::new();
+        let document = Value::Json(json!({"a": null}));
+
+        assert_eq!(
+            call(&func, vec![document.clone(), path("$.a")]).unwrap(),
+            Value::Boolean(true)
+        );
+        assert_eq!(
+            call(&func, vec![document, path("$.b")]).unwrap(),
+            Value::Boolean(false)
+        );
+        assert!(call(&func, vec![Value::Null, path("$.a")])
+            .unwrap()
+            .is_null());
+    }
+}
@@ -9,6 +9,7 @@
 mod aggregate_functions;
 mod function_trait;
 mod graph_functions;
+mod json_functions;
 pub mod list_functions;
 mod mathematical_functions;
 mod null_functions;
@@ -230,6 +231,14 @@ impl FunctionRegistry {
             Box::new(list_functions::ListReverseFunction::new()),
         );
 
+        // Register JSON document functions
+        registry.register("JSON", Box::new(json_functions::JsonFunction::new()));
+        registry.register("JSON_GET", Box::new(json_functions::JsonGetFunction::new()));
+        registry.register(
+            "JSON_EXISTS",
+            Box::new(json_functions::JsonExistsFunction::new()),
+        );
+
         // Register ISO GQL special functions (predicates)
         registry.register(
             "ALL_DIFFERENT",
@@ -251,6 +260,10 @@ impl FunctionRegistry {
             Box::new(graph_functions::PropertiesFunction::new()),
         );
         registry.register("SIZE", Box::new(graph_functions::SizeFunction::new()));
+        registry.register(
+            "PATH_LENGTH",
+            Box::new(graph_functions::PathLengthFunction::new()),
+        );
         registry.register(
             "INFERRED_LABELS",
             Box::new(graph_functions::InferredLabelsFunction::new()),
@@ -50,14 +50,54 @@ pub(crate) mod txn;
 pub(crate) mod types;
 
 // Re-export the public API - QueryCoordinator is the only entry point
-pub use coordinator::{QueryCoordinator, QueryInfo, QueryPlan, QueryResult, QueryType, Row};
+pub use coordinator::{
+    ActiveQuery, BatchError, BulkEdge, BulkNode, CacheStatistics, CatalogProblem,
+    ConformanceReport, ConformanceResult, ConformanceStatus, CsvImportOptions, DatabaseConfig,
+    DatabaseStats, DiskSpaceStatus, Feature, GraphStatistics, GraphWriteMetrics, ImportSummary,
+    IndexStatistics, LockHolder, Notation, NumberFormat, OperatorTiming, QueryCoordinator,
+    QueryErrorKind, QueryInfo, QueryLimits, QueryPlan, QueryResult, QueryType, Row, SessionHandle,
+    VerifyReport, WriteLimit, WriteMetrics,
+};
 
-// Re-export session types for SessionMode configuration
-pub use session::SessionMode;
+// Re-export result compression for servers and bindings that ship results over the wire
+#[cfg(feature = "compression")]
+pub use exec::ContentEncoding;
+
+// Re-export file readers for callers that build bulk records themselves
+pub use coordinator::{key_text, read_csv_records, read_json_lines_records};
+
+// Re-export session types for SessionMode configuration and history inspection
+pub use session::{HistoryEntry, SessionMode};
+
+// Re-export decoded WAL statements returned by QueryCoordinator::decode_wal
+pub use txn::decode::DecodedStatement;
+
+// Re-export storage integrity types returned by QueryCoordinator::check_storage
+pub use storage::integrity::{
+    CorruptRecord, DanglingEdge, ReferenceCheckReport, StorageCheckReport,
+};
+
+// Re-export storage backend selection for DatabaseConfig
+pub use storage::StorageType;
 
 // Re-export Value type (needed for inspecting query results in Row.values)
 pub use storage::Value;
 
+/// Plan-shape assertions for optimizer regression tests (feature `plan-testing`)
+///
+/// ```ignore
+/// use graphlite::plan_testing::{assert_plan_contains, op};
+///
+/// let plan = coordinator.explain_query("MATCH (u:User)-[:FOLLOWS]->(f) RETURN u, count(f)")?;
+/// assert_plan_contains(&plan, op("AdjacencyCount").with_label("FOLLOWS"));
+/// ```
+#[cfg(feature = "plan-testing")]
+pub mod plan_testing {
+    pub use crate::plan::shape::{
+        assert_plan_contains, assert_plan_not_contains, op, PlanMatcher, PlanShape, ShapeNode,
+    };
+}
+
 /// GraphLite version
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 
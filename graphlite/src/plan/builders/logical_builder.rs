@@ -16,7 +16,8 @@ use crate::ast::{
     SetOperationType, Variable,
 };
 use crate::plan::logical::{
-    EntityType, JoinType, LogicalNode, LogicalPlan, ProjectExpression, SortExpression, VariableInfo,
+    EntityType, JoinType, LogicalNode, LogicalPlan, ProjectExpression, SortExpression,
+    VariableInfo, HAVING_AGGREGATE_PREFIX,
 };
 use crate::plan::optimizer::PlanningError;
 
@@ -214,6 +215,24 @@ impl LogicalBuilder {
             logical_plan = logical_plan.apply_filter(where_clause.condition.clone());
         }
 
+        // Plan HAVING against the aggregated rows (requires GROUP BY)
+        let mut having_aggregates = Vec::new();
+        let having_condition = match &query.having_clause {
+            Some(having_clause) => {
+                if query.group_clause.is_none() {
+                    return Err(PlanningError::InvalidQuery(
+                        "HAVING clause requires GROUP BY clause".to_string(),
+                    ));
+                }
+                Some(self.plan_having_condition(
+                    &having_clause.condition,
+                    &query.return_clause,
+                    &mut having_aggregates,
+                ))
+            }
+            None => None,
+        };
+
         // Process GROUP BY clause (must come before RETURN for aggregation)
         if let Some(group_clause) = &query.group_clause {
             let project_expressions = self.plan_return_clause(&query.return_clause, &context)?;
@@ -223,9 +242,22 @@ impl LogicalBuilder {
             // Check if there are any aggregate functions in the project expressions
             let has_aggregates = self.contains_aggregate_functions(&project_expressions);
 
-            // Apply aggregation
-            logical_plan = logical_plan
-                .apply_aggregation(group_expressions.clone(), project_expressions.clone());
+            // Apply aggregation, including the aggregates only HAVING uses
+            let aggregate_expressions = project_expressions
+                .iter()
+                .cloned()
+                .chain(having_aggregates)
+                .collect();
+            logical_plan = logical_plan.apply_grouping_sets_aggregation(
+                group_expressions,
+                group_clause.grouping_sets.clone(),
+                aggregate_expressions,
+            );
+
+            // Filter the groups before they are projected
+            if let Some(condition) = having_condition {
+                logical_plan = logical_plan.apply_having(condition);
+            }
 
             // If there are no aggregates, we need to add a projection node to ensure
             // the GROUP BY columns are properly projected in the output
@@ -266,21 +298,6 @@ impl LogicalBuilder {
             }
         }
 
-        // Process HAVING clause (must come after GROUP BY)
-        if let Some(having_clause) = &query.having_clause {
-            if query.group_clause.is_none() {
-                return Err(PlanningError::InvalidQuery(
-                    "HAVING clause requires GROUP BY clause".to_string(),
-                ));
-            }
-            // Resolve aliases in HAVING clause expressions
-            let resolved_condition = self.resolve_having_expression_with_aliases(
-                &having_clause.condition,
-                &query.return_clause,
-            );
-            logical_plan = logical_plan.apply_having(resolved_condition);
-        }
-
         // Process ORDER BY clause
         if let Some(order_clause) = &query.order_clause {
             let sort_expressions = self.plan_order_clause(order_clause, &context)?;
@@ -374,6 +391,21 @@ impl LogicalBuilder {
         // Check if RETURN clause contains aggregate functions
         let has_aggregates = self.contains_aggregate_functions(&project_expressions);
 
+        // Plan HAVING against the aggregated rows
+        let mut having_aggregates = Vec::new();
+        let having_condition = return_query.having_clause.as_ref().map(|having_clause| {
+            self.plan_having_condition(
+                &having_clause.condition,
+                &return_query.return_clause,
+                &mut having_aggregates,
+            )
+        });
+        let aggregate_expressions: Vec<ProjectExpression> = project_expressions
+            .iter()
+            .cloned()
+            .chain(having_aggregates)
+            .collect();
+
         if let Some(group_clause) = &return_query.group_clause {
             // Explicit GROUP BY - always apply aggregation with alias resolution
             let group_expressions = self.plan_group_clause_with_aliases(
@@ -381,32 +413,31 @@ impl LogicalBuilder {
                 &return_query.return_clause,
                 &context,
             )?;
-            logical_plan = logical_plan.apply_aggregation(group_expressions, project_expressions);
-        } else if has_aggregates {
+            logical_plan = logical_plan.apply_grouping_sets_aggregation(
+                group_expressions,
+                group_clause.grouping_sets.clone(),
+                aggregate_expressions,
+            );
+        } else if has_aggregates || aggregate_expressions.len() > project_expressions.len() {
             // Implicit aggregation - apply aggregation with empty GROUP BY
             let empty_group_expressions = Vec::new();
             logical_plan =
-                logical_plan.apply_aggregation(empty_group_expressions, project_expressions);
+                logical_plan.apply_aggregation(empty_group_expressions, aggregate_expressions);
         } else {
             // Normal projection
             logical_plan = logical_plan.apply_projection(project_expressions);
         }
 
+        // Process HAVING clause if present
+        if let Some(condition) = having_condition {
+            logical_plan = logical_plan.apply_having(condition);
+        }
+
         // Apply DISTINCT if specified
         if return_query.return_clause.distinct == crate::ast::DistinctQualifier::Distinct {
             logical_plan = logical_plan.apply_distinct();
         }
 
-        // Process HAVING clause if present
-        if let Some(having_clause) = &return_query.having_clause {
-            // Resolve aliases in HAVING clause expressions
-            let resolved_condition = self.resolve_having_expression_with_aliases(
-                &having_clause.condition,
-                &return_query.return_clause,
-            );
-            logical_plan = logical_plan.apply_having(resolved_condition);
-        }
-
         // Process ORDER BY clause if present
         if let Some(order_clause) = &return_query.order_clause {
             let sort_expressions: Vec<_> = order_clause
@@ -835,88 +866,118 @@ impl LogicalBuilder {
         }
     }
 
-    /// Resolve HAVING expression with aliases
-    /// Originally: optimizer.rs line 1366
-    fn resolve_having_expression_with_aliases(
+    /// Plan a HAVING condition over the rows produced by aggregation
+    ///
+    /// Aggregates computed by RETURN under an alias read that column. Any other
+    /// aggregate is appended to `having_aggregates` under a hidden column name
+    /// for the aggregation to compute, and read from there. Aliases of grouping
+    /// keys are replaced by the key expression.
+    fn plan_having_condition(
         &self,
         expr: &Expression,
         return_clause: &ReturnClause,
+        having_aggregates: &mut Vec<ProjectExpression>,
     ) -> Expression {
+        let column = |name: String| {
+            Expression::Variable(Variable {
+                name,
+                location: crate::ast::Location::default(),
+            })
+        };
+
         match expr {
             Expression::Variable(Variable { name, .. }) => {
-                // Try to find this variable name as an alias in the RETURN clause
-                for return_item in &return_clause.items {
-                    if let Some(alias) = &return_item.alias {
-                        if alias == name {
-                            // Found the alias! Use a variable reference to the alias instead
-                            return Expression::Variable(Variable {
-                                name: alias.clone(),
-                                location: crate::ast::Location::default(),
-                            });
-                        }
+                let aliased = return_clause
+                    .items
+                    .iter()
+                    .find(|item| item.alias.as_deref() == Some(name.as_str()));
+                match aliased {
+                    Some(item) if !self.is_aggregate_expression(&item.expression) => {
+                        item.expression.clone()
                     }
+                    _ => expr.clone(),
                 }
-                // Not found as alias, keep as-is
-                expr.clone()
             }
-            Expression::FunctionCall(func_call) => {
-                // Check if this function call appears in the RETURN clause by comparing manually
-                for return_item in &return_clause.items {
-                    if let Expression::FunctionCall(return_func_call) = &return_item.expression {
-                        // Compare function name and arguments
-                        if return_func_call.name == func_call.name
-                            && return_func_call.arguments.len() == func_call.arguments.len()
-                        {
-                            // For now, assume they match if name and arg count match
-                            // (A more sophisticated comparison would be needed for complex cases)
-                            if let Some(alias) = &return_item.alias {
-                                return Expression::Variable(Variable {
-                                    name: alias.clone(),
-                                    location: crate::ast::Location::default(),
-                                });
-                            }
-                        }
-                    }
-                }
-                // Recursively resolve arguments
-                let mut resolved_args = Vec::new();
-                for arg in &func_call.arguments {
-                    resolved_args
-                        .push(self.resolve_having_expression_with_aliases(arg, return_clause));
+            Expression::FunctionCall(func_call) if self.is_aggregate_expression(expr) => {
+                let returned = return_clause.items.iter().find(|item| {
+                    matches!(&item.expression, Expression::FunctionCall(returned)
+                        if Self::same_aggregate(returned, func_call))
+                });
+                if let Some(alias) = returned.and_then(|item| item.alias.clone()) {
+                    return column(alias);
                 }
+
+                let existing = having_aggregates.iter().find(|aggregate| {
+                    matches!(&aggregate.expression, Expression::FunctionCall(hidden)
+                        if Self::same_aggregate(hidden, func_call))
+                });
+                let name = match existing.and_then(|aggregate| aggregate.alias.clone()) {
+                    Some(name) => name,
+                    None => {
+                        let name =
+                            format!("{}{}", HAVING_AGGREGATE_PREFIX, having_aggregates.len());
+                        having_aggregates.push(ProjectExpression {
+                            expression: expr.clone(),
+                            alias: Some(name.clone()),
+                        });
+                        name
+                    }
+                };
+                column(name)
+            }
+            Expression::FunctionCall(func_call) => {
                 Expression::FunctionCall(crate::ast::FunctionCall {
                     name: func_call.name.clone(),
-                    arguments: resolved_args,
+                    arguments: func_call
+                        .arguments
+                        .iter()
+                        .map(|arg| {
+                            self.plan_having_condition(arg, return_clause, having_aggregates)
+                        })
+                        .collect(),
                     distinct: func_call.distinct.clone(),
+                    order_clause: func_call.order_clause.clone(),
+                    limit_clause: func_call.limit_clause.clone(),
                     location: func_call.location.clone(),
                 })
             }
             Expression::Binary(binary_expr) => Expression::Binary(BinaryExpression {
-                left: Box::new(
-                    self.resolve_having_expression_with_aliases(&binary_expr.left, return_clause),
-                ),
+                left: Box::new(self.plan_having_condition(
+                    &binary_expr.left,
+                    return_clause,
+                    having_aggregates,
+                )),
                 operator: binary_expr.operator.clone(),
-                right: Box::new(
-                    self.resolve_having_expression_with_aliases(&binary_expr.right, return_clause),
-                ),
+                right: Box::new(self.plan_having_condition(
+                    &binary_expr.right,
+                    return_clause,
+                    having_aggregates,
+                )),
                 location: binary_expr.location.clone(),
             }),
-            Expression::Unary(unary_expr) => {
-                Expression::Unary(crate::ast::UnaryExpression {
-                    operator: unary_expr.operator.clone(),
-                    expression: Box::new(self.resolve_having_expression_with_aliases(
-                        &unary_expr.expression,
-                        return_clause,
-                    )),
-                    location: unary_expr.location.clone(),
-                })
-            }
-            _ => {
-                // For other expressions, return as-is
-                expr.clone()
-            }
+            Expression::Unary(unary_expr) => Expression::Unary(crate::ast::UnaryExpression {
+                operator: unary_expr.operator.clone(),
+                expression: Box::new(self.plan_having_condition(
+                    &unary_expr.expression,
+                    return_clause,
+                    having_aggregates,
+                )),
+                location: unary_expr.location.clone(),
+            }),
+            _ => expr.clone(),
         }
     }
+
+    /// Whether two aggregate calls compute the same value
+    fn same_aggregate(a: &crate::ast::FunctionCall, b: &crate::ast::FunctionCall) -> bool {
+        let canonical = |name: &str| match name.to_uppercase().as_str() {
+            "AVERAGE" => "AVG".to_string(),
+            upper => upper.to_string(),
+        };
+        canonical(&a.name) == canonical(&b.name)
+            && format!("{:?}", a.distinct) == format!("{:?}", b.distinct)
+            && format!("{:?}", a.arguments) == format!("{:?}", b.arguments)
+    }
 }
 
 impl Default for LogicalBuilder {
@@ -980,6 +1041,8 @@ mod tests {
                     location: dummy_location(),
                 })],
                 distinct: crate::ast::DistinctQualifier::None,
+                order_clause: None,
+                limit_clause: None,
                 location: dummy_location(),
             }),
             alias: Some("count".to_string()),
@@ -999,6 +1062,8 @@ mod tests {
                     location: dummy_location(),
                 })],
                 distinct: crate::ast::DistinctQualifier::None,
+                order_clause: None,
+                limit_clause: None,
                 location: dummy_location(),
             }),
             alias: Some("total".to_string()),
@@ -1028,6 +1093,8 @@ mod tests {
             name: "COUNT".to_string(),
             arguments: vec![],
             distinct: crate::ast::DistinctQualifier::None,
+            order_clause: None,
+            limit_clause: None,
             location: dummy_location(),
         });
 
@@ -1041,6 +1108,8 @@ mod tests {
             name: "AVG".to_string(),
             arguments: vec![],
             distinct: crate::ast::DistinctQualifier::None,
+            order_clause: None,
+            limit_clause: None,
             location: dummy_location(),
         });
 
@@ -1054,6 +1123,8 @@ mod tests {
             name: "MIN".to_string(),
             arguments: vec![],
             distinct: crate::ast::DistinctQualifier::None,
+            order_clause: None,
+            limit_clause: None,
             location: dummy_location(),
         });
 
@@ -1067,6 +1138,8 @@ mod tests {
             name: "MAX".to_string(),
             arguments: vec![],
             distinct: crate::ast::DistinctQualifier::None,
+            order_clause: None,
+            limit_clause: None,
             location: dummy_location(),
         });
 
@@ -1080,6 +1153,8 @@ mod tests {
             name: "COLLECT".to_string(),
             arguments: vec![],
             distinct: crate::ast::DistinctQualifier::None,
+            order_clause: None,
+            limit_clause: None,
             location: dummy_location(),
         });
 
@@ -1093,6 +1168,8 @@ mod tests {
             name: "count".to_string(), // lowercase
             arguments: vec![],
             distinct: crate::ast::DistinctQualifier::None,
+            order_clause: None,
+            limit_clause: None,
             location: dummy_location(),
         });
 
@@ -1106,6 +1183,8 @@ mod tests {
             name: "toUpper".to_string(),
             arguments: vec![],
             distinct: crate::ast::DistinctQualifier::None,
+            order_clause: None,
+            limit_clause: None,
             location: dummy_location(),
         });
 
@@ -1147,6 +1226,8 @@ mod tests {
                     name: "COUNT".to_string(),
                     arguments: vec![],
                     distinct: crate::ast::DistinctQualifier::None,
+                    order_clause: None,
+                    limit_clause: None,
                     location: dummy_location(),
                 }),
                 alias: Some("count".to_string()),
@@ -1165,6 +1246,8 @@ mod tests {
                 name: "COUNT".to_string(),
                 arguments: vec![],
                 distinct: crate::ast::DistinctQualifier::None,
+                order_clause: None,
+                limit_clause: None,
                 location: dummy_location(),
             }),
             alias: Some("count".to_string()),
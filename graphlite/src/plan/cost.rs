@@ -183,6 +183,36 @@ impl CostModel {
                 cost
             }
 
+            PhysicalNode::VariableLengthExpand {
+                input,
+                estimated_rows,
+                ..
+            } => {
+                let mut cost = self.estimate_node_cost(input, stats);
+                let expand_cost = self.estimate_expand_cost(*estimated_rows, false);
+                cost.add(&expand_cost);
+                cost
+            }
+
+            PhysicalNode::ShortestPath {
+                input,
+                estimated_rows,
+                ..
+            } => {
+                let mut cost = self.estimate_node_cost(input, stats);
+                let search_cost = self.estimate_expand_cost(*estimated_rows, false);
+                cost.add(&search_cost);
+                cost
+            }
+
+            PhysicalNode::AdjacencyCount { input, .. } => {
+                // One index lookup per input row, like a filter over the input
+                let mut cost = self.estimate_node_cost(input, stats);
+                let count_cost = self.estimate_filter_cost(input.get_row_count(), 1.0);
+                cost.add(&count_cost);
+                cost
+            }
+
             PhysicalNode::Filter {
                 input, selectivity, ..
             } => {
@@ -10,6 +10,9 @@
 pub mod cost;
 pub mod optimizer;
 pub mod pattern_optimization;
+pub mod pinning;
+#[cfg(any(test, feature = "plan-testing"))]
+pub mod shape;
 pub mod trace;
 
 // Phase 2 refactoring: New module structure
@@ -8,7 +8,8 @@
 //! logical equivalence transformations.
 
 use crate::ast::{
-    EdgeDirection, Expression, PathPattern, PathQuantifier, PathType, PatternElement,
+    EdgeDirection, Expression, LimitClause, OrderClause, PathPattern, PathQuantifier, PathSearch,
+    PathType, PatternElement,
 };
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -88,6 +89,9 @@ pub struct NodeIdentifier {
     pub is_reference: bool,
 }
 
+/// Name prefix of aggregate columns computed only for a HAVING condition
+pub const HAVING_AGGREGATE_PREFIX: &str = "__having_";
+
 /// Logical plan node
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum LogicalNode {
@@ -125,6 +129,44 @@ pub enum LogicalNode {
         input: Box<LogicalNode>,
     },
 
+    /// Expand from a node along between `min_hops` and `max_hops` edges
+    ///
+    /// No node repeats within a path, which also bounds an open-ended
+    /// quantifier. `edge_variable` binds the list of edges walked and
+    /// `path_variable` the path, extending a path an earlier hop of the same
+    /// pattern bound.
+    VariableLengthExpand {
+        from_variable: String,
+        edge_variable: Option<String>,
+        to_variable: String,
+        path_variable: Option<String>,
+        edge_labels: Vec<String>,
+        direction: EdgeDirection,
+        edge_properties: Option<HashMap<String, Expression>>,
+        to_labels: Vec<String>,
+        min_hops: u32,
+        max_hops: Option<u32>,
+        input: Box<LogicalNode>,
+    },
+
+    /// Search the shortest paths between the end points bound in each input row
+    ///
+    /// A bidirectional breadth-first search, so longer paths are never walked.
+    /// `all` keeps every shortest path of a row instead of one.
+    ShortestPath {
+        from_variable: String,
+        edge_variable: Option<String>,
+        to_variable: String,
+        path_variable: Option<String>,
+        edge_labels: Vec<String>,
+        direction: EdgeDirection,
+        edge_properties: Option<HashMap<String, Expression>>,
+        min_hops: u32,
+        max_hops: Option<u32>,
+        all: bool,
+        input: Box<LogicalNode>,
+    },
+
     /// Filter rows based on condition
     Filter {
         condition: Expression,
@@ -149,13 +191,21 @@ pub enum LogicalNode {
     Union { inputs: Vec<LogicalNode>, all: bool },
 
     /// Apply aggregation
+    ///
+    /// `grouping_sets` holds the GROUPING SETS / ROLLUP groupings as indexes
+    /// into `group_by`; `None` groups by all of `group_by` once.
     Aggregate {
         group_by: Vec<Expression>,
+        grouping_sets: Option<Vec<Vec<usize>>>,
         aggregates: Vec<AggregateExpression>,
         input: Box<LogicalNode>,
     },
 
     /// HAVING filter for post-aggregation filtering
+    ///
+    /// Aggregates in the condition refer to aggregate columns of the input;
+    /// columns named with `HAVING_AGGREGATE_PREFIX` were computed for the
+    /// condition alone and are dropped from its output.
     Having {
         condition: Expression,
         input: Box<LogicalNode>,
@@ -295,6 +345,12 @@ pub struct AggregateExpression {
     pub function: AggregateFunction,
     pub expression: Expression,
     pub alias: Option<String>,
+    /// ORDER BY of an ordered aggregate: COLLECT(x ORDER BY x.date DESC)
+    #[serde(default)]
+    pub order_clause: Option<OrderClause>,
+    /// LIMIT of an ordered aggregate: COLLECT(x ORDER BY x.date LIMIT 5)
+    #[serde(default)]
+    pub limit_clause: Option<LimitClause>,
 }
 
 /// Aggregate functions
@@ -345,6 +401,11 @@ impl LogicalPlan {
             return Err("Empty path pattern".to_string());
         }
 
+        // Shortest paths are always simple, whatever the path type
+        if let Some(search) = pattern.search {
+            return Self::create_shortest_path(pattern, search);
+        }
+
         // If path type is specified and not WALK, use PathTraversal
         if let Some(path_type) = &pattern.path_type {
             if *path_type != PathType::Walk {
@@ -391,20 +452,44 @@ impl LogicalPlan {
                                         .clone()
                                         .unwrap_or_else(|| format!("_node_{}", i + 2));
 
-                                    let expand = LogicalNode::Expand {
-                                        from_variable: variable,
-                                        edge_variable,
-                                        to_variable: to_variable.clone(),
-                                        edge_labels: edge.labels.clone(),
-                                        direction: edge.direction.clone(),
-                                        properties: edge.properties.as_ref().map(|props| {
-                                            props
-                                                .properties
-                                                .iter()
-                                                .map(|p| (p.key.clone(), p.value.clone()))
-                                                .collect()
-                                        }),
-                                        input: Box::new(current_node.unwrap()),
+                                    let edge_properties = edge.properties.as_ref().map(|props| {
+                                        props
+                                            .properties
+                                            .iter()
+                                            .map(|p| (p.key.clone(), p.value.clone()))
+                                            .collect()
+                                    });
+
+                                    // Quantified edges and edges of a named path walk
+                                    // hop by hop so the edges and path can be bound
+                                    let expand = if edge.quantifier.is_some()
+                                        || pattern.assignment.is_some()
+                                    {
+                                        let (min_hops, max_hops) =
+                                            Self::quantifier_bounds(edge.quantifier.as_ref())?;
+                                        LogicalNode::VariableLengthExpand {
+                                            from_variable: variable,
+                                            edge_variable,
+                                            to_variable: to_variable.clone(),
+                                            path_variable: pattern.assignment.clone(),
+                                            edge_labels: edge.labels.clone(),
+                                            direction: edge.direction.clone(),
+                                            edge_properties,
+                                            to_labels: next_node.labels.clone(),
+                                            min_hops,
+                                            max_hops,
+                                            input: Box::new(current_node.unwrap()),
+                                        }
+                                    } else {
+                                        LogicalNode::Expand {
+                                            from_variable: variable,
+                                            edge_variable,
+                                            to_variable: to_variable.clone(),
+                                            edge_labels: edge.labels.clone(),
+                                            direction: edge.direction.clone(),
+                                            properties: edge_properties,
+                                            input: Box::new(current_node.unwrap()),
+                                        }
                                     };
 
                                     current_node = Some(expand);
@@ -452,6 +537,107 @@ impl LogicalPlan {
         current_node.ok_or_else(|| "Failed to create logical plan from pattern".to_string())
     }
 
+    /// Minimum and maximum hop counts of an edge quantifier; no maximum for
+    /// open-ended quantifiers
+    fn quantifier_bounds(
+        quantifier: Option<&PathQuantifier>,
+    ) -> Result<(u32, Option<u32>), String> {
+        let bounds = match quantifier {
+            None => (1, Some(1)),
+            Some(PathQuantifier::Optional) => (0, Some(1)),
+            Some(PathQuantifier::Exact(n)) => (*n, Some(*n)),
+            Some(PathQuantifier::Range { min, max }) => (*min, Some(*max)),
+            Some(PathQuantifier::AtLeast(min)) => (*min, None),
+            Some(PathQuantifier::AtMost(max)) => (0, Some(*max)),
+        };
+        if let (min, Some(max)) = bounds {
+            if min > max {
+                return Err(format!(
+                    "Invalid edge quantifier: minimum {} is greater than maximum {}",
+                    min, max
+                ));
+            }
+        }
+        Ok(bounds)
+    }
+
+    /// Create a ShortestPath node searching between every pair of the pattern's
+    /// end points
+    fn create_shortest_path(
+        pattern: &PathPattern,
+        search: PathSearch,
+    ) -> Result<LogicalNode, String> {
+        let (start, edge, end) = match pattern.elements.as_slice() {
+            [PatternElement::Node(start), PatternElement::Edge(edge), PatternElement::Node(end)] => {
+                (start, edge, end)
+            }
+            _ => {
+                return Err(format!(
+                    "{} needs a pattern of one relationship between two nodes",
+                    search.as_str()
+                ))
+            }
+        };
+        let (min_hops, max_hops) = Self::quantifier_bounds(edge.quantifier.as_ref())?;
+        if min_hops > 1 {
+            return Err(format!(
+                "{} supports a minimum of at most one hop, got {}",
+                search.as_str(),
+                min_hops
+            ));
+        }
+
+        let scan = |node: &crate::ast::Node, variable: &str| LogicalNode::NodeScan {
+            variable: variable.to_string(),
+            labels: node.labels.clone(),
+            properties: node.properties.as_ref().map(|props| {
+                props
+                    .properties
+                    .iter()
+                    .map(|p| (p.key.clone(), p.value.clone()))
+                    .collect()
+            }),
+        };
+        let from_variable = start
+            .identifier
+            .clone()
+            .unwrap_or_else(|| "_node_0".to_string());
+        let to_variable = end
+            .identifier
+            .clone()
+            .unwrap_or_else(|| "_node_2".to_string());
+        let input = if from_variable == to_variable {
+            scan(start, &from_variable)
+        } else {
+            LogicalNode::Join {
+                join_type: JoinType::Cross,
+                condition: None,
+                left: Box::new(scan(start, &from_variable)),
+                right: Box::new(scan(end, &to_variable)),
+            }
+        };
+
+        Ok(LogicalNode::ShortestPath {
+            from_variable,
+            edge_variable: edge.identifier.clone(),
+            to_variable,
+            path_variable: pattern.assignment.clone(),
+            edge_labels: edge.labels.clone(),
+            direction: edge.direction.clone(),
+            edge_properties: edge.properties.as_ref().map(|props| {
+                props
+                    .properties
+                    .iter()
+                    .map(|p| (p.key.clone(), p.value.clone()))
+                    .collect()
+            }),
+            min_hops,
+            max_hops,
+            all: search == PathSearch::AllShortest,
+            input: Box::new(input),
+        })
+    }
+
     /// Create a PathTraversal node for path types that need special handling
     fn create_path_traversal(
         pattern: &PathPattern,
@@ -569,8 +755,18 @@ impl LogicalPlan {
 
     /// Apply aggregation to the plan
     pub fn apply_aggregation(
+        self,
+        group_by: Vec<Expression>,
+        project_expressions: Vec<ProjectExpression>,
+    ) -> Self {
+        self.apply_grouping_sets_aggregation(group_by, None, project_expressions)
+    }
+
+    /// Apply aggregation over GROUPING SETS of `group_by` to the plan
+    pub fn apply_grouping_sets_aggregation(
         mut self,
         group_by: Vec<Expression>,
+        grouping_sets: Option<Vec<Vec<usize>>>,
         project_expressions: Vec<ProjectExpression>,
     ) -> Self {
         // Convert project expressions to aggregate expressions, preserving order
@@ -599,6 +795,8 @@ impl LogicalPlan {
                     function: aggregate_function,
                     expression: arg_expr,
                     alias: expr.alias.clone(),
+                    order_clause: func_call.order_clause.as_deref().cloned(),
+                    limit_clause: func_call.limit_clause.as_deref().cloned(),
                 });
             }
             // For non-aggregate expressions in group context, they should be in GROUP BY
@@ -607,6 +805,7 @@ impl LogicalPlan {
         // Create aggregate node
         self.root = LogicalNode::Aggregate {
             group_by,
+            grouping_sets,
             aggregates,
             input: Box::new(self.root),
         };
@@ -855,6 +1054,37 @@ impl LogicalNode {
                 }
                 vars
             }
+            LogicalNode::VariableLengthExpand {
+                from_variable,
+                edge_variable,
+                to_variable,
+                path_variable,
+                input,
+                ..
+            } => {
+                let mut vars = input.get_variables();
+                vars.push(from_variable.clone());
+                vars.extend(edge_variable.iter().cloned());
+                vars.push(to_variable.clone());
+                vars.extend(path_variable.iter().cloned());
+                vars
+            }
+
+            LogicalNode::ShortestPath {
+                from_variable,
+                edge_variable,
+                to_variable,
+                path_variable,
+                input,
+                ..
+            } => {
+                let mut vars = input.get_variables();
+                vars.push(from_variable.clone());
+                vars.extend(edge_variable.iter().cloned());
+                vars.push(to_variable.clone());
+                vars.extend(path_variable.iter().cloned());
+                vars
+            }
 
             // Subquery cases
             LogicalNode::ExistsSubquery {
@@ -1001,6 +1231,25 @@ impl LogicalNode {
                     PathType::AcyclicPath => base_cardinality * 5, // Fewest due to strict constraints
                 }
             }
+            LogicalNode::VariableLengthExpand {
+                input,
+                min_hops,
+                max_hops,
+                ..
+            } => {
+                // Average fanout per hop, for each hop count the quantifier allows
+                let hop_counts = max_hops.map_or(10, |max| max - min_hops + 1) as usize;
+                input.estimate_cardinality() * 5 * hop_counts
+            }
+
+            LogicalNode::ShortestPath { input, all, .. } => {
+                // Most pairs of end points are joined by a single shortest path
+                if *all {
+                    input.estimate_cardinality() * 2
+                } else {
+                    input.estimate_cardinality()
+                }
+            }
 
             // Subquery cardinality estimates
             LogicalNode::ExistsSubquery { subquery, .. } => {
@@ -6,8 +6,11 @@
 //! Physical plans represent the actual execution strategy with specific
 //! algorithms and data access methods chosen for optimal performance.
 
-use crate::ast::{EdgeDirection, Expression, PathType};
+use crate::ast::{
+    EdgeDirection, Expression, LimitClause, Literal, Operator, OrderClause, PathType,
+};
 use crate::plan::logical::{AggregateFunction, JoinType, LogicalNode, LogicalPlan, PathElement};
+use crate::storage::json_path::JsonPath;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -37,6 +40,18 @@ pub struct EdgeCreation {
     pub properties: HashMap<String, Expression>,
 }
 
+/// Comparison between a relationship property and a literal, e.g. `r.IC50 < 100`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EdgePropertyPredicate {
+    pub property: String,
+    /// Path into the property's JSON document, for `JSON_GET(r.meta, '$.ph') < 7`
+    #[serde(default)]
+    pub path: Option<JsonPath>,
+    /// `=`, `<`, `<=`, `>` or `>=`, with the property on the left-hand side
+    pub operator: Operator,
+    pub value: Literal,
+}
+
 /// Graph index operations for optimized graph traversals
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum GraphIndexOperation {
@@ -104,6 +119,25 @@ pub enum PhysicalNode {
         estimated_cost: f64,
     },
 
+    /// First hop of a pattern driven by relationship property predicates
+    ///
+    /// At execution time the most selective predicate backed by a relationship
+    /// property index picks the starting edges, whose endpoints are then bound;
+    /// without a usable index `fallback` (the original scan and expand) runs instead.
+    EdgeIndexScan {
+        from_variable: String,
+        from_labels: Vec<String>,
+        from_properties: Option<HashMap<String, Expression>>,
+        edge_variable: String,
+        edge_label: String,
+        to_variable: String,
+        direction: EdgeDirection,
+        predicates: Vec<EdgePropertyPredicate>,
+        fallback: Box<PhysicalNode>,
+        estimated_rows: usize,
+        estimated_cost: f64,
+    },
+
     /// Index-based edge traversal
     IndexedExpand {
         from_variable: String,
@@ -130,6 +164,21 @@ pub enum PhysicalNode {
         estimated_cost: f64,
     },
 
+    /// Relationship count lookup standing in for an expand whose far end is only counted
+    ///
+    /// Emits each input row that has at least one matching relationship, extended with
+    /// `count_variable` holding the number of such relationships as read from the
+    /// adjacency indexes; no edge or neighbor is materialized.
+    AdjacencyCount {
+        from_variable: String,
+        edge_labels: Vec<String>,
+        direction: EdgeDirection,
+        count_variable: String,
+        input: Box<PhysicalNode>,
+        estimated_rows: usize,
+        estimated_cost: f64,
+    },
+
     /// Path traversal with type constraints
     PathTraversal {
         path_type: PathType,
@@ -141,6 +190,42 @@ pub enum PhysicalNode {
         estimated_cost: f64,
     },
 
+    /// Breadth-first expansion over between `min_hops` and `max_hops` edges,
+    /// never revisiting a node within a path
+    VariableLengthExpand {
+        from_variable: String,
+        edge_variable: Option<String>,
+        to_variable: String,
+        path_variable: Option<String>,
+        edge_labels: Vec<String>,
+        direction: EdgeDirection,
+        edge_properties: Option<HashMap<String, Expression>>,
+        to_labels: Vec<String>,
+        min_hops: u32,
+        max_hops: Option<u32>,
+        input: Box<PhysicalNode>,
+        estimated_rows: usize,
+        estimated_cost: f64,
+    },
+
+    /// Bidirectional breadth-first search for the shortest paths between the
+    /// end points bound in each input row
+    ShortestPath {
+        from_variable: String,
+        edge_variable: Option<String>,
+        to_variable: String,
+        path_variable: Option<String>,
+        edge_labels: Vec<String>,
+        direction: EdgeDirection,
+        edge_properties: Option<HashMap<String, Expression>>,
+        min_hops: u32,
+        max_hops: Option<u32>,
+        all: bool,
+        input: Box<PhysicalNode>,
+        estimated_rows: usize,
+        estimated_cost: f64,
+    },
+
     /// Filter with predicate pushdown
     Filter {
         condition: Expression,
@@ -220,6 +305,8 @@ pub enum PhysicalNode {
     /// Hash aggregation
     HashAggregate {
         group_by: Vec<Expression>,
+        #[serde(default)]
+        grouping_sets: Option<Vec<Vec<usize>>>,
         aggregates: Vec<AggregateItem>,
         input: Box<PhysicalNode>,
         estimated_rows: usize,
@@ -229,6 +316,8 @@ pub enum PhysicalNode {
     /// Sort-based aggregation
     SortAggregate {
         group_by: Vec<Expression>,
+        #[serde(default)]
+        grouping_sets: Option<Vec<Vec<usize>>>,
         aggregates: Vec<AggregateItem>,
         input: Box<PhysicalNode>,
         estimated_rows: usize,
@@ -436,6 +525,12 @@ pub struct AggregateItem {
     pub expression: Expression,
     pub alias: Option<String>,
     pub output_type: OutputType,
+    /// Order in which an ordered aggregate sees the rows of a group
+    #[serde(default)]
+    pub order_clause: Option<OrderClause>,
+    /// Values an ordered aggregate keeps
+    #[serde(default)]
+    pub limit_clause: Option<LimitClause>,
 }
 
 /// Sort item with ordering
@@ -536,8 +631,9 @@ impl PhysicalPlan {
             } => {
                 let input_physical = Box::new(Self::convert_logical_node(input));
                 let input_rows = input_physical.get_row_count();
-                let estimated_rows = input_rows * 5; // Average fanout
-                let estimated_cost = estimated_rows as f64 * 0.2;
+                let estimated_rows = input_rows * Self::expand_fanout(direction);
+                let estimated_cost =
+                    estimated_rows as f64 * Self::expand_cost_per_row(direction, edge_labels);
 
                 // Choose expand strategy based on input size and fanout
                 if input_rows > 10000 {
@@ -691,6 +787,7 @@ impl PhysicalPlan {
 
             LogicalNode::Aggregate {
                 group_by,
+                grouping_sets,
                 aggregates,
                 input,
             } => {
@@ -712,6 +809,8 @@ impl PhysicalPlan {
                             expression: agg.expression.clone(),
                             alias: agg.alias.clone(),
                             output_type: OutputType::Float, // Default to Float for most aggregates
+                            order_clause: agg.order_clause.clone(),
+                            limit_clause: agg.limit_clause.clone(),
                         }
                     })
                     .collect();
@@ -720,6 +819,7 @@ impl PhysicalPlan {
                 if input_rows > 10000 {
                     PhysicalNode::HashAggregate {
                         group_by: group_by.clone(),
+                        grouping_sets: grouping_sets.clone(),
                         aggregates: physical_aggregates,
                         input: input_physical,
                         estimated_rows,
@@ -728,6 +828,7 @@ impl PhysicalPlan {
                 } else {
                     PhysicalNode::SortAggregate {
                         group_by: group_by.clone(),
+                        grouping_sets: grouping_sets.clone(),
                         aggregates: physical_aggregates,
                         input: input_physical,
                         estimated_rows,
@@ -784,6 +885,86 @@ impl PhysicalPlan {
                 }
             }
 
+            LogicalNode::VariableLengthExpand {
+                from_variable,
+                edge_variable,
+                to_variable,
+                path_variable,
+                edge_labels,
+                direction,
+                edge_properties,
+                to_labels,
+                min_hops,
+                max_hops,
+                input,
+            } => {
+                let input_physical = Box::new(Self::convert_logical_node(input));
+                let input_rows = input_physical.get_row_count();
+
+                // One expand's fanout for each hop count the quantifier allows;
+                // open-ended quantifiers are costed as ten hop counts
+                let hop_counts = max_hops.map_or(10, |max| max - min_hops + 1) as usize;
+                let estimated_rows = input_rows * Self::expand_fanout(direction) * hop_counts;
+                let estimated_cost = input_physical.get_cost()
+                    + estimated_rows as f64 * Self::expand_cost_per_row(direction, edge_labels);
+
+                PhysicalNode::VariableLengthExpand {
+                    from_variable: from_variable.clone(),
+                    edge_variable: edge_variable.clone(),
+                    to_variable: to_variable.clone(),
+                    path_variable: path_variable.clone(),
+                    edge_labels: edge_labels.clone(),
+                    direction: direction.clone(),
+                    edge_properties: edge_properties.clone(),
+                    to_labels: to_labels.clone(),
+                    min_hops: *min_hops,
+                    max_hops: *max_hops,
+                    input: input_physical,
+                    estimated_rows,
+                    estimated_cost,
+                }
+            }
+
+            LogicalNode::ShortestPath {
+                from_variable,
+                edge_variable,
+                to_variable,
+                path_variable,
+                edge_labels,
+                direction,
+                edge_properties,
+                min_hops,
+                max_hops,
+                all,
+                input,
+            } => {
+                let input_physical = Box::new(Self::convert_logical_node(input));
+                let input_rows = input_physical.get_row_count();
+
+                // Each search grows two frontiers that meet halfway, so its cost
+                // grows with the fanout of half a path rather than a whole one
+                let estimated_rows = if *all { input_rows * 2 } else { input_rows };
+                let estimated_cost = input_physical.get_cost()
+                    + (input_rows * 2 * Self::expand_fanout(direction)) as f64
+                        * Self::expand_cost_per_row(direction, edge_labels);
+
+                PhysicalNode::ShortestPath {
+                    from_variable: from_variable.clone(),
+                    edge_variable: edge_variable.clone(),
+                    to_variable: to_variable.clone(),
+                    path_variable: path_variable.clone(),
+                    edge_labels: edge_labels.clone(),
+                    direction: direction.clone(),
+                    edge_properties: edge_properties.clone(),
+                    min_hops: *min_hops,
+                    max_hops: *max_hops,
+                    all: *all,
+                    input: input_physical,
+                    estimated_rows,
+                    estimated_cost,
+                }
+            }
+
             // Join conversion
             LogicalNode::Join {
                 join_type,
@@ -1136,6 +1317,29 @@ impl PhysicalPlan {
         self.root.get_operators()
     }
 
+    /// Average number of rows an expand produces per input row
+    ///
+    /// Undirected hops follow both the outgoing and the reverse adjacency lists.
+    fn expand_fanout(direction: &EdgeDirection) -> usize {
+        match direction {
+            EdgeDirection::Outgoing | EdgeDirection::Incoming => 5,
+            EdgeDirection::Both | EdgeDirection::Undirected => 10,
+        }
+    }
+
+    /// Estimated cost per row produced by an expand
+    ///
+    /// Incoming hops are served by the reverse adjacency index, keyed by relationship
+    /// type, so they only visit edges of the requested labels. Other directions read
+    /// every adjacent edge and filter on the label afterwards.
+    fn expand_cost_per_row(direction: &EdgeDirection, edge_labels: &[String]) -> f64 {
+        match direction {
+            EdgeDirection::Incoming => 0.2,
+            _ if edge_labels.is_empty() => 0.2,
+            _ => 0.3,
+        }
+    }
+
     /// Extract label information from a logical node (helper for text search optimization)
     #[allow(dead_code)] // FALSE POSITIVE - Recursively called at line 1085, compiler doesn't detect recursive usage
     fn extract_label_from_logical_node(node: &LogicalNode) -> Option<String> {
@@ -1163,9 +1367,13 @@ impl PhysicalNode {
             PhysicalNode::NodeSeqScan { estimated_cost, .. } => *estimated_cost,
             PhysicalNode::NodeIndexScan { estimated_cost, .. } => *estimated_cost,
             PhysicalNode::EdgeSeqScan { estimated_cost, .. } => *estimated_cost,
+            PhysicalNode::EdgeIndexScan { estimated_cost, .. } => *estimated_cost,
             PhysicalNode::IndexedExpand { estimated_cost, .. } => *estimated_cost,
             PhysicalNode::HashExpand { estimated_cost, .. } => *estimated_cost,
+            PhysicalNode::AdjacencyCount { estimated_cost, .. } => *estimated_cost,
             PhysicalNode::PathTraversal { estimated_cost, .. } => *estimated_cost,
+            PhysicalNode::VariableLengthExpand { estimated_cost, .. } => *estimated_cost,
+            PhysicalNode::ShortestPath { estimated_cost, .. } => *estimated_cost,
             PhysicalNode::Filter { estimated_cost, .. } => *estimated_cost,
             PhysicalNode::Project { estimated_cost, .. } => *estimated_cost,
             PhysicalNode::HashJoin { estimated_cost, .. } => *estimated_cost,
@@ -1204,9 +1412,13 @@ impl PhysicalNode {
             PhysicalNode::NodeSeqScan { estimated_rows, .. } => *estimated_rows,
             PhysicalNode::NodeIndexScan { estimated_rows, .. } => *estimated_rows,
             PhysicalNode::EdgeSeqScan { estimated_rows, .. } => *estimated_rows,
+            PhysicalNode::EdgeIndexScan { estimated_rows, .. } => *estimated_rows,
             PhysicalNode::IndexedExpand { estimated_rows, .. } => *estimated_rows,
             PhysicalNode::HashExpand { estimated_rows, .. } => *estimated_rows,
+            PhysicalNode::AdjacencyCount { estimated_rows, .. } => *estimated_rows,
             PhysicalNode::PathTraversal { estimated_rows, .. } => *estimated_rows,
+            PhysicalNode::VariableLengthExpand { estimated_rows, .. } => *estimated_rows,
+            PhysicalNode::ShortestPath { estimated_rows, .. } => *estimated_rows,
             PhysicalNode::Filter { estimated_rows, .. } => *estimated_rows,
             PhysicalNode::Project { estimated_rows, .. } => *estimated_rows,
             PhysicalNode::HashJoin { estimated_rows, .. } => *estimated_rows,
@@ -1239,6 +1451,51 @@ impl PhysicalNode {
         }
     }
 
+    /// Name of this node's operator, as shown in operator timings
+    pub fn operator_name(&self) -> &'static str {
+        match self {
+            PhysicalNode::NodeSeqScan { .. } => "NodeSeqScan",
+            PhysicalNode::NodeIndexScan { .. } => "NodeIndexScan",
+            PhysicalNode::EdgeSeqScan { .. } => "EdgeSeqScan",
+            PhysicalNode::EdgeIndexScan { .. } => "EdgeIndexScan",
+            PhysicalNode::IndexedExpand { .. } => "IndexedExpand",
+            PhysicalNode::HashExpand { .. } => "HashExpand",
+            PhysicalNode::AdjacencyCount { .. } => "AdjacencyCount",
+            PhysicalNode::PathTraversal { .. } => "PathTraversal",
+            PhysicalNode::VariableLengthExpand { .. } => "VariableLengthExpand",
+            PhysicalNode::ShortestPath { .. } => "ShortestPath",
+            PhysicalNode::Filter { .. } => "Filter",
+            PhysicalNode::Project { .. } => "Project",
+            PhysicalNode::HashJoin { .. } => "HashJoin",
+            PhysicalNode::NestedLoopJoin { .. } => "NestedLoopJoin",
+            PhysicalNode::SortMergeJoin { .. } => "SortMergeJoin",
+            PhysicalNode::UnionAll { .. } => "UnionAll",
+            PhysicalNode::Intersect { .. } => "Intersect",
+            PhysicalNode::Except { .. } => "Except",
+            PhysicalNode::HashAggregate { .. } => "HashAggregate",
+            PhysicalNode::SortAggregate { .. } => "SortAggregate",
+            PhysicalNode::Having { .. } => "Having",
+            PhysicalNode::ExternalSort { .. } => "ExternalSort",
+            PhysicalNode::InMemorySort { .. } => "InMemorySort",
+            PhysicalNode::Distinct { .. } => "Distinct",
+            PhysicalNode::Limit { .. } => "Limit",
+            PhysicalNode::GenericFunction { .. } => "GenericFunction",
+            PhysicalNode::ExistsSubquery { .. } => "ExistsSubquery",
+            PhysicalNode::NotExistsSubquery { .. } => "NotExistsSubquery",
+            PhysicalNode::InSubquery { .. } => "InSubquery",
+            PhysicalNode::NotInSubquery { .. } => "NotInSubquery",
+            PhysicalNode::ScalarSubquery { .. } => "ScalarSubquery",
+            PhysicalNode::WithQuery { .. } => "WithQuery",
+            PhysicalNode::Unwind { .. } => "Unwind",
+            PhysicalNode::GraphIndexScan { .. } => "GraphIndexScan",
+            PhysicalNode::IndexJoin { .. } => "IndexJoin",
+            PhysicalNode::Insert { .. } => "Insert",
+            PhysicalNode::Update { .. } => "Update",
+            PhysicalNode::Delete { .. } => "Delete",
+            PhysicalNode::SingleRow { .. } => "SingleRow",
+        }
+    }
+
     /// Get all operators in this subtree
     pub fn get_operators(&self) -> Vec<PhysicalOperator> {
         let mut operators = vec![self.get_operator_type()];
@@ -1246,7 +1503,10 @@ impl PhysicalNode {
         match self {
             PhysicalNode::IndexedExpand { input, .. }
             | PhysicalNode::HashExpand { input, .. }
+            | PhysicalNode::AdjacencyCount { input, .. }
             | PhysicalNode::PathTraversal { input, .. }
+            | PhysicalNode::VariableLengthExpand { input, .. }
+            | PhysicalNode::ShortestPath { input, .. }
             | PhysicalNode::Filter { input, .. }
             | PhysicalNode::Project { input, .. }
             | PhysicalNode::HashAggregate { input, .. }
@@ -1330,11 +1590,14 @@ impl PhysicalNode {
         match self {
             PhysicalNode::NodeSeqScan { .. }
             | PhysicalNode::NodeIndexScan { .. }
-            | PhysicalNode::EdgeSeqScan { .. } => PhysicalOperator::Scan,
-
-            PhysicalNode::IndexedExpand { .. } | PhysicalNode::HashExpand { .. } => {
-                PhysicalOperator::Join
-            }
+            | PhysicalNode::EdgeSeqScan { .. }
+            | PhysicalNode::EdgeIndexScan { .. } => PhysicalOperator::Scan,
+
+            PhysicalNode::IndexedExpand { .. }
+            | PhysicalNode::HashExpand { .. }
+            | PhysicalNode::AdjacencyCount { .. }
+            | PhysicalNode::VariableLengthExpand { .. }
+            | PhysicalNode::ShortestPath { .. } => PhysicalOperator::Join,
 
             PhysicalNode::Filter { .. } | PhysicalNode::Having { .. } => PhysicalOperator::Filter,
             PhysicalNode::Project { .. } => PhysicalOperator::Project,
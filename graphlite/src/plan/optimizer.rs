@@ -575,10 +575,12 @@ impl<'a> IndexAwareOptimizer<'a> {
 
             LogicalNode::Aggregate {
                 group_by,
+                grouping_sets,
                 aggregates,
                 input,
             } => Ok(LogicalNode::Aggregate {
                 group_by,
+                grouping_sets,
                 aggregates,
                 input: Box::new(self.transform_text_search_node(*input)?),
             }),
@@ -160,10 +160,12 @@ impl LogicalOptimizer {
 
             LogicalNode::Aggregate {
                 group_by,
+                grouping_sets,
                 aggregates,
                 input,
             } => Ok(LogicalNode::Aggregate {
                 group_by,
+                grouping_sets,
                 aggregates,
                 input: Box::new(self.optimize_logical_node(*input)?),
             }),
@@ -215,6 +217,58 @@ impl LogicalOptimizer {
                 input: Box::new(self.optimize_logical_node(*input)?),
             }),
 
+            LogicalNode::VariableLengthExpand {
+                from_variable,
+                edge_variable,
+                to_variable,
+                path_variable,
+                edge_labels,
+                direction,
+                edge_properties,
+                to_labels,
+                min_hops,
+                max_hops,
+                input,
+            } => Ok(LogicalNode::VariableLengthExpand {
+                from_variable,
+                edge_variable,
+                to_variable,
+                path_variable,
+                edge_labels,
+                direction,
+                edge_properties,
+                to_labels,
+                min_hops,
+                max_hops,
+                input: Box::new(self.optimize_logical_node(*input)?),
+            }),
+
+            LogicalNode::ShortestPath {
+                from_variable,
+                edge_variable,
+                to_variable,
+                path_variable,
+                edge_labels,
+                direction,
+                edge_properties,
+                min_hops,
+                max_hops,
+                all,
+                input,
+            } => Ok(LogicalNode::ShortestPath {
+                from_variable,
+                edge_variable,
+                to_variable,
+                path_variable,
+                edge_labels,
+                direction,
+                edge_properties,
+                min_hops,
+                max_hops,
+                all,
+                input: Box::new(self.optimize_logical_node(*input)?),
+            }),
+
             LogicalNode::Having { condition, input } => Ok(LogicalNode::Having {
                 condition,
                 input: Box::new(self.optimize_logical_node(*input)?),
@@ -667,6 +721,7 @@ mod tests {
 
         let node = LogicalNode::Aggregate {
             group_by: vec![],
+            grouping_sets: None,
             aggregates: vec![],
             input: Box::new(LogicalNode::NodeScan {
                 variable: "n".to_string(),
@@ -699,6 +754,7 @@ mod tests {
             condition: Expression::Literal(crate::ast::Literal::Boolean(true)),
             input: Box::new(LogicalNode::Aggregate {
                 group_by: vec![],
+                grouping_sets: None,
                 aggregates: vec![],
                 input: Box::new(LogicalNode::NodeScan {
                     variable: "n".to_string(),
@@ -850,6 +906,7 @@ mod tests {
 
         let subquery = LogicalNode::Aggregate {
             group_by: vec![],
+            grouping_sets: None,
             aggregates: vec![],
             input: Box::new(LogicalNode::NodeScan {
                 variable: "n".to_string(),
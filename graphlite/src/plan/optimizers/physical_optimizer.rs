@@ -5,13 +5,18 @@
 //!
 //! This module handles physical plan optimizations such as:
 //! - Index scan vs sequential scan selection
+//! - Driving pattern matches by relationship property predicates
+//! - Answering relationship counts from adjacency indexes
 //! - Operator selection (hash vs nested loop join)
 //! - Parallel execution planning
 //!
 //! Extracted from optimizer.rs as part of Phase 3 refactoring.
 
+use crate::ast::{Expression, Literal, Operator, Variable};
+use crate::plan::logical::AggregateFunction;
 use crate::plan::optimizer::PlanningError;
-use crate::plan::physical::{PhysicalNode, PhysicalPlan};
+use crate::plan::physical::{AggregateItem, EdgePropertyPredicate, PhysicalNode, PhysicalPlan};
+use crate::storage::json_path::JsonPath;
 
 /// Optimizer for physical plans
 #[derive(Debug)]
@@ -35,6 +40,13 @@ impl PhysicalOptimizer {
             optimized_plan = self.disable_index_scans(optimized_plan)?;
         }
 
+        // Relationship property predicates may drive the first hop of a pattern;
+        // whether an index exists is only known once the graph is at hand
+        let mut root = optimized_plan.root;
+        Self::drive_by_edge_predicates(&mut root);
+        Self::push_down_relationship_counts(&mut root);
+        optimized_plan = PhysicalPlan::new(root);
+
         // TODO: Implement other physical optimizations like:
         // - Operator selection (hash vs nested loop join)
         // - Parallel execution planning
@@ -101,6 +113,27 @@ impl PhysicalOptimizer {
                 })
             }
 
+            PhysicalNode::AdjacencyCount {
+                from_variable,
+                edge_labels,
+                direction,
+                count_variable,
+                input,
+                estimated_rows,
+                estimated_cost,
+            } => {
+                let transformed_input = Box::new(self.transform_node_disable_indexes(*input)?);
+                Ok(PhysicalNode::AdjacencyCount {
+                    from_variable,
+                    edge_labels,
+                    direction,
+                    count_variable,
+                    input: transformed_input,
+                    estimated_rows,
+                    estimated_cost,
+                })
+            }
+
             // Recursively transform nodes with single input
             PhysicalNode::Filter {
                 condition,
@@ -151,6 +184,7 @@ impl PhysicalOptimizer {
 
             PhysicalNode::HashAggregate {
                 group_by,
+                grouping_sets,
                 aggregates,
                 input,
                 estimated_rows,
@@ -159,6 +193,7 @@ impl PhysicalOptimizer {
                 let transformed_input = Box::new(self.transform_node_disable_indexes(*input)?);
                 Ok(PhysicalNode::HashAggregate {
                     group_by,
+                    grouping_sets,
                     aggregates,
                     input: transformed_input,
                     estimated_rows,
@@ -168,6 +203,7 @@ impl PhysicalOptimizer {
 
             PhysicalNode::SortAggregate {
                 group_by,
+                grouping_sets,
                 aggregates,
                 input,
                 estimated_rows,
@@ -176,6 +212,7 @@ impl PhysicalOptimizer {
                 let transformed_input = Box::new(self.transform_node_disable_indexes(*input)?);
                 Ok(PhysicalNode::SortAggregate {
                     group_by,
+                    grouping_sets,
                     aggregates,
                     input: transformed_input,
                     estimated_rows,
@@ -351,9 +388,76 @@ impl PhysicalOptimizer {
                 })
             }
 
+            PhysicalNode::VariableLengthExpand {
+                from_variable,
+                edge_variable,
+                to_variable,
+                path_variable,
+                edge_labels,
+                direction,
+                edge_properties,
+                to_labels,
+                min_hops,
+                max_hops,
+                input,
+                estimated_rows,
+                estimated_cost,
+            } => {
+                let transformed_input = Box::new(self.transform_node_disable_indexes(*input)?);
+                Ok(PhysicalNode::VariableLengthExpand {
+                    from_variable,
+                    edge_variable,
+                    to_variable,
+                    path_variable,
+                    edge_labels,
+                    direction,
+                    edge_properties,
+                    to_labels,
+                    min_hops,
+                    max_hops,
+                    input: transformed_input,
+                    estimated_rows,
+                    estimated_cost,
+                })
+            }
+
+            PhysicalNode::ShortestPath {
+                from_variable,
+                edge_variable,
+                to_variable,
+                path_variable,
+                edge_labels,
+                direction,
+                edge_properties,
+                min_hops,
+                max_hops,
+                all,
+                input,
+                estimated_rows,
+                estimated_cost,
+            } => {
+                let transformed_input = Box::new(self.transform_node_disable_indexes(*input)?);
+                Ok(PhysicalNode::ShortestPath {
+                    from_variable,
+                    edge_variable,
+                    to_variable,
+                    path_variable,
+                    edge_labels,
+                    direction,
+                    edge_properties,
+                    min_hops,
+                    max_hops,
+                    all,
+                    input: transformed_input,
+                    estimated_rows,
+                    estimated_cost,
+                })
+            }
+
             // Nodes that are already non-indexed (no transformation needed)
             PhysicalNode::NodeSeqScan { .. }
             | PhysicalNode::EdgeSeqScan { .. }
+            | PhysicalNode::EdgeIndexScan { .. }
             | PhysicalNode::HashExpand { .. }
             | PhysicalNode::GenericFunction { .. }
             | PhysicalNode::SingleRow { .. } => {
@@ -518,6 +622,372 @@ impl PhysicalOptimizer {
     }
 }
 
+impl PhysicalOptimizer {
+    /// Replace the first hop under each WHERE filter with an `EdgeIndexScan` when the
+    /// filter compares a property of that hop's relationship with a literal
+    ///
+    /// The filter stays on top of the scan, so the scan may return a superset.
+    fn drive_by_edge_predicates(node: &mut PhysicalNode) {
+        if let PhysicalNode::Filter {
+            condition, input, ..
+        } = node
+        {
+            let mut conjuncts = Vec::new();
+            collect_conjuncts(condition, &mut conjuncts);
+            Self::rewrite_first_hop(input, &conjuncts);
+        }
+
+        match node {
+            PhysicalNode::Filter { input, .. }
+            | PhysicalNode::Having { input, .. }
+            | PhysicalNode::Project { input, .. }
+            | PhysicalNode::HashAggregate { input, .. }
+            | PhysicalNode::SortAggregate { input, .. }
+            | PhysicalNode::ExternalSort { input, .. }
+            | PhysicalNode::InMemorySort { input, .. }
+            | PhysicalNode::Limit { input, .. }
+            | PhysicalNode::Distinct { input, .. }
+            | PhysicalNode::GenericFunction { input, .. } => {
+                Self::drive_by_edge_predicates(input);
+            }
+            PhysicalNode::HashJoin { build, probe, .. } => {
+                Self::drive_by_edge_predicates(build);
+                Self::drive_by_edge_predicates(probe);
+            }
+            PhysicalNode::NestedLoopJoin { left, right, .. }
+            | PhysicalNode::SortMergeJoin { left, right, .. } => {
+                Self::drive_by_edge_predicates(left);
+                Self::drive_by_edge_predicates(right);
+            }
+            PhysicalNode::UnionAll { inputs, .. } => {
+                for input in inputs {
+                    Self::drive_by_edge_predicates(input);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Walk down a pattern chain to its first hop and rewrite it if a predicate applies
+    fn rewrite_first_hop(node: &mut PhysicalNode, conjuncts: &[&Expression]) {
+        let first_hop = match node {
+            PhysicalNode::Filter { input, .. } => return Self::rewrite_first_hop(input, conjuncts),
+            PhysicalNode::HashExpand { input, .. } | PhysicalNode::IndexedExpand { input, .. } => {
+                match input.as_mut() {
+                    PhysicalNode::NodeSeqScan { .. } | PhysicalNode::NodeIndexScan { .. } => {}
+                    deeper => return Self::rewrite_first_hop(deeper, conjuncts),
+                }
+                node
+            }
+            _ => return,
+        };
+
+        let (from_variable, edge_variable, to_variable, edge_labels, direction, properties, input) =
+            match first_hop {
+                PhysicalNode::HashExpand {
+                    from_variable,
+                    edge_variable,
+                    to_variable,
+                    edge_labels,
+                    direction,
+                    properties,
+                    input,
+                    ..
+                }
+                | PhysicalNode::IndexedExpand {
+                    from_variable,
+                    edge_variable,
+                    to_variable,
+                    edge_labels,
+                    direction,
+                    properties,
+                    input,
+                    ..
+                } => (
+                    from_variable,
+                    edge_variable,
+                    to_variable,
+                    edge_labels,
+                    direction,
+                    properties,
+                    input,
+                ),
+                _ => return,
+            };
+
+        // Inline relationship properties are matched by the expand itself
+        let (Some(edge_variable), [edge_label], None) = (
+            edge_variable.as_ref(),
+            edge_labels.as_slice(),
+            properties.as_ref(),
+        ) else {
+            return;
+        };
+        let (from_labels, from_properties) = match input.as_ref() {
+            PhysicalNode::NodeSeqScan {
+                labels, properties, ..
+            }
+            | PhysicalNode::NodeIndexScan {
+                labels, properties, ..
+            } => (labels.clone(), properties.clone()),
+            _ => return,
+        };
+
+        let predicates: Vec<EdgePropertyPredicate> = conjuncts
+            .iter()
+            .filter_map(|conjunct| edge_property_predicate(conjunct, edge_variable))
+            .collect();
+        if predicates.is_empty() {
+            return;
+        }
+
+        let scan = PhysicalNode::EdgeIndexScan {
+            from_variable: from_variable.clone(),
+            from_labels,
+            from_properties,
+            edge_variable: edge_variable.clone(),
+            edge_label: edge_label.clone(),
+            to_variable: to_variable.clone(),
+            direction: direction.clone(),
+            predicates,
+            fallback: Box::new(PhysicalNode::SingleRow {
+                estimated_rows: 1,
+                estimated_cost: 0.0,
+            }),
+            estimated_rows: (first_hop.get_row_count() / 10).max(1),
+            estimated_cost: first_hop.get_cost() * 0.1,
+        };
+        let expand = std::mem::replace(first_hop, scan);
+        if let PhysicalNode::EdgeIndexScan { fallback, .. } = first_hop {
+            **fallback = expand;
+        }
+    }
+}
+
+impl PhysicalOptimizer {
+    /// Answer grouped relationship counts from the adjacency indexes
+    ///
+    /// `MATCH (n)-[:R]->(m) RETURN n, count(m)` only needs the number of `R` edges per
+    /// `n`, so when the grouping keys depend on the anchor node alone and every
+    /// aggregate counts rows (or the never-null far node), the expand is replaced by an
+    /// `AdjacencyCount` and the counts become sums of the per-node relationship counts.
+    fn push_down_relationship_counts(node: &mut PhysicalNode) {
+        if let PhysicalNode::HashAggregate {
+            group_by,
+            grouping_sets,
+            aggregates,
+            input,
+            ..
+        }
+        | PhysicalNode::SortAggregate {
+            group_by,
+            grouping_sets,
+            aggregates,
+            input,
+            ..
+        } = node
+        {
+            Self::rewrite_counted_expand(group_by, grouping_sets.as_deref(), aggregates, input);
+        }
+
+        match node {
+            PhysicalNode::Filter { input, .. }
+            | PhysicalNode::Having { input, .. }
+            | PhysicalNode::Project { input, .. }
+            | PhysicalNode::HashAggregate { input, .. }
+            | PhysicalNode::SortAggregate { input, .. }
+            | PhysicalNode::ExternalSort { input, .. }
+            | PhysicalNode::InMemorySort { input, .. }
+            | PhysicalNode::Limit { input, .. }
+            | PhysicalNode::Distinct { input, .. }
+            | PhysicalNode::GenericFunction { input, .. } => {
+                Self::push_down_relationship_counts(input);
+            }
+            PhysicalNode::HashJoin { build, probe, .. } => {
+                Self::push_down_relationship_counts(build);
+                Self::push_down_relationship_counts(probe);
+            }
+            PhysicalNode::NestedLoopJoin { left, right, .. }
+            | PhysicalNode::SortMergeJoin { left, right, .. } => {
+                Self::push_down_relationship_counts(left);
+                Self::push_down_relationship_counts(right);
+            }
+            PhysicalNode::UnionAll { inputs, .. } => {
+                for input in inputs {
+                    Self::push_down_relationship_counts(input);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Replace an aggregate's expand input with an `AdjacencyCount` if only counts are needed
+    fn rewrite_counted_expand(
+        group_by: &[Expression],
+        grouping_sets: Option<&[Vec<usize>]>,
+        aggregates: &mut [AggregateItem],
+        input: &mut Box<PhysicalNode>,
+    ) {
+        let expand_rows = input.get_row_count();
+        let (from_variable, to_variable, edge_labels, direction, expand_input) =
+            match input.as_mut() {
+                PhysicalNode::HashExpand {
+                    from_variable,
+                    edge_variable: None,
+                    to_variable,
+                    edge_labels,
+                    direction,
+                    properties: None,
+                    input,
+                    ..
+                }
+                | PhysicalNode::IndexedExpand {
+                    from_variable,
+                    edge_variable: None,
+                    to_variable,
+                    edge_labels,
+                    direction,
+                    properties: None,
+                    input,
+                    ..
+                } => (from_variable, to_variable, edge_labels, direction, input),
+                _ => return,
+            };
+
+        // Without grouping an empty input must still count 0, which a sum would not
+        let grouped = !group_by.is_empty()
+            && grouping_sets.is_none_or(|sets| sets.iter().all(|set| !set.is_empty()));
+        let keyed_by_anchor = grouped
+            && group_by.iter().all(|expr| match expr {
+                Expression::Variable(var) => var.name == *from_variable,
+                Expression::PropertyAccess(access) => access.object == *from_variable,
+                _ => false,
+            });
+        let counts_only = aggregates.iter().all(|aggregate| {
+            matches!(aggregate.function, AggregateFunction::Count)
+                && match &aggregate.expression {
+                    Expression::Variable(var) => var.name == "*" || var.name == *to_variable,
+                    Expression::Literal(Literal::Integer(1)) => true,
+                    _ => false,
+                }
+        });
+        if !keyed_by_anchor || !counts_only {
+            return;
+        }
+
+        let count_variable = format!("_count_{}", from_variable);
+        for aggregate in aggregates.iter_mut() {
+            // Keep the column name the count would have had
+            let counted = match &aggregate.expression {
+                Expression::Variable(var) => var.name.clone(),
+                _ => "literal".to_string(),
+            };
+            aggregate
+                .alias
+                .get_or_insert_with(|| format!("COUNT_{}", counted));
+            aggregate.function = AggregateFunction::Sum;
+            aggregate.expression = Expression::Variable(Variable {
+                name: count_variable.clone(),
+                location: Default::default(),
+            });
+        }
+
+        let count_input = std::mem::replace(
+            expand_input.as_mut(),
+            PhysicalNode::SingleRow {
+                estimated_rows: 1,
+                estimated_cost: 0.0,
+            },
+        );
+        let estimated_rows = count_input.get_row_count().min(expand_rows);
+        let estimated_cost = count_input.get_cost() + estimated_rows as f64 * 0.01;
+        **input = PhysicalNode::AdjacencyCount {
+            from_variable: from_variable.clone(),
+            edge_labels: edge_labels.clone(),
+            direction: direction.clone(),
+            count_variable,
+            input: Box::new(count_input),
+            estimated_rows,
+            estimated_cost,
+        };
+    }
+}
+
+/// Split a condition into its AND-ed parts
+fn collect_conjuncts<'a>(condition: &'a Expression, conjuncts: &mut Vec<&'a Expression>) {
+    match condition {
+        Expression::Binary(binary) if binary.operator == Operator::And => {
+            collect_conjuncts(&binary.left, conjuncts);
+            collect_conjuncts(&binary.right, conjuncts);
+        }
+        other => conjuncts.push(other),
+    }
+}
+
+/// Recognise `edge.property` or `JSON_GET(edge.property, '<path>')`, returning the
+/// property, its edge variable and the parsed path
+fn edge_property_operand(expression: &Expression) -> Option<(&str, &str, Option<JsonPath>)> {
+    match expression {
+        Expression::PropertyAccess(access) => Some((&access.object, &access.property, None)),
+        Expression::FunctionCall(call) if call.name.eq_ignore_ascii_case("JSON_GET") => {
+            match call.arguments.as_slice() {
+                [Expression::PropertyAccess(access), Expression::Literal(Literal::String(path))] => {
+                    let path = JsonPath::parse(path).ok()?;
+                    Some((&access.object, &access.property, Some(path)))
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Recognise `edge.property <op> literal` (either way round) for the given edge variable
+///
+/// The property may also be a `JSON_GET` path into a JSON document property.
+fn edge_property_predicate(
+    condition: &Expression,
+    edge_variable: &str,
+) -> Option<EdgePropertyPredicate> {
+    let Expression::Binary(binary) = condition else {
+        return None;
+    };
+    let (operand, literal, operator) = match (binary.left.as_ref(), binary.right.as_ref()) {
+        (operand, Expression::Literal(literal)) => (operand, literal, binary.operator.clone()),
+        (Expression::Literal(literal), operand) => {
+            let flipped = match binary.operator {
+                Operator::LessThan => Operator::GreaterThan,
+                Operator::LessEqual => Operator::GreaterEqual,
+                Operator::GreaterThan => Operator::LessThan,
+                Operator::GreaterEqual => Operator::LessEqual,
+                ref other => other.clone(),
+            };
+            (operand, literal, flipped)
+        }
+        _ => return None,
+    };
+    let (object, property, path) = edge_property_operand(operand)?;
+
+    let comparable = matches!(
+        operator,
+        Operator::Equal
+            | Operator::LessThan
+            | Operator::LessEqual
+            | Operator::GreaterThan
+            | Operator::GreaterEqual
+    );
+    if !comparable || object != edge_variable {
+        return None;
+    }
+
+    Some(EdgePropertyPredicate {
+        property: property.to_string(),
+        path,
+        operator,
+        value: literal.clone(),
+    })
+}
+
 impl Default for PhysicalOptimizer {
     fn default() -> Self {
         Self::new(true) // By default, avoid index scans
@@ -760,6 +1230,200 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_edge_property_predicate_drives_first_hop() {
+        use crate::ast::{BinaryExpression, Literal, Location, Operator, PropertyAccess};
+
+        let optimizer = PhysicalOptimizer::new(true);
+
+        // 100 > r.IC50, i.e. r.IC50 < 100
+        let condition = Expression::Binary(BinaryExpression {
+            left: Box::new(Expression::Literal(Literal::Integer(100))),
+            operator: Operator::GreaterThan,
+            right: Box::new(Expression::PropertyAccess(PropertyAccess {
+                object: "r".to_string(),
+                property: "IC50".to_string(),
+                location: Location::default(),
+            })),
+            location: Location::default(),
+        });
+        let plan = PhysicalPlan::new(PhysicalNode::Filter {
+            condition,
+            input: Box::new(PhysicalNode::IndexedExpand {
+                from_variable: "c".to_string(),
+                edge_variable: Some("r".to_string()),
+                to_variable: "p".to_string(),
+                edge_labels: vec!["INHIBITS".to_string()],
+                direction: crate::ast::EdgeDirection::Outgoing,
+                properties: None,
+                input: Box::new(PhysicalNode::NodeIndexScan {
+                    variable: "c".to_string(),
+                    labels: vec!["Compound".to_string()],
+                    properties: None,
+                    estimated_rows: 100,
+                    estimated_cost: 5.0,
+                }),
+                estimated_rows: 500,
+                estimated_cost: 100.0,
+            }),
+            selectivity: 0.5,
+            estimated_rows: 250,
+            estimated_cost: 101.0,
+        });
+
+        let optimized = optimizer.optimize(plan).unwrap();
+        let PhysicalNode::Filter { input, .. } = optimized.root else {
+            panic!("Expected Filter node at root");
+        };
+        match *input {
+            PhysicalNode::EdgeIndexScan {
+                from_labels,
+                edge_label,
+                predicates,
+                fallback,
+                ..
+            } => {
+                assert_eq!(from_labels, vec!["Compound"]);
+                assert_eq!(edge_label, "INHIBITS");
+                assert_eq!(predicates.len(), 1);
+                assert_eq!(predicates[0].property, "IC50");
+                assert_eq!(predicates[0].operator, Operator::LessThan);
+                assert!(matches!(*fallback, PhysicalNode::HashExpand { .. }));
+            }
+            other => panic!("Expected EdgeIndexScan, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_json_get_predicate_on_edge_property() {
+        use crate::ast::{
+            BinaryExpression, DistinctQualifier, FunctionCall, Literal, Location, Operator,
+            PropertyAccess,
+        };
+
+        let json_get = |path: &str| {
+            Expression::FunctionCall(FunctionCall {
+                name: "json_get".to_string(),
+                distinct: DistinctQualifier::None,
+                arguments: vec![
+                    Expression::PropertyAccess(PropertyAccess {
+                        object: "r".to_string(),
+                        property: "meta".to_string(),
+                        location: Location::default(),
+                    }),
+                    Expression::Literal(Literal::String(path.to_string())),
+                ],
+                order_clause: None,
+                limit_clause: None,
+                location: Location::default(),
+            })
+        };
+        let compare = |operand: Expression| {
+            Expression::Binary(BinaryExpression {
+                left: Box::new(operand),
+                operator: Operator::GreaterEqual,
+                right: Box::new(Expression::Literal(Literal::Float(7.0))),
+                location: Location::default(),
+            })
+        };
+
+        let predicate = edge_property_predicate(&compare(json_get("$['assay'].ph")), "r").unwrap();
+        assert_eq!(predicate.property, "meta");
+        assert_eq!(predicate.path, Some(JsonPath::parse("$.assay.ph").unwrap()));
+        assert_eq!(predicate.operator, Operator::GreaterEqual);
+
+        // Invalid paths and other variables are left to the filter
+        assert!(edge_property_predicate(&compare(json_get("assay.ph")), "r").is_none());
+        assert!(edge_property_predicate(&compare(json_get("$.assay.ph")), "s").is_none());
+    }
+
+    #[test]
+    fn test_grouped_count_uses_adjacency_count() {
+        use crate::ast::{Location, PropertyAccess, Variable};
+        use crate::plan::logical::AggregateFunction;
+        use crate::plan::physical::{AggregateItem, OutputType};
+
+        let aggregate = |group_object: &str| {
+            PhysicalPlan::new(PhysicalNode::SortAggregate {
+                group_by: vec![Expression::PropertyAccess(PropertyAccess {
+                    object: group_object.to_string(),
+                    property: "name".to_string(),
+                    location: Location::default(),
+                })],
+                grouping_sets: None,
+                aggregates: vec![AggregateItem {
+                    function: AggregateFunction::Count,
+                    expression: Expression::Variable(Variable {
+                        name: "m".to_string(),
+                        location: Location::default(),
+                    }),
+                    alias: None,
+                    output_type: OutputType::Float,
+                    order_clause: None,
+                    limit_clause: None,
+                }],
+                input: Box::new(PhysicalNode::IndexedExpand {
+                    from_variable: "n".to_string(),
+                    edge_variable: None,
+                    to_variable: "m".to_string(),
+                    edge_labels: vec!["KNOWS".to_string()],
+                    direction: crate::ast::EdgeDirection::Outgoing,
+                    properties: None,
+                    input: Box::new(PhysicalNode::NodeSeqScan {
+                        variable: "n".to_string(),
+                        labels: vec!["Person".to_string()],
+                        properties: None,
+                        estimated_rows: 100,
+                        estimated_cost: 10.0,
+                    }),
+                    estimated_rows: 500,
+                    estimated_cost: 150.0,
+                }),
+                estimated_rows: 50,
+                estimated_cost: 175.0,
+            })
+        };
+
+        let optimized = PhysicalOptimizer::new(false)
+            .optimize(aggregate("n"))
+            .unwrap();
+        let PhysicalNode::SortAggregate {
+            aggregates, input, ..
+        } = optimized.root
+        else {
+            panic!("Expected SortAggregate node at root");
+        };
+        assert!(matches!(aggregates[0].function, AggregateFunction::Sum));
+        assert_eq!(aggregates[0].alias.as_deref(), Some("COUNT_m"));
+        match *input {
+            PhysicalNode::AdjacencyCount {
+                from_variable,
+                edge_labels,
+                count_variable,
+                input,
+                ..
+            } => {
+                assert_eq!(from_variable, "n");
+                assert_eq!(edge_labels, vec!["KNOWS"]);
+                assert!(matches!(
+                    aggregates[0].expression,
+                    Expression::Variable(ref var) if var.name == count_variable
+                ));
+                assert!(matches!(*input, PhysicalNode::NodeSeqScan { .. }));
+            }
+            other => panic!("Expected AdjacencyCount, got {:?}", other),
+        }
+
+        // Grouping on the far end keeps the expand
+        let optimized = PhysicalOptimizer::new(false)
+            .optimize(aggregate("m"))
+            .unwrap();
+        let PhysicalNode::SortAggregate { input, .. } = optimized.root else {
+            panic!("Expected SortAggregate node at root");
+        };
+        assert!(matches!(*input, PhysicalNode::IndexedExpand { .. }));
+    }
+
     #[test]
     fn test_disable_index_scans_preserves_single_row() {
         let optimizer = PhysicalOptimizer::new(true);
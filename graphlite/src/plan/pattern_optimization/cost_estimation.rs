@@ -392,6 +392,7 @@ mod tests {
 
         let pattern = PathPattern {
             assignment: None,
+            search: None,
             path_type: None,
             elements: vec![],
             location: Location::default(),
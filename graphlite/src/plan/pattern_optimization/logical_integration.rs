@@ -506,6 +506,7 @@ mod tests {
         // Test with single pattern - should not optimize
         let pattern = PathPattern {
             assignment: None,
+            search: None,
             path_type: None,
             elements: vec![],
             location: Location::default(),
@@ -525,6 +526,7 @@ mod tests {
         let patterns: Vec<PathPattern> = (0..15)
             .map(|_| PathPattern {
                 assignment: None,
+                search: None,
                 path_type: None,
                 elements: vec![],
                 location: Location::default(),
@@ -344,6 +344,7 @@ mod tests {
         // Create a simple pattern: (a)-[:R]->(b)
         let start_pattern = PathPattern {
             assignment: None,
+            search: None,
             path_type: None,
             elements: vec![
                 PatternElement::Node(Node {
@@ -409,6 +410,7 @@ mod tests {
         let path = LinearPath {
             start_pattern: PathPattern {
                 assignment: None,
+                search: None,
                 path_type: None,
                 elements: vec![],
                 location: Location::default(),
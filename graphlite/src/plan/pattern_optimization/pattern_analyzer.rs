@@ -471,6 +471,7 @@ mod tests {
     fn create_test_pattern(elements: Vec<PatternElement>) -> PathPattern {
         PathPattern {
             assignment: None,
+            search: None,
             path_type: None,
             elements,
             location: Location::default(),
@@ -0,0 +1,283 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! Plan pinning
+//!
+//! A query's fingerprint identifies its text with whitespace normalized. Once a
+//! plan is pinned for a fingerprint with `gql.pin_plan(fingerprint)`, the
+//! executor runs that plan instead of planning the query again, so an optimizer
+//! change after an upgrade cannot change how the query runs. A pin lasts until
+//! `gql.unpin_plan` removes it or a schema change (dropping a graph or schema,
+//! changing a graph type, creating or dropping an index) invalidates all pins.
+//!
+//! Pins are persisted with the database. Pins that cannot be read back, for
+//! example because the plan format changed, are dropped with a warning.
+
+use crate::ast::parser::parse_query;
+use crate::plan::optimizer::QueryPlanner;
+use crate::plan::physical::PhysicalPlan;
+use crate::storage::StorageManager;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+
+/// Number of recently executed queries remembered for `pin`
+const RECENT_QUERIES: usize = 256;
+
+/// Fingerprint of a query: a hash of its text with whitespace outside string
+/// literals collapsed and any trailing semicolon removed
+pub fn fingerprint(query: &str) -> String {
+    let mut normalized = String::with_capacity(query.len());
+    let mut quote: Option<char> = None;
+    let mut pending_space = false;
+    for c in query.trim().trim_end_matches(';').trim_end().chars() {
+        match quote {
+            Some(q) => {
+                if c == q {
+                    quote = None;
+                }
+                normalized.push(c);
+            }
+            None if c.is_whitespace() => pending_space = true,
+            None => {
+                if pending_space {
+                    normalized.push(' ');
+                    pending_space = false;
+                }
+                if c == '\'' || c == '"' || c == '`' {
+                    quote = Some(c);
+                }
+                normalized.push(c);
+            }
+        }
+    }
+
+    // FNV-1a, stable across processes and releases
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in normalized.bytes() {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    format!("{:016x}", hash)
+}
+
+/// A plan pinned for a query fingerprint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PinnedPlan {
+    pub fingerprint: String,
+    /// Query text the plan was built from
+    pub query: String,
+    pub plan: PhysicalPlan,
+    pub pinned_at: DateTime<Utc>,
+}
+
+/// Pinned plans and the recently executed queries they can be pinned from
+pub struct PlanPins {
+    storage: Arc<StorageManager>,
+    pins: RwLock<HashMap<String, PinnedPlan>>,
+    /// Executions that used a pinned plan, by fingerprint, since startup
+    hits: RwLock<HashMap<String, Arc<AtomicU64>>>,
+    recent: RwLock<RecentQueries>,
+}
+
+#[derive(Default)]
+struct RecentQueries {
+    queries: HashMap<String, String>,
+    order: VecDeque<String>,
+}
+
+impl PlanPins {
+    /// Load the pins persisted in `storage`
+    pub fn load(storage: Arc<StorageManager>) -> Self {
+        let pins = match storage.load_pinned_plans() {
+            Ok(Some(data)) => match serde_json::from_slice::<Vec<PinnedPlan>>(&data) {
+                Ok(pins) => pins
+                    .into_iter()
+                    .map(|pin| (pin.fingerprint.clone(), pin))
+                    .collect(),
+                Err(e) => {
+                    log::warn!("Dropping pinned plans that cannot be read: {}", e);
+                    HashMap::new()
+                }
+            },
+            Ok(None) => HashMap::new(),
+            Err(e) => {
+                log::warn!("Failed to load pinned plans: {}", e);
+                HashMap::new()
+            }
+        };
+        Self {
+            storage,
+            pins: RwLock::new(pins),
+            hits: RwLock::new(HashMap::new()),
+            recent: RwLock::new(RecentQueries::default()),
+        }
+    }
+
+    /// Pinned plan for `fingerprint`, counting the use
+    pub fn pinned_plan(&self, fingerprint: &str) -> Option<PhysicalPlan> {
+        let plan = self.pins.read().ok()?.get(fingerprint)?.plan.clone();
+        self.hit_counter(fingerprint)
+            .fetch_add(1, Ordering::Relaxed);
+        Some(plan)
+    }
+
+    /// Remember `query` so that its plan can be pinned by fingerprint
+    pub fn record_query(&self, fingerprint: &str, query: &str) {
+        let Ok(mut recent) = self.recent.write() else {
+            return;
+        };
+        if recent.queries.contains_key(fingerprint) {
+            return;
+        }
+        if recent.order.len() >= RECENT_QUERIES {
+            if let Some(oldest) = recent.order.pop_front() {
+                recent.queries.remove(&oldest);
+            }
+        }
+        recent
+            .queries
+            .insert(fingerprint.to_string(), query.trim().to_string());
+        recent.order.push_back(fingerprint.to_string());
+    }
+
+    /// Pin the plan the planner currently produces for the query with `fingerprint`
+    ///
+    /// The query must have run recently. Pinning an already pinned fingerprint
+    /// replaces its plan.
+    pub fn pin(&self, fingerprint: &str) -> Result<PinnedPlan, String> {
+        let query = self
+            .recent
+            .read()
+            .map_err(|_| "Failed to read recent queries".to_string())?
+            .queries
+            .get(fingerprint)
+            .cloned()
+            .or_else(|| {
+                self.pins
+                    .read()
+                    .ok()
+                    .and_then(|pins| pins.get(fingerprint).map(|pin| pin.query.clone()))
+            })
+            .ok_or_else(|| {
+                format!(
+                    "No recent query with fingerprint '{}'. Run the query first",
+                    fingerprint
+                )
+            })?;
+
+        let document =
+            parse_query(&query).map_err(|e| format!("Failed to parse pinned query: {}", e))?;
+        let plan = QueryPlanner::new()
+            .plan_query(&document)
+            .map_err(|e| format!("Failed to plan pinned query: {}", e))?;
+        let pin = PinnedPlan {
+            fingerprint: fingerprint.to_string(),
+            query,
+            plan,
+            pinned_at: Utc::now(),
+        };
+
+        self.pins
+            .write()
+            .map_err(|_| "Failed to update pinned plans".to_string())?
+            .insert(fingerprint.to_string(), pin.clone());
+        self.hit_counter(fingerprint).store(0, Ordering::Relaxed);
+        self.persist();
+        Ok(pin)
+    }
+
+    /// Remove the pin for `fingerprint`; returns whether one existed
+    pub fn unpin(&self, fingerprint: &str) -> bool {
+        let removed = self
+            .pins
+            .write()
+            .map(|mut pins| pins.remove(fingerprint).is_some())
+            .unwrap_or(false);
+        if removed {
+            self.persist();
+        }
+        removed
+    }
+
+    /// Remove every pin after a schema change; returns how many were removed
+    pub fn invalidate(&self, reason: &str) -> usize {
+        let removed = match self.pins.write() {
+            Ok(mut pins) => pins.drain().count(),
+            Err(_) => 0,
+        };
+        if removed > 0 {
+            log::warn!("{} pinned plan(s) invalidated by {}", removed, reason);
+            self.persist();
+        }
+        removed
+    }
+
+    /// Pinned plans with the number of executions that used each, by fingerprint
+    pub fn list(&self) -> Vec<(PinnedPlan, u64)> {
+        let mut pins: Vec<_> = self
+            .pins
+            .read()
+            .map(|pins| pins.values().cloned().collect())
+            .unwrap_or_default();
+        pins.sort_by(|a: &PinnedPlan, b| a.fingerprint.cmp(&b.fingerprint));
+        pins.into_iter()
+            .map(|pin| {
+                let hits = self.hit_counter(&pin.fingerprint).load(Ordering::Relaxed);
+                (pin, hits)
+            })
+            .collect()
+    }
+
+    fn hit_counter(&self, fingerprint: &str) -> Arc<AtomicU64> {
+        if let Some(counter) = self
+            .hits
+            .read()
+            .ok()
+            .and_then(|hits| hits.get(fingerprint).cloned())
+        {
+            return counter;
+        }
+        match self.hits.write() {
+            Ok(mut hits) => hits.entry(fingerprint.to_string()).or_default().clone(),
+            Err(_) => Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    fn persist(&self) {
+        let pins: Vec<PinnedPlan> = match self.pins.read() {
+            Ok(pins) => pins.values().cloned().collect(),
+            Err(_) => return,
+        };
+        let result = serde_json::to_vec(&pins)
+            .map_err(|e| e.to_string())
+            .and_then(|data| {
+                self.storage
+                    .save_pinned_plans(&data)
+                    .map_err(|e| e.to_string())
+            });
+        if let Err(e) = result {
+            log::warn!("Failed to save pinned plans: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fingerprint_normalizes_whitespace_only() {
+        let a = fingerprint("MATCH (p:Person)\n  RETURN p.name;");
+        assert_eq!(a, fingerprint("  MATCH (p:Person) RETURN p.name"));
+        assert_eq!(a.len(), 16);
+        assert_ne!(a, fingerprint("MATCH (p:Person) RETURN p.age"));
+        // Whitespace inside string literals is significant
+        assert_ne!(
+            fingerprint("MATCH (p {name: 'a  b'}) RETURN p"),
+            fingerprint("MATCH (p {name: 'a b'}) RETURN p")
+        );
+    }
+}
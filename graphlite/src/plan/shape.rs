@@ -0,0 +1,898 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! Plan shapes for optimizer regression tests
+//!
+//! A `PlanShape` reduces a physical plan to its operators, the variables and
+//! labels each one works on, and the settings that decide its strategy. Cost
+//! and row estimates, storage ids and other values that drift between runs are
+//! left out, so the text form only changes when the plan itself does:
+//!
+//! ```text
+//! Project columns=[name]
+//!   Filter condition=(p.age > 30)
+//!     NodeIndexScan variable=p labels=[Person]
+//! ```
+//!
+//! Matchers built with `op` look for an operator anywhere in the plan:
+//!
+//! ```text
+//! assert_plan_contains(&plan, op("EdgeIndexScan").with_label("INHIBITS"));
+//! assert_plan_contains(&plan, op("HashAggregate").with_descendant(op("AdjacencyCount")));
+//! ```
+//!
+//! The sequential fallback of an `EdgeIndexScan`, used only when the index
+//! does not exist at run time, is not part of the shape.
+
+use std::fmt;
+
+use crate::ast::{Expression, Literal, Operator};
+use crate::coordinator::QueryPlan;
+use crate::plan::physical::{PhysicalNode, PhysicalPlan};
+
+/// One operator of a plan shape
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShapeNode {
+    /// Operator name, e.g. `NodeIndexScan`
+    pub operator: String,
+    /// Strategy settings in display order, e.g. `("direction", "Outgoing")`
+    pub attributes: Vec<(String, String)>,
+    /// Node and relationship labels the operator reads
+    pub labels: Vec<String>,
+    /// Variables the operator binds or reads
+    pub variables: Vec<String>,
+    /// Input operators
+    pub children: Vec<ShapeNode>,
+}
+
+impl ShapeNode {
+    fn new(operator: &str) -> Self {
+        Self {
+            operator: operator.to_string(),
+            attributes: Vec::new(),
+            labels: Vec::new(),
+            variables: Vec::new(),
+            children: Vec::new(),
+        }
+    }
+
+    fn attr(mut self, key: &str, value: impl Into<String>) -> Self {
+        self.attributes.push((key.to_string(), value.into()));
+        self
+    }
+
+    fn list_attr(self, key: &str, items: &[String]) -> Self {
+        if items.is_empty() {
+            self
+        } else {
+            self.attr(key, format!("[{}]", items.join(", ")))
+        }
+    }
+
+    fn variable(mut self, variable: &str) -> Self {
+        self.variables.push(variable.to_string());
+        self
+    }
+
+    fn labels(mut self, labels: &[String]) -> Self {
+        self.labels.extend(labels.iter().cloned());
+        self
+    }
+
+    fn child(mut self, child: &PhysicalNode) -> Self {
+        self.children.push(ShapeNode::from(child));
+        self
+    }
+
+    /// Value of an attribute, if the operator has it
+    pub fn attribute(&self, key: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(name, _)| name == key)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// This node and every node below it, parents first
+    pub fn nodes(&self) -> Vec<&ShapeNode> {
+        let mut nodes = vec![self];
+        for child in &self.children {
+            nodes.extend(child.nodes());
+        }
+        nodes
+    }
+
+    fn write(&self, f: &mut fmt::Formatter, depth: usize) -> fmt::Result {
+        write!(f, "{}{}", "  ".repeat(depth), self.operator)?;
+        for (key, value) in &self.attributes {
+            write!(f, " {}={}", key, value)?;
+        }
+        writeln!(f)?;
+        for child in &self.children {
+            child.write(f, depth + 1)?;
+        }
+        Ok(())
+    }
+}
+
+impl From<&PhysicalNode> for ShapeNode {
+    fn from(node: &PhysicalNode) -> Self {
+        let shape = ShapeNode::new(node.operator_name());
+        match node {
+            PhysicalNode::NodeSeqScan {
+                variable,
+                labels,
+                properties,
+                ..
+            }
+            | PhysicalNode::NodeIndexScan {
+                variable,
+                labels,
+                properties,
+                ..
+            }
+            | PhysicalNode::EdgeSeqScan {
+                variable,
+                labels,
+                properties,
+                ..
+            } => shape
+                .attr("variable", variable)
+                .list_attr("labels", labels)
+                .list_attr("properties", &property_keys(properties.as_ref()))
+                .variable(variable)
+                .labels(labels),
+            PhysicalNode::EdgeIndexScan {
+                from_variable,
+                from_labels,
+                edge_variable,
+                edge_label,
+                to_variable,
+                direction,
+                predicates,
+                ..
+            } => {
+                let predicates: Vec<String> = predicates
+                    .iter()
+                    .map(|predicate| {
+                        let operand = match &predicate.path {
+                            Some(path) => format!(
+                                "JSON_GET({}.{}, '{}')",
+                                edge_variable, predicate.property, path
+                            ),
+                            None => format!("{}.{}", edge_variable, predicate.property),
+                        };
+                        format!(
+                            "{} {} {}",
+                            operand,
+                            operator_symbol(&predicate.operator),
+                            literal_text(&predicate.value)
+                        )
+                    })
+                    .collect();
+                shape
+                    .attr("from", from_variable)
+                    .list_attr("from_labels", from_labels)
+                    .attr("edge", edge_variable)
+                    .attr("label", edge_label)
+                    .attr("to", to_variable)
+                    .attr("direction", format!("{:?}", direction))
+                    .list_attr("predicates", &predicates)
+                    .variable(from_variable)
+                    .variable(edge_variable)
+                    .variable(to_variable)
+                    .labels(from_labels)
+                    .labels(std::slice::from_ref(edge_label))
+            }
+            PhysicalNode::IndexedExpand {
+                from_variable,
+                edge_variable,
+                to_variable,
+                edge_labels,
+                direction,
+                properties,
+                input,
+                ..
+            }
+            | PhysicalNode::HashExpand {
+                from_variable,
+                edge_variable,
+                to_variable,
+                edge_labels,
+                direction,
+                properties,
+                input,
+                ..
+            } => {
+                let mut shape = shape.attr("from", from_variable).variable(from_variable);
+                if let Some(edge_variable) = edge_variable {
+                    shape = shape.attr("edge", edge_variable).variable(edge_variable);
+                }
+                shape
+                    .attr("to", to_variable)
+                    .list_attr("labels", edge_labels)
+                    .attr("direction", format!("{:?}", direction))
+                    .list_attr("properties", &property_keys(properties.as_ref()))
+                    .variable(to_variable)
+                    .labels(edge_labels)
+                    .child(input)
+            }
+            PhysicalNode::AdjacencyCount {
+                from_variable,
+                edge_labels,
+                direction,
+                count_variable,
+                input,
+                ..
+            } => shape
+                .attr("from", from_variable)
+                .list_attr("labels", edge_labels)
+                .attr("direction", format!("{:?}", direction))
+                .attr("count", count_variable)
+                .variable(from_variable)
+                .variable(count_variable)
+                .labels(edge_labels)
+                .child(input),
+            PhysicalNode::VariableLengthExpand {
+                from_variable,
+                edge_variable,
+                to_variable,
+                path_variable,
+                edge_labels,
+                direction,
+                min_hops,
+                max_hops,
+                input,
+                ..
+            } => {
+                let mut shape = shape.attr("from", from_variable).variable(from_variable);
+                if let Some(edge_variable) = edge_variable {
+                    shape = shape.attr("edge", edge_variable).variable(edge_variable);
+                }
+                if let Some(path_variable) = path_variable {
+                    shape = shape.attr("path", path_variable).variable(path_variable);
+                }
+                if let Some(max_hops) = max_hops {
+                    shape = shape.attr("max_hops", max_hops.to_string());
+                }
+                shape
+                    .attr("to", to_variable)
+                    .list_attr("labels", edge_labels)
+                    .attr("direction", format!("{:?}", direction))
+                    .attr("min_hops", min_hops.to_string())
+                    .variable(to_variable)
+                    .labels(edge_labels)
+                    .child(input)
+            }
+            PhysicalNode::ShortestPath {
+                from_variable,
+                edge_variable,
+                to_variable,
+                path_variable,
+                edge_labels,
+                direction,
+                min_hops,
+                max_hops,
+                all,
+                input,
+                ..
+            } => {
+                let mut shape = shape.attr("from", from_variable).variable(from_variable);
+                if let Some(edge_variable) = edge_variable {
+                    shape = shape.attr("edge", edge_variable).variable(edge_variable);
+                }
+                if let Some(path_variable) = path_variable {
+                    shape = shape.attr("path", path_variable).variable(path_variable);
+                }
+                if let Some(max_hops) = max_hops {
+                    shape = shape.attr("max_hops", max_hops.to_string());
+                }
+                shape
+                    .attr("to", to_variable)
+                    .list_attr("labels", edge_labels)
+                    .attr("direction", format!("{:?}", direction))
+                    .attr("min_hops", min_hops.to_string())
+                    .attr("all", all.to_string())
+                    .variable(to_variable)
+                    .labels(edge_labels)
+                    .child(input)
+            }
+            PhysicalNode::PathTraversal {
+                path_type,
+                from_variable,
+                to_variable,
+                path_elements,
+                input,
+                ..
+            } => {
+                let labels: Vec<String> = path_elements
+                    .iter()
+                    .flat_map(|element| element.edge_labels.iter().cloned())
+                    .collect();
+                shape
+                    .attr("path_type", format!("{:?}", path_type))
+                    .attr("from", from_variable)
+                    .attr("to", to_variable)
+                    .list_attr("labels", &labels)
+                    .variable(from_variable)
+                    .variable(to_variable)
+                    .labels(&labels)
+                    .child(input)
+            }
+            PhysicalNode::Filter {
+                condition, input, ..
+            }
+            | PhysicalNode::Having {
+                condition, input, ..
+            } => shape
+                .attr("condition", expression_text(condition))
+                .child(input),
+            PhysicalNode::Project {
+                expressions, input, ..
+            } => {
+                let columns: Vec<String> = expressions
+                    .iter()
+                    .map(|item| {
+                        item.alias
+                            .clone()
+                            .unwrap_or_else(|| expression_text(&item.expression))
+                    })
+                    .collect();
+                shape.list_attr("columns", &columns).child(input)
+            }
+            PhysicalNode::HashJoin {
+                join_type,
+                build_keys,
+                probe_keys,
+                build,
+                probe,
+                ..
+            } => shape
+                .attr("join_type", format!("{:?}", join_type))
+                .list_attr("build_keys", &expression_texts(build_keys))
+                .list_attr("probe_keys", &expression_texts(probe_keys))
+                .child(build)
+                .child(probe),
+            PhysicalNode::NestedLoopJoin {
+                join_type,
+                left,
+                right,
+                ..
+            } => shape
+                .attr("join_type", format!("{:?}", join_type))
+                .child(left)
+                .child(right),
+            PhysicalNode::SortMergeJoin {
+                join_type,
+                left_keys,
+                right_keys,
+                left,
+                right,
+                ..
+            } => shape
+                .attr("join_type", format!("{:?}", join_type))
+                .list_attr("left_keys", &expression_texts(left_keys))
+                .list_attr("right_keys", &expression_texts(right_keys))
+                .child(left)
+                .child(right),
+            PhysicalNode::UnionAll { inputs, all, .. } => inputs
+                .iter()
+                .fold(shape.attr("all", all.to_string()), |shape, input| {
+                    shape.child(input)
+                }),
+            PhysicalNode::Intersect {
+                left, right, all, ..
+            }
+            | PhysicalNode::Except {
+                left, right, all, ..
+            } => shape.attr("all", all.to_string()).child(left).child(right),
+            PhysicalNode::HashAggregate {
+                group_by,
+                aggregates,
+                input,
+                ..
+            }
+            | PhysicalNode::SortAggregate {
+                group_by,
+                aggregates,
+                input,
+                ..
+            } => {
+                let aggregates: Vec<String> = aggregates
+                    .iter()
+                    .map(|item| {
+                        let call = format!(
+                            "{}({})",
+                            format!("{:?}", item.function).to_uppercase(),
+                            expression_text(&item.expression)
+                        );
+                        match &item.alias {
+                            Some(alias) => format!("{} AS {}", call, alias),
+                            None => call,
+                        }
+                    })
+                    .collect();
+                shape
+                    .list_attr("group_by", &expression_texts(group_by))
+                    .list_attr("aggregates", &aggregates)
+                    .child(input)
+            }
+            PhysicalNode::ExternalSort {
+                expressions, input, ..
+            }
+            | PhysicalNode::InMemorySort {
+                expressions, input, ..
+            } => {
+                let keys: Vec<String> = expressions
+                    .iter()
+                    .map(|item| {
+                        let order = if item.ascending { "ASC" } else { "DESC" };
+                        format!("{} {}", expression_text(&item.expression), order)
+                    })
+                    .collect();
+                shape.list_attr("keys", &keys).child(input)
+            }
+            PhysicalNode::Distinct { input, .. } => shape.child(input),
+            PhysicalNode::Limit {
+                count,
+                offset,
+                input,
+                ..
+            } => {
+                let shape = shape.attr("count", count.to_string());
+                match offset {
+                    Some(offset) => shape.attr("offset", offset.to_string()),
+                    None => shape,
+                }
+                .child(input)
+            }
+            PhysicalNode::GenericFunction {
+                function_name,
+                input,
+                ..
+            } => shape.attr("function", function_name).child(input),
+            PhysicalNode::ExistsSubquery { subplan, .. }
+            | PhysicalNode::NotExistsSubquery { subplan, .. }
+            | PhysicalNode::ScalarSubquery { subplan, .. } => shape.child(subplan),
+            PhysicalNode::InSubquery {
+                expression,
+                subplan,
+                ..
+            }
+            | PhysicalNode::NotInSubquery {
+                expression,
+                subplan,
+                ..
+            } => shape
+                .attr("expression", expression_text(expression))
+                .child(subplan),
+            PhysicalNode::WithQuery { .. } | PhysicalNode::SingleRow { .. } => shape,
+            PhysicalNode::Unwind {
+                expression,
+                variable,
+                input,
+                ..
+            } => {
+                let shape = shape
+                    .attr("expression", expression_text(expression))
+                    .attr("variable", variable)
+                    .variable(variable);
+                match input {
+                    Some(input) => shape.child(input),
+                    None => shape,
+                }
+            }
+            PhysicalNode::GraphIndexScan {
+                index_name,
+                operation,
+                ..
+            } => {
+                let operation = format!("{:?}", operation);
+                let operation = operation
+                    .split(|c: char| !c.is_alphanumeric())
+                    .next()
+                    .unwrap_or_default();
+                shape.attr("index", index_name).attr("operation", operation)
+            }
+            PhysicalNode::IndexJoin {
+                left,
+                right,
+                join_type,
+                index_name,
+                ..
+            } => shape
+                .attr("index", index_name)
+                .attr("join_type", format!("{:?}", join_type))
+                .child(left)
+                .child(right),
+            PhysicalNode::Insert {
+                node_creations,
+                edge_creations,
+                ..
+            } => {
+                let labels: Vec<String> = node_creations
+                    .iter()
+                    .flat_map(|node| node.labels.iter().cloned())
+                    .chain(edge_creations.iter().map(|edge| edge.label.clone()))
+                    .collect();
+                shape
+                    .attr("nodes", node_creations.len().to_string())
+                    .attr("edges", edge_creations.len().to_string())
+                    .list_attr("labels", &labels)
+                    .labels(&labels)
+            }
+            PhysicalNode::Update {
+                target_variable,
+                properties,
+                input,
+                ..
+            } => shape
+                .attr("target", target_variable)
+                .list_attr("properties", &property_keys(Some(properties)))
+                .variable(target_variable)
+                .child(input),
+            PhysicalNode::Delete {
+                target_variables,
+                detach,
+                input,
+                ..
+            } => target_variables
+                .iter()
+                .fold(shape, |shape, variable| shape.variable(variable))
+                .list_attr("targets", target_variables)
+                .attr("detach", detach.to_string())
+                .child(input),
+        }
+    }
+}
+
+/// Sorted property names of a property map
+fn property_keys<V>(properties: Option<&std::collections::HashMap<String, V>>) -> Vec<String> {
+    let mut keys: Vec<String> = properties
+        .map(|properties| properties.keys().cloned().collect())
+        .unwrap_or_default();
+    keys.sort();
+    keys
+}
+
+fn expression_texts(expressions: &[Expression]) -> Vec<String> {
+    expressions.iter().map(expression_text).collect()
+}
+
+/// GQL-like text of an expression; forms the shape does not spell out become `…`
+fn expression_text(expression: &Expression) -> String {
+    match expression {
+        Expression::PropertyAccess(access) => format!("{}.{}", access.object, access.property),
+        Expression::Variable(variable) => variable.name.clone(),
+        Expression::Parameter(parameter) => format!("${}", parameter.name),
+        Expression::Literal(literal) => literal_text(literal),
+        Expression::Binary(binary) => format!(
+            "({} {} {})",
+            expression_text(&binary.left),
+            operator_symbol(&binary.operator),
+            expression_text(&binary.right)
+        ),
+        Expression::Unary(unary) => format!(
+            "{} {}",
+            operator_symbol(&unary.operator),
+            expression_text(&unary.expression)
+        ),
+        Expression::FunctionCall(call) => format!(
+            "{}({})",
+            call.name.to_uppercase(),
+            expression_texts(&call.arguments).join(", ")
+        ),
+        _ => "…".to_string(),
+    }
+}
+
+fn literal_text(literal: &Literal) -> String {
+    match literal {
+        Literal::String(s) => format!("'{}'", s),
+        Literal::Integer(n) => n.to_string(),
+        Literal::Float(n) => n.to_string(),
+        Literal::Boolean(b) => b.to_string().to_uppercase(),
+        Literal::Null => "NULL".to_string(),
+        Literal::DateTime(s) | Literal::Duration(s) | Literal::TimeWindow(s) => s.clone(),
+        Literal::Vector(items) => format!("{:?}", items),
+        Literal::List(items) => format!(
+            "[{}]",
+            items
+                .iter()
+                .map(literal_text)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+fn operator_symbol(operator: &Operator) -> &'static str {
+    match operator {
+        Operator::Plus => "+",
+        Operator::Minus => "-",
+        Operator::Star => "*",
+        Operator::Slash => "/",
+        Operator::Percent => "%",
+        Operator::Caret => "^",
+        Operator::Equal => "=",
+        Operator::NotEqual => "<>",
+        Operator::LessThan => "<",
+        Operator::LessEqual => "<=",
+        Operator::GreaterThan => ">",
+        Operator::GreaterEqual => ">=",
+        Operator::Regex => "=~",
+        Operator::And => "AND",
+        Operator::Or => "OR",
+        Operator::Not => "NOT",
+        Operator::Xor => "XOR",
+        Operator::In => "IN",
+        Operator::NotIn => "NOT IN",
+        Operator::Contains => "CONTAINS",
+        Operator::Starts => "STARTS WITH",
+        Operator::Ends => "ENDS WITH",
+        Operator::Exists => "EXISTS",
+        Operator::Like => "LIKE",
+        Operator::Matches => "MATCHES",
+        Operator::FuzzyEqual => "~=",
+        Operator::Concat => "||",
+        Operator::Within => "WITHIN",
+    }
+}
+
+/// Stable, comparable form of a physical plan
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlanShape {
+    root: ShapeNode,
+}
+
+impl PlanShape {
+    /// Shape of the physical plan of an explained query
+    pub fn of(plan: &QueryPlan) -> Self {
+        Self::from(&plan.physical_plan)
+    }
+
+    /// The root operator
+    pub fn root(&self) -> &ShapeNode {
+        &self.root
+    }
+
+    /// Operators matching `matcher`, parents first
+    pub fn find(&self, matcher: &PlanMatcher) -> Vec<&ShapeNode> {
+        self.root
+            .nodes()
+            .into_iter()
+            .filter(|node| matcher.matches(node))
+            .collect()
+    }
+
+    /// Whether any operator matches `matcher`
+    pub fn contains(&self, matcher: &PlanMatcher) -> bool {
+        !self.find(matcher).is_empty()
+    }
+}
+
+impl From<&PhysicalPlan> for PlanShape {
+    fn from(plan: &PhysicalPlan) -> Self {
+        Self {
+            root: ShapeNode::from(&plan.root),
+        }
+    }
+}
+
+impl fmt::Display for PlanShape {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.root.write(f, 0)
+    }
+}
+
+/// Pattern for one operator of a plan shape, built with `op`
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlanMatcher {
+    operator: String,
+    labels: Vec<String>,
+    variables: Vec<String>,
+    attributes: Vec<(String, String)>,
+    children: Vec<PlanMatcher>,
+    descendants: Vec<PlanMatcher>,
+}
+
+/// Match operators named `operator`, e.g. `op("NodeIndexScan")`; `"*"` matches any
+pub fn op(operator: &str) -> PlanMatcher {
+    PlanMatcher {
+        operator: operator.to_string(),
+        labels: Vec::new(),
+        variables: Vec::new(),
+        attributes: Vec::new(),
+        children: Vec::new(),
+        descendants: Vec::new(),
+    }
+}
+
+impl PlanMatcher {
+    /// The operator also reads `label`
+    pub fn with_label(mut self, label: &str) -> Self {
+        self.labels.push(label.to_string());
+        self
+    }
+
+    /// The operator also binds or reads `variable`
+    pub fn with_variable(mut self, variable: &str) -> Self {
+        self.variables.push(variable.to_string());
+        self
+    }
+
+    /// The operator also has attribute `key` with exactly this text
+    pub fn with_attr(mut self, key: &str, value: &str) -> Self {
+        self.attributes.push((key.to_string(), value.to_string()));
+        self
+    }
+
+    /// One of the operator's direct inputs also matches `child`
+    pub fn with_child(mut self, child: PlanMatcher) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    /// Some operator anywhere below this one also matches `descendant`
+    pub fn with_descendant(mut self, descendant: PlanMatcher) -> Self {
+        self.descendants.push(descendant);
+        self
+    }
+
+    /// Whether `node` itself matches
+    pub fn matches(&self, node: &ShapeNode) -> bool {
+        (self.operator == "*" || self.operator == node.operator)
+            && self.labels.iter().all(|label| node.labels.contains(label))
+            && self
+                .variables
+                .iter()
+                .all(|variable| node.variables.contains(variable))
+            && self
+                .attributes
+                .iter()
+                .all(|(key, value)| node.attribute(key) == Some(value.as_str()))
+            && self
+                .children
+                .iter()
+                .all(|child| node.children.iter().any(|input| child.matches(input)))
+            && self.descendants.iter().all(|descendant| {
+                node.children
+                    .iter()
+                    .flat_map(|input| input.nodes())
+                    .any(|below| descendant.matches(below))
+            })
+    }
+}
+
+impl fmt::Display for PlanMatcher {
+    /// The matcher as it would be written, for assertion messages
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "op({:?})", self.operator)?;
+        for label in &self.labels {
+            write!(f, ".with_label({:?})", label)?;
+        }
+        for variable in &self.variables {
+            write!(f, ".with_variable({:?})", variable)?;
+        }
+        for (key, value) in &self.attributes {
+            write!(f, ".with_attr({:?}, {:?})", key, value)?;
+        }
+        for child in &self.children {
+            write!(f, ".with_child({})", child)?;
+        }
+        for descendant in &self.descendants {
+            write!(f, ".with_descendant({})", descendant)?;
+        }
+        Ok(())
+    }
+}
+
+/// Panic with the plan's shape unless some operator matches `matcher`
+#[track_caller]
+pub fn assert_plan_contains(plan: &QueryPlan, matcher: PlanMatcher) {
+    let shape = PlanShape::of(plan);
+    assert!(
+        shape.contains(&matcher),
+        "expected the plan to contain {}\nplan:\n{}",
+        matcher,
+        shape
+    );
+}
+
+/// Panic with the plan's shape if any operator matches `matcher`
+#[track_caller]
+pub fn assert_plan_not_contains(plan: &QueryPlan, matcher: PlanMatcher) {
+    let shape = PlanShape::of(plan);
+    assert!(
+        !shape.contains(&matcher),
+        "expected the plan not to contain {}\nplan:\n{}",
+        matcher,
+        shape
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::{BinaryExpression, Location, PropertyAccess};
+    use crate::plan::physical::{OutputType, ProjectionItem};
+
+    fn scan_people() -> PhysicalNode {
+        PhysicalNode::NodeIndexScan {
+            variable: "p".to_string(),
+            labels: vec!["Person".to_string()],
+            properties: None,
+            estimated_rows: 1000,
+            estimated_cost: 100.0,
+        }
+    }
+
+    fn adults_by_name() -> PhysicalPlan {
+        let condition = Expression::Binary(BinaryExpression {
+            left: Box::new(Expression::PropertyAccess(PropertyAccess {
+                object: "p".to_string(),
+                property: "age".to_string(),
+                location: Location::default(),
+            })),
+            operator: Operator::GreaterEqual,
+            right: Box::new(Expression::Literal(Literal::Integer(18))),
+            location: Location::default(),
+        });
+        PhysicalPlan::new(PhysicalNode::Project {
+            expressions: vec![ProjectionItem {
+                expression: Expression::PropertyAccess(PropertyAccess {
+                    object: "p".to_string(),
+                    property: "name".to_string(),
+                    location: Location::default(),
+                }),
+                alias: Some("name".to_string()),
+                output_type: OutputType::String,
+            }],
+            input: Box::new(PhysicalNode::Filter {
+                condition,
+                input: Box::new(scan_people()),
+                selectivity: 0.5,
+                estimated_rows: 500,
+                estimated_cost: 150.0,
+            }),
+            estimated_rows: 500,
+            estimated_cost: 160.0,
+        })
+    }
+
+    #[test]
+    fn test_shape_text_leaves_out_estimates() {
+        let shape = PlanShape::from(&adults_by_name());
+        assert_eq!(
+            shape.to_string(),
+            "Project columns=[name]\n\
+             \x20 Filter condition=(p.age >= 18)\n\
+             \x20   NodeIndexScan variable=p labels=[Person]\n"
+        );
+
+        let mut cheaper = adults_by_name();
+        cheaper.estimated_cost = 1.0;
+        assert_eq!(PlanShape::from(&cheaper), shape);
+    }
+
+    #[test]
+    fn test_matchers() {
+        let shape = PlanShape::from(&adults_by_name());
+
+        assert!(shape.contains(&op("NodeIndexScan").with_label("Person")));
+        assert!(shape.contains(&op("NodeIndexScan").with_variable("p")));
+        assert!(!shape.contains(&op("NodeIndexScan").with_label("Company")));
+        assert!(!shape.contains(&op("NodeSeqScan")));
+        assert!(shape.contains(&op("Filter").with_attr("condition", "(p.age >= 18)")));
+        assert!(shape.contains(&op("Project").with_child(op("Filter"))));
+        assert!(!shape.contains(&op("Project").with_child(op("NodeIndexScan"))));
+        assert!(shape.contains(&op("Project").with_descendant(op("NodeIndexScan"))));
+        assert_eq!(shape.find(&op("*")).len(), 3);
+
+        assert_eq!(
+            op("Project")
+                .with_descendant(op("NodeIndexScan").with_label("Person"))
+                .to_string(),
+            r#"op("Project").with_descendant(op("NodeIndexScan").with_label("Person"))"#
+        );
+    }
+}
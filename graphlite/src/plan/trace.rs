@@ -275,6 +275,42 @@ impl PlanTrace {
                     variable
                 ));
             }
+            PhysicalNode::EdgeIndexScan {
+                from_variable,
+                edge_variable,
+                edge_label,
+                to_variable,
+                predicates,
+                estimated_rows,
+                estimated_cost,
+                ..
+            } => {
+                output.push_str(&format!(
+                    "{}EdgeIndexScan[{} -[{}:{}]- {}] → {} rows, cost: {:.1}\n",
+                    prefix,
+                    from_variable,
+                    edge_variable,
+                    edge_label,
+                    to_variable,
+                    estimated_rows,
+                    estimated_cost
+                ));
+                output.push_str(&format!(
+                    "{}    Predicates: {}\n",
+                    " ".repeat(prefix.len()),
+                    predicates
+                        .iter()
+                        .map(|p| match &p.path {
+                            Some(path) => format!(
+                                "JSON_GET({}.{}, '{}') {:?}",
+                                edge_variable, p.property, path, p.operator
+                            ),
+                            None => format!("{}.{} {:?}", edge_variable, p.property, p.operator),
+                        })
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ));
+            }
             PhysicalNode::IndexedExpand {
                 from_variable,
                 to_variable,
@@ -337,6 +373,86 @@ impl PlanTrace {
                 ));
                 self.format_plan_node(input, output, depth + 1, true);
             }
+            PhysicalNode::AdjacencyCount {
+                from_variable,
+                edge_labels,
+                count_variable,
+                input,
+                estimated_rows,
+                estimated_cost,
+                ..
+            } => {
+                output.push_str(&format!(
+                    "{}AdjacencyCount[{}:{}] → {} rows, cost: {:.1}\n",
+                    prefix,
+                    from_variable,
+                    edge_labels.join("|"),
+                    estimated_rows,
+                    estimated_cost
+                ));
+                output.push_str(&format!(
+                    "{}    Count: {} from adjacency index\n",
+                    " ".repeat(prefix.len()),
+                    count_variable
+                ));
+                self.format_plan_node(input, output, depth + 1, true);
+            }
+            PhysicalNode::VariableLengthExpand {
+                from_variable,
+                to_variable,
+                edge_labels,
+                min_hops,
+                max_hops,
+                input,
+                estimated_rows,
+                estimated_cost,
+                ..
+            } => {
+                output.push_str(&format!(
+                    "{}VariableLengthExpand[{} → {}:{}*{}] → {} rows, cost: {:.1}\n",
+                    prefix,
+                    from_variable,
+                    to_variable,
+                    edge_labels.join("|"),
+                    hop_range(*min_hops, *max_hops),
+                    estimated_rows,
+                    estimated_cost
+                ));
+                output.push_str(&format!(
+                    "{}    Method: Breadth-first search without revisiting nodes\n",
+                    " ".repeat(prefix.len())
+                ));
+                self.format_plan_node(input, output, depth + 1, true);
+            }
+            PhysicalNode::ShortestPath {
+                from_variable,
+                to_variable,
+                edge_labels,
+                min_hops,
+                max_hops,
+                all,
+                input,
+                estimated_rows,
+                estimated_cost,
+                ..
+            } => {
+                output.push_str(&format!(
+                    "{}ShortestPath[{} {} → {}:{}*{}] → {} rows, cost: {:.1}\n",
+                    prefix,
+                    search_keyword(*all),
+                    from_variable,
+                    to_variable,
+                    edge_labels.join("|"),
+                    hop_range(*min_hops, *max_hops),
+                    estimated_rows,
+                    estimated_cost
+                ));
+                output.push_str(&format!(
+                    "{}    Method: Bidirectional breadth-first search\n",
+                    " ".repeat(prefix.len())
+                ));
+                self.format_plan_node(input, output, depth + 1, true);
+            }
             PhysicalNode::Filter {
                 condition,
                 input,
@@ -754,6 +870,46 @@ impl PlanTrace {
                 ));
                 output.push_str(&self.format_logical_plan(input, indent + 1));
             }
+            LogicalNode::VariableLengthExpand {
+                from_variable,
+                to_variable,
+                edge_labels,
+                min_hops,
+                max_hops,
+                input,
+                ..
+            } => {
+                output.push_str(&format!(
+                    "{}VariableLengthExpand({} -> {}:{}*{})\n",
+                    prefix,
+                    from_variable,
+                    to_variable,
+                    edge_labels.join("|"),
+                    hop_range(*min_hops, *max_hops)
+                ));
+                output.push_str(&self.format_logical_plan(input, indent + 1));
+            }
+            LogicalNode::ShortestPath {
+                from_variable,
+                to_variable,
+                edge_labels,
+                min_hops,
+                max_hops,
+                all,
+                input,
+                ..
+            } => {
+                output.push_str(&format!(
+                    "{}ShortestPath({} {} -> {}:{}*{})\n",
+                    prefix,
+                    search_keyword(*all),
+                    from_variable,
+                    to_variable,
+                    edge_labels.join("|"),
+                    hop_range(*min_hops, *max_hops)
+                ));
+                output.push_str(&self.format_logical_plan(input, indent + 1));
+            }
             LogicalNode::Filter { condition, input } => {
                 output.push_str(&format!("{}Filter({:?})\n", prefix, condition));
                 output.push_str(&self.format_logical_plan(input, indent + 1));
@@ -839,6 +995,28 @@ impl PlanTrace {
                     estimated_cost
                 ));
             }
+            PhysicalNode::EdgeIndexScan {
+                from_variable,
+                edge_variable,
+                edge_label,
+                to_variable,
+                fallback,
+                estimated_rows,
+                estimated_cost,
+                ..
+            } => {
+                output.push_str(&format!(
+                    "{}EdgeIndexScan({} -[{}:{}]- {}) [rows={}, cost={:.2}]\n",
+                    prefix,
+                    from_variable,
+                    edge_variable,
+                    edge_label,
+                    to_variable,
+                    estimated_rows,
+                    estimated_cost
+                ));
+                output.push_str(&self.format_physical_plan(fallback, indent + 1));
+            }
             PhysicalNode::IndexedExpand {
                 from_variable,
                 to_variable,
@@ -879,6 +1057,72 @@ impl PlanTrace {
                 ));
                 output.push_str(&self.format_physical_plan(input, indent + 1));
             }
+            PhysicalNode::AdjacencyCount {
+                from_variable,
+                edge_labels,
+                input,
+                estimated_rows,
+                estimated_cost,
+                ..
+            } => {
+                output.push_str(&format!(
+                    "{}AdjacencyCount({}:{}) [rows={}, cost={:.2}]\n",
+                    prefix,
+                    from_variable,
+                    edge_labels.join("|"),
+                    estimated_rows,
+                    estimated_cost
+                ));
+                output.push_str(&self.format_physical_plan(input, indent + 1));
+            }
+            PhysicalNode::VariableLengthExpand {
+                from_variable,
+                to_variable,
+                edge_labels,
+                min_hops,
+                max_hops,
+                input,
+                estimated_rows,
+                estimated_cost,
+                ..
+            } => {
+                output.push_str(&format!(
+                    "{}VariableLengthExpand({} -> {}:{}*{}) [rows={}, cost={:.2}]\n",
+                    prefix,
+                    from_variable,
+                    to_variable,
+                    edge_labels.join("|"),
+                    hop_range(*min_hops, *max_hops),
+                    estimated_rows,
+                    estimated_cost
+                ));
+                output.push_str(&self.format_physical_plan(input, indent + 1));
+            }
+            PhysicalNode::ShortestPath {
+                from_variable,
+                to_variable,
+                edge_labels,
+                min_hops,
+                max_hops,
+                all,
+                input,
+                estimated_rows,
+                estimated_cost,
+                ..
+            } => {
+                output.push_str(&format!(
+                    "{}ShortestPath({} {} -> {}:{}*{}) [rows={}, cost={:.2}]\n",
+                    prefix,
+                    search_keyword(*all),
+                    from_variable,
+                    to_variable,
+                    edge_labels.join("|"),
+                    hop_range(*min_hops, *max_hops),
+                    estimated_rows,
+                    estimated_cost
+                ));
+                output.push_str(&self.format_physical_plan(input, indent + 1));
+            }
             PhysicalNode::Filter {
                 input,
                 selectivity,
@@ -1039,6 +1283,23 @@ impl PlanTrace {
     }
 }
 
+/// Hop bounds of a variable-length expand as written in a pattern: `1..3`, `2..`
+fn hop_range(min_hops: u32, max_hops: Option<u32>) -> String {
+    match max_hops {
+        Some(max) => format!("{}..{}", min_hops, max),
+        None => format!("{}..", min_hops),
+    }
+}
+
+/// Path search prefix of a ShortestPath operator, without SHORTEST
+fn search_keyword(all: bool) -> &'static str {
+    if all {
+        "ALL"
+    } else {
+        "ANY"
+    }
+}
+
 impl Default for PlanTracer {
     fn default() -> Self {
         Self::new()
@@ -283,6 +283,8 @@ fn parse_data_type(tokens: &[Token]) -> IResult<&[Token], DataType> {
         map(tag_identifier("TIMESTAMP"), |_| DataType::Timestamp),
         // UUID type
         map(tag_identifier("UUID"), |_| DataType::Uuid),
+        // JSON document type
+        map(tag_identifier("JSON"), |_| DataType::Json),
         // Array type: ARRAY<ElementType>
         map(
             preceded(
@@ -22,6 +22,39 @@ pub struct GraphTypeDefinition {
     pub metadata: HashMap<String, serde_json::Value>,
 }
 
+impl GraphTypeDefinition {
+    /// Node type and key property that property `property` of a node with
+    /// `labels` refers to, if it is a reference property
+    pub fn node_reference(&self, labels: &[String], property: &str) -> Option<(&str, &str)> {
+        self.node_types
+            .iter()
+            .filter(|node_type| labels.contains(&node_type.label))
+            .flat_map(|node_type| &node_type.properties)
+            .find(|definition| definition.name == property)
+            .and_then(PropertyDefinition::reference)
+    }
+
+    /// Node types and key properties referred to by reference properties, without duplicates
+    pub fn reference_keys(&self) -> Vec<(String, String)> {
+        let mut keys: Vec<(String, String)> = Vec::new();
+        let properties = self
+            .node_types
+            .iter()
+            .flat_map(|node_type| &node_type.properties)
+            .chain(
+                self.edge_types
+                    .iter()
+                    .flat_map(|edge_type| &edge_type.properties),
+            );
+        for (label, key) in properties.filter_map(PropertyDefinition::reference) {
+            if !keys.iter().any(|(l, k)| l == label && k == key) {
+                keys.push((label.to_string(), key.to_string()));
+            }
+        }
+        keys
+    }
+}
+
 /// Version information for a graph type
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 pub struct GraphTypeVersion {
@@ -144,6 +177,21 @@ pub struct PropertyDefinition {
     pub constraints: Vec<Constraint>,       // Property-level constraints
 }
 
+impl PropertyDefinition {
+    /// Node type and key property a reference property refers to
+    ///
+    /// Reference properties carry a `ForeignKey` constraint whose `references`
+    /// is `Label.key`.
+    pub fn reference(&self) -> Option<(&str, &str)> {
+        self.constraints
+            .iter()
+            .find_map(|constraint| match constraint {
+                Constraint::ForeignKey { references, .. } => references.split_once('.'),
+                _ => None,
+            })
+    }
+}
+
 /// Supported data types in graph schemas
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum DataType {
@@ -0,0 +1,104 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! Per-session statement history
+//!
+//! Each session keeps its most recent statements so they can be inspected with
+//! `CALL gql.session_history()` or replayed from the REPL. Only the newest
+//! entries are kept; older ones are discarded once the capacity is reached.
+
+use std::collections::VecDeque;
+
+/// Default number of statements kept per session
+pub const DEFAULT_SESSION_HISTORY_SIZE: usize = 100;
+
+/// One executed statement
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistoryEntry {
+    /// Position of the statement in the session, starting at 1
+    pub sequence: u64,
+    /// Statement text as submitted
+    pub statement: String,
+    /// When the statement was submitted
+    pub executed_at: chrono::DateTime<chrono::Utc>,
+    /// Wall-clock execution time in milliseconds
+    pub duration_ms: u64,
+    /// Error message if the statement failed
+    pub error: Option<String>,
+}
+
+impl HistoryEntry {
+    pub fn succeeded(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Bounded statement history for one session
+#[derive(Debug, Clone)]
+pub struct SessionHistory {
+    entries: VecDeque<HistoryEntry>,
+    capacity: usize,
+    next_sequence: u64,
+}
+
+impl SessionHistory {
+    /// Create a history keeping at most `capacity` statements (0 disables recording)
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: VecDeque::new(),
+            capacity,
+            next_sequence: 1,
+        }
+    }
+
+    /// Change the capacity, discarding the oldest entries if needed
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        self.truncate();
+    }
+
+    /// Record a statement; sequence numbers keep increasing even when disabled
+    pub fn record(
+        &mut self,
+        statement: &str,
+        executed_at: chrono::DateTime<chrono::Utc>,
+        duration_ms: u64,
+        error: Option<String>,
+    ) {
+        let sequence = self.next_sequence;
+        self.next_sequence += 1;
+        self.entries.push_back(HistoryEntry {
+            sequence,
+            statement: statement.to_string(),
+            executed_at,
+            duration_ms,
+            error,
+        });
+        self.truncate();
+    }
+
+    /// Entries from oldest to newest
+    pub fn entries(&self) -> impl Iterator<Item = &HistoryEntry> {
+        self.entries.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn truncate(&mut self) {
+        while self.entries.len() > self.capacity {
+            self.entries.pop_front();
+        }
+    }
+}
+
+impl Default for SessionHistory {
+    fn default() -> Self {
+        Self::new(DEFAULT_SESSION_HISTORY_SIZE)
+    }
+}
@@ -14,6 +14,7 @@
 //! - Current graph context per session
 //! - Home graph assignment per user
 //! - Session parameter management
+//! - Per-session statement history
 //! - Multi-tenancy and user isolation
 //!
 //! # Session Providers
@@ -24,6 +25,7 @@
 //! - `GlobalSessionProvider`: All QueryCoordinators share a global session pool (server mode)
 
 pub mod global_provider;
+pub mod history;
 pub mod instance_provider;
 pub mod manager;
 pub mod mode;
@@ -32,6 +34,7 @@ pub mod provider;
 pub mod transaction_state;
 
 pub use global_provider::GlobalSessionProvider;
+pub use history::HistoryEntry;
 pub use instance_provider::InstanceSessionProvider;
 pub use manager::SessionManager;
 pub use mode::SessionMode;
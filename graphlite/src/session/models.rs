@@ -6,7 +6,10 @@
 //! This module provides a consolidated session management model that combines
 //! authentication, authorization, and database session state management.
 
+use crate::exec::OperatorTiming;
+use crate::session::history::SessionHistory;
 use crate::session::transaction_state::SessionTransactionState;
+use crate::storage::multi_graph::UnionConflictPolicy;
 use crate::storage::{GraphCache, StorageManager, Value};
 use crate::txn::TransactionManager;
 use std::collections::HashMap;
@@ -204,6 +207,8 @@ pub struct UserSession {
     pub transaction_state: Arc<SessionTransactionState>,
     /// Catalog metadata cache (reduces contention on CatalogManager)
     pub catalog_cache: SessionCatalogCache,
+    /// Most recent statements executed in this session
+    pub history: SessionHistory,
 
     // === Session Lifecycle ===
     /// When the session was created
@@ -238,6 +243,7 @@ impl UserSession {
             permissions,
             transaction_state,
             catalog_cache: SessionCatalogCache::new(),
+            history: SessionHistory::default(),
             created_at: now,
             last_activity: now,
             active: true,
@@ -368,6 +374,45 @@ impl UserSession {
             .unwrap_or_else(|| self.get_schema_enforcement_mode() != "strict")
     }
 
+    // === Operator Timing ===
+
+    /// Set whether queries return per-operator timing (`QueryResult::operator_timings`)
+    pub fn set_operator_timing(&mut self, enabled: bool) {
+        self.set_parameter(
+            OperatorTiming::SESSION_PARAMETER.to_string(),
+            Value::Boolean(enabled),
+        );
+    }
+
+    /// Get whether queries return per-operator timing (default: false)
+    pub fn get_operator_timing(&self) -> bool {
+        matches!(
+            self.get_parameter(OperatorTiming::SESSION_PARAMETER),
+            Some(Value::Boolean(true))
+        )
+    }
+
+    // === Graph Union Configuration ===
+
+    /// Set how graph UNION resolves elements that appear in several operands
+    pub fn set_union_conflict_policy(&mut self, policy: UnionConflictPolicy) {
+        self.set_parameter(
+            UnionConflictPolicy::SESSION_PARAMETER.to_string(),
+            Value::String(policy.as_str().to_string()),
+        );
+    }
+
+    /// Get the graph UNION conflict policy (default: keep_first)
+    /// Unrecognised values fall back to the default.
+    pub fn get_union_conflict_policy(&self) -> UnionConflictPolicy {
+        self.get_parameter(UnionConflictPolicy::SESSION_PARAMETER)
+            .and_then(|v| match v {
+                Value::String(s) => s.parse().ok(),
+                _ => None,
+            })
+            .unwrap_or_default()
+    }
+
     // === Session Lifecycle Management ===
 
     /// Update the last activity timestamp
@@ -7,7 +7,9 @@
 //! ensuring proper isolation and consistency for multi-statement transactions.
 
 use crate::exec::ExecutionError;
+use crate::storage::outbox::PendingEvent;
 use crate::txn::isolation::IsolationLevel;
+use crate::txn::state::AccessMode;
 use crate::txn::{TransactionId, TransactionLog, TransactionManager, UndoOperation};
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
@@ -29,6 +31,13 @@ pub struct SessionTransactionState {
 
     /// Transaction isolation level
     isolation_level: Arc<RwLock<IsolationLevel>>,
+
+    /// Savepoints of the current transaction, oldest first, with the lengths of
+    /// the transaction log and of the pending outbox events when each was set
+    savepoints: Arc<RwLock<Vec<(String, usize, usize)>>>,
+
+    /// Outbox events appended in the current transaction, written on commit
+    pending_events: Arc<RwLock<Vec<PendingEvent>>>,
 }
 
 impl SessionTransactionState {
@@ -40,6 +49,8 @@ impl SessionTransactionState {
             transaction_logs: Arc::new(RwLock::new(HashMap::new())),
             auto_commit: Arc::new(RwLock::new(true)),
             isolation_level: Arc::new(RwLock::new(IsolationLevel::ReadCommitted)),
+            savepoints: Arc::new(RwLock::new(Vec::new())),
+            pending_events: Arc::new(RwLock::new(Vec::new())),
         }
     }
 
@@ -96,8 +107,35 @@ impl SessionTransactionState {
         Ok(())
     }
 
+    /// Whether the current transaction was started READ ONLY
+    pub fn is_read_only(&self) -> Result<bool, ExecutionError> {
+        let Some(txn_id) = self.current_transaction_id()? else {
+            return Ok(false);
+        };
+        Ok(match self.manager.get_transaction(txn_id)? {
+            Some(transaction) => transaction
+                .lock()
+                .map(|transaction| transaction.is_read_only())
+                .map_err(|_| {
+                    ExecutionError::RuntimeError("Failed to read transaction state".to_string())
+                })?,
+            None => false,
+        })
+    }
+
     /// Begin a new transaction
     pub fn begin_transaction(&self) -> Result<TransactionId, ExecutionError> {
+        self.begin_transaction_with(None, None)
+    }
+
+    /// Begin a new transaction with the given characteristics
+    ///
+    /// Without an isolation level the session's isolation level is used.
+    pub fn begin_transaction_with(
+        &self,
+        isolation_level: Option<IsolationLevel>,
+        access_mode: Option<AccessMode>,
+    ) -> Result<TransactionId, ExecutionError> {
         // Check if there's already an active transaction
         if self.has_active_transaction()? {
             return Err(ExecutionError::RuntimeError(
@@ -106,8 +144,13 @@ impl SessionTransactionState {
         }
 
         // Start a new transaction
-        let isolation = self.isolation_level()?;
-        let txn_id = self.manager.start_transaction(Some(isolation), None)?;
+        let isolation = match isolation_level {
+            Some(level) => level,
+            None => self.isolation_level()?,
+        };
+        let txn_id = self
+            .manager
+            .start_transaction(Some(isolation), access_mode)?;
 
         // Set as current transaction
         let mut current = self.current_transaction.write().map_err(|_| {
@@ -119,7 +162,10 @@ impl SessionTransactionState {
         let mut logs = self.transaction_logs.write().map_err(|_| {
             ExecutionError::RuntimeError("Failed to update transaction logs".to_string())
         })?;
-        logs.insert(txn_id, TransactionLog::new(txn_id));
+        logs.insert(
+            txn_id,
+            TransactionLog::with_spill(txn_id, self.manager.undo_spill()),
+        );
 
         log::info!("Session began transaction: {:?}", txn_id);
         Ok(txn_id)
@@ -127,10 +173,39 @@ impl SessionTransactionState {
 
     /// Commit the current transaction
     pub fn commit_transaction(&self) -> Result<(), ExecutionError> {
+        self.commit_transaction_with_storage(None)
+    }
+
+    /// Commit the current transaction, writing its outbox events to `storage`
+    ///
+    /// If the events cannot be written the transaction stays open, so it can
+    /// still be rolled back.
+    pub fn commit_transaction_with_storage(
+        &self,
+        storage: Option<&Arc<crate::storage::StorageManager>>,
+    ) -> Result<(), ExecutionError> {
         let txn_id = self.current_transaction_id()?.ok_or_else(|| {
             ExecutionError::RuntimeError("No active transaction to commit".to_string())
         })?;
 
+        let mut pending_events = self.pending_events.write().map_err(|_| {
+            ExecutionError::RuntimeError("Failed to update outbox events".to_string())
+        })?;
+        if !pending_events.is_empty() {
+            let storage = storage.ok_or_else(|| {
+                ExecutionError::RuntimeError(
+                    "No storage manager available to write outbox events".to_string(),
+                )
+            })?;
+            storage
+                .append_outbox_events(pending_events.clone(), Some(txn_id.id()))
+                .map_err(|e| {
+                    ExecutionError::StorageError(format!("Failed to write outbox events: {}", e))
+                })?;
+            pending_events.clear();
+        }
+        drop(pending_events);
+
         // Commit the transaction
         self.manager.commit_transaction(txn_id)?;
 
@@ -145,6 +220,7 @@ impl SessionTransactionState {
             ExecutionError::RuntimeError("Failed to update transaction logs".to_string())
         })?;
         logs.remove(&txn_id);
+        self.clear_savepoints()?;
 
         log::info!("Session committed transaction: {:?}", txn_id);
         Ok(())
@@ -167,42 +243,33 @@ impl SessionTransactionState {
             ExecutionError::RuntimeError("No active transaction to rollback".to_string())
         })?;
 
-        // Get the transaction log for rollback operations
-        let logs = self.transaction_logs.read().map_err(|_| {
-            ExecutionError::RuntimeError("Failed to read transaction logs".to_string())
-        })?;
-
-        let log = logs
-            .get(&txn_id)
+        // Take the transaction log for rollback operations
+        let mut log = self
+            .transaction_logs
+            .write()
+            .map_err(|_| {
+                ExecutionError::RuntimeError("Failed to update transaction logs".to_string())
+            })?
+            .remove(&txn_id)
             .ok_or_else(|| ExecutionError::RuntimeError("Transaction log not found".to_string()))?;
 
-        // Collect undo operations before dropping the read lock
-        let undo_operations: Vec<crate::txn::UndoOperation> = log.undo_operations.clone();
-        drop(logs);
-
         // Apply undo operations in reverse order if storage is provided
         if let Some(storage_manager) = storage {
             log::info!(
                 "Applying {} undo operations for transaction {:?}",
-                undo_operations.len(),
+                log.len(),
                 txn_id
             );
-            for operation in undo_operations.iter().rev() {
-                if let Err(e) = self.apply_undo_operation(operation, storage_manager) {
-                    log::error!(
-                        "Failed to apply undo operation: {:?}. Error: {}",
-                        operation,
-                        e
-                    );
-                    // Continue with other undo operations even if one fails
-                }
-            }
+            // Spilled operations are read back one at a time
+            self.undo_operations_after(&mut log, 0, storage_manager);
         } else {
             log::warn!(
                 "No storage manager provided for rollback - undo operations will not be applied!"
             );
         }
 
+        self.clear_pending_events()?;
+
         // Execute rollback in transaction manager (for WAL logging)
         self.manager.rollback_transaction(txn_id)?;
 
@@ -212,16 +279,197 @@ impl SessionTransactionState {
         })?;
         *current = None;
 
-        // Clear transaction log
+        self.clear_savepoints()?;
+
+        log::info!("Session rolled back transaction: {:?}", txn_id);
+        Ok(())
+    }
+
+    /// Set a savepoint in the current transaction
+    ///
+    /// A savepoint with the same name is replaced.
+    pub fn create_savepoint(&self, name: &str) -> Result<(), ExecutionError> {
+        let txn_id = self.current_transaction_id()?.ok_or_else(|| {
+            ExecutionError::RuntimeError("No active transaction for SAVEPOINT".to_string())
+        })?;
+
+        let mark = self
+            .transaction_logs
+            .read()
+            .map_err(|_| {
+                ExecutionError::RuntimeError("Failed to read transaction logs".to_string())
+            })?
+            .get(&txn_id)
+            .map_or(0, |log| log.len());
+        let events = self.pending_event_count()?;
+
+        let mut savepoints = self
+            .savepoints
+            .write()
+            .map_err(|_| ExecutionError::RuntimeError("Failed to update savepoints".to_string()))?;
+        savepoints.retain(|(existing, _, _)| existing != name);
+        savepoints.push((name.to_string(), mark, events));
+        Ok(())
+    }
+
+    /// Undo the changes made since savepoint `name` was set
+    ///
+    /// The savepoint stays, so the transaction can roll back to it again;
+    /// savepoints set after it are removed.
+    pub fn rollback_to_savepoint(
+        &self,
+        name: &str,
+        storage: &Arc<crate::storage::StorageManager>,
+    ) -> Result<(), ExecutionError> {
+        let txn_id = self.current_transaction_id()?.ok_or_else(|| {
+            ExecutionError::RuntimeError(
+                "No active transaction for ROLLBACK TO SAVEPOINT".to_string(),
+            )
+        })?;
+
+        let mut savepoints = self
+            .savepoints
+            .write()
+            .map_err(|_| ExecutionError::RuntimeError("Failed to update savepoints".to_string()))?;
+        let position = Self::savepoint_position(&savepoints, name)?;
+        let (mark, events) = (savepoints[position].1, savepoints[position].2);
+
         let mut logs = self.transaction_logs.write().map_err(|_| {
             ExecutionError::RuntimeError("Failed to update transaction logs".to_string())
         })?;
-        logs.remove(&txn_id);
+        if let Some(log) = logs.get_mut(&txn_id) {
+            log::info!(
+                "Rolling back {} undo operations to savepoint {} of transaction {:?}",
+                log.len().saturating_sub(mark),
+                name,
+                txn_id
+            );
+            self.undo_operations_after(log, mark, storage);
+        }
+        self.pending_events
+            .write()
+            .map_err(|_| {
+                ExecutionError::RuntimeError("Failed to update outbox events".to_string())
+            })?
+            .truncate(events);
 
-        log::info!("Session rolled back transaction: {:?}", txn_id);
+        savepoints.truncate(position + 1);
+        Ok(())
+    }
+
+    /// Remove savepoint `name` and the savepoints set after it, keeping their changes
+    pub fn release_savepoint(&self, name: &str) -> Result<(), ExecutionError> {
+        if !self.has_active_transaction()? {
+            return Err(ExecutionError::RuntimeError(
+                "No active transaction for RELEASE SAVEPOINT".to_string(),
+            ));
+        }
+
+        let mut savepoints = self
+            .savepoints
+            .write()
+            .map_err(|_| ExecutionError::RuntimeError("Failed to update savepoints".to_string()))?;
+        let position = Self::savepoint_position(&savepoints, name)?;
+        savepoints.truncate(position);
+        Ok(())
+    }
+
+    /// Names of the savepoints of the current transaction, oldest first
+    pub fn savepoint_names(&self) -> Result<Vec<String>, ExecutionError> {
+        self.savepoints
+            .read()
+            .map(|savepoints| savepoints.iter().map(|(name, _, _)| name.clone()).collect())
+            .map_err(|_| ExecutionError::RuntimeError("Failed to read savepoints".to_string()))
+    }
+
+    fn savepoint_position(
+        savepoints: &[(String, usize, usize)],
+        name: &str,
+    ) -> Result<usize, ExecutionError> {
+        savepoints
+            .iter()
+            .position(|(existing, _, _)| existing == name)
+            .ok_or_else(|| {
+                ExecutionError::RuntimeError(format!(
+                    "Savepoint {} is not set in the current transaction",
+                    name
+                ))
+            })
+    }
+
+    /// Hold an outbox event until the current transaction commits
+    pub fn append_event(&self, event: PendingEvent) -> Result<(), ExecutionError> {
+        if !self.has_active_transaction()? {
+            return Err(ExecutionError::RuntimeError(
+                "No active transaction for the outbox event".to_string(),
+            ));
+        }
+        if self.is_read_only()? {
+            return Err(ExecutionError::RuntimeError(
+                "Cannot append outbox events in a READ ONLY transaction".to_string(),
+            ));
+        }
+        self.pending_events
+            .write()
+            .map_err(|_| {
+                ExecutionError::RuntimeError("Failed to update outbox events".to_string())
+            })?
+            .push(event);
         Ok(())
     }
 
+    /// Number of outbox events the current transaction will write on commit
+    pub fn pending_event_count(&self) -> Result<usize, ExecutionError> {
+        self.pending_events
+            .read()
+            .map(|events| events.len())
+            .map_err(|_| ExecutionError::RuntimeError("Failed to read outbox events".to_string()))
+    }
+
+    fn clear_pending_events(&self) -> Result<(), ExecutionError> {
+        self.pending_events
+            .write()
+            .map(|mut events| events.clear())
+            .map_err(|_| ExecutionError::RuntimeError("Failed to update outbox events".to_string()))
+    }
+
+    fn clear_savepoints(&self) -> Result<(), ExecutionError> {
+        self.savepoints
+            .write()
+            .map(|mut savepoints| savepoints.clear())
+            .map_err(|_| ExecutionError::RuntimeError("Failed to update savepoints".to_string()))
+    }
+
+    /// Undo and remove the operations of `log` after the first `mark`, most recent first
+    ///
+    /// Continues past operations that fail to apply; stops if a spilled
+    /// operation cannot be read back.
+    fn undo_operations_after(
+        &self,
+        log: &mut TransactionLog,
+        mark: usize,
+        storage: &Arc<crate::storage::StorageManager>,
+    ) {
+        while log.len() > mark {
+            let operation = match log.pop_operation() {
+                Ok(Some(operation)) => operation,
+                Ok(None) => break,
+                Err(e) => {
+                    log::error!("Failed to read undo operation: {}", e);
+                    break;
+                }
+            };
+            if let Err(e) = self.apply_undo_operation(&operation, storage) {
+                log::error!(
+                    "Failed to apply undo operation: {:?}. Error: {}",
+                    operation,
+                    e
+                );
+                // Continue with other undo operations even if one fails
+            }
+        }
+    }
+
     /// Apply a single undo operation to rollback changes
     fn apply_undo_operation(
         &self,
@@ -230,6 +478,15 @@ impl SessionTransactionState {
     ) -> Result<(), ExecutionError> {
         use crate::txn::UndoOperation;
 
+        // Undoing loads, changes and saves the graph like a data statement, so it
+        // holds the same write lock to keep concurrent writers from losing changes
+        let write_lock = operation
+            .graph_path()
+            .map(|graph_path| storage.graph_write_lock(graph_path));
+        let _writing = write_lock
+            .as_ref()
+            .map(|lock| lock.lock().unwrap_or_else(|e| e.into_inner()));
+
         match operation {
             UndoOperation::Batch { operations } => {
                 // Handle batch operations - undo all operations in the batch atomically
@@ -562,15 +819,15 @@ impl SessionTransactionState {
             // Get or create log for this transaction
             let log = logs
                 .entry(txn_id)
-                .or_insert_with(|| TransactionLog::new(txn_id));
+                .or_insert_with(|| TransactionLog::with_spill(txn_id, self.manager.undo_spill()));
 
-            // Add the operation to the log
-            log.undo_operations.push(operation);
+            // Add the operation to the log; large logs spill to disk
+            log.log_operation(operation);
 
             log::info!(
                 "TRANSACTION: Transaction {} now has {} undo operations",
                 txn_id,
-                log.undo_operations.len()
+                log.len()
             );
         } else {
             log::debug!("TRANSACTION: No active transaction, undo operation not logged");
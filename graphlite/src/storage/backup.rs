@@ -0,0 +1,200 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! Online backup and restore
+//!
+//! A backup is a complete Sled database directory holding a copy of every tree of
+//! the source storage driver, so it can be opened like any other database. It is
+//! written through the driver rather than by copying files, which keeps it valid
+//! while the source database is open. Restoring copies a backup into a new
+//! database directory the same way.
+//!
+//! A snapshot is a backup marked as such, which makes the engine open it
+//! read-only. Restoring a snapshot drops the mark, giving a writable database.
+
+use super::persistent::{create_storage_driver, StorageDriver, StorageTree, StorageType};
+use super::StorageError;
+use log::info;
+use std::path::Path;
+
+/// Entries written per batch when copying a tree
+const COPY_BATCH_SIZE: usize = 1024;
+
+/// Tree marking a snapshot; holds the time it was taken
+const SNAPSHOT_TREE: &str = "__snapshot";
+const SNAPSHOT_TAKEN_AT: &[u8] = b"taken_at";
+
+type Driver = dyn StorageDriver<Tree = Box<dyn StorageTree>>;
+
+/// Copy every tree of `source` into a new Sled database at `dest`
+///
+/// `dest` must not exist or be an empty directory. Returns the number of entries copied.
+pub(crate) fn copy_to_new_database(source: &Driver, dest: &Path) -> Result<usize, StorageError> {
+    let (target, copied) = copy_trees(source, dest)?;
+    target.flush().map_err(persistence_error)?;
+
+    info!("Copied {} entries into {}", copied, dest.display());
+    Ok(copied)
+}
+
+/// Copy every tree of `source` into a new Sled database at `dest`, marked as a snapshot
+///
+/// `dest` must not exist or be an empty directory. Returns the number of entries copied.
+pub(crate) fn write_snapshot(source: &Driver, dest: &Path) -> Result<usize, StorageError> {
+    let (target, copied) = copy_trees(source, dest)?;
+    let taken_at = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default();
+    target
+        .open_tree(SNAPSHOT_TREE)
+        .and_then(|tree| tree.insert(SNAPSHOT_TAKEN_AT, &taken_at.to_be_bytes()))
+        .map_err(persistence_error)?;
+    target.flush().map_err(persistence_error)?;
+
+    info!("Wrote snapshot of {} entries to {}", copied, dest.display());
+    Ok(copied)
+}
+
+/// Check whether `driver` holds a snapshot written by `write_snapshot`
+pub(crate) fn is_snapshot(driver: &Driver) -> bool {
+    driver
+        .list_trees()
+        .is_ok_and(|trees| trees.iter().any(|name| name == SNAPSHOT_TREE))
+}
+
+/// Copy every tree of `source` except the snapshot mark into a new Sled database
+fn copy_trees(source: &Driver, dest: &Path) -> Result<(Box<Driver>, usize), StorageError> {
+    ensure_empty_destination(dest)?;
+    source.flush().map_err(persistence_error)?;
+
+    let target = create_storage_driver(StorageType::Sled, dest).map_err(persistence_error)?;
+    let mut copied = 0usize;
+    for name in source.list_trees().map_err(persistence_error)? {
+        if name == SNAPSHOT_TREE {
+            continue;
+        }
+        let from = source.open_tree(&name).map_err(persistence_error)?;
+        let to = target.open_tree(&name).map_err(persistence_error)?;
+
+        let mut batch: Vec<(Vec<u8>, Vec<u8>)> = Vec::with_capacity(COPY_BATCH_SIZE);
+        for entry in from.iter().map_err(persistence_error)? {
+            batch.push(entry.map_err(persistence_error)?);
+            if batch.len() == COPY_BATCH_SIZE {
+                copied += write_batch(to.as_ref(), &mut batch)?;
+            }
+        }
+        copied += write_batch(to.as_ref(), &mut batch)?;
+    }
+    Ok((target, copied))
+}
+
+/// Restore the backup at `backup` into a new database directory at `dest`
+///
+/// The backup must not be open by another process. Returns the number of entries copied.
+pub fn restore(backup: &Path, dest: &Path) -> Result<usize, StorageError> {
+    if !backup.is_dir() || is_empty_dir(backup)? {
+        return Err(StorageError::InvalidOperation(format!(
+            "No backup found at {}",
+            backup.display()
+        )));
+    }
+    let source = create_storage_driver(StorageType::Sled, backup).map_err(persistence_error)?;
+    copy_to_new_database(source.as_ref(), dest)
+}
+
+fn write_batch(
+    tree: &dyn StorageTree,
+    batch: &mut Vec<(Vec<u8>, Vec<u8>)>,
+) -> Result<usize, StorageError> {
+    let entries: Vec<(&[u8], &[u8])> = batch
+        .iter()
+        .map(|(key, value)| (key.as_slice(), value.as_slice()))
+        .collect();
+    tree.batch_insert(&entries).map_err(persistence_error)?;
+    let written = batch.len();
+    batch.clear();
+    Ok(written)
+}
+
+/// Refuse to write into anything but a missing path or an empty directory
+fn ensure_empty_destination(dest: &Path) -> Result<(), StorageError> {
+    if dest.exists() && (!dest.is_dir() || !is_empty_dir(dest)?) {
+        return Err(StorageError::InvalidOperation(format!(
+            "Destination {} already exists and is not empty",
+            dest.display()
+        )));
+    }
+    Ok(())
+}
+
+fn is_empty_dir(path: &Path) -> Result<bool, StorageError> {
+    let mut entries = std::fs::read_dir(path).map_err(|e| {
+        StorageError::PersistenceError(format!("Failed to read {}: {}", path.display(), e))
+    })?;
+    Ok(entries.next().is_none())
+}
+
+fn persistence_error(error: impl std::fmt::Display) -> StorageError {
+    StorageError::PersistenceError(error.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_copy_and_restore_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = create_storage_driver(StorageType::Memory, temp_dir.path()).unwrap();
+        let tree = source.open_tree("nodes").unwrap();
+        for i in 0..(COPY_BATCH_SIZE + 10) {
+            tree.insert(format!("key{}", i).as_bytes(), b"value")
+                .unwrap();
+        }
+        source.open_tree("empty").unwrap();
+
+        let backup = temp_dir.path().join("backup");
+        assert_eq!(
+            copy_to_new_database(source.as_ref(), &backup).unwrap(),
+            COPY_BATCH_SIZE + 10
+        );
+        // The destination now exists and is not empty
+        assert!(copy_to_new_database(source.as_ref(), &backup).is_err());
+
+        let restored = temp_dir.path().join("restored");
+        assert_eq!(restore(&backup, &restored).unwrap(), COPY_BATCH_SIZE + 10);
+        let driver = create_storage_driver(StorageType::Sled, &restored).unwrap();
+        assert_eq!(
+            driver.open_tree("nodes").unwrap().get(b"key7").unwrap(),
+            Some(b"value".to_vec())
+        );
+        assert!(driver.list_trees().unwrap().contains(&"empty".to_string()));
+
+        assert!(restore(&temp_dir.path().join("missing"), &restored).is_err());
+    }
+
+    #[test]
+    fn test_snapshot_is_marked_until_restored() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = create_storage_driver(StorageType::Memory, temp_dir.path()).unwrap();
+        source
+            .open_tree("nodes")
+            .unwrap()
+            .insert(b"key", b"value")
+            .unwrap();
+        assert!(!is_snapshot(source.as_ref()));
+
+        let snapshot = temp_dir.path().join("snapshot");
+        assert_eq!(write_snapshot(source.as_ref(), &snapshot).unwrap(), 1);
+        let driver = create_storage_driver(StorageType::Sled, &snapshot).unwrap();
+        assert!(is_snapshot(driver.as_ref()));
+        drop(driver);
+
+        let restored = temp_dir.path().join("restored");
+        assert_eq!(restore(&snapshot, &restored).unwrap(), 1);
+        let driver = create_storage_driver(StorageType::Sled, &restored).unwrap();
+        assert!(!is_snapshot(driver.as_ref()));
+    }
+}
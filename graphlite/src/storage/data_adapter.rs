@@ -10,12 +10,17 @@
 use crate::catalog::manager::CatalogManager;
 use crate::catalog::providers::graph_metadata::{Graph, GraphType};
 use crate::catalog::providers::schema::Schema;
+use crate::storage::integrity::{
+    self, CorruptRecord, DanglingEdge, ReferenceCheckReport, StorageCheckReport,
+};
 use crate::storage::{
+    json_path::JsonPath,
     types::{Edge, Node},
     value::Value,
     GraphCache,
 };
 use crate::storage::{StorageDriver, StorageTree};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -27,6 +32,19 @@ thread_local! {
         .expect("Failed to create runtime for data adapter operations");
 }
 
+/// Version of the per-graph storage layout written by `save_graph_by_path`
+///
+/// * 1 - nodes, edges and metadata trees
+/// * 2 - adds the reverse adjacency tree (`adjacency_in_<graph>`)
+/// * 3 - appends a checksum to every node and edge record (see `integrity`)
+const LAYOUT_VERSION: u32 = 3;
+
+/// First layout version whose node and edge records carry a checksum
+const CHECKSUM_LAYOUT_VERSION: u32 = 3;
+
+/// Metadata key holding the layout version of a saved graph (absent = 1)
+const LAYOUT_VERSION_KEY: &[u8] = b"layout_version";
+
 /// Serializable node structure for storage
 #[derive(Serialize, Deserialize, Debug)]
 struct SerializableNode {
@@ -45,6 +63,37 @@ struct SerializableEdge {
     properties: HashMap<String, Value>,
 }
 
+/// Serializable relationship property index definition for storage
+///
+/// Only the definition is stored; entries are rebuilt from the edges on load.
+#[derive(Serialize, Deserialize, Debug)]
+struct SerializableEdgePropertyIndex {
+    name: String,
+    label: String,
+    property: String,
+}
+
+/// Serializable definition of a relationship index over a JSONPath into a property
+///
+/// Kept apart from plain property indexes so that the existing record layout is
+/// unchanged. Only the definition is stored; entries are rebuilt on load.
+#[derive(Serialize, Deserialize, Debug)]
+struct SerializableJsonPathIndex {
+    name: String,
+    label: String,
+    property: String,
+    path: String,
+}
+
+/// Serializable node key index definition for storage
+///
+/// Only the definition is stored; entries are rebuilt from the nodes on load.
+#[derive(Serialize, Deserialize, Debug)]
+struct SerializableNodeKeyIndex {
+    label: String,
+    property: String,
+}
+
 /// Serializable catalog structure for storage
 #[allow(dead_code)]
 #[derive(Serialize, Deserialize, Debug)]
@@ -71,7 +120,7 @@ impl DataAdapter {
     }
 
     /// Normalize graph path to be safe for use as storage key
-    fn normalize_graph_path(graph_path: &str) -> String {
+    pub(crate) fn normalize_graph_path(graph_path: &str) -> String {
         graph_path
             .replace("/", "_")
             .replace("\\", "_")
@@ -116,12 +165,19 @@ impl DataAdapter {
             }
         };
 
+        let metadata_tree = driver.open_tree(&format!("metadata_{}", graph_prefix))?;
+        let layout_version = Self::layout_version(metadata_tree.as_ref())?;
+        let checksummed = layout_version >= CHECKSUM_LAYOUT_VERSION;
+        let nodes_tree_name = format!("nodes_{}", graph_prefix);
+        let edges_tree_name = format!("edges_{}", graph_prefix);
+
         let mut graph = GraphCache::new();
 
         // Load all nodes from graph-specific tree
         for result in nodes_tree.iter()? {
-            let (_, data) = result?;
-            let serializable_node: SerializableNode = bincode::deserialize(&data)?;
+            let (key, data) = result?;
+            let serializable_node: SerializableNode =
+                Self::decode_record(&nodes_tree_name, &key, &data, checksummed)?;
             let node = Node {
                 id: serializable_node.id,
                 labels: serializable_node.labels,
@@ -132,8 +188,9 @@ impl DataAdapter {
 
         // Load all edges from graph-specific tree
         for result in edges_tree.iter()? {
-            let (_, data) = result?;
-            let serializable_edge: SerializableEdge = bincode::deserialize(&data)?;
+            let (key, data) = result?;
+            let serializable_edge: SerializableEdge =
+                Self::decode_record(&edges_tree_name, &key, &data, checksummed)?;
             let edge = Edge {
                 id: serializable_edge.id,
                 label: serializable_edge.label,
@@ -144,9 +201,118 @@ impl DataAdapter {
             graph.add_edge(edge)?;
         }
 
+        // Rebuild relationship property indexes
+        if let Some(data) = metadata_tree.get(b"edge_property_indexes")? {
+            let definitions: Vec<SerializableEdgePropertyIndex> = bincode::deserialize(&data)?;
+            for definition in definitions {
+                graph.create_edge_property_index(
+                    &definition.name,
+                    &definition.label,
+                    &definition.property,
+                    None,
+                )?;
+            }
+        }
+        if let Some(data) = metadata_tree.get(b"edge_json_path_indexes")? {
+            let definitions: Vec<SerializableJsonPathIndex> = bincode::deserialize(&data)?;
+            for definition in definitions {
+                let path = JsonPath::parse(&definition.path)?;
+                graph.create_edge_property_index(
+                    &definition.name,
+                    &definition.label,
+                    &definition.property,
+                    Some(&path),
+                )?;
+            }
+        }
+
+        // Rebuild node key indexes
+        if let Some(data) = metadata_tree.get(b"node_key_indexes")? {
+            let definitions: Vec<SerializableNodeKeyIndex> = bincode::deserialize(&data)?;
+            for definition in definitions {
+                graph.create_node_key_index(&definition.label, &definition.property);
+            }
+        }
+
+        // Graphs saved by an older layout are rewritten once in the current one
+        let adjacency_in_tree = driver.open_tree(&format!("adjacency_in_{}", graph_prefix))?;
+        if layout_version < LAYOUT_VERSION {
+            self.save_graph_by_path(driver, &graph, graph_path)?;
+            log::info!(
+                "Rewrote graph '{}' in storage layout v{} (was v{})",
+                graph_path,
+                LAYOUT_VERSION,
+                layout_version
+            );
+        } else if adjacency_in_tree.is_empty()? && graph.stats().edge_count > 0 {
+            Self::write_reverse_adjacency(adjacency_in_tree.as_ref(), &graph)?;
+            driver.flush()?;
+            log::info!("Rebuilt reverse adjacency tree for graph '{}'", graph_path);
+        }
+
         Ok(graph)
     }
 
+    /// Layout version of a saved graph, read from its metadata tree
+    fn layout_version(metadata_tree: &dyn StorageTree) -> Result<u32, Box<dyn std::error::Error>> {
+        Ok(match metadata_tree.get(LAYOUT_VERSION_KEY)? {
+            Some(data) => bincode::deserialize::<u32>(&data)?,
+            None => 1,
+        })
+    }
+
+    /// Decode a node or edge record, verifying its checksum if it has one
+    fn decode_record<T: DeserializeOwned>(
+        tree: &str,
+        key: &[u8],
+        data: &[u8],
+        checksummed: bool,
+    ) -> Result<T, CorruptRecord> {
+        let payload = if checksummed {
+            integrity::open_record(tree, key, data)?
+        } else {
+            data
+        };
+        bincode::deserialize(payload).map_err(|e| {
+            CorruptRecord::detected(tree, key, format!("record cannot be decoded: {}", e))
+        })
+    }
+
+    /// Key of an edge in the reverse adjacency tree: `to_node \0 label \0 edge_id`
+    ///
+    /// Keys sort by target node and then relationship type, so the incoming edges of
+    /// a node (of one type) are a single prefix scan.
+    fn reverse_adjacency_key(edge: &Edge) -> Vec<u8> {
+        let mut key = Vec::with_capacity(edge.to_node.len() + edge.label.len() + edge.id.len() + 2);
+        key.extend_from_slice(edge.to_node.as_bytes());
+        key.push(0);
+        key.extend_from_slice(edge.label.as_bytes());
+        key.push(0);
+        key.extend_from_slice(edge.id.as_bytes());
+        key
+    }
+
+    /// Replace the contents of the reverse adjacency tree with the edges of `graph`
+    ///
+    /// Each entry maps to the source node of the edge.
+    fn write_reverse_adjacency(
+        tree: &dyn StorageTree,
+        graph: &GraphCache,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        tree.clear()?;
+        let entries: Vec<(Vec<u8>, &[u8])> = graph
+            .edge_ids()
+            .filter_map(|id| graph.get_edge(id))
+            .map(|edge| (Self::reverse_adjacency_key(edge), edge.from_node.as_bytes()))
+            .collect();
+        let entries: Vec<(&[u8], &[u8])> = entries
+            .iter()
+            .map(|(key, value)| (key.as_slice(), *value))
+            .collect();
+        tree.batch_insert(&entries)?;
+        Ok(())
+    }
+
     /// Save a GraphCache for a specific graph path using provided driver connection
     pub fn save_graph_by_path(
         &self,
@@ -160,6 +326,7 @@ impl DataAdapter {
         let nodes_tree = driver.open_tree(&format!("nodes_{}", graph_prefix))?;
         let edges_tree = driver.open_tree(&format!("edges_{}", graph_prefix))?;
         let metadata_tree = driver.open_tree(&format!("metadata_{}", graph_prefix))?;
+        let adjacency_in_tree = driver.open_tree(&format!("adjacency_in_{}", graph_prefix))?;
 
         // Clear existing data
         nodes_tree.clear()?;
@@ -172,7 +339,8 @@ impl DataAdapter {
                 labels: node.labels.clone(),
                 properties: node.properties.clone(),
             };
-            let data = bincode::serialize(&serializable_node)?;
+            let data =
+                integrity::seal_record(node.id.as_bytes(), bincode::serialize(&serializable_node)?);
             nodes_tree.insert(node.id.as_bytes(), &data)?;
         }
 
@@ -186,7 +354,10 @@ impl DataAdapter {
                     to_node: edge.to_node.clone(),
                     properties: edge.properties.clone(),
                 };
-                let data = bincode::serialize(&serializable_edge)?;
+                let data = integrity::seal_record(
+                    edge.id.as_bytes(),
+                    bincode::serialize(&serializable_edge)?,
+                );
                 edges_tree.insert(edge.id.as_bytes(), &data)?;
             }
         }
@@ -203,12 +374,238 @@ impl DataAdapter {
         let metadata_data = bincode::serialize(&metadata)?;
         metadata_tree.insert(b"stats", &metadata_data)?;
 
+        let index_definitions: Vec<SerializableEdgePropertyIndex> = graph
+            .edge_property_indexes()
+            .filter(|index| index.path.is_none())
+            .map(|index| SerializableEdgePropertyIndex {
+                name: index.name.clone(),
+                label: index.label.clone(),
+                property: index.property.clone(),
+            })
+            .collect();
+        let index_data = bincode::serialize(&index_definitions)?;
+        metadata_tree.insert(b"edge_property_indexes", &index_data)?;
+
+        let path_definitions: Vec<SerializableJsonPathIndex> = graph
+            .edge_property_indexes()
+            .filter_map(|index| {
+                Some(SerializableJsonPathIndex {
+                    name: index.name.clone(),
+                    label: index.label.clone(),
+                    property: index.property.clone(),
+                    path: index.path.as_ref()?.to_string(),
+                })
+            })
+            .collect();
+        metadata_tree.insert(
+            b"edge_json_path_indexes",
+            &bincode::serialize(&path_definitions)?,
+        )?;
+
+        let key_definitions: Vec<SerializableNodeKeyIndex> = graph
+            .node_key_indexes()
+            .map(|index| SerializableNodeKeyIndex {
+                label: index.label.clone(),
+                property: index.property.clone(),
+            })
+            .collect();
+        metadata_tree.insert(b"node_key_indexes", &bincode::serialize(&key_definitions)?)?;
+
+        Self::write_reverse_adjacency(adjacency_in_tree.as_ref(), graph)?;
+        metadata_tree.insert(LAYOUT_VERSION_KEY, &bincode::serialize(&LAYOUT_VERSION)?)?;
+
         // Flush to disk
         driver.flush()?;
 
         Ok(())
     }
 
+    /// Examine every storage tree for unreadable or corrupt records
+    ///
+    /// The quick check opens every tree and reads its first record. The deep
+    /// check reads every record, verifies the checksum of each node and edge
+    /// record and decodes it.
+    pub fn check_storage(
+        &self,
+        driver: &dyn StorageDriver<Tree = Box<dyn StorageTree>>,
+        deep: bool,
+    ) -> Result<StorageCheckReport, Box<dyn std::error::Error>> {
+        let mut report = StorageCheckReport {
+            deep,
+            ..StorageCheckReport::default()
+        };
+        let mut tree_names = driver.list_trees()?;
+        tree_names.sort();
+
+        for tree_name in tree_names {
+            report.trees += 1;
+            let tree = match driver.open_tree(&tree_name) {
+                Ok(tree) => tree,
+                Err(e) => {
+                    report.problems.push(CorruptRecord::detected(
+                        &tree_name,
+                        b"",
+                        format!("tree cannot be opened: {}", e),
+                    ));
+                    continue;
+                }
+            };
+
+            // Node and edge trees are decoded according to their graph's layout
+            let record_kind = if let Some(prefix) = tree_name.strip_prefix("nodes_") {
+                Some((false, prefix))
+            } else {
+                tree_name
+                    .strip_prefix("edges_")
+                    .map(|prefix| (true, prefix))
+            };
+            let checksummed = match record_kind {
+                Some((_, prefix)) if deep => {
+                    let metadata_tree = driver.open_tree(&format!("metadata_{}", prefix))?;
+                    Self::layout_version(metadata_tree.as_ref())? >= CHECKSUM_LAYOUT_VERSION
+                }
+                _ => false,
+            };
+
+            for entry in tree.iter()? {
+                let (key, data) = match entry {
+                    Ok(entry) => entry,
+                    Err(e) => {
+                        report.problems.push(CorruptRecord::detected(
+                            &tree_name,
+                            b"",
+                            format!("read failed: {}", e),
+                        ));
+                        break;
+                    }
+                };
+                report.records += 1;
+                if !deep {
+                    break;
+                }
+                let decoded = match record_kind {
+                    Some((false, _)) => Self::decode_record::<SerializableNode>(
+                        &tree_name,
+                        &key,
+                        &data,
+                        checksummed,
+                    )
+                    .map(|_| ()),
+                    Some((true, _)) => Self::decode_record::<SerializableEdge>(
+                        &tree_name,
+                        &key,
+                        &data,
+                        checksummed,
+                    )
+                    .map(|_| ()),
+                    None => continue,
+                };
+                match decoded {
+                    Ok(()) if checksummed => report.verified += 1,
+                    Ok(()) => {}
+                    Err(problem) => report.problems.push(problem),
+                }
+            }
+        }
+        Ok(report)
+    }
+
+    /// Find stored edges whose source or target node is not stored
+    ///
+    /// Such edges make their graph fail to load. With `repair` they are deleted
+    /// from the edge and reverse adjacency trees. Records that cannot be decoded
+    /// are skipped; `check_storage` reports those.
+    pub fn check_references(
+        &self,
+        driver: &dyn StorageDriver<Tree = Box<dyn StorageTree>>,
+        repair: bool,
+    ) -> Result<ReferenceCheckReport, Box<dyn std::error::Error>> {
+        let mut report = ReferenceCheckReport::default();
+        let tree_names = driver.list_trees()?;
+
+        for graph_prefix in self.list_graphs(driver)? {
+            report.graphs += 1;
+            let nodes_tree_name = format!("nodes_{}", graph_prefix);
+            let edges_tree_name = format!("edges_{}", graph_prefix);
+
+            // Opening a tree creates it, so missing trees are skipped
+            let mut node_ids = std::collections::HashSet::new();
+            if tree_names.contains(&nodes_tree_name) {
+                for entry in driver.open_tree(&nodes_tree_name)?.iter()? {
+                    let (key, _) = entry?;
+                    report.nodes += 1;
+                    node_ids.insert(key);
+                }
+            }
+            let edges_tree = if tree_names.contains(&edges_tree_name) {
+                Some(driver.open_tree(&edges_tree_name)?)
+            } else {
+                None
+            };
+            let has_edges = match &edges_tree {
+                Some(tree) => !tree.is_empty()?,
+                None => false,
+            };
+            if !node_ids.is_empty() || has_edges {
+                report.stored_graphs.push(graph_prefix.clone());
+            }
+            let Some(edges_tree) = edges_tree.filter(|_| has_edges) else {
+                continue;
+            };
+
+            let metadata_tree = driver.open_tree(&format!("metadata_{}", graph_prefix))?;
+            let checksummed =
+                Self::layout_version(metadata_tree.as_ref())? >= CHECKSUM_LAYOUT_VERSION;
+            let mut dangling = Vec::new();
+            for entry in edges_tree.iter()? {
+                let (key, data) = entry?;
+                report.edges += 1;
+                let Ok(edge) = Self::decode_record::<SerializableEdge>(
+                    &edges_tree_name,
+                    &key,
+                    &data,
+                    checksummed,
+                ) else {
+                    continue;
+                };
+                let from_missing = !node_ids.contains(edge.from_node.as_bytes());
+                let to_missing = !node_ids.contains(edge.to_node.as_bytes());
+                if from_missing || to_missing {
+                    dangling.push((key, edge, from_missing, to_missing));
+                }
+            }
+
+            if repair && !dangling.is_empty() {
+                let adjacency_in_tree =
+                    driver.open_tree(&format!("adjacency_in_{}", graph_prefix))?;
+                for (key, edge, _, _) in &dangling {
+                    edges_tree.remove(key)?;
+                    adjacency_in_tree.remove(&Self::reverse_adjacency_key(&Edge {
+                        id: edge.id.clone(),
+                        label: edge.label.clone(),
+                        from_node: edge.from_node.clone(),
+                        to_node: edge.to_node.clone(),
+                        properties: HashMap::new(),
+                    }))?;
+                }
+                driver.flush()?;
+            }
+            report.dangling_edges.extend(dangling.into_iter().map(
+                |(_, edge, from_missing, to_missing)| DanglingEdge {
+                    graph: graph_prefix.clone(),
+                    edge_id: edge.id,
+                    label: edge.label,
+                    from_node: edge.from_node,
+                    to_node: edge.to_node,
+                    from_missing,
+                    to_missing,
+                    removed: repair,
+                },
+            ));
+        }
+        Ok(report)
+    }
+
     /// Check if the database contains saved graph data
     pub fn has_data(
         &self,
@@ -300,6 +697,7 @@ impl DataAdapter {
         let nodes_tree_name = format!("nodes_{}", graph_prefix);
         let edges_tree_name = format!("edges_{}", graph_prefix);
         let metadata_tree_name = format!("metadata_{}", graph_prefix);
+        let adjacency_in_tree_name = format!("adjacency_in_{}", graph_prefix);
 
         // Open and clear graph-specific trees if they exist
         if let Ok(nodes_tree) = driver.open_tree(&nodes_tree_name) {
@@ -317,6 +715,11 @@ impl DataAdapter {
             debug!("Cleared metadata tree for graph '{}'", graph_name);
         }
 
+        if let Ok(adjacency_in_tree) = driver.open_tree(&adjacency_in_tree_name) {
+            adjacency_in_tree.clear()?;
+            debug!("Cleared reverse adjacency tree for graph '{}'", graph_name);
+        }
+
         // Flush changes to persistent storage
         driver.flush()?;
 
@@ -386,6 +789,52 @@ impl DataAdapter {
         Ok(providers)
     }
 
+    /// Save cache access metadata (the cache hot set) to persistent storage
+    pub fn save_cache_metadata(
+        &self,
+        driver: &dyn StorageDriver<Tree = Box<dyn StorageTree>>,
+        data: &[u8],
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let metadata_tree = driver.open_tree("metadata")?;
+        metadata_tree.insert(b"cache_hot_set", data)?;
+        driver.flush()?;
+        Ok(())
+    }
+
+    /// Load cache access metadata, if any was saved
+    pub fn load_cache_metadata(
+        &self,
+        driver: &dyn StorageDriver<Tree = Box<dyn StorageTree>>,
+    ) -> Result<Option<Vec<u8>>, Box<dyn std::error::Error>> {
+        let metadata_tree = driver.open_tree("metadata")?;
+        Ok(metadata_tree
+            .get(b"cache_hot_set")?
+            .map(|data| data.to_vec()))
+    }
+
+    /// Persist the plans pinned with `gql.pin_plan`
+    pub fn save_pinned_plans(
+        &self,
+        driver: &dyn StorageDriver<Tree = Box<dyn StorageTree>>,
+        data: &[u8],
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let metadata_tree = driver.open_tree("metadata")?;
+        metadata_tree.insert(b"pinned_plans", data)?;
+        driver.flush()?;
+        Ok(())
+    }
+
+    /// Load the pinned plans, if any were saved
+    pub fn load_pinned_plans(
+        &self,
+        driver: &dyn StorageDriver<Tree = Box<dyn StorageTree>>,
+    ) -> Result<Option<Vec<u8>>, Box<dyn std::error::Error>> {
+        let metadata_tree = driver.open_tree("metadata")?;
+        Ok(metadata_tree
+            .get(b"pinned_plans")?
+            .map(|data| data.to_vec()))
+    }
+
     /// Get metadata about stored graph
     pub fn get_metadata(
         &self,
@@ -479,3 +928,198 @@ impl std::fmt::Debug for DataAdapter {
         f.debug_struct("DataAdapter").finish_non_exhaustive()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::persistent::{create_storage_driver, StorageType};
+
+    fn node(id: &str, label: &str) -> Node {
+        Node {
+            id: id.to_string(),
+            labels: vec![label.to_string()],
+            properties: HashMap::new(),
+        }
+    }
+
+    fn edge(id: &str, from: &str, to: &str, label: &str) -> Edge {
+        Edge {
+            id: id.to_string(),
+            from_node: from.to_string(),
+            to_node: to.to_string(),
+            label: label.to_string(),
+            properties: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_reverse_adjacency_migration() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let driver = create_storage_driver(StorageType::Memory, temp_dir.path()).unwrap();
+        let adapter = DataAdapter::new();
+
+        let mut graph = GraphCache::new();
+        graph.add_node(node("c1", "Compound")).unwrap();
+        graph.add_node(node("p1", "Protein")).unwrap();
+        graph.add_edge(edge("e1", "c1", "p1", "INHIBITS")).unwrap();
+        graph.add_edge(edge("e2", "c1", "p1", "BINDS")).unwrap();
+        adapter
+            .save_graph_by_path(driver.as_ref(), &graph, "/pharma/assays")
+            .unwrap();
+
+        let adjacency_in = driver.open_tree("adjacency_in_pharma_assays").unwrap();
+        let incoming: Vec<_> = adjacency_in
+            .scan_prefix(b"p1\0INHIBITS\0")
+            .unwrap()
+            .map(|entry| entry.unwrap())
+            .collect();
+        assert_eq!(
+            incoming,
+            vec![(b"p1\0INHIBITS\0e1".to_vec(), b"c1".to_vec())]
+        );
+
+        // Simulate a graph saved by a version without the reverse adjacency tree
+        adjacency_in.clear().unwrap();
+        let metadata = driver.open_tree("metadata_pharma_assays").unwrap();
+        metadata.remove(LAYOUT_VERSION_KEY).unwrap();
+
+        let loaded = adapter
+            .load_graph_by_path(driver.as_ref(), "/pharma/assays")
+            .unwrap();
+        assert_eq!(
+            loaded.get_incoming_edges_with_labels("p1", &["INHIBITS".to_string()])[0].id,
+            "e1"
+        );
+        assert_eq!(adjacency_in.scan_prefix(b"p1\0").unwrap().count(), 2);
+        let version: u32 =
+            bincode::deserialize(&metadata.get(LAYOUT_VERSION_KEY).unwrap().unwrap()).unwrap();
+        assert_eq!(version, LAYOUT_VERSION);
+    }
+
+    #[test]
+    fn test_corrupt_record_fails_load_and_check() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let driver = create_storage_driver(StorageType::Memory, temp_dir.path()).unwrap();
+        let adapter = DataAdapter::new();
+
+        let mut graph = GraphCache::new();
+        graph.add_node(node("c1", "Compound")).unwrap();
+        graph.add_node(node("p1", "Protein")).unwrap();
+        graph.add_edge(edge("e1", "c1", "p1", "INHIBITS")).unwrap();
+        adapter
+            .save_graph_by_path(driver.as_ref(), &graph, "/pharma/assays")
+            .unwrap();
+
+        let report = adapter.check_storage(driver.as_ref(), true).unwrap();
+        assert!(report.is_ok(), "{:?}", report.problems);
+        assert_eq!(report.verified, 3);
+
+        // Flip one bit of a stored node
+        let nodes = driver.open_tree("nodes_pharma_assays").unwrap();
+        let mut data = nodes.get(b"p1").unwrap().unwrap();
+        data[6] ^= 0x01;
+        nodes.insert(b"p1", &data).unwrap();
+
+        let error = adapter
+            .load_graph_by_path(driver.as_ref(), "/pharma/assays")
+            .unwrap_err();
+        let corrupt = error.downcast_ref::<CorruptRecord>().unwrap();
+        assert_eq!(corrupt.tree, "nodes_pharma_assays");
+        assert_eq!(corrupt.key, "p1");
+
+        // The quick check only samples each tree; the deep check finds the record
+        assert!(adapter
+            .check_storage(driver.as_ref(), false)
+            .unwrap()
+            .is_ok());
+        let report = adapter.check_storage(driver.as_ref(), true).unwrap();
+        assert_eq!(report.problems, vec![corrupt.clone()]);
+    }
+
+    #[test]
+    fn test_records_without_checksums_are_rewritten() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let driver = create_storage_driver(StorageType::Memory, temp_dir.path()).unwrap();
+        let adapter = DataAdapter::new();
+
+        // A graph saved by layout version 2: plain bincode records
+        let nodes = driver.open_tree("nodes_legacy_g").unwrap();
+        let record = SerializableNode {
+            id: "n1".to_string(),
+            labels: vec!["Item".to_string()],
+            properties: HashMap::new(),
+        };
+        nodes
+            .insert(b"n1", &bincode::serialize(&record).unwrap())
+            .unwrap();
+        driver.open_tree("edges_legacy_g").unwrap();
+        let metadata = driver.open_tree("metadata_legacy_g").unwrap();
+        metadata
+            .insert(LAYOUT_VERSION_KEY, &bincode::serialize(&2u32).unwrap())
+            .unwrap();
+
+        let loaded = adapter
+            .load_graph_by_path(driver.as_ref(), "/legacy/g")
+            .unwrap();
+        assert!(loaded.get_node("n1").is_some());
+
+        let stored = nodes.get(b"n1").unwrap().unwrap();
+        assert!(integrity::open_record("nodes_legacy_g", b"n1", &stored).is_ok());
+        let report = adapter.check_storage(driver.as_ref(), true).unwrap();
+        assert!(report.is_ok(), "{:?}", report.problems);
+        assert_eq!(report.verified, 1);
+    }
+
+    #[test]
+    fn test_dangling_edges_are_found_and_repaired() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let driver = create_storage_driver(StorageType::Memory, temp_dir.path()).unwrap();
+        let adapter = DataAdapter::new();
+
+        let mut graph = GraphCache::new();
+        graph.add_node(node("c1", "Compound")).unwrap();
+        graph.add_node(node("p1", "Protein")).unwrap();
+        graph.add_node(node("p2", "Protein")).unwrap();
+        graph.add_edge(edge("e1", "c1", "p1", "INHIBITS")).unwrap();
+        graph.add_edge(edge("e2", "c1", "p2", "BINDS")).unwrap();
+        adapter
+            .save_graph_by_path(driver.as_ref(), &graph, "/pharma/assays")
+            .unwrap();
+
+        let report = adapter.check_references(driver.as_ref(), false).unwrap();
+        assert!(report.is_ok());
+        assert_eq!((report.graphs, report.nodes, report.edges), (1, 3, 2));
+        assert_eq!(report.stored_graphs, vec!["pharma_assays".to_string()]);
+
+        // Lose a node without its edge
+        let nodes = driver.open_tree("nodes_pharma_assays").unwrap();
+        nodes.remove(b"p2").unwrap();
+        assert!(adapter
+            .load_graph_by_path(driver.as_ref(), "/pharma/assays")
+            .is_err());
+
+        let report = adapter.check_references(driver.as_ref(), false).unwrap();
+        assert!(!report.is_ok());
+        assert_eq!(report.dangling_edges.len(), 1);
+        let dangling = &report.dangling_edges[0];
+        assert_eq!(dangling.edge_id, "e2");
+        assert!(!dangling.from_missing && dangling.to_missing);
+        assert!(!dangling.removed);
+
+        let report = adapter.check_references(driver.as_ref(), true).unwrap();
+        assert!(report.is_ok());
+        assert!(report.dangling_edges[0].removed);
+        let adjacency_in = driver.open_tree("adjacency_in_pharma_assays").unwrap();
+        assert_eq!(adjacency_in.scan_prefix(b"p2\0").unwrap().count(), 0);
+
+        let loaded = adapter
+            .load_graph_by_path(driver.as_ref(), "/pharma/assays")
+            .unwrap();
+        assert_eq!(loaded.edge_count().unwrap(), 1);
+        assert!(adapter
+            .check_references(driver.as_ref(), false)
+            .unwrap()
+            .dangling_edges
+            .is_empty());
+    }
+}
@@ -8,8 +8,12 @@
 //! indices for quick lookup by node/edge types.
 
 use crate::catalog::providers::schema::SchemaId;
+use crate::storage::indexes::{EdgePropertyIndex, NodeKeyIndex};
+use crate::storage::json_path::JsonPath;
 use crate::storage::types::{Edge, GraphError, Node};
-use std::collections::HashMap;
+use crate::storage::value::Value;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::ops::Bound;
 
 /// In-memory graph cache with indices for fast lookups
 #[derive(Debug, Clone)]
@@ -32,8 +36,27 @@ pub struct GraphCache {
     /// Adjacency list: node_id -> list of outgoing edge IDs
     adjacency_out: HashMap<String, Vec<String>>,
 
-    /// Adjacency list: node_id -> list of incoming edge IDs
-    adjacency_in: HashMap<String, Vec<String>>,
+    /// Reverse adjacency: (node_id, edge label) -> list of incoming edge IDs
+    ///
+    /// Ordered by node first, so all incoming edges of a node are one range and an
+    /// incoming expansion over one relationship type is a single lookup.
+    adjacency_in: BTreeMap<(String, String), Vec<String>>,
+
+    /// Outgoing edge counts per (node_id, edge label), for degree lookups that do not
+    /// visit the edges themselves
+    out_degrees: HashMap<(String, String), usize>,
+
+    /// Relationship property indexes by index name
+    edge_property_indexes: HashMap<String, EdgePropertyIndex>,
+
+    /// Indexed edges handed out mutably since the indexes were last refreshed
+    stale_indexed_edges: HashSet<String>,
+
+    /// Node key indexes, one per (label, key property)
+    node_key_indexes: Vec<NodeKeyIndex>,
+
+    /// Keyed nodes handed out mutably since the key indexes were last refreshed
+    stale_keyed_nodes: HashSet<String>,
 }
 
 impl GraphCache {
@@ -46,7 +69,12 @@ impl GraphCache {
             node_labels: HashMap::new(),
             edge_labels: HashMap::new(),
             adjacency_out: HashMap::new(),
-            adjacency_in: HashMap::new(),
+            adjacency_in: BTreeMap::new(),
+            out_degrees: HashMap::new(),
+            edge_property_indexes: HashMap::new(),
+            stale_indexed_edges: HashSet::new(),
+            node_key_indexes: Vec::new(),
+            stale_keyed_nodes: HashSet::new(),
         }
     }
 
@@ -65,9 +93,12 @@ impl GraphCache {
                 .push(node.id.clone());
         }
 
-        // Initialize adjacency lists for this node
+        // Initialize the outgoing adjacency list for this node
         self.adjacency_out.insert(node.id.clone(), Vec::new());
-        self.adjacency_in.insert(node.id.clone(), Vec::new());
+
+        for index in &mut self.node_key_indexes {
+            index.insert(&node);
+        }
 
         // Store the node
         self.nodes.insert(node.id.clone(), node);
@@ -82,13 +113,21 @@ impl GraphCache {
             return Err(GraphError::EdgeAlreadyExists(edge.id));
         }
 
-        // Check for semantic duplicate edge (same source, target, label, and properties)
-        let has_duplicate = self.edges.values().any(|existing_edge| {
-            existing_edge.from_node == edge.from_node
-                && existing_edge.to_node == edge.to_node
-                && existing_edge.label == edge.label
-                && existing_edge.properties == edge.properties
-        });
+        // Check for semantic duplicate edge (same source, target, label, and properties).
+        // Any such edge leaves the same source node, so only its outgoing edges are scanned.
+        let has_duplicate = self
+            .adjacency_out
+            .get(&edge.from_node)
+            .is_some_and(|edge_ids| {
+                edge_ids
+                    .iter()
+                    .filter_map(|id| self.edges.get(id))
+                    .any(|existing_edge| {
+                        existing_edge.to_node == edge.to_node
+                            && existing_edge.label == edge.label
+                            && existing_edge.properties == edge.properties
+                    })
+            });
 
         if has_duplicate {
             return Err(GraphError::EdgeAlreadyExists(format!(
@@ -124,9 +163,18 @@ impl GraphCache {
             .push(edge.id.clone());
 
         self.adjacency_in
-            .get_mut(&edge.to_node)
-            .unwrap()
+            .entry((edge.to_node.clone(), edge.label.clone()))
+            .or_default()
             .push(edge.id.clone());
+        *self
+            .out_degrees
+            .entry((edge.from_node.clone(), edge.label.clone()))
+            .or_default() += 1;
+
+        // Update relationship property indexes
+        for index in self.edge_property_indexes.values_mut() {
+            index.insert(&edge);
+        }
 
         // Store the edge
         self.edges.insert(edge.id.clone(), edge);
@@ -140,8 +188,15 @@ impl GraphCache {
     }
 
     /// Get a mutable reference to a node by ID
+    ///
+    /// While the graph has node key indexes, the node is looked up by value
+    /// until `refresh_node_key_indexes` re-indexes it.
     pub fn get_node_mut(&mut self, id: &str) -> Option<&mut Node> {
-        self.nodes.get_mut(id)
+        let node = self.nodes.get_mut(id)?;
+        if !self.node_key_indexes.is_empty() {
+            self.stale_keyed_nodes.insert(id.to_string());
+        }
+        Some(node)
     }
 
     /// Get an edge by ID
@@ -150,8 +205,19 @@ impl GraphCache {
     }
 
     /// Get a mutable reference to an edge by ID
+    ///
+    /// If the edge is covered by a relationship property index it is looked up
+    /// by value until `refresh_edge_property_indexes` re-indexes it.
     pub fn get_edge_mut(&mut self, id: &str) -> Option<&mut Edge> {
-        self.edges.get_mut(id)
+        let edge = self.edges.get_mut(id)?;
+        if self
+            .edge_property_indexes
+            .values()
+            .any(|index| index.covers(edge))
+        {
+            self.stale_indexed_edges.insert(id.to_string());
+        }
+        Some(edge)
     }
 
     /// Get all nodes with a specific label
@@ -230,15 +296,67 @@ impl GraphCache {
 
     /// Get all incoming edges to a node
     pub fn get_incoming_edges(&self, node_id: &str) -> Vec<&Edge> {
+        self.incoming_edge_ids(node_id)
+            .filter_map(|id| self.edges.get(id))
+            .collect()
+    }
+
+    /// Get the incoming edges to a node with one of the given labels
+    ///
+    /// Served by the reverse adjacency index, so only edges of the requested types
+    /// are visited. An empty label list returns all incoming edges.
+    pub fn get_incoming_edges_with_labels(&self, node_id: &str, labels: &[String]) -> Vec<&Edge> {
+        if labels.is_empty() {
+            return self.get_incoming_edges(node_id);
+        }
+        labels
+            .iter()
+            .filter_map(|label| self.adjacency_in.get(&(node_id.to_string(), label.clone())))
+            .flatten()
+            .filter_map(|id| self.edges.get(id))
+            .collect()
+    }
+
+    /// Number of incoming edges to a node, optionally restricted to one label
+    pub fn in_degree(&self, node_id: &str, label: Option<&str>) -> usize {
+        match label {
+            Some(label) => self
+                .adjacency_in
+                .get(&(node_id.to_string(), label.to_string()))
+                .map_or(0, Vec::len),
+            None => self.incoming_edge_ids(node_id).count(),
+        }
+    }
+
+    /// Number of outgoing edges from a node, optionally restricted to one label
+    pub fn out_degree(&self, node_id: &str, label: Option<&str>) -> usize {
+        match label {
+            Some(label) => self
+                .out_degrees
+                .get(&(node_id.to_string(), label.to_string()))
+                .copied()
+                .unwrap_or(0),
+            None => self.adjacency_out.get(node_id).map_or(0, Vec::len),
+        }
+    }
+
+    /// Lower the outgoing count of `label` edges from `node_id`, dropping it at zero
+    fn decrement_out_degree(&mut self, node_id: &str, label: &str, count: usize) {
+        let key = (node_id.to_string(), label.to_string());
+        if let Some(degree) = self.out_degrees.get_mut(&key) {
+            *degree = degree.saturating_sub(count);
+            if *degree == 0 {
+                self.out_degrees.remove(&key);
+            }
+        }
+    }
+
+    /// Ids of all incoming edges to a node, grouped by label
+    fn incoming_edge_ids<'a>(&'a self, node_id: &'a str) -> impl Iterator<Item = &'a String> {
         self.adjacency_in
-            .get(node_id)
-            .map(|edge_ids| {
-                edge_ids
-                    .iter()
-                    .filter_map(|id| self.edges.get(id))
-                    .collect()
-            })
-            .unwrap_or_default()
+            .range((node_id.to_string(), String::new())..)
+            .take_while(move |((to_node, _), _)| to_node == node_id)
+            .flat_map(|(_, edge_ids)| edge_ids.iter())
     }
 
     /// Get all edges connected to a node (both incoming and outgoing)
@@ -299,9 +417,7 @@ impl GraphCache {
         if let Some(outgoing) = self.adjacency_out.get(node_id) {
             edges_to_remove.extend(outgoing.clone());
         }
-        if let Some(incoming) = self.adjacency_in.get(node_id) {
-            edges_to_remove.extend(incoming.clone());
-        }
+        edges_to_remove.extend(self.incoming_edge_ids(node_id).cloned());
 
         // Remove all connected edges
         for edge_id in edges_to_remove {
@@ -310,7 +426,11 @@ impl GraphCache {
 
         // Remove adjacency lists
         self.adjacency_out.remove(node_id);
-        self.adjacency_in.remove(node_id);
+
+        for index in &mut self.node_key_indexes {
+            index.remove(node_id);
+        }
+        self.stale_keyed_nodes.remove(node_id);
 
         Ok(node)
     }
@@ -334,13 +454,94 @@ impl GraphCache {
         if let Some(outgoing) = self.adjacency_out.get_mut(&edge.from_node) {
             outgoing.retain(|id| id != edge_id);
         }
-        if let Some(incoming) = self.adjacency_in.get_mut(&edge.to_node) {
+        self.decrement_out_degree(&edge.from_node, &edge.label, 1);
+        let reverse_key = (edge.to_node.clone(), edge.label.clone());
+        if let Some(incoming) = self.adjacency_in.get_mut(&reverse_key) {
             incoming.retain(|id| id != edge_id);
+            if incoming.is_empty() {
+                self.adjacency_in.remove(&reverse_key);
+            }
         }
 
+        // Remove from relationship property indexes
+        for index in self.edge_property_indexes.values_mut() {
+            index.remove(edge_id);
+        }
+        self.stale_indexed_edges.remove(edge_id);
+
         Ok(edge)
     }
 
+    /// Remove the edges of one relationship type in bulk
+    ///
+    /// With no selection the whole label partition is dropped; otherwise only the
+    /// selected edge ids of that type are removed. Unlike repeated `remove_edge`
+    /// calls, every index is rewritten once per affected node or label rather than
+    /// once per edge. Returns the removed edges.
+    pub fn remove_edges_by_label(
+        &mut self,
+        label: &str,
+        selection: Option<&HashSet<String>>,
+    ) -> Vec<Edge> {
+        let Some(label_ids) = self.edge_labels.get_mut(label) else {
+            return Vec::new();
+        };
+
+        let removed_ids: Vec<String> = match selection {
+            None => std::mem::take(label_ids),
+            Some(selected) => {
+                let (removed, kept): (Vec<String>, Vec<String>) = std::mem::take(label_ids)
+                    .into_iter()
+                    .partition(|id| selected.contains(id));
+                *label_ids = kept;
+                removed
+            }
+        };
+        if label_ids.is_empty() {
+            self.edge_labels.remove(label);
+        }
+        if removed_ids.is_empty() {
+            return Vec::new();
+        }
+
+        let removed: Vec<Edge> = removed_ids
+            .iter()
+            .filter_map(|id| self.edges.remove(id))
+            .collect();
+        let removed_set: HashSet<&str> = removed.iter().map(|edge| edge.id.as_str()).collect();
+
+        let mut sources: HashMap<&str, usize> = HashMap::new();
+        for edge in &removed {
+            *sources.entry(edge.from_node.as_str()).or_default() += 1;
+        }
+        for (source, count) in sources {
+            if let Some(outgoing) = self.adjacency_out.get_mut(source) {
+                outgoing.retain(|id| !removed_set.contains(id.as_str()));
+            }
+            self.decrement_out_degree(source, label, count);
+        }
+
+        let targets: HashSet<&str> = removed.iter().map(|edge| edge.to_node.as_str()).collect();
+        for target in targets {
+            let reverse_key = (target.to_string(), label.to_string());
+            if let Some(incoming) = self.adjacency_in.get_mut(&reverse_key) {
+                incoming.retain(|id| !removed_set.contains(id.as_str()));
+                if incoming.is_empty() {
+                    self.adjacency_in.remove(&reverse_key);
+                }
+            }
+        }
+
+        for edge in &removed {
+            for index in self.edge_property_indexes.values_mut() {
+                index.remove(&edge.id);
+            }
+            self.stale_indexed_edges.remove(&edge.id);
+        }
+
+        removed
+    }
+
     /// Get graph statistics
     pub fn stats(&self) -> GraphStats {
         GraphStats {
@@ -351,6 +552,46 @@ impl GraphCache {
         }
     }
 
+    /// Node labels with the number of nodes carrying each, ordered by label
+    pub fn label_counts(&self) -> Vec<(String, usize)> {
+        let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+        for label in self.nodes.values().flat_map(|node| &node.labels) {
+            *counts.entry(label).or_default() += 1;
+        }
+        counts
+            .into_iter()
+            .map(|(label, count)| (label.to_string(), count))
+            .collect()
+    }
+
+    /// Edge labels with the number of edges carrying each, ordered by label
+    pub fn relationship_type_counts(&self) -> Vec<(String, usize)> {
+        let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+        for edge in self.edges.values() {
+            *counts.entry(&edge.label).or_default() += 1;
+        }
+        counts
+            .into_iter()
+            .map(|(label, count)| (label.to_string(), count))
+            .collect()
+    }
+
+    /// Property keys with the number of nodes and of edges that have each,
+    /// ordered by key
+    pub fn property_key_counts(&self) -> Vec<(String, usize, usize)> {
+        let mut counts: BTreeMap<&str, (usize, usize)> = BTreeMap::new();
+        for key in self.nodes.values().flat_map(|node| node.properties.keys()) {
+            counts.entry(key).or_default().0 += 1;
+        }
+        for key in self.edges.values().flat_map(|edge| edge.properties.keys()) {
+            counts.entry(key).or_default().1 += 1;
+        }
+        counts
+            .into_iter()
+            .map(|(key, (nodes, edges))| (key.to_string(), nodes, edges))
+            .collect()
+    }
+
     /// Check if the graph contains a node
     pub fn contains_node(&self, node_id: &str) -> bool {
         self.nodes.contains_key(node_id)
@@ -379,6 +620,210 @@ impl GraphCache {
         self.edge_labels.clear();
         self.adjacency_out.clear();
         self.adjacency_in.clear();
+        self.out_degrees.clear();
+        for index in self.edge_property_indexes.values_mut() {
+            index.clear();
+        }
+        self.stale_indexed_edges.clear();
+        for index in &mut self.node_key_indexes {
+            index.clear();
+        }
+        self.stale_keyed_nodes.clear();
+    }
+
+    /// Create a relationship property index over `label` edges and index the existing ones
+    ///
+    /// With a `path` the index covers the value at that path in the property's JSON
+    /// document. Returns the number of edges indexed.
+    pub fn create_edge_property_index(
+        &mut self,
+        name: &str,
+        label: &str,
+        property: &str,
+        path: Option<&JsonPath>,
+    ) -> Result<usize, GraphError> {
+        if self.edge_property_indexes.contains_key(name) {
+            return Err(GraphError::IndexError(format!(
+                "Index '{}' already exists",
+                name
+            )));
+        }
+        if let Some(existing) = self.find_edge_property_index(label, property, path) {
+            return Err(GraphError::IndexError(format!(
+                "Property '{}' of '{}' relationships is already indexed by '{}'",
+                existing.target(),
+                label,
+                existing.name
+            )));
+        }
+
+        let mut index = EdgePropertyIndex::new(
+            name.to_string(),
+            label.to_string(),
+            property.to_string(),
+            path.cloned(),
+        );
+        for edge in self.get_edges_by_label(label) {
+            index.insert(edge);
+        }
+        let indexed = index.len();
+
+        self.edge_property_indexes.insert(name.to_string(), index);
+        Ok(indexed)
+    }
+
+    /// Drop a relationship property index, returning whether it existed
+    pub fn drop_edge_property_index(&mut self, name: &str) -> bool {
+        self.edge_property_indexes.remove(name).is_some()
+    }
+
+    /// All relationship property indexes on this graph
+    pub fn edge_property_indexes(&self) -> impl Iterator<Item = &EdgePropertyIndex> {
+        self.edge_property_indexes.values()
+    }
+
+    /// Find the relationship property index for a relationship type and property (or path into it)
+    pub fn find_edge_property_index(
+        &self,
+        label: &str,
+        property: &str,
+        path: Option<&JsonPath>,
+    ) -> Option<&EdgePropertyIndex> {
+        self.edge_property_indexes
+            .values()
+            .find(|index| index.label == label && index.indexes(property, path))
+    }
+
+    /// Get `label` edges whose `property` lies in the given range using a relationship property index
+    ///
+    /// Returns `None` if no index covers the property or the range cannot be answered
+    /// by one, in which case the caller has to scan.
+    pub fn get_edges_by_property_range(
+        &self,
+        label: &str,
+        property: &str,
+        path: Option<&JsonPath>,
+        lower: Bound<&Value>,
+        upper: Bound<&Value>,
+    ) -> Option<Vec<&Edge>> {
+        let index = self.find_edge_property_index(label, property, path)?;
+        let ids = index.range(lower, upper)?;
+
+        // Edges changed in place may have moved in or out of the range
+        let mut edges: Vec<&Edge> = ids
+            .into_iter()
+            .filter(|id| !self.stale_indexed_edges.contains(*id))
+            .filter_map(|id| self.edges.get(id))
+            .collect();
+        edges.extend(
+            self.stale_indexed_edges
+                .iter()
+                .filter_map(|id| self.edges.get(id))
+                .filter(|edge| {
+                    index.covers(edge)
+                        && index.value_of(edge).is_some_and(|value| {
+                            EdgePropertyIndex::value_in_range(&value, lower, upper)
+                        })
+                }),
+        );
+
+        Some(edges)
+    }
+
+    /// Estimate how many `label` edges have `property` in the given range, `None` if not indexed
+    pub fn estimate_edges_by_property_range(
+        &self,
+        label: &str,
+        property: &str,
+        path: Option<&JsonPath>,
+        lower: Bound<&Value>,
+        upper: Bound<&Value>,
+    ) -> Option<usize> {
+        let index = self.find_edge_property_index(label, property, path)?;
+        let count = index.count(lower, upper)?;
+        Some(count + self.stale_indexed_edges.len())
+    }
+
+    /// Re-index edges that were changed in place through `get_edge_mut`
+    pub fn refresh_edge_property_indexes(&mut self) {
+        for id in std::mem::take(&mut self.stale_indexed_edges) {
+            if let Some(edge) = self.edges.get(&id) {
+                for index in self.edge_property_indexes.values_mut() {
+                    index.insert(edge);
+                }
+            }
+        }
+    }
+
+    /// Create a key index over `property` of `label` nodes and index the existing ones
+    ///
+    /// Creating an index that already exists leaves it as it is. Returns the
+    /// number of nodes indexed.
+    pub fn create_node_key_index(&mut self, label: &str, property: &str) -> usize {
+        if let Some(index) = self.find_node_key_index(label, property) {
+            return index.len();
+        }
+
+        let mut index = NodeKeyIndex::new(label.to_string(), property.to_string());
+        for node in self.get_nodes_by_label(label) {
+            index.insert(node);
+        }
+        let indexed = index.len();
+
+        self.node_key_indexes.push(index);
+        indexed
+    }
+
+    /// All node key indexes on this graph
+    pub fn node_key_indexes(&self) -> impl Iterator<Item = &NodeKeyIndex> {
+        self.node_key_indexes.iter()
+    }
+
+    /// Find the key index for a node label and property
+    pub fn find_node_key_index(&self, label: &str, property: &str) -> Option<&NodeKeyIndex> {
+        self.node_key_indexes
+            .iter()
+            .find(|index| index.label == label && index.property == property)
+    }
+
+    /// Get the `label` nodes whose `property` equals `value` using a node key index
+    ///
+    /// Returns `None` if no key index covers the property, in which case the
+    /// caller has to scan.
+    pub fn get_nodes_by_key(
+        &self,
+        label: &str,
+        property: &str,
+        value: &Value,
+    ) -> Option<Vec<&Node>> {
+        let index = self.find_node_key_index(label, property)?;
+
+        // Nodes changed in place may have gained or lost the key
+        let mut nodes: Vec<&Node> = index
+            .get(value)
+            .into_iter()
+            .filter(|id| !self.stale_keyed_nodes.contains(*id))
+            .filter_map(|id| self.nodes.get(id))
+            .collect();
+        nodes.extend(
+            self.stale_keyed_nodes
+                .iter()
+                .filter_map(|id| self.nodes.get(id))
+                .filter(|node| index.node_has_key(node, value)),
+        );
+
+        Some(nodes)
+    }
+
+    /// Re-index nodes that were changed in place through `get_node_mut`
+    pub fn refresh_node_key_indexes(&mut self) {
+        for id in std::mem::take(&mut self.stale_keyed_nodes) {
+            if let Some(node) = self.nodes.get(&id) {
+                for index in &mut self.node_key_indexes {
+                    index.insert(node);
+                }
+            }
+        }
     }
 }
 
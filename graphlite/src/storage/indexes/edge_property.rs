@@ -0,0 +1,404 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! Relationship property indexes
+//!
+//! An `EdgePropertyIndex` keeps the edges of one relationship type ordered by the
+//! value of one property, so that a predicate such as `r.IC50 < 100` can be answered
+//! without visiting every edge of that type. Only numbers, strings and booleans are
+//! indexed; edges whose value has another type (or no value) are left out.
+//!
+//! An index can also cover a JSONPath into a `Value::Json` property, answering
+//! predicates such as `JSON_GET(r.meta, '$.assay.ph') < 7`.
+
+use crate::storage::json_path::{self, JsonPath};
+use crate::storage::types::Edge;
+use crate::storage::value::Value;
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::ops::Bound;
+
+/// Ordered key for an indexed property value
+///
+/// Variants are ordered by type first, so a range over one type never reaches
+/// values of another.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub(super) enum IndexKey {
+    Boolean(bool),
+    Number(OrderedNumber),
+    String(String),
+}
+
+/// f64 with a total order so it can be used as a BTreeMap key
+#[derive(Debug, Clone, Copy)]
+pub(super) struct OrderedNumber(f64);
+
+impl PartialEq for OrderedNumber {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for OrderedNumber {}
+
+impl PartialOrd for OrderedNumber {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OrderedNumber {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+impl IndexKey {
+    pub(super) fn from_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::Boolean(b) => Some(IndexKey::Boolean(*b)),
+            Value::Number(n) => Some(IndexKey::Number(OrderedNumber(*n))),
+            Value::String(s) => Some(IndexKey::String(s.clone())),
+            _ => None,
+        }
+    }
+
+    /// Smallest key of the same type
+    fn type_min(&self) -> Bound<IndexKey> {
+        match self {
+            IndexKey::Boolean(_) => Bound::Included(IndexKey::Boolean(false)),
+            IndexKey::Number(_) => {
+                Bound::Included(IndexKey::Number(OrderedNumber(f64::NEG_INFINITY)))
+            }
+            IndexKey::String(_) => Bound::Included(IndexKey::String(String::new())),
+        }
+    }
+
+    /// Largest key of the same type
+    fn type_max(&self) -> Bound<IndexKey> {
+        match self {
+            IndexKey::Boolean(_) => Bound::Included(IndexKey::Boolean(true)),
+            IndexKey::Number(_) => Bound::Included(IndexKey::Number(OrderedNumber(f64::INFINITY))),
+            // Strings are the last variant, so everything above is a string
+            IndexKey::String(_) => Bound::Unbounded,
+        }
+    }
+}
+
+/// Convert a value bound into a key bound, `None` if the value cannot be indexed
+fn key_bound(bound: Bound<&Value>) -> Option<Bound<IndexKey>> {
+    match bound {
+        Bound::Included(value) => IndexKey::from_value(value).map(Bound::Included),
+        Bound::Excluded(value) => IndexKey::from_value(value).map(Bound::Excluded),
+        Bound::Unbounded => Some(Bound::Unbounded),
+    }
+}
+
+/// Key range for a value range, `None` if it cannot be answered by the index
+///
+/// An empty range (lower above upper) yields `Some(None)`.
+#[allow(clippy::type_complexity)]
+fn key_range(
+    lower: Bound<&Value>,
+    upper: Bound<&Value>,
+) -> Option<Option<(Bound<IndexKey>, Bound<IndexKey>)>> {
+    let lower = key_bound(lower)?;
+    let upper = key_bound(upper)?;
+
+    // Keep an open side within the type of the other side
+    let (lower, upper) = match (lower, upper) {
+        (Bound::Unbounded, Bound::Unbounded) => return None,
+        (Bound::Unbounded, upper) => {
+            let min = match &upper {
+                Bound::Included(key) | Bound::Excluded(key) => key.type_min(),
+                Bound::Unbounded => unreachable!(),
+            };
+            (min, upper)
+        }
+        (lower, Bound::Unbounded) => {
+            let max = match &lower {
+                Bound::Included(key) | Bound::Excluded(key) => key.type_max(),
+                Bound::Unbounded => unreachable!(),
+            };
+            (lower, max)
+        }
+        bounds => bounds,
+    };
+
+    // Values of different types never compare; BTreeMap::range panics on inverted ranges
+    let empty = match (&lower, &upper) {
+        (Bound::Included(l) | Bound::Excluded(l), Bound::Included(u) | Bound::Excluded(u))
+            if std::mem::discriminant(l) != std::mem::discriminant(u) =>
+        {
+            true
+        }
+        (Bound::Included(l), Bound::Included(u)) => l > u,
+        (Bound::Included(l) | Bound::Excluded(l), Bound::Included(u) | Bound::Excluded(u)) => {
+            l >= u
+        }
+        _ => false,
+    };
+
+    Some(if empty { None } else { Some((lower, upper)) })
+}
+
+fn range_contains(range: &(Bound<IndexKey>, Bound<IndexKey>), key: &IndexKey) -> bool {
+    let above_lower = match &range.0 {
+        Bound::Included(l) => key >= l,
+        Bound::Excluded(l) => key > l,
+        Bound::Unbounded => true,
+    };
+    let below_upper = match &range.1 {
+        Bound::Included(u) => key <= u,
+        Bound::Excluded(u) => key < u,
+        Bound::Unbounded => true,
+    };
+    above_lower && below_upper
+}
+
+/// Ordered index over one property of one relationship type
+#[derive(Debug, Clone)]
+pub struct EdgePropertyIndex {
+    pub name: String,
+    pub label: String,
+    pub property: String,
+    /// Path into the property's JSON document, if the index covers an extracted value
+    pub path: Option<JsonPath>,
+    /// Property value -> ids of the edges holding it
+    entries: BTreeMap<IndexKey, BTreeSet<String>>,
+    /// Edge id -> indexed value, so an edge can be removed after its properties changed
+    keys: HashMap<String, IndexKey>,
+}
+
+impl EdgePropertyIndex {
+    /// Create an empty index
+    pub fn new(name: String, label: String, property: String, path: Option<JsonPath>) -> Self {
+        Self {
+            name,
+            label,
+            property,
+            path,
+            entries: BTreeMap::new(),
+            keys: HashMap::new(),
+        }
+    }
+
+    /// Whether this index covers the given edge
+    pub fn covers(&self, edge: &Edge) -> bool {
+        edge.label == self.label
+    }
+
+    /// Whether this index is over the given property, or path into it
+    pub fn indexes(&self, property: &str, path: Option<&JsonPath>) -> bool {
+        self.property == property && self.path.as_ref() == path
+    }
+
+    /// What the index is over, as written in CREATE INDEX
+    pub fn target(&self) -> String {
+        match &self.path {
+            Some(path) => format!("JSON_GET({}, '{}')", self.property, path),
+            None => self.property.clone(),
+        }
+    }
+
+    /// The value this index keys an edge by, if the edge has one
+    pub fn value_of<'a>(&self, edge: &'a Edge) -> Option<Cow<'a, Value>> {
+        let value = edge.properties.get(&self.property)?;
+        match &self.path {
+            Some(path) => path
+                .select(value.as_json()?)
+                .map(|selected| Cow::Owned(json_path::json_to_value(selected))),
+            None => Some(Cow::Borrowed(value)),
+        }
+    }
+
+    /// Index an edge, replacing any entry it already has
+    pub fn insert(&mut self, edge: &Edge) {
+        if !self.covers(edge) {
+            return;
+        }
+        self.remove(&edge.id);
+
+        if let Some(key) = self
+            .value_of(edge)
+            .and_then(|value| IndexKey::from_value(&value))
+        {
+            self.entries
+                .entry(key.clone())
+                .or_default()
+                .insert(edge.id.clone());
+            self.keys.insert(edge.id.clone(), key);
+        }
+    }
+
+    /// Remove an edge from the index
+    pub fn remove(&mut self, edge_id: &str) {
+        if let Some(key) = self.keys.remove(edge_id) {
+            if let Some(ids) = self.entries.get_mut(&key) {
+                ids.remove(edge_id);
+                if ids.is_empty() {
+                    self.entries.remove(&key);
+                }
+            }
+        }
+    }
+
+    /// Remove every entry, keeping the definition
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.keys.clear();
+    }
+
+    /// Number of indexed edges
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Whether no edge is indexed
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Ids of the edges whose value lies in the range
+    ///
+    /// Returns `None` when the bounds cannot be answered by the index (both sides
+    /// open, or a bound value of a type that is not indexed).
+    pub fn range(&self, lower: Bound<&Value>, upper: Bound<&Value>) -> Option<Vec<&String>> {
+        let ids = match key_range(lower, upper)? {
+            Some(range) => self
+                .entries
+                .range(range)
+                .flat_map(|(_, ids)| ids.iter())
+                .collect(),
+            None => Vec::new(),
+        };
+        Some(ids)
+    }
+
+    /// Number of edges whose value lies in the range, `None` if the index cannot answer it
+    pub fn count(&self, lower: Bound<&Value>, upper: Bound<&Value>) -> Option<usize> {
+        let count = match key_range(lower, upper)? {
+            Some(range) => self.entries.range(range).map(|(_, ids)| ids.len()).sum(),
+            None => 0,
+        };
+        Some(count)
+    }
+
+    /// Check whether a value would fall into the range
+    pub fn value_in_range(value: &Value, lower: Bound<&Value>, upper: Bound<&Value>) -> bool {
+        let Some(key) = IndexKey::from_value(value) else {
+            return false;
+        };
+        match key_range(lower, upper) {
+            Some(Some(range)) => range_contains(&range, &key),
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inhibits(id: &str, ic50: Value) -> Edge {
+        Edge {
+            id: id.to_string(),
+            from_node: "c".to_string(),
+            to_node: "p".to_string(),
+            label: "INHIBITS".to_string(),
+            properties: HashMap::from([("IC50".to_string(), ic50)]),
+        }
+    }
+
+    fn index_with(edges: &[Edge]) -> EdgePropertyIndex {
+        let mut index = EdgePropertyIndex::new(
+            "idx_inhibits_ic50".to_string(),
+            "INHIBITS".to_string(),
+            "IC50".to_string(),
+            None,
+        );
+        for edge in edges {
+            index.insert(edge);
+        }
+        index
+    }
+
+    #[test]
+    fn test_numeric_ranges() {
+        let index = index_with(&[
+            inhibits("e1", Value::Number(5.0)),
+            inhibits("e2", Value::Number(100.0)),
+            inhibits("e3", Value::Number(250.0)),
+            inhibits("e4", Value::String("n/a".to_string())),
+        ]);
+
+        let below = Value::Number(100.0);
+        let ids = index
+            .range(Bound::Unbounded, Bound::Excluded(&below))
+            .unwrap();
+        assert_eq!(ids, vec!["e1"]);
+        assert_eq!(
+            index.count(Bound::Unbounded, Bound::Included(&below)),
+            Some(2)
+        );
+        assert_eq!(
+            index.count(Bound::Excluded(&below), Bound::Unbounded),
+            Some(1)
+        );
+        assert_eq!(
+            index.count(Bound::Included(&below), Bound::Included(&below)),
+            Some(1)
+        );
+
+        // Inverted ranges are empty rather than panicking
+        let low = Value::Number(10.0);
+        assert_eq!(
+            index.count(Bound::Included(&below), Bound::Excluded(&low)),
+            Some(0)
+        );
+        assert_eq!(index.count(Bound::Unbounded, Bound::Unbounded), None);
+    }
+
+    #[test]
+    fn test_reinsert_moves_edge() {
+        let mut index = index_with(&[inhibits("e1", Value::Number(5.0))]);
+        index.insert(&inhibits("e1", Value::Number(500.0)));
+
+        let cutoff = Value::Number(100.0);
+        assert_eq!(
+            index.count(Bound::Unbounded, Bound::Excluded(&cutoff)),
+            Some(0)
+        );
+        assert_eq!(index.len(), 1);
+
+        index.remove("e1");
+        assert!(index.is_empty());
+    }
+
+    #[test]
+    fn test_json_path_index() {
+        let assay = |id: &str, meta: serde_json::Value| Edge {
+            properties: HashMap::from([("meta".to_string(), Value::Json(meta))]),
+            ..inhibits(id, Value::Null)
+        };
+        let mut index = EdgePropertyIndex::new(
+            "idx_inhibits_ph".to_string(),
+            "INHIBITS".to_string(),
+            "meta".to_string(),
+            Some(JsonPath::parse("$.assay.ph").unwrap()),
+        );
+        index.insert(&assay("e1", serde_json::json!({"assay": {"ph": 6.5}})));
+        index.insert(&assay("e2", serde_json::json!({"assay": {"ph": 8}})));
+        index.insert(&assay("e3", serde_json::json!({"assay": {}})));
+        index.insert(&inhibits("e4", Value::Number(7.0)));
+
+        assert_eq!(index.len(), 2);
+        assert_eq!(index.target(), "JSON_GET(meta, '$.assay.ph')");
+        let neutral = Value::Number(7.0);
+        let ids = index
+            .range(Bound::Unbounded, Bound::Excluded(&neutral))
+            .unwrap();
+        assert_eq!(ids, vec!["e1"]);
+    }
+}
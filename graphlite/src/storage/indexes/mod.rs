@@ -5,16 +5,22 @@
 //!
 //! This module provides indexing support for:
 //! - Graph indexes (adjacency lists, paths, reachability)
+//! - Relationship property indexes (ordered values of one edge property)
+//! - Node key indexes (nodes of one label by the value of a key property)
 //!
 //! All indexes are designed to be partition-aware for future distribution.
 
+pub mod edge_property;
 pub mod errors;
 pub mod manager;
 pub mod metrics;
+pub mod node_key;
 pub mod traits;
 pub mod types;
 
 // Re-export core types
+pub use edge_property::EdgePropertyIndex;
 pub use errors::*;
 pub use manager::*;
+pub use node_key::NodeKeyIndex;
 pub use types::*;
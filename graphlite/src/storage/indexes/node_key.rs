@@ -0,0 +1,146 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! Node key indexes
+//!
+//! A `NodeKeyIndex` maps the value of one property of the nodes of one label to
+//! those nodes, so that a reference property holding a key (`RESOLVE(o.owner)`)
+//! finds the node it refers to without visiting every node of that label. Keys
+//! are meant to be unique; duplicates are kept rather than rejected so that a
+//! lookup can report them.
+
+use super::edge_property::IndexKey;
+use crate::storage::types::Node;
+use crate::storage::value::Value;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// Key index over one property of one node label
+#[derive(Debug, Clone)]
+pub struct NodeKeyIndex {
+    pub label: String,
+    pub property: String,
+    /// Key value -> ids of the nodes holding it
+    entries: BTreeMap<IndexKey, BTreeSet<String>>,
+    /// Node id -> indexed value, so a node can be removed after its properties changed
+    keys: HashMap<String, IndexKey>,
+}
+
+impl NodeKeyIndex {
+    /// Create an empty index
+    pub fn new(label: String, property: String) -> Self {
+        Self {
+            label,
+            property,
+            entries: BTreeMap::new(),
+            keys: HashMap::new(),
+        }
+    }
+
+    /// Whether this index covers the given node
+    pub fn covers(&self, node: &Node) -> bool {
+        node.labels.contains(&self.label)
+    }
+
+    /// Index a node, replacing any entry it already has
+    pub fn insert(&mut self, node: &Node) {
+        self.remove(&node.id);
+        if !self.covers(node) {
+            return;
+        }
+
+        if let Some(key) = node
+            .properties
+            .get(&self.property)
+            .and_then(IndexKey::from_value)
+        {
+            self.entries
+                .entry(key.clone())
+                .or_default()
+                .insert(node.id.clone());
+            self.keys.insert(node.id.clone(), key);
+        }
+    }
+
+    /// Remove a node from the index
+    pub fn remove(&mut self, node_id: &str) {
+        if let Some(key) = self.keys.remove(node_id) {
+            if let Some(ids) = self.entries.get_mut(&key) {
+                ids.remove(node_id);
+                if ids.is_empty() {
+                    self.entries.remove(&key);
+                }
+            }
+        }
+    }
+
+    /// Remove every entry, keeping the definition
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.keys.clear();
+    }
+
+    /// Number of indexed nodes
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Whether no node is indexed
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Ids of the nodes whose key equals `value`
+    pub fn get(&self, value: &Value) -> Vec<&String> {
+        IndexKey::from_value(value)
+            .and_then(|key| self.entries.get(&key))
+            .map(|ids| ids.iter().collect())
+            .unwrap_or_default()
+    }
+
+    /// Whether a node holds `value` as its key
+    pub fn node_has_key(&self, node: &Node, value: &Value) -> bool {
+        self.covers(node)
+            && node
+                .properties
+                .get(&self.property)
+                .and_then(IndexKey::from_value)
+                .is_some_and(|key| IndexKey::from_value(value) == Some(key))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn person(id: &str, email: &str) -> Node {
+        Node {
+            id: id.to_string(),
+            labels: vec!["Person".to_string()],
+            properties: HashMap::from([("email".to_string(), Value::String(email.to_string()))]),
+        }
+    }
+
+    #[test]
+    fn test_lookup_by_key() {
+        let mut index = NodeKeyIndex::new("Person".to_string(), "email".to_string());
+        index.insert(&person("n1", "ann@example.com"));
+        index.insert(&person("n2", "bo@example.com"));
+        let mut company = person("n3", "ann@example.com");
+        company.labels = vec!["Company".to_string()];
+        index.insert(&company);
+
+        let ann = Value::String("ann@example.com".to_string());
+        assert_eq!(index.get(&ann), vec!["n1"]);
+        assert!(index
+            .get(&Value::String("cy@example.com".to_string()))
+            .is_empty());
+        assert_eq!(index.len(), 2);
+
+        // A changed key moves the node; a second holder of a key is kept
+        index.insert(&person("n2", "ann@example.com"));
+        assert_eq!(index.get(&ann), vec!["n1", "n2"]);
+        index.remove("n1");
+        index.remove("n2");
+        assert!(index.is_empty());
+    }
+}
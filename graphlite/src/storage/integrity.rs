@@ -0,0 +1,213 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! Record checksums and storage integrity checks
+//!
+//! Node and edge records are written with a CRC-32 of their key and payload
+//! appended (storage layout version 3). `DataAdapter` verifies the checksum every
+//! time a graph is loaded, so a flipped bit fails the load with an error naming
+//! the tree and key instead of silently producing a different graph.
+//! `check_storage` runs the same verification over every record on demand.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Length of the checksum appended to a record
+const CHECKSUM_LEN: usize = 4;
+
+/// Records that failed verification since the process started
+static CORRUPT_RECORDS: AtomicU64 = AtomicU64::new(0);
+
+/// A stored record that failed verification
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CorruptRecord {
+    /// Storage tree holding the record, e.g. `nodes_app_social`
+    pub tree: String,
+    /// Record key, lossily decoded as UTF-8
+    pub key: String,
+    /// What is wrong with the record
+    pub reason: String,
+}
+
+impl std::fmt::Display for CorruptRecord {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Corrupt record in tree '{}' at key '{}': {}",
+            self.tree, self.key, self.reason
+        )
+    }
+}
+
+impl std::error::Error for CorruptRecord {}
+
+impl CorruptRecord {
+    /// Record a corruption in the process-wide counter and describe it
+    pub(crate) fn detected(tree: &str, key: &[u8], reason: String) -> Self {
+        CORRUPT_RECORDS.fetch_add(1, Ordering::Relaxed);
+        let record = Self {
+            tree: tree.to_string(),
+            key: String::from_utf8_lossy(key).into_owned(),
+            reason,
+        };
+        log::error!("{}", record);
+        record
+    }
+}
+
+/// Number of corrupt records detected since the process started
+pub(crate) fn corrupt_records_detected() -> u64 {
+    CORRUPT_RECORDS.load(Ordering::Relaxed)
+}
+
+fn checksum(key: &[u8], payload: &[u8]) -> u32 {
+    let mut hasher = crc32fast::Hasher::new();
+    hasher.update(key);
+    hasher.update(payload);
+    hasher.finalize()
+}
+
+/// Append the checksum of `key` and `payload` to `payload`
+pub(crate) fn seal_record(key: &[u8], mut payload: Vec<u8>) -> Vec<u8> {
+    let crc = checksum(key, &payload);
+    payload.extend_from_slice(&crc.to_le_bytes());
+    payload
+}
+
+/// Verify a sealed record and return its payload
+pub(crate) fn open_record<'a>(
+    tree: &str,
+    key: &[u8],
+    data: &'a [u8],
+) -> Result<&'a [u8], CorruptRecord> {
+    if data.len() < CHECKSUM_LEN {
+        return Err(CorruptRecord::detected(
+            tree,
+            key,
+            format!("record is {} bytes, too short for a checksum", data.len()),
+        ));
+    }
+    let (payload, stored) = data.split_at(data.len() - CHECKSUM_LEN);
+    let stored = u32::from_le_bytes([stored[0], stored[1], stored[2], stored[3]]);
+    let computed = checksum(key, payload);
+    if stored != computed {
+        return Err(CorruptRecord::detected(
+            tree,
+            key,
+            format!(
+                "checksum mismatch (stored {:08x}, computed {:08x})",
+                stored, computed
+            ),
+        ));
+    }
+    Ok(payload)
+}
+
+/// Outcome of `QueryCoordinator::check_storage`
+///
+/// Problems are reported as `CorruptRecord`s; a problem affecting a whole tree
+/// has an empty key.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StorageCheckReport {
+    /// Whether every record was read and verified
+    pub deep: bool,
+    /// Storage trees examined
+    pub trees: usize,
+    /// Records read
+    pub records: u64,
+    /// Records whose checksum was verified
+    pub verified: u64,
+    /// Problems found, in tree order
+    pub problems: Vec<CorruptRecord>,
+}
+
+impl StorageCheckReport {
+    /// Whether no problems were found
+    pub fn is_ok(&self) -> bool {
+        self.problems.is_empty()
+    }
+}
+
+/// An edge whose source or target node is not stored
+///
+/// A graph holding such an edge fails to load, so every query on it fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DanglingEdge {
+    /// Storage name of the graph, e.g. `app_social` for `/app/social`
+    pub graph: String,
+    pub edge_id: String,
+    pub label: String,
+    pub from_node: String,
+    pub to_node: String,
+    /// Whether the source node is missing (otherwise only the target is)
+    pub from_missing: bool,
+    /// Whether the target node is missing
+    pub to_missing: bool,
+    /// Whether the edge was deleted by a repair
+    pub removed: bool,
+}
+
+impl std::fmt::Display for DanglingEdge {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let missing = match (self.from_missing, self.to_missing) {
+            (true, true) => format!("nodes '{}' and '{}'", self.from_node, self.to_node),
+            (true, false) => format!("node '{}'", self.from_node),
+            _ => format!("node '{}'", self.to_node),
+        };
+        write!(
+            f,
+            "Edge '{}' (:{}) in graph '{}' references missing {}",
+            self.edge_id, self.label, self.graph, missing
+        )?;
+        if self.removed {
+            write!(f, " (removed)")?;
+        }
+        Ok(())
+    }
+}
+
+/// Outcome of checking that every stored edge references stored nodes
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReferenceCheckReport {
+    /// Graphs examined
+    pub graphs: usize,
+    /// Node records read
+    pub nodes: u64,
+    /// Edge records read
+    pub edges: u64,
+    /// Graphs holding at least one node or edge, by storage name
+    pub stored_graphs: Vec<String>,
+    /// Edges referencing missing nodes, in graph order
+    pub dangling_edges: Vec<DanglingEdge>,
+}
+
+impl ReferenceCheckReport {
+    /// Whether every dangling edge found was removed
+    pub fn is_ok(&self) -> bool {
+        self.dangling_edges.iter().all(|edge| edge.removed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sealed_record_detects_corruption() {
+        let sealed = seal_record(b"n1", b"payload".to_vec());
+        assert_eq!(open_record("nodes_g", b"n1", &sealed).unwrap(), b"payload");
+
+        // Same bytes under another key
+        assert!(open_record("nodes_g", b"n2", &sealed).is_err());
+
+        let mut flipped = sealed.clone();
+        flipped[2] ^= 0x10;
+        let before = corrupt_records_detected();
+        let error = open_record("nodes_g", b"n1", &flipped).unwrap_err();
+        assert_eq!(error.tree, "nodes_g");
+        assert_eq!(error.key, "n1");
+        assert!(error.to_string().contains("checksum mismatch"), "{}", error);
+        assert!(corrupt_records_detected() > before);
+
+        assert!(open_record("nodes_g", b"n1", b"ab").is_err());
+    }
+}
@@ -0,0 +1,218 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! JSONPath access into `Value::Json` documents
+//!
+//! Supports the single-value subset of JSONPath used by `JSON_GET`,
+//! `JSON_EXISTS` and path indexes:
+//! - `$` for the whole document
+//! - `.name` and `['name']` / `["name"]` for object members
+//! - `[n]` for array elements, negative `n` counting from the end
+//!
+//! Wildcards, slices and filters are not supported, so a path selects at most
+//! one value.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::storage::Value;
+
+/// One step of a path
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+enum Step {
+    Member(String),
+    Element(i64),
+}
+
+/// Parsed JSONPath expression
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct JsonPath {
+    steps: Vec<Step>,
+}
+
+impl JsonPath {
+    /// Parse a path such as `$.address.city` or `$.tags[0]`
+    pub fn parse(path: &str) -> Result<Self, String> {
+        let chars: Vec<char> = path.trim().chars().collect();
+        if chars.first() != Some(&'$') {
+            return Err(format!("JSON path '{}' must start with '$'", path));
+        }
+
+        let mut steps = Vec::new();
+        let mut pos = 1;
+        while pos < chars.len() {
+            match chars[pos] {
+                '.' => {
+                    let start = pos + 1;
+                    let mut end = start;
+                    while end < chars.len() && (chars[end].is_alphanumeric() || chars[end] == '_') {
+                        end += 1;
+                    }
+                    if end == start {
+                        return Err(format!(
+                            "JSON path '{}' has an empty member name at position {}",
+                            path, start
+                        ));
+                    }
+                    steps.push(Step::Member(chars[start..end].iter().collect()));
+                    pos = end;
+                }
+                '[' => {
+                    let close = chars[pos..]
+                        .iter()
+                        .position(|&c| c == ']')
+                        .map(|offset| pos + offset)
+                        .ok_or_else(|| format!("JSON path '{}' has an unclosed '['", path))?;
+                    let inner: String = chars[pos + 1..close].iter().collect();
+                    let inner = inner.trim();
+                    let quoted = inner.len() >= 2
+                        && ((inner.starts_with('\'') && inner.ends_with('\''))
+                            || (inner.starts_with('"') && inner.ends_with('"')));
+                    if quoted {
+                        steps.push(Step::Member(inner[1..inner.len() - 1].to_string()));
+                    } else {
+                        let index = inner.parse::<i64>().map_err(|_| {
+                            format!(
+                                "JSON path '{}' has an invalid subscript '[{}]'; expected an index or a quoted name",
+                                path, inner
+                            )
+                        })?;
+                        steps.push(Step::Element(index));
+                    }
+                    pos = close + 1;
+                }
+                other => {
+                    return Err(format!(
+                        "JSON path '{}' has an unexpected '{}' at position {}",
+                        path, other, pos
+                    ))
+                }
+            }
+        }
+
+        Ok(Self { steps })
+    }
+
+    /// The value this path selects, if the document has one there
+    pub fn select<'a>(&self, document: &'a serde_json::Value) -> Option<&'a serde_json::Value> {
+        self.steps
+            .iter()
+            .try_fold(document, |current, step| match (step, current) {
+                (Step::Member(name), serde_json::Value::Object(members)) => members.get(name),
+                (Step::Element(index), serde_json::Value::Array(items)) => {
+                    let index = if *index < 0 {
+                        items.len().checked_sub(index.unsigned_abs() as usize)?
+                    } else {
+                        *index as usize
+                    };
+                    items.get(index)
+                }
+                _ => None,
+            })
+    }
+}
+
+impl fmt::Display for JsonPath {
+    /// Canonical form: `.name` where possible, otherwise `['name']`
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "$")?;
+        for step in &self.steps {
+            match step {
+                Step::Member(name)
+                    if !name.is_empty()
+                        && name.chars().all(|c| c.is_alphanumeric() || c == '_') =>
+                {
+                    write!(f, ".{}", name)?
+                }
+                Step::Member(name) => write!(f, "['{}']", name)?,
+                Step::Element(index) => write!(f, "[{}]", index)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// GQL value for a piece of a JSON document
+///
+/// Scalars become the matching GQL value; arrays and objects stay documents so
+/// they can be navigated further.
+pub fn json_to_value(document: &serde_json::Value) -> Value {
+    match document {
+        serde_json::Value::Null => Value::Null,
+        serde_json::Value::Bool(b) => Value::Boolean(*b),
+        serde_json::Value::Number(n) => n
+            .as_f64()
+            .map(Value::Number)
+            .unwrap_or_else(|| Value::Json(document.clone())),
+        serde_json::Value::String(s) => Value::String(s.clone()),
+        serde_json::Value::Array(_) | serde_json::Value::Object(_) => Value::Json(document.clone()),
+    }
+}
+
+/// JSON document for a GQL value, `None` for values with no JSON form
+pub fn value_to_json(value: &Value) -> Option<serde_json::Value> {
+    match value {
+        Value::Null => Some(serde_json::Value::Null),
+        Value::Boolean(b) => Some(serde_json::Value::Bool(*b)),
+        Value::Number(n) => serde_json::Number::from_f64(*n).map(serde_json::Value::Number),
+        Value::String(s) => Some(serde_json::Value::String(s.clone())),
+        Value::Json(document) => Some(document.clone()),
+        Value::List(items) | Value::Array(items) => items
+            .iter()
+            .map(value_to_json)
+            .collect::<Option<Vec<_>>>()
+            .map(serde_json::Value::Array),
+        Value::Vector(items) => items
+            .iter()
+            .map(|&n| serde_json::Number::from_f64(n as f64).map(serde_json::Value::Number))
+            .collect::<Option<Vec<_>>>()
+            .map(serde_json::Value::Array),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_select_members_and_elements() {
+        let document = json!({"a": {"b": [10, 20, 30]}, "odd key": true});
+
+        let select = |path: &str| JsonPath::parse(path).unwrap().select(&document).cloned();
+        assert_eq!(select("$"), Some(document.clone()));
+        assert_eq!(select("$.a.b[1]"), Some(json!(20)));
+        assert_eq!(select("$.a.b[-1]"), Some(json!(30)));
+        assert_eq!(select("$['odd key']"), Some(json!(true)));
+        assert_eq!(select("$[\"a\"].b[0]"), Some(json!(10)));
+        assert_eq!(select("$.a.c"), None);
+        assert_eq!(select("$.a.b[3]"), None);
+        assert_eq!(select("$.a.b[-4]"), None);
+        assert_eq!(select("$.a.b.c"), None);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(JsonPath::parse("a.b").is_err());
+        assert!(JsonPath::parse("$.").is_err());
+        assert!(JsonPath::parse("$.a[").is_err());
+        assert!(JsonPath::parse("$.a[*]").is_err());
+        assert!(JsonPath::parse("$a").is_err());
+    }
+
+    #[test]
+    fn test_canonical_form() {
+        let path = JsonPath::parse("$['a'][\"b c\"][2]").unwrap();
+        assert_eq!(path.to_string(), "$.a['b c'][2]");
+        assert_eq!(JsonPath::parse(&path.to_string()).unwrap(), path);
+    }
+
+    #[test]
+    fn test_json_to_value() {
+        assert_eq!(json_to_value(&json!(1.5)), Value::Number(1.5));
+        assert_eq!(json_to_value(&json!("x")), Value::String("x".to_string()));
+        assert_eq!(json_to_value(&json!(null)), Value::Null);
+        assert_eq!(json_to_value(&json!([1])), Value::Json(json!([1])));
+    }
+}
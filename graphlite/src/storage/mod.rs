@@ -5,17 +5,25 @@
 //!
 //! This module provides:
 //! - Value type system for graph properties
+//! - JSONPath access into JSON document values
 //! - In-memory graph storage with adjacency lists
 //! - Efficient indexing for nodes and edges by label
 //! - Graph operations (add, get, find)
-//! - Sample fraud data generation
+//! - Sample data generation (fraud, social, pharma, supply chain presets)
 //! - Pluggable storage backend trait for different KV stores
+//! - Online backup and restore
+//! - Transactional per-graph event outboxes
 
+pub mod backup;
 mod data_adapter;
 pub mod graph_cache;
 pub mod indexes;
+pub mod integrity;
+pub mod json_path;
 pub mod multi_graph;
+pub mod outbox;
 mod persistent;
+pub mod sample_data;
 pub mod storage_manager;
 pub mod type_mapping;
 pub mod types;
@@ -28,6 +36,10 @@ pub use value::{TimeWindow, Value};
 pub use persistent::StorageType;
 // Public exports for examples and tests
 pub use persistent::{StorageDriver, StorageTree};
+// Scratch stores outside the graph storage (transaction undo spill)
+pub(crate) use persistent::create_storage_driver;
+// Tree naming of stored graphs, for matching them to catalog entries
+pub(crate) use data_adapter::DataAdapter;
 // Public interface - only StorageManager should be used externally
 pub use storage_manager::{StorageManager, StorageMethod};
 // Index system (stub)
@@ -15,11 +15,130 @@
 //! - FROM CURRENT_PROPERTY_GRAPH - Session-context graph references
 //! - SET SESSION GRAPH = graph_name - Session graph switching
 
-use crate::storage::{GraphCache, StorageError};
+use crate::storage::types::GraphError;
+use crate::storage::{Edge, GraphCache, Node, StorageError};
 use log::{debug, warn};
 use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
 use std::sync::{Arc, RwLock};
 
+/// How a graph UNION resolves a node or edge id that appears in more than one
+/// operand with differing labels or properties.
+///
+/// Only consulted for UNION (distinct); UNION ALL keeps both copies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UnionConflictPolicy {
+    /// Keep the element from the left-most graph that defines it (default)
+    #[default]
+    KeepFirst,
+    /// Keep the element from the right-most graph that defines it
+    KeepLast,
+    /// Union the labels and properties; the left-most value wins on key clashes
+    Merge,
+    /// Fail the union with an error
+    Error,
+}
+
+impl UnionConflictPolicy {
+    /// Session parameter that selects the policy (see `UserSession::get_union_conflict_policy`)
+    pub const SESSION_PARAMETER: &'static str = "graph_union_conflict_policy";
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UnionConflictPolicy::KeepFirst => "keep_first",
+            UnionConflictPolicy::KeepLast => "keep_last",
+            UnionConflictPolicy::Merge => "merge",
+            UnionConflictPolicy::Error => "error",
+        }
+    }
+
+    fn resolve_node(
+        &self,
+        existing: &Node,
+        incoming: &Node,
+        graph_name: &str,
+    ) -> Result<Node, StorageError> {
+        match self {
+            UnionConflictPolicy::KeepFirst => Ok(existing.clone()),
+            UnionConflictPolicy::KeepLast => Ok(incoming.clone()),
+            UnionConflictPolicy::Merge => {
+                let mut merged = existing.clone();
+                for label in &incoming.labels {
+                    merged.add_label(label.clone());
+                }
+                for (key, value) in &incoming.properties {
+                    merged
+                        .properties
+                        .entry(key.clone())
+                        .or_insert_with(|| value.clone());
+                }
+                Ok(merged)
+            }
+            UnionConflictPolicy::Error => Err(StorageError::InvalidOperation(format!(
+                "Graph union conflict: node '{}' from graph '{}' differs from an earlier definition",
+                incoming.id, graph_name
+            ))),
+        }
+    }
+
+    fn resolve_edge(
+        &self,
+        existing: &Edge,
+        incoming: &Edge,
+        graph_name: &str,
+    ) -> Result<Edge, StorageError> {
+        let same_shape = existing.from_node == incoming.from_node
+            && existing.to_node == incoming.to_node
+            && existing.label == incoming.label;
+
+        match self {
+            UnionConflictPolicy::KeepFirst => Ok(existing.clone()),
+            UnionConflictPolicy::KeepLast => Ok(incoming.clone()),
+            // Endpoints and label cannot be merged; only properties can
+            UnionConflictPolicy::Merge if same_shape => {
+                let mut merged = existing.clone();
+                for (key, value) in &incoming.properties {
+                    merged
+                        .properties
+                        .entry(key.clone())
+                        .or_insert_with(|| value.clone());
+                }
+                Ok(merged)
+            }
+            UnionConflictPolicy::Merge | UnionConflictPolicy::Error => {
+                Err(StorageError::InvalidOperation(format!(
+                    "Graph union conflict: edge '{}' from graph '{}' differs from an earlier definition",
+                    incoming.id, graph_name
+                )))
+            }
+        }
+    }
+}
+
+impl fmt::Display for UnionConflictPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for UnionConflictPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "keep_first" | "first" | "left" => Ok(UnionConflictPolicy::KeepFirst),
+            "keep_last" | "last" | "right" => Ok(UnionConflictPolicy::KeepLast),
+            "merge" => Ok(UnionConflictPolicy::Merge),
+            "error" | "fail" => Ok(UnionConflictPolicy::Error),
+            other => Err(format!(
+                "Unknown graph union conflict policy '{}'. Expected one of: keep_first, keep_last, merge, error",
+                other
+            )),
+        }
+    }
+}
+
 /// Multi-graph storage manager that handles multiple named graphs
 #[derive(Debug, Clone)]
 pub struct MultiGraphManager {
@@ -117,11 +236,27 @@ impl MultiGraphManager {
     }
 
     /// Perform a graph union operation
+    ///
+    /// Operands are `(name, graph)` pairs; the name is only used to qualify
+    /// colliding identifiers under UNION ALL and in conflict error messages.
+    ///
+    /// - `all == false` (UNION): nodes and edges are deduplicated by id. When the
+    ///   same id carries differing labels/properties, `policy` decides the outcome.
+    /// - `all == true` (UNION ALL): every node and edge is kept. Later operands whose
+    ///   ids collide with an earlier one are re-keyed as `<graph>::<id>` and their
+    ///   edges are rewired to the re-keyed endpoints.
     pub fn union_graphs(
         &self,
-        graphs_to_union: Vec<GraphCache>,
+        graphs_to_union: Vec<(String, GraphCache)>,
+        all: bool,
+        policy: UnionConflictPolicy,
     ) -> Result<GraphCache, StorageError> {
-        debug!("Performing graph union on {} graphs", graphs_to_union.len());
+        debug!(
+            "Performing graph union{} on {} graphs (conflict policy: {})",
+            if all { " ALL" } else { "" },
+            graphs_to_union.len(),
+            policy
+        );
 
         if graphs_to_union.is_empty() {
             return Err(StorageError::InvalidOperation(
@@ -129,38 +264,134 @@ impl MultiGraphManager {
             ));
         }
 
-        // Start with the first graph
-        let mut result_graph = graphs_to_union[0].clone();
+        let result_graph = if all {
+            Self::union_all(&graphs_to_union)?
+        } else {
+            Self::union_distinct(&graphs_to_union, policy)?
+        };
+
+        debug!("Successfully created union graph");
+        Ok(result_graph)
+    }
+
+    /// UNION ALL: keep every element, re-keying ids that collide with earlier operands
+    fn union_all(graphs: &[(String, GraphCache)]) -> Result<GraphCache, StorageError> {
+        let mut result_graph = GraphCache::new();
+
+        for (graph_name, graph) in graphs {
+            let mut renamed_nodes: HashMap<String, String> = HashMap::new();
 
-        // Union with remaining graphs
-        for graph in graphs_to_union.iter().skip(1) {
-            // Manually union graphs by adding all nodes and edges
             for node in graph.get_all_nodes() {
-                let has_node = result_graph
-                    .has_node(&node.id)
-                    .map_err(StorageError::Graph)?;
-                if !has_node {
-                    result_graph
-                        .add_node(node.clone())
-                        .map_err(StorageError::Graph)?;
+                let mut node = node.clone();
+                if result_graph.contains_node(&node.id) {
+                    let qualified = Self::qualify_id(&result_graph, graph_name, &node.id, true);
+                    renamed_nodes.insert(node.id.clone(), qualified.clone());
+                    node.id = qualified;
                 }
+                result_graph.add_node(node).map_err(StorageError::Graph)?;
             }
+
             for edge in graph.get_all_edges() {
-                let has_edge = result_graph
-                    .has_edge(&edge.id)
-                    .map_err(StorageError::Graph)?;
-                if !has_edge {
-                    result_graph
-                        .add_edge(edge.clone())
-                        .map_err(StorageError::Graph)?;
+                let mut edge = edge.clone();
+                if let Some(from) = renamed_nodes.get(&edge.from_node) {
+                    edge.from_node = from.clone();
+                }
+                if let Some(to) = renamed_nodes.get(&edge.to_node) {
+                    edge.to_node = to.clone();
+                }
+                if result_graph.contains_edge(&edge.id) {
+                    edge.id = Self::qualify_id(&result_graph, graph_name, &edge.id, false);
+                }
+                result_graph.add_edge(edge).map_err(StorageError::Graph)?;
+            }
+        }
+
+        Ok(result_graph)
+    }
+
+    /// UNION: deduplicate by id, resolving differing duplicates with `policy`
+    fn union_distinct(
+        graphs: &[(String, GraphCache)],
+        policy: UnionConflictPolicy,
+    ) -> Result<GraphCache, StorageError> {
+        // Resolve nodes and edges first so the result graph's indices are built once
+        // from the final labels/endpoints rather than patched after the fact.
+        let mut node_order: Vec<String> = Vec::new();
+        let mut nodes: HashMap<String, Node> = HashMap::new();
+        let mut edge_order: Vec<String> = Vec::new();
+        let mut edges: HashMap<String, Edge> = HashMap::new();
+
+        for (graph_name, graph) in graphs {
+            for node in graph.get_all_nodes() {
+                match nodes.get_mut(&node.id) {
+                    Some(existing) if existing != node => {
+                        *existing = policy.resolve_node(existing, node, graph_name)?;
+                    }
+                    Some(_) => {}
+                    None => {
+                        node_order.push(node.id.clone());
+                        nodes.insert(node.id.clone(), node.clone());
+                    }
+                }
+            }
+
+            for edge in graph.get_all_edges() {
+                match edges.get_mut(&edge.id) {
+                    Some(existing) if existing != edge => {
+                        *existing = policy.resolve_edge(existing, edge, graph_name)?;
+                    }
+                    Some(_) => {}
+                    None => {
+                        edge_order.push(edge.id.clone());
+                        edges.insert(edge.id.clone(), edge.clone());
+                    }
+                }
+            }
+        }
+
+        let mut result_graph = GraphCache::new();
+        for id in node_order {
+            if let Some(node) = nodes.remove(&id) {
+                result_graph.add_node(node).map_err(StorageError::Graph)?;
+            }
+        }
+        for id in edge_order {
+            if let Some(edge) = edges.remove(&id) {
+                match result_graph.add_edge(edge) {
+                    Ok(()) => {}
+                    // Same endpoints, label and properties under a different id:
+                    // the relationship is already present, which is what UNION wants.
+                    Err(GraphError::EdgeAlreadyExists(msg)) => {
+                        debug!("Skipping duplicate relationship in union: {}", msg);
+                    }
+                    Err(e) => return Err(StorageError::Graph(e)),
                 }
             }
         }
 
-        debug!("Successfully created union graph");
         Ok(result_graph)
     }
 
+    /// Build a `<graph>::<id>` identifier that is not yet used in `graph`
+    fn qualify_id(graph: &GraphCache, graph_name: &str, id: &str, is_node: bool) -> String {
+        let taken = |candidate: &str| {
+            if is_node {
+                graph.contains_node(candidate)
+            } else {
+                graph.contains_edge(candidate)
+            }
+        };
+
+        let base = format!("{}::{}", graph_name, id);
+        let mut candidate = base.clone();
+        let mut suffix = 1;
+        while taken(&candidate) {
+            suffix += 1;
+            candidate = format!("{}#{}", base, suffix);
+        }
+        candidate
+    }
+
     /// Clear all graphs
     pub fn clear(&self) -> Result<(), StorageError> {
         debug!("Clearing all graphs");
@@ -194,6 +425,7 @@ impl Default for MultiGraphManager {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::storage::Value;
 
     #[test]
     fn test_multi_graph_basic_operations() {
@@ -219,4 +451,132 @@ mod tests {
         manager.remove_graph("test_graph").unwrap();
         assert!(!manager.has_graph("test_graph"));
     }
+
+    fn person(id: &str, name: &str) -> Node {
+        let mut node = Node::with_labels(id.to_string(), vec!["Person".to_string()]);
+        node.set_property("name".to_string(), Value::String(name.to_string()));
+        node
+    }
+
+    fn union_operands() -> Vec<(String, GraphCache)> {
+        let mut left = GraphCache::new();
+        left.add_node(person("n1", "Alice")).unwrap();
+        left.add_node(person("n2", "Bob")).unwrap();
+        left.add_edge(Edge::new(
+            "e1".to_string(),
+            "n1".to_string(),
+            "n2".to_string(),
+            "KNOWS".to_string(),
+        ))
+        .unwrap();
+
+        let mut right = GraphCache::new();
+        let mut alice = person("n1", "Alicia");
+        alice.add_label("Employee".to_string());
+        alice.set_property("age".to_string(), Value::Number(30.0));
+        right.add_node(alice).unwrap();
+        right.add_node(person("n3", "Carol")).unwrap();
+        right
+            .add_edge(Edge::new(
+                "e1".to_string(),
+                "n1".to_string(),
+                "n3".to_string(),
+                "KNOWS".to_string(),
+            ))
+            .unwrap();
+
+        vec![("left".to_string(), left), ("right".to_string(), right)]
+    }
+
+    #[test]
+    fn test_union_distinct_keep_first() {
+        let manager = MultiGraphManager::new();
+        let union = manager
+            .union_graphs(union_operands(), false, UnionConflictPolicy::KeepFirst)
+            .unwrap();
+
+        assert_eq!(union.node_count().unwrap(), 3);
+        assert_eq!(union.edge_count().unwrap(), 1);
+        let alice = union.get_node("n1").unwrap();
+        assert_eq!(
+            alice.get_property("name"),
+            Some(&Value::String("Alice".to_string()))
+        );
+        assert_eq!(union.get_edge("e1").unwrap().to_node, "n2");
+    }
+
+    #[test]
+    fn test_union_distinct_keep_last() {
+        let manager = MultiGraphManager::new();
+        let union = manager
+            .union_graphs(union_operands(), false, UnionConflictPolicy::KeepLast)
+            .unwrap();
+
+        let alice = union.get_node("n1").unwrap();
+        assert_eq!(
+            alice.get_property("name"),
+            Some(&Value::String("Alicia".to_string()))
+        );
+        assert_eq!(union.get_nodes_by_label("Employee").len(), 1);
+        assert_eq!(union.get_edge("e1").unwrap().to_node, "n3");
+        assert_eq!(union.get_incoming_edges("n2").len(), 0);
+    }
+
+    #[test]
+    fn test_union_distinct_merge_and_error() {
+        let manager = MultiGraphManager::new();
+
+        // Nodes merge, but the edge `e1` points at different endpoints
+        let err = manager
+            .union_graphs(union_operands(), false, UnionConflictPolicy::Merge)
+            .unwrap_err();
+        assert!(err.to_string().contains("edge 'e1'"));
+
+        let mut operands = union_operands();
+        operands[1].1.remove_edge("e1").unwrap();
+        let union = manager
+            .union_graphs(operands.clone(), false, UnionConflictPolicy::Merge)
+            .unwrap();
+        let alice = union.get_node("n1").unwrap();
+        assert!(alice.has_label("Person") && alice.has_label("Employee"));
+        assert_eq!(
+            alice.get_property("name"),
+            Some(&Value::String("Alice".to_string()))
+        );
+        assert_eq!(alice.get_property("age"), Some(&Value::Number(30.0)));
+
+        let err = manager
+            .union_graphs(operands, false, UnionConflictPolicy::Error)
+            .unwrap_err();
+        assert!(err.to_string().contains("node 'n1'"));
+    }
+
+    #[test]
+    fn test_union_all_keeps_both_copies() {
+        let manager = MultiGraphManager::new();
+        let union = manager
+            .union_graphs(union_operands(), true, UnionConflictPolicy::Error)
+            .unwrap();
+
+        assert_eq!(union.node_count().unwrap(), 4);
+        assert_eq!(union.edge_count().unwrap(), 2);
+        assert!(union.contains_node("right::n1"));
+        let rekeyed_edge = union.get_edge("right::e1").unwrap();
+        assert_eq!(rekeyed_edge.from_node, "right::n1");
+        assert_eq!(rekeyed_edge.to_node, "n3");
+    }
+
+    #[test]
+    fn test_union_conflict_policy_parsing() {
+        assert_eq!(
+            "MERGE".parse::<UnionConflictPolicy>().unwrap(),
+            UnionConflictPolicy::Merge
+        );
+        assert_eq!(
+            "keep_last".parse::<UnionConflictPolicy>().unwrap(),
+            UnionConflictPolicy::KeepLast
+        );
+        assert!("newest".parse::<UnionConflictPolicy>().is_err());
+        assert_eq!(UnionConflictPolicy::default().as_str(), "keep_first");
+    }
 }
@@ -0,0 +1,386 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! Transactional outbox of graph events
+//!
+//! Applications append domain events to a graph's outbox with
+//! `CALL gql.outbox_append(topic, payload)`. Inside a transaction the session
+//! holds the events until COMMIT writes them, so they become visible together
+//! with the transaction's changes and are dropped by ROLLBACK (or by ROLLBACK TO
+//! SAVEPOINT for events appended after the savepoint). Outside a transaction an
+//! event is written at once. Events get consecutive offsets in commit order.
+//!
+//! Consumers read with `gql.outbox_poll(consumer)`, which returns the events
+//! after the consumer's acknowledged offset, and record their progress with
+//! `gql.outbox_ack(consumer, offset)`. A consumer that stops between publishing
+//! a batch and acknowledging it is handed the batch again, so publishing with
+//! the offset as a deduplication key gives exactly-once delivery. A consumer is
+//! known from its first acknowledgement (`ack` with offset 0 registers it);
+//! events every known consumer has acknowledged are removed.
+//!
+//! Each graph's outbox is the tree `outbox_<graph>`, holding
+//! - `e<offset>`: an event, JSON encoded, keyed by its big-endian offset
+//! - `next`: the offset the next event gets
+//! - `c<consumer>`: a consumer's acknowledged offset
+//!
+//! `next` is written after the events of a commit, and polls only return
+//! events below it, so events a crash left without their `next` update stay
+//! invisible and are overwritten by the next commit.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+
+use super::data_adapter::DataAdapter;
+use super::persistent::{StorageDriver, StorageTree};
+use super::types::StorageError;
+
+const EVENT_PREFIX: &[u8] = b"e";
+const CONSUMER_PREFIX: &[u8] = b"c";
+const NEXT_KEY: &[u8] = b"next";
+
+/// An event appended in a transaction that has not committed yet
+#[derive(Debug, Clone, PartialEq)]
+pub struct PendingEvent {
+    /// Graph whose outbox receives the event
+    pub graph_path: String,
+    pub topic: String,
+    pub payload: serde_json::Value,
+}
+
+/// A committed outbox event
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OutboxEvent {
+    /// Position in the graph's outbox, starting at 1
+    pub offset: u64,
+    pub topic: String,
+    pub payload: serde_json::Value,
+    /// When the event was committed
+    pub created_at: DateTime<Utc>,
+    /// Transaction that appended the event (`None` outside a transaction)
+    pub transaction_id: Option<u64>,
+}
+
+/// A consumer's position after `Outbox::ack`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutboxAck {
+    /// Highest offset the consumer has acknowledged
+    pub acked_offset: u64,
+    /// Committed events after `acked_offset`
+    pub pending: u64,
+}
+
+/// Per-graph event outboxes in the database's storage driver
+#[derive(Debug, Default)]
+pub struct Outbox {
+    /// Serializes offset assignment and trimming
+    write_lock: Mutex<()>,
+}
+
+impl Outbox {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Write committed events, returning their offsets in the order given
+    pub fn append(
+        &self,
+        driver: &dyn StorageDriver<Tree = Box<dyn StorageTree>>,
+        events: Vec<PendingEvent>,
+        transaction_id: Option<u64>,
+    ) -> Result<Vec<u64>, StorageError> {
+        let _guard = self.write_lock.lock().unwrap_or_else(|e| e.into_inner());
+        let created_at = Utc::now();
+
+        let mut by_graph: Vec<(String, Vec<(usize, PendingEvent)>)> = Vec::new();
+        let total = events.len();
+        for (position, event) in events.into_iter().enumerate() {
+            match by_graph
+                .iter_mut()
+                .find(|(graph, _)| *graph == event.graph_path)
+            {
+                Some((_, graph_events)) => graph_events.push((position, event)),
+                None => by_graph.push((event.graph_path.clone(), vec![(position, event)])),
+            }
+        }
+
+        let mut offsets = vec![0; total];
+        for (graph_path, graph_events) in by_graph {
+            let tree = open_tree(driver, &graph_path)?;
+            let mut next = next_offset(tree.as_ref())?;
+            let mut records = Vec::with_capacity(graph_events.len());
+            for (position, event) in graph_events {
+                let record = OutboxEvent {
+                    offset: next,
+                    topic: event.topic,
+                    payload: event.payload,
+                    created_at,
+                    transaction_id,
+                };
+                let data = serde_json::to_vec(&record).map_err(|e| {
+                    StorageError::PersistenceError(format!("Failed to encode outbox event: {}", e))
+                })?;
+                records.push((event_key(next), data));
+                offsets[position] = next;
+                next += 1;
+            }
+
+            let entries: Vec<(&[u8], &[u8])> = records
+                .iter()
+                .map(|(key, data)| (key.as_slice(), data.as_slice()))
+                .collect();
+            tree.batch_insert(&entries).map_err(persistence_error)?;
+            tree.insert(NEXT_KEY, &next.to_be_bytes())
+                .map_err(persistence_error)?;
+            tree.flush().map_err(persistence_error)?;
+        }
+        Ok(offsets)
+    }
+
+    /// Up to `limit` committed events after the consumer's acknowledged offset, oldest first
+    pub fn poll(
+        &self,
+        driver: &dyn StorageDriver<Tree = Box<dyn StorageTree>>,
+        graph_path: &str,
+        consumer: &str,
+        limit: usize,
+    ) -> Result<Vec<OutboxEvent>, StorageError> {
+        let tree = open_tree(driver, graph_path)?;
+        let acked = acked_offset(tree.as_ref(), consumer)?;
+        let next = next_offset(tree.as_ref())?;
+
+        let mut records = Vec::new();
+        for entry in tree.scan_prefix(EVENT_PREFIX).map_err(persistence_error)? {
+            let (key, data) = entry.map_err(persistence_error)?;
+            if let Some(offset) = key_offset(&key) {
+                if offset > acked && offset < next {
+                    records.push((offset, data));
+                }
+            }
+        }
+        records.sort_unstable_by_key(|(offset, _)| *offset);
+        records.truncate(limit);
+
+        records
+            .into_iter()
+            .map(|(offset, data)| {
+                serde_json::from_slice(&data).map_err(|e| {
+                    StorageError::PersistenceError(format!(
+                        "Corrupt outbox event {} of graph {}: {}",
+                        offset, graph_path, e
+                    ))
+                })
+            })
+            .collect()
+    }
+
+    /// Record that the consumer has processed every event up to `offset`
+    ///
+    /// Acknowledging an offset at or below the current one changes nothing, so
+    /// a repeated acknowledgement is harmless. Events every consumer has
+    /// acknowledged are removed.
+    pub fn ack(
+        &self,
+        driver: &dyn StorageDriver<Tree = Box<dyn StorageTree>>,
+        graph_path: &str,
+        consumer: &str,
+        offset: u64,
+    ) -> Result<OutboxAck, StorageError> {
+        let _guard = self.write_lock.lock().unwrap_or_else(|e| e.into_inner());
+        let tree = open_tree(driver, graph_path)?;
+        let next = next_offset(tree.as_ref())?;
+        if offset >= next {
+            return Err(StorageError::InvalidOperation(format!(
+                "Cannot acknowledge offset {}: the outbox of {} ends at offset {}",
+                offset,
+                graph_path,
+                next - 1
+            )));
+        }
+
+        let acked = acked_offset(tree.as_ref(), consumer)?.max(offset);
+        tree.insert(&consumer_key(consumer), &acked.to_be_bytes())
+            .map_err(persistence_error)?;
+
+        // Remove the events that no consumer still needs
+        let mut trim_below = acked;
+        for entry in tree
+            .scan_prefix(CONSUMER_PREFIX)
+            .map_err(persistence_error)?
+        {
+            let (_, data) = entry.map_err(persistence_error)?;
+            trim_below = trim_below.min(decode_offset(&data)?);
+        }
+        let mut trimmed = Vec::new();
+        let mut pending = 0;
+        for entry in tree.scan_prefix(EVENT_PREFIX).map_err(persistence_error)? {
+            let (key, _) = entry.map_err(persistence_error)?;
+            match key_offset(&key) {
+                Some(event) if event <= trim_below => trimmed.push(key),
+                Some(event) if event > acked && event < next => pending += 1,
+                _ => {}
+            }
+        }
+        let keys: Vec<&[u8]> = trimmed.iter().map(Vec::as_slice).collect();
+        tree.batch_remove(&keys).map_err(persistence_error)?;
+        tree.flush().map_err(persistence_error)?;
+
+        Ok(OutboxAck {
+            acked_offset: acked,
+            pending,
+        })
+    }
+
+    /// Remove the outbox of a dropped graph
+    pub fn delete_graph(
+        &self,
+        driver: &dyn StorageDriver<Tree = Box<dyn StorageTree>>,
+        graph_path: &str,
+    ) -> Result<(), StorageError> {
+        let _guard = self.write_lock.lock().unwrap_or_else(|e| e.into_inner());
+        open_tree(driver, graph_path)?
+            .clear()
+            .map_err(persistence_error)
+    }
+}
+
+fn open_tree(
+    driver: &dyn StorageDriver<Tree = Box<dyn StorageTree>>,
+    graph_path: &str,
+) -> Result<Box<dyn StorageTree>, StorageError> {
+    driver
+        .open_tree(&format!(
+            "outbox_{}",
+            DataAdapter::normalize_graph_path(graph_path)
+        ))
+        .map_err(persistence_error)
+}
+
+fn next_offset(tree: &dyn StorageTree) -> Result<u64, StorageError> {
+    match tree.get(NEXT_KEY).map_err(persistence_error)? {
+        Some(data) => decode_offset(&data),
+        None => Ok(1),
+    }
+}
+
+fn acked_offset(tree: &dyn StorageTree, consumer: &str) -> Result<u64, StorageError> {
+    match tree
+        .get(&consumer_key(consumer))
+        .map_err(persistence_error)?
+    {
+        Some(data) => decode_offset(&data),
+        None => Ok(0),
+    }
+}
+
+fn event_key(offset: u64) -> Vec<u8> {
+    [EVENT_PREFIX, &offset.to_be_bytes()].concat()
+}
+
+fn consumer_key(consumer: &str) -> Vec<u8> {
+    [CONSUMER_PREFIX, consumer.as_bytes()].concat()
+}
+
+fn key_offset(key: &[u8]) -> Option<u64> {
+    let bytes = key.strip_prefix(EVENT_PREFIX)?;
+    Some(u64::from_be_bytes(bytes.try_into().ok()?))
+}
+
+fn decode_offset(data: &[u8]) -> Result<u64, StorageError> {
+    data.try_into()
+        .map(u64::from_be_bytes)
+        .map_err(|_| StorageError::PersistenceError("Corrupt outbox offset record".to_string()))
+}
+
+fn persistence_error(e: impl std::fmt::Display) -> StorageError {
+    StorageError::PersistenceError(format!("Outbox storage error: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{create_storage_driver, StorageType};
+    use serde_json::json;
+
+    fn event(graph: &str, topic: &str) -> PendingEvent {
+        PendingEvent {
+            graph_path: graph.to_string(),
+            topic: topic.to_string(),
+            payload: json!({"topic": topic}),
+        }
+    }
+
+    #[test]
+    fn test_offsets_are_per_graph_and_consecutive() {
+        let dir = tempfile::tempdir().unwrap();
+        let driver = create_storage_driver(StorageType::Sled, dir.path()).unwrap();
+        let outbox = Outbox::new();
+
+        let offsets = outbox
+            .append(
+                driver.as_ref(),
+                vec![
+                    event("/a/g", "one"),
+                    event("/a/h", "x"),
+                    event("/a/g", "two"),
+                ],
+                Some(7),
+            )
+            .unwrap();
+        assert_eq!(offsets, vec![1, 1, 2]);
+        let offsets = outbox
+            .append(driver.as_ref(), vec![event("/a/g", "three")], None)
+            .unwrap();
+        assert_eq!(offsets, vec![3]);
+
+        let events = outbox.poll(driver.as_ref(), "/a/g", "mq", 10).unwrap();
+        let topics: Vec<_> = events.iter().map(|e| e.topic.as_str()).collect();
+        assert_eq!(topics, vec!["one", "two", "three"]);
+        assert_eq!(events[0].transaction_id, Some(7));
+        assert_eq!(events[2].transaction_id, None);
+        assert_eq!(events[1].payload, json!({"topic": "two"}));
+        assert_eq!(
+            outbox.poll(driver.as_ref(), "/a/g", "mq", 2).unwrap().len(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_ack_advances_and_trims_for_all_consumers() {
+        let dir = tempfile::tempdir().unwrap();
+        let driver = create_storage_driver(StorageType::Sled, dir.path()).unwrap();
+        let outbox = Outbox::new();
+        let events = (0..4).map(|i| event("/a/g", &format!("e{}", i))).collect();
+        outbox.append(driver.as_ref(), events, None).unwrap();
+
+        outbox.ack(driver.as_ref(), "/a/g", "audit", 0).unwrap();
+        let ack = outbox.ack(driver.as_ref(), "/a/g", "mq", 3).unwrap();
+        assert_eq!(
+            ack,
+            OutboxAck {
+                acked_offset: 3,
+                pending: 1
+            }
+        );
+        // A stale acknowledgement does not move the consumer back
+        let ack = outbox.ack(driver.as_ref(), "/a/g", "mq", 1).unwrap();
+        assert_eq!(ack.acked_offset, 3);
+
+        let offsets = |consumer| {
+            outbox
+                .poll(driver.as_ref(), "/a/g", consumer, 10)
+                .unwrap()
+                .iter()
+                .map(|e| e.offset)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(offsets("mq"), vec![4]);
+        // Nothing is trimmed while "audit" has not caught up
+        assert_eq!(offsets("audit"), vec![1, 2, 3, 4]);
+
+        outbox.ack(driver.as_ref(), "/a/g", "audit", 2).unwrap();
+        assert_eq!(offsets("late"), vec![3, 4]);
+
+        assert!(outbox.ack(driver.as_ref(), "/a/g", "mq", 5).is_err());
+    }
+}
@@ -58,4 +58,20 @@ mod tests {
         let driver = create_storage_driver(StorageType::Sled, temp_dir.path()).unwrap();
         assert_eq!(driver.storage_type(), StorageType::Sled);
     }
+
+    #[test]
+    fn test_open_sled_driver_waits_for_lock_release() {
+        use crate::storage::persistent::sled::SledDriver;
+
+        let temp_dir = TempDir::new().unwrap();
+        let driver = SledDriver::open(temp_dir.path()).unwrap();
+        let closer = std::thread::spawn(move || {
+            std::thread::sleep(std::time::Duration::from_millis(200));
+            drop(driver);
+        });
+
+        let driver = create_storage_driver(StorageType::Sled, temp_dir.path()).unwrap();
+        assert_eq!(driver.storage_type(), StorageType::Sled);
+        closer.join().unwrap();
+    }
 }
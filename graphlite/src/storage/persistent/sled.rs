@@ -5,7 +5,24 @@
 
 use super::traits::{IndexTreeOptions, StorageDriver, StorageTree, TreeStatistics};
 use super::types::{StorageDriverError, StorageResult, StorageType};
+use fs2::FileExt;
+use std::fs::File;
 use std::path::Path;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How long `open` waits for another handle to release the lock
+///
+/// Sled releases its file lock from background threads shortly after the last
+/// handle to a database is dropped, so reopening a database that was just closed
+/// (or just written by a backup) can briefly find it still locked.
+const LOCK_RELEASE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Pause between checks of the lock
+const LOCK_RETRY_INTERVAL: Duration = Duration::from_millis(10);
+
+/// File of a database directory that Sled locks while the database is open
+const LOCK_FILE: &str = "db";
 
 /// Sled driver implementation
 pub struct SledDriver {
@@ -109,10 +126,34 @@ impl StorageTree for SledTree {
     }
 }
 
+impl SledDriver {
+    /// Wait up to `LOCK_RELEASE_TIMEOUT` until no handle holds the lock on the database at `path`
+    ///
+    /// Returns at once for a new database. If the lock is still held afterwards,
+    /// `sled::open` reports it.
+    fn wait_for_lock_release(path: &Path) {
+        let Ok(file) = File::open(path.join(LOCK_FILE)) else {
+            return;
+        };
+        let started = Instant::now();
+        while let Err(e) = file.try_lock_exclusive() {
+            if e.kind() != fs2::lock_contended_error().kind()
+                || started.elapsed() >= LOCK_RELEASE_TIMEOUT
+            {
+                return;
+            }
+            thread::sleep(LOCK_RETRY_INTERVAL);
+        }
+        // Dropping the file releases the lock taken to check it
+    }
+}
+
 impl StorageDriver for SledDriver {
     type Tree = Box<dyn StorageTree>;
 
     fn open<P: AsRef<Path>>(path: P) -> StorageResult<Self> {
+        let path = path.as_ref();
+        SledDriver::wait_for_lock_release(path);
         let db =
             sled::open(path).map_err(|e| StorageDriverError::BackendSpecific(e.to_string()))?;
         Ok(SledDriver { db })
@@ -0,0 +1,614 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! Sample data generation for tutorials and benchmarks
+//!
+//! Each domain preset describes a small, realistic graph at scale 1.0; the scale
+//! factor multiplies every node and edge count. Generation is seeded, so the same
+//! domain and scale always produce the same dataset.
+
+use crate::coordinator::BulkNode;
+use crate::storage::Value;
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// Seed shared by all presets so datasets are reproducible
+const SAMPLE_SEED: u64 = 0x6772_6170_686c_6974;
+
+/// Sample dataset presets available through `gql.sample_data`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SampleDomain {
+    /// Accounts and merchants with Transaction and Transfer edges
+    #[default]
+    Fraud,
+    /// People, cities and posts with KNOWS / LIVES_IN / POSTED / LIKES
+    Social,
+    /// Proteins, compounds and assays matching the drug_discovery examples
+    Pharma,
+    /// Suppliers, parts, factories, warehouses and products
+    SupplyChain,
+}
+
+impl SampleDomain {
+    /// All presets, in the order they are documented
+    pub const ALL: [SampleDomain; 4] = [
+        SampleDomain::Fraud,
+        SampleDomain::Social,
+        SampleDomain::Pharma,
+        SampleDomain::SupplyChain,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SampleDomain::Fraud => "fraud",
+            SampleDomain::Social => "social",
+            SampleDomain::Pharma => "pharma",
+            SampleDomain::SupplyChain => "supply_chain",
+        }
+    }
+}
+
+impl fmt::Display for SampleDomain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for SampleDomain {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().replace('-', "_").as_str() {
+            "fraud" => Ok(SampleDomain::Fraud),
+            "social" => Ok(SampleDomain::Social),
+            "pharma" | "drug_discovery" => Ok(SampleDomain::Pharma),
+            "supply_chain" | "supplychain" => Ok(SampleDomain::SupplyChain),
+            other => Err(format!(
+                "Unknown sample data domain '{}'. Valid domains: {}",
+                other,
+                SampleDomain::ALL
+                    .iter()
+                    .map(|d| d.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )),
+        }
+    }
+}
+
+/// Edge between two nodes of a `SampleData` set, addressed by node index
+#[derive(Debug, Clone, PartialEq)]
+pub struct SampleEdge {
+    pub from: usize,
+    pub to: usize,
+    pub label: String,
+    pub properties: HashMap<String, Value>,
+}
+
+/// Generated nodes and edges, ready for bulk loading
+#[derive(Debug, Clone, Default)]
+pub struct SampleData {
+    pub nodes: Vec<BulkNode>,
+    pub edges: Vec<SampleEdge>,
+}
+
+impl SampleData {
+    /// Generate the dataset for `domain`, with every count multiplied by `scale`
+    pub fn generate(domain: SampleDomain, scale: f64) -> Result<Self, String> {
+        if !scale.is_finite() || scale <= 0.0 {
+            return Err(format!(
+                "Sample data scale must be a positive number, got {}",
+                scale
+            ));
+        }
+
+        let mut builder = SampleBuilder {
+            data: SampleData::default(),
+            rng: fastrand::Rng::with_seed(SAMPLE_SEED),
+            scale,
+        };
+        match domain {
+            SampleDomain::Fraud => builder.fraud(),
+            SampleDomain::Social => builder.social(),
+            SampleDomain::Pharma => builder.pharma(),
+            SampleDomain::SupplyChain => builder.supply_chain(),
+        }
+        Ok(builder.data)
+    }
+}
+
+struct SampleBuilder {
+    data: SampleData,
+    rng: fastrand::Rng,
+    scale: f64,
+}
+
+impl SampleBuilder {
+    /// Scaled count, never below one so every label and relationship is present
+    fn count(&self, base: usize) -> usize {
+        ((base as f64 * self.scale).round() as usize).max(1)
+    }
+
+    fn node(&mut self, label: &str, properties: Vec<(&str, Value)>) -> usize {
+        self.data.nodes.push(BulkNode {
+            labels: vec![label.to_string()],
+            properties: properties
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), v))
+                .collect(),
+        });
+        self.data.nodes.len() - 1
+    }
+
+    fn edge(&mut self, from: usize, to: usize, label: &str, properties: Vec<(&str, Value)>) {
+        self.data.edges.push(SampleEdge {
+            from,
+            to,
+            label: label.to_string(),
+            properties: properties
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), v))
+                .collect(),
+        });
+    }
+
+    fn pick(&mut self, nodes: &[usize]) -> usize {
+        nodes[self.rng.usize(..nodes.len())]
+    }
+
+    /// Two distinct nodes from `nodes` (the same node if only one exists)
+    fn pick_pair(&mut self, nodes: &[usize]) -> (usize, usize) {
+        let first = self.rng.usize(..nodes.len());
+        let second = if nodes.len() > 1 {
+            (first + 1 + self.rng.usize(..nodes.len() - 1)) % nodes.len()
+        } else {
+            first
+        };
+        (nodes[first], nodes[second])
+    }
+
+    /// Random number in `[low, high)` rounded to two decimals
+    fn amount(&mut self, low: f64, high: f64) -> Value {
+        let raw = low + self.rng.f64() * (high - low);
+        Value::Number((raw * 100.0).round() / 100.0)
+    }
+
+    fn int(&mut self, low: i64, high: i64) -> Value {
+        Value::Number(self.rng.i64(low..high) as f64)
+    }
+
+    fn date(&mut self) -> Value {
+        Value::String(format!(
+            "2024-{:02}-{:02}",
+            self.rng.u8(1..13),
+            self.rng.u8(1..29)
+        ))
+    }
+
+    fn text(value: impl Into<String>) -> Value {
+        Value::String(value.into())
+    }
+
+    fn fraud(&mut self) {
+        const ACCOUNT_TYPES: [&str; 4] = ["checking", "savings", "business", "investment"];
+        const CATEGORIES: [&str; 5] = ["retail", "travel", "electronics", "grocery", "gaming"];
+
+        let accounts: Vec<usize> = (1..=self.count(50))
+            .map(|i| {
+                let risk_score = self.amount(0.0, 10.0);
+                let balance = self.amount(100.0, 50_000.0);
+                let status = if i % 10 == 0 { "inactive" } else { "active" };
+                self.node(
+                    "Account",
+                    vec![
+                        ("id", Value::Number(i as f64)),
+                        ("account_number", Self::text(format!("ACC{:06}", i))),
+                        ("name", Self::text(format!("Account{}", i))),
+                        ("account_type", Self::text(ACCOUNT_TYPES[i % 4])),
+                        ("status", Self::text(status)),
+                        ("balance", balance),
+                        ("risk_score", risk_score),
+                    ],
+                )
+            })
+            .collect();
+
+        let merchants: Vec<usize> = (1..=self.count(20))
+            .map(|i| {
+                let category = CATEGORIES[self.rng.usize(..CATEGORIES.len())];
+                self.node(
+                    "Merchant",
+                    vec![
+                        ("id", Value::Number(i as f64)),
+                        ("name", Self::text(format!("Merchant{}", i))),
+                        ("category", Self::text(category)),
+                    ],
+                )
+            })
+            .collect();
+
+        for i in 1..=self.count(100) {
+            let account = self.pick(&accounts);
+            let merchant = self.pick(&merchants);
+            // One transaction in ten is high-value so fraud queries have something to find
+            let amount = if i % 10 == 0 {
+                self.amount(50_000.0, 100_000.0)
+            } else {
+                self.amount(100.0, 10_000.0)
+            };
+            self.edge(
+                account,
+                merchant,
+                "Transaction",
+                vec![("amount", amount), ("timestamp", Value::Number(i as f64))],
+            );
+        }
+
+        for i in 1..=self.count(50) {
+            let (from, to) = self.pick_pair(&accounts);
+            let amount = self.amount(10.0, 5_000.0);
+            self.edge(
+                from,
+                to,
+                "Transfer",
+                vec![("amount", amount), ("timestamp", Value::Number(i as f64))],
+            );
+        }
+    }
+
+    fn social(&mut self) {
+        const CITIES: [&str; 10] = [
+            "Amsterdam",
+            "Berlin",
+            "Chicago",
+            "Lisbon",
+            "Madrid",
+            "Nairobi",
+            "Osaka",
+            "Paris",
+            "Seoul",
+            "Toronto",
+        ];
+        const INTERESTS: [&str; 6] = ["music", "sports", "travel", "cooking", "gaming", "art"];
+
+        let cities: Vec<usize> = (0..self.count(CITIES.len()))
+            .map(|i| {
+                let name = if i < CITIES.len() {
+                    CITIES[i].to_string()
+                } else {
+                    format!("{} {}", CITIES[i % CITIES.len()], i / CITIES.len() + 1)
+                };
+                self.node("City", vec![("name", Self::text(name))])
+            })
+            .collect();
+
+        let people: Vec<usize> = (1..=self.count(100))
+            .map(|i| {
+                let age = self.int(18, 80);
+                let interest = INTERESTS[self.rng.usize(..INTERESTS.len())];
+                self.node(
+                    "Person",
+                    vec![
+                        ("id", Value::Number(i as f64)),
+                        ("name", Self::text(format!("Person{}", i))),
+                        ("age", age),
+                        ("interest", Self::text(interest)),
+                    ],
+                )
+            })
+            .collect();
+
+        for &person in &people {
+            let city = self.pick(&cities);
+            let since = self.int(2000, 2025);
+            self.edge(person, city, "LIVES_IN", vec![("since", since)]);
+        }
+
+        for _ in 0..self.count(300) {
+            let (from, to) = self.pick_pair(&people);
+            let since = self.int(2005, 2025);
+            self.edge(from, to, "KNOWS", vec![("since", since)]);
+        }
+
+        let posts: Vec<usize> = (1..=self.count(200))
+            .map(|i| {
+                let created_at = self.date();
+                self.node(
+                    "Post",
+                    vec![
+                        ("id", Value::Number(i as f64)),
+                        ("title", Self::text(format!("Post {}", i))),
+                        ("created_at", created_at),
+                    ],
+                )
+            })
+            .collect();
+
+        for &post in &posts {
+            let author = self.pick(&people);
+            self.edge(author, post, "POSTED", vec![]);
+        }
+
+        for _ in 0..self.count(500) {
+            let person = self.pick(&people);
+            let post = self.pick(&posts);
+            let rating = self.int(1, 6);
+            self.edge(person, post, "LIKES", vec![("rating", rating)]);
+        }
+    }
+
+    fn pharma(&mut self) {
+        const DISEASES: [&str; 5] = [
+            "Cancer",
+            "Hypertension",
+            "Alzheimers",
+            "Diabetes",
+            "Arthritis",
+        ];
+        const STAGES: [&str; 5] = [
+            "Preclinical",
+            "Clinical Trial Phase 1",
+            "Clinical Trial Phase 2",
+            "Clinical Trial Phase 3",
+            "Approved",
+        ];
+        const ASSAY_TYPES: [(&str, &str); 4] = [
+            ("Enzymatic", "TR-FRET"),
+            ("Binding", "SPR"),
+            ("Enzymatic", "FRET"),
+            ("Protein-Protein Interaction", "HTRF"),
+        ];
+
+        let proteins: Vec<usize> = (1..=self.count(10))
+            .map(|i| {
+                let disease = DISEASES[self.rng.usize(..DISEASES.len())];
+                self.node(
+                    "Protein",
+                    vec![
+                        ("id", Self::text(format!("PR-{:04}", i))),
+                        ("name", Self::text(format!("Protein {}", i))),
+                        ("disease", Self::text(disease)),
+                        (
+                            "gene_location",
+                            Self::text(format!("{}p{}.{}", 1 + i % 22, 10 + i % 20, i % 9)),
+                        ),
+                    ],
+                )
+            })
+            .collect();
+
+        let compounds: Vec<usize> = (1..=self.count(50))
+            .map(|i| {
+                let molecular_weight = self.amount(150.0, 650.0);
+                let stage = STAGES[self.rng.usize(..STAGES.len())];
+                self.node(
+                    "Compound",
+                    vec![
+                        ("id", Self::text(format!("CP-{:04}", i))),
+                        ("name", Self::text(format!("Compound {}", i))),
+                        ("molecular_weight", molecular_weight),
+                        ("development_stage", Self::text(stage)),
+                    ],
+                )
+            })
+            .collect();
+
+        let assays: Vec<usize> = (1..=self.count(20))
+            .map(|i| {
+                let (assay_type, method) = ASSAY_TYPES[i % ASSAY_TYPES.len()];
+                let date = self.date();
+                self.node(
+                    "Assay",
+                    vec![
+                        ("id", Self::text(format!("AS-{:04}", i))),
+                        ("name", Self::text(format!("Assay {}", i))),
+                        ("assay_type", Self::text(assay_type)),
+                        ("method", Self::text(method)),
+                        ("date", date),
+                    ],
+                )
+            })
+            .collect();
+
+        for &assay in &assays {
+            let protein = self.pick(&proteins);
+            self.edge(
+                assay,
+                protein,
+                "MEASURES_ACTIVITY_ON",
+                vec![("readout", Self::text("percent inhibition"))],
+            );
+        }
+
+        for &compound in &compounds {
+            let assay = self.pick(&assays);
+            let test_date = self.date();
+            let replicate_count = self.int(2, 6);
+            self.edge(
+                compound,
+                assay,
+                "TESTED_IN",
+                vec![
+                    ("test_date", test_date),
+                    ("replicate_count", replicate_count),
+                ],
+            );
+        }
+
+        for _ in 0..self.count(100) {
+            let compound = self.pick(&compounds);
+            let protein = self.pick(&proteins);
+            let ic50 = self.amount(0.5, 1_000.0);
+            let ki = self.amount(0.1, 300.0);
+            let selectivity_index = self.amount(1.0, 150.0);
+            let measurement_date = self.date();
+            self.edge(
+                compound,
+                protein,
+                "INHIBITS",
+                vec![
+                    ("IC50", ic50),
+                    ("IC50_unit", Self::text("nM")),
+                    ("Ki", ki),
+                    ("selectivity_index", selectivity_index),
+                    ("measurement_date", measurement_date),
+                ],
+            );
+        }
+    }
+
+    fn supply_chain(&mut self) {
+        const COUNTRIES: [&str; 6] = ["China", "Germany", "Mexico", "Vietnam", "USA", "India"];
+
+        let suppliers: Vec<usize> = (1..=self.count(20))
+            .map(|i| {
+                let country = COUNTRIES[self.rng.usize(..COUNTRIES.len())];
+                let reliability = self.amount(0.7, 1.0);
+                self.node(
+                    "Supplier",
+                    vec![
+                        ("id", Self::text(format!("SUP-{:04}", i))),
+                        ("name", Self::text(format!("Supplier {}", i))),
+                        ("country", Self::text(country)),
+                        ("reliability", reliability),
+                    ],
+                )
+            })
+            .collect();
+
+        let parts: Vec<usize> = (1..=self.count(50))
+            .map(|i| {
+                let unit_cost = self.amount(0.5, 500.0);
+                self.node(
+                    "Part",
+                    vec![
+                        ("id", Self::text(format!("PART-{:05}", i))),
+                        ("name", Self::text(format!("Part {}", i))),
+                        ("unit_cost", unit_cost),
+                    ],
+                )
+            })
+            .collect();
+
+        let factories: Vec<usize> = (1..=self.count(5))
+            .map(|i| {
+                let country = COUNTRIES[self.rng.usize(..COUNTRIES.len())];
+                self.node(
+                    "Factory",
+                    vec![
+                        ("id", Self::text(format!("FAC-{:03}", i))),
+                        ("country", Self::text(country)),
+                    ],
+                )
+            })
+            .collect();
+
+        let warehouses: Vec<usize> = (1..=self.count(10))
+            .map(|i| {
+                let capacity = self.int(1_000, 100_000);
+                self.node(
+                    "Warehouse",
+                    vec![
+                        ("id", Self::text(format!("WH-{:03}", i))),
+                        ("capacity", capacity),
+                    ],
+                )
+            })
+            .collect();
+
+        let products: Vec<usize> = (1..=self.count(30))
+            .map(|i| {
+                let price = self.amount(10.0, 2_000.0);
+                self.node(
+                    "Product",
+                    vec![
+                        ("id", Self::text(format!("PRD-{:04}", i))),
+                        ("name", Self::text(format!("Product {}", i))),
+                        ("price", price),
+                    ],
+                )
+            })
+            .collect();
+
+        for &part in &parts {
+            for _ in 0..2 {
+                let supplier = self.pick(&suppliers);
+                let lead_time_days = self.int(2, 60);
+                self.edge(
+                    supplier,
+                    part,
+                    "SUPPLIES",
+                    vec![("lead_time_days", lead_time_days)],
+                );
+            }
+        }
+
+        for &product in &products {
+            let factory = self.pick(&factories);
+            self.edge(factory, product, "MANUFACTURES", vec![]);
+            for _ in 0..3 {
+                let part = self.pick(&parts);
+                let quantity = self.int(1, 20);
+                self.edge(part, product, "USED_IN", vec![("quantity", quantity)]);
+            }
+        }
+
+        for &warehouse in &warehouses {
+            let factory = self.pick(&factories);
+            let distance_km = self.int(10, 5_000);
+            self.edge(
+                factory,
+                warehouse,
+                "SHIPS_TO",
+                vec![("distance_km", distance_km)],
+            );
+            for _ in 0..5 {
+                let product = self.pick(&products);
+                let quantity = self.int(0, 1_000);
+                self.edge(warehouse, product, "STOCKS", vec![("quantity", quantity)]);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_domain_parsing() {
+        assert_eq!(
+            "drug_discovery".parse::<SampleDomain>().unwrap(),
+            SampleDomain::Pharma
+        );
+        assert_eq!(
+            "Supply-Chain".parse::<SampleDomain>().unwrap(),
+            SampleDomain::SupplyChain
+        );
+        assert!("weather".parse::<SampleDomain>().is_err());
+    }
+
+    #[test]
+    fn test_scale_multiplies_counts() {
+        let base = SampleData::generate(SampleDomain::Social, 1.0).unwrap();
+        let doubled = SampleData::generate(SampleDomain::Social, 2.0).unwrap();
+        assert_eq!(doubled.nodes.len(), base.nodes.len() * 2);
+        assert_eq!(doubled.edges.len(), base.edges.len() * 2);
+        assert!(SampleData::generate(SampleDomain::Social, 0.0).is_err());
+    }
+
+    #[test]
+    fn test_generation_is_deterministic() {
+        for domain in SampleDomain::ALL {
+            let first = SampleData::generate(domain, 0.5).unwrap();
+            let second = SampleData::generate(domain, 0.5).unwrap();
+            assert_eq!(first.nodes, second.nodes);
+            assert_eq!(first.edges, second.edges);
+            assert!(first
+                .edges
+                .iter()
+                .all(|e| e.from < first.nodes.len() && e.to < first.nodes.len()));
+        }
+    }
+}
@@ -14,16 +14,20 @@
 //! At least one of persistent_store or memory_store must be configured.
 
 use crate::catalog::manager::CatalogManager;
+use crate::storage::backup;
 use crate::storage::data_adapter::DataAdapter;
 use crate::storage::indexes::IndexManager;
-use crate::storage::multi_graph::MultiGraphManager;
+use crate::storage::integrity::{CorruptRecord, ReferenceCheckReport, StorageCheckReport};
+use crate::storage::multi_graph::{MultiGraphManager, UnionConflictPolicy};
+use crate::storage::outbox::{Outbox, OutboxAck, OutboxEvent, PendingEvent};
 use crate::storage::persistent::{create_storage_driver, StorageDriver, StorageTree};
 use crate::storage::StorageType;
 use crate::storage::{GraphCache, StorageError};
 use log::{debug, error, info};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::Path;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 /// Storage method configuration
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
@@ -57,6 +61,12 @@ pub struct StorageManager {
 
     /// Index manager for text indexes
     index_manager: Option<Arc<IndexManager>>,
+
+    /// Per-graph event outboxes, kept in the storage driver
+    outbox: Arc<Outbox>,
+
+    /// Per-graph locks held by data statements from loading a graph to saving it
+    write_locks: Arc<Mutex<HashMap<String, Arc<Mutex<()>>>>>,
 }
 
 impl StorageManager {
@@ -118,6 +128,8 @@ impl StorageManager {
             memory_store: None,
             storage_type,
             index_manager: Some(index_manager),
+            outbox: Arc::new(Outbox::new()),
+            write_locks: Arc::new(Mutex::new(HashMap::new())),
         })
     }
 
@@ -192,6 +204,10 @@ impl StorageManager {
                         return Ok(Some(graph));
                     }
                     Err(e) => {
+                        // A corrupt graph must not look like a missing or empty one
+                        if let Some(corrupt) = e.downcast_ref::<CorruptRecord>() {
+                            return Err(StorageError::Corruption(corrupt.clone()));
+                        }
                         debug!(
                             "Failed to load graph '{}' from persistent storage: {}",
                             name, e
@@ -206,9 +222,13 @@ impl StorageManager {
 
     /// Save a graph
     /// Updates cache, memory store (if available), and persistent storage
-    pub fn save_graph(&self, name: &str, graph: GraphCache) -> Result<(), StorageError> {
+    pub fn save_graph(&self, name: &str, mut graph: GraphCache) -> Result<(), StorageError> {
         debug!("Saving graph '{}' to storage manager", name);
 
+        // Elements changed in place are re-indexed once, here, rather than on every lookup
+        graph.refresh_edge_property_indexes();
+        graph.refresh_node_key_indexes();
+
         // Use the provided name consistently - don't try to normalize to short names
         let cache_name = name.to_string();
 
@@ -345,6 +365,11 @@ impl StorageManager {
             );
         }
 
+        // 4. Drop the graph's event outbox
+        if let Some(driver) = &self.storage_driver {
+            self.outbox.delete_graph(driver.as_ref().as_ref(), name)?;
+        }
+
         debug!(
             "Successfully deleted graph '{}' from all storage tiers",
             name
@@ -382,6 +407,20 @@ impl StorageManager {
         &self.cache
     }
 
+    /// Lock that serializes writes to the graph `name`
+    ///
+    /// Data statements load a copy of the graph, change it and save it back.
+    /// Holding this lock for the whole round trip makes concurrent writers to
+    /// one graph take turns, so each sees the changes of the one before; MERGE
+    /// relies on it to create a missing element only once. Every path that
+    /// saves a changed copy of an existing graph takes it: data statements,
+    /// bulk loads and imports, rollback, relationship index builds, CLEAR and
+    /// TRUNCATE, and `gql.sample_data`.
+    pub fn graph_write_lock(&self, name: &str) -> Arc<Mutex<()>> {
+        let mut locks = self.write_locks.lock().unwrap_or_else(|e| e.into_inner());
+        locks.entry(name.to_string()).or_default().clone()
+    }
+
     /// Get access to the index manager (Phase 5: Week 6.4)
     pub fn get_index_manager(&self) -> Option<&Arc<IndexManager>> {
         self.index_manager.as_ref()
@@ -397,17 +436,22 @@ impl StorageManager {
     }
 
     /// Create a graph union (for UNION operations)
-    pub fn create_graph_union(&self, graph_names: Vec<String>) -> Result<GraphCache, StorageError> {
+    pub fn create_graph_union(
+        &self,
+        graph_names: Vec<String>,
+        all: bool,
+        policy: UnionConflictPolicy,
+    ) -> Result<GraphCache, StorageError> {
         let mut graphs = Vec::new();
         for name in graph_names {
             if let Some(graph) = self.get_graph(&name)? {
-                graphs.push(graph);
+                graphs.push((name, graph));
             } else {
                 return Err(StorageError::GraphNotFound(name));
             }
         }
 
-        self.cache.union_graphs(graphs)
+        self.cache.union_graphs(graphs, all, policy)
     }
 
     /// Save catalog to persistent storage
@@ -515,6 +559,165 @@ impl StorageManager {
         (entries, memory_bytes)
     }
 
+    /// Persist cache access metadata so the hot set survives restarts
+    pub fn save_cache_metadata(&self, data: &[u8]) -> Result<(), StorageError> {
+        match (&self.persistent_store, &self.storage_driver) {
+            (Some(persistent_store), Some(driver)) => persistent_store
+                .save_cache_metadata(driver.as_ref().as_ref(), data)
+                .map_err(|e| {
+                    StorageError::PersistenceError(format!("Failed to save cache metadata: {}", e))
+                }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Load previously persisted cache access metadata
+    pub fn load_cache_metadata(&self) -> Result<Option<Vec<u8>>, StorageError> {
+        match (&self.persistent_store, &self.storage_driver) {
+            (Some(persistent_store), Some(driver)) => persistent_store
+                .load_cache_metadata(driver.as_ref().as_ref())
+                .map_err(|e| {
+                    StorageError::PersistenceError(format!("Failed to load cache metadata: {}", e))
+                }),
+            _ => Ok(None),
+        }
+    }
+
+    /// Write committed events to their graphs' outboxes, returning their offsets
+    pub fn append_outbox_events(
+        &self,
+        events: Vec<PendingEvent>,
+        transaction_id: Option<u64>,
+    ) -> Result<Vec<u64>, StorageError> {
+        self.outbox
+            .append(self.outbox_driver()?, events, transaction_id)
+    }
+
+    /// Up to `limit` events of a graph's outbox the consumer has not acknowledged
+    pub fn poll_outbox(
+        &self,
+        graph_path: &str,
+        consumer: &str,
+        limit: usize,
+    ) -> Result<Vec<OutboxEvent>, StorageError> {
+        self.outbox
+            .poll(self.outbox_driver()?, graph_path, consumer, limit)
+    }
+
+    /// Acknowledge the events of a graph's outbox up to `offset` for a consumer
+    pub fn ack_outbox(
+        &self,
+        graph_path: &str,
+        consumer: &str,
+        offset: u64,
+    ) -> Result<OutboxAck, StorageError> {
+        self.outbox
+            .ack(self.outbox_driver()?, graph_path, consumer, offset)
+    }
+
+    fn outbox_driver(
+        &self,
+    ) -> Result<&dyn StorageDriver<Tree = Box<dyn StorageTree>>, StorageError> {
+        self.storage_driver
+            .as_ref()
+            .map(|driver| driver.as_ref().as_ref())
+            .ok_or_else(|| {
+                StorageError::PersistenceError(
+                    "No storage driver available for the outbox".to_string(),
+                )
+            })
+    }
+
+    /// Persist the plans pinned with `gql.pin_plan`
+    pub fn save_pinned_plans(&self, data: &[u8]) -> Result<(), StorageError> {
+        match (&self.persistent_store, &self.storage_driver) {
+            (Some(persistent_store), Some(driver)) => persistent_store
+                .save_pinned_plans(driver.as_ref().as_ref(), data)
+                .map_err(|e| {
+                    StorageError::PersistenceError(format!("Failed to save pinned plans: {}", e))
+                }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Load the pinned plans persisted by a previous run
+    pub fn load_pinned_plans(&self) -> Result<Option<Vec<u8>>, StorageError> {
+        match (&self.persistent_store, &self.storage_driver) {
+            (Some(persistent_store), Some(driver)) => persistent_store
+                .load_pinned_plans(driver.as_ref().as_ref())
+                .map_err(|e| {
+                    StorageError::PersistenceError(format!("Failed to load pinned plans: {}", e))
+                }),
+            _ => Ok(None),
+        }
+    }
+
+    /// Copy all persisted data into a new database directory at `dest`
+    ///
+    /// The copy is a Sled database whatever the backend of this one. `dest` must
+    /// not exist or be an empty directory. Returns the number of entries copied.
+    pub fn backup_to<P: AsRef<Path>>(&self, dest: P) -> Result<usize, StorageError> {
+        let driver = self.storage_driver.as_ref().ok_or_else(|| {
+            StorageError::InvalidOperation("No persistent storage to back up".to_string())
+        })?;
+        backup::copy_to_new_database(driver.as_ref().as_ref(), dest.as_ref())
+    }
+
+    /// Copy all persisted data into a new database directory marked as a snapshot
+    ///
+    /// Like `backup_to`, but the copy opens read-only (see `is_snapshot`).
+    pub fn snapshot_to<P: AsRef<Path>>(&self, dest: P) -> Result<usize, StorageError> {
+        let driver = self.storage_driver.as_ref().ok_or_else(|| {
+            StorageError::InvalidOperation("No persistent storage to snapshot".to_string())
+        })?;
+        backup::write_snapshot(driver.as_ref().as_ref(), dest.as_ref())
+    }
+
+    /// Whether this storage is a snapshot written by `snapshot_to`
+    pub fn is_snapshot(&self) -> bool {
+        self.storage_driver
+            .as_ref()
+            .is_some_and(|driver| backup::is_snapshot(driver.as_ref().as_ref()))
+    }
+
+    /// Examine the persisted trees for unreadable or corrupt records
+    ///
+    /// See `DataAdapter::check_storage`; `deep` verifies every record.
+    pub fn check_storage(&self, deep: bool) -> Result<StorageCheckReport, StorageError> {
+        match (&self.persistent_store, &self.storage_driver) {
+            (Some(persistent_store), Some(driver)) => persistent_store
+                .check_storage(driver.as_ref().as_ref(), deep)
+                .map_err(|e| {
+                    StorageError::PersistenceError(format!("Storage check failed: {}", e))
+                }),
+            _ => Err(StorageError::InvalidOperation(
+                "No persistent storage to check".to_string(),
+            )),
+        }
+    }
+
+    /// Find stored edges whose source or target node is not stored
+    ///
+    /// With `repair` the dangling edges are deleted and cached graphs are
+    /// dropped, so the next access loads the repaired graphs.
+    pub fn check_references(&self, repair: bool) -> Result<ReferenceCheckReport, StorageError> {
+        let (Some(persistent_store), Some(driver)) = (&self.persistent_store, &self.storage_driver)
+        else {
+            return Err(StorageError::InvalidOperation(
+                "No persistent storage to check".to_string(),
+            ));
+        };
+        let report = persistent_store
+            .check_references(driver.as_ref().as_ref(), repair)
+            .map_err(|e| {
+                StorageError::PersistenceError(format!("Reference check failed: {}", e))
+            })?;
+        if repair && !report.dangling_edges.is_empty() {
+            self.clear_cache()?;
+        }
+        Ok(report)
+    }
+
     /// Clear all stored data
     pub fn clear_all_data(&self) -> Result<(), StorageError> {
         // Clear cache
@@ -82,6 +82,9 @@ impl TypeMapping {
                 )
             }
 
+            // Documents
+            (Value::Json(_), GqlType::Json) => true,
+
             _ => false,
         }
     }
@@ -231,6 +234,7 @@ impl TypeMapping {
             Value::Node(_) => GqlType::String { max_length: None }, // Nodes are complex objects, use String for now
             Value::Edge(_) => GqlType::String { max_length: None }, // Edges are complex objects, use String for now
             Value::Temporal(_) => GqlType::String { max_length: None }, // Temporal values are complex, use String for now
+            Value::Json(_) => GqlType::Json,
         }
     }
 
@@ -296,6 +300,7 @@ impl TypeMapping {
             GqlType::Graph { .. } => Value::String("GRAPH()".to_string()), // Empty graph
             GqlType::BindingTable => Value::Array(vec![]),    // Empty binding table
             GqlType::Vector { .. } => Value::Vector(vec![]),  // Empty vector
+            GqlType::Json => Value::Json(serde_json::Value::Null),
         }
     }
 }
@@ -61,6 +61,9 @@ pub enum StorageError {
 
     #[error("Persistence error: {0}")]
     PersistenceError(String),
+
+    #[error("{0}")]
+    Corruption(#[from] crate::storage::integrity::CorruptRecord),
 }
 
 /// Graph node with id, labels, and properties
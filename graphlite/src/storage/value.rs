@@ -7,6 +7,7 @@
 //! - Basic types: String, Number, Boolean, Null
 //! - Temporal types: DateTime
 //! - Collections: Array
+//! - Documents: Json, arbitrary JSON without a declared schema
 
 use crate::storage::types::{Edge, Node};
 use chrono::{DateTime, FixedOffset, Utc};
@@ -161,6 +162,36 @@ pub enum Value {
     Edge(Edge),              // Graph edge with label and properties
     Temporal(TemporalValue), // Temporal value wrapper
     Null,
+    /// Schema-less JSON document, stored as its text
+    Json(#[serde(with = "json_text")] serde_json::Value),
+}
+
+/// Serialize JSON documents as text in binary formats, since bincode cannot
+/// deserialize self-describing data; human-readable formats get the document itself
+mod json_text {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        document: &serde_json::Value,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            document.serialize(serializer)
+        } else {
+            serializer.serialize_str(&document.to_string())
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<serde_json::Value, D::Error> {
+        if deserializer.is_human_readable() {
+            serde_json::Value::deserialize(deserializer)
+        } else {
+            let text = String::deserialize(deserializer)?;
+            serde_json::from_str(&text).map_err(serde::de::Error::custom)
+        }
+    }
 }
 
 impl Value {
@@ -306,6 +337,14 @@ impl Value {
         }
     }
 
+    /// Extract as JSON document if possible
+    pub fn as_json(&self) -> Option<&serde_json::Value> {
+        match self {
+            Value::Json(document) => Some(document),
+            _ => None,
+        }
+    }
+
     /// Check if value is null
     pub fn is_null(&self) -> bool {
         matches!(self, Value::Null)
@@ -329,6 +368,7 @@ impl Value {
             Value::Temporal(_) => "Temporal",
             Value::Null => "Null",
             Value::List(_) => "List",
+            Value::Json(_) => "Json",
         }
     }
 }
@@ -423,6 +463,7 @@ impl fmt::Display for Value {
                 )
             }
             Value::Null => write!(f, "null"),
+            Value::Json(document) => write!(f, "{}", document),
         }
     }
 }
@@ -452,6 +493,12 @@ impl From<i64> for Value {
     }
 }
 
+impl From<i32> for Value {
+    fn from(n: i32) -> Self {
+        Value::Number(n as f64)
+    }
+}
+
 impl From<bool> for Value {
     fn from(b: bool) -> Self {
         Value::Boolean(b)
@@ -635,6 +682,11 @@ impl Hash for Value {
                 14.hash(state);
                 tv.hash(state);
             }
+            Value::Json(document) => {
+                15.hash(state);
+                // Object keys are kept sorted, so equal documents have equal text
+                document.to_string().hash(state);
+            }
         }
     }
 }
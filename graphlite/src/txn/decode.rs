@@ -0,0 +1,239 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! Logical decoding of the write-ahead log
+//!
+//! Every successful write statement is logged as a `Statement` WAL entry holding
+//! its GQL text and the session's schema and graph at the time it ran. Decoding
+//! reads those entries back in log order and keeps only what became durable:
+//! autocommit statements as they appear, and the statements of an explicit
+//! transaction at the point it committed. Rolled back and unfinished
+//! transactions are dropped, and so are the statements of a transaction that
+//! were undone by `ROLLBACK TO SAVEPOINT`.
+//!
+//! The decoded statements replay the changes on another database, but only as
+//! faithfully as re-running the text allows: functions such as `now()` or
+//! `rand()` are evaluated again, and bulk loads and imports, which bypass GQL,
+//! are not logged at all.
+
+use super::wal::{read_wal_entries, WALEntry, WALEntryType};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Transaction id logged with autocommit statements
+const AUTOCOMMIT_TRANSACTION: u64 = 0;
+
+/// Description of a `Statement` WAL entry
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct StatementRecord {
+    pub schema: Option<String>,
+    pub graph: Option<String>,
+    pub statement: String,
+    /// Set for savepoint statements, which steer decoding and are not decoded themselves
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub savepoint: Option<SavepointRecord>,
+}
+
+/// Savepoint statement of a transaction, logged so decoding can drop undone statements
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) enum SavepointRecord {
+    Set(String),
+    RollbackTo(String),
+    Release(String),
+}
+
+impl StatementRecord {
+    pub(crate) fn to_description(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    fn from_entry(entry: &WALEntry) -> Option<Self> {
+        match serde_json::from_str(&entry.description) {
+            Ok(record) => Some(record),
+            Err(e) => {
+                log::warn!(
+                    "Skipping undecodable WAL statement {}: {}",
+                    entry.global_sequence,
+                    e
+                );
+                None
+            }
+        }
+    }
+}
+
+/// A committed write statement read back from the WAL
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedStatement {
+    /// Log sequence number of the statement
+    pub lsn: u64,
+    /// Log sequence number at which the statement became durable: its own for
+    /// autocommit statements, the commit's for statements of a transaction
+    pub commit_lsn: u64,
+    /// Transaction the statement ran in, or `None` for autocommit
+    pub transaction_id: Option<u64>,
+    /// When the statement was logged
+    pub logged_at: DateTime<Utc>,
+    /// Session schema when the statement ran, e.g. `/app`
+    pub schema: Option<String>,
+    /// Session graph when the statement ran, e.g. `/app/social`
+    pub graph: Option<String>,
+    /// GQL text as submitted
+    pub statement: String,
+}
+
+impl DecodedStatement {
+    fn new(entry: &WALEntry, record: StatementRecord) -> Self {
+        let transaction_id = entry.transaction_id.id();
+        Self {
+            lsn: entry.global_sequence,
+            commit_lsn: entry.global_sequence,
+            transaction_id: (transaction_id != AUTOCOMMIT_TRANSACTION).then_some(transaction_id),
+            logged_at: DateTime::<Utc>::from(entry.timestamp),
+            schema: record.schema,
+            graph: record.graph,
+            statement: record.statement,
+        }
+    }
+
+    /// Render decoded statements as a GQL script that replays them
+    ///
+    /// Session schema and graph changes are emitted before the statements that
+    /// need them, and the statements of each transaction are wrapped in
+    /// `START TRANSACTION` / `COMMIT`.
+    pub fn to_gql_script(statements: &[DecodedStatement]) -> String {
+        let mut script = String::new();
+        let mut schema: Option<&str> = None;
+        let mut graph: Option<&str> = None;
+        let mut open_transaction: Option<u64> = None;
+
+        for (index, decoded) in statements.iter().enumerate() {
+            if open_transaction.is_some() && open_transaction != decoded.transaction_id {
+                script.push_str("COMMIT;\n");
+                open_transaction = None;
+            }
+            if open_transaction.is_none() {
+                if let Some(txn) = decoded.transaction_id {
+                    script.push_str(&format!(
+                        "-- transaction {}, committed at lsn {}\n",
+                        txn, decoded.commit_lsn
+                    ));
+                }
+                if decoded.schema.is_some() && decoded.schema.as_deref() != schema {
+                    schema = decoded.schema.as_deref();
+                    script.push_str(&format!("SESSION SET SCHEMA {};\n", schema.unwrap_or("")));
+                }
+                if decoded.graph.is_some() && decoded.graph.as_deref() != graph {
+                    graph = decoded.graph.as_deref();
+                    script.push_str(&format!("SESSION SET GRAPH {};\n", graph.unwrap_or("")));
+                }
+                if let Some(txn) = decoded.transaction_id {
+                    script.push_str("START TRANSACTION;\n");
+                    open_transaction = Some(txn);
+                }
+            }
+
+            script.push_str(&format!(
+                "-- lsn {}, {}\n",
+                decoded.lsn,
+                decoded.logged_at.to_rfc3339()
+            ));
+            script.push_str(decoded.statement.trim().trim_end_matches(';').trim_end());
+            script.push_str(";\n");
+
+            if open_transaction.is_some() && index + 1 == statements.len() {
+                script.push_str("COMMIT;\n");
+            }
+        }
+        script
+    }
+}
+
+/// Decode the committed write statements in the WAL files of `wal_dir`
+///
+/// Only statements that became durable at or after `from_lsn` are returned (see
+/// `DecodedStatement::commit_lsn`), so a transaction is never split; pass the
+/// last `commit_lsn` seen plus one to continue where an earlier call stopped.
+pub fn decode_wal(wal_dir: &Path, from_lsn: u64) -> Result<Vec<DecodedStatement>, String> {
+    let mut files: Vec<_> = std::fs::read_dir(wal_dir)
+        .map_err(|e| format!("Failed to read WAL directory {}: {}", wal_dir.display(), e))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with("wal_") && name.ends_with(".log"))
+        })
+        .collect();
+    files.sort();
+
+    let mut entries = Vec::new();
+    for file in &files {
+        entries.extend(read_wal_entries(file).map_err(|e| e.to_string())?);
+    }
+    entries.sort_by_key(|entry| entry.global_sequence);
+
+    let mut decoded = Vec::new();
+    let mut pending: HashMap<u64, Vec<DecodedStatement>> = HashMap::new();
+    // Savepoints of each open transaction, with the number of its statements before each
+    let mut savepoints: HashMap<u64, Vec<(String, usize)>> = HashMap::new();
+    for entry in &entries {
+        let transaction_id = entry.transaction_id.id();
+        match entry.entry_type {
+            WALEntryType::Statement => {
+                let Some(mut record) = StatementRecord::from_entry(entry) else {
+                    continue;
+                };
+                if let Some(savepoint) = record.savepoint.take() {
+                    let statements = pending.entry(transaction_id).or_default();
+                    let marks = savepoints.entry(transaction_id).or_default();
+                    match savepoint {
+                        SavepointRecord::Set(name) => {
+                            marks.retain(|(existing, _)| *existing != name);
+                            marks.push((name, statements.len()));
+                        }
+                        SavepointRecord::RollbackTo(name) => {
+                            if let Some(position) =
+                                marks.iter().position(|(existing, _)| *existing == name)
+                            {
+                                statements.truncate(marks[position].1);
+                                marks.truncate(position + 1);
+                            }
+                        }
+                        SavepointRecord::Release(name) => {
+                            if let Some(position) =
+                                marks.iter().position(|(existing, _)| *existing == name)
+                            {
+                                marks.truncate(position);
+                            }
+                        }
+                    }
+                    continue;
+                }
+                let statement = DecodedStatement::new(entry, record);
+                match statement.transaction_id {
+                    Some(txn) => pending.entry(txn).or_default().push(statement),
+                    None => decoded.push(statement),
+                }
+            }
+            WALEntryType::Commit => {
+                savepoints.remove(&transaction_id);
+                if let Some(statements) = pending.remove(&transaction_id) {
+                    decoded.extend(statements.into_iter().map(|statement| DecodedStatement {
+                        commit_lsn: entry.global_sequence,
+                        ..statement
+                    }));
+                }
+            }
+            WALEntryType::Rollback => {
+                pending.remove(&transaction_id);
+                savepoints.remove(&transaction_id);
+            }
+            WALEntryType::Begin | WALEntryType::Operation => {}
+        }
+    }
+
+    decoded.retain(|statement| statement.commit_lsn >= from_lsn);
+    Ok(decoded)
+}
@@ -5,14 +5,19 @@
 //!
 //! This module provides a more efficient approach to transaction rollback
 //! by logging individual operations rather than keeping full graph snapshots.
+//! Logs of large transactions spill their operations to disk (see `spill`).
 
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::Arc;
 
+use super::spill::{decode, encode, spill_key, UndoSpill};
 use super::state::TransactionId;
-use crate::storage::{Edge, Node, Value};
+use crate::exec::ExecutionError;
+use crate::storage::{Edge, Node, StorageTree, Value};
 
 /// Represents an operation that can be undone
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum UndoOperation {
     /// A node was inserted - to undo, remove it
     InsertNode { graph_path: String, node_id: String },
@@ -49,18 +54,55 @@ pub enum UndoOperation {
     Batch { operations: Vec<UndoOperation> },
 }
 
+impl UndoOperation {
+    /// Graph the operation changes; `None` for a batch, whose operations each name one
+    pub fn graph_path(&self) -> Option<&str> {
+        match self {
+            UndoOperation::InsertNode { graph_path, .. }
+            | UndoOperation::UpdateNode { graph_path, .. }
+            | UndoOperation::DeleteNode { graph_path, .. }
+            | UndoOperation::InsertEdge { graph_path, .. }
+            | UndoOperation::UpdateEdge { graph_path, .. }
+            | UndoOperation::DeleteEdge { graph_path, .. } => Some(graph_path),
+            UndoOperation::Batch { .. } => None,
+        }
+    }
+}
+
 /// Transaction operation log for a single transaction
-#[derive(Debug, Clone)]
+///
+/// Once the operations held in memory reach the spill threshold of the log's
+/// `UndoSpill`, they move to disk; the log then holds the oldest operations on
+/// disk and the newest in `undo_operations`.
+#[derive(Debug)]
 #[allow(dead_code)] // ROADMAP v0.3.0 - Transaction undo log for ROLLBACK support
 pub struct TransactionLog {
     /// The transaction this log belongs to
     pub transaction_id: TransactionId,
-    /// Operations in reverse order (most recent first) for efficient rollback
+    /// Operations held in memory, oldest first; they follow the spilled ones
     pub undo_operations: Vec<UndoOperation>,
     /// Total number of operations logged
     pub operation_count: usize,
     /// Memory usage estimate in bytes
     pub estimated_size_bytes: usize,
+    /// Store to spill to; `None` keeps every operation in memory
+    spill: Option<Arc<UndoSpill>>,
+    /// Oldest operations, moved to disk
+    spilled: Option<SpilledOperations>,
+}
+
+/// Operations of a log that were moved to its spill tree, keyed by position
+struct SpilledOperations {
+    tree: Box<dyn StorageTree>,
+    count: usize,
+}
+
+impl std::fmt::Debug for SpilledOperations {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SpilledOperations")
+            .field("count", &self.count)
+            .finish()
+    }
 }
 
 impl TransactionLog {
@@ -71,162 +113,133 @@ impl TransactionLog {
             undo_operations: Vec::new(),
             operation_count: 0,
             estimated_size_bytes: std::mem::size_of::<Self>(),
+            spill: None,
+            spilled: None,
         }
     }
 
+    /// Create a new empty transaction log that spills to `spill` when it grows large
+    pub fn with_spill(transaction_id: TransactionId, spill: Arc<UndoSpill>) -> Self {
+        let mut log = Self::new(transaction_id);
+        log.spill = Some(spill);
+        log
+    }
+
     /// Add an undo operation to the log
     pub fn log_operation(&mut self, operation: UndoOperation) {
         // Estimate memory usage
-        let op_size = match &operation {
-            UndoOperation::InsertNode {
-                graph_path,
-                node_id,
-            } => std::mem::size_of::<UndoOperation>() + graph_path.len() + node_id.len(),
-            UndoOperation::UpdateNode {
-                graph_path,
-                node_id,
-                old_properties,
-                old_labels,
-            } => {
-                let props_size = old_properties
-                    .iter()
-                    .map(|(k, v)| k.len() + estimate_value_size(v))
-                    .sum::<usize>();
-                let labels_size = old_labels.iter().map(|l| l.len()).sum::<usize>();
-                std::mem::size_of::<UndoOperation>()
-                    + graph_path.len()
-                    + node_id.len()
-                    + props_size
-                    + labels_size
-            }
-            UndoOperation::DeleteNode {
-                graph_path,
-                node_id,
-                deleted_node,
-            } => {
-                std::mem::size_of::<UndoOperation>()
-                    + graph_path.len()
-                    + node_id.len()
-                    + estimate_node_size(deleted_node)
-            }
-            UndoOperation::InsertEdge {
-                graph_path,
-                edge_id,
-            } => std::mem::size_of::<UndoOperation>() + graph_path.len() + edge_id.len(),
-            UndoOperation::UpdateEdge {
-                graph_path,
-                edge_id,
-                old_properties,
-                old_label,
-            } => {
-                let props_size = old_properties
-                    .iter()
-                    .map(|(k, v)| k.len() + estimate_value_size(v))
-                    .sum::<usize>();
-                let label_size = old_label.len();
-                std::mem::size_of::<UndoOperation>()
-                    + graph_path.len()
-                    + edge_id.len()
-                    + props_size
-                    + label_size
-            }
-            UndoOperation::DeleteEdge {
-                graph_path,
-                edge_id,
-                deleted_edge,
-            } => {
-                std::mem::size_of::<UndoOperation>()
-                    + graph_path.len()
-                    + edge_id.len()
-                    + estimate_edge_size(deleted_edge)
-            }
-            UndoOperation::Batch { operations } => {
-                // For batch operations, sum up the size of all individual operations
-                let mut total_size = std::mem::size_of::<UndoOperation>();
-                for op in operations {
-                    total_size += match op {
-                        UndoOperation::InsertNode {
-                            graph_path,
-                            node_id,
-                        } => {
-                            std::mem::size_of::<UndoOperation>() + graph_path.len() + node_id.len()
-                        }
-                        UndoOperation::UpdateNode {
-                            graph_path,
-                            node_id,
-                            old_properties,
-                            old_labels,
-                        } => {
-                            let props_size = old_properties
-                                .iter()
-                                .map(|(k, v)| k.len() + estimate_value_size(v))
-                                .sum::<usize>();
-                            let labels_size = old_labels.iter().map(|l| l.len()).sum::<usize>();
-                            std::mem::size_of::<UndoOperation>()
-                                + graph_path.len()
-                                + node_id.len()
-                                + props_size
-                                + labels_size
-                        }
-                        UndoOperation::DeleteNode {
-                            graph_path,
-                            node_id,
-                            deleted_node,
-                        } => {
-                            std::mem::size_of::<UndoOperation>()
-                                + graph_path.len()
-                                + node_id.len()
-                                + estimate_node_size(deleted_node)
-                        }
-                        UndoOperation::InsertEdge {
-                            graph_path,
-                            edge_id,
-                        } => {
-                            std::mem::size_of::<UndoOperation>() + graph_path.len() + edge_id.len()
-                        }
-                        UndoOperation::UpdateEdge {
-                            graph_path,
-                            edge_id,
-                            old_properties,
-                            old_label,
-                        } => {
-                            let props_size = old_properties
-                                .iter()
-                                .map(|(k, v)| k.len() + estimate_value_size(v))
-                                .sum::<usize>();
-                            let label_size = old_label.len();
-                            std::mem::size_of::<UndoOperation>()
-                                + graph_path.len()
-                                + edge_id.len()
-                                + props_size
-                                + label_size
-                        }
-                        UndoOperation::DeleteEdge {
-                            graph_path,
-                            edge_id,
-                            deleted_edge,
-                        } => {
-                            std::mem::size_of::<UndoOperation>()
-                                + graph_path.len()
-                                + edge_id.len()
-                                + estimate_edge_size(deleted_edge)
-                        }
-                        UndoOperation::Batch { .. } => {
-                            // Nested batches - just use base size to avoid infinite recursion
-                            std::mem::size_of::<UndoOperation>()
-                        }
-                    };
-                }
-                total_size
-            }
-        };
-
+        let op_size = estimate_operation_size(&operation);
         self.undo_operations.push(operation);
         self.operation_count += 1;
         self.estimated_size_bytes += op_size;
+
+        let should_spill = self
+            .spill
+            .as_ref()
+            .is_some_and(|spill| spill.should_spill(self.undo_operations.len()));
+        if should_spill {
+            if let Err(e) = self.spill_to_disk() {
+                // Keep going in memory rather than failing the statement
+                log::warn!(
+                    "Failed to spill undo log of {}, keeping it in memory: {}",
+                    self.transaction_id,
+                    e
+                );
+                self.spill = None;
+            }
+        }
     }
 
-    /// Get the operations in rollback order (most recent first)
+    /// Move the operations held in memory to the spill tree
+    fn spill_to_disk(&mut self) -> Result<(), ExecutionError> {
+        let Some(spill) = &self.spill else {
+            return Ok(());
+        };
+        if self.spilled.is_none() {
+            self.spilled = Some(SpilledOperations {
+                tree: spill.tree(self.transaction_id)?,
+                count: 0,
+            });
+        }
+        let spilled = self.spilled.as_mut().expect("spill tree opened above");
+
+        let mut entries = Vec::with_capacity(self.undo_operations.len());
+        for (i, operation) in self.undo_operations.iter().enumerate() {
+            entries.push((spill_key(spilled.count + i), encode(operation)?));
+        }
+        let entries: Vec<(&[u8], &[u8])> = entries
+            .iter()
+            .map(|(key, value)| (key.as_slice(), value.as_slice()))
+            .collect();
+        spilled.tree.batch_insert(&entries).map_err(|e| {
+            ExecutionError::StorageError(format!("Failed to spill undo records: {}", e))
+        })?;
+
+        spilled.count += entries.len();
+        let freed: usize = self
+            .undo_operations
+            .iter()
+            .map(estimate_operation_size)
+            .sum();
+        self.estimated_size_bytes = self.estimated_size_bytes.saturating_sub(freed);
+        self.undo_operations.clear();
+        log::debug!(
+            "Spilled {} undo operations of {} to disk ({} in total)",
+            entries.len(),
+            self.transaction_id,
+            spilled.count
+        );
+        Ok(())
+    }
+
+    /// Remove and return the most recent operation, reading it back from disk if it was spilled
+    pub fn pop_operation(&mut self) -> Result<Option<UndoOperation>, ExecutionError> {
+        if let Some(operation) = self.undo_operations.pop() {
+            self.estimated_size_bytes = self
+                .estimated_size_bytes
+                .saturating_sub(estimate_operation_size(&operation));
+            return Ok(Some(operation));
+        }
+        let Some(spilled) = self.spilled.as_mut().filter(|spilled| spilled.count > 0) else {
+            return Ok(None);
+        };
+
+        let key = spill_key(spilled.count - 1);
+        let bytes = spilled
+            .tree
+            .get(&key)
+            .map_err(|e| {
+                ExecutionError::StorageError(format!("Failed to read spilled undo record: {}", e))
+            })?
+            .ok_or_else(|| {
+                ExecutionError::StorageError(format!(
+                    "Spilled undo record {} of {} is missing",
+                    spilled.count - 1,
+                    self.transaction_id
+                ))
+            })?;
+        let operation = decode(&bytes)?;
+        spilled.tree.remove(&key).map_err(|e| {
+            ExecutionError::StorageError(format!("Failed to remove spilled undo record: {}", e))
+        })?;
+        spilled.count -= 1;
+        Ok(Some(operation))
+    }
+
+    /// Number of operations in the log, in memory and on disk
+    pub fn len(&self) -> usize {
+        self.spilled_count() + self.undo_operations.len()
+    }
+
+    /// Number of operations moved to disk
+    pub fn spilled_count(&self) -> usize {
+        self.spilled.as_ref().map_or(0, |spilled| spilled.count)
+    }
+
+    /// Get the in-memory operations in rollback order (most recent first)
+    ///
+    /// Spilled operations are not included; use `pop_operation` to roll back
+    /// a log that may have spilled.
     #[allow(dead_code)] // ROADMAP v0.3.0 - Rollback operation iteration for ROLLBACK execution
     pub fn get_rollback_operations(&self) -> impl Iterator<Item = &UndoOperation> {
         self.undo_operations.iter().rev()
@@ -238,12 +251,13 @@ impl TransactionLog {
         self.undo_operations.clear();
         self.operation_count = 0;
         self.estimated_size_bytes = std::mem::size_of::<Self>();
+        self.discard_spilled();
     }
 
     /// Check if the log is empty
     #[allow(dead_code)] // ROADMAP v0.3.0 - Check if transaction has any undo operations for ROLLBACK decision
     pub fn is_empty(&self) -> bool {
-        self.undo_operations.is_empty()
+        self.len() == 0
     }
 
     /// Get statistics about the log
@@ -253,8 +267,22 @@ impl TransactionLog {
             transaction_id: self.transaction_id,
             operation_count: self.operation_count,
             estimated_size_bytes: self.estimated_size_bytes,
+            spilled_operations: self.spilled_count(),
         }
     }
+
+    fn discard_spilled(&mut self) {
+        if let (Some(spilled), Some(spill)) = (self.spilled.take(), &self.spill) {
+            drop(spilled);
+            spill.discard(self.transaction_id);
+        }
+    }
+}
+
+impl Drop for TransactionLog {
+    fn drop(&mut self) {
+        self.discard_spilled();
+    }
 }
 
 /// Statistics about a transaction log
@@ -264,6 +292,151 @@ pub struct TransactionLogStats {
     pub transaction_id: TransactionId,
     pub operation_count: usize,
     pub estimated_size_bytes: usize,
+    /// Operations moved to disk
+    pub spilled_operations: usize,
+}
+
+/// Estimate memory usage of an undo operation
+fn estimate_operation_size(operation: &UndoOperation) -> usize {
+    match operation {
+        UndoOperation::InsertNode {
+            graph_path,
+            node_id,
+        } => std::mem::size_of::<UndoOperation>() + graph_path.len() + node_id.len(),
+        UndoOperation::UpdateNode {
+            graph_path,
+            node_id,
+            old_properties,
+            old_labels,
+        } => {
+            let props_size = old_properties
+                .iter()
+                .map(|(k, v)| k.len() + estimate_value_size(v))
+                .sum::<usize>();
+            let labels_size = old_labels.iter().map(|l| l.len()).sum::<usize>();
+            std::mem::size_of::<UndoOperation>()
+                + graph_path.len()
+                + node_id.len()
+                + props_size
+                + labels_size
+        }
+        UndoOperation::DeleteNode {
+            graph_path,
+            node_id,
+            deleted_node,
+        } => {
+            std::mem::size_of::<UndoOperation>()
+                + graph_path.len()
+                + node_id.len()
+                + estimate_node_size(deleted_node)
+        }
+        UndoOperation::InsertEdge {
+            graph_path,
+            edge_id,
+        } => std::mem::size_of::<UndoOperation>() + graph_path.len() + edge_id.len(),
+        UndoOperation::UpdateEdge {
+            graph_path,
+            edge_id,
+            old_properties,
+            old_label,
+        } => {
+            let props_size = old_properties
+                .iter()
+                .map(|(k, v)| k.len() + estimate_value_size(v))
+                .sum::<usize>();
+            let label_size = old_label.len();
+            std::mem::size_of::<UndoOperation>()
+                + graph_path.len()
+                + edge_id.len()
+                + props_size
+                + label_size
+        }
+        UndoOperation::DeleteEdge {
+            graph_path,
+            edge_id,
+            deleted_edge,
+        } => {
+            std::mem::size_of::<UndoOperation>()
+                + graph_path.len()
+                + edge_id.len()
+                + estimate_edge_size(deleted_edge)
+        }
+        UndoOperation::Batch { operations } => {
+            // For batch operations, sum up the size of all individual operations
+            let mut total_size = std::mem::size_of::<UndoOperation>();
+            for op in operations {
+                total_size += match op {
+                    UndoOperation::InsertNode {
+                        graph_path,
+                        node_id,
+                    } => std::mem::size_of::<UndoOperation>() + graph_path.len() + node_id.len(),
+                    UndoOperation::UpdateNode {
+                        graph_path,
+                        node_id,
+                        old_properties,
+                        old_labels,
+                    } => {
+                        let props_size = old_properties
+                            .iter()
+                            .map(|(k, v)| k.len() + estimate_value_size(v))
+                            .sum::<usize>();
+                        let labels_size = old_labels.iter().map(|l| l.len()).sum::<usize>();
+                        std::mem::size_of::<UndoOperation>()
+                            + graph_path.len()
+                            + node_id.len()
+                            + props_size
+                            + labels_size
+                    }
+                    UndoOperation::DeleteNode {
+                        graph_path,
+                        node_id,
+                        deleted_node,
+                    } => {
+                        std::mem::size_of::<UndoOperation>()
+                            + graph_path.len()
+                            + node_id.len()
+                            + estimate_node_size(deleted_node)
+                    }
+                    UndoOperation::InsertEdge {
+                        graph_path,
+                        edge_id,
+                    } => std::mem::size_of::<UndoOperation>() + graph_path.len() + edge_id.len(),
+                    UndoOperation::UpdateEdge {
+                        graph_path,
+                        edge_id,
+                        old_properties,
+                        old_label,
+                    } => {
+                        let props_size = old_properties
+                            .iter()
+                            .map(|(k, v)| k.len() + estimate_value_size(v))
+                            .sum::<usize>();
+                        let label_size = old_label.len();
+                        std::mem::size_of::<UndoOperation>()
+                            + graph_path.len()
+                            + edge_id.len()
+                            + props_size
+                            + label_size
+                    }
+                    UndoOperation::DeleteEdge {
+                        graph_path,
+                        edge_id,
+                        deleted_edge,
+                    } => {
+                        std::mem::size_of::<UndoOperation>()
+                            + graph_path.len()
+                            + edge_id.len()
+                            + estimate_edge_size(deleted_edge)
+                    }
+                    UndoOperation::Batch { .. } => {
+                        // Nested batches - just use base size to avoid infinite recursion
+                        std::mem::size_of::<UndoOperation>()
+                    }
+                };
+            }
+            total_size
+        }
+    }
 }
 
 /// Estimate memory usage of a Value
@@ -400,4 +573,34 @@ mod tests {
             _ => panic!("Expected InsertNode operation third"),
         }
     }
+
+    #[test]
+    fn test_spilled_operations_pop_newest_first() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let spill = Arc::new(UndoSpill::new(temp_dir.path()));
+        spill.set_threshold(Some(4));
+        let txn_id = TransactionId::new();
+        let mut log = TransactionLog::with_spill(txn_id, spill.clone());
+
+        for i in 0..10 {
+            log.log_operation(UndoOperation::InsertNode {
+                graph_path: "/test_graph".to_string(),
+                node_id: format!("node{}", i),
+            });
+        }
+        assert_eq!(log.len(), 10);
+        assert_eq!(log.spilled_count(), 8);
+        assert_eq!(log.undo_operations.len(), 2);
+
+        let mut popped = Vec::new();
+        while let Some(operation) = log.pop_operation().unwrap() {
+            match operation {
+                UndoOperation::InsertNode { node_id, .. } => popped.push(node_id),
+                other => panic!("Unexpected operation {:?}", other),
+            }
+        }
+        let expected: Vec<String> = (0..10).rev().map(|i| format!("node{}", i)).collect();
+        assert_eq!(popped, expected);
+        assert!(log.is_empty());
+    }
 }
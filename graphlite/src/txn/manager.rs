@@ -12,6 +12,7 @@ use crate::exec::error::ExecutionError;
 use crate::session::SessionManager;
 
 use super::isolation::IsolationLevel;
+use super::spill::UndoSpill;
 use super::state::{AccessMode, OperationType, TransactionId, TransactionState, TxnIsolationLevel};
 use super::wal::{PersistentWAL, WALEntry, WALEntryType};
 
@@ -27,6 +28,8 @@ pub struct TransactionManager {
     wal: Arc<PersistentWAL>,
     /// Session manager for transaction-session association
     session_manager: Option<Arc<SessionManager>>,
+    /// Disk store for the undo logs of large transactions
+    undo_spill: Arc<UndoSpill>,
 }
 
 impl TransactionManager {
@@ -38,6 +41,7 @@ impl TransactionManager {
     /// # Returns
     /// A new TransactionManager instance with WAL initialized in the database directory
     pub fn new(db_path: std::path::PathBuf) -> Result<Self, ExecutionError> {
+        let undo_spill = Arc::new(UndoSpill::new(&db_path));
         let wal = PersistentWAL::new(db_path).map_err(|e| {
             ExecutionError::RuntimeError(format!("Failed to initialize WAL: {}", e))
         })?;
@@ -48,9 +52,15 @@ impl TransactionManager {
             next_transaction_characteristics: Arc::new(Mutex::new(None)),
             wal: Arc::new(wal),
             session_manager: None,
+            undo_spill,
         })
     }
 
+    /// Disk store that transaction logs spill their undo operations to
+    pub fn undo_spill(&self) -> Arc<UndoSpill> {
+        self.undo_spill.clone()
+    }
+
     /// Start a new transaction
     pub fn start_transaction(
         &self,
@@ -435,6 +445,29 @@ impl TransactionManager {
         }
     }
 
+    /// Log the text of a write statement for logical decoding
+    ///
+    /// `transaction_id` is `None` for autocommit statements. The description is the
+    /// JSON record read back by `txn::decode`.
+    pub fn log_statement(
+        &self,
+        transaction_id: Option<TransactionId>,
+        description: String,
+    ) -> Result<(), ExecutionError> {
+        let wal_entry = WALEntry::new(
+            WALEntryType::Statement,
+            transaction_id.unwrap_or(TransactionId::from_u64(0)),
+            self.wal.next_global_sequence(),
+            0,
+            None,
+            description,
+        );
+
+        self.wal.write_entry(wal_entry).map_err(|e| {
+            ExecutionError::RuntimeError(format!("Failed to write statement to WAL: {}", e))
+        })
+    }
+
     /// Get all active transactions for a session
     pub fn get_session_transactions(
         &self,
@@ -517,6 +550,16 @@ impl TransactionManager {
 
         Ok(stats)
     }
+
+    /// Directory holding the WAL files
+    pub fn wal_dir(&self) -> &std::path::Path {
+        self.wal.wal_dir()
+    }
+
+    /// Time the most recent WAL entry took to flush and sync to disk
+    pub fn wal_flush_lag(&self) -> std::time::Duration {
+        self.wal.last_flush_duration()
+    }
 }
 
 /// Transaction statistics for monitoring
@@ -19,10 +19,12 @@
 //! - Transaction logging and recovery
 //! - Nested transaction support
 
+pub mod decode;
 pub mod isolation;
 pub mod log;
 pub mod manager;
 pub mod recovery;
+pub mod spill;
 pub mod state;
 pub mod wal;
 
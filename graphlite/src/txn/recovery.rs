@@ -181,6 +181,9 @@ impl RecoveryManager {
                     state.operations.push(entry);
                 }
             }
+
+            // Statement text is only read by logical decoding
+            WALEntryType::Statement => {}
         }
 
         Ok(())
@@ -0,0 +1,151 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! Disk-backed storage for the undo records of large transactions
+//!
+//! A `TransactionLog` keeps its undo records in memory until they reach the
+//! spill threshold, then moves them to a tree of its own in a scratch store
+//! under `<db>/undo_spill`, keyed by position. Rollback reads them back one at
+//! a time, newest first, so a multi-million row transaction needs neither the
+//! records nor their rollback in RAM at once.
+//!
+//! The scratch store is opened on the first spill. Its contents only matter
+//! while the transaction is open (crash recovery replays the WAL, not undo
+//! records), so it is wiped when the database is opened and removed on drop.
+
+use std::path::PathBuf;
+use std::sync::{Mutex, RwLock};
+
+use super::log::UndoOperation;
+use super::state::TransactionId;
+use crate::exec::ExecutionError;
+use crate::storage::{create_storage_driver, StorageDriver, StorageTree, StorageType};
+
+/// Default `DatabaseConfig::undo_spill_threshold`
+pub const DEFAULT_UNDO_SPILL_THRESHOLD: usize = 10_000;
+
+/// Scratch directory of the spill store, inside the database directory
+const SPILL_DIR: &str = "undo_spill";
+
+type SpillDriver = Box<dyn StorageDriver<Tree = Box<dyn StorageTree>>>;
+
+/// Scratch store that transaction logs spill their undo records to
+pub struct UndoSpill {
+    /// Scratch directory
+    path: PathBuf,
+    /// Undo records a log holds in memory before spilling (`None` = never spill)
+    threshold: RwLock<Option<usize>>,
+    driver: Mutex<Option<SpillDriver>>,
+}
+
+impl UndoSpill {
+    /// Spill store in `db_path`, discarding records left by a previous process
+    pub fn new(db_path: &std::path::Path) -> Self {
+        let path = db_path.join(SPILL_DIR);
+        if path.exists() {
+            if let Err(e) = std::fs::remove_dir_all(&path) {
+                log::warn!(
+                    "Failed to remove stale undo spill {}: {}",
+                    path.display(),
+                    e
+                );
+            }
+        }
+        Self {
+            path,
+            threshold: RwLock::new(Some(DEFAULT_UNDO_SPILL_THRESHOLD)),
+            driver: Mutex::new(None),
+        }
+    }
+
+    /// Undo records a log holds in memory before spilling them
+    pub fn threshold(&self) -> Option<usize> {
+        *self.threshold.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Change the spill threshold; `None` keeps undo records in memory
+    pub fn set_threshold(&self, threshold: Option<usize>) {
+        *self.threshold.write().unwrap_or_else(|e| e.into_inner()) = threshold.map(|t| t.max(1));
+    }
+
+    /// Whether a log holding `in_memory` records should spill them
+    pub(crate) fn should_spill(&self, in_memory: usize) -> bool {
+        self.threshold().is_some_and(|t| in_memory >= t)
+    }
+
+    /// The tree holding the spilled records of `txn_id`, created if needed
+    pub(crate) fn tree(
+        &self,
+        txn_id: TransactionId,
+    ) -> Result<Box<dyn StorageTree>, ExecutionError> {
+        let path = &self.path;
+        let mut driver = self.driver.lock().unwrap_or_else(|e| e.into_inner());
+        if driver.is_none() {
+            let opened = create_storage_driver(StorageType::Sled, path).map_err(|e| {
+                ExecutionError::StorageError(format!(
+                    "Failed to open undo spill {}: {}",
+                    path.display(),
+                    e
+                ))
+            })?;
+            *driver = Some(opened);
+        }
+        let driver = driver.as_ref().expect("spill driver opened above");
+        driver.open_tree(&tree_name(txn_id)).map_err(|e| {
+            ExecutionError::StorageError(format!("Failed to open undo spill tree: {}", e))
+        })
+    }
+
+    /// Drop the spilled records of `txn_id`
+    pub(crate) fn discard(&self, txn_id: TransactionId) {
+        let driver = self.driver.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(driver) = driver.as_ref() {
+            if let Err(e) = driver.drop_index(&tree_name(txn_id)) {
+                log::warn!("Failed to drop undo spill of {}: {}", txn_id, e);
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for UndoSpill {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UndoSpill")
+            .field("path", &self.path)
+            .field("threshold", &self.threshold())
+            .finish()
+    }
+}
+
+impl Drop for UndoSpill {
+    fn drop(&mut self) {
+        let opened = self
+            .driver
+            .get_mut()
+            .unwrap_or_else(|e| e.into_inner())
+            .take()
+            .is_some();
+        if opened {
+            let _ = std::fs::remove_dir_all(&self.path);
+        }
+    }
+}
+
+fn tree_name(txn_id: TransactionId) -> String {
+    format!("undo_{}", txn_id.id())
+}
+
+/// Key of the record at `position` in a spill tree; big-endian so keys sort by position
+pub(crate) fn spill_key(position: usize) -> [u8; 8] {
+    (position as u64).to_be_bytes()
+}
+
+pub(crate) fn encode(operation: &UndoOperation) -> Result<Vec<u8>, ExecutionError> {
+    bincode::serialize(operation)
+        .map_err(|e| ExecutionError::StorageError(format!("Failed to spill undo record: {}", e)))
+}
+
+pub(crate) fn decode(bytes: &[u8]) -> Result<UndoOperation, ExecutionError> {
+    bincode::deserialize(bytes).map_err(|e| {
+        ExecutionError::StorageError(format!("Failed to read spilled undo record: {}", e))
+    })
+}
@@ -8,9 +8,10 @@
 
 use std::fs::{create_dir_all, File, OpenOptions};
 use std::io::{BufReader, BufWriter, Read, Seek, SeekFrom, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use super::state::{OperationType, TransactionId};
 
@@ -29,6 +30,8 @@ pub enum WALEntryType {
     Operation = 2,
     Commit = 3,
     Rollback = 4,
+    /// GQL text of a write statement, read back by logical decoding (see `txn::decode`)
+    Statement = 5,
 }
 
 /// A single WAL entry that gets written to disk
@@ -65,6 +68,8 @@ pub struct PersistentWAL {
     current_file_path: Arc<Mutex<Option<PathBuf>>>,
     /// Current file size
     current_file_size: Arc<Mutex<u64>>,
+    /// Microseconds the most recent flush and sync took
+    last_flush_micros: AtomicU64,
     /// Separate catalog WAL for faster recovery
     catalog_wal: Option<Arc<CatalogWAL>>,
 }
@@ -225,6 +230,7 @@ impl WALEntry {
             2 => WALEntryType::Operation,
             3 => WALEntryType::Commit,
             4 => WALEntryType::Rollback,
+            5 => WALEntryType::Statement,
             _ => return Err(WALError::CorruptedEntry("Invalid entry type".to_string())),
         };
         offset += 1;
@@ -418,6 +424,7 @@ impl PersistentWAL {
             global_sequence: Arc::new(Mutex::new(0)),
             current_file_path: Arc::new(Mutex::new(None)),
             current_file_size: Arc::new(Mutex::new(0)),
+            last_flush_micros: AtomicU64::new(0),
             catalog_wal: Some(Arc::new(catalog_wal)),
         };
 
@@ -490,6 +497,7 @@ impl PersistentWAL {
                     .map_err(|e| WALError::IOError(format!("Failed to write WAL entry: {}", e)))?;
 
                 // Force write to disk for durability
+                let flush_started = Instant::now();
                 writer
                     .flush()
                     .map_err(|e| WALError::IOError(format!("Failed to flush WAL: {}", e)))?;
@@ -499,6 +507,10 @@ impl PersistentWAL {
                     .get_mut()
                     .sync_data()
                     .map_err(|e| WALError::IOError(format!("Failed to sync WAL: {}", e)))?;
+                self.last_flush_micros.store(
+                    flush_started.elapsed().as_micros() as u64,
+                    Ordering::Relaxed,
+                );
 
                 // Update file size
                 *self.current_file_size.lock().unwrap() += serialized.len() as u64;
@@ -517,6 +529,16 @@ impl PersistentWAL {
         Ok(())
     }
 
+    /// Directory holding the WAL files
+    pub fn wal_dir(&self) -> &Path {
+        &self.wal_dir
+    }
+
+    /// Time the most recent entry took to flush and sync to disk
+    pub fn last_flush_duration(&self) -> Duration {
+        Duration::from_micros(self.last_flush_micros.load(Ordering::Relaxed))
+    }
+
     /// Mark a transaction as committed
     #[allow(dead_code)] // ROADMAP v0.3.0 - WAL commit marker for transaction durability
     pub fn mark_committed(&self, transaction_id: TransactionId) -> Result<(), WALError> {
@@ -653,77 +675,80 @@ impl PersistentWAL {
     /// Read all entries from a specific WAL file
     pub fn read_wal_file(&self, file_number: u64) -> Result<Vec<WALEntry>, WALError> {
         let filename = format!("wal_{:06}.log", file_number);
-        let file_path = self.wal_dir.join(&filename);
-
-        if !file_path.exists() {
-            return Err(WALError::IOError(format!(
-                "WAL file not found: {}",
-                file_path.display()
-            )));
-        }
-
-        let mut file = File::open(&file_path)
-            .map_err(|e| WALError::IOError(format!("Failed to open WAL file: {}", e)))?;
-
-        // Skip file header (64 bytes)
-        file.seek(SeekFrom::Start(64))
-            .map_err(|e| WALError::IOError(format!("Failed to seek in WAL file: {}", e)))?;
-
-        let mut reader = BufReader::new(file);
-        let mut entries = Vec::new();
-        let mut buffer = Vec::new();
+        read_wal_entries(&self.wal_dir.join(filename))
+    }
+}
 
-        // Read entire file
-        reader
-            .read_to_end(&mut buffer)
-            .map_err(|e| WALError::IOError(format!("Failed to read WAL file: {}", e)))?;
+/// Read all entries from a WAL file, skipping corrupted entries
+pub fn read_wal_entries(file_path: &Path) -> Result<Vec<WALEntry>, WALError> {
+    if !file_path.exists() {
+        return Err(WALError::IOError(format!(
+            "WAL file not found: {}",
+            file_path.display()
+        )));
+    }
 
-        let mut offset = 0;
-        while offset < buffer.len() {
-            // Try to find next entry by looking for magic number
-            if offset + 4 <= buffer.len() {
-                let magic = u32::from_le_bytes([
-                    buffer[offset],
-                    buffer[offset + 1],
-                    buffer[offset + 2],
-                    buffer[offset + 3],
-                ]);
-
-                if magic == WAL_MAGIC {
-                    // Read the minimum size to determine actual entry size
-                    if offset + 50 <= buffer.len() {
-                        // Try to deserialize starting from this offset
-                        // The deserializer should handle determining the actual size
-                        match WALEntry::deserialize(&buffer[offset..]) {
-                            Ok(entry) => {
-                                // Calculate the actual size of this entry
-                                let entry_bytes = entry.serialize();
-                                let entry_size = entry_bytes.len();
-
-                                entries.push(entry);
-                                // Move offset past this entry
-                                offset += entry_size;
-                                continue;
-                            }
-                            Err(_) => {
-                                // Skip this corrupted entry
-                                offset += 1;
-                            }
+    let mut file = File::open(file_path)
+        .map_err(|e| WALError::IOError(format!("Failed to open WAL file: {}", e)))?;
+
+    // Skip file header (64 bytes)
+    file.seek(SeekFrom::Start(64))
+        .map_err(|e| WALError::IOError(format!("Failed to seek in WAL file: {}", e)))?;
+
+    let mut reader = BufReader::new(file);
+    let mut entries = Vec::new();
+    let mut buffer = Vec::new();
+
+    // Read entire file
+    reader
+        .read_to_end(&mut buffer)
+        .map_err(|e| WALError::IOError(format!("Failed to read WAL file: {}", e)))?;
+
+    let mut offset = 0;
+    while offset < buffer.len() {
+        // Try to find next entry by looking for magic number
+        if offset + 4 <= buffer.len() {
+            let magic = u32::from_le_bytes([
+                buffer[offset],
+                buffer[offset + 1],
+                buffer[offset + 2],
+                buffer[offset + 3],
+            ]);
+
+            if magic == WAL_MAGIC {
+                // Read the minimum size to determine actual entry size
+                if offset + 50 <= buffer.len() {
+                    // Try to deserialize starting from this offset
+                    // The deserializer should handle determining the actual size
+                    match WALEntry::deserialize(&buffer[offset..]) {
+                        Ok(entry) => {
+                            // Calculate the actual size of this entry
+                            let entry_bytes = entry.serialize();
+                            let entry_size = entry_bytes.len();
+
+                            entries.push(entry);
+                            // Move offset past this entry
+                            offset += entry_size;
+                            continue;
+                        }
+                        Err(_) => {
+                            // Skip this corrupted entry
+                            offset += 1;
                         }
-                    } else {
-                        // Not enough data for a complete entry
-                        break;
                     }
                 } else {
-                    offset += 1;
+                    // Not enough data for a complete entry
+                    break;
                 }
             } else {
-                break;
+                offset += 1;
             }
+        } else {
+            break;
         }
-
-        Ok(entries)
     }
+
+    Ok(entries)
 }
 
 /// WAL-specific errors
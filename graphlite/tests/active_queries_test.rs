@@ -0,0 +1,100 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! Tests for `gql.active_queries` and `QueryCoordinator::active_queries`
+
+use graphlite::{BulkNode, QueryCoordinator, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+const QUERY: &str = "CALL gql.active_queries()";
+
+#[test]
+fn test_active_queries_lists_the_running_call() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let coordinator =
+        QueryCoordinator::from_path(temp_dir.path().join("active_db")).expect("Failed to open");
+    let session_id = coordinator.create_simple_session("admin").unwrap();
+    assert!(coordinator.active_queries().is_empty());
+
+    let result = coordinator.process_query(QUERY, &session_id).unwrap();
+    assert_eq!(
+        result.variables,
+        [
+            "query_id",
+            "session_id",
+            "fingerprint",
+            "query",
+            "started_at",
+            "elapsed_ms",
+            "operator"
+        ]
+    );
+    assert_eq!(result.rows.len(), 1);
+    let row = &result.rows[0];
+    assert_eq!(
+        row.get_value("session_id"),
+        Some(&Value::String(session_id.clone()))
+    );
+    assert_eq!(
+        row.get_value("fingerprint"),
+        Some(&Value::String(QueryCoordinator::query_fingerprint(QUERY)))
+    );
+    assert_eq!(
+        row.get_value("query"),
+        Some(&Value::String(QUERY.to_string()))
+    );
+
+    // Finished statements are no longer listed
+    assert!(coordinator.active_queries().is_empty());
+}
+
+#[test]
+fn test_active_queries_reports_the_running_operator() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let coordinator =
+        QueryCoordinator::from_path(temp_dir.path().join("active_db")).expect("Failed to open");
+    let session_id = coordinator.create_simple_session("admin").unwrap();
+    for query in [
+        "CREATE SCHEMA /active",
+        "CREATE GRAPH /active/g",
+        "SESSION SET GRAPH /active/g",
+    ] {
+        coordinator.process_query(query, &session_id).unwrap();
+    }
+    let nodes = (0..200)
+        .map(|i| BulkNode {
+            labels: vec!["Item".to_string()],
+            properties: HashMap::from([("n".to_string(), Value::Number(i as f64))]),
+        })
+        .collect();
+    coordinator.bulk_insert_nodes(&session_id, nodes).unwrap();
+
+    // A cross product slow enough to be caught while it runs
+    let slow = "MATCH (a:Item), (b:Item) RETURN count(a) AS pairs";
+    let worker = {
+        let coordinator = Arc::clone(&coordinator);
+        let session_id = session_id.clone();
+        std::thread::spawn(move || coordinator.process_query(slow, &session_id))
+    };
+
+    let mut seen = None;
+    while !worker.is_finished() {
+        if let Some(query) = coordinator
+            .active_queries()
+            .into_iter()
+            .find(|query| query.operator.is_some())
+        {
+            seen = Some(query);
+            break;
+        }
+        std::thread::sleep(Duration::from_millis(1));
+    }
+    worker.join().unwrap().unwrap();
+
+    let query = seen.expect("The running query was never listed with an operator");
+    assert_eq!(query.query, slow);
+    assert_eq!(query.session_id, session_id);
+    assert!(coordinator.active_queries().is_empty());
+}
@@ -1012,3 +1012,279 @@ fn test_labels_aggregation_with_multiple_labels_and_order_by() {
         }
     }
 }
+
+#[test]
+fn test_group_by_rollup_and_grouping_sets() {
+    let fixture = TestFixture::new().expect("Failed to create test fixture");
+    fixture
+        .setup_graph("test_group_by_rollup_and_grouping_sets")
+        .expect("Failed to setup graph");
+    for (target, compound, series, ic50) in [
+        ("EGFR", "cmp-1", "A", 10),
+        ("EGFR", "cmp-2", "B", 30),
+        ("EGFR", "cmp-3", "A", 20),
+        ("KRAS", "cmp-4", "A", 80),
+    ] {
+        fixture.assert_query_succeeds(&format!(
+            "INSERT (:Target {{name: '{}'}})-[:INHIBITS {{IC50: {}}}]->(:Compound {{name: '{}', series: '{}'}})",
+            target, ic50, compound, series
+        ));
+    }
+
+    // Per-target averages plus the overall average in one query
+    let result = fixture.assert_query_succeeds(
+        "MATCH (t:Target)-[i:INHIBITS]->(c:Compound)
+         RETURN t.name, AVG(i.IC50) AS avg_ic50, COUNT(c) AS compounds
+         GROUP BY ROLLUP (t.name)",
+    );
+    let summary: Vec<(Value, Value, Value)> = result
+        .rows
+        .iter()
+        .map(|row| {
+            (
+                row.values["t.name"].clone(),
+                row.values["avg_ic50"].clone(),
+                row.values["compounds"].clone(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            (
+                Value::String("EGFR".to_string()),
+                Value::Number(20.0),
+                Value::Number(3.0)
+            ),
+            (
+                Value::String("KRAS".to_string()),
+                Value::Number(80.0),
+                Value::Number(1.0)
+            ),
+            (Value::Null, Value::Number(35.0), Value::Number(4.0)),
+        ]
+    );
+
+    // Multi-column ROLLUP adds a subtotal per target before the grand total
+    let result = fixture.assert_query_succeeds(
+        "MATCH (t:Target)-[i:INHIBITS]->(c:Compound)
+         RETURN t.name, c.series, COUNT(c) AS compounds
+         GROUP BY ROLLUP (t.name, c.series)",
+    );
+    let groups: Vec<(Value, Value, Value)> = result
+        .rows
+        .iter()
+        .map(|row| {
+            (
+                row.values["t.name"].clone(),
+                row.values["c.series"].clone(),
+                row.values["compounds"].clone(),
+            )
+        })
+        .collect();
+    let text = |s: &str| Value::String(s.to_string());
+    assert_eq!(
+        groups,
+        [
+            (text("EGFR"), text("A"), Value::Number(2.0)),
+            (text("EGFR"), text("B"), Value::Number(1.0)),
+            (text("KRAS"), text("A"), Value::Number(1.0)),
+            (text("EGFR"), Value::Null, Value::Number(3.0)),
+            (text("KRAS"), Value::Null, Value::Number(1.0)),
+            (Value::Null, Value::Null, Value::Number(4.0)),
+        ]
+    );
+
+    // GROUPING SETS combined with a plain key: per (target, series) and per target
+    let result = fixture.assert_query_succeeds(
+        "MATCH (t:Target)-[i:INHIBITS]->(c:Compound)
+         RETURN t.name, c.series, MIN(i.IC50) AS best
+         GROUP BY t.name, GROUPING SETS ((c.series), ())",
+    );
+    assert_eq!(result.rows.len(), 5);
+    assert_eq!(
+        result
+            .rows
+            .iter()
+            .filter(|row| row.values["c.series"] == Value::Null)
+            .count(),
+        2
+    );
+
+    // The grand total of an empty input still counts zero
+    let result = fixture.assert_query_succeeds(
+        "MATCH (t:Target)-[i:INHIBITS]->(c:Compound)
+         WHERE i.IC50 > 1000
+         RETURN t.name, COUNT(c) AS compounds
+         GROUP BY ROLLUP (t.name)",
+    );
+    assert_eq!(result.rows.len(), 1);
+    assert_eq!(result.rows[0].values["t.name"], Value::Null);
+    assert_eq!(result.rows[0].values["compounds"], Value::Number(0.0));
+}
+
+#[test]
+fn test_having_uses_post_aggregation_values() {
+    let fixture = TestFixture::new().expect("Failed to create test fixture");
+    fixture
+        .setup_graph("test_having_uses_post_aggregation_values")
+        .expect("Failed to setup graph");
+    for (target, compound, ic50) in [
+        ("EGFR", "cmp-1", 10),
+        ("EGFR", "cmp-2", 30),
+        ("EGFR", "cmp-3", 20),
+        ("KRAS", "cmp-4", 80),
+        ("KRAS", "cmp-5", 20),
+        ("KRAS", "cmp-6", 50),
+        ("BRAF", "cmp-7", 5),
+    ] {
+        fixture.assert_query_succeeds(&format!(
+            "INSERT (:Target {{name: '{}'}})-[:INHIBITS {{IC50: {}}}]->(:Compound {{name: '{}'}})",
+            target, ic50, compound
+        ));
+    }
+    let targets = |query: &str| -> Vec<Value> {
+        let result = fixture.assert_query_succeeds(query);
+        assert!(
+            result.variables.iter().all(|name| !name.starts_with("__")),
+            "Hidden HAVING columns leaked into {:?}",
+            result.variables
+        );
+        let mut names: Vec<Value> = result
+            .rows
+            .iter()
+            .map(|row| {
+                assert!(row.values.keys().all(|name| !name.starts_with("__")));
+                row.values["t.name"].clone()
+            })
+            .collect();
+        names.sort_by_key(|name| name.to_string());
+        names
+    };
+    let text = |s: &str| Value::String(s.to_string());
+
+    // Aggregates that RETURN does not compute
+    assert_eq!(
+        targets(
+            "MATCH (t:Target)-[i:INHIBITS]->(c:Compound)
+             RETURN t.name, MIN(i.IC50) AS best
+             GROUP BY t.name
+             HAVING COUNT(c) > 2 AND AVG(i.IC50) < 50"
+        ),
+        [text("EGFR")]
+    );
+
+    // Aggregate aliases and aggregate expressions combined
+    assert_eq!(
+        targets(
+            "MATCH (t:Target)-[i:INHIBITS]->(c:Compound)
+             RETURN t.name, COUNT(c) AS compounds, AVG(i.IC50) AS avg_ic50
+             GROUP BY t.name
+             HAVING compounds > 2 AND SUM(i.IC50) / compounds > 25"
+        ),
+        [text("KRAS")]
+    );
+
+    // Arguments distinguish aggregates of the same function
+    assert_eq!(
+        targets(
+            "MATCH (t:Target)-[i:INHIBITS]->(c:Compound)
+             RETURN t.name, MAX(c.name) AS last_compound
+             GROUP BY t.name
+             HAVING MAX(i.IC50) >= 50"
+        ),
+        [text("KRAS")]
+    );
+
+    // Grouping key aliases, with no aggregate in RETURN
+    let result = fixture.assert_query_succeeds(
+        "MATCH (t:Target)-[i:INHIBITS]->(c:Compound)
+         RETURN t.name AS target
+         GROUP BY target
+         HAVING target <> 'BRAF' AND MAX(i.IC50) < 50",
+    );
+    assert_eq!(result.rows.len(), 1);
+    assert_eq!(result.rows[0].values["target"], text("EGFR"));
+}
+
+#[test]
+fn test_collect_with_order_by_and_limit() {
+    let fixture = TestFixture::new().expect("Failed to create test fixture");
+    fixture
+        .setup_graph("test_collect_with_order_by_and_limit")
+        .expect("Failed to setup graph");
+    let activities: Vec<String> = [
+        ("alice", "login", "2024-01-03"),
+        ("alice", "purchase", "2024-01-07"),
+        ("alice", "review", "2024-01-05"),
+        ("alice", "logout", "2024-01-01"),
+        ("bob", "signup", "2024-02-01"),
+    ]
+    .iter()
+    .map(|(person, activity, date)| {
+        format!(
+            "({})-[:DID]->(:Activity {{name: '{}', date: '{}'}})",
+            person, activity, date
+        )
+    })
+    .collect();
+    fixture.assert_query_succeeds(&format!(
+        "INSERT (alice:Person {{name: 'Alice'}}), (bob:Person {{name: 'Bob'}}), {}",
+        activities.join(", ")
+    ));
+    let list = |items: &[&str]| {
+        Value::List(
+            items
+                .iter()
+                .map(|item| Value::String(item.to_string()))
+                .collect(),
+        )
+    };
+    let latest_by_person = |query: &str, column: &str| -> HashMap<String, Value> {
+        fixture
+            .assert_query_succeeds(query)
+            .rows
+            .iter()
+            .map(|row| {
+                let name = match &row.values["p.name"] {
+                    Value::String(name) => name.clone(),
+                    other => panic!("Expected person name, got {:?}", other),
+                };
+                (name, row.values[column].clone())
+            })
+            .collect()
+    };
+
+    // Latest two activities per person in RETURN
+    let latest = latest_by_person(
+        "MATCH (p:Person)-[:DID]->(a:Activity)
+         RETURN p.name, COLLECT(a.name ORDER BY a.date DESC LIMIT 2) AS latest
+         GROUP BY p.name",
+        "latest",
+    );
+    assert_eq!(latest["Alice"], list(&["purchase", "review"]));
+    assert_eq!(latest["Bob"], list(&["signup"]));
+
+    // And in WITH, next to an unordered aggregate
+    let latest = latest_by_person(
+        "MATCH (p:Person)-[:DID]->(a:Activity)
+         WITH p, COLLECT(a.name ORDER BY a.date DESC LIMIT 3) AS latest, COUNT(a) AS total
+         RETURN p.name, latest, total",
+        "latest",
+    );
+    assert_eq!(latest["Alice"], list(&["purchase", "review", "login"]));
+
+    // Over all rows, ascending without a limit
+    fixture.assert_first_value(
+        "MATCH (:Person {name: 'Alice'})-[:DID]->(a:Activity)
+         RETURN COLLECT(a.name ORDER BY a.date) AS timeline",
+        "timeline",
+        list(&["logout", "login", "review", "purchase"]),
+    );
+
+    // Only COLLECT is ordered
+    fixture.assert_query_fails(
+        "MATCH (a:Activity) RETURN COUNT(a ORDER BY a.date) AS n",
+        "Parse error",
+    );
+}
@@ -0,0 +1,154 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! Tests for online backup, restore and snapshot export
+
+use graphlite::{DatabaseConfig, QueryCoordinator, StorageType, Value};
+use std::path::Path;
+use std::sync::Arc;
+
+fn populate(coordinator: &QueryCoordinator, session_id: &str) {
+    for query in [
+        "CREATE SCHEMA /backup",
+        "CREATE GRAPH /backup/g",
+        "SESSION SET GRAPH /backup/g",
+        "INSERT (:Item {i: 1}), (:Item {i: 2})",
+    ] {
+        coordinator.process_query(query, session_id).unwrap();
+    }
+}
+
+fn item_count(path: &Path) -> f64 {
+    let coordinator = QueryCoordinator::from_path(path).expect("Failed to open copy");
+    let session_id = coordinator.create_simple_session("admin").unwrap();
+    coordinator
+        .process_query("SESSION SET GRAPH /backup/g", &session_id)
+        .unwrap();
+    let result = coordinator
+        .process_query("MATCH (n:Item) RETURN count(n) AS items", &session_id)
+        .unwrap();
+    match result.rows[0].values.get("items") {
+        Some(Value::Number(n)) => *n,
+        other => panic!("unexpected count: {:?}", other),
+    }
+}
+
+#[test]
+fn test_backup_open_database_and_restore() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let backup_path = temp_dir.path().join("backup");
+    let restored_path = temp_dir.path().join("restored");
+
+    let coordinator: Arc<QueryCoordinator> =
+        QueryCoordinator::from_path(temp_dir.path().join("live")).expect("Failed to open");
+    let session_id = coordinator.create_simple_session("admin").unwrap();
+    populate(&coordinator, &session_id);
+
+    assert!(coordinator.backup(&backup_path).unwrap() > 0);
+    // The live database keeps working after the backup
+    coordinator
+        .process_query("INSERT (:Item {i: 3})", &session_id)
+        .unwrap();
+    assert!(coordinator
+        .backup(&backup_path)
+        .unwrap_err()
+        .contains("not empty"));
+
+    coordinator
+        .process_query("START TRANSACTION", &session_id)
+        .unwrap();
+    assert!(coordinator
+        .backup(temp_dir.path().join("during_txn"))
+        .unwrap_err()
+        .contains("transactions are open"));
+    coordinator.process_query("ROLLBACK", &session_id).unwrap();
+
+    QueryCoordinator::restore_backup(&backup_path, &restored_path).unwrap();
+    assert_eq!(item_count(&restored_path), 2.0);
+    assert!(QueryCoordinator::restore_backup(&backup_path, &restored_path).is_err());
+    assert!(
+        QueryCoordinator::restore_backup(temp_dir.path().join("missing"), &restored_path)
+            .unwrap_err()
+            .contains("No backup found")
+    );
+}
+
+#[test]
+fn test_backup_memory_database() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let backup_path = temp_dir.path().join("backup");
+
+    let coordinator = QueryCoordinator::from_path_with_config(
+        temp_dir.path().join("memory"),
+        DatabaseConfig {
+            storage_type: StorageType::Memory,
+            ..DatabaseConfig::default()
+        },
+    )
+    .expect("Failed to open");
+    let session_id = coordinator.create_simple_session("admin").unwrap();
+    populate(&coordinator, &session_id);
+
+    coordinator.backup(&backup_path).unwrap();
+    drop(coordinator);
+    assert_eq!(item_count(&backup_path), 2.0);
+}
+
+#[test]
+fn test_export_snapshot_opens_read_only() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let snapshot_path = temp_dir.path().join("snapshot");
+
+    let coordinator: Arc<QueryCoordinator> =
+        QueryCoordinator::from_path(temp_dir.path().join("live")).expect("Failed to open");
+    let session_id = coordinator.create_simple_session("admin").unwrap();
+    populate(&coordinator, &session_id);
+
+    // Writes keep arriving while the snapshot is taken
+    let writer = {
+        let coordinator = coordinator.clone();
+        let session_id = session_id.clone();
+        std::thread::spawn(move || {
+            for i in 3..23 {
+                coordinator
+                    .process_query(&format!("INSERT (:Item {{i: {}}})", i), &session_id)
+                    .unwrap();
+            }
+        })
+    };
+    assert!(coordinator.export_snapshot(&snapshot_path).unwrap() > 0);
+    writer.join().unwrap();
+    assert!(!coordinator.is_read_only());
+
+    {
+        let snapshot = QueryCoordinator::from_path(&snapshot_path).expect("Failed to open");
+        assert!(snapshot.is_read_only());
+        let session_id = snapshot.create_simple_session("analyst").unwrap();
+        snapshot
+            .process_query("SESSION SET GRAPH /backup/g", &session_id)
+            .unwrap();
+        assert!(snapshot
+            .process_query("INSERT (:Item {i: 100})", &session_id)
+            .unwrap_err()
+            .contains("read-only"));
+    }
+    let items = item_count(&snapshot_path);
+    assert!((2.0..=22.0).contains(&items), "{} items", items);
+
+    // Restoring a snapshot gives a writable database
+    let restored_path = temp_dir.path().join("restored");
+    QueryCoordinator::restore_backup(&snapshot_path, &restored_path).unwrap();
+    let restored = QueryCoordinator::from_path(&restored_path).expect("Failed to open");
+    assert!(!restored.is_read_only());
+    drop(restored);
+    assert_eq!(item_count(&restored_path), items);
+
+    coordinator
+        .process_query("START TRANSACTION", &session_id)
+        .unwrap();
+    assert!(coordinator
+        .export_snapshot(temp_dir.path().join("during_txn"))
+        .unwrap_err()
+        .contains("transactions are open"));
+    coordinator.process_query("ROLLBACK", &session_id).unwrap();
+}
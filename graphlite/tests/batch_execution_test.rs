@@ -0,0 +1,159 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! Tests for `QueryCoordinator::process_batch` and `QueryCoordinator::process_queries`
+
+use graphlite::{QueryCoordinator, Value};
+use std::sync::Arc;
+
+fn open_with_graph(dir: &tempfile::TempDir) -> (Arc<QueryCoordinator>, String) {
+    let coordinator =
+        QueryCoordinator::from_path(dir.path().join("batch_db")).expect("Failed to open database");
+    let session_id = coordinator.create_simple_session("admin").unwrap();
+    for query in [
+        "CREATE SCHEMA /batch",
+        "CREATE GRAPH /batch/g",
+        "SESSION SET GRAPH /batch/g",
+    ] {
+        coordinator.process_query(query, &session_id).unwrap();
+    }
+    (coordinator, session_id)
+}
+
+fn count_people(coordinator: &QueryCoordinator, session_id: &str) -> Option<Value> {
+    let result = coordinator
+        .process_query("MATCH (p:Person) RETURN count(p) AS people", session_id)
+        .unwrap();
+    result.rows[0].values.get("people").cloned()
+}
+
+#[test]
+fn test_batch_commits_all_statements() {
+    let dir = tempfile::tempdir().unwrap();
+    let (coordinator, session_id) = open_with_graph(&dir);
+
+    let results = coordinator
+        .process_batch(
+            &[
+                "INSERT (:Person {name: 'Alice'})",
+                "INSERT (:Person {name: 'Bob'})",
+                "MATCH (p:Person) RETURN count(p) AS people",
+            ],
+            &session_id,
+        )
+        .expect("Batch should succeed");
+
+    assert_eq!(results.len(), 3);
+    assert_eq!(
+        results[2].rows[0].values.get("people"),
+        Some(&Value::Number(2.0))
+    );
+    assert_eq!(
+        count_people(&coordinator, &session_id),
+        Some(Value::Number(2.0))
+    );
+    assert!(coordinator
+        .process_batch(&[], &session_id)
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn test_batch_rolls_back_on_failure() {
+    let dir = tempfile::tempdir().unwrap();
+    let (coordinator, session_id) = open_with_graph(&dir);
+
+    let err = coordinator
+        .process_batch(
+            &[
+                "INSERT (:Person {name: 'Alice'})",
+                "MATCH (p:Person) RETURN p.name + 1 AS broken",
+                "INSERT (:Person {name: 'Bob'})",
+            ],
+            &session_id,
+        )
+        .unwrap_err();
+    assert_eq!(err.index, 1, "{}", err);
+    assert_eq!(
+        count_people(&coordinator, &session_id),
+        Some(Value::Number(0.0))
+    );
+
+    // Parse errors are reported before anything runs
+    let err = coordinator
+        .process_batch(
+            &["INSERT (:Person {name: 'Alice'})", "INSERT (invalid syntax"],
+            &session_id,
+        )
+        .unwrap_err();
+    assert_eq!(err.index, 1);
+    assert!(err.message.contains("Parse error"), "{}", err.message);
+
+    let err = coordinator
+        .process_batch(&["INSERT (:Person {name: 'Alice'})", "COMMIT"], &session_id)
+        .unwrap_err();
+    assert_eq!(err.index, 1);
+    assert_eq!(
+        count_people(&coordinator, &session_id),
+        Some(Value::Number(0.0))
+    );
+
+    // The session is usable again after a failed batch
+    coordinator
+        .process_batch(&["INSERT (:Person {name: 'Carol'})"], &session_id)
+        .unwrap();
+    assert_eq!(
+        count_people(&coordinator, &session_id),
+        Some(Value::Number(1.0))
+    );
+}
+
+#[test]
+fn test_batch_rejects_open_transaction() {
+    let dir = tempfile::tempdir().unwrap();
+    let (coordinator, session_id) = open_with_graph(&dir);
+
+    coordinator
+        .process_query("START TRANSACTION", &session_id)
+        .unwrap();
+    let err = coordinator
+        .process_batch(&["INSERT (:Person {name: 'Alice'})"], &session_id)
+        .unwrap_err();
+    assert!(
+        err.message.contains("already in progress"),
+        "{}",
+        err.message
+    );
+    coordinator.process_query("ROLLBACK", &session_id).unwrap();
+}
+
+#[test]
+fn test_independent_queries_report_each_result() {
+    let dir = tempfile::tempdir().unwrap();
+    let (coordinator, session_id) = open_with_graph(&dir);
+
+    let results = coordinator.process_queries(
+        &[
+            "INSERT (:Person {name: 'Alice'})",
+            "MATCH (p RETURN p",
+            "INSERT (:Person {name: 'Bob'})",
+            "MATCH (p:Person) RETURN count(p) AS people",
+        ],
+        &session_id,
+    );
+    assert_eq!(results.len(), 4);
+    assert!(results[0].is_ok());
+    assert!(results[1].as_ref().unwrap_err().contains("Parse error"));
+
+    // A failing query does not undo or stop the others
+    assert_eq!(
+        results[3].as_ref().unwrap().rows[0].values.get("people"),
+        Some(&Value::Number(2.0))
+    );
+    assert!(coordinator.process_queries(&[], &session_id).is_empty());
+
+    let results = coordinator.process_queries(&["MATCH (p) RETURN p", "RETURN 1"], "missing");
+    assert!(results
+        .iter()
+        .all(|result| result.as_ref().unwrap_err().contains("Session not found")));
+}
@@ -0,0 +1,99 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! Tests for gql.warm_cache and warming the storage cache on startup
+
+#[path = "testutils/mod.rs"]
+mod testutils;
+use graphlite::{QueryCoordinator, QueryResult, Value};
+use testutils::test_fixture::TestFixture;
+
+fn storage_cache_entries(result: &QueryResult) -> f64 {
+    result
+        .rows
+        .iter()
+        .find(|row| {
+            row.values.get("cache_type") == Some(&Value::String("storage_cache".to_string()))
+        })
+        .and_then(|row| match row.values.get("entries") {
+            Some(Value::Number(entries)) => Some(*entries),
+            _ => None,
+        })
+        .expect("storage_cache row missing")
+}
+
+#[test]
+fn test_warm_cache_procedure() {
+    let fixture = TestFixture::empty().expect("Failed to create fixture");
+    fixture.setup_graph("warm_cache_graph").unwrap();
+    fixture.assert_query_succeeds(
+        "INSERT (:Person {name: 'ann'})-[:KNOWS]->(:Person {name: 'bob'}), (:City {name: 'oslo'})",
+    );
+
+    fixture.assert_query_succeeds("CALL gql.clear_cache('storage_cache')");
+    let result = fixture.assert_query_succeeds("CALL gql.warm_cache('warm_cache_graph')");
+    assert_eq!(result.rows.len(), 1);
+    let row = &result.rows[0];
+    assert_eq!(row.values.get("nodes"), Some(&Value::Number(3.0)));
+    assert_eq!(row.values.get("edges"), Some(&Value::Number(1.0)));
+    assert_eq!(row.values.get("node_labels"), Some(&Value::Number(2.0)));
+    assert_eq!(row.values.get("edge_labels"), Some(&Value::Number(1.0)));
+
+    let stats = fixture.assert_query_succeeds("CALL gql.cache_stats()");
+    assert_eq!(storage_cache_entries(&stats), 1.0);
+
+    fixture.assert_query_fails(
+        "CALL gql.warm_cache('/no_such_schema/no_such_graph')",
+        "Graph does not exist",
+    );
+    fixture.assert_query_fails("CALL gql.warm_cache(42)", "must be a string");
+}
+
+#[test]
+fn test_hot_graphs_are_warmed_after_reopen() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let db_path = temp_dir.path().join("warm_start_db");
+
+    {
+        let coordinator = QueryCoordinator::from_path(&db_path).expect("Failed to create database");
+        let session_id = coordinator.create_simple_session("admin").unwrap();
+        for query in [
+            "CREATE SCHEMA /app",
+            "CREATE GRAPH /app/hot",
+            "CREATE GRAPH /app/cold",
+            "CREATE GRAPH /app/dropped",
+            "SESSION SET GRAPH /app/hot",
+            "INSERT (:Item {name: 'a'}), (:Item {name: 'b'})",
+            "MATCH (i:Item) RETURN i.name",
+            "SESSION SET GRAPH /app/dropped",
+            "MATCH (n) RETURN n",
+            "SESSION SET GRAPH /app/hot",
+            "DROP GRAPH /app/dropped",
+        ] {
+            coordinator.process_query(query, &session_id).unwrap();
+        }
+    }
+
+    let coordinator = QueryCoordinator::from_path(&db_path).expect("Failed to reopen database");
+    let session_id = coordinator.create_simple_session("admin").unwrap();
+
+    // The hot graph is back in the storage cache before any query touched it
+    let stats = coordinator
+        .process_query("CALL gql.cache_stats()", &session_id)
+        .unwrap();
+    assert_eq!(storage_cache_entries(&stats), 1.0);
+
+    let result = coordinator
+        .process_query("CALL gql.warm_cache()", &session_id)
+        .unwrap();
+    let warmed: Vec<&Value> = result
+        .rows
+        .iter()
+        .filter_map(|row| row.values.get("graph_name"))
+        .collect();
+    assert_eq!(warmed, vec![&Value::String("/app/hot".to_string())]);
+    assert_eq!(
+        result.rows[0].values.get("nodes"),
+        Some(&Value::Number(2.0))
+    );
+}
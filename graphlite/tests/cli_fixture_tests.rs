@@ -38,3 +38,78 @@ fn test_catalog_persistence() {
     // SESSION SET GRAPH returns empty result on success
     assert!(result2.is_empty() || result2.is_empty() || result2.rows.is_empty());
 }
+
+#[test]
+fn test_migrate_round_trip() {
+    let fixture = CliFixture::empty().expect("Failed to create CLI fixture");
+    let schema_name = fixture.schema_name();
+    let migrations = tempfile::tempdir().unwrap();
+    let write = |name: &str, text: String| std::fs::write(migrations.path().join(name), text);
+    write(
+        "1_graph.up.gql",
+        format!(
+            "CREATE SCHEMA IF NOT EXISTS /{0}; CREATE GRAPH IF NOT EXISTS /{0}/app",
+            schema_name
+        ),
+    )
+    .unwrap();
+    write(
+        "1_graph.down.gql",
+        format!("DROP GRAPH /{}/app", schema_name),
+    )
+    .unwrap();
+    write(
+        "2_seed.up.gql",
+        format!(
+            "SESSION SET GRAPH /{}/app; INSERT (:User {{name: 'alice'}})",
+            schema_name
+        ),
+    )
+    .unwrap();
+    write(
+        "2_seed.down.gql",
+        format!(
+            "SESSION SET GRAPH /{}/app; MATCH (u:User) DELETE u",
+            schema_name
+        ),
+    )
+    .unwrap();
+    let dir = migrations.path().to_str().unwrap();
+
+    let status = fixture.assert_command_succeeds(&["migrate", "status", "--dir", dir]);
+    assert!(
+        status.contains("2 migration(s), 0 applied, 2 pending"),
+        "{}",
+        status
+    );
+
+    let up = fixture.assert_command_succeeds(&["migrate", "up", "--dir", dir]);
+    assert!(up.contains("2 migration(s) applied"), "{}", up);
+    let status = fixture.assert_command_succeeds(&["migrate", "status", "--dir", dir]);
+    assert!(
+        status.contains("2 migration(s), 2 applied, 0 pending"),
+        "{}",
+        status
+    );
+
+    let down = fixture.assert_command_succeeds(&["migrate", "down", "--dir", dir]);
+    assert!(down.contains("Reverted 2 seed"), "{}", down);
+    let status = fixture.assert_command_succeeds(&["migrate", "status", "--dir", dir]);
+    assert!(
+        status.contains("2 migration(s), 1 applied, 1 pending"),
+        "{}",
+        status
+    );
+
+    let up = fixture.assert_command_succeeds(&["migrate", "up", "--dir", dir]);
+    assert!(up.contains("1 migration(s) applied"), "{}", up);
+    let down = fixture.assert_command_succeeds(&["migrate", "down", "--to", "0", "--dir", dir]);
+    assert!(down.contains("2 migration(s) reverted"), "{}", down);
+    let status = fixture.assert_command_succeeds(&["migrate", "status", "--dir", dir]);
+    assert!(
+        status.contains("2 migration(s), 0 applied, 2 pending"),
+        "{}",
+        status
+    );
+    fixture.assert_query_fails(&format!("SESSION SET GRAPH /{}/app", schema_name));
+}
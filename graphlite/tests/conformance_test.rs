@@ -0,0 +1,82 @@
+//! Tests for the GQL conformance corpus and gql.supported_features
+
+#[path = "testutils/mod.rs"]
+mod testutils;
+
+use graphlite::{ConformanceStatus, QueryCoordinator, Value};
+use testutils::test_fixture::TestFixture;
+
+#[test]
+fn test_engine_matches_feature_matrix() {
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let coordinator =
+        QueryCoordinator::from_path(dir.path().join("conformance_db")).expect("Failed to open");
+
+    let report = coordinator.run_conformance(None).expect("Run failed");
+    let mismatches: Vec<String> = report
+        .results
+        .iter()
+        .filter(|r| {
+            matches!(
+                r.status,
+                ConformanceStatus::Failed | ConformanceStatus::UnexpectedPass
+            )
+        })
+        .map(|r| format!("{} ({}): {:?}", r.feature.id, r.status.as_str(), r.message))
+        .collect();
+    assert!(report.is_ok(), "{}", mismatches.join("\n"));
+    assert!(report.count(ConformanceStatus::Passed) > 0);
+
+    // The run cleans up after itself
+    let session = coordinator.create_simple_session("admin").unwrap();
+    let schemas = coordinator
+        .process_query("CALL gql.list_schemas()", &session)
+        .unwrap();
+    assert!(!schemas.rows.iter().any(|row| {
+        row.values.get("schema_name") == Some(&Value::String("conformance".to_string()))
+    }));
+}
+
+#[test]
+fn test_run_conformance_with_filter() {
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let coordinator =
+        QueryCoordinator::from_path(dir.path().join("conformance_db")).expect("Failed to open");
+
+    let report = coordinator.run_conformance(Some("txn.")).unwrap();
+    assert!(!report.results.is_empty());
+    assert!(report
+        .results
+        .iter()
+        .all(|r| r.feature.id.starts_with("txn.")));
+
+    // Running twice reuses nothing from the first run
+    assert!(coordinator.run_conformance(Some("txn.")).unwrap().is_ok());
+}
+
+#[test]
+fn test_supported_features_procedure() {
+    let fixture = TestFixture::new().expect("Failed to create fixture");
+
+    let all = fixture.assert_query_succeeds("CALL gql.supported_features()");
+    assert!(!all.rows.is_empty());
+    let optional = all
+        .rows
+        .iter()
+        .find(|row| {
+            row.values.get("feature_id") == Some(&Value::String("match.optional".to_string()))
+        })
+        .expect("match.optional should be listed");
+    assert!(matches!(
+        optional.values.get("supported"),
+        Some(Value::Boolean(_))
+    ));
+
+    let transactions = fixture.assert_query_succeeds(
+        "CALL gql.supported_features('transactions') YIELD feature_id, supported WHERE supported = true",
+    );
+    assert!(!transactions.rows.is_empty());
+    assert!(transactions.rows.len() < all.rows.len());
+
+    fixture.assert_query_fails("CALL gql.supported_features(1)", "must be a string");
+}
@@ -0,0 +1,92 @@
+#[path = "testutils/mod.rs"]
+mod testutils;
+use graphlite::Value;
+use testutils::test_fixture::TestFixture;
+
+#[test]
+fn test_show_current_graph_reports_session_context() {
+    let fixture = TestFixture::empty().expect("Failed to create test fixture");
+
+    let result = fixture.assert_query_succeeds("SHOW CURRENT GRAPH");
+    assert_eq!(result.variables, vec!["current_graph", "current_schema"]);
+    assert_eq!(result.rows.len(), 1);
+    assert_eq!(
+        result.rows[0].values.get("current_graph"),
+        Some(&Value::Null)
+    );
+
+    fixture.assert_query_succeeds(&format!(
+        "CREATE SCHEMA IF NOT EXISTS /{}",
+        fixture.schema_name()
+    ));
+    let graph_path = format!("/{}/show_current_graph", fixture.schema_name());
+    fixture.assert_query_succeeds(&format!("CREATE GRAPH {}", graph_path));
+    fixture.assert_query_succeeds(&format!("SESSION SET GRAPH {}", graph_path));
+
+    let result = fixture.assert_query_succeeds("SHOW CURRENT PROPERTY GRAPH");
+    assert_eq!(
+        result.rows[0].values.get("current_graph"),
+        Some(&Value::String(graph_path))
+    );
+}
+
+#[test]
+fn test_session_reset_clears_the_session_graph() {
+    let fixture = TestFixture::empty().expect("Failed to create test fixture");
+    fixture.assert_query_succeeds(&format!(
+        "CREATE SCHEMA IF NOT EXISTS /{}",
+        fixture.schema_name()
+    ));
+    let graph_path = format!("/{}/reset_graph", fixture.schema_name());
+    fixture.assert_query_succeeds(&format!("CREATE GRAPH {}", graph_path));
+    let current_graph = || {
+        fixture.assert_query_succeeds("SHOW CURRENT GRAPH").rows[0]
+            .values
+            .get("current_graph")
+            .cloned()
+    };
+
+    for reset in [
+        "SESSION RESET GRAPH",
+        "SESSION RESET PROPERTY GRAPH",
+        "SESSION RESET",
+    ] {
+        fixture.assert_query_succeeds(&format!("SESSION SET GRAPH {}", graph_path));
+        assert_eq!(current_graph(), Some(Value::String(graph_path.clone())));
+        fixture.assert_query_succeeds(reset);
+        assert_eq!(current_graph(), Some(Value::Null), "{}", reset);
+    }
+
+    // Resetting another setting leaves the graph selected
+    fixture.assert_query_succeeds(&format!("SESSION SET GRAPH {}", graph_path));
+    fixture.assert_query_succeeds("SESSION RESET TIME ZONE");
+    assert_eq!(current_graph(), Some(Value::String(graph_path)));
+}
+
+#[test]
+fn test_queries_never_fall_back_to_another_graph() {
+    let fixture = TestFixture::empty().expect("Failed to create test fixture");
+    fixture.assert_query_succeeds(&format!(
+        "CREATE SCHEMA IF NOT EXISTS /{}",
+        fixture.schema_name()
+    ));
+
+    let populated = format!("/{}/populated_graph", fixture.schema_name());
+    fixture.assert_query_succeeds(&format!("CREATE GRAPH {}", populated));
+    fixture.assert_query_succeeds(&format!("SESSION SET GRAPH {}", populated));
+    fixture.assert_query_succeeds("INSERT (:Person {name: 'Alice'})");
+
+    // A FROM clause naming a graph that does not exist must fail instead of
+    // silently reading some other graph.
+    let result = fixture.query(&format!(
+        "SELECT p.name FROM /{}/missing_graph MATCH (p:Person)",
+        fixture.schema_name()
+    ));
+    assert!(result.is_err(), "unknown FROM graph should be rejected");
+
+    // Both sides of a set operation read the session graph.
+    let result = fixture.assert_query_succeeds(
+        "MATCH (p:Person) RETURN p.name UNION ALL MATCH (p:Person) RETURN p.name",
+    );
+    assert_eq!(result.rows.len(), 2);
+}
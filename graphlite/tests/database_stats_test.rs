@@ -0,0 +1,65 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! Tests for `QueryCoordinator::stats`
+
+use graphlite::QueryCoordinator;
+
+#[test]
+fn test_stats_count_graph_contents_and_indexes() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let coordinator =
+        QueryCoordinator::from_path(temp_dir.path().join("stats_db")).expect("Failed to open");
+    let session_id = coordinator.create_simple_session("admin").unwrap();
+    for query in [
+        "CREATE SCHEMA /stats",
+        "CREATE GRAPH /stats/social",
+        "CREATE GRAPH /stats/empty",
+        "SESSION SET GRAPH /stats/social",
+        "INSERT (:Person {name: 'Alice', age: 30}), (:Person {name: 'Bob'}), (:City {name: 'Oslo'})",
+        "MATCH (a:Person {name: 'Alice'}), (b:Person {name: 'Bob'}) INSERT (a)-[:KNOWS {since: 2020}]->(b)",
+        "CREATE INDEX knows_since ON ()-[r:KNOWS]-() (since)",
+    ] {
+        coordinator
+            .process_query(query, &session_id)
+            .unwrap_or_else(|e| panic!("{}: {}", query, e));
+    }
+
+    let stats = coordinator.stats().unwrap();
+    let paths: Vec<&str> = stats
+        .graphs
+        .iter()
+        .map(|graph| graph.path.as_str())
+        .collect();
+    assert_eq!(paths, ["/stats/empty", "/stats/social"]);
+    assert_eq!((stats.node_count(), stats.edge_count()), (3, 1));
+
+    let empty = &stats.graphs[0];
+    assert_eq!((empty.nodes, empty.edges), (0, 0));
+    assert!(empty.labels.is_empty());
+
+    let social = &stats.graphs[1];
+    assert_eq!((social.nodes, social.edges), (3, 1));
+    assert_eq!(
+        social.labels,
+        [("City".to_string(), 1), ("Person".to_string(), 2)]
+    );
+    assert_eq!(social.relationship_types, [("KNOWS".to_string(), 1)]);
+    assert_eq!(
+        social.property_keys,
+        [
+            ("age".to_string(), 1, 0),
+            ("name".to_string(), 3, 0),
+            ("since".to_string(), 0, 1)
+        ]
+    );
+    assert_eq!(social.indexes.len(), 1);
+    assert_eq!(social.indexes[0].name, "knows_since");
+    assert_eq!(social.indexes[0].target, ":KNOWS(since)");
+    assert_eq!(social.indexes[0].entries, 1);
+
+    assert!(stats.disk_bytes.is_some_and(|bytes| bytes > 0));
+    let caches: Vec<&str> = stats.caches.iter().map(|cache| cache.cache).collect();
+    assert!(caches.contains(&"storage_cache"));
+    assert!(caches.contains(&"plan_cache"));
+}
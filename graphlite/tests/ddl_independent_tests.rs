@@ -144,6 +144,16 @@ fn test_ddl_transaction_behavior() {
     if result.is_ok() {}
 }
 
+#[test]
+fn test_create_graph_if_not_exists_is_idempotent() {
+    let fixture = TestFixture::empty().expect("Failed to create test fixture");
+
+    fixture.assert_query_succeeds("CREATE SCHEMA IF NOT EXISTS /idempotent_schema");
+    fixture.assert_query_succeeds("CREATE GRAPH IF NOT EXISTS /idempotent_schema/g");
+    fixture.assert_query_succeeds("CREATE GRAPH IF NOT EXISTS /idempotent_schema/g");
+    fixture.assert_query_fails("CREATE GRAPH /idempotent_schema/g", "already exists");
+}
+
 #[test]
 fn test_create_graph_with_relative_path() {
     let fixture = TestFixture::empty().expect("Failed to create test fixture");
@@ -0,0 +1,112 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! Tests for bulk relationship deletion with `DELETE EDGES OF TYPE`
+
+#[path = "testutils/mod.rs"]
+mod testutils;
+use graphlite::Value;
+use testutils::test_fixture::TestFixture;
+
+fn setup_trial_graph(fixture: &TestFixture) {
+    fixture.assert_query_succeeds(
+        "INSERT (:Compound {name: 'aspirin'}), (:Compound {name: 'ibuprofen'}), \
+         (:Trial {name: 'T1'}), (:Trial {name: 'T2'}), (:Protein {name: 'COX1'})",
+    );
+    for (compound, trial, phase) in [
+        ("aspirin", "T1", 1),
+        ("aspirin", "T2", 3),
+        ("ibuprofen", "T1", 2),
+        ("ibuprofen", "T2", 4),
+    ] {
+        fixture.assert_query_succeeds(&format!(
+            "MATCH (c:Compound {{name: '{}'}}), (t:Trial {{name: '{}'}}) \
+             INSERT (c)-[:TESTED_IN {{phase: {}}}]->(t)",
+            compound, trial, phase
+        ));
+    }
+    fixture.assert_query_succeeds(
+        "MATCH (c:Compound {name: 'aspirin'}), (p:Protein {name: 'COX1'}) \
+         INSERT (c)-[:INHIBITS {IC50: 50}]->(p)",
+    );
+}
+
+fn count_edges(fixture: &TestFixture, label: &str) -> Option<Value> {
+    let result = fixture.assert_query_succeeds(&format!(
+        "MATCH (a)-[r:{}]->(b) RETURN count(r) AS edges",
+        label
+    ));
+    result.rows[0].values.get("edges").cloned()
+}
+
+#[test]
+fn test_delete_all_edges_of_type() {
+    let fixture = TestFixture::empty().expect("Failed to create fixture");
+    fixture.setup_graph("delete_edges_all").unwrap();
+    setup_trial_graph(&fixture);
+
+    let result = fixture.assert_query_succeeds("DELETE EDGES OF TYPE :TESTED_IN");
+    assert_eq!(result.rows_affected, 4);
+
+    assert_eq!(count_edges(&fixture, "TESTED_IN"), Some(Value::Number(0.0)));
+    assert_eq!(count_edges(&fixture, "INHIBITS"), Some(Value::Number(1.0)));
+    fixture.assert_first_value(
+        "MATCH (t:Trial)<-[r]-(c:Compound) RETURN count(r) AS incoming",
+        "incoming",
+        Value::Number(0.0),
+    );
+    fixture.assert_first_value(
+        "MATCH (n) RETURN count(n) AS nodes",
+        "nodes",
+        Value::Number(5.0),
+    );
+
+    // Deleting a type without edges is a no-op
+    let result = fixture.assert_query_succeeds("DELETE EDGES OF TYPE :TESTED_IN");
+    assert_eq!(result.rows_affected, 0);
+}
+
+#[test]
+fn test_delete_edges_of_type_with_where() {
+    let fixture = TestFixture::empty().expect("Failed to create fixture");
+    fixture.setup_graph("delete_edges_where").unwrap();
+    setup_trial_graph(&fixture);
+
+    let result = fixture
+        .assert_query_succeeds("DELETE EDGES OF TYPE :TESTED_IN WHERE phase < 2 OR phase = 4");
+    assert_eq!(result.rows_affected, 2);
+
+    let result = fixture.assert_query_succeeds(
+        "MATCH (c:Compound)-[r:TESTED_IN]->(t:Trial) RETURN r.phase AS phase ORDER BY phase",
+    );
+    let phases: Vec<_> = result
+        .rows
+        .iter()
+        .map(|row| row.values.get("phase").cloned())
+        .collect();
+    assert_eq!(
+        phases,
+        vec![Some(Value::Number(2.0)), Some(Value::Number(3.0))]
+    );
+
+    // Type mismatches are errors and leave the edges in place
+    fixture.assert_query_fails(
+        "DELETE EDGES OF TYPE :TESTED_IN WHERE phase = 'two'",
+        "Cannot compare",
+    );
+    assert_eq!(count_edges(&fixture, "TESTED_IN"), Some(Value::Number(2.0)));
+}
+
+#[test]
+fn test_delete_edges_of_type_rollback() {
+    let fixture = TestFixture::empty().expect("Failed to create fixture");
+    fixture.setup_graph("delete_edges_rollback").unwrap();
+    setup_trial_graph(&fixture);
+
+    fixture.assert_query_succeeds("BEGIN");
+    fixture.assert_query_succeeds("DELETE EDGES OF TYPE :TESTED_IN");
+    assert_eq!(count_edges(&fixture, "TESTED_IN"), Some(Value::Number(0.0)));
+    fixture.assert_query_succeeds("ROLLBACK");
+
+    assert_eq!(count_edges(&fixture, "TESTED_IN"), Some(Value::Number(4.0)));
+}
@@ -0,0 +1,79 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! Tests for rejecting writes while the database volume is low on space
+
+use graphlite::{BulkNode, DatabaseConfig, QueryCoordinator, QueryErrorKind};
+use std::collections::HashMap;
+
+#[test]
+fn test_low_disk_space_rejects_writes_and_keeps_reads() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let coordinator = QueryCoordinator::from_path(temp_dir.path().join("disk_db")).unwrap();
+    let session_id = coordinator.create_simple_session("admin").unwrap();
+    for query in [
+        "CREATE SCHEMA /disk",
+        "CREATE GRAPH /disk/g",
+        "SESSION SET GRAPH /disk/g",
+        "INSERT (:Item {i: 1})",
+    ] {
+        coordinator.process_query(query, &session_id).unwrap();
+    }
+    let status = coordinator.disk_space();
+    assert!(status.free_bytes.is_some());
+    assert!(!status.writes_rejected);
+
+    // No volume has this much room
+    coordinator.set_min_free_disk_bytes(Some(u64::MAX));
+    let error = coordinator
+        .process_query("INSERT (:Item {i: 2})", &session_id)
+        .unwrap_err();
+    assert!(error.starts_with("Disk full"), "{}", error);
+    assert_eq!(
+        QueryErrorKind::from_message(&error),
+        QueryErrorKind::DiskFull
+    );
+    let node = BulkNode {
+        labels: vec!["Item".to_string()],
+        properties: HashMap::new(),
+    };
+    assert!(coordinator
+        .bulk_insert_nodes(&session_id, vec![node])
+        .unwrap_err()
+        .starts_with("Disk full"));
+
+    let result = coordinator
+        .process_query("MATCH (n:Item) RETURN n.i", &session_id)
+        .unwrap();
+    assert_eq!(result.rows.len(), 1);
+    let status = coordinator.disk_space();
+    assert!(status.writes_rejected);
+    assert_eq!(status.rejected_writes, 2);
+
+    // Writes resume once the volume has room again
+    coordinator.set_min_free_disk_bytes(None);
+    coordinator
+        .process_query("INSERT (:Item {i: 2})", &session_id)
+        .unwrap();
+    assert!(!coordinator.disk_space().writes_rejected);
+}
+
+#[test]
+fn test_min_free_disk_bytes_from_config() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let config = DatabaseConfig {
+        min_free_disk_bytes: Some(u64::MAX),
+        ..DatabaseConfig::default()
+    };
+    let coordinator =
+        QueryCoordinator::from_path_with_config(temp_dir.path().join("config_db"), config).unwrap();
+    let session_id = coordinator.create_simple_session("admin").unwrap();
+    let error = coordinator
+        .process_query("CREATE SCHEMA /disk", &session_id)
+        .unwrap_err();
+    assert_eq!(
+        QueryErrorKind::from_message(&error),
+        QueryErrorKind::DiskFull
+    );
+    assert_eq!(coordinator.disk_space().min_free_bytes, Some(u64::MAX));
+}
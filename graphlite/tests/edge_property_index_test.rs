@@ -0,0 +1,191 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! Tests for relationship property indexes (`CREATE INDEX ON ()-[r:TYPE]-() (prop)`)
+//! and MATCH queries driven by them
+
+#[path = "testutils/mod.rs"]
+mod testutils;
+use graphlite::{QueryCoordinator, QueryResult, Value};
+use testutils::test_fixture::TestFixture;
+
+fn number(result: &QueryResult, column: &str) -> f64 {
+    match result.rows[0].values.get(column) {
+        Some(Value::Number(n)) => *n,
+        other => panic!("Expected number for {}, got {:?}", column, other),
+    }
+}
+
+fn sorted_column(result: &QueryResult, column: &str) -> Vec<String> {
+    let mut values: Vec<String> = result
+        .rows
+        .iter()
+        .map(|row| format!("{:?}", row.values.get(column)))
+        .collect();
+    values.sort();
+    values
+}
+
+fn setup_binding_graph(fixture: &TestFixture) {
+    fixture.assert_query_succeeds(
+        "INSERT (:Compound {name: 'aspirin'}), (:Compound {name: 'ibuprofen'}), \
+         (:Protein {name: 'COX1'}), (:Protein {name: 'COX2'})",
+    );
+    for (compound, protein, ic50) in [
+        ("aspirin", "COX1", 50),
+        ("aspirin", "COX2", 300),
+        ("ibuprofen", "COX1", 120),
+        ("ibuprofen", "COX2", 8),
+    ] {
+        fixture.assert_query_succeeds(&format!(
+            "MATCH (c:Compound {{name: '{}'}}), (p:Protein {{name: '{}'}}) \
+             INSERT (c)-[:INHIBITS {{IC50: {}}}]->(p)",
+            compound, protein, ic50
+        ));
+    }
+}
+
+#[test]
+fn test_indexed_match_returns_same_rows_as_scan() {
+    let fixture = TestFixture::empty().expect("Failed to create fixture");
+    fixture.setup_graph("edge_index_sample").unwrap();
+    fixture.assert_query_succeeds("CALL gql.sample_data('pharma', 1)");
+
+    let queries = [
+        "MATCH (c:Compound)-[r:INHIBITS]->(p:Protein) WHERE r.IC50 < 100 RETURN c.name AS c, p.name AS p, r.IC50 AS ic50",
+        "MATCH (c:Compound)-[r:INHIBITS]->(p:Protein) WHERE 100 > r.IC50 AND r.IC50 >= 10 RETURN c.name AS c, p.name AS p, r.IC50 AS ic50",
+        "MATCH (p:Protein)<-[r:INHIBITS]-(c:Compound) WHERE r.IC50 >= 500 RETURN c.name AS c, p.name AS p, r.IC50 AS ic50",
+    ];
+    let before: Vec<Vec<String>> = queries
+        .iter()
+        .map(|q| sorted_column(&fixture.assert_query_succeeds(q), "ic50"))
+        .collect();
+    assert!(before.iter().all(|rows| !rows.is_empty()));
+
+    let result = fixture.assert_query_succeeds("CREATE INDEX ON ()-[r:INHIBITS]-() (IC50)");
+    let message = format!("{:?}", result.rows[0].values.get("message"));
+    assert!(message.contains("idx_inhibits_ic50"), "{}", message);
+
+    for (query, expected) in queries.iter().zip(&before) {
+        let after = sorted_column(&fixture.assert_query_succeeds(query), "ic50");
+        assert_eq!(&after, expected, "Indexed result differs for: {}", query);
+    }
+
+    let result = fixture.assert_query_succeeds(
+        "MATCH (c:Compound)-[r:INHIBITS]->(p:Protein) WHERE r.IC50 < 100 RETURN count(r) AS potent",
+    );
+    assert_eq!(number(&result, "potent"), before[0].len() as f64);
+}
+
+#[test]
+fn test_edge_index_follows_data_changes() {
+    let fixture = TestFixture::empty().expect("Failed to create fixture");
+    fixture.setup_graph("edge_index_changes").unwrap();
+    setup_binding_graph(&fixture);
+
+    fixture.assert_query_succeeds("CREATE INDEX ic50 ON ()-[r:INHIBITS]-() (IC50)");
+    let potent = "MATCH (c:Compound)-[r:INHIBITS]->(p:Protein) WHERE r.IC50 < 100 \
+                  RETURN c.name AS compound, p.name AS protein";
+
+    let result = fixture.assert_query_succeeds(potent);
+    assert_eq!(
+        sorted_column(&result, "protein"),
+        vec![
+            "Some(String(\"COX1\"))".to_string(),
+            "Some(String(\"COX2\"))".to_string()
+        ]
+    );
+
+    // Edges inserted after the index was built
+    fixture.assert_query_succeeds("INSERT (:Protein {name: 'HER2'})");
+    fixture.assert_query_succeeds(
+        "MATCH (c:Compound {name: 'aspirin'}), (p:Protein {name: 'HER2'}) \
+         INSERT (c)-[:INHIBITS {IC50: 3}]->(p)",
+    );
+    assert_eq!(fixture.assert_query_succeeds(potent).rows.len(), 3);
+
+    // Updated and deleted edges
+    fixture.assert_query_succeeds(
+        "MATCH (c:Compound {name: 'aspirin'})-[r:INHIBITS]->(p:Protein {name: 'COX2'}) SET r.IC50 = 1",
+    );
+    fixture.assert_query_succeeds(
+        "MATCH (c:Compound {name: 'ibuprofen'})-[r:INHIBITS]->(p:Protein {name: 'COX2'}) DELETE r",
+    );
+    let result = fixture.assert_query_succeeds(potent);
+    assert_eq!(
+        sorted_column(&result, "protein"),
+        vec![
+            "Some(String(\"COX1\"))".to_string(),
+            "Some(String(\"COX2\"))".to_string(),
+            "Some(String(\"HER2\"))".to_string()
+        ]
+    );
+
+    fixture.assert_query_succeeds("DROP INDEX ic50");
+    assert_eq!(fixture.assert_query_succeeds(potent).rows.len(), 3);
+}
+
+#[test]
+fn test_edge_index_errors() {
+    let fixture = TestFixture::empty().expect("Failed to create fixture");
+    fixture.setup_graph("edge_index_errors").unwrap();
+    setup_binding_graph(&fixture);
+
+    fixture.assert_query_succeeds("CREATE INDEX ON ()-[r:INHIBITS]-() (IC50)");
+    fixture.assert_query_succeeds("CREATE INDEX IF NOT EXISTS ON ()-[r:INHIBITS]-() (IC50)");
+    fixture.assert_query_fails(
+        "CREATE INDEX other_ic50 ON ()-[r:INHIBITS]-() (IC50)",
+        "already indexed",
+    );
+    fixture.assert_query_fails(
+        "CREATE INDEX pair ON ()-[r:INHIBITS]-() (IC50, Ki)",
+        "exactly one property",
+    );
+}
+
+#[test]
+fn test_edge_index_persists_with_graph() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let db_path = temp_dir.path().join("edge_index_db");
+
+    {
+        let coordinator = QueryCoordinator::from_path(&db_path).expect("Failed to create database");
+        let session_id = coordinator.create_simple_session("admin").unwrap();
+        for query in [
+            "CREATE SCHEMA /pharma",
+            "CREATE GRAPH /pharma/assays",
+            "SESSION SET GRAPH /pharma/assays",
+            "INSERT (:Compound {name: 'aspirin'}), (:Protein {name: 'COX1'})",
+            "MATCH (c:Compound), (p:Protein) INSERT (c)-[:INHIBITS {IC50: 50}]->(p)",
+            "CREATE INDEX ic50 ON ()-[r:INHIBITS]-() (IC50)",
+        ] {
+            coordinator.process_query(query, &session_id).unwrap();
+        }
+    }
+
+    let coordinator = QueryCoordinator::from_path(&db_path).expect("Failed to reopen database");
+    let session_id = coordinator.create_simple_session("admin").unwrap();
+    coordinator
+        .process_query("SESSION SET GRAPH /pharma/assays", &session_id)
+        .unwrap();
+
+    // The reloaded graph still carries the index definition
+    let err = coordinator
+        .process_query(
+            "CREATE INDEX other_ic50 ON ()-[r:INHIBITS]-() (IC50)",
+            &session_id,
+        )
+        .unwrap_err();
+    assert!(err.contains("already indexed"), "{}", err);
+
+    let result = coordinator
+        .process_query(
+            "MATCH (c:Compound)-[r:INHIBITS]->(p:Protein) WHERE r.IC50 <= 50 RETURN p.name AS protein",
+            &session_id,
+        )
+        .unwrap();
+    assert_eq!(
+        result.rows[0].values.get("protein"),
+        Some(&Value::String("COX1".to_string()))
+    );
+}
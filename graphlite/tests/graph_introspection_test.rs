@@ -0,0 +1,98 @@
+#[path = "testutils/mod.rs"]
+mod testutils;
+use graphlite::Value;
+use testutils::test_fixture::TestFixture;
+
+/// Values of `columns` for every row, in row order
+fn rows(result: &graphlite::QueryResult, columns: &[&str]) -> Vec<Vec<Value>> {
+    result
+        .rows
+        .iter()
+        .map(|row| {
+            columns
+                .iter()
+                .map(|column| row.values.get(*column).cloned().unwrap_or(Value::Null))
+                .collect()
+        })
+        .collect()
+}
+
+#[test]
+fn test_list_labels_relationship_types_and_property_keys() {
+    let fixture = TestFixture::empty().expect("Failed to create fixture");
+    fixture.setup_graph("introspection").unwrap();
+    fixture.assert_query_succeeds(
+        "INSERT (:Person:Employee {name: 'Alice'})-[:WORKS_AT {since: 2021}]->(:Company {name: 'Acme', founded: 1999})",
+    );
+
+    // Without an argument the session's current graph is described
+    let result = fixture.assert_query_succeeds("CALL gql.list_labels()");
+    assert_eq!(
+        rows(&result, &["label", "nodes"]),
+        vec![
+            vec![Value::String("Company".to_string()), Value::Number(1.0)],
+            vec![Value::String("Employee".to_string()), Value::Number(1.0)],
+            vec![Value::String("Person".to_string()), Value::Number(1.0)],
+        ]
+    );
+
+    let graph = format!("/{}/introspection", fixture.schema_name());
+    let result =
+        fixture.assert_query_succeeds(&format!("CALL gql.list_relationship_types('{}')", graph));
+    assert_eq!(
+        rows(&result, &["relationship_type", "edges"]),
+        vec![vec![
+            Value::String("WORKS_AT".to_string()),
+            Value::Number(1.0)
+        ]]
+    );
+
+    let result = fixture.assert_query_succeeds("CALL gql.list_property_keys('introspection')");
+    assert_eq!(
+        rows(&result, &["property_key", "nodes", "edges"]),
+        vec![
+            vec![
+                Value::String("founded".to_string()),
+                Value::Number(1.0),
+                Value::Number(0.0)
+            ],
+            vec![
+                Value::String("name".to_string()),
+                Value::Number(2.0),
+                Value::Number(0.0)
+            ],
+            vec![
+                Value::String("since".to_string()),
+                Value::Number(0.0),
+                Value::Number(1.0)
+            ],
+        ]
+    );
+
+    fixture.assert_query_fails("CALL gql.list_labels('missing')", "Graph does not exist");
+    fixture.assert_query_fails("CALL gql.list_labels(42)", "must be a string");
+}
+
+#[test]
+fn test_list_indexes() {
+    let fixture = TestFixture::empty().expect("Failed to create fixture");
+    fixture.setup_graph("indexed").unwrap();
+    fixture.assert_query_succeeds(
+        "INSERT (:Person {name: 'Alice'})-[:KNOWS {since: 2020}]->(:Person {name: 'Bob'})",
+    );
+
+    let result = fixture.assert_query_succeeds("CALL gql.list_indexes()");
+    assert!(result.rows.is_empty());
+
+    fixture.assert_query_succeeds("CREATE INDEX knows_since ON ()-[r:KNOWS]-() (since)");
+    let result = fixture.assert_query_succeeds("CALL gql.list_indexes('indexed')");
+    assert_eq!(
+        rows(&result, &["index_name", "kind", "target", "entries"]),
+        vec![vec![
+            Value::String("knows_since".to_string()),
+            Value::String("edge_property".to_string()),
+            Value::String(":KNOWS(since)".to_string()),
+            Value::Number(1.0)
+        ]]
+    );
+}
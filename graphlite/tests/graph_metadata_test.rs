@@ -0,0 +1,113 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! Tests for graph metadata (`ALTER GRAPH ... SET`) and `gql.find_graphs`
+
+use graphlite::{QueryCoordinator, QueryResult, Value};
+
+fn run(coordinator: &QueryCoordinator, session_id: &str, query: &str) -> QueryResult {
+    coordinator
+        .process_query(query, session_id)
+        .unwrap_or_else(|e| panic!("{}: {}", query, e))
+}
+
+/// Graph name, description, tags and owner as returned by gql.find_graphs
+type Found = (String, Option<String>, Vec<String>, Option<String>);
+
+fn found(coordinator: &QueryCoordinator, session_id: &str, query: &str) -> Vec<Found> {
+    let text = |value: Option<&Value>| match value {
+        Some(Value::String(s)) => Some(s.clone()),
+        Some(Value::Null) | None => None,
+        other => panic!("Expected string or null, got {:?}", other),
+    };
+    run(coordinator, session_id, query)
+        .rows
+        .iter()
+        .map(|row| {
+            let tags = match row.get_value("tags") {
+                Some(Value::List(tags)) => tags.iter().map(|t| text(Some(t)).unwrap()).collect(),
+                other => panic!("Expected list for tags, got {:?}", other),
+            };
+            (
+                text(row.get_value("graph_name")).unwrap(),
+                text(row.get_value("description")),
+                tags,
+                text(row.get_value("owner")),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn test_alter_graph_metadata_and_find_graphs() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let db_path = temp_dir.path().join("metadata_db");
+    let coordinator = QueryCoordinator::from_path(&db_path).expect("Failed to open");
+    let session_id = coordinator.create_simple_session("admin").unwrap();
+    for query in [
+        "CREATE SCHEMA /teams",
+        "CREATE GRAPH /teams/trials",
+        "CREATE GRAPH /teams/sales",
+        "CREATE GRAPH /teams/scratch",
+        "ALTER GRAPH /teams/trials SET DESCRIPTION 'Clinical trials', TAGS ['pharma', 'prod'], OWNER 'research'",
+        "ALTER GRAPH /teams/sales SET TAGS ['crm', 'Prod', 'prod']",
+    ] {
+        run(&coordinator, &session_id, query);
+    }
+
+    // Tags match regardless of case
+    assert_eq!(
+        found(&coordinator, &session_id, "CALL gql.find_graphs('PROD')"),
+        vec![
+            (
+                "sales".to_string(),
+                None,
+                vec!["crm".to_string(), "Prod".to_string()],
+                None
+            ),
+            (
+                "trials".to_string(),
+                Some("Clinical trials".to_string()),
+                vec!["pharma".to_string(), "prod".to_string()],
+                Some("research".to_string()),
+            ),
+        ]
+    );
+    assert_eq!(
+        found(&coordinator, &session_id, "CALL gql.find_graphs('pharma')").len(),
+        1
+    );
+    assert_eq!(
+        found(&coordinator, &session_id, "CALL gql.find_graphs()").len(),
+        3
+    );
+
+    // Only the items in the SET list change; relative paths use the session schema
+    run(&coordinator, &session_id, "SESSION SET SCHEMA /teams");
+    run(
+        &coordinator,
+        &session_id,
+        "ALTER GRAPH trials SET TAGS [], OWNER 'platform'",
+    );
+    assert!(found(&coordinator, &session_id, "CALL gql.find_graphs('pharma')").is_empty());
+
+    let error = coordinator
+        .process_query("ALTER GRAPH /teams/missing SET TAGS ['x']", &session_id)
+        .unwrap_err();
+    assert!(error.contains("not found"), "{}", error);
+
+    // Metadata is persisted with the catalog
+    drop(coordinator);
+    let coordinator = QueryCoordinator::from_path(&db_path).expect("Failed to reopen");
+    let session_id = coordinator.create_simple_session("admin").unwrap();
+    let graphs = found(&coordinator, &session_id, "CALL gql.find_graphs()");
+    assert_eq!(
+        graphs[2],
+        (
+            "trials".to_string(),
+            Some("Clinical trials".to_string()),
+            Vec::new(),
+            Some("platform".to_string()),
+        )
+    );
+}
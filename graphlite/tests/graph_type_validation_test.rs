@@ -0,0 +1,93 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! Tests for checking statements against the graph type of a typed graph
+
+use graphlite::QueryCoordinator;
+
+const LAB_TYPE: &str = "CREATE GRAPH TYPE /lab/LabType ( \
+    NODE TYPE Protein (name STRING, mass DOUBLE), Gene (symbol STRING) \
+    EDGE TYPE ENCODES (confidence DOUBLE) SOURCE Gene DESTINATION Protein )";
+
+#[test]
+fn test_typed_graph_flags_unknown_names() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let coordinator =
+        QueryCoordinator::from_path(temp_dir.path().join("typed_db")).expect("Failed to open");
+    let session_id = coordinator.create_simple_session("admin").unwrap();
+    let session = coordinator.session_handle(&session_id).unwrap();
+    let query = |text: &str| coordinator.process_query_with_handle(text, &session);
+
+    for statement in [
+        "CREATE SCHEMA /lab",
+        LAB_TYPE,
+        "CREATE GRAPH /lab/assays TYPED /lab/LabType",
+        "SESSION SET GRAPH /lab/assays",
+    ] {
+        query(statement).unwrap();
+    }
+    assert!(query("CREATE GRAPH /lab/other TYPED /lab/NoSuchType")
+        .unwrap_err()
+        .contains("Graph type 'lab.NoSuchType' not found"));
+
+    // Names the graph type defines pass without warnings
+    let result = query(
+        "INSERT (:Gene {symbol: 'TP53'})-[:ENCODES {confidence: 0.9}]->(:Protein {name: 'p53', mass: 43.7})",
+    )
+    .unwrap();
+    assert!(result.warnings.is_empty(), "{:?}", result.warnings);
+    let result = query(
+        "MATCH (g:Gene)-[e:ENCODES]->(p:Protein) WHERE e.confidence > 0.5 RETURN g.symbol, p.name",
+    )
+    .unwrap();
+    assert_eq!(result.rows.len(), 1);
+    assert!(result.warnings.is_empty(), "{:?}", result.warnings);
+
+    // Advisory mode runs the statement and explains the empty result
+    let result = query("MATCH (p:Protien) RETURN p.name").unwrap();
+    assert!(result.rows.is_empty());
+    assert_eq!(
+        result.warnings,
+        vec![
+            "Label 'Protien' is not defined in graph type 'lab.LabType' (did you mean 'Protein'?)"
+        ]
+    );
+    let result =
+        query("MATCH (g:Gene)-[:ENCODE]->(p:Protein) WHERE p.nmae = 'p53' RETURN g.symbol, p.mass")
+            .unwrap();
+    assert_eq!(
+        result.warnings,
+        vec![
+            "Edge type 'ENCODE' is not defined in graph type 'lab.LabType' (did you mean 'ENCODES'?)",
+            "Property 'nmae' is not defined for :Protein in graph type 'lab.LabType' (did you mean 'name'?)",
+        ]
+    );
+    let result = query("MATCH (n) WHERE n.weight > 1 RETURN n").unwrap();
+    assert_eq!(
+        result.warnings,
+        vec!["Property 'weight' is not defined for any node type in graph type 'lab.LabType'"]
+    );
+
+    // Strict mode rejects the statement before it runs
+    session.set_schema_enforcement_mode("strict").unwrap();
+    let error = query("INSERT (:Protein {name: 'BRCA1', organism: 'human'})").unwrap_err();
+    assert!(
+        error.contains("Property 'organism' is not defined for :Protein"),
+        "{}",
+        error
+    );
+    let result = query("MATCH (p:Protein) RETURN p.name").unwrap();
+    assert_eq!(result.rows.len(), 1);
+
+    session.set_schema_enforcement_mode("disabled").unwrap();
+    let result = query("MATCH (p:Protien) RETURN p").unwrap();
+    assert!(result.warnings.is_empty());
+    assert!(session.set_schema_enforcement_mode("lenient").is_err());
+
+    // Untyped graphs are not checked
+    session.set_schema_enforcement_mode("strict").unwrap();
+    query("CREATE GRAPH /lab/scratch").unwrap();
+    query("SESSION SET GRAPH /lab/scratch").unwrap();
+    let result = query("INSERT (:Protien {nmae: 'p53'})").unwrap();
+    assert!(result.warnings.is_empty());
+}
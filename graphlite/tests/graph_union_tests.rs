@@ -0,0 +1,73 @@
+#[path = "testutils/mod.rs"]
+mod testutils;
+use graphlite::Value;
+use testutils::test_fixture::TestFixture;
+
+fn setup_union_graphs(fixture: &TestFixture) -> (String, String) {
+    fixture.assert_query_succeeds(&format!(
+        "CREATE SCHEMA IF NOT EXISTS /{}",
+        fixture.schema_name()
+    ));
+
+    let left = format!("/{}/union_left", fixture.schema_name());
+    let right = format!("/{}/union_right", fixture.schema_name());
+
+    fixture.assert_query_succeeds(&format!("CREATE GRAPH {}", left));
+    fixture.assert_query_succeeds(&format!("SESSION SET GRAPH {}", left));
+    fixture.assert_query_succeeds("INSERT (:Person {name: 'Alice'}), (:Person {name: 'Bob'})");
+
+    fixture.assert_query_succeeds(&format!("CREATE GRAPH {}", right));
+    fixture.assert_query_succeeds(&format!("SESSION SET GRAPH {}", right));
+    fixture.assert_query_succeeds("INSERT (:Person {name: 'Carol'})");
+
+    (left, right)
+}
+
+fn person_names(fixture: &TestFixture, query: &str) -> Vec<String> {
+    let result = fixture.assert_query_succeeds(query);
+    let mut names: Vec<String> = result
+        .rows
+        .iter()
+        .filter_map(|row| match row.values.get("p.name") {
+            Some(Value::String(name)) => Some(name.clone()),
+            _ => None,
+        })
+        .collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn test_from_graph_union_reads_both_graphs() {
+    let fixture = TestFixture::empty().expect("Failed to create test fixture");
+    let (left, right) = setup_union_graphs(&fixture);
+
+    for op in ["UNION", "UNION ALL"] {
+        let names = person_names(
+            &fixture,
+            &format!(
+                "SELECT p.name FROM {} {} {} MATCH (p:Person)",
+                left, op, right
+            ),
+        );
+        assert_eq!(
+            names,
+            vec!["Alice", "Bob", "Carol"],
+            "{} over two graphs",
+            op
+        );
+    }
+}
+
+#[test]
+fn test_from_graph_union_missing_operand_fails() {
+    let fixture = TestFixture::empty().expect("Failed to create test fixture");
+    let (left, _) = setup_union_graphs(&fixture);
+
+    let result = fixture.query(&format!(
+        "SELECT p.name FROM {} UNION /{}/no_such_graph MATCH (p:Person)",
+        left,
+        fixture.schema_name()
+    ));
+    assert!(result.is_err(), "union with a missing graph should fail");
+}
@@ -340,3 +340,39 @@ fn test_json_format_raw_output_structure() {
     // execution_time_ms should be a valid u64 value
     let _ = result.execution_time_ms; // Just verify it exists
 }
+
+#[test]
+fn test_json_format_keeps_return_column_order() {
+    let fixture = TestFixture::empty().expect("Failed to create fixture");
+    setup_test_graph!(fixture);
+
+    fixture
+        .query(
+            "INSERT (:Person {name: 'Alice', age: 30, city: 'Oslo', zip: '0150', active: true});",
+        )
+        .expect("Insert failed");
+
+    let columns = ["p.zip", "p.name", "p.active", "p.city", "p.age"];
+    let result = fixture
+        .query(&format!("MATCH (p:Person) RETURN {};", columns.join(", ")))
+        .expect("Query failed");
+    assert_eq!(result.variables, columns);
+    let row = &result.rows[0];
+    assert_eq!(row.values.keys().collect::<Vec<_>>(), columns);
+
+    // Serialized rows list their values in the same order
+    let json = serde_json::to_value(row).expect("Serialize failed");
+    let keys: Vec<&String> = json["values"].as_object().unwrap().keys().collect();
+    assert_eq!(keys, columns);
+
+    // Procedure results follow their declared columns too
+    let procedure = fixture
+        .query("CALL gql.supported_features('transactions');")
+        .expect("Procedure failed");
+    for row in &procedure.rows {
+        assert_eq!(
+            row.values.keys().collect::<Vec<_>>(),
+            procedure.variables.iter().collect::<Vec<_>>()
+        );
+    }
+}
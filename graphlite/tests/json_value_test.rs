@@ -0,0 +1,256 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! Tests for JSON document property values, `JSON_GET` / `JSON_EXISTS`, and
+//! relationship indexes over JSON paths
+
+#[path = "testutils/mod.rs"]
+mod testutils;
+use graphlite::{QueryCoordinator, QueryResult, Value};
+use serde_json::json;
+use std::collections::HashMap;
+use testutils::test_fixture::TestFixture;
+
+fn sorted_column(result: &QueryResult, column: &str) -> Vec<String> {
+    let mut values: Vec<String> = result
+        .rows
+        .iter()
+        .map(|row| format!("{:?}", row.values.get(column)))
+        .collect();
+    values.sort();
+    values
+}
+
+fn setup_assay_graph(fixture: &TestFixture) {
+    fixture.assert_query_succeeds(
+        "INSERT (:Compound {name: 'aspirin'}), (:Compound {name: 'ibuprofen'}), \
+         (:Protein {name: 'COX1'}), (:Protein {name: 'COX2'})",
+    );
+    for (compound, protein, meta) in [
+        (
+            "aspirin",
+            "COX1",
+            r#"{"assay": {"ph": 6.5, "lab": "north"}}"#,
+        ),
+        (
+            "aspirin",
+            "COX2",
+            r#"{"assay": {"ph": 7.4, "lab": "south"}}"#,
+        ),
+        ("ibuprofen", "COX1", r#"{"assay": {"ph": 8.0}}"#),
+        ("ibuprofen", "COX2", r#"{"notes": ["repeat"]}"#),
+    ] {
+        fixture.assert_query_succeeds(&format!(
+            "MATCH (c:Compound {{name: '{}'}}), (p:Protein {{name: '{}'}}) \
+             INSERT (c)-[:INHIBITS {{meta: JSON('{}')}}]->(p)",
+            compound, protein, meta
+        ));
+    }
+}
+
+#[test]
+fn test_json_properties_on_nodes() {
+    let fixture = TestFixture::empty().expect("Failed to create fixture");
+    fixture.setup_graph("json_nodes").unwrap();
+
+    fixture.assert_query_succeeds(
+        r#"INSERT (:Doc {id: 1, body: JSON('{"title": "a", "tags": ["x", "y"], "meta": {"views": 10}}')}),
+                  (:Doc {id: 2, body: JSON('{"title": "b", "meta": {}}')})"#,
+    );
+
+    fixture.assert_first_value(
+        "MATCH (d:Doc {id: 1}) RETURN JSON_GET(d.body, '$.meta.views') AS views",
+        "views",
+        Value::Number(10.0),
+    );
+    fixture.assert_first_value(
+        "MATCH (d:Doc {id: 1}) RETURN JSON_GET(d.body, '$.tags[-1]') AS tag",
+        "tag",
+        Value::String("y".to_string()),
+    );
+    fixture.assert_first_value(
+        "MATCH (d:Doc {id: 1}) RETURN JSON_GET(d.body, '$.meta') AS meta",
+        "meta",
+        Value::Json(json!({"views": 10})),
+    );
+    fixture.assert_first_value(
+        "MATCH (d:Doc {id: 2}) RETURN JSON_GET(d.body, '$.meta.views') AS views",
+        "views",
+        Value::Null,
+    );
+
+    let result = fixture.assert_query_succeeds(
+        "MATCH (d:Doc) WHERE JSON_EXISTS(d.body, '$.meta.views') RETURN d.id AS id",
+    );
+    assert_eq!(sorted_column(&result, "id"), vec!["Some(Number(1.0))"]);
+
+    let result = fixture.assert_query_succeeds(
+        "MATCH (d:Doc) WHERE JSON_GET(d.body, '$.title') = 'b' RETURN d.body AS body",
+    );
+    assert_eq!(
+        result.rows[0].values.get("body"),
+        Some(&Value::Json(json!({"title": "b", "meta": {}})))
+    );
+
+    fixture.assert_query_fails(
+        "MATCH (d:Doc) RETURN JSON_GET(d.body, 'title') AS title",
+        "must start with '$'",
+    );
+}
+
+#[test]
+fn test_json_path_index_returns_same_rows_as_scan() {
+    let fixture = TestFixture::empty().expect("Failed to create fixture");
+    fixture.setup_graph("json_path_index").unwrap();
+    setup_assay_graph(&fixture);
+
+    let queries = [
+        "MATCH (c:Compound)-[r:INHIBITS]->(p:Protein) WHERE JSON_GET(r.meta, '$.assay.ph') < 7.5 \
+         RETURN c.name AS c, p.name AS p",
+        "MATCH (c:Compound)-[r:INHIBITS]->(p:Protein) WHERE 8 <= JSON_GET(r.meta, '$.assay.ph') \
+         RETURN c.name AS c, p.name AS p",
+        "MATCH (c:Compound)-[r:INHIBITS]->(p:Protein) WHERE JSON_GET(r.meta, '$.assay.lab') = 'south' \
+         RETURN c.name AS c, p.name AS p",
+    ];
+    let before: Vec<Vec<String>> = queries
+        .iter()
+        .map(|q| sorted_column(&fixture.assert_query_succeeds(q), "p"))
+        .collect();
+    assert_eq!(before[0].len(), 2);
+    assert_eq!(before[1].len(), 1);
+
+    let result = fixture
+        .assert_query_succeeds("CREATE INDEX ON ()-[r:INHIBITS]-() (JSON_GET(meta, '$.assay.ph'))");
+    let message = format!("{:?}", result.rows[0].values.get("message"));
+    assert!(
+        message.contains("idx_inhibits_meta_assay_ph"),
+        "{}",
+        message
+    );
+    assert!(message.contains("3 relationships indexed"), "{}", message);
+
+    for (query, expected) in queries.iter().zip(&before) {
+        let after = sorted_column(&fixture.assert_query_succeeds(query), "p");
+        assert_eq!(&after, expected, "Indexed result differs for: {}", query);
+    }
+
+    // Documents changed after the index was built
+    fixture.assert_query_succeeds(
+        "MATCH (c:Compound {name: 'ibuprofen'})-[r:INHIBITS]->(p:Protein {name: 'COX2'}) \
+         SET r.meta = JSON('{\"assay\": {\"ph\": 5}}')",
+    );
+    assert_eq!(
+        fixture.assert_query_succeeds(queries[0]).rows.len(),
+        3,
+        "Updated document should be found through the index"
+    );
+
+    fixture.assert_query_fails(
+        "CREATE INDEX ph_again ON ()-[r:INHIBITS]-() (JSON_GET(meta, '$[\"assay\"].ph'))",
+        "already indexed",
+    );
+    fixture.assert_query_fails(
+        "CREATE INDEX doc_title ON Doc (JSON_GET(body, '$.title'))",
+        "relationship properties",
+    );
+}
+
+#[test]
+fn test_json_parameters_and_persistence() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let db_path = temp_dir.path().join("json_db");
+
+    {
+        let coordinator = QueryCoordinator::from_path(&db_path).expect("Failed to create database");
+        let session_id = coordinator.create_simple_session("admin").unwrap();
+        for query in [
+            "CREATE SCHEMA /pharma",
+            "CREATE GRAPH /pharma/assays",
+            "SESSION SET GRAPH /pharma/assays",
+            "INSERT (:Compound {name: 'aspirin'}), (:Protein {name: 'COX1'})",
+        ] {
+            coordinator.process_query(query, &session_id).unwrap();
+        }
+
+        let params = HashMap::from([(
+            "meta".to_string(),
+            Value::Json(json!({"assay": {"ph": 6.5, "note": "it's \"fine\""}})),
+        )]);
+        coordinator
+            .process_query_with_params(
+                "MATCH (c:Compound), (p:Protein) INSERT (c)-[:INHIBITS {meta: $meta}]->(p)",
+                &params,
+                &session_id,
+            )
+            .unwrap();
+        coordinator
+            .process_query(
+                "CREATE INDEX ph ON ()-[r:INHIBITS]-() (JSON_GET(meta, '$.assay.ph'))",
+                &session_id,
+            )
+            .unwrap();
+    }
+
+    let coordinator = QueryCoordinator::from_path(&db_path).expect("Failed to reopen database");
+    let session_id = coordinator.create_simple_session("admin").unwrap();
+    coordinator
+        .process_query("SESSION SET GRAPH /pharma/assays", &session_id)
+        .unwrap();
+
+    // The reloaded graph still carries the path index
+    let err = coordinator
+        .process_query(
+            "CREATE INDEX ph_again ON ()-[r:INHIBITS]-() (JSON_GET(meta, '$.assay.ph'))",
+            &session_id,
+        )
+        .unwrap_err();
+    assert!(err.contains("already indexed"), "{}", err);
+
+    let result = coordinator
+        .process_query(
+            "MATCH (c:Compound)-[r:INHIBITS]->(p:Protein) WHERE JSON_GET(r.meta, '$.assay.ph') < 7 \
+             RETURN r.meta AS meta, JSON_GET(r.meta, '$.assay.note') AS note",
+            &session_id,
+        )
+        .unwrap();
+    assert_eq!(result.rows.len(), 1);
+    assert_eq!(
+        result.rows[0].values.get("meta"),
+        Some(&Value::Json(
+            json!({"assay": {"ph": 6.5, "note": "it's \"fine\""}})
+        ))
+    );
+    assert_eq!(
+        result.rows[0].values.get("note"),
+        Some(&Value::String("it's \"fine\"".to_string()))
+    );
+}
+
+#[test]
+fn test_json_property_type_in_graph_type() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let coordinator =
+        QueryCoordinator::from_path(temp_dir.path().join("typed_json_db")).expect("Failed to open");
+    let session_id = coordinator.create_simple_session("admin").unwrap();
+
+    for statement in [
+        "CREATE SCHEMA /docs",
+        "CREATE GRAPH TYPE /docs/DocType ( NODE TYPE Doc (title STRING, body JSON) )",
+        "CREATE GRAPH /docs/library TYPED /docs/DocType",
+        "SESSION SET GRAPH /docs/library",
+        r#"INSERT (:Doc {title: 'a', body: JSON('{"sections": [{"heading": "Intro"}]}')})"#,
+    ] {
+        coordinator.process_query(statement, &session_id).unwrap();
+    }
+
+    let result = coordinator
+        .process_query(
+            "MATCH (d:Doc) RETURN JSON_GET(d.body, '$.sections[0].heading') AS heading",
+            &session_id,
+        )
+        .unwrap();
+    assert_eq!(
+        result.rows[0].values.get("heading"),
+        Some(&Value::String("Intro".to_string()))
+    );
+}
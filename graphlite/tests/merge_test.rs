@@ -0,0 +1,278 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! Tests for MERGE with ON CREATE and ON MATCH actions
+
+#[path = "testutils/mod.rs"]
+mod testutils;
+use graphlite::{BulkNode, QueryCoordinator, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+use testutils::test_fixture::TestFixture;
+
+fn count(fixture: &TestFixture, query: &str) -> Value {
+    let result = fixture.assert_query_succeeds(query);
+    result.rows[0]
+        .values
+        .get("n")
+        .cloned()
+        .unwrap_or(Value::Null)
+}
+
+#[test]
+fn test_merge_node_creates_once() {
+    let fixture = TestFixture::empty().expect("Failed to create fixture");
+    fixture.setup_graph("merge_nodes").unwrap();
+
+    fixture.assert_query_succeeds("MERGE (p:Person {name: 'Alice'})");
+    fixture.assert_query_succeeds("MERGE (p:Person {name: 'Alice'})");
+    fixture.assert_query_succeeds("merge (:Person {name: 'Bob'})");
+
+    assert_eq!(
+        count(&fixture, "MATCH (p:Person) RETURN count(p) AS n"),
+        Value::Number(2.0)
+    );
+    assert_eq!(
+        count(
+            &fixture,
+            "MATCH (p:Person {name: 'Alice'}) RETURN count(p) AS n"
+        ),
+        Value::Number(1.0)
+    );
+}
+
+#[test]
+fn test_merge_on_create_and_on_match() {
+    let fixture = TestFixture::empty().expect("Failed to create fixture");
+    fixture.setup_graph("merge_actions").unwrap();
+
+    let upsert = "MERGE (p:Person {name: 'Alice'}) \
+                  ON CREATE SET p.seen = 1, p.created = true \
+                  ON MATCH SET p.seen = p.seen + 1";
+    for _ in 0..3 {
+        fixture.assert_query_succeeds(upsert);
+    }
+
+    fixture.assert_first_value(
+        "MATCH (p:Person {name: 'Alice'}) RETURN p.seen AS seen",
+        "seen",
+        Value::Number(3.0),
+    );
+    fixture.assert_first_value(
+        "MATCH (p:Person {name: 'Alice'}) RETURN p.created AS created",
+        "created",
+        Value::Boolean(true),
+    );
+}
+
+#[test]
+fn test_match_merge_edge_between_bound_nodes() {
+    let fixture = TestFixture::empty().expect("Failed to create fixture");
+    fixture.setup_graph("merge_edges").unwrap();
+    fixture.assert_query_succeeds(
+        "INSERT (:Person {name: 'Alice'}), (:Person {name: 'Bob'}), (:Person {name: 'Carol'})",
+    );
+
+    let follow = "MATCH (a:Person {name: 'Alice'}), (b:Person) WHERE b.name <> 'Alice' \
+                  MERGE (a)-[f:FOLLOWS]->(b) ON CREATE SET f.times = 1 \
+                  ON MATCH SET f.times = f.times + 1";
+    fixture.assert_query_succeeds(follow);
+    fixture.assert_query_succeeds(follow);
+
+    let result = fixture.assert_query_succeeds(
+        "MATCH (:Person {name: 'Alice'})-[f:FOLLOWS]->(b:Person) RETURN b.name AS name, f.times AS times",
+    );
+    assert_eq!(result.rows.len(), 2);
+    for row in &result.rows {
+        assert_eq!(row.values.get("times"), Some(&Value::Number(2.0)));
+    }
+
+    // No Person was created for the edge's end points
+    assert_eq!(
+        count(&fixture, "MATCH (p:Person) RETURN count(p) AS n"),
+        Value::Number(3.0)
+    );
+}
+
+#[test]
+fn test_merge_per_matched_row() {
+    let fixture = TestFixture::empty().expect("Failed to create fixture");
+    fixture.setup_graph("merge_rows").unwrap();
+    fixture.assert_query_succeeds(
+        "INSERT (:Person {name: 'Alice', city: 'Paris'}), (:Person {name: 'Bob', city: 'Oslo'}), \
+         (:Person {name: 'Carol', city: 'Paris'})",
+    );
+
+    // Later rows see the City created for an earlier row
+    fixture.assert_query_succeeds("MATCH (p:Person) MERGE (:City {name: p.city})");
+    fixture.assert_query_succeeds(
+        "MATCH (p:Person), (c:City) WHERE c.name = p.city MERGE (p)-[:LIVES_IN]->(c)",
+    );
+
+    assert_eq!(
+        count(&fixture, "MATCH (c:City) RETURN count(c) AS n"),
+        Value::Number(2.0)
+    );
+    assert_eq!(
+        count(
+            &fixture,
+            "MATCH (p:Person)-[:LIVES_IN]->(c:City {name: 'Paris'}) RETURN count(p) AS n"
+        ),
+        Value::Number(2.0)
+    );
+}
+
+#[test]
+fn test_merge_rolls_back_with_the_transaction() {
+    let fixture = TestFixture::empty().expect("Failed to create fixture");
+    fixture.setup_graph("merge_rollback").unwrap();
+    fixture.assert_query_succeeds("INSERT (:Person {name: 'Alice', seen: 1})");
+
+    fixture.assert_query_succeeds("START TRANSACTION");
+    fixture
+        .assert_query_succeeds("MERGE (p:Person {name: 'Alice'}) ON MATCH SET p.seen = 5, p:Admin");
+    fixture.assert_query_succeeds("MERGE (:Person {name: 'Bob'})");
+    fixture.assert_query_succeeds("ROLLBACK");
+
+    assert_eq!(
+        count(&fixture, "MATCH (p:Person) RETURN count(p) AS n"),
+        Value::Number(1.0)
+    );
+    fixture.assert_first_value(
+        "MATCH (p:Person {name: 'Alice'}) RETURN p.seen AS seen",
+        "seen",
+        Value::Number(1.0),
+    );
+}
+
+#[test]
+fn test_merge_rejects_unsupported_forms() {
+    let fixture = TestFixture::empty().expect("Failed to create fixture");
+    fixture.setup_graph("merge_errors").unwrap();
+
+    assert!(fixture
+        .query("MERGE (p:Person {name: 'Alice'}) RETURN p")
+        .is_err());
+    assert!(fixture
+        .query("MERGE (:Person {name: 'A'})-[]->(:Person {name: 'B'})")
+        .is_err());
+    assert!(fixture.query("MERGE (p:Person {name: NULL})").is_err());
+}
+
+#[test]
+fn test_concurrent_merges_create_one_node() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let coordinator =
+        QueryCoordinator::from_path(temp_dir.path().join("upsert_db")).expect("Failed to open");
+    let session_id = coordinator.create_simple_session("admin").unwrap();
+    for query in [
+        "CREATE SCHEMA /upsert",
+        "CREATE GRAPH /upsert/g",
+        "SESSION SET GRAPH /upsert/g",
+    ] {
+        coordinator.process_query(query, &session_id).unwrap();
+    }
+
+    let workers: Vec<_> = (0..8)
+        .map(|_| {
+            let coordinator = Arc::clone(&coordinator);
+            std::thread::spawn(move || {
+                let session_id = coordinator.create_simple_session("admin").unwrap();
+                coordinator
+                    .process_query("SESSION SET GRAPH /upsert/g", &session_id)
+                    .unwrap();
+                for _ in 0..5 {
+                    coordinator
+                        .process_query(
+                            "MERGE (c:Counter {name: 'hits'}) ON CREATE SET c.value = 1 \
+                             ON MATCH SET c.value = c.value + 1",
+                            &session_id,
+                        )
+                        .unwrap();
+                }
+            })
+        })
+        .collect();
+    for worker in workers {
+        worker.join().unwrap();
+    }
+
+    let result = coordinator
+        .process_query(
+            "MATCH (c:Counter) RETURN count(c) AS n, max(c.value) AS value",
+            &session_id,
+        )
+        .unwrap();
+    assert_eq!(result.rows[0].get_value("n"), Some(&Value::Number(1.0)));
+    assert_eq!(
+        result.rows[0].get_value("value"),
+        Some(&Value::Number(40.0))
+    );
+}
+
+#[test]
+fn test_merges_and_bulk_loads_into_one_graph_keep_every_write() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let coordinator =
+        QueryCoordinator::from_path(temp_dir.path().join("mixed_db")).expect("Failed to open");
+    let session_id = coordinator.create_simple_session("admin").unwrap();
+    for query in [
+        "CREATE SCHEMA /mixed",
+        "CREATE GRAPH /mixed/g",
+        "SESSION SET GRAPH /mixed/g",
+    ] {
+        coordinator.process_query(query, &session_id).unwrap();
+    }
+
+    // Bulk loads save the graph like data statements do, so neither may
+    // overwrite the changes of the other
+    let workers: Vec<_> = (0..8)
+        .map(|worker| {
+            let coordinator = Arc::clone(&coordinator);
+            std::thread::spawn(move || {
+                let session_id = coordinator.create_simple_session("admin").unwrap();
+                coordinator
+                    .process_query("SESSION SET GRAPH /mixed/g", &session_id)
+                    .unwrap();
+                for batch in 0..5 {
+                    if worker % 2 == 0 {
+                        coordinator
+                            .process_query(
+                                "MERGE (c:Counter {name: 'hits'}) ON CREATE SET c.value = 1 \
+                                 ON MATCH SET c.value = c.value + 1",
+                                &session_id,
+                            )
+                            .unwrap();
+                    } else {
+                        let node = BulkNode {
+                            labels: vec!["Loaded".to_string()],
+                            properties: HashMap::from([(
+                                "key".to_string(),
+                                Value::Number((worker * 100 + batch) as f64),
+                            )]),
+                        };
+                        coordinator
+                            .bulk_insert_nodes(&session_id, vec![node])
+                            .unwrap();
+                    }
+                }
+            })
+        })
+        .collect();
+    for worker in workers {
+        worker.join().unwrap();
+    }
+
+    let result = coordinator
+        .process_query("MATCH (c:Counter) RETURN c.value AS value", &session_id)
+        .unwrap();
+    assert_eq!(result.rows.len(), 1);
+    assert_eq!(
+        result.rows[0].get_value("value"),
+        Some(&Value::Number(20.0))
+    );
+    let result = coordinator
+        .process_query("MATCH (n:Loaded) RETURN count(n) AS n", &session_id)
+        .unwrap();
+    assert_eq!(result.rows[0].get_value("n"), Some(&Value::Number(20.0)));
+}
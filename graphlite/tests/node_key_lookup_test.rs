@@ -0,0 +1,74 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! Tests for `QueryCoordinator::node_ids_by_key`, used to attach bulk-loaded
+//! edges to nodes loaded in an earlier run
+
+use graphlite::{BulkEdge, BulkNode, QueryCoordinator, Value};
+use std::collections::HashMap;
+
+fn person(id: f64, name: &str) -> BulkNode {
+    BulkNode {
+        labels: vec!["Person".to_string()],
+        properties: HashMap::from([
+            ("id".to_string(), Value::Number(id)),
+            ("name".to_string(), Value::String(name.to_string())),
+        ]),
+    }
+}
+
+#[test]
+fn test_edges_attach_to_nodes_by_key() {
+    let dir = tempfile::tempdir().unwrap();
+    let coordinator =
+        QueryCoordinator::from_path(dir.path().join("key_db")).expect("Failed to open database");
+    let session_id = coordinator.create_simple_session("admin").unwrap();
+    for query in [
+        "CREATE SCHEMA /keys",
+        "CREATE GRAPH /keys/g",
+        "SESSION SET GRAPH /keys/g",
+        "INSERT (:City {id: 1, name: 'Paris'})",
+    ] {
+        coordinator.process_query(query, &session_id).unwrap();
+    }
+    let stored = coordinator
+        .bulk_insert_nodes(&session_id, vec![person(1.0, "Alice"), person(2.0, "Bob")])
+        .unwrap();
+
+    let ids = coordinator
+        .node_ids_by_key(&session_id, Some("Person"), "id")
+        .unwrap();
+    assert_eq!(ids.len(), 2);
+    assert_eq!(ids["1"], stored[0]);
+
+    // String keys read from another file match numeric ones
+    let edge = BulkEdge {
+        from_node: ids[&graphlite::key_text(&Value::String("1".to_string()))].clone(),
+        to_node: ids["2"].clone(),
+        label: "KNOWS".to_string(),
+        properties: HashMap::new(),
+    };
+    assert_eq!(
+        coordinator
+            .bulk_insert_edges(&session_id, vec![edge])
+            .unwrap(),
+        1
+    );
+    let result = coordinator
+        .process_query(
+            "MATCH (a:Person)-[:KNOWS]->(b:Person) RETURN a.name AS a, b.name AS b",
+            &session_id,
+        )
+        .unwrap();
+    assert_eq!(result.rows.len(), 1);
+    assert_eq!(
+        result.rows[0].values.get("b"),
+        Some(&Value::String("Bob".to_string()))
+    );
+
+    // Without a label the city shares key 1 with Alice
+    let error = coordinator
+        .node_ids_by_key(&session_id, None, "id")
+        .unwrap_err();
+    assert!(error.contains("More than one node has id = 1"), "{}", error);
+}
@@ -0,0 +1,56 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! Tests for per-operator timing of query execution
+
+use graphlite::{OperatorTiming, QueryCoordinator};
+
+fn find<'a>(timing: &'a OperatorTiming, operator: &str) -> Option<&'a OperatorTiming> {
+    if timing.operator == operator {
+        return Some(timing);
+    }
+    timing
+        .children
+        .iter()
+        .find_map(|child| find(child, operator))
+}
+
+#[test]
+fn test_operator_timing() {
+    let dir = tempfile::tempdir().unwrap();
+    let coordinator =
+        QueryCoordinator::from_path(dir.path().join("timing_db")).expect("Failed to open");
+    let session_id = coordinator.create_simple_session("admin").unwrap();
+    for query in [
+        "CREATE SCHEMA /timing",
+        "CREATE GRAPH /timing/g",
+        "SESSION SET GRAPH /timing/g",
+    ] {
+        coordinator.process_query(query, &session_id).unwrap();
+    }
+    for i in 0..20 {
+        coordinator
+            .process_query(&format!("INSERT (:Item {{i: {}}})", i), &session_id)
+            .unwrap();
+    }
+    let query = "MATCH (n:Item) WHERE n.i < 5 RETURN n.i";
+
+    // Off by default
+    let result = coordinator.process_query(query, &session_id).unwrap();
+    assert!(result.operator_timings.is_none());
+
+    let handle = coordinator.session_handle(&session_id).unwrap();
+    handle.set_operator_timing(true).unwrap();
+    assert!(handle.operator_timing());
+    let result = coordinator.process_query(query, &session_id).unwrap();
+    let root = result.operator_timings.expect("timings with timing on");
+    assert_eq!(root.rows, 5);
+    let scan = find(&root, "NodeSeqScan").expect("scan in the timing tree");
+    assert_eq!(scan.rows, 20);
+    assert!(root.elapsed_us >= scan.elapsed_us);
+    assert!(root.operator_count() > 1);
+
+    handle.set_operator_timing(false).unwrap();
+    let result = coordinator.process_query(query, &session_id).unwrap();
+    assert!(result.operator_timings.is_none());
+}
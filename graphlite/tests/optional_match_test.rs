@@ -0,0 +1,225 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! Tests for OPTIONAL MATCH and the joining of consecutive MATCH clauses
+
+#[path = "testutils/mod.rs"]
+mod testutils;
+use graphlite::{QueryResult, Value};
+use testutils::test_fixture::TestFixture;
+
+/// Alice works at Acme and Carol at Globex; Bob works nowhere
+fn setup_employment_graph(fixture: &TestFixture) {
+    fixture.setup_graph("optional_match").unwrap();
+    fixture.assert_query_succeeds(
+        "INSERT (:Person {name: 'Alice'})-[:WORKS_AT {since: 2019}]->(:Company {name: 'Acme'}), \
+         (:Person {name: 'Bob'}), \
+         (:Person {name: 'Carol'})-[:WORKS_AT {since: 2022}]->(:Company {name: 'Globex'})",
+    );
+}
+
+/// (first column, second column) of every row, sorted
+fn sorted_pairs(result: &QueryResult, first: &str, second: &str) -> Vec<(Value, Value)> {
+    let mut pairs: Vec<(Value, Value)> = result
+        .rows
+        .iter()
+        .map(|row| {
+            (
+                row.values.get(first).cloned().unwrap_or(Value::Null),
+                row.values.get(second).cloned().unwrap_or(Value::Null),
+            )
+        })
+        .collect();
+    pairs.sort_by_key(|pair| format!("{:?}", pair));
+    pairs
+}
+
+fn text(s: &str) -> Value {
+    Value::String(s.to_string())
+}
+
+#[test]
+fn test_optional_match_keeps_unmatched_rows() {
+    let fixture = TestFixture::empty().expect("Failed to create fixture");
+    setup_employment_graph(&fixture);
+
+    let result = fixture.assert_query_succeeds(
+        "MATCH (p:Person) OPTIONAL MATCH (p)-[:WORKS_AT]->(c:Company) \
+         RETURN p.name AS person, c.name AS company",
+    );
+    assert_eq!(
+        sorted_pairs(&result, "person", "company"),
+        vec![
+            (text("Alice"), text("Acme")),
+            (text("Bob"), Value::Null),
+            (text("Carol"), text("Globex")),
+        ]
+    );
+}
+
+#[test]
+fn test_optional_match_where_filters_matches_not_rows() {
+    let fixture = TestFixture::empty().expect("Failed to create fixture");
+    setup_employment_graph(&fixture);
+
+    let result = fixture.assert_query_succeeds(
+        "MATCH (p:Person) OPTIONAL MATCH (p)-[w:WORKS_AT]->(c:Company) WHERE w.since > 2020 \
+         RETURN p.name AS person, c.name AS company",
+    );
+    assert_eq!(
+        sorted_pairs(&result, "person", "company"),
+        vec![
+            (text("Alice"), Value::Null),
+            (text("Bob"), Value::Null),
+            (text("Carol"), text("Globex")),
+        ]
+    );
+}
+
+#[test]
+fn test_optional_match_without_matches() {
+    let fixture = TestFixture::empty().expect("Failed to create fixture");
+    setup_employment_graph(&fixture);
+
+    // On its own, an OPTIONAL MATCH that matches nothing yields one row of NULLs
+    let result = fixture.assert_query_succeeds("OPTIONAL MATCH (x:Robot) RETURN x");
+    assert_eq!(result.rows.len(), 1);
+    assert_eq!(result.rows[0].values.get("x"), Some(&Value::Null));
+
+    // Lower case keywords, and a chain of optional matches on a NULL variable
+    let result = fixture.assert_query_succeeds(
+        "match (p:Person {name: 'Bob'}) optional match (p)-[:WORKS_AT]->(c) \
+         OPTIONAL MATCH (c)<-[:WORKS_AT]-(colleague) \
+         RETURN p.name AS person, colleague.name AS colleague",
+    );
+    assert_eq!(
+        sorted_pairs(&result, "person", "colleague"),
+        vec![(text("Bob"), Value::Null)]
+    );
+}
+
+#[test]
+fn test_optional_match_with_count() {
+    let fixture = TestFixture::empty().expect("Failed to create fixture");
+    setup_employment_graph(&fixture);
+
+    let result = fixture.assert_query_succeeds(
+        "MATCH (p:Person) OPTIONAL MATCH (p)-[:WORKS_AT]->(c:Company) \
+         WITH p, count(c) AS jobs RETURN p.name AS person, jobs",
+    );
+    assert_eq!(
+        sorted_pairs(&result, "person", "jobs"),
+        vec![
+            (text("Alice"), Value::Number(1.0)),
+            (text("Bob"), Value::Number(0.0)),
+            (text("Carol"), Value::Number(1.0)),
+        ]
+    );
+
+    fixture.assert_first_value(
+        "MATCH (p:Person) OPTIONAL MATCH (p)-[:WORKS_AT]->(c:Company) RETURN count(c) AS jobs",
+        "jobs",
+        Value::Number(2.0),
+    );
+}
+
+#[test]
+fn test_optional_match_groups_on_null() {
+    let fixture = TestFixture::empty().expect("Failed to create fixture");
+    setup_employment_graph(&fixture);
+    fixture.assert_query_succeeds("INSERT (:Person {name: 'Dave'})");
+
+    // Bob and Dave share the NULL company
+    let result = fixture.assert_query_succeeds(
+        "MATCH (p:Person) OPTIONAL MATCH (p)-[:WORKS_AT]->(c:Company) \
+         WITH c, count(p) AS staff RETURN c.name AS company, staff",
+    );
+    assert_eq!(
+        sorted_pairs(&result, "company", "staff"),
+        vec![
+            (Value::Null, Value::Number(2.0)),
+            (text("Acme"), Value::Number(1.0)),
+            (text("Globex"), Value::Number(1.0)),
+        ]
+    );
+
+    // WITH ... WHERE sees the aggregates of every group
+    let result = fixture.assert_query_succeeds(
+        "MATCH (p:Person) OPTIONAL MATCH (p)-[:WORKS_AT]->(c:Company) \
+         WITH p, count(c) AS jobs WHERE jobs = 0 RETURN p.name AS person, jobs",
+    );
+    assert_eq!(
+        sorted_pairs(&result, "person", "jobs"),
+        vec![
+            (text("Bob"), Value::Number(0.0)),
+            (text("Dave"), Value::Number(0.0)),
+        ]
+    );
+}
+
+#[test]
+fn test_grouped_with_is_the_same_with_and_without_nulls() {
+    let fixture = TestFixture::empty().expect("Failed to create fixture");
+    fixture.setup_graph("optional_match_roles").unwrap();
+    fixture.assert_query_succeeds(
+        "INSERT (:Person {name: 'Alice', role: 'engineer'})-[:WORKS_AT]->(:Company {name: 'Acme'}), \
+         (:Person {name: 'Bob'}), \
+         (:Person {name: 'Carol', role: 'engineer'})-[:WORKS_AT]->(:Company {name: 'Globex'})",
+    );
+
+    // Rows group on the role, whether or not Bob's row binds c to NULL
+    let matched = fixture.assert_query_succeeds(
+        "MATCH (p:Person)-[:WORKS_AT]->(c:Company) \
+         WITH p.role AS role, count(c) AS jobs RETURN role, jobs",
+    );
+    assert_eq!(
+        sorted_pairs(&matched, "role", "jobs"),
+        vec![(text("engineer"), Value::Number(2.0))]
+    );
+    let optional = fixture.assert_query_succeeds(
+        "MATCH (p:Person) OPTIONAL MATCH (p)-[:WORKS_AT]->(c:Company) \
+         WITH p.role AS role, count(c) AS jobs RETURN role, jobs",
+    );
+    assert_eq!(
+        sorted_pairs(&optional, "role", "jobs"),
+        vec![
+            (Value::Null, Value::Number(0.0)),
+            (text("engineer"), Value::Number(2.0)),
+        ]
+    );
+}
+
+#[test]
+fn test_consecutive_match_joins_on_shared_variables() {
+    let fixture = TestFixture::empty().expect("Failed to create fixture");
+    setup_employment_graph(&fixture);
+
+    // The second MATCH only extends the rows of the first
+    let result = fixture.assert_query_succeeds(
+        "MATCH (p:Person {name: 'Bob'}) MATCH (p)-[:WORKS_AT]->(c:Company) \
+         RETURN p.name AS person, c.name AS company",
+    );
+    assert!(result.rows.is_empty());
+
+    let result = fixture.assert_query_succeeds(
+        "MATCH (p:Person) WHERE p.name <> 'Alice' MATCH (p)-[:WORKS_AT]->(c:Company) \
+         RETURN p.name AS person, c.name AS company",
+    );
+    assert_eq!(
+        sorted_pairs(&result, "person", "company"),
+        vec![(text("Carol"), text("Globex"))]
+    );
+}
+
+#[test]
+fn test_optional_match_is_rejected_before_mutations() {
+    let fixture = TestFixture::empty().expect("Failed to create fixture");
+    setup_employment_graph(&fixture);
+
+    assert!(fixture
+        .query(
+            "MATCH (p:Person) WITH p OPTIONAL MATCH (p)-[:WORKS_AT]->(c) \
+             SET p.employed = true"
+        )
+        .is_err());
+}
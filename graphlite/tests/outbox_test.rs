@@ -0,0 +1,203 @@
+//! Tests for the graph event outbox (gql.outbox_append, gql.outbox_poll, gql.outbox_ack)
+
+#[path = "testutils/mod.rs"]
+mod testutils;
+
+use graphlite::{QueryCoordinator, QueryResult, Value};
+use testutils::test_fixture::TestFixture;
+
+fn column(result: &QueryResult, column: &str) -> Vec<Value> {
+    result
+        .rows
+        .iter()
+        .map(|row| row.values.get(column).cloned().unwrap_or(Value::Null))
+        .collect()
+}
+
+fn offsets(result: &QueryResult) -> Vec<Value> {
+    column(result, "offset")
+}
+
+fn numbers(values: &[u64]) -> Vec<Value> {
+    values.iter().map(|n| Value::Number(*n as f64)).collect()
+}
+
+#[test]
+fn test_events_become_visible_on_commit() {
+    let fixture = TestFixture::new().expect("Failed to create fixture");
+    fixture
+        .setup_graph("outbox_commit_test")
+        .expect("Failed to setup graph");
+
+    // Outside a transaction the event is written at once
+    let result =
+        fixture.assert_query_succeeds("CALL gql.outbox_append('created', JSON('{\"id\": 1}'))");
+    assert_eq!(offsets(&result), numbers(&[1]));
+
+    fixture.query("START TRANSACTION").unwrap();
+    fixture.query("INSERT (:Person {name: 'Alice'})").unwrap();
+    let result =
+        fixture.assert_query_succeeds("CALL gql.outbox_append('created', JSON('{\"id\": 2}'))");
+    assert_eq!(offsets(&result), vec![Value::Null]);
+    fixture
+        .query("CALL gql.outbox_append('audit', 'alice added')")
+        .unwrap();
+
+    let polled = fixture.assert_query_succeeds("CALL gql.outbox_poll('indexer')");
+    assert_eq!(offsets(&polled), numbers(&[1]));
+
+    fixture.query("COMMIT").unwrap();
+    let polled = fixture.assert_query_succeeds("CALL gql.outbox_poll('indexer')");
+    assert_eq!(offsets(&polled), numbers(&[1, 2, 3]));
+    assert_eq!(
+        column(&polled, "topic"),
+        vec![
+            Value::String("created".to_string()),
+            Value::String("created".to_string()),
+            Value::String("audit".to_string()),
+        ]
+    );
+    assert_eq!(
+        column(&polled, "payload")[2],
+        Value::String("alice added".to_string())
+    );
+    let transaction_ids = column(&polled, "transaction_id");
+    assert_eq!(transaction_ids[0], Value::Null);
+    assert!(matches!(transaction_ids[1], Value::String(_)));
+    assert_eq!(transaction_ids[1], transaction_ids[2]);
+}
+
+#[test]
+fn test_rollback_discards_events() {
+    let fixture = TestFixture::new().expect("Failed to create fixture");
+    fixture
+        .setup_graph("outbox_rollback_test")
+        .expect("Failed to setup graph");
+
+    fixture.query("START TRANSACTION").unwrap();
+    fixture.query("CALL gql.outbox_append('a', 1)").unwrap();
+    fixture.query("ROLLBACK").unwrap();
+
+    fixture.query("START TRANSACTION").unwrap();
+    fixture.query("CALL gql.outbox_append('b', 1)").unwrap();
+    fixture.query("SAVEPOINT sp1").unwrap();
+    fixture.query("CALL gql.outbox_append('c', 1)").unwrap();
+    fixture.query("ROLLBACK TO SAVEPOINT sp1").unwrap();
+    fixture.query("CALL gql.outbox_append('d', 1)").unwrap();
+    fixture.query("COMMIT").unwrap();
+
+    // Rolled-back events never take an offset
+    let polled = fixture.assert_query_succeeds("CALL gql.outbox_poll('consumer')");
+    assert_eq!(offsets(&polled), numbers(&[1, 2]));
+    assert_eq!(
+        column(&polled, "topic"),
+        vec![
+            Value::String("b".to_string()),
+            Value::String("d".to_string())
+        ]
+    );
+}
+
+#[test]
+fn test_poll_and_ack_per_consumer() {
+    let fixture = TestFixture::new().expect("Failed to create fixture");
+    fixture
+        .setup_graph("outbox_ack_test")
+        .expect("Failed to setup graph");
+
+    // Register the second consumer before anything is acknowledged
+    fixture.assert_query_succeeds("CALL gql.outbox_ack('search', 0)");
+    for n in 1..=4 {
+        fixture
+            .query(&format!("CALL gql.outbox_append('tick', {})", n))
+            .unwrap();
+    }
+
+    let polled = fixture.assert_query_succeeds("CALL gql.outbox_poll('mail', 2)");
+    assert_eq!(offsets(&polled), numbers(&[1, 2]));
+
+    let ack = fixture.assert_query_succeeds("CALL gql.outbox_ack('mail', 2)");
+    assert_eq!(column(&ack, "acked_offset"), numbers(&[2]));
+    assert_eq!(column(&ack, "pending"), numbers(&[2]));
+
+    // Acknowledging again is a no-op
+    let ack = fixture.assert_query_succeeds("CALL gql.outbox_ack('mail', 1)");
+    assert_eq!(column(&ack, "acked_offset"), numbers(&[2]));
+
+    let polled = fixture.assert_query_succeeds("CALL gql.outbox_poll('mail')");
+    assert_eq!(offsets(&polled), numbers(&[3, 4]));
+    let polled = fixture.assert_query_succeeds("CALL gql.outbox_poll('search')");
+    assert_eq!(offsets(&polled), numbers(&[1, 2, 3, 4]));
+
+    fixture.assert_query_fails("CALL gql.outbox_ack('mail', 5)", "offset");
+}
+
+#[test]
+fn test_outbox_errors() {
+    let fixture = TestFixture::new().expect("Failed to create fixture");
+
+    fixture.assert_query_fails("CALL gql.outbox_append('topic', 1)", "graph");
+
+    fixture
+        .setup_graph("outbox_error_test")
+        .expect("Failed to setup graph");
+    fixture.assert_query_fails("CALL gql.outbox_append('', 1)", "topic");
+    fixture.assert_query_fails("CALL gql.outbox_poll('consumer', 0)", "limit");
+    fixture.assert_query_fails("CALL gql.outbox_ack('consumer', -1)", "offset");
+    fixture.assert_query_fails(
+        &format!(
+            "CALL gql.outbox_poll('consumer', 10, '/{}/missing')",
+            fixture.schema_name()
+        ),
+        "does not exist",
+    );
+
+    fixture.query("START TRANSACTION READ ONLY").unwrap();
+    fixture.assert_query_fails("CALL gql.outbox_append('topic', 1)", "read-only");
+    fixture.query("ROLLBACK").unwrap();
+}
+
+#[test]
+fn test_outbox_survives_reopen_and_drop_graph_clears_it() {
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let path = dir.path().join("outbox_db");
+    let setup = [
+        "CREATE SCHEMA /outbox",
+        "CREATE GRAPH /outbox/events",
+        "SESSION SET GRAPH /outbox/events",
+        "CALL gql.outbox_append('kept', 1)",
+        "CALL gql.outbox_append('kept', 2)",
+    ];
+    {
+        let coordinator = QueryCoordinator::from_path(&path).expect("Failed to open database");
+        let session = coordinator.create_simple_session("admin").unwrap();
+        for query in setup {
+            coordinator.process_query(query, &session).unwrap();
+        }
+    }
+
+    let coordinator = QueryCoordinator::from_path(&path).expect("Failed to reopen database");
+    let session = coordinator.create_simple_session("admin").unwrap();
+    let polled = coordinator
+        .process_query("CALL gql.outbox_poll('c', 10, '/outbox/events')", &session)
+        .unwrap();
+    assert_eq!(offsets(&polled), numbers(&[1, 2]));
+
+    // Offsets continue after the reopen
+    let appended = coordinator
+        .process_query(
+            "CALL gql.outbox_append('kept', 3, '/outbox/events')",
+            &session,
+        )
+        .unwrap();
+    assert_eq!(offsets(&appended), numbers(&[3]));
+
+    // A graph created under the same name starts with an empty outbox
+    for query in ["DROP GRAPH /outbox/events", "CREATE GRAPH /outbox/events"] {
+        coordinator.process_query(query, &session).unwrap();
+    }
+    let polled = coordinator
+        .process_query("CALL gql.outbox_poll('c', 10, '/outbox/events')", &session)
+        .unwrap();
+    assert!(polled.rows.is_empty());
+}
@@ -0,0 +1,150 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! Tests for plan pinning (`gql.pin_plan`, `gql.unpin_plan`, `gql.list_pinned_plans`)
+
+use graphlite::{QueryCoordinator, QueryResult, Value};
+
+const QUERY: &str = "MATCH (p:Person) WHERE p.age > 30 RETURN p.name AS name";
+
+fn run(coordinator: &QueryCoordinator, session_id: &str, query: &str) -> QueryResult {
+    coordinator
+        .process_query(query, session_id)
+        .unwrap_or_else(|e| panic!("{}: {}", query, e))
+}
+
+fn text(result: &QueryResult, column: &str) -> String {
+    match result.rows[0].get_value(column) {
+        Some(Value::String(s)) => s.clone(),
+        other => panic!("Expected string for {}, got {:?}", column, other),
+    }
+}
+
+fn pinned_uses(coordinator: &QueryCoordinator, session_id: &str) -> Vec<f64> {
+    run(coordinator, session_id, "CALL gql.list_pinned_plans()")
+        .rows
+        .iter()
+        .map(|row| match row.get_value("uses") {
+            Some(Value::Number(n)) => *n,
+            other => panic!("Expected number for uses, got {:?}", other),
+        })
+        .collect()
+}
+
+#[test]
+fn test_pinned_plan_is_reused_until_unpinned() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let db_path = temp_dir.path().join("pin_db");
+    let coordinator = QueryCoordinator::from_path(&db_path).expect("Failed to open");
+    let session_id = coordinator.create_simple_session("admin").unwrap();
+    for query in [
+        "CREATE SCHEMA /pin",
+        "CREATE GRAPH /pin/g",
+        "SESSION SET GRAPH /pin/g",
+        "INSERT (:Person {name: 'Alice', age: 40}), (:Person {name: 'Bob', age: 25})",
+    ] {
+        run(&coordinator, &session_id, query);
+    }
+
+    let fingerprint = text(
+        &run(
+            &coordinator,
+            &session_id,
+            &format!("CALL gql.query_fingerprint('{}')", QUERY),
+        ),
+        "fingerprint",
+    );
+    assert_eq!(fingerprint.len(), 16);
+
+    // Only queries that have run can be pinned
+    let error = coordinator
+        .process_query(
+            &format!("CALL gql.pin_plan('{}')", fingerprint),
+            &session_id,
+        )
+        .unwrap_err();
+    assert!(error.contains("Run the query first"), "{}", error);
+
+    let unpinned = run(&coordinator, &session_id, QUERY);
+    let pinned = run(
+        &coordinator,
+        &session_id,
+        &format!("CALL gql.pin_plan('{}')", fingerprint),
+    );
+    assert_eq!(text(&pinned, "query"), QUERY);
+
+    // Whitespace differences share the pinned plan
+    let reflowed = QUERY.replace(" RETURN", "\n    RETURN");
+    let result = run(&coordinator, &session_id, &reflowed);
+    assert_eq!(result.rows, unpinned.rows);
+    run(&coordinator, &session_id, QUERY);
+    assert_eq!(pinned_uses(&coordinator, &session_id), [2.0]);
+
+    let result = run(
+        &coordinator,
+        &session_id,
+        &format!("CALL gql.unpin_plan('{}')", fingerprint),
+    );
+    assert_eq!(
+        result.rows[0].get_value("unpinned"),
+        Some(&Value::Boolean(true))
+    );
+    assert!(pinned_uses(&coordinator, &session_id).is_empty());
+    let result = run(
+        &coordinator,
+        &session_id,
+        &format!("CALL gql.unpin_plan('{}')", fingerprint),
+    );
+    assert_eq!(
+        result.rows[0].get_value("unpinned"),
+        Some(&Value::Boolean(false))
+    );
+}
+
+#[test]
+fn test_pins_persist_and_schema_changes_invalidate_them() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let db_path = temp_dir.path().join("pin_persist_db");
+    {
+        let coordinator = QueryCoordinator::from_path(&db_path).expect("Failed to open");
+        let session_id = coordinator.create_simple_session("admin").unwrap();
+        for query in [
+            "CREATE SCHEMA /pin",
+            "CREATE GRAPH /pin/g",
+            "SESSION SET GRAPH /pin/g",
+            "INSERT (:Person {name: 'Alice', age: 40})",
+            QUERY,
+            &format!(
+                "CALL gql.pin_plan('{}')",
+                QueryCoordinator::query_fingerprint(QUERY)
+            ),
+        ] {
+            run(&coordinator, &session_id, query);
+        }
+    }
+
+    let coordinator = QueryCoordinator::from_path(&db_path).expect("Failed to reopen");
+    let session_id = coordinator.create_simple_session("admin").unwrap();
+    run(&coordinator, &session_id, "SESSION SET GRAPH /pin/g");
+    let result = run(&coordinator, &session_id, QUERY);
+    assert_eq!(
+        result.rows[0].get_value("name"),
+        Some(&Value::String("Alice".to_string()))
+    );
+    assert_eq!(pinned_uses(&coordinator, &session_id), [1.0]);
+
+    // Data changes keep the pin, schema changes drop it
+    run(
+        &coordinator,
+        &session_id,
+        "INSERT (:Person {name: 'Carol', age: 50})",
+    );
+    assert_eq!(pinned_uses(&coordinator, &session_id).len(), 1);
+    run(
+        &coordinator,
+        &session_id,
+        "CREATE INDEX person_age ON Person (age)",
+    );
+    assert!(pinned_uses(&coordinator, &session_id).is_empty());
+    assert_eq!(run(&coordinator, &session_id, QUERY).rows.len(), 2);
+}
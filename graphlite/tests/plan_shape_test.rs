@@ -0,0 +1,103 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! Tests for plan shapes and plan-shape assertions (`graphlite::plan_testing`)
+
+use graphlite::plan_testing::{assert_plan_contains, assert_plan_not_contains, op, PlanShape};
+use graphlite::{QueryCoordinator, QueryPlan};
+use std::sync::Arc;
+
+fn coordinator() -> (tempfile::TempDir, Arc<QueryCoordinator>) {
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let coordinator =
+        QueryCoordinator::from_path(dir.path().join("plan_db")).expect("Failed to open database");
+    (dir, coordinator)
+}
+
+fn explain(coordinator: &QueryCoordinator, query: &str) -> QueryPlan {
+    coordinator
+        .explain_query(query)
+        .unwrap_or_else(|e| panic!("Failed to explain {}: {}", query, e))
+}
+
+#[test]
+fn test_plan_shape_text() {
+    let (_dir, coordinator) = coordinator();
+
+    let plan = explain(
+        &coordinator,
+        "MATCH (p:Person) WHERE p.age > 30 RETURN p.name AS name",
+    );
+    assert_eq!(
+        PlanShape::of(&plan).to_string(),
+        "Project columns=[name]\n\
+         \x20 Filter condition=(p.age > 30)\n\
+         \x20   NodeSeqScan variable=p labels=[Person]\n"
+    );
+
+    let plan = explain(
+        &coordinator,
+        "MATCH (p:Person) RETURN p.city AS city, count(p) AS n ORDER BY n DESC LIMIT 3",
+    );
+    assert_eq!(
+        PlanShape::of(&plan).to_string(),
+        "Limit count=3\n\
+         \x20 InMemorySort keys=[n DESC]\n\
+         \x20   SortAggregate group_by=[p.city] aggregates=[COUNT(p) AS n]\n\
+         \x20     NodeSeqScan variable=p labels=[Person]\n"
+    );
+
+    // Planning the same query again gives the same shape
+    let again = explain(
+        &coordinator,
+        "MATCH (p:Person) RETURN p.city AS city, count(p) AS n ORDER BY n DESC LIMIT 3",
+    );
+    assert_eq!(PlanShape::of(&again), PlanShape::of(&plan));
+}
+
+#[test]
+fn test_plan_assertions() {
+    let (_dir, coordinator) = coordinator();
+
+    let plan = explain(
+        &coordinator,
+        "MATCH (c:Compound)-[r:INHIBITS]->(p:Protein) WHERE r.IC50 < 100 RETURN c.name, p.name",
+    );
+    assert_plan_contains(
+        &plan,
+        op("EdgeIndexScan")
+            .with_label("INHIBITS")
+            .with_label("Compound")
+            .with_attr("predicates", "[r.IC50 < 100]"),
+    );
+    assert_plan_contains(
+        &plan,
+        op("Filter").with_child(op("EdgeIndexScan").with_variable("r")),
+    );
+    assert_plan_not_contains(&plan, op("NodeSeqScan"));
+
+    let plan = explain(
+        &coordinator,
+        "MATCH (u:User)-[:FOLLOWS]->(f) RETURN u, count(f) AS following",
+    );
+    assert_plan_contains(
+        &plan,
+        op("*").with_descendant(op("AdjacencyCount").with_label("FOLLOWS")),
+    );
+    assert_plan_not_contains(&plan, op("HashExpand"));
+
+    let shape = PlanShape::of(&plan);
+    let counts = shape.find(&op("AdjacencyCount"));
+    assert_eq!(counts.len(), 1);
+    assert_eq!(counts[0].attribute("direction"), Some("Outgoing"));
+}
+
+#[test]
+#[should_panic(
+    expected = "expected the plan to contain op(\"NodeIndexScan\").with_label(\"Person\")"
+)]
+fn test_failed_assertion_names_matcher() {
+    let (_dir, coordinator) = coordinator();
+    let plan = explain(&coordinator, "MATCH (p:Person) RETURN p");
+    assert_plan_contains(&plan, op("NodeIndexScan").with_label("Person"));
+}
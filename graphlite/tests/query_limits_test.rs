@@ -0,0 +1,102 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! Tests for per-coordinator query resource limits
+
+use graphlite::{QueryCoordinator, QueryLimits};
+use std::sync::Arc;
+use std::time::Duration;
+
+fn setup(db_name: &str) -> (tempfile::TempDir, Arc<QueryCoordinator>, String) {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let coordinator =
+        QueryCoordinator::from_path(temp_dir.path().join(db_name)).expect("Failed to open");
+    let session_id = coordinator.create_simple_session("admin").unwrap();
+    for query in [
+        "CREATE SCHEMA /limits",
+        "CREATE GRAPH /limits/g",
+        "SESSION SET GRAPH /limits/g",
+    ] {
+        coordinator.process_query(query, &session_id).unwrap();
+    }
+    for i in 0..50 {
+        coordinator
+            .process_query(&format!("INSERT (:Item {{i: {}}})", i), &session_id)
+            .unwrap();
+    }
+    (temp_dir, coordinator, session_id)
+}
+
+#[test]
+fn test_max_result_rows() {
+    let (_dir, coordinator, session_id) = setup("result_rows_db");
+    coordinator.set_query_limits(QueryLimits {
+        max_result_rows: Some(10),
+        ..QueryLimits::default()
+    });
+
+    let error = coordinator
+        .process_query("MATCH (n:Item) RETURN n.i", &session_id)
+        .unwrap_err();
+    assert!(error.contains("ResultLimitExceeded"), "{}", error);
+
+    let result = coordinator
+        .process_query("MATCH (n:Item) RETURN n.i LIMIT 10", &session_id)
+        .unwrap();
+    assert_eq!(result.rows.len(), 10);
+
+    coordinator.set_query_limits(QueryLimits::default());
+    let result = coordinator
+        .process_query("MATCH (n:Item) RETURN n.i", &session_id)
+        .unwrap();
+    assert_eq!(result.rows.len(), 50);
+}
+
+#[test]
+fn test_max_memory_bytes() {
+    let (_dir, coordinator, session_id) = setup("memory_db");
+    coordinator.set_query_limits(QueryLimits {
+        max_memory_bytes: Some(64 * 1024),
+        ..QueryLimits::default()
+    });
+
+    // A scan fits, its cartesian product with itself does not
+    coordinator
+        .process_query("MATCH (n:Item) RETURN count(n) AS items", &session_id)
+        .unwrap();
+    let error = coordinator
+        .process_query(
+            "MATCH (a:Item), (b:Item) RETURN count(*) AS pairs",
+            &session_id,
+        )
+        .unwrap_err();
+    assert!(error.contains("MemoryLimitExceeded"), "{}", error);
+}
+
+#[test]
+fn test_query_timeout() {
+    let (_dir, coordinator, session_id) = setup("timeout_db");
+    coordinator.set_query_limits(QueryLimits {
+        query_timeout: Some(Duration::from_nanos(1)),
+        ..QueryLimits::default()
+    });
+
+    let error = coordinator
+        .process_query(
+            "MATCH (a:Item), (b:Item) RETURN count(*) AS pairs",
+            &session_id,
+        )
+        .unwrap_err();
+    assert!(error.contains("QueryTimeout"), "{}", error);
+
+    coordinator.set_query_limits(QueryLimits {
+        query_timeout: Some(Duration::from_secs(60)),
+        ..QueryLimits::default()
+    });
+    coordinator
+        .process_query(
+            "MATCH (a:Item), (b:Item) RETURN count(*) AS pairs",
+            &session_id,
+        )
+        .unwrap();
+}
@@ -0,0 +1,102 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! Tests for reference properties of graph types and RESOLVE
+
+use graphlite::{QueryCoordinator, Value};
+
+const SHOP_TYPE: &str = "CREATE GRAPH TYPE /shop/ShopType ( \
+    NODE TYPE Customer (email STRING, name STRING), \
+    Purchase (total DOUBLE, customer STRING REFERENCES Customer(email)) )";
+
+#[test]
+fn test_resolve_reference_properties() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let db_path = temp_dir.path().join("shop_db");
+    {
+        let coordinator = QueryCoordinator::from_path(&db_path).expect("Failed to open");
+        let session_id = coordinator.create_simple_session("admin").unwrap();
+        let session = coordinator.session_handle(&session_id).unwrap();
+        let query = |text: &str| coordinator.process_query_with_handle(text, &session);
+
+        for statement in [
+            "CREATE SCHEMA /shop",
+            SHOP_TYPE,
+            "CREATE GRAPH /shop/orders TYPED /shop/ShopType",
+            "SESSION SET GRAPH /shop/orders",
+            "INSERT (:Customer {email: 'ann@example.com', name: 'Ann'}), \
+             (:Customer {email: 'bo@example.com', name: 'Bo'})",
+            "INSERT (:Purchase {total: 12.5, customer: 'ann@example.com'}), \
+             (:Purchase {total: 99.0, customer: 'bo@example.com'}), \
+             (:Purchase {total: 5.0, customer: 'cy@example.com'}), (:Purchase {total: 1.0})",
+        ] {
+            query(statement).unwrap();
+        }
+
+        // A reference to a key no node of the type holds is rejected up front
+        let error = query(
+            "CREATE GRAPH TYPE /shop/BadType ( \
+             NODE TYPE Purchase (customer STRING REFERENCES Customer(email)) )",
+        )
+        .unwrap_err();
+        assert!(error.contains("references Customer(email)"), "{}", error);
+
+        // Dangling and missing references resolve to NULL
+        let result = query(
+            "MATCH (p:Purchase) WITH p.total AS total, RESOLVE(p.customer) AS c \
+             RETURN total, c.name AS name ORDER BY total",
+        )
+        .unwrap();
+        let names: Vec<_> = result
+            .rows
+            .iter()
+            .map(|row| (row.values["total"].clone(), row.values["name"].clone()))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                (Value::Number(1.0), Value::Null),
+                (Value::Number(5.0), Value::Null),
+                (Value::Number(12.5), Value::String("Ann".to_string())),
+                (Value::Number(99.0), Value::String("Bo".to_string())),
+            ]
+        );
+
+        // A changed key is found under its new value
+        query("MATCH (c:Customer {name: 'Bo'}) SET c.email = 'bob@example.com'").unwrap();
+        query("MATCH (p:Purchase {total: 99.0}) SET p.customer = 'bob@example.com'").unwrap();
+        let result = query(
+            "MATCH (p:Purchase) WHERE RESOLVE(p.customer) IS NOT NULL RETURN p.total ORDER BY p.total",
+        )
+        .unwrap();
+        assert_eq!(result.rows.len(), 2);
+
+        let error = query("MATCH (p:Purchase) RETURN RESOLVE(p.total)").unwrap_err();
+        assert!(error.contains("not a reference property"), "{}", error);
+    }
+
+    // The key index is rebuilt when the graph is loaded again
+    let coordinator = QueryCoordinator::from_path(&db_path).expect("Failed to reopen");
+    let session_id = coordinator.create_simple_session("admin").unwrap();
+    let session = coordinator.session_handle(&session_id).unwrap();
+    let query = |text: &str| coordinator.process_query_with_handle(text, &session);
+    query("SESSION SET GRAPH /shop/orders").unwrap();
+    let result =
+        query("MATCH (p:Purchase) WHERE p.total > 50 RETURN RESOLVE(p.customer) AS c").unwrap();
+    match &result.rows[0].values["c"] {
+        Value::Node(node) => assert_eq!(node.properties["name"], Value::String("Bo".into())),
+        other => panic!("expected a node, got {:?}", other),
+    }
+
+    // Keys are meant to be unique; a duplicate makes the reference ambiguous
+    query("INSERT (:Customer {email: 'ann@example.com', name: 'Ann Again'})").unwrap();
+    let error = query("MATCH (p:Purchase {total: 12.5}) RETURN RESOLVE(p.customer)").unwrap_err();
+    assert!(error.contains("ambiguous"), "{}", error);
+
+    // Untyped graphs have no reference properties
+    query("CREATE GRAPH /shop/scratch").unwrap();
+    query("SESSION SET GRAPH /shop/scratch").unwrap();
+    query("INSERT (:Purchase {customer: 'ann@example.com'})").unwrap();
+    let error = query("MATCH (p:Purchase) RETURN RESOLVE(p.customer)").unwrap_err();
+    assert!(error.contains("typed graph"), "{}", error);
+}
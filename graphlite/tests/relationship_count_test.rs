@@ -0,0 +1,274 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! Tests for relationship counts answered from the adjacency indexes
+
+#[path = "testutils/mod.rs"]
+mod testutils;
+use graphlite::plan_testing::{assert_plan_contains, assert_plan_not_contains, op};
+use graphlite::{QueryCoordinator, QueryResult, Value};
+use std::collections::BTreeMap;
+use testutils::test_fixture::TestFixture;
+
+fn counts_by_name(
+    result: &QueryResult,
+    name_column: &str,
+    count_column: &str,
+) -> BTreeMap<String, f64> {
+    result
+        .rows
+        .iter()
+        .map(|row| {
+            let name = match row.values.get(name_column) {
+                Some(Value::String(name)) => name.clone(),
+                other => panic!("Unexpected name {:?}", other),
+            };
+            let count = match row.values.get(count_column) {
+                Some(Value::Number(count)) => *count,
+                other => panic!("Unexpected count {:?}", other),
+            };
+            (name, count)
+        })
+        .collect()
+}
+
+fn expected(pairs: &[(&str, f64)]) -> BTreeMap<String, f64> {
+    pairs
+        .iter()
+        .map(|(name, count)| (name.to_string(), *count))
+        .collect()
+}
+
+fn setup_follow_graph(fixture: &TestFixture) {
+    fixture.assert_query_succeeds(
+        "INSERT (:User {name: 'ann'}), (:User {name: 'bob'}), (:User {name: 'cat'}), \
+         (:Bot {name: 'zed'})",
+    );
+    for (from, to, label) in [
+        ("ann", "bob", "FOLLOWS"),
+        ("ann", "cat", "FOLLOWS"),
+        ("ann", "zed", "FOLLOWS"),
+        ("bob", "cat", "FOLLOWS"),
+        ("cat", "ann", "BLOCKS"),
+    ] {
+        fixture.assert_query_succeeds(&format!(
+            "MATCH (a {{name: '{}'}}), (b {{name: '{}'}}) INSERT (a)-[:{}]->(b)",
+            from, to, label
+        ));
+    }
+}
+
+#[test]
+fn test_degree_aggregation_uses_adjacency_counts() {
+    let fixture = TestFixture::empty().expect("Failed to create fixture");
+    fixture.setup_graph("degree_agg").unwrap();
+    setup_follow_graph(&fixture);
+
+    let result = fixture.assert_query_succeeds(
+        "MATCH (u:User)-[:FOLLOWS]->(f) RETURN u.name, count(f) AS following",
+    );
+    assert_eq!(
+        counts_by_name(&result, "u.name", "following"),
+        expected(&[("ann", 3.0), ("bob", 1.0)])
+    );
+
+    let result =
+        fixture.assert_query_succeeds("MATCH (u:User)<-[:FOLLOWS]-() RETURN u.name, count(*)");
+    assert_eq!(
+        counts_by_name(&result, "u.name", "COUNT_*"),
+        expected(&[("bob", 1.0), ("cat", 2.0)])
+    );
+
+    // The planner replaces the expand with an adjacency count lookup
+    let dir = tempfile::tempdir().unwrap();
+    let coordinator = QueryCoordinator::from_path(dir.path().join("plan_db")).unwrap();
+    let plan = coordinator
+        .explain_query("MATCH (u:User)-[:FOLLOWS]->(f) RETURN u, count(f) AS following")
+        .unwrap();
+    assert_plan_contains(&plan, op("AdjacencyCount").with_label("FOLLOWS"));
+
+    // Grouping by the far end still needs the full expansion
+    let plan = coordinator
+        .explain_query("MATCH (u:User)-[:FOLLOWS]->(f) RETURN f.name, count(u) AS followers")
+        .unwrap();
+    assert_plan_not_contains(&plan, op("AdjacencyCount"));
+}
+
+#[test]
+fn test_count_subquery() {
+    let fixture = TestFixture::empty().expect("Failed to create fixture");
+    fixture.setup_graph("count_subquery").unwrap();
+    setup_follow_graph(&fixture);
+
+    let result = fixture.assert_query_succeeds(
+        "MATCH (u:User) RETURN u.name AS name, COUNT { (u)-[:FOLLOWS]->() } AS following",
+    );
+    assert_eq!(
+        counts_by_name(&result, "name", "following"),
+        expected(&[("ann", 3.0), ("bob", 1.0), ("cat", 0.0)])
+    );
+
+    // Anchored on the right-hand node, with a constrained far end, and undirected
+    let result = fixture.assert_query_succeeds(
+        "MATCH (u:User) RETURN u.name AS name, COUNT { ()-[:FOLLOWS]->(u) } AS followers",
+    );
+    assert_eq!(
+        counts_by_name(&result, "name", "followers"),
+        expected(&[("ann", 0.0), ("bob", 1.0), ("cat", 2.0)])
+    );
+    let result = fixture.assert_query_succeeds(
+        "MATCH (u:User) RETURN u.name AS name, COUNT { (u)-[:FOLLOWS]->(:Bot) } AS bots",
+    );
+    assert_eq!(
+        counts_by_name(&result, "name", "bots"),
+        expected(&[("ann", 1.0), ("bob", 0.0), ("cat", 0.0)])
+    );
+    let result = fixture.assert_query_succeeds(
+        "MATCH (u:User) RETURN u.name AS name, COUNT { (u)-[:FOLLOWS|BLOCKS]-() } AS links",
+    );
+    assert_eq!(
+        counts_by_name(&result, "name", "links"),
+        expected(&[("ann", 4.0), ("bob", 2.0), ("cat", 3.0)])
+    );
+
+    let result = fixture.assert_query_succeeds(
+        "MATCH (u:User) WHERE COUNT { (u)-[:FOLLOWS]->() } > 1 RETURN u.name AS name",
+    );
+    assert_eq!(result.rows.len(), 1);
+    assert_eq!(
+        result.rows[0].values.get("name"),
+        Some(&Value::String("ann".to_string()))
+    );
+
+    // A pattern without a bound node counts over the whole graph
+    let result = fixture.assert_query_succeeds(
+        "MATCH (u:User) RETURN u.name AS name, COUNT { (x)-[:FOLLOWS]->() } AS c",
+    );
+    assert_eq!(
+        counts_by_name(&result, "name", "c"),
+        expected(&[("ann", 4.0), ("bob", 4.0), ("cat", 4.0)])
+    );
+}
+
+#[test]
+fn test_count_and_collect_subqueries() {
+    let fixture = TestFixture::empty().expect("Failed to create fixture");
+    fixture.setup_graph("collect_subquery").unwrap();
+    setup_follow_graph(&fixture);
+
+    let count = |query: &str| {
+        let result = fixture.assert_query_succeeds(query);
+        counts_by_name(&result, "name", "c")
+    };
+
+    // WHERE inside the body, multi-hop patterns and several patterns sharing variables
+    assert_eq!(
+        count(
+            "MATCH (u:User) RETURN u.name AS name, \
+             COUNT { (u)-[:FOLLOWS]->(f) WHERE f.name <> 'cat' } AS c"
+        ),
+        expected(&[("ann", 2.0), ("bob", 0.0), ("cat", 0.0)])
+    );
+    assert_eq!(
+        count(
+            "MATCH (u:User) RETURN u.name AS name, \
+             COUNT { (u)-[:FOLLOWS]->()-[:FOLLOWS]->(:User) } AS c"
+        ),
+        expected(&[("ann", 1.0), ("bob", 0.0), ("cat", 0.0)])
+    );
+    assert_eq!(
+        count(
+            "MATCH (u:User) RETURN u.name AS name, \
+             COUNT { MATCH (u)-[:FOLLOWS]->(f)-[:FOLLOWS]->(x), (u)-[:FOLLOWS]->(x) } AS c"
+        ),
+        expected(&[("ann", 1.0), ("bob", 0.0), ("cat", 0.0)])
+    );
+    assert_eq!(
+        count(
+            "MATCH (u:User) RETURN u.name AS name, \
+             COUNT { MATCH ()-[:FOLLOWS]->(x:User) RETURN DISTINCT x } AS c"
+        ),
+        expected(&[("ann", 2.0), ("bob", 2.0), ("cat", 2.0)])
+    );
+
+    let result = fixture.assert_query_succeeds(
+        "MATCH (u:User) RETURN u.name AS name, \
+         COLLECT { MATCH (u)-[:FOLLOWS]->(f) RETURN f.name } AS follows",
+    );
+    let mut follows: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for row in &result.rows {
+        let (Some(Value::String(name)), Some(Value::List(values))) =
+            (row.values.get("name"), row.values.get("follows"))
+        else {
+            panic!("Unexpected row {:?}", row.values);
+        };
+        let mut names: Vec<String> = values
+            .iter()
+            .map(|value| match value {
+                Value::String(name) => name.clone(),
+                other => panic!("Unexpected name {:?}", other),
+            })
+            .collect();
+        names.sort();
+        follows.insert(name.clone(), names);
+    }
+    assert_eq!(follows["ann"], ["bob", "cat", "zed"]);
+    assert_eq!(follows["bob"], ["cat"]);
+    assert!(follows["cat"].is_empty());
+
+    let result = fixture.assert_query_succeeds(
+        "MATCH (u:User {name: 'ann'}) \
+         RETURN COLLECT { MATCH ()-[:FOLLOWS]->(x:User) RETURN DISTINCT x.name } AS followed",
+    );
+    let Some(Value::List(followed)) = result.rows[0].values.get("followed") else {
+        panic!("Unexpected row {:?}", result.rows[0].values);
+    };
+    assert_eq!(followed.len(), 2);
+
+    fixture.assert_query_fails(
+        "MATCH (u:User) RETURN COLLECT { MATCH (u)-[:FOLLOWS]->(f) } AS c",
+        "RETURN of exactly one expression",
+    );
+    fixture.assert_query_fails(
+        "MATCH (u:User) RETURN COUNT { p = (u)-[:FOLLOWS]->() } AS c",
+        "do not support path variables",
+    );
+}
+
+#[test]
+fn test_counts_follow_deletes() {
+    let fixture = TestFixture::empty().expect("Failed to create fixture");
+    fixture.setup_graph("count_deletes").unwrap();
+    setup_follow_graph(&fixture);
+
+    let following = |fixture: &TestFixture| {
+        let result = fixture.assert_query_succeeds(
+            "MATCH (u:User) RETURN u.name AS name, COUNT { (u)-[:FOLLOWS]->() } AS following",
+        );
+        counts_by_name(&result, "name", "following")
+    };
+
+    fixture.assert_query_succeeds(
+        "MATCH (:User {name: 'ann'})-[r:FOLLOWS]->(:User {name: 'bob'}) DELETE r",
+    );
+    assert_eq!(
+        following(&fixture),
+        expected(&[("ann", 2.0), ("bob", 1.0), ("cat", 0.0)])
+    );
+
+    fixture.assert_query_succeeds("MATCH (b:Bot) DETACH DELETE b");
+    assert_eq!(
+        following(&fixture),
+        expected(&[("ann", 1.0), ("bob", 1.0), ("cat", 0.0)])
+    );
+
+    fixture.assert_query_succeeds("DELETE EDGES OF TYPE :FOLLOWS");
+    assert_eq!(
+        following(&fixture),
+        expected(&[("ann", 0.0), ("bob", 0.0), ("cat", 0.0)])
+    );
+    let result = fixture.assert_query_succeeds(
+        "MATCH (u:User)-[:FOLLOWS]->(f) RETURN u.name, count(f) AS following",
+    );
+    assert!(result.rows.is_empty());
+}
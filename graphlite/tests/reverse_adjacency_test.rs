@@ -0,0 +1,138 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! Tests for incoming-direction expansions served by the reverse adjacency index
+
+#[path = "testutils/mod.rs"]
+mod testutils;
+use graphlite::{QueryCoordinator, QueryResult, Value};
+use testutils::test_fixture::TestFixture;
+
+fn sorted_pairs(result: &QueryResult) -> Vec<(String, String)> {
+    let mut pairs: Vec<(String, String)> = result
+        .rows
+        .iter()
+        .map(|row| {
+            (
+                format!("{:?}", row.values.get("compound")),
+                format!("{:?}", row.values.get("protein")),
+            )
+        })
+        .collect();
+    pairs.sort();
+    pairs
+}
+
+fn setup_target_graph(fixture: &TestFixture) {
+    fixture.assert_query_succeeds(
+        "INSERT (:Compound {name: 'aspirin'}), (:Compound {name: 'ibuprofen'}), \
+         (:Protein {name: 'COX1'}), (:Protein {name: 'COX2'})",
+    );
+    for (compound, protein, label) in [
+        ("aspirin", "COX1", "INHIBITS"),
+        ("aspirin", "COX2", "INHIBITS"),
+        ("ibuprofen", "COX2", "INHIBITS"),
+        ("ibuprofen", "COX1", "BINDS"),
+        ("aspirin", "COX2", "BINDS"),
+    ] {
+        fixture.assert_query_succeeds(&format!(
+            "MATCH (c:Compound {{name: '{}'}}), (p:Protein {{name: '{}'}}) \
+             INSERT (c)-[:{}]->(p)",
+            compound, protein, label
+        ));
+    }
+}
+
+#[test]
+fn test_incoming_expansion_matches_outgoing() {
+    let fixture = TestFixture::empty().expect("Failed to create fixture");
+    fixture.setup_graph("reverse_adjacency").unwrap();
+    setup_target_graph(&fixture);
+
+    for label in ["INHIBITS", "BINDS"] {
+        let outgoing = fixture.assert_query_succeeds(&format!(
+            "MATCH (c:Compound)-[:{}]->(p:Protein) RETURN c.name AS compound, p.name AS protein",
+            label
+        ));
+        let incoming = fixture.assert_query_succeeds(&format!(
+            "MATCH (p:Protein)<-[:{}]-(c:Compound) RETURN c.name AS compound, p.name AS protein",
+            label
+        ));
+        assert_eq!(
+            sorted_pairs(&incoming),
+            sorted_pairs(&outgoing),
+            "{}",
+            label
+        );
+    }
+
+    fixture.assert_first_value(
+        "MATCH (p:Protein {name: 'COX2'})<-[r]-(c:Compound) RETURN count(r) AS incoming",
+        "incoming",
+        Value::Number(3.0),
+    );
+    fixture.assert_first_value(
+        "MATCH (p:Protein {name: 'COX2'})<-[r:INHIBITS|BINDS]-(c:Compound) RETURN count(r) AS incoming",
+        "incoming",
+        Value::Number(3.0),
+    );
+}
+
+#[test]
+fn test_incoming_expansion_after_deletes() {
+    let fixture = TestFixture::empty().expect("Failed to create fixture");
+    fixture.setup_graph("reverse_adjacency_deletes").unwrap();
+    setup_target_graph(&fixture);
+
+    fixture.assert_query_succeeds(
+        "MATCH (c:Compound {name: 'aspirin'})-[r:INHIBITS]->(p:Protein {name: 'COX2'}) DELETE r",
+    );
+    fixture.assert_first_value(
+        "MATCH (p:Protein {name: 'COX2'})<-[r:INHIBITS]-(c:Compound) RETURN count(r) AS incoming",
+        "incoming",
+        Value::Number(1.0),
+    );
+
+    fixture.assert_query_succeeds("MATCH (c:Compound {name: 'ibuprofen'}) DETACH DELETE c");
+    fixture.assert_first_value(
+        "MATCH (p:Protein)<-[r]-(c:Compound) RETURN count(r) AS incoming",
+        "incoming",
+        Value::Number(2.0),
+    );
+}
+
+#[test]
+fn test_incoming_expansion_after_reopen() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let db_path = temp_dir.path().join("reverse_adjacency_db");
+
+    {
+        let coordinator = QueryCoordinator::from_path(&db_path).expect("Failed to create database");
+        let session_id = coordinator.create_simple_session("admin").unwrap();
+        for query in [
+            "CREATE SCHEMA /pharma",
+            "CREATE GRAPH /pharma/targets",
+            "SESSION SET GRAPH /pharma/targets",
+            "INSERT (:Compound {name: 'aspirin'}), (:Protein {name: 'COX1'})",
+            "MATCH (c:Compound), (p:Protein) INSERT (c)-[:INHIBITS]->(p)",
+        ] {
+            coordinator.process_query(query, &session_id).unwrap();
+        }
+    }
+
+    let coordinator = QueryCoordinator::from_path(&db_path).expect("Failed to reopen database");
+    let session_id = coordinator.create_simple_session("admin").unwrap();
+    coordinator
+        .process_query("SESSION SET GRAPH /pharma/targets", &session_id)
+        .unwrap();
+    let result = coordinator
+        .process_query(
+            "MATCH (p:Protein)<-[:INHIBITS]-(c:Compound) RETURN c.name AS compound",
+            &session_id,
+        )
+        .unwrap();
+    assert_eq!(
+        result.rows[0].values.get("compound"),
+        Some(&Value::String("aspirin".to_string()))
+    );
+}
@@ -0,0 +1,65 @@
+#[path = "testutils/mod.rs"]
+mod testutils;
+use graphlite::Value;
+use testutils::test_fixture::TestFixture;
+
+fn number(result: &graphlite::QueryResult, column: &str) -> f64 {
+    match result.rows[0].values.get(column) {
+        Some(Value::Number(n)) => *n,
+        other => panic!("Expected number for {}, got {:?}", column, other),
+    }
+}
+
+#[test]
+fn test_sample_data_pharma_preset() {
+    let fixture = TestFixture::empty().expect("Failed to create fixture");
+    fixture.setup_graph("pharma_sample").unwrap();
+
+    let result = fixture.assert_query_succeeds("CALL gql.sample_data('pharma', 1)");
+    assert_eq!(
+        result.rows[0].values.get("domain"),
+        Some(&Value::String("pharma".to_string()))
+    );
+    assert_eq!(number(&result, "nodes_created"), 80.0);
+    assert!(number(&result, "edges_created") > 0.0);
+
+    // Same schema as the drug discovery examples
+    let result = fixture.assert_query_succeeds(
+        "MATCH (c:Compound)-[r:INHIBITS]->(p:Protein) WHERE r.IC50 < 100 RETURN count(r) AS potent",
+    );
+    assert!(number(&result, "potent") > 0.0);
+}
+
+#[test]
+fn test_sample_data_scale_and_idempotence() {
+    let fixture = TestFixture::empty().expect("Failed to create fixture");
+    fixture.setup_graph("social_sample").unwrap();
+
+    let result = fixture.assert_query_succeeds("CALL gql.sample_data('social', 0.5)");
+    assert_eq!(number(&result, "nodes_created"), 5.0 + 50.0 + 100.0);
+
+    fixture.assert_first_value(
+        "MATCH (p:Person) RETURN count(p) AS people",
+        "people",
+        Value::Number(50.0),
+    );
+
+    // Loading the same preset again finds every node already present
+    let result = fixture.assert_query_succeeds("CALL gql.sample_data('social', 0.5)");
+    assert_eq!(number(&result, "nodes_created"), 0.0);
+    assert_eq!(number(&result, "edges_created"), 0.0);
+}
+
+#[test]
+fn test_sample_data_errors() {
+    let fixture = TestFixture::empty().expect("Failed to create fixture");
+
+    fixture.assert_query_fails("CALL gql.sample_data('fraud')", "current graph");
+
+    fixture.setup_graph("bad_sample").unwrap();
+    fixture.assert_query_fails(
+        "CALL gql.sample_data('weather')",
+        "Unknown sample data domain",
+    );
+    fixture.assert_query_fails("CALL gql.sample_data('fraud', 0)", "positive number");
+}
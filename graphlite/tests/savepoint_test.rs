@@ -0,0 +1,84 @@
+//! Tests for SAVEPOINT, ROLLBACK TO SAVEPOINT and RELEASE SAVEPOINT
+
+#[path = "testutils/mod.rs"]
+mod testutils;
+
+use graphlite::Value;
+use testutils::test_fixture::TestFixture;
+
+fn person_count(fixture: &TestFixture) -> Value {
+    let result = fixture
+        .query("MATCH (p:Person) RETURN count(p) AS n")
+        .expect("Failed to count persons");
+    result.rows[0].values.get("n").cloned().unwrap()
+}
+
+#[test]
+fn test_rollback_to_savepoint_keeps_earlier_changes() {
+    let fixture = TestFixture::new().expect("Failed to create fixture");
+    fixture
+        .setup_graph("savepoint_rollback_test")
+        .expect("Failed to setup graph");
+
+    fixture.query("START TRANSACTION").unwrap();
+    fixture.query("INSERT (:Person {name: 'Alice'})").unwrap();
+    fixture.query("SAVEPOINT sp1").unwrap();
+    fixture.query("INSERT (:Person {name: 'Bob'})").unwrap();
+    fixture.query("SAVEPOINT sp2").unwrap();
+    fixture.query("INSERT (:Person {name: 'Carol'})").unwrap();
+    assert_eq!(person_count(&fixture), Value::Number(3.0));
+
+    fixture.query("ROLLBACK TO SAVEPOINT sp1").unwrap();
+    assert_eq!(person_count(&fixture), Value::Number(1.0));
+
+    // The savepoint stays set, the ones after it are gone
+    fixture.query("INSERT (:Person {name: 'Dave'})").unwrap();
+    fixture.query("ROLLBACK TO sp1").unwrap();
+    assert_eq!(person_count(&fixture), Value::Number(1.0));
+    let error = fixture
+        .query("ROLLBACK TO SAVEPOINT sp2")
+        .expect_err("sp2 was removed by rolling back to sp1");
+    assert!(error.contains("sp2"), "unexpected error: {}", error);
+
+    fixture.query("COMMIT").unwrap();
+    assert_eq!(person_count(&fixture), Value::Number(1.0));
+}
+
+#[test]
+fn test_release_savepoint_keeps_changes() {
+    let fixture = TestFixture::new().expect("Failed to create fixture");
+    fixture
+        .setup_graph("savepoint_release_test")
+        .expect("Failed to setup graph");
+
+    fixture.query("START TRANSACTION").unwrap();
+    fixture.query("SAVEPOINT sp1").unwrap();
+    fixture.query("INSERT (:Person {name: 'Alice'})").unwrap();
+    fixture.query("RELEASE SAVEPOINT sp1").unwrap();
+    let error = fixture
+        .query("ROLLBACK TO SAVEPOINT sp1")
+        .expect_err("sp1 was released");
+    assert!(error.contains("sp1"), "unexpected error: {}", error);
+    fixture.query("COMMIT").unwrap();
+    assert_eq!(person_count(&fixture), Value::Number(1.0));
+
+    // Rolling back the whole transaction also undoes changes before a savepoint
+    fixture.query("START TRANSACTION").unwrap();
+    fixture.query("INSERT (:Person {name: 'Bob'})").unwrap();
+    fixture.query("SAVEPOINT sp1").unwrap();
+    fixture.query("ROLLBACK").unwrap();
+    assert_eq!(person_count(&fixture), Value::Number(1.0));
+}
+
+#[test]
+fn test_savepoint_requires_transaction() {
+    let fixture = TestFixture::new().expect("Failed to create fixture");
+    fixture
+        .setup_graph("savepoint_no_transaction_test")
+        .expect("Failed to setup graph");
+
+    let error = fixture
+        .query("SAVEPOINT sp1")
+        .expect_err("SAVEPOINT needs an open transaction");
+    assert!(error.contains("transaction"), "unexpected error: {}", error);
+}
@@ -0,0 +1,124 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! Tests for per-session statement history and gql.session_history
+
+use graphlite::{DatabaseConfig, QueryCoordinator, Value};
+
+fn statements(coordinator: &QueryCoordinator, session_id: &str) -> Vec<String> {
+    coordinator
+        .session_history(session_id)
+        .unwrap()
+        .into_iter()
+        .map(|entry| entry.statement)
+        .collect()
+}
+
+#[test]
+fn test_session_history_procedure() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let coordinator =
+        QueryCoordinator::from_path(temp_dir.path().join("history_db")).expect("Failed to open");
+    let session_id = coordinator.create_simple_session("admin").unwrap();
+
+    for query in [
+        "CREATE SCHEMA /hist",
+        "CREATE GRAPH /hist/g",
+        "SESSION SET GRAPH /hist/g",
+        "INSERT (:Item {i: 1})",
+    ] {
+        coordinator.process_query(query, &session_id).unwrap();
+    }
+    coordinator
+        .process_query("MATCH (n:NoSuchThing RETURN n", &session_id)
+        .unwrap_err();
+
+    let result = coordinator
+        .process_query("CALL gql.session_history()", &session_id)
+        .unwrap();
+    assert_eq!(result.rows.len(), 5);
+
+    let first = &result.rows[0];
+    assert_eq!(first.values.get("sequence"), Some(&Value::Number(1.0)));
+    assert_eq!(
+        first.values.get("statement"),
+        Some(&Value::String("CREATE SCHEMA /hist".to_string()))
+    );
+    assert_eq!(
+        first.values.get("status"),
+        Some(&Value::String("ok".to_string()))
+    );
+    assert_eq!(first.values.get("error"), Some(&Value::Null));
+    assert!(matches!(
+        first.values.get("executed_at"),
+        Some(Value::DateTime(_))
+    ));
+
+    let failed = &result.rows[4];
+    assert_eq!(
+        failed.values.get("status"),
+        Some(&Value::String("error".to_string()))
+    );
+    assert!(
+        matches!(failed.values.get("error"), Some(Value::String(e)) if e.contains("Parse error"))
+    );
+
+    // The previous CALL is now part of the history
+    let result = coordinator
+        .process_query("CALL gql.session_history(2)", &session_id)
+        .unwrap();
+    let sequences: Vec<&Value> = result
+        .rows
+        .iter()
+        .filter_map(|row| row.values.get("sequence"))
+        .collect();
+    assert_eq!(sequences, vec![&Value::Number(5.0), &Value::Number(6.0)]);
+
+    // Sessions do not see each other's statements
+    let other_session = coordinator.create_simple_session("admin").unwrap();
+    assert!(statements(&coordinator, &other_session).is_empty());
+
+    assert!(coordinator
+        .process_query("CALL gql.session_history('x')", &session_id)
+        .unwrap_err()
+        .contains("non-negative integer"));
+}
+
+#[test]
+fn test_session_history_size() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let coordinator = QueryCoordinator::from_path_with_config(
+        temp_dir.path().join("history_size_db"),
+        DatabaseConfig {
+            session_history_size: 2,
+            ..DatabaseConfig::default()
+        },
+    )
+    .expect("Failed to open");
+    let session_id = coordinator.create_simple_session("admin").unwrap();
+
+    for query in ["CREATE SCHEMA /a", "CREATE SCHEMA /b", "CREATE SCHEMA /c"] {
+        coordinator.process_query(query, &session_id).unwrap();
+    }
+    assert_eq!(
+        statements(&coordinator, &session_id),
+        vec!["CREATE SCHEMA /b", "CREATE SCHEMA /c"]
+    );
+    let history = coordinator.session_history(&session_id).unwrap();
+    assert_eq!(history[1].sequence, 3);
+
+    let disabled = QueryCoordinator::from_path_with_config(
+        temp_dir.path().join("history_disabled_db"),
+        DatabaseConfig {
+            session_history_size: 0,
+            ..DatabaseConfig::default()
+        },
+    )
+    .expect("Failed to open");
+    let session_id = disabled.create_simple_session("admin").unwrap();
+    disabled
+        .process_query("CREATE SCHEMA /a", &session_id)
+        .unwrap();
+    assert!(statements(&disabled, &session_id).is_empty());
+    assert!(disabled.session_history("no-such-session").is_err());
+}
@@ -0,0 +1,161 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! Tests for ANY SHORTEST and ALL SHORTEST path searches
+
+#[path = "testutils/mod.rs"]
+mod testutils;
+use graphlite::plan_testing::{assert_plan_contains, op};
+use graphlite::{QueryCoordinator, QueryResult, Value};
+use testutils::test_fixture::TestFixture;
+
+/// A -> B -> D and A -> C -> D, then D -> E, with a slow A -> F -> G -> E
+fn setup_roads(fixture: &TestFixture) {
+    fixture.assert_query_succeeds(
+        "INSERT (:City {name: 'A'}), (:City {name: 'B'}), (:City {name: 'C'}), \
+         (:City {name: 'D'}), (:City {name: 'E'}), (:City {name: 'F'}), (:City {name: 'G'})",
+    );
+    for (from, to, toll) in [
+        ("A", "B", true),
+        ("A", "C", false),
+        ("B", "D", false),
+        ("C", "D", false),
+        ("D", "E", false),
+        ("A", "F", false),
+        ("F", "G", false),
+        ("G", "E", false),
+    ] {
+        fixture.assert_query_succeeds(&format!(
+            "MATCH (x:City {{name: '{}'}}), (y:City {{name: '{}'}}) \
+             INSERT (x)-[:ROAD {{toll: {}}}]->(y)",
+            from, to, toll
+        ));
+    }
+}
+
+fn numbers(result: &QueryResult, column: &str) -> Vec<f64> {
+    result
+        .rows
+        .iter()
+        .map(|row| match row.values.get(column) {
+            Some(Value::Number(n)) => *n,
+            other => panic!("Unexpected value {:?}", other),
+        })
+        .collect()
+}
+
+#[test]
+fn test_any_shortest_returns_one_path_per_pair() {
+    let fixture = TestFixture::empty().expect("Failed to create fixture");
+    fixture.setup_graph("shortest_any").unwrap();
+    setup_roads(&fixture);
+
+    let result = fixture.assert_query_succeeds(
+        "MATCH p = ANY SHORTEST (a:City {name: 'A'})-[r:ROAD]->+(e:City {name: 'E'}) \
+         RETURN path_length(p) AS hops, size(r) AS edges",
+    );
+    assert_eq!(numbers(&result, "hops"), vec![3.0]);
+    assert_eq!(numbers(&result, "edges"), vec![3.0]);
+
+    // One row for every reachable city, including A itself with zero hops
+    let result = fixture.assert_query_succeeds(
+        "MATCH p = ANY SHORTEST (a:City {name: 'A'})-[:ROAD]->*(b:City) \
+         RETURN b.name AS name, path_length(p) AS hops ORDER BY name",
+    );
+    assert_eq!(
+        numbers(&result, "hops"),
+        vec![0.0, 1.0, 1.0, 2.0, 3.0, 1.0, 2.0]
+    );
+}
+
+#[test]
+fn test_all_shortest_returns_every_minimal_path() {
+    let fixture = TestFixture::empty().expect("Failed to create fixture");
+    fixture.setup_graph("shortest_all").unwrap();
+    setup_roads(&fixture);
+
+    let result = fixture.assert_query_succeeds(
+        "MATCH p = ALL SHORTEST (a:City {name: 'A'})-[:ROAD]->+(e:City {name: 'E'}) \
+         RETURN path_length(p) AS hops",
+    );
+    assert_eq!(numbers(&result, "hops"), vec![3.0, 3.0, 3.0]);
+
+    // Edge filters apply to every edge of the path
+    let result = fixture.assert_query_succeeds(
+        "MATCH p = ALL SHORTEST (a:City {name: 'A'})-[:ROAD {toll: false}]->+(e:City {name: 'E'}) \
+         RETURN path_length(p) AS hops",
+    );
+    assert_eq!(numbers(&result, "hops"), vec![3.0, 3.0]);
+}
+
+#[test]
+fn test_shortest_path_bounds_and_direction() {
+    let fixture = TestFixture::empty().expect("Failed to create fixture");
+    fixture.setup_graph("shortest_bounds").unwrap();
+    setup_roads(&fixture);
+
+    // No path within the hop limit, and none against the edges' direction
+    let result = fixture.assert_query_succeeds(
+        "MATCH ANY SHORTEST (a:City {name: 'A'})-[:ROAD*..2]->(e:City {name: 'E'}) RETURN e",
+    );
+    assert!(result.rows.is_empty());
+    let result = fixture.assert_query_succeeds(
+        "MATCH ANY SHORTEST (e:City {name: 'E'})-[:ROAD]->+(a:City {name: 'A'}) RETURN a",
+    );
+    assert!(result.rows.is_empty());
+
+    fixture.assert_first_value(
+        "MATCH p = ANY SHORTEST (e:City {name: 'E'})<-[:ROAD]-+(a:City {name: 'A'}) \
+         RETURN path_length(p) AS hops",
+        "hops",
+        Value::Number(3.0),
+    );
+    fixture.assert_first_value(
+        "MATCH p = ANY SHORTEST (b:City {name: 'B'})-[:ROAD]-+(c:City {name: 'C'}) \
+         RETURN path_length(p) AS hops",
+        "hops",
+        Value::Number(2.0),
+    );
+}
+
+#[test]
+fn test_unsupported_shortest_patterns() {
+    let fixture = TestFixture::empty().expect("Failed to create fixture");
+    fixture.setup_graph("shortest_errors").unwrap();
+
+    assert!(fixture
+        .query("MATCH ANY SHORTEST (a)-[:ROAD]->(b)-[:ROAD]->(c) RETURN c")
+        .is_err());
+    // A minimum above one hop cannot be combined with a shortest search
+    for pattern in [
+        "ANY SHORTEST (a)-[:ROAD*2..]->(b)",
+        "ANY SHORTEST (a)-[*2..5]->(b)",
+        "ALL SHORTEST (a)-[:ROAD]->{3,4}(b)",
+    ] {
+        let error = fixture
+            .query(&format!("MATCH {} RETURN b", pattern))
+            .expect_err(pattern);
+        assert!(
+            error.contains("supports a minimum of at most one hop"),
+            "{}: {}",
+            pattern,
+            error
+        );
+    }
+}
+
+#[test]
+fn test_shortest_path_plan() {
+    let dir = tempfile::tempdir().unwrap();
+    let coordinator = QueryCoordinator::from_path(dir.path().join("plan_db")).unwrap();
+    let plan = coordinator
+        .explain_query("MATCH ALL SHORTEST (a:City)-[:ROAD]->+(b:City) RETURN b.name")
+        .unwrap();
+    assert_plan_contains(
+        &plan,
+        op("ShortestPath")
+            .with_label("ROAD")
+            .with_attr("all", "true")
+            .with_attr("min_hops", "1"),
+    );
+}
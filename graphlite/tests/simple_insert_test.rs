@@ -43,3 +43,23 @@ fn test_simple_insert() {
     fixture.query("INSERT (a:Account {id: 1, name: 'Account1', balance: 100.0, status: 'active', account_type: 'savings'})")
         .expect("Failed to insert full Account node");
 }
+
+#[test]
+fn test_insert_non_ascii_string() {
+    let fixture = TestFixture::new().expect("Failed to create fixture");
+    fixture
+        .setup_graph("non_ascii_graph")
+        .expect("Failed to setup graph");
+
+    // Keyword matching must not split multi-byte characters after `{`
+    fixture
+        .query("INSERT (:City {name: 'Zürich 🏔'})")
+        .expect("Failed to insert non-ASCII string");
+    let result = fixture
+        .query("MATCH (c:City) RETURN c.name AS name")
+        .expect("Failed to read non-ASCII string");
+    assert_eq!(
+        result.rows[0].values.get("name"),
+        Some(&graphlite::Value::String("Zürich 🏔".to_string()))
+    );
+}
@@ -0,0 +1,88 @@
+//! Tests for the database directory lock and warm standby takeover
+
+use graphlite::{DatabaseConfig, QueryCoordinator, Value};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+fn standby_config(wait: Duration) -> DatabaseConfig {
+    DatabaseConfig {
+        standby_wait: Some(wait),
+        ..DatabaseConfig::default()
+    }
+}
+
+#[test]
+fn test_second_open_fails_while_locked() {
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let path = dir.path().join("locked_db");
+    let primary = QueryCoordinator::from_path(&path).expect("Failed to open database");
+
+    let holder = QueryCoordinator::lock_holder(&path).expect("database should be locked");
+    assert_eq!(holder.pid, std::process::id());
+
+    let error = QueryCoordinator::from_path(&path)
+        .err()
+        .expect("second open should fail");
+    assert!(error.contains("Database is locked"), "{}", error);
+
+    let error =
+        QueryCoordinator::from_path_with_config(&path, standby_config(Duration::from_millis(300)))
+            .err()
+            .expect("standby should give up after its wait");
+    assert!(error.contains("after waiting"), "{}", error);
+
+    drop(primary);
+    assert_eq!(QueryCoordinator::lock_holder(&path), None);
+    QueryCoordinator::from_path(&path).expect("Failed to reopen database");
+}
+
+#[test]
+fn test_standby_takes_over_when_primary_closes() {
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let path = dir.path().join("standby_db");
+
+    let primary = QueryCoordinator::from_path(&path).expect("Failed to open database");
+    let session = primary.create_simple_session("admin").unwrap();
+    for query in [
+        "CREATE SCHEMA /standby",
+        "CREATE GRAPH /standby/g",
+        "SESSION SET GRAPH /standby/g",
+        "INSERT (:Person {name: 'Alice'})",
+    ] {
+        primary.process_query(query, &session).unwrap();
+    }
+
+    let (opened_tx, opened_rx) = mpsc::channel();
+    let standby_path = path.clone();
+    let standby = thread::spawn(move || {
+        let coordinator =
+            QueryCoordinator::from_path_with_config(&standby_path, standby_config(Duration::MAX));
+        opened_tx.send(()).unwrap();
+        coordinator
+    });
+
+    // The standby keeps waiting while the primary is open
+    assert!(opened_rx.recv_timeout(Duration::from_millis(500)).is_err());
+    primary.close_session(&session).unwrap();
+    drop(primary);
+
+    opened_rx
+        .recv_timeout(Duration::from_secs(30))
+        .expect("standby should take over");
+    let coordinator = standby
+        .join()
+        .unwrap()
+        .expect("standby failed to open database");
+    let session = coordinator.create_simple_session("admin").unwrap();
+    coordinator
+        .process_query("SESSION SET GRAPH /standby/g", &session)
+        .unwrap();
+    let result = coordinator
+        .process_query("MATCH (p:Person) RETURN p.name AS name", &session)
+        .unwrap();
+    assert_eq!(
+        result.rows[0].values.get("name"),
+        Some(&Value::String("Alice".to_string()))
+    );
+}
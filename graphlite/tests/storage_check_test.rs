@@ -0,0 +1,81 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! Tests for storage checksums and `QueryCoordinator::check_storage`
+
+use graphlite::QueryCoordinator;
+
+#[test]
+fn test_check_storage_verifies_saved_records() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let db_path = temp_dir.path().join("check_db");
+    {
+        let coordinator = QueryCoordinator::from_path(&db_path).expect("Failed to open");
+        let session_id = coordinator.create_simple_session("admin").unwrap();
+        for query in [
+            "CREATE SCHEMA /check",
+            "CREATE GRAPH /check/g",
+            "SESSION SET GRAPH /check/g",
+            "INSERT (:Item {n: 1}), (:Item {n: 2}), (:Item {n: 3})",
+            "MATCH (a:Item {n: 1}), (b:Item {n: 2}) INSERT (a)-[:NEXT]->(b)",
+        ] {
+            coordinator.process_query(query, &session_id).unwrap();
+        }
+    }
+
+    let coordinator = QueryCoordinator::from_path(&db_path).expect("Failed to reopen");
+    let quick = coordinator.check_storage(false).unwrap();
+    assert!(quick.is_ok(), "{:?}", quick.problems);
+    assert!(!quick.deep);
+    assert_eq!(quick.verified, 0);
+
+    let deep = coordinator.check_storage(true).unwrap();
+    assert!(deep.is_ok(), "{:?}", deep.problems);
+    assert!(deep.deep);
+    assert!(deep.verified >= 4, "{:?}", deep);
+    assert!(deep.records >= deep.verified);
+
+    // Records written with checksums load normally
+    let session_id = coordinator.create_simple_session("admin").unwrap();
+    coordinator
+        .process_query("SESSION SET GRAPH /check/g", &session_id)
+        .unwrap();
+    let result = coordinator
+        .process_query("MATCH (i:Item) RETURN i.n", &session_id)
+        .unwrap();
+    assert_eq!(result.rows.len(), 3);
+    assert_eq!(coordinator.corrupt_records_detected(), 0);
+}
+
+#[test]
+fn test_verify_clean_database() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let db_path = temp_dir.path().join("verify_db");
+    {
+        let coordinator = QueryCoordinator::from_path(&db_path).expect("Failed to open");
+        let session_id = coordinator.create_simple_session("admin").unwrap();
+        for query in [
+            "CREATE SCHEMA /verify",
+            "CREATE GRAPH TYPE /verify/ItemType ( NODE TYPE Item (n INTEGER) )",
+            "CREATE GRAPH /verify/items TYPED /verify/ItemType",
+            "CREATE GRAPH /verify/g",
+            "SESSION SET GRAPH /verify/g",
+            "INSERT (:Item {n: 1}), (:Item {n: 2})",
+            "MATCH (a:Item {n: 1}), (b:Item {n: 2}) INSERT (a)-[:NEXT]->(b)",
+            "CREATE GRAPH /verify/dropped",
+            "SESSION SET GRAPH /verify/dropped",
+            "INSERT (:Item {n: 3})",
+            "DROP GRAPH /verify/dropped",
+        ] {
+            coordinator.process_query(query, &session_id).unwrap();
+        }
+    }
+
+    let coordinator = QueryCoordinator::from_path(&db_path).expect("Failed to reopen");
+    let report = coordinator.verify(true).unwrap();
+    assert!(report.is_ok(), "{:?}", report);
+    assert_eq!(report.problem_count(), 0);
+    assert!(report.storage.deep);
+    assert!(report.references.nodes >= 2);
+    assert!(report.references.edges >= 1);
+}
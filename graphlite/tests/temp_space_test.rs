@@ -0,0 +1,66 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! Tests for the per-query temp space and its report in gql.storage_stats()
+
+use graphlite::{DatabaseConfig, QueryCoordinator, Value};
+
+#[test]
+fn test_storage_stats_reports_temp_space() {
+    let dir = tempfile::tempdir().unwrap();
+    let db_path = dir.path().join("temp_db");
+
+    // Files left by a crashed process are removed when the database is opened
+    let stale = db_path.join("tmp").join("query_7");
+    std::fs::create_dir_all(&stale).unwrap();
+    std::fs::write(stale.join("sort_run_0.tmp"), b"stale run").unwrap();
+
+    let config = DatabaseConfig {
+        temp_space_quota_bytes: Some(4096),
+        ..DatabaseConfig::default()
+    };
+    let coordinator = QueryCoordinator::from_path_with_config(&db_path, config).unwrap();
+    assert!(!stale.exists());
+
+    let session_id = coordinator.create_simple_session("admin").unwrap();
+    let result = coordinator
+        .process_query("CALL gql.storage_stats()", &session_id)
+        .unwrap();
+    assert_eq!(result.rows.len(), 1);
+    let row = &result.rows[0].values;
+    assert_eq!(
+        row.get("temp_path"),
+        Some(&Value::String(db_path.join("tmp").display().to_string()))
+    );
+    assert_eq!(row.get("temp_quota_bytes"), Some(&Value::Number(4096.0)));
+    assert_eq!(row.get("temp_used_bytes"), Some(&Value::Number(0.0)));
+    assert_eq!(row.get("active_temp_queries"), Some(&Value::Number(0.0)));
+    assert_eq!(row.get("temp_quota_rejections"), Some(&Value::Number(0.0)));
+
+    let err = coordinator
+        .process_query("CALL gql.storage_stats('extra')", &session_id)
+        .unwrap_err();
+    assert!(err.contains("expects no arguments"), "{}", err);
+}
+
+#[test]
+fn test_unlimited_temp_space_quota() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = DatabaseConfig {
+        temp_space_quota_bytes: None,
+        ..DatabaseConfig::default()
+    };
+    let coordinator =
+        QueryCoordinator::from_path_with_config(dir.path().join("db"), config).unwrap();
+    let session_id = coordinator.create_simple_session("admin").unwrap();
+    let result = coordinator
+        .process_query(
+            "CALL gql.storage_stats() YIELD temp_quota_bytes",
+            &session_id,
+        )
+        .unwrap();
+    assert_eq!(
+        result.rows[0].values.get("temp_quota_bytes"),
+        Some(&Value::Null)
+    );
+}
@@ -142,6 +142,38 @@ impl CliFixture {
         String::from_utf8_lossy(&output.stderr).to_string()
     }
 
+    /// Run a CLI subcommand against the database and expect success
+    ///
+    /// `--path`, `--user` and `--password` are added after `args`; returns stdout.
+    pub fn assert_command_succeeds(&self, args: &[&str]) -> String {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "--quiet",
+                "--package",
+                "gql-cli",
+                "--bin",
+                "graphlite",
+                "--",
+            ])
+            .args(args)
+            .arg("--path")
+            .arg(&self.db_path)
+            .arg("--user")
+            .arg(&self.admin_user)
+            .arg("--password")
+            .arg(&self.admin_password)
+            .env("RUST_LOG", "error") // Suppress INFO logs in CLI output
+            .output()
+            .expect("Failed to execute command");
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            panic!("Command failed: {}\nCommand: {:?}", stderr, args);
+        }
+        String::from_utf8_lossy(&output.stdout).to_string()
+    }
+
     /// Get unique schema name for test isolation
     pub fn schema_name(&self) -> String {
         format!("test_{}", fastrand::u64(..))
@@ -0,0 +1,66 @@
+//! Tests for START TRANSACTION isolation level and access mode
+
+#[path = "testutils/mod.rs"]
+mod testutils;
+
+use graphlite::Value;
+use testutils::test_fixture::TestFixture;
+
+#[test]
+fn test_read_only_transaction_rejects_writes() {
+    let fixture = TestFixture::new().expect("Failed to create fixture");
+    fixture
+        .setup_graph("read_only_transaction_test")
+        .expect("Failed to setup graph");
+    fixture
+        .query("INSERT (:Person {name: 'Alice'})")
+        .expect("Failed to create person");
+
+    fixture
+        .query("START TRANSACTION READ ONLY")
+        .expect("START TRANSACTION READ ONLY should succeed");
+    let error = fixture
+        .query("INSERT (:Person {name: 'Bob'})")
+        .expect_err("INSERT should fail in a read-only transaction");
+    assert!(error.contains("read-only"), "unexpected error: {}", error);
+    let result = fixture
+        .query("MATCH (p:Person) RETURN count(p) AS n")
+        .expect("MATCH should succeed in a read-only transaction");
+    assert_eq!(result.rows[0].values.get("n"), Some(&Value::Number(1.0)));
+    fixture.query("COMMIT").expect("COMMIT should succeed");
+
+    // The access mode only lasts for that transaction
+    fixture
+        .query("START TRANSACTION ISOLATION LEVEL READ COMMITTED READ WRITE")
+        .expect("START TRANSACTION READ WRITE should succeed");
+    fixture
+        .query("INSERT (:Person {name: 'Bob'})")
+        .expect("INSERT should succeed in a read-write transaction");
+    fixture.query("COMMIT").expect("COMMIT should succeed");
+    fixture
+        .query("INSERT (:Person {name: 'Carol'})")
+        .expect("INSERT should succeed outside a transaction");
+}
+
+#[test]
+fn test_unsupported_isolation_level_is_rejected() {
+    let fixture = TestFixture::new().expect("Failed to create fixture");
+    fixture
+        .setup_graph("isolation_level_test")
+        .expect("Failed to setup graph");
+
+    let error = fixture
+        .query("START TRANSACTION ISOLATION LEVEL SERIALIZABLE")
+        .expect_err("SERIALIZABLE is not implemented");
+    assert!(
+        error.contains("SERIALIZABLE"),
+        "unexpected error: {}",
+        error
+    );
+
+    // No transaction was left open
+    fixture
+        .query("START TRANSACTION")
+        .expect("START TRANSACTION should succeed");
+    fixture.query("ROLLBACK").expect("ROLLBACK should succeed");
+}
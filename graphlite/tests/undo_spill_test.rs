@@ -0,0 +1,67 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! Tests for spilling the undo log of large transactions to disk
+
+use graphlite::{DatabaseConfig, QueryCoordinator, Value};
+
+fn item_count(coordinator: &QueryCoordinator, session_id: &str) -> Value {
+    let result = coordinator
+        .process_query("MATCH (n:Item) RETURN count(n) AS n", session_id)
+        .unwrap();
+    result.rows[0].values.get("n").cloned().unwrap()
+}
+
+fn insert_items(coordinator: &QueryCoordinator, session_id: &str, range: std::ops::Range<usize>) {
+    for i in range {
+        coordinator
+            .process_query(&format!("INSERT (:Item {{i: {}}})", i), session_id)
+            .unwrap();
+    }
+}
+
+#[test]
+fn test_spilled_transaction_rolls_back() {
+    let dir = tempfile::tempdir().unwrap();
+    let db_path = dir.path().join("spill_db");
+    let config = DatabaseConfig {
+        undo_spill_threshold: Some(8),
+        ..DatabaseConfig::default()
+    };
+    let coordinator = QueryCoordinator::from_path_with_config(&db_path, config).unwrap();
+    let session_id = coordinator.create_simple_session("admin").unwrap();
+    for query in [
+        "CREATE SCHEMA /spill",
+        "CREATE GRAPH /spill/g",
+        "SESSION SET GRAPH /spill/g",
+    ] {
+        coordinator.process_query(query, &session_id).unwrap();
+    }
+    insert_items(&coordinator, &session_id, 0..3);
+
+    // Rollback reads the spilled operations back from disk
+    coordinator
+        .process_query("START TRANSACTION", &session_id)
+        .unwrap();
+    insert_items(&coordinator, &session_id, 3..40);
+    assert!(db_path.join("undo_spill").exists());
+    assert_eq!(item_count(&coordinator, &session_id), Value::Number(40.0));
+    coordinator.process_query("ROLLBACK", &session_id).unwrap();
+    assert_eq!(item_count(&coordinator, &session_id), Value::Number(3.0));
+
+    // A savepoint set after the first spill undoes only the later operations
+    coordinator
+        .process_query("START TRANSACTION", &session_id)
+        .unwrap();
+    insert_items(&coordinator, &session_id, 3..20);
+    coordinator
+        .process_query("SAVEPOINT sp1", &session_id)
+        .unwrap();
+    insert_items(&coordinator, &session_id, 20..45);
+    coordinator
+        .process_query("ROLLBACK TO SAVEPOINT sp1", &session_id)
+        .unwrap();
+    assert_eq!(item_count(&coordinator, &session_id), Value::Number(20.0));
+    coordinator.process_query("COMMIT", &session_id).unwrap();
+    assert_eq!(item_count(&coordinator, &session_id), Value::Number(20.0));
+}
@@ -0,0 +1,192 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! Tests for variable-length relationship patterns
+
+#[path = "testutils/mod.rs"]
+mod testutils;
+use graphlite::plan_testing::{assert_plan_contains, op};
+use graphlite::{QueryCoordinator, QueryResult, Value};
+use testutils::test_fixture::TestFixture;
+
+/// A -> B -> C -> D, with C -> A closing a cycle
+fn setup_chain(fixture: &TestFixture) {
+    fixture.assert_query_succeeds(
+        "INSERT (:Person {name: 'A'}), (:Person {name: 'B'}), (:Person {name: 'C'}), \
+         (:Person {name: 'D'}), (:Company {name: 'Acme'})",
+    );
+    for (from, to, weight) in [("A", "B", 1), ("B", "C", 2), ("C", "D", 1), ("C", "A", 1)] {
+        fixture.assert_query_succeeds(&format!(
+            "MATCH (x:Person {{name: '{}'}}), (y:Person {{name: '{}'}}) \
+             INSERT (x)-[:KNOWS {{weight: {}}}]->(y)",
+            from, to, weight
+        ));
+    }
+    fixture.assert_query_succeeds(
+        "MATCH (x:Person {name: 'D'}), (y:Company {name: 'Acme'}) INSERT (x)-[:KNOWS]->(y)",
+    );
+}
+
+fn names(result: &QueryResult, column: &str) -> Vec<String> {
+    let mut names: Vec<String> = result
+        .rows
+        .iter()
+        .map(|row| match row.values.get(column) {
+            Some(Value::String(name)) => name.clone(),
+            other => panic!("Unexpected name {:?}", other),
+        })
+        .collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn test_star_quantifier_bounds() {
+    let fixture = TestFixture::empty().expect("Failed to create fixture");
+    fixture.setup_graph("var_length_star").unwrap();
+    setup_chain(&fixture);
+
+    let cases = [
+        ("*", vec!["Acme", "B", "C", "D"]),
+        ("*1..2", vec!["B", "C"]),
+        ("*2", vec!["C"]),
+        ("*2..", vec!["Acme", "C", "D"]),
+        ("*..1", vec!["B"]),
+        ("*0..1", vec!["A", "B"]),
+    ];
+    for (quantifier, expected) in cases {
+        let result = fixture.assert_query_succeeds(&format!(
+            "MATCH (a:Person {{name: 'A'}})-[:KNOWS{}]->(b) RETURN b.name AS name",
+            quantifier
+        ));
+        assert_eq!(
+            names(&result, "name"),
+            expected,
+            "quantifier {}",
+            quantifier
+        );
+    }
+}
+
+#[test]
+fn test_brace_quantifiers() {
+    let fixture = TestFixture::empty().expect("Failed to create fixture");
+    fixture.setup_graph("var_length_braces").unwrap();
+    setup_chain(&fixture);
+
+    for pattern in ["-[:KNOWS]{1,2}->", "-[:KNOWS]->{1,2}"] {
+        let result = fixture.assert_query_succeeds(&format!(
+            "MATCH (a:Person {{name: 'A'}}){}(b) RETURN b.name AS name",
+            pattern
+        ));
+        assert_eq!(
+            names(&result, "name"),
+            vec!["B", "C"],
+            "pattern {}",
+            pattern
+        );
+    }
+
+    let result = fixture
+        .assert_query_succeeds("MATCH (a:Person {name: 'D'})<-[:KNOWS]-+(b) RETURN b.name AS name");
+    assert_eq!(names(&result, "name"), vec!["A", "B", "C"]);
+}
+
+#[test]
+fn test_cycles_are_not_revisited() {
+    let fixture = TestFixture::empty().expect("Failed to create fixture");
+    fixture.setup_graph("var_length_cycles").unwrap();
+    setup_chain(&fixture);
+
+    // The open-ended walk ends at the cycle instead of going round it
+    let result = fixture
+        .assert_query_succeeds("MATCH (a:Person {name: 'B'})-[:KNOWS*]->(b) RETURN b.name AS name");
+    assert_eq!(names(&result, "name"), vec!["A", "Acme", "C", "D"]);
+
+    // Undirected walks reach D both ways round the cycle
+    let result = fixture.assert_query_succeeds(
+        "MATCH (a:Person {name: 'B'})-[:KNOWS*2..2]-(b:Person) RETURN b.name AS name",
+    );
+    assert_eq!(names(&result, "name"), vec!["A", "C", "D"]);
+}
+
+#[test]
+fn test_edge_and_target_filters() {
+    let fixture = TestFixture::empty().expect("Failed to create fixture");
+    fixture.setup_graph("var_length_filters").unwrap();
+    setup_chain(&fixture);
+
+    // Every edge walked must match the property map
+    let result = fixture.assert_query_succeeds(
+        "MATCH (a:Person {name: 'C'})-[:KNOWS* {weight: 1}]->(b) RETURN b.name AS name",
+    );
+    assert_eq!(names(&result, "name"), vec!["A", "B", "D"]);
+
+    // Target labels and properties apply to the last node only
+    let result = fixture.assert_query_succeeds(
+        "MATCH (a:Person {name: 'A'})-[:KNOWS*]->(b:Company) RETURN b.name AS name",
+    );
+    assert_eq!(names(&result, "name"), vec!["Acme"]);
+    let result = fixture.assert_query_succeeds(
+        "MATCH (a:Person {name: 'A'})-[:KNOWS*]->(b {name: 'D'}) RETURN b.name AS name",
+    );
+    assert_eq!(names(&result, "name"), vec!["D"]);
+}
+
+#[test]
+fn test_edge_list_and_path_variables() {
+    let fixture = TestFixture::empty().expect("Failed to create fixture");
+    fixture.setup_graph("var_length_bindings").unwrap();
+    setup_chain(&fixture);
+
+    fixture.assert_first_value(
+        "MATCH (a:Person {name: 'A'})-[r:KNOWS*]->(b {name: 'D'}) RETURN size(r) AS hops",
+        "hops",
+        Value::Number(3.0),
+    );
+    fixture.assert_first_value(
+        "MATCH p = (a:Person {name: 'A'})-[:KNOWS*]->(b:Company) RETURN path_length(p) AS hops",
+        "hops",
+        Value::Number(4.0),
+    );
+
+    // The path runs across every hop of the pattern
+    let result = fixture.assert_query_succeeds(
+        "MATCH p = (a:Person {name: 'A'})-[:KNOWS]->(b)-[:KNOWS*1..2]->(c) \
+         RETURN c.name AS name, path_length(p) AS hops",
+    );
+    assert_eq!(result.rows.len(), 3);
+    for row in &result.rows {
+        let expected = match row.values.get("name") {
+            Some(Value::String(name)) if name == "C" => 2.0,
+            _ => 3.0,
+        };
+        assert_eq!(row.values.get("hops"), Some(&Value::Number(expected)));
+    }
+}
+
+#[test]
+fn test_invalid_quantifier_bounds() {
+    let fixture = TestFixture::empty().expect("Failed to create fixture");
+    fixture.setup_graph("var_length_errors").unwrap();
+
+    assert!(fixture
+        .query("MATCH (a)-[:KNOWS*3..1]->(b) RETURN b")
+        .is_err());
+}
+
+#[test]
+fn test_variable_length_plan() {
+    let dir = tempfile::tempdir().unwrap();
+    let coordinator = QueryCoordinator::from_path(dir.path().join("plan_db")).unwrap();
+    let plan = coordinator
+        .explain_query("MATCH (a:Person)-[:KNOWS*1..3]->(b) RETURN b.name")
+        .unwrap();
+    assert_plan_contains(
+        &plan,
+        op("VariableLengthExpand")
+            .with_label("KNOWS")
+            .with_attr("min_hops", "1")
+            .with_attr("max_hops", "3"),
+    );
+}
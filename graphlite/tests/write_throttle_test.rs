@@ -0,0 +1,113 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! Tests for per-graph write throttling and backpressure signals
+
+use graphlite::{BulkNode, QueryCoordinator, QueryErrorKind, WriteLimit};
+use std::collections::HashMap;
+use std::time::Duration;
+
+#[test]
+fn test_write_limit_throttles_and_rejects_writes() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let coordinator =
+        QueryCoordinator::from_path(temp_dir.path().join("throttle_db")).expect("Failed to open");
+    let session_id = coordinator.create_simple_session("admin").unwrap();
+    for query in [
+        "CREATE SCHEMA /throttle",
+        "CREATE GRAPH /throttle/g",
+        "SESSION SET GRAPH /throttle/g",
+    ] {
+        coordinator.process_query(query, &session_id).unwrap();
+    }
+
+    coordinator
+        .set_write_limit(
+            "/throttle/g",
+            Some(WriteLimit {
+                writes_per_second: 20.0,
+                burst: 1,
+                max_wait: Duration::from_millis(200),
+            }),
+        )
+        .unwrap();
+    assert_eq!(
+        coordinator
+            .write_limit("/throttle/g")
+            .unwrap()
+            .writes_per_second,
+        20.0
+    );
+
+    let first = coordinator
+        .process_query("INSERT (:Item {i: 1})", &session_id)
+        .unwrap();
+    assert!(!first.warnings.iter().any(|w| w.contains("throttled")));
+    let second = coordinator
+        .process_query("INSERT (:Item {i: 2})", &session_id)
+        .unwrap();
+    assert!(
+        second
+            .warnings
+            .iter()
+            .any(|w| w.starts_with("Write to /throttle/g throttled")),
+        "{:?}",
+        second.warnings
+    );
+
+    // Reads are never throttled
+    let read = coordinator
+        .process_query("MATCH (n:Item) RETURN count(n) AS items", &session_id)
+        .unwrap();
+    assert!(read.warnings.is_empty(), "{:?}", read.warnings);
+
+    // Bulk loads count as one write each
+    coordinator
+        .bulk_insert_nodes(
+            &session_id,
+            vec![BulkNode {
+                labels: vec!["Item".to_string()],
+                properties: HashMap::new(),
+            }],
+        )
+        .unwrap();
+
+    coordinator
+        .set_write_limit(
+            "/throttle/g",
+            Some(WriteLimit {
+                writes_per_second: 0.5,
+                burst: 1,
+                max_wait: Duration::from_millis(10),
+            }),
+        )
+        .unwrap();
+    coordinator
+        .process_query("INSERT (:Item {i: 3})", &session_id)
+        .unwrap();
+    let error = coordinator
+        .process_query("INSERT (:Item {i: 4})", &session_id)
+        .unwrap_err();
+    assert!(error.starts_with("Write rate limit exceeded"), "{}", error);
+    assert_eq!(
+        QueryErrorKind::from_message(&error),
+        QueryErrorKind::LimitExceeded
+    );
+
+    let metrics = coordinator.write_metrics();
+    assert_eq!(metrics.graphs.len(), 1);
+    let graph = &metrics.graphs[0];
+    assert_eq!(graph.graph, "/throttle/g");
+    assert_eq!(graph.writes, 4);
+    assert!(graph.throttled >= 1);
+    assert_eq!(graph.rejected, 1);
+    assert_eq!(graph.queue_depth, 0);
+
+    coordinator.set_write_limit("/throttle/g", None).unwrap();
+    coordinator
+        .process_query("INSERT (:Item {i: 4})", &session_id)
+        .unwrap();
+    assert!(coordinator
+        .set_write_limit("/throttle/g", Some(WriteLimit::per_second(-1.0)))
+        .is_err());
+}
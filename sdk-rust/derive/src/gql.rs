@@ -0,0 +1,101 @@
+//! `gql!`: queries checked with the engine's parser at compile time
+
+use graphlite::QueryCoordinator;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{Expr, Ident, LitStr, Token};
+
+/// `"query"` followed by optional `name` / `name = expr` parameter bindings
+pub(crate) struct GqlInput {
+    query: LitStr,
+    bindings: Option<Punctuated<Binding, Token![,]>>,
+}
+
+/// A `$name` parameter bound to the local `name` or to an expression
+struct Binding {
+    name: Ident,
+    value: Option<Expr>,
+}
+
+impl Parse for GqlInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let query = input.parse()?;
+        if input.is_empty() {
+            return Ok(Self {
+                query,
+                bindings: None,
+            });
+        }
+        input.parse::<Token![,]>()?;
+        Ok(Self {
+            query,
+            bindings: Some(Punctuated::parse_terminated(input)?),
+        })
+    }
+}
+
+impl Parse for Binding {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse()?;
+        let value = if input.peek(Token![=]) {
+            input.parse::<Token![=]>()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        Ok(Self { name, value })
+    }
+}
+
+pub(crate) fn expand(input: GqlInput) -> syn::Result<TokenStream> {
+    let query = &input.query;
+    let parameters = QueryCoordinator::check_query(&query.value())
+        .map_err(|e| syn::Error::new(query.span(), format!("invalid GQL: {}", e)))?;
+
+    let bindings = match input.bindings {
+        Some(bindings) if !bindings.is_empty() => bindings,
+        _ => return Ok(quote! { #query }),
+    };
+
+    let mut bound: Vec<String> = Vec::new();
+    let mut adds = Vec::new();
+    for binding in &bindings {
+        let name = binding.name.to_string();
+        if !parameters.contains(&name) {
+            return Err(syn::Error::new(
+                binding.name.span(),
+                format!("the query has no parameter ${}", name),
+            ));
+        }
+        if bound.contains(&name) {
+            return Err(syn::Error::new(
+                binding.name.span(),
+                format!("parameter ${} is bound twice", name),
+            ));
+        }
+        let value = match &binding.value {
+            Some(value) => quote! { #value },
+            None => {
+                let local = &binding.name;
+                quote! { #local }
+            }
+        };
+        adds.push(quote! { .add(#name, #value) });
+        bound.push(name);
+    }
+    if let Some(missing) = parameters.iter().find(|name| !bound.contains(name)) {
+        return Err(syn::Error::new(
+            query.span(),
+            format!(
+                "parameter ${} is not bound; add `{}` or `{} = ...`",
+                missing, missing, missing
+            ),
+        ));
+    }
+
+    Ok(quote! {
+        (#query, ::graphlite_sdk::Params::new() #(#adds)*)
+    })
+}
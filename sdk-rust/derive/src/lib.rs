@@ -0,0 +1,257 @@
+//! Procedural macros for the GraphLite SDK
+//!
+//! `#[derive(GraphNode)]` and `#[derive(GraphEdge)]` implement the
+//! `graphlite_sdk::GraphNode` and `graphlite_sdk::GraphEdge` traits for a
+//! struct with named fields, and `gql!` checks a query at compile time. Use
+//! them through the re-exports in `graphlite_sdk` rather than depending on
+//! this crate directly.
+//!
+//! ```ignore
+//! use graphlite_sdk::{GraphEdge, GraphNode};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize, GraphNode)]
+//! #[graph(label = "Person")]
+//! struct Person {
+//!     #[graph(id)]
+//!     key: String,
+//!     name: String,
+//! }
+//!
+//! #[derive(Serialize, Deserialize, GraphEdge)]
+//! #[graph(label = "KNOWS")]
+//! struct Knows {
+//!     since: i64,
+//! }
+//! ```
+
+mod gql;
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::quote;
+use syn::punctuated::Punctuated;
+use syn::{parse_macro_input, Data, DeriveInput, Expr, ExprLit, Fields, Lit, LitStr, Meta, Token};
+
+/// Implement `graphlite_sdk::GraphNode`
+///
+/// The label defaults to the struct name and can be set with
+/// `#[graph(label = "...")]` on the struct. The id property is the field
+/// marked `#[graph(id)]`, or the field named `id`; a `#[serde(rename)]` on
+/// that field is honored.
+#[proc_macro_derive(GraphNode, attributes(graph))]
+pub fn derive_graph_node(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_node(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Implement `graphlite_sdk::GraphEdge`
+///
+/// The label defaults to the struct name in upper snake case (`WorksAt`
+/// becomes `WORKS_AT`) and can be set with `#[graph(label = "...")]` on the
+/// struct.
+#[proc_macro_derive(GraphEdge, attributes(graph))]
+pub fn derive_graph_edge(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_edge(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Check a GQL query at compile time
+///
+/// The query is parsed with the engine's parser and a syntax error fails the
+/// build. `gql!("...")` expands to the query string. Listing the `$params` of
+/// the query after it, as `name` (the local variable of that name) or
+/// `name = expr`, expands to a `(query, graphlite_sdk::Params)` pair instead;
+/// every parameter must then be bound exactly once.
+#[proc_macro]
+pub fn gql(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as gql::GqlInput);
+    gql::expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand_node(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    named_fields(input, "GraphNode")?;
+    let label = struct_label(input)?.unwrap_or_else(|| input.ident.to_string());
+    check_label(&label, input)?;
+    let id_property = id_property(input)?;
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::graphlite_sdk::GraphNode for #name #ty_generics #where_clause {
+            const LABEL: &'static str = #label;
+            const ID_PROPERTY: &'static str = #id_property;
+        }
+    })
+}
+
+fn expand_edge(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    named_fields(input, "GraphEdge")?;
+    let label = struct_label(input)?.unwrap_or_else(|| upper_snake_case(&input.ident.to_string()));
+    check_label(&label, input)?;
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::graphlite_sdk::GraphEdge for #name #ty_generics #where_clause {
+            const LABEL: &'static str = #label;
+        }
+    })
+}
+
+/// The fields of a struct with named fields (`struct S { .. }`, possibly empty)
+fn named_fields<'a>(
+    input: &'a DeriveInput,
+    derive: &str,
+) -> syn::Result<&'a Punctuated<syn::Field, Token![,]>> {
+    match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => Ok(&fields.named),
+            _ => Err(syn::Error::new_spanned(
+                &input.ident,
+                format!(
+                    "{} can only be derived for structs with named fields",
+                    derive
+                ),
+            )),
+        },
+        _ => Err(syn::Error::new_spanned(
+            &input.ident,
+            format!("{} can only be derived for structs", derive),
+        )),
+    }
+}
+
+/// The `label` of a `#[graph(label = "...")]` attribute on the struct
+fn struct_label(input: &DeriveInput) -> syn::Result<Option<String>> {
+    let mut label = None;
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("graph")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("label") {
+                let value: LitStr = meta.value()?.parse()?;
+                label = Some(value.value());
+                Ok(())
+            } else {
+                Err(meta.error("unsupported graph attribute, expected `label = \"...\"`"))
+            }
+        })?;
+    }
+    Ok(label)
+}
+
+/// Labels are spliced into queries, so they must be plain identifiers
+fn check_label(label: &str, input: &DeriveInput) -> syn::Result<()> {
+    let mut chars = label.chars();
+    let valid = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(syn::Error::new_spanned(
+            &input.ident,
+            format!(
+                "label '{}' must start with a letter or '_' and contain only letters, digits and '_'",
+                label
+            ),
+        ))
+    }
+}
+
+/// Property name of the id field: the one marked `#[graph(id)]`, else the one named `id`
+fn id_property(input: &DeriveInput) -> syn::Result<String> {
+    let fields = named_fields(input, "GraphNode")?;
+
+    let mut marked = None;
+    for field in fields {
+        for attr in field.attrs.iter().filter(|a| a.path().is_ident("graph")) {
+            attr.parse_nested_meta(|meta| {
+                if !meta.path.is_ident("id") {
+                    return Err(meta.error("unsupported graph attribute, expected `id`"));
+                }
+                if marked.is_some() {
+                    return Err(meta.error("only one field can be marked #[graph(id)]"));
+                }
+                marked = Some(field);
+                Ok(())
+            })?;
+        }
+    }
+
+    let field = marked
+        .or_else(|| {
+            fields
+                .iter()
+                .find(|field| field.ident.as_ref().is_some_and(|ident| ident == "id"))
+        })
+        .ok_or_else(|| {
+            syn::Error::new(
+                Span::call_site(),
+                "GraphNode needs an id field: name it `id` or mark it with #[graph(id)]",
+            )
+        })?;
+
+    match serde_rename(field)? {
+        Some(renamed) => Ok(renamed),
+        None => Ok(field
+            .ident
+            .as_ref()
+            .map(|ident| ident.to_string().trim_start_matches("r#").to_string())
+            .unwrap_or_default()),
+    }
+}
+
+/// The name given by `#[serde(rename = "...")]` on a field
+fn serde_rename(field: &syn::Field) -> syn::Result<Option<String>> {
+    for attr in field.attrs.iter().filter(|a| a.path().is_ident("serde")) {
+        let metas = attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?;
+        for meta in metas {
+            if let Meta::NameValue(name_value) = meta {
+                if let (
+                    true,
+                    Expr::Lit(ExprLit {
+                        lit: Lit::Str(renamed),
+                        ..
+                    }),
+                ) = (name_value.path.is_ident("rename"), &name_value.value)
+                {
+                    return Ok(Some(renamed.value()));
+                }
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// `WorksAt` -> `WORKS_AT`
+fn upper_snake_case(name: &str) -> String {
+    let mut result = String::new();
+    let mut previous: Option<char> = None;
+    for c in name.chars() {
+        if c.is_uppercase() && previous.is_some_and(|p| p.is_lowercase() || p.is_ascii_digit()) {
+            result.push('_');
+        }
+        result.extend(c.to_uppercase());
+        previous = Some(c);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_upper_snake_case() {
+        assert_eq!(upper_snake_case("Knows"), "KNOWS");
+        assert_eq!(upper_snake_case("WorksAt"), "WORKS_AT");
+        assert_eq!(upper_snake_case("HasV2Key"), "HAS_V2_KEY");
+    }
+}
@@ -0,0 +1,339 @@
+//! Async API on tokio
+//!
+//! GraphLite executes queries synchronously, so calling it from an async task
+//! blocks one of the runtime's worker threads for the length of the query.
+//! `AsyncGraphLite` and `AsyncSession` run every call on tokio's blocking pool
+//! instead (`tokio::task::spawn_blocking`), which keeps web services built on
+//! axum, actix or similar responsive while queries run.
+//!
+//! Requires the `async` feature.
+
+use crate::connection::{GraphLite, Session};
+use crate::error::{Error, Result};
+use crate::hooks::{QueryEvent, QueryHooks};
+use crate::params::Params;
+use crate::retry::RetryPolicy;
+use crate::transaction::{Transaction, TxOptions};
+use graphlite::{QueryCoordinator, QueryResult};
+use serde::de::DeserializeOwned;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Run a blocking closure on tokio's blocking pool
+async fn run_blocking<T, F>(f: F) -> Result<T>
+where
+    F: FnOnce() -> Result<T> + Send + 'static,
+    T: Send + 'static,
+{
+    tokio::task::spawn_blocking(f)
+        .await
+        .map_err(|e| Error::GraphLite(format!("Blocking task failed: {}", e)))?
+}
+
+/// Async handle to a GraphLite database
+///
+/// Cheap to clone; clones share the same open database.
+///
+/// # Examples
+///
+/// ```no_run
+/// use graphlite_sdk::AsyncGraphLite;
+///
+/// # async fn run() -> Result<(), graphlite_sdk::Error> {
+/// let db = AsyncGraphLite::open("./mydb").await?;
+/// let session = db.session("admin").await?;
+/// let result = session.query("MATCH (n:Person) RETURN n.name").await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct AsyncGraphLite {
+    coordinator: Arc<QueryCoordinator>,
+    retry_policy: RetryPolicy,
+    hooks: QueryHooks,
+}
+
+impl AsyncGraphLite {
+    /// Open a GraphLite database at the given path
+    ///
+    /// Opening reads the database from disk, so it runs on the blocking pool too.
+    pub async fn open<P: Into<PathBuf>>(path: P) -> Result<Self> {
+        let path = path.into();
+        run_blocking(move || GraphLite::open(path))
+            .await
+            .map(Self::from)
+    }
+
+    /// Set how sessions created from now on retry transient failures
+    ///
+    /// See `GraphLite::with_retry_policy`.
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = policy;
+        self
+    }
+
+    /// Call `hook` after every statement run by this database's sessions
+    ///
+    /// See `GraphLite::on_query`. Hooks run on the blocking pool.
+    pub fn on_query<F>(&self, hook: F)
+    where
+        F: Fn(&QueryEvent<'_>) + Send + Sync + 'static,
+    {
+        self.hooks.add(hook);
+    }
+
+    /// Create a new session for the given user
+    pub async fn session(&self, username: &str) -> Result<AsyncSession> {
+        let coordinator = self.coordinator.clone();
+        let retry_policy = self.retry_policy.clone();
+        let hooks = self.hooks.clone();
+        let username = username.to_string();
+        run_blocking(move || {
+            let id = coordinator
+                .create_simple_session(&username)
+                .map_err(|e| Error::Session(format!("Failed to create session: {}", e)))?;
+            Ok(AsyncSession {
+                session: Arc::new(Session::new(id, coordinator, username, retry_policy, hooks)),
+            })
+        })
+        .await
+    }
+
+    /// Get access to the underlying QueryCoordinator
+    ///
+    /// Its methods block; call them from `tokio::task::spawn_blocking`.
+    pub fn coordinator(&self) -> &QueryCoordinator {
+        &self.coordinator
+    }
+}
+
+impl From<GraphLite> for AsyncGraphLite {
+    fn from(db: GraphLite) -> Self {
+        let (coordinator, retry_policy, hooks) = db.into_parts();
+        Self {
+            coordinator,
+            retry_policy,
+            hooks,
+        }
+    }
+}
+
+/// Async session, created with `AsyncGraphLite::session`
+///
+/// Cheap to clone; clones share the session and its transaction state.
+#[derive(Clone)]
+pub struct AsyncSession {
+    session: Arc<Session>,
+}
+
+impl AsyncSession {
+    /// Get the session ID
+    pub fn id(&self) -> &str {
+        self.session.id()
+    }
+
+    /// Get the username associated with this session
+    pub fn username(&self) -> &str {
+        self.session.username()
+    }
+
+    /// Execute a GQL query in this session
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use graphlite_sdk::AsyncGraphLite;
+    /// # async fn run() -> Result<(), graphlite_sdk::Error> {
+    /// # let db = AsyncGraphLite::open("./mydb").await?;
+    /// let session = db.session("admin").await?;
+    /// let result = session.query("MATCH (n:Person) RETURN n").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn query(&self, query: &str) -> Result<QueryResult> {
+        let session = self.session.clone();
+        let query = query.to_string();
+        run_blocking(move || session.query(&query)).await
+    }
+
+    /// Execute a GQL query and deserialize each row into `T`
+    ///
+    /// See `Session::query_as`.
+    pub async fn query_as<T>(&self, query: &str) -> Result<Vec<T>>
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        let session = self.session.clone();
+        let query = query.to_string();
+        run_blocking(move || session.query_as(&query)).await
+    }
+
+    /// Execute a statement without returning results
+    pub async fn execute(&self, statement: &str) -> Result<()> {
+        let session = self.session.clone();
+        let statement = statement.to_string();
+        run_blocking(move || session.execute(&statement)).await
+    }
+
+    /// Execute a GQL query with `$name` parameters bound to values
+    ///
+    /// See `Session::query_with_params`.
+    pub async fn query_with_params(&self, query: &str, params: Params) -> Result<QueryResult> {
+        let session = self.session.clone();
+        let query = query.to_string();
+        run_blocking(move || session.query_with_params(&query, params)).await
+    }
+
+    /// Execute a statement with `$name` parameters without returning results
+    pub async fn execute_with_params(&self, statement: &str, params: Params) -> Result<()> {
+        let session = self.session.clone();
+        let statement = statement.to_string();
+        run_blocking(move || session.execute_with_params(&statement, params)).await
+    }
+
+    /// Execute several statements in one transaction and return the result of each
+    ///
+    /// See `Session::execute_batch`.
+    pub async fn execute_batch<S: AsRef<str>>(&self, statements: &[S]) -> Result<Vec<QueryResult>> {
+        let session = self.session.clone();
+        let statements: Vec<String> = statements
+            .iter()
+            .map(|statement| statement.as_ref().to_string())
+            .collect();
+        run_blocking(move || session.execute_batch(&statements)).await
+    }
+
+    /// Run several independent queries and return the result or error of each
+    ///
+    /// See `Session::query_many`.
+    pub async fn query_many<S: AsRef<str>>(&self, queries: &[S]) -> Vec<Result<QueryResult>> {
+        let session = self.session.clone();
+        let queries: Vec<String> = queries
+            .iter()
+            .map(|query| query.as_ref().to_string())
+            .collect();
+        let count = queries.len();
+        match run_blocking(move || Ok(session.query_many(&queries))).await {
+            Ok(results) => results,
+            // The blocking task itself failed, so no query has a result
+            Err(e) => {
+                let message = match e {
+                    Error::GraphLite(message) => message,
+                    other => other.to_string(),
+                };
+                (0..count)
+                    .map(|_| Err(Error::GraphLite(message.clone())))
+                    .collect()
+            }
+        }
+    }
+
+    /// Turn per-operator timing of this session's queries on or off
+    ///
+    /// See `Session::set_operator_timing`. Only flips a session setting, so it
+    /// does not need the blocking pool.
+    pub fn set_operator_timing(&self, enabled: bool) -> Result<()> {
+        self.session.set_operator_timing(enabled)
+    }
+
+    /// Run `f` in a transaction on the blocking pool
+    ///
+    /// The transaction commits if `f` returns `Ok` and rolls back if it returns
+    /// `Err`. `f` runs synchronously from start to commit, so a transaction never
+    /// stays open across an `.await` of the caller.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use graphlite_sdk::AsyncGraphLite;
+    /// # async fn run() -> Result<(), graphlite_sdk::Error> {
+    /// # let db = AsyncGraphLite::open("./mydb").await?;
+    /// let session = db.session("admin").await?;
+    /// let people = session
+    ///     .transaction(|tx| {
+    ///         tx.execute("INSERT (:Person {name: 'Alice'})")?;
+    ///         tx.execute("INSERT (:Person {name: 'Bob'})")?;
+    ///         Ok(tx.query("MATCH (p:Person) RETURN p.name")?.rows.len())
+    ///     })
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn transaction<T, F>(&self, f: F) -> Result<T>
+    where
+        F: FnOnce(&mut Transaction<'_>) -> Result<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        self.transaction_with(TxOptions::default(), f).await
+    }
+
+    /// Run `f` on the blocking pool in a transaction with the given options
+    ///
+    /// See [`AsyncSession::transaction`] and `Session::transaction_with`.
+    pub async fn transaction_with<T, F>(&self, options: TxOptions, f: F) -> Result<T>
+    where
+        F: FnOnce(&mut Transaction<'_>) -> Result<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let session = self.session.clone();
+        run_blocking(move || {
+            let mut tx = session.transaction_with(options)?;
+            let value = f(&mut tx)?;
+            tx.commit()?;
+            Ok(value)
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_async_query_and_transaction() {
+        let dir = std::env::temp_dir().join(format!("sdk_async_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let db = AsyncGraphLite::open(&dir).await.unwrap();
+        let session = db.session("admin").await.unwrap();
+        for statement in [
+            "CREATE SCHEMA /async_test",
+            "CREATE GRAPH /async_test/g",
+            "SESSION SET GRAPH /async_test/g",
+        ] {
+            session.execute(statement).await.unwrap();
+        }
+
+        let count = session
+            .transaction(|tx| {
+                tx.execute("INSERT (:Item {n: 1}), (:Item {n: 2})")?;
+                Ok(tx.query("MATCH (i:Item) RETURN i.n")?.rows.len())
+            })
+            .await
+            .unwrap();
+        assert_eq!(count, 2);
+
+        // A failing closure rolls the transaction back
+        let failed = session
+            .transaction(|tx| {
+                tx.execute("INSERT (:Item {n: 3})")?;
+                Err::<(), _>(Error::InvalidOperation("abort".to_string()))
+            })
+            .await;
+        assert!(failed.is_err());
+
+        // Queries from concurrent tasks share the session
+        let tasks: Vec<_> = (0..4)
+            .map(|_| {
+                let session = session.clone();
+                tokio::spawn(async move { session.query("MATCH (i:Item) RETURN i.n").await })
+            })
+            .collect();
+        for task in tasks {
+            assert_eq!(task.await.unwrap().unwrap().rows.len(), 2);
+        }
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -4,9 +4,15 @@
 //! It follows a similar pattern to rusqlite (SQLite's Rust bindings) but adapted
 //! for graph databases.
 
+use crate::bulk::BulkLoad;
 use crate::error::{Error, Result};
-use crate::transaction::Transaction;
-use graphlite::{QueryCoordinator, QueryResult};
+use crate::hooks::{Counted, QueryEvent, QueryHooks};
+use crate::params::Params;
+use crate::result::TypedResult;
+use crate::retry::RetryPolicy;
+use crate::transaction::{Transaction, TxOptions};
+use graphlite::{OperatorTiming, QueryCoordinator, QueryResult, Value};
+use serde::de::DeserializeOwned;
 use std::sync::Arc;
 
 /// Main entry point for GraphLite database operations
@@ -34,6 +40,8 @@ use std::sync::Arc;
 /// ```
 pub struct GraphLite {
     coordinator: Arc<QueryCoordinator>,
+    retry_policy: RetryPolicy,
+    hooks: QueryHooks,
 }
 
 impl GraphLite {
@@ -58,7 +66,138 @@ impl GraphLite {
     pub fn open<P: AsRef<std::path::Path>>(path: P) -> Result<Self> {
         let coordinator = QueryCoordinator::from_path(path)
             .map_err(|e| Error::Connection(format!("Failed to open database: {}", e)))?;
-        Ok(GraphLite { coordinator })
+        Ok(GraphLite::from_coordinator(coordinator))
+    }
+
+    /// Wrap an open coordinator (for internal SDK use)
+    pub(crate) fn from_coordinator(coordinator: Arc<QueryCoordinator>) -> Self {
+        GraphLite {
+            coordinator,
+            retry_policy: RetryPolicy::default(),
+            hooks: QueryHooks::default(),
+        }
+    }
+
+    /// Set how sessions created from now on retry transient failures
+    ///
+    /// See [`RetryPolicy`]. Use `RetryPolicy::never()` to disable retries.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use graphlite_sdk::{GraphLite, RetryPolicy};
+    ///
+    /// let db = GraphLite::open("./mydb")?.with_retry_policy(RetryPolicy::new().max_attempts(5));
+    /// # Ok::<(), graphlite_sdk::Error>(())
+    /// ```
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = policy;
+        self
+    }
+
+    /// Get the retry policy given to new sessions
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.retry_policy
+    }
+
+    /// Write a copy of the database to the directory `path` while it stays open
+    ///
+    /// The storage engine copies the data itself, so sessions can keep using the
+    /// database during the backup and no file is copied while being written. The
+    /// backup is a complete database directory that [`GraphLite::open`] or
+    /// [`GraphLite::restore_from`] can use. `path` must not exist or be an empty
+    /// directory. Fails while any session has an open transaction, so the backup
+    /// holds committed data only.
+    ///
+    /// Returns the number of storage entries copied.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use graphlite_sdk::GraphLite;
+    ///
+    /// let db = GraphLite::open("./mydb")?;
+    /// db.backup_to("./backups/mydb-2025-01-01")?;
+    /// # Ok::<(), graphlite_sdk::Error>(())
+    /// ```
+    pub fn backup_to<P: AsRef<std::path::Path>>(&self, path: P) -> Result<usize> {
+        self.coordinator.backup(path).map_err(Error::Backup)
+    }
+
+    /// Restore a backup written by [`GraphLite::backup_to`] into `target` and open it
+    ///
+    /// `target` must not exist or be an empty directory, and the backup must not
+    /// be open. The backup itself is left unchanged, so it can be restored again.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use graphlite_sdk::GraphLite;
+    ///
+    /// let db = GraphLite::restore_from("./backups/mydb-2025-01-01", "./mydb-restored")?;
+    /// let session = db.session("admin")?;
+    /// # Ok::<(), graphlite_sdk::Error>(())
+    /// ```
+    pub fn restore_from<P, Q>(backup: P, target: Q) -> Result<Self>
+    where
+        P: AsRef<std::path::Path>,
+        Q: AsRef<std::path::Path>,
+    {
+        QueryCoordinator::restore_backup(backup, &target).map_err(Error::Backup)?;
+        Self::open(target)
+    }
+
+    /// Start a bulk load of nodes and edges into graph `graph` (`/schema/graph`)
+    ///
+    /// See [`BulkLoad`]: records are written straight to storage in large
+    /// batches, far faster than an `INSERT` per element.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use graphlite_sdk::{EdgeRecord, GraphLite, NodeRecord};
+    ///
+    /// let db = GraphLite::open("./mydb")?;
+    /// let summary = db
+    ///     .bulk_load("/etl/social")
+    ///     .nodes([NodeRecord::new("a", "Person"), NodeRecord::new("b", "Person")])
+    ///     .edges([EdgeRecord::new("a", "KNOWS", "b")])
+    ///     .run()?;
+    /// assert_eq!(summary.edges_created, 1);
+    /// # Ok::<(), graphlite_sdk::Error>(())
+    /// ```
+    pub fn bulk_load(&self, graph: impl Into<String>) -> BulkLoad<'_> {
+        BulkLoad::new(self, graph.into())
+    }
+
+    /// Call `hook` after every statement run by this database's sessions
+    ///
+    /// The hook receives a [`QueryEvent`] with the query text, its duration, the
+    /// rows it returned or changed, and its error if it failed. It applies to
+    /// sessions and transactions created before and after it is registered, and
+    /// runs on the thread that ran the statement, so it should be quick.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use graphlite_sdk::GraphLite;
+    ///
+    /// let db = GraphLite::open("./mydb")?;
+    /// db.on_query(|event| {
+    ///     if event.duration.as_millis() > 100 {
+    ///         eprintln!("slow query ({:?}): {}", event.duration, event.query);
+    ///     }
+    ///     if let Some(error) = event.error {
+    ///         eprintln!("query failed: {}", error);
+    ///     }
+    /// });
+    /// # Ok::<(), graphlite_sdk::Error>(())
+    /// ```
+    pub fn on_query<F>(&self, hook: F)
+    where
+        F: Fn(&QueryEvent<'_>) + Send + Sync + 'static,
+    {
+        self.hooks.add(hook);
     }
 
     /// Create a new session for the given user
@@ -89,11 +228,36 @@ impl GraphLite {
             .create_simple_session(username)
             .map_err(|e| Error::Session(format!("Failed to create session: {}", e)))?;
 
-        Ok(Session {
-            id: session_id,
-            coordinator: self.coordinator.clone(),
-            username: username.to_string(),
-        })
+        Ok(Session::new(
+            session_id,
+            self.coordinator.clone(),
+            username.to_string(),
+            self.retry_policy.clone(),
+            self.hooks.clone(),
+        ))
+    }
+
+    /// Start configuring a session for the given user
+    ///
+    /// See [`SessionBuilder`]: the session starts with its schema, graph and
+    /// parameters already set, instead of every caller issuing `SESSION SET`
+    /// statements.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use graphlite_sdk::GraphLite;
+    /// # let db = GraphLite::open("./mydb")?;
+    /// let session = db
+    ///     .session_builder("admin")
+    ///     .schema("/app")
+    ///     .graph("social")
+    ///     .param("tz", "UTC")
+    ///     .build()?;
+    /// # Ok::<(), graphlite_sdk::Error>(())
+    /// ```
+    pub fn session_builder(&self, username: &str) -> SessionBuilder<'_> {
+        SessionBuilder::new(self, username.to_string())
     }
 
     /// Get access to the underlying QueryCoordinator
@@ -108,6 +272,110 @@ impl GraphLite {
     pub fn coordinator(&self) -> &QueryCoordinator {
         &self.coordinator
     }
+
+    /// Take the shared coordinator, retry policy and hooks (for internal SDK use)
+    #[cfg(feature = "async")]
+    pub(crate) fn into_parts(self) -> (Arc<QueryCoordinator>, RetryPolicy, QueryHooks) {
+        (self.coordinator, self.retry_policy, self.hooks)
+    }
+}
+
+/// A session being configured with chained calls and opened with
+/// [`SessionBuilder::build`]
+///
+/// The graph may be a full path (`/schema/graph`) or, when a schema is set, a
+/// graph name within it. Parameters are session parameters, which statements of
+/// the session read as variables.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use graphlite_sdk::GraphLite;
+/// # let db = GraphLite::open("./mydb")?;
+/// let session = db
+///     .session_builder("admin")
+///     .graph("/app/social")
+///     .param("min_age", 18)
+///     .build()?;
+/// let adults = session.query("MATCH (p:Person) WHERE p.age >= min_age RETURN p.name")?;
+/// # Ok::<(), graphlite_sdk::Error>(())
+/// ```
+pub struct SessionBuilder<'a> {
+    db: &'a GraphLite,
+    username: String,
+    schema: Option<String>,
+    graph: Option<String>,
+    params: Vec<(String, Value)>,
+}
+
+impl<'a> SessionBuilder<'a> {
+    pub(crate) fn new(db: &'a GraphLite, username: String) -> Self {
+        Self {
+            db,
+            username,
+            schema: None,
+            graph: None,
+            params: Vec::new(),
+        }
+    }
+
+    /// Start the session in schema `path`
+    pub fn schema(mut self, path: impl Into<String>) -> Self {
+        self.schema = Some(path.into());
+        self
+    }
+
+    /// Start the session on graph `path`
+    pub fn graph(mut self, path: impl Into<String>) -> Self {
+        self.graph = Some(path.into());
+        self
+    }
+
+    /// Set session parameter `name`, replacing an earlier value
+    pub fn param(mut self, name: impl Into<String>, value: impl Into<Value>) -> Self {
+        let name = name.into();
+        self.params.retain(|(existing, _)| *existing != name);
+        self.params.push((name, value.into()));
+        self
+    }
+
+    /// Return per-operator timing with every query result (see [`Session::set_operator_timing`])
+    pub fn operator_timing(self, enabled: bool) -> Self {
+        self.param(OperatorTiming::SESSION_PARAMETER, enabled)
+    }
+
+    /// Open the session
+    ///
+    /// Fails, without leaving the session open, if the schema or graph does not
+    /// exist.
+    pub fn build(self) -> Result<Session> {
+        let session = self.db.session(&self.username)?;
+        if let Err(e) = self.configure(&session) {
+            let _ = session.coordinator().close_session(session.id());
+            return Err(e);
+        }
+        Ok(session)
+    }
+
+    fn configure(&self, session: &Session) -> Result<()> {
+        if let Some(schema) = &self.schema {
+            session.execute(&format!("SESSION SET SCHEMA {}", schema))?;
+        }
+        if let Some(graph) = &self.graph {
+            session.execute(&format!("SESSION SET GRAPH {}", graph))?;
+        }
+
+        let handle = session
+            .coordinator()
+            .session_handle(session.id())
+            .map_err(Error::Session)?;
+        for (name, value) in &self.params {
+            handle
+                .set_parameter(name, value.clone())
+                .map_err(Error::Session)?;
+        }
+        Ok(())
+    }
 }
 
 /// Represents an active database session
@@ -129,9 +397,28 @@ pub struct Session {
     id: String,
     coordinator: Arc<QueryCoordinator>,
     username: String,
+    retry_policy: RetryPolicy,
+    hooks: QueryHooks,
 }
 
 impl Session {
+    /// Wrap a session created on the coordinator (for internal SDK use)
+    pub(crate) fn new(
+        id: String,
+        coordinator: Arc<QueryCoordinator>,
+        username: String,
+        retry_policy: RetryPolicy,
+        hooks: QueryHooks,
+    ) -> Self {
+        Session {
+            id,
+            coordinator,
+            username,
+            retry_policy,
+            hooks,
+        }
+    }
+
     /// Get the session ID
     ///
     /// The session ID is used internally for query execution and
@@ -170,9 +457,86 @@ impl Session {
     /// # Ok::<(), graphlite_sdk::Error>(())
     /// ```
     pub fn query(&self, query: &str) -> Result<QueryResult> {
-        self.coordinator
-            .process_query(query, &self.id)
-            .map_err(|e| Error::Query(format!("Query failed: {}", e)))
+        self.retry_policy.run(|| {
+            self.observe(query, || {
+                self.coordinator
+                    .process_query(query, &self.id)
+                    .map_err(|e| Error::Query(format!("Query failed: {}", e)))
+            })
+        })
+    }
+
+    /// Execute a GQL query with `$name` parameters bound to values
+    ///
+    /// Values are bound as literals, so they are never interpreted as GQL and
+    /// need no quoting or escaping by the caller. Every parameter in the query
+    /// needs a value.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use graphlite_sdk::{params, GraphLite};
+    /// # let db = GraphLite::open("./mydb")?;
+    /// let session = db.session("admin")?;
+    /// let name = "Robert'); DROP GRAPH /app/social; --";
+    /// let result = session.query_with_params(
+    ///     "MATCH (p:Person {name: $name}) WHERE p.age > $age RETURN p",
+    ///     params! { "name" => name, "age" => 30 },
+    /// )?;
+    /// # Ok::<(), graphlite_sdk::Error>(())
+    /// ```
+    pub fn query_with_params(&self, query: &str, params: Params) -> Result<QueryResult> {
+        self.retry_policy.run(|| {
+            self.observe(query, || {
+                self.coordinator
+                    .process_query_with_params(query, params.as_map(), &self.id)
+                    .map_err(|e| Error::Query(format!("Query failed: {}", e)))
+            })
+        })
+    }
+
+    /// Execute a statement with `$name` parameters without returning results
+    ///
+    /// See [`Session::query_with_params`].
+    pub fn execute_with_params(&self, statement: &str, params: Params) -> Result<()> {
+        self.retry_policy.run(|| {
+            self.observe(statement, || {
+                self.coordinator
+                    .process_query_with_params(statement, params.as_map(), &self.id)
+                    .map_err(|e| Error::Query(format!("Execute failed: {}", e)))
+            })
+        })?;
+        Ok(())
+    }
+
+    /// Execute a GQL query and deserialize each row into `T`
+    ///
+    /// Columns map to fields by name, so alias them in the RETURN clause to
+    /// match the struct. A node or edge column deserializes into a nested struct
+    /// of its properties; see [`TypedResult`] for the full mapping.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use serde::Deserialize;
+    /// # use graphlite_sdk::GraphLite;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Person { name: String, age: u32 }
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Friendship { person: Person, since: i64 }
+    ///
+    /// # let db = GraphLite::open("./mydb")?;
+    /// let session = db.session("admin")?;
+    /// let people: Vec<Person> =
+    ///     session.query_as("MATCH (p:Person) RETURN p.name AS name, p.age AS age")?;
+    /// let friends: Vec<Friendship> = session
+    ///     .query_as("MATCH (:Person)-[f:KNOWS]->(p:Person) RETURN p AS person, f.since AS since")?;
+    /// # Ok::<(), graphlite_sdk::Error>(())
+    /// ```
+    pub fn query_as<T: DeserializeOwned>(&self, query: &str) -> Result<Vec<T>> {
+        TypedResult::from(self.query(query)?).deserialize_rows()
     }
 
     /// Execute a statement without returning results
@@ -199,12 +563,128 @@ impl Session {
     /// # Ok::<(), graphlite_sdk::Error>(())
     /// ```
     pub fn execute(&self, statement: &str) -> Result<()> {
-        self.coordinator
-            .process_query(statement, &self.id)
-            .map_err(|e| Error::Query(format!("Execute failed: {}", e)))?;
+        self.retry_policy.run(|| {
+            self.observe(statement, || {
+                self.coordinator
+                    .process_query(statement, &self.id)
+                    .map_err(|e| Error::Query(format!("Execute failed: {}", e)))
+            })
+        })?;
         Ok(())
     }
 
+    /// Execute several statements in one transaction and return the result of each
+    ///
+    /// The statements run in order and commit together; if one fails, the
+    /// whole batch is rolled back and an [`Error::Batch`] names the index of
+    /// the failing statement. This saves the round trips of one `execute` per
+    /// statement when seeding or bulk-updating a graph. Transaction control
+    /// statements are not allowed in a batch, and the session must not have an
+    /// open transaction. A batch failing with an error the session's
+    /// [`RetryPolicy`] retries is run again as a whole.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use graphlite_sdk::GraphLite;
+    /// # let db = GraphLite::open("./mydb")?;
+    /// let session = db.session("admin")?;
+    /// let results = session.execute_batch(&[
+    ///     "INSERT (:Person {name: 'Alice'})",
+    ///     "INSERT (:Person {name: 'Bob'})",
+    ///     "MATCH (p:Person) RETURN count(p) AS people",
+    /// ])?;
+    /// assert_eq!(results.len(), 3);
+    /// # Ok::<(), graphlite_sdk::Error>(())
+    /// ```
+    pub fn execute_batch<S: AsRef<str>>(&self, statements: &[S]) -> Result<Vec<QueryResult>> {
+        let statements: Vec<&str> = statements.iter().map(AsRef::as_ref).collect();
+        let text = statements.join("; ");
+        self.retry_policy.run(|| {
+            self.observe(&text, || {
+                self.coordinator
+                    .process_batch(&statements, &self.id)
+                    .map_err(Error::Batch)
+            })
+        })
+    }
+
+    /// Run several independent queries and return the result or error of each
+    ///
+    /// Unlike [`Session::execute_batch`], every query runs on its own: one
+    /// failing does not stop or undo the others. The queries run in order with
+    /// the session looked up once, which suits dashboards that issue many small
+    /// reads per page. Each query is retried and reported to query hooks as if
+    /// run with [`Session::query`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use graphlite_sdk::GraphLite;
+    /// # let db = GraphLite::open("./mydb")?;
+    /// let session = db.session("admin")?;
+    /// let results = session.query_many(&[
+    ///     "MATCH (p:Person) RETURN count(p) AS people",
+    ///     "MATCH (c:Company) RETURN count(c) AS companies",
+    /// ]);
+    /// for result in results {
+    ///     match result {
+    ///         Ok(result) => println!("{} rows", result.rows.len()),
+    ///         Err(e) => eprintln!("{}", e),
+    ///     }
+    /// }
+    /// # Ok::<(), graphlite_sdk::Error>(())
+    /// ```
+    pub fn query_many<S: AsRef<str>>(&self, queries: &[S]) -> Vec<Result<QueryResult>> {
+        let handle = match self.coordinator.session_handle(&self.id) {
+            Ok(handle) => handle,
+            Err(e) => {
+                return queries
+                    .iter()
+                    .map(|_| Err(Error::Session(e.clone())))
+                    .collect()
+            }
+        };
+        queries
+            .iter()
+            .map(|query| {
+                let query = query.as_ref();
+                self.retry_policy.run(|| {
+                    self.observe(query, || {
+                        self.coordinator
+                            .process_query_with_handle(query, &handle)
+                            .map_err(|e| Error::Query(format!("Query failed: {}", e)))
+                    })
+                })
+            })
+            .collect()
+    }
+
+    /// Turn per-operator timing of this session's queries on or off
+    ///
+    /// While on, every result carries an `operator_timings` tree with the wall
+    /// time and row count of each executed operator.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use graphlite_sdk::GraphLite;
+    /// # let db = GraphLite::open("./mydb")?;
+    /// let session = db.session("admin")?;
+    /// session.set_operator_timing(true)?;
+    /// let result = session.query("MATCH (p:Person) RETURN p.name")?;
+    /// if let Some(timing) = &result.operator_timings {
+    ///     println!("{}: {} rows in {} µs", timing.operator, timing.rows, timing.elapsed_us);
+    /// }
+    /// # Ok::<(), graphlite_sdk::Error>(())
+    /// ```
+    pub fn set_operator_timing(&self, enabled: bool) -> Result<()> {
+        self.coordinator
+            .session_handle(&self.id)
+            .and_then(|handle| handle.set_operator_timing(enabled))
+            .map_err(Error::Session)
+    }
+
     /// Begin a new transaction
     ///
     /// Transactions provide ACID guarantees and can be committed or rolled back.
@@ -233,20 +713,505 @@ impl Session {
     /// # Ok::<(), graphlite_sdk::Error>(())
     /// ```
     pub fn transaction(&self) -> Result<Transaction<'_>> {
-        Transaction::begin(self)
+        Transaction::begin(self, &TxOptions::default())
+    }
+
+    /// Begin a new transaction with the given isolation level, access mode or timeout
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use graphlite_sdk::{GraphLite, TxOptions};
+    /// # let db = GraphLite::open("./mydb")?;
+    /// let session = db.session("admin")?;
+    ///
+    /// // Writes in a read-only transaction fail
+    /// let mut tx = session.transaction_with(TxOptions::read_only())?;
+    /// assert!(tx.execute("INSERT (:Person {name: 'Alice'})").is_err());
+    /// # Ok::<(), graphlite_sdk::Error>(())
+    /// ```
+    pub fn transaction_with(&self, options: TxOptions) -> Result<Transaction<'_>> {
+        Transaction::begin(self, &options)
+    }
+
+    /// Run `f` in a transaction, retrying the whole transaction on transient failures
+    ///
+    /// The transaction commits if `f` returns `Ok` and rolls back if it returns
+    /// `Err`. When `f` or the commit fails with an error the session's
+    /// [`RetryPolicy`] retries, the transaction is rolled back and `f` runs again
+    /// in a new one, so `f` must be safe to run more than once.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use graphlite_sdk::GraphLite;
+    /// # let db = GraphLite::open("./mydb")?;
+    /// let session = db.session("admin")?;
+    /// session.transaction_with_retry(|tx| {
+    ///     tx.execute("MATCH (a:Account {id: 1}) SET a.balance = a.balance - 10")?;
+    ///     tx.execute("MATCH (a:Account {id: 2}) SET a.balance = a.balance + 10")
+    /// })?;
+    /// # Ok::<(), graphlite_sdk::Error>(())
+    /// ```
+    pub fn transaction_with_retry<T, F>(&self, mut f: F) -> Result<T>
+    where
+        F: FnMut(&mut Transaction<'_>) -> Result<T>,
+    {
+        self.retry_policy.run(|| {
+            let mut tx = self.transaction()?;
+            let value = f(&mut tx)?;
+            tx.commit()?;
+            Ok(value)
+        })
+    }
+
+    /// Get the retry policy of this session
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.retry_policy
     }
 
     /// Get the internal coordinator (for internal SDK use)
     pub(crate) fn coordinator(&self) -> &QueryCoordinator {
         &self.coordinator
     }
+
+    /// Run a statement of this session, reporting it to the query hooks (for internal SDK use)
+    pub(crate) fn observe<T: Counted>(
+        &self,
+        query: &str,
+        run: impl FnOnce() -> Result<T>,
+    ) -> Result<T> {
+        self.hooks.observe(query, &self.id, &self.username, run)
+    }
+
+    /// Open another session for the same user (for internal SDK use)
+    pub(crate) fn new_session(&self) -> Result<Session> {
+        let session_id = self
+            .coordinator
+            .create_simple_session(&self.username)
+            .map_err(|e| Error::Session(format!("Failed to create session: {}", e)))?;
+
+        Ok(Session::new(
+            session_id,
+            self.coordinator.clone(),
+            self.username.clone(),
+            self.retry_policy.clone(),
+            self.hooks.clone(),
+        ))
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use serde::Deserialize;
 
     #[test]
     fn test_connection_types_compile() {
         // Compilation test - ensures types are properly defined
     }
+
+    #[test]
+    fn test_query_as_maps_columns_and_nodes() {
+        #[derive(Deserialize)]
+        struct Person {
+            name: String,
+            age: u32,
+            #[serde(rename = "_labels")]
+            labels: Vec<String>,
+        }
+
+        #[derive(Deserialize)]
+        struct Friendship {
+            person: Person,
+            since: i64,
+            note: Option<String>,
+        }
+
+        let dir = std::env::temp_dir().join(format!("sdk_query_as_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let db = GraphLite::open(&dir).unwrap();
+        let session = db.session("admin").unwrap();
+        for statement in [
+            "CREATE SCHEMA /query_as",
+            "CREATE GRAPH /query_as/g",
+            "SESSION SET GRAPH /query_as/g",
+            "INSERT (:Person {name: 'Alice', age: 30}), (:Person {name: 'Bob', age: 25})",
+            "MATCH (a:Person {name: 'Alice'}), (b:Person {name: 'Bob'}) INSERT (a)-[:KNOWS {since: 2020}]->(b)",
+        ] {
+            session.execute(statement).unwrap();
+        }
+
+        #[derive(Deserialize)]
+        struct NameAge {
+            name: String,
+            age: u32,
+        }
+        let people: Vec<NameAge> = session
+            .query_as("MATCH (p:Person) RETURN p.name AS name, p.age AS age ORDER BY age")
+            .unwrap();
+        assert_eq!(people.len(), 2);
+        assert_eq!((people[0].name.as_str(), people[0].age), ("Bob", 25));
+
+        let friends: Vec<Friendship> = session
+            .query_as(
+                "MATCH (:Person)-[f:KNOWS]->(p:Person) RETURN p AS person, f.since AS since, f.note AS note",
+            )
+            .unwrap();
+        assert_eq!(friends.len(), 1);
+        assert_eq!(friends[0].person.name, "Bob");
+        assert_eq!(friends[0].person.age, 25);
+        assert_eq!(friends[0].person.labels, ["Person"]);
+        assert_eq!(friends[0].since, 2020);
+        assert!(friends[0].note.is_none());
+
+        // A column that does not fit the struct is a serialization error
+        let mismatch = session.query_as::<NameAge>("MATCH (p:Person) RETURN p.name AS name");
+        assert!(matches!(mismatch, Err(Error::Serialization(_))));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_query_with_params_binds_values() {
+        let dir = std::env::temp_dir().join(format!("sdk_params_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let db = GraphLite::open(&dir).unwrap();
+        let session = db.session("admin").unwrap();
+        for statement in [
+            "CREATE SCHEMA /params",
+            "CREATE GRAPH /params/g",
+            "SESSION SET GRAPH /params/g",
+        ] {
+            session.execute(statement).unwrap();
+        }
+
+        let hostile = "x'}) DETACH DELETE (p) RETURN ('";
+        for (id, name) in [(1, "Alice"), (2, "O'Brien"), (3, hostile)] {
+            session
+                .execute_with_params(
+                    "INSERT (:Person {id: $id, name: $name})",
+                    crate::params! { "id" => id, "name" => name },
+                )
+                .unwrap();
+        }
+
+        let result = session
+            .query_with_params(
+                "MATCH (p:Person) WHERE p.id >= $min RETURN p.name AS name ORDER BY name",
+                crate::params! { "min" => 2 },
+            )
+            .unwrap();
+        let names: Vec<_> = result
+            .rows
+            .iter()
+            .map(|row| row.get_value("name").cloned())
+            .collect();
+        assert_eq!(
+            names,
+            [
+                Some(graphlite::Value::String("O'Brien".to_string())),
+                Some(graphlite::Value::String(hostile.to_string())),
+            ]
+        );
+
+        let mut tx = session.transaction().unwrap();
+        let result = tx
+            .query_with_params(
+                "MATCH (p:Person {name: $name}) RETURN p.id AS id",
+                crate::params! { "name" => hostile },
+            )
+            .unwrap();
+        assert_eq!(result.rows.len(), 1);
+        drop(tx);
+
+        let missing =
+            session.query_with_params("MATCH (p:Person {id: $id}) RETURN p", Params::new());
+        assert!(matches!(missing, Err(Error::Query(message)) if message.contains("$id")));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_transaction_with_retry_reruns_failed_transaction() {
+        let dir = std::env::temp_dir().join(format!("sdk_retry_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let db = GraphLite::open(&dir)
+            .unwrap()
+            .with_retry_policy(RetryPolicy::new().initial_backoff(std::time::Duration::ZERO));
+        let session = db.session("admin").unwrap();
+        for statement in [
+            "CREATE SCHEMA /retry",
+            "CREATE GRAPH /retry/g",
+            "SESSION SET GRAPH /retry/g",
+        ] {
+            session.execute(statement).unwrap();
+        }
+
+        // The first attempt fails after writing; its write must be rolled back
+        let mut attempts = 0;
+        let result = session.transaction_with_retry(|tx| {
+            attempts += 1;
+            tx.execute(&format!("INSERT (:Attempt {{n: {}}})", attempts))?;
+            if attempts == 1 {
+                return Err(Error::Transaction("lock conflict".into()));
+            }
+            Ok(attempts)
+        });
+        assert_eq!(result.unwrap(), 2);
+
+        let rows = session
+            .query("MATCH (a:Attempt) RETURN a.n AS n")
+            .unwrap()
+            .rows;
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].get_value("n"), Some(&graphlite::Value::Number(2.0)));
+
+        // Errors that are not transient are returned without retrying
+        let mut attempts = 0;
+        let result: Result<()> = session.transaction_with_retry(|_| {
+            attempts += 1;
+            Err(Error::Query("Query failed: unknown label".into()))
+        });
+        assert!(result.is_err());
+        assert_eq!(attempts, 1);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_execute_batch_commits_or_rolls_back_together() {
+        let dir = std::env::temp_dir().join(format!("sdk_batch_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let db = GraphLite::open(&dir).unwrap();
+        let session = db.session("admin").unwrap();
+        session
+            .execute_batch(&["CREATE SCHEMA /batch", "CREATE GRAPH /batch/g"])
+            .unwrap();
+        session.execute("SESSION SET GRAPH /batch/g").unwrap();
+
+        let statements = vec![
+            "INSERT (:Person {name: 'Alice'})".to_string(),
+            "INSERT (:Person {name: 'Bob'})".to_string(),
+            "MATCH (p:Person) RETURN count(p) AS people".to_string(),
+        ];
+        let results = session.execute_batch(&statements).unwrap();
+        assert_eq!(results.len(), 3);
+        assert_eq!(
+            results[2].rows[0].get_value("people"),
+            Some(&graphlite::Value::Number(2.0))
+        );
+
+        // A failing statement rolls back the whole batch
+        let error = session
+            .execute_batch(&[
+                "INSERT (:Person {name: 'Carol'})",
+                "INSERT (broken",
+                "INSERT (:Person {name: 'Dave'})",
+            ])
+            .unwrap_err();
+        assert!(matches!(&error, Error::Batch(batch) if batch.index == 1));
+
+        let count = session
+            .query("MATCH (p:Person) RETURN count(p) AS people")
+            .unwrap();
+        assert_eq!(
+            count.rows[0].get_value("people"),
+            Some(&graphlite::Value::Number(2.0))
+        );
+        assert!(session.execute_batch::<&str>(&[]).unwrap().is_empty());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_backup_to_and_restore_from() {
+        let base = std::env::temp_dir().join(format!("sdk_backup_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&base);
+        let (dir, backup, restored) = (base.join("db"), base.join("backup"), base.join("restored"));
+
+        let db = GraphLite::open(&dir).unwrap();
+        let session = db.session("admin").unwrap();
+        for statement in [
+            "CREATE SCHEMA /backup",
+            "CREATE GRAPH /backup/g",
+            "SESSION SET GRAPH /backup/g",
+            "INSERT (:Person {name: 'Alice'}), (:Person {name: 'Bob'})",
+        ] {
+            session.execute(statement).unwrap();
+        }
+
+        // Not while a transaction is open
+        let tx = session.transaction().unwrap();
+        assert!(matches!(db.backup_to(&backup), Err(Error::Backup(_))));
+        drop(tx);
+
+        assert!(db.backup_to(&backup).unwrap() > 0);
+        // The database stays usable; later writes are not in the backup
+        session.execute("INSERT (:Person {name: 'Carol'})").unwrap();
+        assert!(matches!(db.backup_to(&backup), Err(Error::Backup(_))));
+
+        let restored_db = GraphLite::restore_from(&backup, &restored).unwrap();
+        let restored_session = restored_db.session("admin").unwrap();
+        restored_session
+            .execute("SESSION SET GRAPH /backup/g")
+            .unwrap();
+        let people = restored_session
+            .query("MATCH (p:Person) RETURN p.name")
+            .unwrap();
+        assert_eq!(people.rows.len(), 2);
+        assert!(matches!(
+            GraphLite::restore_from(&backup, &restored),
+            Err(Error::Backup(_))
+        ));
+
+        let _ = std::fs::remove_dir_all(&base);
+    }
+
+    #[test]
+    fn test_on_query_reports_statements() {
+        let dir = std::env::temp_dir().join(format!("sdk_hooks_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let db = GraphLite::open(&dir).unwrap();
+        // Sessions opened before the hook is registered report to it too
+        let session = db.session("admin").unwrap();
+        let events = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded = events.clone();
+        db.on_query(move |event| {
+            assert_eq!(event.username, "admin");
+            recorded.lock().unwrap().push((
+                event.query.to_string(),
+                event.rows,
+                event.rows_affected,
+                event.error.map(|error| error.to_string()),
+            ));
+        });
+
+        for statement in [
+            "CREATE SCHEMA /hooks",
+            "CREATE GRAPH /hooks/g",
+            "SESSION SET GRAPH /hooks/g",
+        ] {
+            session.execute(statement).unwrap();
+        }
+        let mut tx = session.transaction().unwrap();
+        tx.execute("INSERT (:Person {name: 'Alice'}), (:Person {name: 'Bob'})")
+            .unwrap();
+        tx.commit().unwrap();
+        session.query("MATCH (p:Person) RETURN p.name").unwrap();
+        assert!(session.query("MATCH (p RETURN p").is_err());
+
+        let events = events.lock().unwrap();
+        let queries: Vec<_> = events.iter().map(|event| event.0.as_str()).collect();
+        assert_eq!(
+            queries,
+            [
+                "CREATE SCHEMA /hooks",
+                "CREATE GRAPH /hooks/g",
+                "SESSION SET GRAPH /hooks/g",
+                "START TRANSACTION",
+                "INSERT (:Person {name: 'Alice'}), (:Person {name: 'Bob'})",
+                "COMMIT",
+                "MATCH (p:Person) RETURN p.name",
+                "MATCH (p RETURN p",
+            ]
+        );
+        assert_eq!(events[4].2, 2);
+        assert_eq!(events[6].1, 2);
+        assert!(events[..7].iter().all(|event| event.3.is_none()));
+        assert!(events[7].3.as_deref().unwrap().contains("Query failed"));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_session_builder_sets_context_and_params() {
+        let db = crate::testing::TempDb::builder()
+            .graph("/app/social")
+            .build()
+            .unwrap();
+        db.session("admin")
+            .unwrap()
+            .execute("INSERT (:Person {name: 'Ann', age: 17}), (:Person {name: 'Bo', age: 30})")
+            .unwrap();
+
+        let session = db
+            .session_builder("admin")
+            .schema("/app")
+            .graph("social")
+            .param("min_age", 20)
+            .param("min_age", 18)
+            .param("tz", "UTC")
+            .build()
+            .unwrap();
+        let result = session
+            .query("MATCH (p:Person) WHERE p.age >= min_age RETURN p.name")
+            .unwrap();
+        assert_eq!(result.rows.len(), 1);
+        assert_eq!(
+            result.rows[0].values.get("p.name"),
+            Some(&Value::String("Bo".to_string()))
+        );
+        let handle = session.coordinator().session_handle(session.id()).unwrap();
+        assert_eq!(
+            handle.parameter("tz"),
+            Some(Value::String("UTC".to_string()))
+        );
+
+        assert!(db
+            .session_builder("admin")
+            .graph("/app/missing")
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn test_operator_timing() {
+        let db = crate::testing::TempDb::builder()
+            .graph("/app/social")
+            .build()
+            .unwrap();
+        let session = db.session("admin").unwrap();
+        session
+            .execute("INSERT (:Person {name: 'Ann'}), (:Person {name: 'Bo'})")
+            .unwrap();
+        let query = "MATCH (p:Person) RETURN p.name";
+        assert!(session.query(query).unwrap().operator_timings.is_none());
+
+        session.set_operator_timing(true).unwrap();
+        let timing = session.query(query).unwrap().operator_timings.unwrap();
+        assert_eq!(timing.rows, 2);
+
+        let session = db
+            .session_builder("admin")
+            .graph("/app/social")
+            .operator_timing(true)
+            .build()
+            .unwrap();
+        assert!(session.query(query).unwrap().operator_timings.is_some());
+    }
+
+    #[test]
+    fn test_query_many_runs_queries_independently() {
+        let db = crate::testing::TempDb::builder()
+            .graph("/many/g")
+            .build()
+            .unwrap();
+        let session = db.session("admin").unwrap();
+        session.execute("SESSION SET GRAPH /many/g").unwrap();
+        let results = session.query_many(&[
+            "INSERT (:Person {name: 'Ann'})",
+            "MATCH (p RETURN p",
+            "MATCH (p:Person) RETURN p.name",
+        ]);
+        assert_eq!(results.len(), 3);
+        assert!(results[0].is_ok());
+        assert!(matches!(results[1], Err(Error::Query(_))));
+        assert_eq!(results[2].as_ref().unwrap().rows.len(), 1);
+    }
 }
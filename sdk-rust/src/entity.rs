@@ -0,0 +1,467 @@
+//! Mapping structs to nodes and edges
+//!
+//! [`GraphNode`] and [`GraphEdge`] map a serde struct to a node label or edge
+//! label and its fields to properties, and provide insert, find, update and
+//! delete helpers built on parameterized queries. Implement them with
+//! `#[derive(GraphNode)]` and `#[derive(GraphEdge)]`:
+//!
+//! ```no_run
+//! use graphlite_sdk::{GraphEdge, GraphLite, GraphNode};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Debug, Serialize, Deserialize, GraphNode)]
+//! struct Person {
+//!     id: String,
+//!     name: String,
+//!     age: Option<u32>,
+//! }
+//!
+//! #[derive(Debug, Serialize, Deserialize, GraphEdge)]
+//! struct Knows {
+//!     since: i64,
+//! }
+//!
+//! # let db = GraphLite::open("./mydb")?;
+//! let session = db.session("admin")?;
+//! session.execute("SESSION SET GRAPH /app/social")?;
+//!
+//! let alice = Person { id: "p1".into(), name: "Alice".into(), age: Some(30) };
+//! let bob = Person { id: "p2".into(), name: "Bob".into(), age: None };
+//! alice.insert(&session)?;
+//! bob.insert(&session)?;
+//! Knows { since: 2020 }.create(&session, &alice, &bob)?;
+//!
+//! if let Some(mut person) = Person::find_by_id(&session, "p1")? {
+//!     person.age = Some(31);
+//!     person.update(&session)?;
+//! }
+//! # Ok::<(), graphlite_sdk::Error>(())
+//! ```
+//!
+//! Properties are the fields as serde serializes them, so `#[serde(rename)]`
+//! and `#[serde(skip)]` apply. Fields must serialize to strings, numbers,
+//! booleans, lists of those, or `None`; a `None` field is left out on insert
+//! and set to NULL on update.
+
+use crate::connection::Session;
+use crate::error::{Error, Result};
+use crate::params::Params;
+use crate::result::value_to_json;
+use graphlite::Value;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// A struct stored as a node
+///
+/// `LABEL` is the node label and `ID_PROPERTY` the property that identifies
+/// a node among the others with the label; the helpers find nodes by it.
+pub trait GraphNode: Serialize + DeserializeOwned {
+    /// Label of the node
+    const LABEL: &'static str;
+
+    /// Property that identifies the node
+    const ID_PROPERTY: &'static str;
+
+    /// The properties of this node, ordered by name
+    fn properties(&self) -> Result<Vec<(String, Value)>> {
+        properties_of(self, Self::LABEL)
+    }
+
+    /// The value of the id property
+    fn id_value(&self) -> Result<Value> {
+        self.properties()?
+            .into_iter()
+            .find(|(name, _)| name == Self::ID_PROPERTY)
+            .map(|(_, value)| value)
+            .ok_or_else(|| {
+                Error::TypeConversion(format!(
+                    "{} has no id property '{}'",
+                    Self::LABEL,
+                    Self::ID_PROPERTY
+                ))
+            })
+    }
+
+    /// Insert this struct as a new node in the session's current graph
+    fn insert(&self, session: &Session) -> Result<()> {
+        let properties: Vec<_> = self
+            .properties()?
+            .into_iter()
+            .filter(|(_, value)| !matches!(value, Value::Null))
+            .collect();
+        let (map, params) = property_map(&properties);
+        session.execute_with_params(&format!("INSERT (:{} {})", Self::LABEL, map), params)
+    }
+
+    /// Find the node whose id property is `id`
+    ///
+    /// Returns the first match if several nodes share the id.
+    fn find_by_id(session: &Session, id: impl Into<Value>) -> Result<Option<Self>> {
+        let result = session.query_with_params(
+            &format!(
+                "MATCH (n:{} {{{}: $id}}) RETURN n",
+                Self::LABEL,
+                Self::ID_PROPERTY
+            ),
+            Params::new().add("id", id),
+        )?;
+        result
+            .rows
+            .first()
+            .and_then(|row| row.get_value("n"))
+            .map(from_element)
+            .transpose()
+    }
+
+    /// All nodes with the label
+    fn find_all(session: &Session) -> Result<Vec<Self>> {
+        let result = session.query(&format!("MATCH (n:{}) RETURN n", Self::LABEL))?;
+        result
+            .rows
+            .iter()
+            .filter_map(|row| row.get_value("n"))
+            .map(from_element)
+            .collect()
+    }
+
+    /// Write every property of this struct to the node with the same id
+    ///
+    /// Does nothing if no node has the id.
+    fn update(&self, session: &Session) -> Result<()> {
+        let id = self.id_value()?;
+        let properties: Vec<_> = self
+            .properties()?
+            .into_iter()
+            .filter(|(name, _)| name != Self::ID_PROPERTY)
+            .collect();
+        if properties.is_empty() {
+            return Ok(());
+        }
+
+        let assignments: Vec<String> = properties
+            .iter()
+            .map(|(name, _)| format!("n.{} = $p_{}", name, name))
+            .collect();
+        let params = properties
+            .into_iter()
+            .fold(Params::new().add("id", id), |params, (name, value)| {
+                params.add(format!("p_{}", name), value)
+            });
+        session.execute_with_params(
+            &format!(
+                "MATCH (n:{} {{{}: $id}}) SET {}",
+                Self::LABEL,
+                Self::ID_PROPERTY,
+                assignments.join(", ")
+            ),
+            params,
+        )
+    }
+
+    /// Delete the node with the same id as this struct, with its edges
+    fn delete(&self, session: &Session) -> Result<()> {
+        session.execute_with_params(
+            &format!(
+                "MATCH (n:{} {{{}: $id}}) DETACH DELETE n",
+                Self::LABEL,
+                Self::ID_PROPERTY
+            ),
+            Params::new().add("id", self.id_value()?),
+        )
+    }
+}
+
+/// A struct stored as an edge between two [`GraphNode`]s
+pub trait GraphEdge: Serialize + DeserializeOwned {
+    /// Label of the edge
+    const LABEL: &'static str;
+
+    /// The properties of this edge, ordered by name
+    fn properties(&self) -> Result<Vec<(String, Value)>> {
+        properties_of(self, Self::LABEL)
+    }
+
+    /// Insert this struct as a new edge from `from` to `to`
+    ///
+    /// Both nodes are found by their id; nothing is inserted if either is missing.
+    fn create<F: GraphNode, T: GraphNode>(
+        &self,
+        session: &Session,
+        from: &F,
+        to: &T,
+    ) -> Result<()> {
+        let properties: Vec<_> = self
+            .properties()?
+            .into_iter()
+            .filter(|(_, value)| !matches!(value, Value::Null))
+            .collect();
+        let (map, params) = property_map(&properties);
+        let params = params
+            .add("from_id", from.id_value()?)
+            .add("to_id", to.id_value()?);
+        session.execute_with_params(
+            &format!(
+                "MATCH (a:{} {{{}: $from_id}}), (b:{} {{{}: $to_id}}) INSERT (a)-[:{} {}]->(b)",
+                F::LABEL,
+                F::ID_PROPERTY,
+                T::LABEL,
+                T::ID_PROPERTY,
+                Self::LABEL,
+                map
+            ),
+            params,
+        )
+    }
+
+    /// The edges with this label from `from` to `to`
+    fn find_between<F: GraphNode, T: GraphNode>(
+        session: &Session,
+        from: &F,
+        to: &T,
+    ) -> Result<Vec<Self>> {
+        let (pattern, params) = edge_pattern::<Self, F, T>(from, to)?;
+        let result = session.query_with_params(&format!("MATCH {} RETURN e", pattern), params)?;
+        result
+            .rows
+            .iter()
+            .filter_map(|row| row.get_value("e"))
+            .map(from_element)
+            .collect()
+    }
+
+    /// Delete the edges with this label from `from` to `to`
+    fn delete_between<F: GraphNode, T: GraphNode>(
+        session: &Session,
+        from: &F,
+        to: &T,
+    ) -> Result<()> {
+        let (pattern, params) = edge_pattern::<Self, F, T>(from, to)?;
+        session.execute_with_params(&format!("MATCH {} DELETE e", pattern), params)
+    }
+}
+
+/// `(a:From {id: $from_id})-[e:LABEL]->(b:To {id: $to_id})` and its parameters
+fn edge_pattern<E: GraphEdge, F: GraphNode, T: GraphNode>(
+    from: &F,
+    to: &T,
+) -> Result<(String, Params)> {
+    let pattern = format!(
+        "(a:{} {{{}: $from_id}})-[e:{}]->(b:{} {{{}: $to_id}})",
+        F::LABEL,
+        F::ID_PROPERTY,
+        E::LABEL,
+        T::LABEL,
+        T::ID_PROPERTY
+    );
+    let params = Params::new()
+        .add("from_id", from.id_value()?)
+        .add("to_id", to.id_value()?);
+    Ok((pattern, params))
+}
+
+/// `{name: $p_name, ...}` for `properties`, with the parameters it refers to
+fn property_map(properties: &[(String, Value)]) -> (String, Params) {
+    let entries: Vec<String> = properties
+        .iter()
+        .map(|(name, _)| format!("{}: $p_{}", name, name))
+        .collect();
+    let params = properties
+        .iter()
+        .fold(Params::new(), |params, (name, value)| {
+            params.add(format!("p_{}", name), value.clone())
+        });
+    (format!("{{{}}}", entries.join(", ")), params)
+}
+
+/// Serialize `entity` and convert its fields to property values
+fn properties_of<E: Serialize + ?Sized>(entity: &E, label: &str) -> Result<Vec<(String, Value)>> {
+    let fields = match serde_json::to_value(entity)? {
+        serde_json::Value::Object(fields) => fields,
+        other => {
+            return Err(Error::TypeConversion(format!(
+                "{} must serialize to a map of properties, got {}",
+                label, other
+            )))
+        }
+    };
+
+    fields
+        .into_iter()
+        .map(|(name, value)| {
+            // Property names are spliced into queries, so they must be plain identifiers
+            let mut chars = name.chars();
+            let valid = chars
+                .next()
+                .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+                && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+            if !valid {
+                return Err(Error::TypeConversion(format!(
+                    "{} has property '{}', which is not a valid property name",
+                    label, name
+                )));
+            }
+            let value = json_to_value(&value).ok_or_else(|| {
+                Error::TypeConversion(format!(
+                    "Property '{}' of {} must be a string, number, boolean, list, map or null, got {}",
+                    name, label, value
+                ))
+            })?;
+            Ok((name, value))
+        })
+        .collect()
+}
+
+/// Convert a serialized field to a property value; maps are stored as JSON documents
+fn json_to_value(value: &serde_json::Value) -> Option<Value> {
+    match value {
+        serde_json::Value::Null => Some(Value::Null),
+        serde_json::Value::Bool(b) => Some(Value::Boolean(*b)),
+        serde_json::Value::Number(n) => n.as_f64().map(Value::Number),
+        serde_json::Value::String(s) => Some(Value::String(s.clone())),
+        serde_json::Value::Array(items) => items
+            .iter()
+            .map(json_to_value)
+            .collect::<Option<Vec<_>>>()
+            .map(Value::List),
+        serde_json::Value::Object(_) => Some(Value::Json(value.clone())),
+    }
+}
+
+/// Deserialize a node or edge returned by a query
+fn from_element<E: DeserializeOwned>(value: &Value) -> Result<E> {
+    Ok(serde_json::from_value(value_to_json(value))?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GraphEdge, GraphLite, GraphNode};
+    use serde::Deserialize;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize, GraphNode)]
+    struct Person {
+        id: String,
+        name: String,
+        age: Option<u32>,
+        tags: Vec<String>,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize, GraphNode)]
+    #[graph(label = "Company")]
+    struct Employer {
+        #[graph(id)]
+        #[serde(rename = "code")]
+        key: i64,
+        name: String,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize, GraphEdge)]
+    struct WorksAt {
+        since: i64,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize, GraphNode)]
+    struct Nested {
+        id: String,
+        address: Address,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Address {
+        city: String,
+    }
+
+    #[test]
+    fn test_derive_sets_labels_and_id_property() {
+        assert_eq!(Person::LABEL, "Person");
+        assert_eq!(Person::ID_PROPERTY, "id");
+        assert_eq!(Employer::LABEL, "Company");
+        assert_eq!(Employer::ID_PROPERTY, "code");
+        assert_eq!(WorksAt::LABEL, "WORKS_AT");
+
+        let employer = Employer {
+            key: 7,
+            name: "Acme".into(),
+        };
+        assert_eq!(employer.id_value().unwrap(), Value::Number(7.0));
+
+        let nested = Nested {
+            id: "n1".into(),
+            address: Address {
+                city: "Oslo".into(),
+            },
+        };
+        let properties = nested.properties().unwrap();
+        assert!(properties.contains(&(
+            "address".to_string(),
+            Value::Json(serde_json::json!({"city": "Oslo"}))
+        )));
+    }
+
+    #[test]
+    fn test_node_and_edge_round_trip() {
+        let dir = std::env::temp_dir().join(format!("sdk_entity_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let db = GraphLite::open(&dir).unwrap();
+        let session = db.session("admin").unwrap();
+        session.execute("CREATE SCHEMA /app").unwrap();
+        session.execute("CREATE GRAPH /app/g").unwrap();
+        session.execute("SESSION SET GRAPH /app/g").unwrap();
+
+        let mut alice = Person {
+            id: "p1".into(),
+            name: "Alice".into(),
+            age: Some(30),
+            tags: vec!["admin".into()],
+        };
+        let bob = Person {
+            id: "p2".into(),
+            name: "Bob".into(),
+            age: None,
+            tags: Vec::new(),
+        };
+        alice.insert(&session).unwrap();
+        bob.insert(&session).unwrap();
+
+        assert_eq!(Person::find_by_id(&session, "p1").unwrap(), Some(alice));
+        assert_eq!(Person::find_by_id(&session, "p3").unwrap(), None);
+        assert_eq!(Person::find_all(&session).unwrap().len(), 2);
+
+        alice = Person::find_by_id(&session, "p1").unwrap().unwrap();
+        alice.name = "Alice Smith".into();
+        alice.age = None;
+        alice.update(&session).unwrap();
+        assert_eq!(Person::find_by_id(&session, "p1").unwrap(), Some(alice));
+
+        let acme = Employer {
+            key: 7,
+            name: "Acme".into(),
+        };
+        acme.insert(&session).unwrap();
+        assert_eq!(Employer::find_by_id(&session, 7).unwrap(), Some(acme));
+
+        let alice = Person::find_by_id(&session, "p1").unwrap().unwrap();
+        let acme = Employer::find_by_id(&session, 7).unwrap().unwrap();
+        WorksAt { since: 2020 }
+            .create(&session, &alice, &acme)
+            .unwrap();
+        assert_eq!(
+            WorksAt::find_between(&session, &alice, &acme).unwrap(),
+            [WorksAt { since: 2020 }]
+        );
+        assert!(WorksAt::find_between(&session, &bob, &acme)
+            .unwrap()
+            .is_empty());
+
+        WorksAt::delete_between(&session, &alice, &acme).unwrap();
+        assert!(WorksAt::find_between(&session, &alice, &acme)
+            .unwrap()
+            .is_empty());
+
+        alice.delete(&session).unwrap();
+        assert_eq!(Person::find_all(&session).unwrap(), [bob]);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
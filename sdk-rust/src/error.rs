@@ -1,5 +1,6 @@
 //! Error types for the GraphLite SDK
 
+use graphlite::BatchError;
 use thiserror::Error;
 
 /// Result type alias for SDK operations
@@ -32,6 +33,14 @@ pub enum Error {
     #[error("Type conversion error: {0}")]
     TypeConversion(String),
 
+    /// A statement of a batch failed and the batch was rolled back
+    #[error("Batch error: {0}")]
+    Batch(BatchError),
+
+    /// Migration errors
+    #[error("Migration error: {0}")]
+    Migration(String),
+
     /// Invalid operation errors
     #[error("Invalid operation: {0}")]
     InvalidOperation(String),
@@ -44,6 +53,18 @@ pub enum Error {
     #[error("Connection error: {0}")]
     Connection(String),
 
+    /// Backup or restore errors
+    #[error("Backup error: {0}")]
+    Backup(String),
+
+    /// Bulk load errors
+    #[error("Bulk load error: {0}")]
+    BulkLoad(String),
+
+    /// Health check failures
+    #[error("Unhealthy: {0}")]
+    Unhealthy(String),
+
     /// I/O errors
     #[error("I/O error: {0}")]
     Io(#[from] std::io::Error),
@@ -0,0 +1,189 @@
+//! Health checks for services embedding a database
+//!
+//! [`GraphLite::ping`] answers whether the database can serve queries and is
+//! meant for readiness probes, such as a Kubernetes `readinessProbe` handler.
+//! [`GraphLite::health`] reports the numbers behind that answer for dashboards
+//! and liveness endpoints.
+
+use crate::connection::GraphLite;
+use crate::error::{Error, Result};
+use graphlite::{DiskSpaceStatus, StorageCheckReport, Value};
+use std::time::Duration;
+
+/// WAL flushes slower than this make [`Health::is_ready`] false
+///
+/// Matches the lag at which the engine starts warning writers of backpressure.
+pub const WAL_FLUSH_LAG_LIMIT: Duration = Duration::from_millis(100);
+
+/// Usage of one engine cache, as reported by `gql.cache_stats`
+#[derive(Debug, Clone, PartialEq)]
+pub struct CacheHealth {
+    /// Cache name, e.g. `storage_cache` or `plan_cache`
+    pub name: String,
+    /// Entries held
+    pub entries: usize,
+    /// Share of lookups served from the cache, if the cache tracks it
+    pub hit_rate: Option<f64>,
+    /// Memory attributed to the cache in bytes
+    pub memory_bytes: usize,
+}
+
+/// Snapshot of a database's health, returned by [`GraphLite::health`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Health {
+    /// Result of the quick storage check, which opens every storage tree
+    pub storage: StorageCheckReport,
+    /// Corrupt records detected since the process started
+    pub corrupt_records: u64,
+    /// Whether the database rejects writes
+    pub read_only: bool,
+    /// Free space of the database volume; writes fail while it is below the minimum
+    pub disk_space: DiskSpaceStatus,
+    /// Time the most recent write-ahead log flush took to reach disk
+    pub wal_flush_lag: Duration,
+    /// Sessions open on the database
+    pub open_sessions: usize,
+    /// Usage of each engine cache
+    pub caches: Vec<CacheHealth>,
+}
+
+impl Health {
+    /// Whether the database should receive traffic
+    ///
+    /// True when storage is intact and the WAL keeps up with writes
+    /// ([`WAL_FLUSH_LAG_LIMIT`]).
+    pub fn is_ready(&self) -> bool {
+        self.storage.is_ok()
+            && self.corrupt_records == 0
+            && self.wal_flush_lag <= WAL_FLUSH_LAG_LIMIT
+    }
+}
+
+impl GraphLite {
+    /// Check that the database can serve queries
+    ///
+    /// Runs the quick storage check and fails with [`Error::Unhealthy`] when it
+    /// finds a problem or the WAL lags behind, so a probe handler only needs to
+    /// map the result to a status code.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use graphlite_sdk::GraphLite;
+    ///
+    /// let db = GraphLite::open("./mydb")?;
+    /// let status = if db.ping().is_ok() { 200 } else { 503 };
+    /// # Ok::<(), graphlite_sdk::Error>(())
+    /// ```
+    pub fn ping(&self) -> Result<()> {
+        let storage = self.check_storage()?;
+        if let Some(problem) = storage.problems.first() {
+            return Err(Error::Unhealthy(format!(
+                "Storage check found {} problem(s), first: {}",
+                storage.problems.len(),
+                problem
+            )));
+        }
+        let corrupt_records = self.coordinator().corrupt_records_detected();
+        if corrupt_records > 0 {
+            return Err(Error::Unhealthy(format!(
+                "{} corrupt record(s) detected",
+                corrupt_records
+            )));
+        }
+        let wal_flush_lag = self.coordinator().write_metrics().wal_flush_lag;
+        if wal_flush_lag > WAL_FLUSH_LAG_LIMIT {
+            return Err(Error::Unhealthy(format!(
+                "WAL flush lag is {} ms",
+                wal_flush_lag.as_millis()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Report storage status, WAL lag, open sessions and cache usage
+    ///
+    /// Unlike [`GraphLite::ping`], problems are reported in the snapshot rather
+    /// than as an error; see [`Health::is_ready`].
+    pub fn health(&self) -> Result<Health> {
+        let coordinator = self.coordinator();
+        // Counted before the session that reads the cache statistics is opened
+        let open_sessions = coordinator.session_manager().session_count();
+
+        Ok(Health {
+            storage: self.check_storage()?,
+            corrupt_records: coordinator.corrupt_records_detected(),
+            read_only: coordinator.is_read_only(),
+            disk_space: coordinator.disk_space(),
+            wal_flush_lag: coordinator.write_metrics().wal_flush_lag,
+            open_sessions,
+            caches: self.cache_health()?,
+        })
+    }
+
+    fn check_storage(&self) -> Result<StorageCheckReport> {
+        self.coordinator()
+            .check_storage(false)
+            .map_err(|e| Error::Unhealthy(format!("Storage check failed: {}", e)))
+    }
+
+    fn cache_health(&self) -> Result<Vec<CacheHealth>> {
+        let coordinator = self.coordinator();
+        let session_id = coordinator
+            .create_simple_session("admin")
+            .map_err(|e| Error::Session(format!("Failed to create session: {}", e)))?;
+        let result = coordinator.process_query("CALL gql.cache_stats()", &session_id);
+        // Probes run often, so their session must not outlive them
+        let _ = coordinator.close_session(&session_id);
+        let result = result.map_err(|e| Error::Query(format!("Query failed: {}", e)))?;
+
+        let number = |value: Option<&Value>| match value {
+            Some(Value::Number(n)) => Some(*n),
+            _ => None,
+        };
+        Ok(result
+            .rows
+            .iter()
+            .map(|row| CacheHealth {
+                name: match row.get_value("cache_type") {
+                    Some(Value::String(name)) => name.clone(),
+                    _ => String::new(),
+                },
+                entries: number(row.get_value("entries")).unwrap_or(0.0) as usize,
+                hit_rate: number(row.get_value("hit_rate")),
+                memory_bytes: number(row.get_value("memory_bytes")).unwrap_or(0.0) as usize,
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::TempDb;
+
+    #[test]
+    fn test_ping_and_health_of_a_fresh_database() {
+        let db = TempDb::builder()
+            .graph("/app/health")
+            .fixture("INSERT (:Person {name: 'Alice'})")
+            .build()
+            .unwrap();
+        db.ping().unwrap();
+
+        let _session = db.session("admin").unwrap();
+        let health = db.health().unwrap();
+        assert!(health.storage.is_ok(), "{:?}", health.storage);
+        assert!(health.storage.trees > 0);
+        assert!(!health.read_only);
+        assert!(!health.disk_space.writes_rejected);
+        assert!(health.disk_space.free_bytes.is_some());
+        assert!(health.open_sessions >= 1);
+        assert!(health
+            .caches
+            .iter()
+            .any(|cache| cache.name == "storage_cache" && cache.hit_rate.is_none()));
+
+        // Probes leave no sessions behind
+        assert_eq!(db.health().unwrap().open_sessions, health.open_sessions);
+    }
+}
@@ -0,0 +1,107 @@
+//! Query instrumentation hooks
+//!
+//! Callbacks registered with [`GraphLite::on_query`](crate::GraphLite::on_query)
+//! see every statement its sessions and transactions run, with its duration,
+//! row counts and error, so metrics and tracing can be wired in at one place
+//! instead of around every call site.
+
+use crate::error::{Error, Result};
+use graphlite::QueryResult;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+/// Callback receiving query events
+type Hook = Arc<dyn Fn(&QueryEvent<'_>) + Send + Sync>;
+
+/// A statement that ran, as passed to query hooks
+///
+/// One event is emitted per attempt: a statement retried by the session's
+/// [`RetryPolicy`](crate::RetryPolicy) produces an event for every failed
+/// attempt as well. A batch produces one event, with its statements joined
+/// by `;` as the query.
+#[derive(Debug, Clone, Copy)]
+pub struct QueryEvent<'a> {
+    /// Text of the statement, with `$name` parameters unbound
+    pub query: &'a str,
+    /// Session that ran the statement
+    pub session_id: &'a str,
+    /// User of that session
+    pub username: &'a str,
+    /// Wall-clock time the statement took
+    pub duration: Duration,
+    /// Rows returned, 0 if the statement failed
+    pub rows: usize,
+    /// Rows inserted, updated or deleted, 0 if the statement failed
+    pub rows_affected: usize,
+    /// Why the statement failed
+    pub error: Option<&'a Error>,
+}
+
+/// Row counts reported in a [`QueryEvent`]
+pub(crate) trait Counted {
+    fn counts(&self) -> (usize, usize);
+}
+
+impl Counted for QueryResult {
+    fn counts(&self) -> (usize, usize) {
+        (self.rows.len(), self.rows_affected)
+    }
+}
+
+impl Counted for Vec<QueryResult> {
+    fn counts(&self) -> (usize, usize) {
+        self.iter()
+            .map(Counted::counts)
+            .fold((0, 0), |(rows, affected), (r, a)| (rows + r, affected + a))
+    }
+}
+
+/// Hooks of a database, shared with its sessions
+#[derive(Clone, Default)]
+pub(crate) struct QueryHooks {
+    hooks: Arc<RwLock<Vec<Hook>>>,
+}
+
+impl QueryHooks {
+    pub(crate) fn add<F>(&self, hook: F)
+    where
+        F: Fn(&QueryEvent<'_>) + Send + Sync + 'static,
+    {
+        self.hooks
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(Arc::new(hook));
+    }
+
+    /// Run a statement and report it to every hook
+    pub(crate) fn observe<T: Counted>(
+        &self,
+        query: &str,
+        session_id: &str,
+        username: &str,
+        run: impl FnOnce() -> Result<T>,
+    ) -> Result<T> {
+        // Hooks are called without the lock held, so they may register hooks too
+        let hooks = self.hooks.read().unwrap_or_else(|e| e.into_inner()).clone();
+        if hooks.is_empty() {
+            return run();
+        }
+
+        let started = Instant::now();
+        let result = run();
+        let (rows, rows_affected) = result.as_ref().map(Counted::counts).unwrap_or_default();
+        let event = QueryEvent {
+            query,
+            session_id,
+            username,
+            duration: started.elapsed(),
+            rows,
+            rows_affected,
+            error: result.as_ref().err(),
+        };
+        for hook in &hooks {
+            hook(&event);
+        }
+        result
+    }
+}
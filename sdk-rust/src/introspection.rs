@@ -0,0 +1,240 @@
+//! Discovering the structure of a database
+//!
+//! Typed wrappers around the catalog system procedures, so tools can list
+//! schemas, graphs, labels, relationship types and property keys without
+//! issuing `CALL` statements and picking apart their rows.
+
+use crate::connection::GraphLite;
+use crate::error::Result;
+use crate::params::Params;
+use crate::result::TypedResult;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+
+/// User that runs the introspection procedures
+const INTROSPECTION_USER: &str = "admin";
+
+/// A graph of the database, as listed by [`GraphLite::graphs`]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct GraphInfo {
+    /// Schema the graph belongs to
+    #[serde(rename = "schema_name")]
+    pub schema: String,
+    /// Name of the graph within its schema
+    #[serde(rename = "graph_name")]
+    pub name: String,
+    /// Description set with `ALTER GRAPH ... SET DESCRIPTION`
+    pub description: Option<String>,
+    /// Tags set with `ALTER GRAPH ... SET TAGS`
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Owner set with `ALTER GRAPH ... SET OWNER`
+    pub owner: Option<String>,
+}
+
+impl GraphInfo {
+    /// Full path of the graph, `/schema/graph`
+    pub fn path(&self) -> String {
+        format!("/{}/{}", self.schema, self.name)
+    }
+}
+
+/// A node label and how many nodes carry it
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct LabelInfo {
+    /// The label
+    #[serde(rename = "label")]
+    pub name: String,
+    /// Nodes with the label
+    pub nodes: usize,
+}
+
+/// A relationship type (edge label) and how many edges have it
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct RelationshipTypeInfo {
+    /// The relationship type
+    #[serde(rename = "relationship_type")]
+    pub name: String,
+    /// Edges of the type
+    pub edges: usize,
+}
+
+/// A property key and how many nodes and edges have it
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct PropertyKeyInfo {
+    /// The property key
+    #[serde(rename = "property_key")]
+    pub name: String,
+    /// Nodes with the property
+    pub nodes: usize,
+    /// Edges with the property
+    pub edges: usize,
+}
+
+/// One graph of a database, returned by [`GraphLite::graph`]
+///
+/// Each method reads the graph as it is when called.
+///
+/// # Examples
+///
+/// ```no_run
+/// use graphlite_sdk::GraphLite;
+///
+/// let db = GraphLite::open("./mydb")?;
+/// for info in db.graphs()? {
+///     let graph = db.graph(info.path());
+///     for label in graph.labels()? {
+///         println!("{} :{} ({} nodes)", info.path(), label.name, label.nodes);
+///     }
+/// }
+/// # Ok::<(), graphlite_sdk::Error>(())
+/// ```
+#[derive(Clone)]
+pub struct Graph<'a> {
+    db: &'a GraphLite,
+    path: String,
+}
+
+impl<'a> Graph<'a> {
+    pub(crate) fn new(db: &'a GraphLite, path: String) -> Self {
+        Self { db, path }
+    }
+
+    /// Full path of the graph, `/schema/graph`
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Node labels in use, ordered by label
+    pub fn labels(&self) -> Result<Vec<LabelInfo>> {
+        self.call("gql.list_labels")
+    }
+
+    /// Relationship types in use, ordered by type
+    pub fn relationship_types(&self) -> Result<Vec<RelationshipTypeInfo>> {
+        self.call("gql.list_relationship_types")
+    }
+
+    /// Property keys of nodes and edges, ordered by key
+    pub fn property_keys(&self) -> Result<Vec<PropertyKeyInfo>> {
+        self.call("gql.list_property_keys")
+    }
+
+    fn call<T: DeserializeOwned>(&self, procedure: &str) -> Result<Vec<T>> {
+        let session = self.db.session(INTROSPECTION_USER)?;
+        let result = session.query_with_params(
+            &format!("CALL {}($graph)", procedure),
+            Params::new().add("graph", self.path.as_str()),
+        )?;
+        TypedResult::from(result).deserialize_rows()
+    }
+}
+
+impl std::fmt::Debug for Graph<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Graph").field("path", &self.path).finish()
+    }
+}
+
+impl GraphLite {
+    /// Names of the schemas of the database, ordered by name
+    pub fn schemas(&self) -> Result<Vec<String>> {
+        #[derive(Deserialize)]
+        struct SchemaRow {
+            schema_name: String,
+        }
+
+        let session = self.session(INTROSPECTION_USER)?;
+        let mut names: Vec<String> = session
+            .query_as::<SchemaRow>("CALL gql.list_schemas()")?
+            .into_iter()
+            .map(|row| row.schema_name)
+            .collect();
+        names.sort();
+        Ok(names)
+    }
+
+    /// Graphs of every schema with their metadata, ordered by schema and name
+    pub fn graphs(&self) -> Result<Vec<GraphInfo>> {
+        self.session(INTROSPECTION_USER)?
+            .query_as("CALL gql.find_graphs()")
+    }
+
+    /// Graph `path` (`/schema/graph`), to list its labels, relationship types
+    /// and property keys
+    ///
+    /// The graph is not looked up until one of its methods is called.
+    pub fn graph(&self, path: impl Into<String>) -> Graph<'_> {
+        Graph::new(self, path.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::TempDb;
+
+    #[test]
+    fn test_introspect_schemas_graphs_and_graph_structure() {
+        let db = TempDb::builder()
+            .graph("/app/social")
+            .fixture("INSERT (:Person {name: 'Alice', age: 30})-[:KNOWS {since: 2020}]->(:Person {name: 'Bob'}), (:Company {name: 'Acme'})")
+            .build()
+            .unwrap();
+        // TempDb::graph names the seeded graph; introspect through the database
+        let db: &GraphLite = &db;
+        db.session("admin")
+            .unwrap()
+            .execute("CREATE GRAPH /app/empty")
+            .unwrap();
+
+        assert!(db.schemas().unwrap().contains(&"app".to_string()));
+        let graphs: Vec<String> = db
+            .graphs()
+            .unwrap()
+            .iter()
+            .filter(|info| info.schema == "app")
+            .map(GraphInfo::path)
+            .collect();
+        assert_eq!(graphs, vec!["/app/empty", "/app/social"]);
+
+        let graph = db.graph("/app/social");
+        assert_eq!(
+            graph.labels().unwrap(),
+            vec![
+                LabelInfo {
+                    name: "Company".to_string(),
+                    nodes: 1
+                },
+                LabelInfo {
+                    name: "Person".to_string(),
+                    nodes: 2
+                },
+            ]
+        );
+        assert_eq!(
+            graph.relationship_types().unwrap(),
+            vec![RelationshipTypeInfo {
+                name: "KNOWS".to_string(),
+                edges: 1
+            }]
+        );
+        let keys: Vec<(String, usize, usize)> = graph
+            .property_keys()
+            .unwrap()
+            .into_iter()
+            .map(|key| (key.name, key.nodes, key.edges))
+            .collect();
+        assert_eq!(
+            keys,
+            vec![
+                ("age".to_string(), 1, 0),
+                ("name".to_string(), 3, 0),
+                ("since".to_string(), 0, 1),
+            ]
+        );
+
+        assert!(db.graph("/app/empty").labels().unwrap().is_empty());
+        assert!(db.graph("/app/missing").labels().is_err());
+    }
+}
@@ -31,8 +31,16 @@
 //! - **Query Builder** - Fluent API for building type-safe GQL queries
 //! - **Transaction Support** - ACID transactions with automatic rollback
 //! - **Typed Results** - Deserialize query results into Rust structs
+//! - **Typed Elements** - `row.get_node("p")`, `get_edge` and `get_path` via `RowExt`
+//! - **Checked Queries** - `gql!` parses queries at compile time and binds `$params` to local variables
+//! - **Entity Mapping** - `#[derive(GraphNode)]` / `#[derive(GraphEdge)]` with insert, find, update and delete helpers
 //! - **Connection Pooling** - Efficient concurrent access (future)
-//! - **Async Support** - Full tokio integration (future)
+//! - **Async Support** - `AsyncGraphLite` / `AsyncSession` on tokio (`async` feature)
+//! - **Bulk Loading** - `db.bulk_load(graph).nodes(iter).edges(iter).run()` for ETL ingestion
+//! - **Introspection** - `db.schemas()`, `db.graphs()` and `db.graph(path).labels()` for discovering structure
+//! - **Health Checks** - `db.ping()` for readiness probes, `db.health()` for storage, WAL, session and cache status
+//! - **Instrumentation** - `GraphLite::on_query` hooks for metrics and tracing
+//! - **Test Databases** - `testing::TempDb`, a seeded throwaway database per test
 //!
 //! # Architecture
 //!
@@ -63,24 +71,60 @@
 //! # Module Organization
 //!
 //! - [`connection`] - Database connection and session management
+//! - [`bulk`] - Bulk loading of nodes and edges (`GraphLite::bulk_load`)
+//! - [`health`] - Health checks and readiness probes (`GraphLite::ping`, `GraphLite::health`)
+//! - [`introspection`] - Listing schemas, graphs, labels and property keys
 //! - [`query`] - Query builder and execution
 //! - [`transaction`] - Transaction support
 //! - [`result`] - Result handling and deserialization
+//! - [`entity`] - Mapping structs to nodes and edges (`GraphNode`, `GraphEdge`)
 //! - [`error`] - Error types and handling
+//! - [`migrations`] - Versioned schema migrations (`Migrations`)
+//! - [`params`] - Query parameters (`Params`, `params!`)
+//! - [`retry`] - Retrying transient failures (`RetryPolicy`)
+//! - `async_api` - Async API on tokio (`async` feature)
+
+// Lets the code generated by `#[derive(GraphNode)]` name this crate from inside it
+extern crate self as graphlite_sdk;
 
 // Re-export core types for convenience
-pub use graphlite::{QueryInfo, QueryPlan, QueryResult, QueryType, Row, Value};
+pub use graphlite::{
+    BatchError, DiskSpaceStatus, QueryInfo, QueryPlan, QueryResult, QueryType, Row, Value,
+};
 
 // SDK modules
+#[cfg(feature = "async")]
+pub mod async_api;
+pub mod bulk;
 pub mod connection;
+pub mod entity;
 pub mod error;
+pub mod health;
+pub mod hooks;
+pub mod introspection;
+pub mod migrations;
+pub mod params;
 pub mod query;
 pub mod result;
+pub mod retry;
+pub mod row;
+pub mod testing;
 pub mod transaction;
 
 // Re-export main types for convenience
-pub use connection::{GraphLite, Session};
+#[cfg(feature = "async")]
+pub use async_api::{AsyncGraphLite, AsyncSession};
+pub use bulk::{BulkLoad, BulkLoadSummary, EdgeRecord, NodeRecord};
+pub use connection::{GraphLite, Session, SessionBuilder};
+pub use entity::{GraphEdge, GraphNode};
 pub use error::{Error, Result};
+pub use graphlite_sdk_derive::{gql, GraphEdge, GraphNode};
+pub use health::{CacheHealth, Health};
+pub use hooks::QueryEvent;
+pub use introspection::{Graph, GraphInfo, LabelInfo, PropertyKeyInfo, RelationshipTypeInfo};
+pub use migrations::Migrations;
+pub use params::Params;
 pub use query::QueryBuilder;
 pub use result::TypedResult;
-pub use transaction::Transaction;
+pub use row::{Edge, Node, Path, RowExt};
+pub use transaction::{AccessMode, IsolationLevel, Transaction, TxOptions};
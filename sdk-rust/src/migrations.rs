@@ -0,0 +1,393 @@
+//! Schema migrations
+//!
+//! `Migrations` applies versioned GQL scripts to a database, each one once and in
+//! version order, the way refinery or sqlx migrations work for SQL databases.
+//! Applied versions are recorded as `SchemaMigration` nodes in a history graph
+//! (`/graphlite/migrations` by default), so running the same set of migrations
+//! again only applies the ones added since.
+
+use crate::connection::Session;
+use crate::error::{Error, Result};
+use crate::params::Params;
+use graphlite::Value;
+
+/// Graph where applied migrations are recorded unless `Migrations::history_graph` says otherwise
+pub const DEFAULT_HISTORY_GRAPH: &str = "/graphlite/migrations";
+
+/// A versioned GQL script
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Migration {
+    version: u64,
+    name: String,
+    script: String,
+}
+
+impl Migration {
+    /// Version that orders this migration among the others
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    /// Short description of the migration
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// GQL statements of the migration, separated by `;`
+    pub fn script(&self) -> &str {
+        &self.script
+    }
+
+    /// The statements of the script, without empty and comment-only ones
+    pub fn statements(&self) -> Vec<&str> {
+        split_statements(&self.script)
+    }
+}
+
+/// A set of migrations to bring a database up to date
+///
+/// Each pending migration runs in its own transaction together with the
+/// record that marks it applied, so a failing migration leaves neither its
+/// data changes nor its record behind and is attempted again on the next run.
+/// Catalog statements such as `CREATE GRAPH` take effect immediately and are
+/// not rolled back; write them with `IF NOT EXISTS` so a retried migration
+/// does not trip over them.
+///
+/// Migrations run on a separate session of the same user, so statements like
+/// `SESSION SET GRAPH` in a script do not change the session passed in. Each
+/// script starts with no graph selected, never on the history graph, so a
+/// script that writes data selects its graph with `SESSION SET GRAPH` first.
+///
+/// # Examples
+///
+/// ```no_run
+/// use graphlite_sdk::{GraphLite, Migrations};
+///
+/// let db = GraphLite::open("./mydb")?;
+/// let session = db.session("admin")?;
+///
+/// let applied = Migrations::new()
+///     .add(1, "create_graph", "
+///         CREATE SCHEMA IF NOT EXISTS /app;
+///         CREATE GRAPH IF NOT EXISTS /app/social;
+///     ")
+///     .add(2, "seed_admin", "
+///         SESSION SET GRAPH /app/social;
+///         INSERT (:User {name: 'admin', role: 'admin'});
+///     ")
+///     .run(&session)?;
+/// println!("applied migrations {:?}", applied);
+/// # Ok::<(), graphlite_sdk::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct Migrations {
+    migrations: Vec<Migration>,
+    history_graph: String,
+}
+
+impl Migrations {
+    /// Create an empty set of migrations
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a migration
+    ///
+    /// Migrations may be added in any order; they are applied by ascending version.
+    pub fn add(mut self, version: u64, name: impl Into<String>, script: impl Into<String>) -> Self {
+        self.migrations.push(Migration {
+            version,
+            name: name.into(),
+            script: script.into(),
+        });
+        self
+    }
+
+    /// Record applied migrations in `path` (`/schema/graph`) instead of `/graphlite/migrations`
+    pub fn history_graph(mut self, path: impl Into<String>) -> Self {
+        self.history_graph = path.into();
+        self
+    }
+
+    /// The registered migrations, ordered by version
+    pub fn migrations(&self) -> Vec<&Migration> {
+        let mut migrations: Vec<_> = self.migrations.iter().collect();
+        migrations.sort_by_key(|migration| migration.version);
+        migrations
+    }
+
+    /// Versions already applied to the database, ascending
+    pub fn applied(&self, session: &Session) -> Result<Vec<u64>> {
+        let session = session.new_session()?;
+        self.applied_versions(&session)
+    }
+
+    /// Registered migrations that have not been applied yet, in the order `run` applies them
+    pub fn pending(&self, session: &Session) -> Result<Vec<&Migration>> {
+        self.validate()?;
+        let session = session.new_session()?;
+        let applied = self.applied_versions(&session)?;
+        Ok(self
+            .migrations()
+            .into_iter()
+            .filter(|migration| !applied.contains(&migration.version))
+            .collect())
+    }
+
+    /// Apply the pending migrations and return their versions
+    ///
+    /// Stops at the first migration that fails and returns an
+    /// [`Error::Migration`] naming it; the migrations applied before it stay
+    /// applied.
+    pub fn run(&self, session: &Session) -> Result<Vec<u64>> {
+        self.validate()?;
+        let session = session.new_session()?;
+        let applied = self.applied_versions(&session)?;
+
+        let mut newly_applied = Vec::new();
+        for migration in self.migrations() {
+            if applied.contains(&migration.version) {
+                continue;
+            }
+            self.apply(&session, migration).map_err(|e| {
+                Error::Migration(format!(
+                    "Migration {} ({}) failed: {}",
+                    migration.version, migration.name, e
+                ))
+            })?;
+            newly_applied.push(migration.version);
+        }
+        Ok(newly_applied)
+    }
+
+    /// Reject versions registered twice
+    fn validate(&self) -> Result<()> {
+        let migrations = self.migrations();
+        for pair in migrations.windows(2) {
+            if pair[0].version == pair[1].version {
+                return Err(Error::Migration(format!(
+                    "Migration version {} is registered twice ({} and {})",
+                    pair[0].version, pair[0].name, pair[1].name
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Create the history graph if needed and select it
+    fn use_history_graph(&self, session: &Session) -> Result<()> {
+        let schema = self
+            .history_graph
+            .rsplit_once('/')
+            .map(|(schema, _)| schema)
+            .filter(|schema| !schema.is_empty())
+            .ok_or_else(|| {
+                Error::InvalidOperation(format!(
+                    "Migration history graph must be a /schema/graph path, got '{}'",
+                    self.history_graph
+                ))
+            })?;
+        session.execute(&format!("CREATE SCHEMA IF NOT EXISTS {}", schema))?;
+        session.execute(&format!(
+            "CREATE GRAPH IF NOT EXISTS {}",
+            self.history_graph
+        ))?;
+        session.execute(&format!("SESSION SET GRAPH {}", self.history_graph))
+    }
+
+    fn applied_versions(&self, session: &Session) -> Result<Vec<u64>> {
+        self.use_history_graph(session)?;
+        let result = session.query("MATCH (m:SchemaMigration) RETURN m.id AS id")?;
+        let mut versions: Vec<u64> = result
+            .rows
+            .iter()
+            .filter_map(|row| match row.get_value("id") {
+                Some(Value::Number(id)) => Some(*id as u64),
+                _ => None,
+            })
+            .collect();
+        versions.sort_unstable();
+        versions.dedup();
+        Ok(versions)
+    }
+
+    /// Run the script of `migration` and record it in one transaction
+    fn apply(&self, session: &Session, migration: &Migration) -> Result<()> {
+        let mut tx = session.transaction()?;
+        tx.execute("SESSION RESET GRAPH")?;
+        for statement in migration.statements() {
+            tx.execute(statement)?;
+        }
+        tx.execute(&format!("SESSION SET GRAPH {}", self.history_graph))?;
+        tx.execute_with_params(
+            "INSERT (:SchemaMigration {id: $id, name: $name, applied_at: NOW()})",
+            Params::new()
+                .add("id", migration.version as f64)
+                .add("name", migration.name.as_str()),
+        )?;
+        tx.commit()
+    }
+}
+
+impl Default for Migrations {
+    fn default() -> Self {
+        Self {
+            migrations: Vec::new(),
+            history_graph: DEFAULT_HISTORY_GRAPH.to_string(),
+        }
+    }
+}
+
+/// Split a script on `;`, ignoring the ones inside strings, quoted names and comments
+pub(crate) fn split_statements(script: &str) -> Vec<&str> {
+    let mut statements = Vec::new();
+    let mut start = 0;
+    let mut has_content = false;
+    let mut chars = script.char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\'' | '"' | '`' => {
+                has_content = true;
+                while let Some((_, next)) = chars.next() {
+                    if next == '\\' {
+                        chars.next();
+                    } else if next == c {
+                        break;
+                    }
+                }
+            }
+            '/' if matches!(chars.peek(), Some((_, '/'))) => {
+                while chars.next_if(|&(_, next)| next != '\n').is_some() {}
+            }
+            '/' if matches!(chars.peek(), Some((_, '*'))) => {
+                chars.next();
+                let mut previous = ' ';
+                for (_, next) in chars.by_ref() {
+                    if previous == '*' && next == '/' {
+                        break;
+                    }
+                    previous = next;
+                }
+            }
+            ';' => {
+                if has_content {
+                    statements.push(script[start..i].trim());
+                }
+                start = i + 1;
+                has_content = false;
+            }
+            c if !c.is_whitespace() => has_content = true,
+            _ => {}
+        }
+    }
+    if has_content {
+        statements.push(script[start..].trim());
+    }
+    statements
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GraphLite;
+
+    #[test]
+    fn test_split_statements_respects_quotes_and_comments() {
+        let script = "
+            // create the graph; once
+            CREATE GRAPH IF NOT EXISTS /app/g;
+            /* seed; data */
+            INSERT (:Note {text: 'a; b', other: \"it\\\"s; fine\"});;
+            MATCH (n:`odd;label`) RETURN n
+        ";
+        assert_eq!(
+            split_statements(script),
+            [
+                "// create the graph; once\n            CREATE GRAPH IF NOT EXISTS /app/g",
+                "/* seed; data */\n            INSERT (:Note {text: 'a; b', other: \"it\\\"s; fine\"})",
+                "MATCH (n:`odd;label`) RETURN n",
+            ]
+        );
+        assert!(split_statements(" ; // nothing here\n ;").is_empty());
+    }
+
+    #[test]
+    fn test_run_applies_pending_migrations_once() {
+        let dir = std::env::temp_dir().join(format!("sdk_migrations_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let db = GraphLite::open(&dir).unwrap();
+        let session = db.session("admin").unwrap();
+
+        let migrations = Migrations::new()
+            .add(
+                2,
+                "seed",
+                "SESSION SET GRAPH /app/g; INSERT (:User {name: 'alice'});",
+            )
+            .add(
+                1,
+                "create_graph",
+                "CREATE SCHEMA IF NOT EXISTS /app; CREATE GRAPH IF NOT EXISTS /app/g",
+            );
+        assert_eq!(migrations.pending(&session).unwrap().len(), 2);
+        assert_eq!(migrations.run(&session).unwrap(), [1, 2]);
+        assert_eq!(migrations.run(&session).unwrap(), Vec::<u64>::new());
+        assert_eq!(migrations.applied(&session).unwrap(), [1, 2]);
+
+        // A failing migration is rolled back and not recorded
+        let migrations = migrations.add(
+            3,
+            "broken",
+            "SESSION SET GRAPH /app/g; INSERT (:User {name: 'bob'}); NOT GQL AT ALL",
+        );
+        let error = migrations.run(&session).unwrap_err();
+        assert!(
+            matches!(&error, Error::Migration(message) if message.contains("Migration 3 (broken)"))
+        );
+        assert_eq!(migrations.applied(&session).unwrap(), [1, 2]);
+
+        session.execute("SESSION SET GRAPH /app/g").unwrap();
+        let users = session.query("MATCH (u:User) RETURN u.name").unwrap();
+        assert_eq!(users.rows.len(), 1);
+
+        let duplicate = Migrations::new().add(1, "a", "").add(1, "b", "");
+        assert!(matches!(duplicate.run(&session), Err(Error::Migration(_))));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_scripts_do_not_run_on_the_history_graph() {
+        let dir = std::env::temp_dir().join(format!("sdk_migrations_graph_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let db = GraphLite::open(&dir).unwrap();
+        let session = db.session("admin").unwrap();
+
+        // Without a graph of its own the script has nowhere to write
+        let migrations = Migrations::new()
+            .add(
+                1,
+                "create_graph",
+                "CREATE SCHEMA IF NOT EXISTS /app; CREATE GRAPH IF NOT EXISTS /app/g",
+            )
+            .add(2, "no_graph", "INSERT (:User {name: 'alice'})");
+        let error = migrations.run(&session).unwrap_err();
+        assert!(
+            matches!(&error, Error::Migration(message) if message.contains("Migration 2 (no_graph)"))
+        );
+        assert_eq!(migrations.applied(&session).unwrap(), [1]);
+
+        session
+            .execute(&format!("SESSION SET GRAPH {}", DEFAULT_HISTORY_GRAPH))
+            .unwrap();
+        let users = session.query("MATCH (u:User) RETURN u").unwrap();
+        assert!(users.rows.is_empty());
+        let records = session.query("MATCH (m:SchemaMigration) RETURN m").unwrap();
+        assert_eq!(records.rows.len(), 1);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
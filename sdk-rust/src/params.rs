@@ -0,0 +1,129 @@
+//! Query parameters
+//!
+//! `Params` holds values for the `$name` parameters of a query. Values are
+//! bound by GraphLite as literals, never spliced into the query text as GQL, so
+//! user input passed as a parameter cannot change what a query does.
+
+use graphlite::Value;
+use std::collections::HashMap;
+
+/// Values for the `$name` parameters of a query
+///
+/// Build with [`params!`](crate::params) or chained [`Params::add`] calls.
+///
+/// # Examples
+///
+/// ```no_run
+/// use graphlite_sdk::{params, GraphLite, Params};
+///
+/// # let db = GraphLite::open("./mydb")?;
+/// # let session = db.session("admin")?;
+/// let by_macro = params! { "id" => 42, "name" => "Alice" };
+/// let by_builder = Params::new().add("id", 42).add("name", "Alice");
+///
+/// let result = session.query_with_params("MATCH (p:Person {id: $id}) RETURN p", by_macro)?;
+/// # Ok::<(), graphlite_sdk::Error>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Params {
+    values: HashMap<String, Value>,
+}
+
+impl Params {
+    /// Create an empty parameter set
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a parameter, replacing any earlier value with the same name
+    pub fn add(mut self, name: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.insert(name, value);
+        self
+    }
+
+    /// Set a parameter, replacing any earlier value with the same name
+    pub fn insert(&mut self, name: impl Into<String>, value: impl Into<Value>) {
+        self.values.insert(name.into(), value.into());
+    }
+
+    /// Get the value of a parameter
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.values.get(name)
+    }
+
+    /// Number of parameters
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Check if there are no parameters
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Parameter values by name
+    pub fn as_map(&self) -> &HashMap<String, Value> {
+        &self.values
+    }
+}
+
+impl From<HashMap<String, Value>> for Params {
+    fn from(values: HashMap<String, Value>) -> Self {
+        Self { values }
+    }
+}
+
+/// Build [`Params`] from `name => value` pairs
+///
+/// Values are anything that converts into [`Value`]: strings, numbers,
+/// booleans, `Value` itself and so on.
+///
+/// # Examples
+///
+/// ```
+/// use graphlite_sdk::params;
+///
+/// let params = params! { "name" => "Alice", "age" => 30, "active" => true };
+/// assert_eq!(params.len(), 3);
+/// ```
+#[macro_export]
+macro_rules! params {
+    () => {
+        $crate::Params::new()
+    };
+    ($($name:expr => $value:expr),+ $(,)?) => {
+        $crate::Params::new()$(.add($name, $value))+
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gql_macro_binds_locals_and_expressions() {
+        let query: &str = crate::gql!("MATCH (p:Person) RETURN p.name");
+        assert_eq!(query, "MATCH (p:Person) RETURN p.name");
+
+        let name = "Alice";
+        let (query, params) = crate::gql!(
+            "MATCH (p:Person {name: $name}) WHERE p.age > $min AND p.name <> $name RETURN p",
+            name,
+            min = 18 + 3,
+        );
+        assert!(query.starts_with("MATCH (p:Person {name: $name})"));
+        assert_eq!(params, Params::new().add("name", "Alice").add("min", 21));
+    }
+
+    #[test]
+    fn test_params_macro_and_builder_agree() {
+        let from_macro = crate::params! { "id" => 42, "name" => "Alice", "score" => 1.5 };
+        let from_builder = Params::new()
+            .add("id", 42)
+            .add("name", "Alice")
+            .add("score", 1.5);
+        assert_eq!(from_macro, from_builder);
+        assert_eq!(from_macro.get("id"), Some(&Value::Number(42.0)));
+        assert!(crate::params! {}.is_empty());
+    }
+}
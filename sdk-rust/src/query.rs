@@ -31,9 +31,7 @@ use graphlite::QueryResult;
 /// ```
 pub struct QueryBuilder<'session> {
     session: &'session Session,
-    match_patterns: Vec<String>,
-    where_clauses: Vec<String>,
-    with_clauses: Vec<String>,
+    clauses: Vec<Clause>,
     return_clause: Option<String>,
     order_by: Option<String>,
     skip: Option<usize>,
@@ -45,9 +43,7 @@ impl<'session> QueryBuilder<'session> {
     pub(crate) fn new(session: &'session Session) -> Self {
         QueryBuilder {
             session,
-            match_patterns: Vec::new(),
-            where_clauses: Vec::new(),
-            with_clauses: Vec::new(),
+            clauses: Vec::new(),
             return_clause: None,
             order_by: None,
             skip: None,
@@ -76,13 +72,87 @@ impl<'session> QueryBuilder<'session> {
     /// # Ok::<(), graphlite_sdk::Error>(())
     /// ```
     pub fn match_pattern(mut self, pattern: &str) -> Self {
-        self.match_patterns.push(pattern.to_string());
+        self.clauses.push(Clause::Match(pattern.to_string()));
+        self
+    }
+
+    /// Add an OPTIONAL MATCH pattern
+    ///
+    /// Rows that the pattern does not match are kept, with its new variables
+    /// bound to null.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - Graph pattern to match (without the OPTIONAL MATCH keywords)
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use graphlite_sdk::GraphLite;
+    /// # let db = GraphLite::open("./mydb")?;
+    /// # let session = db.session("admin")?;
+    /// session.query_builder()
+    ///     .match_pattern("(p:Person)")
+    ///     .optional_match("(p)-[:WORKS_AT]->(c:Company)")
+    ///     .return_clause("p.name, c.name");
+    /// # Ok::<(), graphlite_sdk::Error>(())
+    /// ```
+    pub fn optional_match(mut self, pattern: &str) -> Self {
+        self.clauses
+            .push(Clause::OptionalMatch(pattern.to_string()));
+        self
+    }
+
+    /// Add a MERGE pattern
+    ///
+    /// A query with a MERGE clause does not need a RETURN clause.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - Graph pattern to find or create (without the MERGE keyword)
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use graphlite_sdk::GraphLite;
+    /// # let db = GraphLite::open("./mydb")?;
+    /// # let session = db.session("admin")?;
+    /// session.query_builder()
+    ///     .match_pattern("(p:Person {name: 'Alice'})")
+    ///     .merge("(p)-[:LIVES_IN]->(:City {name: 'Berlin'})");
+    /// # Ok::<(), graphlite_sdk::Error>(())
+    /// ```
+    pub fn merge(mut self, pattern: &str) -> Self {
+        self.clauses.push(Clause::Merge(pattern.to_string()));
+        self
+    }
+
+    /// Add an UNWIND clause
+    ///
+    /// Produces one row per element of the list `expression`, bound to `alias`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use graphlite_sdk::GraphLite;
+    /// # let db = GraphLite::open("./mydb")?;
+    /// # let session = db.session("admin")?;
+    /// session.query_builder()
+    ///     .unwind("['Alice', 'Bob']", "name")
+    ///     .match_pattern("(p:Person {name: name})")
+    ///     .return_clause("p");
+    /// # Ok::<(), graphlite_sdk::Error>(())
+    /// ```
+    pub fn unwind(mut self, expression: &str, alias: &str) -> Self {
+        self.clauses
+            .push(Clause::Unwind(format!("{} AS {}", expression, alias)));
         self
     }
 
     /// Add a WHERE clause condition
     ///
-    /// Can be called multiple times - conditions are AND'ed together.
+    /// The condition applies to the clause added just before it. Can be
+    /// called multiple times - consecutive conditions are AND'ed together.
     ///
     /// # Arguments
     ///
@@ -102,7 +172,12 @@ impl<'session> QueryBuilder<'session> {
     /// # Ok::<(), graphlite_sdk::Error>(())
     /// ```
     pub fn where_clause(mut self, condition: &str) -> Self {
-        self.where_clauses.push(condition.to_string());
+        match self.clauses.last_mut() {
+            Some(Clause::Where(conditions)) => conditions.push(condition.to_string()),
+            _ => self
+                .clauses
+                .push(Clause::Where(vec![condition.to_string()])),
+        }
         self
     }
 
@@ -122,19 +197,27 @@ impl<'session> QueryBuilder<'session> {
     /// # let session = db.session("admin")?;
     /// session.query_builder()
     ///     .match_pattern("(p:Person)")
-    ///     .with_clause("p, p.age as age")
+    ///     .with("p, p.age as age")
     ///     .where_clause("age > 25")
     ///     .return_clause("p.name");
     /// # Ok::<(), graphlite_sdk::Error>(())
     /// ```
-    pub fn with_clause(mut self, clause: &str) -> Self {
-        self.with_clauses.push(clause.to_string());
+    pub fn with(mut self, clause: &str) -> Self {
+        self.clauses.push(Clause::With(clause.to_string()));
         self
     }
 
+    /// Add a WITH clause
+    ///
+    /// Same as [`QueryBuilder::with`].
+    pub fn with_clause(self, clause: &str) -> Self {
+        self.with(clause)
+    }
+
     /// Set the RETURN clause
     ///
-    /// Specifies what to return from the query. Required for MATCH queries.
+    /// Specifies what to return from the query. Required unless the query
+    /// contains a MERGE clause.
     ///
     /// # Arguments
     ///
@@ -251,36 +334,33 @@ impl<'session> QueryBuilder<'session> {
     pub fn build(&self) -> Result<String> {
         let mut query = String::new();
 
-        // MATCH clauses
-        if !self.match_patterns.is_empty() {
-            for pattern in &self.match_patterns {
-                if !query.is_empty() {
-                    query.push(' ');
+        for clause in &self.clauses {
+            if !query.is_empty() {
+                query.push(' ');
+            }
+            match clause {
+                Clause::Match(pattern) => query.push_str(&format!("MATCH {}", pattern)),
+                Clause::OptionalMatch(pattern) => {
+                    query.push_str(&format!("OPTIONAL MATCH {}", pattern))
+                }
+                Clause::Merge(pattern) => query.push_str(&format!("MERGE {}", pattern)),
+                Clause::Unwind(unwind) => query.push_str(&format!("UNWIND {}", unwind)),
+                Clause::With(with) => query.push_str(&format!("WITH {}", with)),
+                Clause::Where(conditions) => {
+                    query.push_str(&format!("WHERE {}", conditions.join(" AND ")))
                 }
-                query.push_str("MATCH ");
-                query.push_str(pattern);
             }
         }
 
-        // WHERE clause
-        if !self.where_clauses.is_empty() {
-            query.push_str(" WHERE ");
-            query.push_str(&self.where_clauses.join(" AND "));
-        }
-
-        // WITH clauses
-        for with_clause in &self.with_clauses {
-            query.push_str(" WITH ");
-            query.push_str(with_clause);
-        }
-
         // RETURN clause
         if let Some(ref return_clause) = self.return_clause {
             query.push_str(" RETURN ");
             query.push_str(return_clause);
-        } else if !self.match_patterns.is_empty() {
+        } else if !self.clauses.is_empty()
+            && !self.clauses.iter().any(|c| matches!(c, Clause::Merge(_)))
+        {
             return Err(Error::InvalidOperation(
-                "MATCH query requires a RETURN clause".to_string(),
+                "Query requires a RETURN clause unless it contains MERGE".to_string(),
             ));
         }
 
@@ -331,6 +411,16 @@ impl<'session> QueryBuilder<'session> {
     }
 }
 
+/// A clause of the query, in the order it was added
+enum Clause {
+    Match(String),
+    OptionalMatch(String),
+    Merge(String),
+    Unwind(String),
+    With(String),
+    Where(Vec<String>),
+}
+
 impl Session {
     /// Get a query builder for this session
     ///
@@ -355,12 +445,55 @@ impl Session {
 
 #[cfg(test)]
 mod tests {
-
-    // Note: These are unit tests that test query building logic
-    // Integration tests would require a real database
+    use crate::GraphLite;
 
     #[test]
-    fn test_query_builder_types_compile() {
-        // Compilation test
+    fn test_build_keeps_clause_order() {
+        let dir = std::env::temp_dir().join(format!("sdk_query_builder_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let db = GraphLite::open(&dir).unwrap();
+        let session = db.session("admin").unwrap();
+
+        let query = session
+            .query_builder()
+            .match_pattern("(p:Person)")
+            .where_clause("p.age > 25")
+            .where_clause("p.active = true")
+            .optional_match("(p)-[:WORKS_AT]->(c:Company)")
+            .with("p, c, count(c) AS jobs")
+            .where_clause("jobs > 0")
+            .unwind("p.tags", "tag")
+            .return_clause("p.name, tag")
+            .order_by("p.name")
+            .skip(5)
+            .limit(10)
+            .build()
+            .unwrap();
+        assert_eq!(
+            query,
+            "MATCH (p:Person) WHERE p.age > 25 AND p.active = true \
+             OPTIONAL MATCH (p)-[:WORKS_AT]->(c:Company) \
+             WITH p, c, count(c) AS jobs WHERE jobs > 0 \
+             UNWIND p.tags AS tag RETURN p.name, tag ORDER BY p.name SKIP 5 LIMIT 10"
+        );
+
+        // MERGE does not need a RETURN, a reading query does
+        let query = session
+            .query_builder()
+            .unwind("['Berlin', 'Paris']", "name")
+            .merge("(:City {name: name})")
+            .build()
+            .unwrap();
+        assert_eq!(
+            query,
+            "UNWIND ['Berlin', 'Paris'] AS name MERGE (:City {name: name})"
+        );
+        assert!(session
+            .query_builder()
+            .optional_match("(p:Person)")
+            .build()
+            .is_err());
+
+        let _ = std::fs::remove_dir_all(&dir);
     }
 }
@@ -12,6 +12,11 @@ use serde::de::DeserializeOwned;
 /// TypedResult provides convenient methods for deserializing query results
 /// into Rust types.
 ///
+/// Each row deserializes as an object keyed by column name. Whole numbers
+/// deserialize into integer fields, datetimes into RFC 3339 strings, and nodes
+/// and edges into nested structs of their properties, with the element's
+/// identity under `_id`, `_labels` (nodes) and `_label`, `_from`, `_to` (edges).
+///
 /// # Examples
 ///
 /// ```no_run
@@ -137,8 +142,7 @@ impl TypedResult {
     /// # }
     /// ```
     pub fn deserialize_row<T: DeserializeOwned>(&self, row: &Row) -> Result<T> {
-        // Convert row.values HashMap to JSON
-        let json_value = serde_json::to_value(&row.values)?;
+        let json_value = row_to_json(row);
         let result = serde_json::from_value(json_value)?;
         Ok(result)
     }
@@ -234,17 +238,77 @@ fn value_to_type<T: DeserializeOwned>(value: &Value) -> Result<T> {
     serde_json::from_value(json_value).map_err(|e| e.into())
 }
 
+/// Convert a row to a JSON object keyed by column name
+fn row_to_json(row: &Row) -> serde_json::Value {
+    serde_json::Value::Object(
+        row.values
+            .iter()
+            .map(|(column, value)| (column.clone(), value_to_json(value)))
+            .collect(),
+    )
+}
+
+/// Convert a map of properties to a JSON object
+fn properties_to_json(
+    properties: &std::collections::HashMap<String, Value>,
+) -> serde_json::Map<String, serde_json::Value> {
+    properties
+        .iter()
+        .map(|(key, value)| (key.clone(), value_to_json(value)))
+        .collect()
+}
+
 /// Convert a GraphLite Value to a serde_json Value
-fn value_to_json(value: &Value) -> serde_json::Value {
+///
+/// Whole numbers become JSON integers so they deserialize into integer fields.
+/// Nodes and edges become objects of their properties, so they deserialize into
+/// nested structs; the element's identity is added under `_id`, `_labels`
+/// (nodes) and `_label`, `_from`, `_to` (edges) unless a property of the same
+/// name exists.
+pub(crate) fn value_to_json(value: &Value) -> serde_json::Value {
     match value {
         Value::Null => serde_json::Value::Null,
         Value::Boolean(b) => serde_json::Value::Bool(*b),
+        Value::Number(n) if n.fract() == 0.0 && n.abs() < i64::MAX as f64 => {
+            serde_json::json!(*n as i64)
+        }
         Value::Number(n) => serde_json::json!(n),
         Value::String(s) => serde_json::Value::String(s.clone()),
+        Value::DateTime(dt) => serde_json::Value::String(dt.to_rfc3339()),
+        Value::DateTimeWithFixedOffset(dt) => serde_json::Value::String(dt.to_rfc3339()),
+        Value::DateTimeWithNamedTz(_, dt) => serde_json::Value::String(dt.to_rfc3339()),
         Value::Array(arr) | Value::List(arr) => {
             let items: Vec<serde_json::Value> = arr.iter().map(value_to_json).collect();
             serde_json::Value::Array(items)
         }
+        Value::Vector(v) => serde_json::json!(v),
+        Value::Json(document) => document.clone(),
+        Value::Node(node) => {
+            let mut object = properties_to_json(&node.properties);
+            object
+                .entry("_id")
+                .or_insert_with(|| serde_json::json!(node.id));
+            object
+                .entry("_labels")
+                .or_insert_with(|| serde_json::json!(node.labels));
+            serde_json::Value::Object(object)
+        }
+        Value::Edge(edge) => {
+            let mut object = properties_to_json(&edge.properties);
+            object
+                .entry("_id")
+                .or_insert_with(|| serde_json::json!(edge.id));
+            object
+                .entry("_label")
+                .or_insert_with(|| serde_json::json!(edge.label));
+            object
+                .entry("_from")
+                .or_insert_with(|| serde_json::json!(edge.from_node));
+            object
+                .entry("_to")
+                .or_insert_with(|| serde_json::json!(edge.to_node));
+            serde_json::Value::Object(object)
+        }
         // For complex types like Node, Edge, Path, etc., use serde serialization
         _ => serde_json::to_value(value).unwrap_or(serde_json::Value::Null),
     }
@@ -258,6 +322,7 @@ mod tests {
     fn test_value_conversion() {
         let value = Value::Number(42.0);
         let json = value_to_json(&value);
-        assert_eq!(json, serde_json::json!(42.0));
+        assert_eq!(json, serde_json::json!(42));
+        assert_eq!(value_to_json(&Value::Number(4.5)), serde_json::json!(4.5));
     }
 }
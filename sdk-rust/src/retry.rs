@@ -0,0 +1,220 @@
+//! Retrying transient failures
+//!
+//! A [`RetryPolicy`] set on [`GraphLite`](crate::GraphLite) makes its sessions
+//! retry statements that fail with a transient error, such as a lock conflict,
+//! waiting longer before each attempt (exponential backoff). Statements inside
+//! an explicit [`Transaction`](crate::Transaction) are not retried one by one,
+//! because the transaction has already failed; use
+//! [`Session::transaction_with_retry`](crate::Session::transaction_with_retry)
+//! to retry a whole transaction instead.
+
+use crate::error::Error;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Decides whether an error is worth retrying
+type Classifier = Arc<dyn Fn(&Error) -> bool + Send + Sync>;
+
+/// How failed statements are retried
+///
+/// The default policy makes up to 3 attempts, waiting 10ms before the second
+/// and doubling the wait each time up to 1s, and retries the errors accepted
+/// by [`is_transient`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use graphlite_sdk::{Error, GraphLite, RetryPolicy};
+/// use std::time::Duration;
+///
+/// let db = GraphLite::open("./mydb")?.with_retry_policy(
+///     RetryPolicy::new()
+///         .max_attempts(5)
+///         .initial_backoff(Duration::from_millis(20))
+///         .retry_if(|e| graphlite_sdk::retry::is_transient(e) || e.to_string().contains("busy")),
+/// );
+/// # Ok::<(), Error>(())
+/// ```
+#[derive(Clone)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    multiplier: f64,
+    classifier: Classifier,
+}
+
+impl RetryPolicy {
+    /// Create the default policy
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A policy that never retries
+    pub fn never() -> Self {
+        Self::new().max_attempts(1)
+    }
+
+    /// Total number of attempts, including the first (at least 1)
+    pub fn max_attempts(mut self, attempts: u32) -> Self {
+        self.max_attempts = attempts.max(1);
+        self
+    }
+
+    /// Wait before the first retry
+    pub fn initial_backoff(mut self, backoff: Duration) -> Self {
+        self.initial_backoff = backoff;
+        self
+    }
+
+    /// Upper bound on the wait between attempts
+    pub fn max_backoff(mut self, backoff: Duration) -> Self {
+        self.max_backoff = backoff;
+        self
+    }
+
+    /// Factor the wait grows by after each retry (at least 1.0)
+    pub fn multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = multiplier.max(1.0);
+        self
+    }
+
+    /// Retry the errors for which `classifier` returns true
+    ///
+    /// Replaces the default classification by [`is_transient`].
+    pub fn retry_if<F>(mut self, classifier: F) -> Self
+    where
+        F: Fn(&Error) -> bool + Send + Sync + 'static,
+    {
+        self.classifier = Arc::new(classifier);
+        self
+    }
+
+    /// Check if the policy retries `error`
+    pub fn is_retryable(&self, error: &Error) -> bool {
+        (self.classifier)(error)
+    }
+
+    /// Wait before attempt `attempt` (the first retry is attempt 2)
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let retries = attempt.saturating_sub(2).min(i32::MAX as u32) as i32;
+        let backoff = self.initial_backoff.as_secs_f64() * self.multiplier.powi(retries);
+        Duration::from_secs_f64(backoff.min(self.max_backoff.as_secs_f64()))
+    }
+
+    /// Run `operation`, retrying it while it fails with a retryable error
+    ///
+    /// Returns the first success, the first error that is not retryable, or the
+    /// error of the last attempt.
+    pub fn run<T, F>(&self, mut operation: F) -> Result<T, Error>
+    where
+        F: FnMut() -> Result<T, Error>,
+    {
+        let mut attempt = 1;
+        loop {
+            match operation() {
+                Err(ref error) if attempt < self.max_attempts && self.is_retryable(error) => {
+                    attempt += 1;
+                    std::thread::sleep(self.backoff(attempt));
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(10),
+            max_backoff: Duration::from_secs(1),
+            multiplier: 2.0,
+            classifier: Arc::new(is_transient),
+        }
+    }
+}
+
+impl fmt::Debug for RetryPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RetryPolicy")
+            .field("max_attempts", &self.max_attempts)
+            .field("initial_backoff", &self.initial_backoff)
+            .field("max_backoff", &self.max_backoff)
+            .field("multiplier", &self.multiplier)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Check if an error is transient: retrying the same statement may succeed
+///
+/// Accepts lock conflicts and lock timeouts, deadlocks, serialization
+/// failures, storage that is temporarily unavailable, and write rate limits.
+pub fn is_transient(error: &Error) -> bool {
+    const TRANSIENT: &[&str] = &[
+        "lock conflict",
+        "could not acquire lock",
+        "lock timeout",
+        "deadlock",
+        "serialization failure",
+        "could not serialize",
+        "resource temporarily unavailable",
+        "write rate limit exceeded",
+    ];
+    let message = error.to_string().to_lowercase();
+    TRANSIENT.iter().any(|pattern| message.contains(pattern))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn test_backoff_grows_and_is_capped() {
+        let policy = RetryPolicy::new()
+            .initial_backoff(Duration::from_millis(10))
+            .max_backoff(Duration::from_millis(50));
+        assert_eq!(policy.backoff(2), Duration::from_millis(10));
+        assert_eq!(policy.backoff(3), Duration::from_millis(20));
+        assert_eq!(policy.backoff(4), Duration::from_millis(40));
+        assert_eq!(policy.backoff(5), Duration::from_millis(50));
+    }
+
+    #[test]
+    fn test_run_retries_only_retryable_errors() {
+        let policy = RetryPolicy::new()
+            .max_attempts(4)
+            .initial_backoff(Duration::ZERO);
+
+        let calls = Cell::new(0);
+        let result = policy.run(|| {
+            calls.set(calls.get() + 1);
+            if calls.get() < 3 {
+                Err(Error::Query(
+                    "Execute failed: lock conflict on node 7".into(),
+                ))
+            } else {
+                Ok(calls.get())
+            }
+        });
+        assert_eq!(result.unwrap(), 3);
+
+        calls.set(0);
+        let result: Result<(), Error> = policy.run(|| {
+            calls.set(calls.get() + 1);
+            Err(Error::Query("Parse error: unexpected token".into()))
+        });
+        assert!(result.is_err());
+        assert_eq!(calls.get(), 1);
+
+        calls.set(0);
+        let result: Result<(), Error> = policy.clone().retry_if(|_| true).run(|| {
+            calls.set(calls.get() + 1);
+            Err(Error::Query("anything".into()))
+        });
+        assert!(result.is_err());
+        assert_eq!(calls.get(), 4);
+    }
+}
@@ -0,0 +1,256 @@
+//! Typed access to the nodes, edges and paths of result rows
+//!
+//! [`RowExt`] reads a column of a [`Row`] as a [`Node`], [`Edge`] or [`Path`],
+//! so callers get the element's id, labels and properties directly instead of
+//! matching on [`Value`] variants.
+
+use crate::error::{Error, Result};
+use crate::result::value_to_json;
+use graphlite::{Row, Value};
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+
+/// A node returned by a query
+#[derive(Debug, Clone, PartialEq)]
+pub struct Node {
+    /// Element id of the node
+    pub id: String,
+    /// Labels of the node
+    pub labels: Vec<String>,
+    /// Properties of the node
+    pub properties: HashMap<String, Value>,
+}
+
+impl Node {
+    /// Check whether the node has `label`
+    pub fn has_label(&self, label: &str) -> bool {
+        self.labels.iter().any(|l| l == label)
+    }
+
+    /// Get a property value
+    pub fn property(&self, name: &str) -> Option<&Value> {
+        self.properties.get(name)
+    }
+
+    /// Get a property converted to `T`, e.g. `node.get::<String>("name")`
+    pub fn get<T: DeserializeOwned>(&self, name: &str) -> Result<T> {
+        property_as(&self.properties, name)
+    }
+}
+
+/// An edge returned by a query
+#[derive(Debug, Clone, PartialEq)]
+pub struct Edge {
+    /// Element id of the edge
+    pub id: String,
+    /// Label (type) of the edge
+    pub label: String,
+    /// Element id of the node the edge starts at
+    pub source: String,
+    /// Element id of the node the edge ends at
+    pub target: String,
+    /// Properties of the edge
+    pub properties: HashMap<String, Value>,
+}
+
+impl Edge {
+    /// Get a property value
+    pub fn property(&self, name: &str) -> Option<&Value> {
+        self.properties.get(name)
+    }
+
+    /// Get a property converted to `T`, e.g. `edge.get::<i64>("since")`
+    pub fn get<T: DeserializeOwned>(&self, name: &str) -> Result<T> {
+        property_as(&self.properties, name)
+    }
+}
+
+/// A path returned by a query, as the element ids along it
+///
+/// `node_ids` has one more entry than `edge_ids`: edge `i` connects node `i`
+/// and node `i + 1`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Path {
+    /// Element ids of the nodes, in path order
+    pub node_ids: Vec<String>,
+    /// Element ids of the edges, in path order
+    pub edge_ids: Vec<String>,
+}
+
+impl Path {
+    /// Number of edges in the path
+    pub fn len(&self) -> usize {
+        self.edge_ids.len()
+    }
+
+    /// Check whether the path has no edges
+    pub fn is_empty(&self) -> bool {
+        self.edge_ids.is_empty()
+    }
+}
+
+/// Typed accessors for graph element columns of a [`Row`]
+///
+/// Each accessor fails with [`Error::NotFound`] if the row has no such column
+/// and with [`Error::TypeConversion`] if the column holds another kind of
+/// value, including NULL.
+///
+/// # Examples
+///
+/// ```no_run
+/// use graphlite_sdk::{GraphLite, RowExt};
+///
+/// # let db = GraphLite::open("./mydb")?;
+/// let session = db.session("admin")?;
+/// let result = session.query("MATCH (p:Person)-[k:KNOWS]->(q:Person) RETURN p, k, q")?;
+/// for row in &result.rows {
+///     let person = row.get_node("p")?;
+///     let knows = row.get_edge("k")?;
+///     let name: String = person.get("name")?;
+///     println!("{} ({}) knows {} since {:?}", name, person.id, knows.target, knows.property("since"));
+/// }
+/// # Ok::<(), graphlite_sdk::Error>(())
+/// ```
+pub trait RowExt {
+    /// Read a node column
+    fn get_node(&self, column: &str) -> Result<Node>;
+
+    /// Read an edge column
+    fn get_edge(&self, column: &str) -> Result<Edge>;
+
+    /// Read a path column, such as one built with `PATH[...]`
+    fn get_path(&self, column: &str) -> Result<Path>;
+}
+
+impl RowExt for Row {
+    fn get_node(&self, column: &str) -> Result<Node> {
+        match column_value(self, column)? {
+            Value::Node(node) => Ok(Node {
+                id: node.id.clone(),
+                labels: node.labels.clone(),
+                properties: node.properties.clone(),
+            }),
+            other => Err(wrong_type(column, other, "node")),
+        }
+    }
+
+    fn get_edge(&self, column: &str) -> Result<Edge> {
+        match column_value(self, column)? {
+            Value::Edge(edge) => Ok(Edge {
+                id: edge.id.clone(),
+                label: edge.label.clone(),
+                source: edge.from_node.clone(),
+                target: edge.to_node.clone(),
+                properties: edge.properties.clone(),
+            }),
+            other => Err(wrong_type(column, other, "edge")),
+        }
+    }
+
+    fn get_path(&self, column: &str) -> Result<Path> {
+        match column_value(self, column)? {
+            Value::Path(path) => Ok(Path {
+                node_ids: path
+                    .elements
+                    .iter()
+                    .map(|element| element.node_id.clone())
+                    .collect(),
+                edge_ids: path
+                    .elements
+                    .iter()
+                    .filter_map(|element| element.edge_id.clone())
+                    .collect(),
+            }),
+            other => Err(wrong_type(column, other, "path")),
+        }
+    }
+}
+
+fn column_value<'a>(row: &'a Row, column: &str) -> Result<&'a Value> {
+    row.get_value(column)
+        .ok_or_else(|| Error::NotFound(format!("Column not found: {}", column)))
+}
+
+fn wrong_type(column: &str, value: &Value, expected: &str) -> Error {
+    Error::TypeConversion(format!(
+        "Column {} holds a {}, not a {}",
+        column,
+        value.type_name(),
+        expected
+    ))
+}
+
+fn property_as<T: DeserializeOwned>(properties: &HashMap<String, Value>, name: &str) -> Result<T> {
+    let value = properties
+        .get(name)
+        .ok_or_else(|| Error::NotFound(format!("Property not found: {}", name)))?;
+    serde_json::from_value(value_to_json(value))
+        .map_err(|e| Error::TypeConversion(format!("Property {} has the wrong type: {}", name, e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GraphLite;
+
+    #[test]
+    fn test_row_node_edge_and_path_accessors() {
+        let dir = std::env::temp_dir().join(format!("sdk_row_ext_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let db = GraphLite::open(&dir).unwrap();
+        let session = db.session("admin").unwrap();
+        for statement in [
+            "CREATE SCHEMA /row_ext",
+            "CREATE GRAPH /row_ext/g",
+            "SESSION SET GRAPH /row_ext/g",
+            "INSERT (:Person:Employee {name: 'Alice', age: 30})-[:KNOWS {since: 2020}]->(:Person {name: 'Bob'})",
+        ] {
+            session.execute(statement).unwrap();
+        }
+
+        let result = session
+            .query("MATCH (p:Employee)-[k:KNOWS]->(q) RETURN p, k, q, p.name AS name")
+            .unwrap();
+        let row = &result.rows[0];
+
+        let alice = row.get_node("p").unwrap();
+        assert!(alice.has_label("Person") && alice.has_label("Employee"));
+        assert_eq!(alice.get::<String>("name").unwrap(), "Alice");
+        assert_eq!(alice.get::<u32>("age").unwrap(), 30);
+        assert!(matches!(
+            alice.get::<u32>("name"),
+            Err(Error::TypeConversion(_))
+        ));
+        assert!(matches!(
+            alice.get::<u32>("missing"),
+            Err(Error::NotFound(_))
+        ));
+
+        let bob = row.get_node("q").unwrap();
+        let knows = row.get_edge("k").unwrap();
+        assert_eq!(knows.label, "KNOWS");
+        assert_eq!(
+            (knows.source.as_str(), knows.target.as_str()),
+            (alice.id.as_str(), bob.id.as_str())
+        );
+        assert_eq!(knows.get::<i64>("since").unwrap(), 2020);
+
+        assert!(matches!(row.get_edge("p"), Err(Error::TypeConversion(_))));
+        assert!(matches!(
+            row.get_node("name"),
+            Err(Error::TypeConversion(_))
+        ));
+        assert!(matches!(row.get_node("missing"), Err(Error::NotFound(_))));
+
+        let result = session
+            .query("RETURN PATH['a', 'ab', 'b', 'bc', 'c'] AS path")
+            .unwrap();
+        let path = result.rows[0].get_path("path").unwrap();
+        assert_eq!(path.node_ids, ["a", "b", "c"]);
+        assert_eq!(path.edge_ids, ["ab", "bc"]);
+        assert_eq!(path.len(), 2);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
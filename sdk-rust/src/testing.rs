@@ -0,0 +1,260 @@
+//! Throwaway databases for integration tests
+//!
+//! `TempDb` opens a database in a fresh temporary directory, optionally creates a
+//! graph and seeds it with fixture scripts, and deletes the directory again when
+//! it is dropped, so every test gets its own isolated database.
+
+use crate::connection::{GraphLite, Session};
+use crate::error::{Error, Result};
+use crate::migrations::split_statements;
+use graphlite::{DatabaseConfig, QueryCoordinator, StorageType};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// User that creates the graph and runs the fixtures
+const FIXTURE_USER: &str = "admin";
+
+/// Databases created by this process, to keep their directories apart
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+/// A database in a temporary directory that is deleted on drop
+///
+/// Derefs to [`GraphLite`], so it is used like any other database. Sessions from
+/// [`TempDb::session`] start on the graph set with [`TempDbBuilder::graph`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use graphlite_sdk::testing::TempDb;
+///
+/// let db = TempDb::builder()
+///     .graph("/test/social")
+///     .fixture("INSERT (:Person {name: 'Alice'}); INSERT (:Person {name: 'Bob'})")
+///     .build()?;
+/// let session = db.session("admin")?;
+/// let people = session.query("MATCH (p:Person) RETURN p.name")?;
+/// assert_eq!(people.rows.len(), 2);
+/// # Ok::<(), graphlite_sdk::Error>(())
+/// ```
+pub struct TempDb {
+    // Taken in `drop` so the database is closed before its directory is removed
+    db: Option<GraphLite>,
+    path: PathBuf,
+    graph: Option<String>,
+}
+
+impl TempDb {
+    /// Open an empty database on the default (sled) storage backend
+    pub fn new() -> Result<Self> {
+        Self::builder().build()
+    }
+
+    /// Open an empty database on the in-memory storage backend
+    pub fn in_memory() -> Result<Self> {
+        Self::builder().in_memory().build()
+    }
+
+    /// Configure a database with a graph and fixtures
+    pub fn builder() -> TempDbBuilder {
+        TempDbBuilder::default()
+    }
+
+    /// Create a session, on the graph of the database if it has one
+    pub fn session(&self, username: &str) -> Result<Session> {
+        let session = self.db().session(username)?;
+        if let Some(graph) = &self.graph {
+            session.execute(&format!("SESSION SET GRAPH {}", graph))?;
+        }
+        Ok(session)
+    }
+
+    /// The database
+    pub fn db(&self) -> &GraphLite {
+        self.db.as_ref().expect("TempDb is open until dropped")
+    }
+
+    /// Temporary directory holding the database
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Graph created by [`TempDbBuilder::graph`]
+    pub fn graph(&self) -> Option<&str> {
+        self.graph.as_deref()
+    }
+}
+
+impl std::ops::Deref for TempDb {
+    type Target = GraphLite;
+
+    fn deref(&self) -> &GraphLite {
+        self.db()
+    }
+}
+
+impl Drop for TempDb {
+    fn drop(&mut self) {
+        drop(self.db.take());
+        let _ = std::fs::remove_dir_all(&self.path);
+    }
+}
+
+impl std::fmt::Debug for TempDb {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TempDb")
+            .field("path", &self.path)
+            .field("graph", &self.graph)
+            .finish()
+    }
+}
+
+/// Builder for [`TempDb`]
+#[derive(Debug, Clone, Default)]
+pub struct TempDbBuilder {
+    in_memory: bool,
+    graph: Option<String>,
+    fixtures: Vec<Fixture>,
+}
+
+#[derive(Debug, Clone)]
+enum Fixture {
+    Script(String),
+    File(PathBuf),
+}
+
+impl TempDbBuilder {
+    /// Use the in-memory storage backend instead of sled
+    ///
+    /// The write-ahead log still lives in the temporary directory.
+    pub fn in_memory(mut self) -> Self {
+        self.in_memory = true;
+        self
+    }
+
+    /// Create graph `path` (`/schema/graph`) and run the fixtures in it
+    pub fn graph(mut self, path: impl Into<String>) -> Self {
+        self.graph = Some(path.into());
+        self
+    }
+
+    /// Run a GQL script, with statements separated by `;`
+    ///
+    /// Fixtures run in the order they were added, as user `admin`.
+    pub fn fixture(mut self, script: impl Into<String>) -> Self {
+        self.fixtures.push(Fixture::Script(script.into()));
+        self
+    }
+
+    /// Run the GQL script in a file, e.g. `tests/fixtures/social.gql`
+    pub fn fixture_file(mut self, path: impl AsRef<Path>) -> Self {
+        self.fixtures
+            .push(Fixture::File(path.as_ref().to_path_buf()));
+        self
+    }
+
+    /// Open the database and seed it
+    pub fn build(self) -> Result<TempDb> {
+        let schema = match &self.graph {
+            Some(graph) => match graph.trim_start_matches('/').split_once('/') {
+                Some((schema, name)) if !schema.is_empty() && !name.is_empty() => {
+                    Some(schema.to_string())
+                }
+                _ => {
+                    return Err(Error::InvalidOperation(format!(
+                        "Graph path must look like /schema/graph: {}",
+                        graph
+                    )))
+                }
+            },
+            None => None,
+        };
+
+        let path = std::env::temp_dir().join(format!(
+            "graphlite-test-{}-{}-{}",
+            std::process::id(),
+            NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|elapsed| elapsed.as_nanos())
+                .unwrap_or_default()
+        ));
+        let config = DatabaseConfig {
+            storage_type: if self.in_memory {
+                StorageType::Memory
+            } else {
+                StorageType::Sled
+            },
+            ..DatabaseConfig::default()
+        };
+        let coordinator = QueryCoordinator::from_path_with_config(&path, config)
+            .map_err(|e| Error::Connection(format!("Failed to open database: {}", e)))?;
+
+        // From here on the directory is removed on failure too
+        let mut temp_db = TempDb {
+            db: Some(GraphLite::from_coordinator(coordinator)),
+            path,
+            graph: None,
+        };
+        let session = temp_db.session(FIXTURE_USER)?;
+        if let (Some(schema), Some(graph)) = (schema, self.graph) {
+            session.execute(&format!("CREATE SCHEMA IF NOT EXISTS /{}", schema))?;
+            session.execute(&format!("CREATE GRAPH IF NOT EXISTS {}", graph))?;
+            session.execute(&format!("SESSION SET GRAPH {}", graph))?;
+            temp_db.graph = Some(graph);
+        }
+
+        for fixture in &self.fixtures {
+            let script = match fixture {
+                Fixture::Script(script) => script.clone(),
+                Fixture::File(path) => std::fs::read_to_string(path)?,
+            };
+            for statement in split_statements(&script) {
+                session.execute(statement)?;
+            }
+        }
+        Ok(temp_db)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_temp_db_is_seeded_and_removed() {
+        let fixture_path =
+            std::env::temp_dir().join(format!("graphlite_sdk_fixture_{}.gql", std::process::id()));
+        std::fs::write(&fixture_path, "INSERT (:Person {name: 'Carol'});").unwrap();
+
+        let db = TempDb::builder()
+            .graph("/test/social")
+            .fixture("INSERT (:Person {name: 'Alice'}); INSERT (:Person {name: 'Bob'})")
+            .fixture_file(&fixture_path)
+            .build()
+            .unwrap();
+        let path = db.path().to_path_buf();
+        assert!(path.exists());
+        assert_eq!(db.graph(), Some("/test/social"));
+
+        let session = db.session("admin").unwrap();
+        let people = session.query("MATCH (p:Person) RETURN p.name").unwrap();
+        assert_eq!(people.rows.len(), 3);
+        drop(session);
+
+        // Each database is isolated
+        let other = TempDb::in_memory().unwrap();
+        assert_ne!(other.path(), path);
+        let other_session = other.session("admin").unwrap();
+        assert!(other_session.query("MATCH (p:Person) RETURN p").is_err());
+
+        drop(db);
+        assert!(!path.exists());
+        let _ = std::fs::remove_file(&fixture_path);
+    }
+
+    #[test]
+    fn test_temp_db_rejects_bad_graph_path() {
+        let error = TempDb::builder().graph("social").build().unwrap_err();
+        assert!(matches!(error, Error::InvalidOperation(_)));
+    }
+}
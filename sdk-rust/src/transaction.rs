@@ -7,7 +7,9 @@
 
 use crate::connection::Session;
 use crate::error::{Error, Result};
+use crate::params::Params;
 use graphlite::QueryResult;
+use std::time::{Duration, Instant};
 
 /// Represents an active database transaction
 ///
@@ -41,6 +43,109 @@ pub struct Transaction<'conn> {
     session: &'conn Session,
     committed: bool,
     drop_behavior: DropBehavior,
+    timeout: Option<Duration>,
+    deadline: Option<Instant>,
+}
+
+/// Options for a transaction started with `Session::transaction_with`
+///
+/// Fields left `None` use the database defaults: READ COMMITTED, READ WRITE
+/// and no timeout.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use graphlite_sdk::{AccessMode, GraphLite, TxOptions};
+/// # use std::time::Duration;
+/// # let db = GraphLite::open("./mydb")?;
+/// # let session = db.session("admin")?;
+/// let mut tx = session.transaction_with(TxOptions {
+///     access_mode: Some(AccessMode::ReadOnly),
+///     timeout: Some(Duration::from_secs(5)),
+///     ..TxOptions::default()
+/// })?;
+/// let result = tx.query("MATCH (p:Person) RETURN p.name")?;
+/// tx.commit()?;
+/// # Ok::<(), graphlite_sdk::Error>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TxOptions {
+    /// Isolation level of the transaction
+    ///
+    /// The engine currently implements READ COMMITTED only; asking for another
+    /// level fails when the transaction begins.
+    pub isolation: Option<IsolationLevel>,
+    /// Whether the transaction may modify data
+    pub access_mode: Option<AccessMode>,
+    /// How long the transaction may stay open
+    ///
+    /// Checked before each statement and on commit: once it has passed, the
+    /// transaction is rolled back and those calls fail. A statement that is
+    /// already running is not interrupted.
+    pub timeout: Option<Duration>,
+}
+
+impl TxOptions {
+    /// Options for a READ ONLY transaction
+    pub fn read_only() -> Self {
+        TxOptions {
+            access_mode: Some(AccessMode::ReadOnly),
+            ..TxOptions::default()
+        }
+    }
+
+    /// The START TRANSACTION statement requesting these options
+    fn start_statement(&self) -> String {
+        let mut statement = "START TRANSACTION".to_string();
+        if let Some(isolation) = self.isolation {
+            statement.push_str(" ISOLATION LEVEL ");
+            statement.push_str(isolation.as_str());
+        }
+        if let Some(access_mode) = self.access_mode {
+            statement.push(' ');
+            statement.push_str(access_mode.as_str());
+        }
+        statement
+    }
+}
+
+/// Transaction isolation levels
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IsolationLevel {
+    ReadUncommitted,
+    ReadCommitted,
+    RepeatableRead,
+    Serializable,
+}
+
+impl IsolationLevel {
+    /// GQL name of the isolation level
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IsolationLevel::ReadUncommitted => "READ UNCOMMITTED",
+            IsolationLevel::ReadCommitted => "READ COMMITTED",
+            IsolationLevel::RepeatableRead => "REPEATABLE READ",
+            IsolationLevel::Serializable => "SERIALIZABLE",
+        }
+    }
+}
+
+/// Transaction access modes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccessMode {
+    /// Statements that modify data or the catalog are rejected
+    ReadOnly,
+    ReadWrite,
+}
+
+impl AccessMode {
+    /// GQL name of the access mode
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AccessMode::ReadOnly => "READ ONLY",
+            AccessMode::ReadWrite => "READ WRITE",
+        }
+    }
 }
 
 /// Behavior when a transaction is dropped
@@ -59,19 +164,24 @@ pub enum DropBehavior {
 impl<'conn> Transaction<'conn> {
     /// Begin a new transaction
     ///
-    /// This is called internally by `Session::transaction()`.
+    /// This is called internally by `Session::transaction()` and
+    /// `Session::transaction_with()`.
     /// The transaction will automatically roll back when dropped unless committed.
-    pub(crate) fn begin(session: &'conn Session) -> Result<Self> {
-        // Execute BEGIN TRANSACTION
-        session
-            .coordinator()
-            .process_query("BEGIN TRANSACTION", session.id())
-            .map_err(|e| Error::Transaction(format!("Failed to begin transaction: {}", e)))?;
+    pub(crate) fn begin(session: &'conn Session, options: &TxOptions) -> Result<Self> {
+        let statement = options.start_statement();
+        session.observe(&statement, || {
+            session
+                .coordinator()
+                .process_query(&statement, session.id())
+                .map_err(|e| Error::Transaction(format!("Failed to begin transaction: {}", e)))
+        })?;
 
         Ok(Transaction {
             session,
             committed: false,
             drop_behavior: DropBehavior::Rollback,
+            timeout: options.timeout,
+            deadline: options.timeout.map(|timeout| Instant::now() + timeout),
         })
     }
 
@@ -94,16 +204,14 @@ impl<'conn> Transaction<'conn> {
     /// # Ok::<(), graphlite_sdk::Error>(())
     /// ```
     pub fn execute(&mut self, statement: &str) -> Result<()> {
-        if self.committed {
-            return Err(Error::Transaction(
-                "Transaction already committed".to_string(),
-            ));
-        }
+        self.ensure_active()?;
 
-        self.session
-            .coordinator()
-            .process_query(statement, self.session.id())
-            .map_err(|e| Error::Transaction(format!("Execute failed: {}", e)))?;
+        self.session.observe(statement, || {
+            self.session
+                .coordinator()
+                .process_query(statement, self.session.id())
+                .map_err(|e| Error::Transaction(format!("Execute failed: {}", e)))
+        })?;
 
         Ok(())
     }
@@ -127,16 +235,44 @@ impl<'conn> Transaction<'conn> {
     /// # Ok::<(), graphlite_sdk::Error>(())
     /// ```
     pub fn query(&mut self, query: &str) -> Result<QueryResult> {
-        if self.committed {
-            return Err(Error::Transaction(
-                "Transaction already committed".to_string(),
-            ));
-        }
+        self.ensure_active()?;
+
+        self.session.observe(query, || {
+            self.session
+                .coordinator()
+                .process_query(query, self.session.id())
+                .map_err(|e| Error::Transaction(format!("Query failed: {}", e)))
+        })
+    }
 
-        self.session
-            .coordinator()
-            .process_query(query, self.session.id())
-            .map_err(|e| Error::Transaction(format!("Query failed: {}", e)))
+    /// Execute a statement with `$name` parameters within this transaction
+    ///
+    /// See `Session::execute_with_params`.
+    pub fn execute_with_params(&mut self, statement: &str, params: Params) -> Result<()> {
+        self.ensure_active()?;
+
+        self.session.observe(statement, || {
+            self.session
+                .coordinator()
+                .process_query_with_params(statement, params.as_map(), self.session.id())
+                .map_err(|e| Error::Transaction(format!("Execute failed: {}", e)))
+        })?;
+
+        Ok(())
+    }
+
+    /// Execute a query with `$name` parameters within this transaction
+    ///
+    /// See `Session::query_with_params`.
+    pub fn query_with_params(&mut self, query: &str, params: Params) -> Result<QueryResult> {
+        self.ensure_active()?;
+
+        self.session.observe(query, || {
+            self.session
+                .coordinator()
+                .process_query_with_params(query, params.as_map(), self.session.id())
+                .map_err(|e| Error::Transaction(format!("Query failed: {}", e)))
+        })
     }
 
     /// Commit the transaction
@@ -180,6 +316,62 @@ impl<'conn> Transaction<'conn> {
         self.rollback_internal()
     }
 
+    /// Set a savepoint the transaction can later roll back to
+    ///
+    /// Setting a savepoint with the name of an existing one moves it.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use graphlite_sdk::GraphLite;
+    /// # let db = GraphLite::open("./mydb")?;
+    /// # let session = db.session("admin")?;
+    /// let mut tx = session.transaction()?;
+    /// tx.execute("INSERT (:Person {name: 'Alice'})")?;
+    /// tx.savepoint("sp1")?;
+    /// tx.execute("INSERT (:Person {name: 'Bob'})")?;
+    /// tx.rollback_to("sp1")?;  // Only Alice is inserted
+    /// tx.commit()?;
+    /// # Ok::<(), graphlite_sdk::Error>(())
+    /// ```
+    pub fn savepoint(&mut self, name: &str) -> Result<()> {
+        self.savepoint_statement("SAVEPOINT", name)
+    }
+
+    /// Undo the changes made since savepoint `name` was set
+    ///
+    /// The savepoint stays set; savepoints set after it are removed.
+    pub fn rollback_to(&mut self, name: &str) -> Result<()> {
+        self.savepoint_statement("ROLLBACK TO SAVEPOINT", name)
+    }
+
+    /// Remove savepoint `name` and the savepoints set after it, keeping their changes
+    pub fn release_savepoint(&mut self, name: &str) -> Result<()> {
+        self.savepoint_statement("RELEASE SAVEPOINT", name)
+    }
+
+    fn savepoint_statement(&mut self, keyword: &str, name: &str) -> Result<()> {
+        let valid = name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !valid {
+            return Err(Error::InvalidOperation(format!(
+                "Invalid savepoint name: {:?}",
+                name
+            )));
+        }
+        self.ensure_active()?;
+
+        let statement = format!("{} {}", keyword, name);
+        self.session.observe(&statement, || {
+            self.session
+                .coordinator()
+                .process_query(&statement, self.session.id())
+                .map_err(|e| Error::Transaction(format!("{} failed: {}", keyword, e)))
+        })?;
+
+        Ok(())
+    }
+
     /// Set the behavior when this transaction is dropped
     ///
     /// By default, transactions roll back when dropped. This can be changed to:
@@ -204,18 +396,36 @@ impl<'conn> Transaction<'conn> {
         self.drop_behavior = behavior;
     }
 
-    /// Internal commit implementation
-    fn commit_internal(&mut self) -> Result<()> {
+    /// Fail if the transaction has finished, rolling it back if it timed out
+    fn ensure_active(&mut self) -> Result<()> {
         if self.committed {
             return Err(Error::Transaction(
                 "Transaction already committed".to_string(),
             ));
         }
 
-        self.session
-            .coordinator()
-            .process_query("COMMIT", self.session.id())
-            .map_err(|e| Error::Transaction(format!("Failed to commit: {}", e)))?;
+        if let (Some(timeout), Some(deadline)) = (self.timeout, self.deadline) {
+            if Instant::now() >= deadline {
+                self.rollback_internal()?;
+                return Err(Error::Transaction(format!(
+                    "Transaction timed out after {:?} and was rolled back",
+                    timeout
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Internal commit implementation
+    fn commit_internal(&mut self) -> Result<()> {
+        self.ensure_active()?;
+
+        self.session.observe("COMMIT", || {
+            self.session
+                .coordinator()
+                .process_query("COMMIT", self.session.id())
+                .map_err(|e| Error::Transaction(format!("Failed to commit: {}", e)))
+        })?;
 
         self.committed = true;
         Ok(())
@@ -227,10 +437,12 @@ impl<'conn> Transaction<'conn> {
             return Ok(()); // Already committed, nothing to rollback
         }
 
-        self.session
-            .coordinator()
-            .process_query("ROLLBACK", self.session.id())
-            .map_err(|e| Error::Transaction(format!("Failed to rollback: {}", e)))?;
+        self.session.observe("ROLLBACK", || {
+            self.session
+                .coordinator()
+                .process_query("ROLLBACK", self.session.id())
+                .map_err(|e| Error::Transaction(format!("Failed to rollback: {}", e)))
+        })?;
 
         self.committed = true; // Mark as finished
         Ok(())
@@ -277,4 +489,90 @@ mod tests {
         assert_eq!(DropBehavior::Rollback, DropBehavior::Rollback);
         assert_ne!(DropBehavior::Rollback, DropBehavior::Commit);
     }
+
+    #[test]
+    fn test_transaction_with_options() {
+        let dir = std::env::temp_dir().join(format!("sdk_tx_options_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let db = crate::GraphLite::open(&dir).unwrap();
+        let session = db.session("admin").unwrap();
+        session.execute("CREATE SCHEMA IF NOT EXISTS /app").unwrap();
+        session
+            .execute("CREATE GRAPH IF NOT EXISTS /app/g")
+            .unwrap();
+        session.execute("SESSION SET GRAPH /app/g").unwrap();
+
+        assert_eq!(
+            TxOptions {
+                isolation: Some(IsolationLevel::ReadCommitted),
+                access_mode: Some(AccessMode::ReadOnly),
+                timeout: None,
+            }
+            .start_statement(),
+            "START TRANSACTION ISOLATION LEVEL READ COMMITTED READ ONLY"
+        );
+
+        // A read-only transaction can read but not write
+        let mut tx = session.transaction_with(TxOptions::read_only()).unwrap();
+        assert!(tx.execute("INSERT (:Person {name: 'Alice'})").is_err());
+        tx.query("MATCH (p:Person) RETURN p").unwrap();
+        tx.commit().unwrap();
+
+        // Isolation levels the engine does not implement fail to begin
+        let serializable = TxOptions {
+            isolation: Some(IsolationLevel::Serializable),
+            ..TxOptions::default()
+        };
+        assert!(session.transaction_with(serializable).is_err());
+
+        // An expired transaction is rolled back on its next use
+        let mut tx = session
+            .transaction_with(TxOptions {
+                timeout: Some(Duration::ZERO),
+                ..TxOptions::default()
+            })
+            .unwrap();
+        let error = tx.execute("INSERT (:Person {name: 'Bob'})").unwrap_err();
+        assert!(error.to_string().contains("timed out"));
+        assert!(tx.commit().is_err());
+
+        let mut tx = session.transaction().unwrap();
+        tx.execute("INSERT (:Person {name: 'Carol'})").unwrap();
+        tx.commit().unwrap();
+        let people = session.query("MATCH (p:Person) RETURN p.name").unwrap();
+        assert_eq!(people.rows.len(), 1);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_transaction_savepoints() {
+        let dir = std::env::temp_dir().join(format!("sdk_tx_savepoint_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let db = crate::GraphLite::open(&dir).unwrap();
+        let session = db.session("admin").unwrap();
+        session.execute("CREATE SCHEMA IF NOT EXISTS /app").unwrap();
+        session
+            .execute("CREATE GRAPH IF NOT EXISTS /app/g")
+            .unwrap();
+        session.execute("SESSION SET GRAPH /app/g").unwrap();
+
+        let mut tx = session.transaction().unwrap();
+        tx.execute("INSERT (:Person {name: 'Alice'})").unwrap();
+        tx.savepoint("sp1").unwrap();
+        tx.execute("INSERT (:Person {name: 'Bob'})").unwrap();
+        tx.rollback_to("sp1").unwrap();
+        tx.release_savepoint("sp1").unwrap();
+        assert!(tx.rollback_to("sp1").is_err());
+        assert!(matches!(
+            tx.savepoint("sp1; COMMIT"),
+            Err(Error::InvalidOperation(_))
+        ));
+        tx.commit().unwrap();
+
+        let people = session.query("MATCH (p:Person) RETURN p.name").unwrap();
+        assert_eq!(people.rows.len(), 1);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
 }
@@ -0,0 +1,10 @@
+// Copyright (c) 2024-2025 DeepGraph Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+//! Queries `gql!` rejects at compile time, with the error each one reports
+
+#[test]
+fn test_gql_compile_errors() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/gql_*.rs");
+}
@@ -0,0 +1,3 @@
+fn main() {
+    let _query: &str = graphlite_sdk::gql!("MATCH (p:Person RETURN p");
+}
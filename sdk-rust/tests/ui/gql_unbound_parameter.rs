@@ -0,0 +1,7 @@
+fn main() {
+    let name = "Alice";
+    let _ = graphlite_sdk::gql!(
+        "MATCH (p:Person {name: $name}) WHERE p.age > $min RETURN p",
+        name,
+    );
+}
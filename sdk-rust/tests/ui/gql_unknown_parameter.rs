@@ -0,0 +1,4 @@
+fn main() {
+    let name = "Alice";
+    let _ = graphlite_sdk::gql!("MATCH (p:Person) RETURN p", name);
+}